@@ -1,8 +1,10 @@
+use crate::action_series::{self, ActionRecurrence};
 use crate::actions::Model;
+use crate::audit;
 use crate::coaching_session;
-use crate::error::Error;
+use crate::error::{DomainErrorKind, Error};
 use crate::events::{DomainEvent, EventPublisher};
-use crate::Id;
+use crate::{ExpectedVersion, Id};
 use entity_api::query::{IntoQueryFilterMap, QuerySort};
 use entity_api::status::Status;
 use entity_api::{actions, actions_user, query};
@@ -10,11 +12,12 @@ use log::*;
 use sea_orm::DatabaseConnection;
 
 // Mutations that emit SSE (create_with_assignees, update_with_assignees, update_status,
-// delete_by_id) are wrapped below; the rest are direct re-exports.
+// delete_by_id, restore, bulk) are wrapped below; the rest are direct re-exports.
 pub use entity_api::action::{
     create, find_by_coaching_relationship, find_by_id, find_by_id_with_assignees, find_by_user,
-    find_by_user_relationships, update, ActionWithAssignees, AssigneeFilter, AssigneeScope,
-    CallerVisibility, FindByRelationshipParams, FindByUserParams, Scope,
+    find_by_user_relationships, find_deleted_by_id, update, with_includes, ActionWithAssignees,
+    AssigneeFilter, AssigneeScope, BulkOperation, BulkOutcome, CallerVisibility, EnrichedAction,
+    FindByRelationshipParams, FindByUserParams, IncludeOptions, Scope,
 };
 
 pub async fn find_by<P>(db: &DatabaseConnection, params: P) -> Result<Vec<Model>, Error>
@@ -79,7 +82,7 @@ async fn action_notify_user_ids(
 }
 
 /// Publishes `ActionCreated` or `ActionUpdated` carrying the full action (with assignees).
-async fn publish_action_changed(
+pub(crate) async fn publish_action_changed(
     db: &DatabaseConnection,
     event_publisher: &EventPublisher,
     action: &ActionWithAssignees,
@@ -115,48 +118,74 @@ async fn publish_action_changed(
 }
 
 /// Creates an action (with optional assignees) and publishes `ActionCreated` to both participants.
+/// With a `recurrence`, the action becomes the first instance of a new action series.
 pub async fn create_with_assignees(
     db: &DatabaseConnection,
     event_publisher: &EventPublisher,
     action_model: Model,
     user_id: Id,
     assignee_ids: Option<Vec<Id>>,
+    recurrence: Option<ActionRecurrence>,
 ) -> Result<ActionWithAssignees, Error> {
-    let action =
-        entity_api::action::create_with_assignees(db, action_model, user_id, assignee_ids).await?;
+    let action = match recurrence {
+        Some(recurrence) => {
+            action_series::create_with_first_instance(
+                db,
+                action_model,
+                user_id,
+                assignee_ids,
+                recurrence,
+            )
+            .await?
+        }
+        None => {
+            entity_api::action::create_with_assignees(db, action_model, user_id, assignee_ids)
+                .await?
+        }
+    };
     publish_action_changed(db, event_publisher, &action, true).await;
+    record_action(db, audit::Action::Created, &action).await;
     Ok(action)
 }
 
 /// Updates an action (with optional assignee changes) and publishes `ActionUpdated`.
+/// Completing a recurring action generates its next instance.
 pub async fn update_with_assignees(
     db: &DatabaseConnection,
     event_publisher: &EventPublisher,
     id: Id,
     model: Model,
     assignee_ids: Option<Vec<Id>>,
+    expected: &ExpectedVersion,
 ) -> Result<ActionWithAssignees, Error> {
-    let action = entity_api::action::update_with_assignees(db, id, model, assignee_ids).await?;
+    let action =
+        entity_api::action::update_with_assignees(db, id, model, assignee_ids, expected).await?;
     publish_action_changed(db, event_publisher, &action, false).await;
+    record_action(db, audit::Action::Updated, &action).await;
+    action_series::generate_next_on_completion(db, event_publisher, &action.action).await;
     Ok(action)
 }
 
 /// Updates an action's status and publishes `ActionUpdated` (with assignees re-read for the payload).
+/// Completing a recurring action generates its next instance.
 pub async fn update_status(
     db: &DatabaseConnection,
     event_publisher: &EventPublisher,
     id: Id,
     status: Status,
+    expected: &ExpectedVersion,
 ) -> Result<Model, Error> {
-    let action = entity_api::action::update_status(db, id, status).await?;
+    let action = entity_api::action::update_status(db, id, status, expected).await?;
     match entity_api::action::find_by_id_with_assignees(db, id).await {
         Ok(with_assignees) => {
             publish_action_changed(db, event_publisher, &with_assignees, false).await;
+            record_action(db, audit::Action::Updated, &with_assignees).await;
         }
         Err(e) => {
             error!("action SSE: failed to re-read assignees for action {id} after status update; skipping ActionUpdated: {e:?}");
         }
     }
+    action_series::generate_next_on_completion(db, event_publisher, &action).await;
     Ok(action)
 }
 
@@ -170,16 +199,129 @@ pub async fn delete_by_id(
         .await?
         .coaching_session_id;
     entity_api::action::delete_by_id(db, id).await?;
+    publish_action_deleted(db, event_publisher, coaching_session_id, id).await;
+    record_action_deleted(db, coaching_session_id, id).await;
+    Ok(())
+}
+
+/// Restores a soft-deleted action and publishes `ActionCreated`, as it reappears to
+/// participants just as a new one would.
+pub async fn restore(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    id: Id,
+) -> Result<ActionWithAssignees, Error> {
+    entity_api::action::restore(db, id).await?;
+    let action = entity_api::action::find_by_id_with_assignees(db, id).await?;
+    publish_action_changed(db, event_publisher, &action, true).await;
+    record_action(db, audit::Action::Restored, &action).await;
+    Ok(action)
+}
+
+/// Publishes `ActionDeleted` for an action of the session `coaching_session_id`.
+async fn publish_action_deleted(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    coaching_session_id: Id,
+    action_id: Id,
+) {
     if let Some(notify_user_ids) = action_notify_user_ids(db, coaching_session_id).await {
         event_publisher
             .publish(DomainEvent::ActionDeleted {
                 coaching_session_id,
-                action_id: id,
+                action_id,
                 notify_user_ids,
             })
             .await;
     }
-    Ok(())
+}
+
+/// Audits `action` on an action, snapshotting it with its assignees.
+pub(crate) async fn record_action(
+    db: &DatabaseConnection,
+    action: audit::Action,
+    with_assignees: &ActionWithAssignees,
+) {
+    audit::record(
+        db,
+        action,
+        "action",
+        with_assignees.action.id,
+        audit::Scope::CoachingSession(with_assignees.action.coaching_session_id),
+        Some(with_assignees),
+    )
+    .await;
+}
+
+/// Audits the deletion of an action of the session `coaching_session_id`.
+async fn record_action_deleted(db: &DatabaseConnection, coaching_session_id: Id, action_id: Id) {
+    audit::record(
+        db,
+        audit::Action::Deleted,
+        "action",
+        action_id,
+        audit::Scope::CoachingSession(coaching_session_id),
+        None::<&ActionWithAssignees>,
+    )
+    .await;
+}
+
+/// Most operations one bulk request may carry.
+pub const MAX_BULK_OPERATIONS: usize = 100;
+
+/// Applies `operations` for `user_id` in a single transaction that commits only if every
+/// one of them succeeds, returning each one's outcome in order. Once committed, publishes
+/// the events the single-action mutations do and generates the next instance of each
+/// recurring action that was completed.
+pub async fn bulk(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    user_id: Id,
+    operations: Vec<BulkOperation>,
+) -> Result<Vec<Result<BulkOutcome, Error>>, Error> {
+    if operations.is_empty() || operations.len() > MAX_BULK_OPERATIONS {
+        return Err(Error {
+            source: None,
+            error_kind: DomainErrorKind::Validation(format!(
+                "a bulk request takes between 1 and {MAX_BULK_OPERATIONS} operations (got {})",
+                operations.len()
+            )),
+        });
+    }
+
+    let outcomes = entity_api::action::bulk(db, user_id, operations).await?;
+    // Nothing took effect unless every operation succeeded
+    let committed = outcomes.iter().all(Result::is_ok);
+    if committed {
+        for outcome in outcomes.iter().flatten() {
+            match outcome {
+                BulkOutcome::Created(action) => {
+                    publish_action_changed(db, event_publisher, action, true).await;
+                    record_action(db, audit::Action::Created, action).await;
+                }
+                BulkOutcome::Updated { action, .. } => {
+                    publish_action_changed(db, event_publisher, action, false).await;
+                    record_action(db, audit::Action::Updated, action).await;
+                    action_series::generate_next_on_completion(db, event_publisher, &action.action)
+                        .await;
+                }
+                BulkOutcome::Deleted(action) => {
+                    publish_action_deleted(
+                        db,
+                        event_publisher,
+                        action.coaching_session_id,
+                        action.id,
+                    )
+                    .await;
+                    record_action_deleted(db, action.coaching_session_id, action.id).await;
+                }
+            }
+        }
+    }
+    Ok(outcomes
+        .into_iter()
+        .map(|outcome| outcome.map_err(Error::from))
+        .collect())
 }
 
 #[cfg(test)]
@@ -188,7 +330,7 @@ mod tests {
     use super::*;
     use crate::test_support::recording_publisher;
     use crate::{coaching_relationships, coaching_sessions};
-    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+    use sea_orm::{DatabaseBackend, MockDatabase};
 
     fn action_model(coaching_session_id: Id) -> Model {
         let now = chrono::Utc::now().fixed_offset();
@@ -197,12 +339,16 @@ mod tests {
             coaching_session_id,
             goal_id: None,
             user_id: Id::new_v4(),
+            action_series_id: None,
+            previous_action_id: None,
             body: Some("Do the thing".to_string()),
+            effort_points: None,
             due_by: None,
             status: Status::default(),
             status_changed_at: now,
             created_at: now,
             updated_at: now,
+            deleted_at: None,
         }
     }
 
@@ -224,6 +370,8 @@ mod tests {
             created_at: now,
             updated_at: now,
             hydrated_at: None,
+            session_type_id: None,
+            deleted_at: None,
         };
         let relationship = coaching_relationships::Model {
             id: relationship_id,
@@ -231,6 +379,8 @@ mod tests {
             coach_id: Id::new_v4(),
             coachee_id: Id::new_v4(),
             slug: "test-slug".to_string(),
+            ai_privacy_level: Default::default(),
+            pro_bono: false,
             created_at: now,
             updated_at: now,
         };
@@ -277,7 +427,8 @@ mod tests {
             .into_connection();
 
         let result =
-            create_with_assignees(&db, &publisher, action.clone(), action.user_id, None).await;
+            create_with_assignees(&db, &publisher, action.clone(), action.user_id, None, None)
+                .await;
 
         assert!(result.is_ok());
         assert_one_action_event(&events.lock().unwrap(), session_id, true);
@@ -299,7 +450,14 @@ mod tests {
             .append_query_results(vec![vec![session_with_relationship(session_id)]])
             .into_connection();
 
-        let result = update_status(&db, &publisher, action.id, Status::default()).await;
+        let result = update_status(
+            &db,
+            &publisher,
+            action.id,
+            Status::default(),
+            &ExpectedVersion::Any,
+        )
+        .await;
 
         assert!(result.is_ok());
         assert_one_action_event(&events.lock().unwrap(), session_id, false);
@@ -311,14 +469,11 @@ mod tests {
         let action = action_model(session_id);
         let (publisher, events) = recording_publisher();
 
-        // delete: wrapper find_by_id → entity delete_by_id (find_by_id + DELETE) → participant lookup.
+        // delete: wrapper find_by_id → entity delete_by_id (find_by_id + soft-delete UPDATE) → participant lookup.
         let db = MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results(vec![vec![action.clone()]])
             .append_query_results(vec![vec![action.clone()]])
-            .append_exec_results(vec![MockExecResult {
-                last_insert_id: 0,
-                rows_affected: 1,
-            }])
+            .append_query_results(vec![vec![action.clone()]])
             .append_query_results(vec![vec![session_with_relationship(session_id)]])
             .into_connection();
 
@@ -340,4 +495,79 @@ mod tests {
             other => panic!("expected ActionDeleted, got {other:?}"),
         }
     }
+
+    #[tokio::test]
+    async fn bulk_publishes_events_once_the_batch_commits() {
+        let session_id = Id::new_v4();
+        let action = action_model(session_id);
+        let (session, relationship) = session_with_relationship(session_id);
+        let caller_id = relationship.coach_id;
+        let (publisher, events) = recording_publisher();
+
+        // delete: find_by_id → relationship of the session → soft-delete UPDATE, then after the commit
+        // the participant lookup.
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![action.clone()]])
+            .append_query_results(vec![vec![relationship.clone()]])
+            .append_query_results(vec![vec![action.clone()]])
+            .append_query_results(vec![vec![(session, relationship)]])
+            .into_connection();
+
+        let outcomes = bulk(
+            &db,
+            &publisher,
+            caller_id,
+            vec![BulkOperation::Delete { id: action.id }],
+        )
+        .await
+        .unwrap();
+
+        assert!(matches!(outcomes[..], [Ok(BulkOutcome::Deleted(_))]));
+        let recorded = events.lock().unwrap();
+        assert!(matches!(
+            recorded[..],
+            [DomainEvent::ActionDeleted { action_id, .. }] if action_id == action.id
+        ));
+    }
+
+    #[tokio::test]
+    async fn bulk_publishes_nothing_when_the_batch_rolls_back() {
+        let (publisher, events) = recording_publisher();
+
+        // The only operation's find_by_id finds nothing
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![Vec::<Model>::new()])
+            .into_connection();
+
+        let outcomes = bulk(
+            &db,
+            &publisher,
+            Id::new_v4(),
+            vec![BulkOperation::Delete { id: Id::new_v4() }],
+        )
+        .await
+        .unwrap();
+
+        assert!(matches!(outcomes[..], [Err(_)]));
+        assert!(events.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn bulk_refuses_empty_and_oversized_batches() {
+        let (publisher, _events) = recording_publisher();
+        let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
+        let delete = BulkOperation::Delete { id: Id::new_v4() };
+
+        for operations in [vec![], vec![delete; MAX_BULK_OPERATIONS + 1]] {
+            let result = bulk(&db, &publisher, Id::new_v4(), operations).await;
+
+            assert!(matches!(
+                result,
+                Err(Error {
+                    error_kind: DomainErrorKind::Validation(_),
+                    ..
+                })
+            ));
+        }
+    }
 }
@@ -0,0 +1,421 @@
+//! Repeating actions (e.g. "weekly retro journaling").
+//!
+//! An `action_series` owns the recurrence rule; its actions are the instances.
+//! Instances are generated one at a time rather than materialized up front: the
+//! next one is created when the current one is completed, or by the roll-over job
+//! ([`roll_over`]) once its due date passes. Each instance links back to the one it
+//! was generated from via `previous_action_id`.
+//!
+//! This module owns the JSONB rule serialization: callers interact with the typed
+//! [`ActionRecurrence`], never with `serde_json::Value` directly.
+
+use std::collections::HashMap;
+
+use chrono::{Duration, Months, NaiveDate, Utc};
+use entity_api::action::ActionWithAssignees;
+use entity_api::action_series;
+use log::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::{DatabaseConnection, TransactionTrait};
+use serde::{Deserialize, Serialize};
+
+use crate::actions;
+use crate::coaching_session;
+use crate::error::Error;
+use crate::events::EventPublisher;
+use crate::status::Status;
+use crate::Id;
+
+pub use coaching_session::Frequency;
+pub use entity_api::action_series::{find_by_id, Model};
+
+/// Upper bound on the periods [`next_due_by`] will step over, so a long-stalled daily
+/// series can't loop unbounded (about 13 years of missed daily instances).
+const MAX_SKIPPED_PERIODS: u32 = 5000;
+
+/// How an action repeats. Unlike session recurrences there is no occurrence count:
+/// a series runs until `until` (inclusive) or indefinitely when it is omitted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActionRecurrence {
+    pub frequency: Frequency,
+    /// Step multiplier. `weekly` with `interval: 2` is the same as `biweekly`.
+    /// Defaults to 1 if omitted in JSON.
+    #[serde(default = "default_interval")]
+    pub interval: u32,
+    /// Last date an instance may fall due on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until: Option<NaiveDate>,
+}
+
+fn default_interval() -> u32 {
+    1
+}
+
+/// Typed shape of the JSONB `rule` column on `action_series`. Due dates are stepped
+/// from `first_due_by` rather than from the previous instance so monthly series
+/// don't drift after a month-end clamp (Jan 31 → Feb 28 → Mar 31, not Mar 28).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActionSeriesRule {
+    pub first_due_by: DateTimeWithTimeZone,
+    pub recurrence: ActionRecurrence,
+}
+
+/// Streak statistics for one series, returned by the analytics endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ActionSeriesStreak {
+    pub action_series_id: Id,
+    pub recurrence: ActionRecurrence,
+    /// Body of the most recent instance.
+    pub body: Option<String>,
+    /// Consecutive completed instances, counting back from the most recent settled one.
+    pub current_streak: usize,
+    pub longest_streak: usize,
+    pub completed_count: usize,
+    pub instance_count: usize,
+}
+
+/// Creates a series and its first instance (with optional assignees) in a single
+/// transaction. The action's `due_by` anchors the rule and is required.
+pub(crate) async fn create_with_first_instance(
+    db: &DatabaseConnection,
+    mut action_model: actions::Model,
+    user_id: Id,
+    assignee_ids: Option<Vec<Id>>,
+    recurrence: ActionRecurrence,
+) -> Result<ActionWithAssignees, Error> {
+    let first_due_by = action_model
+        .due_by
+        .ok_or_else(|| Error::validation("Recurring actions require a `due_by`"))?;
+    validate_recurrence(first_due_by, &recurrence)?;
+
+    let (_, relationship) = coaching_session::find_by_id_with_coaching_relationship(
+        db,
+        action_model.coaching_session_id,
+    )
+    .await?;
+
+    let rule = serde_json::to_value(ActionSeriesRule {
+        first_due_by,
+        recurrence,
+    })?;
+
+    let txn = db.begin().await.map_err(entity_api::error::Error::from)?;
+
+    let series_input = Model {
+        id: Id::nil(),
+        coaching_relationship_id: relationship.id,
+        rule,
+        created_by_user_id: user_id,
+        created_at: Utc::now().into(),
+        updated_at: Utc::now().into(),
+    };
+    let series = action_series::create(&txn, series_input).await?;
+
+    action_model.action_series_id = Some(series.id);
+    let action =
+        entity_api::action::create_with_assignees(&txn, action_model, user_id, assignee_ids)
+            .await?;
+
+    txn.commit().await.map_err(entity_api::error::Error::from)?;
+
+    Ok(action)
+}
+
+/// Generates the instance following `previous` and publishes `ActionCreated` for it.
+///
+/// Returns `None` when `previous` isn't recurring, already has a successor, or the
+/// series has ended.
+pub(crate) async fn generate_next(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    previous: &actions::Model,
+) -> Result<Option<ActionWithAssignees>, Error> {
+    let (Some(series_id), Some(previous_due_by)) = (previous.action_series_id, previous.due_by)
+    else {
+        return Ok(None);
+    };
+
+    let series = action_series::find_by_id(db, series_id).await?;
+    let rule: ActionSeriesRule = serde_json::from_value(series.rule)?;
+
+    let Some(due_by) = next_due_by(&rule, previous_due_by, Utc::now().into()) else {
+        debug!(
+            "Action series {series_id} has ended; no instance follows {}",
+            previous.id
+        );
+        return Ok(None);
+    };
+
+    let next = action_series::create_next_instance(db, previous, due_by).await?;
+    if let Some(next) = &next {
+        crate::action::publish_action_changed(db, event_publisher, next, true).await;
+    }
+
+    Ok(next)
+}
+
+/// Best-effort wrapper around [`generate_next`] for completions: the status write is
+/// the contract, so a failure here is logged rather than surfaced.
+pub(crate) async fn generate_next_on_completion(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    action: &actions::Model,
+) {
+    if action.status != Status::Completed || action.action_series_id.is_none() {
+        return;
+    }
+    if let Err(e) = generate_next(db, event_publisher, action).await {
+        error!(
+            "action recurrence: failed to generate the instance following {}: {e:?}",
+            action.id
+        );
+    }
+}
+
+/// Generates the next instance for every recurring action whose due date has passed
+/// without a successor. Returns how many instances were created.
+///
+/// The overdue instance is left as-is (it still counts against the streak unless it's
+/// completed later). Per-instance failures are logged and skipped.
+pub async fn roll_over(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+) -> Result<usize, Error> {
+    let due = action_series::find_due_for_roll_over(db, Utc::now().into()).await?;
+
+    let mut created = 0;
+    for previous in &due {
+        match generate_next(db, event_publisher, previous).await {
+            Ok(Some(_)) => created += 1,
+            Ok(None) => {}
+            Err(e) => error!(
+                "action recurrence: failed to roll over action {}: {e:?}",
+                previous.id
+            ),
+        }
+    }
+
+    Ok(created)
+}
+
+/// Streak statistics for every action series in a coaching relationship.
+pub async fn relationship_streaks(
+    db: &DatabaseConnection,
+    coaching_relationship_id: Id,
+) -> Result<Vec<ActionSeriesStreak>, Error> {
+    let series = action_series::find_by_relationship(db, coaching_relationship_id).await?;
+    let series_ids: Vec<Id> = series.iter().map(|s| s.id).collect();
+
+    let mut instances_by_series: HashMap<Id, Vec<actions::Model>> = HashMap::new();
+    for instance in action_series::find_instances(db, &series_ids).await? {
+        if let Some(series_id) = instance.action_series_id {
+            instances_by_series
+                .entry(series_id)
+                .or_default()
+                .push(instance);
+        }
+    }
+
+    let now: DateTimeWithTimeZone = Utc::now().into();
+    series
+        .into_iter()
+        .map(|series| {
+            let rule: ActionSeriesRule = serde_json::from_value(series.rule)?;
+            let instances = instances_by_series.remove(&series.id).unwrap_or_default();
+            Ok(streak(series.id, rule.recurrence, &instances, now))
+        })
+        .collect()
+}
+
+/// The due date following `previous_due_by`, skipping periods that are already over
+/// (so a stalled series resumes at the current period rather than back-filling).
+/// Returns `None` once the series has run past `until`.
+pub fn next_due_by(
+    rule: &ActionSeriesRule,
+    previous_due_by: DateTimeWithTimeZone,
+    now: DateTimeWithTimeZone,
+) -> Option<DateTimeWithTimeZone> {
+    (1..=MAX_SKIPPED_PERIODS)
+        .map_while(|n| occurrence(rule, n))
+        .take_while(|due_by| {
+            rule.recurrence
+                .until
+                .is_none_or(|until| due_by.date_naive() <= until)
+        })
+        .find(|due_by| *due_by > previous_due_by && *due_by >= now)
+}
+
+/// The `n`th due date of the series, where `n = 0` is `first_due_by`.
+fn occurrence(rule: &ActionSeriesRule, n: u32) -> Option<DateTimeWithTimeZone> {
+    let steps = n.checked_mul(rule.recurrence.interval)?;
+    match rule.recurrence.frequency {
+        Frequency::Daily => rule
+            .first_due_by
+            .checked_add_signed(Duration::days(steps.into())),
+        Frequency::Weekly => rule
+            .first_due_by
+            .checked_add_signed(Duration::weeks(steps.into())),
+        Frequency::Biweekly => rule
+            .first_due_by
+            .checked_add_signed(Duration::weeks(i64::from(steps) * 2)),
+        Frequency::Monthly => rule.first_due_by.checked_add_months(Months::new(steps)),
+    }
+}
+
+/// Computes streaks over a series' instances (sorted by due date). Only settled
+/// instances count: completed ones, and ones whose due date has passed. The current,
+/// not-yet-due instance neither extends nor breaks the streak.
+fn streak(
+    action_series_id: Id,
+    recurrence: ActionRecurrence,
+    instances: &[actions::Model],
+    now: DateTimeWithTimeZone,
+) -> ActionSeriesStreak {
+    let (mut run, mut longest_streak) = (0, 0);
+    for instance in instances {
+        let completed = instance.status == Status::Completed;
+        let overdue = instance.due_by.is_some_and(|due_by| due_by < now);
+        if completed {
+            run += 1;
+            longest_streak = longest_streak.max(run);
+        } else if overdue {
+            run = 0;
+        }
+    }
+
+    ActionSeriesStreak {
+        action_series_id,
+        recurrence,
+        body: instances.last().and_then(|instance| instance.body.clone()),
+        current_streak: run,
+        longest_streak,
+        completed_count: instances
+            .iter()
+            .filter(|instance| instance.status == Status::Completed)
+            .count(),
+        instance_count: instances.len(),
+    }
+}
+
+fn validate_recurrence(
+    first_due_by: DateTimeWithTimeZone,
+    recurrence: &ActionRecurrence,
+) -> Result<(), Error> {
+    if recurrence.interval < 1 {
+        return Err(Error::validation("`interval` must be at least 1"));
+    }
+    if recurrence
+        .until
+        .is_some_and(|until| until < first_due_by.date_naive())
+    {
+        return Err(Error::validation("`until` must not be before `due_by`"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, TimeZone};
+
+    fn at(y: i32, m: u32, d: u32) -> DateTimeWithTimeZone {
+        Utc.with_ymd_and_hms(y, m, d, 17, 0, 0).unwrap().into()
+    }
+
+    fn rule(frequency: Frequency, until: Option<NaiveDate>) -> ActionSeriesRule {
+        ActionSeriesRule {
+            first_due_by: at(2026, 1, 31),
+            recurrence: ActionRecurrence {
+                frequency,
+                interval: 1,
+                until,
+            },
+        }
+    }
+
+    fn instance(status: Status, due_by: DateTimeWithTimeZone) -> actions::Model {
+        let now = Utc::now().into();
+        actions::Model {
+            id: Id::new_v4(),
+            coaching_session_id: Id::new_v4(),
+            goal_id: None,
+            user_id: Id::new_v4(),
+            action_series_id: None,
+            previous_action_id: None,
+            body: Some("Retro journaling".to_string()),
+            effort_points: None,
+            due_by: Some(due_by),
+            status,
+            status_changed_at: now,
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn next_due_by_steps_from_the_anchor_without_month_end_drift() {
+        let monthly = rule(Frequency::Monthly, None);
+        let early: DateTime<_> = at(2026, 1, 1);
+
+        let feb = next_due_by(&monthly, at(2026, 1, 31), early).unwrap();
+        assert_eq!(feb, at(2026, 2, 28));
+        assert_eq!(next_due_by(&monthly, feb, early).unwrap(), at(2026, 3, 31));
+    }
+
+    #[test]
+    fn next_due_by_skips_periods_that_are_already_over() {
+        let weekly = rule(Frequency::Weekly, None);
+
+        let next = next_due_by(&weekly, at(2026, 1, 31), at(2026, 2, 20)).unwrap();
+
+        assert_eq!(next, at(2026, 2, 21));
+    }
+
+    #[test]
+    fn next_due_by_stops_after_until() {
+        let daily = rule(Frequency::Daily, NaiveDate::from_ymd_opt(2026, 2, 1));
+        let early = at(2026, 1, 1);
+
+        assert_eq!(
+            next_due_by(&daily, at(2026, 1, 31), early),
+            Some(at(2026, 2, 1))
+        );
+        assert_eq!(next_due_by(&daily, at(2026, 2, 1), early), None);
+    }
+
+    #[test]
+    fn streak_ignores_the_open_instance_and_resets_on_missed_periods() {
+        let now = at(2026, 3, 1);
+        let instances = vec![
+            instance(Status::Completed, at(2026, 1, 31)),
+            instance(Status::Completed, at(2026, 2, 7)),
+            instance(Status::NotStarted, at(2026, 2, 14)),
+            instance(Status::Completed, at(2026, 2, 21)),
+            instance(Status::Completed, at(2026, 2, 28)),
+            instance(Status::NotStarted, at(2026, 3, 7)),
+        ];
+
+        let streak = streak(
+            Id::new_v4(),
+            rule(Frequency::Weekly, None).recurrence,
+            &instances,
+            now,
+        );
+
+        assert_eq!(streak.current_streak, 2);
+        assert_eq!(streak.longest_streak, 2);
+        assert_eq!(streak.completed_count, 4);
+        assert_eq!(streak.instance_count, 6);
+    }
+
+    #[test]
+    fn validate_recurrence_rejects_zero_interval_and_until_before_first_due() {
+        let mut recurrence = rule(Frequency::Daily, None).recurrence;
+        recurrence.interval = 0;
+        assert!(validate_recurrence(at(2026, 1, 31), &recurrence).is_err());
+
+        let recurrence = rule(Frequency::Daily, NaiveDate::from_ymd_opt(2026, 1, 30)).recurrence;
+        assert!(validate_recurrence(at(2026, 1, 31), &recurrence).is_err());
+    }
+}
@@ -0,0 +1,148 @@
+//! Focus-timer (pomodoro-style) work logged against actions.
+//!
+//! Work is either timed with start/stop or logged after the fact. Any participant
+//! of the action's coaching session may log work; only the logger may stop their
+//! own timer, and each user has at most one running timer.
+
+use chrono::Utc;
+use entity_api::action_work_log;
+use entity_api::resource_type::ResourceType;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::DatabaseConnection;
+
+use crate::error::{EntityErrorKind, Error};
+use crate::resource_view::authorize_participant;
+use crate::{action_work_logs::Model, Id};
+
+pub use entity_api::action_work_log::find_totals;
+
+/// Longest single span of work that can be logged (12 hours). A timer left running
+/// longer is capped here when stopped so a forgotten timer can't skew the rollups.
+pub const MAX_WORK_LOG_SECONDS: i32 = 12 * 60 * 60;
+
+/// Starts a running timer on an action for `user_id`.
+pub async fn start(
+    db: &DatabaseConnection,
+    user_id: Id,
+    action_id: Id,
+    note: Option<String>,
+) -> Result<Model, Error> {
+    authorize_participant(db, user_id, ResourceType::Action, action_id).await?;
+
+    if action_work_log::find_running_by_user(db, user_id)
+        .await?
+        .is_some()
+    {
+        return Err(Error::validation(
+            "A focus timer is already running; stop it before starting another",
+        ));
+    }
+
+    Ok(action_work_log::create(
+        db,
+        Model {
+            id: Id::nil(),
+            action_id,
+            user_id,
+            started_at: Utc::now().into(),
+            duration_seconds: None,
+            note,
+            created_at: Utc::now().into(),
+            updated_at: Utc::now().into(),
+        },
+    )
+    .await?)
+}
+
+/// Stops the caller's running timer, recording the elapsed time (capped at
+/// [`MAX_WORK_LOG_SECONDS`]). A `note` replaces the one given at start.
+pub async fn stop(
+    db: &DatabaseConnection,
+    user_id: Id,
+    id: Id,
+    note: Option<String>,
+) -> Result<Model, Error> {
+    let work_log = action_work_log::find_by_id(db, id).await?;
+    if work_log.user_id != user_id {
+        return Err(Error::entity(EntityErrorKind::Unauthenticated));
+    }
+    if work_log.duration_seconds.is_some() {
+        return Err(Error::validation(
+            "This focus timer has already been stopped",
+        ));
+    }
+
+    let duration_seconds = elapsed_seconds(work_log.started_at, Utc::now().into());
+    Ok(action_work_log::stop(db, work_log, duration_seconds, note).await?)
+}
+
+/// Logs a finished span of work after the fact.
+pub async fn log(
+    db: &DatabaseConnection,
+    user_id: Id,
+    action_id: Id,
+    started_at: DateTimeWithTimeZone,
+    duration_seconds: i32,
+    note: Option<String>,
+) -> Result<Model, Error> {
+    if !(1..=MAX_WORK_LOG_SECONDS).contains(&duration_seconds) {
+        return Err(Error::validation(format!(
+            "`duration_seconds` must be between 1 and {MAX_WORK_LOG_SECONDS}"
+        )));
+    }
+    if started_at > Utc::now() {
+        return Err(Error::validation("`started_at` must not be in the future"));
+    }
+
+    authorize_participant(db, user_id, ResourceType::Action, action_id).await?;
+
+    Ok(action_work_log::create(
+        db,
+        Model {
+            id: Id::nil(),
+            action_id,
+            user_id,
+            started_at,
+            duration_seconds: Some(duration_seconds),
+            note,
+            created_at: Utc::now().into(),
+            updated_at: Utc::now().into(),
+        },
+    )
+    .await?)
+}
+
+/// All work logged on an action, most recent first. Participant-only.
+pub async fn find_by_action(
+    db: &DatabaseConnection,
+    user_id: Id,
+    action_id: Id,
+) -> Result<Vec<Model>, Error> {
+    authorize_participant(db, user_id, ResourceType::Action, action_id).await?;
+    Ok(action_work_log::find_by_action(db, action_id).await?)
+}
+
+/// Whole seconds from `started_at` to `stopped_at`, at least 1 and at most
+/// [`MAX_WORK_LOG_SECONDS`].
+fn elapsed_seconds(started_at: DateTimeWithTimeZone, stopped_at: DateTimeWithTimeZone) -> i32 {
+    let elapsed = stopped_at.signed_duration_since(started_at).num_seconds();
+    elapsed.clamp(1, MAX_WORK_LOG_SECONDS.into()) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn elapsed_seconds_is_clamped_to_a_positive_bounded_span() {
+        let start: DateTimeWithTimeZone = Utc::now().into();
+
+        assert_eq!(elapsed_seconds(start, start + Duration::minutes(25)), 1500);
+        assert_eq!(elapsed_seconds(start, start), 1);
+        assert_eq!(
+            elapsed_seconds(start, start + Duration::days(2)),
+            MAX_WORK_LOG_SECONDS
+        );
+    }
+}
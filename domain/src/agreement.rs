@@ -1,15 +1,17 @@
 use crate::agreements::Model;
+use crate::audit;
 use crate::coaching_session;
 use crate::error::Error;
 use crate::events::{DomainEvent, EventPublisher};
-use crate::Id;
+use crate::{ExpectedVersion, Id};
 use entity_api::query::{IntoQueryFilterMap, QuerySort};
 use entity_api::{agreements, query};
 use log::*;
 use sea_orm::DatabaseConnection;
 
-// Mutations (create, update, delete_by_id) are wrapped below to emit SSE; reads re-export directly.
-pub use entity_api::agreement::find_by_id;
+// Mutations (create, update, delete_by_id, restore) are wrapped below to emit SSE; reads
+// re-export directly.
+pub use entity_api::agreement::{find_by_id, find_deleted_by_id};
 
 pub async fn find_by<P>(db: &DatabaseConnection, params: P) -> Result<Vec<Model>, Error>
 where
@@ -69,6 +71,19 @@ async fn publish_agreement_changed(
     event_publisher.publish(event).await;
 }
 
+/// Audits `action` on an agreement.
+async fn record_agreement(db: &DatabaseConnection, action: audit::Action, agreement: &Model) {
+    audit::record(
+        db,
+        action,
+        "agreement",
+        agreement.id,
+        audit::Scope::CoachingSession(agreement.coaching_session_id),
+        Some(agreement),
+    )
+    .await;
+}
+
 /// Creates an agreement and publishes `AgreementCreated` to both session participants.
 pub async fn create(
     db: &DatabaseConnection,
@@ -78,6 +93,7 @@ pub async fn create(
 ) -> Result<Model, Error> {
     let agreement = entity_api::agreement::create(db, agreement_model, user_id).await?;
     publish_agreement_changed(db, event_publisher, &agreement, true).await;
+    record_agreement(db, audit::Action::Created, &agreement).await;
     Ok(agreement)
 }
 
@@ -87,9 +103,11 @@ pub async fn update(
     event_publisher: &EventPublisher,
     id: Id,
     model: Model,
+    expected: &ExpectedVersion,
 ) -> Result<Model, Error> {
-    let agreement = entity_api::agreement::update(db, id, model).await?;
+    let agreement = entity_api::agreement::update(db, id, model, expected).await?;
     publish_agreement_changed(db, event_publisher, &agreement, false).await;
+    record_agreement(db, audit::Action::Updated, &agreement).await;
     Ok(agreement)
 }
 
@@ -112,16 +130,38 @@ pub async fn delete_by_id(
             })
             .await;
     }
+    audit::record(
+        db,
+        audit::Action::Deleted,
+        "agreement",
+        id,
+        audit::Scope::CoachingSession(coaching_session_id),
+        None::<&Model>,
+    )
+    .await;
     Ok(())
 }
 
+/// Restores a soft-deleted agreement and publishes `AgreementCreated`, as it reappears to
+/// participants just as a new one would.
+pub async fn restore(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    id: Id,
+) -> Result<Model, Error> {
+    let agreement = entity_api::agreement::restore(db, id).await?;
+    publish_agreement_changed(db, event_publisher, &agreement, true).await;
+    record_agreement(db, audit::Action::Restored, &agreement).await;
+    Ok(agreement)
+}
+
 #[cfg(test)]
 #[cfg(feature = "mock")]
 mod tests {
     use super::*;
     use crate::test_support::recording_publisher;
     use crate::{coaching_relationships, coaching_sessions};
-    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+    use sea_orm::{DatabaseBackend, MockDatabase};
 
     fn agreement_model(coaching_session_id: Id) -> Model {
         let now = chrono::Utc::now().fixed_offset();
@@ -132,6 +172,7 @@ mod tests {
             user_id: Id::new_v4(),
             created_at: now,
             updated_at: now,
+            deleted_at: None,
         }
     }
 
@@ -153,6 +194,8 @@ mod tests {
             created_at: now,
             updated_at: now,
             hydrated_at: None,
+            session_type_id: None,
+            deleted_at: None,
         };
         let relationship = coaching_relationships::Model {
             id: relationship_id,
@@ -160,6 +203,8 @@ mod tests {
             coach_id: Id::new_v4(),
             coachee_id: Id::new_v4(),
             slug: "test-slug".to_string(),
+            ai_privacy_level: Default::default(),
+            pro_bono: false,
             created_at: now,
             updated_at: now,
         };
@@ -209,7 +254,14 @@ mod tests {
             .append_query_results(vec![vec![session_with_relationship(session_id)]])
             .into_connection();
 
-        let result = update(&db, &publisher, agreement.id, agreement.clone()).await;
+        let result = update(
+            &db,
+            &publisher,
+            agreement.id,
+            agreement.clone(),
+            &ExpectedVersion::Any,
+        )
+        .await;
 
         assert!(result.is_ok());
         let recorded = events.lock().unwrap();
@@ -226,14 +278,11 @@ mod tests {
         let agreement = agreement_model(session_id);
         let (publisher, events) = recording_publisher();
 
-        // delete: wrapper find_by_id → entity delete_by_id (find_by_id + DELETE) → participant lookup.
+        // delete: wrapper find_by_id → entity delete_by_id (find_by_id + soft-delete UPDATE) → participant lookup.
         let db = MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results(vec![vec![agreement.clone()]])
             .append_query_results(vec![vec![agreement.clone()]])
-            .append_exec_results(vec![MockExecResult {
-                last_insert_id: 0,
-                rows_affected: 1,
-            }])
+            .append_query_results(vec![vec![agreement.clone()]])
             .append_query_results(vec![vec![session_with_relationship(session_id)]])
             .into_connection();
 
@@ -255,4 +304,31 @@ mod tests {
             other => panic!("expected AgreementDeleted, got {other:?}"),
         }
     }
+
+    #[tokio::test]
+    async fn restore_publishes_agreement_created() {
+        let session_id = Id::new_v4();
+        let agreement = agreement_model(session_id);
+        let deleted = Model {
+            deleted_at: Some(chrono::Utc::now().fixed_offset()),
+            ..agreement.clone()
+        };
+        let (publisher, events) = recording_publisher();
+
+        // restore: find_deleted_by_id → UPDATE RETURNING → participant lookup.
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![deleted]])
+            .append_query_results(vec![vec![agreement.clone()]])
+            .append_query_results(vec![vec![session_with_relationship(session_id)]])
+            .into_connection();
+
+        let result = restore(&db, &publisher, agreement.id).await;
+
+        assert_eq!(result.unwrap().deleted_at, None);
+        let recorded = events.lock().unwrap();
+        assert!(matches!(
+            &recorded[..],
+            [DomainEvent::AgreementCreated { coaching_session_id, .. }] if *coaching_session_id == session_id
+        ));
+    }
 }
@@ -0,0 +1,529 @@
+//! Questions about a coaching relationship's history, answered by the LLM.
+//!
+//! Notes and (privacy level permitting) transcripts are retrieved semantically through
+//! [`crate::retrieval`] when an embedding provider and the pgvector index are available.
+//! Otherwise, and always for agreements, retrieval is keyword based: the question's
+//! significant words are matched against the relationship's content and the
+//! best-scoring excerpts are kept. A month named in the question ("in March") favours
+//! sessions held in that month rather than being searched for literally. The excerpts
+//! are numbered and handed to the model as its only sources.
+
+use chrono::{Datelike, NaiveDateTime};
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use log::*;
+use meeting_ai::traits::{analysis, embedding};
+use meeting_ai::types::analysis::{Message, StreamChunk};
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::ai_settings::{self, TaskType};
+use crate::embedding_source_type::EmbeddingSourceType;
+use crate::error::{DomainErrorKind, EntityErrorKind, Error, InternalErrorKind};
+use crate::prompt_key::PromptKey;
+use crate::{coaching_relationships, cost, prompt_template, retrieval, Id};
+use entity_api::{agreement, coaching_session, note, transcript_segment};
+
+/// Longest question accepted, in characters.
+pub const MAX_QUESTION_CHARS: usize = 2_000;
+
+/// Candidates fetched per source kind before ranking.
+const CANDIDATES_PER_KIND: u64 = 25;
+
+/// Excerpts handed to the model.
+const MAX_SOURCES: usize = 12;
+
+/// Excerpts taken from semantic retrieval; keyword matches fill the remaining slots.
+const MAX_SEMANTIC_SOURCES: usize = 9;
+
+/// Longest excerpt, in characters.
+const MAX_EXCERPT_CHARS: usize = 1_500;
+
+/// Most keywords searched for from a single question.
+const MAX_TERMS: usize = 8;
+
+const NO_SOURCES_ANSWER: &str =
+    "I couldn't find anything in this relationship's notes, agreements or transcripts \
+     related to that question.";
+
+/// Built-in system prompt; see [`crate::prompt_template`].
+pub(crate) const DEFAULT_PROMPT: &str =
+    "You help a professional coach recall what happened across \
+their sessions with a coachee. Answer the question using only the numbered sources \
+provided. Cite the sources you rely on like [1] or [2][3] and mention session dates where \
+helpful. If the sources don't contain the answer, say so plainly instead of guessing. \
+Be concise.";
+
+const STOPWORDS: &[&str] = &[
+    "about",
+    "after",
+    "again",
+    "all",
+    "and",
+    "any",
+    "are",
+    "been",
+    "before",
+    "but",
+    "can",
+    "could",
+    "did",
+    "does",
+    "for",
+    "from",
+    "had",
+    "has",
+    "have",
+    "her",
+    "his",
+    "how",
+    "into",
+    "its",
+    "last",
+    "not",
+    "our",
+    "said",
+    "she",
+    "should",
+    "that",
+    "the",
+    "their",
+    "them",
+    "then",
+    "there",
+    "they",
+    "this",
+    "was",
+    "were",
+    "what",
+    "when",
+    "where",
+    "which",
+    "who",
+    "why",
+    "will",
+    "with",
+    "would",
+    "you",
+    "your",
+    "agree",
+    "agreed",
+    "talk",
+    "talked",
+    "discuss",
+    "discussed",
+    "session",
+    "sessions",
+];
+
+const MONTHS: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceKind {
+    Note,
+    Agreement,
+    Transcript,
+}
+
+/// An excerpt the answer may cite; `[n]` in the answer refers to the n-th source.
+#[derive(Debug, Clone, Serialize)]
+pub struct Source {
+    pub kind: SourceKind,
+    pub id: Id,
+    pub coaching_session_id: Id,
+    pub session_date: NaiveDateTime,
+    pub excerpt: String,
+}
+
+/// Text deltas of the answer as the model generates them.
+pub type AnswerStream = BoxStream<'static, Result<String, Error>>;
+
+pub struct Answer {
+    pub sources: Vec<Source>,
+    pub text: AnswerStream,
+}
+
+/// Answers `question` from the relationship's history. Coach-only.
+///
+/// Fails when the relationship's AI privacy level is `disabled`, and leaves transcripts
+/// out below `full`. Without an `embedding_provider` retrieval is keyword only. Token
+/// usage is recorded against the relationship once the stream completes.
+pub async fn ask(
+    db: Arc<DatabaseConnection>,
+    analysis_provider: Option<Arc<dyn analysis::Provider>>,
+    embedding_provider: Option<Arc<dyn embedding::Provider>>,
+    user_id: Id,
+    relationship: &coaching_relationships::Model,
+    question: &str,
+) -> Result<Answer, Error> {
+    if relationship.coach_id != user_id {
+        return Err(Error::entity(EntityErrorKind::Unauthenticated));
+    }
+    if !relationship.ai_privacy_level.allows_ai() {
+        return Err(Error::validation(
+            "AI features are disabled for this coaching relationship",
+        ));
+    }
+    let question = question.trim();
+    if question.is_empty() {
+        return Err(Error::validation("`question` must not be empty"));
+    }
+    if question.chars().count() > MAX_QUESTION_CHARS {
+        return Err(Error::validation(format!(
+            "`question` must be at most {MAX_QUESTION_CHARS} characters"
+        )));
+    }
+    let provider = analysis_provider.ok_or_else(|| {
+        warn!("Analysis provider not configured");
+        Error {
+            source: None,
+            error_kind: DomainErrorKind::Internal(InternalErrorKind::Config),
+        }
+    })?;
+
+    let sources = retrieve(&db, embedding_provider.as_deref(), relationship, question).await?;
+    if sources.is_empty() {
+        let text: AnswerStream =
+            futures::stream::once(async { Ok(NO_SOURCES_ANSWER.to_string()) }).boxed();
+        return Ok(Answer { sources, text });
+    }
+
+    let prompt = prompt_template::render(&db, PromptKey::AiChat, &[]).await?;
+    debug!(
+        "ai chat: answering with prompt version {} for relationship {}",
+        prompt.version, relationship.id
+    );
+    let request = ai_settings::request(
+        &db,
+        provider.as_ref(),
+        relationship.organization_id,
+        TaskType::Chat,
+        vec![
+            Message::system(prompt.text),
+            Message::user(build_prompt(&sources, question)),
+        ],
+        false,
+    )
+    .await?;
+    let mut chunks = provider.complete_stream(request).await?;
+
+    let relationship_id = relationship.id;
+    let text = async_stream::stream! {
+        while let Some(chunk) = chunks.next().await {
+            match chunk {
+                Ok(StreamChunk::Text(delta)) => yield Ok(delta),
+                Ok(StreamChunk::Done(completion)) => {
+                    if let Err(e) =
+                        cost::record_llm_tokens(&db, None, relationship_id, completion.total_tokens())
+                            .await
+                    {
+                        warn!("ai chat: failed to record LLM cost: {e:?}");
+                    }
+                }
+                Err(e) => {
+                    warn!("ai chat: {} stream failed: {e}", provider.provider_id());
+                    yield Err(Error::from(e));
+                    break;
+                }
+            }
+        }
+    }
+    .boxed();
+
+    Ok(Answer { sources, text })
+}
+
+/// The most relevant excerpts for `question` across the relationship's sessions.
+async fn retrieve(
+    db: &DatabaseConnection,
+    embedding_provider: Option<&dyn embedding::Provider>,
+    relationship: &coaching_relationships::Model,
+    question: &str,
+) -> Result<Vec<Source>, Error> {
+    let (terms, months) = keywords(question);
+
+    let session_dates: HashMap<Id, NaiveDateTime> =
+        coaching_session::find_by_relationship(db, relationship.id)
+            .await?
+            .into_iter()
+            .map(|session| (session.id, session.date))
+            .collect();
+    let session_ids: Vec<Id> = session_dates.keys().copied().collect();
+
+    let semantic = match embedding_provider {
+        Some(provider) => semantic_sources(db, provider, relationship, question, &session_dates)
+            .await
+            .unwrap_or_else(|e| {
+                warn!("ai chat: semantic retrieval failed, using keywords only: {e:?}");
+                Vec::new()
+            }),
+        None => Vec::new(),
+    };
+    if terms.is_empty() {
+        return Ok(semantic);
+    }
+
+    let mut candidates: Vec<(SourceKind, Id, Id, String)> = Vec::new();
+    for agreement in
+        agreement::search_in_sessions(db, &session_ids, &terms, CANDIDATES_PER_KIND).await?
+    {
+        if let Some(body) = agreement.body {
+            candidates.push((
+                SourceKind::Agreement,
+                agreement.id,
+                agreement.coaching_session_id,
+                body,
+            ));
+        }
+    }
+    // Notes and transcripts come from semantic retrieval when it found anything.
+    if semantic.is_empty() {
+        // Encrypted notes can only be found through their blind index
+        let notes = note::search_in_sessions(db, &session_ids, &terms, CANDIDATES_PER_KIND)
+            .await?
+            .into_iter()
+            .chain(
+                note::search_encrypted_in_relationship(
+                    db,
+                    relationship.id,
+                    &terms,
+                    CANDIDATES_PER_KIND,
+                )
+                .await?,
+            );
+        for note in notes {
+            if let Some(body) = note.body {
+                candidates.push((SourceKind::Note, note.id, note.coaching_session_id, body));
+            }
+        }
+        if relationship.ai_privacy_level.allows_transcripts() {
+            for (segment, coaching_session_id) in transcript_segment::search_in_sessions(
+                db,
+                &session_ids,
+                &terms,
+                CANDIDATES_PER_KIND,
+            )
+            .await?
+            {
+                candidates.push((
+                    SourceKind::Transcript,
+                    segment.id,
+                    coaching_session_id,
+                    format!("{}: {}", segment.speaker_label, segment.text),
+                ));
+            }
+        }
+    }
+
+    let scored = candidates
+        .into_iter()
+        .filter_map(|(kind, id, coaching_session_id, text)| {
+            let session_date = *session_dates.get(&coaching_session_id)?;
+            let excerpt = excerpt(&text);
+            let score = score(&excerpt, &terms, &months, session_date);
+            Some((
+                score,
+                Source {
+                    kind,
+                    id,
+                    coaching_session_id,
+                    session_date,
+                    excerpt,
+                },
+            ))
+        })
+        .collect();
+
+    let mut sources = semantic;
+    let remaining = MAX_SOURCES - sources.len();
+    sources.extend(rank(scored).into_iter().take(remaining));
+    Ok(sources)
+}
+
+/// Up to [`MAX_SEMANTIC_SOURCES`] notes and transcript excerpts closest in meaning to
+/// the question; empty when the embedding index is unavailable.
+async fn semantic_sources(
+    db: &DatabaseConnection,
+    provider: &dyn embedding::Provider,
+    relationship: &coaching_relationships::Model,
+    question: &str,
+    session_dates: &HashMap<Id, NaiveDateTime>,
+) -> Result<Vec<Source>, Error> {
+    let matches = retrieval::search(
+        db,
+        provider,
+        relationship,
+        question,
+        MAX_SEMANTIC_SOURCES as u64,
+    )
+    .await?
+    .unwrap_or_default();
+
+    Ok(matches
+        .into_iter()
+        .filter_map(|found| {
+            Some(Source {
+                kind: match found.source_type {
+                    EmbeddingSourceType::Note => SourceKind::Note,
+                    EmbeddingSourceType::TranscriptSegment => SourceKind::Transcript,
+                },
+                id: found.source_id,
+                session_date: *session_dates.get(&found.coaching_session_id)?,
+                coaching_session_id: found.coaching_session_id,
+                excerpt: excerpt(&found.content),
+            })
+        })
+        .collect())
+}
+
+/// Significant lowercase words of the question, plus the month numbers (1-12) it names.
+fn keywords(question: &str) -> (Vec<String>, HashSet<u32>) {
+    let mut terms = Vec::new();
+    let mut months = HashSet::new();
+
+    for word in question
+        .split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+    {
+        if let Some(index) = MONTHS.iter().position(|month| *month == word) {
+            months.insert(index as u32 + 1);
+        } else if word.chars().count() >= 3
+            && !STOPWORDS.contains(&word.as_str())
+            && !terms.contains(&word)
+        {
+            terms.push(word);
+        }
+    }
+    terms.truncate(MAX_TERMS);
+
+    (terms, months)
+}
+
+/// Distinct terms present in the excerpt, plus one when the session falls in a month the
+/// question named.
+fn score(excerpt: &str, terms: &[String], months: &HashSet<u32>, date: NaiveDateTime) -> usize {
+    let excerpt = excerpt.to_lowercase();
+    let matches = terms
+        .iter()
+        .filter(|term| excerpt.contains(term.as_str()))
+        .count();
+    matches + usize::from(months.contains(&date.month()))
+}
+
+/// Best score first, then most recent session; keeps the top [`MAX_SOURCES`].
+fn rank(mut scored: Vec<(usize, Source)>) -> Vec<Source> {
+    scored.sort_by(|(a_score, a), (b_score, b)| {
+        b_score
+            .cmp(a_score)
+            .then(b.session_date.cmp(&a.session_date))
+    });
+    scored
+        .into_iter()
+        .take(MAX_SOURCES)
+        .map(|(_, source)| source)
+        .collect()
+}
+
+/// The text as plain prose, cut to [`MAX_EXCERPT_CHARS`].
+fn excerpt(text: &str) -> String {
+    retrieval::plain_text(text, MAX_EXCERPT_CHARS)
+}
+
+fn build_prompt(sources: &[Source], question: &str) -> String {
+    let mut prompt = String::from("Sources:\n");
+    for (index, source) in sources.iter().enumerate() {
+        let kind = match source.kind {
+            SourceKind::Note => "Note",
+            SourceKind::Agreement => "Agreement",
+            SourceKind::Transcript => "Transcript excerpt",
+        };
+        prompt.push_str(&format!(
+            "[{}] {kind} from the session on {}: {}\n",
+            index + 1,
+            source.session_date.date(),
+            source.excerpt
+        ));
+    }
+    prompt.push_str(&format!("\nQuestion: {question}"));
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn date(month: u32, day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, month, day)
+            .unwrap()
+            .and_hms_opt(15, 0, 0)
+            .unwrap()
+    }
+
+    fn source(session_date: NaiveDateTime, excerpt: &str) -> Source {
+        Source {
+            kind: SourceKind::Note,
+            id: Id::new_v4(),
+            coaching_session_id: Id::new_v4(),
+            session_date,
+            excerpt: excerpt.to_string(),
+        }
+    }
+
+    #[test]
+    fn keywords_drop_stopwords_and_pull_out_months() {
+        let (terms, months) = keywords("What did we agree about Delegation in March?");
+
+        assert_eq!(terms, vec!["delegation"]);
+        assert_eq!(months, HashSet::from([3]));
+    }
+
+    #[test]
+    fn rank_orders_by_matches_plus_month_bonus_then_recency() {
+        let terms = vec!["delegation".to_string(), "team".to_string()];
+        let months = HashSet::from([3]);
+        let sources = vec![
+            source(date(5, 2), "Delegation to the team lead"),
+            source(date(3, 10), "Delegation was stuck"),
+            source(date(4, 1), "Delegation again"),
+            source(date(6, 1), "Team offsite planning"),
+        ];
+
+        let ranked = rank(
+            sources
+                .into_iter()
+                .map(|s| (score(&s.excerpt, &terms, &months, s.session_date), s))
+                .collect(),
+        );
+
+        let dates: Vec<_> = ranked.iter().map(|s| s.session_date).collect();
+        assert_eq!(dates, vec![date(5, 2), date(3, 10), date(6, 1), date(4, 1)]);
+    }
+
+    #[test]
+    fn excerpt_strips_markup_and_truncates() {
+        assert_eq!(
+            excerpt("<p>Try <strong>delegating</strong>\n the  report</p>"),
+            "Try delegating the report"
+        );
+
+        let long = "a".repeat(MAX_EXCERPT_CHARS + 10);
+        assert_eq!(excerpt(&long).chars().count(), MAX_EXCERPT_CHARS + 1);
+    }
+}
@@ -0,0 +1,397 @@
+//! Organization-level AI configuration.
+//!
+//! An organization's admins choose which providers and models its AI features may use
+//! and, per task type, the model, temperature and completion token budget. Settings are
+//! validated against [`REGISTRY`], the models the platform knows how to call. AI features
+//! build their completion requests with [`request`], so anything an organization leaves
+//! unset falls back to the task's platform default and the provider's default model.
+
+use chrono::Utc;
+use entity_api::organization_ai_setting;
+use meeting_ai::traits::analysis;
+use meeting_ai::types::analysis::{Message, Request};
+use sea_orm::DatabaseConnection;
+
+use crate::error::Error;
+use crate::organization_ai_settings::{AllowList, Model, TaskSetting, TaskSettings};
+use crate::Id;
+
+/// Highest completion token budget accepted for a task that doesn't name a model.
+pub const MAX_TOKEN_BUDGET: u32 = 16_384;
+
+/// Highest sampling temperature accepted.
+pub const MAX_TEMPERATURE: f32 = 2.0;
+
+/// The kinds of work AI features ask a model to do, each configured separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskType {
+    /// Structured output pulled from session content (themes reports).
+    Extraction,
+    /// Summaries of sessions (prep briefs).
+    Summary,
+    /// Answers to questions about a relationship's history.
+    Chat,
+}
+
+impl TaskType {
+    fn name(self) -> &'static str {
+        match self {
+            TaskType::Extraction => "extraction",
+            TaskType::Summary => "summary",
+            TaskType::Chat => "chat",
+        }
+    }
+
+    /// The platform's temperature and completion token budget for the task.
+    fn defaults(self) -> (f32, u32) {
+        match self {
+            TaskType::Extraction => (0.3, 1_200),
+            TaskType::Summary => (0.3, 800),
+            TaskType::Chat => (0.2, 700),
+        }
+    }
+
+    fn setting(self, tasks: &TaskSettings) -> &TaskSetting {
+        match self {
+            TaskType::Extraction => &tasks.extraction,
+            TaskType::Summary => &tasks.summary,
+            TaskType::Chat => &tasks.chat,
+        }
+    }
+}
+
+/// A model the platform can call, the provider serving it and its output limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegisteredModel {
+    pub provider: &'static str,
+    pub id: &'static str,
+    pub max_output_tokens: u32,
+}
+
+/// Models organizations may choose from. Ids are as the provider expects them; the LLM
+/// gateway serves both OpenAI's own ids and OpenRouter-style `vendor/model` ids.
+pub const REGISTRY: &[RegisteredModel] = &[
+    RegisteredModel {
+        provider: "llm_gateway",
+        id: "gpt-4o-mini",
+        max_output_tokens: 16_384,
+    },
+    RegisteredModel {
+        provider: "llm_gateway",
+        id: "gpt-4o",
+        max_output_tokens: 16_384,
+    },
+    RegisteredModel {
+        provider: "llm_gateway",
+        id: "gpt-4.1-mini",
+        max_output_tokens: 32_768,
+    },
+    RegisteredModel {
+        provider: "llm_gateway",
+        id: "gpt-4.1",
+        max_output_tokens: 32_768,
+    },
+    RegisteredModel {
+        provider: "llm_gateway",
+        id: "anthropic/claude-3.5-haiku",
+        max_output_tokens: 8_192,
+    },
+    RegisteredModel {
+        provider: "llm_gateway",
+        id: "anthropic/claude-sonnet-4",
+        max_output_tokens: 64_000,
+    },
+];
+
+fn registered(model: &str) -> Option<&'static RegisteredModel> {
+    REGISTRY.iter().find(|registered| registered.id == model)
+}
+
+/// The organization's AI settings, or the unrestricted defaults when it has none.
+pub async fn find(db: &DatabaseConnection, organization_id: Id) -> Result<Model, Error> {
+    Ok(
+        organization_ai_setting::find_by_organization_id(db, organization_id)
+            .await?
+            .unwrap_or_else(|| defaults(organization_id)),
+    )
+}
+
+/// Validates and stores the organization's AI settings, replacing any earlier ones.
+pub async fn update(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    settings: Model,
+) -> Result<Model, Error> {
+    validate(&settings)?;
+    Ok(organization_ai_setting::upsert(db, organization_id, settings).await?)
+}
+
+/// A completion request for `task` configured by the organization's settings.
+///
+/// Fails with a validation error when the settings don't allow `provider` or any model
+/// it serves.
+pub async fn request(
+    db: &DatabaseConnection,
+    provider: &dyn analysis::Provider,
+    organization_id: Id,
+    task: TaskType,
+    messages: Vec<Message>,
+    json_response: bool,
+) -> Result<Request, Error> {
+    let settings = find(db, organization_id).await?;
+    let (model, temperature, max_tokens) = resolve(
+        &settings,
+        provider.provider_id(),
+        provider.default_model(),
+        task,
+    )?;
+
+    Ok(Request {
+        model: Some(model),
+        messages,
+        max_tokens,
+        temperature,
+        json_response,
+    })
+}
+
+/// The completion token budget `task` runs with on the provider, for estimates.
+pub async fn max_tokens(
+    db: &DatabaseConnection,
+    provider: &dyn analysis::Provider,
+    organization_id: Id,
+    task: TaskType,
+) -> Result<u32, Error> {
+    let settings = find(db, organization_id).await?;
+    let (_, _, max_tokens) = resolve(
+        &settings,
+        provider.provider_id(),
+        provider.default_model(),
+        task,
+    )?;
+    Ok(max_tokens)
+}
+
+/// The model, temperature and token budget `task` runs with on the provider.
+///
+/// The task's own model wins; otherwise the provider's default if allowed, else the
+/// first allowed model the provider serves. Budgets are capped at a registered model's
+/// output limit.
+fn resolve(
+    settings: &Model,
+    provider_id: &str,
+    default_model: &str,
+    task: TaskType,
+) -> Result<(String, f32, u32), Error> {
+    if !allows(&settings.allowed_providers, provider_id) {
+        return Err(Error::validation(format!(
+            "The organization's AI settings don't allow the `{provider_id}` provider"
+        )));
+    }
+
+    let setting = task.setting(&settings.tasks);
+    let model = match setting.model.as_deref() {
+        Some(model) if registered(model).is_some_and(|r| r.provider != provider_id) => {
+            return Err(Error::validation(format!(
+                "The {} model `{model}` isn't served by the `{provider_id}` provider",
+                task.name()
+            )));
+        }
+        Some(model) => model.to_string(),
+        None if allows(&settings.allowed_models, default_model) => default_model.to_string(),
+        None => settings
+            .allowed_models
+            .0
+            .iter()
+            .find(|model| registered(model).is_some_and(|r| r.provider == provider_id))
+            .cloned()
+            .ok_or_else(|| {
+                Error::validation(format!(
+                    "None of the organization's allowed AI models is served by the \
+                     `{provider_id}` provider"
+                ))
+            })?,
+    };
+
+    let (default_temperature, default_max_tokens) = task.defaults();
+    let mut max_tokens = setting.max_tokens.unwrap_or(default_max_tokens);
+    if let Some(registered) = registered(&model) {
+        max_tokens = max_tokens.min(registered.max_output_tokens);
+    }
+
+    Ok((
+        model,
+        setting.temperature.unwrap_or(default_temperature),
+        max_tokens,
+    ))
+}
+
+fn validate(settings: &Model) -> Result<(), Error> {
+    for provider in &settings.allowed_providers.0 {
+        if !REGISTRY.iter().any(|r| r.provider == provider) {
+            return Err(Error::validation(format!(
+                "`allowed_providers` contains the unknown provider `{provider}`"
+            )));
+        }
+    }
+    for model in &settings.allowed_models.0 {
+        let registered = registered(model).ok_or_else(|| {
+            Error::validation(format!(
+                "`allowed_models` contains the unknown model `{model}`"
+            ))
+        })?;
+        if !allows(&settings.allowed_providers, registered.provider) {
+            return Err(Error::validation(format!(
+                "`allowed_models` contains `{model}`, whose provider `{}` isn't allowed",
+                registered.provider
+            )));
+        }
+    }
+
+    for task in [TaskType::Extraction, TaskType::Summary, TaskType::Chat] {
+        let setting = task.setting(&settings.tasks);
+        let name = task.name();
+
+        let mut max_output_tokens = MAX_TOKEN_BUDGET;
+        if let Some(model) = setting.model.as_deref() {
+            let registered = registered(model).ok_or_else(|| {
+                Error::validation(format!("`tasks.{name}.model` `{model}` is unknown"))
+            })?;
+            if !allows(&settings.allowed_models, model)
+                || !allows(&settings.allowed_providers, registered.provider)
+            {
+                return Err(Error::validation(format!(
+                    "`tasks.{name}.model` `{model}` isn't allowed by the organization"
+                )));
+            }
+            max_output_tokens = registered.max_output_tokens;
+        }
+
+        if let Some(temperature) = setting.temperature {
+            if !(0.0..=MAX_TEMPERATURE).contains(&temperature) {
+                return Err(Error::validation(format!(
+                    "`tasks.{name}.temperature` must be between 0 and {MAX_TEMPERATURE}"
+                )));
+            }
+        }
+        if let Some(max_tokens) = setting.max_tokens {
+            if !(1..=max_output_tokens).contains(&max_tokens) {
+                return Err(Error::validation(format!(
+                    "`tasks.{name}.max_tokens` must be between 1 and {max_output_tokens}"
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether `id` passes the allow-list; an empty list allows everything.
+fn allows(list: &AllowList, id: &str) -> bool {
+    list.0.is_empty() || list.0.iter().any(|allowed| allowed == id)
+}
+
+fn defaults(organization_id: Id) -> Model {
+    let now = Utc::now().fixed_offset();
+    Model {
+        organization_id,
+        allowed_providers: AllowList::default(),
+        allowed_models: AllowList::default(),
+        tasks: TaskSettings::default(),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(allowed_models: &[&str], summary: TaskSetting) -> Model {
+        Model {
+            allowed_models: AllowList(allowed_models.iter().map(|m| m.to_string()).collect()),
+            tasks: TaskSettings {
+                summary,
+                ..Default::default()
+            },
+            ..defaults(Id::new_v4())
+        }
+    }
+
+    #[test]
+    fn resolve_uses_task_defaults_without_settings() {
+        let resolved = resolve(
+            &defaults(Id::new_v4()),
+            "llm_gateway",
+            "gpt-4o-mini",
+            TaskType::Chat,
+        )
+        .unwrap();
+
+        assert_eq!(resolved, ("gpt-4o-mini".to_string(), 0.2, 700));
+    }
+
+    #[test]
+    fn resolve_falls_back_to_an_allowed_model_and_caps_the_budget() {
+        let settings = settings(
+            &["anthropic/claude-3.5-haiku"],
+            TaskSetting {
+                model: None,
+                temperature: Some(0.7),
+                max_tokens: Some(10_000),
+            },
+        );
+
+        let resolved = resolve(&settings, "llm_gateway", "gpt-4o-mini", TaskType::Summary).unwrap();
+
+        assert_eq!(
+            resolved,
+            ("anthropic/claude-3.5-haiku".to_string(), 0.7, 8_192)
+        );
+    }
+
+    #[test]
+    fn resolve_rejects_a_provider_that_isnt_allowed() {
+        let settings = Model {
+            allowed_providers: AllowList(vec!["llm_gateway".to_string()]),
+            ..defaults(Id::new_v4())
+        };
+
+        assert!(resolve(&settings, "anthropic", "claude-sonnet-4", TaskType::Chat).is_err());
+    }
+
+    #[test]
+    fn validate_checks_models_against_the_registry_and_allow_list() {
+        let unknown = settings(&["gpt-2"], TaskSetting::default());
+        assert!(validate(&unknown).is_err());
+
+        let not_allowed = settings(
+            &["gpt-4o-mini"],
+            TaskSetting {
+                model: Some("gpt-4o".to_string()),
+                ..Default::default()
+            },
+        );
+        assert!(validate(&not_allowed).is_err());
+
+        let over_budget = settings(
+            &[],
+            TaskSetting {
+                model: Some("gpt-4o".to_string()),
+                temperature: Some(0.5),
+                max_tokens: Some(20_000),
+            },
+        );
+        assert!(validate(&over_budget).is_err());
+
+        let valid = settings(
+            &["gpt-4o", "gpt-4o-mini"],
+            TaskSetting {
+                model: Some("gpt-4o".to_string()),
+                temperature: Some(0.5),
+                max_tokens: Some(2_000),
+            },
+        );
+        assert!(validate(&valid).is_ok());
+    }
+}
@@ -0,0 +1,435 @@
+//! Per-organization API usage and quotas.
+//!
+//! The web layer reports each request an organization makes to an [`ApiUsageTracker`],
+//! which tallies requests, errors and latencies per endpoint in memory. Flushing it adds
+//! the tallies to hourly rollups every replica shares; admins read them with [`report`].
+//!
+//! SuperAdmins may give an organization a quota of requests per hour, overall and per
+//! endpoint (see [`update_quota`]). The tracker refuses requests past it, counting what
+//! every replica had stored at its last flush plus its own requests since, so other
+//! replicas' requests between flushes can overrun a quota slightly.
+
+use chrono::{DateTime, DurationRound, TimeDelta, Utc};
+use entity_api::{api_usage_rollup, organization_api_quota};
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::DatabaseConnection;
+use std::collections::HashMap;
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+
+use crate::api_usage_rollups;
+use crate::error::Error;
+use crate::organization_api_quotas::{EndpointLimits, Model};
+use crate::Id;
+
+pub use entity_api::api_usage_rollup::EndpointUsage;
+
+/// Days reported when the caller doesn't give a start.
+pub const DEFAULT_REPORT_DAYS: i64 = 7;
+
+/// Longest window reported at once.
+pub const MAX_REPORT_DAYS: i64 = 366;
+
+/// Requests, errors and latencies of one organization's calls to one endpoint in one hour.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Tally {
+    requests: i64,
+    errors: i64,
+    total_latency_ms: i64,
+    max_latency_ms: i64,
+}
+
+impl Tally {
+    fn add(&mut self, other: Tally) {
+        self.requests += other.requests;
+        self.errors += other.errors;
+        self.total_latency_ms += other.total_latency_ms;
+        self.max_latency_ms = self.max_latency_ms.max(other.max_latency_ms);
+    }
+}
+
+/// Organization, endpoint and start of the hour tallied.
+type TallyKey = (Id, String, DateTime<Utc>);
+
+/// What quotas are checked against, as of the last flush.
+#[derive(Debug, Default)]
+struct QuotaWindow {
+    /// Start of the hour `stored` counts.
+    period_start: Option<DateTime<Utc>>,
+    quotas: HashMap<Id, Model>,
+    /// Requests every replica had stored, per organization with a quota and endpoint.
+    stored: HashMap<(Id, String), i64>,
+}
+
+/// A request refused because the organization used up its quota for the hour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuotaExceeded {
+    /// Requests per hour allowed.
+    pub limit: u32,
+    /// Time until the hour ends and the quota is renewed.
+    pub retry_after: Duration,
+}
+
+/// Tallies organizations' requests until the next flush and enforces their quotas.
+#[derive(Debug, Default)]
+pub struct ApiUsageTracker {
+    pending: Mutex<HashMap<TallyKey, Tally>>,
+    window: RwLock<QuotaWindow>,
+}
+
+impl ApiUsageTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts a request the organization made to `endpoint`, e.g. `GET /actions/:id`.
+    pub fn record(&self, organization_id: Id, endpoint: &str, failed: bool, latency: Duration) {
+        self.record_at(organization_id, endpoint, failed, latency, Utc::now());
+    }
+
+    fn record_at(
+        &self,
+        organization_id: Id,
+        endpoint: &str,
+        failed: bool,
+        latency: Duration,
+        now: DateTime<Utc>,
+    ) {
+        let latency_ms = latency.as_millis().min(i64::MAX as u128) as i64;
+        let key = (organization_id, endpoint.to_string(), hour_start(now));
+        self.pending
+            .lock()
+            .unwrap()
+            .entry(key)
+            .or_default()
+            .add(Tally {
+                requests: 1,
+                errors: failed as i64,
+                total_latency_ms: latency_ms,
+                max_latency_ms: latency_ms,
+            });
+    }
+
+    /// Whether the organization may make another request to `endpoint` this hour.
+    pub fn check_quota(&self, organization_id: Id, endpoint: &str) -> Result<(), QuotaExceeded> {
+        self.check_quota_at(organization_id, endpoint, Utc::now())
+    }
+
+    fn check_quota_at(
+        &self,
+        organization_id: Id,
+        endpoint: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), QuotaExceeded> {
+        let window = self.window.read().unwrap();
+        let Some(quota) = window.quotas.get(&organization_id) else {
+            return Ok(());
+        };
+        let period_start = hour_start(now);
+        let endpoint_limit = quota.endpoint_limits.0.get(endpoint).copied();
+        let overall_limit = quota.requests_per_hour.map(|limit| limit.max(0) as u32);
+        if endpoint_limit.is_none() && overall_limit.is_none() {
+            return Ok(());
+        }
+
+        // Counts stored for an earlier hour don't count against this one.
+        let current = window.period_start == Some(period_start);
+        let (mut endpoint_count, mut overall_count) = (0, 0);
+        for ((id, name), count) in window.stored.iter().filter(|_| current) {
+            if *id == organization_id {
+                overall_count += count;
+                if name == endpoint {
+                    endpoint_count += count;
+                }
+            }
+        }
+        for ((id, name, start), tally) in self.pending.lock().unwrap().iter() {
+            if *id == organization_id && *start == period_start {
+                overall_count += tally.requests;
+                if name == endpoint {
+                    endpoint_count += tally.requests;
+                }
+            }
+        }
+
+        let retry_after = (period_start + TimeDelta::hours(1) - now)
+            .to_std()
+            .unwrap_or_default();
+        for (limit, count) in [
+            (endpoint_limit, endpoint_count),
+            (overall_limit, overall_count),
+        ] {
+            if let Some(limit) = limit {
+                if count >= limit as i64 {
+                    return Err(QuotaExceeded { limit, retry_after });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds the requests tallied since the last flush to the stored rollups, then reloads
+    /// quotas and this hour's stored counts. Tallies that fail to store are kept for the
+    /// next flush. Returns how many rollups were stored.
+    pub async fn flush(&self, db: &DatabaseConnection) -> Result<usize, Error> {
+        let pending = std::mem::take(&mut *self.pending.lock().unwrap());
+        let rollups: Vec<api_usage_rollups::Model> = pending
+            .iter()
+            .map(
+                |((organization_id, endpoint, period_start), tally)| api_usage_rollups::Model {
+                    organization_id: *organization_id,
+                    endpoint: endpoint.clone(),
+                    period_start: (*period_start).into(),
+                    request_count: tally.requests,
+                    error_count: tally.errors,
+                    total_latency_ms: tally.total_latency_ms,
+                    max_latency_ms: tally.max_latency_ms,
+                },
+            )
+            .collect();
+
+        if let Err(e) = api_usage_rollup::add(db, &rollups).await {
+            let mut current = self.pending.lock().unwrap();
+            for (key, tally) in pending {
+                current.entry(key).or_default().add(tally);
+            }
+            return Err(e.into());
+        }
+
+        let period_start = hour_start(Utc::now());
+        let quotas = organization_api_quota::find_all(db).await?;
+        let counts = api_usage_rollup::find_quota_counts(db, period_start.into()).await?;
+        *self.window.write().unwrap() = QuotaWindow {
+            period_start: Some(period_start),
+            quotas: quotas
+                .into_iter()
+                .map(|quota| (quota.organization_id, quota))
+                .collect(),
+            stored: counts
+                .into_iter()
+                .map(|count| ((count.organization_id, count.endpoint), count.request_count))
+                .collect(),
+        };
+
+        Ok(rollups.len())
+    }
+}
+
+/// The organization's usage per endpoint over the hours starting within `[from, to)`,
+/// busiest endpoint first. `to` defaults to now and `from` to [`DEFAULT_REPORT_DAYS`]
+/// before `to`.
+pub async fn report(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    from: Option<DateTimeWithTimeZone>,
+    to: Option<DateTimeWithTimeZone>,
+) -> Result<Vec<EndpointUsage>, Error> {
+    let to = to.unwrap_or_else(|| Utc::now().into());
+    let from = from.unwrap_or(to - TimeDelta::days(DEFAULT_REPORT_DAYS));
+    if from >= to {
+        return Err(Error::validation("`from` must be before `to`"));
+    }
+    if to - from > TimeDelta::days(MAX_REPORT_DAYS) {
+        return Err(Error::validation(format!(
+            "Usage can be reported for at most {MAX_REPORT_DAYS} days at once"
+        )));
+    }
+
+    Ok(api_usage_rollup::summarize(db, organization_id, from, to).await?)
+}
+
+/// The organization's API quota, or none (no limits) when it has none.
+pub async fn find_quota(db: &DatabaseConnection, organization_id: Id) -> Result<Model, Error> {
+    Ok(
+        organization_api_quota::find_by_organization_id(db, organization_id)
+            .await?
+            .unwrap_or_else(|| unlimited(organization_id)),
+    )
+}
+
+/// Validates and stores the organization's API quota, replacing any earlier one. Takes
+/// effect on every replica at its next flush.
+pub async fn update_quota(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    quota: Model,
+) -> Result<Model, Error> {
+    validate(&quota)?;
+    Ok(organization_api_quota::upsert(db, organization_id, quota).await?)
+}
+
+/// Removes the organization's API quota.
+pub async fn delete_quota(db: &DatabaseConnection, organization_id: Id) -> Result<(), Error> {
+    Ok(organization_api_quota::delete_by_organization_id(db, organization_id).await?)
+}
+
+fn unlimited(organization_id: Id) -> Model {
+    let now = Utc::now();
+    Model {
+        organization_id,
+        requests_per_hour: None,
+        endpoint_limits: EndpointLimits::default(),
+        created_at: now.into(),
+        updated_at: now.into(),
+    }
+}
+
+fn validate(quota: &Model) -> Result<(), Error> {
+    if quota.requests_per_hour.is_some_and(|limit| limit < 1) {
+        return Err(Error::validation("`requests_per_hour` must be at least 1"));
+    }
+    for (endpoint, limit) in &quota.endpoint_limits.0 {
+        let valid = endpoint.split_once(' ').is_some_and(|(method, path)| {
+            !method.is_empty()
+                && method.chars().all(|c| c.is_ascii_uppercase())
+                && path.starts_with('/')
+        });
+        if !valid {
+            return Err(Error::validation(format!(
+                "`{endpoint}` is not an endpoint; name one as `METHOD /route`, e.g. `GET /actions/:id`"
+            )));
+        }
+        if *limit < 1 {
+            return Err(Error::validation(format!(
+                "The limit for `{endpoint}` must be at least 1"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn hour_start(time: DateTime<Utc>) -> DateTime<Utc> {
+    time.duration_trunc(TimeDelta::hours(1)).unwrap_or(time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const ENDPOINT: &str = "GET /actions/:id";
+
+    fn quota(organization_id: Id, requests_per_hour: Option<i32>, endpoint: Option<u32>) -> Model {
+        Model {
+            requests_per_hour,
+            endpoint_limits: EndpointLimits(
+                endpoint
+                    .map(|limit| (ENDPOINT.to_string(), limit))
+                    .into_iter()
+                    .collect(),
+            ),
+            ..unlimited(organization_id)
+        }
+    }
+
+    fn tracker_with(quota: Model, stored: i64, period_start: DateTime<Utc>) -> ApiUsageTracker {
+        let tracker = ApiUsageTracker::new();
+        *tracker.window.write().unwrap() = QuotaWindow {
+            period_start: Some(period_start),
+            stored: [((quota.organization_id, ENDPOINT.to_string()), stored)]
+                .into_iter()
+                .collect(),
+            quotas: [(quota.organization_id, quota)].into_iter().collect(),
+        };
+        tracker
+    }
+
+    #[test]
+    fn record_tallies_requests_per_endpoint_and_hour() {
+        let tracker = ApiUsageTracker::new();
+        let organization_id = Id::new_v4();
+        let now = Utc.with_ymd_and_hms(2026, 10, 14, 9, 30, 0).unwrap();
+
+        tracker.record_at(
+            organization_id,
+            ENDPOINT,
+            false,
+            Duration::from_millis(20),
+            now,
+        );
+        tracker.record_at(
+            organization_id,
+            ENDPOINT,
+            true,
+            Duration::from_millis(80),
+            now,
+        );
+
+        let pending = tracker.pending.lock().unwrap();
+        let hour = Utc.with_ymd_and_hms(2026, 10, 14, 9, 0, 0).unwrap();
+        assert_eq!(
+            pending[&(organization_id, ENDPOINT.to_string(), hour)],
+            Tally {
+                requests: 2,
+                errors: 1,
+                total_latency_ms: 100,
+                max_latency_ms: 80,
+            }
+        );
+    }
+
+    #[test]
+    fn check_quota_counts_stored_and_pending_requests() {
+        let organization_id = Id::new_v4();
+        let now = Utc.with_ymd_and_hms(2026, 10, 14, 9, 45, 0).unwrap();
+        let tracker = tracker_with(quota(organization_id, Some(3), None), 2, hour_start(now));
+
+        assert_eq!(
+            tracker.check_quota_at(organization_id, ENDPOINT, now),
+            Ok(())
+        );
+        tracker.record_at(organization_id, "POST /notes", false, Duration::ZERO, now);
+
+        assert_eq!(
+            tracker.check_quota_at(organization_id, ENDPOINT, now),
+            Err(QuotaExceeded {
+                limit: 3,
+                retry_after: Duration::from_secs(15 * 60),
+            })
+        );
+        assert_eq!(tracker.check_quota_at(Id::new_v4(), ENDPOINT, now), Ok(()));
+    }
+
+    #[test]
+    fn check_quota_applies_endpoint_limits_to_their_endpoint_only() {
+        let organization_id = Id::new_v4();
+        let now = Utc.with_ymd_and_hms(2026, 10, 14, 9, 0, 0).unwrap();
+        let tracker = tracker_with(quota(organization_id, None, Some(2)), 2, hour_start(now));
+
+        assert!(tracker
+            .check_quota_at(organization_id, ENDPOINT, now)
+            .is_err());
+        assert_eq!(
+            tracker.check_quota_at(organization_id, "POST /notes", now),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn check_quota_ignores_counts_stored_for_an_earlier_hour() {
+        let organization_id = Id::new_v4();
+        let now = Utc.with_ymd_and_hms(2026, 10, 14, 10, 5, 0).unwrap();
+        let earlier = Utc.with_ymd_and_hms(2026, 10, 14, 9, 0, 0).unwrap();
+        let tracker = tracker_with(quota(organization_id, Some(2), None), 5, earlier);
+
+        assert_eq!(
+            tracker.check_quota_at(organization_id, ENDPOINT, now),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn validate_rejects_malformed_endpoints_and_zero_limits() {
+        let organization_id = Id::new_v4();
+        assert!(validate(&quota(organization_id, Some(100), Some(10))).is_ok());
+        assert!(validate(&quota(organization_id, Some(0), None)).is_err());
+        assert!(validate(&quota(organization_id, None, Some(0))).is_err());
+
+        let mut malformed = quota(organization_id, None, None);
+        malformed
+            .endpoint_limits
+            .0
+            .insert("actions/:id".to_string(), 10);
+        assert!(validate(&malformed).is_err());
+    }
+}
@@ -0,0 +1,396 @@
+//! Files shared in coaching sessions, such as PDFs and worksheets.
+//!
+//! A file is attached to a session, optionally on one of its notes or actions. Its bytes
+//! never pass through the API: attaching a file records it and answers a presigned URL
+//! the client uploads it to, which only accepts the declared size and type, and
+//! downloading answers a presigned URL to fetch it from. Files are checked against the
+//! allowed types and the largest size configured, and each organization's attachments
+//! together against its storage quota.
+
+use chrono::{DateTime, Utc};
+use entity_api::{action, attachment, coaching_relationship, note};
+use log::*;
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+use service::config::Config;
+use std::time::Duration;
+
+use crate::attachments::Model;
+use crate::error::{EntityErrorKind, Error};
+use crate::gateway::object_storage::ObjectStorage;
+use crate::{coaching_sessions, Id};
+
+/// How long upload and download URLs stay valid.
+pub const URL_EXPIRY: Duration = Duration::from_secs(15 * 60);
+
+/// Longest file name kept, in characters.
+const MAX_FILE_NAME_CHARS: usize = 255;
+
+/// Content types that may be attached, each with the file extensions it's accepted under.
+const ALLOWED_TYPES: &[(&str, &[&str])] = &[
+    ("application/pdf", &["pdf"]),
+    ("application/msword", &["doc"]),
+    (
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        &["docx"],
+    ),
+    ("application/vnd.ms-excel", &["xls"]),
+    (
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        &["xlsx"],
+    ),
+    ("application/vnd.ms-powerpoint", &["ppt"]),
+    (
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        &["pptx"],
+    ),
+    ("application/vnd.oasis.opendocument.text", &["odt"]),
+    ("application/vnd.oasis.opendocument.spreadsheet", &["ods"]),
+    ("application/vnd.oasis.opendocument.presentation", &["odp"]),
+    ("text/plain", &["txt"]),
+    ("text/markdown", &["md", "markdown"]),
+    ("text/csv", &["csv"]),
+    ("image/png", &["png"]),
+    ("image/jpeg", &["jpg", "jpeg"]),
+    ("image/gif", &["gif"]),
+    ("image/webp", &["webp"]),
+];
+
+/// A file to attach, as declared by the client before uploading it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewAttachment {
+    pub file_name: String,
+    pub content_type: String,
+    pub byte_size: i64,
+    pub note_id: Option<Id>,
+    pub action_id: Option<Id>,
+}
+
+/// An attachment just recorded, with where to upload its file.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Upload {
+    pub attachment: Model,
+    /// Presigned URL to PUT the file to, with the declared `Content-Type` and
+    /// `Content-Length`.
+    pub upload_url: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Where to download an attachment's file from.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Download {
+    pub download_url: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Attaches a file to the session (or one of its notes or actions) for `user_id` to
+/// upload. Refused when the file's type isn't allowed, it's larger than allowed, or the
+/// organization's attachments would exceed its quota. Unavailable (service unavailable)
+/// unless object storage is configured.
+pub async fn create(
+    db: &DatabaseConnection,
+    config: &Config,
+    session: &coaching_sessions::Model,
+    user_id: Id,
+    new_attachment: NewAttachment,
+) -> Result<Upload, Error> {
+    let storage = storage(config)?;
+    let new_attachment = validate(config, new_attachment)?;
+
+    if let Some(note_id) = new_attachment.note_id {
+        let note = note::find_by_id(db, note_id).await?;
+        if note.is_none_or(|note| note.coaching_session_id != session.id) {
+            return Err(Error::validation(
+                "The note isn't one of this session's notes",
+            ));
+        }
+    }
+    if let Some(action_id) = new_attachment.action_id {
+        if action::find_by_id(db, action_id).await?.coaching_session_id != session.id {
+            return Err(Error::validation(
+                "The action isn't one of this session's actions",
+            ));
+        }
+    }
+
+    let relationship =
+        coaching_relationship::find_by_id(db, session.coaching_relationship_id).await?;
+    let id = Id::new_v4();
+    let now = Utc::now();
+    let model = Model {
+        id,
+        organization_id: relationship.organization_id,
+        coaching_session_id: session.id,
+        note_id: new_attachment.note_id,
+        action_id: new_attachment.action_id,
+        user_id: Some(user_id),
+        file_name: new_attachment.file_name,
+        content_type: new_attachment.content_type,
+        byte_size: new_attachment.byte_size,
+        storage_key: format!("attachments/{}/{id}", session.id),
+        created_at: now.into(),
+        updated_at: now.into(),
+    };
+
+    let quota_bytes = megabytes(config.attachment_organization_quota_mb());
+    let Some(attachment) = attachment::create_within_quota(db, model, quota_bytes).await? else {
+        return Err(Error::validation(format!(
+            "The organization's attachments would exceed its {} MB of storage",
+            config.attachment_organization_quota_mb()
+        )));
+    };
+    info!(
+        "Attached {} ({} bytes) to coaching session {}",
+        attachment.id, attachment.byte_size, session.id
+    );
+
+    let upload_url = storage.presigned_put_url(
+        &attachment.storage_key,
+        &attachment.content_type,
+        attachment.byte_size as u64,
+        URL_EXPIRY,
+    );
+    Ok(Upload {
+        attachment,
+        upload_url,
+        expires_at: expires_at(),
+    })
+}
+
+/// The session's attachments, oldest first; only those on the note or action when one
+/// is given.
+pub async fn find_by_coaching_session(
+    db: &DatabaseConnection,
+    session: &coaching_sessions::Model,
+    note_id: Option<Id>,
+    action_id: Option<Id>,
+) -> Result<Vec<Model>, Error> {
+    Ok(attachment::find_by_coaching_session_id(db, session.id)
+        .await?
+        .into_iter()
+        .filter(|attachment| note_id.is_none_or(|id| attachment.note_id == Some(id)))
+        .filter(|attachment| action_id.is_none_or(|id| attachment.action_id == Some(id)))
+        .collect())
+}
+
+/// Where to download the session's attachment `id` from.
+pub async fn download(
+    db: &DatabaseConnection,
+    config: &Config,
+    session: &coaching_sessions::Model,
+    id: Id,
+) -> Result<Download, Error> {
+    let storage = storage(config)?;
+    let attachment = find_in_session(db, session, id).await?;
+
+    Ok(Download {
+        download_url: storage.presigned_get_url(
+            &attachment.storage_key,
+            &attachment.file_name,
+            URL_EXPIRY,
+        ),
+        expires_at: expires_at(),
+    })
+}
+
+/// Deletes the session's attachment `id` and its file. Only whoever attached it or the
+/// session's coach may.
+pub async fn delete(
+    db: &DatabaseConnection,
+    config: &Config,
+    session: &coaching_sessions::Model,
+    user_id: Id,
+    id: Id,
+) -> Result<(), Error> {
+    let storage = storage(config)?;
+    let attachment = find_in_session(db, session, id).await?;
+    if attachment.user_id != Some(user_id) {
+        let relationship =
+            coaching_relationship::find_by_id(db, session.coaching_relationship_id).await?;
+        if relationship.coach_id != user_id {
+            return Err(Error::entity(EntityErrorKind::Unauthenticated));
+        }
+    }
+
+    storage.delete(&attachment.storage_key).await?;
+    attachment::delete_by_id(db, attachment.id).await?;
+    info!("Deleted attachment {id} of coaching session {}", session.id);
+    Ok(())
+}
+
+/// Deletes the files of the session's attachments, if object storage is configured.
+/// Their rows go with the session itself.
+pub async fn delete_stored(
+    db: &DatabaseConnection,
+    config: &Config,
+    coaching_session_id: Id,
+) -> Result<(), Error> {
+    if let Some(storage) = ObjectStorage::from_config(config)? {
+        for attachment in attachment::find_by_coaching_session_id(db, coaching_session_id).await? {
+            storage.delete(&attachment.storage_key).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn find_in_session(
+    db: &DatabaseConnection,
+    session: &coaching_sessions::Model,
+    id: Id,
+) -> Result<Model, Error> {
+    let attachment = attachment::find_by_id(db, id).await?;
+    if attachment.coaching_session_id != session.id {
+        return Err(Error::entity(EntityErrorKind::NotFound));
+    }
+    Ok(attachment)
+}
+
+/// The attachment with its file name trimmed to a base name and its content type
+/// normalized, or why it can't be attached.
+fn validate(config: &Config, mut new_attachment: NewAttachment) -> Result<NewAttachment, Error> {
+    let file_name = new_attachment
+        .file_name
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .chars()
+        .filter(|c| !c.is_control())
+        .collect::<String>();
+    if file_name.is_empty() {
+        return Err(Error::validation("`file_name` must not be empty"));
+    }
+    if file_name.chars().count() > MAX_FILE_NAME_CHARS {
+        return Err(Error::validation(format!(
+            "`file_name` must be at most {MAX_FILE_NAME_CHARS} characters"
+        )));
+    }
+
+    let content_type = new_attachment
+        .content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase();
+    let Some((_, extensions)) = ALLOWED_TYPES.iter().find(|(t, _)| *t == content_type) else {
+        return Err(Error::validation(format!(
+            "Files of type `{content_type}` can't be attached"
+        )));
+    };
+    let extension = file_name
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_lowercase())
+        .unwrap_or_default();
+    if !extensions.contains(&extension.as_str()) {
+        return Err(Error::validation(format!(
+            "`file_name` must end in .{} for type `{content_type}`",
+            extensions.join(" or .")
+        )));
+    }
+
+    let max_bytes = megabytes(config.attachment_max_size_mb());
+    if new_attachment.byte_size <= 0 {
+        return Err(Error::validation("`byte_size` must be positive"));
+    }
+    if new_attachment.byte_size > max_bytes {
+        return Err(Error::validation(format!(
+            "Files larger than {} MB can't be attached",
+            config.attachment_max_size_mb()
+        )));
+    }
+
+    new_attachment.file_name = file_name;
+    new_attachment.content_type = content_type;
+    Ok(new_attachment)
+}
+
+fn storage(config: &Config) -> Result<ObjectStorage, Error> {
+    ObjectStorage::from_config(config)?.ok_or_else(|| {
+        warn!("Attachments were requested, but object storage isn't configured");
+        Error::entity(EntityErrorKind::ServiceUnavailable)
+    })
+}
+
+fn expires_at() -> DateTime<Utc> {
+    Utc::now() + URL_EXPIRY
+}
+
+fn megabytes(mb: u64) -> i64 {
+    i64::try_from(mb.saturating_mul(1024 * 1024)).unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::DomainErrorKind;
+
+    fn new_attachment(file_name: &str, content_type: &str, byte_size: i64) -> NewAttachment {
+        NewAttachment {
+            file_name: file_name.to_string(),
+            content_type: content_type.to_string(),
+            byte_size,
+            note_id: None,
+            action_id: None,
+        }
+    }
+
+    fn message(result: Result<NewAttachment, Error>) -> String {
+        match result.unwrap_err().error_kind {
+            DomainErrorKind::Validation(message) => message,
+            other => panic!("expected a validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn validate_normalizes_the_file_name_and_content_type() {
+        let config = Config::from_args(["test"]);
+
+        let validated = validate(
+            &config,
+            new_attachment(
+                "C:\\Users\\coach\\Values Worksheet.PDF",
+                "Application/PDF; charset=binary",
+                2048,
+            ),
+        )
+        .unwrap();
+
+        assert_eq!(validated.file_name, "Values Worksheet.PDF");
+        assert_eq!(validated.content_type, "application/pdf");
+    }
+
+    #[test]
+    fn validate_refuses_disallowed_types_and_oversized_files() {
+        let config = Config::from_args(["test", "--attachment-max-size-mb=1"]);
+
+        assert_eq!(
+            message(validate(
+                &config,
+                new_attachment("setup.exe", "application/x-msdownload", 10)
+            )),
+            "Files of type `application/x-msdownload` can't be attached"
+        );
+        assert_eq!(
+            message(validate(
+                &config,
+                new_attachment("worksheet.exe", "application/pdf", 10)
+            )),
+            "`file_name` must end in .pdf for type `application/pdf`"
+        );
+        assert_eq!(
+            message(validate(
+                &config,
+                new_attachment("worksheet.pdf", "application/pdf", 1024 * 1024 + 1)
+            )),
+            "Files larger than 1 MB can't be attached"
+        );
+        assert_eq!(
+            message(validate(
+                &config,
+                new_attachment("worksheet.pdf", "application/pdf", 0)
+            )),
+            "`byte_size` must be positive"
+        );
+    }
+}
@@ -0,0 +1,286 @@
+//! Audit trail of changes to coaching data, read by organization admins.
+//!
+//! The domain's create, update, delete and restore paths call [`record`] once the change
+//! is committed. Each entry names the entity and the organization it belongs to, the user
+//! whose request made the change (see [`events::with_actor`]), the client address it came
+//! from (see [`events::with_client_ip`]) and the fields it changed, diffed against the
+//! entity as its previous entry left it. Recording is best-effort: a failure is logged and
+//! never fails the change itself.
+//!
+//! What coaches and coachees write stays between them, so `body` fields are diffed by hash
+//! and only ever shown as `"[redacted]"`.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::DateTime;
+use entity_api::{audit_entry, coaching_relationship, coaching_session};
+use log::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+use crate::audit_entries::Model;
+use crate::error::{DomainErrorKind, Error};
+use crate::Id;
+
+pub use entity_api::audit_entry::{AuditFilter, AuditPage};
+
+const DEFAULT_LIMIT: u64 = 100;
+const MAX_LIMIT: u64 = 1000;
+
+/// Fields holding what coaches and coachees write, kept out of the trail.
+const REDACTED_FIELDS: &[&str] = &["body"];
+const REDACTED: &str = "[redacted]";
+
+/// Fields every change touches, left out of diffs.
+const IGNORED_FIELDS: &[&str] = &["created_at", "updated_at"];
+
+/// What a change did to the entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Created,
+    Updated,
+    Deleted,
+    Restored,
+}
+
+impl Action {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Action::Created => "created",
+            Action::Updated => "updated",
+            Action::Deleted => "deleted",
+            Action::Restored => "restored",
+        }
+    }
+}
+
+/// Where the changed entity sits, which decides the organization its entry is filed under.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Scope {
+    Organization(Id),
+    CoachingRelationship(Id),
+    CoachingSession(Id),
+}
+
+/// Records `action` on the entity, as it's left by the change; `entity` is `None` for
+/// deletes. Logs rather than returns a failure.
+pub(crate) async fn record<T: Serialize>(
+    db: &DatabaseConnection,
+    action: Action,
+    entity_type: &str,
+    entity_id: Id,
+    scope: Scope,
+    entity: Option<&T>,
+) {
+    if let Err(e) = try_record(db, action, entity_type, entity_id, scope, entity).await {
+        error!(
+            "[audit] failed to record {} of {entity_type} {entity_id}: {e:?}",
+            action.as_str()
+        );
+    }
+}
+
+async fn try_record<T: Serialize>(
+    db: &DatabaseConnection,
+    action: Action,
+    entity_type: &str,
+    entity_id: Id,
+    scope: Scope,
+    entity: Option<&T>,
+) -> Result<(), Error> {
+    let snapshot = entity
+        .map(serde_json::to_value)
+        .transpose()
+        .map_err(|e| Error {
+            source: Some(Box::new(e)),
+            error_kind: DomainErrorKind::Internal(crate::error::InternalErrorKind::Other(
+                "failed to serialize audited entity".to_string(),
+            )),
+        })?
+        .map(into_snapshot);
+    let changes = match &snapshot {
+        Some(snapshot) => {
+            let previous = audit_entry::find_latest_snapshot(db, entity_type, entity_id).await?;
+            diff(previous.as_ref(), snapshot)
+        }
+        None => Value::Object(Map::new()),
+    };
+
+    audit_entry::create(
+        db,
+        Model {
+            id: Default::default(),
+            organization_id: Some(organization_id(db, scope).await?),
+            actor_user_id: events::current_actor(),
+            action: action.as_str().to_string(),
+            entity_type: entity_type.to_string(),
+            entity_id,
+            changes,
+            snapshot,
+            ip_address: events::current_client_ip(),
+            created_at: Default::default(),
+        },
+    )
+    .await?;
+    Ok(())
+}
+
+async fn organization_id(db: &DatabaseConnection, scope: Scope) -> Result<Id, Error> {
+    Ok(match scope {
+        Scope::Organization(organization_id) => organization_id,
+        Scope::CoachingRelationship(id) => {
+            coaching_relationship::find_by_id(db, id)
+                .await?
+                .organization_id
+        }
+        Scope::CoachingSession(id) => {
+            coaching_session::find_by_id_with_coaching_relationship(db, id)
+                .await?
+                .1
+                .organization_id
+        }
+    })
+}
+
+/// The entity as stored to diff against: redacted fields hashed, ignored ones dropped.
+fn into_snapshot(mut entity: Value) -> Value {
+    if let Value::Object(fields) = &mut entity {
+        fields.retain(|field, _| !IGNORED_FIELDS.contains(&field.as_str()));
+        for field in REDACTED_FIELDS {
+            if let Some(value) = fields.get_mut(*field) {
+                if !value.is_null() {
+                    let hash = Sha256::digest(value.to_string().as_bytes());
+                    *value = Value::String(format!("sha256:{}", hex::encode(hash)));
+                }
+            }
+        }
+    }
+    entity
+}
+
+/// Each field of `snapshot` that differs from `previous`, as `{"from": …, "to": …}`.
+fn diff(previous: Option<&Value>, snapshot: &Value) -> Value {
+    let Value::Object(fields) = snapshot else {
+        return Value::Object(Map::new());
+    };
+    let changes = fields
+        .iter()
+        .filter_map(|(field, to)| {
+            let from = previous
+                .and_then(|previous| previous.get(field))
+                .unwrap_or(&Value::Null);
+            if from == to {
+                return None;
+            }
+            let shown = |value: &Value| {
+                if REDACTED_FIELDS.contains(&field.as_str()) && !value.is_null() {
+                    Value::String(REDACTED.to_string())
+                } else {
+                    value.clone()
+                }
+            };
+            Some((
+                field.clone(),
+                serde_json::json!({ "from": shown(from), "to": shown(to) }),
+            ))
+        })
+        .collect();
+    Value::Object(changes)
+}
+
+/// A page of the organization's entries matching `filter`, newest first. `cursor` is a
+/// previous page's `next_cursor`.
+pub async fn find(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    filter: AuditFilter,
+    cursor: Option<&str>,
+    limit: Option<u64>,
+) -> Result<AuditPage, Error> {
+    if let (Some(from), Some(to)) = (filter.from, filter.to) {
+        if from >= to {
+            return Err(Error::validation("`from` must be before `to`"));
+        }
+    }
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    if !(1..=MAX_LIMIT).contains(&limit) {
+        return Err(Error::validation(format!(
+            "`limit` must be between 1 and {MAX_LIMIT}"
+        )));
+    }
+    let before = cursor.map(decode_cursor).transpose()?;
+
+    // One extra entry tells whether there's another page.
+    let mut entries =
+        audit_entry::find_by_organization(db, organization_id, &filter, before, limit + 1).await?;
+    let next_cursor = if entries.len() as u64 > limit {
+        entries.truncate(limit as usize);
+        entries.last().map(encode_cursor)
+    } else {
+        None
+    };
+    Ok(AuditPage {
+        entries,
+        next_cursor,
+    })
+}
+
+fn encode_cursor(entry: &Model) -> String {
+    URL_SAFE_NO_PAD.encode(format!("{}|{}", entry.created_at.to_rfc3339(), entry.id))
+}
+
+fn decode_cursor(cursor: &str) -> Result<(DateTimeWithTimeZone, Id), Error> {
+    let invalid = || Error::validation("`cursor` is not a cursor this endpoint returned");
+    let decoded = URL_SAFE_NO_PAD.decode(cursor).map_err(|_| invalid())?;
+    let decoded = String::from_utf8(decoded).map_err(|_| invalid())?;
+    let (created_at, id) = decoded.split_once('|').ok_or_else(invalid)?;
+    Ok((
+        DateTime::parse_from_rfc3339(created_at).map_err(|_| invalid())?,
+        id.parse().map_err(|_| invalid())?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn diff_lists_changed_fields_and_redacts_bodies() {
+        let previous = into_snapshot(json!({
+            "id": "a",
+            "title": "Old",
+            "body": "private",
+            "updated_at": "2026-10-01T00:00:00Z",
+        }));
+        let snapshot = into_snapshot(json!({
+            "id": "a",
+            "title": "New",
+            "body": "still private",
+            "updated_at": "2026-10-02T00:00:00Z",
+        }));
+
+        let changes = diff(Some(&previous), &snapshot);
+
+        assert_eq!(
+            changes,
+            json!({
+                "title": { "from": "Old", "to": "New" },
+                "body": { "from": "[redacted]", "to": "[redacted]" },
+            })
+        );
+        assert!(!snapshot.to_string().contains("still private"));
+    }
+
+    #[test]
+    fn diff_of_a_new_entity_lists_every_field() {
+        let snapshot = into_snapshot(json!({ "id": "a", "body": null }));
+
+        assert_eq!(
+            diff(None, &snapshot),
+            json!({ "id": { "from": null, "to": "a" } })
+        );
+    }
+}
@@ -0,0 +1,186 @@
+//! Batch reads of specific records by id, so a client needing N actions or users
+//! fetches them in one round trip.
+//!
+//! Each record is authorized on its own, the same way its single-record read is: an id
+//! the caller may not see lands in `forbidden` instead of failing the whole batch, and
+//! one that doesn't exist (or was deleted) lands in `missing`.
+
+use std::collections::{HashMap, HashSet};
+
+use entity_api::{action, user};
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+
+use crate::action::ActionWithAssignees;
+use crate::error::{DomainErrorKind, Error};
+use crate::users;
+use crate::Id;
+
+/// Most ids one batch request may ask for.
+pub const MAX_BATCH_IDS: usize = 100;
+
+/// The outcome of a batch read, partitioning the requested ids.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BatchFetch<T> {
+    /// Records the caller may see, in the order they were requested
+    pub found: Vec<T>,
+    pub missing: Vec<Id>,
+    pub forbidden: Vec<Id>,
+}
+
+/// Reads the actions `ids` on behalf of `viewer`, who may see those of the coaching
+/// relationships they're the coach or coachee of.
+pub async fn actions(
+    db: &DatabaseConnection,
+    viewer: &users::Model,
+    ids: Vec<Id>,
+) -> Result<BatchFetch<ActionWithAssignees>, Error> {
+    let ids = dedup_ids(ids)?;
+    let records = action::find_by_ids_with_coaching_relationship(db, &ids)
+        .await?
+        .into_iter()
+        .map(|(action, relationship)| {
+            let visible =
+                relationship.coach_id == viewer.id || relationship.coachee_id == viewer.id;
+            (action.action.id, (action, visible))
+        })
+        .collect();
+
+    Ok(partition(&ids, records))
+}
+
+/// Reads the users `ids` on behalf of `viewer`, who may only see themselves, as with a
+/// single user's read.
+pub async fn users(
+    db: &DatabaseConnection,
+    viewer: &users::Model,
+    ids: Vec<Id>,
+) -> Result<BatchFetch<users::Model>, Error> {
+    let ids = dedup_ids(ids)?;
+    let records = user::find_by_ids(db, &ids)
+        .await?
+        .into_iter()
+        .map(|user| {
+            let visible = can_see_user(viewer, &user);
+            (user.id, (user, visible))
+        })
+        .collect();
+
+    Ok(partition(&ids, records))
+}
+
+/// As `protect::users::read`: a user may only read themselves.
+fn can_see_user(viewer: &users::Model, user: &users::Model) -> bool {
+    viewer.id == user.id
+}
+
+/// Drops repeated ids, keeping the first occurrence, and enforces [`MAX_BATCH_IDS`].
+fn dedup_ids(ids: Vec<Id>) -> Result<Vec<Id>, Error> {
+    let mut seen = HashSet::new();
+    let ids: Vec<Id> = ids.into_iter().filter(|id| seen.insert(*id)).collect();
+
+    if ids.len() > MAX_BATCH_IDS {
+        return Err(Error {
+            source: None,
+            error_kind: DomainErrorKind::Validation(format!(
+                "at most {MAX_BATCH_IDS} ids may be fetched at once (got {})",
+                ids.len()
+            )),
+        });
+    }
+    Ok(ids)
+}
+
+/// Splits `ids` by whether `records` holds them and, if so, whether they're visible.
+fn partition<T>(ids: &[Id], mut records: HashMap<Id, (T, bool)>) -> BatchFetch<T> {
+    let mut batch = BatchFetch {
+        found: Vec::new(),
+        missing: Vec::new(),
+        forbidden: Vec::new(),
+    };
+    for id in ids {
+        match records.remove(id) {
+            Some((record, true)) => batch.found.push(record),
+            Some((_, false)) => batch.forbidden.push(*id),
+            None => batch.missing.push(*id),
+        }
+    }
+    batch
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::user_roles;
+    use crate::users::Role;
+    use chrono::Utc;
+
+    fn user_in(organization_ids: &[Option<Id>], role: Role) -> users::Model {
+        let now = Utc::now().fixed_offset();
+        let id = Id::new_v4();
+        users::Model {
+            id,
+            email: format!("{id}@example.com"),
+            first_name: "Test".to_string(),
+            last_name: "User".to_string(),
+            display_name: None,
+            password: None,
+            github_username: None,
+            github_profile_url: None,
+            timezone: "UTC".to_string(),
+            default_coaching_session_duration_minutes: 60,
+            role: Role::User,
+            roles: organization_ids
+                .iter()
+                .map(|organization_id| user_roles::Model {
+                    id: Id::new_v4(),
+                    role: role.clone(),
+                    organization_id: *organization_id,
+                    user_id: id,
+                    created_at: now,
+                    updated_at: now,
+                })
+                .collect(),
+            invite_status: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn partition_keeps_request_order_and_sorts_out_missing_and_forbidden() {
+        let (a, b, c, d) = (Id::new_v4(), Id::new_v4(), Id::new_v4(), Id::new_v4());
+        let records = HashMap::from([(a, ("a", true)), (b, ("b", false)), (d, ("d", true))]);
+
+        let batch = partition(&[d, b, c, a], records);
+
+        assert_eq!(batch.found, vec!["d", "a"]);
+        assert_eq!(batch.forbidden, vec![b]);
+        assert_eq!(batch.missing, vec![c]);
+    }
+
+    #[test]
+    fn dedup_ids_drops_repeats_and_caps_the_batch() {
+        let id = Id::new_v4();
+        assert_eq!(dedup_ids(vec![id, id]).unwrap(), vec![id]);
+
+        let too_many = (0..=MAX_BATCH_IDS).map(|_| Id::new_v4()).collect();
+        assert!(matches!(
+            dedup_ids(too_many).unwrap_err().error_kind,
+            DomainErrorKind::Validation(_)
+        ));
+    }
+
+    #[test]
+    fn users_are_visible_only_to_themselves() {
+        let org = Some(Id::new_v4());
+        let viewer = user_in(&[org], Role::User);
+        let colleague = user_in(&[org], Role::User);
+        let super_admin = user_in(&[None], Role::SuperAdmin);
+
+        assert!(can_see_user(&viewer, &viewer));
+        assert!(!can_see_user(&viewer, &colleague));
+        assert!(!can_see_user(&super_admin, &viewer));
+        assert!(can_see_user(&super_admin, &super_admin));
+    }
+}
@@ -0,0 +1,154 @@
+//! What booking a session should warn about.
+//!
+//! A session can still be booked while a participant is out of office or on one of the
+//! organization's holidays, since sometimes that's intended, but the booker is told so.
+
+use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
+use chrono_tz::Tz;
+use entity_api::{coaching_relationship, organization_holiday, out_of_office_period, user};
+use log::*;
+use sea_orm::DatabaseConnection;
+
+use crate::error::Error;
+use crate::{coaching_relationships, coaching_sessions};
+
+/// Human-readable warnings about when `session` is booked: a participant being out of
+/// office during it, or it falling on an organization holiday (in the coach's timezone).
+/// Best-effort: a failed lookup is logged and yields no warnings.
+pub async fn booking_warnings(
+    db: &DatabaseConnection,
+    session: &coaching_sessions::Model,
+) -> Vec<String> {
+    let result: Result<Vec<String>, Error> = async {
+        let relationship =
+            coaching_relationship::find_by_id(db, session.coaching_relationship_id).await?;
+        let starts_at = session.date.and_utc();
+        let ends_at = starts_at + TimeDelta::minutes(session.duration_minutes.into());
+
+        let mut warnings = out_of_office_warnings(db, &relationship, starts_at, ends_at).await?;
+        warnings.extend(holiday_warnings(db, &relationship, starts_at, ends_at).await?);
+        Ok(warnings)
+    }
+    .await;
+
+    result.unwrap_or_else(|e| {
+        warn!(
+            "Failed to check session {} against participants' calendars: {e:?}",
+            session.id
+        );
+        Vec::new()
+    })
+}
+
+async fn out_of_office_warnings(
+    db: &DatabaseConnection,
+    relationship: &coaching_relationships::Model,
+    starts_at: DateTime<Utc>,
+    ends_at: DateTime<Utc>,
+) -> Result<Vec<String>, Error> {
+    let periods = out_of_office_period::find_overlapping(
+        db,
+        &[relationship.coach_id, relationship.coachee_id],
+        starts_at.into(),
+        ends_at.into(),
+    )
+    .await?;
+
+    Ok(periods
+        .into_iter()
+        .map(|period| {
+            let who = if period.user_id == relationship.coach_id {
+                "coach"
+            } else {
+                "coachee"
+            };
+            let note = period
+                .note
+                .map(|note| format!(" ({note})"))
+                .unwrap_or_default();
+            format!(
+                "The {who} is out of office from {} to {}{note}",
+                period
+                    .starts_at
+                    .with_timezone(&Utc)
+                    .format("%Y-%m-%d %H:%M UTC"),
+                period
+                    .ends_at
+                    .with_timezone(&Utc)
+                    .format("%Y-%m-%d %H:%M UTC"),
+            )
+        })
+        .collect())
+}
+
+async fn holiday_warnings(
+    db: &DatabaseConnection,
+    relationship: &coaching_relationships::Model,
+    starts_at: DateTime<Utc>,
+    ends_at: DateTime<Utc>,
+) -> Result<Vec<String>, Error> {
+    let coach = user::find_by_id(db, relationship.coach_id).await?;
+    let (from, to) = local_dates(&coach.timezone, starts_at, ends_at);
+    let holidays =
+        organization_holiday::find_overlapping(db, relationship.organization_id, from, to).await?;
+
+    Ok(holidays
+        .into_iter()
+        .map(|holiday| {
+            if holiday.starts_on == holiday.ends_on {
+                format!(
+                    "The session falls on {} ({})",
+                    holiday.name, holiday.starts_on
+                )
+            } else {
+                format!(
+                    "The session falls on {} ({} to {})",
+                    holiday.name, holiday.starts_on, holiday.ends_on
+                )
+            }
+        })
+        .collect())
+}
+
+/// The first and last days `[starts_at, ends_at)` covers in `timezone`, or in UTC when
+/// the timezone isn't known.
+fn local_dates(
+    timezone: &str,
+    starts_at: DateTime<Utc>,
+    ends_at: DateTime<Utc>,
+) -> (NaiveDate, NaiveDate) {
+    let last = ends_at - TimeDelta::seconds(1);
+    match timezone.parse::<Tz>() {
+        Ok(tz) => (
+            starts_at.with_timezone(&tz).date_naive(),
+            last.with_timezone(&tz).date_naive(),
+        ),
+        Err(_) => (starts_at.date_naive(), last.date_naive()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn local_dates_follow_the_coachs_timezone() {
+        // 11pm-midnight on Dec 24 in New York is Dec 25 in UTC
+        let starts_at = Utc.with_ymd_and_hms(2026, 12, 25, 4, 0, 0).unwrap();
+        let ends_at = starts_at + TimeDelta::hours(1);
+        let christmas_eve = NaiveDate::from_ymd_opt(2026, 12, 24).unwrap();
+
+        assert_eq!(
+            local_dates("America/New_York", starts_at, ends_at),
+            (christmas_eve, christmas_eve)
+        );
+        assert_eq!(
+            local_dates("Not/AZone", starts_at, ends_at),
+            (
+                christmas_eve.succ_opt().unwrap(),
+                christmas_eve.succ_opt().unwrap()
+            )
+        );
+    }
+}
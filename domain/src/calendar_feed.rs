@@ -0,0 +1,358 @@
+//! Users' coaching sessions as an iCalendar feed their calendar apps subscribe to.
+//!
+//! Calendar apps fetch a feed by URL alone, without cookies, so the URL carries a token
+//! signed with `CALENDAR_FEED_SIGNING_KEY` over the user's feed secret ([`token`]); a
+//! user gets theirs while logged in, and [`regenerate`]s the secret to revoke the URLs
+//! handed out before. Sessions are given in UTC, which every calendar app converts, and
+//! the feed names the user's timezone as the calendar's.
+
+use chrono::{DateTime, NaiveDateTime, TimeDelta, Utc};
+use chrono_tz::Tz;
+use entity_api::coaching_session::{self, EnrichedSession, IncludeOptions, SessionQueryOptions};
+use entity_api::{calendar_feed, user};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sea_orm::DatabaseConnection;
+use service::config::Config;
+use sha2::Sha256;
+
+use crate::error::{EntityErrorKind, Error};
+use crate::{users, Id};
+
+/// How far back the feed goes; older sessions are left to the app.
+const PAST_DAYS: u64 = 90;
+
+/// Longest content line, in octets, before it's folded (RFC 5545 §3.1).
+const MAX_LINE_OCTETS: usize = 75;
+
+/// The token of `user_id`'s feed URL, giving them a feed secret on first use; not found
+/// when feeds aren't configured.
+pub async fn token(db: &DatabaseConnection, config: &Config, user_id: Id) -> Result<String, Error> {
+    let key = signing_key(config)?;
+    let feed = calendar_feed::find_or_create(db, user_id, random_secret()).await?;
+    Ok(signature(&key, user_id, &feed.secret))
+}
+
+/// Replaces `user_id`'s feed secret, so the feed URLs handed out before stop working,
+/// returning the new URL's token.
+pub async fn regenerate(
+    db: &DatabaseConnection,
+    config: &Config,
+    user_id: Id,
+) -> Result<String, Error> {
+    let key = signing_key(config)?;
+    let feed = calendar_feed::replace_secret(db, user_id, random_secret()).await?;
+    Ok(signature(&key, user_id, &feed.secret))
+}
+
+/// The feed of `user_id`'s coaching sessions from [`PAST_DAYS`] ago on, if `token` is
+/// theirs.
+pub async fn feed(
+    db: &DatabaseConnection,
+    config: &Config,
+    user_id: Id,
+    token: &str,
+) -> Result<String, Error> {
+    let signature =
+        hex::decode(token).map_err(|_| Error::entity(EntityErrorKind::Unauthenticated))?;
+    let key = signing_key(config)?;
+    let feed = calendar_feed::find_by_user_id(db, user_id)
+        .await?
+        .ok_or_else(|| Error::entity(EntityErrorKind::Unauthenticated))?;
+    mac(&key, user_id, &feed.secret)
+        .verify_slice(&signature)
+        .map_err(|_| Error::entity(EntityErrorKind::Unauthenticated))?;
+
+    let user = user::find_by_id(db, user_id).await?;
+    let tz: Tz = user.timezone.parse().unwrap_or(Tz::UTC);
+    let from_date = Utc::now().with_timezone(&tz).date_naive() - chrono::Days::new(PAST_DAYS);
+    let sessions = coaching_session::find_by_user_with_includes(
+        db,
+        user_id,
+        SessionQueryOptions {
+            from_date: Some(from_date),
+            tz: Some(tz.name().to_string()),
+            ..Default::default()
+        },
+        IncludeOptions {
+            relationship: true,
+            ..IncludeOptions::none()
+        },
+    )
+    .await?;
+
+    Ok(render(&user, tz, &sessions, Utc::now()))
+}
+
+/// The key feed tokens are signed with; not found when feeds aren't configured.
+fn signing_key(config: &Config) -> Result<String, Error> {
+    config
+        .calendar_feed_signing_key()
+        .ok_or_else(|| Error::entity(EntityErrorKind::NotFound))
+}
+
+fn signature(key: &str, user_id: Id, secret: &str) -> String {
+    hex::encode(mac(key, user_id, secret).finalize().into_bytes())
+}
+
+fn mac(key: &str, user_id: Id, secret: &str) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(key.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(format!("calendar-feed:{user_id}:{secret}").as_bytes());
+    mac
+}
+
+fn random_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+fn render(user: &users::Model, tz: Tz, sessions: &[EnrichedSession], now: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Refactor Coaching//Coaching Sessions//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        "X-WR-CALNAME:Coaching sessions".to_string(),
+        format!("X-WR-TIMEZONE:{}", tz.name()),
+    ];
+    for enriched in sessions {
+        let session = &enriched.session;
+        let ends_at = session.date + TimeDelta::minutes(session.duration_minutes.into());
+        let other = if enriched
+            .relationship
+            .as_ref()
+            .is_some_and(|relationship| relationship.coach_id == user.id)
+        {
+            enriched.coachee.as_ref()
+        } else {
+            enriched.coach.as_ref()
+        };
+        let summary = enriched
+            .display_title
+            .clone()
+            .or_else(|| other.map(|other| format!("Coaching session with {}", name(other))))
+            .unwrap_or_else(|| "Coaching session".to_string());
+
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}@coaching-sessions", session.id));
+        lines.push(format!("DTSTAMP:{}", utc(now.naive_utc())));
+        lines.push(format!("DTSTART:{}", utc(session.date)));
+        lines.push(format!("DTEND:{}", utc(ends_at)));
+        lines.push(format!(
+            "LAST-MODIFIED:{}",
+            utc(session.updated_at.naive_utc())
+        ));
+        lines.push(format!("SUMMARY:{}", escape(&summary)));
+        if let Some(meeting_url) = &session.meeting_url {
+            lines.push(format!("LOCATION:{}", escape(meeting_url)));
+            lines.push(format!("URL:{meeting_url}"));
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold(line) + "\r\n").collect()
+}
+
+fn name(user: &users::Model) -> String {
+    user.display_name
+        .clone()
+        .unwrap_or_else(|| format!("{} {}", user.first_name, user.last_name))
+}
+
+/// `date`, taken as UTC, in the iCalendar UTC form, e.g. `20261015T093000Z`.
+fn utc(date: NaiveDateTime) -> String {
+    date.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// `text` as an iCalendar TEXT value.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Splits `line` into lines of at most [`MAX_LINE_OCTETS`], each continuation starting
+/// with a space; never inside a character.
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut octets = 0;
+    for c in line.chars() {
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            octets = 1;
+        }
+        folded.push(c);
+        octets += c.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entity_api::ai_privacy_level::AiPrivacyLevel;
+    use entity_api::users::Role;
+    use entity_api::{coaching_relationships, coaching_sessions};
+
+    fn user(first_name: &str, timezone: &str) -> users::Model {
+        let now = Utc::now().fixed_offset();
+        users::Model {
+            id: Id::new_v4(),
+            email: format!("{first_name}@example.com"),
+            first_name: first_name.to_string(),
+            last_name: "Doe".to_string(),
+            display_name: None,
+            password: None,
+            github_username: None,
+            github_profile_url: None,
+            timezone: timezone.to_string(),
+            default_coaching_session_duration_minutes: 60,
+            role: Role::User,
+            roles: vec![],
+            invite_status: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// A session of `coach` with `coachee` starting at `date` (UTC), 45 minutes long.
+    fn session(coach: &users::Model, coachee: &users::Model, date: &str) -> EnrichedSession {
+        let updated_at = "2026-10-01T08:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let relationship = coaching_relationships::Model {
+            id: Id::new_v4(),
+            organization_id: Id::new_v4(),
+            coach_id: coach.id,
+            coachee_id: coachee.id,
+            slug: "jane-joe".to_string(),
+            ai_privacy_level: AiPrivacyLevel::default(),
+            pro_bono: false,
+            created_at: updated_at.into(),
+            updated_at: updated_at.into(),
+        };
+        EnrichedSession {
+            session: coaching_sessions::Model {
+                id: Id::new_v4(),
+                coaching_relationship_id: relationship.id,
+                coaching_session_series_id: None,
+                collab_document_name: None,
+                date: NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M").unwrap(),
+                duration_minutes: 45,
+                title: None,
+                meeting_url: None,
+                provider: None,
+                created_at: updated_at.into(),
+                updated_at: updated_at.into(),
+                hydrated_at: None,
+                session_type_id: None,
+                deleted_at: None,
+            },
+            relationship: Some(relationship),
+            coach: Some(coach.clone()),
+            coachee: Some(coachee.clone()),
+            organization: None,
+            goals: None,
+            agreement: None,
+            viewer_last_viewed_at: None,
+            has_unread: false,
+            display_title: None,
+            topics: None,
+            carried_actions: None,
+        }
+    }
+
+    fn lines(feed: &str) -> Vec<&str> {
+        feed.split("\r\n").collect()
+    }
+
+    #[test]
+    fn token_is_signed_over_the_user_and_their_secret_and_needs_a_key() {
+        let user_id = Id::new_v4();
+
+        let token = signature("key", user_id, "first");
+
+        assert!(mac("key", user_id, "first")
+            .verify_slice(&hex::decode(&token).unwrap())
+            .is_ok());
+        assert_ne!(token, signature("key", Id::new_v4(), "first"));
+        // A regenerated secret revokes the token, as does a new key
+        assert_ne!(token, signature("key", user_id, "second"));
+        assert_ne!(token, signature("other key", user_id, "first"));
+        assert_ne!(random_secret(), random_secret());
+
+        let disabled = Config::from_args(vec!["test".to_string()]);
+        assert!(signing_key(&disabled).is_err());
+    }
+
+    #[test]
+    fn render_gives_sessions_in_utc_and_names_the_users_timezone() {
+        let coach = user("Jane", "America/Los_Angeles");
+        let coachee = user("Joe", "Europe/Berlin");
+        let tz: Tz = coach.timezone.parse().unwrap();
+        // 23:30 UTC is still the 15th in Los Angeles, and ends past midnight UTC
+        let sessions = [session(&coach, &coachee, "2026-10-15 23:30")];
+        let now = "2026-10-16T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+
+        let feed = render(&coach, tz, &sessions, now);
+
+        let lines = lines(&feed);
+        assert!(lines.contains(&"X-WR-TIMEZONE:America/Los_Angeles"));
+        assert!(lines.contains(&"DTSTART:20261015T233000Z"));
+        assert!(lines.contains(&"DTEND:20261016T001500Z"));
+        assert!(lines.contains(&"DTSTAMP:20261016T120000Z"));
+        assert!(lines.contains(&"LAST-MODIFIED:20261001T080000Z"));
+        assert!(feed.ends_with("END:VCALENDAR\r\n"));
+    }
+
+    #[test]
+    fn render_names_the_other_participant_and_the_meeting_link() {
+        let coach = user("Jane", "UTC");
+        let coachee = user("Joe", "UTC");
+        let mut with_link = session(&coach, &coachee, "2026-10-15 09:00");
+        with_link.session.meeting_url = Some("https://meet.example.com/a,b".to_string());
+        let mut titled = session(&coach, &coachee, "2026-10-16 09:00");
+        titled.display_title = Some("Quarterly goals; review".to_string());
+        let sessions = [with_link.clone(), titled];
+        let now = Utc::now();
+
+        let coach_lines = render(&coach, Tz::UTC, &sessions, now);
+        let coachee_lines = render(&coachee, Tz::UTC, &sessions, now);
+
+        let coach_lines = lines(&coach_lines);
+        assert!(coach_lines
+            .contains(&format!("UID:{}@coaching-sessions", with_link.session.id).as_str()));
+        assert!(coach_lines.contains(&"SUMMARY:Coaching session with Joe Doe"));
+        assert!(coach_lines.contains(&"LOCATION:https://meet.example.com/a\\,b"));
+        assert!(coach_lines.contains(&"URL:https://meet.example.com/a,b"));
+        assert!(coach_lines.contains(&"SUMMARY:Quarterly goals\\; review"));
+        assert_eq!(
+            coach_lines
+                .iter()
+                .filter(|line| **line == "BEGIN:VEVENT")
+                .count(),
+            2
+        );
+        assert!(lines(&coachee_lines).contains(&"SUMMARY:Coaching session with Jane Doe"));
+    }
+
+    #[test]
+    fn text_is_escaped_and_long_lines_folded() {
+        assert_eq!(
+            escape("Goals; plans, and\nmore\\"),
+            "Goals\\; plans\\, and\\nmore\\\\"
+        );
+
+        let line = format!("SUMMARY:{}", "é".repeat(40));
+        let folded = fold(&line);
+
+        assert!(folded
+            .split("\r\n")
+            .all(|part| part.len() <= MAX_LINE_OCTETS));
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+}
@@ -0,0 +1,355 @@
+//! Coaches' logs of coaching hours, as ICF credential applications ask for them.
+//!
+//! Hours accumulate from the sessions a coach has held: a session counts for the length
+//! of its completed meeting recording when there is one, otherwise for its scheduled
+//! duration, and as pro bono when its relationship is marked so. The log lists each
+//! client once, with the span and hours of their coaching, and [`to_csv`] lays it out in
+//! the columns of the ICF client coaching log.
+
+use chrono::{NaiveDate, Utc};
+use entity_api::{coaching_session, meeting_recording};
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::error::Error;
+use crate::{coaching_relationships, coaching_sessions, user, users, Id};
+
+/// Header row of the CSV export, in the ICF client coaching log's order.
+const CSV_HEADER: [&str; 8] = [
+    "Client Name",
+    "Contact Information",
+    "Individual/Group",
+    "Number in Group",
+    "Start Date",
+    "End Date",
+    "Paid Hours",
+    "Pro Bono Hours",
+];
+
+/// One client's coaching within a coach's log.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Entry {
+    pub client_id: Id,
+    pub client_name: String,
+    /// The client's email address.
+    pub contact: String,
+    /// "Individual"; relationships have a single coachee.
+    pub coaching_type: String,
+    pub group_size: u32,
+    /// Dates of the client's first and last session in the log.
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub session_count: usize,
+    pub paid_hours: f64,
+    pub pro_bono_hours: f64,
+}
+
+/// A coach's coaching hours over the sessions they've held.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CoachingLog {
+    pub coach_id: Id,
+    pub from: Option<NaiveDate>,
+    pub to: NaiveDate,
+    /// By client name.
+    pub entries: Vec<Entry>,
+    pub total_hours: f64,
+    pub paid_hours: f64,
+    pub pro_bono_hours: f64,
+    pub session_count: usize,
+    pub client_count: usize,
+}
+
+/// The coach's log over the sessions held from `from` (when given) until `to` (today
+/// when not), both inclusive.
+pub async fn find_by_coach(
+    db: &DatabaseConnection,
+    coach_id: Id,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> Result<CoachingLog, Error> {
+    let now = Utc::now().naive_utc();
+    let to = to.unwrap_or(now.date());
+    let until = to
+        .and_hms_micro_opt(23, 59, 59, 999_999)
+        .map_or(now, |end_of_day| end_of_day.min(now));
+    let from_start = from.and_then(|day| day.and_hms_opt(0, 0, 0));
+
+    let sessions = coaching_session::find_held_by_coach(db, coach_id, from_start, until).await?;
+    let recorded = meeting_recording::find_completed_durations(
+        db,
+        sessions.iter().map(|(session, _)| session.id).collect(),
+    )
+    .await?
+    .into_iter()
+    .collect::<HashMap<_, _>>();
+
+    let mut coachee_ids = sessions
+        .iter()
+        .map(|(_, relationship)| relationship.coachee_id)
+        .collect::<Vec<_>>();
+    coachee_ids.sort();
+    coachee_ids.dedup();
+    let coachees = user::find_by_ids(db, &coachee_ids).await?;
+
+    Ok(compile(coach_id, from, to, &sessions, &recorded, &coachees))
+}
+
+/// The log as CSV, one row per client, in the ICF client coaching log's columns.
+pub fn to_csv(log: &CoachingLog) -> String {
+    let mut csv = CSV_HEADER.join(",");
+    csv.push_str("\r\n");
+    for entry in &log.entries {
+        let row = [
+            csv_field(&entry.client_name),
+            csv_field(&entry.contact),
+            csv_field(&entry.coaching_type),
+            entry.group_size.to_string(),
+            entry.start_date.to_string(),
+            entry.end_date.to_string(),
+            format!("{:.2}", entry.paid_hours),
+            format!("{:.2}", entry.pro_bono_hours),
+        ];
+        csv.push_str(&row.join(","));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+/// The log over `sessions`, each lasting as long as its entry in `recorded` (in
+/// seconds) or else as scheduled.
+fn compile(
+    coach_id: Id,
+    from: Option<NaiveDate>,
+    to: NaiveDate,
+    sessions: &[(coaching_sessions::Model, coaching_relationships::Model)],
+    recorded: &HashMap<Id, i32>,
+    coachees: &[users::Model],
+) -> CoachingLog {
+    let mut entries: HashMap<Id, Entry> = HashMap::new();
+    for (session, relationship) in sessions {
+        let hours = match recorded.get(&session.id) {
+            Some(&seconds) => f64::from(seconds) / 3600.0,
+            None => f64::from(session.duration_minutes) / 60.0,
+        };
+        let date = session.date.date();
+        let entry = entries
+            .entry(relationship.coachee_id)
+            .or_insert_with(|| new_entry(relationship.coachee_id, coachees, date));
+        entry.start_date = entry.start_date.min(date);
+        entry.end_date = entry.end_date.max(date);
+        entry.session_count += 1;
+        if relationship.pro_bono {
+            entry.pro_bono_hours += hours;
+        } else {
+            entry.paid_hours += hours;
+        }
+    }
+
+    let mut entries = entries.into_values().collect::<Vec<_>>();
+    entries.sort_by(|a, b| {
+        a.client_name
+            .to_lowercase()
+            .cmp(&b.client_name.to_lowercase())
+            .then(a.start_date.cmp(&b.start_date))
+    });
+    for entry in &mut entries {
+        entry.paid_hours = round_hours(entry.paid_hours);
+        entry.pro_bono_hours = round_hours(entry.pro_bono_hours);
+    }
+
+    let paid_hours = round_hours(entries.iter().map(|entry| entry.paid_hours).sum());
+    let pro_bono_hours = round_hours(entries.iter().map(|entry| entry.pro_bono_hours).sum());
+    CoachingLog {
+        coach_id,
+        from,
+        to,
+        total_hours: round_hours(paid_hours + pro_bono_hours),
+        paid_hours,
+        pro_bono_hours,
+        session_count: sessions.len(),
+        client_count: entries.len(),
+        entries,
+    }
+}
+
+fn new_entry(client_id: Id, coachees: &[users::Model], date: NaiveDate) -> Entry {
+    let coachee = coachees.iter().find(|user| user.id == client_id);
+    Entry {
+        client_id,
+        client_name: coachee
+            .map(|user| {
+                format!("{} {}", user.first_name, user.last_name)
+                    .trim()
+                    .to_string()
+            })
+            .unwrap_or_default(),
+        contact: coachee.map(|user| user.email.clone()).unwrap_or_default(),
+        coaching_type: "Individual".to_string(),
+        group_size: 1,
+        start_date: date,
+        end_date: date,
+        session_count: 0,
+        paid_hours: 0.0,
+        pro_bono_hours: 0.0,
+    }
+}
+
+/// Hours to the hundredth, as logged.
+fn round_hours(hours: f64) -> f64 {
+    (hours * 100.0).round() / 100.0
+}
+
+/// The field quoted when it holds a comma, quote or line break, with quotes doubled.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relationship(coach_id: Id, coachee_id: Id, pro_bono: bool) -> coaching_relationships::Model {
+        let now = Utc::now().fixed_offset();
+        coaching_relationships::Model {
+            id: Id::new_v4(),
+            organization_id: Id::new_v4(),
+            coach_id,
+            coachee_id,
+            slug: "test-slug".to_string(),
+            ai_privacy_level: Default::default(),
+            pro_bono,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn session(
+        relationship: &coaching_relationships::Model,
+        date: &str,
+        duration_minutes: i16,
+    ) -> (coaching_sessions::Model, coaching_relationships::Model) {
+        let now = Utc::now().fixed_offset();
+        let session = coaching_sessions::Model {
+            id: Id::new_v4(),
+            coaching_relationship_id: relationship.id,
+            coaching_session_series_id: None,
+            collab_document_name: None,
+            date: NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .unwrap()
+                .and_hms_opt(15, 0, 0)
+                .unwrap(),
+            duration_minutes,
+            title: None,
+            meeting_url: None,
+            provider: None,
+            created_at: now,
+            updated_at: now,
+            hydrated_at: None,
+            session_type_id: None,
+            deleted_at: None,
+        };
+        (session, relationship.clone())
+    }
+
+    fn user(id: Id, first_name: &str, last_name: &str, email: &str) -> users::Model {
+        let now = Utc::now().fixed_offset();
+        users::Model {
+            id,
+            email: email.into(),
+            first_name: first_name.into(),
+            last_name: last_name.into(),
+            display_name: None,
+            password: None,
+            github_username: None,
+            github_profile_url: None,
+            timezone: "UTC".into(),
+            default_coaching_session_duration_minutes: 60,
+            role: Default::default(),
+            roles: vec![],
+            invite_status: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn date(date: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn compile_totals_recorded_and_scheduled_hours_per_client() {
+        let coach_id = Id::new_v4();
+        let (ada_id, bob_id) = (Id::new_v4(), Id::new_v4());
+        let paid = relationship(coach_id, ada_id, false);
+        let pro_bono = relationship(coach_id, bob_id, true);
+        let sessions = vec![
+            session(&paid, "2026-03-02", 60),
+            session(&pro_bono, "2026-03-05", 45),
+            session(&paid, "2026-04-06", 60),
+        ];
+        // The second paid session ran 50 minutes, as recorded.
+        let recorded = HashMap::from([(sessions[2].0.id, 3000)]);
+        let coachees = vec![
+            user(bob_id, "Bob", "Brown", "bob@example.com"),
+            user(ada_id, "Ada", "Adams", "ada@example.com"),
+        ];
+
+        let log = compile(
+            coach_id,
+            None,
+            date("2026-10-15"),
+            &sessions,
+            &recorded,
+            &coachees,
+        );
+
+        assert_eq!(log.session_count, 3);
+        assert_eq!(log.client_count, 2);
+        assert_eq!(log.paid_hours, 1.83);
+        assert_eq!(log.pro_bono_hours, 0.75);
+        assert_eq!(log.total_hours, 2.58);
+
+        let ada = &log.entries[0];
+        assert_eq!(ada.client_name, "Ada Adams");
+        assert_eq!(ada.contact, "ada@example.com");
+        assert_eq!(ada.start_date, date("2026-03-02"));
+        assert_eq!(ada.end_date, date("2026-04-06"));
+        assert_eq!(ada.session_count, 2);
+        assert_eq!((ada.paid_hours, ada.pro_bono_hours), (1.83, 0.0));
+
+        let bob = &log.entries[1];
+        assert_eq!(bob.client_name, "Bob Brown");
+        assert_eq!((bob.paid_hours, bob.pro_bono_hours), (0.0, 0.75));
+    }
+
+    #[test]
+    fn to_csv_lays_out_one_row_per_client_in_icf_columns() {
+        let coach_id = Id::new_v4();
+        let client_id = Id::new_v4();
+        let sessions = vec![session(
+            &relationship(coach_id, client_id, false),
+            "2026-05-04",
+            90,
+        )];
+        let coachees = vec![user(client_id, "Jo \"JJ\"", "Smith, Jr.", "jo@example.com")];
+        let log = compile(
+            coach_id,
+            Some(date("2026-01-01")),
+            date("2026-06-30"),
+            &sessions,
+            &HashMap::new(),
+            &coachees,
+        );
+
+        assert_eq!(
+            to_csv(&log),
+            "Client Name,Contact Information,Individual/Group,Number in Group,Start Date,End Date,Paid Hours,Pro Bono Hours\r\n\
+             \"Jo \"\"JJ\"\" Smith, Jr.\",jo@example.com,Individual,1,2026-05-04,2026-05-04,1.50,0.00\r\n"
+        );
+    }
+}
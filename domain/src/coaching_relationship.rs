@@ -1,3 +1,5 @@
+use crate::ai_privacy_level::AiPrivacyLevel;
+use crate::audit;
 use crate::coaching_relationships::Model;
 use crate::error::{DomainErrorKind, EntityErrorKind, Error, InternalErrorKind};
 use entity_api::query::{IntoQueryFilterMap, QuerySort};
@@ -23,6 +25,89 @@ where
     Ok(coaching_relationships)
 }
 
+/// Sets how much of the relationship's content AI features may send to the LLM provider.
+///
+/// The content is largely the coachee's, so the coachee may choose any level; the coach
+/// may only make it stricter.
+pub async fn set_ai_privacy_level(
+    db: &DatabaseConnection,
+    user_id: crate::Id,
+    relationship: Model,
+    ai_privacy_level: AiPrivacyLevel,
+) -> Result<Model, Error> {
+    let allowed = relationship.coachee_id == user_id
+        || (relationship.coach_id == user_id && ai_privacy_level <= relationship.ai_privacy_level);
+    if !allowed {
+        return Err(Error::entity(EntityErrorKind::Unauthenticated));
+    }
+
+    let relationship = entity_api::coaching_relationship::update_ai_privacy_level(
+        db,
+        relationship,
+        ai_privacy_level,
+    )
+    .await?;
+    record_updated(db, &relationship).await;
+    Ok(relationship)
+}
+
+/// Marks whether the coach coaches the relationship without pay, so coaching logs count
+/// its hours as pro bono. Coach-only.
+pub async fn set_pro_bono(
+    db: &DatabaseConnection,
+    user_id: crate::Id,
+    relationship: Model,
+    pro_bono: bool,
+) -> Result<Model, Error> {
+    if relationship.coach_id != user_id {
+        return Err(Error::entity(EntityErrorKind::Unauthenticated));
+    }
+
+    let relationship =
+        entity_api::coaching_relationship::update_pro_bono(db, relationship, pro_bono).await?;
+    record_updated(db, &relationship).await;
+    Ok(relationship)
+}
+
+/// Hands a relationship to another coach and/or coachee (whichever are given), e.g. when
+/// a coach leaves. Its sessions and the rest of its history stay with it.
+pub async fn reassign(
+    db: &DatabaseConnection,
+    id: crate::Id,
+    coach_id: Option<crate::Id>,
+    coachee_id: Option<crate::Id>,
+) -> Result<Model, Error> {
+    if coach_id.is_none() && coachee_id.is_none() {
+        return Err(Error {
+            source: None,
+            error_kind: DomainErrorKind::Validation(
+                "At least one of `coach_id` and `coachee_id` must be set".to_string(),
+            ),
+        });
+    }
+
+    let relationship = find_by_id(db, id).await?;
+    let coach_id = coach_id.unwrap_or(relationship.coach_id);
+    let coachee_id = coachee_id.unwrap_or(relationship.coachee_id);
+    let relationship =
+        entity_api::coaching_relationship::reassign(db, relationship, coach_id, coachee_id).await?;
+    record_updated(db, &relationship).await;
+    Ok(relationship)
+}
+
+/// Audits an update to a relationship.
+async fn record_updated(db: &DatabaseConnection, relationship: &Model) {
+    audit::record(
+        db,
+        audit::Action::Updated,
+        "coaching_relationship",
+        relationship.id,
+        audit::Scope::Organization(relationship.organization_id),
+        Some(relationship),
+    )
+    .await;
+}
+
 /// Finds coaching relationships for a user within an organization, respecting role-based access.
 ///
 /// - SuperAdmins (global role with organization_id = NULL) see all relationships in the organization
@@ -1,5 +1,6 @@
 pub(crate) mod recurrence;
 
+use crate::audit;
 use crate::coaching_relationships;
 use crate::coaching_session_hydration::{
     run_coaching_session_hydration_tasks, CoachingSessionHydrationContext,
@@ -9,6 +10,7 @@ use crate::error::{DomainErrorKind, EntityErrorKind, Error, InternalErrorKind};
 use crate::events::{DomainEvent, EventPublisher};
 use crate::gateway::tiptap::TiptapDocument;
 use crate::meeting_provider::MeetingProperties;
+use crate::session_type;
 use crate::Id;
 use chrono::{DurationRound, NaiveDateTime, TimeDelta};
 use entity_api::{
@@ -19,10 +21,11 @@ use log::*;
 use sea_orm::{DatabaseConnection, IntoActiveModel, TransactionTrait};
 use service::config::Config;
 
+pub use entity_api::carried_action::CarriedAction;
 pub use entity_api::coaching_session::{
-    find_by_id, find_by_series_id, find_by_user_with_includes, find_counts_by_month_for_user,
-    find_next_session, find_participant_ids, CountByMonth, EnrichedSession, IncludeOptions,
-    SessionQueryOptions,
+    find_badges_for_user, find_by_id, find_by_series_id, find_by_user_with_includes,
+    find_counts_by_month_for_user, find_deleted_by_id, find_next_session, find_participant_ids,
+    CountByMonth, EnrichedSession, IncludeOptions, SessionBadges, SessionQueryOptions,
 };
 pub use entity_api::coaching_session_display_title::SessionWithDisplayTitle;
 
@@ -92,6 +95,18 @@ pub async fn create(
     }
     let coach_id = coaching_relationship.coach_id;
 
+    // A session of a type defaults to the type's duration, ahead of the coach's default
+    let requested_duration = match coaching_session_model.session_type_id {
+        Some(session_type_id) => {
+            let session_type =
+                session_type::find_for_session(db, organization.id, session_type_id).await?;
+            requested_duration.or(Some(Duration::from_minutes_unchecked(
+                session_type.default_duration_minutes,
+            )))
+        }
+        None => requested_duration,
+    };
+
     coaching_session_model.date = SessionDate::new(coaching_session_model.date)?.into_inner();
 
     let document_name = generate_document_name(
@@ -134,6 +149,15 @@ pub async fn create(
     match result {
         Ok((session, events)) => {
             publish_events(event_publisher, events).await;
+            audit::record(
+                db,
+                audit::Action::Created,
+                "coaching_session",
+                session.id,
+                audit::Scope::Organization(organization.id),
+                Some(&session),
+            )
+            .await;
             Ok(session)
         }
         Err(e) => {
@@ -323,15 +347,30 @@ pub async fn update(
         "Domain update coaching_session id={id} relationship_id={} update_map={update_map:?}",
         coaching_session.coaching_relationship_id
     );
+    if let Some(sea_orm::Value::Uuid(Some(session_type_id))) =
+        update_map.get_value("session_type_id")
+    {
+        let relationship =
+            coaching_relationship::find_by_id(db, coaching_session.coaching_relationship_id)
+                .await?;
+        session_type::find_for_session(db, relationship.organization_id, **session_type_id).await?;
+    }
     let active_model = coaching_session.into_active_model();
-    Ok(
-        mutate::update::<coaching_sessions::ActiveModel, coaching_sessions::Column>(
-            db,
-            active_model,
-            update_map,
-        )
-        .await?,
+    let coaching_session = mutate::update::<
+        coaching_sessions::ActiveModel,
+        coaching_sessions::Column,
+    >(db, active_model, update_map)
+    .await?;
+    audit::record(
+        db,
+        audit::Action::Updated,
+        "coaching_session",
+        id,
+        audit::Scope::CoachingRelationship(coaching_session.coaching_relationship_id),
+        Some(&coaching_session),
     )
+    .await;
+    Ok(coaching_session)
 }
 
 /// Best-effort SSE notify that a session's own row changed (a title edit). The DB write is the
@@ -373,18 +412,56 @@ pub async fn update_title(
     Ok(coaching_session)
 }
 
-pub async fn delete(db: &DatabaseConnection, config: &Config, id: Id) -> Result<(), Error> {
-    let coaching_session = find_by_id(db, id).await?;
+/// Soft-deletes a session. Its notes, agreements, actions, collaborative document,
+/// recordings and attachments are kept, so restoring it brings everything back; they go
+/// when it's purged.
+pub async fn delete(db: &DatabaseConnection, id: Id) -> Result<(), Error> {
+    debug!("Domain delete coaching_session id={id}");
+    let session = coaching_session::delete(db, id).await?;
+    audit::record(
+        db,
+        audit::Action::Deleted,
+        "coaching_session",
+        id,
+        audit::Scope::CoachingRelationship(session.coaching_relationship_id),
+        None::<&Model>,
+    )
+    .await;
+    Ok(())
+}
+
+/// Restores a soft-deleted session.
+pub async fn restore(db: &DatabaseConnection, id: Id) -> Result<Model, Error> {
+    debug!("Domain restore coaching_session id={id}");
+    let session = coaching_session::restore(db, id).await?;
+    audit::record(
+        db,
+        audit::Action::Restored,
+        "coaching_session",
+        id,
+        audit::Scope::CoachingRelationship(session.coaching_relationship_id),
+        Some(&session),
+    )
+    .await;
+    Ok(session)
+}
+
+/// Permanently deletes a soft-deleted session: its collaborative document, stored
+/// recordings and attachments first, then its row and those of its notes, agreements
+/// and actions.
+pub async fn purge(db: &DatabaseConnection, config: &Config, session: &Model) -> Result<(), Error> {
     debug!(
-        "Domain delete coaching_session id={id} relationship_id={} tiptap_doc={:?}",
-        coaching_session.coaching_relationship_id, coaching_session.collab_document_name,
+        "Domain purge coaching_session id={} relationship_id={} tiptap_doc={:?}",
+        session.id, session.coaching_relationship_id, session.collab_document_name,
     );
-    if let Some(document_name) = coaching_session.collab_document_name {
+    if let Some(document_name) = &session.collab_document_name {
         let tiptap = TiptapDocument::new(config).await?;
-        tiptap.delete(&document_name).await?;
+        tiptap.delete(document_name).await?;
     }
+    crate::session_record::delete_stored(config, session.id).await?;
+    crate::attachment::delete_stored(db, config, session.id).await?;
 
-    coaching_session::delete(db, id).await?;
+    coaching_session::purge(db, session.id).await?;
     Ok(())
 }
 
@@ -522,10 +599,10 @@ fn generate_document_name(
 #[cfg(feature = "mock")]
 mod tests {
     use super::*;
-    use crate::test_support::recording_publisher;
+    use crate::test_support::{audit_entry, recording_publisher};
     use crate::{
-        coaching_relationships, coaching_sessions, goals, meeting_provider::Provider,
-        oauth_connections, organizations,
+        audit_entries, coaching_relationships, coaching_sessions, goals,
+        meeting_provider::Provider, oauth_connections, organizations,
     };
     use mockito::Server;
     use sea_orm::{DatabaseBackend, MockDatabase};
@@ -556,6 +633,8 @@ mod tests {
             coach_id,
             coachee_id: Id::new_v4(),
             slug: "test-slug".to_string(),
+            ai_privacy_level: Default::default(),
+            pro_bono: false,
             created_at: now.into(),
             updated_at: now.into(),
         }
@@ -576,6 +655,8 @@ mod tests {
             created_at: now.into(),
             updated_at: now.into(),
             hydrated_at: Some(now.into()),
+            session_type_id: None,
+            deleted_at: None,
         }
     }
 
@@ -611,10 +692,14 @@ mod tests {
 
         let (publisher, events) = recording_publisher();
 
-        // update: find_by_id → UPDATE ... RETURNING; then the participant lookup (find_also_related).
+        // update: find_by_id → UPDATE ... RETURNING → audit (previous snapshot, relationship,
+        // INSERT); then the participant lookup (find_also_related).
         let db = MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results(vec![vec![session.clone()]])
             .append_query_results(vec![vec![updated.clone()]])
+            .append_query_results(vec![Vec::<audit_entries::Model>::new()])
+            .append_query_results(vec![vec![relationship.clone()]])
+            .append_query_results(vec![vec![audit_entry()]])
             .append_query_results(vec![vec![(session.clone(), relationship.clone())]])
             .into_connection();
 
@@ -676,6 +761,7 @@ mod tests {
             target_date: None,
             created_at: now.into(),
             updated_at: now.into(),
+            deleted_at: None,
         };
         let link = entity_api::coaching_sessions_goals::Model {
             id: Id::new_v4(),
@@ -693,7 +779,9 @@ mod tests {
             .append_query_results(vec![vec![session.clone()]])
             .append_query_results(vec![vec![goal.clone()]])
             .append_query_results(vec![vec![link.clone()]])
-            // find_prior_session → None, so topics carry-over no-ops.
+            // find_prior_session → None (once per task), so topics and actions
+            // carry-over no-op.
+            .append_query_results(vec![Vec::<coaching_sessions::Model>::new()])
             .append_query_results(vec![Vec::<coaching_sessions::Model>::new()])
             .into_connection();
 
@@ -715,12 +803,10 @@ mod tests {
                 coaching_relationship_id,
                 coaching_session_id,
                 goal_id,
-                notify_user_ids,
             } => {
                 assert_eq!(*coaching_relationship_id, relationship.id);
                 assert_eq!(*coaching_session_id, session.id);
                 assert_eq!(*goal_id, goal.id);
-                assert_eq!(notify_user_ids, &vec![coach_id, relationship.coachee_id]);
             }
             other => panic!("expected CoachingSessionGoalCreated, got {other:?}"),
         }
@@ -761,6 +847,42 @@ mod tests {
         ));
     }
 
+    /// A session type of another organization is refused before any Tiptap/meeting
+    /// side effects run.
+    #[tokio::test]
+    async fn create_rejects_a_session_type_of_another_organization() {
+        let now = chrono::Utc::now();
+        let org = test_organization();
+        let relationship = test_coaching_relationship(Id::new_v4(), org.id);
+        let session_type = crate::session_types::Model {
+            id: Id::new_v4(),
+            organization_id: Id::new_v4(),
+            name: "Intro call".to_string(),
+            default_duration_minutes: 30,
+            color: None,
+            requires_recording: false,
+            created_at: now.into(),
+            updated_at: now.into(),
+        };
+        let session = coaching_sessions::Model {
+            session_type_id: Some(session_type.id),
+            ..test_session(relationship.id, None)
+        };
+
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![relationship.clone()]])
+            .append_query_results(vec![vec![org.clone()]])
+            .append_query_results(vec![vec![session_type]])
+            .into_connection();
+
+        let config = test_config("http://unused.test");
+        let (publisher, _recorded) = recording_publisher();
+        let result = create(&db, &config, &publisher, session, None).await;
+
+        let err = result.expect_err("expected the foreign session type to be refused");
+        assert!(matches!(err.error_kind, DomainErrorKind::Validation(_)));
+    }
+
     /// A prior session with a Deferred topic carries that topic forward at create
     /// and publishes a `TopicsChanged` scoped to coach + coachee, alongside the
     /// usual (here empty) goal events.
@@ -810,7 +932,8 @@ mod tests {
         // relationship → organization → session INSERT → in-progress goals SELECT
         // (empty, no goal event) → find_prior_session (returns prior) →
         // move_deferred_to_session source SELECT (one Deferred topic) → target SELECT
-        // (empty, for base) → UPDATE (the moved topic).
+        // (empty, for base) → UPDATE (the moved topic) → find_prior_session again →
+        // open actions SELECT (empty, so nothing is carried).
         let db = MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results(vec![vec![relationship.clone()]])
             .append_query_results(vec![vec![org.clone()]])
@@ -820,6 +943,8 @@ mod tests {
             .append_query_results(vec![vec![deferred_topic.clone()]])
             .append_query_results(vec![Vec::<crate::coaching_session_topics::Model>::new()])
             .append_query_results(vec![vec![moved_topic.clone()]])
+            .append_query_results(vec![vec![prior.clone()]])
+            .append_query_results(vec![Vec::<crate::actions::Model>::new()])
             .into_connection();
 
         let config = test_config(&server.url());
@@ -857,6 +982,84 @@ mod tests {
         Ok(())
     }
 
+    /// The prior session's open actions are linked to the new session at create,
+    /// recording which session they were carried from.
+    #[tokio::test]
+    async fn create_carries_forward_prior_session_open_actions() -> Result<(), Error> {
+        let mut server = Server::new_async().await;
+        let _tiptap_mock = server
+            .mock("POST", mockito::Matcher::Any)
+            .with_status(200)
+            .create_async()
+            .await;
+
+        let org = test_organization();
+        let relationship = test_coaching_relationship(Id::new_v4(), org.id);
+        let session = test_session(relationship.id, None);
+        let prior = coaching_sessions::Model {
+            id: Id::new_v4(),
+            date: chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap().into(),
+            ..session.clone()
+        };
+        let now = chrono::Utc::now();
+        let open_action = crate::actions::Model {
+            id: Id::new_v4(),
+            coaching_session_id: prior.id,
+            goal_id: None,
+            user_id: relationship.coachee_id,
+            action_series_id: None,
+            previous_action_id: None,
+            body: Some("Draft the proposal".to_string()),
+            effort_points: None,
+            due_by: None,
+            status: crate::status::Status::InProgress,
+            status_changed_at: now.into(),
+            created_at: now.into(),
+            updated_at: now.into(),
+            deleted_at: None,
+        };
+
+        // relationship → organization → session INSERT → in-progress goals SELECT
+        // (empty) → find_prior_session (prior) → topics source and target SELECTs
+        // (empty, nothing moves) → find_prior_session (prior) → open actions SELECT
+        // (one) → carried_actions INSERT.
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![relationship.clone()]])
+            .append_query_results(vec![vec![org.clone()]])
+            .append_query_results(vec![vec![session.clone()]])
+            .append_query_results(vec![Vec::<goals::Model>::new()])
+            .append_query_results(vec![vec![prior.clone()]])
+            .append_query_results(vec![Vec::<crate::coaching_session_topics::Model>::new()])
+            .append_query_results(vec![Vec::<crate::coaching_session_topics::Model>::new()])
+            .append_query_results(vec![vec![prior.clone()]])
+            .append_query_results(vec![vec![open_action.clone()]])
+            .append_exec_results(vec![sea_orm::MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            }])
+            .into_connection();
+
+        let config = test_config(&server.url());
+        create(
+            &db,
+            &config,
+            &EventPublisher::new(),
+            session.clone(),
+            Some(Duration::default()),
+        )
+        .await?;
+
+        let log = format!("{:?}", db.into_transaction_log());
+        assert!(
+            log.contains(r#"INSERT INTO \"refactor_platform\".\"carried_actions\""#),
+            "expected the open action to be carried forward, got {log}"
+        );
+        assert!(log.contains(&open_action.id.to_string()));
+        assert!(log.contains(&prior.id.to_string()));
+
+        Ok(())
+    }
+
     /// When no provider is set, the oauth credentials lookup is skipped entirely.
     #[tokio::test]
     async fn create_without_provider_skips_oauth_lookup() -> Result<(), Error> {
@@ -881,7 +1084,9 @@ mod tests {
             .append_query_results(vec![vec![org.clone()]])
             .append_query_results(vec![vec![session.clone()]])
             .append_query_results(vec![Vec::<goals::Model>::new()])
-            // find_prior_session → None, so topics carry-over no-ops.
+            // find_prior_session → None (once per task), so topics and actions
+            // carry-over no-op.
+            .append_query_results(vec![Vec::<coaching_sessions::Model>::new()])
             .append_query_results(vec![Vec::<coaching_sessions::Model>::new()])
             .into_connection();
 
@@ -933,6 +1138,8 @@ mod tests {
             hydrated_at: Some(
                 chrono::DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z").unwrap(),
             ),
+            session_type_id: None,
+            deleted_at: None,
         };
 
         // The session as the DB would return it after INSERT (with the reused meeting URL)
@@ -954,7 +1161,9 @@ mod tests {
             .append_query_results(vec![vec![existing_session_with_url]])
             .append_query_results(vec![vec![saved_session]])
             .append_query_results(vec![Vec::<goals::Model>::new()])
-            // find_prior_session → None, so topics carry-over no-ops.
+            // find_prior_session → None (once per task), so topics and actions
+            // carry-over no-op.
+            .append_query_results(vec![Vec::<coaching_sessions::Model>::new()])
             .append_query_results(vec![Vec::<coaching_sessions::Model>::new()])
             .into_connection();
 
@@ -1008,7 +1217,9 @@ mod tests {
             )
             .append_query_results(vec![vec![session.clone()]])
             .append_query_results(vec![Vec::<goals::Model>::new()])
-            // find_prior_session → None, so topics carry-over no-ops.
+            // find_prior_session → None (once per task), so topics and actions
+            // carry-over no-op.
+            .append_query_results(vec![Vec::<coaching_sessions::Model>::new()])
             .append_query_results(vec![Vec::<coaching_sessions::Model>::new()])
             .into_connection();
 
@@ -1201,7 +1412,7 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn delete_unhydrated_session_skips_tiptap_and_succeeds() -> Result<(), Error> {
+    async fn purge_unhydrated_session_skips_tiptap_and_succeeds() -> Result<(), Error> {
         let mut server = Server::new_async().await;
         let config = test_config(&server.url());
 
@@ -1215,18 +1426,24 @@ mod tests {
         let session = coaching_sessions::Model {
             collab_document_name: None,
             hydrated_at: None,
+            deleted_at: Some(chrono::Utc::now().fixed_offset()),
             ..test_session(Id::new_v4(), None)
         };
 
+        // Locking the deleted session, then deleting its notes, agreements, actions and
+        // itself.
         let db = MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results(vec![vec![session.clone()]])
-            .append_exec_results(vec![sea_orm::MockExecResult {
-                last_insert_id: 0,
-                rows_affected: 1,
-            }])
+            .append_exec_results(vec![
+                sea_orm::MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 1,
+                };
+                4
+            ])
             .into_connection();
 
-        delete(&db, &config, session.id).await?;
+        purge(&db, &config, &session).await?;
 
         tiptap_mock.assert_async().await;
         Ok(())
@@ -34,7 +34,6 @@ pub async fn link_to_coaching_session(
     let (_, relationship) =
         crate::coaching_session::find_by_id_with_coaching_relationship(db, coaching_session_id)
             .await?;
-    let notify_user_ids = vec![relationship.coach_id, relationship.coachee_id];
 
     let txn = db.begin().await.map_err(entity_api::error::Error::from)?;
     let (link, promoted_goal) =
@@ -46,7 +45,6 @@ pub async fn link_to_coaching_session(
             coaching_relationship_id: relationship.id,
             coaching_session_id,
             goal_id,
-            notify_user_ids: notify_user_ids.clone(),
         })
         .await;
 
@@ -64,7 +62,6 @@ pub async fn link_to_coaching_session(
                 // that's a real bug we want loud — not a silent null payload
                 // that the FE would have to defensively handle.
                 goal: serde_json::to_value(&goal).expect("Goal model must be JSON-serializable"),
-                notify_user_ids,
             })
             .await;
 
@@ -183,14 +180,11 @@ async fn publish_session_goal_deleted(
     link: &coaching_sessions_goals::Model,
     relationship: &entity_api::coaching_relationships::Model,
 ) {
-    let notify_user_ids = vec![relationship.coach_id, relationship.coachee_id];
-
     event_publisher
         .publish(DomainEvent::CoachingSessionGoalDeleted {
             coaching_relationship_id: relationship.id,
             coaching_session_id: link.coaching_session_id,
             goal_id: link.goal_id,
-            notify_user_ids,
         })
         .await;
 
@@ -226,6 +220,7 @@ mod integration_tests {
             target_date: None,
             created_at: now,
             updated_at: now,
+            deleted_at: None,
         }
     }
 
@@ -237,6 +232,8 @@ mod integration_tests {
             coach_id: Id::new_v4(),
             coachee_id: Id::new_v4(),
             slug: "test-rel".to_string(),
+            ai_privacy_level: Default::default(),
+            pro_bono: false,
             created_at: now,
             updated_at: now,
         }
@@ -257,6 +254,8 @@ mod integration_tests {
             created_at: now.into(),
             updated_at: now.into(),
             hydrated_at: Some(now.into()),
+            session_type_id: None,
+            deleted_at: None,
         }
     }
 
@@ -2,6 +2,7 @@ use crate::coaching_relationships;
 use crate::coaching_sessions::Model;
 use crate::error::Error;
 use crate::events::DomainEvent;
+use entity_api::carried_action;
 use entity_api::coaching_session;
 use entity_api::coaching_session_goal;
 use entity_api::coaching_session_topic;
@@ -35,6 +36,7 @@ fn coaching_session_hydration_tasks() -> Vec<Box<dyn CoachingSessionHydrationTas
     vec![
         Box::new(GoalsCarryForwardTask),
         Box::new(TopicsMoveForwardTask),
+        Box::new(ActionsCarryForwardTask),
     ]
 }
 
@@ -61,7 +63,7 @@ pub(crate) async fn run_coaching_session_hydration_tasks(
 
 /// Links the relationship's in-progress goals to the session and emits one
 /// `CoachingSessionGoalCreated` per newly-linked goal. Behavior-identical to the
-/// pre-registry inline path (same query, same events, same notify list).
+/// pre-registry inline path (same query, same events).
 struct GoalsCarryForwardTask;
 
 #[async_trait::async_trait]
@@ -78,7 +80,6 @@ impl CoachingSessionHydrationTask for GoalsCarryForwardTask {
         &self,
         ctx: &CoachingSessionHydrationContext<'_>,
     ) -> Result<Vec<DomainEvent>, Error> {
-        let notify_user_ids = vec![ctx.relationship.coach_id, ctx.relationship.coachee_id];
         Ok(coaching_session_goal::link_in_progress_goals_to_session(
             ctx.txn,
             ctx.session.coaching_relationship_id,
@@ -90,7 +91,6 @@ impl CoachingSessionHydrationTask for GoalsCarryForwardTask {
             coaching_relationship_id: ctx.relationship.id,
             coaching_session_id: ctx.session.id,
             goal_id,
-            notify_user_ids: notify_user_ids.clone(),
         })
         .collect())
     }
@@ -139,3 +139,39 @@ impl CoachingSessionHydrationTask for TopicsMoveForwardTask {
         ])
     }
 }
+
+/// Carries the prior session's open actions (its own and those carried into it) into
+/// this session, so they surface there until completed or dropped.
+struct ActionsCarryForwardTask;
+
+#[async_trait::async_trait]
+impl CoachingSessionHydrationTask for ActionsCarryForwardTask {
+    fn name(&self) -> &'static str {
+        "actions_carry_forward"
+    }
+
+    /// Reads as a sentence: find the prior session, collect its open actions, link them
+    /// to this session. The links are read back through the session's enriched payload,
+    /// so there is nothing to announce.
+    async fn run(
+        &self,
+        ctx: &CoachingSessionHydrationContext<'_>,
+    ) -> Result<Vec<DomainEvent>, Error> {
+        let Some(prior) = coaching_session::find_prior_session(
+            ctx.txn,
+            ctx.session.coaching_relationship_id,
+            ctx.session.date,
+        )
+        .await?
+        else {
+            return Ok(Vec::new());
+        };
+        let action_ids: Vec<_> = carried_action::find_open_to_carry(ctx.txn, prior.id)
+            .await?
+            .into_iter()
+            .map(|action| action.id)
+            .collect();
+        carried_action::carry(ctx.txn, &action_ids, prior.id, ctx.session.id).await?;
+        Ok(Vec::new())
+    }
+}
@@ -308,6 +308,8 @@ mod tests {
             coach_id,
             coachee_id: Id::new_v4(),
             slug: "test".into(),
+            ai_privacy_level: Default::default(),
+            pro_bono: false,
             created_at: now.into(),
             updated_at: now.into(),
         };
@@ -352,6 +354,8 @@ mod tests {
             created_at: now.into(),
             updated_at: now.into(),
             hydrated_at: None,
+            session_type_id: None,
+            deleted_at: None,
         };
 
         let expected_sessions = vec![
@@ -402,6 +406,8 @@ mod tests {
             coach_id,
             coachee_id: Id::new_v4(),
             slug: "test".into(),
+            ai_privacy_level: Default::default(),
+            pro_bono: false,
             created_at: now.into(),
             updated_at: now.into(),
         };
@@ -467,6 +473,8 @@ mod tests {
             created_at: now.into(),
             updated_at: now.into(),
             hydrated_at: None,
+            session_type_id: None,
+            deleted_at: None,
         };
 
         // Two future sessions, neither hydrated, neither carrying a Tiptap doc.
@@ -603,6 +611,8 @@ mod tests {
             created_at: now.into(),
             updated_at: now.into(),
             hydrated_at: None,
+            session_type_id: None,
+            deleted_at: None,
         };
 
         let future_sessions = vec![
@@ -668,6 +678,8 @@ mod tests {
             created_at: now.into(),
             updated_at: now.into(),
             hydrated_at: None,
+            session_type_id: None,
+            deleted_at: None,
         };
 
         let db = MockDatabase::new(DatabaseBackend::Postgres)
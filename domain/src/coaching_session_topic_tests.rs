@@ -43,6 +43,8 @@ fn session_with_relationship(
         created_at: now,
         updated_at: now,
         hydrated_at: None,
+        session_type_id: None,
+        deleted_at: None,
     };
     let relationship = coaching_relationships::Model {
         id: relationship_id,
@@ -50,6 +52,8 @@ fn session_with_relationship(
         coach_id: Id::new_v4(),
         coachee_id: Id::new_v4(),
         slug: "test-slug".to_string(),
+        ai_privacy_level: Default::default(),
+        pro_bono: false,
         created_at: now,
         updated_at: now,
     };
@@ -76,6 +80,8 @@ fn coaching_session(
         created_at: now,
         updated_at: now,
         hydrated_at: None,
+        session_type_id: None,
+        deleted_at: None,
     }
 }
 
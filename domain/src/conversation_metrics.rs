@@ -0,0 +1,367 @@
+//! Talk-time and conversation balance analytics.
+//!
+//! [`compute`] derives per-speaker talk time, question ratios, interruptions and the
+//! longest monologue from a transcript's segments. [`crate::transcription`] stores the
+//! metrics on the transcription once its segments are saved; transcriptions stored
+//! before then are backfilled the first time their metrics are requested.
+
+pub use entity::transcription::{ConversationMetrics, Monologue, SpeakerMetrics};
+
+use chrono::NaiveDateTime;
+use entity::transcript_segment::Model as Segment;
+use entity::transcription::Model as Transcription;
+use entity_api::{transcript_segment as segment_api, transcription as transcription_api};
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use crate::error::{EntityErrorKind, Error};
+use crate::Id;
+
+/// A session's metrics within a relationship's trend.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SessionMetrics {
+    pub coaching_session_id: Id,
+    pub date: NaiveDateTime,
+    pub metrics: ConversationMetrics,
+}
+
+/// A speaker's averages across the sessions they spoke in.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SpeakerTrend {
+    pub speaker_label: String,
+    pub session_count: u32,
+    pub average_talk_share: f64,
+    pub average_question_ratio: f64,
+}
+
+/// Conversation metrics across a coaching relationship's transcribed sessions.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Trend {
+    /// Oldest session first.
+    pub sessions: Vec<SessionMetrics>,
+    /// Most sessions first.
+    pub speakers: Vec<SpeakerTrend>,
+    pub average_interruption_count: f64,
+}
+
+/// Metrics of the coaching session's latest completed transcription. Fails with not
+/// found when the session has none.
+pub async fn find_by_coaching_session(
+    db: &DatabaseConnection,
+    coaching_session_id: Id,
+) -> Result<ConversationMetrics, Error> {
+    let transcription =
+        transcription_api::find_completed_by_coaching_sessions(db, &[coaching_session_id])
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::entity(EntityErrorKind::NotFound))?;
+    metrics_of(db, transcription).await
+}
+
+/// Metrics of each transcribed session of the coaching relationship, with per-speaker
+/// averages. Callers authorize access to the relationship.
+pub async fn find_trend_by_coaching_relationship(
+    db: &DatabaseConnection,
+    coaching_relationship_id: Id,
+) -> Result<Trend, Error> {
+    let sessions =
+        entity_api::coaching_session::find_by_relationship(db, coaching_relationship_id).await?;
+    let session_ids: Vec<Id> = sessions.iter().map(|session| session.id).collect();
+
+    // Newest first, so the first transcription seen for a session is its latest.
+    let mut latest: HashMap<Id, Transcription> = HashMap::new();
+    for transcription in
+        transcription_api::find_completed_by_coaching_sessions(db, &session_ids).await?
+    {
+        latest
+            .entry(transcription.coaching_session_id)
+            .or_insert(transcription);
+    }
+
+    let mut series = Vec::new();
+    for session in sessions {
+        if let Some(transcription) = latest.remove(&session.id) {
+            series.push(SessionMetrics {
+                coaching_session_id: session.id,
+                date: session.date,
+                metrics: metrics_of(db, transcription).await?,
+            });
+        }
+    }
+    series.sort_by_key(|session| session.date);
+
+    Ok(trend(series))
+}
+
+/// Conversation metrics of `segments`, in any order.
+pub fn compute(segments: &[Segment]) -> ConversationMetrics {
+    let mut ordered: Vec<&Segment> = segments.iter().collect();
+    ordered.sort_by_key(|segment| segment.start_ms);
+
+    let mut speakers: Vec<SpeakerMetrics> = Vec::new();
+    let mut sentence_counts: Vec<u32> = Vec::new();
+    let mut interruption_count = 0;
+    let mut longest_monologue: Option<Monologue> = None;
+    let mut run: Option<Monologue> = None;
+    let mut previous: Option<&Segment> = None;
+
+    for segment in ordered {
+        let index = match speakers
+            .iter()
+            .position(|speaker| speaker.speaker_label == segment.speaker_label)
+        {
+            Some(index) => index,
+            None => {
+                speakers.push(SpeakerMetrics {
+                    speaker_label: segment.speaker_label.clone(),
+                    ..Default::default()
+                });
+                sentence_counts.push(0);
+                speakers.len() - 1
+            }
+        };
+
+        let (sentences, questions) = sentences(&segment.text);
+        let speaker = &mut speakers[index];
+        speaker.talk_ms += duration_ms(segment.start_ms, segment.end_ms);
+        speaker.segment_count += 1;
+        speaker.question_count += questions;
+        sentence_counts[index] += sentences;
+
+        let same_speaker =
+            previous.is_some_and(|previous| previous.speaker_label == segment.speaker_label);
+        if !same_speaker && previous.is_some_and(|previous| segment.start_ms < previous.end_ms) {
+            speaker.interruption_count += 1;
+            interruption_count += 1;
+        }
+
+        match run.as_mut() {
+            Some(current) if same_speaker => {
+                current.duration_ms = current
+                    .duration_ms
+                    .max(duration_ms(current.start_ms, segment.end_ms));
+            }
+            _ => {
+                longest_monologue = longer(longest_monologue, run.take());
+                run = Some(Monologue {
+                    speaker_label: segment.speaker_label.clone(),
+                    start_ms: segment.start_ms,
+                    duration_ms: duration_ms(segment.start_ms, segment.end_ms),
+                });
+            }
+        }
+        let current = run.as_ref().map_or(0, |run| run.duration_ms);
+        speaker.longest_monologue_ms = speaker.longest_monologue_ms.max(current);
+
+        previous = Some(segment);
+    }
+    let longest_monologue = longer(longest_monologue, run);
+
+    let total_talk_ms: i64 = speakers.iter().map(|speaker| speaker.talk_ms).sum();
+    for (speaker, sentences) in speakers.iter_mut().zip(sentence_counts) {
+        speaker.talk_share = ratio(speaker.talk_ms as f64, total_talk_ms as f64);
+        speaker.question_ratio = ratio(f64::from(speaker.question_count), f64::from(sentences));
+    }
+    speakers.sort_by_key(|speaker| Reverse(speaker.talk_ms));
+
+    ConversationMetrics {
+        total_talk_ms,
+        interruption_count,
+        longest_monologue,
+        speakers,
+    }
+}
+
+/// The transcription's stored metrics, computing and storing them first if it has none.
+async fn metrics_of(
+    db: &DatabaseConnection,
+    transcription: Transcription,
+) -> Result<ConversationMetrics, Error> {
+    if let Some(metrics) = transcription.conversation_metrics {
+        return Ok(metrics);
+    }
+    let segments = segment_api::find_by_transcription(db, transcription.id).await?;
+    let metrics = compute(&segments);
+    transcription_api::update_conversation_metrics(db, transcription.id, metrics.clone()).await?;
+    Ok(metrics)
+}
+
+fn trend(sessions: Vec<SessionMetrics>) -> Trend {
+    // Sums per speaker first, divided into averages below.
+    let mut speakers: Vec<SpeakerTrend> = Vec::new();
+    for metrics in sessions
+        .iter()
+        .flat_map(|session| &session.metrics.speakers)
+    {
+        let index = match speakers
+            .iter()
+            .position(|speaker| speaker.speaker_label == metrics.speaker_label)
+        {
+            Some(index) => index,
+            None => {
+                speakers.push(SpeakerTrend {
+                    speaker_label: metrics.speaker_label.clone(),
+                    session_count: 0,
+                    average_talk_share: 0.0,
+                    average_question_ratio: 0.0,
+                });
+                speakers.len() - 1
+            }
+        };
+        let speaker = &mut speakers[index];
+        speaker.session_count += 1;
+        speaker.average_talk_share += metrics.talk_share;
+        speaker.average_question_ratio += metrics.question_ratio;
+    }
+    for speaker in &mut speakers {
+        let count = f64::from(speaker.session_count);
+        speaker.average_talk_share = ratio(speaker.average_talk_share, count);
+        speaker.average_question_ratio = ratio(speaker.average_question_ratio, count);
+    }
+    speakers.sort_by_key(|speaker| Reverse(speaker.session_count));
+
+    let interruptions: u32 = sessions
+        .iter()
+        .map(|session| session.metrics.interruption_count)
+        .sum();
+    Trend {
+        average_interruption_count: ratio(f64::from(interruptions), sessions.len() as f64),
+        sessions,
+        speakers,
+    }
+}
+
+/// Sentences in `text` and how many of them are questions. Text after the last
+/// terminator counts as a sentence.
+fn sentences(text: &str) -> (u32, u32) {
+    let (mut sentences, mut questions) = (0, 0);
+    let mut in_sentence = false;
+    for c in text.chars() {
+        match c {
+            '?' if in_sentence => {
+                sentences += 1;
+                questions += 1;
+                in_sentence = false;
+            }
+            '.' | '!' if in_sentence => {
+                sentences += 1;
+                in_sentence = false;
+            }
+            c if c.is_alphanumeric() => in_sentence = true,
+            _ => {}
+        }
+    }
+    if in_sentence {
+        sentences += 1;
+    }
+    (sentences, questions)
+}
+
+fn longer(a: Option<Monologue>, b: Option<Monologue>) -> Option<Monologue> {
+    match (a, b) {
+        (Some(a), Some(b)) if b.duration_ms > a.duration_ms => Some(b),
+        (Some(a), _) => Some(a),
+        (None, b) => b,
+    }
+}
+
+fn duration_ms(start_ms: i32, end_ms: i32) -> i64 {
+    (i64::from(end_ms) - i64::from(start_ms)).max(0)
+}
+
+fn ratio(part: f64, whole: f64) -> f64 {
+    if whole > 0.0 {
+        part / whole
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(speaker: &str, text: &str, start_ms: i32, end_ms: i32) -> Segment {
+        Segment {
+            id: Id::new_v4(),
+            transcription_id: Id::nil(),
+            speaker_label: speaker.to_string(),
+            text: text.to_string(),
+            start_ms,
+            end_ms,
+            confidence: None,
+            sentiment: None,
+            created_at: chrono::Utc::now().into(),
+        }
+    }
+
+    #[test]
+    fn compute_balances_talk_time_questions_and_monologues() {
+        let metrics = compute(&[
+            segment("Coach", "What went well? Tell me more.", 0, 4_000),
+            segment("Coachee", "The launch. It shipped on time.", 4_000, 10_000),
+            segment("Coachee", "And the team was happy.", 10_000, 16_000),
+            segment("Coach", "Why?", 15_000, 16_000),
+        ]);
+
+        assert_eq!(metrics.total_talk_ms, 17_000);
+        assert_eq!(metrics.interruption_count, 1);
+        assert_eq!(
+            metrics.longest_monologue,
+            Some(Monologue {
+                speaker_label: "Coachee".to_string(),
+                start_ms: 4_000,
+                duration_ms: 12_000,
+            })
+        );
+
+        let coachee = &metrics.speakers[0];
+        assert_eq!(coachee.speaker_label, "Coachee");
+        assert_eq!(coachee.talk_ms, 12_000);
+        assert_eq!(coachee.question_count, 0);
+        assert_eq!(coachee.longest_monologue_ms, 12_000);
+
+        let coach = &metrics.speakers[1];
+        assert_eq!(coach.segment_count, 2);
+        assert_eq!(coach.question_count, 2);
+        assert!((coach.question_ratio - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(coach.interruption_count, 1);
+    }
+
+    #[test]
+    fn trend_averages_each_speaker_over_their_sessions() {
+        let session = |segments: &[Segment]| SessionMetrics {
+            coaching_session_id: Id::new_v4(),
+            date: chrono::Utc::now().naive_utc(),
+            metrics: compute(segments),
+        };
+
+        let trend = trend(vec![
+            session(&[
+                segment("Coach", "Ready?", 0, 1_000),
+                segment("Coachee", "Yes.", 1_000, 4_000),
+            ]),
+            session(&[segment("Coachee", "Solo.", 0, 1_000)]),
+        ]);
+
+        assert_eq!(trend.speakers[0].speaker_label, "Coachee");
+        assert_eq!(trend.speakers[0].session_count, 2);
+        assert!((trend.speakers[0].average_talk_share - 0.875).abs() < 1e-9);
+        assert_eq!(trend.speakers[1].average_question_ratio, 1.0);
+        assert_eq!(trend.average_interruption_count, 0.0);
+    }
+
+    #[test]
+    fn compute_handles_an_empty_transcript() {
+        assert_eq!(compute(&[]), ConversationMetrics::default());
+    }
+
+    #[test]
+    fn sentences_counts_trailing_text_and_ignores_stray_punctuation() {
+        assert_eq!(sentences("Really?! Yes... and then"), (3, 1));
+        assert_eq!(sentences("?"), (0, 0));
+    }
+}
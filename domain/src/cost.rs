@@ -4,6 +4,7 @@ use crate::pipeline_provider::Provider;
 use crate::Id;
 use entity_api::{cost_pricing_config, meeting_recording as recording_api, platform_cost_metrics};
 use log::warn;
+use sea_orm::prelude::Decimal;
 use sea_orm::DatabaseConnection;
 
 /// Records the Recall.ai bot-minutes cost for a completed recording.
@@ -35,6 +36,85 @@ pub async fn record_transcription_hours(
     .await
 }
 
+/// Records the LLM gateway token cost of one completion.
+///
+/// `source_record_id` is the record the completion produced (e.g. the session whose
+/// prep brief was generated). No-ops with a warning if no pricing row is configured
+/// or the provider didn't report usage.
+pub async fn record_llm_tokens(
+    db: &DatabaseConnection,
+    coaching_session_id: Option<Id>,
+    source_record_id: Id,
+    tokens: Option<u32>,
+) -> Result<(), Error> {
+    record_tokens(
+        db,
+        Metric::LlmTokens,
+        coaching_session_id,
+        source_record_id,
+        tokens,
+    )
+    .await
+}
+
+/// Records the LLM gateway token cost of one embedding request.
+///
+/// Same no-op rules as [`record_llm_tokens`]; embedding tokens have their own rate.
+pub async fn record_embedding_tokens(
+    db: &DatabaseConnection,
+    coaching_session_id: Option<Id>,
+    source_record_id: Id,
+    tokens: Option<u32>,
+) -> Result<(), Error> {
+    record_tokens(
+        db,
+        Metric::EmbeddingTokens,
+        coaching_session_id,
+        source_record_id,
+        tokens,
+    )
+    .await
+}
+
+/// Shared cost-recording path for the token-priced LLM gateway metrics.
+async fn record_tokens(
+    db: &DatabaseConnection,
+    metric: Metric,
+    coaching_session_id: Option<Id>,
+    source_record_id: Id,
+    tokens: Option<u32>,
+) -> Result<(), Error> {
+    let Some(tokens) = tokens.filter(|tokens| *tokens > 0) else {
+        warn!("cost: no token usage reported — skipping {metric:?} cost for {source_record_id}");
+        return Ok(());
+    };
+
+    let Some(rate) =
+        cost_pricing_config::find_current_rate(db, Provider::LlmGateway, metric).await?
+    else {
+        warn!("cost: no pricing config for (LlmGateway, {metric:?}) — skipping {source_record_id}");
+        return Ok(());
+    };
+
+    let costs = rate.cost_for(Decimal::from(tokens));
+
+    platform_cost_metrics::create(
+        db,
+        platform_cost_metrics::CreateParams {
+            provider: Provider::LlmGateway,
+            metric,
+            coaching_session_id,
+            source_record_id,
+            cost_low: costs.low,
+            cost_high: costs.high,
+            cost_avg: costs.avg,
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
 /// Shared cost-recording path for the duration-derived Recall.ai metrics.
 ///
 /// Looks up the current rate, fetches the recording that carries the billable
@@ -110,7 +190,7 @@ mod tests {
         let unit = match metric {
             Metric::BotMinutes => Unit::Minutes,
             Metric::TranscriptionHours => Unit::Hours,
-            Metric::LlmTokens => Unit::Tokens,
+            Metric::LlmTokens | Metric::EmbeddingTokens => Unit::Tokens,
         };
         RateModel {
             id: Id::new_v4(),
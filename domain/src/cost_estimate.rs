@@ -0,0 +1,205 @@
+//! Expected cost of an AI operation on a coaching session before it's triggered.
+//!
+//! Token counts are estimated from the length of the stored material the operation would
+//! send (notes, agreements, transcripts) and the completion budget the organization's
+//! [`crate::ai_settings`] give the task; transcription is priced by the recording's
+//! duration. Costs use the same pricing table [`crate::cost`] records actual usage with,
+//! so an estimate and the cost recorded afterwards are comparable. Quota checks can call
+//! [`estimate`] directly.
+
+use entity_api::{cost_pricing_config, meeting_recording};
+use log::*;
+use meeting_ai::traits::analysis;
+use sea_orm::prelude::Decimal;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+
+use crate::ai_settings::{self, TaskType};
+use crate::cost_metric::Metric;
+use crate::error::{DomainErrorKind, Error, InternalErrorKind};
+use crate::pipeline_provider::Provider;
+use crate::{coaching_relationships, coaching_session, session_prep, themes, Id};
+
+/// Rough characters per token for English text, used to turn lengths into tokens.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// An AI operation that can be run (or re-run) for a coaching session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Operation {
+    /// The prep brief's summary and focus questions for the session.
+    Summary,
+    /// The themes report of the session's coaching relationship.
+    Extraction,
+    /// Transcribing the session's latest recording.
+    Transcription,
+}
+
+/// A cost range in the pricing table's currency.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Cost {
+    pub low: Decimal,
+    pub high: Decimal,
+    pub avg: Decimal,
+}
+
+/// What an operation is expected to use and cost. `cost` is `None` when no rate is
+/// configured for the operation's metric.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Estimate {
+    pub operation: Operation,
+    /// Estimated prompt tokens (LLM operations only).
+    pub input_tokens: Option<u32>,
+    /// Completion token budget, the most the operation can produce (LLM operations only).
+    pub output_tokens: Option<u32>,
+    /// Billable recording duration (transcription only).
+    pub duration_seconds: Option<i32>,
+    pub cost: Option<Cost>,
+}
+
+/// Estimates `operation` for the coaching session. Callers authorize access to the
+/// session.
+pub async fn estimate(
+    db: &DatabaseConnection,
+    analysis_provider: Option<&dyn analysis::Provider>,
+    coaching_session_id: Id,
+    operation: Operation,
+) -> Result<Estimate, Error> {
+    let (session, relationship) =
+        coaching_session::find_by_id_with_coaching_relationship(db, coaching_session_id).await?;
+
+    match operation {
+        Operation::Summary => {
+            let prompt_chars = session_prep::prompt_chars(db, &session, &relationship).await?;
+            estimate_completion(
+                db,
+                analysis_provider,
+                &relationship,
+                operation,
+                TaskType::Summary,
+                prompt_chars,
+            )
+            .await
+        }
+        Operation::Extraction => {
+            let prompt_chars = themes::prompt_chars(db, &relationship).await?;
+            estimate_completion(
+                db,
+                analysis_provider,
+                &relationship,
+                operation,
+                TaskType::Extraction,
+                prompt_chars,
+            )
+            .await
+        }
+        Operation::Transcription => {
+            let duration_seconds =
+                meeting_recording::find_latest_by_coaching_session(db, coaching_session_id)
+                    .await?
+                    .and_then(|recording| recording.duration_seconds)
+                    .filter(|seconds| *seconds > 0)
+                    .ok_or_else(|| {
+                        Error::validation("The coaching session has no recording with a duration")
+                    })?;
+
+            let rate = cost_pricing_config::find_current_rate(
+                db,
+                Provider::RecallAi,
+                Metric::TranscriptionHours,
+            )
+            .await?;
+            let cost = rate.and_then(|rate| {
+                let quantity = rate.unit.quantity_from_seconds(Some(duration_seconds))?;
+                Some(cost_of(&rate, quantity))
+            });
+
+            Ok(Estimate {
+                operation,
+                input_tokens: None,
+                output_tokens: None,
+                duration_seconds: Some(duration_seconds),
+                cost,
+            })
+        }
+    }
+}
+
+/// Estimate of an LLM completion sending `prompt_chars` characters for `task`.
+async fn estimate_completion(
+    db: &DatabaseConnection,
+    analysis_provider: Option<&dyn analysis::Provider>,
+    relationship: &coaching_relationships::Model,
+    operation: Operation,
+    task: TaskType,
+    prompt_chars: usize,
+) -> Result<Estimate, Error> {
+    if !relationship.ai_privacy_level.allows_ai() {
+        return Err(Error::validation(
+            "AI features are disabled for this coaching relationship",
+        ));
+    }
+    let provider = analysis_provider.ok_or_else(|| {
+        warn!("Analysis provider not configured");
+        Error {
+            source: None,
+            error_kind: DomainErrorKind::Internal(InternalErrorKind::Config),
+        }
+    })?;
+
+    let input_tokens = tokens_for(prompt_chars);
+    let output_tokens =
+        ai_settings::max_tokens(db, provider, relationship.organization_id, task).await?;
+
+    // Completions are recorded at one rate for their total tokens; see `cost`.
+    let cost = cost_pricing_config::find_current_rate(db, Provider::LlmGateway, Metric::LlmTokens)
+        .await?
+        .map(|rate| cost_of(&rate, Decimal::from(input_tokens + output_tokens)));
+
+    Ok(Estimate {
+        operation,
+        input_tokens: Some(input_tokens),
+        output_tokens: Some(output_tokens),
+        duration_seconds: None,
+        cost,
+    })
+}
+
+fn tokens_for(chars: usize) -> u32 {
+    chars
+        .div_ceil(CHARS_PER_TOKEN)
+        .try_into()
+        .unwrap_or(u32::MAX)
+}
+
+fn cost_of(rate: &entity::cost_pricing_config::Model, quantity: Decimal) -> Cost {
+    let range = rate.cost_for(quantity);
+    Cost {
+        low: range.low,
+        high: range.high,
+        avg: range.avg,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_for_rounds_partial_tokens_up() {
+        assert_eq!(tokens_for(0), 0);
+        assert_eq!(tokens_for(1), 1);
+        assert_eq!(tokens_for(8), 2);
+        assert_eq!(tokens_for(9), 3);
+    }
+
+    #[test]
+    fn operation_uses_snake_case_names() {
+        let operation: Operation = serde_json::from_str("\"transcription\"").unwrap();
+        assert_eq!(operation, Operation::Transcription);
+        assert_eq!(
+            serde_json::to_string(&Operation::Extraction).unwrap(),
+            "\"extraction\""
+        );
+    }
+}
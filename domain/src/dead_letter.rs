@@ -0,0 +1,192 @@
+//! Domain events that an event handler failed to process.
+//!
+//! [`DeadLetterStore`] is the application's [`DeadLetterSink`]: an event a handler fails
+//! on permanently, or on every retry, is stored with the handler's name, its last error
+//! and the attempts made. SuperAdmins inspect them with [`find`] and, once the cause is
+//! fixed, [`requeue`] them, which delivers the event again to that handler only. The
+//! number waiting per handler ([`depth`]) is exported at GET /metrics.
+
+use async_trait::async_trait;
+use entity_api::dead_letter_event;
+use events::{envelope, DeadLetter, DeadLetterSink, DomainEvent, EnvelopeError, EventPublisher};
+use log::*;
+use sea_orm::DatabaseConnection;
+use std::sync::Arc;
+
+use crate::dead_letter_events::Model;
+use crate::error::{DomainErrorKind, Error, InternalErrorKind};
+use crate::Id;
+
+/// Dead letters returned when the caller doesn't ask for a number.
+pub const DEFAULT_LIMIT: u64 = 100;
+
+/// Most dead letters returned at once.
+pub const MAX_LIMIT: u64 = 1_000;
+
+/// Dead letters of one handler and event type that haven't been requeued.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadLetterDepth {
+    pub handler_name: String,
+    pub event_type: String,
+    pub count: u64,
+}
+
+/// Stores each dead letter in the `dead_letter_events` table.
+pub struct DeadLetterStore {
+    db: Arc<DatabaseConnection>,
+}
+
+impl DeadLetterStore {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl DeadLetterSink for DeadLetterStore {
+    async fn record(&self, dead_letter: DeadLetter) {
+        let handler_name = dead_letter.handler_name.clone();
+        let event_type = dead_letter.event.kind();
+        let result = match record(dead_letter) {
+            Ok(record) => dead_letter_event::create(self.db.as_ref(), record)
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = result {
+            error!("Failed to dead-letter {event_type} event for {handler_name}: {e}");
+        }
+    }
+}
+
+/// Dead letters, newest first, optionally only `handler_name`'s and only those of
+/// `event_type`; requeued ones only if `include_requeued` is set.
+pub async fn find(
+    db: &DatabaseConnection,
+    handler_name: Option<&str>,
+    event_type: Option<&str>,
+    include_requeued: bool,
+    limit: Option<u64>,
+) -> Result<Vec<Model>, Error> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    if !(1..=MAX_LIMIT).contains(&limit) {
+        return Err(Error::validation(format!(
+            "`limit` must be between 1 and {MAX_LIMIT}"
+        )));
+    }
+
+    Ok(dead_letter_event::find(db, handler_name, event_type, include_requeued, limit).await?)
+}
+
+/// The number of dead letters waiting to be requeued, per handler and event type.
+pub async fn depth(db: &DatabaseConnection) -> Result<Vec<DeadLetterDepth>, Error> {
+    Ok(dead_letter_event::count_pending(db)
+        .await?
+        .into_iter()
+        .map(|(handler_name, event_type, count)| DeadLetterDepth {
+            handler_name,
+            event_type,
+            count: count.max(0) as u64,
+        })
+        .collect())
+}
+
+/// Delivers the dead-lettered event again to the handler that failed on it, retrying
+/// per the handler's policy. Returns the dead letter with the outcome recorded:
+/// `requeued_at` is set when the handler succeeded, `error` and `attempts` are updated
+/// when it failed again.
+pub async fn requeue(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    id: Id,
+) -> Result<Model, Error> {
+    let dead_letter = dead_letter_event::find_by_id(db, id).await?;
+    if dead_letter.requeued_at.is_some() {
+        return Err(Error::validation("The event has already been requeued"));
+    }
+
+    let event: DomainEvent = envelope::from_value(dead_letter.payload.clone()).map_err(|e| {
+        warn!("Dead letter {id} has an unreadable payload: {e}");
+        Error {
+            source: None,
+            error_kind: DomainErrorKind::Internal(InternalErrorKind::Other(
+                "The dead-lettered event could not be read".to_string(),
+            )),
+        }
+    })?;
+
+    let outcome = event_publisher
+        .redeliver(&dead_letter.handler_name, &event)
+        .await
+        .ok_or_else(|| {
+            Error::validation(format!(
+                "No event handler named `{}` is registered",
+                dead_letter.handler_name
+            ))
+        })?;
+
+    let (attempts, error) = match outcome {
+        Ok(()) => {
+            info!("Requeued dead letter {id} to {}", dead_letter.handler_name);
+            (0, None)
+        }
+        Err((error, attempts)) => {
+            warn!(
+                "Requeue of dead letter {id} to {} failed: {error}",
+                dead_letter.handler_name
+            );
+            (attempts as i32, Some(error.to_string()))
+        }
+    };
+    Ok(dead_letter_event::record_requeue(db, dead_letter, attempts, error).await?)
+}
+
+/// The row storing `dead_letter`; `id`, the requeue fields and the timestamps are
+/// assigned on insert.
+fn record(dead_letter: DeadLetter) -> Result<Model, EnvelopeError> {
+    Ok(Model {
+        id: Default::default(),
+        handler_name: dead_letter.handler_name,
+        event_type: dead_letter.event.kind().as_str().to_string(),
+        payload: envelope::to_value(&dead_letter.event)?,
+        error: dead_letter.error.to_string(),
+        attempts: dead_letter.attempts as i32,
+        requeue_count: 0,
+        requeued_at: None,
+        created_at: Default::default(),
+        updated_at: Default::default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use events::{EventKind, HandlerError};
+
+    #[test]
+    fn record_stores_the_event_the_handler_and_its_error() {
+        let event = DomainEvent::TopicsChanged {
+            coaching_session_id: Id::new_v4(),
+            notify_user_ids: Vec::new(),
+        };
+
+        let record = record(DeadLetter {
+            handler_name: "sse::SseDomainEventHandler".to_string(),
+            event,
+            error: HandlerError::Permanent("no such session".to_string()),
+            attempts: 1,
+        })
+        .unwrap();
+
+        assert_eq!(record.handler_name, "sse::SseDomainEventHandler");
+        assert_eq!(record.event_type, "topics_changed");
+        assert_eq!(record.payload["event_type"], "topics_changed");
+        assert_eq!(
+            record.payload["version"],
+            EventKind::TopicsChanged.version()
+        );
+        assert_eq!(record.error, "permanent: no such session");
+        assert_eq!(record.attempts, 1);
+    }
+}
@@ -5,11 +5,13 @@ use sea_orm::DatabaseConnection;
 use service::config::Config;
 
 use crate::{
-    actions, coaching_relationship, coaching_session, coaching_sessions,
+    actions, coaching_relationship, coaching_relationships, coaching_session, coaching_sessions,
     error::Error,
     error::{DomainErrorKind, InternalErrorKind},
     gateway::resend::{Client as ResendClient, SendEmailRequestBuilder},
-    goal, organization, organizations, user, users, Id,
+    goal, mentions, organization, organizations, progress_reports,
+    resource_type::ResourceType,
+    session_requests, user, users, Id,
 };
 
 /// Trait for email notifications that need common config prerequisites.
@@ -98,6 +100,45 @@ impl EmailNotification for ActionAssigned {
     }
 }
 
+struct Mentioned;
+impl EmailNotification for Mentioned {
+    fn template_id(config: &Config) -> Option<String> {
+        config.mention_email_template_id()
+    }
+    fn notification_name() -> &'static str {
+        "mentioned"
+    }
+    fn url_path_template(config: &Config) -> Option<String> {
+        Some(config.session_scheduled_email_url_path().to_owned())
+    }
+}
+
+struct ProgressReportEmail;
+impl EmailNotification for ProgressReportEmail {
+    fn template_id(config: &Config) -> Option<String> {
+        config.progress_report_email_template_id()
+    }
+    fn notification_name() -> &'static str {
+        "progress report"
+    }
+    fn url_path_template(config: &Config) -> Option<String> {
+        Some(config.progress_report_email_url_path().to_owned())
+    }
+}
+
+struct SessionRequested;
+impl EmailNotification for SessionRequested {
+    fn template_id(config: &Config) -> Option<String> {
+        config.session_request_email_template_id()
+    }
+    fn notification_name() -> &'static str {
+        "session requested"
+    }
+    fn url_path_template(config: &Config) -> Option<String> {
+        Some(config.session_request_email_url_path().to_owned())
+    }
+}
+
 struct WelcomeEmail;
 impl EmailNotification for WelcomeEmail {
     fn template_id(config: &Config) -> Option<String> {
@@ -124,6 +165,16 @@ impl EmailNotification for PasswordResetEmail {
     }
 }
 
+struct AccountLockEmail;
+impl EmailNotification for AccountLockEmail {
+    fn template_id(config: &Config) -> Option<String> {
+        config.account_lock_email_template_id()
+    }
+    fn notification_name() -> &'static str {
+        "account lock"
+    }
+}
+
 /// Create a magic link token and send a welcome email to a user.
 ///
 /// `inviter` is the user who triggered the invite (typically the coach or
@@ -272,6 +323,7 @@ fn format_session_date_time(date: NaiveDateTime, timezone: &str) -> (String, Str
 
 const TOKEN_PLACEHOLDER: &str = "{token}";
 const SESSION_ID_PLACEHOLDER: &str = "{session_id}";
+const RELATIONSHIP_ID_PLACEHOLDER: &str = "{relationship_id}";
 
 /// The `From:` address used for every transactional email sent through this module.
 /// Kept on the `mail.` subdomain so production DMARC/SPF/DKIM records for the
@@ -504,6 +556,301 @@ async fn send_action_assigned_email(
     Ok(())
 }
 
+/// Context for a mention email: what the author wrote and where.
+struct MentionEmailContext<'a> {
+    /// `"note"` or `"action"`, interpolated into the email copy.
+    resource_label: &'a str,
+    excerpt: &'a str,
+    session_id: Id,
+    organization: &'a organizations::Model,
+}
+
+/// Send mention notification emails to every newly mentioned user.
+async fn send_mention_email(
+    config: &Config,
+    recipients: &[users::Model],
+    author: &users::Model,
+    ctx: &MentionEmailContext<'_>,
+) -> Result<(), Error> {
+    info!(
+        "Initiating mention emails for {} recipient(s) (author: {})",
+        recipients.len(),
+        author.email
+    );
+
+    let email_config = ResolvedEmailConfig::new::<Mentioned>(config).await?;
+    let session_url = email_config.build_session_url(&ctx.session_id)?;
+
+    for recipient in recipients {
+        let email_request = SendEmailRequestBuilder::new()
+            .from(FROM_ADDRESS)
+            .to_with_name(
+                &recipient.email,
+                format!("{} {}", recipient.first_name, recipient.last_name),
+            )
+            .template_id(&email_config.template_id)
+            .add_variable("first_name", recipient.first_name.as_str())
+            .add_variable("author_first_name", author.first_name.as_str())
+            .add_variable("author_last_name", author.last_name.as_str())
+            .add_variable("resource_type", ctx.resource_label)
+            .add_variable("excerpt", ctx.excerpt)
+            .add_variable("organization_name", ctx.organization.name.as_str())
+            .add_variable("session_url", session_url.as_str())
+            .build()
+            .await;
+
+        match email_request {
+            Ok(request) => {
+                if let Err(e) = email_config.client.send_email(request).await {
+                    warn!(
+                        "Failed to send mention email for {}: {e:?}",
+                        recipient.email
+                    );
+                }
+            }
+            Err(e) => warn!(
+                "Failed to build mention email for {}: {e:?}",
+                recipient.email
+            ),
+        }
+    }
+
+    Ok(())
+}
+
+/// Send a coach their weekly progress report on one coaching relationship.
+///
+/// Unlike the `notify_*` functions this returns delivery errors, so the caller only
+/// marks the report emailed once the send succeeded.
+pub(crate) async fn send_progress_report_email(
+    config: &Config,
+    coach: &users::Model,
+    coachee: &users::Model,
+    organization: &organizations::Model,
+    report: &progress_reports::Model,
+) -> Result<(), Error> {
+    info!(
+        "Initiating progress report email for coach {} (relationship {})",
+        coach.id, report.coaching_relationship_id
+    );
+
+    let email_config = ResolvedEmailConfig::new::<ProgressReportEmail>(config).await?;
+
+    let progress_reports_url = email_config
+        .session_url_builder
+        .as_ref()
+        .map(|b| {
+            b.build(
+                RELATIONSHIP_ID_PLACEHOLDER,
+                &report.coaching_relationship_id.to_string(),
+            )
+        })
+        .unwrap_or_default();
+    let period_start = report.period_start.format("%B %-d, %Y").to_string();
+    let period_end = report.period_end.format("%B %-d, %Y").to_string();
+
+    let email_request = SendEmailRequestBuilder::new()
+        .from(FROM_ADDRESS)
+        .to_with_name(
+            &coach.email,
+            format!("{} {}", coach.first_name, coach.last_name),
+        )
+        .template_id(&email_config.template_id)
+        .add_variable("first_name", coach.first_name.as_str())
+        .add_variable("coachee_first_name", coachee.first_name.as_str())
+        .add_variable("coachee_last_name", coachee.last_name.as_str())
+        .add_variable("organization_name", organization.name.as_str())
+        .add_variable("period_start", period_start.as_str())
+        .add_variable("period_end", period_end.as_str())
+        .add_variable("narrative", report.narrative.as_str())
+        .add_variable("progress_reports_url", progress_reports_url.as_str())
+        .build()
+        .await?;
+
+    email_config.client.send_email(email_request).await
+}
+
+/// Tell a coach their coachee asked for a session, listing the windows that suit the
+/// coachee in the coach's timezone.
+async fn send_session_requested_email(
+    config: &Config,
+    coach: &users::Model,
+    coachee: &users::Model,
+    organization: &organizations::Model,
+    request: &session_requests::Model,
+) -> Result<(), Error> {
+    info!(
+        "Initiating session requested email for coach {} (relationship {})",
+        coach.id, request.coaching_relationship_id
+    );
+
+    let email_config = ResolvedEmailConfig::new::<SessionRequested>(config).await?;
+
+    let session_requests_url = email_config
+        .session_url_builder
+        .as_ref()
+        .map(|b| {
+            b.build(
+                RELATIONSHIP_ID_PLACEHOLDER,
+                &request.coaching_relationship_id.to_string(),
+            )
+        })
+        .unwrap_or_default();
+    let preferred_windows = request
+        .preferred_windows
+        .0
+        .iter()
+        .map(|window| {
+            let (date, starts) =
+                format_session_date_time(window.starts_at.naive_utc(), &coach.timezone);
+            let (_, ends) = format_session_date_time(window.ends_at.naive_utc(), &coach.timezone);
+            format!("{date}, {starts} - {ends}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let email_request = SendEmailRequestBuilder::new()
+        .from(FROM_ADDRESS)
+        .to_with_name(
+            &coach.email,
+            format!("{} {}", coach.first_name, coach.last_name),
+        )
+        .template_id(&email_config.template_id)
+        .add_variable("first_name", coach.first_name.as_str())
+        .add_variable("coachee_first_name", coachee.first_name.as_str())
+        .add_variable("coachee_last_name", coachee.last_name.as_str())
+        .add_variable("organization_name", organization.name.as_str())
+        .add_variable("preferred_windows", preferred_windows.as_str())
+        .add_variable("message", request.message.as_deref().unwrap_or(""))
+        .add_variable("session_requests_url", session_requests_url.as_str())
+        .build()
+        .await?;
+
+    email_config.client.send_email(email_request).await
+}
+
+/// Orchestrate sending the session requested email to the relationship's coach
+/// (best-effort).
+///
+/// Looks up both participants and the organization. Errors are logged internally —
+/// email delivery must never block or fail the calling operation.
+pub async fn notify_session_requested(
+    db: &DatabaseConnection,
+    config: &Config,
+    relationship: &coaching_relationships::Model,
+    request: &session_requests::Model,
+) {
+    let result: Result<(), Error> = async {
+        let coach = user::find_by_id(db, relationship.coach_id).await?;
+        let coachee = user::find_by_id(db, relationship.coachee_id).await?;
+        let org = organization::find_by_id(db, relationship.organization_id).await?;
+
+        send_session_requested_email(config, &coach, &coachee, &org, request).await
+    }
+    .await;
+
+    if let Err(e) = result {
+        warn!(
+            "Failed to send session requested email for request {}: {e:?}",
+            request.id
+        );
+    }
+}
+
+/// What happened to a user's account, for [`notify_account_lock`].
+pub struct AccountLockChange<'a> {
+    /// The locked or unlocked user.
+    pub user: &'a users::Model,
+    /// The admin who locked or unlocked them.
+    pub actor: &'a users::Model,
+    pub locked: bool,
+    pub reason: Option<&'a str>,
+}
+
+/// Tell each of the organization's admins that one of its users was locked or unlocked.
+async fn send_account_lock_email(
+    config: &Config,
+    recipients: &[users::Model],
+    change: &AccountLockChange<'_>,
+    organization: &organizations::Model,
+) -> Result<(), Error> {
+    info!(
+        "Initiating account lock emails for {} admin(s) (user {})",
+        recipients.len(),
+        change.user.id
+    );
+
+    let email_config = ResolvedEmailConfig::new::<AccountLockEmail>(config).await?;
+    let action = if change.locked { "locked" } else { "unlocked" };
+
+    for recipient in recipients {
+        let email_request = SendEmailRequestBuilder::new()
+            .from(FROM_ADDRESS)
+            .to_with_name(
+                &recipient.email,
+                format!("{} {}", recipient.first_name, recipient.last_name),
+            )
+            .template_id(&email_config.template_id)
+            .add_variable("first_name", recipient.first_name.as_str())
+            .add_variable("user_first_name", change.user.first_name.as_str())
+            .add_variable("user_last_name", change.user.last_name.as_str())
+            .add_variable("user_email", change.user.email.as_str())
+            .add_variable("actor_first_name", change.actor.first_name.as_str())
+            .add_variable("actor_last_name", change.actor.last_name.as_str())
+            .add_variable("action", action)
+            .add_variable("reason", change.reason.unwrap_or_default())
+            .add_variable("organization_name", organization.name.as_str())
+            .build()
+            .await;
+
+        match email_request {
+            Ok(request) => {
+                if let Err(e) = email_config.client.send_email(request).await {
+                    warn!(
+                        "Failed to send account lock email to admin {}: {e:?}",
+                        recipient.id
+                    );
+                }
+            }
+            Err(e) => warn!(
+                "Failed to build account lock email for admin {}: {e:?}",
+                recipient.id
+            ),
+        }
+    }
+
+    Ok(())
+}
+
+/// Orchestrate sending account lock emails (best-effort).
+///
+/// Looks up the organization and its admins, then tells each of them that `change.user`
+/// was locked or unlocked and by whom. Errors are logged internally — email delivery
+/// must never block or fail the calling operation.
+pub async fn notify_account_lock(
+    db: &DatabaseConnection,
+    config: &Config,
+    organization_id: Id,
+    change: &AccountLockChange<'_>,
+) {
+    let result: Result<(), Error> = async {
+        let org = organization::find_by_id(db, organization_id).await?;
+        let admins = entity_api::user::find_admins_of_organization(db, organization_id).await?;
+        if admins.is_empty() {
+            return Ok(());
+        }
+        send_account_lock_email(config, &admins, change, &org).await
+    }
+    .await;
+
+    if let Err(e) = result {
+        warn!(
+            "Failed to send account lock emails for user {}: {e:?}",
+            change.user.id
+        );
+    }
+}
+
 /// Orchestrate sending session-scheduled emails (best-effort).
 ///
 /// Looks up the coaching relationship, both users, and the organization,
@@ -730,6 +1077,55 @@ pub async fn notify_action_assigned(
     }
 }
 
+/// Orchestrate sending mention emails (best-effort).
+///
+/// `mentions` are the newly created mention rows for a single note or action;
+/// looks up the mentioned users and the organization, then emails each of them
+/// an excerpt of what `author` wrote. Errors are logged internally — email
+/// delivery must never block or fail the calling operation.
+pub async fn notify_mentioned(
+    db: &DatabaseConnection,
+    config: &Config,
+    author: &users::Model,
+    mentions: &[mentions::Model],
+    excerpt: &str,
+) {
+    let Some(first) = mentions.first() else {
+        return;
+    };
+
+    let result: Result<(), Error> = async {
+        let mentioned_ids: Vec<Id> = mentions.iter().map(|m| m.mentioned_user_id).collect();
+        let recipients = user::find_by_ids(db, &mentioned_ids).await?;
+
+        let (_, relationship) =
+            coaching_session::find_by_id_with_coaching_relationship(db, first.coaching_session_id)
+                .await?;
+        let org = organization::find_by_id(db, relationship.organization_id).await?;
+
+        let ctx = MentionEmailContext {
+            resource_label: match first.resource_type {
+                ResourceType::Action => "action",
+                ResourceType::Note => "note",
+                ResourceType::CoachingSession => "coaching session",
+            },
+            excerpt,
+            session_id: first.coaching_session_id,
+            organization: &org,
+        };
+
+        send_mention_email(config, &recipients, author, &ctx).await
+    }
+    .await;
+
+    if let Err(e) = result {
+        warn!(
+            "Failed to send mention emails for {:?} {}: {e:?}",
+            first.resource_type, first.resource_id
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -814,6 +1210,8 @@ mod tests {
             created_at: chrono::Utc::now().fixed_offset(),
             updated_at: chrono::Utc::now().fixed_offset(),
             hydrated_at: Some(chrono::Utc::now().fixed_offset()),
+            session_type_id: None,
+            deleted_at: None,
         }
     }
 
@@ -848,6 +1246,9 @@ mod tests {
             "--session-scheduled-email-template-id=session_template_456",
             "--recurring-sessions-scheduled-email-template-id=recurring_template_xyz",
             "--action-assigned-email-template-id=action_template_789",
+            "--mention-email-template-id=mention_template_321",
+            "--progress-report-email-template-id=progress_template_654",
+            "--account-lock-email-template-id=account_lock_template_987",
             "--frontend-base-url=https://app.example.com",
             &format!("--resend-base-url={server_url}"),
         ])
@@ -1504,6 +1905,8 @@ mod tests {
             created_at: chrono::Utc::now().fixed_offset(),
             updated_at: chrono::Utc::now().fixed_offset(),
             hydrated_at: None,
+            session_type_id: None,
+            deleted_at: None,
         }
     }
 
@@ -1684,6 +2087,159 @@ mod tests {
         }
     }
 
+    // ── Mention Email Tests ────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_send_mention_email_success() {
+        let mut server = setup_test_server().await;
+        let config = create_full_config_with_mock(&server.url());
+
+        let author = create_test_user_with("Alex", "Smith", "alex@example.com", "UTC");
+        let recipient = create_test_user_with("Jane", "Doe", "jane@example.com", "UTC");
+        let session_id = Id::new_v4();
+        let org = create_test_organization();
+
+        let _mock = server
+            .mock("POST", "/emails")
+            .match_body(expect_resend_body(serde_json::json!({
+                "from": FROM_ADDRESS,
+                "to": ["\"Jane Doe\" <jane@example.com>"],
+                "template": {
+                    "id": "mention_template_321",
+                    "variables": {
+                        "first_name": "Jane",
+                        "author_first_name": "Alex",
+                        "author_last_name": "Smith",
+                        "resource_type": "note",
+                        "excerpt": "@Jane Doe can you take a look?",
+                        "organization_name": "Acme Corp",
+                        "session_url": format!("https://app.example.com/coaching-sessions/{session_id}"),
+                    }
+                }
+            })))
+            .with_status(200)
+            .with_body(r#"{"id":"email_test"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let ctx = MentionEmailContext {
+            resource_label: "note",
+            excerpt: "@Jane Doe can you take a look?",
+            session_id,
+            organization: &org,
+        };
+
+        let result = send_mention_email(&config, &[recipient], &author, &ctx).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_send_account_lock_email_success() {
+        let mut server = setup_test_server().await;
+        let config = create_full_config_with_mock(&server.url());
+
+        let admin = create_test_user_with("Alex", "Smith", "alex@example.com", "UTC");
+        let user = create_test_user_with("Jane", "Doe", "jane@example.com", "UTC");
+        let org = create_test_organization();
+
+        let _mock = server
+            .mock("POST", "/emails")
+            .match_body(expect_resend_body(serde_json::json!({
+                "from": FROM_ADDRESS,
+                "to": ["\"Alex Smith\" <alex@example.com>"],
+                "template": {
+                    "id": "account_lock_template_987",
+                    "variables": {
+                        "first_name": "Alex",
+                        "user_first_name": "Jane",
+                        "user_last_name": "Doe",
+                        "user_email": "jane@example.com",
+                        "actor_first_name": "Alex",
+                        "actor_last_name": "Smith",
+                        "action": "locked",
+                        "reason": "Left the company",
+                        "organization_name": "Acme Corp",
+                    }
+                }
+            })))
+            .with_status(200)
+            .with_body(r#"{"id":"email_test"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let change = AccountLockChange {
+            user: &user,
+            actor: &admin,
+            locked: true,
+            reason: Some("Left the company"),
+        };
+
+        let result =
+            send_account_lock_email(&config, std::slice::from_ref(&admin), &change, &org).await;
+        assert!(result.is_ok());
+    }
+
+    // ── Progress Report Email Tests ─────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_send_progress_report_email_success() {
+        let mut server = setup_test_server().await;
+        let config = create_full_config_with_mock(&server.url());
+
+        let coach = create_test_user_with("Alex", "Smith", "alex@example.com", "UTC");
+        let coachee = create_test_user_with("Jane", "Doe", "jane@example.com", "UTC");
+        let org = create_test_organization();
+        let relationship_id = Id::new_v4();
+        let now = chrono::Utc::now().fixed_offset();
+        let report = progress_reports::Model {
+            id: Id::new_v4(),
+            coaching_relationship_id: relationship_id,
+            period_start: NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2026, 3, 8).unwrap(),
+            narrative: "Jane delegated the weekly report for the first time.".to_string(),
+            session_count: 1,
+            open_action_count: 2,
+            model: "gpt-4o-mini".to_string(),
+            prompt_version: Some(0),
+            generated_at: now,
+            emailed_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+
+        let _mock = server
+            .mock("POST", "/emails")
+            .match_body(expect_resend_body(serde_json::json!({
+                "from": FROM_ADDRESS,
+                "to": ["\"Alex Smith\" <alex@example.com>"],
+                "template": {
+                    "id": "progress_template_654",
+                    "variables": {
+                        "first_name": "Alex",
+                        "coachee_first_name": "Jane",
+                        "coachee_last_name": "Doe",
+                        "organization_name": "Acme Corp",
+                        "period_start": "March 2, 2026",
+                        "period_end": "March 8, 2026",
+                        "narrative": "Jane delegated the weekly report for the first time.",
+                        "progress_reports_url": format!(
+                            "https://app.example.com/coaching-relationships/{relationship_id}/progress-reports"
+                        ),
+                    }
+                }
+            })))
+            .with_status(200)
+            .with_body(r#"{"id":"email_test"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let result = send_progress_report_email(&config, &coach, &coachee, &org, &report).await;
+        assert!(result.is_ok());
+    }
+
     // ── format_session_date_time Unit Tests ────────────────────────────
 
     #[test]
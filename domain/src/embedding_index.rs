@@ -0,0 +1,200 @@
+//! Background indexing of notes and transcript segments for semantic retrieval.
+//!
+//! A periodic job (see `web::init_server`) calls [`index_pending`], which embeds every
+//! note created or edited since it was last embedded and every new transcript segment,
+//! so content becomes searchable shortly after it's written. Sweeping rather than
+//! embedding inline keeps note and transcript writes independent of the embedding
+//! provider, and anything missed while the provider was down is picked up on the next
+//! pass. Relationships' AI privacy levels decide what may be embedded.
+
+use entity_api::embedding::{self, NewEmbedding, Pending};
+use log::*;
+use meeting_ai::traits::embedding::Provider;
+use meeting_ai::types::embedding::Request;
+use sea_orm::DatabaseConnection;
+use std::collections::BTreeMap;
+
+use crate::embedding_source_type::EmbeddingSourceType;
+use crate::error::{DomainErrorKind, Error, InternalErrorKind};
+use crate::retrieval::plain_text;
+use crate::{cost, Id};
+
+/// Source records embedded per query and embedding request.
+const BATCH_SIZE: u64 = 64;
+
+/// Batches per source kind in one pass, bounding the work of a single tick.
+const MAX_BATCHES_PER_PASS: usize = 20;
+
+/// Longest text embedded, in characters (roughly 2k tokens, well within model limits).
+pub(crate) const MAX_INPUT_CHARS: usize = 8_000;
+
+/// Embeds pending notes and transcript segments and removes embeddings that are no
+/// longer allowed or whose source is gone. Returns how many records were embedded.
+///
+/// A no-op when the embedding index isn't available (no pgvector).
+pub async fn index_pending(
+    db: &DatabaseConnection,
+    provider: &dyn Provider,
+) -> Result<usize, Error> {
+    if !embedding::is_available(db).await? {
+        return Ok(0);
+    }
+
+    let removed = embedding::delete_stale(db).await?;
+    if removed > 0 {
+        debug!("embedding index: removed {removed} stale embedding(s)");
+    }
+
+    let model = provider.model().to_string();
+    let mut indexed = 0;
+    for source_type in [
+        EmbeddingSourceType::Note,
+        EmbeddingSourceType::TranscriptSegment,
+    ] {
+        for _ in 0..MAX_BATCHES_PER_PASS {
+            let pending = match source_type {
+                EmbeddingSourceType::Note => {
+                    embedding::find_pending_notes(db, &model, BATCH_SIZE).await?
+                }
+                EmbeddingSourceType::TranscriptSegment => {
+                    embedding::find_pending_transcript_segments(db, &model, BATCH_SIZE).await?
+                }
+            };
+            let fetched = pending.len();
+
+            for (coaching_session_id, batch) in group_by_session(pending) {
+                indexed += embed_batch(
+                    db,
+                    provider,
+                    &model,
+                    source_type,
+                    coaching_session_id,
+                    batch,
+                )
+                .await?;
+            }
+
+            if (fetched as u64) < BATCH_SIZE {
+                break;
+            }
+        }
+    }
+
+    Ok(indexed)
+}
+
+/// Embeds one session's pending records of a kind, recording the cost against the session.
+async fn embed_batch(
+    db: &DatabaseConnection,
+    provider: &dyn Provider,
+    model: &str,
+    source_type: EmbeddingSourceType,
+    coaching_session_id: Id,
+    batch: Vec<Pending>,
+) -> Result<usize, Error> {
+    let inputs: Vec<String> = batch
+        .iter()
+        .map(|pending| plain_text(&pending.content, MAX_INPUT_CHARS))
+        .collect();
+
+    let embedded = provider
+        .embed(Request {
+            model: Some(model.to_string()),
+            inputs: inputs.clone(),
+            dimensions: Some(embedding::DIMENSIONS as u32),
+        })
+        .await?;
+
+    if let Err(e) = cost::record_embedding_tokens(
+        db,
+        Some(coaching_session_id),
+        coaching_session_id,
+        embedded.input_tokens,
+    )
+    .await
+    {
+        warn!("embedding index: failed to record embedding cost: {e:?}");
+    }
+
+    if let Some(vector) = embedded
+        .vectors
+        .iter()
+        .find(|vector| vector.len() != embedding::DIMENSIONS)
+    {
+        warn!(
+            "embedding index: {} returned {}-dimension vectors, expected {}",
+            provider.provider_id(),
+            vector.len(),
+            embedding::DIMENSIONS
+        );
+        return Err(Error {
+            source: None,
+            error_kind: DomainErrorKind::Internal(InternalErrorKind::Config),
+        });
+    }
+
+    let rows: Vec<NewEmbedding> = batch
+        .into_iter()
+        .zip(inputs)
+        .zip(embedded.vectors)
+        .map(|((pending, content), vector)| NewEmbedding {
+            source_type,
+            source_id: pending.source_id,
+            coaching_relationship_id: pending.coaching_relationship_id,
+            coaching_session_id: pending.coaching_session_id,
+            content,
+            model: model.to_string(),
+            vector,
+        })
+        .collect();
+    embedding::upsert(db, &rows).await?;
+
+    Ok(rows.len())
+}
+
+/// Splits pending records by coaching session, keeping their order within each session.
+fn group_by_session(pending: Vec<Pending>) -> BTreeMap<Id, Vec<Pending>> {
+    let mut groups: BTreeMap<Id, Vec<Pending>> = BTreeMap::new();
+    for record in pending {
+        groups
+            .entry(record.coaching_session_id)
+            .or_default()
+            .push(record);
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending(coaching_session_id: Id, content: &str) -> Pending {
+        Pending {
+            source_id: Id::new_v4(),
+            coaching_relationship_id: Id::new_v4(),
+            coaching_session_id,
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn group_by_session_keeps_order_within_each_session() {
+        let (first, second) = (Id::new_v4(), Id::new_v4());
+        let groups = group_by_session(vec![
+            pending(first, "Coach: How did the week go?"),
+            pending(second, "Delegation plan"),
+            pending(first, "Coachee: Better than expected."),
+        ]);
+
+        assert_eq!(groups.len(), 2);
+        let contents: Vec<_> = groups[&first].iter().map(|p| p.content.as_str()).collect();
+        assert_eq!(
+            contents,
+            vec![
+                "Coach: How did the week go?",
+                "Coachee: Better than expected."
+            ]
+        );
+        assert_eq!(groups[&second].len(), 1);
+    }
+}
@@ -0,0 +1,256 @@
+//! Field-level encryption of note and journal entry bodies, for organizations that treat
+//! coaching notes as highly sensitive.
+//!
+//! Each organization that opts in gets its own AES-256-GCM data key, stored encrypted
+//! with the platform's `ENCRYPTION_KEY`. `entity_api` encrypts and decrypts bodies
+//! transparently once [`install`] has run (see [`entity_api::field_encryption`]), so
+//! every process that reads or writes notes must call it at startup. Enabling, rotating
+//! and disabling only change which key new bodies are sealed with; run
+//! [`reseal_organization`] afterwards to bring existing rows in line. The
+//! `field_encryption` binary does both.
+//!
+//! Encrypted notes stay keyword-searchable through a blind index unless
+//! `ENCRYPTED_NOTE_SEARCH_ENABLED` is off. Its tokens are HMAC-SHA256s of a word and the
+//! relationship, keyed by a key derived from the organization's data key, so the same
+//! word has unrelated tokens in different relationships.
+
+use crate::error::{DomainErrorKind, EntityErrorKind, Error, InternalErrorKind};
+use crate::organization_data_keys::Model as DataKey;
+use crate::Id;
+use entity_api::field_encryption::{self, FieldCipher};
+use entity_api::organization_data_key;
+use hkdf::Hkdf;
+use hmac::{Hmac, Mac};
+use log::*;
+use meeting_auth::oauth::token::encryption;
+use rand::RngCore;
+use sea_orm::{DatabaseConnection, TransactionTrait};
+use secrecy::{ExposeSecret, SecretString};
+use service::config::Config;
+use sha2::Sha256;
+
+pub use entity_api::field_encryption::{reseal_organization, Resealed};
+pub use entity_api::organization_data_key::{find_active, find_by_organization};
+
+/// Seals data keys with `ENCRYPTION_KEY` and bodies with the data keys.
+struct DataKeyCipher {
+    master_key: SecretString,
+}
+
+impl DataKeyCipher {
+    fn new(master_key: SecretString) -> Result<Self, Error> {
+        // Fails fast on a key that isn't 64 hex characters.
+        encryption::encrypt("", master_key.expose_secret()).map_err(|_| config_error())?;
+        Ok(Self { master_key })
+    }
+
+    fn wrap(&self, data_key: &str) -> Result<String, String> {
+        encryption::encrypt(data_key, self.master_key.expose_secret()).map_err(|e| e.to_string())
+    }
+
+    fn unwrap(&self, wrapped_key: &str) -> Result<SecretString, String> {
+        encryption::decrypt(wrapped_key, self.master_key.expose_secret())
+            .map(SecretString::from)
+            .map_err(|e| e.to_string())
+    }
+}
+
+impl FieldCipher for DataKeyCipher {
+    fn encrypt(&self, wrapped_key: &str, plaintext: &str) -> Result<String, String> {
+        let data_key = self.unwrap(wrapped_key)?;
+        encryption::encrypt(plaintext, data_key.expose_secret()).map_err(|e| e.to_string())
+    }
+
+    fn decrypt(&self, wrapped_key: &str, ciphertext: &str) -> Result<String, String> {
+        let data_key = self.unwrap(wrapped_key)?;
+        encryption::decrypt(ciphertext, data_key.expose_secret()).map_err(|e| e.to_string())
+    }
+
+    fn blind_index(&self, wrapped_key: &str, scope: Id, term: &str) -> Result<String, String> {
+        let data_key = self.unwrap(wrapped_key)?;
+        let data_key = hex::decode(data_key.expose_secret()).map_err(|e| e.to_string())?;
+        // A key of its own, so tokens reveal nothing about the encryption key
+        let mut index_key = [0u8; 32];
+        Hkdf::<Sha256>::new(None, &data_key)
+            .expand(b"note-blind-index", &mut index_key)
+            .map_err(|e| e.to_string())?;
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(&index_key).map_err(|e| e.to_string())?;
+        mac.update(scope.as_bytes());
+        mac.update(term.as_bytes());
+        // 128 bits is plenty to tell a relationship's words apart
+        Ok(hex::encode(&mac.finalize().into_bytes()[..16]))
+    }
+}
+
+/// Lets this process read and write encrypted bodies. Returns whether it can: without
+/// `ENCRYPTION_KEY` bodies are written in plaintext and encrypted ones can't be read.
+pub fn install(config: &Config) -> Result<bool, Error> {
+    let Some(master_key) = config.encryption_key() else {
+        return Ok(false);
+    };
+    field_encryption::install(
+        Box::new(DataKeyCipher::new(SecretString::from(master_key))?),
+        config.encrypted_note_search_enabled(),
+    );
+    Ok(true)
+}
+
+/// Starts encrypting the organization's new bodies with a fresh data key.
+pub async fn enable(
+    db: &DatabaseConnection,
+    config: &Config,
+    organization_id: Id,
+) -> Result<DataKey, Error> {
+    let cipher = cipher(config)?;
+    if find_active(db, organization_id).await?.is_some() {
+        return Err(Error::entity(EntityErrorKind::Conflict {
+            message: "The organization already encrypts its notes".to_string(),
+            details: None,
+        }));
+    }
+
+    let key = organization_data_key::create(db, organization_id, new_data_key(&cipher)?).await?;
+    info!(
+        "Field encryption enabled for organization {organization_id} (key {})",
+        key.id
+    );
+    Ok(key)
+}
+
+/// Retires the organization's data key in favor of a fresh one.
+pub async fn rotate(
+    db: &DatabaseConnection,
+    config: &Config,
+    organization_id: Id,
+) -> Result<DataKey, Error> {
+    let cipher = cipher(config)?;
+    let Some(retired) = find_active(db, organization_id).await? else {
+        return Err(Error::entity(EntityErrorKind::NotFound));
+    };
+    let wrapped_key = new_data_key(&cipher)?;
+
+    let txn = db.begin().await.map_err(transaction_error)?;
+    organization_data_key::retire_active(&txn, organization_id).await?;
+    let key = organization_data_key::create(&txn, organization_id, wrapped_key).await?;
+    txn.commit().await.map_err(transaction_error)?;
+
+    info!(
+        "Field encryption key of organization {organization_id} rotated from {} to {}",
+        retired.id, key.id
+    );
+    Ok(key)
+}
+
+/// Stops encrypting the organization's new bodies. Its keys are kept, so existing
+/// encrypted bodies stay readable until they're resealed.
+pub async fn disable(db: &DatabaseConnection, organization_id: Id) -> Result<(), Error> {
+    if organization_data_key::retire_active(db, organization_id).await? == 0 {
+        return Err(Error::entity(EntityErrorKind::NotFound));
+    }
+    info!("Field encryption disabled for organization {organization_id}");
+    Ok(())
+}
+
+/// A random data key, wrapped.
+fn new_data_key(cipher: &DataKeyCipher) -> Result<String, Error> {
+    let mut data_key = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut data_key);
+    cipher
+        .wrap(&hex::encode(data_key))
+        .map_err(|_| config_error())
+}
+
+fn cipher(config: &Config) -> Result<DataKeyCipher, Error> {
+    let master_key = config.encryption_key().ok_or_else(config_error)?;
+    DataKeyCipher::new(SecretString::from(master_key))
+}
+
+fn config_error() -> Error {
+    Error {
+        source: None,
+        error_kind: DomainErrorKind::Internal(InternalErrorKind::Config),
+    }
+}
+
+fn transaction_error(e: sea_orm::DbErr) -> Error {
+    Error {
+        source: Some(Box::new(e)),
+        error_kind: DomainErrorKind::Internal(InternalErrorKind::Entity(
+            EntityErrorKind::DbTransaction,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MASTER_KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    fn cipher() -> DataKeyCipher {
+        DataKeyCipher::new(SecretString::from(MASTER_KEY.to_string())).unwrap()
+    }
+
+    #[test]
+    fn bodies_round_trip_through_a_wrapped_data_key() {
+        let cipher = cipher();
+        let wrapped_key = new_data_key(&cipher).unwrap();
+
+        let ciphertext = cipher.encrypt(&wrapped_key, "<p>Private</p>").unwrap();
+
+        assert!(!ciphertext.contains("Private"));
+        assert_eq!(
+            cipher.decrypt(&wrapped_key, &ciphertext).unwrap(),
+            "<p>Private</p>"
+        );
+    }
+
+    #[test]
+    fn bodies_dont_decrypt_with_another_data_key() {
+        let cipher = cipher();
+        let ciphertext = cipher
+            .encrypt(&new_data_key(&cipher).unwrap(), "secret")
+            .unwrap();
+
+        assert!(cipher
+            .decrypt(&new_data_key(&cipher).unwrap(), &ciphertext)
+            .is_err());
+    }
+
+    #[test]
+    fn blind_index_tokens_are_stable_per_key_and_relationship() {
+        let cipher = cipher();
+        let wrapped_key = new_data_key(&cipher).unwrap();
+        let (relationship, other_relationship) = (Id::new_v4(), Id::new_v4());
+
+        let token = cipher
+            .blind_index(&wrapped_key, relationship, "delegation")
+            .unwrap();
+
+        assert_eq!(token.len(), 32);
+        assert_eq!(
+            cipher
+                .blind_index(&wrapped_key, relationship, "delegation")
+                .unwrap(),
+            token
+        );
+        assert_ne!(
+            cipher
+                .blind_index(&wrapped_key, other_relationship, "delegation")
+                .unwrap(),
+            token
+        );
+        assert_ne!(
+            cipher
+                .blind_index(&new_data_key(&cipher).unwrap(), relationship, "delegation")
+                .unwrap(),
+            token
+        );
+    }
+
+    #[test]
+    fn malformed_master_keys_are_rejected() {
+        assert!(DataKeyCipher::new(SecretString::from("not-hex".to_string())).is_err());
+    }
+}
@@ -28,6 +28,9 @@ pub enum DomainErrorKind {
     Internal(InternalErrorKind),
     External(ExternalErrorKind),
     Validation(String),
+    /// A new password failed these rules of the password policy
+    /// (see `domain::password_policy`).
+    PasswordPolicy(Vec<crate::password_policy::PasswordRuleFailure>),
 }
 /// Enum representing the various kinds of internal errors that can occur in the `domain` layer.
 #[derive(Debug, PartialEq)]
@@ -68,6 +71,10 @@ pub enum EntityErrorKind {
     PasswordResetRateLimited,
     DbTransaction,
     ServiceUnavailable,
+    // A database statement ran past its `statement_timeout` and was cancelled
+    StatementTimeout,
+    // The record changed since the version the write expected to replace
+    StaleVersion,
     Other(String),
 }
 
@@ -79,6 +86,24 @@ pub enum ExternalErrorKind {
     Other(String),
 }
 
+impl Error {
+    /// An entity error of `kind`, without a source.
+    pub(crate) fn entity(kind: EntityErrorKind) -> Self {
+        Self {
+            source: None,
+            error_kind: DomainErrorKind::Internal(InternalErrorKind::Entity(kind)),
+        }
+    }
+
+    /// A validation error, answered as unprocessable, saying what was wrong.
+    pub(crate) fn validation(message: impl Into<String>) -> Self {
+        Self {
+            source: None,
+            error_kind: DomainErrorKind::Validation(message.into()),
+        }
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{:?}", self.error_kind)?;
@@ -164,6 +189,8 @@ impl From<EntityApiError> for Error {
             }
             EntityApiErrorKind::OrganizationArchived => EntityErrorKind::OrganizationArchived,
             EntityApiErrorKind::SystemError => EntityErrorKind::ServiceUnavailable,
+            EntityApiErrorKind::StatementTimeout => EntityErrorKind::StatementTimeout,
+            EntityApiErrorKind::StaleVersion => EntityErrorKind::StaleVersion,
             _ => EntityErrorKind::Other("EntityErrorKind".to_string()),
         };
 
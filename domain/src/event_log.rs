@@ -0,0 +1,305 @@
+//! Persistent store of domain events, behind the audit log and activity feeds.
+//!
+//! [`AuditLogHandler`] is registered on the application's [`EventPublisher`] and stores
+//! every published event, with the user whose request emitted it (see
+//! [`events::with_actor`]) and the coaching relationship it happened in. SuperAdmins read
+//! the whole store with [`find`]; a relationship's participants read its events with
+//! [`activity`]. Both page newest first with an opaque cursor.
+//!
+//! The store is partitioned by month. [`maintain`] creates the coming months' partitions,
+//! compacts old updates and drops months past retention.
+//!
+//! [`EventPublisher`]: events::EventPublisher
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Datelike, Months, NaiveDate, Utc};
+use entity_api::{coaching_session, error::EntityApiErrorKind, stored_event};
+use events::{DomainEvent, EventHandler, HandlerError};
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::DatabaseConnection;
+use std::sync::Arc;
+
+use crate::error::Error;
+use crate::event_store::Model;
+use crate::Id;
+
+pub use entity_api::stored_event::EventPage;
+
+/// Events returned when the caller doesn't ask for a number.
+pub const DEFAULT_LIMIT: u64 = 100;
+
+/// Most events returned at once.
+pub const MAX_LIMIT: u64 = 1_000;
+
+/// Months ahead of the current one whose partitions [`maintain`] creates.
+const PARTITION_MONTHS_AHEAD: u32 = 2;
+
+/// Stores each published event in the `event_store` table.
+pub struct AuditLogHandler {
+    db: Arc<DatabaseConnection>,
+}
+
+impl AuditLogHandler {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl EventHandler for AuditLogHandler {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), HandlerError> {
+        let mut record = record(event).map_err(|e| HandlerError::Permanent(e.to_string()))?;
+        record.coaching_relationship_id = coaching_relationship_id(self.db.as_ref(), event)
+            .await
+            .map_err(|e| HandlerError::Transient(e.to_string()))?;
+        stored_event::create(self.db.as_ref(), record)
+            .await
+            .map_err(|e| HandlerError::Transient(e.to_string()))?;
+        Ok(())
+    }
+}
+
+/// The relationship `event` happened in. Session-scoped events carry only their session,
+/// so its relationship is looked up; `None` once the session is gone.
+pub(crate) async fn coaching_relationship_id(
+    db: &DatabaseConnection,
+    event: &DomainEvent,
+) -> Result<Option<Id>, entity_api::error::Error> {
+    if let Some(id) = event.coaching_relationship_id() {
+        return Ok(Some(id));
+    }
+    let Some(session_id) = event.coaching_session_id() else {
+        return Ok(None);
+    };
+    match coaching_session::find_by_id(db, session_id).await {
+        Ok(session) => Ok(Some(session.coaching_relationship_id)),
+        Err(e) if e.error_kind == EntityApiErrorKind::RecordNotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// A page of stored events, newest first, optionally only those about `entity_type` and
+/// created within `[from, to)`. `cursor` is a previous page's `next_cursor`.
+pub async fn find(
+    db: &DatabaseConnection,
+    entity_type: Option<&str>,
+    from: Option<DateTimeWithTimeZone>,
+    to: Option<DateTimeWithTimeZone>,
+    cursor: Option<&str>,
+    limit: Option<u64>,
+) -> Result<EventPage, Error> {
+    if let (Some(from), Some(to)) = (from, to) {
+        if from >= to {
+            return Err(Error::validation("`from` must be before `to`"));
+        }
+    }
+    let limit = checked_limit(limit)?;
+    let before = cursor.map(decode_cursor).transpose()?;
+
+    // One extra event tells whether there's another page.
+    let events = stored_event::find(db, entity_type, from, to, before, limit + 1).await?;
+    Ok(into_page(events, limit))
+}
+
+/// A page of the events that happened in the coaching relationship, newest first;
+/// `cursor` as for [`find`].
+pub async fn activity(
+    db: &DatabaseConnection,
+    coaching_relationship_id: Id,
+    cursor: Option<&str>,
+    limit: Option<u64>,
+) -> Result<EventPage, Error> {
+    let limit = checked_limit(limit)?;
+    let before = cursor.map(decode_cursor).transpose()?;
+
+    let events = stored_event::find_by_coaching_relationship(
+        db,
+        coaching_relationship_id,
+        before,
+        limit + 1,
+    )
+    .await?;
+    Ok(into_page(events, limit))
+}
+
+/// Creates the partitions for this month and the next two, then, when set, deletes
+/// superseded updates older than `compact_after_days` and drops the partitions of months
+/// that ended more than `retention_months` ago. Compaction and dropping pause while a
+/// legal hold is active. Returns how many events were compacted.
+pub async fn maintain(
+    db: &DatabaseConnection,
+    retention_months: Option<u32>,
+    compact_after_days: Option<u32>,
+) -> Result<u64, Error> {
+    let this_month = first_of_month(Utc::now().date_naive());
+    for ahead in 0..=PARTITION_MONTHS_AHEAD {
+        stored_event::ensure_partition(db, this_month + Months::new(ahead)).await?;
+    }
+
+    if crate::legal_hold::purges_paused(db).await? {
+        log::info!("[event-store] a legal hold is active; skipping compaction and retention");
+        return Ok(0);
+    }
+
+    let mut compacted = 0;
+    if let Some(days) = compact_after_days {
+        let cutoff = Utc::now() - chrono::Duration::days(days.into());
+        compacted = stored_event::compact_updates_before(db, cutoff.into()).await?;
+    }
+
+    if let Some(months) = retention_months {
+        let partitions = stored_event::partitions(db).await?;
+        for name in expired_partitions(&partitions, this_month - Months::new(months)) {
+            stored_event::drop_partition(db, name).await?;
+        }
+    }
+
+    Ok(compacted)
+}
+
+/// The monthly partitions among `partitions` holding only events from before `cutoff`,
+/// the first of a month. The default partition is never expired.
+fn expired_partitions(partitions: &[String], cutoff: NaiveDate) -> Vec<&str> {
+    partitions
+        .iter()
+        .filter(|name| {
+            name.strip_prefix("event_store_")
+                .and_then(|month| {
+                    NaiveDate::parse_from_str(&format!("{month}_01"), "%Y_%m_%d").ok()
+                })
+                .is_some_and(|month| month + Months::new(1) <= cutoff)
+        })
+        .map(String::as_str)
+        .collect()
+}
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).expect("every month has a first day")
+}
+
+fn checked_limit(limit: Option<u64>) -> Result<u64, Error> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    if !(1..=MAX_LIMIT).contains(&limit) {
+        return Err(Error::validation(format!(
+            "`limit` must be between 1 and {MAX_LIMIT}"
+        )));
+    }
+    Ok(limit)
+}
+
+/// Trims `events`, fetched one past `limit`, to a page pointing at the rest.
+fn into_page(mut events: Vec<Model>, limit: u64) -> EventPage {
+    let next_cursor = if events.len() as u64 > limit {
+        events.truncate(limit as usize);
+        events.last().map(encode_cursor)
+    } else {
+        None
+    };
+    EventPage {
+        events,
+        next_cursor,
+    }
+}
+
+/// The cursor continuing after `event`: its position in the newest-first order.
+fn encode_cursor(event: &Model) -> String {
+    URL_SAFE_NO_PAD.encode(format!("{}|{}", event.created_at.to_rfc3339(), event.id))
+}
+
+fn decode_cursor(cursor: &str) -> Result<(DateTimeWithTimeZone, Id), Error> {
+    let invalid = || Error::validation("`cursor` is not a cursor this endpoint returned");
+    let decoded = URL_SAFE_NO_PAD.decode(cursor).map_err(|_| invalid())?;
+    let decoded = String::from_utf8(decoded).map_err(|_| invalid())?;
+    let (created_at, id) = decoded.split_once('|').ok_or_else(invalid)?;
+    Ok((
+        DateTime::parse_from_rfc3339(created_at).map_err(|_| invalid())?,
+        id.parse().map_err(|_| invalid())?,
+    ))
+}
+
+/// The row storing `event`; `id` and `created_at` are assigned on insert and
+/// `coaching_relationship_id` is taken from the event alone.
+fn record(event: &DomainEvent) -> Result<Model, serde_json::Error> {
+    let (entity_type, entity_id) = event.entity();
+    Ok(Model {
+        id: Default::default(),
+        event_type: event.kind().as_str().to_string(),
+        entity_type: entity_type.to_string(),
+        entity_id,
+        coaching_relationship_id: event.coaching_relationship_id(),
+        coaching_session_id: event.coaching_session_id(),
+        actor_user_id: events::current_actor(),
+        payload: serde_json::to_value(event)?,
+        created_at: Default::default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::DomainErrorKind;
+
+    #[tokio::test]
+    async fn record_attributes_the_event_to_the_current_actor() {
+        let actor_id = Id::new_v4();
+        let action_id = Id::new_v4();
+        let coaching_session_id = Id::new_v4();
+        let event = DomainEvent::ActionDeleted {
+            coaching_session_id,
+            action_id,
+            notify_user_ids: vec![actor_id],
+        };
+
+        let record = events::with_actor(actor_id, async { record(&event) })
+            .await
+            .unwrap();
+
+        assert_eq!(record.event_type, "action_deleted");
+        assert_eq!(record.entity_type, "action");
+        assert_eq!(record.entity_id, Some(action_id));
+        assert_eq!(record.coaching_session_id, Some(coaching_session_id));
+        assert_eq!(record.actor_user_id, Some(actor_id));
+        assert_eq!(record.payload["type"], "action_deleted");
+    }
+
+    #[test]
+    fn cursor_round_trips_the_position_of_the_last_event() {
+        let event = Model {
+            id: Id::new_v4(),
+            event_type: "goal_created".to_string(),
+            entity_type: "goal".to_string(),
+            entity_id: None,
+            coaching_relationship_id: None,
+            coaching_session_id: None,
+            actor_user_id: None,
+            payload: serde_json::Value::Null,
+            created_at: Utc::now().into(),
+        };
+
+        let (created_at, id) = decode_cursor(&encode_cursor(&event)).unwrap();
+
+        assert_eq!((created_at, id), (event.created_at, event.id));
+        assert!(matches!(
+            decode_cursor("not-a-cursor").unwrap_err().error_kind,
+            DomainErrorKind::Validation(_)
+        ));
+    }
+
+    #[test]
+    fn expired_partitions_are_the_months_ending_before_the_cutoff() {
+        let partitions = [
+            "event_store_2024_09",
+            "event_store_2024_10",
+            "event_store_2024_11",
+            "event_store_default",
+        ]
+        .map(String::from);
+        let cutoff = NaiveDate::from_ymd_opt(2024, 11, 1).unwrap();
+
+        assert_eq!(
+            expired_partitions(&partitions, cutoff),
+            ["event_store_2024_09", "event_store_2024_10"]
+        );
+    }
+}
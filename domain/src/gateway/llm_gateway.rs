@@ -0,0 +1,512 @@
+//! OpenAI-compatible client used as the LLM analysis and text embedding provider.
+//!
+//! Works against any gateway exposing `POST {base_url}/chat/completions` and
+//! `POST {base_url}/embeddings` with bearer auth (OpenAI, Azure OpenAI, OpenRouter,
+//! LiteLLM, vLLM, Ollama, ...).
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use log::*;
+use meeting_ai::traits::analysis::{self, CompletionStream};
+use meeting_ai::traits::embedding;
+use meeting_ai::types::analysis::{Completion, Message, Request, StreamChunk};
+use meeting_ai::types::embedding::Embeddings;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{DomainErrorKind, Error, InternalErrorKind};
+
+/// Identifier reported by [`analysis::Provider::provider_id`] and
+/// [`embedding::Provider::provider_id`].
+const PROVIDER_ID: &str = "llm_gateway";
+
+/// LLM gateway client. Built once at startup and shared via `AppState`.
+#[derive(Clone)]
+pub struct Provider {
+    client: reqwest::Client,
+    base_url: String,
+    default_model: String,
+    embedding_model: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatCompletionRequest<'a> {
+    model: &'a str,
+    messages: &'a [Message],
+    max_tokens: u32,
+    temperature: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<ResponseFormat>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<StreamOptions>,
+}
+
+#[derive(Debug, Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dimensions: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingResponse {
+    model: Option<String>,
+    #[serde(default)]
+    data: Vec<EmbeddingData>,
+    usage: Option<Usage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingData {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+#[derive(Debug, Serialize)]
+struct StreamOptions {
+    include_usage: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct ResponseFormat {
+    #[serde(rename = "type")]
+    kind: &'static str,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionResponse {
+    model: Option<String>,
+    #[serde(default)]
+    choices: Vec<Choice>,
+    usage: Option<Usage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Choice {
+    message: ChoiceMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChoiceMessage {
+    content: Option<String>,
+}
+
+/// One `data:` event of a streamed completion.
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChunk {
+    model: Option<String>,
+    #[serde(default)]
+    choices: Vec<ChunkChoice>,
+    usage: Option<Usage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChunkChoice {
+    delta: ChunkDelta,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChunkDelta {
+    content: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Usage {
+    prompt_tokens: Option<u32>,
+    completion_tokens: Option<u32>,
+}
+
+impl Provider {
+    pub fn new(
+        api_key: &str,
+        base_url: &str,
+        default_model: &str,
+        embedding_model: &str,
+    ) -> Result<Self, Error> {
+        let mut headers = reqwest::header::HeaderMap::new();
+
+        let mut header_value = reqwest::header::HeaderValue::from_str(&format!("Bearer {api_key}"))
+            .map_err(|e| {
+                warn!("Failed to create LLM gateway auth header: {:?}", e);
+                Error {
+                    source: Some(Box::new(e)),
+                    error_kind: DomainErrorKind::Internal(InternalErrorKind::Other(
+                        "Invalid LLM gateway API key format".to_string(),
+                    )),
+                }
+            })?;
+        header_value.set_sensitive(true);
+        headers.insert(reqwest::header::AUTHORIZATION, header_value);
+
+        let client = reqwest::Client::builder()
+            .use_rustls_tls()
+            .default_headers(headers)
+            .connect_timeout(std::time::Duration::from_secs(10))
+            .timeout(std::time::Duration::from_secs(120))
+            .build()?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            default_model: default_model.to_string(),
+            embedding_model: embedding_model.to_string(),
+        })
+    }
+}
+
+impl Provider {
+    /// Posts a request to `{base_url}/{path}`, mapping transport and HTTP failures to
+    /// provider errors.
+    async fn send(
+        &self,
+        path: &str,
+        body: &impl Serialize,
+    ) -> std::result::Result<reqwest::Response, meeting_ai::Error> {
+        let url = format!("{}/{path}", self.base_url);
+
+        let response = self
+            .client
+            .post(&url)
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| {
+                warn!("LLM gateway request failed: {:?}", e);
+                if e.is_timeout() {
+                    meeting_ai::Error::Timeout(e.to_string())
+                } else {
+                    meeting_ai::Error::Network(e.to_string())
+                }
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            warn!("LLM gateway error ({}): {}", status, error_text);
+            return Err(match status.as_u16() {
+                401 | 403 => meeting_ai::Error::Authentication(error_text),
+                429 => meeting_ai::Error::RateLimited {
+                    retry_after_seconds: 30,
+                },
+                _ => meeting_ai::Error::Provider(format!("{status}: {error_text}")),
+            });
+        }
+
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl analysis::Provider for Provider {
+    async fn complete(
+        &self,
+        request: Request,
+    ) -> std::result::Result<Completion, meeting_ai::Error> {
+        let model = request.model.as_deref().unwrap_or(&self.default_model);
+        let body = ChatCompletionRequest {
+            model,
+            messages: &request.messages,
+            max_tokens: request.max_tokens,
+            temperature: request.temperature,
+            response_format: request.json_response.then_some(ResponseFormat {
+                kind: "json_object",
+            }),
+            stream: false,
+            stream_options: None,
+        };
+
+        debug!("Requesting LLM gateway completion with model {model}");
+
+        let completion: ChatCompletionResponse = self
+            .send("chat/completions", &body)
+            .await?
+            .json()
+            .await
+            .map_err(|e| meeting_ai::Error::Deserialization(e.to_string()))?;
+
+        into_completion(completion, model)
+    }
+
+    async fn complete_stream(
+        &self,
+        request: Request,
+    ) -> std::result::Result<CompletionStream, meeting_ai::Error> {
+        let model = request
+            .model
+            .clone()
+            .unwrap_or_else(|| self.default_model.clone());
+        let body = ChatCompletionRequest {
+            model: &model,
+            messages: &request.messages,
+            max_tokens: request.max_tokens,
+            temperature: request.temperature,
+            response_format: request.json_response.then_some(ResponseFormat {
+                kind: "json_object",
+            }),
+            stream: true,
+            stream_options: Some(StreamOptions {
+                include_usage: true,
+            }),
+        };
+
+        debug!("Requesting streamed LLM gateway completion with model {model}");
+
+        let mut bytes = self.send("chat/completions", &body).await?.bytes_stream();
+
+        Ok(Box::pin(async_stream::try_stream! {
+            let mut buffer: Vec<u8> = Vec::new();
+            let mut completion = Completion {
+                text: String::new(),
+                model,
+                input_tokens: None,
+                output_tokens: None,
+            };
+            let mut finished = false;
+
+            while !finished {
+                let Some(received) = bytes.next().await else {
+                    break;
+                };
+                let received = received.map_err(|e| meeting_ai::Error::Network(e.to_string()))?;
+                buffer.extend_from_slice(&received);
+
+                // Events are newline-delimited; a network chunk may end mid-line.
+                while let Some(newline) = buffer.iter().position(|byte| *byte == b'\n') {
+                    let line: Vec<u8> = buffer.drain(..=newline).collect();
+                    match parse_event_line(String::from_utf8_lossy(&line).trim())? {
+                        Event::Chunk(chunk) => {
+                            if let Some(reported) = chunk.model {
+                                completion.model = reported;
+                            }
+                            if let Some(usage) = chunk.usage {
+                                completion.input_tokens = usage.prompt_tokens;
+                                completion.output_tokens = usage.completion_tokens;
+                            }
+                            let delta = chunk
+                                .choices
+                                .into_iter()
+                                .next()
+                                .and_then(|choice| choice.delta.content)
+                                .filter(|delta| !delta.is_empty());
+                            if let Some(delta) = delta {
+                                completion.text.push_str(&delta);
+                                yield StreamChunk::Text(delta);
+                            }
+                        }
+                        Event::Done => {
+                            finished = true;
+                            break;
+                        }
+                        Event::Ignored => {}
+                    }
+                }
+            }
+
+            yield StreamChunk::Done(completion);
+        }))
+    }
+
+    fn default_model(&self) -> &str {
+        &self.default_model
+    }
+
+    fn provider_id(&self) -> &str {
+        PROVIDER_ID
+    }
+}
+
+#[async_trait]
+impl embedding::Provider for Provider {
+    async fn embed(
+        &self,
+        request: meeting_ai::types::embedding::Request,
+    ) -> std::result::Result<Embeddings, meeting_ai::Error> {
+        let model = request.model.as_deref().unwrap_or(&self.embedding_model);
+        let body = EmbeddingRequest {
+            model,
+            input: &request.inputs,
+            dimensions: request.dimensions,
+        };
+
+        debug!(
+            "Requesting {} LLM gateway embedding(s) with model {model}",
+            request.inputs.len()
+        );
+
+        let response: EmbeddingResponse = self
+            .send("embeddings", &body)
+            .await?
+            .json()
+            .await
+            .map_err(|e| meeting_ai::Error::Deserialization(e.to_string()))?;
+
+        into_embeddings(response, model, request.inputs.len())
+    }
+
+    fn model(&self) -> &str {
+        &self.embedding_model
+    }
+
+    fn provider_id(&self) -> &str {
+        PROVIDER_ID
+    }
+}
+
+/// A parsed line of the gateway's server-sent event stream.
+enum Event {
+    Chunk(ChatCompletionChunk),
+    Done,
+    /// Blank separators, comments and non-data fields.
+    Ignored,
+}
+
+fn parse_event_line(line: &str) -> std::result::Result<Event, meeting_ai::Error> {
+    let Some(data) = line.strip_prefix("data:") else {
+        return Ok(Event::Ignored);
+    };
+    match data.trim() {
+        "[DONE]" => Ok(Event::Done),
+        data => serde_json::from_str(data)
+            .map(Event::Chunk)
+            .map_err(|e| meeting_ai::Error::Deserialization(e.to_string())),
+    }
+}
+
+/// Takes the first choice's text, reporting the model the gateway actually used.
+fn into_completion(
+    response: ChatCompletionResponse,
+    requested_model: &str,
+) -> std::result::Result<Completion, meeting_ai::Error> {
+    let text = response
+        .choices
+        .into_iter()
+        .next()
+        .and_then(|choice| choice.message.content)
+        .ok_or_else(|| meeting_ai::Error::Provider("LLM gateway returned no content".into()))?;
+
+    Ok(Completion {
+        text,
+        model: response
+            .model
+            .unwrap_or_else(|| requested_model.to_string()),
+        input_tokens: response.usage.as_ref().and_then(|u| u.prompt_tokens),
+        output_tokens: response.usage.as_ref().and_then(|u| u.completion_tokens),
+    })
+}
+
+/// Orders the vectors by input index, checking there is exactly one per input.
+fn into_embeddings(
+    mut response: EmbeddingResponse,
+    requested_model: &str,
+    input_count: usize,
+) -> std::result::Result<Embeddings, meeting_ai::Error> {
+    response.data.sort_by_key(|data| data.index);
+    let in_order = response
+        .data
+        .iter()
+        .enumerate()
+        .all(|(position, data)| data.index == position);
+    if response.data.len() != input_count || !in_order {
+        return Err(meeting_ai::Error::Provider(format!(
+            "LLM gateway returned {} embedding(s) for {input_count} input(s)",
+            response.data.len()
+        )));
+    }
+
+    Ok(Embeddings {
+        vectors: response
+            .data
+            .into_iter()
+            .map(|data| data.embedding)
+            .collect(),
+        model: response
+            .model
+            .unwrap_or_else(|| requested_model.to_string()),
+        input_tokens: response.usage.and_then(|usage| usage.prompt_tokens),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn into_completion_reads_the_first_choice_and_usage() {
+        let response: ChatCompletionResponse = serde_json::from_value(serde_json::json!({
+            "model": "gpt-4o-mini-2024-07-18",
+            "choices": [{ "message": { "role": "assistant", "content": "{\"ok\":true}" } }],
+            "usage": { "prompt_tokens": 120, "completion_tokens": 30 }
+        }))
+        .unwrap();
+
+        let completion = into_completion(response, "gpt-4o-mini").unwrap();
+
+        assert_eq!(completion.text, "{\"ok\":true}");
+        assert_eq!(completion.model, "gpt-4o-mini-2024-07-18");
+        assert_eq!(completion.total_tokens(), Some(150));
+    }
+
+    #[test]
+    fn into_completion_errors_without_content() {
+        let response: ChatCompletionResponse =
+            serde_json::from_value(serde_json::json!({ "choices": [] })).unwrap();
+
+        assert!(into_completion(response, "gpt-4o-mini").is_err());
+    }
+
+    #[test]
+    fn parse_event_line_reads_deltas_usage_and_the_done_marker() {
+        let Event::Chunk(chunk) = parse_event_line(
+            r#"data: {"model":"gpt-4o-mini","choices":[{"delta":{"content":"Hel"}}]}"#,
+        )
+        .unwrap() else {
+            panic!("expected a chunk");
+        };
+        assert_eq!(chunk.choices[0].delta.content.as_deref(), Some("Hel"));
+
+        let Event::Chunk(chunk) = parse_event_line(
+            r#"data: {"choices":[],"usage":{"prompt_tokens":10,"completion_tokens":2}}"#,
+        )
+        .unwrap() else {
+            panic!("expected a chunk");
+        };
+        assert_eq!(chunk.usage.unwrap().completion_tokens, Some(2));
+
+        assert!(matches!(parse_event_line("data: [DONE]"), Ok(Event::Done)));
+        assert!(matches!(
+            parse_event_line(": keep-alive"),
+            Ok(Event::Ignored)
+        ));
+        assert!(parse_event_line("data: {not json").is_err());
+    }
+
+    #[test]
+    fn into_embeddings_orders_vectors_by_input_index() {
+        let response: EmbeddingResponse = serde_json::from_value(serde_json::json!({
+            "model": "text-embedding-3-small",
+            "data": [
+                { "index": 1, "embedding": [0.5, 0.5] },
+                { "index": 0, "embedding": [1.0, 0.0] }
+            ],
+            "usage": { "prompt_tokens": 8, "total_tokens": 8 }
+        }))
+        .unwrap();
+
+        let embeddings = into_embeddings(response, "text-embedding-3-small", 2).unwrap();
+
+        assert_eq!(embeddings.vectors, vec![vec![1.0, 0.0], vec![0.5, 0.5]]);
+        assert_eq!(embeddings.input_tokens, Some(8));
+    }
+
+    #[test]
+    fn into_embeddings_errors_when_a_vector_is_missing() {
+        let response: EmbeddingResponse = serde_json::from_value(serde_json::json!({
+            "data": [{ "index": 0, "embedding": [1.0, 0.0] }]
+        }))
+        .unwrap();
+
+        assert!(into_embeddings(response, "text-embedding-3-small", 2).is_err());
+    }
+}
@@ -1,5 +1,7 @@
 pub mod google_meet;
+pub mod llm_gateway;
 pub mod oauth;
+pub mod object_storage;
 pub mod recall_ai;
 pub(crate) mod resend;
 pub(crate) mod tiptap;
@@ -53,6 +53,10 @@ struct AssemblyAiConfig {
     speech_models: Vec<&'static str>,
     language_detection: bool,
     sentiment_analysis: bool,
+    auto_chapters: bool,
+    /// Terms AssemblyAI should favor when recognizing speech.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    word_boost: Vec<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -63,6 +67,8 @@ struct DiarizationConfig {
 #[derive(Debug, Deserialize)]
 struct TranscriptLinks {
     download_url: Option<String>,
+    /// The transcription provider's own response (AssemblyAI's transcript JSON).
+    provider_data_download_url: Option<String>,
 }
 
 /// Recall.ai returns `status` as an object: `{"code": "processing", "message": null}`.
@@ -84,11 +90,32 @@ impl TranscriptMetadata {
         self.data.as_ref()?.download_url.as_deref()
     }
 
+    pub fn provider_data_download_url(&self) -> Option<&str> {
+        self.data.as_ref()?.provider_data_download_url.as_deref()
+    }
+
     pub fn status_str(&self) -> Option<&str> {
         self.status.as_ref()?.code.as_deref()
     }
 }
 
+/// The parts of AssemblyAI's transcript JSON (Recall.ai's provider data) used here.
+#[derive(Debug, Deserialize)]
+struct AssemblyAiProviderData {
+    /// Null unless `auto_chapters` was requested.
+    chapters: Option<Vec<AssemblyAiChapter>>,
+}
+
+/// An AssemblyAI auto chapter; `start` and `end` are milliseconds into the recording.
+#[derive(Debug, Deserialize)]
+struct AssemblyAiChapter {
+    headline: String,
+    summary: String,
+    gist: String,
+    start: i64,
+    end: i64,
+}
+
 /// One entry in the transcript download array — all words spoken by one participant.
 #[derive(Debug, Deserialize)]
 pub struct ParticipantEntry {
@@ -449,10 +476,13 @@ impl Provider {
     ///
     /// `recall_recording_id` is Recall's recording UUID from the `recording.done` webhook
     /// (`data.recording.id`) — distinct from the bot ID. Uses AssemblyAI with speaker
-    /// diarization and sentiment analysis. Completion is signaled via `transcript.done` webhook.
+    /// diarization, optionally detecting chapters, and boosting the `word_boost` terms (the
+    /// organization's custom vocabulary). Completion is signaled via `transcript.done` webhook.
     pub async fn create_async_transcript(
         &self,
         recall_recording_id: &str,
+        auto_chapters: bool,
+        word_boost: Vec<String>,
     ) -> Result<String, Error> {
         let url = format!(
             "{}/recording/{}/create_transcript/",
@@ -465,6 +495,8 @@ impl Provider {
                     speech_models: vec!["universal-2"],
                     language_detection: true,
                     sentiment_analysis: false,
+                    auto_chapters,
+                    word_boost,
                 },
             },
             diarization: DiarizationConfig {
@@ -604,6 +636,63 @@ impl Provider {
         }
     }
 
+    /// Downloads the provider data JSON from a pre-signed URL and returns its chapters
+    /// (empty when none were detected).
+    pub async fn download_chapters(
+        &self,
+        provider_data_download_url: &str,
+    ) -> Result<Vec<transcription_types::Chapter>, Error> {
+        debug!("Downloading transcript provider data from pre-signed URL");
+
+        let response = self
+            .download_client
+            .get(provider_data_download_url)
+            .send()
+            .await
+            .map_err(|e| {
+                warn!("Failed to download transcript provider data: {:?}", e);
+                Error {
+                    source: Some(Box::new(e)),
+                    error_kind: DomainErrorKind::External(ExternalErrorKind::Network),
+                }
+            })?;
+
+        if response.status().is_success() {
+            let data: AssemblyAiProviderData = response.json().await.map_err(|e| {
+                warn!("Failed to parse transcript provider data: {:?}", e);
+                Error {
+                    source: Some(Box::new(e)),
+                    error_kind: DomainErrorKind::External(ExternalErrorKind::Other(
+                        "Invalid provider data JSON from download URL".to_string(),
+                    )),
+                }
+            })?;
+            Ok(data
+                .chapters
+                .unwrap_or_default()
+                .into_iter()
+                .map(|chapter| transcription_types::Chapter {
+                    title: chapter.headline,
+                    summary: chapter.summary,
+                    gist: chapter.gist,
+                    start_ms: chapter.start,
+                    end_ms: chapter.end,
+                })
+                .collect())
+        } else {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            warn!(
+                "Transcript provider data download error ({}): {}",
+                status, error_text
+            );
+            Err(Error {
+                source: None,
+                error_kind: DomainErrorKind::External(ExternalErrorKind::Other(error_text)),
+            })
+        }
+    }
+
     async fn bot_detail(&self, bot_id: &str) -> Result<BotDetailResponse, Error> {
         let url = format!("{}/bot/{}/", self.base_url, bot_id);
 
@@ -778,7 +867,11 @@ impl transcription_trait::Provider for Provider {
             })?;
 
         let transcript_id = self
-            .create_async_transcript(recall_recording_id)
+            .create_async_transcript(
+                recall_recording_id,
+                config.enable_auto_chapters,
+                config.custom_vocabulary,
+            )
             .await
             .map_err(to_meeting_ai_err)?;
 
@@ -819,13 +912,22 @@ impl transcription_trait::Provider for Provider {
             vec![]
         };
 
+        // Chapters only help navigation, so a failed download doesn't fail the transcript.
+        let chapters = match metadata.provider_data_download_url() {
+            Some(url) => self.download_chapters(url).await.unwrap_or_else(|e| {
+                warn!("Skipping chapters of transcript {}: {:?}", metadata.id, e);
+                vec![]
+            }),
+            None => vec![],
+        };
+
         Ok(transcription_types::Transcription {
             id: metadata.id,
             status,
             text: None,
             words: vec![],
             segments,
-            chapters: vec![],
+            chapters,
             sentiment_analysis: vec![],
             confidence: None,
             duration_seconds: None,
@@ -1029,6 +1131,81 @@ mod tests {
         assert_eq!(segments[0].speaker, "Unknown");
     }
 
+    // ── HTTP — create_async_transcript ────────────────────────────────────────
+
+    #[tokio::test]
+    async fn create_async_transcript_sends_custom_vocabulary_as_word_boost() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/recording/rec-123/create_transcript/")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "provider": {"assembly_ai_async": {"word_boost": ["OKR", "Refactor"]}}
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"id":"trans-123"}"#)
+            .create_async()
+            .await;
+
+        let provider = test_provider(&server.url());
+        let result = provider
+            .create_async_transcript(
+                "rec-123",
+                false,
+                vec!["OKR".to_string(), "Refactor".to_string()],
+            )
+            .await;
+
+        assert_eq!(result.unwrap(), "trans-123");
+        mock.assert_async().await;
+    }
+
+    // ── HTTP — download_chapters ──────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn download_chapters_maps_assembly_ai_chapters() {
+        let mut server = mockito::Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/provider-data")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"id":"aai-1","chapters":[{"headline":"Delegation","summary":"They discussed handing off reviews.","gist":"Delegating reviews","start":1200,"end":95000}]}"#,
+            )
+            .create_async()
+            .await;
+
+        let provider = test_provider(&server.url());
+        let chapters = provider
+            .download_chapters(&format!("{}/provider-data", server.url()))
+            .await
+            .unwrap();
+
+        assert_eq!(chapters.len(), 1);
+        assert_eq!(chapters[0].title, "Delegation");
+        assert_eq!((chapters[0].start_ms, chapters[0].end_ms), (1200, 95000));
+    }
+
+    #[tokio::test]
+    async fn download_chapters_returns_empty_without_chapters() {
+        let mut server = mockito::Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/provider-data")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"id":"aai-1","chapters":null}"#)
+            .create_async()
+            .await;
+
+        let provider = test_provider(&server.url());
+        let chapters = provider
+            .download_chapters(&format!("{}/provider-data", server.url()))
+            .await
+            .unwrap();
+
+        assert!(chapters.is_empty());
+    }
+
     // ── HTTP — create_bot ─────────────────────────────────────────────────────
 
     #[tokio::test]
@@ -1,14 +1,17 @@
+use crate::audit;
 use crate::error::Error;
 use crate::events::{DomainEvent, EventPublisher};
 use crate::goals::Model;
-use crate::Id;
+use crate::{ExpectedVersion, Id};
 use entity_api::coaching_session_goal as CoachingSessionGoalApi;
 use entity_api::query::{IntoQueryFilterMap, QuerySort};
 use entity_api::{goal as GoalApi, goals, query};
 use log::*;
 use sea_orm::{ConnectionTrait, DatabaseConnection, TransactionTrait};
 
-pub use entity_api::goal::find_by_id;
+pub use entity_api::goal::{
+    find_by_id, find_deleted_by_id, with_includes, EnrichedGoal, IncludeOptions,
+};
 
 // Re-export coaching-session ↔ goal join operations so the web layer
 // interacts with goals as a single domain concept rather than knowing
@@ -33,14 +36,18 @@ pub async fn create(
 
     txn.commit().await.map_err(entity_api::error::Error::from)?;
 
-    let notify_user_ids =
-        find_notify_user_ids_for_relationship(db, goal.coaching_relationship_id).await?;
+    publish_goal_created(event_publisher, &goal).await;
+    record_goal(db, audit::Action::Created, &goal).await;
+
+    Ok(goal)
+}
 
+/// Publishes a `GoalCreated` SSE event. Shared by `create` and template instantiation.
+pub(crate) async fn publish_goal_created(event_publisher: &EventPublisher, goal: &Model) {
     event_publisher
         .publish(DomainEvent::GoalCreated {
             coaching_relationship_id: goal.coaching_relationship_id,
-            goal: serde_json::to_value(&goal).unwrap_or(serde_json::Value::Null),
-            notify_user_ids,
+            goal: serde_json::to_value(goal).unwrap_or(serde_json::Value::Null),
         })
         .await;
 
@@ -48,13 +55,14 @@ pub async fn create(
         "Published GoalCreated event for goal {} in relationship {}",
         goal.id, goal.coaching_relationship_id
     );
-
-    Ok(goal)
 }
 
 /// Links a newly created goal to its `created_in_session` in the join table
 /// so that "goals linked to session X" queries return it immediately.
-async fn link_to_created_in_session(db: &impl ConnectionTrait, goal: &Model) -> Result<(), Error> {
+pub(crate) async fn link_to_created_in_session(
+    db: &impl ConnectionTrait,
+    goal: &Model,
+) -> Result<(), Error> {
     if let Some(session_id) = goal.created_in_session_id {
         CoachingSessionGoalApi::create(db, session_id, goal.id).await?;
         debug!(
@@ -70,9 +78,11 @@ pub async fn update(
     event_publisher: &EventPublisher,
     id: Id,
     model: Model,
+    expected: &ExpectedVersion,
 ) -> Result<Model, Error> {
-    let goal = GoalApi::update(db, id, model).await?;
-    publish_goal_updated(db, event_publisher, &goal).await?;
+    let goal = GoalApi::update(db, id, model, expected).await?;
+    publish_goal_updated(event_publisher, &goal).await;
+    record_goal(db, audit::Action::Updated, &goal).await;
     Ok(goal)
 }
 
@@ -81,9 +91,11 @@ pub async fn update_status(
     event_publisher: &EventPublisher,
     id: Id,
     status: entity_api::status::Status,
+    expected: &ExpectedVersion,
 ) -> Result<Model, Error> {
-    let goal = GoalApi::update_status(db, id, status).await?;
-    publish_goal_updated(db, event_publisher, &goal).await?;
+    let goal = GoalApi::update_status(db, id, status, expected).await?;
+    publish_goal_updated(event_publisher, &goal).await;
+    record_goal(db, audit::Action::Updated, &goal).await;
     Ok(goal)
 }
 
@@ -96,14 +108,10 @@ pub async fn delete(
     // delete_by_id returns the model before deletion so we can publish the event
     let goal = GoalApi::delete_by_id(db, id).await?;
 
-    let notify_user_ids =
-        find_notify_user_ids_for_relationship(db, goal.coaching_relationship_id).await?;
-
     event_publisher
         .publish(DomainEvent::GoalDeleted {
             coaching_relationship_id: goal.coaching_relationship_id,
             goal_id: goal.id,
-            notify_user_ids,
         })
         .await;
 
@@ -111,37 +119,54 @@ pub async fn delete(
         "Published GoalDeleted event for goal {} in relationship {}",
         goal.id, goal.coaching_relationship_id
     );
+    audit::record(
+        db,
+        audit::Action::Deleted,
+        "goal",
+        goal.id,
+        audit::Scope::CoachingRelationship(goal.coaching_relationship_id),
+        None::<&Model>,
+    )
+    .await;
 
     Ok(())
 }
 
+/// Restores a soft-deleted goal and publishes `GoalCreated`, as it reappears to the
+/// relationship just as a new one would. Fails if it's in progress and the relationship
+/// has since reached its in-progress limit.
+pub async fn restore(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    id: Id,
+) -> Result<Model, Error> {
+    let goal = GoalApi::restore(db, id).await?;
+    publish_goal_created(event_publisher, &goal).await;
+    record_goal(db, audit::Action::Restored, &goal).await;
+    Ok(goal)
+}
+
 // ── Event publishing helpers ─────────────────────────────────────────
 
-/// Looks up the coaching relationship and returns the user IDs that should
-/// receive SSE notifications (coach + coachee).
-async fn find_notify_user_ids_for_relationship(
-    db: &DatabaseConnection,
-    coaching_relationship_id: Id,
-) -> Result<Vec<Id>, Error> {
-    let relationship =
-        crate::coaching_relationship::find_by_id(db, coaching_relationship_id).await?;
-    Ok(vec![relationship.coach_id, relationship.coachee_id])
+/// Audits `action` on a goal. Shared by the mutations above and template instantiation.
+pub(crate) async fn record_goal(db: &DatabaseConnection, action: audit::Action, goal: &Model) {
+    audit::record(
+        db,
+        action,
+        "goal",
+        goal.id,
+        audit::Scope::CoachingRelationship(goal.coaching_relationship_id),
+        Some(goal),
+    )
+    .await;
 }
 
 /// Publishes a `GoalUpdated` SSE event. Shared by `update` and `update_status`.
-async fn publish_goal_updated(
-    db: &DatabaseConnection,
-    event_publisher: &EventPublisher,
-    goal: &Model,
-) -> Result<(), Error> {
-    let notify_user_ids =
-        find_notify_user_ids_for_relationship(db, goal.coaching_relationship_id).await?;
-
+async fn publish_goal_updated(event_publisher: &EventPublisher, goal: &Model) {
     event_publisher
         .publish(DomainEvent::GoalUpdated {
             coaching_relationship_id: goal.coaching_relationship_id,
             goal: serde_json::to_value(goal).unwrap_or(serde_json::Value::Null),
-            notify_user_ids,
         })
         .await;
 
@@ -149,8 +174,6 @@ async fn publish_goal_updated(
         "Published GoalUpdated event for goal {} in relationship {}",
         goal.id, goal.coaching_relationship_id
     );
-
-    Ok(())
 }
 
 pub async fn find_by<P>(db: &DatabaseConnection, params: P) -> Result<Vec<Model>, Error>
@@ -198,19 +221,7 @@ mod integration_tests {
             target_date: None,
             created_at: now,
             updated_at: now,
-        }
-    }
-
-    fn create_test_relationship(id: Id) -> crate::coaching_relationships::Model {
-        let now = chrono::Utc::now().fixed_offset();
-        crate::coaching_relationships::Model {
-            id,
-            organization_id: Id::new_v4(),
-            coach_id: Id::new_v4(),
-            coachee_id: Id::new_v4(),
-            slug: "test-slug".to_string(),
-            created_at: now,
-            updated_at: now,
+            deleted_at: None,
         }
     }
 
@@ -224,12 +235,10 @@ mod integration_tests {
             Some("New goal".to_string()),
             relationship_id,
         );
-        let relationship = create_test_relationship(relationship_id);
 
-        // Mock sequence (inside txn): goal save → (no session link) → relationship lookup
+        // Mock sequence (inside txn): goal save → (no session link)
         let db = MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results(vec![vec![new_goal.clone()]])
-            .append_query_results(vec![vec![relationship]])
             .into_connection();
 
         let result = create(&db, &event_publisher, new_goal, Id::new_v4()).await;
@@ -249,7 +258,6 @@ mod integration_tests {
             relationship_id,
             Some(session_id),
         );
-        let relationship = create_test_relationship(relationship_id);
 
         let now = chrono::Utc::now().fixed_offset();
         let join_row = coaching_sessions_goals::Model {
@@ -274,8 +282,6 @@ mod integration_tests {
         //   4. coaching_session_goal::create — cap check (SELECT in-progress goals)
         //   5. coaching_session_goal::create — join row insert (INSERT)
         //   6. coaching_session_goal::create — promotion update (UPDATE goals)
-        // After commit:
-        //   7. relationship lookup
         let db = MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results(vec![vec![new_goal.clone()]])
             .append_query_results(vec![vec![new_goal.clone()]])
@@ -283,7 +289,6 @@ mod integration_tests {
             .append_query_results(vec![Vec::<Model>::new()])
             .append_query_results(vec![vec![join_row]])
             .append_query_results(vec![vec![promoted_goal]])
-            .append_query_results(vec![vec![relationship]])
             .into_connection();
 
         let result = create(&db, &event_publisher, new_goal, Id::new_v4()).await;
@@ -301,16 +306,21 @@ mod integration_tests {
             Some("Already in-progress".to_string()),
             relationship_id,
         );
-        let relationship = create_test_relationship(relationship_id);
 
-        // Mock sequence: find_by_id → update_status save → relationship lookup
+        // Mock sequence: find_by_id → update_status save
         let db = MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results(vec![vec![current_goal.clone()]])
             .append_query_results(vec![vec![current_goal.clone()]])
-            .append_query_results(vec![vec![relationship]])
             .into_connection();
 
-        let result = update_status(&db, &event_publisher, current_goal.id, Status::Completed).await;
+        let result = update_status(
+            &db,
+            &event_publisher,
+            current_goal.id,
+            Status::Completed,
+            &ExpectedVersion::Any,
+        )
+        .await;
 
         assert!(result.is_ok());
     }
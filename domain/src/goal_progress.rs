@@ -6,6 +6,11 @@
 //! Two modes of computation:
 //! - **Duration-based**: When `target_date` is set, compares elapsed time against action progress.
 //! - **Momentum-based**: When `target_date` is null, looks at action completion cadence.
+//!
+//! Also computes each goal's effort burndown: points completed over time, weighed by
+//! the `effort_points` estimated on its actions.
+
+use std::collections::BTreeMap;
 
 use chrono::{NaiveDate, Utc};
 use sea_orm::prelude::DateTimeWithTimeZone;
@@ -14,7 +19,7 @@ use serde::Serialize;
 
 use crate::error::Error;
 use crate::status::Status;
-use crate::Id;
+use crate::{actions, goals, Id};
 
 pub use entity_api::goal_progress::BatchProgressParams;
 
@@ -35,6 +40,8 @@ pub struct ProgressMetrics {
     pub linked_coaching_session_count: usize,
     pub last_coaching_session_date: Option<chrono::NaiveDateTime>,
     pub next_action_due: Option<DateTimeWithTimeZone>,
+    /// Seconds of focused work logged across the goal's actions.
+    pub total_work_seconds: i64,
 }
 
 /// Computes progress metrics for a goal by gathering data and applying heuristics.
@@ -56,6 +63,7 @@ pub async fn progress_metrics(
         linked_coaching_session_count: data.linked_coaching_session_count,
         last_coaching_session_date: data.last_coaching_session_date,
         next_action_due: data.next_action_due,
+        total_work_seconds: data.total_work_seconds,
     })
 }
 
@@ -110,6 +118,7 @@ pub async fn relationship_goal_progress(
                 linked_coaching_session_count: data.linked_coaching_session_count,
                 last_coaching_session_date: data.last_coaching_session_date,
                 next_action_due: data.next_action_due,
+                total_work_seconds: data.total_work_seconds,
             };
 
             GoalProgressEntry {
@@ -232,6 +241,109 @@ fn compute_momentum_based_progress(data: &entity_api::goal_progress::ProgressDat
     }
 }
 
+// ── Burndown ──────────────────────────────────────────────────────────
+
+/// Weight given to actions without an effort estimate, so they still move the chart.
+const UNESTIMATED_EFFORT_POINTS: i64 = 1;
+
+/// One day's reading in a goal's burndown series.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BurndownPoint {
+    pub date: NaiveDate,
+    /// Points of all actions created on or before `date`.
+    pub scope_points: i64,
+    /// Points of those actions completed on or before `date`.
+    pub completed_points: i64,
+    pub remaining_points: i64,
+}
+
+/// Effort burndown for a goal, returned by the burndown endpoint.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Burndown {
+    pub goal_id: Id,
+    pub target_date: Option<NaiveDate>,
+    pub total_points: i64,
+    pub completed_points: i64,
+    /// Actions counted at the default weight because they carry no estimate.
+    pub unestimated_actions: usize,
+    /// One point per day on which scope or completion changed, starting at the
+    /// goal's creation and ending today.
+    pub series: Vec<BurndownPoint>,
+}
+
+/// Computes the effort burndown for a goal.
+///
+/// # Errors
+///
+/// Returns `Error` if the goal is not found or any database query fails.
+pub async fn burndown(db: &impl ConnectionTrait, goal_id: Id) -> Result<Burndown, Error> {
+    let data = entity_api::goal_progress::gather_burndown_data(db, goal_id).await?;
+    Ok(compute_burndown(
+        &data.goal,
+        &data.actions,
+        Utc::now().date_naive(),
+    ))
+}
+
+/// Builds the burndown series from the goal's actions as they stand today.
+///
+/// Actions marked `WontDo` drop out of scope entirely. A completed action counts
+/// as done on the day of its last status change; actions created before the goal
+/// (linked to it later) count from the goal's creation day.
+fn compute_burndown(goal: &goals::Model, actions: &[actions::Model], today: NaiveDate) -> Burndown {
+    let start = goal.created_at.date_naive();
+    let end = today.max(start);
+
+    // date → (scope added, points completed)
+    let mut deltas: BTreeMap<NaiveDate, (i64, i64)> = BTreeMap::new();
+    deltas.insert(start, (0, 0));
+    deltas.insert(end, (0, 0));
+
+    let mut unestimated_actions = 0;
+    for action in actions.iter().filter(|a| a.status != Status::WontDo) {
+        let points = match action.effort_points {
+            Some(points) => i64::from(points),
+            None => {
+                unestimated_actions += 1;
+                UNESTIMATED_EFFORT_POINTS
+            }
+        };
+
+        let added_on = action.created_at.date_naive().clamp(start, end);
+        deltas.entry(added_on).or_default().0 += points;
+
+        if action.status == Status::Completed {
+            let completed_on = action.status_changed_at.date_naive().clamp(start, end);
+            deltas.entry(completed_on).or_default().1 += points;
+        }
+    }
+
+    let mut scope_points = 0;
+    let mut completed_points = 0;
+    let series = deltas
+        .into_iter()
+        .map(|(date, (added, completed))| {
+            scope_points += added;
+            completed_points += completed;
+            BurndownPoint {
+                date,
+                scope_points,
+                completed_points,
+                remaining_points: scope_points - completed_points,
+            }
+        })
+        .collect();
+
+    Burndown {
+        goal_id: goal.id,
+        target_date: goal.target_date,
+        total_points: scope_points,
+        completed_points,
+        unestimated_actions,
+        series,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -264,6 +376,7 @@ mod tests {
                 target_date,
                 created_at,
                 updated_at: now,
+                deleted_at: None,
             },
             actions_total,
             actions_completed,
@@ -271,6 +384,7 @@ mod tests {
             next_action_due: None,
             linked_coaching_session_count: 0,
             last_coaching_session_date: None,
+            total_work_seconds: 0,
         }
     }
 
@@ -375,6 +489,110 @@ mod tests {
         ];
         assert_eq!(compute_progress(&data), Progress::LetsRefocus);
     }
+
+    fn day(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    fn burndown_action(
+        effort_points: Option<i16>,
+        status: Status,
+        created: NaiveDate,
+        status_changed: NaiveDate,
+    ) -> actions::Model {
+        let at = |date: NaiveDate| date.and_hms_opt(12, 0, 0).unwrap().and_utc().fixed_offset();
+        actions::Model {
+            id: Id::new_v4(),
+            coaching_session_id: Id::new_v4(),
+            goal_id: None,
+            user_id: Id::new_v4(),
+            action_series_id: None,
+            previous_action_id: None,
+            body: None,
+            effort_points,
+            due_by: None,
+            status,
+            status_changed_at: at(status_changed),
+            created_at: at(created),
+            updated_at: at(status_changed),
+            deleted_at: None,
+        }
+    }
+
+    fn burndown_goal(created: NaiveDate) -> goals::Model {
+        let mut goal = create_test_progress_data(None, 0, 0, 0).goal;
+        goal.created_at = created
+            .and_hms_opt(9, 0, 0)
+            .unwrap()
+            .and_utc()
+            .fixed_offset();
+        goal
+    }
+
+    #[test]
+    fn burndown_without_actions_is_flat_from_creation_to_today() {
+        let goal = burndown_goal(day(3, 1));
+        let burndown = compute_burndown(&goal, &[], day(3, 10));
+
+        assert_eq!(burndown.total_points, 0);
+        assert_eq!(
+            burndown.series.iter().map(|p| p.date).collect::<Vec<_>>(),
+            vec![day(3, 1), day(3, 10)]
+        );
+        assert!(burndown.series.iter().all(|p| p.remaining_points == 0));
+    }
+
+    #[test]
+    fn burndown_weighs_completions_by_effort_points() {
+        let goal = burndown_goal(day(3, 1));
+        let actions = vec![
+            burndown_action(Some(5), Status::Completed, day(3, 1), day(3, 4)),
+            burndown_action(Some(3), Status::InProgress, day(3, 2), day(3, 2)),
+            burndown_action(None, Status::Completed, day(3, 2), day(3, 6)),
+        ];
+        let burndown = compute_burndown(&goal, &actions, day(3, 10));
+
+        assert_eq!(burndown.total_points, 9);
+        assert_eq!(burndown.completed_points, 6);
+        assert_eq!(burndown.unestimated_actions, 1);
+        assert_eq!(
+            burndown
+                .series
+                .iter()
+                .map(|p| (p.date, p.scope_points, p.remaining_points))
+                .collect::<Vec<_>>(),
+            vec![
+                (day(3, 1), 5, 5),
+                (day(3, 2), 9, 9),
+                (day(3, 4), 9, 4),
+                (day(3, 6), 9, 3),
+                (day(3, 10), 9, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn burndown_drops_wont_do_actions_and_clamps_early_actions_to_goal_start() {
+        let goal = burndown_goal(day(3, 5));
+        let actions = vec![
+            burndown_action(Some(8), Status::WontDo, day(3, 6), day(3, 7)),
+            burndown_action(Some(2), Status::Completed, day(2, 20), day(2, 25)),
+        ];
+        let burndown = compute_burndown(&goal, &actions, day(3, 10));
+
+        assert_eq!(burndown.total_points, 2);
+        assert_eq!(burndown.unestimated_actions, 0);
+        assert_eq!(
+            burndown.series[0],
+            BurndownPoint {
+                date: day(3, 5),
+                scope_points: 2,
+                completed_points: 2,
+                remaining_points: 0,
+            }
+        );
+        assert_eq!(burndown.series.len(), 2);
+    }
 }
 
 /// Tests for `relationship_goal_progress` that require MockDatabase.
@@ -408,6 +626,7 @@ mod batch_tests {
             target_date,
             created_at,
             updated_at: now,
+            deleted_at: None,
         }
     }
 
@@ -0,0 +1,288 @@
+//! Organization-level goal templates ("Improve delegation") that coaches reuse.
+//!
+//! Admins manage an organization's templates; any member can list them. Instantiating
+//! a template in a coaching relationship creates the goal and one action per milestone
+//! in a single transaction, and counts the use on the template.
+
+use chrono::{Days, NaiveDate, Utc};
+use entity_api::{action as ActionApi, coaching_session, goal as GoalApi, goal_template};
+use log::*;
+use sea_orm::{DatabaseConnection, TransactionTrait};
+use serde::Serialize;
+
+use crate::action::ActionWithAssignees;
+use crate::error::{EntityErrorKind, Error};
+use crate::events::EventPublisher;
+use crate::goal_templates::{Milestone, Model};
+use crate::status::Status;
+use crate::{actions, coaching_relationships, goal, goals, Id};
+
+pub use entity_api::goal_template::find_by_organization;
+
+/// Most milestones a single template may carry.
+pub const MAX_MILESTONES: usize = 25;
+
+/// The goal created from a template together with the actions made from its milestones.
+#[derive(Debug, Serialize)]
+pub struct GoalFromTemplate {
+    pub goal: goals::Model,
+    pub actions: Vec<actions::Model>,
+}
+
+pub async fn create(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    user_id: Id,
+    model: Model,
+) -> Result<Model, Error> {
+    validate(&model)?;
+    Ok(goal_template::create(db, organization_id, user_id, model).await?)
+}
+
+pub async fn update(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    id: Id,
+    model: Model,
+) -> Result<Model, Error> {
+    validate(&model)?;
+    let existing = find_in_organization(db, organization_id, id).await?;
+    Ok(goal_template::update(db, existing, model).await?)
+}
+
+/// Deletes a template. Goals already created from it are unaffected.
+pub async fn delete(db: &DatabaseConnection, organization_id: Id, id: Id) -> Result<(), Error> {
+    find_in_organization(db, organization_id, id).await?;
+    Ok(goal_template::delete_by_id(db, id).await?)
+}
+
+/// A template by id, reported as not found unless it belongs to `organization_id`.
+pub async fn find_in_organization(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    id: Id,
+) -> Result<Model, Error> {
+    let template = goal_template::find_by_id(db, id).await?;
+    if template.organization_id != organization_id {
+        return Err(Error::entity(EntityErrorKind::NotFound));
+    }
+    Ok(template)
+}
+
+/// Creates a goal in `relationship` from a template of the relationship's organization.
+///
+/// Milestones become actions on `coaching_session_id`, which is therefore required when
+/// the template has milestones and must belong to the relationship. That session is
+/// also recorded as the goal's `created_in_session_id`.
+pub async fn instantiate(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    user_id: Id,
+    relationship: &coaching_relationships::Model,
+    template_id: Id,
+    coaching_session_id: Option<Id>,
+) -> Result<GoalFromTemplate, Error> {
+    let template = find_in_organization(db, relationship.organization_id, template_id).await?;
+
+    match coaching_session_id {
+        Some(session_id) => {
+            let session = coaching_session::find_by_id(db, session_id).await?;
+            if session.coaching_relationship_id != relationship.id {
+                return Err(Error::validation(
+                    "`coaching_session_id` must belong to the coaching relationship",
+                ));
+            }
+        }
+        None if !template.milestones.0.is_empty() => {
+            return Err(Error::validation(
+                "`coaching_session_id` is required to create this template's milestones",
+            ));
+        }
+        None => {}
+    }
+
+    let today = Utc::now().date_naive();
+    let txn = db.begin().await.map_err(entity_api::error::Error::from)?;
+
+    let goal = GoalApi::create(
+        &txn,
+        goal_model(&template, relationship.id, coaching_session_id, today),
+        user_id,
+    )
+    .await?;
+    goal::link_to_created_in_session(&txn, &goal).await?;
+
+    let mut actions = Vec::with_capacity(template.milestones.0.len());
+    if let Some(session_id) = coaching_session_id {
+        for milestone in &template.milestones.0 {
+            let action = ActionApi::create(
+                &txn,
+                action_model(milestone, session_id, goal.id, today),
+                user_id,
+            )
+            .await?;
+            actions.push(action);
+        }
+    }
+
+    goal_template::record_usage(&txn, template.id).await?;
+    txn.commit().await.map_err(entity_api::error::Error::from)?;
+
+    info!(
+        "Created goal {} from template {} in relationship {} with {} milestone actions",
+        goal.id,
+        template.id,
+        relationship.id,
+        actions.len()
+    );
+
+    goal::publish_goal_created(event_publisher, &goal).await;
+    goal::record_goal(db, crate::audit::Action::Created, &goal).await;
+    for action in &actions {
+        let action = ActionWithAssignees {
+            action: action.clone(),
+            assignee_ids: Vec::new(),
+        };
+        crate::action::publish_action_changed(db, event_publisher, &action, true).await;
+        crate::action::record_action(db, crate::audit::Action::Created, &action).await;
+    }
+
+    Ok(GoalFromTemplate { goal, actions })
+}
+
+fn goal_model(
+    template: &Model,
+    coaching_relationship_id: Id,
+    created_in_session_id: Option<Id>,
+    today: NaiveDate,
+) -> goals::Model {
+    let now = Utc::now().into();
+    goals::Model {
+        id: Id::nil(),
+        coaching_relationship_id,
+        created_in_session_id,
+        user_id: Id::nil(),
+        title: Some(template.title.clone()),
+        body: template.body.clone(),
+        status: Status::NotStarted,
+        status_changed_at: None,
+        completed_at: None,
+        target_date: template
+            .target_duration_days
+            .and_then(|days| offset_date(today, days)),
+        created_at: now,
+        updated_at: now,
+        deleted_at: None,
+    }
+}
+
+fn action_model(
+    milestone: &Milestone,
+    coaching_session_id: Id,
+    goal_id: Id,
+    today: NaiveDate,
+) -> actions::Model {
+    let now = Utc::now().into();
+    actions::Model {
+        id: Id::nil(),
+        coaching_session_id,
+        goal_id: Some(goal_id),
+        user_id: Id::nil(),
+        action_series_id: None,
+        previous_action_id: None,
+        body: Some(milestone.body.clone()),
+        effort_points: None,
+        due_by: milestone
+            .due_offset_days
+            .and_then(|days| offset_date(today, days))
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|due| due.and_utc().into()),
+        status: Status::NotStarted,
+        status_changed_at: now,
+        created_at: now,
+        updated_at: now,
+        deleted_at: None,
+    }
+}
+
+fn offset_date(today: NaiveDate, days: i32) -> Option<NaiveDate> {
+    today.checked_add_days(Days::new(u64::try_from(days).ok()?))
+}
+
+fn validate(model: &Model) -> Result<(), Error> {
+    if model.title.trim().is_empty() {
+        return Err(Error::validation("`title` must not be empty"));
+    }
+    if model.target_duration_days.is_some_and(|days| days <= 0) {
+        return Err(Error::validation("`target_duration_days` must be positive"));
+    }
+    if model.milestones.0.len() > MAX_MILESTONES {
+        return Err(Error::validation(format!(
+            "A template may have at most {MAX_MILESTONES} milestones"
+        )));
+    }
+    for milestone in &model.milestones.0 {
+        if milestone.body.trim().is_empty() {
+            return Err(Error::validation("Milestone `body` must not be empty"));
+        }
+        if milestone.due_offset_days.is_some_and(|days| days < 0) {
+            return Err(Error::validation(
+                "Milestone `due_offset_days` must not be negative",
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::goal_templates::Milestones;
+
+    fn template(milestones: Vec<Milestone>) -> Model {
+        let now = Utc::now().into();
+        Model {
+            id: Id::new_v4(),
+            organization_id: Id::new_v4(),
+            title: "Improve delegation".to_string(),
+            body: Some("Hand off work that others can own".to_string()),
+            target_duration_days: Some(90),
+            milestones: Milestones(milestones),
+            created_by_user_id: Id::new_v4(),
+            usage_count: 0,
+            last_used_at: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn milestone(body: &str, due_offset_days: Option<i32>) -> Milestone {
+        Milestone {
+            body: body.to_string(),
+            due_offset_days,
+        }
+    }
+
+    #[test]
+    fn validate_rejects_blank_milestones_and_negative_offsets() {
+        assert!(validate(&template(vec![milestone("List tasks", Some(7))])).is_ok());
+        assert!(validate(&template(vec![milestone("  ", None)])).is_err());
+        assert!(validate(&template(vec![milestone("List tasks", Some(-1))])).is_err());
+    }
+
+    #[test]
+    fn goal_and_actions_are_dated_from_the_instantiation_day() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 14).unwrap();
+        let template = template(vec![milestone("List tasks", Some(7))]);
+
+        let goal = goal_model(&template, Id::new_v4(), None, today);
+        let action = action_model(&template.milestones.0[0], Id::new_v4(), Id::new_v4(), today);
+
+        assert_eq!(goal.target_date, NaiveDate::from_ymd_opt(2027, 1, 12));
+        assert_eq!(goal.status, Status::NotStarted);
+        assert_eq!(
+            action.due_by.map(|due| due.date_naive()),
+            NaiveDate::from_ymd_opt(2026, 10, 21)
+        );
+    }
+}
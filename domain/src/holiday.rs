@@ -0,0 +1,239 @@
+//! Organizations' holiday calendars.
+//!
+//! An organization admin imports the organization's holidays from an ICS (iCalendar,
+//! RFC 5545) file, such as a public holiday calendar exported from a calendar app.
+//! Each event becomes a holiday spanning the days it covers; importing an updated file
+//! replaces the events it shares with the earlier import by `UID`. Booking a session on
+//! a holiday warns the booker (see [`crate::calendar::booking_warnings`]).
+//!
+//! Only what a holiday needs is read from the file: each `VEVENT`'s `DTSTART`, `DTEND`,
+//! `SUMMARY` and `UID`. Recurrence rules aren't expanded, so a recurring event counts
+//! once, on its first occurrence; holiday calendars list each year's dates anyway.
+
+use chrono::{Days, NaiveDate, Utc};
+use entity_api::organization_holiday;
+use log::*;
+use sea_orm::DatabaseConnection;
+
+use crate::error::{EntityErrorKind, Error};
+use crate::organization_holidays::Model;
+use crate::Id;
+
+pub use entity_api::organization_holiday::find_by_organization;
+
+/// Most holidays a single import may hold.
+pub const MAX_HOLIDAYS: usize = 1000;
+
+/// Longest stored holiday name; longer `SUMMARY`s are cut short.
+const MAX_NAME_CHARS: usize = 255;
+
+/// Imports the holidays of the ICS calendar `ics` into the organization's calendar.
+/// Returns the organization's holidays after the import.
+pub async fn import(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    ics: &str,
+) -> Result<Vec<Model>, Error> {
+    let holidays = parse_ics(ics)?;
+    let imported = organization_holiday::upsert_many(db, organization_id, holidays).await?;
+    info!("Imported {imported} holiday(s) into organization {organization_id}'s calendar");
+
+    Ok(find_by_organization(db, organization_id).await?)
+}
+
+pub async fn delete(db: &DatabaseConnection, organization_id: Id, id: Id) -> Result<(), Error> {
+    match organization_holiday::delete_by_organization_and_id(db, organization_id, id).await? {
+        0 => Err(Error::entity(EntityErrorKind::NotFound)),
+        _ => Ok(()),
+    }
+}
+
+/// Reads the holidays of an ICS calendar; `id`, `organization_id` and the timestamps are
+/// left for the import to fill in.
+fn parse_ics(ics: &str) -> Result<Vec<Model>, Error> {
+    let mut holidays = Vec::new();
+    let mut event: Option<Vec<(String, String)>> = None;
+
+    for line in unfold(ics) {
+        let Some((name, value)) = split_property(&line) else {
+            continue;
+        };
+        match (name.as_str(), value.as_str()) {
+            ("BEGIN", "VEVENT") => event = Some(Vec::new()),
+            ("END", "VEVENT") => {
+                if let Some(properties) = event.take() {
+                    holidays.push(holiday(&properties)?);
+                }
+            }
+            _ => {
+                if let Some(properties) = event.as_mut() {
+                    properties.push((name, value));
+                }
+            }
+        }
+    }
+
+    if holidays.is_empty() {
+        return Err(Error::validation("The calendar has no events"));
+    }
+    if holidays.len() > MAX_HOLIDAYS {
+        return Err(Error::validation(format!(
+            "A calendar may hold at most {MAX_HOLIDAYS} events"
+        )));
+    }
+    Ok(holidays)
+}
+
+/// The holiday one `VEVENT`'s properties describe.
+fn holiday(properties: &[(String, String)]) -> Result<Model, Error> {
+    let property = |wanted: &str| {
+        properties
+            .iter()
+            .find(|(name, _)| name == wanted)
+            .map(|(_, value)| value.as_str())
+    };
+
+    let starts_on = parse_date(
+        property("DTSTART").ok_or_else(|| Error::validation("An event has no DTSTART"))?,
+    )?;
+    let ends_on = match property("DTEND") {
+        // A whole-day event's DTEND is the day after its last
+        Some(end) if !end.contains('T') => parse_date(end)?
+            .checked_sub_days(Days::new(1))
+            .unwrap_or(starts_on),
+        Some(end) => parse_date(end)?,
+        None => starts_on,
+    }
+    .max(starts_on);
+
+    let name: String = property("SUMMARY")
+        .map(unescape)
+        .filter(|summary| !summary.trim().is_empty())
+        .unwrap_or_else(|| "Holiday".to_string())
+        .trim()
+        .chars()
+        .take(MAX_NAME_CHARS)
+        .collect();
+    let uid = property("UID")
+        .map(|uid| uid.trim().to_string())
+        .filter(|uid| !uid.is_empty())
+        .unwrap_or_else(|| format!("{starts_on}-{name}"));
+
+    let now = Utc::now().into();
+    Ok(Model {
+        id: Id::nil(),
+        organization_id: Id::nil(),
+        name,
+        starts_on,
+        ends_on,
+        uid,
+        created_at: now,
+        updated_at: now,
+    })
+}
+
+/// The calendar's lines with folded lines (continued on lines starting with a space or
+/// tab) joined back up.
+fn unfold(ics: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in ics.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// Splits `NAME;PARAM=VALUE:value` into its upper-cased name and its value; the
+/// parameters don't matter to a holiday.
+fn split_property(line: &str) -> Option<(String, String)> {
+    let (head, value) = line.split_once(':')?;
+    let name = head.split(';').next().unwrap_or(head);
+    Some((name.trim().to_ascii_uppercase(), value.trim().to_string()))
+}
+
+/// The date of a `YYYYMMDD` or `YYYYMMDDTHHMMSS[Z]` value.
+fn parse_date(value: &str) -> Result<NaiveDate, Error> {
+    value
+        .get(..8)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+        .ok_or_else(|| Error::validation(format!("`{value}` is not an ICS date")))
+}
+
+/// Undoes the escaping of commas, semicolons, backslashes and newlines in a text value.
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => unescaped.push(' '),
+            Some(escaped) => unescaped.push(escaped),
+            None => {}
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn parse_ics_reads_whole_day_events() {
+        let ics = "BEGIN:VCALENDAR\r\n\
+                   VERSION:2.0\r\n\
+                   BEGIN:VEVENT\r\n\
+                   DTSTART;VALUE=DATE:20261225\r\n\
+                   DTEND;VALUE=DATE:20261227\r\n\
+                   SUMMARY:Christmas\\, Boxing Day\r\n\
+                   UID:xmas-2026@example.com\r\n\
+                   END:VEVENT\r\n\
+                   BEGIN:VEVENT\r\n\
+                   DTSTART;VALUE=DATE:20270101\r\n\
+                   SUMMARY:New Year\r\n \
+                   's Day\r\n\
+                   END:VEVENT\r\n\
+                   END:VCALENDAR\r\n";
+
+        let holidays = parse_ics(ics).unwrap();
+
+        assert_eq!(holidays.len(), 2);
+        assert_eq!(holidays[0].name, "Christmas, Boxing Day");
+        assert_eq!(holidays[0].starts_on, date(2026, 12, 25));
+        assert_eq!(holidays[0].ends_on, date(2026, 12, 26));
+        assert_eq!(holidays[0].uid, "xmas-2026@example.com");
+        assert_eq!(holidays[1].name, "New Year's Day");
+        assert_eq!(holidays[1].ends_on, date(2027, 1, 1));
+        assert_eq!(holidays[1].uid, "2027-01-01-New Year's Day");
+    }
+
+    #[test]
+    fn parse_ics_keeps_the_last_day_of_timed_events() {
+        let ics = "BEGIN:VEVENT\n\
+                   DTSTART:20261231T120000Z\n\
+                   DTEND:20270101T120000Z\n\
+                   SUMMARY:Office closed\n\
+                   END:VEVENT\n";
+
+        let holidays = parse_ics(ics).unwrap();
+
+        assert_eq!(holidays[0].starts_on, date(2026, 12, 31));
+        assert_eq!(holidays[0].ends_on, date(2027, 1, 1));
+    }
+
+    #[test]
+    fn parse_ics_rejects_calendars_without_usable_events() {
+        assert!(parse_ics("BEGIN:VCALENDAR\nEND:VCALENDAR\n").is_err());
+        assert!(parse_ics("BEGIN:VEVENT\nSUMMARY:No date\nEND:VEVENT\n").is_err());
+        assert!(parse_ics("BEGIN:VEVENT\nDTSTART:2026-12-25\nEND:VEVENT\n").is_err());
+    }
+}
@@ -0,0 +1,157 @@
+//! Coachee journal entries, written between sessions.
+//!
+//! Only a relationship's coachee writes entries, and only their author may change them.
+//! An entry stays private to its author until `shared_with_coach` is set, at which point
+//! the coach can read it and it's included in the coach's session prep.
+
+use entity_api::journal_entry;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::DatabaseConnection;
+
+use crate::error::{EntityErrorKind, Error};
+use crate::journal_entries::Model;
+use crate::{coaching_relationships, Id};
+
+/// Longest title accepted, matching the column width.
+const MAX_TITLE_CHARS: usize = 255;
+
+/// Writes a journal entry in `relationship`. Coachee-only.
+pub async fn create(
+    db: &DatabaseConnection,
+    user_id: Id,
+    relationship: &coaching_relationships::Model,
+    model: Model,
+) -> Result<Model, Error> {
+    if relationship.coachee_id != user_id {
+        return Err(Error::entity(EntityErrorKind::Unauthenticated));
+    }
+
+    let model = validate(model)?;
+    Ok(journal_entry::create(db, relationship.id, user_id, model).await?)
+}
+
+/// Replaces an entry's title, body and sharing. Author-only.
+pub async fn update(
+    db: &DatabaseConnection,
+    user_id: Id,
+    relationship: &coaching_relationships::Model,
+    id: Id,
+    model: Model,
+) -> Result<Model, Error> {
+    let model = validate(model)?;
+    let existing = find_authored(db, user_id, relationship, id).await?;
+    Ok(journal_entry::update(db, existing, model).await?)
+}
+
+/// Deletes an entry. Author-only.
+pub async fn delete(
+    db: &DatabaseConnection,
+    user_id: Id,
+    relationship: &coaching_relationships::Model,
+    id: Id,
+) -> Result<(), Error> {
+    find_authored(db, user_id, relationship, id).await?;
+    Ok(journal_entry::delete_by_id(db, id).await?)
+}
+
+/// An entry of `relationship` that `user_id` may read.
+pub async fn find_by_id(
+    db: &DatabaseConnection,
+    user_id: Id,
+    relationship: &coaching_relationships::Model,
+    id: Id,
+) -> Result<Model, Error> {
+    let entry = journal_entry::find_visible_by_id(db, user_id, id).await?;
+    if entry.coaching_relationship_id != relationship.id {
+        return Err(Error::entity(EntityErrorKind::NotFound));
+    }
+    Ok(entry)
+}
+
+/// The entries of `relationship` that `user_id` may read, newest first: all of them for
+/// the coachee, only the shared ones for the coach.
+pub async fn find_by_relationship(
+    db: &DatabaseConnection,
+    user_id: Id,
+    relationship: &coaching_relationships::Model,
+) -> Result<Vec<Model>, Error> {
+    Ok(journal_entry::find_visible_by_relationship(db, user_id, relationship.id).await?)
+}
+
+/// Entries the coachee has shared with the coach since `since`, newest first. Used to
+/// put recent reflections in front of the coach when preparing for a session.
+pub async fn find_shared_since(
+    db: &DatabaseConnection,
+    coaching_relationship_id: Id,
+    since: DateTimeWithTimeZone,
+) -> Result<Vec<Model>, Error> {
+    Ok(
+        journal_entry::find_shared_by_relationship_since(db, coaching_relationship_id, since)
+            .await?,
+    )
+}
+
+async fn find_authored(
+    db: &DatabaseConnection,
+    user_id: Id,
+    relationship: &coaching_relationships::Model,
+    id: Id,
+) -> Result<Model, Error> {
+    let entry = find_by_id(db, user_id, relationship, id).await?;
+    if entry.user_id != user_id {
+        return Err(Error::entity(EntityErrorKind::Unauthenticated));
+    }
+    Ok(entry)
+}
+
+/// Trims the title (dropping an empty one) and requires a non-empty body.
+fn validate(mut model: Model) -> Result<Model, Error> {
+    model.title = model
+        .title
+        .map(|title| title.trim().to_string())
+        .filter(|title| !title.is_empty());
+    if model
+        .title
+        .as_ref()
+        .is_some_and(|title| title.chars().count() > MAX_TITLE_CHARS)
+    {
+        return Err(Error::validation(format!(
+            "`title` must be at most {MAX_TITLE_CHARS} characters"
+        )));
+    }
+    if model.body.trim().is_empty() {
+        return Err(Error::validation("`body` must not be empty"));
+    }
+    Ok(model)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn entry(title: Option<&str>, body: &str) -> Model {
+        let now = Utc::now().into();
+        Model {
+            id: Id::new_v4(),
+            coaching_relationship_id: Id::new_v4(),
+            user_id: Id::new_v4(),
+            title: title.map(str::to_string),
+            body: body.to_string(),
+            shared_with_coach: false,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn validate_drops_a_blank_title_and_rejects_a_blank_body() {
+        assert_eq!(
+            validate(entry(Some("   "), "Felt heard today"))
+                .unwrap()
+                .title,
+            None
+        );
+        assert!(validate(entry(Some("Tuesday"), "  \n")).is_err());
+    }
+}
@@ -0,0 +1,199 @@
+//! Legal holds preserving data, e.g. while litigation requires it.
+//!
+//! A SuperAdmin places a hold on an organization or a coaching relationship with the
+//! reason it's needed, and releases it once it no longer is. While a hold is active,
+//! deleting a user whose data it covers or the organization it covers fails with a
+//! conflict naming the hold, and the retention purges (old domain events, dispatched
+//! outbox events, password-reset attempts, soft-deleted records) pause: their rows aren't
+//! kept per organization, so any active hold pauses them all. Holds are kept once released, as
+//! the record of who held what, when and why.
+
+use entity_api::{coaching_relationship, legal_hold, organization};
+use log::*;
+use sea_orm::DatabaseConnection;
+
+use crate::error::{EntityErrorKind, Error};
+use crate::legal_holds::Model;
+use crate::Id;
+
+pub use entity_api::legal_hold::{find, find_by_id};
+
+/// Longest reason a hold may give.
+pub const MAX_REASON_CHARS: usize = 2000;
+
+/// Places a hold on the organization or the relationship `hold` names (exactly one),
+/// unless one is already active there.
+pub async fn place(db: &DatabaseConnection, user_id: Id, hold: Model) -> Result<Model, Error> {
+    let hold = validate(hold)?;
+    match (hold.organization_id, hold.coaching_relationship_id) {
+        (Some(organization_id), _) => {
+            organization::find_by_id(db, organization_id).await?;
+        }
+        (None, Some(relationship_id)) => {
+            coaching_relationship::find_by_id(db, relationship_id).await?;
+        }
+        (None, None) => unreachable!("validate requires a target"),
+    }
+
+    if let Some(active) =
+        legal_hold::find_active_on(db, hold.organization_id, hold.coaching_relationship_id).await?
+    {
+        return Err(Error::entity(EntityErrorKind::Conflict {
+            message: format!("A legal hold ({}) is already active there", active.id),
+            details: None,
+        }));
+    }
+
+    let hold = legal_hold::create(db, hold, user_id).await?;
+    info!(
+        "Legal hold {} placed by user {user_id} on {}",
+        hold.id,
+        target(&hold)
+    );
+    Ok(hold)
+}
+
+/// Releases an active hold.
+pub async fn release(db: &DatabaseConnection, user_id: Id, id: Id) -> Result<Model, Error> {
+    let hold = legal_hold::find_by_id(db, id).await?;
+    if hold.released_at.is_some() {
+        return Err(Error::entity(EntityErrorKind::Conflict {
+            message: "The legal hold was already released".to_string(),
+            details: None,
+        }));
+    }
+
+    let hold = legal_hold::release(db, hold, user_id).await?;
+    info!(
+        "Legal hold {} released by user {user_id} from {}",
+        hold.id,
+        target(&hold)
+    );
+    Ok(hold)
+}
+
+/// Fails with a conflict explaining the hold when the user's data is held.
+pub async fn ensure_user_not_held(db: &DatabaseConnection, user_id: Id) -> Result<(), Error> {
+    let holds = legal_hold::find_active_covering_user(db, user_id).await?;
+    ensure_not_held(&holds, "The user")
+}
+
+/// Fails with a conflict explaining the hold when the organization's data is held.
+pub async fn ensure_organization_not_held(
+    db: &DatabaseConnection,
+    organization_id: Id,
+) -> Result<(), Error> {
+    let holds = legal_hold::find_active_covering_organization(db, organization_id).await?;
+    ensure_not_held(&holds, "The organization")
+}
+
+/// Whether retention purges must pause, because a hold is active.
+pub async fn purges_paused(db: &DatabaseConnection) -> Result<bool, Error> {
+    Ok(legal_hold::count_active(db).await? > 0)
+}
+
+/// Checks the hold names exactly one target and trims its reason, which must be given.
+fn validate(mut hold: Model) -> Result<Model, Error> {
+    if hold.organization_id.is_some() == hold.coaching_relationship_id.is_some() {
+        return Err(Error::validation(
+            "Exactly one of `organization_id` and `coaching_relationship_id` must be set",
+        ));
+    }
+    hold.reason = hold.reason.trim().to_string();
+    if hold.reason.is_empty() {
+        return Err(Error::validation("`reason` must not be empty"));
+    }
+    if hold.reason.chars().count() > MAX_REASON_CHARS {
+        return Err(Error::validation(format!(
+            "`reason` must be at most {MAX_REASON_CHARS} characters"
+        )));
+    }
+    Ok(hold)
+}
+
+fn ensure_not_held(holds: &[Model], whose: &str) -> Result<(), Error> {
+    let Some(hold) = holds.first() else {
+        return Ok(());
+    };
+    Err(Error::entity(EntityErrorKind::Conflict {
+        message: format!(
+            "{whose}'s data is under a legal hold placed on {} on {} ({}), so it can't be \
+             deleted until the hold is released",
+            target(hold),
+            hold.created_at.date_naive(),
+            hold.reason
+        ),
+        details: Some(serde_json::json!({
+            "legal_hold_ids": holds.iter().map(|hold| hold.id).collect::<Vec<_>>(),
+        })),
+    }))
+}
+
+/// What a hold is on, for messages and logs.
+fn target(hold: &Model) -> String {
+    match (hold.organization_id, hold.coaching_relationship_id) {
+        (Some(organization_id), _) => format!("organization {organization_id}"),
+        (None, Some(relationship_id)) => format!("coaching relationship {relationship_id}"),
+        (None, None) => "nothing".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::DomainErrorKind;
+    use chrono::Utc;
+
+    fn hold(organization_id: Option<Id>, coaching_relationship_id: Option<Id>) -> Model {
+        let now = Utc::now();
+        Model {
+            id: Id::new_v4(),
+            organization_id,
+            coaching_relationship_id,
+            reason: "Smith v. Acme".to_string(),
+            placed_by_user_id: Id::new_v4(),
+            released_by_user_id: None,
+            released_at: None,
+            created_at: now.into(),
+            updated_at: now.into(),
+        }
+    }
+
+    #[test]
+    fn ensure_not_held_explains_the_hold() {
+        assert!(ensure_not_held(&[], "The user").is_ok());
+
+        let relationship_id = Id::new_v4();
+        let err = ensure_not_held(&[hold(None, Some(relationship_id))], "The user").unwrap_err();
+        match err.error_kind {
+            DomainErrorKind::Internal(crate::error::InternalErrorKind::Entity(
+                EntityErrorKind::Conflict { message, details },
+            )) => {
+                assert!(message.starts_with("The user's data is under a legal hold"));
+                assert!(message.contains(&format!("coaching relationship {relationship_id}")));
+                assert!(message.contains("Smith v. Acme"));
+                assert!(details.is_some());
+            }
+            other => panic!("expected a conflict, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn validate_requires_exactly_one_target_and_a_reason() {
+        let organization_hold = validate(Model {
+            reason: "  Smith v. Acme ".to_string(),
+            ..hold(Some(Id::new_v4()), None)
+        })
+        .unwrap();
+        assert_eq!(organization_hold.reason, "Smith v. Acme");
+        assert!(validate(hold(None, Some(Id::new_v4()))).is_ok());
+
+        assert!(validate(hold(Some(Id::new_v4()), Some(Id::new_v4()))).is_err());
+        assert!(validate(hold(None, None)).is_err());
+        assert!(validate(Model {
+            reason: " ".to_string(),
+            ..hold(Some(Id::new_v4()), None)
+        })
+        .is_err());
+    }
+}
@@ -5,21 +5,45 @@
 //! consistent interface for working with query filters within the domain layer, while encapsulating
 //! the underlying implementation details remain in the `entity_api` crate.
 pub use entity_api::{
-    mutate::{IntoUpdateMap, UpdateMap},
+    mutate::{ExpectedVersion, IntoUpdateMap, UpdateMap},
     query::{FilterOnly, IntoQueryFilterMap, QueryFilterMap},
 };
 
 // Re-exports from `entity` crate via `entity_api`
 pub use entity_api::{
-    actions, agreements, coachees, coaches, coaching_relationships, coaching_session_topics,
-    coaching_session_views, coaching_sessions, coaching_sessions_goals, cost_metric, cost_unit,
-    duration, goals, jwts, magic_link_tokens, meeting_provider, notes, oauth_connections,
-    organizations, password_reset_attempts, pipeline_provider, query::QuerySort, status,
-    token_purpose, topic_priority, topic_status, user_roles, users, Id,
+    action_work_logs, actions, agreements, ai_privacy_level, api_usage_rollups, attachments,
+    audit_entries, carried_actions, coachees, coaches, coaching_relationships,
+    coaching_session_topics, coaching_session_views, coaching_sessions, coaching_sessions_goals,
+    cost_metric, cost_unit, dead_letter_events, duration, embedding_source_type, event_store,
+    goal_templates, goals, journal_entries, jwts, legal_holds, library_assignments,
+    library_item_kind, library_items, magic_link_tokens, meeting_provider, mentions,
+    note_blind_index_tokens, notes, oauth_authorization_codes, oauth_clients, oauth_connections,
+    oauth_grants, organization_ai_settings, organization_api_quotas, organization_data_keys,
+    organization_holidays, organization_transcription_vocabularies, organizations,
+    out_of_office_periods, outbox_events, password_reset_attempts, pipeline_provider,
+    platform_settings, policy_acceptances, policy_documents, policy_kind, progress_report_settings,
+    progress_reports, prompt_key, prompt_templates, push_subscriptions, query::QuerySort,
+    question_quality_summaries, reactions, relationship_health_scores,
+    relationship_health_settings, remembered_devices, resource_type, resource_views,
+    scheduled_events, scim_tokens, scim_users, session_prep_briefs, session_pulses,
+    session_record_status, session_records, session_request_status, session_requests,
+    session_types, status, theme_reports, token_purpose, topic_priority, topic_status, user_locks,
+    user_roles, users, warehouse_checkpoints, webhook_subscriptions, Id,
 };
 
 pub mod action;
+pub mod action_series;
+pub mod action_work_log;
 pub mod agreement;
+pub mod ai_chat;
+pub mod ai_settings;
+pub mod api_usage;
+pub mod attachment;
+pub mod audit;
+pub mod batch;
+pub mod calendar;
+pub mod calendar_feed;
+pub mod coaching_log;
 pub mod coaching_relationship;
 pub mod coaching_session;
 pub(crate) mod coaching_session_goal;
@@ -27,25 +51,70 @@ mod coaching_session_hydration;
 pub mod coaching_session_series;
 pub mod coaching_session_topic;
 pub mod coaching_session_view;
+pub mod conversation_metrics;
 pub mod cost;
+pub mod cost_estimate;
+pub mod dead_letter;
 pub mod emails;
+pub mod embedding_index;
+pub mod encryption;
 pub mod error;
+pub mod event_log;
 pub mod goal;
 pub mod goal_progress;
+pub mod goal_template;
+pub mod holiday;
+pub mod journal_entry;
 pub mod jwt;
+pub mod legal_hold;
+pub mod library;
 pub mod magic_link_token;
 pub mod meeting_recording;
+pub mod mention;
 pub mod note;
 
 pub mod oauth_connection;
+pub mod oauth_server;
 pub mod oauth_token_storage;
 pub mod organization;
+pub mod organization_logo;
+pub mod out_of_office;
+pub mod outbox;
 pub mod password_policy;
 pub mod password_reset;
+pub mod pdf;
+pub mod platform_setting;
+pub mod png;
+pub mod policy;
+pub mod progress_report;
+pub mod prompt_template;
+pub mod push;
+pub mod question_quality;
+pub mod reaction;
+pub mod relationship_health;
+pub mod remembered_device;
+pub mod resource_view;
+pub mod retrieval;
+pub mod scheduled_event;
+pub mod scim;
+pub mod search;
+pub mod seed;
+pub mod session_prep;
+pub mod session_pulse;
+pub mod session_record;
+pub mod session_request;
+pub mod session_type;
+pub mod soft_delete;
+pub mod themes;
 pub mod tiptap_metrics;
+pub mod transcript_chapter;
 pub mod transcript_segment;
 pub mod transcription;
+pub mod transcription_vocabulary;
 pub mod user;
+pub mod user_lock;
+pub mod warehouse;
+pub mod webhook_subscription;
 
 pub mod gateway;
 pub mod webhook;
@@ -0,0 +1,304 @@
+//! An organization's content library (articles, exercises) and its assignment to coachees.
+//!
+//! Org admins and the org's coaches manage items; any member can browse them. A coach
+//! assigns an item within one of their relationships, optionally with a due date, and
+//! either participant can mark it completed. Assigning and completing notify the other
+//! participant over SSE.
+
+use chrono::Utc;
+use entity_api::library;
+use log::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::DatabaseConnection;
+
+use crate::error::{EntityErrorKind, Error};
+use crate::events::{DomainEvent, EventPublisher};
+use crate::library_items::{Model, Tags};
+use crate::users::{self, Role};
+use crate::{coaching_relationship, coaching_relationships, Id};
+
+pub use entity_api::library::AssignmentWithItem;
+
+/// Most tags a single item may carry.
+pub const MAX_TAGS: usize = 20;
+
+/// Whether `user` may manage `organization_id`'s library: a SuperAdmin, an admin of the
+/// organization, or a coach in any of its relationships.
+pub async fn can_manage(
+    db: &DatabaseConnection,
+    user: &users::Model,
+    organization_id: Id,
+) -> Result<bool, Error> {
+    let is_admin = user.roles.iter().any(|r| {
+        (r.role == Role::SuperAdmin && r.organization_id.is_none())
+            || (r.role == Role::Admin && r.organization_id == Some(organization_id))
+    });
+    if is_admin {
+        return Ok(true);
+    }
+
+    let coached =
+        coaching_relationship::find_by_coach_and_organization(db, user.id, organization_id).await?;
+    Ok(!coached.is_empty())
+}
+
+pub async fn create_item(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    user_id: Id,
+    model: Model,
+) -> Result<Model, Error> {
+    let model = validate_item(model)?;
+    Ok(library::create_item(db, organization_id, user_id, model).await?)
+}
+
+pub async fn update_item(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    id: Id,
+    model: Model,
+) -> Result<Model, Error> {
+    let model = validate_item(model)?;
+    let existing = find_item_in_organization(db, organization_id, id).await?;
+    Ok(library::update_item(db, existing, model).await?)
+}
+
+/// Deletes an item together with all of its assignments.
+pub async fn delete_item(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    id: Id,
+) -> Result<(), Error> {
+    find_item_in_organization(db, organization_id, id).await?;
+    Ok(library::delete_item(db, id).await?)
+}
+
+/// An organization's items, optionally only those tagged `tag`.
+pub async fn find_items(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    tag: Option<&str>,
+) -> Result<Vec<Model>, Error> {
+    let tag = tag.map(normalize_tag).filter(|tag| !tag.is_empty());
+    Ok(library::find_items_by_organization(db, organization_id, tag.as_deref()).await?)
+}
+
+/// An item by id, reported as not found unless it belongs to `organization_id`.
+pub async fn find_item_in_organization(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    id: Id,
+) -> Result<Model, Error> {
+    let item = library::find_item_by_id(db, id).await?;
+    if item.organization_id != organization_id {
+        return Err(Error::entity(EntityErrorKind::NotFound));
+    }
+    Ok(item)
+}
+
+/// Assigns an item from the relationship's organization to its coachee. Coach-only.
+pub async fn assign(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    user_id: Id,
+    relationship: &coaching_relationships::Model,
+    library_item_id: Id,
+    due_by: Option<DateTimeWithTimeZone>,
+) -> Result<AssignmentWithItem, Error> {
+    if relationship.coach_id != user_id {
+        return Err(Error::entity(EntityErrorKind::Unauthenticated));
+    }
+
+    let item = find_item_in_organization(db, relationship.organization_id, library_item_id).await?;
+    let assignment =
+        library::create_assignment(db, relationship.id, user_id, item.id, due_by).await?;
+    let assignment = AssignmentWithItem { assignment, item };
+
+    info!(
+        "Library item {} assigned in relationship {}",
+        assignment.item.id, relationship.id
+    );
+
+    publish(
+        event_publisher,
+        relationship,
+        user_id,
+        &assignment,
+        |coaching_relationship_id, assignment, notify_user_ids| {
+            DomainEvent::LibraryAssignmentCreated {
+                coaching_relationship_id,
+                assignment,
+                notify_user_ids,
+            }
+        },
+    )
+    .await;
+
+    Ok(assignment)
+}
+
+/// A relationship's assignments with their items, soonest due first.
+pub async fn find_assignments(
+    db: &DatabaseConnection,
+    coaching_relationship_id: Id,
+) -> Result<Vec<AssignmentWithItem>, Error> {
+    Ok(library::find_assignments_by_relationship(db, coaching_relationship_id).await?)
+}
+
+/// Marks an assignment completed. Completing an already completed assignment is a
+/// no-op that returns it unchanged.
+pub async fn complete(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    user_id: Id,
+    relationship: &coaching_relationships::Model,
+    assignment_id: Id,
+) -> Result<AssignmentWithItem, Error> {
+    let assignment = library::find_assignment_by_id(db, assignment_id).await?;
+    if assignment.coaching_relationship_id != relationship.id {
+        return Err(Error::entity(EntityErrorKind::NotFound));
+    }
+    let item = library::find_item_by_id(db, assignment.library_item_id).await?;
+
+    if assignment.completed_at.is_some() {
+        return Ok(AssignmentWithItem { assignment, item });
+    }
+
+    let assignment =
+        library::set_assignment_completed_at(db, assignment, Some(Utc::now().into())).await?;
+    let assignment = AssignmentWithItem { assignment, item };
+
+    publish(
+        event_publisher,
+        relationship,
+        user_id,
+        &assignment,
+        |coaching_relationship_id, assignment, notify_user_ids| {
+            DomainEvent::LibraryAssignmentCompleted {
+                coaching_relationship_id,
+                assignment,
+                notify_user_ids,
+            }
+        },
+    )
+    .await;
+
+    Ok(assignment)
+}
+
+/// Notifies the participant other than `actor_id` about an assignment change.
+async fn publish(
+    event_publisher: &EventPublisher,
+    relationship: &coaching_relationships::Model,
+    actor_id: Id,
+    assignment: &AssignmentWithItem,
+    event: impl FnOnce(Id, serde_json::Value, Vec<Id>) -> DomainEvent,
+) {
+    let notify_user_ids: Vec<Id> = [relationship.coach_id, relationship.coachee_id]
+        .into_iter()
+        .filter(|id| *id != actor_id)
+        .collect();
+    if notify_user_ids.is_empty() {
+        return;
+    }
+
+    match serde_json::to_value(assignment) {
+        Ok(payload) => {
+            event_publisher
+                .publish(event(relationship.id, payload, notify_user_ids))
+                .await
+        }
+        Err(e) => error!(
+            "library SSE: failed to serialize assignment {}: {e:?}",
+            assignment.assignment.id
+        ),
+    }
+}
+
+/// Trims the title, normalizes and de-duplicates tags, and checks that the item has
+/// content: a body, an http(s) URL, or both.
+fn validate_item(mut model: Model) -> Result<Model, Error> {
+    model.title = model.title.trim().to_string();
+    if model.title.is_empty() {
+        return Err(Error::validation("`title` must not be empty"));
+    }
+
+    model.body = model.body.filter(|body| !body.trim().is_empty());
+    model.url = model
+        .url
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty());
+    if model.body.is_none() && model.url.is_none() {
+        return Err(Error::validation("Either `body` or `url` is required"));
+    }
+    if let Some(url) = &model.url {
+        let is_web_url = reqwest::Url::parse(url)
+            .map(|url| matches!(url.scheme(), "http" | "https"))
+            .unwrap_or(false);
+        if !is_web_url {
+            return Err(Error::validation("`url` must be an http(s) URL"));
+        }
+    }
+
+    let mut tags: Vec<String> = Vec::new();
+    for tag in model.tags.0.iter().map(|tag| normalize_tag(tag)) {
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    if tags.len() > MAX_TAGS {
+        return Err(Error::validation(format!(
+            "An item may have at most {MAX_TAGS} tags"
+        )));
+    }
+    model.tags = Tags(tags);
+
+    Ok(model)
+}
+
+fn normalize_tag(tag: &str) -> String {
+    tag.trim().to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::library_item_kind::LibraryItemKind;
+
+    fn item(body: Option<&str>, url: Option<&str>, tags: &[&str]) -> Model {
+        let now = Utc::now().into();
+        Model {
+            id: Id::new_v4(),
+            organization_id: Id::new_v4(),
+            kind: LibraryItemKind::Exercise,
+            title: " Delegation audit ".to_string(),
+            body: body.map(str::to_string),
+            url: url.map(str::to_string),
+            tags: Tags(tags.iter().map(|tag| tag.to_string()).collect()),
+            created_by_user_id: Id::new_v4(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn validate_item_normalizes_title_and_tags() {
+        let model = validate_item(item(
+            Some("List your tasks"),
+            None,
+            &["Delegation", " delegation", ""],
+        ))
+        .unwrap();
+
+        assert_eq!(model.title, "Delegation audit");
+        assert_eq!(model.tags, Tags(vec!["delegation".to_string()]));
+    }
+
+    #[test]
+    fn validate_item_requires_content_and_a_web_url() {
+        assert!(validate_item(item(None, None, &[])).is_err());
+        assert!(validate_item(item(Some("  "), None, &[])).is_err());
+        assert!(validate_item(item(None, Some("javascript:alert(1)"), &[])).is_err());
+        assert!(validate_item(item(None, Some("https://example.com/article"), &[])).is_ok());
+    }
+}
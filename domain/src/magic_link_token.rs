@@ -116,6 +116,7 @@ pub async fn validate_token(
 ///
 pub async fn complete_setup(
     db: &DatabaseConnection,
+    config: &Config,
     params: impl mutate::IntoUpdateMap,
 ) -> Result<users::Model, Error> {
     let mut params = params.into_update_map();
@@ -139,7 +140,7 @@ pub async fn complete_setup(
     // Server-side policy enforcement — independent of any FE validation.
     // Same policy as `password_reset::complete_password_reset` so setup
     // and reset flows can't diverge on what's an acceptable password.
-    crate::password_policy::validate_password(&password)?;
+    crate::password_policy::enforce(config, &password).await?;
 
     params.insert(
         "password".to_string(),
@@ -360,7 +361,7 @@ mod tests {
                 "my_secure_password_2024",
                 "raw_token",
             );
-            let result = complete_setup(&db, params).await;
+            let result = complete_setup(&db, &Config::default(), params).await;
 
             let returned_user = result.unwrap();
             assert_eq!(returned_user.id, updated_user.id);
@@ -372,7 +373,7 @@ mod tests {
             let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
             let params = setup_params("password123", "different456", "dummy_token");
 
-            let result = complete_setup(&db, params).await;
+            let result = complete_setup(&db, &Config::default(), params).await;
 
             let err = result.unwrap_err();
             match err.error_kind {
@@ -408,7 +409,7 @@ mod tests {
                 "my_secure_password_2024",
                 "raw_token",
             );
-            let result = complete_setup(&db, params).await;
+            let result = complete_setup(&db, &Config::default(), params).await;
             assert!(result.is_ok());
 
             // Second call with the same token fails
@@ -417,7 +418,7 @@ mod tests {
                 "my_secure_password_2024",
                 "raw_token",
             );
-            let result = complete_setup(&db, params).await;
+            let result = complete_setup(&db, &Config::default(), params).await;
 
             let err = result.unwrap_err();
             assert_eq!(
@@ -8,13 +8,56 @@ pub use entity_api::meeting_recording::{
 
 use crate::error::{DomainErrorKind, EntityErrorKind, Error, InternalErrorKind};
 use entity::Id;
-use entity_api::meeting_recording as recording_api;
+use entity_api::{meeting_recording as recording_api, transcript_chapter as chapter_api};
 use log::*;
 use meeting_ai::traits::recording_bot;
 use meeting_ai::types::recording as recording_types;
 use sea_orm::DatabaseConnection;
+use serde::Serialize;
 use std::collections::HashMap;
 
+/// The latest recording with the chapter markers of its transcript, for playback.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Playback {
+    #[serde(flatten)]
+    pub recording: Model,
+    /// Empty until the transcript is complete, or when no chapters were detected.
+    pub chapters: Vec<ChapterMarker>,
+}
+
+/// Where a transcript chapter starts and ends in the recording.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChapterMarker {
+    pub title: String,
+    pub start_ms: i32,
+    pub end_ms: i32,
+}
+
+/// The session's latest recording and its chapter markers; `None` without a recording.
+pub async fn find_playback_by_coaching_session(
+    db: &DatabaseConnection,
+    session_id: Id,
+) -> Result<Option<Playback>, Error> {
+    let Some(recording) = recording_api::find_latest_by_coaching_session(db, session_id).await?
+    else {
+        return Ok(None);
+    };
+    let chapters = chapter_api::find_by_meeting_recording(db, recording.id)
+        .await?
+        .into_iter()
+        .map(|chapter| ChapterMarker {
+            title: chapter.title,
+            start_ms: chapter.start_ms,
+            end_ms: chapter.end_ms,
+        })
+        .collect();
+
+    Ok(Some(Playback {
+        recording,
+        chapters,
+    }))
+}
+
 /// Creates a recording bot and persists the initial `meeting_recordings` row.
 pub async fn start(
     db: &DatabaseConnection,
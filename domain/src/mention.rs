@@ -0,0 +1,250 @@
+//! `@display_name` mentions in note and action bodies.
+//!
+//! On every save the body is parsed against the session's participants (the only
+//! users who can be mentioned), the stored mentions are reconciled, and users who
+//! are newly mentioned get an SSE event and an email. Mentioning someone who is not
+//! a participant is not an error; the text is simply left as-is.
+
+use crate::emails;
+use crate::error::Error;
+use crate::events::{DomainEvent, EventPublisher};
+use crate::{actions, coaching_session, mentions, notes, user, users, Id};
+use entity_api::mention;
+use entity_api::resource_type::ResourceType;
+use log::*;
+use sea_orm::DatabaseConnection;
+use service::config::Config;
+
+pub use entity_api::mention::find_by_mentioned_user;
+
+/// Maximum characters of the body quoted in a mention email.
+const MAX_EXCERPT_CHARS: usize = 200;
+
+/// The note or action whose body may contain mentions.
+#[derive(Debug, Clone, Copy)]
+pub struct MentionSource<'a> {
+    pub resource_type: ResourceType,
+    pub resource_id: Id,
+    pub coaching_session_id: Id,
+    pub body: Option<&'a str>,
+}
+
+impl<'a> From<&'a notes::Model> for MentionSource<'a> {
+    fn from(note: &'a notes::Model) -> Self {
+        Self {
+            resource_type: ResourceType::Note,
+            resource_id: note.id,
+            coaching_session_id: note.coaching_session_id,
+            body: note.body.as_deref(),
+        }
+    }
+}
+
+impl<'a> From<&'a actions::Model> for MentionSource<'a> {
+    fn from(action: &'a actions::Model) -> Self {
+        Self {
+            resource_type: ResourceType::Action,
+            resource_id: action.id,
+            coaching_session_id: action.coaching_session_id,
+            body: action.body.as_deref(),
+        }
+    }
+}
+
+/// Reconciles the stored mentions for `source` with its body and notifies newly
+/// mentioned users over SSE and email (best-effort).
+///
+/// The note or action write is the contract: failures here are logged and never
+/// surface to the caller, matching the other post-save notifications.
+pub async fn sync_and_notify(
+    db: &DatabaseConnection,
+    config: &Config,
+    event_publisher: &EventPublisher,
+    author: &users::Model,
+    source: MentionSource<'_>,
+) {
+    let created = match sync(db, author.id, source).await {
+        Ok(created) => created,
+        Err(e) => {
+            error!(
+                "mentions: failed to sync mentions for {:?} {}: {e:?}",
+                source.resource_type, source.resource_id
+            );
+            return;
+        }
+    };
+    if created.is_empty() {
+        return;
+    }
+
+    for mention in &created {
+        match serde_json::to_value(mention) {
+            Ok(payload) => {
+                event_publisher
+                    .publish(DomainEvent::MentionCreated {
+                        coaching_session_id: mention.coaching_session_id,
+                        mention: payload,
+                        notify_user_ids: vec![mention.mentioned_user_id],
+                    })
+                    .await;
+            }
+            Err(e) => error!(
+                "mentions: failed to serialize mention {}: {e:?}",
+                mention.id
+            ),
+        }
+    }
+
+    let excerpt = excerpt(source.body.unwrap_or_default());
+    emails::notify_mentioned(db, config, author, &created, &excerpt).await;
+}
+
+async fn sync(
+    db: &DatabaseConnection,
+    author_id: Id,
+    source: MentionSource<'_>,
+) -> Result<Vec<mentions::Model>, Error> {
+    let (_, relationship) =
+        coaching_session::find_by_id_with_coaching_relationship(db, source.coaching_session_id)
+            .await?;
+
+    // Only the other participant is mentionable; self-mentions never notify.
+    let candidate_ids: Vec<Id> = [relationship.coach_id, relationship.coachee_id]
+        .into_iter()
+        .filter(|id| *id != author_id)
+        .collect();
+    let candidates: Vec<(Id, String)> = user::find_by_ids(db, &candidate_ids)
+        .await?
+        .iter()
+        .map(|u| (u.id, mention_name(u)))
+        .collect();
+
+    let mentioned = parse_mentions(source.body.unwrap_or_default(), &candidates);
+
+    Ok(mention::sync(
+        db,
+        source.resource_type,
+        source.resource_id,
+        source.coaching_session_id,
+        author_id,
+        &mentioned,
+    )
+    .await?)
+}
+
+/// The name a user is mentioned by: their display name, else "first last".
+fn mention_name(user: &users::Model) -> String {
+    match user.display_name.as_deref().map(str::trim) {
+        Some(display_name) if !display_name.is_empty() => display_name.to_string(),
+        _ => format!("{} {}", user.first_name, user.last_name),
+    }
+}
+
+/// Returns the ids of `candidates` mentioned as `@name` in `body`, in order of first mention.
+///
+/// Matching is case-insensitive and names may contain spaces. An `@` only starts a
+/// mention at the beginning of a word (so e-mail addresses don't match), and the name
+/// must end at a word boundary. When names share a prefix the longest match wins.
+fn parse_mentions(body: &str, candidates: &[(Id, String)]) -> Vec<Id> {
+    let mut by_length: Vec<(Id, Vec<char>)> = candidates
+        .iter()
+        .filter(|(_, name)| !name.is_empty())
+        .map(|(id, name)| (*id, name.to_lowercase().chars().collect()))
+        .collect();
+    by_length.sort_by_key(|(_, name)| std::cmp::Reverse(name.len()));
+
+    let chars: Vec<char> = body.chars().collect();
+    let mut mentioned = Vec::new();
+
+    for (at, c) in chars.iter().enumerate() {
+        if *c != '@' || (at > 0 && chars[at - 1].is_alphanumeric()) {
+            continue;
+        }
+        let rest = &chars[at + 1..];
+
+        let matched = by_length.iter().find(|(_, name)| {
+            rest.len() >= name.len()
+                && rest[..name.len()]
+                    .iter()
+                    .zip(name)
+                    .all(|(r, n)| r.to_lowercase().eq(n.to_lowercase()))
+                && rest
+                    .get(name.len())
+                    .is_none_or(|next| !next.is_alphanumeric())
+        });
+
+        if let Some((id, _)) = matched {
+            if !mentioned.contains(id) {
+                mentioned.push(*id);
+            }
+        }
+    }
+
+    mentioned
+}
+
+fn excerpt(body: &str) -> String {
+    let trimmed = body.trim();
+    match trimmed.char_indices().nth(MAX_EXCERPT_CHARS) {
+        Some((end, _)) => format!("{}…", &trimmed[..end]),
+        None => trimmed.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidates() -> (Id, Id, Vec<(Id, String)>) {
+        let (jim, jimmy) = (Id::new_v4(), Id::new_v4());
+        (
+            jim,
+            jimmy,
+            vec![
+                (jim, "Jim H".to_string()),
+                (jimmy, "Jim Henson".to_string()),
+            ],
+        )
+    }
+
+    #[test]
+    fn parse_mentions_matches_display_names_with_spaces_case_insensitively() {
+        let (jim, _, candidates) = candidates();
+
+        assert_eq!(parse_mentions("Thanks @jim h!", &candidates), vec![jim]);
+        assert_eq!(parse_mentions("<p>@Jim H</p>", &candidates), vec![jim]);
+    }
+
+    #[test]
+    fn parse_mentions_prefers_the_longest_name_and_dedups() {
+        let (jim, jimmy, candidates) = candidates();
+
+        assert_eq!(
+            parse_mentions(
+                "@Jim Henson and @Jim H, then @Jim Henson again",
+                &candidates
+            ),
+            vec![jimmy, jim]
+        );
+    }
+
+    #[test]
+    fn parse_mentions_ignores_emails_partial_words_and_strangers() {
+        let (_, _, candidates) = candidates();
+
+        assert!(parse_mentions("mail jim@Jim H.com", &candidates).is_empty());
+        assert!(parse_mentions("@Jim Hodapp", &candidates).is_empty());
+        assert!(parse_mentions("@Someone Else", &candidates).is_empty());
+        assert!(parse_mentions("", &candidates).is_empty());
+    }
+
+    #[test]
+    fn excerpt_truncates_long_bodies_on_char_boundaries() {
+        let long = "é".repeat(MAX_EXCERPT_CHARS + 10);
+        let excerpt = excerpt(&long);
+
+        assert_eq!(excerpt.chars().count(), MAX_EXCERPT_CHARS + 1);
+        assert!(excerpt.ends_with('…'));
+        assert_eq!(super::excerpt("  short  "), "short");
+    }
+}
@@ -1 +1,64 @@
-pub use entity_api::note::{create, find_by, find_by_id, update};
+use crate::audit;
+use crate::error::Error;
+use crate::notes::Model;
+use crate::{ExpectedVersion, Id};
+use sea_orm::DatabaseConnection;
+
+// Mutations (create, update, delete_by_id, restore) are wrapped below to record them in the
+// audit trail; reads re-export directly.
+pub use entity_api::note::{find_by, find_by_id, find_deleted_by_id};
+
+pub async fn create(
+    db: &DatabaseConnection,
+    note_model: Model,
+    user_id: Id,
+) -> Result<Model, Error> {
+    let note = entity_api::note::create(db, note_model, user_id).await?;
+    record_note(db, audit::Action::Created, &note).await;
+    Ok(note)
+}
+
+pub async fn update(
+    db: &DatabaseConnection,
+    id: Id,
+    model: Model,
+    expected: &ExpectedVersion,
+) -> Result<Model, Error> {
+    let note = entity_api::note::update(db, id, model, expected).await?;
+    record_note(db, audit::Action::Updated, &note).await;
+    Ok(note)
+}
+
+/// Soft-deletes a note, returning it as it was.
+pub async fn delete_by_id(db: &DatabaseConnection, id: Id) -> Result<Model, Error> {
+    let note = entity_api::note::delete_by_id(db, id).await?;
+    audit::record(
+        db,
+        audit::Action::Deleted,
+        "note",
+        id,
+        audit::Scope::CoachingSession(note.coaching_session_id),
+        None::<&Model>,
+    )
+    .await;
+    Ok(note)
+}
+
+pub async fn restore(db: &DatabaseConnection, id: Id) -> Result<Model, Error> {
+    let note = entity_api::note::restore(db, id).await?;
+    record_note(db, audit::Action::Restored, &note).await;
+    Ok(note)
+}
+
+/// Audits `action` on a note.
+async fn record_note(db: &DatabaseConnection, action: audit::Action, note: &Model) {
+    audit::record(
+        db,
+        action,
+        "note",
+        note.id,
+        audit::Scope::CoachingSession(note.coaching_session_id),
+        Some(note),
+    )
+    .await;
+}
@@ -0,0 +1,587 @@
+//! OAuth 2.0 authorization server: third-party apps acting on behalf of users.
+//!
+//! An organization's admins register the apps (clients) its users may authorize. An app
+//! sends the user to the frontend's consent page, which checks the request with
+//! [`check_authorization`] and, once the user agrees, gets an authorization code with
+//! [`authorize`] and redirects back to the app with it. The app then exchanges the code
+//! for tokens ([`exchange_code`]), proving with PKCE (S256 only) that it started the
+//! flow, and keeps them fresh with [`refresh`]. Refreshing rotates both tokens.
+//!
+//! Access tokens are sent as `Authorization: Bearer` headers and authenticate requests as
+//! the user who granted them, limited to the granted scopes:
+//!
+//! - `read`: safe (`GET`/`HEAD`/`OPTIONS`) requests
+//! - `write`: every request
+//!
+//! Codes and tokens are random strings of which only SHA-256 hashes are stored; tokens
+//! carry a prefix (`rfp_at_`, `rfp_rt_`) so they can be told apart from other bearer
+//! credentials. Users see the apps they authorized with [`find_authorized_apps`] and
+//! revoke them with [`revoke_app`].
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Duration, Utc};
+use entity_api::{oauth_authorization_code, oauth_client, oauth_grant};
+use log::*;
+use meeting_auth::oauth::PkceVerifier;
+use rand::RngCore;
+use sea_orm::DatabaseConnection;
+use sha2::{Digest, Sha256};
+
+use crate::error::{EntityErrorKind, Error};
+use crate::oauth_clients::{Model as Client, RedirectUris};
+use crate::oauth_grants::Model as Grant;
+use crate::{oauth_authorization_codes, users, Id};
+
+pub use entity_api::oauth_client::{find_by_organization as find_clients, CreatedOAuthClient};
+pub use entity_api::oauth_grant::{find_authorized_apps, AuthorizedApp};
+
+/// How long an access token authenticates requests.
+pub const ACCESS_TOKEN_TTL: Duration = Duration::hours(1);
+
+/// How long a refresh token can be exchanged for new tokens.
+pub const REFRESH_TOKEN_TTL: Duration = Duration::days(30);
+
+/// How long an authorization code waits to be exchanged.
+const CODE_TTL: Duration = Duration::minutes(10);
+
+/// Prefix of access tokens, marking a bearer credential as one of ours.
+pub const ACCESS_TOKEN_PREFIX: &str = "rfp_at_";
+const REFRESH_TOKEN_PREFIX: &str = "rfp_rt_";
+const CLIENT_SECRET_PREFIX: &str = "rfp_cs_";
+
+/// Scopes an app may be granted, from least to most privileged.
+pub const SCOPES: [&str; 2] = ["read", "write"];
+
+/// Scope granted when an app doesn't ask for any.
+const DEFAULT_SCOPE: &str = "read";
+
+/// Longest accepted client name.
+pub const MAX_NAME_LENGTH: usize = 255;
+
+/// Longest accepted redirect URI.
+pub const MAX_REDIRECT_URI_LENGTH: usize = 2048;
+
+/// The tokens issued to an app.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssuedTokens {
+    pub access_token: String,
+    pub refresh_token: String,
+    /// Seconds until the access token expires.
+    pub expires_in: i64,
+    /// Space-separated scopes granted.
+    pub scope: String,
+}
+
+/// What an introspected token grants, when it is active.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenInfo {
+    pub client_id: Id,
+    /// The user the token acts on behalf of.
+    pub user_id: Id,
+    pub scope: String,
+    pub expires_at: DateTime<Utc>,
+    /// `access_token` or `refresh_token`.
+    pub token_type: &'static str,
+}
+
+/// Why a token request was refused.
+#[derive(Debug)]
+pub enum TokenError {
+    /// Refused with an RFC 6749 error code (e.g. `invalid_grant`) and a description for
+    /// the app's developers.
+    Rejected {
+        error: &'static str,
+        description: String,
+    },
+    Internal(Error),
+}
+
+impl TokenError {
+    fn rejected(error: &'static str, description: &str) -> Self {
+        TokenError::Rejected {
+            error,
+            description: description.to_string(),
+        }
+    }
+}
+
+impl From<Error> for TokenError {
+    fn from(e: Error) -> Self {
+        TokenError::Internal(e)
+    }
+}
+
+impl From<entity_api::error::Error> for TokenError {
+    fn from(e: entity_api::error::Error) -> Self {
+        TokenError::Internal(e.into())
+    }
+}
+
+/// Registers a client for the organization. A confidential client gets a secret,
+/// returned only now; a public one relies on PKCE alone.
+pub async fn register_client(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    user_id: Id,
+    name: String,
+    redirect_uris: Vec<String>,
+    confidential: bool,
+) -> Result<CreatedOAuthClient, Error> {
+    validate_client(&name, &redirect_uris)?;
+    let client_secret = confidential.then(|| format!("{CLIENT_SECRET_PREFIX}{}", random_token()));
+    let model = Client {
+        id: Id::nil(),
+        organization_id,
+        name,
+        client_secret_hash: None,
+        redirect_uris: RedirectUris(redirect_uris),
+        created_by_user_id: None,
+        created_at: Utc::now().into(),
+        updated_at: Utc::now().into(),
+    };
+    let client = oauth_client::create(
+        db,
+        organization_id,
+        user_id,
+        client_secret.as_deref().map(hash_token),
+        model,
+    )
+    .await?;
+    info!(
+        "Registered OAuth client {} for organization {organization_id}",
+        client.id
+    );
+    Ok(CreatedOAuthClient {
+        client,
+        client_secret,
+    })
+}
+
+/// Deletes a client, revoking every token it holds.
+pub async fn delete_client(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    client_id: Id,
+) -> Result<(), Error> {
+    let client = oauth_client::find_by_id(db, client_id).await?;
+    if client.organization_id != organization_id {
+        return Err(Error::entity(EntityErrorKind::NotFound));
+    }
+    oauth_client::delete_by_id(db, client_id).await?;
+    info!("Deleted OAuth client {client_id} of organization {organization_id}");
+    Ok(())
+}
+
+/// The client and normalized scope of a valid authorization request by `user`. The
+/// client must belong to one of the user's organizations and list `redirect_uri`, and
+/// the request must carry an S256 PKCE challenge.
+pub async fn check_authorization(
+    db: &DatabaseConnection,
+    user: &users::Model,
+    client_id: Id,
+    redirect_uri: &str,
+    scope: Option<&str>,
+    code_challenge: &str,
+    code_challenge_method: Option<&str>,
+) -> Result<(Client, String), Error> {
+    let client = oauth_client::find_by_id(db, client_id).await?;
+    let is_member = user
+        .roles
+        .iter()
+        .any(|role| role.organization_id == Some(client.organization_id));
+    if !is_member {
+        warn!(
+            "User {} tried to authorize OAuth client {client_id} of another organization",
+            user.id
+        );
+        return Err(Error::entity(EntityErrorKind::NotFound));
+    }
+    if !client.redirect_uris.0.iter().any(|uri| uri == redirect_uri) {
+        return Err(Error::validation(
+            "`redirect_uri` is not registered for this client",
+        ));
+    }
+    if code_challenge_method != Some("S256") {
+        return Err(Error::validation("`code_challenge_method` must be `S256`"));
+    }
+    // A base64url SHA-256 digest.
+    if code_challenge.len() != 43 {
+        return Err(Error::validation(
+            "`code_challenge` is not an S256 PKCE challenge",
+        ));
+    }
+    let scope = normalize_scope(scope).map_err(|message| Error::validation(&message))?;
+    Ok((client, scope))
+}
+
+/// Records the user's consent to an authorization request (see
+/// [`check_authorization`]), returning the URL to send the user back to the app with:
+/// `redirect_uri` with the authorization `code` and the app's `state`.
+#[allow(clippy::too_many_arguments)]
+pub async fn authorize(
+    db: &DatabaseConnection,
+    user: &users::Model,
+    client_id: Id,
+    redirect_uri: &str,
+    scope: Option<&str>,
+    code_challenge: &str,
+    code_challenge_method: Option<&str>,
+    state: Option<&str>,
+) -> Result<String, Error> {
+    let (client, scope) = check_authorization(
+        db,
+        user,
+        client_id,
+        redirect_uri,
+        scope,
+        code_challenge,
+        code_challenge_method,
+    )
+    .await?;
+
+    let code = random_token();
+    let now = Utc::now();
+    oauth_authorization_code::create(
+        db,
+        oauth_authorization_codes::Model {
+            id: Id::nil(),
+            code_hash: hash_token(&code),
+            client_id: client.id,
+            user_id: user.id,
+            redirect_uri: redirect_uri.to_string(),
+            code_challenge: code_challenge.to_string(),
+            scope: scope.clone(),
+            expires_at: (now + CODE_TTL).into(),
+            created_at: now.into(),
+        },
+    )
+    .await?;
+    info!(
+        "User {} authorized OAuth client {} for `{scope}`",
+        user.id, client.id
+    );
+
+    let mut url = reqwest::Url::parse(redirect_uri)
+        .map_err(|_| Error::validation("`redirect_uri` is not a URL"))?;
+    {
+        let mut query = url.query_pairs_mut();
+        query.append_pair("code", &code);
+        if let Some(state) = state {
+            query.append_pair("state", state);
+        }
+    }
+    Ok(url.into())
+}
+
+/// The client identified by `client_id`, checking `client_secret` when it's
+/// confidential.
+pub async fn authenticate_client(
+    db: &DatabaseConnection,
+    client_id: Id,
+    client_secret: Option<&str>,
+) -> Result<Client, TokenError> {
+    let client = match oauth_client::find_by_id(db, client_id).await {
+        Ok(client) => client,
+        Err(e) if e.error_kind == entity_api::error::EntityApiErrorKind::RecordNotFound => {
+            return Err(TokenError::rejected("invalid_client", "Unknown client"))
+        }
+        Err(e) => return Err(e.into()),
+    };
+    if let Some(secret_hash) = &client.client_secret_hash {
+        if client_secret.map(hash_token).as_ref() != Some(secret_hash) {
+            return Err(TokenError::rejected(
+                "invalid_client",
+                "Client authentication failed",
+            ));
+        }
+    }
+    Ok(client)
+}
+
+/// Exchanges an authorization code issued to `client` for tokens. The code is consumed
+/// whether or not the exchange succeeds.
+pub async fn exchange_code(
+    db: &DatabaseConnection,
+    client: &Client,
+    code: &str,
+    redirect_uri: &str,
+    code_verifier: &str,
+) -> Result<IssuedTokens, TokenError> {
+    let invalid = || TokenError::rejected("invalid_grant", "Invalid authorization code");
+    let authorization = oauth_authorization_code::take_by_code_hash(db, &hash_token(code))
+        .await?
+        .ok_or_else(invalid)?;
+    if authorization.client_id != client.id
+        || authorization.expires_at < Utc::now()
+        || authorization.redirect_uri != redirect_uri
+    {
+        return Err(invalid());
+    }
+    let challenge = PkceVerifier::from_string(code_verifier.to_string()).challenge();
+    if challenge.as_str() != authorization.code_challenge {
+        return Err(TokenError::rejected(
+            "invalid_grant",
+            "`code_verifier` doesn't match the code challenge",
+        ));
+    }
+
+    let (tokens, grant) = issue(client.id, authorization.user_id, authorization.scope);
+    oauth_grant::create(db, grant).await?;
+    info!(
+        "Issued OAuth tokens to client {} for user {}",
+        client.id, authorization.user_id
+    );
+    Ok(tokens)
+}
+
+/// Exchanges a refresh token held by `client` for new tokens, invalidating both old ones.
+pub async fn refresh(
+    db: &DatabaseConnection,
+    client: &Client,
+    refresh_token: &str,
+) -> Result<IssuedTokens, TokenError> {
+    let invalid = || TokenError::rejected("invalid_grant", "Invalid refresh token");
+    let token_hash = hash_token(refresh_token);
+    let grant = oauth_grant::find_by_token_hash(db, &token_hash)
+        .await?
+        .filter(|grant| grant.refresh_token_hash == token_hash)
+        .ok_or_else(invalid)?;
+    if grant.client_id != client.id || grant.refresh_token_expires_at < Utc::now() {
+        return Err(invalid());
+    }
+
+    let (tokens, replacement) = issue(client.id, grant.user_id, grant.scope.clone());
+    oauth_grant::rotate(db, grant, replacement).await?;
+    Ok(tokens)
+}
+
+/// What `token` grants, if it's an active token of `client`'s. Other clients' tokens
+/// are reported inactive.
+pub async fn introspect(
+    db: &DatabaseConnection,
+    client: &Client,
+    token: &str,
+) -> Result<Option<TokenInfo>, Error> {
+    let token_hash = hash_token(token);
+    let Some(grant) = oauth_grant::find_by_token_hash(db, &token_hash).await? else {
+        return Ok(None);
+    };
+    if grant.client_id != client.id {
+        return Ok(None);
+    }
+    let (expires_at, token_type) = if grant.access_token_hash == token_hash {
+        (grant.access_token_expires_at, "access_token")
+    } else {
+        (grant.refresh_token_expires_at, "refresh_token")
+    };
+    let expires_at = expires_at.with_timezone(&Utc);
+    if expires_at < Utc::now() {
+        return Ok(None);
+    }
+    Ok(Some(TokenInfo {
+        client_id: grant.client_id,
+        user_id: grant.user_id,
+        scope: grant.scope,
+        expires_at,
+        token_type,
+    }))
+}
+
+/// Revokes the grant behind `token`, access or refresh, if `client` holds it. Unknown
+/// tokens are ignored, as RFC 7009 requires.
+pub async fn revoke(db: &DatabaseConnection, client: &Client, token: &str) -> Result<(), Error> {
+    if let Some(grant) = oauth_grant::find_by_token_hash(db, &hash_token(token)).await? {
+        if grant.client_id == client.id {
+            oauth_grant::revoke(db, grant.id).await?;
+            info!(
+                "OAuth client {} revoked its grant for user {}",
+                client.id, grant.user_id
+            );
+        }
+    }
+    Ok(())
+}
+
+/// The unexpired grant behind an access token presented as a bearer credential.
+pub async fn find_access_grant(
+    db: &DatabaseConnection,
+    access_token: &str,
+) -> Result<Option<Grant>, Error> {
+    let token_hash = hash_token(access_token);
+    Ok(oauth_grant::find_by_token_hash(db, &token_hash)
+        .await?
+        .filter(|grant| {
+            grant.access_token_hash == token_hash && grant.access_token_expires_at > Utc::now()
+        }))
+}
+
+/// Whether `scope` lets a token make a request, `write` telling whether the request
+/// changes anything.
+pub fn scope_allows(scope: &str, write: bool) -> bool {
+    scope
+        .split_whitespace()
+        .any(|granted| granted == "write" || (!write && granted == "read"))
+}
+
+/// Revokes every token the user granted the client.
+pub async fn revoke_app(db: &DatabaseConnection, user_id: Id, client_id: Id) -> Result<(), Error> {
+    if oauth_grant::revoke_for_client(db, user_id, client_id).await? == 0 {
+        return Err(Error::entity(EntityErrorKind::NotFound));
+    }
+    info!("User {user_id} revoked OAuth client {client_id}");
+    Ok(())
+}
+
+/// New tokens for the user's grant to the client, and the grant row storing their hashes.
+fn issue(client_id: Id, user_id: Id, scope: String) -> (IssuedTokens, Grant) {
+    let access_token = format!("{ACCESS_TOKEN_PREFIX}{}", random_token());
+    let refresh_token = format!("{REFRESH_TOKEN_PREFIX}{}", random_token());
+    let now = Utc::now();
+    let grant = Grant {
+        id: Id::nil(),
+        client_id,
+        user_id,
+        scope: scope.clone(),
+        access_token_hash: hash_token(&access_token),
+        access_token_expires_at: (now + ACCESS_TOKEN_TTL).into(),
+        refresh_token_hash: hash_token(&refresh_token),
+        refresh_token_expires_at: (now + REFRESH_TOKEN_TTL).into(),
+        revoked_at: None,
+        created_at: now.into(),
+        updated_at: now.into(),
+    };
+    let tokens = IssuedTokens {
+        access_token,
+        refresh_token,
+        expires_in: ACCESS_TOKEN_TTL.num_seconds(),
+        scope,
+    };
+    (tokens, grant)
+}
+
+/// The requested scopes, deduplicated in [`SCOPES`] order; [`DEFAULT_SCOPE`] when none
+/// are requested.
+fn normalize_scope(scope: Option<&str>) -> Result<String, String> {
+    let requested: Vec<&str> = scope.unwrap_or_default().split_whitespace().collect();
+    if requested.is_empty() {
+        return Ok(DEFAULT_SCOPE.to_string());
+    }
+    if let Some(unknown) = requested.iter().find(|scope| !SCOPES.contains(scope)) {
+        return Err(format!("`{unknown}` is not a scope"));
+    }
+    Ok(SCOPES
+        .iter()
+        .filter(|scope| requested.contains(scope))
+        .copied()
+        .collect::<Vec<_>>()
+        .join(" "))
+}
+
+fn validate_client(name: &str, redirect_uris: &[String]) -> Result<(), Error> {
+    if name.trim().is_empty() || name.len() > MAX_NAME_LENGTH {
+        return Err(Error::validation(format!(
+            "`name` must be 1 to {MAX_NAME_LENGTH} characters"
+        )));
+    }
+    if redirect_uris.is_empty() {
+        return Err(Error::validation(
+            "`redirect_uris` must list at least one URI",
+        ));
+    }
+    for redirect_uri in redirect_uris {
+        if redirect_uri.len() > MAX_REDIRECT_URI_LENGTH {
+            return Err(Error::validation(format!(
+                "Redirect URIs must be at most {MAX_REDIRECT_URI_LENGTH} characters"
+            )));
+        }
+        let url = reqwest::Url::parse(redirect_uri)
+            .map_err(|_| Error::validation(format!("`{redirect_uri}` is not a URL")))?;
+        // Plain HTTP is only allowed back to the user's own machine (native apps).
+        let loopback = matches!(url.host_str(), Some("localhost" | "127.0.0.1" | "[::1]"));
+        if url.fragment().is_some()
+            || !(url.scheme() == "https" || (url.scheme() == "http" && loopback))
+        {
+            return Err(Error::validation(format!(
+                "`{redirect_uri}` must be an https:// (or loopback http://) URL without a fragment"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn random_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Compute the SHA-256 hex digest of a raw token string.
+fn hash_token(raw_token: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(raw_token.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::DomainErrorKind;
+
+    #[test]
+    fn normalize_scope_defaults_to_read_and_rejects_unknown_scopes() {
+        assert_eq!(normalize_scope(None).unwrap(), "read");
+        assert_eq!(normalize_scope(Some("  ")).unwrap(), "read");
+        assert_eq!(
+            normalize_scope(Some("write read write")).unwrap(),
+            "read write"
+        );
+        assert!(normalize_scope(Some("read admin")).is_err());
+    }
+
+    #[test]
+    fn scope_allows_writes_only_with_the_write_scope() {
+        assert!(scope_allows("read", false));
+        assert!(!scope_allows("read", true));
+        assert!(scope_allows("write", true));
+        assert!(scope_allows("read write", false));
+        assert!(!scope_allows("", false));
+    }
+
+    #[test]
+    fn validate_client_requires_https_or_loopback_redirect_uris() {
+        let uris = |uris: &[&str]| uris.iter().map(|uri| uri.to_string()).collect::<Vec<_>>();
+
+        assert!(validate_client("App", &uris(&["https://app.example.com/callback"])).is_ok());
+        assert!(validate_client("App", &uris(&["http://127.0.0.1:8123/callback"])).is_ok());
+
+        for (name, redirect_uris) in [
+            ("", uris(&["https://app.example.com/callback"])),
+            ("App", uris(&[])),
+            ("App", uris(&["http://app.example.com/callback"])),
+            ("App", uris(&["https://app.example.com/callback#x"])),
+            ("App", uris(&["not a url"])),
+        ] {
+            assert!(matches!(
+                validate_client(name, &redirect_uris)
+                    .unwrap_err()
+                    .error_kind,
+                DomainErrorKind::Validation(_)
+            ));
+        }
+    }
+
+    #[test]
+    fn issue_stores_only_token_hashes() {
+        let client_id = Id::new_v4();
+        let user_id = Id::new_v4();
+
+        let (tokens, grant) = issue(client_id, user_id, "read".to_string());
+
+        assert!(tokens.access_token.starts_with(ACCESS_TOKEN_PREFIX));
+        assert!(tokens.refresh_token.starts_with(REFRESH_TOKEN_PREFIX));
+        assert_eq!(tokens.expires_in, 3600);
+        assert_eq!(grant.access_token_hash, hash_token(&tokens.access_token));
+        assert_eq!(grant.refresh_token_hash, hash_token(&tokens.refresh_token));
+        assert_eq!((grant.client_id, grant.user_id), (client_id, user_id));
+    }
+}
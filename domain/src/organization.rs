@@ -1,4 +1,64 @@
+use entity_api::organization;
+use sea_orm::DatabaseConnection;
+
+use crate::audit;
+use crate::error::Error;
+use crate::organizations::Model;
+use crate::Id;
+
 pub use entity_api::organization::{
-    archive, create, delete_by_id, find_all, find_by, find_by_id, find_by_user, unarchive, update,
-    StatusFilter,
+    find_all, find_by, find_by_id, find_by_user, stats, OrganizationStats, StatusFilter,
 };
+
+pub async fn create(db: &DatabaseConnection, organization_model: Model) -> Result<Model, Error> {
+    let organization = organization::create(db, organization_model).await?;
+    record(db, audit::Action::Created, &organization).await;
+    Ok(organization)
+}
+
+pub async fn update(db: &DatabaseConnection, id: Id, model: Model) -> Result<Model, Error> {
+    let organization = organization::update(db, id, model).await?;
+    record(db, audit::Action::Updated, &organization).await;
+    Ok(organization)
+}
+
+pub async fn archive(db: &DatabaseConnection, id: Id, archived_by: Id) -> Result<Model, Error> {
+    let organization = organization::archive(db, id, archived_by).await?;
+    record(db, audit::Action::Updated, &organization).await;
+    Ok(organization)
+}
+
+pub async fn unarchive(db: &DatabaseConnection, id: Id) -> Result<Model, Error> {
+    let organization = organization::unarchive(db, id).await?;
+    record(db, audit::Action::Updated, &organization).await;
+    Ok(organization)
+}
+
+/// Deletes an empty organization, unless a legal hold covers its data.
+pub async fn delete_by_id(db: &DatabaseConnection, id: Id) -> Result<(), Error> {
+    crate::legal_hold::ensure_organization_not_held(db, id).await?;
+    organization::delete_by_id(db, id).await?;
+    audit::record(
+        db,
+        audit::Action::Deleted,
+        "organization",
+        id,
+        audit::Scope::Organization(id),
+        None::<&Model>,
+    )
+    .await;
+    Ok(())
+}
+
+/// Audits `action` on an organization.
+async fn record(db: &DatabaseConnection, action: audit::Action, organization: &Model) {
+    audit::record(
+        db,
+        action,
+        "organization",
+        organization.id,
+        audit::Scope::Organization(organization.id),
+        Some(organization),
+    )
+    .await;
+}
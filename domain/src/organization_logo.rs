@@ -0,0 +1,190 @@
+//! Organization logos, uploaded by an organization's admins and served from object storage.
+//!
+//! An uploaded logo is a PNG, scaled down to fit within [`MAX_DIMENSION`] pixels and
+//! stored under a name derived from its content, so the URL recorded as the
+//! organization's logo never changes what it serves and can be cached indefinitely, by
+//! browsers or a CDN in front of it. A new logo gets a new URL, and the old one's file is
+//! removed.
+
+use entity_api::organization;
+use log::*;
+use sea_orm::DatabaseConnection;
+use service::config::Config;
+use sha2::{Digest, Sha256};
+
+use crate::error::{DomainErrorKind, EntityErrorKind, Error, InternalErrorKind};
+use crate::gateway::object_storage::ObjectStorage;
+use crate::organizations::Model;
+use crate::png;
+use crate::Id;
+
+/// Largest logo accepted, in bytes.
+pub const MAX_UPLOAD_BYTES: usize = 2 * 1024 * 1024;
+
+/// Widest and tallest a stored logo is, in pixels.
+pub const MAX_DIMENSION: u32 = 512;
+
+pub const CONTENT_TYPE: &str = "image/png";
+
+/// Hex digits of the content hash naming a stored logo.
+const NAME_HASH_CHARS: usize = 16;
+
+/// Stores `bytes` as the organization's logo, returning the organization with its new
+/// logo URL.
+pub async fn upload(
+    db: &DatabaseConnection,
+    config: &Config,
+    organization_id: Id,
+    bytes: &[u8],
+) -> Result<Model, Error> {
+    let organization = organization::find_by_id(db, organization_id).await?;
+    let storage = storage(config)?;
+
+    if bytes.len() > MAX_UPLOAD_BYTES {
+        return Err(Error::validation(format!(
+            "The logo may be at most {} MB",
+            MAX_UPLOAD_BYTES / (1024 * 1024)
+        )));
+    }
+    let bytes = bytes.to_vec();
+    // Decoding and resizing a large image takes long enough to hold up other requests
+    let (file_name, png) = tokio::task::spawn_blocking(move || process(&bytes))
+        .await
+        .map_err(|err| {
+            warn!("Processing an organization logo panicked: {err}");
+            Error {
+                source: Some(Box::new(err)),
+                error_kind: DomainErrorKind::Internal(InternalErrorKind::Other(
+                    "Processing the logo failed".to_string(),
+                )),
+            }
+        })??;
+
+    storage
+        .put(&object_key(organization_id, &file_name), png, CONTENT_TYPE)
+        .await?;
+    let url = logo_url(config, organization_id, &file_name);
+    let updated = organization::update_logo(db, organization_id, Some(url)).await?;
+    info!("Organization {organization_id} has a new logo, {file_name}");
+
+    // The previous logo's URL may still be cached, but nothing links to it any more
+    let previous = organization
+        .logo
+        .as_deref()
+        .and_then(|logo| stored_file_name(organization_id, logo));
+    if let Some(previous) = previous.filter(|previous| *previous != file_name) {
+        if let Err(err) = storage.delete(&object_key(organization_id, previous)).await {
+            warn!("Failed to delete organization {organization_id}'s previous logo: {err:?}");
+        }
+    }
+
+    Ok(updated)
+}
+
+/// The PNG stored as `file_name` among the organization's logos.
+pub async fn fetch(
+    config: &Config,
+    organization_id: Id,
+    file_name: &str,
+) -> Result<Vec<u8>, Error> {
+    if !is_file_name(file_name) {
+        return Err(Error::entity(EntityErrorKind::NotFound));
+    }
+    storage(config)?
+        .get(&object_key(organization_id, file_name))
+        .await?
+        .ok_or_else(|| Error::entity(EntityErrorKind::NotFound))
+}
+
+/// The logo in `bytes` fit within [`MAX_DIMENSION`] and re-encoded, with the file name
+/// it's stored under.
+fn process(bytes: &[u8]) -> Result<(String, Vec<u8>), Error> {
+    let image = png::decode(bytes).map_err(|err| {
+        Error::validation(format!("The logo must be a PNG image, but this is {err}"))
+    })?;
+    let png = png::encode(&image.fit_within(MAX_DIMENSION));
+    let hash = hex::encode(Sha256::digest(&png));
+    Ok((format!("{}.png", &hash[..NAME_HASH_CHARS]), png))
+}
+
+fn object_key(organization_id: Id, file_name: &str) -> String {
+    format!("organization-logos/{organization_id}/{file_name}")
+}
+
+fn logo_url(config: &Config, organization_id: Id, file_name: &str) -> String {
+    format!(
+        "{}/organizations/{organization_id}/logo/{file_name}",
+        config.organization_logo_base_url()
+    )
+}
+
+/// The file name of a logo URL pointing at one of the organization's stored logos.
+fn stored_file_name(organization_id: Id, url: &str) -> Option<&str> {
+    let (prefix, file_name) = url.rsplit_once('/')?;
+    let ours = prefix.ends_with(&format!("/organizations/{organization_id}/logo"));
+    (ours && is_file_name(file_name)).then_some(file_name)
+}
+
+fn is_file_name(file_name: &str) -> bool {
+    file_name.strip_suffix(".png").is_some_and(|hash| {
+        hash.len() == NAME_HASH_CHARS
+            && hash.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+    })
+}
+
+fn storage(config: &Config) -> Result<ObjectStorage, Error> {
+    ObjectStorage::from_config(config)?.ok_or_else(|| {
+        warn!("An organization logo was uploaded, but object storage isn't configured");
+        Error::entity(EntityErrorKind::ServiceUnavailable)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::png::Image;
+
+    #[test]
+    fn process_shrinks_the_logo_and_names_it_by_its_content() {
+        let image = Image {
+            width: 1024,
+            height: 256,
+            rgba: vec![200; 1024 * 256 * 4],
+        };
+
+        let (file_name, stored) = process(&png::encode(&image)).unwrap();
+
+        let stored_image = png::decode(&stored).unwrap();
+        assert_eq!((stored_image.width, stored_image.height), (512, 128));
+        assert!(is_file_name(&file_name));
+        assert_eq!(process(&stored).unwrap().0, file_name);
+    }
+
+    #[test]
+    fn process_refuses_what_isnt_a_png() {
+        let err = process(b"\xff\xd8\xff\xe0 a JPEG").unwrap_err();
+
+        assert!(matches!(err.error_kind, DomainErrorKind::Validation(_)));
+    }
+
+    #[test]
+    fn stored_file_name_only_matches_the_organizations_own_logos() {
+        let id = Id::new_v4();
+        let url = format!("https://cdn.example.com/organizations/{id}/logo/0123456789abcdef.png");
+
+        assert_eq!(stored_file_name(id, &url), Some("0123456789abcdef.png"));
+        assert_eq!(
+            stored_file_name(
+                id,
+                &format!("/organizations/{id}/logo/0123456789abcdef.png")
+            ),
+            Some("0123456789abcdef.png")
+        );
+        assert_eq!(stored_file_name(Id::new_v4(), &url), None);
+        assert_eq!(
+            stored_file_name(id, "https://example.com/images/logo.png"),
+            None
+        );
+        assert!(!is_file_name("../0123456789abcdef.png"));
+    }
+}
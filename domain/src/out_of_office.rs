@@ -0,0 +1,111 @@
+//! Users' out-of-office periods.
+//!
+//! A user records the stretches of time they're away; booking a session with them inside
+//! one warns the booker (see [`crate::calendar::booking_warnings`]). Users see and manage
+//! only their own periods.
+
+use chrono::Utc;
+use entity_api::out_of_office_period;
+use log::*;
+use sea_orm::DatabaseConnection;
+
+use crate::error::{EntityErrorKind, Error};
+use crate::out_of_office_periods::Model;
+use crate::Id;
+
+/// Longest note a period may carry.
+pub const MAX_NOTE_CHARS: usize = 500;
+
+/// The user's periods that haven't ended yet, soonest first.
+pub async fn find_upcoming(db: &DatabaseConnection, user_id: Id) -> Result<Vec<Model>, Error> {
+    Ok(out_of_office_period::find_by_user(db, user_id, Utc::now().into()).await?)
+}
+
+pub async fn create(db: &DatabaseConnection, user_id: Id, model: Model) -> Result<Model, Error> {
+    let model = validate(model)?;
+    let period = out_of_office_period::create(db, user_id, model).await?;
+    info!("User {user_id} is out of office as of {}", period.starts_at);
+    Ok(period)
+}
+
+pub async fn update(
+    db: &DatabaseConnection,
+    user_id: Id,
+    id: Id,
+    model: Model,
+) -> Result<Model, Error> {
+    let existing = find_own(db, user_id, id).await?;
+    let model = validate(model)?;
+    Ok(out_of_office_period::update(db, existing, model).await?)
+}
+
+pub async fn delete(db: &DatabaseConnection, user_id: Id, id: Id) -> Result<(), Error> {
+    find_own(db, user_id, id).await?;
+    Ok(out_of_office_period::delete_by_id(db, id).await?)
+}
+
+/// The user's period with `id`, reported as not found if it is someone else's.
+async fn find_own(db: &DatabaseConnection, user_id: Id, id: Id) -> Result<Model, Error> {
+    let period = out_of_office_period::find_by_id(db, id).await?;
+    if period.user_id != user_id {
+        return Err(Error::entity(EntityErrorKind::NotFound));
+    }
+    Ok(period)
+}
+
+/// Checks that the period ends after it starts, and trims the note, dropping an empty one.
+fn validate(mut model: Model) -> Result<Model, Error> {
+    if model.ends_at <= model.starts_at {
+        return Err(Error::validation("`ends_at` must be after `starts_at`"));
+    }
+
+    model.note = model
+        .note
+        .map(|note| note.trim().to_string())
+        .filter(|note| !note.is_empty());
+    if model
+        .note
+        .as_ref()
+        .is_some_and(|note| note.chars().count() > MAX_NOTE_CHARS)
+    {
+        return Err(Error::validation(format!(
+            "`note` must be at most {MAX_NOTE_CHARS} characters"
+        )));
+    }
+    Ok(model)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn period(hours: i64, note: Option<&str>) -> Model {
+        let now = Utc::now();
+        Model {
+            id: Id::new_v4(),
+            user_id: Id::new_v4(),
+            starts_at: now.into(),
+            ends_at: (now + chrono::Duration::hours(hours)).into(),
+            note: note.map(str::to_string),
+            created_at: now.into(),
+            updated_at: now.into(),
+        }
+    }
+
+    #[test]
+    fn validate_requires_the_period_to_end_after_it_starts() {
+        assert!(validate(period(24, None)).is_ok());
+        assert!(validate(period(0, None)).is_err());
+        assert!(validate(period(-1, None)).is_err());
+    }
+
+    #[test]
+    fn validate_trims_the_note() {
+        assert_eq!(
+            validate(period(24, Some(" Vacation "))).unwrap().note,
+            Some("Vacation".to_string())
+        );
+        assert_eq!(validate(period(24, Some("  "))).unwrap().note, None);
+        assert!(validate(period(24, Some(&"x".repeat(MAX_NOTE_CHARS + 1)))).is_err());
+    }
+}
@@ -0,0 +1,129 @@
+//! Domain events handed from the web process to the events worker.
+//!
+//! When the events worker is enabled, the web process's [`EventPublisher`] runs only the
+//! handlers that must run where the event was published (SSE) and stores each event with
+//! [`OutboxStore`]. The `events-worker` binary builds its own publisher from
+//! [`register_handlers`] and calls [`dispatch_pending`] in a loop. Workers claim events
+//! with a lease, so several can run side by side; an event whose worker stopped before
+//! dispatching it is claimed again once the lease lapses.
+
+use async_trait::async_trait;
+use entity_api::outbox_event;
+use events::{envelope, DomainEvent, EventPublisher, Outbox, RetryPolicy};
+use log::*;
+use sea_orm::DatabaseConnection;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::Error;
+use crate::event_log::AuditLogHandler;
+use crate::webhook_subscription::WebhookDeliveryHandler;
+use crate::Id;
+
+/// Stores each published event in the `outbox_events` table.
+pub struct OutboxStore {
+    db: Arc<DatabaseConnection>,
+}
+
+impl OutboxStore {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl Outbox for OutboxStore {
+    async fn enqueue(&self, event: &DomainEvent, actor_id: Option<Id>) {
+        let event_type = event.kind();
+        let result = match envelope::to_value(event) {
+            Ok(payload) => {
+                outbox_event::create(self.db.as_ref(), event_type.as_str(), payload, actor_id)
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| e.to_string())
+            }
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = result {
+            error!("Failed to add {event_type} event to the outbox: {e}");
+        }
+    }
+}
+
+/// Registers the handlers that don't need the publishing process (everything but SSE),
+/// so the web process and the events worker share one list.
+pub fn register_handlers(
+    event_publisher: EventPublisher,
+    db: &Arc<DatabaseConnection>,
+) -> EventPublisher {
+    event_publisher
+        .with_handler(Arc::new(AuditLogHandler::new(Arc::clone(db))))
+        // Retries each delivery itself: retrying the handler would redeliver to every
+        // subscription that already received the event.
+        .with_handler_policy(
+            Arc::new(WebhookDeliveryHandler::new(Arc::clone(db))),
+            RetryPolicy::none(),
+        )
+}
+
+/// Claims up to `batch_size` undispatched events for `worker_id` and publishes each on
+/// behalf of the user who published it originally. Returns how many were dispatched.
+///
+/// An event is marked dispatched once its handlers have run; handler failures are the
+/// publisher's to retry and dead-letter. An event whose payload can't be read is left
+/// claimed and logged, and is retried when its lease lapses.
+pub async fn dispatch_pending(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    worker_id: &str,
+    batch_size: u64,
+    lease: Duration,
+) -> Result<usize, Error> {
+    let claimed = outbox_event::claim(db, worker_id, batch_size, lease).await?;
+
+    let mut dispatched = 0;
+    for outbox_event in claimed {
+        let (event, correlation_id): (DomainEvent, _) =
+            match envelope::parse(outbox_event.payload.clone()).and_then(|envelope| {
+                let correlation_id = envelope.correlation_id;
+                Ok((envelope.into_event()?, correlation_id))
+            }) {
+                Ok(read) => read,
+                Err(e) => {
+                    warn!(
+                        "Outbox event {} ({}) has an unreadable payload: {e}",
+                        outbox_event.id, outbox_event.event_type
+                    );
+                    continue;
+                }
+            };
+
+        // Published under the correlation ID it was stored with, so it still traces back
+        // to the request that caused it.
+        events::with_correlation_id(correlation_id, async {
+            match outbox_event.actor_id {
+                Some(actor_id) => {
+                    events::with_actor(actor_id, event_publisher.publish(event)).await
+                }
+                None => event_publisher.publish(event).await,
+            }
+        })
+        .await;
+
+        outbox_event::mark_dispatched(db, outbox_event.id).await?;
+        dispatched += 1;
+    }
+
+    Ok(dispatched)
+}
+
+/// Deletes events dispatched more than `retention_days` ago, returning how many were
+/// deleted. Deletes none while a legal hold is active.
+pub async fn delete_dispatched(db: &DatabaseConnection, retention_days: i64) -> Result<u64, Error> {
+    if crate::legal_hold::purges_paused(db).await? {
+        info!("[outbox] a legal hold is active; keeping dispatched events");
+        return Ok(0);
+    }
+    let before = chrono::Utc::now() - chrono::Duration::days(retention_days);
+    Ok(outbox_event::delete_dispatched_before(db, before.into()).await?)
+}
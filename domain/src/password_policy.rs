@@ -7,19 +7,26 @@
 //! boundary.
 //!
 //! See `docs/architecture/password_reset.md` for the policy decision and
-//! the rationale (NIST 800-63B recommendations, no complexity rules).
+//! the rationale (NIST 800-63B recommendations, no complexity rules by default).
+//!
+//! The rules come from config: a minimum length, optional character classes, a
+//! deny-list of common passwords, and optionally a check against the passwords
+//! exposed in known breaches (HaveIBeenPwned's Pwned Passwords). A password that
+//! fails is rejected with every rule it failed, which `web` returns as a 422.
 //!
 //! Applied at:
 //! - [`crate::password_reset::complete_password_reset`]
 //! - [`crate::magic_link_token::complete_setup`]
+//! - [`crate::user::update_password`]
+//! - [`crate::user::create_by_organization`], when the new user is given a password
 
-use crate::error::{DomainErrorKind, Error};
+use std::collections::HashSet;
 
-/// Minimum password length in **characters** (Unicode scalar values).
-///
-/// 12 is the modern industry baseline. NIST 800-63B requires ≥8; 12 raises
-/// the bar against offline brute-force while remaining typeable by humans.
-pub const MIN_PASSWORD_LENGTH: usize = 12;
+use crate::error::{DomainErrorKind, Error};
+use aws_lc_rs::digest;
+use log::*;
+use serde::Serialize;
+use service::config::Config;
 
 /// Maximum password length in characters.
 ///
@@ -29,127 +36,452 @@ pub const MIN_PASSWORD_LENGTH: usize = 12;
 /// above any reasonable user password or password-manager output.
 pub const MAX_PASSWORD_LENGTH: usize = 128;
 
-/// Validate a candidate password against the BE-enforced policy.
-///
-/// Returns `Err(DomainErrorKind::Validation)` with a user-facing message
-/// when the password is rejected. Currently checks: non-empty after trim,
-/// length in `[MIN_PASSWORD_LENGTH, MAX_PASSWORD_LENGTH]`.
-///
-/// Deliberately does NOT enforce character-class complexity rules
-/// (uppercase / lowercase / digit / symbol). NIST 800-63B recommends
-/// *against* such rules — they push users toward predictable patterns
-/// (e.g. `Password1!`) that reduce real-world entropy. Length is the
-/// load-bearing dimension; raise the minimum if more entropy is needed.
-pub fn validate_password(password: &str) -> Result<(), Error> {
-    if password.trim().is_empty() {
-        return Err(Error {
-            source: None,
-            error_kind: DomainErrorKind::Validation(
-                "Password cannot be empty or whitespace".to_string(),
-            ),
-        });
-    }
-
-    let length = password.chars().count();
-    if length < MIN_PASSWORD_LENGTH {
-        return Err(Error {
-            source: None,
-            error_kind: DomainErrorKind::Validation(format!(
-                "Password must be at least {MIN_PASSWORD_LENGTH} characters"
-            )),
-        });
-    }
-    if length > MAX_PASSWORD_LENGTH {
-        return Err(Error {
-            source: None,
-            error_kind: DomainErrorKind::Validation(format!(
-                "Password must be at most {MAX_PASSWORD_LENGTH} characters"
-            )),
-        });
-    }
-
-    Ok(())
+/// Common passwords refused whatever the config adds, compared case-insensitively.
+/// Mostly ones long enough to pass the length rule, which is what makes them worth
+/// listing.
+const COMMON_PASSWORDS: &[&str] = &[
+    "password",
+    "password1",
+    "password12",
+    "password123",
+    "password1234",
+    "password12345",
+    "passwordpassword",
+    "12345678",
+    "123456789",
+    "1234567890",
+    "12345678910",
+    "123456789012",
+    "1234567890123",
+    "11111111",
+    "111111111111",
+    "000000000000",
+    "qwerty123",
+    "qwertyuiop",
+    "qwerty123456",
+    "qwertyuiopasdf",
+    "1q2w3e4r5t6y",
+    "1qaz2wsx3edc",
+    "iloveyou",
+    "iloveyou123",
+    "letmein123",
+    "welcome123",
+    "welcome12345",
+    "changeme",
+    "changeme123",
+    "admin123",
+    "administrator",
+    "abc12345",
+    "abcdefghijkl",
+    "trustno1",
+    "sunshine123",
+    "football123",
+    "baseball123",
+    "princess123",
+    "refactorplatform",
+];
+
+/// Longest the breach check waits for Pwned Passwords before letting the password through.
+const BREACH_CHECK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// A rule of the password policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PasswordRule {
+    NotBlank,
+    MinLength,
+    MaxLength,
+    Lowercase,
+    Uppercase,
+    Digit,
+    Symbol,
+    NotCommon,
+    NotBreached,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl PasswordRule {
+    /// The character class rule named in config, if it is one
+    fn character_class(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "lowercase" => Some(Self::Lowercase),
+            "uppercase" => Some(Self::Uppercase),
+            "digit" => Some(Self::Digit),
+            "symbol" => Some(Self::Symbol),
+            _ => None,
+        }
+    }
 
-    #[test]
-    fn rejects_empty_password() {
-        let err = validate_password("").expect_err("empty password must be rejected");
-        match err.error_kind {
-            DomainErrorKind::Validation(msg) => {
-                assert!(msg.contains("empty") || msg.contains("whitespace"));
+    /// Whether `c` belongs to the character class this rule asks for
+    fn matches(self, c: char) -> bool {
+        match self {
+            Self::Lowercase => c.is_lowercase(),
+            Self::Uppercase => c.is_uppercase(),
+            Self::Digit => c.is_numeric(),
+            Self::Symbol => !c.is_alphanumeric() && !c.is_whitespace(),
+            _ => false,
+        }
+    }
+}
+
+/// A rule a password failed, and what it asks for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PasswordRuleFailure {
+    pub rule: PasswordRule,
+    pub message: String,
+}
+
+impl PasswordRuleFailure {
+    fn new(rule: PasswordRule, message: impl Into<String>) -> Self {
+        Self {
+            rule,
+            message: message.into(),
+        }
+    }
+}
+
+/// The password rules in force, from config
+#[derive(Debug, Clone)]
+pub struct PasswordPolicy {
+    min_length: usize,
+    required_classes: Vec<PasswordRule>,
+    /// Lowercased
+    deny_list: HashSet<String>,
+    /// Pwned Passwords API base URL, when breached passwords are refused
+    breach_check_url: Option<String>,
+}
+
+impl PasswordPolicy {
+    pub fn from_config(config: &Config) -> Self {
+        let mut required_classes = Vec::new();
+        for name in config.password_required_character_classes() {
+            match PasswordRule::character_class(name) {
+                Some(class) if !required_classes.contains(&class) => required_classes.push(class),
+                _ => {}
             }
-            other => panic!("expected Validation, got {other:?}"),
+        }
+
+        let deny_list = COMMON_PASSWORDS
+            .iter()
+            .map(|password| password.to_string())
+            .chain(
+                config
+                    .password_deny_list()
+                    .iter()
+                    .map(|password| password.trim().to_lowercase()),
+            )
+            .filter(|password| !password.is_empty())
+            .collect();
+
+        Self {
+            min_length: config.password_min_length().clamp(1, MAX_PASSWORD_LENGTH),
+            required_classes,
+            deny_list,
+            breach_check_url: config
+                .pwned_passwords_api_url()
+                .map(|url| url.trim_end_matches('/').to_string()),
         }
     }
 
-    #[test]
-    fn rejects_whitespace_only_password() {
-        // Various whitespace combinations
-        for password in ["   ", "\t", "\n", " \t \n "] {
-            let err =
-                validate_password(password).expect_err("whitespace-only password must be rejected");
-            assert!(
-                matches!(err.error_kind, DomainErrorKind::Validation(_)),
-                "expected Validation for {password:?}"
-            );
+    /// The rules `password` fails, short of the breach check. A blank password only
+    /// fails [`PasswordRule::NotBlank`].
+    pub fn check(&self, password: &str) -> Vec<PasswordRuleFailure> {
+        if password.trim().is_empty() {
+            return vec![PasswordRuleFailure::new(
+                PasswordRule::NotBlank,
+                "Password cannot be empty or whitespace",
+            )];
+        }
+
+        let mut failures = Vec::new();
+        let length = password.chars().count();
+        if length < self.min_length {
+            failures.push(PasswordRuleFailure::new(
+                PasswordRule::MinLength,
+                format!("Password must be at least {} characters", self.min_length),
+            ));
+        }
+        if length > MAX_PASSWORD_LENGTH {
+            failures.push(PasswordRuleFailure::new(
+                PasswordRule::MaxLength,
+                format!("Password must be at most {MAX_PASSWORD_LENGTH} characters"),
+            ));
+        }
+
+        for &class in &self.required_classes {
+            if !password.chars().any(|c| class.matches(c)) {
+                let name = match class {
+                    PasswordRule::Lowercase => "a lowercase letter",
+                    PasswordRule::Uppercase => "an uppercase letter",
+                    PasswordRule::Digit => "a digit",
+                    _ => "a symbol",
+                };
+                failures.push(PasswordRuleFailure::new(
+                    class,
+                    format!("Password must contain {name}"),
+                ));
+            }
+        }
+
+        if self.deny_list.contains(&password.to_lowercase()) {
+            failures.push(PasswordRuleFailure::new(
+                PasswordRule::NotCommon,
+                "Password is too common",
+            ));
         }
+
+        failures
     }
 
-    #[test]
-    fn rejects_too_short_password() {
-        // 11 chars — one below the minimum
-        let err = validate_password("12345678901").expect_err("11-char password must be rejected");
-        match err.error_kind {
-            DomainErrorKind::Validation(msg) => {
-                assert!(
-                    msg.contains("12"),
-                    "error message must name the policy: {msg}"
-                );
+    /// Rejects `password` with every rule it fails. The breach check only runs once
+    /// the other rules pass, and lets the password through if Pwned Passwords can't
+    /// be reached: an outage there shouldn't lock users out of setting passwords.
+    pub async fn enforce(&self, password: &str) -> Result<(), Error> {
+        let mut failures = self.check(password);
+
+        if failures.is_empty() {
+            if let Some(url) = &self.breach_check_url {
+                match breach_count(url, password).await {
+                    Ok(0) => {}
+                    Ok(count) => {
+                        info!("Refusing a password found in {count} breaches");
+                        failures.push(PasswordRuleFailure::new(
+                            PasswordRule::NotBreached,
+                            "Password has appeared in a data breach; choose another",
+                        ));
+                    }
+                    Err(e) => warn!("Skipping the breached-password check: {e}"),
+                }
             }
-            other => panic!("expected Validation, got {other:?}"),
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(Error {
+                source: None,
+                error_kind: DomainErrorKind::PasswordPolicy(failures),
+            })
         }
     }
+}
+
+/// Enforces the configured password policy on a new password.
+pub async fn enforce(config: &Config, password: &str) -> Result<(), Error> {
+    PasswordPolicy::from_config(config).enforce(password).await
+}
+
+/// How many times `password` appears in Pwned Passwords. Uses the k-anonymity range
+/// API: only the first 5 hex characters of the password's SHA-1 are sent, and the
+/// rest is matched against the suffixes returned (padded, so the response size
+/// doesn't give the prefix's popularity away).
+async fn breach_count(base_url: &str, password: &str) -> Result<u64, reqwest::Error> {
+    let hash = hex::encode_upper(digest::digest(
+        &digest::SHA1_FOR_LEGACY_USE_ONLY,
+        password.as_bytes(),
+    ));
+    let (prefix, suffix) = hash.split_at(5);
+
+    let body = reqwest::Client::new()
+        .get(format!("{base_url}/range/{prefix}"))
+        .header("Add-Padding", "true")
+        .timeout(BREACH_CHECK_TIMEOUT)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+
+    Ok(body
+        .lines()
+        .filter_map(|line| line.trim().split_once(':'))
+        .find(|(candidate, _)| candidate.eq_ignore_ascii_case(suffix))
+        .and_then(|(_, count)| count.trim().parse().ok())
+        .unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Server;
+
+    fn policy(args: &[&str]) -> PasswordPolicy {
+        let args = std::iter::once("test").chain(args.iter().copied());
+        PasswordPolicy::from_config(&Config::from_args(args))
+    }
+
+    fn failed(policy: &PasswordPolicy, password: &str) -> Vec<PasswordRule> {
+        policy
+            .check(password)
+            .into_iter()
+            .map(|failure| failure.rule)
+            .collect()
+    }
 
     #[test]
-    fn accepts_password_at_minimum_length() {
-        // exactly 12 chars
-        validate_password("123456789012").expect("12-char password must be accepted");
+    fn rejects_empty_and_whitespace_only_passwords() {
+        let policy = policy(&[]);
+        for password in ["", "   ", "\t", "\n", " \t \n "] {
+            assert_eq!(
+                failed(&policy, password),
+                [PasswordRule::NotBlank],
+                "{password:?}"
+            );
+        }
     }
 
     #[test]
-    fn accepts_password_at_maximum_length() {
-        let password: String = "x".repeat(MAX_PASSWORD_LENGTH);
-        validate_password(&password).expect("password at MAX_PASSWORD_LENGTH must be accepted");
+    fn enforces_the_configured_minimum_length() {
+        let default = policy(&[]);
+        let failures = default.check("12345678901");
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].rule, PasswordRule::MinLength);
+        assert!(
+            failures[0].message.contains("12"),
+            "error message must name the policy: {}",
+            failures[0].message
+        );
+        assert!(default.check("correct horse").is_empty());
+
+        let longer = policy(&["--password-min-length=16"]);
+        assert_eq!(failed(&longer, "correct horse"), [PasswordRule::MinLength]);
     }
 
     #[test]
     fn rejects_too_long_password() {
         let password: String = "x".repeat(MAX_PASSWORD_LENGTH + 1);
-        let err = validate_password(&password).expect_err("over-max password must be rejected");
-        match err.error_kind {
-            DomainErrorKind::Validation(msg) => {
-                assert!(
-                    msg.contains("128"),
-                    "error message must name the policy: {msg}"
-                );
-            }
-            other => panic!("expected Validation, got {other:?}"),
-        }
+        let failures = policy(&[]).check(&password);
+        assert_eq!(failures[0].rule, PasswordRule::MaxLength);
+        assert!(failures[0].message.contains("128"));
+
+        let password: String = "x".repeat(MAX_PASSWORD_LENGTH);
+        assert!(policy(&[]).check(&password).is_empty());
     }
 
     #[test]
     fn unicode_password_counts_by_char_not_byte() {
         // 12 emoji = 12 chars, but ~48 bytes (each emoji is 4 bytes in UTF-8).
-        // The check must use char-count, so this must be ACCEPTED (12 chars >= 12 min).
-        let twelve_emoji: String = "🔐".repeat(12);
-        validate_password(&twelve_emoji).expect("12 unicode scalar values must be accepted");
+        let policy = policy(&[]);
+        assert!(policy.check(&"🔐".repeat(12)).is_empty());
+        assert_eq!(failed(&policy, &"🔐".repeat(11)), [PasswordRule::MinLength]);
+    }
+
+    #[test]
+    fn character_classes_are_only_required_when_configured() {
+        assert!(policy(&[]).check("all lowercase words").is_empty());
+
+        let strict = policy(&["--password-required-character-classes=uppercase,digit,symbol"]);
+        assert_eq!(
+            failed(&strict, "all lowercase words"),
+            [
+                PasswordRule::Uppercase,
+                PasswordRule::Digit,
+                PasswordRule::Symbol
+            ]
+        );
+        assert!(strict.check("All lowercase w0rds!").is_empty());
+    }
+
+    #[test]
+    fn every_failed_rule_is_reported() {
+        let strict = policy(&["--password-required-character-classes=digit"]);
+        assert_eq!(
+            failed(&strict, "short"),
+            [PasswordRule::MinLength, PasswordRule::Digit]
+        );
+    }
+
+    #[test]
+    fn deny_list_is_built_in_and_extended_by_config() {
+        let policy = policy(&["--password-deny-list=Our Company Name,another one"]);
+        assert_eq!(failed(&policy, "Password1234"), [PasswordRule::NotCommon]);
+        assert_eq!(
+            failed(&policy, "our company name"),
+            [PasswordRule::NotCommon]
+        );
+        assert!(policy.check("not on any list").is_empty());
+    }
+
+    #[tokio::test]
+    async fn enforce_rejects_with_the_failed_rules() {
+        let err = policy(&[])
+            .enforce("short")
+            .await
+            .expect_err("short password must be rejected");
+
+        match err.error_kind {
+            DomainErrorKind::PasswordPolicy(failures) => {
+                assert_eq!(failures[0].rule, PasswordRule::MinLength);
+            }
+            other => panic!("expected PasswordPolicy, got {other:?}"),
+        }
+    }
+
+    /// A Pwned Passwords server answering `password`'s hash prefix with `count` for its
+    /// suffix, among padding, and the policy checking against it
+    async fn pwned_passwords(password: &str, count: u64) -> (mockito::ServerGuard, PasswordPolicy) {
+        let hash = hex::encode_upper(digest::digest(
+            &digest::SHA1_FOR_LEGACY_USE_ONLY,
+            password.as_bytes(),
+        ));
+        let (prefix, suffix) = hash.split_at(5);
+
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", format!("/range/{prefix}").as_str())
+            .match_header("add-padding", "true")
+            .with_body(format!(
+                "0018A45C4D1DEF81644B54AB7F969B88D65:0\r\n{suffix}:{count}\r\n"
+            ))
+            .create_async()
+            .await;
+        let policy = policy(&[
+            "--password-breach-check-enabled",
+            &format!("--pwned-passwords-api-url={}", server.url()),
+        ]);
+        (server, policy)
+    }
+
+    #[tokio::test]
+    async fn breached_passwords_are_rejected_by_hash_prefix() {
+        let (_server, policy) = pwned_passwords("correct horse battery", 42).await;
+
+        let err = policy
+            .enforce("correct horse battery")
+            .await
+            .expect_err("breached password must be rejected");
+        assert!(matches!(
+            err.error_kind,
+            DomainErrorKind::PasswordPolicy(ref failures)
+                if failures[0].rule == PasswordRule::NotBreached
+        ));
+    }
+
+    #[tokio::test]
+    async fn padding_entries_are_not_breaches() {
+        let (_server, policy) = pwned_passwords("correct horse battery", 0).await;
+
+        policy
+            .enforce("correct horse battery")
+            .await
+            .expect("a suffix listed with a count of 0 is padding");
+    }
+
+    #[tokio::test]
+    async fn breach_check_outage_lets_the_password_through() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("GET", mockito::Matcher::Any)
+            .with_status(503)
+            .create_async()
+            .await;
+        let policy = policy(&[
+            "--password-breach-check-enabled",
+            &format!("--pwned-passwords-api-url={}", server.url()),
+        ]);
 
-        // 11 emoji = 11 chars — must be REJECTED (below min).
-        let eleven_emoji: String = "🔐".repeat(11);
-        validate_password(&eleven_emoji).expect_err("11 unicode scalar values must be rejected");
+        policy
+            .enforce("correct horse battery")
+            .await
+            .expect("an unreachable breach check must not block the password");
     }
 }
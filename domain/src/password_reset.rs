@@ -293,6 +293,7 @@ pub async fn validate_reset_token(
 /// transaction. On failure, no state changes.
 pub async fn complete_password_reset(
     db: &DatabaseConnection,
+    config: &Config,
     params: impl mutate::IntoUpdateMap,
 ) -> Result<users::Model, Error> {
     let mut params = params.into_update_map();
@@ -313,7 +314,7 @@ pub async fn complete_password_reset(
 
     // Server-side policy enforcement — independent of any FE validation.
     // See `domain::password_policy` for the rules.
-    crate::password_policy::validate_password(&password)?;
+    crate::password_policy::enforce(config, &password).await?;
 
     params.insert(
         "password".to_string(),
@@ -334,6 +335,8 @@ pub async fn complete_password_reset(
 
     entity_api::magic_link_token::delete_all_for_user(&txn, user.id, TokenPurpose::PasswordReset)
         .await?;
+    // Sign out the browsers "remember me" kept logged in, which may be the attacker's
+    entity_api::remembered_device::delete_all_for_user(&txn, user.id).await?;
 
     let active_model = user.into_active_model();
     let updated_user =
@@ -433,6 +436,8 @@ async fn enforce_rate_limit(
 ///
 /// Returns `Err(Validation)` if `retention_days < 1` (would purge records
 /// still needed for the 24-hour daily-cap window).
+///
+/// Deletes nothing while a legal hold is active (see `crate::legal_hold`).
 pub async fn sweep_old_attempts(
     db: &DatabaseConnection,
     retention_days: i64,
@@ -447,6 +452,11 @@ pub async fn sweep_old_attempts(
         });
     }
 
+    if crate::legal_hold::purges_paused(db).await? {
+        info!("[password-reset] a legal hold is active; skipping the attempt sweep");
+        return Ok(0);
+    }
+
     let cutoff = (Utc::now() - ChronoDuration::days(retention_days)).into();
     let deleted = entity_api::password_reset_attempt::delete_older_than(db, cutoff).await?;
 
@@ -493,6 +503,7 @@ fn rate_limited_error() -> Error {
 mod tests {
     use super::*;
     use crate::magic_link_tokens;
+    use crate::password_policy::PasswordRule;
     use crate::users;
     use chrono::Duration as ChronoDuration;
     use sea_orm::{DatabaseBackend, MockDatabase};
@@ -802,6 +813,14 @@ mod tests {
         }
     }
 
+    /// Scalar row for the active legal holds `.count()` the sweep runs first.
+    fn active_legal_holds(n: i64) -> std::collections::BTreeMap<String, sea_orm::Value> {
+        std::collections::BTreeMap::from([(
+            "num_items".to_owned(),
+            sea_orm::Value::BigInt(Some(n)),
+        )])
+    }
+
     /// Confirm the sweep delegates to the entity_api correctly and returns
     /// the count from the underlying delete. Uses MockDatabase's exec result
     /// to simulate the DELETE's `rows_affected`.
@@ -810,6 +829,7 @@ mod tests {
         use sea_orm::MockExecResult;
 
         let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([[active_legal_holds(0)]])
             .append_exec_results(vec![MockExecResult {
                 last_insert_id: 0,
                 rows_affected: 42,
@@ -823,6 +843,25 @@ mod tests {
         assert_eq!(count, 42, "sweep must return rows_affected from the DELETE");
     }
 
+    /// While a legal hold is active the sweep deletes nothing.
+    #[tokio::test]
+    async fn sweep_old_attempts_pauses_under_a_legal_hold() {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([[active_legal_holds(1)]])
+            .into_connection();
+
+        let count = sweep_old_attempts(&db, 30)
+            .await
+            .expect("a paused sweep must succeed");
+
+        assert_eq!(count, 0);
+        assert_eq!(
+            db.into_transaction_log().len(),
+            1,
+            "only the legal hold count may run"
+        );
+    }
+
     /// Hard signal-ceiling: `request_password_reset` must always take at
     /// least `HANDLER_TARGET_DURATION_MS` wall-clock time. Combined with
     /// the spawn-everything-path-distinguishing structural change, this
@@ -887,7 +926,7 @@ mod tests {
     // _HANDLER_TARGET_DURATION_BOUNDS_CHECK at module scope.
 
     /// Server-side password policy must fire from `complete_password_reset`,
-    /// independently of any FE validation. Empty password → 422 Validation,
+    /// independently of any FE validation. Empty password → 422 PasswordPolicy,
     /// even if the FE bug or malicious client lets one through.
     #[tokio::test]
     async fn complete_password_reset_rejects_empty_password() {
@@ -913,18 +952,15 @@ mod tests {
             }
         }
 
-        let err = complete_password_reset(&db, P(params))
+        let err = complete_password_reset(&db, &Config::default(), P(params))
             .await
             .expect_err("empty password must be rejected before token validation");
 
         match err.error_kind {
-            DomainErrorKind::Validation(msg) => {
-                assert!(
-                    msg.contains("empty") || msg.contains("whitespace"),
-                    "expected empty/whitespace message, got: {msg}"
-                );
+            DomainErrorKind::PasswordPolicy(failures) => {
+                assert_eq!(failures[0].rule, PasswordRule::NotBlank);
             }
-            other => panic!("expected Validation, got {other:?}"),
+            other => panic!("expected PasswordPolicy, got {other:?}"),
         }
     }
 
@@ -954,18 +990,20 @@ mod tests {
             }
         }
 
-        let err = complete_password_reset(&db, P(params))
+        let err = complete_password_reset(&db, &Config::default(), P(params))
             .await
             .expect_err("8-char password must be rejected by min-length policy");
 
         match err.error_kind {
-            DomainErrorKind::Validation(msg) => {
+            DomainErrorKind::PasswordPolicy(failures) => {
+                assert_eq!(failures[0].rule, PasswordRule::MinLength);
                 assert!(
-                    msg.contains("12"),
-                    "error message must name the minimum length: {msg}"
+                    failures[0].message.contains("12"),
+                    "error message must name the minimum length: {}",
+                    failures[0].message
                 );
             }
-            other => panic!("expected Validation, got {other:?}"),
+            other => panic!("expected PasswordPolicy, got {other:?}"),
         }
     }
 }
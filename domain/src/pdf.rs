@@ -0,0 +1,450 @@
+//! A minimal PDF writer for the documents the platform produces, such as session
+//! records.
+//!
+//! Documents are flowing text: titles, headings, paragraphs and bulleted items, wrapped
+//! to a US Letter page and paginated with a footer. Text is set in the standard
+//! Helvetica fonts, which every PDF reader provides, so nothing is embedded; characters
+//! outside their (Windows-1252) encoding are printed as `?`.
+
+/// US Letter, in points.
+const PAGE_WIDTH: f32 = 612.0;
+const PAGE_HEIGHT: f32 = 792.0;
+const MARGIN: f32 = 72.0;
+/// Space kept free above the bottom margin for the footer.
+const FOOTER_SPACE: f32 = 24.0;
+const BULLET_INDENT: f32 = 14.0;
+const LINE_SPACING: f32 = 1.35;
+
+/// Widths of the printable ASCII characters in Helvetica, in thousandths of the font
+/// size (from its Adobe font metrics).
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, // ' '../
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, // 0..?
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778, // @..O
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556, // P.._
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556, // `..o
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584, // p..~
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Style {
+    font: Font,
+    size: f32,
+    /// Space above the block.
+    space_before: f32,
+}
+
+const TITLE: Style = Style {
+    font: Font::Bold,
+    size: 18.0,
+    space_before: 0.0,
+};
+const HEADING: Style = Style {
+    font: Font::Bold,
+    size: 13.0,
+    space_before: 16.0,
+};
+const SUBHEADING: Style = Style {
+    font: Font::Bold,
+    size: 10.5,
+    space_before: 8.0,
+};
+const BODY: Style = Style {
+    font: Font::Regular,
+    size: 10.5,
+    space_before: 4.0,
+};
+const FOOTER: Style = Style {
+    font: Font::Regular,
+    size: 8.0,
+    space_before: 0.0,
+};
+
+enum Block {
+    Text { style: Style, text: String },
+    Item(String),
+}
+
+/// A line placed on a page.
+struct Line {
+    font: Font,
+    size: f32,
+    x: f32,
+    y: f32,
+    text: String,
+}
+
+/// A document under construction; [`Document::render`] lays it out.
+pub struct Document {
+    title: String,
+    blocks: Vec<Block>,
+}
+
+impl Document {
+    /// A document whose metadata and page footers carry `title`.
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            blocks: Vec::new(),
+        }
+    }
+
+    /// Large bold text, e.g. the document's title on its first page.
+    pub fn title(&mut self, text: impl Into<String>) -> &mut Self {
+        self.text(TITLE, text)
+    }
+
+    /// Starts a section.
+    pub fn heading(&mut self, text: impl Into<String>) -> &mut Self {
+        self.text(HEADING, text)
+    }
+
+    /// Bold body text, e.g. the label of a group of paragraphs.
+    pub fn subheading(&mut self, text: impl Into<String>) -> &mut Self {
+        self.text(SUBHEADING, text)
+    }
+
+    pub fn paragraph(&mut self, text: impl Into<String>) -> &mut Self {
+        self.text(BODY, text)
+    }
+
+    /// A bulleted item.
+    pub fn item(&mut self, text: impl Into<String>) -> &mut Self {
+        self.blocks.push(Block::Item(text.into()));
+        self
+    }
+
+    fn text(&mut self, style: Style, text: impl Into<String>) -> &mut Self {
+        self.blocks.push(Block::Text {
+            style,
+            text: text.into(),
+        });
+        self
+    }
+
+    /// The document as a PDF file.
+    pub fn render(&self) -> Vec<u8> {
+        let pages = self.layout();
+        let page_count = pages.len();
+
+        let mut objects: Vec<Vec<u8>> = vec![
+            b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+            Vec::new(), // the page tree, once the pages are numbered
+            font_object("Helvetica"),
+            font_object("Helvetica-Bold"),
+            [
+                b"<< /Title ".as_slice(),
+                &literal(&self.title),
+                b" /Producer (Refactor Platform) >>",
+            ]
+            .concat(),
+        ];
+        let mut kids = Vec::with_capacity(page_count);
+        for (index, mut lines) in pages.into_iter().enumerate() {
+            lines.push(self.footer(index + 1, page_count));
+            let content = content_stream(&lines);
+            let page_id = objects.len() + 1;
+            kids.push(format!("{page_id} 0 R"));
+            objects.push(
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAGE_WIDTH} {PAGE_HEIGHT}] \
+                     /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                    page_id + 1
+                )
+                .into_bytes(),
+            );
+            objects.push(
+                [
+                    format!("<< /Length {} >>\nstream\n", content.len()).as_bytes(),
+                    &content,
+                    b"\nendstream",
+                ]
+                .concat(),
+            );
+        }
+        objects[1] = format!(
+            "<< /Type /Pages /Kids [{}] /Count {page_count} >>",
+            kids.join(" ")
+        )
+        .into_bytes();
+
+        let mut pdf = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.extend_from_slice(format!("{} 0 obj\n", index + 1).as_bytes());
+            pdf.extend_from_slice(object);
+            pdf.extend_from_slice(b"\nendobj\n");
+        }
+        let xref_offset = pdf.len();
+        pdf.extend_from_slice(
+            format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes(),
+        );
+        for offset in offsets {
+            pdf.extend_from_slice(format!("{offset:010} 00000 n \n").as_bytes());
+        }
+        pdf.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R /Info 5 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n",
+                objects.len() + 1
+            )
+            .as_bytes(),
+        );
+        pdf
+    }
+
+    /// The blocks wrapped into lines and the lines split into pages (always at least one).
+    fn layout(&self) -> Vec<Vec<Line>> {
+        let top = PAGE_HEIGHT - MARGIN;
+        let bottom = MARGIN + FOOTER_SPACE;
+        let width = PAGE_WIDTH - 2.0 * MARGIN;
+
+        let mut pages = vec![Vec::new()];
+        let mut y = top;
+        for (index, block) in self.blocks.iter().enumerate() {
+            let (style, wrapped, x) = match block {
+                Block::Text { style, text } => (*style, wrap(text, style, width), MARGIN),
+                Block::Item(text) => (
+                    BODY,
+                    wrap(text, &BODY, width - BULLET_INDENT),
+                    MARGIN + BULLET_INDENT,
+                ),
+            };
+            let line_height = style.size * LINE_SPACING;
+            // Headings are kept with at least two lines of what follows them
+            let keep = if style.font == Font::Bold && index + 1 < self.blocks.len() {
+                2.0 * BODY.size * LINE_SPACING
+            } else {
+                0.0
+            };
+
+            if y < top {
+                y -= style.space_before;
+            }
+            if y - line_height - keep < bottom && y < top {
+                pages.push(Vec::new());
+                y = top;
+            }
+            for (number, text) in wrapped.into_iter().enumerate() {
+                if y - line_height < bottom {
+                    pages.push(Vec::new());
+                    y = top;
+                }
+                y -= line_height;
+                let page = pages.last_mut().expect("there is always a page");
+                if number == 0 && matches!(block, Block::Item(_)) {
+                    page.push(Line {
+                        font: Font::Regular,
+                        size: style.size,
+                        x: MARGIN + 2.0,
+                        y,
+                        text: "\u{2022}".to_string(),
+                    });
+                }
+                page.push(Line {
+                    font: style.font,
+                    size: style.size,
+                    x,
+                    y,
+                    text,
+                });
+            }
+        }
+        pages
+    }
+
+    fn footer(&self, page: usize, page_count: usize) -> Line {
+        Line {
+            font: FOOTER.font,
+            size: FOOTER.size,
+            x: MARGIN,
+            y: MARGIN / 2.0,
+            text: format!("{} \u{2014} page {page} of {page_count}", self.title),
+        }
+    }
+}
+
+fn font_object(base_font: &str) -> Vec<u8> {
+    format!("<< /Type /Font /Subtype /Type1 /BaseFont /{base_font} /Encoding /WinAnsiEncoding >>")
+        .into_bytes()
+}
+
+fn content_stream(lines: &[Line]) -> Vec<u8> {
+    let mut content = Vec::new();
+    for line in lines {
+        content.extend_from_slice(
+            format!(
+                "BT /{} {} Tf {:.2} {:.2} Td ",
+                line.font.resource(),
+                line.size,
+                line.x,
+                line.y
+            )
+            .as_bytes(),
+        );
+        content.extend_from_slice(&literal(&line.text));
+        content.extend_from_slice(b" Tj ET\n");
+    }
+    content
+}
+
+/// `text` split at whitespace into lines at most `width` points wide; words wider than
+/// a line are split wherever they must be. Blank text takes one (empty) line.
+fn wrap(text: &str, style: &Style, width: f32) -> Vec<String> {
+    let space = text_width(" ", style);
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut line_width = 0.0;
+    for word in text.split_whitespace() {
+        let word_width = text_width(word, style);
+        if !line.is_empty() && line_width + space + word_width <= width {
+            line.push(' ');
+            line.push_str(word);
+            line_width += space + word_width;
+            continue;
+        }
+        if !line.is_empty() {
+            lines.push(std::mem::take(&mut line));
+        }
+        line_width = 0.0;
+        for c in word.chars() {
+            let c_width = char_width(c, style);
+            if !line.is_empty() && line_width + c_width > width {
+                lines.push(std::mem::take(&mut line));
+                line_width = 0.0;
+            }
+            line.push(c);
+            line_width += c_width;
+        }
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+fn text_width(text: &str, style: &Style) -> f32 {
+    text.chars().map(|c| char_width(c, style)).sum()
+}
+
+fn char_width(c: char, style: &Style) -> f32 {
+    let thousandths = match c {
+        ' '..='~' => HELVETICA_WIDTHS[c as usize - 0x20],
+        _ => 556,
+    };
+    // Bold glyphs run a little wider; erring wide keeps lines inside the margin
+    let bold = if style.font == Font::Bold { 1.08 } else { 1.0 };
+    f32::from(thousandths) * style.size * bold / 1000.0
+}
+
+/// `text` as a PDF literal string in the fonts' Windows-1252 encoding.
+fn literal(text: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(text.len() + 2);
+    bytes.push(b'(');
+    for c in text.chars() {
+        let byte = encode(c);
+        if matches!(byte, b'(' | b')' | b'\\') {
+            bytes.push(b'\\');
+        }
+        bytes.push(byte);
+    }
+    bytes.push(b')');
+    bytes
+}
+
+fn encode(c: char) -> u8 {
+    match c {
+        ' '..='~' | '\u{A0}'..='\u{FF}' => c as u8,
+        '\u{20AC}' => 0x80,
+        '\u{2026}' => 0x85,
+        '\u{2018}' => 0x91,
+        '\u{2019}' => 0x92,
+        '\u{201C}' => 0x93,
+        '\u{201D}' => 0x94,
+        '\u{2022}' => 0x95,
+        '\u{2013}' => 0x96,
+        '\u{2014}' => 0x97,
+        '\u{2122}' => 0x99,
+        _ => b'?',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack
+            .windows(needle.len())
+            .any(|window| window == needle)
+    }
+
+    #[test]
+    fn render_produces_a_well_formed_pdf() {
+        let mut document = Document::new("Record (draft)");
+        document
+            .title("Session record")
+            .heading("Agreements")
+            .item("Meet weekly \u{2014} \u{201C}no exceptions\u{201D} \u{4E2D}");
+        let pdf = document.render();
+
+        assert!(pdf.starts_with(b"%PDF-1.4\n"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+        assert!(contains(&pdf, b"/Title (Record \\(draft\\))"));
+        assert!(contains(
+            &pdf,
+            b"(Meet weekly \x97 \x93no exceptions\x94 ?) Tj"
+        ));
+        assert!(contains(&pdf, b"(Record \\(draft\\) \x97 page 1 of 1) Tj"));
+
+        // Every object sits where the cross-reference table says it does
+        let tail = std::str::from_utf8(&pdf[pdf.len() - 32..]).unwrap();
+        let startxref: usize = tail
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|tail| tail.lines().next())
+            .and_then(|offset| offset.parse().ok())
+            .unwrap();
+        let xref = std::str::from_utf8(&pdf[startxref..]).unwrap();
+        assert!(xref.starts_with("xref\n0 8\n"));
+        for (index, entry) in xref.lines().skip(3).take(7).enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(format!("{} 0 obj\n", index + 1).as_bytes()));
+        }
+    }
+
+    #[test]
+    fn long_text_wraps_within_the_margins_and_paginates() {
+        let paragraph = "The coachee agreed to practice delegating decisions. ".repeat(40);
+        let mut document = Document::new("Record");
+        for _ in 0..5 {
+            document.paragraph(paragraph.clone());
+        }
+        let pages = document.layout();
+
+        assert!(pages.len() > 1);
+        let width = PAGE_WIDTH - 2.0 * MARGIN;
+        for line in pages.iter().flatten() {
+            assert!(text_width(&line.text, &BODY) <= width);
+            assert!(line.y >= MARGIN + FOOTER_SPACE);
+        }
+        // 19 five-and-a-quarter point "x"s fit in 100 points
+        assert_eq!(wrap(&"x".repeat(200), &BODY, 100.0).len(), 11);
+        assert_eq!(wrap("  ", &BODY, 100.0), vec![String::new()]);
+    }
+}
@@ -0,0 +1,97 @@
+//! The platform's settings and its first-run bootstrap.
+//!
+//! A self-hosted platform starts with an empty database. Its operator sets it up once
+//! through POST /bootstrap, which creates the first SuperAdmin, the first organization
+//! and these settings, and then turns itself off for good.
+
+use crate::error::Error;
+use crate::{organizations, platform_settings, users};
+use chrono_tz::Tz;
+use sea_orm::DatabaseConnection;
+use service::config::Config;
+
+pub use entity_api::platform_setting::{find, is_bootstrappable, Bootstrapped};
+
+/// Bootstraps the platform with `user` as its SuperAdmin, the admin of the new
+/// `organization`, and `settings` as its settings, once they validate.
+pub async fn bootstrap(
+    db: &DatabaseConnection,
+    config: &Config,
+    user: users::Model,
+    organization: organizations::Model,
+    settings: platform_settings::Model,
+) -> Result<Bootstrapped, Error> {
+    if settings.platform_name.trim().is_empty() {
+        return Err(Error::validation("platform_name must not be empty"));
+    }
+    if settings.default_timezone.parse::<Tz>().is_err() {
+        return Err(Error::validation(format!(
+            "default_timezone {:?} is not an IANA timezone",
+            settings.default_timezone
+        )));
+    }
+    let Some(password) = user.password.as_deref() else {
+        return Err(Error::validation("The SuperAdmin needs a password"));
+    };
+    crate::password_policy::enforce(config, password).await?;
+
+    Ok(entity_api::platform_setting::bootstrap(db, user, organization, settings).await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::DomainErrorKind;
+
+    fn user(password: Option<&str>) -> users::Model {
+        serde_json::from_value(serde_json::json!({
+            "email": "admin@example.com",
+            "first_name": "Ada",
+            "last_name": "Admin",
+            "display_name": null,
+            "password": password,
+            "github_username": null,
+            "github_profile_url": null,
+        }))
+        .unwrap()
+    }
+
+    fn organization() -> organizations::Model {
+        serde_json::from_value(serde_json::json!({"name": "Acme", "logo": null})).unwrap()
+    }
+
+    fn settings(default_timezone: &str) -> platform_settings::Model {
+        serde_json::from_value(serde_json::json!({
+            "platform_name": "Acme Coaching",
+            "default_timezone": default_timezone,
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn bootstrap_validates_before_touching_the_database() {
+        let db = DatabaseConnection::Disconnected;
+        let config = Config::default();
+
+        for (user, settings) in [
+            (
+                user(Some("a long passphrase")),
+                settings("Mars/Olympus_Mons"),
+            ),
+            (user(None), settings("America/Chicago")),
+        ] {
+            let result = bootstrap(&db, &config, user, organization(), settings).await;
+
+            assert!(
+                matches!(
+                    result,
+                    Err(Error {
+                        error_kind: DomainErrorKind::Validation(_),
+                        ..
+                    })
+                ),
+                "{result:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,326 @@
+//! Reading and writing the PNGs the platform resizes, such as organization logos.
+//!
+//! [`decode`] reads a PNG of any color type, bit depth or interlacing into 8-bit RGBA,
+//! refusing canvases over [`MAX_PIXELS`] before inflating any of their data; [`encode`]
+//! writes 8-bit RGBA. Both are the `png` crate's. [`Image::fit_within`] scales an image
+//! down by averaging the pixels each new one covers.
+
+use std::fmt;
+use std::io::Cursor;
+
+use ::png::{BitDepth, ColorType, Decoder, DecodingError, Encoder, Limits, Transformations};
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+/// Largest canvas decoded, so a small file can't claim a huge one.
+const MAX_PIXELS: u64 = 4096 * 4096;
+
+/// Most the decoder may allocate besides the decoded pixels, e.g. for scanlines and
+/// compressed metadata chunks.
+const DECODER_LIMIT_BYTES: usize = 16 * 1024 * 1024;
+
+/// An image as 8-bit RGBA pixels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    /// Four bytes per pixel, row by row from the top left.
+    pub rgba: Vec<u8>,
+}
+
+/// Why a PNG couldn't be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PngError {
+    /// The bytes aren't a PNG.
+    NotPng,
+    /// The PNG is truncated or corrupt.
+    Malformed(String),
+    /// The canvas is larger than decoded.
+    TooLarge,
+}
+
+impl fmt::Display for PngError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PngError::NotPng => f.write_str("not a PNG image"),
+            PngError::Malformed(what) => write!(f, "malformed PNG: {what}"),
+            PngError::TooLarge => write!(f, "the image is larger than {MAX_PIXELS} pixels"),
+        }
+    }
+}
+
+impl std::error::Error for PngError {}
+
+impl From<DecodingError> for PngError {
+    fn from(err: DecodingError) -> Self {
+        match err {
+            DecodingError::LimitsExceeded => PngError::TooLarge,
+            err => PngError::Malformed(err.to_string()),
+        }
+    }
+}
+
+impl Image {
+    /// The image scaled down to fit within `max` pixels on each side, keeping its aspect
+    /// ratio; unchanged when it fits already.
+    pub fn fit_within(&self, max: u32) -> Image {
+        let max = max.max(1);
+        if self.width <= max && self.height <= max {
+            return self.clone();
+        }
+        let scale = f64::from(max) / f64::from(self.width.max(self.height));
+        let width = ((f64::from(self.width) * scale).round() as u32).clamp(1, max);
+        let height = ((f64::from(self.height) * scale).round() as u32).clamp(1, max);
+
+        // Averaged with premultiplied alpha, so transparent pixels don't bleed their color.
+        let premultiplied = self
+            .rgba
+            .chunks_exact(4)
+            .flat_map(|pixel| {
+                let alpha = f32::from(pixel[3]) / 255.0;
+                [
+                    f32::from(pixel[0]) * alpha,
+                    f32::from(pixel[1]) * alpha,
+                    f32::from(pixel[2]) * alpha,
+                    f32::from(pixel[3]),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        let columns = coverage(self.width, width);
+        let mut narrowed = vec![0.0; width as usize * self.height as usize * 4];
+        for y in 0..self.height as usize {
+            for (x, sources) in columns.iter().enumerate() {
+                let to = (y * width as usize + x) * 4;
+                for &(source, weight) in sources {
+                    let from = (y * self.width as usize + source) * 4;
+                    for channel in 0..4 {
+                        narrowed[to + channel] += premultiplied[from + channel] * weight;
+                    }
+                }
+            }
+        }
+
+        let rows = coverage(self.height, height);
+        let mut rgba = Vec::with_capacity(width as usize * height as usize * 4);
+        for sources in &rows {
+            for x in 0..width as usize {
+                let mut pixel = [0.0f32; 4];
+                for &(source, weight) in sources {
+                    let from = (source * width as usize + x) * 4;
+                    for (channel, value) in pixel.iter_mut().enumerate() {
+                        *value += narrowed[from + channel] * weight;
+                    }
+                }
+                let alpha = pixel[3];
+                let unpremultiply = if alpha > 0.0 { 255.0 / alpha } else { 0.0 };
+                rgba.extend([
+                    to_byte(pixel[0] * unpremultiply),
+                    to_byte(pixel[1] * unpremultiply),
+                    to_byte(pixel[2] * unpremultiply),
+                    to_byte(alpha),
+                ]);
+            }
+        }
+
+        Image {
+            width,
+            height,
+            rgba,
+        }
+    }
+}
+
+/// For each of `to` new pixels along an axis of `from`, the old pixels it covers with
+/// the share of it each makes up.
+fn coverage(from: u32, to: u32) -> Vec<Vec<(usize, f32)>> {
+    let ratio = f64::from(from) / f64::from(to);
+    (0..to)
+        .map(|i| {
+            let start = f64::from(i) * ratio;
+            let end = (f64::from(i) + 1.0) * ratio;
+            (start.floor() as usize..(end.ceil() as usize).min(from as usize))
+                .map(|j| {
+                    let covered = end.min(j as f64 + 1.0) - start.max(j as f64);
+                    (j, (covered / ratio) as f32)
+                })
+                .filter(|&(_, weight)| weight > 0.0)
+                .collect()
+        })
+        .collect()
+}
+
+fn to_byte(value: f32) -> u8 {
+    value.round().clamp(0.0, 255.0) as u8
+}
+
+/// The image in `bytes`.
+pub fn decode(bytes: &[u8]) -> Result<Image, PngError> {
+    if !bytes.starts_with(&SIGNATURE) {
+        return Err(PngError::NotPng);
+    }
+    let limits = Limits {
+        bytes: DECODER_LIMIT_BYTES,
+    };
+    let mut decoder = Decoder::new_with_limits(Cursor::new(bytes), limits);
+    decoder.set_transformations(Transformations::ALPHA | Transformations::STRIP_16);
+    let mut reader = decoder.read_info()?;
+
+    // The header alone says how much the pixels take; refuse before allocating them
+    let (width, height) = reader.info().size();
+    if u64::from(width) * u64::from(height) > MAX_PIXELS {
+        return Err(PngError::TooLarge);
+    }
+    let mut pixels = vec![0; reader.output_buffer_size().ok_or(PngError::TooLarge)?];
+    let frame = reader.next_frame(&mut pixels)?;
+    pixels.truncate(frame.buffer_size());
+
+    let rgba = match frame.color_type {
+        ColorType::Rgba => pixels,
+        ColorType::Rgb => pixels
+            .chunks_exact(3)
+            .flat_map(|pixel| [pixel[0], pixel[1], pixel[2], u8::MAX])
+            .collect(),
+        ColorType::GrayscaleAlpha => pixels
+            .chunks_exact(2)
+            .flat_map(|pixel| [pixel[0], pixel[0], pixel[0], pixel[1]])
+            .collect(),
+        ColorType::Grayscale => pixels
+            .iter()
+            .flat_map(|&gray| [gray, gray, gray, u8::MAX])
+            .collect(),
+        ColorType::Indexed => return Err(PngError::Malformed("unexpanded palette".into())),
+    };
+    Ok(Image {
+        width,
+        height,
+        rgba,
+    })
+}
+
+/// `image` as an 8-bit RGBA PNG.
+pub fn encode(image: &Image) -> Vec<u8> {
+    let mut png = Vec::new();
+    let mut encoder = Encoder::new(&mut png, image.width, image.height);
+    encoder.set_color(ColorType::Rgba);
+    encoder.set_depth(BitDepth::Eight);
+    let mut writer = encoder
+        .write_header()
+        .expect("an image has at least one pixel and writing to memory can't fail");
+    writer
+        .write_image_data(&image.rgba)
+        .expect("an image has four bytes for each of its pixels");
+    writer.finish().expect("writing to memory can't fail");
+    png
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+
+    /// A 32x32 4-bit palette image with every row filter, a transparent first palette
+    /// entry and an ancillary text chunk, compressed by zlib with dynamic Huffman codes.
+    /// Pixel (x, y) is palette entry (x / 4 + y / 4) % 16, entry i being
+    /// (16i, 255 - 16i, 37i % 256).
+    const FIXTURE: &str = "iVBORw0KGgoAAAANSUhEUgAAACAAAAAgBAMAAACBVGfHAAAAMFBMVEUA/wAQ7yUg30owz29Av5RQr7lgn95wjwOAfyiQb02gX3KwT5fAP7zQL+HgHwbwDysGwvM4AAAAAXRSTlMAQObYZgAAAA90RVh0Q29tbWVudABmaXh0dXJllw/GWAAAAHxJREFUeNqF0DEVACEIBmA4XRxZHV1vtIIVrGAFK1jBClawghWsYIa78eAG+DcefI8HABCFEGNKOZdSK74NlgtEGQDHYkmsCLI1JBX1jlisnABO9o5BoiTKeHnpD+XkGJjUS4OKfsE518Ksorf6U07ujUVFk/pTTp6DVaAPcjw+DzB8TB4AAAAASUVORK5CYII=";
+
+    fn pixel(image: &Image, x: u32, y: u32) -> [u8; 4] {
+        let at = ((y * image.width + x) * 4) as usize;
+        image.rgba[at..at + 4].try_into().unwrap()
+    }
+
+    #[test]
+    fn decode_reads_a_compressed_palette_image() {
+        let image = decode(&STANDARD.decode(FIXTURE).unwrap()).unwrap();
+
+        assert_eq!((image.width, image.height), (32, 32));
+        for (x, y) in [(0, 0), (5, 0), (31, 2), (13, 17), (30, 31)] {
+            let index = (x / 4 + y / 4) % 16;
+            let alpha = if index == 0 { 0 } else { 255 };
+            let expected = [
+                (index * 16) as u8,
+                (255 - index * 16) as u8,
+                (index * 37 % 256) as u8,
+                alpha,
+            ];
+            assert_eq!(pixel(&image, x, y), expected, "pixel ({x}, {y})");
+        }
+    }
+
+    #[test]
+    fn encode_round_trips_through_decode() {
+        let (width, height) = (61, 47);
+        let rgba = (0..width * height)
+            .flat_map(|i| {
+                let (x, y) = (i % width, i / width);
+                [
+                    (x * 4) as u8,
+                    (y * 5) as u8,
+                    ((x ^ y) * 3) as u8,
+                    (x + y) as u8,
+                ]
+            })
+            .collect();
+        let image = Image {
+            width,
+            height,
+            rgba,
+        };
+
+        let png = encode(&image);
+
+        assert_eq!(decode(&png).unwrap(), image);
+    }
+
+    #[test]
+    fn fit_within_averages_the_pixels_each_new_one_covers() {
+        let image = Image {
+            width: 4,
+            height: 2,
+            rgba: [
+                [0, 0, 0, 255],
+                [200, 100, 0, 255],
+                [10, 10, 10, 0],
+                [50, 50, 50, 255],
+            ]
+            .repeat(2)
+            .concat(),
+        };
+
+        let smaller = image.fit_within(2);
+
+        assert_eq!((smaller.width, smaller.height), (2, 1));
+        assert_eq!(pixel(&smaller, 0, 0), [100, 50, 0, 255]);
+        // The transparent pixel's color doesn't bleed into its neighbour's
+        assert_eq!(pixel(&smaller, 1, 0), [50, 50, 50, 128]);
+    }
+
+    #[test]
+    fn decode_refuses_huge_canvases_before_inflating_them() {
+        let mut png = Vec::new();
+        let mut writer = Encoder::new(&mut png, 100_000, 100_000)
+            .write_header()
+            .unwrap();
+        writer
+            .write_chunk(::png::chunk::IDAT, &[0x78, 0x9c])
+            .unwrap();
+        drop(writer);
+
+        assert_eq!(decode(&png), Err(PngError::TooLarge));
+    }
+
+    #[test]
+    fn decode_rejects_what_isnt_a_png() {
+        assert_eq!(decode(b"GIF89a"), Err(PngError::NotPng));
+
+        let mut png = encode(&Image {
+            width: 1,
+            height: 1,
+            rgba: vec![1, 2, 3, 4],
+        });
+        let last = png.len() - 20;
+        png[last] ^= 0xff;
+        assert!(matches!(decode(&png), Err(PngError::Malformed(_))));
+    }
+}
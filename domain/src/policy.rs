@@ -0,0 +1,150 @@
+//! Consent to the platform's legal policies (terms of service, privacy policy).
+//!
+//! Each policy is published as versioned documents; the version of a kind in effect is
+//! the latest whose effective date has passed. A user accepts a version once, and the
+//! acceptance is kept as the record of their consent. Logging in lists the versions in
+//! effect the user hasn't accepted, so the frontend asks them to before going on.
+
+use chrono::Utc;
+use entity_api::{policy_acceptance, policy_document};
+use sea_orm::ConnectionTrait;
+
+use crate::error::{DomainErrorKind, Error};
+use crate::{policy_acceptances, policy_documents, Id};
+
+pub use entity_api::policy_document::find_in_effect;
+
+/// The policy versions in effect that the user hasn't accepted yet.
+pub async fn pending_for_user(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+) -> Result<Vec<policy_documents::Model>, Error> {
+    let in_effect = find_in_effect(db, Utc::now()).await?;
+    if in_effect.is_empty() {
+        return Ok(in_effect);
+    }
+
+    let ids: Vec<Id> = in_effect.iter().map(|document| document.id).collect();
+    let accepted = policy_acceptance::find_by_user_and_documents(db, user_id, &ids).await?;
+    Ok(in_effect
+        .into_iter()
+        .filter(|document| {
+            !accepted
+                .iter()
+                .any(|acceptance| acceptance.policy_document_id == document.id)
+        })
+        .collect())
+}
+
+/// Records the user's acceptance of a policy version in effect. Accepting a version
+/// again returns the original acceptance.
+pub async fn accept(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+    policy_document_id: Id,
+) -> Result<policy_acceptances::Model, Error> {
+    let document = policy_document::find_by_id(db, policy_document_id).await?;
+    let in_effect = find_in_effect(db, Utc::now()).await?;
+    if !in_effect.iter().any(|current| current.id == document.id) {
+        return Err(Error {
+            source: None,
+            error_kind: DomainErrorKind::Validation(
+                "Only the version of a policy in effect can be accepted".to_string(),
+            ),
+        });
+    }
+
+    if let Some(acceptance) =
+        policy_acceptance::find_by_user_and_document(db, user_id, policy_document_id).await?
+    {
+        return Ok(acceptance);
+    }
+    Ok(policy_acceptance::create(db, user_id, policy_document_id).await?)
+}
+
+#[cfg(test)]
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use crate::policy_kind::PolicyKind;
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    fn document(kind: PolicyKind, days_ago: i64) -> policy_documents::Model {
+        let effective_at = Utc::now() - chrono::Duration::days(days_ago);
+        policy_documents::Model {
+            id: Id::new_v4(),
+            kind,
+            version: format!("{days_ago}"),
+            effective_at: effective_at.into(),
+            content_url: "https://example.com/policy".to_string(),
+            created_at: effective_at.into(),
+        }
+    }
+
+    fn acceptance(user_id: Id, document: &policy_documents::Model) -> policy_acceptances::Model {
+        policy_acceptances::Model {
+            id: Id::new_v4(),
+            user_id,
+            policy_document_id: document.id,
+            accepted_at: Utc::now().into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn pending_for_user_lists_versions_in_effect_not_yet_accepted() {
+        let user_id = Id::new_v4();
+        let terms = document(PolicyKind::TermsOfService, 1);
+        let privacy = document(PolicyKind::PrivacyPolicy, 5);
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![terms.clone(), privacy.clone()]])
+            .append_query_results([vec![acceptance(user_id, &privacy)]])
+            .into_connection();
+
+        let pending = pending_for_user(&db, user_id).await.unwrap();
+
+        assert_eq!(pending, [terms]);
+    }
+
+    #[tokio::test]
+    async fn pending_for_user_is_empty_without_policies() {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<policy_documents::Model>::new()])
+            .into_connection();
+
+        assert!(pending_for_user(&db, Id::new_v4())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn accept_refuses_a_superseded_version() {
+        let superseded = document(PolicyKind::TermsOfService, 30);
+        let current = document(PolicyKind::TermsOfService, 1);
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![superseded.clone()]])
+            .append_query_results([vec![current]])
+            .into_connection();
+
+        let result = accept(&db, Id::new_v4(), superseded.id).await;
+
+        assert!(matches!(
+            result.unwrap_err().error_kind,
+            DomainErrorKind::Validation(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn accepting_again_returns_the_original_acceptance() {
+        let user_id = Id::new_v4();
+        let current = document(PolicyKind::TermsOfService, 1);
+        let original = acceptance(user_id, &current);
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![current.clone()]])
+            .append_query_results([vec![current.clone()]])
+            .append_query_results([vec![original.clone()]])
+            .into_connection();
+
+        assert_eq!(accept(&db, user_id, current.id).await.unwrap(), original);
+    }
+}
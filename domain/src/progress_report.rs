@@ -0,0 +1,436 @@
+//! Weekly AI-written progress reports on coaching relationships.
+//!
+//! The LLM turns a week of a relationship's material into a short narrative for the
+//! coach: the week's held sessions as digested for [`crate::themes`] (AI summaries,
+//! agreements, notes and actions), their conversation metrics, and the actions still
+//! open. [`send_weekly`] runs once a week has ended: for each coach who opted in through
+//! their [`settings`], it writes a report on every relationship that held a session that
+//! week and emails it. Reports are kept and listed by [`find_by_coaching_relationship`].
+
+use chrono::{Datelike, Days, NaiveDate, NaiveDateTime, Utc};
+use entity_api::{progress_report, progress_report_setting};
+use log::*;
+use meeting_ai::traits::analysis;
+use meeting_ai::types::analysis::Message;
+use sea_orm::DatabaseConnection;
+use service::config::Config;
+
+use crate::action::{self, FindByRelationshipParams};
+use crate::ai_settings::{self, TaskType};
+use crate::conversation_metrics::{self, SessionMetrics};
+use crate::error::{DomainErrorKind, EntityErrorKind, Error, InternalErrorKind};
+use crate::progress_report_settings::Model as Settings;
+use crate::progress_reports::Model;
+use crate::prompt_key::PromptKey;
+use crate::retrieval::plain_text;
+use crate::status::Status;
+use crate::themes::{self, Digest};
+use crate::{
+    actions, coaching_relationship, coaching_relationships, cost, emails, organization,
+    prompt_template, user, users, Id,
+};
+
+/// Longest narrative the LLM is asked for, in words.
+const MAX_WORDS: usize = 250;
+
+/// Most open actions listed in the prompt.
+const MAX_OPEN_ACTIONS: usize = 15;
+
+/// Longest open action body in the prompt, in characters.
+const MAX_ACTION_CHARS: usize = 200;
+
+/// Built-in system prompt; see [`crate::prompt_template`].
+pub(crate) const DEFAULT_PROMPT: &str = "You write a professional coach a weekly progress \
+update on one of their coaching engagements. You are given digests of the week's sessions, \
+their conversation metrics and the coachee's open actions. In at most {{max_words}} words of \
+plain prose, say what the coachee worked on, where they made progress, what is still open or \
+overdue, and one thing the coach might focus on next. Ground everything in the material; \
+never invent facts. Reply with the update only, without a greeting or headings.";
+
+/// The user's progress report settings; opted out when they have none stored.
+pub async fn settings(db: &DatabaseConnection, user_id: Id) -> Result<Settings, Error> {
+    Ok(progress_report_setting::find_by_user_id(db, user_id)
+        .await?
+        .unwrap_or_else(|| {
+            let now = Utc::now().fixed_offset();
+            Settings {
+                user_id,
+                weekly_email_enabled: false,
+                created_at: now,
+                updated_at: now,
+            }
+        }))
+}
+
+/// Stores the user's progress report settings, replacing any earlier ones.
+pub async fn update_settings(
+    db: &DatabaseConnection,
+    user_id: Id,
+    settings: Settings,
+) -> Result<Settings, Error> {
+    Ok(progress_report_setting::upsert(db, user_id, settings).await?)
+}
+
+/// The relationship's progress reports, most recent week first. Coach-only.
+pub async fn find_by_coaching_relationship(
+    db: &DatabaseConnection,
+    user_id: Id,
+    relationship: &coaching_relationships::Model,
+) -> Result<Vec<Model>, Error> {
+    if relationship.coach_id != user_id {
+        return Err(Error::entity(EntityErrorKind::Unauthenticated));
+    }
+
+    Ok(progress_report::find_by_coaching_relationship_id(db, relationship.id).await?)
+}
+
+/// Writes and emails last week's progress report on each relationship of every coach who
+/// opted in, returning the number of emails sent. A relationship is skipped when AI
+/// features are disabled for it or it held no session that week; reports already
+/// emailed aren't sent again, and a report whose email failed is resent on the next call.
+/// Failures are logged per relationship so one doesn't hold up the others.
+pub async fn send_weekly(
+    db: &DatabaseConnection,
+    config: &Config,
+    provider: &dyn analysis::Provider,
+) -> Result<usize, Error> {
+    let (period_start, period_end) = previous_week(Utc::now().date_naive());
+
+    let mut sent = 0;
+    for setting in progress_report_setting::find_weekly_email_enabled(db).await? {
+        let coach = match user::find_by_id(db, setting.user_id).await {
+            Ok(coach) => coach,
+            Err(e) => {
+                warn!(
+                    "progress report: failed to load coach {}: {e:?}",
+                    setting.user_id
+                );
+                continue;
+            }
+        };
+        let relationships = coaching_relationship::find_by_user(db, coach.id)
+            .await?
+            .into_iter()
+            .filter(|relationship| relationship.coach_id == coach.id);
+        for relationship in relationships {
+            match send(
+                db,
+                config,
+                provider,
+                &coach,
+                &relationship,
+                period_start,
+                period_end,
+            )
+            .await
+            {
+                Ok(true) => sent += 1,
+                Ok(false) => {}
+                Err(e) => warn!(
+                    "progress report: failed for relationship {} (week of {period_start}): {e:?}",
+                    relationship.id
+                ),
+            }
+        }
+    }
+    Ok(sent)
+}
+
+/// Writes the relationship's report for the week unless it exists, then emails it unless
+/// it was already emailed. Returns whether an email was sent.
+async fn send(
+    db: &DatabaseConnection,
+    config: &Config,
+    provider: &dyn analysis::Provider,
+    coach: &users::Model,
+    relationship: &coaching_relationships::Model,
+    period_start: NaiveDate,
+    period_end: NaiveDate,
+) -> Result<bool, Error> {
+    if !relationship.ai_privacy_level.allows_ai() {
+        return Ok(false);
+    }
+
+    let report = match progress_report::find_by_period(db, relationship.id, period_start).await? {
+        Some(report) if report.emailed_at.is_some() => return Ok(false),
+        Some(report) => report,
+        None => match generate(db, provider, relationship, period_start, period_end).await? {
+            Some(report) => report,
+            None => return Ok(false),
+        },
+    };
+
+    let coachee = user::find_by_id(db, relationship.coachee_id).await?;
+    let organization = organization::find_by_id(db, relationship.organization_id).await?;
+    emails::send_progress_report_email(config, coach, &coachee, &organization, &report).await?;
+    progress_report::mark_emailed(db, report).await?;
+    Ok(true)
+}
+
+/// Writes and stores the relationship's report on the week from `period_start` through
+/// `period_end`; `None` when it held no session that week.
+async fn generate(
+    db: &DatabaseConnection,
+    provider: &dyn analysis::Provider,
+    relationship: &coaching_relationships::Model,
+    period_start: NaiveDate,
+    period_end: NaiveDate,
+) -> Result<Option<Model>, Error> {
+    let in_period = |date: NaiveDateTime| (period_start..=period_end).contains(&date.date());
+
+    let held = themes::held_sessions(db, relationship.id).await?;
+    let session_count = held
+        .iter()
+        .filter(|session| in_period(session.date))
+        .count();
+    if session_count == 0 {
+        return Ok(None);
+    }
+
+    let digests: Vec<Digest> = themes::digests(db, relationship.id, &held)
+        .await?
+        .into_iter()
+        .filter(|digest| in_period(digest.date))
+        .collect();
+    let metrics: Vec<SessionMetrics> =
+        conversation_metrics::find_trend_by_coaching_relationship(db, relationship.id)
+            .await?
+            .sessions
+            .into_iter()
+            .filter(|session| in_period(session.date))
+            .collect();
+    let open_actions: Vec<actions::Model> = action::find_by_coaching_relationship(
+        db,
+        relationship.id,
+        FindByRelationshipParams::default(),
+    )
+    .await?
+    .into_iter()
+    .map(|with_assignees| with_assignees.action)
+    .filter(|action| !matches!(action.status, Status::Completed | Status::WontDo))
+    .collect();
+
+    let prompt = prompt_template::render(
+        db,
+        PromptKey::ProgressReport,
+        &[("max_words", MAX_WORDS.to_string())],
+    )
+    .await?;
+    let request = ai_settings::request(
+        db,
+        provider,
+        relationship.organization_id,
+        TaskType::Summary,
+        vec![
+            Message::system(prompt.text),
+            Message::user(build_prompt(
+                period_start,
+                period_end,
+                &digests,
+                &metrics,
+                &open_actions,
+            )),
+        ],
+        false,
+    )
+    .await?;
+    let completion = provider.complete(request).await?;
+
+    if let Err(e) =
+        cost::record_llm_tokens(db, None, relationship.id, completion.total_tokens()).await
+    {
+        warn!("progress report: failed to record LLM cost: {e:?}");
+    }
+
+    let narrative = completion.text.trim();
+    if narrative.is_empty() {
+        warn!(
+            "progress report: empty {} completion for relationship {}",
+            provider.provider_id(),
+            relationship.id
+        );
+        return Err(Error {
+            source: None,
+            error_kind: DomainErrorKind::Internal(InternalErrorKind::Other(
+                "The progress report could not be generated".to_string(),
+            )),
+        });
+    }
+
+    let generated_at = Utc::now().fixed_offset();
+    Ok(Some(
+        progress_report::create(
+            db,
+            Model {
+                id: Default::default(),
+                coaching_relationship_id: relationship.id,
+                period_start,
+                period_end,
+                narrative: narrative.to_string(),
+                session_count: session_count as i32,
+                open_action_count: open_actions.len() as i32,
+                model: completion.model,
+                prompt_version: Some(prompt.version),
+                generated_at,
+                emailed_at: None,
+                created_at: generated_at,
+                updated_at: generated_at,
+            },
+        )
+        .await?,
+    ))
+}
+
+/// The Monday and Sunday of the last full week before `today`.
+fn previous_week(today: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let this_monday = today - Days::new(today.weekday().num_days_from_monday().into());
+    (this_monday - Days::new(7), this_monday - Days::new(1))
+}
+
+fn build_prompt(
+    period_start: NaiveDate,
+    period_end: NaiveDate,
+    digests: &[Digest],
+    metrics: &[SessionMetrics],
+    open_actions: &[actions::Model],
+) -> String {
+    let mut prompt = format!("# Week of {period_start} to {period_end}\n\n");
+
+    for digest in digests {
+        prompt.push_str(&format!("## Session on {}", digest.date.date()));
+        if let Some(title) = digest.title.as_deref() {
+            prompt.push_str(&format!(": {title}"));
+        }
+        prompt.push('\n');
+        prompt.push_str(&digest.text);
+        prompt.push_str("\n\n");
+    }
+
+    if !metrics.is_empty() {
+        prompt.push_str("## Conversation metrics\n");
+        for session in metrics {
+            let speakers: Vec<String> = session
+                .metrics
+                .speakers
+                .iter()
+                .map(|speaker| {
+                    format!(
+                        "{} {:.0}% of talk time, {:.0}% questions",
+                        speaker.speaker_label,
+                        speaker.talk_share * 100.0,
+                        speaker.question_ratio * 100.0
+                    )
+                })
+                .collect();
+            prompt.push_str(&format!(
+                "- {}: {} min of talk, {} interruptions; {}\n",
+                session.date.date(),
+                session.metrics.total_talk_ms / 60_000,
+                session.metrics.interruption_count,
+                speakers.join("; ")
+            ));
+        }
+        prompt.push('\n');
+    }
+
+    prompt.push_str("## Open actions\n");
+    if open_actions.is_empty() {
+        prompt.push_str("None\n");
+    }
+    for action in open_actions.iter().take(MAX_OPEN_ACTIONS) {
+        let body = plain_text(action.body.as_deref().unwrap_or(""), MAX_ACTION_CHARS);
+        if body.is_empty() {
+            continue;
+        }
+        prompt.push_str(&format!("- {body} ({}", action.status));
+        if let Some(due_by) = action.due_by {
+            let due = due_by.date_naive();
+            prompt.push_str(&format!(", due {due}"));
+            if due <= period_end {
+                prompt.push_str(", overdue");
+            }
+        }
+        prompt.push_str(")\n");
+    }
+
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn previous_week_is_the_last_full_monday_to_sunday() {
+        let expected = (
+            NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 3, 8).unwrap(),
+        );
+
+        assert_eq!(
+            previous_week(NaiveDate::from_ymd_opt(2026, 3, 9).unwrap()),
+            expected
+        );
+        assert_eq!(
+            previous_week(NaiveDate::from_ymd_opt(2026, 3, 15).unwrap()),
+            expected
+        );
+    }
+
+    #[test]
+    fn build_prompt_flags_open_actions_due_by_the_end_of_the_week() {
+        let now = Utc::now().fixed_offset();
+        let action = |body: &str, due: Option<(u32, u32)>| actions::Model {
+            id: Id::new_v4(),
+            coaching_session_id: Id::new_v4(),
+            goal_id: None,
+            user_id: Id::new_v4(),
+            action_series_id: None,
+            previous_action_id: None,
+            body: Some(body.to_string()),
+            effort_points: None,
+            due_by: due.map(|(month, day)| {
+                Utc.with_ymd_and_hms(2026, month, day, 12, 0, 0)
+                    .unwrap()
+                    .fixed_offset()
+            }),
+            status: Status::InProgress,
+            status_changed_at: now,
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+        };
+        let digest = Digest {
+            date: NaiveDate::from_ymd_opt(2026, 3, 4)
+                .unwrap()
+                .and_hms_opt(15, 0, 0)
+                .unwrap(),
+            title: Some("Delegation".to_string()),
+            text: "Agreements: Hand off the weekly report".to_string(),
+        };
+
+        let prompt = build_prompt(
+            NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 3, 8).unwrap(),
+            &[digest],
+            &[],
+            &[
+                action("<p>Draft the handoff doc</p>", Some((3, 6))),
+                action("Pick a delegate", Some((3, 20))),
+                action("Reflect on guilt", None),
+            ],
+        );
+
+        assert_eq!(
+            prompt,
+            "# Week of 2026-03-02 to 2026-03-08\n\n\
+             ## Session on 2026-03-04: Delegation\n\
+             Agreements: Hand off the weekly report\n\n\
+             ## Open actions\n\
+             - Draft the handoff doc (In Progress, due 2026-03-06, overdue)\n\
+             - Pick a delegate (In Progress, due 2026-03-20)\n\
+             - Reflect on guilt (In Progress)\n"
+        );
+    }
+}
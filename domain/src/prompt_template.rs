@@ -0,0 +1,209 @@
+//! Versioned system prompts for AI features.
+//!
+//! Each AI feature's system prompt ships as code ([`default_body`], version 0). A
+//! SuperAdmin can store edited versions; the active one replaces the built-in prompt, and
+//! rolling back activates an earlier version (or 0 for the built-in one). Prompts may use
+//! `{{variable}}` placeholders from the feature's [`variables`], filled in by [`render`].
+//! Stored AI output records the version it was generated with.
+
+use entity_api::prompt_template;
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+
+use crate::error::Error;
+use crate::prompt_key::PromptKey;
+use crate::prompt_templates::Model;
+use crate::{ai_chat, progress_report, question_quality, session_prep, themes, Id};
+
+/// Longest prompt accepted, in characters.
+pub const MAX_BODY_CHARS: usize = 10_000;
+
+/// A system prompt ready to send and the version it came from (0 for the built-in one).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Prompt {
+    pub text: String,
+    pub version: i32,
+}
+
+/// A key's built-in prompt, placeholders and stored versions (newest first).
+#[derive(Debug, Serialize)]
+pub struct PromptHistory {
+    pub key: PromptKey,
+    pub active_version: i32,
+    pub default_body: &'static str,
+    pub variables: &'static [&'static str],
+    pub versions: Vec<Model>,
+}
+
+/// The prompt shipped with the feature (version 0).
+pub fn default_body(key: PromptKey) -> &'static str {
+    match key {
+        PromptKey::SessionPrep => session_prep::DEFAULT_PROMPT,
+        PromptKey::ThemesReport => themes::DEFAULT_PROMPT,
+        PromptKey::AiChat => ai_chat::DEFAULT_PROMPT,
+        PromptKey::QuestionQuality => question_quality::DEFAULT_PROMPT,
+        PromptKey::ProgressReport => progress_report::DEFAULT_PROMPT,
+    }
+}
+
+/// Placeholders the feature fills in; templates may use no others.
+pub fn variables(key: PromptKey) -> &'static [&'static str] {
+    match key {
+        PromptKey::SessionPrep => &["max_focus_questions"],
+        PromptKey::ThemesReport => &["max_themes"],
+        PromptKey::AiChat => &[],
+        PromptKey::QuestionQuality => &[],
+        PromptKey::ProgressReport => &["max_words"],
+    }
+}
+
+/// The active prompt for `key` with its placeholders replaced by `values`.
+pub async fn render(
+    db: &DatabaseConnection,
+    key: PromptKey,
+    values: &[(&str, String)],
+) -> Result<Prompt, Error> {
+    let prompt = match prompt_template::find_active(db, key).await? {
+        Some(template) => Prompt {
+            text: interpolate(&template.body, values),
+            version: template.version,
+        },
+        None => Prompt {
+            text: interpolate(default_body(key), values),
+            version: 0,
+        },
+    };
+    Ok(prompt)
+}
+
+pub async fn find_history(db: &DatabaseConnection, key: PromptKey) -> Result<PromptHistory, Error> {
+    let versions = prompt_template::find_by_key(db, key).await?;
+    Ok(PromptHistory {
+        key,
+        active_version: versions
+            .iter()
+            .find(|version| version.is_active)
+            .map_or(0, |version| version.version),
+        default_body: default_body(key),
+        variables: variables(key),
+        versions,
+    })
+}
+
+/// Stores `body` as the next version of `key`'s prompt and makes it active.
+pub async fn create_version(
+    db: &DatabaseConnection,
+    key: PromptKey,
+    user_id: Id,
+    body: &str,
+) -> Result<Model, Error> {
+    let body = body.trim();
+    validate(key, body)?;
+    Ok(prompt_template::create_version(db, key, user_id, body.to_string()).await?)
+}
+
+/// Makes an earlier (or later) stored version active; version 0 restores the built-in
+/// prompt.
+pub async fn activate(
+    db: &DatabaseConnection,
+    key: PromptKey,
+    version: i32,
+) -> Result<PromptHistory, Error> {
+    if version < 0 {
+        return Err(Error::validation("`version` must not be negative"));
+    }
+    prompt_template::activate(db, key, version).await?;
+    find_history(db, key).await
+}
+
+fn validate(key: PromptKey, body: &str) -> Result<(), Error> {
+    if body.is_empty() {
+        return Err(Error::validation("`body` must not be empty"));
+    }
+    if body.chars().count() > MAX_BODY_CHARS {
+        return Err(Error::validation(format!(
+            "`body` must be at most {MAX_BODY_CHARS} characters"
+        )));
+    }
+
+    let allowed = variables(key);
+    let mut rest = body;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            return Err(Error::validation("`body` has an unclosed `{{` placeholder"));
+        };
+        let name = rest[start + 2..start + 2 + len].trim();
+        if !allowed.contains(&name) {
+            return Err(Error::validation(format!(
+                "`body` uses the unknown placeholder `{{{{{name}}}}}`; {} prompts support: {}",
+                key,
+                if allowed.is_empty() {
+                    "none".to_string()
+                } else {
+                    allowed.join(", ")
+                }
+            )));
+        }
+        rest = &rest[start + 2 + len + 2..];
+    }
+
+    Ok(())
+}
+
+/// Replaces each `{{name}}` (spaces inside the braces allowed) with its value; unknown
+/// placeholders are left as written.
+fn interpolate(body: &str, values: &[(&str, String)]) -> String {
+    let mut text = String::with_capacity(body.len());
+    let mut rest = body;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + len + 2;
+        let name = rest[start + 2..start + 2 + len].trim();
+        text.push_str(&rest[..start]);
+        match values.iter().find(|(key, _)| *key == name) {
+            Some((_, value)) => text.push_str(value),
+            None => text.push_str(&rest[start..end]),
+        }
+        rest = &rest[end..];
+    }
+    text.push_str(rest);
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpolate_fills_known_placeholders() {
+        let text = interpolate(
+            "Ask at most {{ max_focus_questions }} questions about {{topic}}.",
+            &[("max_focus_questions", "5".to_string())],
+        );
+
+        assert_eq!(text, "Ask at most 5 questions about {{topic}}.");
+    }
+
+    #[test]
+    fn validate_rejects_unknown_and_unclosed_placeholders() {
+        assert!(validate(PromptKey::ThemesReport, "Name up to {{max_themes}} themes.").is_ok());
+        assert!(validate(PromptKey::ThemesReport, "Name up to {{max_questions}}.").is_err());
+        assert!(validate(PromptKey::ThemesReport, "Name up to {{max_themes themes.").is_err());
+        assert!(validate(PromptKey::AiChat, "").is_err());
+    }
+
+    #[test]
+    fn built_in_prompts_only_use_their_variables() {
+        for key in [
+            PromptKey::SessionPrep,
+            PromptKey::ThemesReport,
+            PromptKey::AiChat,
+            PromptKey::QuestionQuality,
+            PromptKey::ProgressReport,
+        ] {
+            assert!(validate(key, default_body(key)).is_ok(), "{key}");
+        }
+    }
+}
@@ -0,0 +1,571 @@
+//! Web Push notifications: reaching users' browsers and devices while the app is closed.
+//!
+//! Each browser a user allows notifications in subscribes with the server's VAPID public
+//! key and registers the push service endpoint and keys its `PushManager` hands out.
+//! [`PushNotificationHandler`] is registered on the application's [`EventPublisher`] and,
+//! for events that call for the user's attention (being @-mentioned), sends a
+//! notification to every subscription of each recipient with no SSE connection open.
+//!
+//! Payloads are encrypted to the browser's keys (RFC 8291, `aes128gcm`) and requests are
+//! signed with the server's VAPID key (RFC 8292). A subscription its push service reports
+//! gone (404 or 410) is deleted.
+//!
+//! [`EventPublisher`]: events::EventPublisher
+
+use aes_gcm::{aead::Aead, Aes128Gcm, KeyInit, Nonce};
+use async_trait::async_trait;
+use aws_lc_rs::agreement::{self, UnparsedPublicKey, ECDH_P256};
+use aws_lc_rs::rand::SystemRandom;
+use aws_lc_rs::signature::{EcdsaKeyPair, ECDSA_P256_SHA256_FIXED_SIGNING};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::Utc;
+use entity_api::{push_subscription, user};
+use events::{DomainEvent, EventHandler, EventKind, EventKinds, HandlerError};
+use hkdf::Hkdf;
+use log::*;
+use rand::RngCore;
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+use serde_json::Value;
+use service::config::Config;
+use sha2::Sha256;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::{EntityErrorKind, Error};
+use crate::push_subscriptions::Model;
+use crate::Id;
+
+pub use entity_api::push_subscription::find_by_user;
+
+/// Longest accepted push service endpoint.
+pub const MAX_ENDPOINT_LENGTH: usize = 2048;
+
+/// How long a push service holds a notification for an offline device.
+const TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How long a signed VAPID token is valid; push services reject more than 24 hours.
+const VAPID_TOKEN_LIFETIME: Duration = Duration::from_secs(12 * 60 * 60);
+
+/// How long a push service has to respond.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Length in bytes of an uncompressed P-256 public key.
+const P256_PUBLIC_KEY_LENGTH: usize = 65;
+
+/// Length in bytes of a subscription's authentication secret.
+const AUTH_SECRET_LENGTH: usize = 16;
+
+/// Record size written into the `aes128gcm` header; payloads are a single record.
+const RECORD_SIZE: u32 = 4096;
+
+/// Subscribes a browser of `user_id`'s to push notifications, replacing any subscription
+/// its `endpoint` already had.
+pub async fn subscribe(
+    db: &DatabaseConnection,
+    user_id: Id,
+    endpoint: String,
+    p256dh: String,
+    auth: String,
+) -> Result<Model, Error> {
+    validate(&endpoint, &p256dh, &auth)?;
+    let subscription = push_subscription::upsert(db, user_id, endpoint, p256dh, auth).await?;
+    info!(
+        "Subscribed user {user_id} to push notifications ({})",
+        subscription.id
+    );
+    Ok(subscription)
+}
+
+/// Deletes the user's subscription with `id`.
+pub async fn unsubscribe(db: &DatabaseConnection, user_id: Id, id: Id) -> Result<(), Error> {
+    match push_subscription::delete_by_user_and_id(db, user_id, id).await? {
+        0 => Err(Error::entity(EntityErrorKind::NotFound)),
+        _ => Ok(()),
+    }
+}
+
+/// The VAPID public key (base64url) browsers subscribe with; not found when push
+/// notifications aren't configured.
+pub fn vapid_public_key(config: &Config) -> Result<String, Error> {
+    config
+        .vapid_private_key()
+        .and(config.vapid_public_key())
+        .ok_or_else(|| Error::entity(EntityErrorKind::NotFound))
+}
+
+/// The server's VAPID key pair, which push requests are signed with.
+pub struct Vapid {
+    key_pair: EcdsaKeyPair,
+    /// The public key as configured (base64url), for browsers to subscribe with.
+    public_key: String,
+    /// Contact for the push service, a `mailto:` or `https:` URL.
+    subject: String,
+}
+
+impl Vapid {
+    /// The configured VAPID keys; `None` when push notifications aren't configured.
+    pub fn from_config(config: &Config) -> Result<Option<Self>, String> {
+        let (Some(public_key), Some(private_key)) =
+            (config.vapid_public_key(), config.vapid_private_key())
+        else {
+            return Ok(None);
+        };
+        let subject = config
+            .vapid_subject()
+            .ok_or("VAPID_SUBJECT (or FRONTEND_BASE_URL) must be set")?;
+        Self::new(&public_key, &private_key, subject).map(Some)
+    }
+
+    /// The key pair with the given base64url public and private keys.
+    pub fn new(public_key: &str, private_key: &str, subject: String) -> Result<Self, String> {
+        if !subject.starts_with("mailto:") && !subject.starts_with("https:") {
+            return Err("the VAPID subject must be a mailto: or https: URL".to_string());
+        }
+        let public_bytes = decode_key(public_key).ok_or("the VAPID public key is not base64url")?;
+        let private_bytes =
+            decode_key(private_key).ok_or("the VAPID private key is not base64url")?;
+        let key_pair = EcdsaKeyPair::from_private_key_and_public_key(
+            &ECDSA_P256_SHA256_FIXED_SIGNING,
+            &private_bytes,
+            &public_bytes,
+        )
+        .map_err(|e| format!("the VAPID keys are not a P-256 key pair: {e}"))?;
+
+        Ok(Self {
+            key_pair,
+            public_key: URL_SAFE_NO_PAD.encode(public_bytes),
+            subject,
+        })
+    }
+
+    /// The public key (base64url) browsers pass to `PushManager.subscribe` as their
+    /// `applicationServerKey`.
+    pub fn public_key(&self) -> &str {
+        &self.public_key
+    }
+
+    /// The `Authorization` header for a request to `endpoint`:
+    /// `vapid t=<signed JWT>, k=<public key>`.
+    fn authorization(&self, endpoint: &reqwest::Url) -> Result<String, String> {
+        #[derive(Serialize)]
+        struct Claims<'a> {
+            aud: String,
+            exp: i64,
+            sub: &'a str,
+        }
+
+        let header = URL_SAFE_NO_PAD.encode(br#"{"typ":"JWT","alg":"ES256"}"#);
+        let claims = Claims {
+            aud: endpoint.origin().ascii_serialization(),
+            exp: Utc::now().timestamp() + VAPID_TOKEN_LIFETIME.as_secs() as i64,
+            sub: &self.subject,
+        };
+        let claims =
+            URL_SAFE_NO_PAD.encode(serde_json::to_vec(&claims).map_err(|e| e.to_string())?);
+        let signing_input = format!("{header}.{claims}");
+        let signature = self
+            .key_pair
+            .sign(&SystemRandom::new(), signing_input.as_bytes())
+            .map_err(|_| "the VAPID token could not be signed".to_string())?;
+
+        Ok(format!(
+            "vapid t={signing_input}.{}, k={}",
+            URL_SAFE_NO_PAD.encode(signature.as_ref()),
+            self.public_key
+        ))
+    }
+}
+
+/// Whether a user has an SSE connection open, and so will see events in the app.
+pub type Presence = Arc<dyn Fn(Id) -> bool + Send + Sync>;
+
+/// What a browser's service worker receives, to show as a notification.
+#[derive(Debug, Serialize)]
+struct Notification {
+    /// The event type, e.g. `mention_created`.
+    #[serde(rename = "type")]
+    event_type: &'static str,
+    title: String,
+    body: String,
+    coaching_session_id: Id,
+}
+
+/// Sends push notifications for events that call for a user's attention to those of
+/// their browsers subscribed to them.
+pub struct PushNotificationHandler {
+    db: Arc<DatabaseConnection>,
+    vapid: Vapid,
+    client: reqwest::Client,
+    presence: Option<Presence>,
+}
+
+impl PushNotificationHandler {
+    /// The events that are pushed; register the handler for only these.
+    pub const KINDS: EventKinds = EventKinds::of(&[EventKind::MentionCreated]);
+
+    pub fn new(db: Arc<DatabaseConnection>, vapid: Vapid) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(DELIVERY_TIMEOUT)
+            .build()
+            .expect("the push HTTP client config is valid");
+        Self {
+            db,
+            vapid,
+            client,
+            presence: None,
+        }
+    }
+
+    /// Skip users `presence` reports connected. Without it every recipient is pushed to,
+    /// e.g. in the events worker, which holds no SSE connections.
+    pub fn with_presence(mut self, presence: Presence) -> Self {
+        self.presence = Some(presence);
+        self
+    }
+
+    /// "<author> mentioned you in a <note|action>", naming the author if they can be
+    /// found.
+    async fn mention_notification(&self, coaching_session_id: Id, mention: &Value) -> Notification {
+        let author = match id_field(mention, "author_user_id") {
+            Some(author_id) => user::find_by_id(self.db.as_ref(), author_id)
+                .await
+                .ok()
+                .map(|author| author.display_name.unwrap_or(author.first_name)),
+            None => None,
+        };
+        let target = mention
+            .get("resource_type")
+            .and_then(Value::as_str)
+            .unwrap_or("note");
+        Notification {
+            event_type: EventKind::MentionCreated.as_str(),
+            title: "You were mentioned".to_string(),
+            body: format!(
+                "{} mentioned you in a {target}",
+                author.as_deref().unwrap_or("Someone")
+            ),
+            coaching_session_id,
+        }
+    }
+
+    /// Pushes `payload` to `subscription`, deleting the subscription if its push service
+    /// reports it gone. Failures are logged; a notification isn't worth retrying.
+    async fn deliver(&self, subscription: &Model, payload: &[u8]) {
+        if let Err(e) = self.try_deliver(subscription, payload).await {
+            warn!(
+                "Push notification to subscription {} failed: {e}",
+                subscription.id
+            );
+        }
+    }
+
+    async fn try_deliver(&self, subscription: &Model, payload: &[u8]) -> Result<(), String> {
+        let endpoint = reqwest::Url::parse(&subscription.endpoint).map_err(|e| e.to_string())?;
+        let (Some(p256dh), Some(auth)) = (
+            decode_key(&subscription.p256dh),
+            decode_key(&subscription.auth),
+        ) else {
+            return Err("the subscription's keys are not base64url".to_string());
+        };
+        let body = encrypt(&p256dh, &auth, payload)?;
+
+        let response = self
+            .client
+            .post(endpoint.clone())
+            .header(
+                reqwest::header::AUTHORIZATION,
+                self.vapid.authorization(&endpoint)?,
+            )
+            .header(reqwest::header::CONTENT_ENCODING, "aes128gcm")
+            .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+            .header("TTL", TTL.as_secs())
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND || status == reqwest::StatusCode::GONE {
+            info!("Push subscription {} expired; deleting it", subscription.id);
+            push_subscription::delete_by_id(self.db.as_ref(), subscription.id)
+                .await
+                .map_err(|e| e.to_string())?;
+            return Ok(());
+        }
+        if !status.is_success() {
+            return Err(format!("the push service responded {status}"));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EventHandler for PushNotificationHandler {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), HandlerError> {
+        let DomainEvent::MentionCreated {
+            coaching_session_id,
+            mention,
+            notify_user_ids,
+        } = event
+        else {
+            return Ok(());
+        };
+        let offline: Vec<Id> = notify_user_ids
+            .iter()
+            .copied()
+            .filter(|&user_id| !self.presence.as_ref().is_some_and(|online| online(user_id)))
+            .collect();
+        if offline.is_empty() {
+            return Ok(());
+        }
+
+        let notification = self
+            .mention_notification(*coaching_session_id, mention)
+            .await;
+        let payload = serde_json::to_vec(&notification)
+            .map_err(|e| HandlerError::Permanent(e.to_string()))?;
+
+        for user_id in offline {
+            let subscriptions = find_by_user(self.db.as_ref(), user_id)
+                .await
+                .map_err(|e| HandlerError::Transient(e.to_string()))?;
+            for subscription in &subscriptions {
+                self.deliver(subscription, &payload).await;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The UUID in `value`'s `field`, if it has one.
+fn id_field(value: &Value, field: &str) -> Option<Id> {
+    value.get(field)?.as_str()?.parse().ok()
+}
+
+/// Encrypts `plaintext` to a subscription's P-256 public key and authentication secret
+/// as a single-record `aes128gcm` body (RFC 8291), under a fresh key pair and salt.
+fn encrypt(ua_public: &[u8], auth_secret: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    let as_private = agreement::PrivateKey::generate(&ECDH_P256)
+        .map_err(|_| "a key pair could not be generated".to_string())?;
+    let as_public = as_private
+        .compute_public_key()
+        .map_err(|_| "a key pair could not be generated".to_string())?;
+    let ecdh_secret = agreement::agree(
+        &as_private,
+        UnparsedPublicKey::new(&ECDH_P256, ua_public),
+        "the subscription's public key is not a P-256 point".to_string(),
+        |secret| Ok(secret.to_vec()),
+    )?;
+    let mut salt = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut salt);
+
+    let (cek, nonce) = content_key(
+        &ecdh_secret,
+        auth_secret,
+        ua_public,
+        as_public.as_ref(),
+        &salt,
+    )?;
+    // A single record ends with the last-record delimiter.
+    let mut record = plaintext.to_vec();
+    record.push(2);
+    let ciphertext = Aes128Gcm::new_from_slice(&cek)
+        .map_err(|e| e.to_string())?
+        .encrypt(Nonce::from_slice(&nonce), record.as_slice())
+        .map_err(|_| "the payload could not be encrypted".to_string())?;
+
+    let mut body = Vec::with_capacity(salt.len() + 5 + as_public.as_ref().len() + ciphertext.len());
+    body.extend_from_slice(&salt);
+    body.extend_from_slice(&RECORD_SIZE.to_be_bytes());
+    body.push(as_public.as_ref().len() as u8);
+    body.extend_from_slice(as_public.as_ref());
+    body.extend_from_slice(&ciphertext);
+    Ok(body)
+}
+
+/// The content encryption key and nonce RFC 8291 derives from the ECDH secret, the
+/// subscription's authentication secret, both public keys and the salt.
+fn content_key(
+    ecdh_secret: &[u8],
+    auth_secret: &[u8],
+    ua_public: &[u8],
+    as_public: &[u8],
+    salt: &[u8],
+) -> Result<([u8; 16], [u8; 12]), String> {
+    let invalid = |_| "the content key could not be derived".to_string();
+
+    let mut key_info = b"WebPush: info\0".to_vec();
+    key_info.extend_from_slice(ua_public);
+    key_info.extend_from_slice(as_public);
+    let mut ikm = [0u8; 32];
+    Hkdf::<Sha256>::new(Some(auth_secret), ecdh_secret)
+        .expand(&key_info, &mut ikm)
+        .map_err(invalid)?;
+
+    let prk = Hkdf::<Sha256>::new(Some(salt), &ikm);
+    let mut cek = [0u8; 16];
+    prk.expand(b"Content-Encoding: aes128gcm\0", &mut cek)
+        .map_err(invalid)?;
+    let mut nonce = [0u8; 12];
+    prk.expand(b"Content-Encoding: nonce\0", &mut nonce)
+        .map_err(invalid)?;
+    Ok((cek, nonce))
+}
+
+/// Decodes a base64url key, with or without padding as browsers vary.
+fn decode_key(key: &str) -> Option<Vec<u8>> {
+    URL_SAFE_NO_PAD
+        .decode(key.trim().trim_end_matches('='))
+        .ok()
+}
+
+fn validate(endpoint: &str, p256dh: &str, auth: &str) -> Result<(), Error> {
+    if endpoint.len() > MAX_ENDPOINT_LENGTH {
+        return Err(Error::validation(format!(
+            "`endpoint` must be at most {MAX_ENDPOINT_LENGTH} characters"
+        )));
+    }
+    let url =
+        reqwest::Url::parse(endpoint).map_err(|_| Error::validation("`endpoint` is not a URL"))?;
+    if url.scheme() != "https" || url.host_str().is_none() {
+        return Err(Error::validation("`endpoint` must be an https:// URL"));
+    }
+
+    if decode_key(p256dh).is_none_or(|key| key.len() != P256_PUBLIC_KEY_LENGTH || key[0] != 4) {
+        return Err(Error::validation(
+            "`keys.p256dh` must be a base64url uncompressed P-256 public key",
+        ));
+    }
+    if decode_key(auth).is_none_or(|secret| secret.len() != AUTH_SECRET_LENGTH) {
+        return Err(Error::validation(
+            "`keys.auth` must be a base64url 16-byte secret",
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_lc_rs::encoding::AsBigEndian;
+    use aws_lc_rs::signature::{
+        KeyPair, UnparsedPublicKey as SignaturePublicKey, ECDSA_P256_SHA256_FIXED,
+    };
+
+    fn vapid() -> Vapid {
+        let key_pair = EcdsaKeyPair::generate(&ECDSA_P256_SHA256_FIXED_SIGNING).unwrap();
+        let private_key = key_pair.private_key().as_be_bytes().unwrap();
+        Vapid::new(
+            &URL_SAFE_NO_PAD.encode(key_pair.public_key()),
+            &URL_SAFE_NO_PAD.encode(private_key.as_ref()),
+            "mailto:ops@example.com".to_string(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn encrypt_produces_a_body_the_subscription_can_decrypt() {
+        let ua_private = agreement::PrivateKey::generate(&ECDH_P256).unwrap();
+        let ua_public = ua_private.compute_public_key().unwrap();
+        let auth_secret = [7u8; AUTH_SECRET_LENGTH];
+
+        let body = encrypt(ua_public.as_ref(), &auth_secret, b"hello").unwrap();
+
+        let (salt, rest) = body.split_at(16);
+        assert_eq!(rest[..4], RECORD_SIZE.to_be_bytes());
+        let key_length = rest[4] as usize;
+        let (as_public, ciphertext) = rest[5..].split_at(key_length);
+        let ecdh_secret = agreement::agree(
+            &ua_private,
+            UnparsedPublicKey::new(&ECDH_P256, as_public),
+            (),
+            |secret| Ok(secret.to_vec()),
+        )
+        .unwrap();
+        let (cek, nonce) = content_key(
+            &ecdh_secret,
+            &auth_secret,
+            ua_public.as_ref(),
+            as_public,
+            salt,
+        )
+        .unwrap();
+        let record = Aes128Gcm::new_from_slice(&cek)
+            .unwrap()
+            .decrypt(Nonce::from_slice(&nonce), ciphertext)
+            .unwrap();
+
+        assert_eq!(record, b"hello\x02");
+    }
+
+    #[test]
+    fn authorization_is_a_vapid_token_signed_for_the_push_service_origin() {
+        let vapid = vapid();
+        let endpoint = reqwest::Url::parse("https://push.example.com/send/abc").unwrap();
+
+        let header = vapid.authorization(&endpoint).unwrap();
+
+        let (token, public_key) = header
+            .strip_prefix("vapid t=")
+            .and_then(|rest| rest.split_once(", k="))
+            .unwrap();
+        assert_eq!(public_key, vapid.public_key());
+        let (signing_input, signature) = token.rsplit_once('.').unwrap();
+        SignaturePublicKey::new(
+            &ECDSA_P256_SHA256_FIXED,
+            URL_SAFE_NO_PAD.decode(public_key).unwrap(),
+        )
+        .verify(
+            signing_input.as_bytes(),
+            &URL_SAFE_NO_PAD.decode(signature).unwrap(),
+        )
+        .unwrap();
+        let claims: Value = serde_json::from_slice(
+            &URL_SAFE_NO_PAD
+                .decode(signing_input.split_once('.').unwrap().1)
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(claims["aud"], "https://push.example.com");
+        assert_eq!(claims["sub"], "mailto:ops@example.com");
+    }
+
+    #[test]
+    fn vapid_rejects_mismatched_keys_and_subjects() {
+        let key_pair = EcdsaKeyPair::generate(&ECDSA_P256_SHA256_FIXED_SIGNING).unwrap();
+        let other = EcdsaKeyPair::generate(&ECDSA_P256_SHA256_FIXED_SIGNING).unwrap();
+        let public_key = URL_SAFE_NO_PAD.encode(key_pair.public_key());
+        let own_private = key_pair.private_key().as_be_bytes().unwrap();
+        let own_private = URL_SAFE_NO_PAD.encode(own_private.as_ref());
+        let other_private = other.private_key().as_be_bytes().unwrap();
+        let other_private = URL_SAFE_NO_PAD.encode(other_private.as_ref());
+
+        assert!(Vapid::new(&public_key, &own_private, "mailto:a@example.com".into()).is_ok());
+        assert!(Vapid::new(&public_key, &other_private, "mailto:a@example.com".into()).is_err());
+        assert!(Vapid::new(&public_key, &own_private, "a@example.com".into()).is_err());
+    }
+
+    #[test]
+    fn validate_checks_the_endpoint_and_keys() {
+        let p256dh = URL_SAFE_NO_PAD.encode(
+            agreement::PrivateKey::generate(&ECDH_P256)
+                .unwrap()
+                .compute_public_key()
+                .unwrap(),
+        );
+        let auth = URL_SAFE_NO_PAD.encode([1u8; AUTH_SECRET_LENGTH]);
+
+        assert!(validate("https://push.example.com/abc", &p256dh, &auth).is_ok());
+        // Padded keys are accepted too
+        assert!(validate(
+            "https://push.example.com/abc",
+            &p256dh,
+            &format!("{auth}==")
+        )
+        .is_ok());
+        assert!(validate("http://push.example.com/abc", &p256dh, &auth).is_err());
+        assert!(validate("https://push.example.com/abc", &auth, &auth).is_err());
+        assert!(validate("https://push.example.com/abc", &p256dh, &p256dh).is_err());
+    }
+}
@@ -0,0 +1,461 @@
+//! Question quality feedback for coaches.
+//!
+//! The LLM reads a session's transcript, identifies which speaker is the coach and
+//! classifies each of the coach's utterances as an open or closed question (or neither)
+//! and as advice-giving or inquiry. Only the counts are stored, one summary per session,
+//! reused until the session has a newer transcription; `refresh` reclassifies. [`trend`]
+//! lays a coach's summaries out over time.
+//!
+//! This is feedback on the coach's own skills, so it's only ever shown to the coach.
+
+use chrono::{NaiveDateTime, Utc};
+use entity::transcript_segment::Model as Segment;
+use entity_api::{
+    question_quality_summary, transcript_segment as segment_api, transcription as transcription_api,
+};
+use log::*;
+use meeting_ai::traits::analysis;
+use meeting_ai::types::analysis::Message;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+
+use crate::ai_settings::{self, TaskType};
+use crate::error::{DomainErrorKind, EntityErrorKind, Error, InternalErrorKind};
+use crate::prompt_key::PromptKey;
+use crate::prompt_template;
+use crate::question_quality_summaries::Model;
+use crate::{coaching_session, cost, Id};
+
+/// Most transcript characters sent for classification.
+const MAX_TRANSCRIPT_CHARS: usize = 12_000;
+
+/// Built-in system prompt; see [`crate::prompt_template`].
+pub(crate) const DEFAULT_PROMPT: &str = "You help a professional coach reflect on how they \
+ask questions. You are given a coaching session transcript as numbered lines of \
+\"[index] speaker: text\" and the names of the coach and the coachee. Decide which speaker \
+label is the coach, then classify every line the coach spoke. \"question\" is \"open\" for \
+questions that invite reflection or a free answer (what, how, tell me more), \"closed\" for \
+questions answerable with yes, no or a single fact, and \"none\" when the line asks \
+nothing. \"intent\" is \"advice\" when the coach tells the coachee what to do, suggests a \
+solution or shares their own opinion, \"inquiry\" when they explore the coachee's thinking, \
+and \"other\" for anything else (greetings, logistics, acknowledgements). Reply with a JSON \
+object with exactly two keys: \"coach_speaker_label\" and \"utterances\", an array of \
+objects with \"index\", \"question\" and \"intent\".";
+
+/// How a coach's utterance asks, as classified by the LLM.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Question {
+    Open,
+    Closed,
+    #[default]
+    #[serde(other)]
+    None,
+}
+
+/// What a coach's utterance is for, as classified by the LLM.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Intent {
+    Advice,
+    Inquiry,
+    #[default]
+    #[serde(other)]
+    Other,
+}
+
+/// The JSON object the LLM is asked to reply with.
+#[derive(Debug, Deserialize)]
+struct GeneratedClassification {
+    coach_speaker_label: Option<String>,
+    #[serde(default)]
+    utterances: Vec<GeneratedUtterance>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeneratedUtterance {
+    index: usize,
+    #[serde(default)]
+    question: Question,
+    #[serde(default)]
+    intent: Intent,
+}
+
+/// Counts of the coach's utterances in the classified lines.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct Counts {
+    coach_speaker_label: Option<String>,
+    utterance_count: i32,
+    open_question_count: i32,
+    closed_question_count: i32,
+    advice_count: i32,
+    inquiry_count: i32,
+}
+
+/// A session's summary within a coach's trend.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SessionQuestionQuality {
+    pub coaching_session_id: Id,
+    pub coaching_relationship_id: Id,
+    pub date: NaiveDateTime,
+    /// Open questions among all questions asked.
+    pub open_question_ratio: f64,
+    /// Advice among advice-giving and inquiring utterances.
+    pub advice_ratio: f64,
+    pub summary: Model,
+}
+
+/// A coach's question quality across their classified sessions.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Trend {
+    /// Oldest session first.
+    pub sessions: Vec<SessionQuestionQuality>,
+    /// Ratios over all sessions' utterances together.
+    pub open_question_ratio: f64,
+    pub advice_ratio: f64,
+}
+
+/// The session's question quality summary, classified when there is none yet, it was
+/// made from an older transcription, or `refresh` is set. Coach-only.
+pub async fn summary(
+    db: &DatabaseConnection,
+    analysis_provider: Option<&dyn analysis::Provider>,
+    user_id: Id,
+    coaching_session_id: Id,
+    refresh: bool,
+) -> Result<Model, Error> {
+    let (_, relationship) =
+        coaching_session::find_by_id_with_coaching_relationship(db, coaching_session_id).await?;
+    if relationship.coach_id != user_id {
+        return Err(Error::entity(EntityErrorKind::Unauthenticated));
+    }
+    if !relationship.ai_privacy_level.allows_transcripts() {
+        return Err(Error::validation(
+            "Transcripts may not be analyzed by AI for this coaching relationship",
+        ));
+    }
+
+    let transcription =
+        transcription_api::find_completed_by_coaching_sessions(db, &[coaching_session_id])
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::entity(EntityErrorKind::NotFound))?;
+
+    let cached = question_quality_summary::find_by_coaching_session_id(db, coaching_session_id)
+        .await?
+        .filter(|cached| !refresh && cached.transcription_id == transcription.id);
+    if let Some(cached) = cached {
+        return Ok(cached);
+    }
+
+    let provider = analysis_provider.ok_or_else(|| {
+        warn!("Analysis provider not configured");
+        Error {
+            source: None,
+            error_kind: DomainErrorKind::Internal(InternalErrorKind::Config),
+        }
+    })?;
+
+    let segments = segment_api::find_by_transcription(db, transcription.id).await?;
+    let segments = truncate(&segments);
+    if segments.is_empty() {
+        return Err(Error::validation("The session's transcript is empty"));
+    }
+
+    let coach = entity_api::user::find_by_id(db, relationship.coach_id).await?;
+    let coachee = entity_api::user::find_by_id(db, relationship.coachee_id).await?;
+
+    let prompt = prompt_template::render(db, PromptKey::QuestionQuality, &[]).await?;
+    let request = ai_settings::request(
+        db,
+        provider,
+        relationship.organization_id,
+        TaskType::Extraction,
+        vec![
+            Message::system(prompt.text),
+            Message::user(build_prompt(
+                &format!("{} {}", coach.first_name, coach.last_name),
+                &format!("{} {}", coachee.first_name, coachee.last_name),
+                &segments,
+            )),
+        ],
+        true,
+    )
+    .await?;
+    let completion = provider.complete(request).await?;
+
+    if let Err(e) = cost::record_llm_tokens(
+        db,
+        Some(coaching_session_id),
+        coaching_session_id,
+        completion.total_tokens(),
+    )
+    .await
+    {
+        warn!("question quality: failed to record LLM cost: {e:?}");
+    }
+
+    let Some(counts) = parse_completion(&completion.text, &segments) else {
+        warn!(
+            "question quality: unparseable {} completion for session {coaching_session_id}",
+            provider.provider_id(),
+        );
+        return Err(Error {
+            source: None,
+            error_kind: DomainErrorKind::Internal(InternalErrorKind::Other(
+                "The session's questions could not be classified".to_string(),
+            )),
+        });
+    };
+
+    let generated_at = Utc::now().fixed_offset();
+    Ok(question_quality_summary::upsert(
+        db,
+        Model {
+            coaching_session_id,
+            transcription_id: transcription.id,
+            coach_speaker_label: counts.coach_speaker_label,
+            utterance_count: counts.utterance_count,
+            open_question_count: counts.open_question_count,
+            closed_question_count: counts.closed_question_count,
+            advice_count: counts.advice_count,
+            inquiry_count: counts.inquiry_count,
+            model: completion.model,
+            prompt_version: Some(prompt.version),
+            generated_at,
+            created_at: generated_at,
+            updated_at: generated_at,
+        },
+    )
+    .await?)
+}
+
+/// The summaries stored for sessions the user coached, oldest first. Sessions are only
+/// included once their summary has been requested.
+pub async fn trend(db: &DatabaseConnection, coach_id: Id) -> Result<Trend, Error> {
+    let sessions = question_quality_summary::find_by_coach(db, coach_id)
+        .await?
+        .into_iter()
+        .map(|(summary, session)| SessionQuestionQuality {
+            coaching_session_id: session.id,
+            coaching_relationship_id: session.coaching_relationship_id,
+            date: session.date,
+            open_question_ratio: open_question_ratio(&summary),
+            advice_ratio: advice_ratio(&summary),
+            summary,
+        })
+        .collect();
+    Ok(trend_of(sessions))
+}
+
+fn trend_of(sessions: Vec<SessionQuestionQuality>) -> Trend {
+    let sum = |count: fn(&Model) -> i32| -> f64 {
+        sessions
+            .iter()
+            .map(|session| f64::from(count(&session.summary)))
+            .sum()
+    };
+    let open = sum(|summary| summary.open_question_count);
+    let closed = sum(|summary| summary.closed_question_count);
+    let advice = sum(|summary| summary.advice_count);
+    let inquiry = sum(|summary| summary.inquiry_count);
+
+    Trend {
+        open_question_ratio: ratio(open, open + closed),
+        advice_ratio: ratio(advice, advice + inquiry),
+        sessions,
+    }
+}
+
+fn open_question_ratio(summary: &Model) -> f64 {
+    ratio(
+        f64::from(summary.open_question_count),
+        f64::from(summary.open_question_count + summary.closed_question_count),
+    )
+}
+
+fn advice_ratio(summary: &Model) -> f64 {
+    ratio(
+        f64::from(summary.advice_count),
+        f64::from(summary.advice_count + summary.inquiry_count),
+    )
+}
+
+fn ratio(part: f64, whole: f64) -> f64 {
+    if whole > 0.0 {
+        part / whole
+    } else {
+        0.0
+    }
+}
+
+/// The leading segments that fit in [`MAX_TRANSCRIPT_CHARS`] of prompt.
+fn truncate(segments: &[Segment]) -> Vec<&Segment> {
+    let mut chars = 0;
+    segments
+        .iter()
+        .take_while(|segment| {
+            chars += segment.speaker_label.len() + segment.text.len();
+            chars <= MAX_TRANSCRIPT_CHARS
+        })
+        .collect()
+}
+
+fn build_prompt(coach: &str, coachee: &str, segments: &[&Segment]) -> String {
+    let mut prompt = format!("Coach: {coach}\nCoachee: {coachee}\n\n## Transcript\n");
+    for (index, segment) in segments.iter().enumerate() {
+        prompt.push_str(&format!(
+            "[{index}] {}: {}\n",
+            segment.speaker_label, segment.text
+        ));
+    }
+    prompt
+}
+
+/// Parses the LLM's JSON reply, tolerating a surrounding Markdown code fence, into
+/// counts of the coach's utterances among `segments`. Indices that aren't segments, or
+/// are another speaker's, are ignored.
+fn parse_completion(text: &str, segments: &[&Segment]) -> Option<Counts> {
+    let json = text
+        .trim()
+        .trim_start_matches("```json")
+        .trim_start_matches("```")
+        .trim_end_matches("```")
+        .trim();
+    let generated: GeneratedClassification = serde_json::from_str(json).ok()?;
+
+    let labels: HashSet<&str> = segments
+        .iter()
+        .map(|segment| segment.speaker_label.as_str())
+        .collect();
+    let coach_speaker_label = generated
+        .coach_speaker_label
+        .map(|label| label.trim().to_string())
+        .filter(|label| labels.contains(label.as_str()))
+        .or_else(|| most_classified_speaker(&generated.utterances, segments));
+    let Some(coach_speaker_label) = coach_speaker_label else {
+        return Some(Counts::default());
+    };
+
+    let is_coach = |index: usize| {
+        segments
+            .get(index)
+            .is_some_and(|segment| segment.speaker_label == coach_speaker_label)
+    };
+    let mut counts = Counts {
+        utterance_count: (0..segments.len()).filter(|index| is_coach(*index)).count() as i32,
+        ..Default::default()
+    };
+    let mut seen = HashSet::new();
+    for utterance in generated.utterances {
+        if !is_coach(utterance.index) || !seen.insert(utterance.index) {
+            continue;
+        }
+        match utterance.question {
+            Question::Open => counts.open_question_count += 1,
+            Question::Closed => counts.closed_question_count += 1,
+            Question::None => {}
+        }
+        match utterance.intent {
+            Intent::Advice => counts.advice_count += 1,
+            Intent::Inquiry => counts.inquiry_count += 1,
+            Intent::Other => {}
+        }
+    }
+    counts.coach_speaker_label = Some(coach_speaker_label);
+    Some(counts)
+}
+
+/// The speaker of most classified lines, for replies that name no (or an unknown) coach.
+fn most_classified_speaker(
+    utterances: &[GeneratedUtterance],
+    segments: &[&Segment],
+) -> Option<String> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for utterance in utterances {
+        if let Some(segment) = segments.get(utterance.index) {
+            *counts.entry(segment.speaker_label.as_str()).or_default() += 1;
+        }
+    }
+    counts
+        .into_iter()
+        .max_by_key(|(label, count)| (*count, Reverse(*label)))
+        .map(|(label, _)| label.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(speaker_label: &str, text: &str) -> Segment {
+        let now = Utc::now();
+        Segment {
+            id: Id::new_v4(),
+            transcription_id: Id::new_v4(),
+            speaker_label: speaker_label.to_string(),
+            text: text.to_string(),
+            start_ms: 0,
+            end_ms: 1_000,
+            confidence: None,
+            sentiment: None,
+            created_at: now.into(),
+        }
+    }
+
+    #[test]
+    fn parse_completion_counts_only_the_coachs_utterances() {
+        let segments = [
+            segment("A", "What would success look like?"),
+            segment("B", "Shipping the launch on time."),
+            segment("A", "Is the date fixed?"),
+            segment("A", "You should talk to your manager."),
+        ];
+        let segments: Vec<&Segment> = segments.iter().collect();
+        let reply = r#"```json
+{"coach_speaker_label": "A", "utterances": [
+  {"index": 0, "question": "open", "intent": "inquiry"},
+  {"index": 1, "question": "none", "intent": "advice"},
+  {"index": 2, "question": "closed", "intent": "inquiry"},
+  {"index": 2, "question": "closed", "intent": "inquiry"},
+  {"index": 3, "question": "none", "intent": "advice"},
+  {"index": 9, "question": "open", "intent": "inquiry"}
+]}
+```"#;
+
+        let counts = parse_completion(reply, &segments).unwrap();
+
+        assert_eq!(
+            counts,
+            Counts {
+                coach_speaker_label: Some("A".to_string()),
+                utterance_count: 3,
+                open_question_count: 1,
+                closed_question_count: 1,
+                advice_count: 1,
+                inquiry_count: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_completion_falls_back_to_the_most_classified_speaker() {
+        let segments = [
+            segment("Speaker 1", "How did it go?"),
+            segment("Speaker 2", "Fine."),
+        ];
+        let segments: Vec<&Segment> = segments.iter().collect();
+        let reply = r#"{"coach_speaker_label": "Coach", "utterances": [
+            {"index": 0, "question": "open", "intent": "something else"}
+        ]}"#;
+
+        let counts = parse_completion(reply, &segments).unwrap();
+
+        assert_eq!(counts.coach_speaker_label.as_deref(), Some("Speaker 1"));
+        assert_eq!(counts.open_question_count, 1);
+        assert_eq!(counts.inquiry_count + counts.advice_count, 0);
+        assert!(parse_completion("not json", &segments).is_none());
+    }
+}
@@ -0,0 +1,159 @@
+//! Emoji reactions on notes and actions.
+//!
+//! Any participant of the owning coaching session may react; each change publishes
+//! `ReactionsChanged` with the target's full aggregate so the other participant's UI
+//! updates live.
+
+use crate::error::{EntityErrorKind, Error};
+use crate::events::{DomainEvent, EventPublisher};
+use crate::resource_view::{authorize_participant, Participants};
+use crate::{reactions::Model, Id};
+use entity_api::reaction;
+use entity_api::resource_type::ResourceType;
+use log::*;
+use sea_orm::{ActiveEnum, DatabaseConnection};
+
+pub use entity_api::reaction::ReactionCount;
+
+/// Maximum emoji length in bytes (the column bound). Generous enough for ZWJ
+/// sequences such as family or skin-tone variants.
+pub const MAX_EMOJI_BYTES: usize = 32;
+
+/// Adds the caller's reaction to a note or action and publishes `ReactionsChanged`.
+/// Reacting again with the same emoji is a no-op that returns the existing reaction.
+pub async fn create(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    user_id: Id,
+    resource_type: ResourceType,
+    resource_id: Id,
+    emoji: String,
+) -> Result<Model, Error> {
+    if resource_type == ResourceType::CoachingSession {
+        return Err(Error::validation(
+            "Reactions can only target notes and actions",
+        ));
+    }
+    let emoji = validate_emoji(&emoji)?;
+
+    let participants = authorize_participant(db, user_id, resource_type, resource_id).await?;
+
+    let reaction = reaction::create(db, user_id, resource_type, resource_id, emoji).await?;
+    publish_reactions_changed(
+        db,
+        event_publisher,
+        participants,
+        resource_type,
+        resource_id,
+    )
+    .await;
+
+    Ok(reaction)
+}
+
+/// Removes one of the caller's own reactions and publishes `ReactionsChanged`.
+pub async fn delete(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    user_id: Id,
+    id: Id,
+) -> Result<(), Error> {
+    let existing = reaction::find_by_id(db, id).await?;
+    if existing.user_id != user_id {
+        return Err(Error::entity(EntityErrorKind::Unauthenticated));
+    }
+
+    let (resource_type, resource_id) = (existing.resource_type, existing.resource_id);
+    let participants = authorize_participant(db, user_id, resource_type, resource_id).await?;
+
+    reaction::delete(db, existing).await?;
+    publish_reactions_changed(
+        db,
+        event_publisher,
+        participants,
+        resource_type,
+        resource_id,
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Trims and checks an emoji: non-empty, at most [`MAX_EMOJI_BYTES`], no whitespace
+/// or control characters, and not plain ASCII text.
+pub fn validate_emoji(emoji: &str) -> Result<String, Error> {
+    let emoji = emoji.trim();
+
+    if emoji.is_empty() {
+        return Err(Error::validation("Emoji cannot be empty"));
+    }
+    if emoji.len() > MAX_EMOJI_BYTES {
+        return Err(Error::validation(format!(
+            "Emoji must be at most {MAX_EMOJI_BYTES} bytes"
+        )));
+    }
+    if emoji.is_ascii() || emoji.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(Error::validation("Emoji must be a single emoji"));
+    }
+
+    Ok(emoji.to_string())
+}
+
+/// Best-effort SSE: the DB write is the contract, so a failed aggregate lookup or
+/// serialization is logged and the event skipped rather than failing the mutation.
+async fn publish_reactions_changed(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    participants: Participants,
+    resource_type: ResourceType,
+    resource_id: Id,
+) {
+    let reactions = match reaction::find_counts_for(db, resource_type, resource_id).await {
+        Ok(reactions) => reactions,
+        Err(e) => {
+            error!("reaction SSE: failed to load reactions for {resource_id}: {e:?}");
+            return;
+        }
+    };
+    let reactions = match serde_json::to_value(reactions) {
+        Ok(reactions) => reactions,
+        Err(e) => {
+            error!("reaction SSE: failed to serialize reactions for {resource_id}: {e:?}");
+            return;
+        }
+    };
+
+    event_publisher
+        .publish(DomainEvent::ReactionsChanged {
+            coaching_session_id: participants.coaching_session_id,
+            resource_type: resource_type.to_value(),
+            resource_id,
+            reactions,
+            notify_user_ids: participants.user_ids,
+        })
+        .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::DomainErrorKind;
+
+    #[test]
+    fn validate_emoji_accepts_and_trims_emoji() {
+        assert_eq!(validate_emoji(" 👍 ").unwrap(), "👍");
+        assert_eq!(validate_emoji("👩‍👩‍👧‍👦").unwrap(), "👩‍👩‍👧‍👦");
+        assert_eq!(validate_emoji("👍🏽").unwrap(), "👍🏽");
+    }
+
+    #[test]
+    fn validate_emoji_rejects_empty_text_and_oversized_input() {
+        for rejected in ["", "   ", "lol", ":+1:", "👍 👍", &"🎉".repeat(9)] {
+            let err = validate_emoji(rejected).unwrap_err();
+            assert!(
+                matches!(err.error_kind, DomainErrorKind::Validation(_)),
+                "expected validation error for {rejected:?}"
+            );
+        }
+    }
+}
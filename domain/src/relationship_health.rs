@@ -0,0 +1,476 @@
+//! Relationship health scores.
+//!
+//! Once a day the web process's health task scores every coaching relationship 0–100
+//! from four signals over the last 90 days, each scored 0–100 itself:
+//!
+//! - **pulse**: the participants' post-session pulse answers (see [`crate::session_pulse`]),
+//!   once enough were given to average, from the average rounded to the nearest half
+//!   point so the daily history can't give back one day's answer;
+//! - **cadence**: how long ago the last session was, against the relationship's usual gap
+//!   between sessions;
+//! - **action completion**: the share of the actions that fell due which were completed;
+//! - **sentiment**: the sentiment of the session transcripts' segments.
+//!
+//! The score is the mean of the signals that had data, weighted by the organization's
+//! settings. Every run is recorded, so the history shows the trend. When a relationship's
+//! score drops below the organization's alert threshold, its admins are alerted over SSE.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDateTime, TimeDelta, Utc};
+use entity_api::{coaching_relationship, organization, relationship_health, session_pulse, user};
+use log::*;
+use sea_orm::DatabaseConnection;
+
+use crate::error::{EntityErrorKind, Error};
+use crate::events::{DomainEvent, EventPublisher};
+use crate::relationship_health_settings::Model;
+use crate::status::Status;
+use crate::{coaching_relationships, relationship_health_scores, Id};
+
+/// Weight of each signal for organizations that haven't set their own.
+pub const DEFAULT_WEIGHT: i16 = 25;
+
+/// Alert threshold for organizations that haven't set their own.
+pub const DEFAULT_ALERT_THRESHOLD: i16 = 40;
+
+/// How far back the signals look.
+const WINDOW: TimeDelta = TimeDelta::days(90);
+
+/// How far back a relationship's history goes.
+const HISTORY_WINDOW: TimeDelta = TimeDelta::days(365);
+
+/// Cadence keeps full marks until the last session is this many usual gaps ago...
+const CADENCE_SLACK: f64 = 1.5;
+
+/// ...and falls to 0 by this many.
+const CADENCE_LAPSED: f64 = 3.0;
+
+/// The organization's health score settings, or the defaults when it has none.
+pub async fn find_settings(db: &DatabaseConnection, organization_id: Id) -> Result<Model, Error> {
+    Ok(
+        relationship_health::find_settings_by_organization_id(db, organization_id)
+            .await?
+            .unwrap_or_else(|| defaults(organization_id)),
+    )
+}
+
+/// Validates and stores the organization's health score settings, replacing any earlier
+/// ones.
+pub async fn update_settings(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    settings: Model,
+) -> Result<Model, Error> {
+    validate(&settings)?;
+    Ok(relationship_health::upsert_settings(db, organization_id, settings).await?)
+}
+
+/// The latest score of each of the organization's relationships that has been scored.
+pub async fn find_latest_by_organization(
+    db: &DatabaseConnection,
+    organization_id: Id,
+) -> Result<Vec<relationship_health_scores::Model>, Error> {
+    let relationships = coaching_relationship::find_by_organization(db, organization_id).await?;
+    let relationship_ids: Vec<Id> = relationships.iter().map(|r| r.id).collect();
+    let mut latest = relationship_health::find_latest_scores(db, &relationship_ids).await?;
+
+    Ok(relationship_ids
+        .iter()
+        .filter_map(|id| latest.remove(id))
+        .collect())
+}
+
+/// The relationship's scores over the last year, oldest first. The relationship must
+/// belong to the organization.
+pub async fn find_history(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    coaching_relationship_id: Id,
+) -> Result<Vec<relationship_health_scores::Model>, Error> {
+    let relationship = coaching_relationship::find_by_id(db, coaching_relationship_id).await?;
+    if relationship.organization_id != organization_id {
+        return Err(Error::entity(EntityErrorKind::NotFound));
+    }
+
+    let since = Utc::now() - HISTORY_WINDOW;
+    Ok(relationship_health::find_scores_by_relationship(db, relationship.id, since.into()).await?)
+}
+
+/// Scores every relationship of every active organization and alerts admins of those that
+/// dropped below their threshold. Returns how many relationships were scored.
+/// Per-organization failures are logged and skipped.
+pub async fn score_all(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+) -> Result<usize, Error> {
+    let organizations = organization::find_all(db).await?;
+
+    let mut scored = 0;
+    for organization in organizations
+        .into_iter()
+        .filter(|o| o.archived_at.is_none())
+    {
+        match score_organization(db, event_publisher, organization.id).await {
+            Ok(count) => scored += count,
+            Err(e) => error!(
+                "relationship health: failed to score organization {}: {e:?}",
+                organization.id
+            ),
+        }
+    }
+
+    Ok(scored)
+}
+
+async fn score_organization(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    organization_id: Id,
+) -> Result<usize, Error> {
+    let relationships = coaching_relationship::find_by_organization(db, organization_id).await?;
+    if relationships.is_empty() {
+        return Ok(0);
+    }
+    let settings = find_settings(db, organization_id).await?;
+
+    let now = Utc::now();
+    let since = now - WINDOW;
+    let ids: Vec<Id> = relationships.iter().map(|r| r.id).collect();
+
+    let pulses = by_relationship(
+        session_pulse::find_responses_by_relationships(db, &ids, since.naive_utc()).await?,
+        |r| (r.coaching_relationship_id, r.score),
+    );
+    let sessions = by_relationship(
+        relationship_health::find_held_sessions(db, &ids, since.naive_utc(), now.naive_utc())
+            .await?,
+        |s| (s.coaching_relationship_id, s.date),
+    );
+    let actions = by_relationship(
+        relationship_health::find_due_actions(db, &ids, since.into(), now.into()).await?,
+        |a| (a.coaching_relationship_id, a.status),
+    );
+    let sentiments = by_relationship(
+        relationship_health::find_segment_sentiments(db, &ids, since.naive_utc()).await?,
+        |s| (s.coaching_relationship_id, s.sentiment),
+    );
+    let previous = relationship_health::find_latest_scores(db, &ids).await?;
+
+    let mut scores = Vec::new();
+    let mut at_risk = Vec::new();
+    for relationship in &relationships {
+        let signals = Signals {
+            pulse: pulse_score(signal(&pulses, relationship)),
+            cadence: cadence_score(signal(&sessions, relationship), started(relationship), now),
+            action_completion: action_completion_score(signal(&actions, relationship)),
+            sentiment: sentiment_score(signal(&sentiments, relationship)),
+        };
+        let Some(score) = combine(&signals, &settings) else {
+            continue;
+        };
+
+        let previous_score = previous.get(&relationship.id).map(|p| p.score);
+        if dropped_below(previous_score, score, settings.alert_threshold) {
+            at_risk.push((relationship.id, score));
+        }
+        scores.push(relationship_health_scores::Model {
+            id: Id::new_v4(),
+            coaching_relationship_id: relationship.id,
+            score,
+            pulse_score: signals.pulse,
+            cadence_score: signals.cadence,
+            action_completion_score: signals.action_completion,
+            sentiment_score: signals.sentiment,
+            computed_at: now.into(),
+        });
+    }
+
+    let scored = scores.len();
+    relationship_health::insert_scores(db, scores).await?;
+
+    if !at_risk.is_empty() {
+        let admin_ids: Vec<Id> = user::find_admins_of_organization(db, organization_id)
+            .await?
+            .into_iter()
+            .map(|admin| admin.id)
+            .collect();
+        for (coaching_relationship_id, score) in at_risk {
+            event_publisher
+                .publish(DomainEvent::RelationshipHealthAtRisk {
+                    coaching_relationship_id,
+                    score,
+                    threshold: settings.alert_threshold,
+                    notify_user_ids: admin_ids.clone(),
+                })
+                .await;
+        }
+    }
+
+    Ok(scored)
+}
+
+/// One relationship's signals, each 0–100, `None` when it had nothing to go on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Signals {
+    pulse: Option<i16>,
+    cadence: Option<i16>,
+    action_completion: Option<i16>,
+    sentiment: Option<i16>,
+}
+
+/// The mean of the signals that had data, weighted by `settings`; `None` when none of
+/// the weighted signals had data.
+fn combine(signals: &Signals, settings: &Model) -> Option<i16> {
+    let weighted = [
+        (signals.pulse, settings.pulse_weight),
+        (signals.cadence, settings.cadence_weight),
+        (signals.action_completion, settings.action_completion_weight),
+        (signals.sentiment, settings.sentiment_weight),
+    ];
+    let (total, weights) = weighted
+        .iter()
+        .filter_map(|(score, weight)| score.map(|score| (score, *weight)))
+        .filter(|(_, weight)| *weight > 0)
+        .fold((0.0, 0.0), |(total, weights), (score, weight)| {
+            (
+                total + f64::from(score) * f64::from(weight),
+                weights + f64::from(weight),
+            )
+        });
+
+    (weights > 0.0).then(|| (total / weights).round() as i16)
+}
+
+/// Whether the score just went below `threshold`. A relationship already below it isn't
+/// alerted for again until it recovers.
+fn dropped_below(previous_score: Option<i16>, score: i16, threshold: i16) -> bool {
+    score < threshold && previous_score.is_none_or(|previous| previous >= threshold)
+}
+
+/// The health score of the answers' rounded average ([`crate::session_pulse::aggregate`]).
+fn pulse_score(scores: &[i16]) -> Option<i16> {
+    crate::session_pulse::aggregate(scores)
+        .health_score
+        .map(i16::from)
+}
+
+/// Full marks while the last session is within [`CADENCE_SLACK`] usual gaps, falling to 0
+/// at [`CADENCE_LAPSED`]. The usual gap is the median between the window's sessions, so
+/// it needs two of them. A relationship older than the window that held none in it
+/// scores 0.
+fn cadence_score(
+    session_dates: &[NaiveDateTime],
+    started: NaiveDateTime,
+    now: DateTime<Utc>,
+) -> Option<i16> {
+    let now = now.naive_utc();
+    let Some(last) = session_dates.iter().max() else {
+        return (started <= now - WINDOW).then_some(0);
+    };
+
+    let mut sorted = session_dates.to_vec();
+    sorted.sort();
+    let mut gaps: Vec<TimeDelta> = sorted.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    if gaps.is_empty() {
+        return None;
+    }
+    gaps.sort();
+    let usual_gap = gaps[gaps.len() / 2].max(TimeDelta::days(1));
+
+    let overdue = (now - *last).num_minutes() as f64 / usual_gap.num_minutes() as f64;
+    let score = (CADENCE_LAPSED - overdue) / (CADENCE_LAPSED - CADENCE_SLACK) * 100.0;
+    Some(score.round().clamp(0.0, 100.0) as i16)
+}
+
+/// The share of due actions that were completed. Actions marked won't do don't count.
+fn action_completion_score(statuses: &[Status]) -> Option<i16> {
+    let counted: Vec<&Status> = statuses
+        .iter()
+        .filter(|status| **status != Status::WontDo)
+        .collect();
+    if counted.is_empty() {
+        return None;
+    }
+
+    let completed = counted
+        .iter()
+        .filter(|status| ***status == Status::Completed)
+        .count();
+    Some((completed as f64 / counted.len() as f64 * 100.0).round() as i16)
+}
+
+/// Positive segments count 100, neutral 50 and negative 0.
+fn sentiment_score(labels: &[String]) -> Option<i16> {
+    let values: Vec<f64> = labels
+        .iter()
+        .filter_map(|label| match label.to_lowercase().as_str() {
+            "positive" => Some(100.0),
+            "neutral" => Some(50.0),
+            "negative" => Some(0.0),
+            _ => None,
+        })
+        .collect();
+    if values.is_empty() {
+        return None;
+    }
+
+    Some((values.iter().sum::<f64>() / values.len() as f64).round() as i16)
+}
+
+fn by_relationship<T, V>(rows: Vec<T>, split: impl Fn(T) -> (Id, V)) -> HashMap<Id, Vec<V>> {
+    let mut grouped: HashMap<Id, Vec<V>> = HashMap::new();
+    for row in rows {
+        let (id, value) = split(row);
+        grouped.entry(id).or_default().push(value);
+    }
+    grouped
+}
+
+fn signal<'a, V>(
+    grouped: &'a HashMap<Id, Vec<V>>,
+    relationship: &coaching_relationships::Model,
+) -> &'a [V] {
+    grouped
+        .get(&relationship.id)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+fn started(relationship: &coaching_relationships::Model) -> NaiveDateTime {
+    relationship.created_at.naive_utc()
+}
+
+fn defaults(organization_id: Id) -> Model {
+    let now = Utc::now().fixed_offset();
+    Model {
+        organization_id,
+        pulse_weight: DEFAULT_WEIGHT,
+        cadence_weight: DEFAULT_WEIGHT,
+        action_completion_weight: DEFAULT_WEIGHT,
+        sentiment_weight: DEFAULT_WEIGHT,
+        alert_threshold: DEFAULT_ALERT_THRESHOLD,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+fn validate(settings: &Model) -> Result<(), Error> {
+    let fields = [
+        ("pulse_weight", settings.pulse_weight),
+        ("cadence_weight", settings.cadence_weight),
+        (
+            "action_completion_weight",
+            settings.action_completion_weight,
+        ),
+        ("sentiment_weight", settings.sentiment_weight),
+        ("alert_threshold", settings.alert_threshold),
+    ];
+    if let Some((name, value)) = fields.iter().find(|(_, value)| !(0..=100).contains(value)) {
+        return Err(Error::validation(format!(
+            "{name} must be between 0 and 100 (got {value})"
+        )));
+    }
+    if fields[..4].iter().all(|(_, weight)| *weight == 0) {
+        return Err(Error::validation(
+            "At least one signal must have a weight above 0".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weights(pulse: i16, cadence: i16, action_completion: i16, sentiment: i16) -> Model {
+        Model {
+            pulse_weight: pulse,
+            cadence_weight: cadence,
+            action_completion_weight: action_completion,
+            sentiment_weight: sentiment,
+            ..defaults(Id::new_v4())
+        }
+    }
+
+    #[test]
+    fn combine_weighs_only_the_signals_with_data() {
+        let signals = Signals {
+            pulse: Some(80),
+            cadence: Some(20),
+            action_completion: None,
+            sentiment: Some(50),
+        };
+
+        assert_eq!(combine(&signals, &weights(50, 25, 25, 0)), Some(60));
+        assert_eq!(combine(&signals, &weights(0, 0, 100, 0)), None);
+        assert_eq!(combine(&Signals::default(), &weights(25, 25, 25, 25)), None);
+    }
+
+    #[test]
+    fn pulse_moves_in_half_point_steps_of_the_average() {
+        assert_eq!(pulse_score(&[5, 4]), None);
+        assert_eq!(pulse_score(&[5, 4, 4]), Some(88));
+        assert_eq!(pulse_score(&[5, 4, 4, 3]), Some(75));
+        assert_eq!(pulse_score(&[5, 4, 4, 3, 4]), Some(75));
+    }
+
+    #[test]
+    fn admins_are_alerted_once_when_the_score_drops_below_the_threshold() {
+        assert!(dropped_below(None, 30, 40));
+        assert!(dropped_below(Some(55), 39, 40));
+        assert!(!dropped_below(Some(35), 30, 40));
+        assert!(!dropped_below(Some(55), 40, 40));
+    }
+
+    #[test]
+    fn cadence_falls_off_as_the_next_session_is_overdue() {
+        let now = Utc::now();
+        let weeks_ago = |weeks: i64| now.naive_utc() - TimeDelta::weeks(weeks);
+        let started = weeks_ago(52);
+
+        // Weekly sessions, the last one a week ago
+        assert_eq!(
+            cadence_score(&[weeks_ago(3), weeks_ago(2), weeks_ago(1)], started, now),
+            Some(100)
+        );
+        // Weekly sessions, the last one three weeks ago
+        assert_eq!(
+            cadence_score(&[weeks_ago(5), weeks_ago(4), weeks_ago(3)], started, now),
+            Some(0)
+        );
+        assert_eq!(cadence_score(&[weeks_ago(1)], started, now), None);
+        assert_eq!(cadence_score(&[], started, now), Some(0));
+        assert_eq!(cadence_score(&[], weeks_ago(2), now), None);
+    }
+
+    #[test]
+    fn action_completion_ignores_wont_do() {
+        let statuses = [
+            Status::Completed,
+            Status::InProgress,
+            Status::WontDo,
+            Status::Completed,
+        ];
+
+        assert_eq!(action_completion_score(&statuses), Some(67));
+        assert_eq!(action_completion_score(&[Status::WontDo]), None);
+    }
+
+    #[test]
+    fn sentiment_averages_the_labelled_segments() {
+        let labels = ["POSITIVE", "neutral", "negative", "positive", "unclear"]
+            .map(String::from)
+            .to_vec();
+
+        assert_eq!(sentiment_score(&labels), Some(63));
+        assert_eq!(sentiment_score(&[]), None);
+    }
+
+    #[test]
+    fn settings_need_weights_in_range_and_one_above_zero() {
+        assert!(validate(&weights(25, 25, 25, 25)).is_ok());
+        assert!(validate(&weights(101, 0, 0, 0)).is_err());
+        assert!(validate(&weights(0, 0, 0, 0)).is_err());
+    }
+}
@@ -0,0 +1,155 @@
+//! "Remember me" devices.
+//!
+//! Logging in with "remember me" gives the browser a device token, kept in a cookie
+//! next to the session's, and binds the session to the device it was issued for. The
+//! session lasts the device's lifetime rather than ending after a day of inactivity,
+//! but only for requests that present the token, and deleting the device ends it.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{Duration, Utc};
+use entity_api::remembered_device;
+use log::*;
+use rand::RngCore;
+use sea_orm::DatabaseConnection;
+use sha2::{Digest, Sha256};
+
+use crate::error::{EntityErrorKind, Error};
+use crate::remembered_devices::Model;
+use crate::Id;
+
+pub use entity_api::remembered_device::find_by_user;
+
+/// Longest `User-Agent` kept to tell a user's devices apart.
+const MAX_USER_AGENT_LENGTH: usize = 512;
+
+/// Remembers the browser `user_id` just logged in on for `lifetime_seconds`. Returns the
+/// device and its raw token (URL-safe base64), which only the browser keeps.
+pub async fn remember(
+    db: &DatabaseConnection,
+    user_id: Id,
+    user_agent: Option<String>,
+    lifetime_seconds: u64,
+) -> Result<(Model, String), Error> {
+    let mut raw_bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut raw_bytes);
+    let raw_token = URL_SAFE_NO_PAD.encode(raw_bytes);
+
+    let user_agent = user_agent.map(|user_agent| {
+        user_agent
+            .chars()
+            .take(MAX_USER_AGENT_LENGTH)
+            .collect::<String>()
+    });
+    let expires_at = Utc::now() + Duration::seconds(lifetime_seconds as i64);
+
+    remembered_device::delete_expired_for_user(db, user_id).await?;
+    let device = remembered_device::create(
+        db,
+        user_id,
+        hash_token(&raw_token),
+        user_agent,
+        expires_at.into(),
+    )
+    .await?;
+
+    info!("Remembered a device ({}) for user {user_id}", device.id);
+    Ok((device, raw_token))
+}
+
+/// The device `id`, if it still exists, hasn't expired and has `raw_token` as its token.
+pub async fn verify(
+    db: &DatabaseConnection,
+    id: Id,
+    raw_token: &str,
+) -> Result<Option<Model>, Error> {
+    Ok(remembered_device::find_by_id(db, id)
+        .await?
+        .filter(|device| device.expires_at > Utc::now())
+        .filter(|device| device.token_hash == hash_token(raw_token)))
+}
+
+/// Deletes the user's device with `id`, ending its session.
+pub async fn revoke(db: &DatabaseConnection, user_id: Id, id: Id) -> Result<(), Error> {
+    match remembered_device::delete_by_user_and_id(db, user_id, id).await? {
+        0 => Err(Error::entity(EntityErrorKind::NotFound)),
+        _ => {
+            info!("Revoked remembered device {id} of user {user_id}");
+            Ok(())
+        }
+    }
+}
+
+/// Deletes the device `id` when its session logs out.
+pub async fn forget(db: &DatabaseConnection, id: Id) -> Result<(), Error> {
+    remembered_device::delete_by_id(db, id).await?;
+    Ok(())
+}
+
+/// Compute the SHA-256 hex digest of a raw device token.
+fn hash_token(raw_token: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(raw_token.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_token_is_deterministic_and_not_the_token() {
+        assert_eq!(hash_token("device-token"), hash_token("device-token"));
+        assert_ne!(hash_token("device-token"), hash_token("other-token"));
+        assert_ne!(hash_token("device-token"), "device-token");
+    }
+
+    #[cfg(feature = "mock")]
+    mod mock_tests {
+        use super::*;
+        use sea_orm::{DatabaseBackend, MockDatabase};
+
+        fn device(expires_in: Duration, raw_token: &str) -> Model {
+            let now = Utc::now();
+            Model {
+                id: Id::new_v4(),
+                user_id: Id::new_v4(),
+                token_hash: hash_token(raw_token),
+                user_agent: None,
+                expires_at: (now + expires_in).into(),
+                created_at: now.into(),
+                updated_at: now.into(),
+            }
+        }
+
+        #[tokio::test]
+        async fn verify_accepts_only_the_devices_own_live_token() {
+            let live = device(Duration::days(1), "device-token");
+            let expired = device(Duration::days(-1), "device-token");
+            let db = MockDatabase::new(DatabaseBackend::Postgres)
+                .append_query_results(vec![
+                    vec![live.clone()],
+                    vec![live.clone()],
+                    vec![expired.clone()],
+                    vec![],
+                ])
+                .into_connection();
+
+            assert_eq!(
+                verify(&db, live.id, "device-token").await.unwrap(),
+                Some(live.clone())
+            );
+            assert!(verify(&db, live.id, "stolen-guess")
+                .await
+                .unwrap()
+                .is_none());
+            assert!(verify(&db, expired.id, "device-token")
+                .await
+                .unwrap()
+                .is_none());
+            assert!(verify(&db, Id::new_v4(), "device-token")
+                .await
+                .unwrap()
+                .is_none());
+        }
+    }
+}
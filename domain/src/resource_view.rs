@@ -0,0 +1,139 @@
+//! "Unread changes" tracking: per-(user, resource) view markers and the
+//! `has_unread` flag derived from them.
+//!
+//! Coaching sessions keep their dedicated `coaching_session_views` marker;
+//! actions and notes use `resource_views`. [`mark_viewed`] routes to the right one.
+
+use crate::coaching_session_view::MarkViewed;
+use crate::error::{EntityErrorKind, Error};
+use crate::{actions, coaching_session, notes, Id};
+use entity_api::action::ActionWithAssignees;
+use entity_api::{action, action_work_log, coaching_session_view, note, reaction, resource_view};
+use sea_orm::DatabaseConnection;
+
+pub use entity_api::resource_type::ResourceType;
+pub use entity_api::resource_view::{has_unread, Annotated, AnnotatedAction, AnnotatedNote};
+
+/// Mark a session, action or note as viewed by `viewer_id`, returning the prior marker.
+///
+/// The viewer must be a participant (coach or coachee) of the coaching relationship
+/// that owns the resource; otherwise this returns an `Unauthenticated` error (401).
+pub async fn mark_viewed(
+    db: &DatabaseConnection,
+    viewer_id: Id,
+    resource_type: ResourceType,
+    resource_id: Id,
+) -> Result<MarkViewed, Error> {
+    authorize_participant(db, viewer_id, resource_type, resource_id).await?;
+
+    Ok(match resource_type {
+        ResourceType::CoachingSession => {
+            coaching_session_view::mark_viewed(db, resource_id, viewer_id).await?
+        }
+        ResourceType::Action | ResourceType::Note => {
+            resource_view::mark_viewed(db, viewer_id, resource_type, resource_id).await?
+        }
+    })
+}
+
+/// The coaching session that owns a resource and that session's participants.
+pub(crate) struct Participants {
+    pub(crate) coaching_session_id: Id,
+    pub(crate) user_ids: Vec<Id>,
+}
+
+/// Resolves the session owning a resource and checks `user_id` is its coach or coachee,
+/// returning an `Unauthenticated` error (401) otherwise.
+pub(crate) async fn authorize_participant(
+    db: &DatabaseConnection,
+    user_id: Id,
+    resource_type: ResourceType,
+    resource_id: Id,
+) -> Result<Participants, Error> {
+    let coaching_session_id = match resource_type {
+        ResourceType::CoachingSession => resource_id,
+        ResourceType::Action => {
+            action::find_by_id(db, resource_id)
+                .await?
+                .coaching_session_id
+        }
+        // `note::find_by_id` already errors on a missing row; `None` is unreachable in practice.
+        ResourceType::Note => note::find_by_id(db, resource_id)
+            .await?
+            .map(|note| note.coaching_session_id)
+            .ok_or_else(|| Error::entity(EntityErrorKind::NotFound))?,
+    };
+
+    let (_, relationship) =
+        coaching_session::find_by_id_with_coaching_relationship(db, coaching_session_id).await?;
+    if relationship.coach_id != user_id && relationship.coachee_id != user_id {
+        return Err(Error::entity(EntityErrorKind::Unauthenticated));
+    }
+
+    Ok(Participants {
+        coaching_session_id,
+        user_ids: vec![relationship.coach_id, relationship.coachee_id],
+    })
+}
+
+/// Attach the viewer's `has_unread` flag, the reaction counts and the total logged work
+/// to each action.
+pub async fn annotate_actions(
+    db: &DatabaseConnection,
+    viewer_id: Id,
+    actions: Vec<ActionWithAssignees>,
+) -> Result<Vec<AnnotatedAction>, Error> {
+    let ids: Vec<Id> = actions.iter().map(|a| a.action.id).collect();
+    let views = resource_view::find_last_viewed(db, viewer_id, ResourceType::Action, &ids).await?;
+    let mut reactions = reaction::find_counts(db, ResourceType::Action, &ids).await?;
+    let work_totals = action_work_log::find_totals(db, &ids).await?;
+
+    Ok(actions
+        .into_iter()
+        .map(|resource| {
+            let action: &actions::Model = &resource.action;
+            let has_unread = has_unread(
+                action.updated_at,
+                views.get(&action.id).copied(),
+                action.user_id == viewer_id,
+            );
+            let reactions = reactions.remove(&action.id).unwrap_or_default();
+            let total_work_seconds = Some(work_totals.get(&action.id).copied().unwrap_or(0));
+            Annotated {
+                resource,
+                has_unread,
+                reactions,
+                total_work_seconds,
+            }
+        })
+        .collect())
+}
+
+/// Attach the viewer's `has_unread` flag and the reaction counts to each note.
+pub async fn annotate_notes(
+    db: &DatabaseConnection,
+    viewer_id: Id,
+    notes: Vec<notes::Model>,
+) -> Result<Vec<AnnotatedNote>, Error> {
+    let ids: Vec<Id> = notes.iter().map(|n| n.id).collect();
+    let views = resource_view::find_last_viewed(db, viewer_id, ResourceType::Note, &ids).await?;
+    let mut reactions = reaction::find_counts(db, ResourceType::Note, &ids).await?;
+
+    Ok(notes
+        .into_iter()
+        .map(|note| {
+            let has_unread = has_unread(
+                note.updated_at,
+                views.get(&note.id).copied(),
+                note.user_id == viewer_id,
+            );
+            let reactions = reactions.remove(&note.id).unwrap_or_default();
+            Annotated {
+                resource: note,
+                has_unread,
+                reactions,
+                total_work_seconds: None,
+            }
+        })
+        .collect())
+}
@@ -0,0 +1,113 @@
+//! Semantic (embedding similarity) search over a coaching relationship's history.
+//!
+//! Notes and transcript segments are embedded in the background by
+//! [`crate::embedding_index`]; a search embeds the query with the same model and returns
+//! the nearest excerpts within one relationship. Semantic search needs the pgvector
+//! extension, so [`search`] reports whether it ran and callers fall back to keyword
+//! search when it didn't.
+
+use entity_api::embedding;
+use log::*;
+use meeting_ai::traits::embedding::Provider;
+use meeting_ai::types::embedding::Request;
+use sea_orm::DatabaseConnection;
+
+use crate::embedding_source_type::EmbeddingSourceType;
+use crate::error::Error;
+use crate::{coaching_relationships, cost, Id};
+
+/// An excerpt close to the query. `similarity` is the cosine similarity: 1 for the
+/// same meaning, near 0 when unrelated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match {
+    pub source_type: EmbeddingSourceType,
+    pub source_id: Id,
+    pub coaching_session_id: Id,
+    pub content: String,
+    pub similarity: f64,
+}
+
+/// The `k` embedded excerpts of the relationship most similar to `query`, most similar
+/// first. Returns `None` when the embedding index isn't available (no pgvector).
+///
+/// Callers authorize access to the relationship. Its AI privacy level is applied here:
+/// nothing is returned when AI is disabled and transcripts are left out below `full`.
+/// The query's embedding cost is recorded against the relationship.
+pub async fn search(
+    db: &DatabaseConnection,
+    provider: &dyn Provider,
+    relationship: &coaching_relationships::Model,
+    query: &str,
+    k: u64,
+) -> Result<Option<Vec<Match>>, Error> {
+    if !embedding::is_available(db).await? {
+        return Ok(None);
+    }
+    if !relationship.ai_privacy_level.allows_ai() || query.trim().is_empty() {
+        return Ok(Some(Vec::new()));
+    }
+
+    let model = provider.model().to_string();
+    let embedded = provider
+        .embed(Request {
+            model: Some(model.clone()),
+            inputs: vec![plain_text(query, crate::embedding_index::MAX_INPUT_CHARS)],
+            dimensions: Some(embedding::DIMENSIONS as u32),
+        })
+        .await?;
+    if let Err(e) =
+        cost::record_embedding_tokens(db, None, relationship.id, embedded.input_tokens).await
+    {
+        warn!("retrieval: failed to record embedding cost: {e:?}");
+    }
+    let Some(vector) = embedded.vectors.into_iter().next() else {
+        return Ok(Some(Vec::new()));
+    };
+
+    let matches = embedding::search(
+        db,
+        relationship.id,
+        &vector,
+        &model,
+        relationship.ai_privacy_level.allows_transcripts(),
+        k,
+    )
+    .await?;
+
+    Ok(Some(
+        matches
+            .into_iter()
+            .map(|found| Match {
+                source_type: found.source_type,
+                source_id: found.source_id,
+                coaching_session_id: found.coaching_session_id,
+                content: found.content,
+                similarity: 1.0 - found.distance,
+            })
+            .collect(),
+    ))
+}
+
+/// The text with markup tags removed and whitespace collapsed, cut to `max_chars`
+/// (an ellipsis marks the cut).
+pub(crate) fn plain_text(text: &str, max_chars: usize) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                plain.push(' ');
+            }
+            _ if !in_tag => plain.push(c),
+            _ => {}
+        }
+    }
+
+    let collapsed = plain.split_whitespace().collect::<Vec<_>>().join(" ");
+    match collapsed.char_indices().nth(max_chars) {
+        Some((cut, _)) => format!("{}…", &collapsed[..cut]),
+        None => collapsed,
+    }
+}
@@ -0,0 +1,100 @@
+//! Domain events published at a later time.
+//!
+//! [`schedule_publish`] stores an event with the time it's due, e.g. "session starting in
+//! 15 minutes" reminders, and the web process's scheduler task calls [`publish_due`] to
+//! publish the events whose time has come. Scheduled events live in the database, so
+//! they fire after a restart; one the process stopped publishing is claimed again once
+//! its lease lapses.
+
+use chrono::{DateTime, Utc};
+use entity_api::scheduled_event;
+use events::{envelope, DomainEvent, EventPublisher};
+use log::*;
+use sea_orm::DatabaseConnection;
+use std::time::Duration;
+
+use crate::error::{DomainErrorKind, Error, InternalErrorKind};
+use crate::scheduled_events::Model;
+use crate::Id;
+
+/// Most due events published per pass.
+const BATCH_SIZE: u64 = 100;
+
+/// How long a pass holds the events it claimed before another may claim them.
+const LEASE: Duration = Duration::from_secs(60);
+
+/// Schedules `event` to be published at `at` on behalf of the current actor (see
+/// [`events::with_actor`]). An `at` in the past publishes it on the next pass.
+pub async fn schedule_publish(
+    db: &DatabaseConnection,
+    event: DomainEvent,
+    at: DateTime<Utc>,
+) -> Result<Model, Error> {
+    let payload = envelope::to_value(&event).map_err(|e| Error {
+        source: Some(Box::new(e)),
+        error_kind: DomainErrorKind::Internal(InternalErrorKind::Other(
+            "The event could not be serialized".to_string(),
+        )),
+    })?;
+
+    Ok(scheduled_event::create(
+        db,
+        event.kind().as_str(),
+        payload,
+        events::current_actor(),
+        at.into(),
+    )
+    .await?)
+}
+
+/// Cancels the scheduled event with `id`; fails with not found once it's published.
+pub async fn cancel(db: &DatabaseConnection, id: Id) -> Result<(), Error> {
+    Ok(scheduled_event::delete_unpublished(db, id).await?)
+}
+
+/// Publishes the scheduled events that are due, earliest first, each on behalf of the
+/// user who scheduled it. Returns how many were published.
+///
+/// An event whose payload can't be read is left claimed and logged, and is retried
+/// when its lease lapses.
+pub async fn publish_due(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+) -> Result<usize, Error> {
+    let due = scheduled_event::claim_due(db, BATCH_SIZE, LEASE).await?;
+
+    let mut published = 0;
+    for scheduled in due {
+        let (event, correlation_id): (DomainEvent, _) =
+            match envelope::parse(scheduled.payload.clone()).and_then(|envelope| {
+                let correlation_id = envelope.correlation_id;
+                Ok((envelope.into_event()?, correlation_id))
+            }) {
+                Ok(read) => read,
+                Err(e) => {
+                    warn!(
+                        "Scheduled event {} ({}) has an unreadable payload: {e}",
+                        scheduled.id, scheduled.event_type
+                    );
+                    continue;
+                }
+            };
+
+        // Published under the correlation ID it was stored with, so it still traces back
+        // to the request that caused it.
+        events::with_correlation_id(correlation_id, async {
+            match scheduled.actor_id {
+                Some(actor_id) => {
+                    events::with_actor(actor_id, event_publisher.publish(event)).await
+                }
+                None => event_publisher.publish(event).await,
+            }
+        })
+        .await;
+
+        scheduled_event::mark_published(db, scheduled.id).await?;
+        published += 1;
+    }
+
+    Ok(published)
+}
@@ -0,0 +1,1183 @@
+//! SCIM 2.0 user provisioning (RFC 7643, RFC 7644) for organizations that manage their
+//! users in an identity provider such as Okta or Azure AD.
+//!
+//! An organization admin creates the organization's SCIM token ([`create_token`]) and
+//! configures the identity provider with it. The provider then creates, updates and
+//! deactivates the organization's users through the `/scim/v2` API. A provisioned user is
+//! an ordinary user with a role in the organization: deactivating them takes away their
+//! roles in it without deleting their account, and reactivating them restores the `user`
+//! role.
+//!
+//! Groups map onto organization roles. The fixed `Admins` and `Users` groups hold the
+//! provisioned users with the `admin` and `user` roles, and adding or removing members
+//! grants or takes away those roles. The provider only sees, and can only change, the
+//! users it provisioned.
+//!
+//! Whenever a user's roles change, [`DomainEvent::UserRolesChanged`] sends their SSE
+//! connections `session_invalidated`, so the frontend refetches the user and their
+//! permissions rather than acting on the roles it loaded at login.
+
+use chrono::Utc;
+use entity_api::{mutate::UpdateMap, scim_token, scim_user, user, user_role};
+use log::*;
+use rand::RngCore;
+use sea_orm::{ConnectionTrait, DatabaseConnection, IntoActiveModel, TransactionTrait, Value};
+use serde::Deserialize;
+use serde_json::Value as Json;
+use service::config::Config;
+use sha2::{Digest, Sha256};
+
+use crate::error::{EntityErrorKind, Error};
+use crate::events::{DomainEvent, EventPublisher};
+use crate::{emails, scim_tokens, scim_users, users, Id};
+
+pub use entity_api::scim_token::CreatedScimToken;
+pub use entity_api::user::Role;
+
+/// Prefix of SCIM tokens, marking a bearer credential as one.
+pub const TOKEN_PREFIX: &str = "rfp_scim_";
+
+/// The groups the identity provider may assign users to, by the role they grant.
+pub const GROUPS: [(Role, &str); 2] = [(Role::Admin, "Admins"), (Role::User, "Users")];
+
+/// Longest accepted name (`givenName`, `familyName`, `displayName`).
+pub const MAX_NAME_LENGTH: usize = 255;
+
+/// A user the organization's identity provider provisioned.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProvisionedUser {
+    /// The user, with their roles in every organization.
+    pub user: users::Model,
+    /// The provider's own ID for the user.
+    pub external_id: Option<String>,
+    pub active: bool,
+}
+
+impl ProvisionedUser {
+    /// Whether the user has `role` in the organization.
+    pub fn has_role(&self, organization_id: Id, role: &Role) -> bool {
+        self.user
+            .roles
+            .iter()
+            .any(|r| r.organization_id == Some(organization_id) && &r.role == role)
+    }
+}
+
+/// The attributes of a provisioned user the identity provider manages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserAttributes {
+    /// The user's email address, which they log in with.
+    pub user_name: String,
+    pub given_name: String,
+    pub family_name: String,
+    pub display_name: Option<String>,
+    pub external_id: Option<String>,
+    pub active: bool,
+}
+
+impl From<&ProvisionedUser> for UserAttributes {
+    fn from(provisioned: &ProvisionedUser) -> Self {
+        UserAttributes {
+            user_name: provisioned.user.email.clone(),
+            given_name: provisioned.user.first_name.clone(),
+            family_name: provisioned.user.last_name.clone(),
+            display_name: provisioned.user.display_name.clone(),
+            external_id: provisioned.external_id.clone(),
+            active: provisioned.active,
+        }
+    }
+}
+
+/// One of the organization's groups and the provisioned users in it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Group {
+    /// The role members have in the organization. Its name is the group's ID.
+    pub role: Role,
+    pub display_name: &'static str,
+    pub members: Vec<ProvisionedUser>,
+}
+
+/// One operation of a SCIM `PATCH` request.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PatchOperation {
+    /// `add`, `remove` or `replace`, in any case.
+    pub op: String,
+    pub path: Option<String>,
+    pub value: Option<Json>,
+}
+
+/// A SCIM filter (`userName eq "ada@example.com"`): `eq` comparisons joined by `and`,
+/// which is what identity providers send to find the resources they provisioned.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Filter {
+    /// Each comparison's attribute, lowercased, and value.
+    comparisons: Vec<(String, String)>,
+}
+
+impl Filter {
+    /// Parses a filter, failing with a validation error naming what isn't supported.
+    pub fn parse(filter: &str) -> Result<Self, Error> {
+        let tokens = tokenize(filter)?;
+        let mut comparisons = Vec::new();
+        let mut tokens = tokens.into_iter();
+        loop {
+            let (Some(attribute), Some(operator), Some(value)) =
+                (tokens.next(), tokens.next(), tokens.next())
+            else {
+                return Err(Error::validation(
+                    "Filters must be `<attribute> eq <value>`",
+                ));
+            };
+            if !operator.eq_ignore_ascii_case("eq") {
+                return Err(Error::validation(format!(
+                    "The `{operator}` operator isn't supported; use `eq`"
+                )));
+            }
+            comparisons.push((attribute.to_ascii_lowercase(), value));
+            match tokens.next() {
+                None => break,
+                Some(and) if and.eq_ignore_ascii_case("and") => continue,
+                Some(other) => {
+                    return Err(Error::validation(format!(
+                        "`{other}` isn't supported; join comparisons with `and`"
+                    )))
+                }
+            }
+        }
+        Ok(Filter { comparisons })
+    }
+
+    /// Whether a user has every compared attribute's value. `userName` and emails compare
+    /// case-insensitively.
+    pub fn matches_user(&self, provisioned: &ProvisionedUser) -> bool {
+        self.comparisons
+            .iter()
+            .all(|(attribute, value)| match attribute.as_str() {
+                "id" => provisioned.user.id.to_string() == *value,
+                "username" | "emails.value" | "emails" => {
+                    provisioned.user.email.eq_ignore_ascii_case(value)
+                }
+                "externalid" => provisioned.external_id.as_deref() == Some(value),
+                "displayname" => provisioned.user.display_name.as_deref() == Some(value),
+                "name.givenname" => provisioned.user.first_name == *value,
+                "name.familyname" => provisioned.user.last_name == *value,
+                "active" => provisioned.active.to_string() == *value,
+                _ => false,
+            })
+    }
+
+    /// Whether a group has every compared attribute's value.
+    pub fn matches_group(&self, group: &Group) -> bool {
+        self.comparisons
+            .iter()
+            .all(|(attribute, value)| match attribute.as_str() {
+                "id" => group.role.to_string() == *value,
+                "displayname" => group.display_name.eq_ignore_ascii_case(value),
+                "members.value" | "members" => group
+                    .members
+                    .iter()
+                    .any(|member| member.user.id.to_string() == *value),
+                _ => false,
+            })
+    }
+}
+
+/// Creates the organization's SCIM token, replacing (and so revoking) any it had. The
+/// token is returned only now.
+pub async fn create_token(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    user_id: Id,
+) -> Result<CreatedScimToken, Error> {
+    let token = format!("{TOKEN_PREFIX}{}", random_token());
+    let scim_token = scim_token::upsert(db, organization_id, user_id, hash_token(&token)).await?;
+    info!("User {user_id} created a SCIM token for organization {organization_id}");
+
+    Ok(CreatedScimToken { scim_token, token })
+}
+
+/// The organization's SCIM token, without its value.
+pub async fn find_token(
+    db: &DatabaseConnection,
+    organization_id: Id,
+) -> Result<scim_tokens::Model, Error> {
+    scim_token::find_by_organization(db, organization_id)
+        .await?
+        .ok_or_else(|| Error::entity(EntityErrorKind::NotFound))
+}
+
+/// Revokes the organization's SCIM token.
+pub async fn delete_token(db: &DatabaseConnection, organization_id: Id) -> Result<(), Error> {
+    if scim_token::delete_by_organization(db, organization_id).await? == 0 {
+        return Err(Error::entity(EntityErrorKind::NotFound));
+    }
+    info!("Revoked the SCIM token of organization {organization_id}");
+    Ok(())
+}
+
+/// The organization a SCIM token belongs to, if it's one.
+pub async fn authenticate(db: &DatabaseConnection, token: &str) -> Result<Option<Id>, Error> {
+    if !token.starts_with(TOKEN_PREFIX) {
+        return Ok(None);
+    }
+    Ok(scim_token::find_by_token_hash(db, &hash_token(token))
+        .await?
+        .map(|scim_token| scim_token.organization_id))
+}
+
+/// The users the organization's identity provider provisioned that match `filter`, in
+/// the order they were provisioned.
+pub async fn find_users(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    filter: Option<&Filter>,
+) -> Result<Vec<ProvisionedUser>, Error> {
+    Ok(provisioned_users(db, organization_id)
+        .await?
+        .into_iter()
+        .filter(|provisioned| filter.is_none_or(|filter| filter.matches_user(provisioned)))
+        .collect())
+}
+
+pub async fn find_user(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    user_id: Id,
+) -> Result<ProvisionedUser, Error> {
+    let record = find_record(db, organization_id, user_id).await?;
+    provisioned_user(db, record).await
+}
+
+/// Provisions a user in the organization.
+///
+/// A new user gets the `user` role and, when active, a welcome email inviting them to set
+/// up their account, sent on behalf of the admin who created the SCIM token. An existing
+/// member of the organization is taken over as is while the organization controls their
+/// account (`organization_controls`) and they haven't set a password; anyone else with
+/// the same email is a conflict.
+pub async fn create_user(
+    db: &DatabaseConnection,
+    config: &Config,
+    organization_id: Id,
+    attributes: UserAttributes,
+) -> Result<ProvisionedUser, Error> {
+    validate(&attributes)?;
+
+    let txn = db.begin().await.map_err(entity_api::error::Error::from)?;
+    let (user, is_new) = match user::find_by_email(&txn, &attributes.user_name).await? {
+        Some(existing) => {
+            if scim_user::find_by_organization_and_user(&txn, organization_id, existing.id)
+                .await?
+                .is_some()
+            {
+                return Err(conflict(&format!(
+                    "{} is already provisioned",
+                    attributes.user_name
+                )));
+            }
+            if !existing
+                .roles
+                .iter()
+                .any(|role| role.organization_id == Some(organization_id))
+            {
+                return Err(conflict(&format!(
+                    "{} belongs to another organization",
+                    attributes.user_name
+                )));
+            }
+            // Their email could then be changed and their password reset through it
+            if !organization_controls(&existing, organization_id) || existing.password.is_some() {
+                return Err(conflict(&format!(
+                    "{} has an account the organization doesn't manage",
+                    attributes.user_name
+                )));
+            }
+            (existing, false)
+        }
+        None => {
+            let now = Utc::now();
+            let new_user = users::Model {
+                id: Id::nil(),
+                email: attributes.user_name.clone(),
+                first_name: attributes.given_name.clone(),
+                last_name: attributes.family_name.clone(),
+                display_name: attributes.display_name.clone(),
+                password: None,
+                github_username: None,
+                github_profile_url: None,
+                timezone: "UTC".to_string(),
+                default_coaching_session_duration_minutes:
+                    crate::duration::Duration::default_minutes(),
+                role: Role::User,
+                roles: Vec::new(),
+                invite_status: None,
+                created_at: now.into(),
+                updated_at: now.into(),
+            };
+            (
+                user::create_by_organization(&txn, organization_id, new_user).await?,
+                true,
+            )
+        }
+    };
+    let record = scim_user::create(
+        &txn,
+        organization_id,
+        user.id,
+        attributes.external_id.clone(),
+    )
+    .await?;
+    let record = if attributes.active {
+        record
+    } else {
+        user_role::delete_by_user_and_organization(&txn, user.id, organization_id).await?;
+        scim_user::update(&txn, record, attributes.external_id.clone(), false).await?
+    };
+    txn.commit().await.map_err(entity_api::error::Error::from)?;
+    info!(
+        "Provisioned user {} in organization {organization_id}",
+        user.id
+    );
+
+    let provisioned = provisioned_user(db, record).await?;
+    if is_new && provisioned.active {
+        send_welcome_email(db, config, organization_id, &provisioned.user).await;
+    }
+    Ok(provisioned)
+}
+
+/// Replaces a provisioned user's attributes, deactivating or reactivating them when
+/// `active` changes.
+///
+/// A user's email (`userName`) can only be changed while the organization controls their
+/// account (`organization_controls`).
+pub async fn update_user(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    organization_id: Id,
+    user_id: Id,
+    attributes: UserAttributes,
+) -> Result<ProvisionedUser, Error> {
+    validate(&attributes)?;
+    let record = find_record(db, organization_id, user_id).await?;
+    let existing = user::find_by_id(db, user_id).await?;
+
+    let email_changed = !existing.email.eq_ignore_ascii_case(&attributes.user_name);
+    if email_changed {
+        if !organization_controls(&existing, organization_id) {
+            return Err(Error::validation(
+                "`userName` can't be changed for a SuperAdmin or a user who belongs to other organizations",
+            ));
+        }
+        if user::find_by_email(db, &attributes.user_name)
+            .await?
+            .is_some_and(|other| other.id != user_id)
+        {
+            return Err(conflict(&format!(
+                "{} is already taken",
+                attributes.user_name
+            )));
+        }
+    }
+
+    let txn = db.begin().await.map_err(entity_api::error::Error::from)?;
+    let mut update_map = UpdateMap::new();
+    update_map.insert("email".to_string(), Some(string(attributes.user_name)));
+    update_map.insert(
+        "first_name".to_string(),
+        Some(string(attributes.given_name)),
+    );
+    update_map.insert(
+        "last_name".to_string(),
+        Some(string(attributes.family_name)),
+    );
+    update_map.insert(
+        "display_name".to_string(),
+        Some(Value::String(attributes.display_name.map(Box::new))),
+    );
+    entity_api::mutate::update::<users::ActiveModel, users::Column>(
+        &txn,
+        existing.into_active_model(),
+        update_map,
+    )
+    .await?;
+
+    if record.active != attributes.active {
+        if attributes.active {
+            user_role::add(&txn, user_id, organization_id, Role::User).await?;
+            info!("Reactivated user {user_id} in organization {organization_id}");
+        } else {
+            user_role::delete_by_user_and_organization(&txn, user_id, organization_id).await?;
+            info!("Deactivated user {user_id} in organization {organization_id}");
+        }
+    }
+    let roles_changed = record.active != attributes.active;
+    let record = scim_user::update(&txn, record, attributes.external_id, attributes.active).await?;
+    txn.commit().await.map_err(entity_api::error::Error::from)?;
+
+    if roles_changed {
+        notify_roles_changed(db, event_publisher, organization_id, &[user_id]).await?;
+    }
+
+    provisioned_user(db, record).await
+}
+
+/// Applies a `PATCH` request's operations to a provisioned user.
+pub async fn patch_user(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    organization_id: Id,
+    user_id: Id,
+    operations: &[PatchOperation],
+) -> Result<ProvisionedUser, Error> {
+    let provisioned = find_user(db, organization_id, user_id).await?;
+    let mut attributes = UserAttributes::from(&provisioned);
+    for operation in operations {
+        apply_user_operation(&mut attributes, operation)?;
+    }
+    update_user(db, event_publisher, organization_id, user_id, attributes).await
+}
+
+/// Deprovisions a user: they lose their roles in the organization and the identity
+/// provider no longer sees them. Their account is kept.
+pub async fn delete_user(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    organization_id: Id,
+    user_id: Id,
+) -> Result<(), Error> {
+    let record = find_record(db, organization_id, user_id).await?;
+
+    let txn = db.begin().await.map_err(entity_api::error::Error::from)?;
+    user_role::delete_by_user_and_organization(&txn, user_id, organization_id).await?;
+    scim_user::delete_by_id(&txn, record.id).await?;
+    txn.commit().await.map_err(entity_api::error::Error::from)?;
+    notify_roles_changed(db, event_publisher, organization_id, &[user_id]).await?;
+
+    info!("Deprovisioned user {user_id} from organization {organization_id}");
+    Ok(())
+}
+
+/// The organization's groups that match `filter`.
+pub async fn find_groups(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    filter: Option<&Filter>,
+) -> Result<Vec<Group>, Error> {
+    let provisioned = provisioned_users(db, organization_id).await?;
+    Ok(GROUPS
+        .iter()
+        .map(|(role, display_name)| group(organization_id, role, display_name, &provisioned))
+        .filter(|group| filter.is_none_or(|filter| filter.matches_group(group)))
+        .collect())
+}
+
+/// The group with ID `group_id` (its role's name, e.g. `admin`).
+pub async fn find_group(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    group_id: &str,
+) -> Result<Group, Error> {
+    let (role, _) = group_by_id(group_id)?;
+    find_groups(db, organization_id, None)
+        .await?
+        .into_iter()
+        .find(|group| group.role == *role)
+        .ok_or_else(|| Error::entity(EntityErrorKind::NotFound))
+}
+
+/// "Creates" a group: the groups are fixed, so this is the one named `display_name`,
+/// letting identity providers that push groups link to it.
+pub async fn create_group(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    display_name: &str,
+) -> Result<Group, Error> {
+    let Some((role, _)) = GROUPS
+        .iter()
+        .find(|(_, name)| name.eq_ignore_ascii_case(display_name))
+    else {
+        return Err(Error::validation(format!(
+            "Groups can't be created; use one of {}",
+            GROUPS.map(|(_, name)| name).join(", ")
+        )));
+    };
+    find_group(db, organization_id, &role.to_string()).await
+}
+
+/// Sets a group's members to exactly `member_ids`, granting and taking away its role.
+pub async fn replace_group_members(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    organization_id: Id,
+    group_id: &str,
+    member_ids: &[Id],
+) -> Result<Group, Error> {
+    let group = find_group(db, organization_id, group_id).await?;
+    let removed: Vec<Id> = group
+        .members
+        .iter()
+        .map(|member| member.user.id)
+        .filter(|id| !member_ids.contains(id))
+        .collect();
+    change_group_members(db, organization_id, &group.role, member_ids, &removed).await?;
+
+    let changed: Vec<Id> = member_ids
+        .iter()
+        .filter(|id| !group.members.iter().any(|member| member.user.id == **id))
+        .chain(&removed)
+        .copied()
+        .collect();
+    notify_roles_changed(db, event_publisher, organization_id, &changed).await?;
+    find_group(db, organization_id, group_id).await
+}
+
+/// Applies a `PATCH` request's operations to a group's members.
+pub async fn patch_group(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    organization_id: Id,
+    group_id: &str,
+    operations: &[PatchOperation],
+) -> Result<Group, Error> {
+    let group = find_group(db, organization_id, group_id).await?;
+    let mut members: Vec<Id> = group.members.iter().map(|member| member.user.id).collect();
+    for operation in operations {
+        apply_group_operation(&mut members, operation)?;
+    }
+    replace_group_members(db, event_publisher, organization_id, group_id, &members).await
+}
+
+/// Marks the users whose roles in the organization changed as updated and tells their
+/// clients to refetch them.
+async fn notify_roles_changed(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    organization_id: Id,
+    user_ids: &[Id],
+) -> Result<(), Error> {
+    for &user_id in user_ids {
+        user::touch(db, user_id).await?;
+        event_publisher
+            .publish(DomainEvent::UserRolesChanged {
+                organization_id,
+                user_id,
+            })
+            .await;
+    }
+    Ok(())
+}
+
+/// Grants the group's role to `added` and takes it away from `removed`, all of whom must
+/// be active provisioned users.
+async fn change_group_members(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    role: &Role,
+    added: &[Id],
+    removed: &[Id],
+) -> Result<(), Error> {
+    let records = scim_user::find_by_organization(db, organization_id).await?;
+    for id in added.iter().chain(removed) {
+        if !records
+            .iter()
+            .any(|record| record.user_id == *id && record.active)
+        {
+            return Err(Error::validation(format!(
+                "{id} isn't an active user provisioned in this organization"
+            )));
+        }
+    }
+
+    let txn = db.begin().await.map_err(entity_api::error::Error::from)?;
+    for id in added {
+        user_role::add(&txn, *id, organization_id, role.clone()).await?;
+    }
+    for id in removed {
+        user_role::remove(&txn, *id, organization_id, role.clone()).await?;
+    }
+    txn.commit().await.map_err(entity_api::error::Error::from)?;
+    Ok(())
+}
+
+/// Applies one `PATCH` operation to a user's attributes. Operations without a `path`
+/// carry an object of attributes to replace, as Okta and Azure AD send them.
+pub fn apply_user_operation(
+    attributes: &mut UserAttributes,
+    operation: &PatchOperation,
+) -> Result<(), Error> {
+    let op = operation.op.to_ascii_lowercase();
+    if op != "add" && op != "replace" {
+        return Err(Error::validation(format!(
+            "`{}` operations aren't supported on users",
+            operation.op
+        )));
+    }
+    let value = operation.value.as_ref().unwrap_or(&Json::Null);
+    match operation.path.as_deref() {
+        Some(path) => set_user_attribute(attributes, path, value),
+        None => {
+            let Json::Object(values) = value else {
+                return Err(Error::validation(
+                    "Operations without a `path` need an object `value`",
+                ));
+            };
+            for (path, value) in values {
+                set_user_attribute(attributes, path, value)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Applies one `PATCH` operation to a group's member IDs.
+pub fn apply_group_operation(
+    members: &mut Vec<Id>,
+    operation: &PatchOperation,
+) -> Result<(), Error> {
+    let op = operation.op.to_ascii_lowercase();
+    let path = operation.path.as_deref().unwrap_or("members");
+    let value = operation.value.as_ref().unwrap_or(&Json::Null);
+
+    // `members[value eq "<id>"]` names the one member to remove.
+    if let Some(selector) = path
+        .strip_prefix("members[")
+        .and_then(|rest| rest.strip_suffix(']'))
+    {
+        if op != "remove" {
+            return Err(Error::validation(
+                "Only `remove` operations may select a member",
+            ));
+        }
+        let filter = Filter::parse(selector)?;
+        let ids = filter
+            .comparisons
+            .iter()
+            .filter(|(attribute, _)| attribute == "value")
+            .map(|(_, value)| parse_id(value))
+            .collect::<Result<Vec<_>, _>>()?;
+        members.retain(|member| !ids.contains(member));
+        return Ok(());
+    }
+    if !path.eq_ignore_ascii_case("members") {
+        return Err(Error::validation(format!(
+            "`{path}` can't be changed; groups only have their members changed"
+        )));
+    }
+
+    let ids = member_ids(value)?;
+    match op.as_str() {
+        "add" => {
+            for id in ids {
+                if !members.contains(&id) {
+                    members.push(id);
+                }
+            }
+        }
+        "remove" if value.is_null() => members.clear(),
+        "remove" => members.retain(|member| !ids.contains(member)),
+        "replace" => *members = ids,
+        _ => {
+            return Err(Error::validation(format!(
+                "`{}` operations aren't supported",
+                operation.op
+            )))
+        }
+    }
+    Ok(())
+}
+
+fn set_user_attribute(
+    attributes: &mut UserAttributes,
+    path: &str,
+    value: &Json,
+) -> Result<(), Error> {
+    match path.to_ascii_lowercase().as_str() {
+        "username" => attributes.user_name = required_string(path, value)?,
+        "name.givenname" => attributes.given_name = required_string(path, value)?,
+        "name.familyname" => attributes.family_name = required_string(path, value)?,
+        "name" => {
+            if let Some(given_name) = value.get("givenName") {
+                attributes.given_name = required_string("name.givenName", given_name)?;
+            }
+            if let Some(family_name) = value.get("familyName") {
+                attributes.family_name = required_string("name.familyName", family_name)?;
+            }
+        }
+        "displayname" => attributes.display_name = value.as_str().map(str::to_string),
+        "externalid" => attributes.external_id = value.as_str().map(str::to_string),
+        // Azure AD sends booleans as the strings "True" and "False".
+        "active" => {
+            attributes.active = match value {
+                Json::Bool(active) => *active,
+                Json::String(active) if active.eq_ignore_ascii_case("true") => true,
+                Json::String(active) if active.eq_ignore_ascii_case("false") => false,
+                _ => return Err(Error::validation("`active` must be a boolean")),
+            }
+        }
+        // Attributes the platform doesn't store are accepted and ignored, as SCIM allows.
+        _ => debug!("Ignoring SCIM attribute `{path}`"),
+    }
+    Ok(())
+}
+
+/// The user IDs in a `members` value: `[{"value": "<id>"}, ...]`.
+fn member_ids(value: &Json) -> Result<Vec<Id>, Error> {
+    match value {
+        Json::Null => Ok(Vec::new()),
+        Json::Array(members) => members
+            .iter()
+            .map(|member| {
+                member
+                    .get("value")
+                    .and_then(Json::as_str)
+                    .ok_or_else(|| Error::validation("Each member needs a `value`"))
+                    .and_then(parse_id)
+            })
+            .collect(),
+        _ => Err(Error::validation("`members` must be an array")),
+    }
+}
+
+async fn provisioned_users(
+    db: &DatabaseConnection,
+    organization_id: Id,
+) -> Result<Vec<ProvisionedUser>, Error> {
+    let records = scim_user::find_by_organization(db, organization_id).await?;
+    let ids: Vec<Id> = records.iter().map(|record| record.user_id).collect();
+    let users = user::find_by_ids(db, &ids).await?;
+    Ok(records
+        .into_iter()
+        .filter_map(|record| {
+            let user = users.iter().find(|user| user.id == record.user_id)?.clone();
+            Some(ProvisionedUser {
+                user,
+                external_id: record.external_id,
+                active: record.active,
+            })
+        })
+        .collect())
+}
+
+async fn provisioned_user(
+    db: &impl ConnectionTrait,
+    record: scim_users::Model,
+) -> Result<ProvisionedUser, Error> {
+    Ok(ProvisionedUser {
+        user: user::find_by_id(db, record.user_id).await?,
+        external_id: record.external_id,
+        active: record.active,
+    })
+}
+
+async fn find_record(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    user_id: Id,
+) -> Result<scim_users::Model, Error> {
+    scim_user::find_by_organization_and_user(db, organization_id, user_id)
+        .await?
+        .ok_or_else(|| Error::entity(EntityErrorKind::NotFound))
+}
+
+fn group(
+    organization_id: Id,
+    role: &Role,
+    display_name: &'static str,
+    provisioned: &[ProvisionedUser],
+) -> Group {
+    Group {
+        role: role.clone(),
+        display_name,
+        members: provisioned
+            .iter()
+            .filter(|user| user.has_role(organization_id, role))
+            .cloned()
+            .collect(),
+    }
+}
+
+fn group_by_id(group_id: &str) -> Result<&'static (Role, &'static str), Error> {
+    GROUPS
+        .iter()
+        .find(|(role, _)| role.to_string() == group_id)
+        .ok_or_else(|| Error::entity(EntityErrorKind::NotFound))
+}
+
+/// Welcomes a newly provisioned user on behalf of the admin who created the SCIM token.
+async fn send_welcome_email(
+    db: &DatabaseConnection,
+    config: &Config,
+    organization_id: Id,
+    new_user: &users::Model,
+) {
+    let inviter = match scim_token::find_by_organization(db, organization_id).await {
+        Ok(Some(scim_tokens::Model {
+            created_by_user_id: Some(inviter_id),
+            ..
+        })) => user::find_by_id(db, inviter_id).await.ok(),
+        _ => None,
+    };
+    match inviter {
+        Some(inviter) => emails::notify_welcome_email(db, config, new_user, &inviter).await,
+        None => warn!(
+            "Not sending a welcome email to provisioned user {}: the SCIM token's creator is gone",
+            new_user.id
+        ),
+    }
+}
+
+fn validate(attributes: &UserAttributes) -> Result<(), Error> {
+    let email = attributes.user_name.trim();
+    if email.is_empty() || !email.contains('@') || email.len() > MAX_NAME_LENGTH {
+        return Err(Error::validation("`userName` must be an email address"));
+    }
+    for (name, value) in [
+        ("name.givenName", Some(&attributes.given_name)),
+        ("name.familyName", Some(&attributes.family_name)),
+        ("displayName", attributes.display_name.as_ref()),
+    ] {
+        if value.is_some_and(|value| value.len() > MAX_NAME_LENGTH) {
+            return Err(Error::validation(format!(
+                "`{name}` must be at most {MAX_NAME_LENGTH} characters"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Splits a filter into attributes, operators, keywords and (unquoted) values.
+fn tokenize(filter: &str) -> Result<Vec<String>, Error> {
+    let mut tokens = Vec::new();
+    let mut chars = filter.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            let mut value = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => value.extend(chars.next()),
+                    Some(c) => value.push(c),
+                    None => return Err(Error::validation("Unterminated string in filter")),
+                }
+            }
+            tokens.push(value);
+        } else {
+            let mut token = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                token.push(c);
+                chars.next();
+            }
+            tokens.push(token);
+        }
+    }
+    Ok(tokens)
+}
+
+fn required_string(path: &str, value: &Json) -> Result<String, Error> {
+    value
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| Error::validation(format!("`{path}` must be a string")))
+}
+
+fn parse_id(value: &str) -> Result<Id, Error> {
+    value
+        .parse()
+        .map_err(|_| Error::validation(format!("`{value}` isn't a user ID")))
+}
+
+fn string(value: String) -> Value {
+    Value::String(Some(Box::new(value)))
+}
+
+fn random_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// Compute the SHA-256 hex digest of a raw token string.
+fn hash_token(raw_token: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(raw_token.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Whether the organization alone controls `user`'s account: they're no SuperAdmin and
+/// have no role outside it. Only then may its provider take them over or change their
+/// email, which would hand over the account through a password reset.
+fn organization_controls(user: &users::Model, organization_id: Id) -> bool {
+    user.roles
+        .iter()
+        .all(|role| role.role != Role::SuperAdmin && role.organization_id == Some(organization_id))
+}
+
+fn conflict(message: &str) -> Error {
+    Error::entity(EntityErrorKind::Conflict {
+        message: message.to_string(),
+        details: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::user_roles;
+    use serde_json::json;
+
+    fn provisioned(email: &str, external_id: Option<&str>) -> ProvisionedUser {
+        ProvisionedUser {
+            user: users::Model {
+                id: Id::new_v4(),
+                email: email.to_string(),
+                first_name: "Ada".to_string(),
+                last_name: "Lovelace".to_string(),
+                display_name: None,
+                password: None,
+                github_username: None,
+                github_profile_url: None,
+                timezone: "UTC".to_string(),
+                default_coaching_session_duration_minutes:
+                    crate::duration::Duration::default_minutes(),
+                role: Role::User,
+                roles: Vec::new(),
+                invite_status: None,
+                created_at: Utc::now().into(),
+                updated_at: Utc::now().into(),
+            },
+            external_id: external_id.map(str::to_string),
+            active: true,
+        }
+    }
+
+    fn operation(op: &str, path: Option<&str>, value: Json) -> PatchOperation {
+        PatchOperation {
+            op: op.to_string(),
+            path: path.map(str::to_string),
+            value: Some(value),
+        }
+    }
+
+    #[test]
+    fn filters_match_user_names_case_insensitively_and_join_with_and() {
+        let ada = provisioned("Ada@Example.com", Some("00u1"));
+
+        let filter = Filter::parse(r#"userName eq "ada@example.com""#).unwrap();
+        assert!(filter.matches_user(&ada));
+
+        let filter =
+            Filter::parse(r#"userName eq "ada@example.com" and externalId eq "00u2""#).unwrap();
+        assert!(!filter.matches_user(&ada));
+
+        let filter = Filter::parse(r#"externalId EQ "00u1""#).unwrap();
+        assert!(filter.matches_user(&ada));
+    }
+
+    #[test]
+    fn unsupported_filters_are_rejected() {
+        assert!(Filter::parse(r#"userName sw "ada""#).is_err());
+        assert!(Filter::parse(r#"userName eq "ada" or userName eq "bob""#).is_err());
+        assert!(Filter::parse(r#"userName eq "ada"#).is_err());
+        assert!(Filter::parse("userName").is_err());
+    }
+
+    #[test]
+    fn user_operations_replace_attributes_by_path_or_object() {
+        let mut attributes = UserAttributes::from(&provisioned("ada@example.com", None));
+
+        apply_user_operation(
+            &mut attributes,
+            &operation("Replace", Some("active"), json!("False")),
+        )
+        .unwrap();
+        apply_user_operation(
+            &mut attributes,
+            &operation(
+                "replace",
+                None,
+                json!({"name.familyName": "Byron", "externalId": "00u1"}),
+            ),
+        )
+        .unwrap();
+
+        assert!(!attributes.active);
+        assert_eq!(attributes.family_name, "Byron");
+        assert_eq!(attributes.external_id.as_deref(), Some("00u1"));
+        assert!(apply_user_operation(
+            &mut attributes,
+            &operation("remove", Some("userName"), Json::Null)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn group_operations_add_remove_and_replace_members() {
+        let (a, b, c) = (Id::new_v4(), Id::new_v4(), Id::new_v4());
+        let mut members = vec![a];
+
+        apply_group_operation(
+            &mut members,
+            &operation(
+                "add",
+                Some("members"),
+                json!([{"value": b.to_string()}, {"value": a.to_string()}]),
+            ),
+        )
+        .unwrap();
+        assert_eq!(members, vec![a, b]);
+
+        apply_group_operation(
+            &mut members,
+            &operation(
+                "remove",
+                Some(&format!(r#"members[value eq "{a}"]"#)),
+                Json::Null,
+            ),
+        )
+        .unwrap();
+        assert_eq!(members, vec![b]);
+
+        apply_group_operation(
+            &mut members,
+            &operation(
+                "replace",
+                Some("members"),
+                json!([{"value": c.to_string()}]),
+            ),
+        )
+        .unwrap();
+        assert_eq!(members, vec![c]);
+
+        assert!(apply_group_operation(
+            &mut members,
+            &operation("replace", Some("displayName"), json!("Coaches")),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn organizations_control_only_accounts_with_no_role_elsewhere() {
+        let organization_id = Id::new_v4();
+        let role = |role: Role, organization_id: Option<Id>| user_roles::Model {
+            id: Id::new_v4(),
+            role,
+            organization_id,
+            user_id: Id::nil(),
+            created_at: Utc::now().into(),
+            updated_at: Utc::now().into(),
+        };
+        let with_roles = |roles| users::Model {
+            roles,
+            ..provisioned("ada@example.com", None).user
+        };
+
+        let member = with_roles(vec![
+            role(Role::User, Some(organization_id)),
+            role(Role::Admin, Some(organization_id)),
+        ]);
+        assert!(organization_controls(&member, organization_id));
+
+        // A SuperAdmin's role belongs to no organization, yet they're a member too
+        let super_admin = with_roles(vec![
+            role(Role::User, Some(organization_id)),
+            role(Role::SuperAdmin, None),
+        ]);
+        assert!(!organization_controls(&super_admin, organization_id));
+
+        let admin_elsewhere = with_roles(vec![
+            role(Role::User, Some(organization_id)),
+            role(Role::Admin, Some(Id::new_v4())),
+        ]);
+        assert!(!organization_controls(&admin_elsewhere, organization_id));
+    }
+
+    #[cfg(feature = "mock")]
+    mod mock_tests {
+        use super::*;
+        use crate::test_support::recording_publisher;
+        use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+
+        #[tokio::test]
+        async fn create_user_refuses_to_take_over_a_super_admin_member() {
+            let organization_id = Id::new_v4();
+            let super_admin = provisioned("root@example.com", None).user;
+            let role = |role: Role, organization_id: Option<Id>| user_roles::Model {
+                id: Id::new_v4(),
+                role,
+                organization_id,
+                user_id: super_admin.id,
+                created_at: Utc::now().into(),
+                updated_at: Utc::now().into(),
+            };
+            let attributes = UserAttributes::from(&provisioned("root@example.com", None));
+
+            // find_by_email → find_by_organization_and_user
+            let db = MockDatabase::new(DatabaseBackend::Postgres)
+                .append_query_results([vec![
+                    (super_admin.clone(), role(Role::User, Some(organization_id))),
+                    (super_admin.clone(), role(Role::SuperAdmin, None)),
+                ]])
+                .append_query_results([Vec::<scim_users::Model>::new()])
+                .into_connection();
+
+            let result = create_user(
+                &db,
+                &Config::from_args(["test"]),
+                organization_id,
+                attributes,
+            )
+            .await;
+
+            assert!(matches!(
+                result.unwrap_err().error_kind,
+                crate::error::DomainErrorKind::Internal(crate::error::InternalErrorKind::Entity(
+                    EntityErrorKind::Conflict { .. }
+                ))
+            ));
+        }
+
+        #[tokio::test]
+        async fn delete_user_tells_the_user_their_roles_changed() {
+            let (user_id, organization_id) = (Id::new_v4(), Id::new_v4());
+            let record = scim_users::Model {
+                id: Id::new_v4(),
+                organization_id,
+                user_id,
+                external_id: None,
+                active: true,
+                created_at: Utc::now().into(),
+                updated_at: Utc::now().into(),
+            };
+            let (publisher, events) = recording_publisher();
+            let deleted = MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            };
+
+            // find_record → DELETE roles → DELETE scim user → UPDATE users.updated_at
+            let db = MockDatabase::new(DatabaseBackend::Postgres)
+                .append_query_results(vec![vec![record]])
+                .append_exec_results(vec![deleted.clone(), deleted.clone(), deleted])
+                .into_connection();
+
+            delete_user(&db, &publisher, organization_id, user_id)
+                .await
+                .unwrap();
+
+            assert!(matches!(
+                events.lock().unwrap().as_slice(),
+                [DomainEvent::UserRolesChanged {
+                    organization_id: o,
+                    user_id: u,
+                }] if *o == organization_id && *u == user_id
+            ));
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! Full-text search across the notes, actions, agreements and goals of the user's
+//! coaching relationships. Encrypted notes can't be searched this way.
+
+use entity_api::search;
+use sea_orm::DatabaseConnection;
+
+use crate::error::Error;
+use crate::Id;
+
+pub use entity_api::search::{SearchResult, SearchResultType};
+
+/// Results returned when the caller doesn't ask for a number.
+pub const DEFAULT_LIMIT: u64 = 20;
+
+/// Most results returned at once.
+pub const MAX_LIMIT: u64 = 100;
+
+/// Longest query accepted, in characters.
+pub const MAX_QUERY_LENGTH: usize = 200;
+
+/// The best matches of `query` in the user's relationships, best first.
+pub async fn search(
+    db: &DatabaseConnection,
+    user_id: Id,
+    query: &str,
+    limit: Option<u64>,
+) -> Result<Vec<SearchResult>, Error> {
+    let query = validate_query(query)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    if !(1..=MAX_LIMIT).contains(&limit) {
+        return Err(Error::validation(format!(
+            "`limit` must be between 1 and {MAX_LIMIT}"
+        )));
+    }
+
+    Ok(search::search(db, user_id, query, limit).await?)
+}
+
+fn validate_query(query: &str) -> Result<&str, Error> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Err(Error::validation("`q` must not be empty".to_string()));
+    }
+    if query.chars().count() > MAX_QUERY_LENGTH {
+        return Err(Error::validation(format!(
+            "`q` must be at most {MAX_QUERY_LENGTH} characters"
+        )));
+    }
+    Ok(query)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queries_are_trimmed_and_must_not_be_blank_or_too_long() {
+        assert_eq!(validate_query("  career change ").unwrap(), "career change");
+        assert!(validate_query("   ").is_err());
+        assert!(validate_query(&"a".repeat(MAX_QUERY_LENGTH + 1)).is_err());
+    }
+}
@@ -0,0 +1,114 @@
+//! Seeding a fresh database.
+//!
+//! The demo data of [`demo`] has users with a known password, so a production database
+//! is only ever seeded with [`bootstrap_admin`]: one SuperAdmin with a generated password
+//! for the operator to sign in with once and change.
+
+use crate::error::{EntityErrorKind, Error};
+use crate::users;
+use log::*;
+use rand::seq::SliceRandom;
+use sea_orm::DatabaseConnection;
+
+pub use entity_api::seed_database as demo;
+
+/// Length of a generated bootstrap password
+const PASSWORD_LENGTH: usize = 24;
+
+const LOWERCASE: &[u8] = b"abcdefghijkmnopqrstuvwxyz";
+const UPPERCASE: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ";
+const DIGITS: &[u8] = b"23456789";
+const SYMBOLS: &[u8] = b"!#%+-=?@^_";
+
+/// The bootstrap SuperAdmin and the password to sign in with, shown nowhere else.
+pub struct BootstrapAdmin {
+    pub user: users::Model,
+    pub password: String,
+}
+
+/// Creates the platform's first SuperAdmin with a generated password. Refuses when there
+/// is a SuperAdmin already, so a second run can't add another account with a known
+/// password.
+pub async fn bootstrap_admin(
+    db: &DatabaseConnection,
+    email: &str,
+) -> Result<BootstrapAdmin, Error> {
+    let password = generate_password();
+    let Some(user) = entity_api::seed_bootstrap_admin(db, email, &password).await? else {
+        return Err(Error::entity(EntityErrorKind::Conflict {
+            message: "The platform already has a SuperAdmin".to_string(),
+            details: None,
+        }));
+    };
+    info!("Created the bootstrap SuperAdmin {} ({email})", user.id);
+    Ok(BootstrapAdmin { user, password })
+}
+
+/// A random password with a character of each class, so it passes any password policy.
+/// Characters that are easily confused (0/O, 1/l/I) are left out for typing it in.
+fn generate_password() -> String {
+    let mut rng = rand::thread_rng();
+    let classes = [LOWERCASE, UPPERCASE, DIGITS, SYMBOLS];
+    let all = classes.concat();
+
+    let mut password: Vec<u8> = classes
+        .iter()
+        .map(|class| *class.choose(&mut rng).unwrap())
+        .collect();
+    password.extend((password.len()..PASSWORD_LENGTH).map(|_| *all.choose(&mut rng).unwrap()));
+    password.shuffle(&mut rng);
+    String::from_utf8(password).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_passwords_have_every_character_class() {
+        let password = generate_password();
+
+        assert_eq!(password.len(), PASSWORD_LENGTH);
+        for class in [LOWERCASE, UPPERCASE, DIGITS, SYMBOLS] {
+            assert!(password.bytes().any(|c| class.contains(&c)), "{password}");
+        }
+        assert_ne!(generate_password(), password);
+    }
+
+    #[cfg(feature = "mock")]
+    mod mock_tests {
+        use super::super::*;
+        use crate::error::{DomainErrorKind, InternalErrorKind};
+        use crate::user_roles;
+        use chrono::Utc;
+        use entity_api::Role;
+        use sea_orm::{DatabaseBackend, MockDatabase};
+
+        #[tokio::test]
+        async fn no_second_admin_is_bootstrapped() {
+            let now = Utc::now();
+            let db = MockDatabase::new(DatabaseBackend::Postgres)
+                .append_query_results([vec![user_roles::Model {
+                    id: crate::Id::new_v4(),
+                    role: Role::SuperAdmin,
+                    organization_id: None,
+                    user_id: crate::Id::new_v4(),
+                    created_at: now.into(),
+                    updated_at: now.into(),
+                }]])
+                .into_connection();
+
+            let result = bootstrap_admin(&db, "admin@example.com").await;
+
+            assert!(matches!(
+                result,
+                Err(Error {
+                    error_kind: DomainErrorKind::Internal(InternalErrorKind::Entity(
+                        EntityErrorKind::Conflict { .. }
+                    )),
+                    ..
+                })
+            ));
+        }
+    }
+}
@@ -0,0 +1,596 @@
+//! Prep briefs helping a coach get ready for an upcoming coaching session.
+//!
+//! A brief gathers the previous session's agreements, the relationship's open actions,
+//! in-progress goal progress and journal entries the coachee has shared since then.
+//! When an analysis provider is configured, the LLM adds a summary of the previous
+//! session and suggested focus questions; that part is cached per session for
+//! [`CACHE_TTL_HOURS`] since it's the only costly piece. Without a provider, or when the
+//! LLM call fails, focus questions are derived from overdue actions and stalled goals.
+//! The relationship's AI privacy level decides whether the LLM is used at all and whether
+//! the previous session's transcript is part of the prompt.
+
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use entity_api::{agreement, note, session_prep_brief, transcript_segment, transcription};
+use log::*;
+use meeting_ai::traits::analysis;
+use meeting_ai::types::analysis::Message;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+
+use crate::action::{self, FindByRelationshipParams};
+use crate::ai_settings::{self, TaskType};
+use crate::error::{EntityErrorKind, Error};
+use crate::goal_progress::{self, BatchProgressParams, GoalProgressEntry};
+use crate::prompt_key::PromptKey;
+use crate::session_prep_briefs::{FocusQuestions, Model as CachedBrief};
+use crate::status::Status;
+use crate::transcription::TranscriptionStatus;
+use crate::{
+    actions, coaching_relationships, coaching_session, coaching_sessions, cost, journal_entries,
+    journal_entry, prompt_template, Id,
+};
+
+/// How long a generated summary and its questions are reused before regenerating.
+pub const CACHE_TTL_HOURS: i64 = 6;
+
+/// Journal look-back when the relationship has no previous session.
+const JOURNAL_LOOKBACK_DAYS: i64 = 30;
+
+/// Upper bound on the transcript text sent to the LLM, in characters.
+const MAX_TRANSCRIPT_CHARS: usize = 12_000;
+
+/// Allowance for the headings, open actions, goals and journal entries in a prompt when
+/// estimating its size.
+const PROMPT_OVERHEAD_CHARS: usize = 2_000;
+
+/// Most focus questions returned in a brief.
+const MAX_FOCUS_QUESTIONS: usize = 5;
+
+/// Built-in system prompt; see [`crate::prompt_template`].
+pub(crate) const DEFAULT_PROMPT: &str = "You are an assistant helping a professional coach \
+prepare for their next session with a coachee. Be concise, specific and grounded only in the \
+material provided; never invent facts. Reply with a JSON object with exactly two keys: \
+\"last_session_summary\" (a short paragraph, or null if there is no previous session) and \
+\"focus_questions\" (an array of at most {{max_focus_questions}} open-ended questions the \
+coach could ask).";
+
+/// The previous session in the relationship and what came out of it.
+#[derive(Debug, Clone, Serialize)]
+pub struct LastSession {
+    pub coaching_session_id: Id,
+    pub date: NaiveDateTime,
+    pub title: Option<String>,
+    /// LLM-written summary; `None` without an analysis provider.
+    pub summary: Option<String>,
+    pub agreements: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionPrepBrief {
+    pub coaching_session_id: Id,
+    pub last_session: Option<LastSession>,
+    /// Not yet completed (or abandoned) actions, soonest due first.
+    pub open_actions: Vec<actions::Model>,
+    pub goal_progress: Vec<GoalProgressEntry>,
+    /// Entries shared with the coach since the previous session.
+    pub shared_journal_entries: Vec<journal_entries::Model>,
+    pub focus_questions: Vec<String>,
+    /// The model that wrote the summary and questions; `None` when they're heuristic.
+    pub generated_by_model: Option<String>,
+    /// Version of the system prompt used (0 for the built-in one).
+    pub prompt_version: Option<i32>,
+    pub generated_at: Option<DateTimeWithTimeZone>,
+}
+
+/// The JSON object the LLM is asked to reply with.
+#[derive(Debug, Deserialize)]
+struct GeneratedPrep {
+    last_session_summary: Option<String>,
+    #[serde(default)]
+    focus_questions: Vec<String>,
+}
+
+/// Assembles the prep brief for `coaching_session_id`. Coach-only.
+///
+/// Reuses the cached AI part unless it's older than [`CACHE_TTL_HOURS`] or `refresh`
+/// is set.
+pub async fn brief(
+    db: &DatabaseConnection,
+    analysis_provider: Option<&dyn analysis::Provider>,
+    user_id: Id,
+    coaching_session_id: Id,
+    refresh: bool,
+) -> Result<SessionPrepBrief, Error> {
+    let (session, relationship) =
+        coaching_session::find_by_id_with_coaching_relationship(db, coaching_session_id).await?;
+    if relationship.coach_id != user_id {
+        return Err(Error::entity(EntityErrorKind::Unauthenticated));
+    }
+
+    let prior =
+        entity_api::coaching_session::find_prior_session(db, relationship.id, session.date).await?;
+    let agreements = match &prior {
+        Some(prior) => agreement::find_by_coaching_session_id(db, prior.id)
+            .await?
+            .into_iter()
+            .filter_map(|agreement| agreement.body)
+            .filter(|body| !body.trim().is_empty())
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let mut open_actions: Vec<actions::Model> = action::find_by_coaching_relationship(
+        db,
+        relationship.id,
+        FindByRelationshipParams::default(),
+    )
+    .await?
+    .into_iter()
+    .map(|with_assignees| with_assignees.action)
+    .filter(|action| !matches!(action.status, Status::Completed | Status::WontDo))
+    .collect();
+    open_actions.sort_by_key(|action| (action.due_by.is_none(), action.due_by));
+
+    let goal_progress = goal_progress::relationship_goal_progress(
+        db,
+        relationship.id,
+        BatchProgressParams {
+            status: Some(Status::InProgress),
+            ..Default::default()
+        },
+    )
+    .await?
+    .goal_progress;
+
+    let journal_since = match &prior {
+        Some(prior) => prior.date.and_utc().fixed_offset(),
+        None => (Utc::now() - Duration::days(JOURNAL_LOOKBACK_DAYS)).fixed_offset(),
+    };
+    let shared_journal_entries =
+        journal_entry::find_shared_since(db, relationship.id, journal_since).await?;
+
+    let now = Utc::now();
+    let mut brief = SessionPrepBrief {
+        coaching_session_id,
+        last_session: prior.as_ref().map(|prior| LastSession {
+            coaching_session_id: prior.id,
+            date: prior.date,
+            title: prior.title.clone(),
+            summary: None,
+            agreements,
+        }),
+        open_actions,
+        goal_progress,
+        shared_journal_entries,
+        focus_questions: Vec::new(),
+        generated_by_model: None,
+        prompt_version: None,
+        generated_at: None,
+    };
+
+    let privacy_level = relationship.ai_privacy_level;
+    let generated = match analysis_provider.filter(|_| privacy_level.allows_ai()) {
+        Some(provider) => {
+            // A cache written before the relationship last changed (e.g. its privacy
+            // level was lowered) may hold content that's no longer allowed.
+            let cached = session_prep_brief::find_by_coaching_session_id(db, coaching_session_id)
+                .await?
+                .filter(|cached| {
+                    !refresh
+                        && is_fresh(cached, now)
+                        && cached.generated_at >= relationship.updated_at
+                });
+            match cached {
+                Some(cached) => Some(cached),
+                None => {
+                    let prior_session_id = prior.as_ref().map(|p| p.id);
+                    let transcript_session_id =
+                        prior_session_id.filter(|_| privacy_level.allows_transcripts());
+                    generate(
+                        db,
+                        provider,
+                        relationship.organization_id,
+                        &brief,
+                        prior_session_id,
+                        transcript_session_id,
+                    )
+                    .await
+                }
+            }
+        }
+        None => None,
+    };
+
+    match generated {
+        Some(generated) => {
+            if let Some(last_session) = brief.last_session.as_mut() {
+                last_session.summary = generated.last_session_summary;
+            }
+            brief.focus_questions = generated.focus_questions.0;
+            brief.generated_by_model = Some(generated.model);
+            brief.prompt_version = generated.prompt_version;
+            brief.generated_at = Some(generated.generated_at);
+        }
+        None => {
+            brief.focus_questions =
+                fallback_questions(&brief.open_actions, &brief.goal_progress, now);
+        }
+    }
+
+    Ok(brief)
+}
+
+/// Asks the LLM for the summary and questions and caches them. Returns `None`, falling
+/// back to heuristic questions, when the call or the reply's parsing fails.
+///
+/// `transcript_session_id` is the previous session when the relationship's privacy level
+/// lets its transcript be sent.
+async fn generate(
+    db: &DatabaseConnection,
+    provider: &dyn analysis::Provider,
+    organization_id: Id,
+    brief: &SessionPrepBrief,
+    prior_session_id: Option<Id>,
+    transcript_session_id: Option<Id>,
+) -> Option<CachedBrief> {
+    let notes = match prior_session_id {
+        Some(id) => prior_notes(db, id).await,
+        None => Vec::new(),
+    };
+    let transcript = match transcript_session_id {
+        Some(id) => prior_transcript(db, id).await,
+        None => None,
+    };
+
+    let prompt = match prompt_template::render(
+        db,
+        PromptKey::SessionPrep,
+        &[("max_focus_questions", MAX_FOCUS_QUESTIONS.to_string())],
+    )
+    .await
+    {
+        Ok(prompt) => prompt,
+        Err(e) => {
+            warn!(
+                "session prep: failed to load the prompt for session {}: {e:?}",
+                brief.coaching_session_id
+            );
+            return None;
+        }
+    };
+    let request = match ai_settings::request(
+        db,
+        provider,
+        organization_id,
+        TaskType::Summary,
+        vec![
+            Message::system(prompt.text),
+            Message::user(build_prompt(brief, &notes, transcript.as_deref())),
+        ],
+        true,
+    )
+    .await
+    {
+        Ok(request) => request,
+        Err(e) => {
+            warn!(
+                "session prep: no AI request allowed for session {}: {e:?}",
+                brief.coaching_session_id
+            );
+            return None;
+        }
+    };
+
+    let completion = match provider.complete(request).await {
+        Ok(completion) => completion,
+        Err(e) => {
+            warn!(
+                "session prep: {} completion failed for session {}: {e}",
+                provider.provider_id(),
+                brief.coaching_session_id
+            );
+            return None;
+        }
+    };
+
+    if let Err(e) = cost::record_llm_tokens(
+        db,
+        Some(brief.coaching_session_id),
+        brief.coaching_session_id,
+        completion.total_tokens(),
+    )
+    .await
+    {
+        warn!("session prep: failed to record LLM cost: {e:?}");
+    }
+
+    let Some(generated) = parse_completion(&completion.text) else {
+        warn!(
+            "session prep: unparseable completion for session {}",
+            brief.coaching_session_id
+        );
+        return None;
+    };
+
+    let now = Utc::now().fixed_offset();
+    let cached = CachedBrief {
+        coaching_session_id: brief.coaching_session_id,
+        last_session_summary: generated.last_session_summary,
+        focus_questions: FocusQuestions(generated.focus_questions),
+        model: completion.model,
+        prompt_version: Some(prompt.version),
+        generated_at: now,
+        created_at: now,
+        updated_at: now,
+    };
+
+    match session_prep_brief::upsert(db, cached.clone()).await {
+        Ok(stored) => Some(stored),
+        Err(e) => {
+            warn!("session prep: failed to cache brief: {e:?}");
+            Some(cached)
+        }
+    }
+}
+
+/// Characters of the system and user prompts a brief for the session would send, for
+/// cost estimates: the previous session's agreements, notes and (when the privacy level
+/// allows) transcript, plus [`PROMPT_OVERHEAD_CHARS`] for everything else.
+pub(crate) async fn prompt_chars(
+    db: &DatabaseConnection,
+    session: &coaching_sessions::Model,
+    relationship: &coaching_relationships::Model,
+) -> Result<usize, Error> {
+    let mut chars = DEFAULT_PROMPT.chars().count() + PROMPT_OVERHEAD_CHARS;
+    let Some(prior) =
+        entity_api::coaching_session::find_prior_session(db, relationship.id, session.date).await?
+    else {
+        return Ok(chars);
+    };
+
+    chars += agreement::find_by_coaching_session_id(db, prior.id)
+        .await?
+        .into_iter()
+        .filter_map(|agreement| agreement.body)
+        .map(|body| body.chars().count())
+        .sum::<usize>();
+    chars += prior_notes(db, prior.id)
+        .await
+        .iter()
+        .map(|note| note.chars().count())
+        .sum::<usize>();
+    if relationship.ai_privacy_level.allows_transcripts() {
+        if let Some(transcript) = prior_transcript(db, prior.id).await {
+            chars += transcript.chars().count();
+        }
+    }
+    Ok(chars)
+}
+
+/// Note bodies of the previous session; best effort, as they only enrich the prompt.
+async fn prior_notes(db: &DatabaseConnection, coaching_session_id: Id) -> Vec<String> {
+    match note::find_by_coaching_session_ids(db, &[coaching_session_id]).await {
+        Ok(notes) => notes.into_iter().filter_map(|note| note.body).collect(),
+        Err(e) => {
+            warn!("session prep: failed to load notes for session {coaching_session_id}: {e:?}");
+            Vec::new()
+        }
+    }
+}
+
+/// The previous session's completed transcript as `speaker: text` lines, truncated to
+/// [`MAX_TRANSCRIPT_CHARS`]; best effort.
+async fn prior_transcript(db: &DatabaseConnection, coaching_session_id: Id) -> Option<String> {
+    let transcription = transcription::find_by_coaching_session(db, coaching_session_id)
+        .await
+        .ok()
+        .flatten()
+        .filter(|t| t.status == TranscriptionStatus::Completed)?;
+    let segments = transcript_segment::find_by_transcription(db, transcription.id)
+        .await
+        .ok()?;
+
+    let mut transcript = String::new();
+    for segment in segments {
+        let line = format!("{}: {}\n", segment.speaker_label, segment.text);
+        if transcript.len() + line.len() > MAX_TRANSCRIPT_CHARS {
+            break;
+        }
+        transcript.push_str(&line);
+    }
+    (!transcript.is_empty()).then_some(transcript)
+}
+
+fn build_prompt(brief: &SessionPrepBrief, notes: &[String], transcript: Option<&str>) -> String {
+    let mut prompt = String::new();
+
+    match &brief.last_session {
+        Some(last) => {
+            prompt.push_str(&format!("## Previous session ({})\n", last.date.date()));
+            push_list(&mut prompt, "Agreements", &last.agreements);
+            push_list(&mut prompt, "Notes", notes);
+            if let Some(transcript) = transcript {
+                prompt.push_str("### Transcript excerpt\n");
+                prompt.push_str(transcript);
+                prompt.push('\n');
+            }
+        }
+        None => prompt.push_str("## Previous session\nThis is the first session.\n"),
+    }
+
+    let actions: Vec<String> = brief
+        .open_actions
+        .iter()
+        .filter_map(|action| {
+            let body = action.body.as_deref()?;
+            Some(match action.due_by {
+                Some(due_by) => format!("{body} (due {})", due_by.date_naive()),
+                None => body.to_string(),
+            })
+        })
+        .collect();
+    push_list(&mut prompt, "## Open actions", &actions);
+
+    let goals: Vec<String> = brief
+        .goal_progress
+        .iter()
+        .filter_map(|goal| {
+            let title = goal.title.as_deref()?;
+            Some(format!(
+                "{title} ({}/{} actions completed)",
+                goal.progress_metrics.actions_completed, goal.progress_metrics.actions_total
+            ))
+        })
+        .collect();
+    push_list(&mut prompt, "## Goals in progress", &goals);
+
+    let journal: Vec<String> = brief
+        .shared_journal_entries
+        .iter()
+        .map(|entry| match &entry.title {
+            Some(title) => format!("{title}: {}", entry.body),
+            None => entry.body.clone(),
+        })
+        .collect();
+    push_list(
+        &mut prompt,
+        "## Coachee journal entries shared since",
+        &journal,
+    );
+
+    prompt
+}
+
+fn push_list(prompt: &mut String, heading: &str, items: &[String]) {
+    if items.is_empty() {
+        return;
+    }
+    prompt.push_str(heading);
+    prompt.push('\n');
+    for item in items {
+        prompt.push_str("- ");
+        prompt.push_str(item.trim());
+        prompt.push('\n');
+    }
+}
+
+/// Parses the LLM's JSON reply, tolerating a surrounding Markdown code fence.
+fn parse_completion(text: &str) -> Option<GeneratedPrep> {
+    let json = text
+        .trim()
+        .trim_start_matches("```json")
+        .trim_start_matches("```")
+        .trim_end_matches("```")
+        .trim();
+    let mut generated: GeneratedPrep = serde_json::from_str(json).ok()?;
+
+    generated.last_session_summary = generated
+        .last_session_summary
+        .map(|summary| summary.trim().to_string())
+        .filter(|summary| !summary.is_empty());
+    generated.focus_questions = generated
+        .focus_questions
+        .into_iter()
+        .map(|question| question.trim().to_string())
+        .filter(|question| !question.is_empty())
+        .take(MAX_FOCUS_QUESTIONS)
+        .collect();
+    Some(generated)
+}
+
+fn is_fresh(cached: &CachedBrief, now: DateTime<Utc>) -> bool {
+    now.signed_duration_since(cached.generated_at) < Duration::hours(CACHE_TTL_HOURS)
+}
+
+/// Questions derived without an LLM: one per overdue action, then one per in-progress
+/// goal with no completed actions, then a general opener.
+fn fallback_questions(
+    open_actions: &[actions::Model],
+    goal_progress: &[GoalProgressEntry],
+    now: DateTime<Utc>,
+) -> Vec<String> {
+    let overdue = open_actions.iter().filter_map(|action| {
+        let body = action.body.as_deref()?;
+        action
+            .due_by
+            .filter(|due_by| *due_by < now)
+            .map(|_| format!("What got in the way of \u{201c}{}\u{201d}?", body.trim()))
+    });
+    let stalled = goal_progress.iter().filter_map(|goal| {
+        let title = goal.title.as_deref()?;
+        (goal.progress_metrics.actions_completed == 0).then(|| {
+            format!(
+                "What would a first step toward \u{201c}{}\u{201d} look like?",
+                title.trim()
+            )
+        })
+    });
+
+    overdue
+        .chain(stalled)
+        .take(MAX_FOCUS_QUESTIONS - 1)
+        .chain(std::iter::once(
+            "What would make this session most valuable for you?".to_string(),
+        ))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn action(body: &str, due_by: Option<DateTime<Utc>>) -> actions::Model {
+        let now = Utc::now();
+        actions::Model {
+            id: Id::new_v4(),
+            coaching_session_id: Id::new_v4(),
+            goal_id: None,
+            user_id: Id::new_v4(),
+            action_series_id: None,
+            previous_action_id: None,
+            body: Some(body.to_string()),
+            effort_points: None,
+            due_by: due_by.map(|d| d.fixed_offset()),
+            status: Status::InProgress,
+            status_changed_at: now.into(),
+            created_at: now.into(),
+            updated_at: now.into(),
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn parse_completion_accepts_fenced_json_and_caps_questions() {
+        let text = "```json\n{\"last_session_summary\": \" Talked delegation. \", \
+            \"focus_questions\": [\"a\", \"\", \"b\", \"c\", \"d\", \"e\", \"f\"]}\n```";
+
+        let generated = parse_completion(text).unwrap();
+
+        assert_eq!(
+            generated.last_session_summary.as_deref(),
+            Some("Talked delegation.")
+        );
+        assert_eq!(generated.focus_questions, vec!["a", "b", "c", "d", "e"]);
+        assert!(parse_completion("not json").is_none());
+    }
+
+    #[test]
+    fn fallback_questions_ask_about_overdue_actions_first() {
+        let now = Utc::now();
+        let actions = vec![
+            action("Draft the roadmap", Some(now - Duration::days(2))),
+            action("Book a 1:1 with Sam", Some(now + Duration::days(2))),
+        ];
+
+        let questions = fallback_questions(&actions, &[], now);
+
+        assert_eq!(
+            questions,
+            vec![
+                "What got in the way of \u{201c}Draft the roadmap\u{201d}?".to_string(),
+                "What would make this session most valuable for you?".to_string(),
+            ]
+        );
+    }
+}
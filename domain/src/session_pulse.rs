@@ -0,0 +1,308 @@
+//! Post-session pulse check-ins.
+//!
+//! Once a session ends, the web process's pulse task prompts both participants over SSE
+//! with one question: "How valuable was this session?", answered 1–5. A prompt stays
+//! open for a week. Answers are private to whoever gave them; the coach only sees the
+//! coachee's answers averaged, and organization admins only each relationship's
+//! average. Neither sees an average until it spans [`MIN_RESPONSES_FOR_AGGREGATE`]
+//! answers, and then only rounded to the nearest [`AGGREGATE_STEP`] and without how many
+//! answers it spans: an exact average and count over a rolling window would give each new
+//! answer back as the difference between two of them, `n·avg_n − (n−1)·avg_{n−1}`.
+
+use chrono::{NaiveDateTime, TimeDelta, Utc};
+use entity_api::coaching_relationship;
+use entity_api::session_pulse::{self, PulseResponse};
+use log::*;
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+
+use crate::error::{EntityErrorKind, Error};
+use crate::events::{DomainEvent, EventPublisher};
+use crate::session_pulses::Model;
+use crate::{coaching_relationships, coaching_sessions, Id};
+
+pub const MIN_SCORE: i16 = 1;
+pub const MAX_SCORE: i16 = 5;
+
+/// Fewest answers an average is shown for.
+pub const MIN_RESPONSES_FOR_AGGREGATE: usize = 3;
+
+/// What averages are rounded to.
+pub const AGGREGATE_STEP: f64 = 0.5;
+
+/// Sessions that ended longer ago than this aren't prompted for, e.g. while the
+/// process was down for a day, or every past session when pulses were introduced.
+const PROMPT_WINDOW: TimeDelta = TimeDelta::hours(24);
+
+/// How long a prompt stays open.
+const PROMPT_OPEN_FOR: TimeDelta = TimeDelta::days(7);
+
+/// How far back averages look.
+const AGGREGATE_WINDOW: TimeDelta = TimeDelta::days(90);
+
+/// Longest a session can run (see `entity::duration`), so the latest start of a
+/// session that can have ended within the prompt window.
+const MAX_SESSION_LENGTH: TimeDelta =
+    TimeDelta::minutes(crate::duration::MAX_DURATION_MINUTES as i64);
+
+/// Prompts the participants of every session that ended within the last
+/// [`PROMPT_WINDOW`] and hasn't been prompted for yet. Returns how many sessions were
+/// prompted for. Per-session failures are logged and skipped.
+pub async fn request_due(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+) -> Result<usize, Error> {
+    let now = Utc::now().naive_utc();
+    let candidates = session_pulse::find_sessions_awaiting_pulse(
+        db,
+        now - PROMPT_WINDOW - MAX_SESSION_LENGTH,
+        now,
+    )
+    .await?;
+
+    let mut prompted = 0;
+    for (session, relationship) in candidates {
+        if !is_due(&session, now) {
+            continue;
+        }
+
+        let notify_user_ids = vec![relationship.coach_id, relationship.coachee_id];
+        match session_pulse::request(db, session.id, &notify_user_ids).await {
+            Ok(_) => {
+                event_publisher
+                    .publish(DomainEvent::SessionPulseRequested {
+                        coaching_relationship_id: relationship.id,
+                        coaching_session_id: session.id,
+                        notify_user_ids,
+                    })
+                    .await;
+                prompted += 1;
+            }
+            Err(e) => error!(
+                "session pulse: failed to prompt for coaching session {}: {e:?}",
+                session.id
+            ),
+        }
+    }
+
+    Ok(prompted)
+}
+
+/// The user's prompts still open, newest first.
+pub async fn find_pending(db: &DatabaseConnection, user_id: Id) -> Result<Vec<Model>, Error> {
+    let since = Utc::now() - PROMPT_OPEN_FOR;
+    Ok(session_pulse::find_pending_by_user(db, user_id, since.into()).await?)
+}
+
+/// The user's own pulse for the session.
+pub async fn find_own(
+    db: &DatabaseConnection,
+    user_id: Id,
+    coaching_session_id: Id,
+) -> Result<Model, Error> {
+    Ok(session_pulse::find_by_session_and_user(db, coaching_session_id, user_id).await?)
+}
+
+/// Records the participant's answer for a session that has ended, replacing an earlier
+/// one.
+pub async fn respond(
+    db: &DatabaseConnection,
+    user_id: Id,
+    session: &coaching_sessions::Model,
+    score: i16,
+) -> Result<Model, Error> {
+    if !(MIN_SCORE..=MAX_SCORE).contains(&score) {
+        return Err(Error::validation(format!(
+            "score must be between {MIN_SCORE} and {MAX_SCORE} (got {score})"
+        )));
+    }
+    if ends_at(session) > Utc::now().naive_utc() {
+        return Err(Error::validation(
+            "A session can be scored once it has ended".to_string(),
+        ));
+    }
+
+    Ok(session_pulse::respond(db, session.id, user_id, score).await?)
+}
+
+/// Answers over the last 90 days averaged, withheld below
+/// [`MIN_RESPONSES_FOR_AGGREGATE`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PulseAggregate {
+    /// Mean score, 1–5, to the nearest half point; `null` while too few answers were given
+    pub average_score: Option<f64>,
+    /// Relationship health from the average, 0–100; `null` alongside `average_score`
+    pub health_score: Option<u8>,
+}
+
+/// The coachee's answers in the relationship, averaged for its coach.
+pub async fn coachee_aggregate(
+    db: &DatabaseConnection,
+    user_id: Id,
+    relationship: &coaching_relationships::Model,
+) -> Result<PulseAggregate, Error> {
+    if relationship.coach_id != user_id {
+        return Err(Error::entity(EntityErrorKind::Unauthenticated));
+    }
+
+    let since = Utc::now().naive_utc() - AGGREGATE_WINDOW;
+    let responses =
+        session_pulse::find_responses_by_relationships(db, &[relationship.id], since).await?;
+    let scores: Vec<i16> = responses
+        .iter()
+        .filter(|response| response.user_id == relationship.coachee_id)
+        .map(|response| response.score)
+        .collect();
+
+    Ok(aggregate(&scores))
+}
+
+/// One relationship's pulse in the organization's analytics.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RelationshipPulse {
+    pub coaching_relationship_id: Id,
+    pub coach_id: Id,
+    pub coachee_id: Id,
+    /// Both participants' answers together
+    #[serde(flatten)]
+    pub aggregate: PulseAggregate,
+}
+
+/// Every relationship in the organization with its participants' answers averaged.
+pub async fn organization_analytics(
+    db: &DatabaseConnection,
+    organization_id: Id,
+) -> Result<Vec<RelationshipPulse>, Error> {
+    let relationships = coaching_relationship::find_by_organization(db, organization_id).await?;
+    let relationship_ids: Vec<Id> = relationships.iter().map(|r| r.id).collect();
+    let since = Utc::now().naive_utc() - AGGREGATE_WINDOW;
+    let responses =
+        session_pulse::find_responses_by_relationships(db, &relationship_ids, since).await?;
+
+    Ok(relationships
+        .into_iter()
+        .map(|relationship| {
+            let scores = scores_in(&responses, relationship.id);
+            RelationshipPulse {
+                coaching_relationship_id: relationship.id,
+                coach_id: relationship.coach_id,
+                coachee_id: relationship.coachee_id,
+                aggregate: aggregate(&scores),
+            }
+        })
+        .collect())
+}
+
+fn scores_in(responses: &[PulseResponse], coaching_relationship_id: Id) -> Vec<i16> {
+    responses
+        .iter()
+        .filter(|response| response.coaching_relationship_id == coaching_relationship_id)
+        .map(|response| response.score)
+        .collect()
+}
+
+pub(crate) fn aggregate(scores: &[i16]) -> PulseAggregate {
+    let average_score = (scores.len() >= MIN_RESPONSES_FOR_AGGREGATE).then(|| {
+        let average =
+            scores.iter().map(|score| f64::from(*score)).sum::<f64>() / scores.len() as f64;
+        (average / AGGREGATE_STEP).round() * AGGREGATE_STEP
+    });
+
+    PulseAggregate {
+        average_score,
+        health_score: average_score.map(health_score),
+    }
+}
+
+/// Maps an average on the 1–5 scale onto 0–100.
+fn health_score(average_score: f64) -> u8 {
+    let span = f64::from(MAX_SCORE - MIN_SCORE);
+    ((average_score - f64::from(MIN_SCORE)) / span * 100.0)
+        .round()
+        .clamp(0.0, 100.0) as u8
+}
+
+fn ends_at(session: &coaching_sessions::Model) -> NaiveDateTime {
+    session.date + TimeDelta::minutes(session.duration_minutes.into())
+}
+
+/// Whether the session ended within the last [`PROMPT_WINDOW`].
+fn is_due(session: &coaching_sessions::Model, now: NaiveDateTime) -> bool {
+    let ends_at = ends_at(session);
+    ends_at <= now && now - ends_at <= PROMPT_WINDOW
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session_at(date: NaiveDateTime, duration_minutes: i16) -> coaching_sessions::Model {
+        let now = Utc::now().fixed_offset();
+        coaching_sessions::Model {
+            id: Id::new_v4(),
+            coaching_relationship_id: Id::new_v4(),
+            coaching_session_series_id: None,
+            collab_document_name: None,
+            date,
+            duration_minutes,
+            title: None,
+            meeting_url: None,
+            provider: None,
+            created_at: now,
+            updated_at: now,
+            hydrated_at: None,
+            session_type_id: None,
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn sessions_are_due_from_their_end_until_the_prompt_window_closes() {
+        let now = Utc::now().naive_utc();
+
+        assert!(!is_due(&session_at(now - TimeDelta::minutes(30), 60), now));
+        assert!(is_due(&session_at(now - TimeDelta::minutes(90), 60), now));
+        assert!(!is_due(&session_at(now - TimeDelta::hours(26), 60), now));
+    }
+
+    #[test]
+    fn aggregate_withholds_averages_below_the_anonymity_threshold() {
+        let withheld = aggregate(&[5, 4]);
+        assert_eq!(withheld.average_score, None);
+        assert_eq!(withheld.health_score, None);
+
+        let shown = aggregate(&[5, 4, 3]);
+        assert_eq!(shown.average_score, Some(4.0));
+        assert_eq!(shown.health_score, Some(75));
+    }
+
+    #[test]
+    fn aggregate_rounds_averages_so_one_more_answer_cant_be_read_back_out() {
+        // Exactly 4.33 and 4.0, 4·4.0 − 3·4.33 would give back the fourth answer, a 3
+        assert_eq!(aggregate(&[5, 4, 4]).average_score, Some(4.5));
+        assert_eq!(aggregate(&[5, 4, 4, 3]).average_score, Some(4.0));
+
+        assert_eq!(aggregate(&[2, 1, 1]).average_score, Some(1.5));
+        assert_eq!(aggregate(&[2, 1, 1]).health_score, Some(13));
+    }
+
+    #[test]
+    fn health_score_spans_the_scale() {
+        assert_eq!(health_score(1.0), 0);
+        assert_eq!(health_score(3.0), 50);
+        assert_eq!(health_score(5.0), 100);
+    }
+
+    #[test]
+    fn scores_in_keeps_only_the_relationships_answers() {
+        let (ours, theirs) = (Id::new_v4(), Id::new_v4());
+        let response = |coaching_relationship_id, score| PulseResponse {
+            coaching_relationship_id,
+            user_id: Id::new_v4(),
+            score,
+        };
+        let responses = vec![response(ours, 5), response(theirs, 1), response(ours, 3)];
+
+        assert_eq!(scores_in(&responses, ours), vec![5, 3]);
+    }
+}
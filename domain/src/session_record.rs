@@ -0,0 +1,466 @@
+//! Records of engagement: one compiled document per coaching session, e.g. as evidence
+//! of coaching hours for a certification.
+//!
+//! A record gathers the session's summary, agreements, actions with their status,
+//! notes and the consent in force for it (the policy versions each participant had
+//! accepted and the relationship's AI privacy level) into a PDF. Either participant
+//! asks for it; a background pass compiles the waiting records and keeps the PDFs in
+//! object storage, from where later requests are answered. A record reflects the
+//! session as it was when compiled, so asking again with `refresh` recompiles it.
+//!
+//! The summary is the AI summary the following session's prep brief produced, so a
+//! record has one only once that brief was generated.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use entity_api::{
+    agreement, coaching_relationship, coaching_session, note, organization, policy_acceptance,
+    policy_document, session_prep_brief, session_record, user,
+};
+use log::*;
+use sea_orm::DatabaseConnection;
+use service::config::Config;
+
+use crate::action::{self, FindByRelationshipParams};
+use crate::ai_privacy_level::AiPrivacyLevel;
+use crate::error::{EntityErrorKind, Error};
+use crate::gateway::object_storage::ObjectStorage;
+use crate::pdf::Document;
+use crate::policy_kind::PolicyKind;
+use crate::retrieval::plain_text;
+use crate::session_record_status::SessionRecordStatus;
+use crate::session_records::Model;
+use crate::status::Status;
+use crate::{coaching_sessions, users, Id};
+
+/// Most waiting records a background pass compiles.
+const BATCH_SIZE: u64 = 10;
+
+/// Longest text kept from a single note, agreement or summary, in characters.
+const MAX_TEXT_CHARS: usize = 20_000;
+
+/// A session's record, as answered to a participant asking for it.
+pub enum Record {
+    /// The compiled PDF.
+    Document { file_name: String, bytes: Vec<u8> },
+    /// The record is waiting to be compiled (or failed to be).
+    Queued(Model),
+}
+
+/// The session's compiled record, queueing it when it isn't compiled yet, failed to
+/// compile, or `refresh` asks for it to be compiled afresh. Unavailable (service
+/// unavailable) unless object storage is configured.
+pub async fn fetch(
+    db: &DatabaseConnection,
+    config: &Config,
+    session: &coaching_sessions::Model,
+    refresh: bool,
+) -> Result<Record, Error> {
+    let storage = storage(config)?;
+
+    if let Some(record) = session_record::find_by_coaching_session_id(db, session.id).await? {
+        match (record.status, &record.storage_key) {
+            (SessionRecordStatus::Pending, _) if !refresh => return Ok(Record::Queued(record)),
+            (SessionRecordStatus::Ready, Some(storage_key)) if !refresh => {
+                if let Some(bytes) = storage.get(storage_key).await? {
+                    return Ok(Record::Document {
+                        file_name: file_name(session),
+                        bytes,
+                    });
+                }
+                warn!(
+                    "Record of coaching session {} is missing from object storage; recompiling it",
+                    session.id
+                );
+            }
+            _ => {}
+        }
+    }
+
+    let record = session_record::queue(db, session.id).await?;
+    info!("Queued the record of coaching session {}", session.id);
+    Ok(Record::Queued(record))
+}
+
+/// Compiles the waiting records and stores them, returning how many were stored. Does
+/// nothing unless object storage is configured.
+pub async fn compile_pending(db: &DatabaseConnection, config: &Config) -> Result<usize, Error> {
+    let Some(storage) = ObjectStorage::from_config(config)? else {
+        return Ok(0);
+    };
+
+    let mut stored = 0;
+    for record in session_record::find_pending(db, BATCH_SIZE).await? {
+        let coaching_session_id = record.coaching_session_id;
+        let key = storage_key(coaching_session_id);
+        let compiled = match compile(db, coaching_session_id).await {
+            Ok(bytes) => {
+                let byte_size = bytes.len() as i64;
+                storage
+                    .put(&key, bytes, "application/pdf")
+                    .await
+                    .map(|()| byte_size)
+                    .map_err(|e| (e, "The record couldn't be stored"))
+            }
+            Err(e) => Err((e, "The record couldn't be compiled")),
+        };
+
+        match compiled {
+            Ok(byte_size) => {
+                if session_record::mark_ready(db, &record, key, byte_size).await? {
+                    stored += 1;
+                }
+            }
+            Err((e, message)) => {
+                warn!(
+                    "Failed to compile the record of coaching session {coaching_session_id}: {e:?}"
+                );
+                session_record::mark_failed(
+                    db,
+                    &record,
+                    format!("{message}; request it again to retry"),
+                )
+                .await?;
+            }
+        }
+    }
+    Ok(stored)
+}
+
+/// Deletes the session's stored record, if object storage is configured. Its row goes
+/// with the session itself.
+pub async fn delete_stored(config: &Config, coaching_session_id: Id) -> Result<(), Error> {
+    if let Some(storage) = ObjectStorage::from_config(config)? {
+        storage.delete(&storage_key(coaching_session_id)).await?;
+    }
+    Ok(())
+}
+
+/// What a record shows, gathered from the session and its relationship.
+struct Contents {
+    session: coaching_sessions::Model,
+    organization: String,
+    coach: String,
+    coachee: String,
+    summary: Option<String>,
+    agreements: Vec<String>,
+    actions: Vec<RecordedAction>,
+    notes: Vec<String>,
+    ai_privacy_level: AiPrivacyLevel,
+    consents: Vec<Consent>,
+    compiled_at: DateTime<Utc>,
+}
+
+struct RecordedAction {
+    body: String,
+    status: Status,
+    due_by: Option<DateTime<Utc>>,
+}
+
+/// A policy version in effect at the session and when a participant accepted it.
+struct Consent {
+    participant: String,
+    kind: PolicyKind,
+    version: String,
+    accepted_at: Option<DateTime<Utc>>,
+}
+
+async fn compile(db: &DatabaseConnection, coaching_session_id: Id) -> Result<Vec<u8>, Error> {
+    let contents = gather(db, coaching_session_id).await?;
+    Ok(render(&contents))
+}
+
+async fn gather(db: &DatabaseConnection, coaching_session_id: Id) -> Result<Contents, Error> {
+    let session = coaching_session::find_by_id(db, coaching_session_id).await?;
+    let relationship =
+        coaching_relationship::find_by_id(db, session.coaching_relationship_id).await?;
+    let organization = organization::find_by_id(db, relationship.organization_id).await?;
+    let coach = user::find_by_id(db, relationship.coach_id).await?;
+    let coachee = user::find_by_id(db, relationship.coachee_id).await?;
+
+    // The summary of a session lives in the prep brief of the session after it
+    let next = coaching_session::find_by_relationship(db, relationship.id)
+        .await?
+        .into_iter()
+        .find(|later| later.date > session.date);
+    let summary = match next {
+        Some(next) => session_prep_brief::find_by_coaching_session_id(db, next.id)
+            .await?
+            .and_then(|brief| brief.last_session_summary),
+        None => None,
+    };
+
+    let agreements = agreement::find_by_coaching_session_ids(db, &[session.id])
+        .await?
+        .into_iter()
+        .filter_map(|agreement| agreement.body)
+        .map(|body| plain_text(&body, MAX_TEXT_CHARS))
+        .filter(|body| !body.is_empty())
+        .collect();
+    let notes = note::find_by_coaching_session_ids(db, &[session.id])
+        .await?
+        .into_iter()
+        .filter_map(|note| note.body)
+        .map(|body| plain_text(&body, MAX_TEXT_CHARS))
+        .filter(|body| !body.is_empty())
+        .collect();
+    let actions = action::find_by_coaching_relationship(
+        db,
+        relationship.id,
+        FindByRelationshipParams::default(),
+    )
+    .await?
+    .into_iter()
+    .map(|with_assignees| with_assignees.action)
+    .filter(|action| action.coaching_session_id == session.id)
+    .map(|action| RecordedAction {
+        body: action
+            .body
+            .map(|body| plain_text(&body, MAX_TEXT_CHARS))
+            .unwrap_or_default(),
+        status: action.status,
+        due_by: action.due_by.map(|due_by| due_by.with_timezone(&Utc)),
+    })
+    .collect();
+
+    let mut consents = Vec::new();
+    let policies = policy_document::find_in_effect(db, session.date.and_utc()).await?;
+    let policy_ids: Vec<Id> = policies.iter().map(|policy| policy.id).collect();
+    for participant in [&coach, &coachee] {
+        let acceptances =
+            policy_acceptance::find_by_user_and_documents(db, participant.id, &policy_ids).await?;
+        consents.extend(policies.iter().map(|policy| {
+            Consent {
+                participant: full_name(participant),
+                kind: policy.kind,
+                version: policy.version.clone(),
+                accepted_at: acceptances
+                    .iter()
+                    .find(|acceptance| acceptance.policy_document_id == policy.id)
+                    .map(|acceptance| acceptance.accepted_at.with_timezone(&Utc)),
+            }
+        }));
+    }
+
+    Ok(Contents {
+        session,
+        organization: organization.name,
+        coach: full_name(&coach),
+        coachee: full_name(&coachee),
+        summary: summary.map(|summary| plain_text(&summary, MAX_TEXT_CHARS)),
+        agreements,
+        actions,
+        notes,
+        ai_privacy_level: relationship.ai_privacy_level,
+        consents,
+        compiled_at: Utc::now(),
+    })
+}
+
+fn render(contents: &Contents) -> Vec<u8> {
+    let session = &contents.session;
+    let title = session
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("Coaching session on {}", session.date.date()));
+    let mut document = Document::new(format!("Record of engagement: {title}"));
+
+    document
+        .title("Record of engagement")
+        .paragraph(title)
+        .heading("Session")
+        .paragraph(format!("Organization: {}", contents.organization))
+        .paragraph(format!("Coach: {}", contents.coach))
+        .paragraph(format!("Coachee: {}", contents.coachee))
+        .paragraph(format!("Held: {}", format_naive(session.date)))
+        .paragraph(format!("Duration: {} minutes", session.duration_minutes));
+
+    document.heading("Summary");
+    match &contents.summary {
+        Some(summary) => document.paragraph(summary.as_str()),
+        None => document.paragraph("No summary was generated for this session."),
+    };
+
+    document.heading("Agreements");
+    if contents.agreements.is_empty() {
+        document.paragraph("No agreements were recorded.");
+    }
+    for agreement in &contents.agreements {
+        document.item(agreement.as_str());
+    }
+
+    document.heading("Actions");
+    if contents.actions.is_empty() {
+        document.paragraph("No actions were recorded.");
+    }
+    for action in &contents.actions {
+        let due = action
+            .due_by
+            .map(|due_by| format!(", due {}", due_by.date_naive()))
+            .unwrap_or_default();
+        document.item(format!("{} ({}{due})", action.body, action.status));
+    }
+
+    document.heading("Notes");
+    if contents.notes.is_empty() {
+        document.paragraph("No notes were recorded.");
+    }
+    for note in &contents.notes {
+        document.paragraph(note.as_str());
+    }
+
+    document.heading("Consent").paragraph(format!(
+        "AI privacy level of the coaching relationship: {}",
+        privacy_level_description(contents.ai_privacy_level)
+    ));
+    if contents.consents.is_empty() {
+        document.paragraph("No policies were in effect when the session was held.");
+    }
+    for consent in &contents.consents {
+        let accepted = consent
+            .accepted_at
+            .map(|accepted_at| format!("accepted {}", format_utc(accepted_at)))
+            .unwrap_or_else(|| "not accepted".to_string());
+        document.item(format!(
+            "{}: {} version {}, {accepted}",
+            consent.participant,
+            policy_name(consent.kind),
+            consent.version
+        ));
+    }
+
+    document.paragraph(format!(
+        "Compiled {} from the session as recorded on the Refactor Platform.",
+        format_utc(contents.compiled_at)
+    ));
+    document.render()
+}
+
+fn storage(config: &Config) -> Result<ObjectStorage, Error> {
+    ObjectStorage::from_config(config)?.ok_or_else(|| {
+        warn!("Session records were requested, but object storage isn't configured");
+        Error::entity(EntityErrorKind::ServiceUnavailable)
+    })
+}
+
+fn storage_key(coaching_session_id: Id) -> String {
+    format!("session-records/{coaching_session_id}.pdf")
+}
+
+fn file_name(session: &coaching_sessions::Model) -> String {
+    format!("session-record-{}.pdf", session.date.date())
+}
+
+fn full_name(user: &users::Model) -> String {
+    format!("{} {}", user.first_name, user.last_name)
+}
+
+fn format_naive(date: NaiveDateTime) -> String {
+    date.format("%Y-%m-%d %H:%M UTC").to_string()
+}
+
+fn format_utc(at: DateTime<Utc>) -> String {
+    format_naive(at.naive_utc())
+}
+
+fn policy_name(kind: PolicyKind) -> &'static str {
+    match kind {
+        PolicyKind::TermsOfService => "Terms of Service",
+        PolicyKind::PrivacyPolicy => "Privacy Policy",
+    }
+}
+
+fn privacy_level_description(level: AiPrivacyLevel) -> &'static str {
+    match level {
+        AiPrivacyLevel::Disabled => "disabled (no content is shared with AI)",
+        AiPrivacyLevel::NotesOnly => "notes only (no transcripts are shared with AI)",
+        AiPrivacyLevel::Full => "full (all content, including transcripts)",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone};
+
+    fn contents() -> Contents {
+        let now = Utc::now();
+        let date = NaiveDate::from_ymd_opt(2026, 10, 1)
+            .unwrap()
+            .and_hms_opt(15, 0, 0)
+            .unwrap();
+        Contents {
+            session: coaching_sessions::Model {
+                id: Id::new_v4(),
+                coaching_relationship_id: Id::new_v4(),
+                coaching_session_series_id: None,
+                collab_document_name: None,
+                date,
+                duration_minutes: 60,
+                title: None,
+                meeting_url: None,
+                provider: None,
+                created_at: now.into(),
+                updated_at: now.into(),
+                hydrated_at: None,
+                session_type_id: None,
+                deleted_at: None,
+            },
+            organization: "Acme".to_string(),
+            coach: "Caroline Coach".to_string(),
+            coachee: "Chris Coachee".to_string(),
+            summary: None,
+            agreements: vec!["Meet every other week".to_string()],
+            actions: vec![RecordedAction {
+                body: "Draft the delegation plan".to_string(),
+                status: Status::InProgress,
+                due_by: Some(Utc.with_ymd_and_hms(2026, 10, 8, 0, 0, 0).unwrap()),
+            }],
+            notes: Vec::new(),
+            ai_privacy_level: AiPrivacyLevel::NotesOnly,
+            consents: vec![Consent {
+                participant: "Chris Coachee".to_string(),
+                kind: PolicyKind::PrivacyPolicy,
+                version: "2026-09".to_string(),
+                accepted_at: None,
+            }],
+            compiled_at: now,
+        }
+    }
+
+    fn contains(haystack: &[u8], needle: &str) -> bool {
+        haystack
+            .windows(needle.len())
+            .any(|window| window == needle.as_bytes())
+    }
+
+    #[test]
+    fn render_lays_out_every_section_of_the_record() {
+        let pdf = render(&contents());
+
+        assert!(pdf.starts_with(b"%PDF-"));
+        for text in [
+            "(Coaching session on 2026-10-01)",
+            "(Held: 2026-10-01 15:00 UTC)",
+            "(No summary was generated for this session.)",
+            "(Meet every other week)",
+            "(Draft the delegation plan \\(In Progress, due 2026-10-08\\))",
+            "(No notes were recorded.)",
+            "(Chris Coachee: Privacy Policy version 2026-09, not accepted)",
+        ] {
+            assert!(contains(&pdf, text), "the record lacks {text}");
+        }
+    }
+
+    #[test]
+    fn records_are_kept_and_named_by_session() {
+        let contents = contents();
+        assert_eq!(
+            storage_key(contents.session.id),
+            format!("session-records/{}.pdf", contents.session.id)
+        );
+        assert_eq!(
+            file_name(&contents.session),
+            "session-record-2026-10-01.pdf"
+        );
+    }
+}
@@ -0,0 +1,370 @@
+//! Coachees asking for ad-hoc sessions.
+//!
+//! A relationship's coachee requests a session with the time windows that suit them; the
+//! coach is notified over SSE and by email, and the pending requests form their waiting
+//! list. The coach either accepts a request, which schedules the session, or declines it
+//! with a reason; either way the coachee is notified. Every status a request moves
+//! through is kept in its history.
+
+use chrono::Utc;
+use entity_api::session_request;
+use log::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::DatabaseConnection;
+use service::config::Config;
+
+use crate::coaching_session;
+use crate::duration::Duration;
+use crate::error::{EntityErrorKind, Error};
+use crate::events::{DomainEvent, EventPublisher};
+use crate::session_request_status::SessionRequestStatus;
+use crate::session_requests::{Model, StatusChange};
+use crate::{coaching_relationships, coaching_sessions, emails, Id};
+
+/// Most time windows a single request may offer.
+pub const MAX_WINDOWS: usize = 5;
+/// Longest message a coachee may send with a request.
+pub const MAX_MESSAGE_CHARS: usize = 2000;
+/// Longest reason a coach may give for declining.
+pub const MAX_REASON_CHARS: usize = 1000;
+
+/// Requests a session in `relationship`. Coachee-only; the coach is notified.
+pub async fn create(
+    db: &DatabaseConnection,
+    config: &Config,
+    event_publisher: &EventPublisher,
+    user_id: Id,
+    relationship: &coaching_relationships::Model,
+    model: Model,
+) -> Result<Model, Error> {
+    if relationship.coachee_id != user_id {
+        return Err(Error::entity(EntityErrorKind::Unauthenticated));
+    }
+
+    let model = validate(model)?;
+    let request = session_request::create(db, relationship.id, user_id, model).await?;
+
+    info!(
+        "Session request {} created in relationship {}",
+        request.id, relationship.id
+    );
+
+    publish(
+        event_publisher,
+        relationship,
+        user_id,
+        &request,
+        |coaching_relationship_id, session_request, notify_user_ids| {
+            DomainEvent::SessionRequestCreated {
+                coaching_relationship_id,
+                session_request,
+                notify_user_ids,
+            }
+        },
+    )
+    .await;
+    emails::notify_session_requested(db, config, relationship, &request).await;
+
+    Ok(request)
+}
+
+/// A relationship's requests, optionally only those in `status`, oldest first.
+pub async fn find_by_relationship(
+    db: &DatabaseConnection,
+    relationship: &coaching_relationships::Model,
+    status: Option<SessionRequestStatus>,
+) -> Result<Vec<Model>, Error> {
+    Ok(session_request::find_by_relationship(db, relationship.id, status).await?)
+}
+
+/// A request of `relationship`, reported as not found if it belongs to another.
+pub async fn find_by_id(
+    db: &DatabaseConnection,
+    relationship: &coaching_relationships::Model,
+    id: Id,
+) -> Result<Model, Error> {
+    let request = session_request::find_by_id(db, id).await?;
+    if request.coaching_relationship_id != relationship.id {
+        return Err(Error::entity(EntityErrorKind::NotFound));
+    }
+    Ok(request)
+}
+
+/// Accepts a pending request by scheduling its session. Coach-only. The session starts
+/// at `starts_at`, or the start of the request's first window, and lasts
+/// `duration_minutes`, or what the request asked for. The coachee is notified.
+///
+/// Returns the accepted request and its new session.
+#[allow(clippy::too_many_arguments)]
+pub async fn accept(
+    db: &DatabaseConnection,
+    config: &Config,
+    event_publisher: &EventPublisher,
+    user_id: Id,
+    relationship: &coaching_relationships::Model,
+    id: Id,
+    starts_at: Option<DateTimeWithTimeZone>,
+    duration_minutes: Option<i16>,
+) -> Result<(Model, coaching_sessions::Model), Error> {
+    let request = find_pending_as_coach(db, user_id, relationship, id).await?;
+    let starts_at = starts_at
+        .or_else(|| request.preferred_windows.0.first().map(|w| w.starts_at))
+        .ok_or_else(|| Error::validation("`starts_at` is required"))?;
+    let duration =
+        coaching_session::parse_duration_minutes(duration_minutes.or(request.duration_minutes))?;
+
+    let now = Utc::now();
+    let session = coaching_session::create(
+        db,
+        config,
+        event_publisher,
+        coaching_sessions::Model {
+            id: Id::nil(),
+            coaching_relationship_id: relationship.id,
+            coaching_session_series_id: None,
+            collab_document_name: None,
+            date: starts_at.naive_utc(),
+            duration_minutes: Duration::default_minutes(),
+            title: None,
+            meeting_url: None,
+            provider: None,
+            created_at: now.into(),
+            updated_at: now.into(),
+            hydrated_at: None,
+            session_type_id: None,
+            deleted_at: None,
+        },
+        duration,
+    )
+    .await?;
+
+    let change = StatusChange {
+        status: SessionRequestStatus::Accepted,
+        changed_by_user_id: user_id,
+        reason: None,
+        changed_at: now.into(),
+    };
+    let request = match session_request::resolve(db, request, change, Some(session.id)).await {
+        Ok(request) => request,
+        Err(e) => {
+            // Answered by someone else meanwhile; don't leave a second session behind
+            if let Err(delete_error) = coaching_session::delete(db, session.id).await {
+                warn!(
+                    "Failed to delete session {} of an already answered request: {delete_error:?}",
+                    session.id
+                );
+            }
+            return Err(e.into());
+        }
+    };
+
+    info!(
+        "Session request {} accepted as session {}",
+        request.id, session.id
+    );
+    publish_resolved(event_publisher, relationship, user_id, &request).await;
+
+    Ok((request, session))
+}
+
+/// Declines a pending request with the coach's `reason`. Coach-only; the coachee is
+/// notified.
+pub async fn decline(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    user_id: Id,
+    relationship: &coaching_relationships::Model,
+    id: Id,
+    reason: &str,
+) -> Result<Model, Error> {
+    let reason = reason.trim();
+    if reason.is_empty() {
+        return Err(Error::validation("`reason` must not be empty"));
+    }
+    if reason.chars().count() > MAX_REASON_CHARS {
+        return Err(Error::validation(format!(
+            "`reason` must be at most {MAX_REASON_CHARS} characters"
+        )));
+    }
+
+    let request = find_pending_as_coach(db, user_id, relationship, id).await?;
+    let change = StatusChange {
+        status: SessionRequestStatus::Declined,
+        changed_by_user_id: user_id,
+        reason: Some(reason.to_string()),
+        changed_at: Utc::now().into(),
+    };
+    let request = session_request::resolve(db, request, change, None).await?;
+
+    info!("Session request {} declined", request.id);
+    publish_resolved(event_publisher, relationship, user_id, &request).await;
+
+    Ok(request)
+}
+
+/// A request of `relationship` the coach `user_id` can still answer.
+async fn find_pending_as_coach(
+    db: &DatabaseConnection,
+    user_id: Id,
+    relationship: &coaching_relationships::Model,
+    id: Id,
+) -> Result<Model, Error> {
+    if relationship.coach_id != user_id {
+        return Err(Error::entity(EntityErrorKind::Unauthenticated));
+    }
+
+    let request = find_by_id(db, relationship, id).await?;
+    if request.status != SessionRequestStatus::Pending {
+        return Err(Error::entity(EntityErrorKind::Conflict {
+            message: "The session request was already answered".to_string(),
+            details: None,
+        }));
+    }
+    Ok(request)
+}
+
+async fn publish_resolved(
+    event_publisher: &EventPublisher,
+    relationship: &coaching_relationships::Model,
+    actor_id: Id,
+    request: &Model,
+) {
+    publish(
+        event_publisher,
+        relationship,
+        actor_id,
+        request,
+        |coaching_relationship_id, session_request, notify_user_ids| {
+            DomainEvent::SessionRequestResolved {
+                coaching_relationship_id,
+                session_request,
+                notify_user_ids,
+            }
+        },
+    )
+    .await;
+}
+
+/// Notifies the participant other than `actor_id` about a request change.
+async fn publish(
+    event_publisher: &EventPublisher,
+    relationship: &coaching_relationships::Model,
+    actor_id: Id,
+    request: &Model,
+    event: impl FnOnce(Id, serde_json::Value, Vec<Id>) -> DomainEvent,
+) {
+    let notify_user_ids: Vec<Id> = [relationship.coach_id, relationship.coachee_id]
+        .into_iter()
+        .filter(|id| *id != actor_id)
+        .collect();
+    if notify_user_ids.is_empty() {
+        return;
+    }
+
+    match serde_json::to_value(request) {
+        Ok(payload) => {
+            event_publisher
+                .publish(event(relationship.id, payload, notify_user_ids))
+                .await
+        }
+        Err(e) => error!(
+            "session request SSE: failed to serialize request {}: {e:?}",
+            request.id
+        ),
+    }
+}
+
+/// Checks the windows (one to [`MAX_WINDOWS`], each ending after it starts and not yet
+/// over) and the requested duration, and trims the message, dropping an empty one.
+fn validate(mut model: Model) -> Result<Model, Error> {
+    let windows = &model.preferred_windows.0;
+    if windows.is_empty() {
+        return Err(Error::validation(
+            "`preferred_windows` must offer at least one window",
+        ));
+    }
+    if windows.len() > MAX_WINDOWS {
+        return Err(Error::validation(format!(
+            "`preferred_windows` may offer at most {MAX_WINDOWS} windows"
+        )));
+    }
+    let now = Utc::now();
+    for window in windows {
+        if window.ends_at <= window.starts_at {
+            return Err(Error::validation(
+                "each preferred window must end after it starts",
+            ));
+        }
+        if window.ends_at <= now {
+            return Err(Error::validation("preferred windows must be in the future"));
+        }
+    }
+
+    coaching_session::parse_duration_minutes(model.duration_minutes)?;
+
+    model.message = model
+        .message
+        .map(|message| message.trim().to_string())
+        .filter(|message| !message.is_empty());
+    if model
+        .message
+        .as_ref()
+        .is_some_and(|message| message.chars().count() > MAX_MESSAGE_CHARS)
+    {
+        return Err(Error::validation(format!(
+            "`message` must be at most {MAX_MESSAGE_CHARS} characters"
+        )));
+    }
+    Ok(model)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session_requests::{StatusHistory, TimeWindow, TimeWindows};
+
+    fn request(windows: Vec<(i64, i64)>) -> Model {
+        let now = Utc::now();
+        Model {
+            id: Id::new_v4(),
+            coaching_relationship_id: Id::new_v4(),
+            requested_by_user_id: Id::new_v4(),
+            preferred_windows: TimeWindows(
+                windows
+                    .into_iter()
+                    .map(|(starts, ends)| TimeWindow {
+                        starts_at: (now + chrono::Duration::hours(starts)).into(),
+                        ends_at: (now + chrono::Duration::hours(ends)).into(),
+                    })
+                    .collect(),
+            ),
+            duration_minutes: None,
+            message: Some("  ".to_string()),
+            status: SessionRequestStatus::Pending,
+            decline_reason: None,
+            coaching_session_id: None,
+            status_history: StatusHistory::default(),
+            created_at: now.into(),
+            updated_at: now.into(),
+        }
+    }
+
+    #[test]
+    fn validate_requires_one_to_five_future_windows() {
+        assert!(validate(request(vec![(24, 26)])).is_ok());
+        assert!(validate(request(vec![])).is_err());
+        assert!(validate(request(vec![(24, 26); MAX_WINDOWS + 1])).is_err());
+        assert!(validate(request(vec![(26, 24)])).is_err());
+        assert!(validate(request(vec![(-3, -1)])).is_err());
+    }
+
+    #[test]
+    fn validate_drops_a_blank_message_and_checks_the_duration() {
+        assert_eq!(validate(request(vec![(24, 26)])).unwrap().message, None);
+        assert!(validate(Model {
+            duration_minutes: Some(0),
+            ..request(vec![(24, 26)])
+        })
+        .is_err());
+    }
+}
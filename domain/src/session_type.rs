@@ -0,0 +1,246 @@
+//! Organization-level session types ("Intro call", "Regular session", "Review").
+//!
+//! Admins manage an organization's types; any member can list them. A coaching session
+//! may be of one of its organization's types, in which case it defaults to the type's
+//! duration. [`usage`] reports how many sessions of each type were held.
+
+use chrono::{Days, NaiveDate};
+use entity_api::error::EntityApiErrorKind;
+use entity_api::session_type;
+use sea_orm::{ConnectionTrait, DatabaseConnection};
+
+use crate::duration::Duration;
+use crate::error::{EntityErrorKind, Error};
+use crate::session_types::Model;
+use crate::Id;
+
+pub use entity_api::session_type::{find_by_organization, SessionTypeUsage};
+
+/// Longest a session type's name may be.
+pub const MAX_NAME_LENGTH: usize = 100;
+
+pub async fn create(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    model: Model,
+) -> Result<Model, Error> {
+    validate(&model)?;
+    Ok(session_type::create(db, organization_id, model).await?)
+}
+
+pub async fn update(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    id: Id,
+    model: Model,
+) -> Result<Model, Error> {
+    validate(&model)?;
+    let existing = find_in_organization(db, organization_id, id).await?;
+    Ok(session_type::update(db, existing, model).await?)
+}
+
+/// Deletes a session type. Its sessions are kept, untyped.
+pub async fn delete(db: &DatabaseConnection, organization_id: Id, id: Id) -> Result<(), Error> {
+    find_in_organization(db, organization_id, id).await?;
+    Ok(session_type::delete_by_id(db, id).await?)
+}
+
+/// A session type by id, reported as not found unless it belongs to `organization_id`.
+pub async fn find_in_organization(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+    id: Id,
+) -> Result<Model, Error> {
+    let session_type = session_type::find_by_id(db, id).await?;
+    if session_type.organization_id != organization_id {
+        return Err(Error::entity(EntityErrorKind::NotFound));
+    }
+    Ok(session_type)
+}
+
+/// The session type a coaching session in `organization_id` is given, refusing types of
+/// other organizations as invalid input rather than a missing resource.
+pub async fn find_for_session(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+    id: Id,
+) -> Result<Model, Error> {
+    let not_in_organization = || {
+        Error::validation("`session_type_id` must be a session type of the session's organization")
+    };
+    match session_type::find_by_id(db, id).await {
+        Ok(session_type) if session_type.organization_id == organization_id => Ok(session_type),
+        Ok(_) => Err(not_in_organization()),
+        Err(e) if matches!(e.error_kind, EntityApiErrorKind::RecordNotFound) => {
+            Err(not_in_organization())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Sessions of the organization dated from `from_date` through `to_date`, counted per
+/// session type: every type, including those without sessions, alphabetically, then the
+/// sessions without a type.
+pub async fn usage(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    from_date: NaiveDate,
+    to_date: NaiveDate,
+) -> Result<Vec<SessionTypeUsage>, Error> {
+    if from_date > to_date {
+        return Err(Error::validation("`from_date` must not be after `to_date`"));
+    }
+    let from = from_date.and_time(chrono::NaiveTime::MIN);
+    let to = to_date
+        .checked_add_days(Days::new(1))
+        .unwrap_or(to_date)
+        .and_time(chrono::NaiveTime::MIN);
+
+    let types = session_type::find_by_organization(db, organization_id).await?;
+    let counts = session_type::count_sessions_by_type(db, organization_id, from, to).await?;
+    Ok(bucket(types, counts))
+}
+
+/// One entry per type plus one for untyped sessions, with the counts found for each.
+fn bucket(types: Vec<Model>, counts: Vec<session_type::TypeCount>) -> Vec<SessionTypeUsage> {
+    let count_of = |session_type_id: Option<Id>| {
+        counts
+            .iter()
+            .find(|count| count.session_type_id == session_type_id)
+            .map_or((0, 0), |count| (count.session_count, count.total_minutes))
+    };
+
+    let mut usage: Vec<SessionTypeUsage> = types
+        .into_iter()
+        .map(|session_type| {
+            let (session_count, total_minutes) = count_of(Some(session_type.id));
+            SessionTypeUsage {
+                session_type_id: Some(session_type.id),
+                name: Some(session_type.name),
+                session_count,
+                total_minutes,
+            }
+        })
+        .collect();
+    let (session_count, total_minutes) = count_of(None);
+    usage.push(SessionTypeUsage {
+        session_type_id: None,
+        name: None,
+        session_count,
+        total_minutes,
+    });
+    usage
+}
+
+fn validate(model: &Model) -> Result<(), Error> {
+    let name = model.name.trim();
+    if name.is_empty() {
+        return Err(Error::validation("`name` must not be empty"));
+    }
+    if name.chars().count() > MAX_NAME_LENGTH {
+        return Err(Error::validation(format!(
+            "`name` may be at most {MAX_NAME_LENGTH} characters"
+        )));
+    }
+    Duration::try_from(model.default_duration_minutes)
+        .map_err(|out| Error::from(entity_api::error::Error::from(out)))?;
+    if let Some(color) = &model.color {
+        if !is_hex_color(color) {
+            return Err(Error::validation(
+                "`color` must be a hex color like #3b82f6",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Whether `color` is `#` followed by six hex digits.
+fn is_hex_color(color: &str) -> bool {
+    color
+        .strip_prefix('#')
+        .is_some_and(|hex| hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session_type(name: &str) -> Model {
+        let now = chrono::Utc::now().into();
+        Model {
+            id: Id::new_v4(),
+            organization_id: Id::new_v4(),
+            name: name.to_string(),
+            default_duration_minutes: 30,
+            color: Some("#3B82F6".to_string()),
+            requires_recording: false,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn validate_rejects_blank_names_bad_durations_and_colors() {
+        assert!(validate(&session_type("Intro call")).is_ok());
+        assert!(validate(&session_type("  ")).is_err());
+        assert!(validate(&Model {
+            default_duration_minutes: 0,
+            ..session_type("Intro call")
+        })
+        .is_err());
+        for color in ["blue", "#3b82f", "#3b82fg", "3b82f6"] {
+            assert!(
+                validate(&Model {
+                    color: Some(color.to_string()),
+                    ..session_type("Intro call")
+                })
+                .is_err(),
+                "{color} should be refused"
+            );
+        }
+    }
+
+    #[test]
+    fn usage_lists_every_type_then_the_untyped_sessions() {
+        let intro = session_type("Intro call");
+        let review = session_type("Review");
+        let counts = vec![
+            session_type::TypeCount {
+                session_type_id: None,
+                session_count: 2,
+                total_minutes: 120,
+            },
+            session_type::TypeCount {
+                session_type_id: Some(review.id),
+                session_count: 1,
+                total_minutes: 45,
+            },
+        ];
+
+        let usage = bucket(vec![intro.clone(), review.clone()], counts);
+
+        assert_eq!(
+            usage,
+            vec![
+                SessionTypeUsage {
+                    session_type_id: Some(intro.id),
+                    name: Some(intro.name),
+                    session_count: 0,
+                    total_minutes: 0,
+                },
+                SessionTypeUsage {
+                    session_type_id: Some(review.id),
+                    name: Some(review.name),
+                    session_count: 1,
+                    total_minutes: 45,
+                },
+                SessionTypeUsage {
+                    session_type_id: None,
+                    name: None,
+                    session_count: 2,
+                    total_minutes: 120,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! Purging soft-deleted records once they're past the retention window.
+//!
+//! Deleting an action, agreement, note, goal or coaching session only marks it deleted:
+//! reads leave it out, and it can be restored until it's `retention_days` old. After
+//! that the daily purge deletes it for good, a session together with its notes,
+//! agreements and actions, collaborative document, stored recordings and attachments.
+
+use chrono::Utc;
+use entity_api::{action, agreement, coaching_session, goal, note};
+use log::*;
+use sea_orm::DatabaseConnection;
+use service::config::Config;
+
+use crate::error::Error;
+
+/// Most sessions purged per batch; each one deletes external resources too.
+const SESSION_BATCH_SIZE: u64 = 50;
+
+/// Permanently deletes what was soft-deleted more than `retention_days` ago, returning
+/// how many records were purged. A session whose external resources fail to delete is
+/// logged and left for the next pass. Purges none while a legal hold is active.
+pub async fn purge(
+    db: &DatabaseConnection,
+    config: &Config,
+    retention_days: u32,
+) -> Result<u64, Error> {
+    if crate::legal_hold::purges_paused(db).await? {
+        info!("[soft-delete] a legal hold is active; keeping deleted records");
+        return Ok(0);
+    }
+    let cutoff = (Utc::now() - chrono::Duration::days(retention_days.into())).into();
+
+    let mut purged = 0;
+    loop {
+        let sessions =
+            coaching_session::find_deleted_before(db, cutoff, SESSION_BATCH_SIZE).await?;
+        let batch_len = sessions.len() as u64;
+        let mut batch_purged = 0;
+        for session in sessions {
+            match crate::coaching_session::purge(db, config, &session).await {
+                Ok(()) => batch_purged += 1,
+                Err(e) => warn!(
+                    "[soft-delete] failed to purge session {}: {e:?}",
+                    session.id
+                ),
+            }
+        }
+        purged += batch_purged;
+        // A short batch was the last; one where nothing was purged would only repeat
+        if batch_len < SESSION_BATCH_SIZE || batch_purged == 0 {
+            break;
+        }
+    }
+
+    purged += action::purge_deleted_before(db, cutoff).await?;
+    purged += agreement::purge_deleted_before(db, cutoff).await?;
+    purged += note::purge_deleted_before(db, cutoff).await?;
+    purged += goal::purge_deleted_before(db, cutoff).await?;
+    Ok(purged)
+}
+
+#[cfg(test)]
+// We need to gate seaORM's mock feature behind conditional compilation because
+// the feature removes the Clone trait implementation from seaORM's DatabaseConnection.
+// see https://github.com/SeaQL/sea-orm/issues/830
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use crate::coaching_sessions;
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+
+    /// Scalar row for the active legal holds `.count()` the purge runs first.
+    fn active_legal_holds(n: i64) -> std::collections::BTreeMap<String, sea_orm::Value> {
+        std::collections::BTreeMap::from([(
+            "num_items".to_owned(),
+            sea_orm::Value::BigInt(Some(n)),
+        )])
+    }
+
+    fn deleted(rows_affected: u64) -> MockExecResult {
+        MockExecResult {
+            last_insert_id: 0,
+            rows_affected,
+        }
+    }
+
+    #[tokio::test]
+    async fn purge_sums_what_each_entity_purged() {
+        // No sessions due, then actions, agreements, notes and goals by cutoff
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([[active_legal_holds(0)]])
+            .append_query_results([Vec::<coaching_sessions::Model>::new()])
+            .append_exec_results([deleted(1), deleted(2), deleted(3), deleted(4)])
+            .into_connection();
+
+        let purged = purge(&db, &Config::default(), 30).await.unwrap();
+
+        assert_eq!(purged, 10);
+    }
+
+    #[tokio::test]
+    async fn purge_pauses_under_a_legal_hold() {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([[active_legal_holds(1)]])
+            .into_connection();
+
+        let purged = purge(&db, &Config::default(), 30).await.unwrap();
+
+        assert_eq!(purged, 0);
+        assert_eq!(
+            db.into_transaction_log().len(),
+            1,
+            "only the hold count ran"
+        );
+    }
+}
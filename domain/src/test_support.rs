@@ -2,7 +2,7 @@
 
 use crate::events::{DomainEvent, EventPublisher};
 use async_trait::async_trait;
-use events::EventHandler;
+use events::{EventHandler, HandlerError};
 use std::sync::{Arc, Mutex};
 
 /// Captures every published event, in order, for assertion.
@@ -12,8 +12,26 @@ struct RecordingHandler {
 
 #[async_trait]
 impl EventHandler for RecordingHandler {
-    async fn handle(&self, event: &DomainEvent) {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), HandlerError> {
         self.events.lock().unwrap().push(event.clone());
+        Ok(())
+    }
+}
+
+/// The row the audit trail's INSERT ... RETURNING yields in mock sequences.
+#[cfg(feature = "mock")]
+pub(crate) fn audit_entry() -> crate::audit_entries::Model {
+    crate::audit_entries::Model {
+        id: crate::Id::new_v4(),
+        organization_id: None,
+        actor_user_id: None,
+        action: "updated".to_string(),
+        entity_type: String::new(),
+        entity_id: crate::Id::new_v4(),
+        changes: serde_json::Value::Null,
+        snapshot: None,
+        ip_address: None,
+        created_at: chrono::Utc::now().into(),
     }
 }
 
@@ -0,0 +1,452 @@
+//! Reports of recurring themes across a coaching relationship's sessions.
+//!
+//! The LLM reads a digest of each held session and names the challenges that keep
+//! coming up and the arcs of progress across the engagement. A session's digest is its
+//! AI summary when a later session's prep brief produced one, plus its agreements, notes
+//! and the actions created in it. Transcripts aren't sent, so any AI privacy level other
+//! than `disabled` allows a report.
+//!
+//! The report is stored per relationship and reused until another session has been held
+//! or the relationship changed (e.g. its privacy level); `refresh` regenerates it.
+
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use entity_api::{agreement, note, session_prep_brief, theme_report};
+use log::*;
+use meeting_ai::traits::analysis;
+use meeting_ai::types::analysis::Message;
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+
+use crate::action::{self, FindByRelationshipParams};
+use crate::ai_settings::{self, TaskType};
+use crate::error::{DomainErrorKind, EntityErrorKind, Error, InternalErrorKind};
+use crate::prompt_key::PromptKey;
+use crate::prompt_template;
+use crate::retrieval::plain_text;
+use crate::theme_reports::{Model, Theme, Themes};
+use crate::{coaching_relationships, coaching_sessions, cost, Id};
+
+/// Most recent held sessions a report is generated from.
+pub const MAX_SESSIONS: usize = 20;
+
+/// Sessions with content needed before themes can recur.
+const MIN_SESSIONS: usize = 2;
+
+/// Longest digest of a single session, in characters.
+const MAX_DIGEST_CHARS: usize = 1_500;
+
+/// Most themes kept per list.
+const MAX_THEMES: usize = 6;
+
+/// Built-in system prompt; see [`crate::prompt_template`].
+pub(crate) const DEFAULT_PROMPT: &str = "You help a professional coach see patterns across \
+an engagement with a coachee. You are given digests of their sessions, oldest first. \
+Identify recurring challenges (issues that come up in more than one session) and progress \
+arcs (how the coachee has moved on a topic over time). Ground everything in the digests; \
+never invent facts. Reply with a JSON object with exactly three keys: \"overview\" (two or \
+three sentences), \"recurring_challenges\" and \"progress_arcs\" (each an array of at most \
+{{max_themes}} objects with \"title\", \"summary\" and \"session_dates\", the YYYY-MM-DD \
+dates of the sessions the theme appears in).";
+
+/// The JSON object the LLM is asked to reply with.
+#[derive(Debug, Deserialize)]
+struct GeneratedReport {
+    overview: Option<String>,
+    #[serde(default)]
+    recurring_challenges: Vec<GeneratedTheme>,
+    #[serde(default)]
+    progress_arcs: Vec<GeneratedTheme>,
+}
+
+/// A theme as the LLM wrote it; dates are validated against the digested sessions.
+#[derive(Debug, Deserialize)]
+struct GeneratedTheme {
+    title: String,
+    summary: String,
+    #[serde(default)]
+    session_dates: Vec<String>,
+}
+
+/// One held session's material for the prompt.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Digest {
+    pub(crate) date: NaiveDateTime,
+    pub(crate) title: Option<String>,
+    pub(crate) text: String,
+}
+
+/// The relationship's themes report, generated when there is none yet, it doesn't cover
+/// the latest held session, or `refresh` is set. Coach-only.
+pub async fn report(
+    db: &DatabaseConnection,
+    analysis_provider: Option<&dyn analysis::Provider>,
+    user_id: Id,
+    relationship: &coaching_relationships::Model,
+    refresh: bool,
+) -> Result<Model, Error> {
+    if relationship.coach_id != user_id {
+        return Err(Error::entity(EntityErrorKind::Unauthenticated));
+    }
+    if !relationship.ai_privacy_level.allows_ai() {
+        return Err(Error::validation(
+            "AI features are disabled for this coaching relationship",
+        ));
+    }
+
+    let held = held_sessions(db, relationship.id).await?;
+    let Some(latest) = held.last() else {
+        return Err(not_enough_sessions());
+    };
+    let session_count = held.len() as i32;
+    let covered_through = latest.date;
+
+    let cached = theme_report::find_by_coaching_relationship_id(db, relationship.id)
+        .await?
+        .filter(|cached| {
+            !refresh
+                && cached.session_count == session_count
+                && cached.covered_through == covered_through
+                && cached.generated_at >= relationship.updated_at
+        });
+    if let Some(cached) = cached {
+        return Ok(cached);
+    }
+
+    let provider = analysis_provider.ok_or_else(|| {
+        warn!("Analysis provider not configured");
+        Error {
+            source: None,
+            error_kind: DomainErrorKind::Internal(InternalErrorKind::Config),
+        }
+    })?;
+
+    let digests = digests(db, relationship.id, &held).await?;
+    if digests.len() < MIN_SESSIONS {
+        return Err(not_enough_sessions());
+    }
+
+    let prompt = prompt_template::render(
+        db,
+        PromptKey::ThemesReport,
+        &[("max_themes", MAX_THEMES.to_string())],
+    )
+    .await?;
+    let request = ai_settings::request(
+        db,
+        provider,
+        relationship.organization_id,
+        TaskType::Extraction,
+        vec![
+            Message::system(prompt.text),
+            Message::user(build_prompt(&digests)),
+        ],
+        true,
+    )
+    .await?;
+    let completion = provider.complete(request).await?;
+
+    if let Err(e) =
+        cost::record_llm_tokens(db, None, relationship.id, completion.total_tokens()).await
+    {
+        warn!("themes: failed to record LLM cost: {e:?}");
+    }
+
+    let session_dates: HashSet<NaiveDate> = digests.iter().map(|d| d.date.date()).collect();
+    let Some(generated) = parse_completion(&completion.text, &session_dates) else {
+        warn!(
+            "themes: unparseable {} completion for relationship {}",
+            provider.provider_id(),
+            relationship.id
+        );
+        return Err(Error {
+            source: None,
+            error_kind: DomainErrorKind::Internal(InternalErrorKind::Other(
+                "The themes report could not be generated".to_string(),
+            )),
+        });
+    };
+
+    let generated_at = Utc::now().fixed_offset();
+    Ok(theme_report::upsert(
+        db,
+        Model {
+            coaching_relationship_id: relationship.id,
+            overview: generated.0,
+            recurring_challenges: generated.1,
+            progress_arcs: generated.2,
+            session_count,
+            covered_through,
+            model: completion.model,
+            prompt_version: Some(prompt.version),
+            generated_at,
+            created_at: generated_at,
+            updated_at: generated_at,
+        },
+    )
+    .await?)
+}
+
+/// Characters of the system and user prompts a report on the relationship would send,
+/// for cost estimates. Fails like [`report`] when there's too little material.
+pub(crate) async fn prompt_chars(
+    db: &DatabaseConnection,
+    relationship: &coaching_relationships::Model,
+) -> Result<usize, Error> {
+    let held = held_sessions(db, relationship.id).await?;
+    let digests = digests(db, relationship.id, &held).await?;
+    if digests.len() < MIN_SESSIONS {
+        return Err(not_enough_sessions());
+    }
+    Ok(DEFAULT_PROMPT.chars().count() + build_prompt(&digests).chars().count())
+}
+
+/// The relationship's [`MAX_SESSIONS`] most recent sessions that have taken place,
+/// oldest first.
+pub(crate) async fn held_sessions(
+    db: &DatabaseConnection,
+    coaching_relationship_id: Id,
+) -> Result<Vec<coaching_sessions::Model>, Error> {
+    let now = Utc::now().naive_utc();
+    let mut held: Vec<coaching_sessions::Model> =
+        entity_api::coaching_session::find_by_relationship(db, coaching_relationship_id)
+            .await?
+            .into_iter()
+            .filter(|session| session.date <= now)
+            .collect();
+    Ok(held.split_off(held.len().saturating_sub(MAX_SESSIONS)))
+}
+
+/// Digests of the `held` sessions (oldest first) that have any material.
+pub(crate) async fn digests(
+    db: &DatabaseConnection,
+    coaching_relationship_id: Id,
+    held: &[coaching_sessions::Model],
+) -> Result<Vec<Digest>, Error> {
+    let session_ids: Vec<Id> = held.iter().map(|session| session.id).collect();
+
+    let mut agreements: HashMap<Id, Vec<String>> = HashMap::new();
+    for agreement in agreement::find_by_coaching_session_ids(db, &session_ids).await? {
+        if let Some(body) = agreement.body {
+            agreements
+                .entry(agreement.coaching_session_id)
+                .or_default()
+                .push(body);
+        }
+    }
+    let mut notes: HashMap<Id, Vec<String>> = HashMap::new();
+    for note in note::find_by_coaching_session_ids(db, &session_ids).await? {
+        if let Some(body) = note.body {
+            notes
+                .entry(note.coaching_session_id)
+                .or_default()
+                .push(body);
+        }
+    }
+    let mut actions: HashMap<Id, Vec<String>> = HashMap::new();
+    for with_assignees in action::find_by_coaching_relationship(
+        db,
+        coaching_relationship_id,
+        FindByRelationshipParams::default(),
+    )
+    .await?
+    {
+        let action = with_assignees.action;
+        if let Some(body) = action.body {
+            actions
+                .entry(action.coaching_session_id)
+                .or_default()
+                .push(format!("{body} ({})", action.status));
+        }
+    }
+
+    // A prep brief summarizes the session before its own, so session i's summary lives
+    // in session i + 1's brief.
+    let briefs: HashMap<Id, Option<String>> =
+        session_prep_brief::find_by_coaching_session_ids(db, &session_ids)
+            .await?
+            .into_iter()
+            .map(|brief| (brief.coaching_session_id, brief.last_session_summary))
+            .collect();
+    let summaries: HashMap<Id, String> = held
+        .windows(2)
+        .filter_map(|pair| {
+            let summary = briefs.get(&pair[1].id)?.clone()?;
+            Some((pair[0].id, summary))
+        })
+        .collect();
+
+    Ok(held
+        .iter()
+        .filter_map(|session| {
+            let text = digest_text(
+                summaries.get(&session.id).map(String::as_str),
+                agreements
+                    .get(&session.id)
+                    .map(Vec::as_slice)
+                    .unwrap_or(&[]),
+                notes.get(&session.id).map(Vec::as_slice).unwrap_or(&[]),
+                actions.get(&session.id).map(Vec::as_slice).unwrap_or(&[]),
+            )?;
+            Some(Digest {
+                date: session.date,
+                title: session.title.clone(),
+                text,
+            })
+        })
+        .collect())
+}
+
+/// A session's material as plain text cut to [`MAX_DIGEST_CHARS`]; `None` when it has none.
+fn digest_text(
+    summary: Option<&str>,
+    agreements: &[String],
+    notes: &[String],
+    actions: &[String],
+) -> Option<String> {
+    let mut text = String::new();
+    if let Some(summary) = summary {
+        text.push_str(&format!("Summary: {summary}\n"));
+    }
+    for (label, items) in [
+        ("Agreements", agreements),
+        ("Notes", notes),
+        ("Actions", actions),
+    ] {
+        let items: Vec<String> = items
+            .iter()
+            .map(|item| plain_text(item, MAX_DIGEST_CHARS))
+            .filter(|item| !item.is_empty())
+            .collect();
+        if !items.is_empty() {
+            text.push_str(&format!("{label}: {}\n", items.join("; ")));
+        }
+    }
+
+    let text = plain_text(&text, MAX_DIGEST_CHARS);
+    (!text.is_empty()).then_some(text)
+}
+
+fn build_prompt(digests: &[Digest]) -> String {
+    let mut prompt = String::new();
+    for digest in digests {
+        prompt.push_str(&format!("## Session on {}", digest.date.date()));
+        if let Some(title) = digest.title.as_deref() {
+            prompt.push_str(&format!(": {title}"));
+        }
+        prompt.push('\n');
+        prompt.push_str(&digest.text);
+        prompt.push_str("\n\n");
+    }
+    prompt
+}
+
+/// Parses the LLM's JSON reply, tolerating a surrounding Markdown code fence, into the
+/// overview and the two theme lists. Dates that aren't digested sessions are dropped.
+fn parse_completion(
+    text: &str,
+    session_dates: &HashSet<NaiveDate>,
+) -> Option<(Option<String>, Themes, Themes)> {
+    let json = text
+        .trim()
+        .trim_start_matches("```json")
+        .trim_start_matches("```")
+        .trim_end_matches("```")
+        .trim();
+    let generated: GeneratedReport = serde_json::from_str(json).ok()?;
+
+    let themes = |themes: Vec<GeneratedTheme>| {
+        Themes(
+            themes
+                .into_iter()
+                .filter(|theme| !theme.title.trim().is_empty())
+                .take(MAX_THEMES)
+                .map(|theme| {
+                    let mut session_dates: Vec<NaiveDate> = theme
+                        .session_dates
+                        .iter()
+                        .filter_map(|date| NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").ok())
+                        .filter(|date| session_dates.contains(date))
+                        .collect();
+                    session_dates.sort();
+                    session_dates.dedup();
+                    Theme {
+                        title: theme.title.trim().to_string(),
+                        summary: theme.summary.trim().to_string(),
+                        session_dates,
+                    }
+                })
+                .collect(),
+        )
+    };
+
+    let overview = generated
+        .overview
+        .map(|overview| overview.trim().to_string())
+        .filter(|overview| !overview.is_empty());
+    Some((
+        overview,
+        themes(generated.recurring_challenges),
+        themes(generated.progress_arcs),
+    ))
+}
+
+fn not_enough_sessions() -> Error {
+    Error::validation(
+        "A themes report needs at least two held sessions with agreements, notes or actions",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digest_text_is_none_without_material() {
+        assert_eq!(digest_text(None, &[], &["<p></p>".to_string()], &[]), None);
+
+        let text = digest_text(
+            Some("Talked through delegation."),
+            &["Hand off the weekly report".to_string()],
+            &["<p>Feels <em>guilty</em> delegating</p>".to_string()],
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            text,
+            "Summary: Talked through delegation. Agreements: Hand off the weekly report \
+             Notes: Feels guilty delegating"
+        );
+    }
+
+    #[test]
+    fn parse_completion_keeps_only_digested_session_dates() {
+        let session_dates = HashSet::from([
+            NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 3, 16).unwrap(),
+        ]);
+        let text = r#"```json
+        {
+            "overview": " Delegation is the through-line. ",
+            "recurring_challenges": [
+                { "title": "Delegation", "summary": "Keeps redoing work",
+                  "session_dates": ["2026-03-16", "2026-03-02", "2026-04-01", "soon"] },
+                { "title": " ", "summary": "Blank titles are dropped" }
+            ]
+        }
+        ```"#;
+
+        let (overview, challenges, arcs) = parse_completion(text, &session_dates).unwrap();
+
+        assert_eq!(overview.as_deref(), Some("Delegation is the through-line."));
+        assert_eq!(challenges.0.len(), 1);
+        assert_eq!(
+            challenges.0[0].session_dates,
+            vec![
+                NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(),
+                NaiveDate::from_ymd_opt(2026, 3, 16).unwrap()
+            ]
+        );
+        assert!(arcs.0.is_empty());
+    }
+}
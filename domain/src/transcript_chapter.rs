@@ -0,0 +1,24 @@
+//! Chapters of a session's transcript, for navigating long recordings.
+
+pub use entity::transcript_chapter::Model;
+pub use entity_api::transcript_chapter::find_by_transcription;
+
+use entity::Id;
+use entity_api::{transcript_chapter as chapter_api, transcription as transcription_api};
+use sea_orm::DatabaseConnection;
+
+use crate::error::Error;
+
+/// Chapters of the coaching session's latest transcription, ordered by start time.
+/// Empty when the session has no transcription or the provider detected no chapters.
+pub async fn find_by_coaching_session(
+    db: &DatabaseConnection,
+    coaching_session_id: Id,
+) -> Result<Vec<Model>, Error> {
+    let Some(transcription) =
+        transcription_api::find_by_coaching_session(db, coaching_session_id).await?
+    else {
+        return Ok(Vec::new());
+    };
+    Ok(chapter_api::find_by_transcription(db, transcription.id).await?)
+}
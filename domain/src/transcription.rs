@@ -7,10 +7,15 @@ pub use entity_api::transcription::{
 };
 
 use crate::error::{DomainErrorKind, EntityErrorKind, Error, InternalErrorKind};
+use crate::{conversation_metrics, transcription_vocabulary};
 use entity::meeting_recording::Model as RecordingModel;
+use entity::transcript_chapter::ActiveModel as ChapterActiveModel;
 use entity::transcript_segment::ActiveModel as SegmentActiveModel;
 use entity::Id;
-use entity_api::{transcript_segment as segment_api, transcription as transcription_api};
+use entity_api::{
+    transcript_chapter as chapter_api, transcript_segment as segment_api,
+    transcription as transcription_api,
+};
 use log::*;
 use meeting_ai::traits::transcription as transcription_trait;
 use meeting_ai::types::transcription as transcription_types;
@@ -41,14 +46,27 @@ pub async fn start(
         recall_recording_id.to_string(),
     );
 
+    // Best effort: a transcript without the organization's vocabulary is still worth having.
+    let custom_vocabulary =
+        transcription_vocabulary::find_for_session(db, recording.coaching_session_id)
+            .await
+            .unwrap_or_else(|e| {
+                warn!(
+                    "Failed to load the transcription vocabulary for session {}: {e:?}",
+                    recording.coaching_session_id
+                );
+                Vec::new()
+            });
+
     let config = transcription_types::Config {
         media_url: String::new(),
         webhook_url: None,
         enable_speaker_labels: true,
         enable_sentiment_analysis: false,
-        enable_auto_chapters: false,
+        enable_auto_chapters: true,
         enable_entity_detection: false,
         language_code: None,
+        custom_vocabulary,
         provider_options,
     };
 
@@ -76,6 +94,7 @@ pub async fn start(
         duration_seconds: None,
         confidence: None,
         error_message: None,
+        conversation_metrics: None,
         created_at: now.into(),
         updated_at: now.into(),
     };
@@ -83,17 +102,29 @@ pub async fn start(
     Ok(transcription_api::create(db, model).await?)
 }
 
+/// What [`handle_completion`] stored.
+#[derive(Debug)]
+pub struct Completion {
+    /// The transcription, now Completed.
+    pub transcription: Model,
+    pub segment_count: usize,
+    /// Chapters the provider's AI detected, each with a title, summary and gist.
+    pub chapter_count: usize,
+}
+
 /// Fetches the completed transcript from the provider and persists segments.
 ///
 /// Called after `transcript.done` webhook:
 /// 1. Retrieves coalesced transcript segments from the provider
 /// 2. Updates the `transcriptions` row with word count and Completed status
 /// 3. Inserts all utterance segments as `transcript_segments`
+/// 4. Stores the conversation metrics computed from the segments
+/// 5. Inserts the detected chapters as `transcript_chapters`
 pub async fn handle_completion(
     db: &DatabaseConnection,
     provider: Option<&dyn transcription_trait::Provider>,
     external_id: &str,
-) -> Result<(), Error> {
+) -> Result<Completion, Error> {
     info!(
         "Handling transcript completion for external_id={}",
         external_id
@@ -139,11 +170,12 @@ pub async fn handle_completion(
     )
     .await?;
 
-    if result.segments.is_empty() {
+    let segments = if result.segments.is_empty() {
         warn!(
             "No segments in transcript external_id={} — no segments inserted",
             external_id
         );
+        Vec::new()
     } else {
         let now = chrono::Utc::now();
         let segment_models: Vec<SegmentActiveModel> = result
@@ -162,13 +194,45 @@ pub async fn handle_completion(
             })
             .collect();
 
-        segment_api::create_batch(db, segment_models).await?;
+        segment_api::create_batch(db, segment_models).await?
+    };
+
+    let completed = transcription_api::update_conversation_metrics(
+        db,
+        transcription.id,
+        conversation_metrics::compute(&segments),
+    )
+    .await?;
+
+    let chapter_count = result.chapters.len();
+    if !result.chapters.is_empty() {
+        let now = chrono::Utc::now();
+        let chapter_models: Vec<ChapterActiveModel> = result
+            .chapters
+            .into_iter()
+            .map(|chapter| ChapterActiveModel {
+                id: Set(Id::new_v4()),
+                transcription_id: Set(transcription.id),
+                title: Set(chapter.title),
+                summary: Set(chapter.summary),
+                gist: Set(chapter.gist),
+                start_ms: Set(i32::try_from(chapter.start_ms).unwrap_or(i32::MAX)),
+                end_ms: Set(i32::try_from(chapter.end_ms).unwrap_or(i32::MAX)),
+                created_at: Set(now.into()),
+            })
+            .collect();
+
+        chapter_api::create_batch(db, chapter_models).await?;
     }
 
     info!(
-        "Transcript completion handled for session_id={}: {} segments inserted",
-        transcription.coaching_session_id, segment_count
+        "Transcript completion handled for session_id={}: {} segments, {} chapters inserted",
+        transcription.coaching_session_id, segment_count, chapter_count
     );
 
-    Ok(())
+    Ok(Completion {
+        transcription: completed,
+        segment_count,
+        chapter_count,
+    })
 }
@@ -0,0 +1,116 @@
+//! Organization-level custom vocabulary for transcription.
+//!
+//! Acronyms ("OKR"), product and people names are often mis-transcribed. An
+//! organization's admins keep a list of such terms; [`crate::transcription::start`]
+//! passes it to the transcription provider, which boosts recognition of the terms where
+//! it supports custom vocabulary (AssemblyAI word boost, Deepgram keywords).
+
+use chrono::Utc;
+use entity_api::organization_transcription_vocabulary;
+use sea_orm::DatabaseConnection;
+use std::collections::HashSet;
+
+use crate::error::Error;
+use crate::organization_transcription_vocabularies::{Model, Terms};
+use crate::{coaching_session, Id};
+
+/// Most terms an organization may keep (AssemblyAI's word boost limit).
+pub const MAX_TERMS: usize = 1_000;
+
+/// Most words in one term (AssemblyAI boosts phrases of up to six words).
+pub const MAX_TERM_WORDS: usize = 6;
+
+/// Longest term accepted, in characters.
+pub const MAX_TERM_CHARS: usize = 100;
+
+/// The organization's vocabulary, or an empty one when it has none.
+pub async fn find(db: &DatabaseConnection, organization_id: Id) -> Result<Model, Error> {
+    Ok(
+        organization_transcription_vocabulary::find_by_organization_id(db, organization_id)
+            .await?
+            .unwrap_or_else(|| empty(organization_id)),
+    )
+}
+
+/// The vocabulary terms of the organization the coaching session belongs to.
+pub async fn find_for_session(
+    db: &DatabaseConnection,
+    coaching_session_id: Id,
+) -> Result<Vec<String>, Error> {
+    let (_, relationship) =
+        coaching_session::find_by_id_with_coaching_relationship(db, coaching_session_id).await?;
+    Ok(find(db, relationship.organization_id).await?.terms.0)
+}
+
+/// Validates and stores the organization's vocabulary, replacing any earlier one.
+///
+/// Terms are trimmed with inner whitespace collapsed; blanks and case-insensitive
+/// duplicates are dropped.
+pub async fn update(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    terms: Terms,
+) -> Result<Model, Error> {
+    let terms = normalize(terms)?;
+    Ok(organization_transcription_vocabulary::upsert(db, organization_id, terms).await?)
+}
+
+fn normalize(terms: Terms) -> Result<Terms, Error> {
+    let mut seen = HashSet::new();
+    let mut normalized = Vec::new();
+    for term in terms.0 {
+        let words: Vec<&str> = term.split_whitespace().collect();
+        if words.is_empty() {
+            continue;
+        }
+        let term = words.join(" ");
+        if words.len() > MAX_TERM_WORDS || term.chars().count() > MAX_TERM_CHARS {
+            return Err(Error::validation(format!(
+                "`{term}` is too long; terms may have at most {MAX_TERM_WORDS} words and \
+                 {MAX_TERM_CHARS} characters"
+            )));
+        }
+        if seen.insert(term.to_lowercase()) {
+            normalized.push(term);
+        }
+    }
+
+    if normalized.len() > MAX_TERMS {
+        return Err(Error::validation(format!(
+            "A vocabulary may have at most {MAX_TERMS} terms"
+        )));
+    }
+    Ok(Terms(normalized))
+}
+
+fn empty(organization_id: Id) -> Model {
+    let now = Utc::now().fixed_offset();
+    Model {
+        organization_id,
+        terms: Terms::default(),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(terms: &[&str]) -> Terms {
+        Terms(terms.iter().map(|term| term.to_string()).collect())
+    }
+
+    #[test]
+    fn normalize_trims_and_drops_blanks_and_duplicates() {
+        let normalized = normalize(terms(&["  OKR ", "okr", "", "Refactor \t Platform"])).unwrap();
+
+        assert_eq!(normalized, terms(&["OKR", "Refactor Platform"]));
+    }
+
+    #[test]
+    fn normalize_rejects_overlong_terms() {
+        assert!(normalize(terms(&["one two three four five six seven"])).is_err());
+        assert!(normalize(Terms(vec!["x".repeat(MAX_TERM_CHARS + 1)])).is_err());
+    }
+}
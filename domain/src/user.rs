@@ -6,6 +6,7 @@ use crate::{
     magic_link_token, magic_link_tokens, users, Id,
 };
 use chrono::Utc;
+pub use entity_api::authorized_session::find_expiry as find_session_expiry;
 use entity_api::{
     coaching_relationship, coaching_session, mutate, query,
     query::{IntoQueryFilterMap, QuerySort},
@@ -21,6 +22,7 @@ pub use entity_api::{
 use log::*;
 use sea_orm::IntoActiveModel;
 use sea_orm::{DatabaseConnection, TransactionTrait, Value};
+use service::config::Config;
 
 pub async fn find_by<P>(db: &DatabaseConnection, params: P) -> Result<Vec<users::Model>, Error>
 where
@@ -30,6 +32,33 @@ where
     Ok(users)
 }
 
+/// Most users [`search`] returns when no `limit` is given, and the most it may be given.
+const DEFAULT_SEARCH_LIMIT: u64 = 50;
+const MAX_SEARCH_LIMIT: u64 = 200;
+
+/// Users across all organizations whose email or names contain `query`, optionally only
+/// the members of `organization_id`, ordered by name with their roles.
+pub async fn search(
+    db: &DatabaseConnection,
+    query: Option<&str>,
+    organization_id: Option<Id>,
+    limit: Option<u64>,
+    offset: Option<u64>,
+) -> Result<Vec<users::Model>, Error> {
+    let limit = limit.unwrap_or(DEFAULT_SEARCH_LIMIT);
+    if !(1..=MAX_SEARCH_LIMIT).contains(&limit) {
+        return Err(Error {
+            source: None,
+            error_kind: DomainErrorKind::Validation(format!(
+                "`limit` must be between 1 and {MAX_SEARCH_LIMIT}"
+            )),
+        });
+    }
+    let query = query.map(str::trim).filter(|query| !query.is_empty());
+
+    Ok(user::search(db, query, organization_id, limit, offset.unwrap_or(0)).await?)
+}
+
 pub async fn find_by_organization_with_invite_status(
     db: &DatabaseConnection,
     organization_id: Id,
@@ -83,6 +112,7 @@ pub async fn update(
 
 pub async fn update_password(
     db: &DatabaseConnection,
+    config: &Config,
     user_id: Id,
     params: impl mutate::IntoUpdateMap,
 ) -> Result<users::Model, Error> {
@@ -108,6 +138,7 @@ pub async fn update_password(
             ),
         });
     }
+    crate::password_policy::enforce(config, &password).await?;
 
     // generate new password hash and insert it back into params overwriting the raw password
     params.insert(
@@ -116,7 +147,12 @@ pub async fn update_password(
     );
 
     let active_model = existing_user.into_active_model();
-    Ok(mutate::update::<users::ActiveModel, users::Column>(db, active_model, params).await?)
+    let user =
+        mutate::update::<users::ActiveModel, users::Column>(db, active_model, params).await?;
+
+    // Sessions kept by "remember me" outlive the password they logged in with otherwise
+    entity_api::remembered_device::delete_all_for_user(db, user.id).await?;
+    Ok(user)
 }
 
 // This function is intended to be a temporary solution until we finalize our user experience strategy for assigning a new user
@@ -151,6 +187,8 @@ pub async fn create_user_and_coaching_relationship(
         organization_id: Default::default(),
         id: Default::default(),
         slug: "".to_string(),
+        ai_privacy_level: Default::default(),
+        pro_bono: false,
         created_at: Utc::now().into(),
         updated_at: Utc::now().into(),
     };
@@ -170,7 +208,11 @@ pub async fn create_user_and_coaching_relationship(
     Ok(new_user)
 }
 
+/// Deletes the user along with their relationships and roles, unless a legal hold
+/// covers their data.
 pub async fn delete(db: &DatabaseConnection, user_id: Id) -> Result<(), Error> {
+    crate::legal_hold::ensure_user_not_held(db, user_id).await?;
+
     let txn = db.begin().await.map_err(|e| Error {
         source: Some(Box::new(e)),
         error_kind: DomainErrorKind::Internal(InternalErrorKind::Entity(
@@ -194,9 +236,15 @@ pub async fn delete(db: &DatabaseConnection, user_id: Id) -> Result<(), Error> {
 
 pub async fn create_by_organization(
     db: &DatabaseConnection,
+    config: &Config,
     organization_id: Id,
     user_model: users::Model,
 ) -> Result<users::Model, Error> {
+    // Invited users without a password choose one at setup, under the same policy
+    if let Some(password) = &user_model.password {
+        crate::password_policy::enforce(config, password).await?;
+    }
+
     // Create the user first using the entity_api function
     let new_user =
         entity_api::user::create_by_organization(db, organization_id, user_model).await?;
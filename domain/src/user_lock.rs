@@ -0,0 +1,527 @@
+//! Accounts locked by an organization admin.
+//!
+//! Unlike an automatic lockout, a lock is an admin's on-demand switch and stays until an
+//! admin of the same organization lifts it. While a user is locked they can't log in,
+//! a request in any of their sessions logs that session out, and their remembered
+//! devices are forgotten. Locking publishes [`DomainEvent::UserLocked`], which sends the
+//! user's SSE connections `force_logout` and closes them; both it and
+//! [`DomainEvent::UserUnlocked`] are kept in the audit log with the admin who acted.
+//!
+//! A lock keeps the user out of every organization, so an organization's admins may only
+//! lock members who answer to no one else: not SuperAdmins, nor admins of other
+//! organizations ([`organization_may_lock`]).
+
+use log::*;
+use sea_orm::{DatabaseConnection, TransactionTrait};
+
+use crate::error::{DomainErrorKind, EntityErrorKind, Error, InternalErrorKind};
+use crate::events::{DomainEvent, EventPublisher};
+use crate::user_locks::Model;
+use crate::users::{self, Role};
+use crate::Id;
+
+pub use entity_api::user_lock::find_by_user;
+
+/// Longest reason an admin may give for a lock.
+pub const MAX_REASON_LENGTH: usize = 500;
+
+/// Locks `user_id`'s account on behalf of `actor_id`, an admin of `organization_id`, if
+/// the organization may lock them.
+pub async fn lock(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    organization_id: Id,
+    user_id: Id,
+    actor_id: Id,
+    reason: Option<String>,
+) -> Result<Model, Error> {
+    if !may_lock(db, organization_id, user_id).await? {
+        return Err(Error::entity(EntityErrorKind::Unauthenticated));
+    }
+
+    create(
+        db,
+        event_publisher,
+        organization_id,
+        user_id,
+        actor_id,
+        reason,
+    )
+    .await
+}
+
+/// Whether `organization_id`'s admins may lock `user_id` ([`organization_may_lock`]).
+pub async fn may_lock(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    user_id: Id,
+) -> Result<bool, Error> {
+    let user = entity_api::user::find_by_id(db, user_id).await?;
+    Ok(organization_may_lock(&user, organization_id))
+}
+
+/// Whether an organization's admins may lock `user`: they're a member of it, and neither
+/// a SuperAdmin nor an admin of another organization, whom the lock would also shut out.
+pub fn organization_may_lock(user: &users::Model, organization_id: Id) -> bool {
+    let member = user
+        .roles
+        .iter()
+        .any(|role| role.organization_id == Some(organization_id));
+    let answers_elsewhere = user.roles.iter().any(|role| {
+        role.role == Role::SuperAdmin
+            || (role.role == Role::Admin && role.organization_id != Some(organization_id))
+    });
+    member && !answers_elsewhere
+}
+
+async fn create(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    organization_id: Id,
+    user_id: Id,
+    actor_id: Id,
+    reason: Option<String>,
+) -> Result<Model, Error> {
+    let reason = validate_reason(reason)?;
+    if find_by_user(db, user_id).await?.is_some() {
+        return Err(conflict("The user is already locked"));
+    }
+
+    let txn = db.begin().await.map_err(transaction_error)?;
+    let lock =
+        entity_api::user_lock::create(&txn, user_id, organization_id, actor_id, reason).await?;
+    entity_api::remembered_device::delete_all_for_user(&txn, user_id).await?;
+    txn.commit().await.map_err(transaction_error)?;
+
+    info!("User {user_id} locked by {actor_id} (organization {organization_id})");
+    event_publisher
+        .publish(DomainEvent::UserLocked {
+            organization_id,
+            user_id,
+            reason: lock.reason.clone(),
+        })
+        .await;
+
+    Ok(lock)
+}
+
+/// Unlocks `user_id`'s account on behalf of `actor_id`, an admin of `organization_id`.
+/// Only the organization that locked the user may unlock them.
+pub async fn unlock(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    organization_id: Id,
+    user_id: Id,
+    actor_id: Id,
+) -> Result<(), Error> {
+    let Some(lock) = find_by_user(db, user_id).await? else {
+        return Err(Error::entity(EntityErrorKind::NotFound));
+    };
+    if lock.organization_id != organization_id {
+        return Err(conflict("The user was locked by another organization"));
+    }
+
+    remove(db, event_publisher, &lock, actor_id).await
+}
+
+/// Locks `user_id`'s account on behalf of `actor_id`, a SuperAdmin, in the name of
+/// `organization_id`, which must be one of the user's organizations: its admins are told
+/// of the lock and may lift it.
+pub async fn lock_as_super_admin(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    organization_id: Id,
+    user_id: Id,
+    actor_id: Id,
+    reason: Option<String>,
+) -> Result<Model, Error> {
+    let user = entity_api::user::find_by_id(db, user_id).await?;
+    if !user
+        .roles
+        .iter()
+        .any(|role| role.organization_id == Some(organization_id))
+    {
+        return Err(Error::validation(
+            "The user doesn't belong to `organization_id`",
+        ));
+    }
+
+    create(
+        db,
+        event_publisher,
+        organization_id,
+        user_id,
+        actor_id,
+        reason,
+    )
+    .await
+}
+
+/// Unlocks `user_id`'s account on behalf of `actor_id`, a SuperAdmin, whichever
+/// organization locked it. Returns the lifted lock.
+pub async fn unlock_as_super_admin(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    user_id: Id,
+    actor_id: Id,
+) -> Result<Model, Error> {
+    let Some(lock) = find_by_user(db, user_id).await? else {
+        return Err(Error::entity(EntityErrorKind::NotFound));
+    };
+
+    remove(db, event_publisher, &lock, actor_id).await?;
+    Ok(lock)
+}
+
+/// Whether `user_id`'s account is locked.
+pub async fn is_locked(db: &DatabaseConnection, user_id: Id) -> Result<bool, Error> {
+    Ok(find_by_user(db, user_id).await?.is_some())
+}
+
+async fn remove(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    lock: &Model,
+    actor_id: Id,
+) -> Result<(), Error> {
+    let (organization_id, user_id) = (lock.organization_id, lock.user_id);
+    entity_api::user_lock::delete_by_user(db, user_id).await?;
+
+    info!("User {user_id} unlocked by {actor_id} (organization {organization_id})");
+    event_publisher
+        .publish(DomainEvent::UserUnlocked {
+            organization_id,
+            user_id,
+        })
+        .await;
+
+    Ok(())
+}
+
+/// Trims the reason, dropping a blank one.
+fn validate_reason(reason: Option<String>) -> Result<Option<String>, Error> {
+    let reason = reason
+        .map(|reason| reason.trim().to_string())
+        .filter(|reason| !reason.is_empty());
+    if reason
+        .as_ref()
+        .is_some_and(|reason| reason.chars().count() > MAX_REASON_LENGTH)
+    {
+        return Err(Error {
+            source: None,
+            error_kind: DomainErrorKind::Validation(format!(
+                "`reason` must be at most {MAX_REASON_LENGTH} characters"
+            )),
+        });
+    }
+    Ok(reason)
+}
+
+fn conflict(message: &str) -> Error {
+    Error::entity(EntityErrorKind::Conflict {
+        message: message.to_string(),
+        details: None,
+    })
+}
+
+fn transaction_error(e: sea_orm::DbErr) -> Error {
+    Error {
+        source: Some(Box::new(e)),
+        error_kind: DomainErrorKind::Internal(InternalErrorKind::Entity(
+            EntityErrorKind::DbTransaction,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::user_roles;
+    use chrono::Utc;
+
+    #[test]
+    fn validate_reason_trims_and_drops_blank_reasons() {
+        assert_eq!(
+            validate_reason(Some("  Left the company ".to_string())).unwrap(),
+            Some("Left the company".to_string())
+        );
+        assert_eq!(validate_reason(Some("   ".to_string())).unwrap(), None);
+        assert_eq!(validate_reason(None).unwrap(), None);
+        assert!(validate_reason(Some("x".repeat(MAX_REASON_LENGTH + 1))).is_err());
+    }
+
+    fn user_with(roles: &[(Role, Option<Id>)]) -> users::Model {
+        let now = Utc::now().fixed_offset();
+        let id = Id::new_v4();
+        users::Model {
+            id,
+            email: format!("{id}@example.com"),
+            first_name: "Test".to_string(),
+            last_name: "User".to_string(),
+            display_name: None,
+            password: None,
+            github_username: None,
+            github_profile_url: None,
+            timezone: "UTC".to_string(),
+            default_coaching_session_duration_minutes: 60,
+            role: Role::User,
+            roles: roles
+                .iter()
+                .map(|(role, organization_id)| user_roles::Model {
+                    id: Id::new_v4(),
+                    role: role.clone(),
+                    organization_id: *organization_id,
+                    user_id: id,
+                    created_at: now,
+                    updated_at: now,
+                })
+                .collect(),
+            invite_status: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn organizations_may_lock_only_members_who_answer_to_no_one_else() {
+        let (organization_id, elsewhere) = (Id::new_v4(), Id::new_v4());
+
+        let member = user_with(&[
+            (Role::User, Some(organization_id)),
+            (Role::Admin, Some(organization_id)),
+            (Role::User, Some(elsewhere)),
+        ]);
+        assert!(organization_may_lock(&member, organization_id));
+
+        let outsider = user_with(&[(Role::User, Some(elsewhere))]);
+        assert!(!organization_may_lock(&outsider, organization_id));
+
+        let super_admin = user_with(&[
+            (Role::User, Some(organization_id)),
+            (Role::SuperAdmin, None),
+        ]);
+        assert!(!organization_may_lock(&super_admin, organization_id));
+
+        let admin_elsewhere = user_with(&[
+            (Role::User, Some(organization_id)),
+            (Role::Admin, Some(elsewhere)),
+        ]);
+        assert!(!organization_may_lock(&admin_elsewhere, organization_id));
+    }
+
+    #[cfg(feature = "mock")]
+    mod mock_tests {
+        use super::*;
+        use crate::test_support::recording_publisher;
+        use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+
+        /// The `(user, role)` rows `find_by_id` loads a user with.
+        fn user_rows(user: &users::Model) -> Vec<(users::Model, user_roles::Model)> {
+            user.roles
+                .iter()
+                .map(|role| (user.clone(), role.clone()))
+                .collect()
+        }
+
+        async fn refused_lock(user: users::Model, organization_id: Id) {
+            let (publisher, events) = recording_publisher();
+            let db = MockDatabase::new(DatabaseBackend::Postgres)
+                .append_query_results([user_rows(&user)])
+                .into_connection();
+
+            let result = lock(
+                &db,
+                &publisher,
+                organization_id,
+                user.id,
+                Id::new_v4(),
+                None,
+            )
+            .await;
+
+            assert!(matches!(
+                result.unwrap_err().error_kind,
+                DomainErrorKind::Internal(InternalErrorKind::Entity(
+                    EntityErrorKind::Unauthenticated
+                ))
+            ));
+            assert!(events.lock().unwrap().is_empty());
+        }
+
+        #[tokio::test]
+        async fn organization_admins_may_not_lock_a_super_admin() {
+            let organization_id = Id::new_v4();
+            let super_admin = user_with(&[
+                (Role::User, Some(organization_id)),
+                (Role::SuperAdmin, None),
+            ]);
+
+            refused_lock(super_admin, organization_id).await;
+        }
+
+        #[tokio::test]
+        async fn organization_admins_may_not_lock_an_admin_of_another_organization() {
+            let organization_id = Id::new_v4();
+            let admin_elsewhere = user_with(&[
+                (Role::User, Some(organization_id)),
+                (Role::Admin, Some(Id::new_v4())),
+            ]);
+
+            refused_lock(admin_elsewhere, organization_id).await;
+        }
+
+        fn lock_model(user_id: Id, organization_id: Id) -> Model {
+            Model {
+                id: Id::new_v4(),
+                user_id,
+                organization_id,
+                locked_by_id: Some(Id::new_v4()),
+                reason: Some("Left the company".to_string()),
+                created_at: Utc::now().into(),
+            }
+        }
+
+        #[tokio::test]
+        async fn lock_publishes_user_locked() {
+            let organization_id = Id::new_v4();
+            let user = user_with(&[(Role::User, Some(organization_id))]);
+            let user_id = user.id;
+            let lock_row = lock_model(user_id, organization_id);
+            let (publisher, events) = recording_publisher();
+
+            // find_by_id → find_by_user → INSERT RETURNING → DELETE remembered devices
+            let db = MockDatabase::new(DatabaseBackend::Postgres)
+                .append_query_results([user_rows(&user)])
+                .append_query_results(vec![Vec::<Model>::new()])
+                .append_query_results(vec![vec![lock_row.clone()]])
+                .append_exec_results(vec![MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 2,
+                }])
+                .into_connection();
+
+            let lock = lock(
+                &db,
+                &publisher,
+                organization_id,
+                user_id,
+                Id::new_v4(),
+                Some("Left the company".to_string()),
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(lock, lock_row);
+            let recorded = events.lock().unwrap();
+            assert!(matches!(
+                recorded.as_slice(),
+                [DomainEvent::UserLocked { user_id: locked, .. }] if *locked == user_id
+            ));
+        }
+
+        #[tokio::test]
+        async fn locking_a_locked_user_conflicts() {
+            let organization_id = Id::new_v4();
+            let user = user_with(&[(Role::User, Some(organization_id))]);
+            let user_id = user.id;
+            let (publisher, events) = recording_publisher();
+            let db = MockDatabase::new(DatabaseBackend::Postgres)
+                .append_query_results([user_rows(&user)])
+                .append_query_results(vec![vec![lock_model(user_id, organization_id)]])
+                .into_connection();
+
+            let result = lock(
+                &db,
+                &publisher,
+                organization_id,
+                user_id,
+                Id::new_v4(),
+                None,
+            )
+            .await;
+
+            assert!(matches!(
+                result.unwrap_err().error_kind,
+                DomainErrorKind::Internal(InternalErrorKind::Entity(
+                    EntityErrorKind::Conflict { .. }
+                ))
+            ));
+            assert!(events.lock().unwrap().is_empty());
+        }
+
+        #[tokio::test]
+        async fn unlock_publishes_user_unlocked() {
+            let (user_id, organization_id) = (Id::new_v4(), Id::new_v4());
+            let (publisher, events) = recording_publisher();
+            let db = MockDatabase::new(DatabaseBackend::Postgres)
+                .append_query_results(vec![vec![lock_model(user_id, organization_id)]])
+                .append_exec_results(vec![MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 1,
+                }])
+                .into_connection();
+
+            unlock(&db, &publisher, organization_id, user_id, Id::new_v4())
+                .await
+                .unwrap();
+
+            assert!(matches!(
+                events.lock().unwrap().as_slice(),
+                [DomainEvent::UserUnlocked { .. }]
+            ));
+        }
+
+        #[tokio::test]
+        async fn super_admins_unlock_whichever_organization_locked() {
+            let (user_id, organization_id) = (Id::new_v4(), Id::new_v4());
+            let (publisher, events) = recording_publisher();
+            let db = MockDatabase::new(DatabaseBackend::Postgres)
+                .append_query_results(vec![vec![lock_model(user_id, organization_id)]])
+                .append_exec_results(vec![MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 1,
+                }])
+                .into_connection();
+
+            let lock = unlock_as_super_admin(&db, &publisher, user_id, Id::new_v4())
+                .await
+                .unwrap();
+
+            assert_eq!(lock.organization_id, organization_id);
+            assert!(matches!(
+                events.lock().unwrap().as_slice(),
+                [DomainEvent::UserUnlocked { organization_id: unlocked_by, .. }]
+                    if *unlocked_by == organization_id
+            ));
+        }
+
+        #[tokio::test]
+        async fn only_the_locking_organization_may_unlock() {
+            let user_id = Id::new_v4();
+            let (publisher, events) = recording_publisher();
+            let db = MockDatabase::new(DatabaseBackend::Postgres)
+                .append_query_results(vec![vec![lock_model(user_id, Id::new_v4())]])
+                .into_connection();
+
+            let result = unlock(&db, &publisher, Id::new_v4(), user_id, Id::new_v4()).await;
+
+            assert!(result.is_err());
+            assert!(events.lock().unwrap().is_empty());
+        }
+
+        #[tokio::test]
+        async fn unlocking_an_unlocked_user_is_not_found() {
+            let (publisher, _) = recording_publisher();
+            let db = MockDatabase::new(DatabaseBackend::Postgres)
+                .append_query_results(vec![Vec::<Model>::new()])
+                .into_connection();
+
+            let result = unlock(&db, &publisher, Id::new_v4(), Id::new_v4(), Id::new_v4()).await;
+
+            assert!(matches!(
+                result.unwrap_err().error_kind,
+                DomainErrorKind::Internal(InternalErrorKind::Entity(EntityErrorKind::NotFound))
+            ));
+        }
+    }
+}
@@ -0,0 +1,631 @@
+//! Append-only export of domain events to an analytics warehouse (BigQuery, Snowflake,
+//! ...), by way of object storage the warehouse loads from.
+//!
+//! [`WarehouseSink`] is registered on the [`EventPublisher`] and buffers every published
+//! event as a [`Record`], one JSON object per line. Batches are written as new objects
+//! under `{prefix}/domain_events/dt={export date}/`, when a batch fills up or every flush
+//! interval, and never rewritten; a batch that fails to upload stays buffered for the
+//! next flush. Alongside them, `{prefix}/_schemas/domain_events.json` describes the
+//! records' fields and the current schema version of each event type, for loaders to
+//! build tables from.
+//!
+//! Events published before the export was enabled are backfilled from the event store:
+//! [`start_backfill`] sets the span to export and [`backfill_pass`], run in the
+//! background, exports it a batch at a time. Both streams keep a checkpoint of how far
+//! they've got, which the backfill resumes from after a restart.
+//!
+//! Any S3-compatible storage works, including Google Cloud Storage through its XML API
+//! with HMAC keys.
+//!
+//! [`EventPublisher`]: events::EventPublisher
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use entity_api::{stored_event, warehouse_checkpoint};
+use events::envelope::{self, EnvelopeError, EventEnvelope};
+use events::{DomainEvent, EventHandler, EventKind, HandlerError};
+use log::*;
+use sea_orm::{DatabaseConnection, TransactionTrait};
+use serde::Serialize;
+use serde_json::{json, Value};
+use service::config::Config;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+use crate::error::{EntityErrorKind, Error};
+use crate::event_log::coaching_relationship_id;
+use crate::gateway::object_storage::ObjectStorage;
+use crate::warehouse_checkpoints::Model;
+use crate::{event_store, Id};
+
+/// Namespace of the records' schema names.
+const SCHEMA_NAMESPACE: &str = "refactor_platform.domain_event";
+
+const CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// Most events kept waiting for storage to take them; the oldest are dropped past this.
+const MAX_PENDING_EVENTS: usize = 100_000;
+
+/// The records' fields, with their warehouse types, as the schema registry lists them.
+const FIELDS: [(&str, &str); 15] = [
+    ("event_id", "STRING"),
+    ("event_type", "STRING"),
+    ("schema", "STRING"),
+    ("schema_version", "INTEGER"),
+    ("entity_type", "STRING"),
+    ("entity_id", "STRING"),
+    ("coaching_relationship_id", "STRING"),
+    ("coaching_session_id", "STRING"),
+    ("actor_user_id", "STRING"),
+    ("correlation_id", "STRING"),
+    ("occurred_at", "TIMESTAMP"),
+    ("exported_at", "TIMESTAMP"),
+    ("source", "STRING"),
+    ("payload", "JSON"),
+    // The partition of the object holding the record: its export date
+    ("dt", "DATE"),
+];
+
+/// How a record reached the warehouse; also the name of its stream's checkpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Source {
+    /// Exported as it was published.
+    Live,
+    /// Re-exported from the event store.
+    Backfill,
+}
+
+impl Source {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Source::Live => "live",
+            Source::Backfill => "backfill",
+        }
+    }
+}
+
+/// One exported event, a line of a batch.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Record {
+    pub event_id: Id,
+    pub event_type: String,
+    pub schema: String,
+    pub schema_version: Option<u32>,
+    pub entity_type: String,
+    pub entity_id: Option<Id>,
+    pub coaching_relationship_id: Option<Id>,
+    pub coaching_session_id: Option<Id>,
+    pub actor_user_id: Option<Id>,
+    pub correlation_id: Option<Id>,
+    pub occurred_at: DateTime<Utc>,
+    pub exported_at: DateTime<Utc>,
+    pub source: Source,
+    pub payload: Value,
+}
+
+impl Record {
+    /// `event` as it's published now, on behalf of the current actor.
+    fn live(
+        event: &DomainEvent,
+        coaching_relationship_id: Option<Id>,
+        now: DateTime<Utc>,
+    ) -> Result<Self, EnvelopeError> {
+        let envelope = EventEnvelope::new(event, now)?;
+        let (entity_type, entity_id) = event.entity();
+        Ok(Self {
+            event_id: Id::new_v4(),
+            schema: schema(&envelope.event_type, Some(envelope.version)),
+            event_type: envelope.event_type,
+            schema_version: Some(envelope.version),
+            entity_type: entity_type.to_string(),
+            entity_id,
+            coaching_relationship_id,
+            coaching_session_id: event.coaching_session_id(),
+            actor_user_id: events::current_actor(),
+            correlation_id: envelope.correlation_id,
+            occurred_at: envelope.occurred_at,
+            exported_at: now,
+            source: Source::Live,
+            payload: envelope.payload,
+        })
+    }
+
+    /// The stored `event`. A payload this build can't read is exported as stored, with
+    /// no schema version.
+    fn backfill(event: &event_store::Model, now: DateTime<Utc>) -> Self {
+        let (schema_version, payload, correlation_id) = match envelope::parse(event.payload.clone())
+        {
+            Ok(envelope) => (
+                Some(envelope.version),
+                envelope.payload,
+                envelope.correlation_id,
+            ),
+            Err(e) => {
+                debug!(
+                    "Backfilling stored event {} ({}) as stored: {e}",
+                    event.id, event.event_type
+                );
+                let mut payload = event.payload.clone();
+                if let Some(fields) = payload.as_object_mut() {
+                    fields.remove("type");
+                }
+                (None, payload, None)
+            }
+        };
+        Self {
+            event_id: event.id,
+            event_type: event.event_type.clone(),
+            schema: schema(&event.event_type, schema_version),
+            schema_version,
+            entity_type: event.entity_type.clone(),
+            entity_id: event.entity_id,
+            coaching_relationship_id: event.coaching_relationship_id,
+            coaching_session_id: event.coaching_session_id,
+            actor_user_id: event.actor_user_id,
+            correlation_id,
+            occurred_at: event.created_at.to_utc(),
+            exported_at: now,
+            source: Source::Backfill,
+            payload,
+        }
+    }
+}
+
+/// Exports each published event to the warehouse's object storage, in batches.
+pub struct WarehouseSink {
+    db: Arc<DatabaseConnection>,
+    storage: ObjectStorage,
+    prefix: String,
+    batch_size: usize,
+    /// Serialized records waiting to be written, oldest first.
+    pending: Mutex<VecDeque<String>>,
+    /// Wakes the flusher when a batch fills up.
+    batch_ready: Notify,
+    /// Held while flushing, so batches are written one at a time; whether the schema
+    /// registry has been written since the process started.
+    flushing: tokio::sync::Mutex<bool>,
+}
+
+impl WarehouseSink {
+    /// The configured sink; `None` unless the export is enabled and object storage is
+    /// configured.
+    pub fn from_config(
+        config: &Config,
+        db: Arc<DatabaseConnection>,
+    ) -> Result<Option<Self>, Error> {
+        let Some(storage) = storage(config)? else {
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            db,
+            storage,
+            prefix: config.warehouse_export_prefix().to_string(),
+            batch_size: config.warehouse_export_batch_size(),
+            pending: Mutex::new(VecDeque::new()),
+            batch_ready: Notify::new(),
+            flushing: tokio::sync::Mutex::new(false),
+        }))
+    }
+
+    /// Writes every waiting record, a batch per object, returning how many were written.
+    /// Records of a batch that fails to upload wait for the next flush.
+    pub async fn flush(&self) -> Result<usize, Error> {
+        let mut schema_written = self.flushing.lock().await;
+        let mut exported = 0;
+        loop {
+            let batch = {
+                let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+                let size = pending.len().min(self.batch_size);
+                pending.drain(..size).collect::<Vec<_>>()
+            };
+            if batch.is_empty() {
+                return Ok(exported);
+            }
+
+            let now = Utc::now();
+            let key = object_key(&self.prefix, Source::Live, now);
+            let mut written = Ok(());
+            if !*schema_written {
+                written = write_schema_registry(&self.storage, &self.prefix, now).await;
+                *schema_written = written.is_ok();
+            }
+            if written.is_ok() {
+                written = self.storage.put(&key, ndjson(&batch), CONTENT_TYPE).await;
+            }
+
+            if let Err(e) = written {
+                let checkpoint = warehouse_checkpoint::record_error(
+                    self.db.as_ref(),
+                    Source::Live.as_str(),
+                    &e.to_string(),
+                )
+                .await;
+                if let Err(checkpoint_error) = checkpoint {
+                    warn!("Failed to record the warehouse export's error: {checkpoint_error:?}");
+                }
+                self.requeue(batch);
+                return Err(e);
+            }
+
+            let count = batch.len();
+            if let Err(e) = warehouse_checkpoint::record_batch(
+                self.db.as_ref(),
+                Source::Live.as_str(),
+                count as i64,
+                &key,
+                None,
+            )
+            .await
+            {
+                warn!("Exported {count} events to {key} but failed to checkpoint them: {e:?}");
+            }
+            debug!("Exported {count} events to {key}");
+            exported += count;
+        }
+    }
+
+    /// Flushes whenever a batch fills up, and every `interval` otherwise. After a failed
+    /// flush, waits out an interval before retrying.
+    pub fn spawn_flusher(self: &Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let sink = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = tokio::time::sleep(interval) => {}
+                    _ = sink.batch_ready.notified() => {}
+                }
+                if let Err(e) = sink.flush().await {
+                    warn!("[warehouse] export failed, retrying later: {e:?}");
+                    tokio::time::sleep(interval).await;
+                }
+            }
+        })
+    }
+
+    /// Queues `line`, returning whether a batch is full.
+    fn push(&self, line: String) -> bool {
+        let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+        pending.push_back(line);
+        drop_overflow(&mut pending);
+        pending.len() >= self.batch_size
+    }
+
+    /// Puts the records of a batch that failed to upload back at the front of the queue.
+    fn requeue(&self, batch: Vec<String>) {
+        let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+        for line in batch.into_iter().rev() {
+            pending.push_front(line);
+        }
+        drop_overflow(&mut pending);
+    }
+}
+
+#[async_trait]
+impl EventHandler for WarehouseSink {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), HandlerError> {
+        let coaching_relationship_id = coaching_relationship_id(self.db.as_ref(), event)
+            .await
+            .map_err(|e| HandlerError::Transient(e.to_string()))?;
+        let record = Record::live(event, coaching_relationship_id, Utc::now())
+            .map_err(|e| HandlerError::Permanent(e.to_string()))?;
+        let line =
+            serde_json::to_string(&record).map_err(|e| HandlerError::Permanent(e.to_string()))?;
+        if self.push(line) {
+            self.batch_ready.notify_one();
+        }
+        Ok(())
+    }
+}
+
+/// Every export stream's checkpoint.
+pub async fn checkpoints(db: &DatabaseConnection) -> Result<Vec<Model>, Error> {
+    Ok(warehouse_checkpoint::find_all(db).await?)
+}
+
+/// Starts backfilling the stored events created within `[from, to)`, replacing any
+/// backfill under way. `from` defaults to the oldest stored event, and `to` to when the
+/// live export started (or now, if it hasn't), so the two don't overlap. Unavailable
+/// (service unavailable) unless the export is configured.
+pub async fn start_backfill(
+    db: &DatabaseConnection,
+    config: &Config,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> Result<Model, Error> {
+    let Some(storage) = storage(config)? else {
+        warn!("A warehouse backfill was requested, but the warehouse export isn't configured");
+        return Err(Error::entity(EntityErrorKind::ServiceUnavailable));
+    };
+
+    let now = Utc::now();
+    let to = match to {
+        Some(to) => to,
+        None => warehouse_checkpoint::find(db, Source::Live.as_str())
+            .await?
+            .map_or(now, |live| live.created_at.to_utc()),
+    }
+    .min(now);
+    let from = from.unwrap_or(DateTime::UNIX_EPOCH);
+    if from >= to {
+        return Err(Error::validation(
+            "`from` must be before `to` and the present",
+        ));
+    }
+
+    write_schema_registry(&storage, config.warehouse_export_prefix(), now).await?;
+    let checkpoint =
+        warehouse_checkpoint::start(db, Source::Backfill.as_str(), from.into(), to.into()).await?;
+    info!("Backfilling the stored events from {from} until {to} to the warehouse");
+    Ok(checkpoint)
+}
+
+/// Exports the next batch of the backfill under way, returning how many events it
+/// held; 0 once the backfill completes, or while another process exports a batch.
+pub async fn backfill_pass(db: &DatabaseConnection, config: &Config) -> Result<usize, Error> {
+    let Some(storage) = storage(config)? else {
+        return Ok(0);
+    };
+
+    // The checkpoint stays locked while the batch uploads, so no other replica exports
+    // it too.
+    let txn = db.begin().await.map_err(entity_api::error::Error::from)?;
+    let stream = Source::Backfill.as_str();
+    let Some(checkpoint) = warehouse_checkpoint::claim(&txn, stream).await? else {
+        return Ok(0);
+    };
+    let (Some(position_at), Some(until_at)) = (checkpoint.position_at, checkpoint.until_at) else {
+        return Ok(0);
+    };
+
+    let stored = stored_event::find_after(
+        &txn,
+        (position_at, checkpoint.position_id.unwrap_or_default()),
+        until_at,
+        config.warehouse_export_batch_size() as u64,
+    )
+    .await?;
+    let Some(last) = stored.last() else {
+        warehouse_checkpoint::complete(&txn, stream).await?;
+        txn.commit().await.map_err(entity_api::error::Error::from)?;
+        info!(
+            "Warehouse backfill complete: {} events in {} batches",
+            checkpoint.events_exported, checkpoint.batches_exported
+        );
+        return Ok(0);
+    };
+
+    let now = Utc::now();
+    let lines = stored
+        .iter()
+        .map(|event| serde_json::to_string(&Record::backfill(event, now)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let key = object_key(config.warehouse_export_prefix(), Source::Backfill, now);
+    if let Err(e) = storage.put(&key, ndjson(&lines), CONTENT_TYPE).await {
+        warehouse_checkpoint::record_error(&txn, stream, &e.to_string()).await?;
+        txn.commit().await.map_err(entity_api::error::Error::from)?;
+        return Err(e);
+    }
+
+    warehouse_checkpoint::record_batch(
+        &txn,
+        stream,
+        lines.len() as i64,
+        &key,
+        Some((last.created_at, last.id)),
+    )
+    .await?;
+    txn.commit().await.map_err(entity_api::error::Error::from)?;
+    debug!("Backfilled {} events to {key}", lines.len());
+    Ok(lines.len())
+}
+
+/// The description of the records and event types loaders build tables from.
+pub fn schema_registry(now: DateTime<Utc>) -> Value {
+    json!({
+        "name": SCHEMA_NAMESPACE,
+        "format": "ndjson",
+        "partitioning": "dt=YYYY-MM-DD",
+        "updated_at": now,
+        "fields": FIELDS
+            .iter()
+            .map(|(name, field_type)| json!({ "name": name, "type": field_type }))
+            .collect::<Vec<_>>(),
+        "event_types": EventKind::ALL
+            .into_iter()
+            .map(|kind| {
+                json!({
+                    "event_type": kind.as_str(),
+                    "schema_version": kind.version(),
+                    "schema": schema(kind.as_str(), Some(kind.version())),
+                })
+            })
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// The warehouse's object storage; `None` unless the export is enabled and object storage
+/// is configured.
+fn storage(config: &Config) -> Result<Option<ObjectStorage>, Error> {
+    if !config.warehouse_export_enabled() {
+        return Ok(None);
+    }
+    let Some(storage) = ObjectStorage::from_config(config)? else {
+        warn!("WAREHOUSE_EXPORT_ENABLED is set, but object storage isn't configured");
+        return Ok(None);
+    };
+    Ok(Some(match config.warehouse_export_bucket() {
+        Some(bucket) => storage.with_bucket(bucket),
+        None => storage,
+    }))
+}
+
+async fn write_schema_registry(
+    storage: &ObjectStorage,
+    prefix: &str,
+    now: DateTime<Utc>,
+) -> Result<(), Error> {
+    let body = serde_json::to_vec_pretty(&schema_registry(now))?;
+    storage
+        .put(
+            &prefixed(prefix, "_schemas/domain_events.json"),
+            body,
+            "application/json",
+        )
+        .await
+}
+
+/// Where a batch exported at `now` is written, e.g.
+/// `warehouse/domain_events/dt=2026-10-15/live-20261015T093000.123Z-{uuid}.ndjson`.
+fn object_key(prefix: &str, source: Source, now: DateTime<Utc>) -> String {
+    prefixed(
+        prefix,
+        &format!(
+            "domain_events/dt={}/{}-{}-{}.ndjson",
+            now.format("%Y-%m-%d"),
+            source.as_str(),
+            now.format("%Y%m%dT%H%M%S%.3fZ"),
+            Id::new_v4()
+        ),
+    )
+}
+
+fn prefixed(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{prefix}/{key}")
+    }
+}
+
+/// e.g. `refactor_platform.domain_event.goal_created.v2`.
+fn schema(event_type: &str, version: Option<u32>) -> String {
+    match version {
+        Some(version) => format!("{SCHEMA_NAMESPACE}.{event_type}.v{version}"),
+        None => format!("{SCHEMA_NAMESPACE}.{event_type}"),
+    }
+}
+
+fn ndjson(lines: &[String]) -> Vec<u8> {
+    let mut body = lines.join("\n");
+    body.push('\n');
+    body.into_bytes()
+}
+
+fn drop_overflow(pending: &mut VecDeque<String>) {
+    let overflow = pending.len().saturating_sub(MAX_PENDING_EVENTS);
+    if overflow > 0 {
+        pending.drain(..overflow);
+        warn!("Dropped {overflow} events waiting for the warehouse export");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn live_record_carries_the_envelope_and_the_actor() {
+        let actor_id = Id::new_v4();
+        let correlation_id = Id::new_v4();
+        let coaching_session_id = Id::new_v4();
+        let action_id = Id::new_v4();
+        let coaching_relationship_id = Id::new_v4();
+        let event = DomainEvent::ActionDeleted {
+            coaching_session_id,
+            action_id,
+            notify_user_ids: vec![actor_id],
+        };
+
+        let record = events::with_correlation_id(
+            Some(correlation_id),
+            events::with_actor(actor_id, async {
+                Record::live(&event, Some(coaching_relationship_id), Utc::now()).unwrap()
+            }),
+        )
+        .await;
+
+        let version = EventKind::ActionDeleted.version();
+        assert_eq!(record.event_type, "action_deleted");
+        assert_eq!(
+            record.schema,
+            format!("refactor_platform.domain_event.action_deleted.v{version}")
+        );
+        assert_eq!(record.schema_version, Some(version));
+        assert_eq!(record.entity_id, Some(action_id));
+        assert_eq!(
+            record.coaching_relationship_id,
+            Some(coaching_relationship_id)
+        );
+        assert_eq!(record.coaching_session_id, Some(coaching_session_id));
+        assert_eq!(record.actor_user_id, Some(actor_id));
+        assert_eq!(record.correlation_id, Some(correlation_id));
+        assert_eq!(record.source, Source::Live);
+        assert!(record.payload.get("type").is_none());
+    }
+
+    #[test]
+    fn backfill_record_exports_an_unreadable_payload_as_stored() {
+        let now = Utc::now();
+        let stored = |payload: Value| event_store::Model {
+            id: Id::new_v4(),
+            event_type: "topics_changed".to_string(),
+            entity_type: "coaching_session".to_string(),
+            entity_id: None,
+            coaching_relationship_id: Some(Id::new_v4()),
+            coaching_session_id: Some(Id::new_v4()),
+            actor_user_id: None,
+            payload,
+            created_at: now.into(),
+        };
+        let readable = stored(
+            serde_json::to_value(DomainEvent::TopicsChanged {
+                coaching_session_id: Id::new_v4(),
+                notify_user_ids: vec![],
+            })
+            .unwrap(),
+        );
+        let unreadable = stored(json!({ "type": "topics_changed", "unexpected": true }));
+
+        let read = Record::backfill(&readable, now);
+        let as_stored = Record::backfill(&unreadable, now);
+
+        assert_eq!(read.event_id, readable.id);
+        assert_eq!(read.source, Source::Backfill);
+        assert_eq!(
+            read.schema_version,
+            Some(EventKind::TopicsChanged.version())
+        );
+        assert!(read.payload.get("coaching_session_id").is_some());
+        assert_eq!(as_stored.schema_version, None);
+        assert_eq!(
+            as_stored.schema,
+            "refactor_platform.domain_event.topics_changed"
+        );
+        assert_eq!(as_stored.payload, json!({ "unexpected": true }));
+    }
+
+    #[test]
+    fn batches_are_partitioned_by_export_date_and_listed_with_every_event_type() {
+        let now = DateTime::parse_from_rfc3339("2026-10-15T09:30:00.123Z")
+            .unwrap()
+            .to_utc();
+
+        let key = object_key("warehouse", Source::Backfill, now);
+        let registry = schema_registry(now);
+
+        assert!(
+            key.starts_with("warehouse/domain_events/dt=2026-10-15/backfill-20261015T093000.123Z-")
+        );
+        assert!(key.ends_with(".ndjson"));
+        assert!(object_key("", Source::Live, now).starts_with("domain_events/dt=2026-10-15/live-"));
+        assert_eq!(
+            registry["event_types"].as_array().unwrap().len(),
+            EventKind::ALL.len()
+        );
+        assert_eq!(registry["fields"].as_array().unwrap().len(), FIELDS.len());
+        assert_eq!(ndjson(&["{}".to_string(), "{}".to_string()]), b"{}\n{}\n");
+    }
+}
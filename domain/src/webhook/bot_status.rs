@@ -1,6 +1,6 @@
 use crate::error::Error;
 use crate::meeting_recording::{self as recording_api, MeetingRecordingStatus, RecordingArtifacts};
-use events::{DomainEvent, EventPublisher};
+use events::EventPublisher;
 use log::*;
 use sea_orm::DatabaseConnection;
 
@@ -50,7 +50,7 @@ pub async fn handle(
     recording_api::update_status(
         db,
         recording.id,
-        status,
+        status.clone(),
         RecordingArtifacts {
             started_at,
             ended_at,
@@ -59,21 +59,8 @@ pub async fn handle(
     )
     .await?;
 
-    let coaching_session_id = recording.coaching_session_id;
-    match crate::coaching_session::find_participant_ids(db, coaching_session_id).await {
-        Ok(user_ids) => {
-            event_publisher
-                .publish(DomainEvent::MeetingRecordingUpdated {
-                    coaching_session_id,
-                    notify_user_ids: user_ids,
-                })
-                .await;
-        }
-        Err(e) => warn!(
-            "bot_status: could not resolve participants for session {}: {:?}",
-            coaching_session_id, e
-        ),
-    }
+    super::publish_recording_status(db, event_publisher, &recording, status, None, "bot_status")
+        .await;
 
     Ok(())
 }
@@ -306,4 +293,85 @@ mod tests {
             "Joining transition should not write ended_at"
         );
     }
+
+    #[tokio::test]
+    async fn bot_status_tells_participants_the_new_status() {
+        use crate::events::DomainEvent;
+        use crate::test_support::recording_publisher;
+        use crate::{coaching_relationships, coaching_sessions};
+
+        let existing = recording_with_status(MeetingRecordingStatus::InMeeting);
+        let now = chrono::Utc::now();
+        let relationship = coaching_relationships::Model {
+            id: Id::new_v4(),
+            organization_id: Id::new_v4(),
+            coach_id: Id::new_v4(),
+            coachee_id: Id::new_v4(),
+            slug: "test-slug".to_string(),
+            ai_privacy_level: Default::default(),
+            pro_bono: false,
+            created_at: now.into(),
+            updated_at: now.into(),
+        };
+        let session = coaching_sessions::Model {
+            id: existing.coaching_session_id,
+            coaching_relationship_id: relationship.id,
+            coaching_session_series_id: None,
+            collab_document_name: None,
+            date: now.naive_utc(),
+            duration_minutes: crate::duration::Duration::default_minutes(),
+            title: None,
+            meeting_url: None,
+            provider: None,
+            created_at: now.into(),
+            updated_at: now.into(),
+            hydrated_at: None,
+            session_type_id: None,
+            deleted_at: None,
+        };
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![existing.clone()]])
+            .append_query_results(vec![vec![existing.clone()]])
+            .append_query_results(vec![vec![Model {
+                status: MeetingRecordingStatus::Recording,
+                ..existing.clone()
+            }]])
+            .append_query_results(vec![vec![(session, relationship.clone())]])
+            .into_connection();
+        let (publisher, events) = recording_publisher();
+
+        handle(
+            &db,
+            &publisher,
+            "bot-skip-test",
+            MeetingRecordingStatus::Recording,
+        )
+        .await
+        .expect("handler should succeed");
+
+        let events = events.lock().unwrap();
+        assert!(matches!(
+            &events[0],
+            DomainEvent::MeetingRecordingUpdated { coaching_session_id, .. }
+                if *coaching_session_id == existing.coaching_session_id
+        ));
+        match &events[1] {
+            DomainEvent::RecordingStatusChanged {
+                meeting_recording_id,
+                status,
+                error_message,
+                notify_user_ids,
+                ..
+            } => {
+                assert_eq!(*meeting_recording_id, existing.id);
+                assert_eq!(status, "recording");
+                assert_eq!(*error_message, None);
+                assert_eq!(
+                    *notify_user_ids,
+                    [relationship.coach_id, relationship.coachee_id]
+                );
+            }
+            other => panic!("expected RecordingStatusChanged, got {other:?}"),
+        }
+    }
 }
@@ -9,10 +9,10 @@ pub mod transcript_processing;
 use crate::error::{DomainErrorKind, Error};
 use crate::meeting_recording::MeetingRecordingStatus;
 use entity::Id;
-use events::EventPublisher;
-use log::debug;
+use events::{DomainEvent, EventPublisher};
+use log::{debug, warn};
 use meeting_ai::traits::transcription as transcription_trait;
-use sea_orm::DatabaseConnection;
+use sea_orm::{ActiveEnum, DatabaseConnection};
 use serde::Deserialize;
 use std::sync::Arc;
 
@@ -263,6 +263,89 @@ pub async fn dispatch(
     }
 }
 
+// ── Notifications ─────────────────────────────────────────────────────────────
+
+/// Publishes the events `events` builds for the coaching session's participants (coach
+/// and coachee). Logs instead when they can't be resolved: the webhook was still handled.
+async fn publish_to_participants(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    coaching_session_id: Id,
+    context: &str,
+    events: impl FnOnce(Vec<Id>) -> Vec<DomainEvent>,
+) {
+    match crate::coaching_session::find_participant_ids(db, coaching_session_id).await {
+        Ok(user_ids) => {
+            for event in events(user_ids) {
+                event_publisher.publish(event).await;
+            }
+        }
+        Err(e) => warn!(
+            "{}: could not resolve participants for session {}: {:?}",
+            context, coaching_session_id, e
+        ),
+    }
+}
+
+/// Tells the session's participants its recording moved to `status`: the coarse
+/// `MeetingRecordingUpdated` clients refetch on, and `RecordingStatusChanged` with the
+/// status itself.
+async fn publish_recording_status(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    recording: &crate::meeting_recording::Model,
+    status: MeetingRecordingStatus,
+    error_message: Option<String>,
+    context: &str,
+) {
+    let coaching_session_id = recording.coaching_session_id;
+    let meeting_recording_id = recording.id;
+    publish_to_participants(
+        db,
+        event_publisher,
+        coaching_session_id,
+        context,
+        |user_ids| {
+            vec![
+                DomainEvent::MeetingRecordingUpdated {
+                    coaching_session_id,
+                    notify_user_ids: user_ids.clone(),
+                },
+                DomainEvent::RecordingStatusChanged {
+                    coaching_session_id,
+                    meeting_recording_id,
+                    status: status.to_value(),
+                    error_message,
+                    notify_user_ids: user_ids,
+                },
+            ]
+        },
+    )
+    .await;
+}
+
+/// Tells the session's participants its transcription changed, so they refetch it.
+async fn publish_transcription_updated(
+    db: &DatabaseConnection,
+    event_publisher: &EventPublisher,
+    coaching_session_id: Id,
+    context: &str,
+) {
+    publish_to_participants(
+        db,
+        event_publisher,
+        coaching_session_id,
+        context,
+        |user_ids| {
+            vec![DomainEvent::TranscriptionUpdated {
+                coaching_session_id,
+                notify_user_ids: user_ids,
+            }]
+        },
+    )
+    .await;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,7 +1,7 @@
 use crate::error::Error;
 use crate::meeting_recording::{self as recording_api, MeetingRecordingStatus, RecordingArtifacts};
 use entity::Id;
-use events::{DomainEvent, EventPublisher};
+use events::EventPublisher;
 use log::*;
 use meeting_ai::traits::transcription as transcription_trait;
 use sea_orm::DatabaseConnection;
@@ -55,20 +55,15 @@ pub async fn handle(
         return Ok(());
     }
 
-    match crate::coaching_session::find_participant_ids(&db, coaching_session_id).await {
-        Ok(user_ids) => {
-            event_publisher
-                .publish(DomainEvent::MeetingRecordingUpdated {
-                    coaching_session_id,
-                    notify_user_ids: user_ids,
-                })
-                .await;
-        }
-        Err(e) => warn!(
-            "recording_done: could not resolve participants for session {}: {:?}",
-            coaching_session_id, e
-        ),
-    }
+    super::publish_recording_status(
+        &db,
+        &event_publisher,
+        &recording,
+        MeetingRecordingStatus::Completed,
+        None,
+        "recording_done",
+    )
+    .await;
 
     let recall_recording_id = recall_recording_id.to_string();
 
@@ -94,52 +89,39 @@ pub async fn handle(
         .await
         {
             Ok(_) => {
-                match crate::coaching_session::find_participant_ids(&db, coaching_session_id).await
-                {
-                    Ok(user_ids) => {
-                        event_publisher
-                            .publish(DomainEvent::TranscriptionUpdated {
-                                coaching_session_id,
-                                notify_user_ids: user_ids,
-                            })
-                            .await;
-                    }
-                    Err(e) => warn!(
-                        "recording_done: could not resolve participants for TranscriptionUpdated: {:?}",
-                        e
-                    ),
-                }
+                super::publish_transcription_updated(
+                    &db,
+                    &event_publisher,
+                    coaching_session_id,
+                    "recording_done",
+                )
+                .await;
             }
             Err(e) => {
                 error!(
                     "recording.done: transcription start failed for session={}: {:?}",
                     coaching_session_id, e
                 );
+                let error_message = Some(e.to_string());
                 let _ = recording_api::update_status(
                     &db,
                     recording.id,
                     MeetingRecordingStatus::Failed,
                     RecordingArtifacts {
-                        error_message: Some(e.to_string()),
+                        error_message: error_message.clone(),
                         ..Default::default()
                     },
                 )
                 .await;
-                match crate::coaching_session::find_participant_ids(&db, coaching_session_id).await
-                {
-                    Ok(user_ids) => {
-                        event_publisher
-                            .publish(DomainEvent::MeetingRecordingUpdated {
-                                coaching_session_id,
-                                notify_user_ids: user_ids,
-                            })
-                            .await;
-                    }
-                    Err(e) => warn!(
-                        "recording_done: could not resolve participants for failure MeetingRecordingUpdated: {:?}",
-                        e
-                    ),
-                }
+                super::publish_recording_status(
+                    &db,
+                    &event_publisher,
+                    &recording,
+                    MeetingRecordingStatus::Failed,
+                    error_message,
+                    "recording_done",
+                )
+                .await;
             }
         }
     });
@@ -1,6 +1,6 @@
 use crate::error::Error;
 use crate::meeting_recording::{self as recording_api, MeetingRecordingStatus, RecordingArtifacts};
-use events::{DomainEvent, EventPublisher};
+use events::EventPublisher;
 use log::*;
 use sea_orm::DatabaseConnection;
 
@@ -41,28 +41,22 @@ pub async fn handle(
         recording.id,
         MeetingRecordingStatus::Failed,
         RecordingArtifacts {
-            error_message,
+            error_message: error_message.clone(),
             ended_at,
             ..Default::default()
         },
     )
     .await?;
 
-    let coaching_session_id = recording.coaching_session_id;
-    match crate::coaching_session::find_participant_ids(db, coaching_session_id).await {
-        Ok(user_ids) => {
-            event_publisher
-                .publish(DomainEvent::MeetingRecordingUpdated {
-                    coaching_session_id,
-                    notify_user_ids: user_ids,
-                })
-                .await;
-        }
-        Err(e) => warn!(
-            "recording_failed: could not resolve participants for session {}: {:?}",
-            coaching_session_id, e
-        ),
-    }
+    super::publish_recording_status(
+        db,
+        event_publisher,
+        &recording,
+        MeetingRecordingStatus::Failed,
+        error_message,
+        "recording_failed",
+    )
+    .await;
 
     Ok(())
 }
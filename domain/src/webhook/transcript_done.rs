@@ -1,5 +1,5 @@
 use crate::error::Error;
-use crate::transcription::{self as transcription_api, TranscriptionStatus};
+use crate::transcription::{self as transcription_api, Completion, TranscriptionStatus};
 use entity::Id;
 use events::{DomainEvent, EventPublisher};
 use log::*;
@@ -45,28 +45,31 @@ pub async fn handle(
     let transcript_id = transcript_id.to_string();
 
     tokio::spawn(async move {
-        let result = crate::transcription::handle_completion(
+        let completion = match crate::transcription::handle_completion(
             &db,
             transcription_provider.as_deref(),
             &transcript_id,
         )
-        .await;
-
-        if let Err(e) = result {
-            error!(
-                "transcript.done: completion failed for external_id={}: {:?}",
-                transcript_id, e
-            );
-            let _ = transcription_api::update_status(
-                &db,
-                transcription_id,
-                TranscriptionStatus::Failed,
-                None,
-                None,
-                Some(e.to_string()),
-            )
-            .await;
-        }
+        .await
+        {
+            Ok(completion) => Some(completion),
+            Err(e) => {
+                error!(
+                    "transcript.done: completion failed for external_id={}: {:?}",
+                    transcript_id, e
+                );
+                let _ = transcription_api::update_status(
+                    &db,
+                    transcription_id,
+                    TranscriptionStatus::Failed,
+                    None,
+                    None,
+                    Some(e.to_string()),
+                )
+                .await;
+                None
+            }
+        };
 
         // Recorded unconditionally (outside the success branch): Recall.ai bills for
         // the transcription attempt regardless of whether completion succeeds.
@@ -80,25 +83,57 @@ pub async fn handle(
             );
         }
 
-        match crate::coaching_session::find_participant_ids(&db, coaching_session_id).await {
-            Ok(user_ids) => {
-                event_publisher
-                    .publish(DomainEvent::TranscriptionUpdated {
-                        coaching_session_id,
-                        notify_user_ids: user_ids,
-                    })
-                    .await;
-            }
-            Err(e) => warn!(
-                "transcript_done: could not resolve participants for session {}: {:?}",
-                coaching_session_id, e
-            ),
-        }
+        super::publish_to_participants(
+            &db,
+            &event_publisher,
+            coaching_session_id,
+            "transcript_done",
+            |user_ids| completion_events(coaching_session_id, completion, user_ids),
+        )
+        .await;
     });
 
     Ok(())
 }
 
+/// `TranscriptionUpdated`, then on success `TranscriptionCompleted` and, when the
+/// provider detected chapters, `AiSuggestionsReady`.
+fn completion_events(
+    coaching_session_id: Id,
+    completion: Option<Completion>,
+    user_ids: Vec<Id>,
+) -> Vec<DomainEvent> {
+    let mut events = vec![DomainEvent::TranscriptionUpdated {
+        coaching_session_id,
+        notify_user_ids: user_ids.clone(),
+    }];
+    let Some(completion) = completion else {
+        return events;
+    };
+
+    match serde_json::to_value(&completion.transcription) {
+        Ok(transcription) => events.push(DomainEvent::TranscriptionCompleted {
+            coaching_session_id,
+            transcription,
+            segment_count: completion.segment_count,
+            notify_user_ids: user_ids.clone(),
+        }),
+        Err(e) => warn!(
+            "transcript_done: could not serialize transcription {}: {:?}",
+            completion.transcription.id, e
+        ),
+    }
+    if completion.chapter_count > 0 {
+        events.push(DomainEvent::AiSuggestionsReady {
+            coaching_session_id,
+            transcription_id: completion.transcription.id,
+            chapter_count: completion.chapter_count,
+            notify_user_ids: user_ids,
+        });
+    }
+    events
+}
+
 #[cfg(test)]
 #[cfg(feature = "mock")]
 mod tests {
@@ -124,6 +159,7 @@ mod tests {
             duration_seconds: None,
             confidence: None,
             error_message: None,
+            conversation_metrics: None,
             created_at: now.into(),
             updated_at: now.into(),
         }
@@ -166,4 +202,47 @@ mod tests {
 
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn completion_events_announce_ai_suggestions_only_when_chapters_were_found() {
+        let mut transcription = queued_transcription();
+        transcription.status = TranscriptionStatus::Completed;
+        let session_id = transcription.coaching_session_id;
+        let user_ids = vec![Id::new_v4(), Id::new_v4()];
+        let completion = |chapter_count| Completion {
+            transcription: transcription.clone(),
+            segment_count: 12,
+            chapter_count,
+        };
+
+        let kinds =
+            |events: Vec<DomainEvent>| -> Vec<_> { events.iter().map(DomainEvent::kind).collect() };
+        assert_eq!(
+            kinds(completion_events(
+                session_id,
+                Some(completion(3)),
+                user_ids.clone()
+            )),
+            [
+                events::EventKind::TranscriptionUpdated,
+                events::EventKind::TranscriptionCompleted,
+                events::EventKind::AiSuggestionsReady,
+            ]
+        );
+        assert_eq!(
+            kinds(completion_events(
+                session_id,
+                Some(completion(0)),
+                user_ids.clone()
+            )),
+            [
+                events::EventKind::TranscriptionUpdated,
+                events::EventKind::TranscriptionCompleted,
+            ]
+        );
+        assert_eq!(
+            kinds(completion_events(session_id, None, user_ids)),
+            [events::EventKind::TranscriptionUpdated]
+        );
+    }
 }
@@ -1,6 +1,6 @@
 use crate::error::Error;
 use crate::transcription::{self as transcription_api, TranscriptionStatus};
-use events::{DomainEvent, EventPublisher};
+use events::EventPublisher;
 use log::*;
 use sea_orm::DatabaseConnection;
 
@@ -31,21 +31,13 @@ pub async fn handle(
     )
     .await?;
 
-    let coaching_session_id = transcription.coaching_session_id;
-    match crate::coaching_session::find_participant_ids(db, coaching_session_id).await {
-        Ok(user_ids) => {
-            event_publisher
-                .publish(DomainEvent::TranscriptionUpdated {
-                    coaching_session_id,
-                    notify_user_ids: user_ids,
-                })
-                .await;
-        }
-        Err(e) => warn!(
-            "transcript_failed: could not resolve participants for session {}: {:?}",
-            coaching_session_id, e
-        ),
-    }
+    super::publish_transcription_updated(
+        db,
+        event_publisher,
+        transcription.coaching_session_id,
+        "transcript_failed",
+    )
+    .await;
 
     Ok(())
 }
@@ -0,0 +1,733 @@
+//! Outbound webhooks: organizations receiving their domain events at their own URLs.
+//!
+//! Admins manage an organization's subscriptions, each naming the event types it wants
+//! from the catalog [`event_types`] lists.
+//! [`WebhookDeliveryHandler`] is registered on the application's [`EventPublisher`] and
+//! POSTs each event happening in one of the organization's coaching relationships to
+//! the matching subscriptions as a JSON [`EventEnvelope`], signed with the
+//! subscription's secret:
+//!
+//! - `X-Refactor-Event`: the event type, e.g. `goal_created`
+//! - `X-Refactor-Delivery`: the payload's `id`, the same on every retry
+//! - `X-Refactor-Signature`: `t=<unix timestamp>,v1=<hex HMAC-SHA256 of "<t>.<body>">`,
+//!   keyed with the secret
+//!
+//! A failed delivery is retried a few times; one still failing dead-letters the event,
+//! and replaying it delivers it again to every subscription it was for.
+//!
+//! Subscription URLs are chosen by organization admins, so deliveries only go to public
+//! addresses: a URL whose host is or resolves to a loopback, private, link-local or
+//! unique-local address is refused when the subscription is saved, and again when
+//! delivering, since DNS can change in between. Redirects aren't followed.
+//!
+//! [`EventPublisher`]: events::EventPublisher
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+use entity_api::{coaching_relationship, error::EntityApiErrorKind, webhook_subscription};
+use events::{DomainEvent, EnvelopeError, EventEnvelope, EventHandler, EventKind, HandlerError};
+use futures::future::join_all;
+use hmac::{Hmac, Mac};
+use log::*;
+use rand::RngCore;
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::{EntityErrorKind, Error};
+use crate::event_log;
+use crate::webhook_subscriptions::Model;
+use crate::Id;
+
+pub use entity_api::webhook_subscription::{find_by_organization, CreatedWebhookSubscription};
+
+/// Longest accepted subscription URL.
+pub const MAX_URL_LENGTH: usize = 2048;
+
+/// Attempts per delivery, including the first.
+const DELIVERY_ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry of a delivery, doubled for each later one.
+const RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+/// How long a receiver has to respond.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub async fn create(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    user_id: Id,
+    model: Model,
+) -> Result<CreatedWebhookSubscription, Error> {
+    validate(&model)?;
+    check_destination(&model.url)
+        .await
+        .map_err(|_| Error::validation("`url` must point to a public address"))?;
+    let secret = generate_secret();
+    let subscription =
+        webhook_subscription::create(db, organization_id, user_id, secret.clone(), model).await?;
+    info!(
+        "Created webhook subscription {} for organization {organization_id}",
+        subscription.id
+    );
+    Ok(CreatedWebhookSubscription {
+        subscription,
+        secret,
+    })
+}
+
+pub async fn update(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    id: Id,
+    model: Model,
+) -> Result<Model, Error> {
+    validate(&model)?;
+    check_destination(&model.url)
+        .await
+        .map_err(|_| Error::validation("`url` must point to a public address"))?;
+    let existing = find_in_organization(db, organization_id, id).await?;
+    Ok(webhook_subscription::update(db, existing, model).await?)
+}
+
+pub async fn delete(db: &DatabaseConnection, organization_id: Id, id: Id) -> Result<(), Error> {
+    find_in_organization(db, organization_id, id).await?;
+    Ok(webhook_subscription::delete_by_id(db, id).await?)
+}
+
+/// An event type subscriptions may name.
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookEventType {
+    /// e.g. `goal_created`, as sent in `X-Refactor-Event`.
+    pub event_type: &'static str,
+    /// Schema version of the payload deliveries carry.
+    pub version: u32,
+    pub description: Option<String>,
+    /// JSON Schema of the bodies of the type's deliveries.
+    pub schema: Value,
+}
+
+/// Every event type delivered to webhooks, with the schema of its deliveries.
+pub fn event_types() -> Vec<WebhookEventType> {
+    EventKind::ALL
+        .into_iter()
+        .filter(|kind| is_delivered(*kind))
+        .map(|kind| {
+            let mut payload = events::schema::payload_schema(kind);
+            let description = payload
+                .as_object_mut()
+                .and_then(|fields| fields.remove("description"))
+                .and_then(|description| description.as_str().map(str::to_string));
+            // See `payload`: who gets an SSE notification isn't delivered.
+            if let Some(properties) = payload["properties"].as_object_mut() {
+                properties.remove("notify_user_ids");
+            }
+            if let Some(required) = payload["required"].as_array_mut() {
+                required.retain(|field| field != "notify_user_ids");
+            }
+            WebhookEventType {
+                event_type: kind.as_str(),
+                version: kind.version(),
+                description,
+                schema: delivery_schema(kind, payload),
+            }
+        })
+        .collect()
+}
+
+/// Events about a user rather than a coaching relationship never reach webhooks.
+fn is_delivered(kind: EventKind) -> bool {
+    !matches!(
+        kind,
+        EventKind::UserLocked | EventKind::UserUnlocked | EventKind::UserRolesChanged
+    )
+}
+
+/// The schema of a [`WebhookPayload`] carrying a `kind` event whose fields match `payload`.
+fn delivery_schema(kind: EventKind, payload: Value) -> Value {
+    let uuid = json!({ "type": "string", "format": "uuid" });
+    json!({
+        "type": "object",
+        "required": [
+            "id",
+            "organization_id",
+            "coaching_relationship_id",
+            "event_type",
+            "version",
+            "payload",
+            "occurred_at",
+        ],
+        "properties": {
+            "id": uuid,
+            "organization_id": uuid,
+            "coaching_relationship_id": uuid,
+            "event_type": { "type": "string", "enum": [kind.as_str()] },
+            "version": { "type": "integer", "enum": [kind.version()] },
+            "payload": payload,
+            "occurred_at": { "type": "string", "format": "date-time" },
+            "correlation_id": { "type": "string", "format": "uuid", "nullable": true },
+        },
+    })
+}
+
+/// A subscription by id, reported as not found unless it belongs to `organization_id`.
+pub async fn find_in_organization(
+    db: &DatabaseConnection,
+    organization_id: Id,
+    id: Id,
+) -> Result<Model, Error> {
+    let subscription = webhook_subscription::find_by_id(db, id).await?;
+    if subscription.organization_id != organization_id {
+        return Err(Error::entity(EntityErrorKind::NotFound));
+    }
+    Ok(subscription)
+}
+
+/// Delivers each event to the webhook subscriptions of the organization it happened in.
+pub struct WebhookDeliveryHandler {
+    db: Arc<DatabaseConnection>,
+    client: reqwest::Client,
+}
+
+impl WebhookDeliveryHandler {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            db,
+            client: delivery_client(),
+        }
+    }
+}
+
+/// The client deliveries are sent with: it doesn't follow redirects, which could lead
+/// anywhere, and only connects to public addresses.
+fn delivery_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(DELIVERY_TIMEOUT)
+        .redirect(reqwest::redirect::Policy::none())
+        .dns_resolver(Arc::new(PublicAddressResolver))
+        .build()
+        .expect("the webhook HTTP client config is valid")
+}
+
+#[async_trait]
+impl EventHandler for WebhookDeliveryHandler {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), HandlerError> {
+        let db = self.db.as_ref();
+        let transient = |e: entity_api::error::Error| HandlerError::Transient(e.to_string());
+
+        let Some(relationship_id) = event_log::coaching_relationship_id(db, event)
+            .await
+            .map_err(transient)?
+        else {
+            return Ok(());
+        };
+        let relationship = match coaching_relationship::find_by_id(db, relationship_id).await {
+            Ok(relationship) => relationship,
+            Err(e) if e.error_kind == EntityApiErrorKind::RecordNotFound => return Ok(()),
+            Err(e) => return Err(transient(e)),
+        };
+
+        let kind = event.kind();
+        let subscriptions = webhook_subscription::find_enabled_for_event(
+            db,
+            relationship.organization_id,
+            kind.as_str(),
+        )
+        .await
+        .map_err(transient)?;
+        if subscriptions.is_empty() {
+            return Ok(());
+        }
+
+        let payload = payload(
+            event,
+            relationship.organization_id,
+            relationship_id,
+            Utc::now(),
+        )
+        .map_err(|e| HandlerError::Permanent(e.to_string()))?;
+        let body =
+            serde_json::to_vec(&payload).map_err(|e| HandlerError::Permanent(e.to_string()))?;
+
+        let results = join_all(subscriptions.iter().map(|subscription| async {
+            if let Err(reason) = check_destination(&subscription.url).await {
+                warn!(
+                    "Not delivering {kind} to webhook subscription {}: {reason}",
+                    subscription.id
+                );
+                return Err(reason);
+            }
+            deliver(
+                &self.client,
+                subscription,
+                payload.id,
+                kind,
+                &body,
+                RETRY_BACKOFF,
+            )
+            .await
+        }))
+        .await;
+        let failed = results.iter().filter(|result| result.is_err()).count();
+        if failed > 0 {
+            return Err(HandlerError::Transient(format!(
+                "{failed} of {} webhook deliveries failed",
+                results.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// The JSON body POSTed to a subscription: the event's envelope, with where it happened.
+#[derive(Debug, Serialize)]
+struct WebhookPayload {
+    /// Identifies the delivery, so receivers can drop repeats.
+    id: Id,
+    organization_id: Id,
+    coaching_relationship_id: Id,
+    #[serde(flatten)]
+    event: EventEnvelope,
+}
+
+fn payload(
+    event: &DomainEvent,
+    organization_id: Id,
+    coaching_relationship_id: Id,
+    occurred_at: DateTime<Utc>,
+) -> Result<WebhookPayload, EnvelopeError> {
+    let mut envelope = EventEnvelope::new(event, occurred_at)?;
+    // Who gets an SSE notification is of no use outside the platform.
+    if let Some(fields) = envelope.payload.as_object_mut() {
+        fields.remove("notify_user_ids");
+    }
+    Ok(WebhookPayload {
+        id: Id::new_v4(),
+        organization_id,
+        coaching_relationship_id,
+        event: envelope,
+    })
+}
+
+/// POSTs `body` to the subscription, retrying network errors, timeouts, 429s and 5xx
+/// responses up to [`DELIVERY_ATTEMPTS`] times in all.
+async fn deliver(
+    client: &reqwest::Client,
+    subscription: &Model,
+    delivery_id: Id,
+    kind: EventKind,
+    body: &[u8],
+    retry_backoff: Duration,
+) -> Result<(), String> {
+    let mut attempt = 1;
+    loop {
+        let timestamp = Utc::now().timestamp();
+        let result = client
+            .post(&subscription.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header("X-Refactor-Event", kind.as_str())
+            .header("X-Refactor-Delivery", delivery_id.to_string())
+            .header(
+                "X-Refactor-Signature",
+                signature_header(&subscription.secret, timestamp, body),
+            )
+            .body(body.to_vec())
+            .send()
+            .await;
+
+        let (failure, retryable) = match result {
+            Ok(response) if response.status().is_success() => return Ok(()),
+            Ok(response) => {
+                let status = response.status();
+                let retryable = status.is_server_error()
+                    || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                    || status == reqwest::StatusCode::REQUEST_TIMEOUT;
+                (format!("responded {status}"), retryable)
+            }
+            Err(e) => (e.to_string(), true),
+        };
+
+        if !retryable || attempt >= DELIVERY_ATTEMPTS {
+            warn!(
+                "Webhook delivery {delivery_id} ({kind}) to subscription {} failed after {attempt} attempt(s): {failure}",
+                subscription.id
+            );
+            return Err(failure);
+        }
+        tokio::time::sleep(retry_backoff * 2u32.pow(attempt - 1)).await;
+        attempt += 1;
+    }
+}
+
+/// Refuses a URL whose host is, or resolves to, an address [`is_public`] rejects.
+async fn check_destination(url: &str) -> Result<(), String> {
+    let url = reqwest::Url::parse(url).map_err(|_| "not a URL".to_string())?;
+    let host = url.host_str().ok_or_else(|| "no host".to_string())?;
+    // IPv6 hosts keep their brackets in URLs
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        return match is_public(ip) {
+            true => Ok(()),
+            false => Err(format!("{ip} is not a public address")),
+        };
+    }
+    public_addrs(host, url.port_or_known_default().unwrap_or(443))
+        .await
+        .map(|_| ())
+}
+
+/// The addresses `host` resolves to, unless any of them isn't public: a host pointing
+/// at both public and internal addresses is refused rather than filtered.
+async fn public_addrs(host: &str, port: u16) -> Result<Vec<SocketAddr>, String> {
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| format!("{host} doesn't resolve: {e}"))?
+        .collect();
+    if let Some(addr) = addrs.iter().find(|addr| !is_public(addr.ip())) {
+        return Err(format!(
+            "{host} resolves to {}, not a public address",
+            addr.ip()
+        ));
+    }
+    if addrs.is_empty() {
+        return Err(format!("{host} doesn't resolve"));
+    }
+    Ok(addrs)
+}
+
+/// Whether webhooks may be delivered to `ip`: not a loopback, private, link-local,
+/// unique-local, shared (CGNAT), documentation, multicast or unspecified address, nor an
+/// IPv6 address embedding one.
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || ip.is_documentation()
+                || a == 0
+                || (a == 100 && (b & 0xc0) == 64))
+        }
+        IpAddr::V6(ip) => {
+            if let Some(ipv4) = ip.to_ipv4_mapped() {
+                return is_public(IpAddr::V4(ipv4));
+            }
+            let segments = ip.segments();
+            // NAT64 (64:ff9b::/96) reaches the IPv4 address in its last 32 bits
+            if segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+                let [.., a, b, c, d] = ip.octets();
+                return is_public(IpAddr::from([a, b, c, d]));
+            }
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                || (segments[0] & 0xfe00) == 0xfc00
+                || (segments[0] & 0xffc0) == 0xfe80)
+        }
+    }
+}
+
+/// Resolves delivery hosts like the system resolver, refusing any with a non-public
+/// address. Checking the addresses actually connected to, not just the URL when it was
+/// saved, stops a host from being re-pointed at an internal one afterwards.
+struct PublicAddressResolver;
+
+impl reqwest::dns::Resolve for PublicAddressResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addrs = public_addrs(&host, 0).await?;
+            Ok(Box::new(addrs.into_iter()) as reqwest::dns::Addrs)
+        })
+    }
+}
+
+/// `t=<timestamp>,v1=<signature>`, where the signature is the hex HMAC-SHA256 of
+/// `"<timestamp>.<body>"` keyed with `secret`. Signing the timestamp lets receivers
+/// reject replayed deliveries.
+fn signature_header(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(format!("{timestamp}.").as_bytes());
+    mac.update(body);
+    format!(
+        "t={timestamp},v1={}",
+        hex::encode(mac.finalize().into_bytes())
+    )
+}
+
+fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    format!("whsec_{}", URL_SAFE_NO_PAD.encode(bytes))
+}
+
+fn validate(model: &Model) -> Result<(), Error> {
+    if model.url.len() > MAX_URL_LENGTH {
+        return Err(Error::validation(format!(
+            "`url` must be at most {MAX_URL_LENGTH} characters"
+        )));
+    }
+    let url =
+        reqwest::Url::parse(&model.url).map_err(|_| Error::validation("`url` is not a URL"))?;
+    if url.scheme() != "https" || url.host_str().is_none() {
+        return Err(Error::validation("`url` must be an https:// URL"));
+    }
+
+    if model.event_types.0.is_empty() {
+        return Err(Error::validation(
+            "`event_types` must name at least one event type",
+        ));
+    }
+    if let Some(unknown) = model
+        .event_types
+        .0
+        .iter()
+        .find(|name| !EventKind::from_name(name).is_some_and(is_delivered))
+    {
+        return Err(Error::validation(format!(
+            "`{unknown}` is not an event type"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::DomainErrorKind;
+    use crate::webhook_subscriptions::EventTypes;
+
+    fn subscription(url: &str, event_types: &[&str]) -> Model {
+        let now = Utc::now();
+        Model {
+            id: Id::new_v4(),
+            organization_id: Id::new_v4(),
+            url: url.to_string(),
+            secret: "whsec_test".to_string(),
+            event_types: EventTypes(event_types.iter().map(|name| name.to_string()).collect()),
+            enabled: true,
+            created_by_user_id: None,
+            created_at: now.into(),
+            updated_at: now.into(),
+        }
+    }
+
+    #[test]
+    fn signature_header_signs_the_timestamp_and_body() {
+        assert_eq!(
+            signature_header(
+                "whsec_test",
+                1_700_000_000,
+                br#"{"event_type":"goal_created"}"#
+            ),
+            "t=1700000000,v1=e5595fb1757febecfbd66104256d5d10ee8da2c730c1a216c2e0f170a83aaa01"
+        );
+    }
+
+    #[test]
+    fn validate_requires_an_https_url_and_known_event_types() {
+        assert!(validate(&subscription(
+            "https://hooks.example.com/x",
+            &["goal_created"]
+        ))
+        .is_ok());
+
+        for invalid in [
+            subscription("http://hooks.example.com/x", &["goal_created"]),
+            subscription("not a url", &["goal_created"]),
+            subscription("https://hooks.example.com/x", &[]),
+            subscription("https://hooks.example.com/x", &["goal_exploded"]),
+            subscription("https://hooks.example.com/x", &["user_locked"]),
+        ] {
+            assert!(matches!(
+                validate(&invalid).unwrap_err().error_kind,
+                DomainErrorKind::Validation(_)
+            ));
+        }
+    }
+
+    #[test]
+    fn payload_leaves_out_who_gets_notified() {
+        let event = DomainEvent::ActionDeleted {
+            coaching_session_id: Id::new_v4(),
+            action_id: Id::new_v4(),
+            notify_user_ids: vec![Id::new_v4()],
+        };
+
+        let payload = payload(&event, Id::new_v4(), Id::new_v4(), Utc::now()).unwrap();
+
+        let body = serde_json::to_value(&payload).unwrap();
+        assert_eq!(body["event_type"], "action_deleted");
+        assert_eq!(body["version"], EventKind::ActionDeleted.version());
+        assert!(body["payload"].get("action_id").is_some());
+        assert!(body["payload"].get("notify_user_ids").is_none());
+    }
+
+    #[test]
+    fn event_types_describe_the_deliveries_of_each_delivered_kind() {
+        let event_types = event_types();
+
+        assert!(event_types
+            .iter()
+            .all(|event_type| event_type.event_type != "user_locked"));
+        let action_deleted = event_types
+            .iter()
+            .find(|event_type| event_type.event_type == "action_deleted")
+            .unwrap();
+        let payload = &action_deleted.schema["properties"]["payload"];
+        assert!(payload["properties"].get("action_id").is_some());
+        assert!(payload["properties"].get("notify_user_ids").is_none());
+        assert_eq!(
+            action_deleted.schema["properties"]["event_type"]["enum"],
+            json!(["action_deleted"])
+        );
+    }
+
+    #[tokio::test]
+    async fn deliver_retries_server_errors_with_the_same_delivery_id() {
+        let mut server = mockito::Server::new_async().await;
+        let delivery_id = Id::new_v4();
+        let failure = server
+            .mock("POST", "/hook")
+            .match_header("x-refactor-delivery", delivery_id.to_string().as_str())
+            .match_header("x-refactor-event", "goal_created")
+            .match_header(
+                "x-refactor-signature",
+                mockito::Matcher::Regex("^t=\\d+,v1=[0-9a-f]{64}$".to_string()),
+            )
+            .with_status(503)
+            .expect(1)
+            .create_async()
+            .await;
+        let success = server
+            .mock("POST", "/hook")
+            .match_header("x-refactor-delivery", delivery_id.to_string().as_str())
+            .with_status(204)
+            .expect(1)
+            .create_async()
+            .await;
+        let subscription = subscription(&format!("{}/hook", server.url()), &["goal_created"]);
+
+        let result = deliver(
+            &reqwest::Client::new(),
+            &subscription,
+            delivery_id,
+            EventKind::GoalCreated,
+            b"{}",
+            Duration::ZERO,
+        )
+        .await;
+
+        assert!(result.is_ok());
+        failure.assert_async().await;
+        success.assert_async().await;
+    }
+
+    #[test]
+    fn is_public_rejects_internal_addresses() {
+        for internal in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "64:ff9b::a9fe:a9fe",
+        ] {
+            assert!(!is_public(internal.parse().unwrap()), "{internal}");
+        }
+        for public in ["93.184.215.14", "2606:2800:21f:cb07:6820:80da:af6b:8b2c"] {
+            assert!(is_public(public.parse().unwrap()), "{public}");
+        }
+    }
+
+    #[tokio::test]
+    async fn check_destination_refuses_internal_hosts() {
+        for internal in [
+            "https://127.0.0.1/hook",
+            "https://169.254.169.254/latest/meta-data",
+            "https://[::1]:8443/hook",
+            "https://10.0.0.5/hook",
+            "https://localhost/hook",
+        ] {
+            assert!(check_destination(internal).await.is_err(), "{internal}");
+        }
+        assert!(check_destination("https://93.184.215.14/hook")
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn deliveries_do_not_follow_redirects() {
+        let mut server = mockito::Server::new_async().await;
+        let redirect = server
+            .mock("POST", "/hook")
+            .with_status(307)
+            .with_header("location", "/internal")
+            .expect(1)
+            .create_async()
+            .await;
+        let followed = server
+            .mock("POST", "/internal")
+            .expect(0)
+            .create_async()
+            .await;
+        let subscription = subscription(&format!("{}/hook", server.url()), &["goal_created"]);
+
+        let result = deliver(
+            &delivery_client(),
+            &subscription,
+            Id::new_v4(),
+            EventKind::GoalCreated,
+            b"{}",
+            Duration::ZERO,
+        )
+        .await;
+
+        assert!(result.is_err());
+        redirect.assert_async().await;
+        followed.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn deliver_gives_up_on_a_client_error() {
+        let mut server = mockito::Server::new_async().await;
+        let rejection = server
+            .mock("POST", "/hook")
+            .with_status(410)
+            .expect(1)
+            .create_async()
+            .await;
+        let subscription = subscription(&format!("{}/hook", server.url()), &["goal_created"]);
+
+        let result = deliver(
+            &reqwest::Client::new(),
+            &subscription,
+            Id::new_v4(),
+            EventKind::GoalCreated,
+            b"{}",
+            Duration::ZERO,
+        )
+        .await;
+
+        assert!(result.is_err());
+        rejection.assert_async().await;
+    }
+}
@@ -0,0 +1,63 @@
+//! `SeaORM` Entity for the action_work_logs table.
+//! A span of focused work one user logged against an action; `duration_seconds` is
+//! `None` while the timer is running.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::action_work_logs::Model)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "action_work_logs")]
+pub struct Model {
+    #[serde(skip_deserializing)]
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    pub action_id: Id,
+    pub user_id: Id,
+    #[schema(value_type = String, format = DateTime)]
+    pub started_at: DateTimeWithTimeZone,
+    pub duration_seconds: Option<i32>,
+    pub note: Option<String>,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::actions::Entity",
+        from = "Column::ActionId",
+        to = "super::actions::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Actions,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Users,
+}
+
+impl Related<super::actions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Actions.def()
+    }
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Users.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
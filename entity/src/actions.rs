@@ -15,7 +15,15 @@ pub struct Model {
     pub goal_id: Option<Id>,
     #[serde(skip_deserializing)]
     pub user_id: Id,
+    /// Set when the action repeats; groups all instances generated from one rule.
+    #[serde(skip_deserializing)]
+    pub action_series_id: Option<Id>,
+    /// The instance this one was generated from, `null` for the first instance.
+    #[serde(skip_deserializing)]
+    pub previous_action_id: Option<Id>,
     pub body: Option<String>,
+    /// Estimated effort (1–100), which goal burndowns weigh the action by.
+    pub effort_points: Option<i16>,
     pub due_by: Option<DateTimeWithTimeZone>,
     pub status: status::Status,
     #[serde(skip_deserializing)]
@@ -26,10 +34,22 @@ pub struct Model {
     #[serde(skip_deserializing)]
     #[schema(value_type = String, format = DateTime)] // Applies to OpenAPI schema
     pub updated_at: DateTimeWithTimeZone,
+    // Soft-delete marker; null for live rows. Reads exclude non-null. Server-only.
+    #[sea_orm(nullable)]
+    #[serde(skip)]
+    pub deleted_at: Option<DateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::action_series::Entity",
+        from = "Column::ActionSeriesId",
+        to = "super::action_series::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    ActionSeries,
     #[sea_orm(has_many = "super::actions_users::Entity")]
     ActionsUsers,
     #[sea_orm(
@@ -58,6 +78,12 @@ pub enum Relation {
     Users,
 }
 
+impl Related<super::action_series::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ActionSeries.def()
+    }
+}
+
 impl Related<super::actions_users::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::ActionsUsers.def()
@@ -0,0 +1,45 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// How much of a coaching relationship's content AI features may send to the LLM provider.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    Eq,
+    PartialEq,
+    PartialOrd,
+    Ord,
+    EnumIter,
+    Deserialize,
+    Serialize,
+    DeriveActiveEnum,
+    ToSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "ai_privacy_level")]
+#[serde(rename_all = "snake_case")]
+#[schema(as = entity::ai_privacy_level::AiPrivacyLevel)]
+pub enum AiPrivacyLevel {
+    /// No content leaves the platform; AI features fall back or are unavailable.
+    #[sea_orm(string_value = "disabled")]
+    Disabled,
+    /// Notes, agreements, actions, goals and shared journal entries, but no transcripts.
+    #[sea_orm(string_value = "notes_only")]
+    NotesOnly,
+    /// Everything, including meeting transcripts.
+    #[default]
+    #[sea_orm(string_value = "full")]
+    Full,
+}
+
+impl AiPrivacyLevel {
+    pub fn allows_ai(self) -> bool {
+        self != Self::Disabled
+    }
+
+    pub fn allows_transcripts(self) -> bool {
+        self == Self::Full
+    }
+}
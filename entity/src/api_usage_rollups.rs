@@ -0,0 +1,45 @@
+//! `SeaORM` Entity for the api_usage_rollups table.
+//! The requests an organization made to one endpoint in one hour, with their errors and
+//! latencies.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "api_usage_rollups")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub organization_id: Id,
+    /// Method and route, e.g. `GET /actions/:id`.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub endpoint: String,
+    /// Start of the hour counted.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub period_start: DateTimeWithTimeZone,
+    pub request_count: i64,
+    /// Requests answered with a 4xx or 5xx status.
+    pub error_count: i64,
+    pub total_latency_ms: i64,
+    pub max_latency_ms: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::organizations::Entity",
+        from = "Column::OrganizationId",
+        to = "super::organizations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Organizations,
+}
+
+impl Related<super::organizations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Organizations.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,79 @@
+//! `SeaORM` Entity for the attachments table.
+//! A file shared in a coaching session, optionally on one of its notes or actions, kept in
+//! object storage.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::attachments::Model)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "attachments")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    pub organization_id: Id,
+    pub coaching_session_id: Id,
+    /// The note the file is attached to, if any.
+    pub note_id: Option<Id>,
+    /// The action the file is attached to, if any.
+    pub action_id: Option<Id>,
+    /// Who attached the file.
+    pub user_id: Option<Id>,
+    pub file_name: String,
+    pub content_type: String,
+    /// Size of the file in bytes.
+    pub byte_size: i64,
+    /// Internal only — where the file is kept in object storage. Never sent to clients.
+    #[serde(skip_serializing)]
+    pub storage_key: String,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::organizations::Entity",
+        from = "Column::OrganizationId",
+        to = "super::organizations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Organizations,
+    #[sea_orm(
+        belongs_to = "super::coaching_sessions::Entity",
+        from = "Column::CoachingSessionId",
+        to = "super::coaching_sessions::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    CoachingSessions,
+    #[sea_orm(
+        belongs_to = "super::notes::Entity",
+        from = "Column::NoteId",
+        to = "super::notes::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    Notes,
+    #[sea_orm(
+        belongs_to = "super::actions::Entity",
+        from = "Column::ActionId",
+        to = "super::actions::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    Actions,
+}
+
+impl Related<super::coaching_sessions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CoachingSessions.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,46 @@
+//! `SeaORM` Entity for the audit_entries table.
+//! A change to an audited entity: who made it, from where, when, and which fields it
+//! changed.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::audit_entries::Model)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "audit_entries")]
+pub struct Model {
+    #[serde(skip_deserializing)]
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Id,
+    /// The organization the entity belongs to, when it belongs to one.
+    pub organization_id: Option<Id>,
+    /// The user who made the change; `None` for webhooks and background work.
+    pub actor_user_id: Option<Id>,
+    /// `created`, `updated`, `deleted` or `restored`.
+    pub action: String,
+    /// Snake-case type of the entity changed, e.g. `goal`.
+    pub entity_type: String,
+    pub entity_id: Id,
+    /// The fields changed, each as `{"from": …, "to": …}`. Values of sensitive fields
+    /// (e.g. note bodies) are recorded as `"[redacted]"`.
+    #[sea_orm(column_type = "JsonBinary")]
+    #[schema(value_type = Object)]
+    pub changes: serde_json::Value,
+    /// The entity as the change left it, which the next change is diffed against;
+    /// `None` once deleted. Server-only.
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    #[serde(skip)]
+    pub snapshot: Option<serde_json::Value>,
+    /// The client address the change was requested from, when made by a request.
+    pub ip_address: Option<String>,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,47 @@
+//! `SeaORM` Entity for the carried_actions table.
+//! An open action brought forward into a later session of its coaching relationship.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "carried_actions")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    pub action_id: Id,
+    /// The session the action was carried into.
+    pub coaching_session_id: Id,
+    /// The session before it, which the action was carried from.
+    pub carried_from_session_id: Id,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::actions::Entity",
+        from = "Column::ActionId",
+        to = "super::actions::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Actions,
+    #[sea_orm(
+        belongs_to = "super::coaching_sessions::Entity",
+        from = "Column::CoachingSessionId",
+        to = "super::coaching_sessions::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    CoachingSessions,
+}
+
+impl Related<super::actions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Actions.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,6 +1,6 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.12.3
 
-use crate::Id;
+use crate::{ai_privacy_level::AiPrivacyLevel, Id};
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -28,6 +28,12 @@ pub struct Model {
     // We'll need to add a migration for that eventually.
     #[sea_orm(unique)]
     pub slug: String,
+    /// Changed through its own endpoint so only the coachee can loosen it.
+    #[serde(skip_deserializing)]
+    pub ai_privacy_level: AiPrivacyLevel,
+    /// Whether the coach coaches without pay; coaching logs count its hours as pro bono.
+    #[serde(default)]
+    pub pro_bono: bool,
 
     #[serde(skip_deserializing)]
     #[schema(value_type = String, format = DateTime)] // Applies to OpenAPI schema
@@ -121,6 +127,8 @@ mod tests {
             coach_id,
             coachee_id,
             slug: "test-slug".to_string(),
+            ai_privacy_level: AiPrivacyLevel::Full,
+            pro_bono: false,
             created_at: now,
             updated_at: now,
         }
@@ -34,6 +34,13 @@ pub struct Model {
     #[serde(skip_deserializing)]
     #[schema(value_type = String, format = DateTime)] // Applies to OpenAPI schema
     pub hydrated_at: Option<DateTimeWithTimeZone>,
+    /// The organization's session type this session is of; `None` when untyped.
+    #[serde(default)]
+    pub session_type_id: Option<Id>,
+    // Soft-delete marker; null for live rows. Reads exclude non-null. Server-only.
+    #[sea_orm(nullable)]
+    #[serde(skip)]
+    pub deleted_at: Option<DateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -60,6 +67,14 @@ pub enum Relation {
     CoachingSessionSeries,
     #[sea_orm(has_many = "super::notes::Entity")]
     Notes,
+    #[sea_orm(
+        belongs_to = "super::session_types::Entity",
+        from = "Column::SessionTypeId",
+        to = "super::session_types::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    SessionTypes,
     #[sea_orm(has_many = "super::coaching_session_topics::Entity")]
     CoachingSessionTopics,
     #[sea_orm(has_many = "super::goals::Entity")]
@@ -98,6 +113,12 @@ impl Related<super::notes::Entity> for Entity {
     }
 }
 
+impl Related<super::session_types::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SessionTypes.def()
+    }
+}
+
 impl Related<super::coaching_session_topics::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::CoachingSessionTopics.def()
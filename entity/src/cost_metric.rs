@@ -28,4 +28,7 @@ pub enum Metric {
 
     #[sea_orm(string_value = "llm_tokens")]
     LlmTokens,
+
+    #[sea_orm(string_value = "embedding_tokens")]
+    EmbeddingTokens,
 }
@@ -0,0 +1,43 @@
+//! `SeaORM` Entity for the dead_letter_events table.
+//! An event an event handler failed to process, kept so it can be inspected and requeued.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::dead_letter_events::Model)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "dead_letter_events")]
+pub struct Model {
+    #[serde(skip_deserializing)]
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    /// Name of the handler that failed, as it is registered.
+    pub handler_name: String,
+    /// Snake-case event name, e.g. `goal_created`.
+    pub event_type: String,
+    /// The serialized event.
+    #[sea_orm(column_type = "JsonBinary")]
+    #[schema(value_type = Object)]
+    pub payload: serde_json::Value,
+    /// The handler's last error.
+    pub error: String,
+    /// Attempts made to deliver the event, including failed requeues.
+    pub attempts: i32,
+    pub requeue_count: i32,
+    /// When a requeue delivered the event; `None` while it's still dead.
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub requeued_at: Option<DateTimeWithTimeZone>,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
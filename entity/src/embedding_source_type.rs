@@ -0,0 +1,31 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Kind of record an embedding was computed from (which table `source_id` points into).
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Hash,
+    EnumIter,
+    Deserialize,
+    Serialize,
+    DeriveActiveEnum,
+    ToSchema,
+)]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "Enum",
+    enum_name = "embedding_source_type"
+)]
+#[serde(rename_all = "snake_case")]
+#[schema(as = entity::embedding_source_type::EmbeddingSourceType)]
+pub enum EmbeddingSourceType {
+    #[sea_orm(string_value = "note")]
+    Note,
+    #[sea_orm(string_value = "transcript_segment")]
+    TranscriptSegment,
+}
@@ -0,0 +1,57 @@
+//! `SeaORM` Entity for the event_store table.
+//! A stored domain event: what happened, to which entity, on whose behalf and when. The
+//! table is partitioned by month of `created_at`.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::event_store::Model)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "event_store")]
+pub struct Model {
+    #[serde(skip_deserializing)]
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Id,
+    /// Snake-case event name, e.g. `goal_created`.
+    pub event_type: String,
+    /// Snake-case type of the entity the event is about, e.g. `goal`.
+    pub entity_type: String,
+    /// The entity's ID, when the event identifies a single one.
+    pub entity_id: Option<Id>,
+    /// The coaching relationship the event happened in, if any.
+    pub coaching_relationship_id: Option<Id>,
+    /// The coaching session the event happened in, if any.
+    pub coaching_session_id: Option<Id>,
+    /// The user whose request emitted the event; `None` for webhooks and background work.
+    pub actor_user_id: Option<Id>,
+    /// The serialized event.
+    #[sea_orm(column_type = "JsonBinary")]
+    #[schema(value_type = Object)]
+    pub payload: serde_json::Value,
+    #[serde(skip_deserializing)]
+    #[sea_orm(primary_key, auto_increment = false)]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::ActorUserId",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    Users,
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Users.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
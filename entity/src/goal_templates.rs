@@ -0,0 +1,89 @@
+//! `SeaORM` Entity for the goal_templates table.
+//! A reusable goal shared across an organization; instantiating it in a coaching
+//! relationship creates a goal plus one action per milestone.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use sea_orm::FromJsonQueryResult;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A step toward the templated goal, created as an action on instantiation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Milestone {
+    pub body: String,
+    /// Days after instantiation the resulting action is due; `None` leaves it undated.
+    pub due_offset_days: Option<i32>,
+}
+
+/// The ordered milestones of a template, stored as a JSONB array.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+#[serde(transparent)]
+pub struct Milestones(pub Vec<Milestone>);
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::goal_templates::Model)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "goal_templates")]
+pub struct Model {
+    #[serde(skip_deserializing)]
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    #[serde(skip_deserializing)]
+    pub organization_id: Id,
+    pub title: String,
+    pub body: Option<String>,
+    /// Days from instantiation to the goal's `target_date`; `None` for momentum-based goals.
+    pub target_duration_days: Option<i32>,
+    #[sea_orm(column_type = "JsonBinary")]
+    #[serde(default)]
+    #[schema(value_type = Vec<Milestone>)]
+    pub milestones: Milestones,
+    #[serde(skip_deserializing)]
+    pub created_by_user_id: Id,
+    /// Number of goals created from this template.
+    #[serde(skip_deserializing)]
+    pub usage_count: i32,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub last_used_at: Option<DateTimeWithTimeZone>,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::organizations::Entity",
+        from = "Column::OrganizationId",
+        to = "super::organizations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Organizations,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::CreatedByUserId",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Restrict"
+    )]
+    Users,
+}
+
+impl Related<super::organizations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Organizations.def()
+    }
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Users.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
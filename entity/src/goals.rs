@@ -29,6 +29,10 @@ pub struct Model {
     pub created_at: DateTimeWithTimeZone,
     #[serde(skip_deserializing)]
     pub updated_at: DateTimeWithTimeZone,
+    // Soft-delete marker; null for live rows. Reads exclude non-null. Server-only.
+    #[sea_orm(nullable)]
+    #[serde(skip)]
+    pub deleted_at: Option<DateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -110,6 +114,7 @@ mod tests {
             target_date: None,
             created_at: now,
             updated_at: now,
+            deleted_at: None,
         }
     }
 
@@ -0,0 +1,65 @@
+//! `SeaORM` Entity for the journal_entries table.
+//! A coachee's journal entry within a coaching relationship, independent of any session.
+//! Only its author can see it unless `shared_with_coach` is set.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::journal_entries::Model)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "journal_entries")]
+pub struct Model {
+    #[serde(skip_deserializing)]
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    #[serde(skip_deserializing)]
+    pub coaching_relationship_id: Id,
+    #[serde(skip_deserializing)]
+    pub user_id: Id,
+    pub title: Option<String>,
+    pub body: String,
+    #[serde(default)]
+    pub shared_with_coach: bool,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::coaching_relationships::Entity",
+        from = "Column::CoachingRelationshipId",
+        to = "super::coaching_relationships::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    CoachingRelationships,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Users,
+}
+
+impl Related<super::coaching_relationships::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CoachingRelationships.def()
+    }
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Users.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
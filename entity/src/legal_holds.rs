@@ -0,0 +1,43 @@
+//! `SeaORM` Entity for the legal_holds table.
+//! A hold pausing the purge and deletion of an organization's or a coaching
+//! relationship's data, e.g. during litigation.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::legal_holds::Model)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "legal_holds")]
+pub struct Model {
+    #[serde(skip_deserializing)]
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    /// The organization held; exactly one of this and `coaching_relationship_id` is set.
+    pub organization_id: Option<Id>,
+    /// The coaching relationship held.
+    pub coaching_relationship_id: Option<Id>,
+    /// Why the data must be preserved, e.g. the matter it's preserved for.
+    pub reason: String,
+    #[serde(skip_deserializing)]
+    pub placed_by_user_id: Id,
+    #[serde(skip_deserializing)]
+    pub released_by_user_id: Option<Id>,
+    /// When the hold was released; `null` while it's active.
+    #[serde(skip_deserializing)]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub released_at: Option<DateTimeWithTimeZone>,
+    /// When the hold was placed.
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
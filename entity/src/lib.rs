@@ -3,9 +3,17 @@ use uuid::Uuid;
 pub mod prelude;
 
 // Core entities
+pub mod action_series;
+pub mod action_work_logs;
 pub mod actions;
 pub mod actions_users;
 pub mod agreements;
+pub mod ai_privacy_level;
+pub mod api_usage_rollups;
+pub mod attachments;
+pub mod audit_entries;
+pub mod calendar_feeds;
+pub mod carried_actions;
 pub mod coachees;
 pub mod coaches;
 pub mod coaching_relationships;
@@ -17,29 +25,81 @@ pub mod coaching_sessions_goals;
 pub mod cost_metric;
 pub mod cost_pricing_config;
 pub mod cost_unit;
+pub mod dead_letter_events;
 pub mod duration;
+pub mod embedding_source_type;
+pub mod event_store;
+pub mod goal_templates;
 pub mod goals;
+pub mod journal_entries;
 pub mod jwts;
+pub mod legal_holds;
+pub mod library_assignments;
+pub mod library_item_kind;
+pub mod library_items;
 pub mod links;
 pub mod magic_link_tokens;
 pub mod meeting_provider;
 pub mod meeting_recording;
+pub mod mentions;
+pub mod note_blind_index_tokens;
 pub mod notes;
+pub mod oauth_authorization_codes;
+pub mod oauth_clients;
 pub mod oauth_connections;
+pub mod oauth_grants;
+pub mod organization_ai_settings;
+pub mod organization_api_quotas;
+pub mod organization_data_keys;
+pub mod organization_holidays;
+pub mod organization_transcription_vocabularies;
 pub mod organizations;
+pub mod out_of_office_periods;
+pub mod outbox_events;
 pub mod password_reset_attempts;
 pub mod pipeline_provider;
 pub mod platform_cost_metrics;
+pub mod platform_settings;
+pub mod policy_acceptances;
+pub mod policy_documents;
+pub mod policy_kind;
+pub mod progress_report_settings;
+pub mod progress_reports;
+pub mod prompt_key;
+pub mod prompt_templates;
+pub mod push_subscriptions;
+pub mod question_quality_summaries;
+pub mod reactions;
+pub mod relationship_health_scores;
+pub mod relationship_health_settings;
+pub mod remembered_devices;
+pub mod resource_type;
+pub mod resource_views;
 pub mod roles;
+pub mod scheduled_events;
+pub mod scim_tokens;
+pub mod scim_users;
+pub mod session_prep_briefs;
+pub mod session_pulses;
+pub mod session_record_status;
+pub mod session_records;
+pub mod session_request_status;
+pub mod session_requests;
+pub mod session_types;
 pub mod status;
+pub mod theme_reports;
 pub mod token_purpose;
 pub mod topic_priority;
 pub mod topic_status;
+pub mod transcript_chapter;
 pub mod transcript_segment;
 pub mod transcription;
 pub mod user_invite_status;
+pub mod user_locks;
 pub mod user_roles;
 pub mod users;
+pub mod warehouse_checkpoints;
+pub mod webhook_subscriptions;
 
 /// A type alias that represents any Entity's internal id field data type.
 /// Aliased so that it's easy to change the underlying type if necessary.
@@ -0,0 +1,67 @@
+//! `SeaORM` Entity for the library_assignments table.
+//! A library item assigned to a coaching relationship's coachee, with an optional due
+//! date; `completed_at` is set once the coachee has finished it.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::library_assignments::Model)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "library_assignments")]
+pub struct Model {
+    #[serde(skip_deserializing)]
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    pub library_item_id: Id,
+    #[serde(skip_deserializing)]
+    pub coaching_relationship_id: Id,
+    #[serde(skip_deserializing)]
+    pub assigned_by_user_id: Id,
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub due_by: Option<DateTimeWithTimeZone>,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub completed_at: Option<DateTimeWithTimeZone>,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::library_items::Entity",
+        from = "Column::LibraryItemId",
+        to = "super::library_items::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    LibraryItems,
+    #[sea_orm(
+        belongs_to = "super::coaching_relationships::Entity",
+        from = "Column::CoachingRelationshipId",
+        to = "super::coaching_relationships::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    CoachingRelationships,
+}
+
+impl Related<super::library_items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::LibraryItems.def()
+    }
+}
+
+impl Related<super::coaching_relationships::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CoachingRelationships.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,17 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// What kind of content a library item is.
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, EnumIter, Deserialize, Serialize, DeriveActiveEnum, ToSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "library_item_kind")]
+#[serde(rename_all = "snake_case")]
+#[schema(as = entity::library_item_kind::LibraryItemKind)]
+pub enum LibraryItemKind {
+    #[sea_orm(string_value = "article")]
+    Article,
+    #[sea_orm(string_value = "exercise")]
+    Exercise,
+}
@@ -0,0 +1,71 @@
+//! `SeaORM` Entity for the library_items table.
+//! An article or exercise in an organization's content library, assignable to coachees.
+
+use crate::library_item_kind::LibraryItemKind;
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use sea_orm::FromJsonQueryResult;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Free-form labels on a library item, stored as a JSONB array of strings.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+#[serde(transparent)]
+pub struct Tags(pub Vec<String>);
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::library_items::Model)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "library_items")]
+pub struct Model {
+    #[serde(skip_deserializing)]
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    #[serde(skip_deserializing)]
+    pub organization_id: Id,
+    pub kind: LibraryItemKind,
+    pub title: String,
+    /// Inline content; at least one of `body` and `url` is set.
+    pub body: Option<String>,
+    /// Link to external content; at least one of `body` and `url` is set.
+    pub url: Option<String>,
+    #[sea_orm(column_type = "JsonBinary")]
+    #[serde(default)]
+    #[schema(value_type = Vec<String>)]
+    pub tags: Tags,
+    #[serde(skip_deserializing)]
+    pub created_by_user_id: Id,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::organizations::Entity",
+        from = "Column::OrganizationId",
+        to = "super::organizations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Organizations,
+    #[sea_orm(has_many = "super::library_assignments::Entity")]
+    LibraryAssignments,
+}
+
+impl Related<super::organizations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Organizations.def()
+    }
+}
+
+impl Related<super::library_assignments::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::LibraryAssignments.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,62 @@
+//! `SeaORM` Entity for the mentions table.
+//! A user @-mentioned in a note or action body; at most one row per (target, mentioned user).
+
+use crate::resource_type::ResourceType;
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::mentions::Model)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "mentions")]
+pub struct Model {
+    #[serde(skip_deserializing)]
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    pub resource_type: ResourceType,
+    pub resource_id: Id,
+    pub coaching_session_id: Id,
+    pub mentioned_user_id: Id,
+    pub author_user_id: Id,
+    #[serde(skip_deserializing)]
+    pub created_at: DateTimeWithTimeZone,
+    #[serde(skip_deserializing)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::coaching_sessions::Entity",
+        from = "Column::CoachingSessionId",
+        to = "super::coaching_sessions::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    CoachingSessions,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::MentionedUserId",
+        to = "super::users::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    MentionedUser,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::AuthorUserId",
+        to = "super::users::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Author,
+}
+
+impl Related<super::coaching_sessions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CoachingSessions.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,47 @@
+//! `SeaORM` Entity for the note_blind_index_tokens table.
+//! One word of an encrypted note, blinded with an HMAC keyed by its organization's data
+//! key and scoped to its relationship, so the note can be keyword-searched.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(
+    schema_name = "refactor_platform",
+    table_name = "note_blind_index_tokens"
+)]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub note_id: Id,
+    pub coaching_relationship_id: Id,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub token: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::notes::Entity",
+        from = "Column::NoteId",
+        to = "super::notes::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Notes,
+    #[sea_orm(
+        belongs_to = "super::coaching_relationships::Entity",
+        from = "Column::CoachingRelationshipId",
+        to = "super::coaching_relationships::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    CoachingRelationships,
+}
+
+impl Related<super::notes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Notes.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,64 @@
+//! `SeaORM` Entity for the oauth_authorization_codes table.
+//! A single-use code a user's consent produced for an OAuth client, exchanged for tokens
+//! by presenting the PKCE verifier behind `code_challenge`.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(
+    schema_name = "refactor_platform",
+    table_name = "oauth_authorization_codes"
+)]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    /// SHA-256 hash of the code; the code itself is never stored.
+    #[sea_orm(unique)]
+    pub code_hash: String,
+    pub client_id: Id,
+    pub user_id: Id,
+    /// The redirect URI the code was sent to, which the exchange must repeat.
+    pub redirect_uri: String,
+    /// The S256 PKCE challenge of the client's code verifier.
+    pub code_challenge: String,
+    /// Space-separated scopes the user granted.
+    pub scope: String,
+    pub expires_at: DateTimeWithTimeZone,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::oauth_clients::Entity",
+        from = "Column::ClientId",
+        to = "super::oauth_clients::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    OauthClients,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Users,
+}
+
+impl Related<super::oauth_clients::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::OauthClients.def()
+    }
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Users.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,85 @@
+//! `SeaORM` Entity for the oauth_clients table.
+//! A third-party app an organization registered to act on behalf of its users through
+//! the OAuth 2.0 authorization-code flow. Its `id` is the app's client ID.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use sea_orm::FromJsonQueryResult;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// The URIs authorization codes may be sent to, stored as a JSONB array.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+#[serde(transparent)]
+pub struct RedirectUris(pub Vec<String>);
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::oauth_clients::Model)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "oauth_clients")]
+pub struct Model {
+    /// The app's OAuth client ID.
+    #[serde(skip_deserializing)]
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    #[serde(skip_deserializing)]
+    pub organization_id: Id,
+    /// Shown to users when they're asked to authorize the app.
+    pub name: String,
+    /// SHA-256 hash of the client secret; `None` for a public client, which proves
+    /// itself with PKCE alone.
+    #[serde(skip)]
+    pub client_secret_hash: Option<String>,
+    #[sea_orm(column_type = "JsonBinary")]
+    #[schema(value_type = Vec<String>)]
+    pub redirect_uris: RedirectUris,
+    #[serde(skip_deserializing)]
+    pub created_by_user_id: Option<Id>,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::organizations::Entity",
+        from = "Column::OrganizationId",
+        to = "super::organizations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Organizations,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::CreatedByUserId",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    Users,
+    #[sea_orm(has_many = "super::oauth_grants::Entity")]
+    OauthGrants,
+}
+
+impl Related<super::organizations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Organizations.def()
+    }
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Users.def()
+    }
+}
+
+impl Related<super::oauth_grants::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::OauthGrants.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,61 @@
+//! `SeaORM` Entity for the oauth_grants table.
+//! The access and refresh token an OAuth client holds to act on behalf of a user, stored
+//! as SHA-256 hashes. Refreshing replaces both; revoking stamps `revoked_at`.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "oauth_grants")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    pub client_id: Id,
+    pub user_id: Id,
+    /// Space-separated scopes the user granted.
+    pub scope: String,
+    #[sea_orm(unique)]
+    pub access_token_hash: String,
+    pub access_token_expires_at: DateTimeWithTimeZone,
+    #[sea_orm(unique)]
+    pub refresh_token_hash: String,
+    pub refresh_token_expires_at: DateTimeWithTimeZone,
+    pub revoked_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::oauth_clients::Entity",
+        from = "Column::ClientId",
+        to = "super::oauth_clients::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    OauthClients,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Users,
+}
+
+impl Related<super::oauth_clients::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::OauthClients.def()
+    }
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Users.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
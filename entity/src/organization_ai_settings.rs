@@ -0,0 +1,93 @@
+//! `SeaORM` Entity for the organization_ai_settings table.
+//! Which AI providers and models an organization allows, and the model, temperature and
+//! token budget each kind of AI task uses.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use sea_orm::FromJsonQueryResult;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Provider or model ids stored as a JSONB array. Empty means no restriction.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+#[serde(transparent)]
+pub struct AllowList(pub Vec<String>);
+
+/// Overrides for one task type; unset fields use the platform defaults.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::organization_ai_settings::TaskSetting)]
+pub struct TaskSetting {
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
+}
+
+/// Per-task overrides, stored as a JSONB object.
+#[derive(
+    Clone, Debug, Default, PartialEq, Serialize, Deserialize, FromJsonQueryResult, ToSchema,
+)]
+#[schema(as = entity::organization_ai_settings::TaskSettings)]
+pub struct TaskSettings {
+    /// Structured output pulled from session content (e.g. themes reports).
+    #[serde(default)]
+    pub extraction: TaskSetting,
+    /// Summaries of sessions (e.g. prep briefs).
+    #[serde(default)]
+    pub summary: TaskSetting,
+    /// Answers to questions about a relationship's history.
+    #[serde(default)]
+    pub chat: TaskSetting,
+}
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::organization_ai_settings::Model)]
+#[sea_orm(
+    schema_name = "refactor_platform",
+    table_name = "organization_ai_settings"
+)]
+pub struct Model {
+    #[serde(skip_deserializing)]
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub organization_id: Id,
+    #[sea_orm(column_type = "JsonBinary")]
+    #[serde(default)]
+    #[schema(value_type = Vec<String>)]
+    pub allowed_providers: AllowList,
+    #[sea_orm(column_type = "JsonBinary")]
+    #[serde(default)]
+    #[schema(value_type = Vec<String>)]
+    pub allowed_models: AllowList,
+    #[sea_orm(column_type = "JsonBinary")]
+    #[serde(default)]
+    #[schema(value_type = TaskSettings)]
+    pub tasks: TaskSettings,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::organizations::Entity",
+        from = "Column::OrganizationId",
+        to = "super::organizations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Organizations,
+}
+
+impl Related<super::organizations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Organizations.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
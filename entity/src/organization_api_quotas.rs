@@ -0,0 +1,60 @@
+//! `SeaORM` Entity for the organization_api_quotas table.
+//! How many requests per hour an organization may make, overall and per endpoint.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use sea_orm::FromJsonQueryResult;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+
+/// Requests per hour allowed per endpoint (e.g. `GET /actions/:id`), stored as a JSONB
+/// object.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+#[serde(transparent)]
+pub struct EndpointLimits(pub BTreeMap<String, u32>);
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::organization_api_quotas::Model)]
+#[sea_orm(
+    schema_name = "refactor_platform",
+    table_name = "organization_api_quotas"
+)]
+pub struct Model {
+    #[serde(skip_deserializing)]
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub organization_id: Id,
+    /// Requests per hour allowed across every endpoint; `None` for no overall limit.
+    #[serde(default)]
+    pub requests_per_hour: Option<i32>,
+    #[sea_orm(column_type = "JsonBinary")]
+    #[serde(default)]
+    #[schema(value_type = BTreeMap<String, u32>)]
+    pub endpoint_limits: EndpointLimits,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::organizations::Entity",
+        from = "Column::OrganizationId",
+        to = "super::organizations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Organizations,
+}
+
+impl Related<super::organizations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Organizations.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
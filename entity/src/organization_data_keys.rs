@@ -0,0 +1,44 @@
+//! `SeaORM` Entity for the organization_data_keys table.
+//! A key an organization's note and journal entry bodies are encrypted with, stored
+//! encrypted with the platform's `ENCRYPTION_KEY`.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[sea_orm(
+    schema_name = "refactor_platform",
+    table_name = "organization_data_keys"
+)]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    pub organization_id: Id,
+    /// The data key, encrypted with `ENCRYPTION_KEY`. Never serialized.
+    #[serde(skip_serializing)]
+    pub wrapped_key: String,
+    pub created_at: DateTimeWithTimeZone,
+    /// When the key was rotated out; new bodies are only sealed with the active key.
+    pub retired_at: Option<DateTimeWithTimeZone>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::organizations::Entity",
+        from = "Column::OrganizationId",
+        to = "super::organizations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Organizations,
+}
+
+impl Related<super::organizations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Organizations.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,52 @@
+//! `SeaORM` Entity for the organization_holidays table.
+//! A holiday in an organization's calendar, imported from an ICS file.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::organization_holidays::Model)]
+#[sea_orm(
+    schema_name = "refactor_platform",
+    table_name = "organization_holidays"
+)]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    pub organization_id: Id,
+    pub name: String,
+    /// First day of the holiday.
+    #[schema(value_type = String, format = Date)]
+    pub starts_on: Date,
+    /// Last day of the holiday, inclusive.
+    #[schema(value_type = String, format = Date)]
+    pub ends_on: Date,
+    /// The event's UID in the imported calendar.
+    pub uid: String,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::organizations::Entity",
+        from = "Column::OrganizationId",
+        to = "super::organizations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Organizations,
+}
+
+impl Related<super::organizations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Organizations.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
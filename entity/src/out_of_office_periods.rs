@@ -0,0 +1,54 @@
+//! `SeaORM` Entity for the out_of_office_periods table.
+//! A stretch of time a user is away, e.g. on vacation.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::out_of_office_periods::Model)]
+#[sea_orm(
+    schema_name = "refactor_platform",
+    table_name = "out_of_office_periods"
+)]
+pub struct Model {
+    #[serde(skip_deserializing)]
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    #[serde(skip_deserializing)]
+    pub user_id: Id,
+    #[schema(value_type = String, format = DateTime)]
+    pub starts_at: DateTimeWithTimeZone,
+    /// When the user is back; after `starts_at`.
+    #[schema(value_type = String, format = DateTime)]
+    pub ends_at: DateTimeWithTimeZone,
+    /// Shown to the people booking with the user, e.g. "Parental leave".
+    pub note: Option<String>,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Users,
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Users.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,34 @@
+//! `SeaORM` Entity for the outbox_events table.
+//! Domain events the web process published for the events worker to dispatch to the
+//! handlers that don't run in the web process.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "outbox_events")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    /// The event's kind, e.g. `goal_created`.
+    pub event_type: String,
+    /// The serialized `DomainEvent`.
+    #[sea_orm(column_type = "JsonBinary")]
+    pub payload: Json,
+    /// The user whose request published the event, if any.
+    pub actor_id: Option<Id>,
+    /// Times a worker has claimed the event.
+    pub attempts: i32,
+    /// The worker holding the claim, if any.
+    pub locked_by: Option<String>,
+    /// When the claim lapses.
+    pub locked_until: Option<DateTimeWithTimeZone>,
+    pub dispatched_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
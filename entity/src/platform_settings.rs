@@ -0,0 +1,58 @@
+//! `SeaORM` Entity for the platform_settings table.
+//! The settings of the whole platform, in a single row written when it's bootstrapped.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::platform_settings::Model)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "platform_settings")]
+pub struct Model {
+    /// Always 1; the table holds at most one row.
+    #[serde(skip)]
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: i16,
+    /// Name the platform goes by in emails and the UI.
+    pub platform_name: String,
+    /// Where users are told to ask for help.
+    #[serde(default)]
+    pub support_email: Option<String>,
+    /// IANA timezone for users who haven't picked one.
+    #[serde(default = "default_timezone")]
+    pub default_timezone: String,
+    /// The SuperAdmin who bootstrapped the platform; `None` once they're deleted.
+    #[serde(skip_deserializing)]
+    pub bootstrapped_by_id: Option<Id>,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+fn default_timezone() -> String {
+    "UTC".to_string()
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::BootstrappedById",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    Users,
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Users.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
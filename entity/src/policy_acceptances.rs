@@ -0,0 +1,55 @@
+//! `SeaORM` Entity for the policy_acceptances table.
+//! A user's acceptance of a policy document version, kept as the record of their consent.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::policy_acceptances::Model)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "policy_acceptances")]
+pub struct Model {
+    #[serde(skip_deserializing)]
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    pub user_id: Id,
+    pub policy_document_id: Id,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub accepted_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Users,
+    #[sea_orm(
+        belongs_to = "super::policy_documents::Entity",
+        from = "Column::PolicyDocumentId",
+        to = "super::policy_documents::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    PolicyDocuments,
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Users.def()
+    }
+}
+
+impl Related<super::policy_documents::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PolicyDocuments.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,43 @@
+//! `SeaORM` Entity for the policy_documents table.
+//! A published version of a legal policy (terms of service, privacy policy) that users
+//! accept.
+
+use crate::policy_kind::PolicyKind;
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::policy_documents::Model)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "policy_documents")]
+pub struct Model {
+    #[serde(skip_deserializing)]
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    pub kind: PolicyKind,
+    /// The version as shown to users, e.g. `2026-10`.
+    pub version: String,
+    /// When this version takes over from the previous one.
+    #[schema(value_type = String, format = DateTime)]
+    pub effective_at: DateTimeWithTimeZone,
+    /// Where the policy's text is published.
+    pub content_url: String,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::policy_acceptances::Entity")]
+    PolicyAcceptances,
+}
+
+impl Related<super::policy_acceptances::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PolicyAcceptances.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,17 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// Which legal policy a policy document is a version of.
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, EnumIter, Deserialize, Serialize, DeriveActiveEnum, ToSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "policy_kind")]
+#[serde(rename_all = "snake_case")]
+#[schema(as = entity::policy_kind::PolicyKind)]
+pub enum PolicyKind {
+    #[sea_orm(string_value = "terms_of_service")]
+    TermsOfService,
+    #[sea_orm(string_value = "privacy_policy")]
+    PrivacyPolicy,
+}
@@ -0,0 +1,61 @@
+//! `SeaORM` Entity for the progress_reports table.
+//! A weekly AI-written narrative of a coaching relationship's progress, drawn from the
+//! week's sessions, their conversation metrics and AI summaries, and the open actions.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::progress_reports::Model)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "progress_reports")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    pub coaching_relationship_id: Id,
+    /// First day (a Monday) of the week the report covers.
+    #[schema(value_type = String, format = Date)]
+    pub period_start: Date,
+    /// Last day (a Sunday) of the week the report covers.
+    #[schema(value_type = String, format = Date)]
+    pub period_end: Date,
+    pub narrative: String,
+    /// Sessions held during the week.
+    pub session_count: i32,
+    /// Actions still open when the report was generated.
+    pub open_action_count: i32,
+    /// The model that wrote the narrative.
+    pub model: String,
+    /// Version of the system prompt used (0 for the built-in one).
+    pub prompt_version: Option<i32>,
+    #[schema(value_type = String, format = DateTime)]
+    pub generated_at: DateTimeWithTimeZone,
+    /// When the report was emailed to the coach; `None` until it has been.
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub emailed_at: Option<DateTimeWithTimeZone>,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::coaching_relationships::Entity",
+        from = "Column::CoachingRelationshipId",
+        to = "super::coaching_relationships::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    CoachingRelationships,
+}
+
+impl Related<super::coaching_relationships::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CoachingRelationships.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
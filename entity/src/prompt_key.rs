@@ -0,0 +1,35 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// Which AI feature's system prompt a template replaces.
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, EnumIter, Deserialize, Serialize, DeriveActiveEnum, ToSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "prompt_key")]
+#[serde(rename_all = "snake_case")]
+#[schema(as = entity::prompt_key::PromptKey)]
+pub enum PromptKey {
+    #[sea_orm(string_value = "session_prep")]
+    SessionPrep,
+    #[sea_orm(string_value = "themes_report")]
+    ThemesReport,
+    #[sea_orm(string_value = "ai_chat")]
+    AiChat,
+    #[sea_orm(string_value = "question_quality")]
+    QuestionQuality,
+    #[sea_orm(string_value = "progress_report")]
+    ProgressReport,
+}
+
+impl std::fmt::Display for PromptKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PromptKey::SessionPrep => write!(f, "session_prep"),
+            PromptKey::ThemesReport => write!(f, "themes_report"),
+            PromptKey::AiChat => write!(f, "ai_chat"),
+            PromptKey::QuestionQuality => write!(f, "question_quality"),
+            PromptKey::ProgressReport => write!(f, "progress_report"),
+        }
+    }
+}
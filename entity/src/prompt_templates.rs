@@ -0,0 +1,51 @@
+//! `SeaORM` Entity for the prompt_templates table.
+//! An edited version of an AI feature's system prompt. Versions are kept so an admin can
+//! roll back to an earlier one; at most one version per key is active.
+
+use crate::prompt_key::PromptKey;
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::prompt_templates::Model)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "prompt_templates")]
+pub struct Model {
+    #[serde(skip_deserializing)]
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    pub key: PromptKey,
+    /// Numbered per key from 1; version 0 is the built-in prompt.
+    #[serde(skip_deserializing)]
+    pub version: i32,
+    /// Prompt text with `{{variable}}` placeholders.
+    pub body: String,
+    #[serde(skip_deserializing)]
+    pub is_active: bool,
+    #[serde(skip_deserializing)]
+    pub created_by_user_id: Id,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::CreatedByUserId",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Restrict"
+    )]
+    Users,
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Users.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
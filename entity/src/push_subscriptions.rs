@@ -0,0 +1,53 @@
+//! `SeaORM` Entity for the push_subscriptions table.
+//! A browser or device a user allowed to receive Web Push notifications, as the push
+//! service it subscribed through knows it.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::push_subscriptions::Model)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "push_subscriptions")]
+pub struct Model {
+    #[serde(skip_deserializing)]
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    #[serde(skip_deserializing)]
+    pub user_id: Id,
+    /// Push service URL notifications are sent to.
+    pub endpoint: String,
+    /// The browser's P-256 public key (base64url), which payloads are encrypted to.
+    #[serde(skip)]
+    pub p256dh: String,
+    /// The browser's authentication secret (base64url).
+    #[serde(skip)]
+    pub auth: String,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Users,
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Users.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,61 @@
+//! `SeaORM` Entity for the question_quality_summaries table.
+//! Counts of the coach's open and closed questions, advice and inquiry in a coaching
+//! session's transcript, as classified by the LLM.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::question_quality_summaries::Model)]
+#[sea_orm(
+    schema_name = "refactor_platform",
+    table_name = "question_quality_summaries"
+)]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub coaching_session_id: Id,
+    /// The transcription the utterances were classified from.
+    pub transcription_id: Id,
+    /// The transcript speaker identified as the coach, if one could be.
+    pub coach_speaker_label: Option<String>,
+    /// Transcript segments spoken by the coach.
+    pub utterance_count: i32,
+    pub open_question_count: i32,
+    pub closed_question_count: i32,
+    /// Utterances telling the coachee what to do or offering solutions.
+    pub advice_count: i32,
+    /// Utterances exploring the coachee's thinking (including questions).
+    pub inquiry_count: i32,
+    /// The model that classified the utterances.
+    pub model: String,
+    /// Version of the system prompt used (0 for the built-in one).
+    pub prompt_version: Option<i32>,
+    #[schema(value_type = String, format = DateTime)]
+    pub generated_at: DateTimeWithTimeZone,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::coaching_sessions::Entity",
+        from = "Column::CoachingSessionId",
+        to = "super::coaching_sessions::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    CoachingSessions,
+}
+
+impl Related<super::coaching_sessions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CoachingSessions.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,45 @@
+//! `SeaORM` Entity for the reactions table.
+//! One emoji reaction by one user on a note or action; at most one row per (user, target, emoji).
+
+use crate::resource_type::ResourceType;
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::reactions::Model)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "reactions")]
+pub struct Model {
+    #[serde(skip_deserializing)]
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    pub user_id: Id,
+    pub resource_type: ResourceType,
+    pub resource_id: Id,
+    pub emoji: String,
+    #[serde(skip_deserializing)]
+    pub created_at: DateTimeWithTimeZone,
+    #[serde(skip_deserializing)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Users,
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Users.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,49 @@
+//! `SeaORM` Entity for the relationship_health_scores table.
+//! A coaching relationship's 0–100 health score from one scoring run, with the score of
+//! each signal it combined.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::relationship_health_scores::Model)]
+#[sea_orm(
+    schema_name = "refactor_platform",
+    table_name = "relationship_health_scores"
+)]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    pub coaching_relationship_id: Id,
+    /// The weighted combination of the signals that had data.
+    pub score: i16,
+    /// `null` for a signal that had nothing to go on in this run.
+    pub pulse_score: Option<i16>,
+    pub cadence_score: Option<i16>,
+    pub action_completion_score: Option<i16>,
+    pub sentiment_score: Option<i16>,
+    #[schema(value_type = String, format = DateTime)]
+    pub computed_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::coaching_relationships::Entity",
+        from = "Column::CoachingRelationshipId",
+        to = "super::coaching_relationships::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    CoachingRelationships,
+}
+
+impl Related<super::coaching_relationships::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CoachingRelationships.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,56 @@
+//! `SeaORM` Entity for the relationship_health_settings table.
+//! How an organization weighs each signal of its relationships' health scores, and the
+//! score below which its admins are alerted.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::relationship_health_settings::Model)]
+#[sea_orm(
+    schema_name = "refactor_platform",
+    table_name = "relationship_health_settings"
+)]
+pub struct Model {
+    #[serde(skip_deserializing)]
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub organization_id: Id,
+    /// Weight (0–100) of the participants' post-session pulse answers.
+    pub pulse_weight: i16,
+    /// Weight (0–100) of how regularly sessions are held.
+    pub cadence_weight: i16,
+    /// Weight (0–100) of the share of due actions that were completed.
+    pub action_completion_weight: i16,
+    /// Weight (0–100) of the sentiment of recent session transcripts.
+    pub sentiment_weight: i16,
+    /// Admins are alerted when a relationship's score drops below this (0–100).
+    pub alert_threshold: i16,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::organizations::Entity",
+        from = "Column::OrganizationId",
+        to = "super::organizations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Organizations,
+}
+
+impl Related<super::organizations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Organizations.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
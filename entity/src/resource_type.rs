@@ -0,0 +1,29 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Kind of session-scoped record a row refers to (e.g. which table `resource_id` points into).
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Hash,
+    EnumIter,
+    Deserialize,
+    Serialize,
+    DeriveActiveEnum,
+    ToSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "resource_type")]
+#[serde(rename_all = "snake_case")]
+#[schema(as = entity::resource_type::ResourceType)]
+pub enum ResourceType {
+    #[sea_orm(string_value = "coaching_session")]
+    CoachingSession,
+    #[sea_orm(string_value = "action")]
+    Action,
+    #[sea_orm(string_value = "note")]
+    Note,
+}
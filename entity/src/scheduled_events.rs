@@ -0,0 +1,33 @@
+//! `SeaORM` Entity for the scheduled_events table.
+//! A domain event to publish at a later time.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "scheduled_events")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    /// The event's kind, e.g. `goal_created`.
+    pub event_type: String,
+    /// The serialized `DomainEvent`.
+    #[sea_orm(column_type = "JsonBinary")]
+    pub payload: Json,
+    /// The user whose request scheduled the event, if any.
+    pub actor_id: Option<Id>,
+    /// When the event is due to be published.
+    pub publish_at: DateTimeWithTimeZone,
+    /// Times the scheduler has claimed the event.
+    pub attempts: i32,
+    /// When the scheduler's claim lapses.
+    pub locked_until: Option<DateTimeWithTimeZone>,
+    pub published_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
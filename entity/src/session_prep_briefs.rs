@@ -0,0 +1,56 @@
+//! `SeaORM` Entity for the session_prep_briefs table.
+//! The cached AI-generated part of a coaching session's prep brief: a summary of the
+//! previous session and suggested focus questions.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use sea_orm::FromJsonQueryResult;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Suggested questions for the coach, stored as a JSONB array of strings.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+#[serde(transparent)]
+pub struct FocusQuestions(pub Vec<String>);
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::session_prep_briefs::Model)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "session_prep_briefs")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub coaching_session_id: Id,
+    pub last_session_summary: Option<String>,
+    #[sea_orm(column_type = "JsonBinary")]
+    #[schema(value_type = Vec<String>)]
+    pub focus_questions: FocusQuestions,
+    /// The model that generated this brief.
+    pub model: String,
+    /// Version of the system prompt used (0 for the built-in one).
+    pub prompt_version: Option<i32>,
+    #[schema(value_type = String, format = DateTime)]
+    pub generated_at: DateTimeWithTimeZone,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::coaching_sessions::Entity",
+        from = "Column::CoachingSessionId",
+        to = "super::coaching_sessions::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    CoachingSessions,
+}
+
+impl Related<super::coaching_sessions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CoachingSessions.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
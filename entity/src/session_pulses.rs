@@ -0,0 +1,69 @@
+//! `SeaORM` Entity for the session_pulses table.
+//! A participant's 1–5 answer to "How valuable was this session?", asked once it ended.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::session_pulses::Model)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "session_pulses")]
+pub struct Model {
+    #[serde(skip_deserializing)]
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    #[serde(skip_deserializing)]
+    pub coaching_session_id: Id,
+    #[serde(skip_deserializing)]
+    pub user_id: Id,
+    /// 1 (not valuable) to 5 (very valuable); `null` until the participant answers.
+    pub score: Option<i16>,
+    /// When the participant was prompted.
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub requested_at: DateTimeWithTimeZone,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub responded_at: Option<DateTimeWithTimeZone>,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::coaching_sessions::Entity",
+        from = "Column::CoachingSessionId",
+        to = "super::coaching_sessions::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    CoachingSessions,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Users,
+}
+
+impl Related<super::coaching_sessions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CoachingSessions.def()
+    }
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Users.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
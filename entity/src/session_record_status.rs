@@ -0,0 +1,35 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// Where a session's record of engagement stands: waiting to be compiled, stored, or
+// not compiled because compiling it failed.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    Eq,
+    PartialEq,
+    EnumIter,
+    Deserialize,
+    Serialize,
+    DeriveActiveEnum,
+    ToSchema,
+)]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "Enum",
+    enum_name = "session_record_status"
+)]
+#[serde(rename_all = "snake_case")]
+#[schema(as = entity::session_record_status::SessionRecordStatus)]
+pub enum SessionRecordStatus {
+    #[default]
+    #[sea_orm(string_value = "pending")]
+    Pending,
+    #[sea_orm(string_value = "ready")]
+    Ready,
+    #[sea_orm(string_value = "failed")]
+    Failed,
+}
@@ -0,0 +1,52 @@
+//! `SeaORM` Entity for the session_records table.
+//! A coaching session's compiled record of engagement, stored as a PDF in object storage.
+
+use crate::session_record_status::SessionRecordStatus;
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::session_records::Model)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "session_records")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub coaching_session_id: Id,
+    pub status: SessionRecordStatus,
+    /// Internal only — where the PDF is kept in object storage. Never sent to clients.
+    #[serde(skip_serializing)]
+    pub storage_key: Option<String>,
+    /// Size of the stored PDF in bytes.
+    pub byte_size: Option<i64>,
+    /// Why compiling the record last failed.
+    pub error_message: Option<String>,
+    /// When the stored PDF was compiled.
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub generated_at: Option<DateTimeWithTimeZone>,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+    /// When the record was last requested or compiled.
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::coaching_sessions::Entity",
+        from = "Column::CoachingSessionId",
+        to = "super::coaching_sessions::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    CoachingSessions,
+}
+
+impl Related<super::coaching_sessions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CoachingSessions.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,34 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// Where a coachee's session request stands: waiting for the coach, or answered.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    Eq,
+    PartialEq,
+    EnumIter,
+    Deserialize,
+    Serialize,
+    DeriveActiveEnum,
+    ToSchema,
+)]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "Enum",
+    enum_name = "session_request_status"
+)]
+#[serde(rename_all = "snake_case")]
+#[schema(as = entity::session_request_status::SessionRequestStatus)]
+pub enum SessionRequestStatus {
+    #[default]
+    #[sea_orm(string_value = "pending")]
+    Pending,
+    #[sea_orm(string_value = "accepted")]
+    Accepted,
+    #[sea_orm(string_value = "declined")]
+    Declined,
+}
@@ -0,0 +1,127 @@
+//! `SeaORM` Entity for the session_requests table.
+//! A coachee's request for an ad-hoc session within a coaching relationship, answered by
+//! the coach accepting it (which schedules the session) or declining it with a reason.
+
+use crate::session_request_status::SessionRequestStatus;
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use sea_orm::FromJsonQueryResult;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A span of time the coachee could meet in.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::session_requests::TimeWindow)]
+pub struct TimeWindow {
+    #[schema(value_type = String, format = DateTime)]
+    pub starts_at: DateTimeWithTimeZone,
+    #[schema(value_type = String, format = DateTime)]
+    pub ends_at: DateTimeWithTimeZone,
+}
+
+/// The windows a request was made with, most preferred first, stored as a JSONB array.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+#[serde(transparent)]
+pub struct TimeWindows(pub Vec<TimeWindow>);
+
+/// A status a request entered, who moved it there and when.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::session_requests::StatusChange)]
+pub struct StatusChange {
+    pub status: SessionRequestStatus,
+    pub changed_by_user_id: Id,
+    /// The coach's reason, for a decline.
+    pub reason: Option<String>,
+    #[schema(value_type = String, format = DateTime)]
+    pub changed_at: DateTimeWithTimeZone,
+}
+
+/// Every status a request has been in, oldest first, stored as a JSONB array.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+#[serde(transparent)]
+pub struct StatusHistory(pub Vec<StatusChange>);
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::session_requests::Model)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "session_requests")]
+pub struct Model {
+    #[serde(skip_deserializing)]
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    #[serde(skip_deserializing)]
+    pub coaching_relationship_id: Id,
+    #[serde(skip_deserializing)]
+    pub requested_by_user_id: Id,
+    #[sea_orm(column_type = "JsonBinary")]
+    #[schema(value_type = Vec<TimeWindow>)]
+    pub preferred_windows: TimeWindows,
+    /// Length of session asked for; the coach's default when unset.
+    pub duration_minutes: Option<i16>,
+    /// What the coachee would like to talk about.
+    pub message: Option<String>,
+    #[serde(skip_deserializing)]
+    pub status: SessionRequestStatus,
+    #[serde(skip_deserializing)]
+    pub decline_reason: Option<String>,
+    /// The session scheduled by accepting the request.
+    #[serde(skip_deserializing)]
+    pub coaching_session_id: Option<Id>,
+    #[sea_orm(column_type = "JsonBinary")]
+    #[serde(skip_deserializing)]
+    #[schema(value_type = Vec<StatusChange>)]
+    pub status_history: StatusHistory,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::coaching_relationships::Entity",
+        from = "Column::CoachingRelationshipId",
+        to = "super::coaching_relationships::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    CoachingRelationships,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::RequestedByUserId",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Users,
+    #[sea_orm(
+        belongs_to = "super::coaching_sessions::Entity",
+        from = "Column::CoachingSessionId",
+        to = "super::coaching_sessions::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    CoachingSessions,
+}
+
+impl Related<super::coaching_relationships::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CoachingRelationships.def()
+    }
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Users.def()
+    }
+}
+
+impl Related<super::coaching_sessions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CoachingSessions.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,62 @@
+//! `SeaORM` Entity for the session_types table.
+//! A kind of coaching session an organization runs ("Intro call", "Review"), with the
+//! duration new sessions of that kind default to.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::session_types::Model)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "session_types")]
+pub struct Model {
+    #[serde(skip_deserializing)]
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    #[serde(skip_deserializing)]
+    pub organization_id: Id,
+    pub name: String,
+    /// Duration in minutes (1..=480) of sessions created with this type and no explicit
+    /// duration.
+    pub default_duration_minutes: i16,
+    /// Hex color (`#rrggbb`) the type is shown with; `None` for the default color.
+    pub color: Option<String>,
+    /// Whether sessions of this type are meant to be recorded.
+    #[serde(default)]
+    pub requires_recording: bool,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::organizations::Entity",
+        from = "Column::OrganizationId",
+        to = "super::organizations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Organizations,
+    #[sea_orm(has_many = "super::coaching_sessions::Entity")]
+    CoachingSessions,
+}
+
+impl Related<super::organizations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Organizations.def()
+    }
+}
+
+impl Related<super::coaching_sessions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CoachingSessions.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
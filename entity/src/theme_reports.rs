@@ -0,0 +1,76 @@
+//! `SeaORM` Entity for the theme_reports table.
+//! The latest AI-generated report of recurring themes across a coaching relationship's
+//! sessions: recurring challenges and progress arcs.
+
+use crate::Id;
+use chrono::NaiveDate;
+use sea_orm::entity::prelude::*;
+use sea_orm::FromJsonQueryResult;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A theme found across sessions and the dates of the sessions it came up in.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::theme_reports::Theme)]
+pub struct Theme {
+    pub title: String,
+    pub summary: String,
+    #[serde(default)]
+    #[schema(value_type = Vec<String>, format = Date)]
+    pub session_dates: Vec<NaiveDate>,
+}
+
+/// Themes stored as a JSONB array.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+#[serde(transparent)]
+pub struct Themes(pub Vec<Theme>);
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::theme_reports::Model)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "theme_reports")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub coaching_relationship_id: Id,
+    pub overview: Option<String>,
+    #[sea_orm(column_type = "JsonBinary")]
+    #[schema(value_type = Vec<entity::theme_reports::Theme>)]
+    pub recurring_challenges: Themes,
+    #[sea_orm(column_type = "JsonBinary")]
+    #[schema(value_type = Vec<entity::theme_reports::Theme>)]
+    pub progress_arcs: Themes,
+    /// Number of sessions the report was generated from.
+    pub session_count: i32,
+    /// Date of the most recent session the report covers.
+    #[schema(value_type = String, format = DateTime)]
+    pub covered_through: DateTime,
+    /// The model that generated this report.
+    pub model: String,
+    /// Version of the system prompt used (0 for the built-in one).
+    pub prompt_version: Option<i32>,
+    #[schema(value_type = String, format = DateTime)]
+    pub generated_at: DateTimeWithTimeZone,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::coaching_relationships::Entity",
+        from = "Column::CoachingRelationshipId",
+        to = "super::coaching_relationships::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    CoachingRelationships,
+}
+
+impl Related<super::coaching_relationships::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CoachingRelationships.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,45 @@
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A topical section of a transcript, as detected by the transcription provider.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize, ToSchema)]
+#[sea_orm(schema_name = "refactor_platform", table_name = "transcript_chapters")]
+pub struct Model {
+    #[serde(skip_deserializing)]
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    pub transcription_id: Id,
+    /// Short headline of the chapter.
+    pub title: String,
+    /// Paragraph summarizing the chapter.
+    pub summary: String,
+    /// A few words on what the chapter is about.
+    pub gist: String,
+    pub start_ms: i32,
+    pub end_ms: i32,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::transcription::Entity",
+        from = "Column::TranscriptionId",
+        to = "super::transcription::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Transcriptions,
+}
+
+impl Related<super::transcription::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Transcriptions.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
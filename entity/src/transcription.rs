@@ -1,5 +1,6 @@
 use crate::Id;
 use sea_orm::entity::prelude::*;
+use sea_orm::FromJsonQueryResult;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
@@ -33,6 +34,49 @@ pub enum TranscriptionStatus {
     Failed,
 }
 
+/// Conversation balance of a transcript, computed from its segments and stored as JSONB.
+#[derive(
+    Clone, Debug, Default, PartialEq, Serialize, Deserialize, FromJsonQueryResult, ToSchema,
+)]
+#[schema(as = entity::transcription::ConversationMetrics)]
+pub struct ConversationMetrics {
+    /// Time anyone was speaking.
+    pub total_talk_ms: i64,
+    /// Times a speaker started before the previous speaker had finished.
+    pub interruption_count: u32,
+    /// The longest uninterrupted run of one speaker, if anyone spoke.
+    pub longest_monologue: Option<Monologue>,
+    /// One entry per speaker, most talk time first.
+    pub speakers: Vec<SpeakerMetrics>,
+}
+
+/// How much one speaker talked, asked and interrupted.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::transcription::SpeakerMetrics)]
+pub struct SpeakerMetrics {
+    pub speaker_label: String,
+    pub talk_ms: i64,
+    /// Fraction of `total_talk_ms`, from 0 to 1.
+    pub talk_share: f64,
+    pub segment_count: u32,
+    /// Sentences ending in a question mark.
+    pub question_count: u32,
+    /// Fraction of the speaker's sentences that are questions, from 0 to 1.
+    pub question_ratio: f64,
+    /// Times this speaker started before another had finished.
+    pub interruption_count: u32,
+    pub longest_monologue_ms: i64,
+}
+
+/// An uninterrupted run of consecutive segments by one speaker.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::transcription::Monologue)]
+pub struct Monologue {
+    pub speaker_label: String,
+    pub start_ms: i32,
+    pub duration_ms: i64,
+}
+
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, ToSchema)]
 #[sea_orm(schema_name = "refactor_platform", table_name = "transcriptions")]
 pub struct Model {
@@ -54,6 +98,11 @@ pub struct Model {
     pub duration_seconds: Option<i32>,
     pub confidence: Option<f64>,
     pub error_message: Option<String>,
+    /// Talk time, interruptions and questions per speaker; set once the transcript is
+    /// stored.
+    #[serde(skip_deserializing)]
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub conversation_metrics: Option<ConversationMetrics>,
     #[serde(skip_deserializing)]
     #[schema(value_type = String, format = DateTime)]
     pub created_at: DateTimeWithTimeZone,
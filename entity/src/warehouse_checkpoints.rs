@@ -0,0 +1,46 @@
+//! `SeaORM` Entity for the warehouse_checkpoints table.
+//! How far a stream of domain events exported to the analytics warehouse has got.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::warehouse_checkpoints::Model)]
+#[sea_orm(
+    schema_name = "refactor_platform",
+    table_name = "warehouse_checkpoints"
+)]
+pub struct Model {
+    /// `live` for events exported as they're published, `backfill` for events re-exported
+    /// from the event store.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub stream: String,
+    /// When the last exported stored event was created; backfill only.
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub position_at: Option<DateTimeWithTimeZone>,
+    /// ID of the last exported stored event; backfill only.
+    pub position_id: Option<Id>,
+    /// Stored events created from this time on aren't backfilled; backfill only.
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub until_at: Option<DateTimeWithTimeZone>,
+    pub events_exported: i64,
+    pub batches_exported: i64,
+    /// Object the last batch was written to.
+    pub last_object_key: Option<String>,
+    /// Why the last export failed, cleared by the next one that succeeds.
+    pub error_message: Option<String>,
+    /// When the backfill exported its last event.
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub completed_at: Option<DateTimeWithTimeZone>,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
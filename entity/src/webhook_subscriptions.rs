@@ -0,0 +1,85 @@
+//! `SeaORM` Entity for the webhook_subscriptions table.
+//! An organization's outbound webhook: a URL its domain events are POSTed to, signed
+//! with a secret only the server and the receiver know.
+
+use crate::Id;
+use sea_orm::entity::prelude::*;
+use sea_orm::FromJsonQueryResult;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Names of the event types delivered, e.g. `goal_created`, stored as a JSONB array.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+#[serde(transparent)]
+pub struct EventTypes(pub Vec<String>);
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = entity::webhook_subscriptions::Model)]
+#[sea_orm(
+    schema_name = "refactor_platform",
+    table_name = "webhook_subscriptions"
+)]
+pub struct Model {
+    #[serde(skip_deserializing)]
+    #[sea_orm(primary_key)]
+    pub id: Id,
+    #[serde(skip_deserializing)]
+    pub organization_id: Id,
+    /// HTTPS endpoint events are POSTed to.
+    pub url: String,
+    /// Key the payloads are signed with; generated on creation and only returned then.
+    #[serde(skip)]
+    pub secret: String,
+    #[sea_orm(column_type = "JsonBinary")]
+    #[schema(value_type = Vec<String>)]
+    pub event_types: EventTypes,
+    /// Whether events are delivered; a disabled subscription keeps its settings.
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    #[serde(skip_deserializing)]
+    pub created_by_user_id: Option<Id>,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTimeWithTimeZone,
+    #[serde(skip_deserializing)]
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::organizations::Entity",
+        from = "Column::OrganizationId",
+        to = "super::organizations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Organizations,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::CreatedByUserId",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    Users,
+}
+
+impl Related<super::organizations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Organizations.def()
+    }
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Users.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -3,7 +3,8 @@ use std::collections::{HashMap, HashSet};
 use sea_orm::{
     entity::prelude::*,
     ActiveValue::{Set, Unchanged},
-    DatabaseConnection, JoinType, Order, QueryOrder, QuerySelect, TryIntoModel,
+    DatabaseConnection, DatabaseTransaction, JoinType, Order, QueryOrder, QuerySelect,
+    TransactionTrait, TryIntoModel,
 };
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -11,9 +12,17 @@ use utoipa::ToSchema;
 use log::*;
 
 use super::actions_user;
+use super::coaching_session::{batch_load_relationships, batch_load_sessions, batch_load_users};
 use super::error::{EntityApiErrorKind, Error};
-use entity::actions::{ActiveModel, Entity, Model};
-use entity::{actions, coaching_relationships, coaching_sessions, status::Status, Id};
+use super::mutate::{update_if_version, ExpectedVersion};
+use super::resource_view::AnnotatedAction;
+use entity::actions::{ActiveModel, Column, Entity, Model};
+use entity::duration::OutOfRange;
+use entity::{actions, coaching_relationships, coaching_sessions, status::Status, users, Id};
+
+/// Range an action's `effort_points` must fall in.
+pub const MIN_EFFORT_POINTS: i16 = 1;
+pub const MAX_EFFORT_POINTS: i16 = 100;
 
 /// An action with its associated assignee user IDs.
 ///
@@ -45,11 +54,12 @@ impl ActionWithAssignees {
 }
 
 pub async fn create(
-    db: &DatabaseConnection,
+    db: &impl ConnectionTrait,
     action_model: Model,
     user_id: Id,
 ) -> Result<Model, Error> {
     debug!("New Action Model to be inserted: {action_model:?}");
+    check_effort_points(action_model.effort_points)?;
 
     let now = chrono::Utc::now();
 
@@ -57,7 +67,10 @@ pub async fn create(
         coaching_session_id: Set(action_model.coaching_session_id),
         goal_id: Set(action_model.goal_id),
         user_id: Set(user_id),
+        action_series_id: Set(action_model.action_series_id),
+        previous_action_id: Set(action_model.previous_action_id),
         body: Set(action_model.body),
+        effort_points: Set(action_model.effort_points),
         status: Set(action_model.status),
         due_by: Set(action_model.due_by),
         status_changed_at: Set(now.into()),
@@ -69,8 +82,18 @@ pub async fn create(
     Ok(action_active_model.save(db).await?.try_into_model()?)
 }
 
-pub async fn update(db: &DatabaseConnection, id: Id, model: Model) -> Result<Model, Error> {
-    let result = Entity::find_by_id(id).one(db).await?;
+/// Updates an action, provided it's still at a version `expected` names.
+pub async fn update(
+    db: &impl ConnectionTrait,
+    id: Id,
+    model: Model,
+    expected: &ExpectedVersion,
+) -> Result<Model, Error> {
+    check_effort_points(model.effort_points)?;
+    let result = Entity::find_by_id(id)
+        .filter(Column::DeletedAt.is_null())
+        .one(db)
+        .await?;
 
     match result {
         Some(action) => {
@@ -81,15 +104,19 @@ pub async fn update(db: &DatabaseConnection, id: Id, model: Model) -> Result<Mod
                 coaching_session_id: Unchanged(action.coaching_session_id),
                 goal_id: Set(model.goal_id),
                 user_id: Unchanged(model.user_id),
+                action_series_id: Unchanged(action.action_series_id),
+                previous_action_id: Unchanged(action.previous_action_id),
                 body: Set(model.body),
+                effort_points: Set(model.effort_points),
                 due_by: Set(model.due_by),
                 status: Set(model.status),
                 status_changed_at: Set(chrono::Utc::now().into()),
                 updated_at: Set(chrono::Utc::now().into()),
                 created_at: Unchanged(action.created_at),
+                deleted_at: Unchanged(action.deleted_at),
             };
 
-            Ok(active_model.update(db).await?.try_into_model()?)
+            update_if_version(db, active_model, Column::UpdatedAt, expected).await
         }
         None => {
             error!("Action with id {id} not found");
@@ -102,12 +129,29 @@ pub async fn update(db: &DatabaseConnection, id: Id, model: Model) -> Result<Mod
     }
 }
 
+fn check_effort_points(effort_points: Option<i16>) -> Result<(), Error> {
+    match effort_points {
+        Some(got) if !(MIN_EFFORT_POINTS..=MAX_EFFORT_POINTS).contains(&got) => Err(OutOfRange {
+            got,
+            min: MIN_EFFORT_POINTS,
+            max: MAX_EFFORT_POINTS,
+        }
+        .into()),
+        _ => Ok(()),
+    }
+}
+
+/// Sets an action's status, provided it's still at a version `expected` names.
 pub async fn update_status(
-    db: &DatabaseConnection,
+    db: &impl ConnectionTrait,
     id: Id,
     status: Status,
+    expected: &ExpectedVersion,
 ) -> Result<Model, Error> {
-    let result = Entity::find_by_id(id).one(db).await?;
+    let result = Entity::find_by_id(id)
+        .filter(Column::DeletedAt.is_null())
+        .one(db)
+        .await?;
 
     match result {
         Some(action) => {
@@ -118,15 +162,19 @@ pub async fn update_status(
                 coaching_session_id: Unchanged(action.coaching_session_id),
                 goal_id: Unchanged(action.goal_id),
                 user_id: Unchanged(action.user_id),
+                action_series_id: Unchanged(action.action_series_id),
+                previous_action_id: Unchanged(action.previous_action_id),
                 body: Unchanged(action.body),
+                effort_points: Unchanged(action.effort_points),
                 due_by: Unchanged(action.due_by),
                 status: Set(status),
                 status_changed_at: Set(chrono::Utc::now().into()),
                 updated_at: Set(chrono::Utc::now().into()),
                 created_at: Unchanged(action.created_at),
+                deleted_at: Unchanged(action.deleted_at),
             };
 
-            Ok(active_model.update(db).await?.try_into_model()?)
+            update_if_version(db, active_model, Column::UpdatedAt, expected).await
         }
         None => {
             error!("Action with id {id} not found");
@@ -139,19 +187,63 @@ pub async fn update_status(
     }
 }
 
-pub async fn delete_by_id(db: &DatabaseConnection, id: Id) -> Result<(), Error> {
+/// Soft-deletes an action: it's left out of reads until restored, or purged once past the
+/// retention window.
+pub async fn delete_by_id(db: &impl ConnectionTrait, id: Id) -> Result<(), Error> {
     let result = find_by_id(db, id).await?;
 
-    result.delete(db).await?;
+    soft_delete(db, result).await?;
 
     Ok(())
 }
 
-pub async fn find_by_id(db: &DatabaseConnection, id: Id) -> Result<Model, Error> {
-    Entity::find_by_id(id).one(db).await?.ok_or_else(|| Error {
-        source: None,
-        error_kind: EntityApiErrorKind::RecordNotFound,
-    })
+async fn soft_delete(db: &impl ConnectionTrait, action: Model) -> Result<Model, Error> {
+    let mut active_model: ActiveModel = action.into();
+    active_model.deleted_at = Set(Some(chrono::Utc::now().into()));
+    Ok(active_model.update(db).await?.try_into_model()?)
+}
+
+/// Restores a soft-deleted action.
+pub async fn restore(db: &impl ConnectionTrait, id: Id) -> Result<Model, Error> {
+    let action = find_deleted_by_id(db, id).await?;
+    let mut active_model: ActiveModel = action.into();
+    active_model.deleted_at = Set(None);
+    Ok(active_model.update(db).await?.try_into_model()?)
+}
+
+/// Permanently deletes the actions soft-deleted before `cutoff`, returning how many.
+pub async fn purge_deleted_before(
+    db: &impl ConnectionTrait,
+    cutoff: DateTimeWithTimeZone,
+) -> Result<u64, Error> {
+    let result = Entity::delete_many()
+        .filter(Column::DeletedAt.lt(cutoff))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected)
+}
+
+pub async fn find_by_id(db: &impl ConnectionTrait, id: Id) -> Result<Model, Error> {
+    Entity::find_by_id(id)
+        .filter(Column::DeletedAt.is_null())
+        .one(db)
+        .await?
+        .ok_or_else(|| Error {
+            source: None,
+            error_kind: EntityApiErrorKind::RecordNotFound,
+        })
+}
+
+/// Like [`find_by_id`], but finds the action only while it's soft-deleted.
+pub async fn find_deleted_by_id(db: &impl ConnectionTrait, id: Id) -> Result<Model, Error> {
+    Entity::find_by_id(id)
+        .filter(Column::DeletedAt.is_not_null())
+        .one(db)
+        .await?
+        .ok_or_else(|| Error {
+            source: None,
+            error_kind: EntityApiErrorKind::RecordNotFound,
+        })
 }
 
 /// Creates a new action with optional assignees.
@@ -167,7 +259,7 @@ pub async fn find_by_id(db: &DatabaseConnection, id: Id) -> Result<Model, Error>
 ///
 /// Returns `Error` if the database operation fails.
 pub async fn create_with_assignees(
-    db: &DatabaseConnection,
+    db: &(impl ConnectionTrait + TransactionTrait),
     action_model: Model,
     user_id: Id,
     assignee_ids: Option<Vec<Id>>,
@@ -200,17 +292,20 @@ pub async fn create_with_assignees(
 /// * `assignee_ids` - Optional list of user IDs to set as assignees.
 ///   If `Some`, replaces existing assignees.
 ///   If `None`, assignees remain unchanged.
+/// * `expected` - The versions of the action the update may replace
 ///
 /// # Errors
 ///
-/// Returns `Error` if the action is not found or database operation fails.
+/// Returns `Error` if the action is not found, has changed since the version `expected`
+/// names, or database operation fails.
 pub async fn update_with_assignees(
-    db: &DatabaseConnection,
+    db: &(impl ConnectionTrait + TransactionTrait),
     id: Id,
     model: Model,
     assignee_ids: Option<Vec<Id>>,
+    expected: &ExpectedVersion,
 ) -> Result<ActionWithAssignees, Error> {
-    let action = update(db, id, model).await?;
+    let action = update(db, id, model, expected).await?;
 
     let assignee_ids = if let Some(ids) = assignee_ids {
         let assignments = actions_user::set_assignees(db, action.id, ids).await?;
@@ -246,6 +341,308 @@ pub async fn find_by_id_with_assignees(
     })
 }
 
+/// An annotated action with the related resources requested via `?include=`, batch-loaded
+/// for the whole list the way [`super::coaching_session::EnrichedSession`] is.
+///
+/// The action's fields stay at the JSON root; a related resource only appears when it was
+/// requested, so a request without `include` gets the same shape as before.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[schema(as = domain::action::EnrichedAction)]
+pub struct EnrichedAction {
+    #[serde(flatten)]
+    pub action: AnnotatedAction,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session: Option<coaching_sessions::Model>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relationship: Option<coaching_relationships::Model>,
+    /// The users the action is assigned to, in `assignee_ids` order
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignees: Option<Vec<users::Model>>,
+}
+
+/// Which related resources to batch-load alongside a list of actions.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IncludeOptions {
+    pub session: bool,
+    pub relationship: bool,
+    pub assignee: bool,
+}
+
+impl IncludeOptions {
+    /// An action reaches its coaching relationship through its session, so `relationship`
+    /// needs the sessions loaded as well.
+    fn needs_sessions(&self) -> bool {
+        self.session || self.relationship
+    }
+}
+
+/// Attaches the requested related resources to `actions` with one `IN` query per kind of
+/// resource, however many actions there are. Runs no queries when nothing is requested.
+pub async fn with_includes(
+    db: &impl ConnectionTrait,
+    actions: Vec<AnnotatedAction>,
+    includes: IncludeOptions,
+) -> Result<Vec<EnrichedAction>, Error> {
+    let mut sessions = HashMap::new();
+    if includes.needs_sessions() {
+        let session_ids: Vec<Id> = actions
+            .iter()
+            .map(|a| a.resource.action.coaching_session_id)
+            .collect();
+        sessions = batch_load_sessions(db, &session_ids).await?;
+    }
+
+    let mut relationships = HashMap::new();
+    if includes.relationship {
+        let relationship_ids: Vec<Id> = sessions
+            .values()
+            .map(|s| s.coaching_relationship_id)
+            .collect();
+        relationships = batch_load_relationships(db, &relationship_ids).await?;
+    }
+
+    let mut assignees = HashMap::new();
+    if includes.assignee {
+        let user_ids: Vec<Id> = actions
+            .iter()
+            .flat_map(|a| a.resource.assignee_ids.iter().copied())
+            .collect();
+        assignees = batch_load_users(db, &user_ids).await?;
+    }
+
+    Ok(actions
+        .into_iter()
+        .map(|action| {
+            let session = sessions.get(&action.resource.action.coaching_session_id);
+            EnrichedAction {
+                session: session.filter(|_| includes.session).cloned(),
+                relationship: session
+                    .and_then(|s| relationships.get(&s.coaching_relationship_id))
+                    .cloned(),
+                assignees: includes.assignee.then(|| {
+                    action
+                        .resource
+                        .assignee_ids
+                        .iter()
+                        .filter_map(|id| assignees.get(id).cloned())
+                        .collect()
+                }),
+                action,
+            }
+        })
+        .collect())
+}
+
+/// Finds the live actions among `ids`, with their assignee IDs and the coaching
+/// relationship of each one's session, so callers can authorize them one by one.
+/// Actions whose session was deleted are left out like deleted actions.
+///
+/// # Errors
+///
+/// Returns `Error` if a database query fails.
+pub async fn find_by_ids_with_coaching_relationship(
+    db: &DatabaseConnection,
+    ids: &[Id],
+) -> Result<Vec<(ActionWithAssignees, coaching_relationships::Model)>, Error> {
+    if ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let rows = Entity::find()
+        .join(
+            JoinType::InnerJoin,
+            actions::Relation::CoachingSessions.def(),
+        )
+        .join(
+            JoinType::InnerJoin,
+            coaching_sessions::Relation::CoachingRelationships.def(),
+        )
+        .select_also(coaching_relationships::Entity)
+        .filter(Column::Id.is_in(ids.iter().copied()))
+        .filter(Column::DeletedAt.is_null())
+        .filter(coaching_sessions::Column::DeletedAt.is_null())
+        .all(db)
+        .await?;
+
+    let action_ids = rows.iter().map(|(action, _)| action.id).collect();
+    let mut assignees = actions_user::find_assignees_for_actions(db, action_ids).await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(action, relationship)| {
+            let assignee_ids = assignees.remove(&action.id).unwrap_or_default();
+            relationship.map(|relationship| {
+                (
+                    ActionWithAssignees {
+                        action,
+                        assignee_ids,
+                    },
+                    relationship,
+                )
+            })
+        })
+        .collect())
+}
+
+/// One change applied by [`bulk`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BulkOperation {
+    /// Creates an action, as [`create_with_assignees`] does, owned by the caller.
+    Create {
+        action: Model,
+        assignee_ids: Option<Vec<Id>>,
+    },
+    /// Updates an action, as [`update_with_assignees`] does.
+    Update {
+        id: Id,
+        action: Model,
+        assignee_ids: Option<Vec<Id>>,
+    },
+    /// Changes only an action's status.
+    UpdateStatus { id: Id, status: Status },
+    /// Deletes an action.
+    Delete { id: Id },
+}
+
+/// What one [`BulkOperation`] did.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BulkOutcome {
+    Created(ActionWithAssignees),
+    /// An updated action, or one whose status changed, with the assignees it had before.
+    Updated {
+        action: ActionWithAssignees,
+        previous_assignee_ids: Vec<Id>,
+    },
+    /// The action as it was before it was deleted.
+    Deleted(Model),
+}
+
+/// Applies `operations` on behalf of `user_id` in a single transaction, returning the
+/// outcome of each in order.
+///
+/// Each operation runs in its own savepoint, so one that fails doesn't stop the rest from
+/// being tried and reported. The transaction commits only when all of them succeed; if
+/// any fails, it rolls back and none take effect. An operation fails with
+/// `RecordUnauthenticated` when the user is neither the coach nor the coachee of the
+/// action's coaching session.
+///
+/// # Errors
+///
+/// Returns `Error` if the transaction itself can't be started or committed.
+pub async fn bulk(
+    db: &DatabaseConnection,
+    user_id: Id,
+    operations: Vec<BulkOperation>,
+) -> Result<Vec<Result<BulkOutcome, Error>>, Error> {
+    let txn = db.begin().await?;
+
+    let mut outcomes = Vec::with_capacity(operations.len());
+    for operation in operations {
+        let savepoint = txn.begin().await?;
+        let outcome = apply(&savepoint, user_id, operation).await;
+        if outcome.is_ok() {
+            savepoint.commit().await?;
+        } else {
+            savepoint.rollback().await?;
+        }
+        outcomes.push(outcome);
+    }
+
+    if outcomes.iter().all(Result::is_ok) {
+        txn.commit().await?;
+    } else {
+        debug!("Rolling back bulk action operations, at least one failed");
+        txn.rollback().await?;
+    }
+
+    Ok(outcomes)
+}
+
+async fn apply(
+    db: &DatabaseTransaction,
+    user_id: Id,
+    operation: BulkOperation,
+) -> Result<BulkOutcome, Error> {
+    match operation {
+        BulkOperation::Create {
+            action,
+            assignee_ids,
+        } => {
+            authorize_participant(db, user_id, action.coaching_session_id).await?;
+            let action = create_with_assignees(db, action, user_id, assignee_ids).await?;
+            Ok(BulkOutcome::Created(action))
+        }
+        BulkOperation::Update {
+            id,
+            action,
+            assignee_ids,
+        } => {
+            let existing = find_by_id(db, id).await?;
+            authorize_participant(db, user_id, existing.coaching_session_id).await?;
+            let previous_assignee_ids = actions_user::find_user_ids_by_action_id(db, id).await?;
+            let action =
+                update_with_assignees(db, id, action, assignee_ids, &ExpectedVersion::Any).await?;
+            Ok(BulkOutcome::Updated {
+                action,
+                previous_assignee_ids,
+            })
+        }
+        BulkOperation::UpdateStatus { id, status } => {
+            let existing = find_by_id(db, id).await?;
+            authorize_participant(db, user_id, existing.coaching_session_id).await?;
+            let action = update_status(db, id, status, &ExpectedVersion::Any).await?;
+            let assignee_ids = actions_user::find_user_ids_by_action_id(db, id).await?;
+            Ok(BulkOutcome::Updated {
+                action: ActionWithAssignees {
+                    action,
+                    assignee_ids: assignee_ids.clone(),
+                },
+                previous_assignee_ids: assignee_ids,
+            })
+        }
+        BulkOperation::Delete { id } => {
+            let existing = find_by_id(db, id).await?;
+            authorize_participant(db, user_id, existing.coaching_session_id).await?;
+            soft_delete(db, existing.clone()).await?;
+            Ok(BulkOutcome::Deleted(existing))
+        }
+    }
+}
+
+/// Fails unless `user_id` is the coach or the coachee of the relationship that the
+/// coaching session `coaching_session_id` belongs to.
+async fn authorize_participant(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+    coaching_session_id: Id,
+) -> Result<(), Error> {
+    let relationship = coaching_relationships::Entity::find()
+        .join(
+            JoinType::InnerJoin,
+            coaching_sessions::Relation::CoachingRelationships
+                .def()
+                .rev(),
+        )
+        .filter(coaching_sessions::Column::Id.eq(coaching_session_id))
+        .filter(coaching_sessions::Column::DeletedAt.is_null())
+        .one(db)
+        .await?
+        .ok_or(Error {
+            source: None,
+            error_kind: EntityApiErrorKind::RecordNotFound,
+        })?;
+
+    if relationship.coach_id == user_id || relationship.coachee_id == user_id {
+        Ok(())
+    } else {
+        warn!("User {user_id} isn't a participant of coaching session {coaching_session_id}");
+        Err(Error {
+            source: None,
+            error_kind: EntityApiErrorKind::RecordUnauthenticated,
+        })
+    }
+}
+
 /// Filter for querying actions by assignee status.
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub enum AssigneeFilter {
@@ -292,8 +689,8 @@ pub struct FindByUserParams {
 /// Optional filters narrow results further: `coaching_session_id`, `coaching_relationship_id`,
 /// `status`, and `assignee_filter`. The `coaching_relationship_id` filter uses the
 /// `coaching_sessions.coaching_relationship_id` FK column, so it requires a join to
-/// `coaching_sessions` (not `coaching_relationships`). For `Scope::Sessions` this join
-/// already exists in the base query; for `Scope::Assigned` it is added dynamically.
+/// `coaching_sessions` (not `coaching_relationships`), which the base query of either scope
+/// has. Soft-deleted actions, and the actions of soft-deleted sessions, are left out.
 pub async fn find_by_user(
     db: &DatabaseConnection,
     user_id: Id,
@@ -304,9 +701,6 @@ pub async fn find_by_user(
         params.scope, params.coaching_session_id, params.coaching_relationship_id, params.status, params.assignee_filter
     );
 
-    // Track whether the base query already joins coaching_sessions (Sessions does, Assigned does not)
-    let needs_sessions_join = matches!(params.scope, Scope::Assigned);
-
     // Build base query based on scope
     let base_select = match params.scope {
         Scope::Assigned => {
@@ -316,7 +710,12 @@ pub async fn find_by_user(
                 return Ok(vec![]);
             }
 
-            actions::Entity::find().filter(actions::Column::Id.is_in(action_ids))
+            actions::Entity::find()
+                .join(
+                    JoinType::InnerJoin,
+                    actions::Relation::CoachingSessions.def(),
+                )
+                .filter(actions::Column::Id.is_in(action_ids))
         }
         Scope::Sessions => actions::Entity::find()
             .join(
@@ -335,19 +734,15 @@ pub async fn find_by_user(
     };
 
     // Apply filters
-    let mut select = base_select;
+    let mut select = base_select
+        .filter(actions::Column::DeletedAt.is_null())
+        .filter(coaching_sessions::Column::DeletedAt.is_null());
 
     if let Some(session_id) = params.coaching_session_id {
         select = select.filter(actions::Column::CoachingSessionId.eq(session_id));
     }
 
     if let Some(relationship_id) = params.coaching_relationship_id {
-        if needs_sessions_join {
-            select = select.join(
-                JoinType::InnerJoin,
-                actions::Relation::CoachingSessions.def(),
-            );
-        }
         select =
             select.filter(coaching_sessions::Column::CoachingRelationshipId.eq(relationship_id));
     }
@@ -486,7 +881,9 @@ pub async fn find_by_coaching_relationship(
             JoinType::InnerJoin,
             actions::Relation::CoachingSessions.def(),
         )
-        .filter(coaching_sessions::Column::CoachingRelationshipId.eq(relationship_id));
+        .filter(coaching_sessions::Column::CoachingRelationshipId.eq(relationship_id))
+        .filter(coaching_sessions::Column::DeletedAt.is_null())
+        .filter(actions::Column::DeletedAt.is_null());
 
     let select = match &params.status {
         Some(status) => select.filter(actions::Column::Status.eq(status.clone())),
@@ -605,14 +1002,18 @@ mod tests {
         let action_model = Model {
             id: Id::new_v4(),
             user_id: Id::new_v4(),
+            action_series_id: None,
+            previous_action_id: None,
             coaching_session_id: Id::new_v4(),
             goal_id: None,
             body: Some("This is a action".to_owned()),
+            effort_points: None,
             due_by: Some(now.into()),
             status_changed_at: now.into(),
             status: Default::default(),
             created_at: now.into(),
             updated_at: now.into(),
+            deleted_at: None,
         };
 
         let db = MockDatabase::new(DatabaseBackend::Postgres)
@@ -636,18 +1037,28 @@ mod tests {
             goal_id: None,
             due_by: Some(now.into()),
             body: Some("This is a action".to_owned()),
+            effort_points: None,
             user_id: Id::new_v4(),
+            action_series_id: None,
+            previous_action_id: None,
             status_changed_at: now.into(),
             status: Default::default(),
             created_at: now.into(),
             updated_at: now.into(),
+            deleted_at: None,
         };
 
         let db = MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results(vec![vec![action_model.clone()], vec![action_model.clone()]])
             .into_connection();
 
-        let action = update(&db, action_model.id, action_model.clone()).await?;
+        let action = update(
+            &db,
+            action_model.id,
+            action_model.clone(),
+            &ExpectedVersion::Any,
+        )
+        .await?;
 
         assert_eq!(action.body, action_model.body);
 
@@ -664,11 +1075,15 @@ mod tests {
             goal_id: None,
             due_by: Some(now.into()),
             body: Some("This is a action".to_owned()),
+            effort_points: None,
             user_id: Id::new_v4(),
+            action_series_id: None,
+            previous_action_id: None,
             status_changed_at: now.into(),
             status: Default::default(),
             created_at: now.into(),
             updated_at: now.into(),
+            deleted_at: None,
         };
 
         let updated_action_model = Model {
@@ -677,11 +1092,15 @@ mod tests {
             goal_id: None,
             due_by: Some(now.into()),
             body: Some("This is a action".to_owned()),
+            effort_points: None,
             user_id: Id::new_v4(),
+            action_series_id: None,
+            previous_action_id: None,
             status_changed_at: now.into(),
             status: Status::Completed,
             created_at: now.into(),
             updated_at: now.into(),
+            deleted_at: None,
         };
 
         let db = MockDatabase::new(DatabaseBackend::Postgres)
@@ -691,7 +1110,13 @@ mod tests {
             ])
             .into_connection();
 
-        let action = update_status(&db, action_model.id, Status::Completed).await?;
+        let action = update_status(
+            &db,
+            action_model.id,
+            Status::Completed,
+            &ExpectedVersion::Any,
+        )
+        .await?;
 
         assert_eq!(action.status, Status::Completed);
 
@@ -702,7 +1127,8 @@ mod tests {
     async fn update_status_returns_error_when_action_not_found() -> Result<(), Error> {
         let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
 
-        let result = update_status(&db, Id::new_v4(), Status::Completed).await;
+        let result =
+            update_status(&db, Id::new_v4(), Status::Completed, &ExpectedVersion::Any).await;
 
         assert!(result.is_err());
 
@@ -719,14 +1145,18 @@ mod tests {
         let action_model = Model {
             id: action_id,
             user_id: Id::new_v4(),
+            action_series_id: None,
+            previous_action_id: None,
             coaching_session_id: Id::new_v4(),
             goal_id: None,
             body: Some("Assigned action".to_owned()),
+            effort_points: None,
             due_by: Some(now.into()),
             status_changed_at: now.into(),
             status: Default::default(),
             created_at: now.into(),
             updated_at: now.into(),
+            deleted_at: None,
         };
 
         // Mock: 1) actions_user query returns action IDs, 2) actions query, 3) assignee lookup
@@ -802,14 +1232,18 @@ mod tests {
         let action_model = Model {
             id: action_id,
             user_id: Id::new_v4(),
+            action_series_id: None,
+            previous_action_id: None,
             coaching_session_id: Id::new_v4(),
             goal_id: None,
             body: Some("Session action".to_owned()),
+            effort_points: None,
             due_by: Some(now.into()),
             status_changed_at: now.into(),
             status: Default::default(),
             created_at: now.into(),
             updated_at: now.into(),
+            deleted_at: None,
         };
 
         // Mock: 1) actions join query, 2) assignee lookup for each action
@@ -851,14 +1285,18 @@ mod tests {
         let action_model = Model {
             id: action_id,
             user_id: Id::new_v4(),
+            action_series_id: None,
+            previous_action_id: None,
             coaching_session_id: Id::new_v4(),
             goal_id: None,
             body: Some("Action with assignee".to_owned()),
+            effort_points: None,
             due_by: Some(now.into()),
             status_changed_at: now.into(),
             status: Default::default(),
             created_at: now.into(),
             updated_at: now.into(),
+            deleted_at: None,
         };
 
         // Mock: action has an assignee, so should be filtered out
@@ -900,14 +1338,18 @@ mod tests {
         let action_model = Model {
             id: action_id,
             user_id: Id::new_v4(),
+            action_series_id: None,
+            previous_action_id: None,
             coaching_session_id: Id::new_v4(),
             goal_id: None,
             body: Some("Action without assignee".to_owned()),
+            effort_points: None,
             due_by: Some(now.into()),
             status_changed_at: now.into(),
             status: Default::default(),
             created_at: now.into(),
             updated_at: now.into(),
+            deleted_at: None,
         };
 
         // Mock: action has no assignees, so should be filtered out
@@ -1074,14 +1516,18 @@ mod tests {
             action: Model {
                 id: Id::new_v4(),
                 user_id: Id::new_v4(),
+                action_series_id: None,
+                previous_action_id: None,
                 coaching_session_id: Id::new_v4(),
                 goal_id: None,
                 body: None,
+                effort_points: None,
                 due_by: None,
                 status_changed_at: now.into(),
                 status: Default::default(),
                 created_at: now.into(),
                 updated_at: now.into(),
+                deleted_at: None,
             },
             assignee_ids,
         }
@@ -1169,6 +1615,8 @@ mod tests {
             coach_id,
             coachee_id,
             slug: format!("test-slug-{}", relationship_id),
+            ai_privacy_level: Default::default(),
+            pro_bono: false,
             created_at: now,
             updated_at: now,
         }
@@ -1180,14 +1628,18 @@ mod tests {
         Model {
             id: action_id,
             user_id: Id::new_v4(),
+            action_series_id: None,
+            previous_action_id: None,
             coaching_session_id: session_id,
             goal_id: None,
             body: Some(format!("Action {action_id}")),
+            effort_points: None,
             due_by: Some(now.into()),
             status_changed_at: now.into(),
             status: Default::default(),
             created_at: now.into(),
             updated_at: now.into(),
+            deleted_at: None,
         }
     }
 
@@ -1833,4 +2285,148 @@ mod tests {
 
         Ok(())
     }
+
+    // ─── Bulk Operation Tests ─────────────────────────────────────────
+
+    /// Tests that every operation is reported, and that a failing one rolls back the
+    /// whole batch.
+    #[tokio::test]
+    async fn bulk_reports_each_operation_and_rolls_back_when_one_fails() -> Result<(), Error> {
+        let coach_id = Id::new_v4();
+        let session_id = Id::new_v4();
+        let action = create_test_action(Id::new_v4(), session_id);
+        let completed = Model {
+            status: Status::Completed,
+            ..action.clone()
+        };
+        let relationship =
+            create_test_relationship(Id::new_v4(), coach_id, Id::new_v4(), Id::new_v4());
+
+        // Mock: the status change's 1) action lookup, 2) relationship, 3) action re-read,
+        // 4) update, 5) assignees, then the delete's 6) action lookup, which finds nothing
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![action.clone()]])
+            .append_query_results(vec![vec![relationship]])
+            .append_query_results(vec![vec![action.clone()]])
+            .append_query_results(vec![vec![completed.clone()]])
+            .append_query_results(vec![Vec::<entity::actions_users::Model>::new()])
+            .append_query_results(vec![Vec::<Model>::new()])
+            .into_connection();
+
+        let outcomes = bulk(
+            &db,
+            coach_id,
+            vec![
+                BulkOperation::UpdateStatus {
+                    id: action.id,
+                    status: Status::Completed,
+                },
+                BulkOperation::Delete { id: Id::new_v4() },
+            ],
+        )
+        .await?;
+
+        assert_eq!(
+            outcomes,
+            vec![
+                Ok(BulkOutcome::Updated {
+                    action: ActionWithAssignees {
+                        action: completed,
+                        assignee_ids: vec![],
+                    },
+                    previous_assignee_ids: vec![],
+                }),
+                Err(Error {
+                    source: None,
+                    error_kind: EntityApiErrorKind::RecordNotFound,
+                }),
+            ]
+        );
+        let log = format!("{:?}", db.into_transaction_log());
+        assert!(log.contains("ROLLBACK"), "{log}");
+
+        Ok(())
+    }
+
+    /// Tests that an action can't be created in a session the caller takes no part in.
+    #[tokio::test]
+    async fn bulk_refuses_actions_outside_the_callers_sessions() -> Result<(), Error> {
+        let session_id = Id::new_v4();
+        let relationship =
+            create_test_relationship(Id::new_v4(), Id::new_v4(), Id::new_v4(), Id::new_v4());
+
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![relationship]])
+            .into_connection();
+
+        let outcomes = bulk(
+            &db,
+            Id::new_v4(),
+            vec![BulkOperation::Create {
+                action: create_test_action(Id::new_v4(), session_id),
+                assignee_ids: None,
+            }],
+        )
+        .await?;
+
+        assert_eq!(
+            outcomes,
+            vec![Err(Error {
+                source: None,
+                error_kind: EntityApiErrorKind::RecordUnauthenticated,
+            })]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn with_includes_reaches_the_relationship_through_the_session() -> Result<(), Error> {
+        let now = chrono::Utc::now();
+        let relationship =
+            create_test_relationship(Id::new_v4(), Id::new_v4(), Id::new_v4(), Id::new_v4());
+        let session = entity::coaching_sessions::Model {
+            id: Id::new_v4(),
+            coaching_relationship_id: relationship.id,
+            coaching_session_series_id: None,
+            collab_document_name: None,
+            date: now.naive_utc(),
+            duration_minutes: 60,
+            title: None,
+            meeting_url: None,
+            provider: None,
+            created_at: now.into(),
+            updated_at: now.into(),
+            hydrated_at: None,
+            session_type_id: None,
+            deleted_at: None,
+        };
+        let action = crate::resource_view::Annotated {
+            resource: ActionWithAssignees {
+                action: create_test_action(Id::new_v4(), session.id),
+                assignee_ids: vec![],
+            },
+            has_unread: false,
+            reactions: vec![],
+            total_work_seconds: None,
+        };
+
+        // Mock: 1) sessions batch, 2) relationships batch; no users query
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![session.clone()]])
+            .append_query_results(vec![vec![relationship.clone()]])
+            .into_connection();
+
+        let includes = IncludeOptions {
+            relationship: true,
+            ..Default::default()
+        };
+        let enriched = with_includes(&db, vec![action], includes).await?;
+
+        assert_eq!(enriched[0].session, None);
+        assert_eq!(enriched[0].relationship, Some(relationship));
+        assert_eq!(enriched[0].assignees, None);
+        assert_eq!(db.into_transaction_log().len(), 2);
+        Ok(())
+    }
 }
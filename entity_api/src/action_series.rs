@@ -0,0 +1,231 @@
+use super::action::ActionWithAssignees;
+use super::actions_user;
+use super::error::{EntityApiErrorKind, Error};
+pub use entity::action_series::Model;
+use entity::action_series::{ActiveModel, Column, Entity};
+use entity::{actions, status::Status, Id};
+use log::debug;
+use sea_orm::{
+    entity::prelude::*,
+    sea_query::{OnConflict, Query},
+    ActiveValue::Set,
+    ConnectionTrait, DatabaseConnection, QueryOrder, TransactionTrait, TryIntoModel,
+};
+
+/// Inserts a new action_series row. The `id`, `created_at`, and `updated_at`
+/// fields on `model` are ignored — the DB assigns them.
+pub async fn create(db: &impl ConnectionTrait, model: Model) -> Result<Model, Error> {
+    debug!(
+        "Creating action_series for relationship {}",
+        model.coaching_relationship_id
+    );
+
+    let now = chrono::Utc::now();
+    let active_model = ActiveModel {
+        coaching_relationship_id: Set(model.coaching_relationship_id),
+        rule: Set(model.rule),
+        created_by_user_id: Set(model.created_by_user_id),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+        ..Default::default()
+    };
+
+    Ok(active_model.save(db).await?.try_into_model()?)
+}
+
+pub async fn find_by_id(db: &impl ConnectionTrait, id: Id) -> Result<Model, Error> {
+    Entity::find_by_id(id).one(db).await?.ok_or_else(|| Error {
+        source: None,
+        error_kind: EntityApiErrorKind::RecordNotFound,
+    })
+}
+
+/// Returns every series owned by the given coaching relationship, oldest first.
+pub async fn find_by_relationship(
+    db: &impl ConnectionTrait,
+    coaching_relationship_id: Id,
+) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::CoachingRelationshipId.eq(coaching_relationship_id))
+        .order_by_asc(Column::CreatedAt)
+        .order_by_asc(Column::Id)
+        .all(db)
+        .await?)
+}
+
+/// Inserts the instance following `previous`, copying its body, goal, creator and
+/// assignees, and returns it. Returns `None` when `previous` already has a successor.
+///
+/// The unique index on `actions.previous_action_id` is the conflict target, so a
+/// completion racing the roll-over job can't create the same instance twice.
+pub async fn create_next_instance(
+    db: &DatabaseConnection,
+    previous: &actions::Model,
+    due_by: DateTimeWithTimeZone,
+) -> Result<Option<ActionWithAssignees>, Error> {
+    debug!(
+        "Creating the instance following recurring action {}",
+        previous.id
+    );
+
+    let txn = db.begin().await?;
+
+    let now = chrono::Utc::now();
+    let active_model = actions::ActiveModel {
+        coaching_session_id: Set(previous.coaching_session_id),
+        goal_id: Set(previous.goal_id),
+        user_id: Set(previous.user_id),
+        action_series_id: Set(previous.action_series_id),
+        previous_action_id: Set(Some(previous.id)),
+        body: Set(previous.body.clone()),
+        effort_points: Set(previous.effort_points),
+        due_by: Set(Some(due_by)),
+        status: Set(Status::NotStarted),
+        status_changed_at: Set(now.into()),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+        ..Default::default()
+    };
+
+    let on_conflict = OnConflict::column(actions::Column::PreviousActionId)
+        .do_nothing()
+        .to_owned();
+
+    // DO NOTHING skips the insert on conflict, so RETURNING yields no row.
+    let Some(action) = actions::Entity::insert_many([active_model])
+        .on_conflict(on_conflict)
+        .exec_with_returning_many(&txn)
+        .await?
+        .pop()
+    else {
+        txn.commit().await?;
+        return Ok(None);
+    };
+
+    let previous_assignee_ids = actions_user::find_user_ids_by_action_id(&txn, previous.id).await?;
+    let assignee_ids = actions_user::set_assignees(&txn, action.id, previous_assignee_ids)
+        .await?
+        .into_iter()
+        .map(|assignment| assignment.user_id)
+        .collect();
+
+    txn.commit().await?;
+
+    Ok(Some(ActionWithAssignees {
+        action,
+        assignee_ids,
+    }))
+}
+
+/// Recurring actions whose due date has passed and that have no successor yet,
+/// i.e. the instances whose period has rolled over.
+pub async fn find_due_for_roll_over(
+    db: &impl ConnectionTrait,
+    now: DateTimeWithTimeZone,
+) -> Result<Vec<actions::Model>, Error> {
+    let with_successor = Query::select()
+        .column(actions::Column::PreviousActionId)
+        .from(actions::Entity)
+        .and_where(actions::Column::PreviousActionId.is_not_null())
+        .to_owned();
+
+    Ok(actions::Entity::find()
+        .filter(actions::Column::ActionSeriesId.is_not_null())
+        .filter(actions::Column::DeletedAt.is_null())
+        .filter(actions::Column::DueBy.lt(now))
+        .filter(actions::Column::Id.not_in_subquery(with_successor))
+        .order_by_asc(actions::Column::DueBy)
+        .all(db)
+        .await?)
+}
+
+/// Every instance of the given series, oldest due date first.
+pub async fn find_instances(
+    db: &impl ConnectionTrait,
+    series_ids: &[Id],
+) -> Result<Vec<actions::Model>, Error> {
+    if series_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    Ok(actions::Entity::find()
+        .filter(actions::Column::ActionSeriesId.is_in(series_ids.iter().copied()))
+        .filter(actions::Column::DeletedAt.is_null())
+        .order_by_asc(actions::Column::DueBy)
+        .order_by_asc(actions::Column::CreatedAt)
+        .all(db)
+        .await?)
+}
+
+#[cfg(test)]
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    fn action(action_series_id: Id) -> actions::Model {
+        let now = chrono::Utc::now();
+        actions::Model {
+            id: Id::new_v4(),
+            coaching_session_id: Id::new_v4(),
+            goal_id: None,
+            user_id: Id::new_v4(),
+            action_series_id: Some(action_series_id),
+            previous_action_id: None,
+            body: Some("Weekly retro journaling".to_string()),
+            effort_points: None,
+            due_by: Some(now.into()),
+            status: Status::Completed,
+            status_changed_at: now.into(),
+            created_at: now.into(),
+            updated_at: now.into(),
+            deleted_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn create_next_instance_returns_none_when_a_successor_exists() -> Result<(), Error> {
+        let previous = action(Id::new_v4());
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<actions::Model>::new()])
+            .into_connection();
+
+        let next = create_next_instance(&db, &previous, chrono::Utc::now().into()).await?;
+
+        assert!(next.is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn create_next_instance_links_to_previous_and_copies_assignees() -> Result<(), Error> {
+        let previous = action(Id::new_v4());
+        let mut inserted = action(previous.action_series_id.unwrap());
+        inserted.previous_action_id = Some(previous.id);
+        inserted.status = Status::NotStarted;
+        let assignee = entity::actions_users::Model {
+            id: Id::new_v4(),
+            action_id: inserted.id,
+            user_id: Id::new_v4(),
+            created_at: chrono::Utc::now().into(),
+            updated_at: chrono::Utc::now().into(),
+        };
+
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![inserted.clone()]])
+            .append_query_results([vec![assignee.clone()]])
+            .append_exec_results([sea_orm::MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            }])
+            .append_query_results([vec![assignee.clone()]])
+            .into_connection();
+
+        let next = create_next_instance(&db, &previous, chrono::Utc::now().into())
+            .await?
+            .unwrap();
+
+        assert_eq!(next.action.previous_action_id, Some(previous.id));
+        assert_eq!(next.assignee_ids, vec![assignee.user_id]);
+        Ok(())
+    }
+}
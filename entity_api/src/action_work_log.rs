@@ -0,0 +1,171 @@
+use super::error::{EntityApiErrorKind, Error};
+use entity::action_work_logs::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{
+    entity::prelude::*,
+    ActiveValue::{Set, Unchanged},
+    ConnectionTrait, FromQueryResult, QueryOrder, QuerySelect, TryIntoModel,
+};
+use std::collections::HashMap;
+
+use log::*;
+
+/// Inserts a work log. A `None` duration starts a running timer.
+pub async fn create(db: &impl ConnectionTrait, model: Model) -> Result<Model, Error> {
+    debug!(
+        "New work log for action {} by user {}",
+        model.action_id, model.user_id
+    );
+
+    let now = chrono::Utc::now();
+    let active_model = ActiveModel {
+        action_id: Set(model.action_id),
+        user_id: Set(model.user_id),
+        started_at: Set(model.started_at),
+        duration_seconds: Set(model.duration_seconds),
+        note: Set(model.note),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+        ..Default::default()
+    };
+
+    Ok(active_model.save(db).await?.try_into_model()?)
+}
+
+pub async fn find_by_id(db: &impl ConnectionTrait, id: Id) -> Result<Model, Error> {
+    Entity::find_by_id(id).one(db).await?.ok_or_else(|| {
+        error!("Work log with id {id} not found");
+
+        Error {
+            source: None,
+            error_kind: EntityApiErrorKind::RecordNotFound,
+        }
+    })
+}
+
+/// The user's running timer, if any (at most one, enforced by a partial unique index).
+pub async fn find_running_by_user(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+) -> Result<Option<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::UserId.eq(user_id))
+        .filter(Column::DurationSeconds.is_null())
+        .one(db)
+        .await?)
+}
+
+/// Stops a running timer by recording its duration, replacing the note when one is given.
+pub async fn stop(
+    db: &impl ConnectionTrait,
+    work_log: Model,
+    duration_seconds: i32,
+    note: Option<String>,
+) -> Result<Model, Error> {
+    let active_model = ActiveModel {
+        id: Unchanged(work_log.id),
+        action_id: Unchanged(work_log.action_id),
+        user_id: Unchanged(work_log.user_id),
+        started_at: Unchanged(work_log.started_at),
+        duration_seconds: Set(Some(duration_seconds)),
+        note: Set(note.or(work_log.note)),
+        created_at: Unchanged(work_log.created_at),
+        updated_at: Set(chrono::Utc::now().into()),
+    };
+
+    Ok(active_model.update(db).await?.try_into_model()?)
+}
+
+/// All work logged on an action, most recent first.
+pub async fn find_by_action(db: &impl ConnectionTrait, action_id: Id) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::ActionId.eq(action_id))
+        .order_by_desc(Column::StartedAt)
+        .all(db)
+        .await?)
+}
+
+#[derive(Debug, FromQueryResult)]
+struct TotalRow {
+    action_id: Id,
+    total_seconds: i64,
+}
+
+/// Batch load the seconds of finished work per action. Running timers don't count
+/// and actions without logged work are absent from the map.
+pub async fn find_totals(
+    db: &impl ConnectionTrait,
+    action_ids: &[Id],
+) -> Result<HashMap<Id, i64>, Error> {
+    if action_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let rows = Entity::find()
+        .select_only()
+        .column(Column::ActionId)
+        .column_as(Expr::cust("SUM(duration_seconds)::bigint"), "total_seconds")
+        .filter(Column::ActionId.is_in(action_ids.iter().copied()))
+        .filter(Column::DurationSeconds.is_not_null())
+        .group_by(Column::ActionId)
+        .into_model::<TotalRow>()
+        .all(db)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.action_id, row.total_seconds))
+        .collect())
+}
+
+#[cfg(test)]
+// We need to gate seaORM's mock feature behind conditional compilation because
+// the feature removes the Clone trait implementation from seaORM's DatabaseConnection.
+// see https://github.com/SeaQL/sea-orm/issues/830
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    fn work_log(duration_seconds: Option<i32>) -> Model {
+        let now = chrono::Utc::now();
+        Model {
+            id: Id::new_v4(),
+            action_id: Id::new_v4(),
+            user_id: Id::new_v4(),
+            started_at: now.into(),
+            duration_seconds,
+            note: Some("Drafted the retro".to_string()),
+            created_at: now.into(),
+            updated_at: now.into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn stop_keeps_the_existing_note_when_none_is_given() -> Result<(), Error> {
+        let running = work_log(None);
+        let stopped = Model {
+            duration_seconds: Some(1500),
+            ..running.clone()
+        };
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![stopped.clone()]])
+            .into_connection();
+
+        let result = stop(&db, running, 1500, None).await?;
+
+        assert_eq!(result, stopped);
+        let log = db.into_transaction_log();
+        assert!(format!("{log:?}").contains("Drafted the retro"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_totals_skips_the_query_for_no_actions() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
+
+        assert!(find_totals(&db, &[]).await?.is_empty());
+        assert!(db.into_transaction_log().is_empty());
+        Ok(())
+    }
+}
@@ -80,7 +80,7 @@ pub async fn delete_all_for_action(db: &impl ConnectionTrait, action_id: Id) ->
 ///
 /// Returns `Error` if the database query fails.
 pub async fn find_by_action_id(
-    db: &DatabaseConnection,
+    db: &impl ConnectionTrait,
     action_id: Id,
 ) -> Result<Vec<Model>, Error> {
     debug!("Finding assignees for action_id={action_id}");
@@ -102,7 +102,7 @@ pub async fn find_by_action_id(
 ///
 /// Returns `Error` if the database query fails.
 pub async fn find_user_ids_by_action_id(
-    db: &DatabaseConnection,
+    db: &impl ConnectionTrait,
     action_id: Id,
 ) -> Result<Vec<Id>, Error> {
     let assignees = find_by_action_id(db, action_id).await?;
@@ -195,7 +195,7 @@ pub async fn find_assignees_for_actions(
 /// Returns `Error` if any database operation fails. On error, the transaction
 /// is rolled back and no changes are persisted.
 pub async fn set_assignees(
-    db: &DatabaseConnection,
+    db: &impl TransactionTrait,
     action_id: Id,
     user_ids: Vec<Id>,
 ) -> Result<Vec<Model>, Error> {
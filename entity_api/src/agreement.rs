@@ -1,10 +1,12 @@
 use super::error::{EntityApiErrorKind, Error};
-use entity::agreements::{ActiveModel, Entity, Model};
+use crate::mutate::{update_if_version, ExpectedVersion};
+use crate::query::contains_any_term;
+use entity::agreements::{ActiveModel, Column, Entity, Model};
 use entity::Id;
 use sea_orm::{
     entity::prelude::*,
     ActiveValue::{Set, Unchanged},
-    DatabaseConnection, TryIntoModel,
+    DatabaseConnection, QueryOrder, QuerySelect, TryIntoModel,
 };
 
 use log::*;
@@ -30,8 +32,17 @@ pub async fn create(
     Ok(agreement_active_model.save(db).await?.try_into_model()?)
 }
 
-pub async fn update(db: &DatabaseConnection, id: Id, model: Model) -> Result<Model, Error> {
-    let result = Entity::find_by_id(id).one(db).await?;
+/// Updates an agreement's body, provided it's still at a version `expected` names.
+pub async fn update(
+    db: &DatabaseConnection,
+    id: Id,
+    model: Model,
+    expected: &ExpectedVersion,
+) -> Result<Model, Error> {
+    let result = Entity::find_by_id(id)
+        .filter(Column::DeletedAt.is_null())
+        .one(db)
+        .await?;
 
     match result {
         Some(agreement) => {
@@ -44,9 +55,10 @@ pub async fn update(db: &DatabaseConnection, id: Id, model: Model) -> Result<Mod
                 user_id: Unchanged(agreement.user_id),
                 updated_at: Set(chrono::Utc::now().into()),
                 created_at: Unchanged(agreement.created_at),
+                deleted_at: Unchanged(agreement.deleted_at),
             };
 
-            Ok(active_model.update(db).await?.try_into_model()?)
+            update_if_version(db, active_model, Column::UpdatedAt, expected).await
         }
         None => {
             debug!("Agreement with id {id} not found");
@@ -59,18 +71,114 @@ pub async fn update(db: &DatabaseConnection, id: Id, model: Model) -> Result<Mod
     }
 }
 
+/// Soft-deletes an agreement: it's left out of reads until restored, or purged once past
+/// the retention window.
 pub async fn delete_by_id(db: &DatabaseConnection, id: Id) -> Result<(), Error> {
     let result = find_by_id(db, id).await?;
 
-    result.delete(db).await?;
+    soft_delete(db, result).await?;
     Ok(())
 }
 
+async fn soft_delete(db: &DatabaseConnection, agreement: Model) -> Result<Model, Error> {
+    let mut active_model: ActiveModel = agreement.into();
+    active_model.deleted_at = Set(Some(chrono::Utc::now().into()));
+    Ok(active_model.update(db).await?.try_into_model()?)
+}
+
+/// Restores a soft-deleted agreement.
+pub async fn restore(db: &DatabaseConnection, id: Id) -> Result<Model, Error> {
+    let agreement = find_deleted_by_id(db, id).await?;
+    let mut active_model: ActiveModel = agreement.into();
+    active_model.deleted_at = Set(None);
+    Ok(active_model.update(db).await?.try_into_model()?)
+}
+
+/// Permanently deletes the agreements soft-deleted before `cutoff`, returning how many.
+pub async fn purge_deleted_before(
+    db: &impl ConnectionTrait,
+    cutoff: DateTimeWithTimeZone,
+) -> Result<u64, Error> {
+    let result = Entity::delete_many()
+        .filter(Column::DeletedAt.lt(cutoff))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected)
+}
+
 pub async fn find_by_id(db: &DatabaseConnection, id: Id) -> Result<Model, Error> {
-    Entity::find_by_id(id).one(db).await?.ok_or_else(|| Error {
-        source: None,
-        error_kind: EntityApiErrorKind::RecordNotFound,
-    })
+    Entity::find_by_id(id)
+        .filter(Column::DeletedAt.is_null())
+        .one(db)
+        .await?
+        .ok_or_else(|| Error {
+            source: None,
+            error_kind: EntityApiErrorKind::RecordNotFound,
+        })
+}
+
+/// Like [`find_by_id`], but finds the agreement only while it's soft-deleted.
+pub async fn find_deleted_by_id(db: &DatabaseConnection, id: Id) -> Result<Model, Error> {
+    Entity::find_by_id(id)
+        .filter(Column::DeletedAt.is_not_null())
+        .one(db)
+        .await?
+        .ok_or_else(|| Error {
+            source: None,
+            error_kind: EntityApiErrorKind::RecordNotFound,
+        })
+}
+
+/// A session's agreements, oldest first.
+pub async fn find_by_coaching_session_id(
+    db: &DatabaseConnection,
+    coaching_session_id: Id,
+) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::DeletedAt.is_null())
+        .filter(Column::CoachingSessionId.eq(coaching_session_id))
+        .order_by_asc(Column::CreatedAt)
+        .all(db)
+        .await?)
+}
+
+/// Agreements of all of `coaching_session_ids`, oldest first.
+pub async fn find_by_coaching_session_ids(
+    db: &DatabaseConnection,
+    coaching_session_ids: &[Id],
+) -> Result<Vec<Model>, Error> {
+    if coaching_session_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    Ok(Entity::find()
+        .filter(Column::DeletedAt.is_null())
+        .filter(Column::CoachingSessionId.is_in(coaching_session_ids.iter().copied()))
+        .order_by_asc(Column::CreatedAt)
+        .all(db)
+        .await?)
+}
+
+/// Up to `limit` agreements from `coaching_session_ids` whose body mentions any of
+/// `terms`, most recently updated first.
+pub async fn search_in_sessions(
+    db: &DatabaseConnection,
+    coaching_session_ids: &[Id],
+    terms: &[String],
+    limit: u64,
+) -> Result<Vec<Model>, Error> {
+    if coaching_session_ids.is_empty() || terms.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    Ok(Entity::find()
+        .filter(Column::DeletedAt.is_null())
+        .filter(Column::CoachingSessionId.is_in(coaching_session_ids.iter().copied()))
+        .filter(contains_any_term(Column::Body, terms))
+        .order_by_desc(Column::UpdatedAt)
+        .limit(limit)
+        .all(db)
+        .await?)
 }
 
 #[cfg(test)]
@@ -94,6 +202,7 @@ mod tests {
             body: Some("This is a agreement".to_owned()),
             created_at: now.into(),
             updated_at: now.into(),
+            deleted_at: None,
         };
 
         let db = MockDatabase::new(DatabaseBackend::Postgres)
@@ -118,6 +227,7 @@ mod tests {
             user_id: Id::new_v4(),
             created_at: now.into(),
             updated_at: now.into(),
+            deleted_at: None,
         };
 
         let db = MockDatabase::new(DatabaseBackend::Postgres)
@@ -127,10 +237,53 @@ mod tests {
             ])
             .into_connection();
 
-        let agreement = update(&db, agreement_model.id, agreement_model.clone()).await?;
+        let agreement = update(
+            &db,
+            agreement_model.id,
+            agreement_model.clone(),
+            &ExpectedVersion::Any,
+        )
+        .await?;
 
         assert_eq!(agreement.body, agreement_model.body);
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn update_only_replaces_the_expected_version() -> Result<(), Error> {
+        let now = chrono::Utc::now();
+
+        let agreement_model = Model {
+            id: Id::new_v4(),
+            coaching_session_id: Id::new_v4(),
+            body: Some("This is a agreement".to_owned()),
+            user_id: Id::new_v4(),
+            created_at: now.into(),
+            updated_at: now.into(),
+            deleted_at: None,
+        };
+
+        // The conditional UPDATE returns no row: someone changed the agreement since.
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![agreement_model.clone()], vec![]])
+            .into_connection();
+
+        let expected = ExpectedVersion::UpdatedAt(vec![now.into()]);
+        let result = update(&db, agreement_model.id, agreement_model.clone(), &expected).await;
+
+        assert_eq!(
+            result.unwrap_err().error_kind,
+            EntityApiErrorKind::StaleVersion
+        );
+
+        let log = db.into_transaction_log();
+        let update = format!("{:?}", log.last().expect("an UPDATE ran"));
+        assert!(
+            update.contains(r#"\"agreements\".\"updated_at\" IN ($4)"#),
+            "the version check belongs to the UPDATE itself: {update}"
+        );
+
+        Ok(())
+    }
 }
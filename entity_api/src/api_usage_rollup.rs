@@ -0,0 +1,165 @@
+//! Hourly per-endpoint request counts of each organization.
+//!
+//! Every replica adds its counts to the same rows, so additions are upserts that sum
+//! into what's stored.
+
+use super::error::Error;
+use entity::api_usage_rollups::Model;
+use entity::Id;
+use sea_orm::{
+    prelude::DateTimeWithTimeZone, ConnectionTrait, DatabaseBackend, DatabaseConnection,
+    FromQueryResult, Statement, TransactionTrait,
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use log::*;
+
+/// An organization's requests to one endpoint over a reporting window.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, FromQueryResult, ToSchema)]
+#[schema(as = domain::api_usage::EndpointUsage)]
+pub struct EndpointUsage {
+    /// Method and route, e.g. `GET /actions/:id`.
+    pub endpoint: String,
+    pub request_count: i64,
+    /// Requests answered with a 4xx or 5xx status.
+    pub error_count: i64,
+    pub average_latency_ms: i64,
+    pub max_latency_ms: i64,
+}
+
+/// Requests an organization with a quota made to one endpoint in the current hour.
+#[derive(Debug, Clone, PartialEq, Eq, FromQueryResult)]
+pub struct PeriodCount {
+    pub organization_id: Id,
+    pub endpoint: String,
+    pub request_count: i64,
+}
+
+/// Adds each rollup's counts to the stored row for its organization, endpoint and hour,
+/// in a single transaction. Rollups of organizations deleted since are dropped.
+pub async fn add(db: &DatabaseConnection, rollups: &[Model]) -> Result<(), Error> {
+    if rollups.is_empty() {
+        return Ok(());
+    }
+    debug!("Adding {} API usage rollup(s)", rollups.len());
+
+    let txn = db.begin().await?;
+    for rollup in rollups {
+        txn.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"INSERT INTO refactor_platform.api_usage_rollups
+                   (organization_id, endpoint, period_start, request_count, error_count,
+                    total_latency_ms, max_latency_ms)
+               SELECT $1, $2, $3, $4, $5, $6, $7
+               WHERE EXISTS (SELECT 1 FROM refactor_platform.organizations WHERE id = $1)
+               ON CONFLICT (organization_id, endpoint, period_start) DO UPDATE SET
+                   request_count = api_usage_rollups.request_count + EXCLUDED.request_count,
+                   error_count = api_usage_rollups.error_count + EXCLUDED.error_count,
+                   total_latency_ms =
+                       api_usage_rollups.total_latency_ms + EXCLUDED.total_latency_ms,
+                   max_latency_ms =
+                       GREATEST(api_usage_rollups.max_latency_ms, EXCLUDED.max_latency_ms)"#,
+            [
+                rollup.organization_id.into(),
+                rollup.endpoint.clone().into(),
+                rollup.period_start.into(),
+                rollup.request_count.into(),
+                rollup.error_count.into(),
+                rollup.total_latency_ms.into(),
+                rollup.max_latency_ms.into(),
+            ],
+        ))
+        .await?;
+    }
+    txn.commit().await?;
+    Ok(())
+}
+
+/// The organization's usage per endpoint over the hours starting within `[from, to)`,
+/// busiest endpoint first.
+pub async fn summarize(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+    from: DateTimeWithTimeZone,
+    to: DateTimeWithTimeZone,
+) -> Result<Vec<EndpointUsage>, Error> {
+    let stmt = Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"SELECT endpoint,
+                  SUM(request_count)::BIGINT AS request_count,
+                  SUM(error_count)::BIGINT AS error_count,
+                  COALESCE(SUM(total_latency_ms) / NULLIF(SUM(request_count), 0), 0)::BIGINT
+                      AS average_latency_ms,
+                  MAX(max_latency_ms) AS max_latency_ms
+           FROM refactor_platform.api_usage_rollups
+           WHERE organization_id = $1 AND period_start >= $2 AND period_start < $3
+           GROUP BY endpoint
+           ORDER BY request_count DESC, endpoint"#,
+        [organization_id.into(), from.into(), to.into()],
+    );
+    Ok(EndpointUsage::find_by_statement(stmt).all(db).await?)
+}
+
+/// Stored counts for the hour starting at `period_start` of every organization with a
+/// quota.
+pub async fn find_quota_counts(
+    db: &impl ConnectionTrait,
+    period_start: DateTimeWithTimeZone,
+) -> Result<Vec<PeriodCount>, Error> {
+    let stmt = Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"SELECT u.organization_id, u.endpoint, u.request_count
+           FROM refactor_platform.api_usage_rollups u
+           JOIN refactor_platform.organization_api_quotas q
+               ON q.organization_id = u.organization_id
+           WHERE u.period_start = $1"#,
+        [period_start.into()],
+    );
+    Ok(PeriodCount::find_by_statement(stmt).all(db).await?)
+}
+
+#[cfg(test)]
+// We need to gate seaORM's mock feature behind conditional compilation because
+// the feature removes the Clone trait implementation from seaORM's DatabaseConnection.
+// see https://github.com/SeaQL/sea-orm/issues/830
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+
+    #[tokio::test]
+    async fn add_sums_into_the_stored_rollup() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results([MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            }])
+            .into_connection();
+
+        add(
+            &db,
+            &[Model {
+                organization_id: Id::new_v4(),
+                endpoint: "GET /actions/:id".to_string(),
+                period_start: chrono::Utc::now().into(),
+                request_count: 3,
+                error_count: 1,
+                total_latency_ms: 120,
+                max_latency_ms: 80,
+            }],
+        )
+        .await?;
+
+        let log = db.into_transaction_log();
+        let sql = log
+            .iter()
+            .flat_map(|transaction| transaction.statements())
+            .map(|statement| statement.sql.as_str())
+            .find(|sql| sql.starts_with("INSERT"))
+            .unwrap();
+        assert!(sql.contains("ON CONFLICT (organization_id, endpoint, period_start)"));
+        assert!(sql.contains("api_usage_rollups.request_count + EXCLUDED.request_count"));
+        Ok(())
+    }
+}
@@ -0,0 +1,184 @@
+//! Files attached to coaching sessions, their notes and actions.
+
+use super::error::{EntityApiErrorKind, Error};
+use chrono::Utc;
+use entity::attachments::{ActiveModel, Column, Entity, Model};
+use entity::{organizations, Id};
+use log::*;
+use sea_orm::{
+    entity::prelude::*, sea_query::Expr, ActiveValue::Set, ConnectionTrait, QueryOrder,
+    QuerySelect, TransactionError, TransactionTrait,
+};
+
+/// Records `model` unless the organization's attachments would then exceed `quota_bytes`
+/// in total, returning `None` when they would. The organization's row is locked while
+/// checking, so concurrent uploads can't overrun the quota together.
+pub async fn create_within_quota(
+    db: &DatabaseConnection,
+    model: Model,
+    quota_bytes: i64,
+) -> Result<Option<Model>, Error> {
+    db.transaction::<_, Option<Model>, Error>(|txn| {
+        Box::pin(async move {
+            organizations::Entity::find_by_id(model.organization_id)
+                .lock_exclusive()
+                .one(txn)
+                .await?
+                .ok_or(Error {
+                    source: None,
+                    error_kind: EntityApiErrorKind::RecordNotFound,
+                })?;
+
+            let used = total_byte_size(txn, model.organization_id).await?;
+            if used.saturating_add(model.byte_size) > quota_bytes {
+                debug!(
+                    "Attachment of {} bytes would exceed organization {}'s quota ({used} of {quota_bytes} bytes used)",
+                    model.byte_size, model.organization_id
+                );
+                return Ok(None);
+            }
+
+            let now = Utc::now();
+            let active_model = ActiveModel {
+                id: Set(model.id),
+                organization_id: Set(model.organization_id),
+                coaching_session_id: Set(model.coaching_session_id),
+                note_id: Set(model.note_id),
+                action_id: Set(model.action_id),
+                user_id: Set(model.user_id),
+                file_name: Set(model.file_name),
+                content_type: Set(model.content_type),
+                byte_size: Set(model.byte_size),
+                storage_key: Set(model.storage_key),
+                created_at: Set(now.into()),
+                updated_at: Set(now.into()),
+            };
+            Ok(Some(active_model.insert(txn).await?))
+        })
+    })
+    .await
+    .map_err(|e| match e {
+        TransactionError::Connection(db_err) => db_err.into(),
+        TransactionError::Transaction(err) => err,
+    })
+}
+
+/// Bytes the organization's attachments take up in total.
+pub async fn total_byte_size(db: &impl ConnectionTrait, organization_id: Id) -> Result<i64, Error> {
+    Ok(Entity::find()
+        .select_only()
+        .column_as(Expr::cust("COALESCE(SUM(byte_size), 0)::bigint"), "total")
+        .filter(Column::OrganizationId.eq(organization_id))
+        .into_tuple::<i64>()
+        .one(db)
+        .await?
+        .unwrap_or_default())
+}
+
+pub async fn find_by_id(db: &impl ConnectionTrait, id: Id) -> Result<Model, Error> {
+    Entity::find_by_id(id).one(db).await?.ok_or(Error {
+        source: None,
+        error_kind: EntityApiErrorKind::RecordNotFound,
+    })
+}
+
+/// The session's attachments, oldest first.
+pub async fn find_by_coaching_session_id(
+    db: &impl ConnectionTrait,
+    coaching_session_id: Id,
+) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::CoachingSessionId.eq(coaching_session_id))
+        .order_by_asc(Column::CreatedAt)
+        .all(db)
+        .await?)
+}
+
+pub async fn delete_by_id(db: &impl ConnectionTrait, id: Id) -> Result<(), Error> {
+    Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+// We need to gate seaORM's mock feature behind conditional compilation because
+// the feature removes the Clone trait implementation from seaORM's DatabaseConnection.
+// see https://github.com/SeaQL/sea-orm/issues/830
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase};
+    use std::collections::BTreeMap;
+
+    fn attachment(organization_id: Id, byte_size: i64) -> Model {
+        let now = Utc::now();
+        Model {
+            id: Id::new_v4(),
+            organization_id,
+            coaching_session_id: Id::new_v4(),
+            note_id: None,
+            action_id: None,
+            user_id: Some(Id::new_v4()),
+            file_name: "worksheet.pdf".to_string(),
+            content_type: "application/pdf".to_string(),
+            byte_size,
+            storage_key: "attachments/key".to_string(),
+            created_at: now.into(),
+            updated_at: now.into(),
+        }
+    }
+
+    fn organization(id: Id) -> organizations::Model {
+        let now = Utc::now();
+        organizations::Model {
+            id,
+            name: "Acme".to_string(),
+            logo: None,
+            slug: "acme".to_string(),
+            created_at: now.into(),
+            updated_at: now.into(),
+            archived_at: None,
+            archived_by: None,
+        }
+    }
+
+    fn total(bytes: i64) -> BTreeMap<String, sea_orm::Value> {
+        BTreeMap::from([("total".to_string(), sea_orm::Value::BigInt(Some(bytes)))])
+    }
+
+    #[tokio::test]
+    async fn create_within_quota_refuses_an_attachment_past_the_quota() -> Result<(), Error> {
+        let organization_id = Id::new_v4();
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([[organization(organization_id)]])
+            .append_query_results([[total(900)]])
+            .into_connection();
+
+        let created = create_within_quota(&db, attachment(organization_id, 200), 1000).await?;
+
+        assert!(created.is_none());
+        let log = db.into_transaction_log();
+        let statements = log[0].statements();
+        assert!(statements[1].sql.ends_with("FOR UPDATE"));
+        assert!(statements[2]
+            .sql
+            .contains("COALESCE(SUM(byte_size), 0)::bigint"));
+        assert!(!statements.iter().any(|s| s.sql.starts_with("INSERT")));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn create_within_quota_records_an_attachment_that_fits() -> Result<(), Error> {
+        let organization_id = Id::new_v4();
+        let model = attachment(organization_id, 100);
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([[organization(organization_id)]])
+            .append_query_results([[total(900)]])
+            .append_query_results([[model.clone()]])
+            .into_connection();
+
+        let created = create_within_quota(&db, model.clone(), 1000).await?;
+
+        assert_eq!(created.map(|m| m.id), Some(model.id));
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+use super::error::Error;
+use entity::audit_entries::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{
+    entity::prelude::*, sea_query::Expr, ActiveValue::Set, ConnectionTrait, QueryOrder,
+    QuerySelect, TryIntoModel,
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use log::*;
+
+/// A page of audit entries, newest first.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[schema(as = domain::audit::AuditPage)]
+pub struct AuditPage {
+    pub entries: Vec<Model>,
+    /// Pass as `cursor` to fetch the next page; `None` on the last page.
+    pub next_cursor: Option<String>,
+}
+
+/// Narrows an organization's entries; every field set must match.
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub entity_type: Option<String>,
+    pub entity_id: Option<Id>,
+    pub actor_user_id: Option<Id>,
+    pub action: Option<String>,
+    /// Entries created at or after this time.
+    pub from: Option<DateTimeWithTimeZone>,
+    /// Entries created before this time.
+    pub to: Option<DateTimeWithTimeZone>,
+}
+
+/// Appends an entry; `id` and `created_at` are assigned here.
+pub async fn create(db: &impl ConnectionTrait, entry: Model) -> Result<Model, Error> {
+    debug!(
+        "Recording audit entry: {} {} {}",
+        entry.action, entry.entity_type, entry.entity_id
+    );
+
+    let active_model = ActiveModel {
+        id: Set(Id::new_v4()),
+        organization_id: Set(entry.organization_id),
+        actor_user_id: Set(entry.actor_user_id),
+        action: Set(entry.action),
+        entity_type: Set(entry.entity_type),
+        entity_id: Set(entry.entity_id),
+        changes: Set(entry.changes),
+        snapshot: Set(entry.snapshot),
+        ip_address: Set(entry.ip_address),
+        created_at: Set(chrono::Utc::now().into()),
+    };
+
+    Ok(active_model.insert(db).await?.try_into_model()?)
+}
+
+/// The snapshot of the entity's latest entry, if it has one and wasn't deleted.
+pub async fn find_latest_snapshot(
+    db: &impl ConnectionTrait,
+    entity_type: &str,
+    entity_id: Id,
+) -> Result<Option<serde_json::Value>, Error> {
+    Ok(Entity::find()
+        .filter(Column::EntityType.eq(entity_type))
+        .filter(Column::EntityId.eq(entity_id))
+        .order_by_desc(Column::CreatedAt)
+        .one(db)
+        .await?
+        .and_then(|entry| entry.snapshot))
+}
+
+/// Up to `limit` of the organization's entries matching `filter`, newest first. `before`
+/// continues a previous page from the `(created_at, id)` of its last entry.
+pub async fn find_by_organization(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+    filter: &AuditFilter,
+    before: Option<(DateTimeWithTimeZone, Id)>,
+    limit: u64,
+) -> Result<Vec<Model>, Error> {
+    let mut query = Entity::find().filter(Column::OrganizationId.eq(organization_id));
+    if let Some(entity_type) = &filter.entity_type {
+        query = query.filter(Column::EntityType.eq(entity_type.as_str()));
+    }
+    if let Some(entity_id) = filter.entity_id {
+        query = query.filter(Column::EntityId.eq(entity_id));
+    }
+    if let Some(actor_user_id) = filter.actor_user_id {
+        query = query.filter(Column::ActorUserId.eq(actor_user_id));
+    }
+    if let Some(action) = &filter.action {
+        query = query.filter(Column::Action.eq(action.as_str()));
+    }
+    if let Some(from) = filter.from {
+        query = query.filter(Column::CreatedAt.gte(from));
+    }
+    if let Some(to) = filter.to {
+        query = query.filter(Column::CreatedAt.lt(to));
+    }
+    if let Some((created_at, id)) = before {
+        query = query.filter(
+            Expr::tuple([
+                Expr::col((Entity, Column::CreatedAt)).into(),
+                Expr::col((Entity, Column::Id)).into(),
+            ])
+            .lt(Expr::tuple([Expr::value(created_at), Expr::value(id)])),
+        );
+    }
+
+    Ok(query
+        .order_by_desc(Column::CreatedAt)
+        .order_by_desc(Column::Id)
+        .limit(limit)
+        .all(db)
+        .await?)
+}
@@ -0,0 +1,32 @@
+//! Read access to login sessions.
+//!
+//! `refactor_platform.authorized_sessions` is created and written by the session
+//! store in the web layer, so there's no entity for it; this module reads it with raw
+//! statements.
+
+use super::error::Error;
+use sea_orm::{
+    prelude::DateTimeWithTimeZone, ConnectionTrait, DatabaseBackend, FromQueryResult, Statement,
+};
+
+#[derive(Debug, FromQueryResult)]
+struct Expiry {
+    expiry_date: DateTimeWithTimeZone,
+}
+
+/// When the session expires unless it's used again; `None` once it has been deleted
+/// (logged out, or expired and swept).
+pub async fn find_expiry(
+    db: &impl ConnectionTrait,
+    session_id: &str,
+) -> Result<Option<DateTimeWithTimeZone>, Error> {
+    let stmt = Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        "SELECT expiry_date FROM refactor_platform.authorized_sessions WHERE id = $1",
+        [session_id.into()],
+    );
+    Ok(Expiry::find_by_statement(stmt)
+        .one(db)
+        .await?
+        .map(|row| row.expiry_date))
+}
@@ -0,0 +1,63 @@
+use super::error::Error;
+use entity::calendar_feeds::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{entity::prelude::*, sea_query::OnConflict, ActiveValue::Set, ConnectionTrait};
+
+use log::*;
+
+pub async fn find_by_user_id(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+) -> Result<Option<Model>, Error> {
+    Ok(Entity::find_by_id(user_id).one(db).await?)
+}
+
+/// The user's feed, created with `secret` unless they already have one.
+pub async fn find_or_create(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+    secret: String,
+) -> Result<Model, Error> {
+    let now = chrono::Utc::now();
+    let active_model = ActiveModel {
+        user_id: Set(user_id),
+        secret: Set(secret),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+    };
+
+    // Two first reads at once mustn't hand out different URLs: the second keeps the first's
+    Entity::insert(active_model)
+        .on_conflict(OnConflict::column(Column::UserId).do_nothing().to_owned())
+        .exec_without_returning(db)
+        .await?;
+    find_by_user_id(db, user_id)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("calendar feed of user {user_id}")).into())
+}
+
+/// Replaces the user's feed secret with `secret`, revoking their earlier feed URLs.
+pub async fn replace_secret(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+    secret: String,
+) -> Result<Model, Error> {
+    debug!("Replacing the calendar feed secret of user {user_id}");
+
+    let now = chrono::Utc::now();
+    let active_model = ActiveModel {
+        user_id: Set(user_id),
+        secret: Set(secret),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+    };
+
+    let on_conflict = OnConflict::column(Column::UserId)
+        .update_columns([Column::Secret, Column::UpdatedAt])
+        .to_owned();
+
+    Ok(Entity::insert(active_model)
+        .on_conflict(on_conflict)
+        .exec_with_returning(db)
+        .await?)
+}
@@ -0,0 +1,179 @@
+//! Open actions brought forward into later sessions of their coaching relationship.
+
+use super::error::Error;
+use entity::carried_actions::{ActiveModel, Column, Entity, Model};
+use entity::{actions, status::Status, Id};
+use sea_orm::{
+    entity::prelude::*, sea_query::OnConflict, sea_query::Query, ActiveValue::Set, Condition,
+    ConnectionTrait, FromQueryResult, QueryOrder, QuerySelect,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use utoipa::ToSchema;
+
+/// An action carried into a session, with where it came from and how often it has been
+/// carried in all.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[schema(as = domain::coaching_session::CarriedAction)]
+pub struct CarriedAction {
+    #[serde(flatten)]
+    pub action: actions::Model,
+    /// The session the action was carried from.
+    pub carried_from_session_id: Id,
+    /// How many sessions the action has been carried into, this one included.
+    pub carry_count: i64,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct CarryCount {
+    action_id: Id,
+    carry_count: i64,
+}
+
+/// The actions still open (not started, in progress or on hold) that belong to the
+/// session `from_session_id`: those created in it and those carried into it.
+pub async fn find_open_to_carry(
+    db: &impl ConnectionTrait,
+    from_session_id: Id,
+) -> Result<Vec<actions::Model>, Error> {
+    let carried_in = Query::select()
+        .column(Column::ActionId)
+        .from(Entity.table_ref())
+        .and_where(Column::CoachingSessionId.eq(from_session_id))
+        .to_owned();
+
+    Ok(actions::Entity::find()
+        .filter(actions::Column::DeletedAt.is_null())
+        .filter(actions::Column::Status.is_in([
+            Status::NotStarted,
+            Status::InProgress,
+            Status::OnHold,
+        ]))
+        .filter(
+            Condition::any()
+                .add(actions::Column::CoachingSessionId.eq(from_session_id))
+                .add(actions::Column::Id.in_subquery(carried_in)),
+        )
+        .order_by_asc(actions::Column::CreatedAt)
+        .all(db)
+        .await?)
+}
+
+/// Links `action_ids` to the session `to_session_id` as carried from `from_session_id`.
+/// Actions already carried into that session are left as they are. Returns how many
+/// were newly carried.
+pub async fn carry(
+    db: &impl ConnectionTrait,
+    action_ids: &[Id],
+    from_session_id: Id,
+    to_session_id: Id,
+) -> Result<u64, Error> {
+    if action_ids.is_empty() {
+        return Ok(0);
+    }
+
+    let now = chrono::Utc::now();
+    let active_models = action_ids.iter().map(|action_id| ActiveModel {
+        action_id: Set(*action_id),
+        coaching_session_id: Set(to_session_id),
+        carried_from_session_id: Set(from_session_id),
+        created_at: Set(now.into()),
+        ..Default::default()
+    });
+
+    let on_conflict = OnConflict::columns([Column::ActionId, Column::CoachingSessionId])
+        .do_nothing()
+        .to_owned();
+
+    Ok(Entity::insert_many(active_models)
+        .on_conflict(on_conflict)
+        .exec_without_returning(db)
+        .await?)
+}
+
+/// The actions carried into each of `session_ids`, oldest action first, by session id.
+/// Sessions nothing was carried into have no entry.
+pub async fn find_by_session_ids(
+    db: &impl ConnectionTrait,
+    session_ids: &[Id],
+) -> Result<HashMap<Id, Vec<CarriedAction>>, Error> {
+    if session_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let carried: Vec<(Model, Option<actions::Model>)> = Entity::find()
+        .filter(Column::CoachingSessionId.is_in(session_ids.iter().copied()))
+        .find_also_related(actions::Entity)
+        .filter(actions::Column::DeletedAt.is_null())
+        .order_by_asc(actions::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    let action_ids: Vec<Id> = carried.iter().map(|(link, _)| link.action_id).collect();
+    let counts = count_by_action(db, &action_ids).await?;
+
+    let mut by_session: HashMap<Id, Vec<CarriedAction>> = HashMap::new();
+    for (link, action) in carried {
+        let Some(action) = action else { continue };
+        by_session
+            .entry(link.coaching_session_id)
+            .or_default()
+            .push(CarriedAction {
+                carry_count: counts.get(&action.id).copied().unwrap_or(1),
+                action,
+                carried_from_session_id: link.carried_from_session_id,
+            });
+    }
+    Ok(by_session)
+}
+
+/// How many sessions each of `action_ids` has been carried into. Actions never carried
+/// have no entry.
+pub async fn count_by_action(
+    db: &impl ConnectionTrait,
+    action_ids: &[Id],
+) -> Result<HashMap<Id, i64>, Error> {
+    if action_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    Ok(Entity::find()
+        .select_only()
+        .column(Column::ActionId)
+        .column_as(Column::Id.count(), "carry_count")
+        .filter(Column::ActionId.is_in(action_ids.iter().copied()))
+        .group_by(Column::ActionId)
+        .into_model::<CarryCount>()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|count| (count.action_id, count.carry_count))
+        .collect())
+}
+
+#[cfg(test)]
+// We need to gate seaORM's mock feature behind conditional compilation because
+// the feature removes the Clone trait implementation from seaORM's DatabaseConnection.
+// see https://github.com/SeaQL/sea-orm/issues/830
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    #[tokio::test]
+    async fn find_open_to_carry_includes_actions_carried_into_the_session() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<actions::Model>::new()])
+            .into_connection();
+
+        find_open_to_carry(&db, Id::new_v4()).await?;
+
+        let log = db.into_transaction_log();
+        let sql = &log[0].statements()[0].sql;
+        assert!(sql.contains(r#""actions"."status" IN ("#));
+        assert!(sql.contains(
+            r#"OR "actions"."id" IN (SELECT "action_id" FROM "refactor_platform"."carried_actions" WHERE "carried_actions"."coaching_session_id" = $5)"#
+        ));
+        Ok(())
+    }
+}
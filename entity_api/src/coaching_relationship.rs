@@ -5,6 +5,7 @@ use super::{
 use crate::user;
 use chrono::Utc;
 use entity::{
+    ai_privacy_level::AiPrivacyLevel,
     coachees, coaches,
     coaching_relationships::{self, ActiveModel, Entity, Model},
     Id,
@@ -94,6 +95,7 @@ pub async fn create(
         coach_id: Set(coaching_relationship_model.coach_id),
         coachee_id: Set(coaching_relationship_model.coachee_id),
         slug: Set(slug),
+        pro_bono: Set(coaching_relationship_model.pro_bono),
         created_at: Set(now.into()),
         updated_at: Set(now.into()),
         ..Default::default()
@@ -108,6 +110,8 @@ pub async fn create(
         coach_last_name: coach.last_name,
         coachee_first_name: coachee.first_name,
         coachee_last_name: coachee.last_name,
+        ai_privacy_level: inserted.ai_privacy_level,
+        pro_bono: inserted.pro_bono,
         created_at: inserted.created_at,
         updated_at: inserted.updated_at,
     })
@@ -120,6 +124,103 @@ pub async fn find_by_id(db: &DatabaseConnection, id: Id) -> Result<Model, Error>
     })
 }
 
+/// Sets how much of the relationship's content AI features may send to the LLM provider.
+pub async fn update_ai_privacy_level(
+    db: &DatabaseConnection,
+    relationship: Model,
+    ai_privacy_level: AiPrivacyLevel,
+) -> Result<Model, Error> {
+    debug!(
+        "Setting AI privacy level of relationship {} to {ai_privacy_level:?}",
+        relationship.id
+    );
+
+    let mut active_model: ActiveModel = relationship.into();
+    active_model.ai_privacy_level = Set(ai_privacy_level);
+    active_model.updated_at = Set(Utc::now().into());
+
+    Ok(active_model.update(db).await?)
+}
+
+/// Marks whether the coach coaches the relationship without pay.
+pub async fn update_pro_bono(
+    db: &DatabaseConnection,
+    relationship: Model,
+    pro_bono: bool,
+) -> Result<Model, Error> {
+    debug!(
+        "Setting pro bono of relationship {} to {pro_bono}",
+        relationship.id
+    );
+
+    let mut active_model: ActiveModel = relationship.into();
+    active_model.pro_bono = Set(pro_bono);
+    active_model.updated_at = Set(Utc::now().into());
+
+    Ok(active_model.update(db).await?)
+}
+
+/// Hands the relationship to another coach and/or coachee, who must belong to its
+/// organization. Its sessions, goals and the rest of its history stay with it.
+pub async fn reassign(
+    db: &DatabaseConnection,
+    relationship: Model,
+    coach_id: Id,
+    coachee_id: Id,
+) -> Result<Model, Error> {
+    let invalid = |message: &str| Error {
+        source: None,
+        error_kind: EntityApiErrorKind::ValidationError {
+            message: message.into(),
+            details: None,
+        },
+    };
+    if coach_id == coachee_id {
+        return Err(invalid(
+            "The coach and the coachee must be different users.",
+        ));
+    }
+
+    for user_id in [coach_id, coachee_id] {
+        user::find_by_id(db, user_id).await?;
+        // membership is independent of archive state
+        let organizations =
+            organization::find_by_user(db, user_id, organization::StatusFilter::All).await?;
+        if !organizations
+            .iter()
+            .any(|organization| organization.id == relationship.organization_id)
+        {
+            return Err(invalid(
+                "Coach and coachee must belong to the relationship's organization.",
+            ));
+        }
+    }
+
+    let existing = Entity::find()
+        .filter(coaching_relationships::Column::OrganizationId.eq(relationship.organization_id))
+        .filter(coaching_relationships::Column::CoachId.eq(coach_id))
+        .filter(coaching_relationships::Column::CoacheeId.eq(coachee_id))
+        .filter(coaching_relationships::Column::Id.ne(relationship.id))
+        .one(db)
+        .await?;
+    if existing.is_some() {
+        return Err(invalid(
+            "Coaching relationship already exists for this coach and coachee in the organization.",
+        ));
+    }
+
+    info!(
+        "Reassigning coaching relationship {} from coach {} and coachee {} to coach {coach_id} and coachee {coachee_id}",
+        relationship.id, relationship.coach_id, relationship.coachee_id
+    );
+    let mut active_model: ActiveModel = relationship.into();
+    active_model.coach_id = Set(coach_id);
+    active_model.coachee_id = Set(coachee_id);
+    active_model.updated_at = Set(Utc::now().into());
+
+    Ok(active_model.update(db).await?)
+}
+
 pub async fn find_by_user(db: &DatabaseConnection, user_id: Id) -> Result<Vec<Model>, Error> {
     let coaching_relationships: Vec<coaching_relationships::Model> =
         coaching_relationships::Entity::find()
@@ -229,6 +330,8 @@ pub async fn find_by_organization_with_user_names(
         .column(coaching_relationships::Column::OrganizationId)
         .column(coaching_relationships::Column::CoachId)
         .column(coaching_relationships::Column::CoacheeId)
+        .column(coaching_relationships::Column::AiPrivacyLevel)
+        .column(coaching_relationships::Column::ProBono)
         .column(coaching_relationships::Column::CreatedAt)
         .column(coaching_relationships::Column::UpdatedAt)
         .column_as(Expr::cust("coaches.first_name"), "coach_first_name")
@@ -270,6 +373,8 @@ pub async fn find_by_user_and_organization_with_user_names(
         .column(coaching_relationships::Column::OrganizationId)
         .column(coaching_relationships::Column::CoachId)
         .column(coaching_relationships::Column::CoacheeId)
+        .column(coaching_relationships::Column::AiPrivacyLevel)
+        .column(coaching_relationships::Column::ProBono)
         .column(coaching_relationships::Column::CreatedAt)
         .column(coaching_relationships::Column::UpdatedAt)
         .column_as(Expr::cust("coaches.first_name"), "coach_first_name")
@@ -305,6 +410,8 @@ pub async fn get_relationship_with_user_names(
         .column(coaching_relationships::Column::OrganizationId)
         .column(coaching_relationships::Column::CoachId)
         .column(coaching_relationships::Column::CoacheeId)
+        .column(coaching_relationships::Column::AiPrivacyLevel)
+        .column(coaching_relationships::Column::ProBono)
         .column(coaching_relationships::Column::CreatedAt)
         .column(coaching_relationships::Column::UpdatedAt)
         .column_as(Expr::cust("coaches.first_name"), "coach_first_name")
@@ -407,6 +514,8 @@ pub async fn find_by_user_id_with_user_names(
         .column(coaching_relationships::Column::OrganizationId)
         .column(coaching_relationships::Column::CoachId)
         .column(coaching_relationships::Column::CoacheeId)
+        .column(coaching_relationships::Column::AiPrivacyLevel)
+        .column(coaching_relationships::Column::ProBono)
         .column(coaching_relationships::Column::CreatedAt)
         .column(coaching_relationships::Column::UpdatedAt)
         .column_as(Expr::cust("coaches.first_name"), "coach_first_name")
@@ -430,6 +539,8 @@ pub struct CoachingRelationshipWithUserNames {
     pub coach_last_name: String,
     pub coachee_first_name: String,
     pub coachee_last_name: String,
+    pub ai_privacy_level: AiPrivacyLevel,
+    pub pro_bono: bool,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
@@ -441,7 +552,7 @@ impl Serialize for CoachingRelationshipWithUserNames {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("CoachingRelationship", 9)?;
+        let mut state = serializer.serialize_struct("CoachingRelationship", 11)?;
         state.serialize_field("id", &self.id)?;
         state.serialize_field("coach_id", &self.coach_id)?;
         state.serialize_field("coachee_id", &self.coachee_id)?;
@@ -449,6 +560,8 @@ impl Serialize for CoachingRelationshipWithUserNames {
         state.serialize_field("coach_last_name", &self.coach_last_name)?;
         state.serialize_field("coachee_first_name", &self.coachee_first_name)?;
         state.serialize_field("coachee_last_name", &self.coachee_last_name)?;
+        state.serialize_field("ai_privacy_level", &self.ai_privacy_level)?;
+        state.serialize_field("pro_bono", &self.pro_bono)?;
         state.serialize_field("created_at", &self.created_at)?;
         state.serialize_field("updated_at", &self.updated_at)?;
         state.end()
@@ -475,7 +588,7 @@ mod tests {
             db.into_transaction_log(),
             [Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "coaching_relationships"."id", "coaching_relationships"."organization_id", "coaching_relationships"."coach_id", "coaching_relationships"."coachee_id", "coaching_relationships"."slug", "coaching_relationships"."created_at", "coaching_relationships"."updated_at" FROM "refactor_platform"."coaching_relationships" WHERE "coaching_relationships"."id" = $1 LIMIT $2"#,
+                r#"SELECT "coaching_relationships"."id", "coaching_relationships"."organization_id", "coaching_relationships"."coach_id", "coaching_relationships"."coachee_id", "coaching_relationships"."slug", CAST("coaching_relationships"."ai_privacy_level" AS "text"), "coaching_relationships"."pro_bono", "coaching_relationships"."created_at", "coaching_relationships"."updated_at" FROM "refactor_platform"."coaching_relationships" WHERE "coaching_relationships"."id" = $1 LIMIT $2"#,
                 [
                     coaching_relationship_id.into(),
                     sea_orm::Value::BigUnsigned(Some(1))
@@ -497,7 +610,7 @@ mod tests {
             db.into_transaction_log(),
             [Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "coaching_relationships"."id", "coaching_relationships"."organization_id", "coaching_relationships"."coach_id", "coaching_relationships"."coachee_id", "coaching_relationships"."slug", "coaching_relationships"."created_at", "coaching_relationships"."updated_at" FROM "refactor_platform"."coaching_relationships" WHERE "coaching_relationships"."coach_id" = $1 OR "coaching_relationships"."coachee_id" = $2"#,
+                r#"SELECT "coaching_relationships"."id", "coaching_relationships"."organization_id", "coaching_relationships"."coach_id", "coaching_relationships"."coachee_id", "coaching_relationships"."slug", CAST("coaching_relationships"."ai_privacy_level" AS "text"), "coaching_relationships"."pro_bono", "coaching_relationships"."created_at", "coaching_relationships"."updated_at" FROM "refactor_platform"."coaching_relationships" WHERE "coaching_relationships"."coach_id" = $1 OR "coaching_relationships"."coachee_id" = $2"#,
                 [user_id.into(), user_id.into()]
             )]
         );
@@ -516,7 +629,7 @@ mod tests {
             db.into_transaction_log(),
             [Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "coaching_relationships"."id", "coaching_relationships"."organization_id", "coaching_relationships"."coach_id", "coaching_relationships"."coachee_id", "coaching_relationships"."slug", "coaching_relationships"."created_at", "coaching_relationships"."updated_at" FROM "refactor_platform"."coaching_relationships" WHERE "coaching_relationships"."organization_id" IN (SELECT "organizations"."id" FROM "refactor_platform"."organizations" WHERE "organizations"."id" = $1)"#,
+                r#"SELECT "coaching_relationships"."id", "coaching_relationships"."organization_id", "coaching_relationships"."coach_id", "coaching_relationships"."coachee_id", "coaching_relationships"."slug", CAST("coaching_relationships"."ai_privacy_level" AS "text"), "coaching_relationships"."pro_bono", "coaching_relationships"."created_at", "coaching_relationships"."updated_at" FROM "refactor_platform"."coaching_relationships" WHERE "coaching_relationships"."organization_id" IN (SELECT "organizations"."id" FROM "refactor_platform"."organizations" WHERE "organizations"."id" = $1)"#,
                 [organization_id.into()]
             )]
         );
@@ -536,7 +649,7 @@ mod tests {
             db.into_transaction_log(),
             [Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "coaching_relationships"."id", "coaching_relationships"."organization_id", "coaching_relationships"."coach_id", "coaching_relationships"."coachee_id", "coaching_relationships"."created_at", "coaching_relationships"."updated_at", coaches.first_name AS "coach_first_name", coaches.last_name AS "coach_last_name", coachees.first_name AS "coachee_first_name", coachees.last_name AS "coachee_last_name" FROM "refactor_platform"."coaching_relationships" JOIN "refactor_platform"."users" AS "coaches" ON "coaching_relationships"."coach_id" = "coaches"."id" JOIN "refactor_platform"."users" AS "coachees" ON "coaching_relationships"."coachee_id" = "coachees"."id" WHERE "coaching_relationships"."organization_id" IN (SELECT "organizations"."id" FROM "refactor_platform"."organizations" WHERE "organizations"."id" = $1)"#,
+                r#"SELECT "coaching_relationships"."id", "coaching_relationships"."organization_id", "coaching_relationships"."coach_id", "coaching_relationships"."coachee_id", CAST("coaching_relationships"."ai_privacy_level" AS "text"), "coaching_relationships"."pro_bono", "coaching_relationships"."created_at", "coaching_relationships"."updated_at", coaches.first_name AS "coach_first_name", coaches.last_name AS "coach_last_name", coachees.first_name AS "coachee_first_name", coachees.last_name AS "coachee_last_name" FROM "refactor_platform"."coaching_relationships" JOIN "refactor_platform"."users" AS "coaches" ON "coaching_relationships"."coach_id" = "coaches"."id" JOIN "refactor_platform"."users" AS "coachees" ON "coaching_relationships"."coachee_id" = "coachees"."id" WHERE "coaching_relationships"."organization_id" IN (SELECT "organizations"."id" FROM "refactor_platform"."organizations" WHERE "organizations"."id" = $1)"#,
                 [organization_id.into()]
             )]
         );
@@ -575,6 +688,8 @@ mod tests {
             coach_id,
             coachee_id,
             slug: "test-relationship".to_string(),
+            ai_privacy_level: Default::default(),
+            pro_bono: false,
             created_at: now.into(),
             updated_at: now.into(),
         };
@@ -650,6 +765,8 @@ mod tests {
             coach_id: Id::new_v4(),
             coachee_id: Id::new_v4(),
             slug: String::new(),
+            ai_privacy_level: Default::default(),
+            pro_bono: false,
             created_at: now.into(),
             updated_at: now.into(),
         };
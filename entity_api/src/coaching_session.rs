@@ -1,19 +1,20 @@
+use super::carried_action::{self, CarriedAction};
 use super::error::{EntityApiErrorKind, Error};
 use crate::duration::Duration;
 use crate::mutate::UpdateMap;
 use chrono::NaiveDateTime;
 use entity::{
-    agreements, coaching_relationships, coaching_session_topics, coaching_session_views,
+    actions, agreements, coaching_relationships, coaching_session_topics, coaching_session_views,
     coaching_sessions::{self, ActiveModel, Column, Entity, Model, Relation},
     goals,
     meeting_provider::Provider,
-    organizations, users, Id,
+    notes, organizations, users, Id,
 };
 use log::debug;
 use sea_orm::{
     entity::prelude::*, sea_query::Expr, ActiveValue::Unchanged, ConnectionTrait, DatabaseBackend,
     DatabaseConnection, FromQueryResult, JoinType, Order, QueryOrder, QuerySelect, QueryTrait,
-    Select, Set, Statement, TryIntoModel, Value,
+    Select, Set, Statement, TransactionTrait, TryIntoModel, Value,
 };
 use serde::Serialize;
 use std::collections::HashMap;
@@ -141,6 +142,7 @@ pub async fn create(
         created_at: Set(now.into()),
         updated_at: Set(now.into()),
         hydrated_at: Set(coaching_session_model.hydrated_at),
+        session_type_id: Set(coaching_session_model.session_type_id),
         ..Default::default()
     };
 
@@ -192,6 +194,7 @@ pub async fn bulk_create_recurring(
             meeting_url: Set(None),
             provider: Set(None),
             hydrated_at: Set(None),
+            session_type_id: Set(None),
             created_at: Set(now.into()),
             updated_at: Set(now.into()),
             ..Default::default()
@@ -204,10 +207,39 @@ pub async fn bulk_create_recurring(
 }
 
 pub async fn find_by_id(db: &impl ConnectionTrait, id: Id) -> Result<Model, Error> {
-    Entity::find_by_id(id).one(db).await?.ok_or_else(|| Error {
-        source: None,
-        error_kind: EntityApiErrorKind::RecordNotFound,
-    })
+    Entity::find_by_id(id)
+        .filter(Column::DeletedAt.is_null())
+        .one(db)
+        .await?
+        .ok_or_else(|| Error {
+            source: None,
+            error_kind: EntityApiErrorKind::RecordNotFound,
+        })
+}
+
+/// Like [`find_by_id`], but finds the session only while it's soft-deleted.
+pub async fn find_deleted_by_id(db: &impl ConnectionTrait, id: Id) -> Result<Model, Error> {
+    Entity::find_by_id(id)
+        .filter(Column::DeletedAt.is_not_null())
+        .one(db)
+        .await?
+        .ok_or_else(|| Error {
+            source: None,
+            error_kind: EntityApiErrorKind::RecordNotFound,
+        })
+}
+
+/// Every session in a coaching relationship, oldest first.
+pub async fn find_by_relationship(
+    db: &impl ConnectionTrait,
+    coaching_relationship_id: Id,
+) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::DeletedAt.is_null())
+        .filter(Column::CoachingRelationshipId.eq(coaching_relationship_id))
+        .order_by_asc(Column::Date)
+        .all(db)
+        .await?)
 }
 
 /// Returns every coaching session linked to the given series, ordered by date ascending.
@@ -216,6 +248,7 @@ pub async fn find_by_series_id(
     series_id: Id,
 ) -> Result<Vec<Model>, Error> {
     Ok(Entity::find()
+        .filter(Column::DeletedAt.is_null())
         .filter(Column::CoachingSessionSeriesId.eq(series_id))
         .order_by_asc(Column::Date)
         .all(db)
@@ -231,6 +264,7 @@ pub async fn find_future_sessions_by_series_id(
     boundary: chrono::NaiveDateTime,
 ) -> Result<Vec<Model>, Error> {
     Ok(Entity::find()
+        .filter(Column::DeletedAt.is_null())
         .filter(Column::CoachingSessionSeriesId.eq(series_id))
         .filter(Column::Date.gte(boundary))
         .order_by_asc(Column::Date)
@@ -261,6 +295,7 @@ pub async fn find_prior_session(
     before: NaiveDateTime,
 ) -> Result<Option<Model>, Error> {
     Ok(Entity::find()
+        .filter(Column::DeletedAt.is_null())
         .filter(Column::CoachingRelationshipId.eq(coaching_relationship_id))
         .filter(Column::Date.lt(before))
         .order_by_desc(Column::Date)
@@ -276,6 +311,7 @@ pub async fn find_next_session(
     after: NaiveDateTime,
 ) -> Result<Option<Model>, Error> {
     Ok(Entity::find()
+        .filter(Column::DeletedAt.is_null())
         .filter(Column::CoachingRelationshipId.eq(coaching_relationship_id))
         .filter(Column::Date.gt(after))
         .order_by_asc(Column::Date)
@@ -301,6 +337,7 @@ pub async fn find_by_id_with_coaching_relationship(
 ) -> Result<(Model, coaching_relationships::Model), Error> {
     if let Some(results) = Entity::find_by_id(id)
         .find_also_related(coaching_relationships::Entity)
+        .filter(Column::DeletedAt.is_null())
         .one(db)
         .await?
     {
@@ -314,8 +351,92 @@ pub async fn find_by_id_with_coaching_relationship(
     })
 }
 
-pub async fn delete(db: &impl ConnectionTrait, coaching_session_id: Id) -> Result<(), Error> {
-    Entity::delete_by_id(coaching_session_id).exec(db).await?;
+/// The sessions held by the coach up to `until` (and from `from`, when given), each with
+/// its relationship, oldest first.
+pub async fn find_held_by_coach(
+    db: &DatabaseConnection,
+    coach_id: Id,
+    from: Option<NaiveDateTime>,
+    until: NaiveDateTime,
+) -> Result<Vec<(Model, coaching_relationships::Model)>, Error> {
+    let mut query = Entity::find()
+        .find_also_related(coaching_relationships::Entity)
+        .filter(Column::DeletedAt.is_null())
+        .filter(coaching_relationships::Column::CoachId.eq(coach_id))
+        .filter(Column::Date.lte(until));
+    if let Some(from) = from {
+        query = query.filter(Column::Date.gte(from));
+    }
+
+    Ok(query
+        .order_by_asc(Column::Date)
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|(session, relationship)| relationship.map(|r| (session, r)))
+        .collect())
+}
+
+/// Soft-deletes a coaching session: it, and with it its notes, agreements and actions, are
+/// left out of reads until it's restored, or purged once past the retention window.
+/// Returns the session as deleted.
+pub async fn delete(db: &impl ConnectionTrait, coaching_session_id: Id) -> Result<Model, Error> {
+    let session = find_by_id(db, coaching_session_id).await?;
+    let mut active_model: ActiveModel = session.into();
+    active_model.deleted_at = Set(Some(chrono::Utc::now().into()));
+    Ok(active_model.update(db).await?)
+}
+
+/// Restores a soft-deleted coaching session.
+pub async fn restore(db: &impl ConnectionTrait, coaching_session_id: Id) -> Result<Model, Error> {
+    let session = find_deleted_by_id(db, coaching_session_id).await?;
+    let mut active_model: ActiveModel = session.into();
+    active_model.deleted_at = Set(None);
+    Ok(active_model.update(db).await?.try_into_model()?)
+}
+
+/// Up to `limit` of the sessions soft-deleted before `cutoff`, longest deleted first.
+pub async fn find_deleted_before(
+    db: &impl ConnectionTrait,
+    cutoff: DateTimeWithTimeZone,
+    limit: u64,
+) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::DeletedAt.lt(cutoff))
+        .order_by_asc(Column::DeletedAt)
+        .limit(limit)
+        .all(db)
+        .await?)
+}
+
+/// Permanently deletes a soft-deleted session along with its notes, agreements and
+/// actions, whose references to it don't cascade. Other dependent rows go by cascade.
+/// Locks the session first, so one restored meanwhile is left alone (`RecordNotFound`).
+pub async fn purge(db: &DatabaseConnection, coaching_session_id: Id) -> Result<(), Error> {
+    let txn = db.begin().await?;
+    Entity::find_by_id(coaching_session_id)
+        .filter(Column::DeletedAt.is_not_null())
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .ok_or(Error {
+            source: None,
+            error_kind: EntityApiErrorKind::RecordNotFound,
+        })?;
+    notes::Entity::delete_many()
+        .filter(notes::Column::CoachingSessionId.eq(coaching_session_id))
+        .exec(&txn)
+        .await?;
+    agreements::Entity::delete_many()
+        .filter(agreements::Column::CoachingSessionId.eq(coaching_session_id))
+        .exec(&txn)
+        .await?;
+    actions::Entity::delete_many()
+        .filter(actions::Column::CoachingSessionId.eq(coaching_session_id))
+        .exec(&txn)
+        .await?;
+    Entity::delete_by_id(coaching_session_id).exec(&txn).await?;
+    txn.commit().await?;
     Ok(())
 }
 
@@ -363,6 +484,8 @@ pub async fn mark_hydrated(txn: &impl ConnectionTrait, target: &Model) -> Result
         created_at: Unchanged(target.created_at),
         updated_at: Set(now.into()),
         hydrated_at: Set(Some(now.into())),
+        session_type_id: Unchanged(target.session_type_id),
+        deleted_at: Unchanged(target.deleted_at),
     };
     Ok(active_model.update(txn).await?.try_into_model()?)
 }
@@ -393,6 +516,7 @@ pub async fn find_meeting_url_by_relationship_and_provider(
     provider: Provider,
 ) -> Result<Option<String>, Error> {
     Ok(Entity::find()
+        .filter(Column::DeletedAt.is_null())
         .filter(Column::CoachingRelationshipId.eq(coaching_relationship_id))
         .filter(Column::Provider.eq(provider))
         .filter(Column::MeetingUrl.is_not_null())
@@ -464,6 +588,7 @@ pub async fn find_counts_by_month_for_user(
         .join(JoinType::InnerJoin, Relation::CoachingRelationships.def())
         .filter(Column::Date.gte(from_date))
         .filter(Column::Date.lt(to_exclusive))
+        .filter(Column::DeletedAt.is_null())
         .filter(
             coaching_relationships::Column::CoachId
                 .eq(user_id)
@@ -480,6 +605,83 @@ pub async fn find_counts_by_month_for_user(
     Ok(rows)
 }
 
+/// Per-session badge counts for a session list, scoped to one viewer.
+///
+/// - `unread_notes`: notes written by someone else and created or edited after the
+///   viewer last marked the session viewed (all such notes if never viewed).
+/// - `open_actions`: actions not yet `completed` or `wont_do`.
+/// - `agreements`: agreements recorded for the session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, FromQueryResult, ToSchema)]
+pub struct SessionBadges {
+    pub coaching_session_id: Id,
+    pub unread_notes: i64,
+    pub open_actions: i64,
+    pub agreements: i64,
+}
+
+/// Computes [`SessionBadges`] for the given sessions in a single statement.
+///
+/// Sessions the viewer does not participate in (as coach or coachee) are silently
+/// omitted, as are unknown IDs, so callers can pass IDs straight from a client.
+/// Each count is a correlated sub-select against the session row, which keeps the
+/// aggregation from fanning out across the notes × actions × agreements join.
+pub async fn find_badges_for_user(
+    db: &impl ConnectionTrait,
+    viewer_id: Id,
+    session_ids: &[Id],
+) -> Result<Vec<SessionBadges>, Error> {
+    if session_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // $1 is the viewer; session IDs bind as $2..$n+1.
+    let placeholders = (2..session_ids.len() + 2)
+        .map(|n| format!("${n}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let sql = format!(
+        r#"SELECT s.id AS coaching_session_id,
+               (SELECT COUNT(*) FROM refactor_platform.notes n
+                 WHERE n.coaching_session_id = s.id
+                   AND n.user_id <> $1
+                   AND n.deleted_at IS NULL
+                   AND (v.last_viewed_at IS NULL OR n.updated_at > v.last_viewed_at))::bigint
+                   AS unread_notes,
+               (SELECT COUNT(*) FROM refactor_platform.actions a
+                 WHERE a.coaching_session_id = s.id
+                   AND a.status NOT IN ('completed', 'wont_do')
+                   AND a.deleted_at IS NULL)::bigint
+                   AS open_actions,
+               (SELECT COUNT(*) FROM refactor_platform.agreements g
+                 WHERE g.coaching_session_id = s.id
+                   AND g.deleted_at IS NULL)::bigint
+                   AS agreements
+           FROM refactor_platform.coaching_sessions s
+           JOIN refactor_platform.coaching_relationships r
+             ON r.id = s.coaching_relationship_id
+           LEFT JOIN refactor_platform.coaching_session_views v
+             ON v.coaching_session_id = s.id AND v.user_id = $1
+           WHERE s.id IN ({placeholders})
+             AND s.deleted_at IS NULL
+             AND (r.coach_id = $1 OR r.coachee_id = $1)
+           ORDER BY s.date ASC"#
+    );
+
+    let values = std::iter::once(Value::from(viewer_id))
+        .chain(session_ids.iter().copied().map(Value::from))
+        .collect::<Vec<_>>();
+
+    Ok(
+        SessionBadges::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            sql,
+            values,
+        ))
+        .all(db)
+        .await?,
+    )
+}
+
 /// Public API response type: a single coaching session with its optional related resources.
 ///
 /// # Purpose
@@ -532,12 +734,17 @@ pub struct EnrichedSession {
     pub agreement: Option<agreements::Model>,
     // Caller-scoped read receipt: when the path user last marked this session viewed; null if never.
     pub viewer_last_viewed_at: Option<DateTimeWithTimeZone>,
+    // True when the session row changed after `viewer_last_viewed_at` (or was never viewed).
+    pub has_unread: bool,
     // Server-composed fallback title: human title -> first topic body -> first goal title;
     // null when none derive. Always present (serialized as null, never omitted), so consumers
     // get one canonical title without re-deriving the chain.
     pub display_title: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub topics: Option<Vec<coaching_session_topics::Model>>,
+    // Open actions carried forward from earlier sessions, each with its carry count.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub carried_actions: Option<Vec<CarriedAction>>,
 }
 
 /// Configuration for which related resources to include when fetching coaching sessions.
@@ -581,6 +788,7 @@ pub struct IncludeOptions {
     pub goal: bool,
     pub agreements: bool,
     pub topics: bool,
+    pub carried_actions: bool,
 }
 
 impl IncludeOptions {
@@ -595,6 +803,7 @@ impl IncludeOptions {
             goal: false,
             agreements: false,
             topics: false,
+            carried_actions: false,
         }
     }
 
@@ -637,6 +846,8 @@ impl IncludeOptions {
 pub struct SessionQueryOptions {
     /// Filter sessions to only those in this coaching relationship
     pub coaching_relationship_id: Option<Id>,
+    /// Filter sessions to only those of this session type
+    pub session_type_id: Option<Id>,
     /// Filter sessions starting from this date (inclusive). Interpreted in
     /// `tz` when present; otherwise UTC.
     pub from_date: Option<chrono::NaiveDate>,
@@ -703,12 +914,16 @@ async fn find_by_user_filtered(
                 .eq(user_id)
                 .or(coaching_relationships::Column::CoacheeId.eq(user_id)),
         )
+        .filter(coaching_sessions::Column::DeletedAt.is_null())
         .apply_if(
             options.coaching_relationship_id,
             |q: Select<Entity>, rel_id| {
                 q.filter(coaching_sessions::Column::CoachingRelationshipId.eq(rel_id))
             },
         )
+        .apply_if(options.session_type_id, |q: Select<Entity>, type_id| {
+            q.filter(coaching_sessions::Column::SessionTypeId.eq(type_id))
+        })
         .apply_if(lower_bound_filter, |q: Select<Entity>, expr| q.filter(expr))
         .apply_if(upper_bound_filter, |q: Select<Entity>, expr| q.filter(expr))
         .apply_if(
@@ -773,6 +988,7 @@ struct RelatedData {
     goals: HashMap<Id, Vec<goals::Model>>,
     agreements: HashMap<Id, agreements::Model>,
     topics: HashMap<Id, Vec<coaching_session_topics::Model>>,
+    carried_actions: HashMap<Id, Vec<CarriedAction>>,
 }
 
 /// Load all requested related data in efficient batches
@@ -829,11 +1045,16 @@ async fn load_related_data(
         data.topics = batch_load_topics(db, &session_ids).await?;
     }
 
+    // Load carried-forward actions by session_id
+    if includes.carried_actions {
+        data.carried_actions = carried_action::find_by_session_ids(db, &session_ids).await?;
+    }
+
     Ok(data)
 }
 
 /// Batch load coaching relationships by IDs
-async fn batch_load_relationships(
+pub(crate) async fn batch_load_relationships(
     db: &impl ConnectionTrait,
     ids: &[Id],
 ) -> Result<HashMap<Id, coaching_relationships::Model>, Error> {
@@ -850,8 +1071,27 @@ async fn batch_load_relationships(
         .collect())
 }
 
+/// Batch load live coaching sessions by IDs
+pub(crate) async fn batch_load_sessions(
+    db: &impl ConnectionTrait,
+    ids: &[Id],
+) -> Result<HashMap<Id, Model>, Error> {
+    if ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    Ok(Entity::find()
+        .filter(coaching_sessions::Column::Id.is_in(ids.iter().copied()))
+        .filter(coaching_sessions::Column::DeletedAt.is_null())
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|s| (s.id, s))
+        .collect())
+}
+
 /// Batch load users by IDs
-async fn batch_load_users(
+pub(crate) async fn batch_load_users(
     db: &impl ConnectionTrait,
     ids: &[Id],
 ) -> Result<HashMap<Id, users::Model>, Error> {
@@ -927,6 +1167,7 @@ async fn batch_load_agreements(
 
     Ok(agreements::Entity::find()
         .filter(agreements::Column::CoachingSessionId.is_in(session_ids.iter().copied()))
+        .filter(agreements::Column::DeletedAt.is_null())
         .all(db)
         .await?
         .into_iter()
@@ -1022,6 +1263,8 @@ fn assemble_enriched_session(
     let agreement = related.agreements.get(&session.id).cloned();
 
     let viewer_last_viewed_at = views.get(&session.id).copied();
+    let has_unread =
+        crate::resource_view::has_unread(session.updated_at, viewer_last_viewed_at, false);
 
     let display_title = display_titles.get(&session.id).cloned().flatten();
 
@@ -1031,6 +1274,18 @@ fn assemble_enriched_session(
         None
     };
 
+    let carried_actions = if includes.carried_actions {
+        Some(
+            related
+                .carried_actions
+                .get(&session.id)
+                .cloned()
+                .unwrap_or_default(),
+        )
+    } else {
+        None
+    };
+
     EnrichedSession {
         session,
         relationship,
@@ -1040,8 +1295,10 @@ fn assemble_enriched_session(
         goals,
         agreement,
         viewer_last_viewed_at,
+        has_unread,
         display_title,
         topics,
+        carried_actions,
     }
 }
 
@@ -1088,6 +1345,8 @@ mod tests {
             created_at: now.into(),
             updated_at: now.into(),
             hydrated_at: None,
+            session_type_id: None,
+            deleted_at: None,
         };
         let session2 = Model {
             id: Id::new_v4(),
@@ -1150,6 +1409,8 @@ mod tests {
             created_at: now.into(),
             updated_at: now.into(),
             hydrated_at: None,
+            session_type_id: None,
+            deleted_at: None,
         };
         // Whatever the DB returns from UPDATE ... RETURNING.
         let after = Model {
@@ -1181,7 +1442,7 @@ mod tests {
             db.into_transaction_log(),
             [Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "coaching_sessions"."id", "coaching_sessions"."coaching_relationship_id", "coaching_sessions"."coaching_session_series_id", "coaching_sessions"."collab_document_name", "coaching_sessions"."date", "coaching_sessions"."duration_minutes", "coaching_sessions"."title", "coaching_sessions"."meeting_url", CAST("coaching_sessions"."provider" AS "text"), "coaching_sessions"."created_at", "coaching_sessions"."updated_at", "coaching_sessions"."hydrated_at" FROM "refactor_platform"."coaching_sessions" WHERE "coaching_sessions"."id" = $1 LIMIT $2"#,
+                r#"SELECT "coaching_sessions"."id", "coaching_sessions"."coaching_relationship_id", "coaching_sessions"."coaching_session_series_id", "coaching_sessions"."collab_document_name", "coaching_sessions"."date", "coaching_sessions"."duration_minutes", "coaching_sessions"."title", "coaching_sessions"."meeting_url", CAST("coaching_sessions"."provider" AS "text"), "coaching_sessions"."created_at", "coaching_sessions"."updated_at", "coaching_sessions"."hydrated_at", "coaching_sessions"."session_type_id", "coaching_sessions"."deleted_at" FROM "refactor_platform"."coaching_sessions" WHERE "coaching_sessions"."id" = $1 AND "coaching_sessions"."deleted_at" IS NULL LIMIT $2"#,
                 [
                     coaching_session_id.into(),
                     sea_orm::Value::BigUnsigned(Some(1))
@@ -1212,7 +1473,7 @@ mod tests {
             db.into_transaction_log(),
             [Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "coaching_sessions"."id", "coaching_sessions"."coaching_relationship_id", "coaching_sessions"."coaching_session_series_id", "coaching_sessions"."collab_document_name", "coaching_sessions"."date", "coaching_sessions"."duration_minutes", "coaching_sessions"."title", "coaching_sessions"."meeting_url", CAST("coaching_sessions"."provider" AS "text"), "coaching_sessions"."created_at", "coaching_sessions"."updated_at", "coaching_sessions"."hydrated_at" FROM "refactor_platform"."coaching_sessions" WHERE "coaching_sessions"."coaching_relationship_id" = $1 AND "coaching_sessions"."date" < $2 ORDER BY "coaching_sessions"."date" DESC LIMIT $3"#,
+                r#"SELECT "coaching_sessions"."id", "coaching_sessions"."coaching_relationship_id", "coaching_sessions"."coaching_session_series_id", "coaching_sessions"."collab_document_name", "coaching_sessions"."date", "coaching_sessions"."duration_minutes", "coaching_sessions"."title", "coaching_sessions"."meeting_url", CAST("coaching_sessions"."provider" AS "text"), "coaching_sessions"."created_at", "coaching_sessions"."updated_at", "coaching_sessions"."hydrated_at", "coaching_sessions"."session_type_id", "coaching_sessions"."deleted_at" FROM "refactor_platform"."coaching_sessions" WHERE "coaching_sessions"."deleted_at" IS NULL AND "coaching_sessions"."coaching_relationship_id" = $1 AND "coaching_sessions"."date" < $2 ORDER BY "coaching_sessions"."date" DESC LIMIT $3"#,
                 [
                     relationship_id.into(),
                     before.into(),
@@ -1244,7 +1505,7 @@ mod tests {
             db.into_transaction_log(),
             [Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "coaching_sessions"."id", "coaching_sessions"."coaching_relationship_id", "coaching_sessions"."coaching_session_series_id", "coaching_sessions"."collab_document_name", "coaching_sessions"."date", "coaching_sessions"."duration_minutes", "coaching_sessions"."title", "coaching_sessions"."meeting_url", CAST("coaching_sessions"."provider" AS "text"), "coaching_sessions"."created_at", "coaching_sessions"."updated_at", "coaching_sessions"."hydrated_at" FROM "refactor_platform"."coaching_sessions" WHERE "coaching_sessions"."coaching_relationship_id" = $1 AND "coaching_sessions"."date" > $2 ORDER BY "coaching_sessions"."date" ASC LIMIT $3"#,
+                r#"SELECT "coaching_sessions"."id", "coaching_sessions"."coaching_relationship_id", "coaching_sessions"."coaching_session_series_id", "coaching_sessions"."collab_document_name", "coaching_sessions"."date", "coaching_sessions"."duration_minutes", "coaching_sessions"."title", "coaching_sessions"."meeting_url", CAST("coaching_sessions"."provider" AS "text"), "coaching_sessions"."created_at", "coaching_sessions"."updated_at", "coaching_sessions"."hydrated_at", "coaching_sessions"."session_type_id", "coaching_sessions"."deleted_at" FROM "refactor_platform"."coaching_sessions" WHERE "coaching_sessions"."deleted_at" IS NULL AND "coaching_sessions"."coaching_relationship_id" = $1 AND "coaching_sessions"."date" > $2 ORDER BY "coaching_sessions"."date" ASC LIMIT $3"#,
                 [
                     relationship_id.into(),
                     after.into(),
@@ -1267,7 +1528,7 @@ mod tests {
             db.into_transaction_log(),
             [Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "coaching_sessions"."id" AS "A_id", "coaching_sessions"."coaching_relationship_id" AS "A_coaching_relationship_id", "coaching_sessions"."coaching_session_series_id" AS "A_coaching_session_series_id", "coaching_sessions"."collab_document_name" AS "A_collab_document_name", "coaching_sessions"."date" AS "A_date", "coaching_sessions"."duration_minutes" AS "A_duration_minutes", "coaching_sessions"."title" AS "A_title", "coaching_sessions"."meeting_url" AS "A_meeting_url", CAST("coaching_sessions"."provider" AS "text") AS "A_provider", "coaching_sessions"."created_at" AS "A_created_at", "coaching_sessions"."updated_at" AS "A_updated_at", "coaching_sessions"."hydrated_at" AS "A_hydrated_at", "coaching_relationships"."id" AS "B_id", "coaching_relationships"."organization_id" AS "B_organization_id", "coaching_relationships"."coach_id" AS "B_coach_id", "coaching_relationships"."coachee_id" AS "B_coachee_id", "coaching_relationships"."slug" AS "B_slug", "coaching_relationships"."created_at" AS "B_created_at", "coaching_relationships"."updated_at" AS "B_updated_at" FROM "refactor_platform"."coaching_sessions" LEFT JOIN "refactor_platform"."coaching_relationships" ON "coaching_sessions"."coaching_relationship_id" = "coaching_relationships"."id" WHERE "coaching_sessions"."id" = $1 LIMIT $2"#,
+                r#"SELECT "coaching_sessions"."id" AS "A_id", "coaching_sessions"."coaching_relationship_id" AS "A_coaching_relationship_id", "coaching_sessions"."coaching_session_series_id" AS "A_coaching_session_series_id", "coaching_sessions"."collab_document_name" AS "A_collab_document_name", "coaching_sessions"."date" AS "A_date", "coaching_sessions"."duration_minutes" AS "A_duration_minutes", "coaching_sessions"."title" AS "A_title", "coaching_sessions"."meeting_url" AS "A_meeting_url", CAST("coaching_sessions"."provider" AS "text") AS "A_provider", "coaching_sessions"."created_at" AS "A_created_at", "coaching_sessions"."updated_at" AS "A_updated_at", "coaching_sessions"."hydrated_at" AS "A_hydrated_at", "coaching_sessions"."session_type_id" AS "A_session_type_id", "coaching_sessions"."deleted_at" AS "A_deleted_at", "coaching_relationships"."id" AS "B_id", "coaching_relationships"."organization_id" AS "B_organization_id", "coaching_relationships"."coach_id" AS "B_coach_id", "coaching_relationships"."coachee_id" AS "B_coachee_id", "coaching_relationships"."slug" AS "B_slug", CAST("coaching_relationships"."ai_privacy_level" AS "text") AS "B_ai_privacy_level", "coaching_relationships"."pro_bono" AS "B_pro_bono", "coaching_relationships"."created_at" AS "B_created_at", "coaching_relationships"."updated_at" AS "B_updated_at" FROM "refactor_platform"."coaching_sessions" LEFT JOIN "refactor_platform"."coaching_relationships" ON "coaching_sessions"."coaching_relationship_id" = "coaching_relationships"."id" WHERE "coaching_sessions"."id" = $1 AND "coaching_sessions"."deleted_at" IS NULL LIMIT $2"#,
                 [
                     coaching_session_id.into(),
                     sea_orm::Value::BigUnsigned(Some(1))
@@ -1279,24 +1540,69 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn delete_deletes_a_single_record() -> Result<(), Error> {
-        let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
+    async fn delete_marks_the_session_deleted() -> Result<(), Error> {
+        let now = chrono::Utc::now();
+        let session = Model {
+            id: Id::new_v4(),
+            coaching_relationship_id: Id::new_v4(),
+            coaching_session_series_id: None,
+            date: now.naive_utc(),
+            collab_document_name: None,
+            duration_minutes: crate::duration::Duration::default_minutes(),
+            title: None,
+            meeting_url: None,
+            provider: None,
+            created_at: now.into(),
+            updated_at: now.into(),
+            hydrated_at: None,
+            session_type_id: None,
+            deleted_at: None,
+        };
+        let deleted = Model {
+            deleted_at: Some(now.into()),
+            ..session.clone()
+        };
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![session.clone()]])
+            .append_query_results(vec![vec![deleted]])
+            .into_connection();
 
-        let coaching_session_id = Id::new_v4();
-        let _ = delete(&db, coaching_session_id).await;
+        delete(&db, session.id).await?;
 
-        assert_eq!(
-            db.into_transaction_log(),
-            [Transaction::from_sql_and_values(
-                DatabaseBackend::Postgres,
-                r#"DELETE FROM "refactor_platform"."coaching_sessions" WHERE "coaching_sessions"."id" = $1"#,
-                [coaching_session_id.into(),]
-            )]
+        let log = db.into_transaction_log();
+        assert_eq!(log.len(), 2, "find, then UPDATE; no DELETE");
+        let sql = &log[1].statements()[0].sql;
+        assert!(
+            sql.starts_with(r#"UPDATE "refactor_platform"."coaching_sessions" SET"#),
+            "{sql}"
         );
+        assert!(sql.contains(r#""deleted_at" = $"#), "{sql}");
 
         Ok(())
     }
 
+    #[tokio::test]
+    async fn purge_leaves_a_session_restored_meanwhile_alone() {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![Vec::<Model>::new()])
+            .into_connection();
+
+        let result = purge(&db, Id::new_v4()).await;
+
+        assert_eq!(
+            result.unwrap_err().error_kind,
+            EntityApiErrorKind::RecordNotFound
+        );
+        let log = db.into_transaction_log();
+        let statements = log[0].statements();
+        assert!(
+            statements
+                .iter()
+                .all(|statement| !statement.sql.starts_with("DELETE")),
+            "nothing deleted: {statements:?}"
+        );
+    }
+
     #[tokio::test]
     async fn find_by_user_returns_sessions_where_user_is_coach_or_coachee() -> Result<(), Error> {
         let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
@@ -1308,7 +1614,7 @@ mod tests {
             db.into_transaction_log(),
             [Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "coaching_sessions"."id", "coaching_sessions"."coaching_relationship_id", "coaching_sessions"."coaching_session_series_id", "coaching_sessions"."collab_document_name", "coaching_sessions"."date", "coaching_sessions"."duration_minutes", "coaching_sessions"."title", "coaching_sessions"."meeting_url", CAST("coaching_sessions"."provider" AS "text"), "coaching_sessions"."created_at", "coaching_sessions"."updated_at", "coaching_sessions"."hydrated_at" FROM "refactor_platform"."coaching_sessions" INNER JOIN "refactor_platform"."coaching_relationships" ON "coaching_sessions"."coaching_relationship_id" = "coaching_relationships"."id" WHERE "coaching_relationships"."coach_id" = $1 OR "coaching_relationships"."coachee_id" = $2"#,
+                r#"SELECT "coaching_sessions"."id", "coaching_sessions"."coaching_relationship_id", "coaching_sessions"."coaching_session_series_id", "coaching_sessions"."collab_document_name", "coaching_sessions"."date", "coaching_sessions"."duration_minutes", "coaching_sessions"."title", "coaching_sessions"."meeting_url", CAST("coaching_sessions"."provider" AS "text"), "coaching_sessions"."created_at", "coaching_sessions"."updated_at", "coaching_sessions"."hydrated_at", "coaching_sessions"."session_type_id", "coaching_sessions"."deleted_at" FROM "refactor_platform"."coaching_sessions" INNER JOIN "refactor_platform"."coaching_relationships" ON "coaching_sessions"."coaching_relationship_id" = "coaching_relationships"."id" WHERE ("coaching_relationships"."coach_id" = $1 OR "coaching_relationships"."coachee_id" = $2) AND "coaching_sessions"."deleted_at" IS NULL"#,
                 [user_id.into(), user_id.into()]
             )]
         );
@@ -1354,7 +1660,7 @@ mod tests {
             db.into_transaction_log(),
             [Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT to_char(date_trunc('month', "coaching_sessions"."date" AT TIME ZONE $1::text), 'YYYY-MM') AS "month", COUNT(*)::bigint AS "count" FROM "refactor_platform"."coaching_sessions" INNER JOIN "refactor_platform"."coaching_relationships" ON "coaching_sessions"."coaching_relationship_id" = "coaching_relationships"."id" WHERE "coaching_sessions"."date" >= $2 AND "coaching_sessions"."date" < $3 AND ("coaching_relationships"."coach_id" = $4 OR "coaching_relationships"."coachee_id" = $5) GROUP BY "month" ORDER BY "month" ASC"#,
+                r#"SELECT to_char(date_trunc('month', "coaching_sessions"."date" AT TIME ZONE $1::text), 'YYYY-MM') AS "month", COUNT(*)::bigint AS "count" FROM "refactor_platform"."coaching_sessions" INNER JOIN "refactor_platform"."coaching_relationships" ON "coaching_sessions"."coaching_relationship_id" = "coaching_relationships"."id" WHERE "coaching_sessions"."date" >= $2 AND "coaching_sessions"."date" < $3 AND "coaching_sessions"."deleted_at" IS NULL AND ("coaching_relationships"."coach_id" = $4 OR "coaching_relationships"."coachee_id" = $5) GROUP BY "month" ORDER BY "month" ASC"#,
                 [
                     "America/Los_Angeles".into(),
                     from_date.into(),
@@ -1368,6 +1674,53 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn find_badges_for_user_returns_empty_for_no_ids() -> Result<(), Error> {
+        // No mock query expected — the function short-circuits before touching the DB.
+        let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
+        let result = find_badges_for_user(&db, Id::new_v4(), &[]).await?;
+        assert!(result.is_empty());
+        assert!(db.into_transaction_log().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_badges_for_user_binds_viewer_then_session_ids() -> Result<(), Error> {
+        let viewer_id = Id::new_v4();
+        let session_ids = [Id::new_v4(), Id::new_v4()];
+        let row = SessionBadges {
+            coaching_session_id: session_ids[0],
+            unread_notes: 2,
+            open_actions: 3,
+            agreements: 1,
+        };
+
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![std::collections::BTreeMap::from([
+                ("coaching_session_id", Value::from(row.coaching_session_id)),
+                ("unread_notes", Value::from(row.unread_notes)),
+                ("open_actions", Value::from(row.open_actions)),
+                ("agreements", Value::from(row.agreements)),
+            ])]])
+            .into_connection();
+
+        let result = find_badges_for_user(&db, viewer_id, &session_ids).await?;
+        assert_eq!(result, vec![row]);
+
+        let log = db.into_transaction_log();
+        let statement = &log[0].statements()[0];
+        assert!(statement.sql.contains("WHERE s.id IN ($2, $3)"));
+        assert_eq!(
+            statement.values.as_ref().map(|v| v.0.clone()),
+            Some(vec![
+                viewer_id.into(),
+                session_ids[0].into(),
+                session_ids[1].into()
+            ])
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn find_counts_by_month_for_user_emits_expected_sql_with_relationship_filter(
     ) -> Result<(), Error> {
@@ -1395,7 +1748,7 @@ mod tests {
             db.into_transaction_log(),
             [Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT to_char(date_trunc('month', "coaching_sessions"."date" AT TIME ZONE $1::text), 'YYYY-MM') AS "month", COUNT(*)::bigint AS "count" FROM "refactor_platform"."coaching_sessions" INNER JOIN "refactor_platform"."coaching_relationships" ON "coaching_sessions"."coaching_relationship_id" = "coaching_relationships"."id" WHERE "coaching_sessions"."date" >= $2 AND "coaching_sessions"."date" < $3 AND ("coaching_relationships"."coach_id" = $4 OR "coaching_relationships"."coachee_id" = $5) AND "coaching_sessions"."coaching_relationship_id" = $6 GROUP BY "month" ORDER BY "month" ASC"#,
+                r#"SELECT to_char(date_trunc('month', "coaching_sessions"."date" AT TIME ZONE $1::text), 'YYYY-MM') AS "month", COUNT(*)::bigint AS "count" FROM "refactor_platform"."coaching_sessions" INNER JOIN "refactor_platform"."coaching_relationships" ON "coaching_sessions"."coaching_relationship_id" = "coaching_relationships"."id" WHERE "coaching_sessions"."date" >= $2 AND "coaching_sessions"."date" < $3 AND "coaching_sessions"."deleted_at" IS NULL AND ("coaching_relationships"."coach_id" = $4 OR "coaching_relationships"."coachee_id" = $5) AND "coaching_sessions"."coaching_relationship_id" = $6 GROUP BY "month" ORDER BY "month" ASC"#,
                 [
                     "Europe/Berlin".into(),
                     from_date.into(),
@@ -1430,6 +1783,8 @@ mod tests {
             created_at: now.into(),
             updated_at: now.into(),
             hydrated_at: Some(now.into()),
+            session_type_id: None,
+            deleted_at: None,
         };
 
         let db = MockDatabase::new(DatabaseBackend::Postgres)
@@ -1454,6 +1809,7 @@ mod tests {
         assert!(results[0].goals.is_none());
         assert!(results[0].agreement.is_none());
         assert!(results[0].viewer_last_viewed_at.is_none());
+        assert!(results[0].has_unread);
 
         Ok(())
     }
@@ -1481,6 +1837,8 @@ mod tests {
             created_at: now.into(),
             updated_at: now.into(),
             hydrated_at: Some(now.into()),
+            session_type_id: None,
+            deleted_at: None,
         };
 
         let view = coaching_session_views::Model {
@@ -1554,6 +1912,8 @@ mod tests {
             created_at: now.into(),
             updated_at: now.into(),
             hydrated_at: Some(now.into()),
+            session_type_id: None,
+            deleted_at: None,
         };
 
         let db = MockDatabase::new(DatabaseBackend::Postgres)
@@ -1601,6 +1961,8 @@ mod tests {
             created_at: now.into(),
             updated_at: now.into(),
             hydrated_at: Some(now.into()),
+            session_type_id: None,
+            deleted_at: None,
         };
 
         let db = MockDatabase::new(DatabaseBackend::Postgres)
@@ -1662,7 +2024,7 @@ mod tests {
             db.into_transaction_log(),
             [Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "coaching_sessions"."id", "coaching_sessions"."coaching_relationship_id", "coaching_sessions"."coaching_session_series_id", "coaching_sessions"."collab_document_name", "coaching_sessions"."date", "coaching_sessions"."duration_minutes", "coaching_sessions"."title", "coaching_sessions"."meeting_url", CAST("coaching_sessions"."provider" AS "text"), "coaching_sessions"."created_at", "coaching_sessions"."updated_at", "coaching_sessions"."hydrated_at" FROM "refactor_platform"."coaching_sessions" INNER JOIN "refactor_platform"."coaching_relationships" ON "coaching_sessions"."coaching_relationship_id" = "coaching_relationships"."id" WHERE ("coaching_relationships"."coach_id" = $1 OR "coaching_relationships"."coachee_id" = $2) AND ("coaching_sessions"."date" >= ($3::timestamp AT TIME ZONE $4::text) AT TIME ZONE 'UTC') AND ("coaching_sessions"."date" < ($5::timestamp AT TIME ZONE $6::text) AT TIME ZONE 'UTC')"#,
+                r#"SELECT "coaching_sessions"."id", "coaching_sessions"."coaching_relationship_id", "coaching_sessions"."coaching_session_series_id", "coaching_sessions"."collab_document_name", "coaching_sessions"."date", "coaching_sessions"."duration_minutes", "coaching_sessions"."title", "coaching_sessions"."meeting_url", CAST("coaching_sessions"."provider" AS "text"), "coaching_sessions"."created_at", "coaching_sessions"."updated_at", "coaching_sessions"."hydrated_at", "coaching_sessions"."session_type_id", "coaching_sessions"."deleted_at" FROM "refactor_platform"."coaching_sessions" INNER JOIN "refactor_platform"."coaching_relationships" ON "coaching_sessions"."coaching_relationship_id" = "coaching_relationships"."id" WHERE ("coaching_relationships"."coach_id" = $1 OR "coaching_relationships"."coachee_id" = $2) AND "coaching_sessions"."deleted_at" IS NULL AND ("coaching_sessions"."date" >= ($3::timestamp AT TIME ZONE $4::text) AT TIME ZONE 'UTC') AND ("coaching_sessions"."date" < ($5::timestamp AT TIME ZONE $6::text) AT TIME ZONE 'UTC')"#,
                 [
                     user_id.into(),
                     user_id.into(),
@@ -1711,7 +2073,7 @@ mod tests {
             db.into_transaction_log(),
             [Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "coaching_sessions"."id", "coaching_sessions"."coaching_relationship_id", "coaching_sessions"."coaching_session_series_id", "coaching_sessions"."collab_document_name", "coaching_sessions"."date", "coaching_sessions"."duration_minutes", "coaching_sessions"."title", "coaching_sessions"."meeting_url", CAST("coaching_sessions"."provider" AS "text"), "coaching_sessions"."created_at", "coaching_sessions"."updated_at", "coaching_sessions"."hydrated_at" FROM "refactor_platform"."coaching_sessions" INNER JOIN "refactor_platform"."coaching_relationships" ON "coaching_sessions"."coaching_relationship_id" = "coaching_relationships"."id" WHERE ("coaching_relationships"."coach_id" = $1 OR "coaching_relationships"."coachee_id" = $2) AND "coaching_sessions"."date" >= $3 AND "coaching_sessions"."date" < $4"#,
+                r#"SELECT "coaching_sessions"."id", "coaching_sessions"."coaching_relationship_id", "coaching_sessions"."coaching_session_series_id", "coaching_sessions"."collab_document_name", "coaching_sessions"."date", "coaching_sessions"."duration_minutes", "coaching_sessions"."title", "coaching_sessions"."meeting_url", CAST("coaching_sessions"."provider" AS "text"), "coaching_sessions"."created_at", "coaching_sessions"."updated_at", "coaching_sessions"."hydrated_at", "coaching_sessions"."session_type_id", "coaching_sessions"."deleted_at" FROM "refactor_platform"."coaching_sessions" INNER JOIN "refactor_platform"."coaching_relationships" ON "coaching_sessions"."coaching_relationship_id" = "coaching_relationships"."id" WHERE ("coaching_relationships"."coach_id" = $1 OR "coaching_relationships"."coachee_id" = $2) AND "coaching_sessions"."deleted_at" IS NULL AND "coaching_sessions"."date" >= $3 AND "coaching_sessions"."date" < $4"#,
                 [
                     user_id.into(),
                     user_id.into(),
@@ -1753,7 +2115,7 @@ mod tests {
             db.into_transaction_log(),
             [Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "coaching_sessions"."id", "coaching_sessions"."coaching_relationship_id", "coaching_sessions"."coaching_session_series_id", "coaching_sessions"."collab_document_name", "coaching_sessions"."date", "coaching_sessions"."duration_minutes", "coaching_sessions"."title", "coaching_sessions"."meeting_url", CAST("coaching_sessions"."provider" AS "text"), "coaching_sessions"."created_at", "coaching_sessions"."updated_at", "coaching_sessions"."hydrated_at" FROM "refactor_platform"."coaching_sessions" INNER JOIN "refactor_platform"."coaching_relationships" ON "coaching_sessions"."coaching_relationship_id" = "coaching_relationships"."id" WHERE ("coaching_relationships"."coach_id" = $1 OR "coaching_relationships"."coachee_id" = $2) AND ("coaching_sessions"."date" >= ($3::timestamp AT TIME ZONE $4::text) AT TIME ZONE 'UTC')"#,
+                r#"SELECT "coaching_sessions"."id", "coaching_sessions"."coaching_relationship_id", "coaching_sessions"."coaching_session_series_id", "coaching_sessions"."collab_document_name", "coaching_sessions"."date", "coaching_sessions"."duration_minutes", "coaching_sessions"."title", "coaching_sessions"."meeting_url", CAST("coaching_sessions"."provider" AS "text"), "coaching_sessions"."created_at", "coaching_sessions"."updated_at", "coaching_sessions"."hydrated_at", "coaching_sessions"."session_type_id", "coaching_sessions"."deleted_at" FROM "refactor_platform"."coaching_sessions" INNER JOIN "refactor_platform"."coaching_relationships" ON "coaching_sessions"."coaching_relationship_id" = "coaching_relationships"."id" WHERE ("coaching_relationships"."coach_id" = $1 OR "coaching_relationships"."coachee_id" = $2) AND "coaching_sessions"."deleted_at" IS NULL AND ("coaching_sessions"."date" >= ($3::timestamp AT TIME ZONE $4::text) AT TIME ZONE 'UTC')"#,
                 [
                     user_id.into(),
                     user_id.into(),
@@ -1799,7 +2161,7 @@ mod tests {
             db.into_transaction_log(),
             [Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "coaching_sessions"."id", "coaching_sessions"."coaching_relationship_id", "coaching_sessions"."coaching_session_series_id", "coaching_sessions"."collab_document_name", "coaching_sessions"."date", "coaching_sessions"."duration_minutes", "coaching_sessions"."title", "coaching_sessions"."meeting_url", CAST("coaching_sessions"."provider" AS "text"), "coaching_sessions"."created_at", "coaching_sessions"."updated_at", "coaching_sessions"."hydrated_at" FROM "refactor_platform"."coaching_sessions" INNER JOIN "refactor_platform"."coaching_relationships" ON "coaching_sessions"."coaching_relationship_id" = "coaching_relationships"."id" WHERE ("coaching_relationships"."coach_id" = $1 OR "coaching_relationships"."coachee_id" = $2) AND ("coaching_sessions"."date" < ($3::timestamp AT TIME ZONE $4::text) AT TIME ZONE 'UTC')"#,
+                r#"SELECT "coaching_sessions"."id", "coaching_sessions"."coaching_relationship_id", "coaching_sessions"."coaching_session_series_id", "coaching_sessions"."collab_document_name", "coaching_sessions"."date", "coaching_sessions"."duration_minutes", "coaching_sessions"."title", "coaching_sessions"."meeting_url", CAST("coaching_sessions"."provider" AS "text"), "coaching_sessions"."created_at", "coaching_sessions"."updated_at", "coaching_sessions"."hydrated_at", "coaching_sessions"."session_type_id", "coaching_sessions"."deleted_at" FROM "refactor_platform"."coaching_sessions" INNER JOIN "refactor_platform"."coaching_relationships" ON "coaching_sessions"."coaching_relationship_id" = "coaching_relationships"."id" WHERE ("coaching_relationships"."coach_id" = $1 OR "coaching_relationships"."coachee_id" = $2) AND "coaching_sessions"."deleted_at" IS NULL AND ("coaching_sessions"."date" < ($3::timestamp AT TIME ZONE $4::text) AT TIME ZONE 'UTC')"#,
                 [
                     user_id.into(),
                     user_id.into(),
@@ -1859,6 +2221,8 @@ mod tests {
             created_at: now.into(),
             updated_at: now.into(),
             hydrated_at: Some(now.into()),
+            session_type_id: None,
+            deleted_at: None,
         };
 
         let related = RelatedData::default();
@@ -1893,6 +2257,8 @@ mod tests {
             created_at: now.into(),
             updated_at: now.into(),
             hydrated_at: Some(now.into()),
+            session_type_id: None,
+            deleted_at: None,
         };
 
         let related = RelatedData::default();
@@ -1906,6 +2272,70 @@ mod tests {
         assert!(enriched.goals.is_none());
     }
 
+    #[test]
+    fn assemble_returns_carried_actions_of_the_session_when_included() {
+        let now = chrono::Utc::now();
+        let session = Model {
+            id: Id::new_v4(),
+            coaching_relationship_id: Id::new_v4(),
+            coaching_session_series_id: None,
+            date: chrono::Local::now().naive_utc(),
+            collab_document_name: None,
+            duration_minutes: crate::duration::Duration::default_minutes(),
+            title: None,
+            meeting_url: None,
+            provider: None,
+            created_at: now.into(),
+            updated_at: now.into(),
+            hydrated_at: Some(now.into()),
+            session_type_id: None,
+            deleted_at: None,
+        };
+        let carried = CarriedAction {
+            action: entity::actions::Model {
+                id: Id::new_v4(),
+                coaching_session_id: Id::new_v4(),
+                goal_id: None,
+                user_id: Id::new_v4(),
+                action_series_id: None,
+                previous_action_id: None,
+                body: Some("Draft the proposal".to_string()),
+                effort_points: None,
+                due_by: None,
+                status: entity::status::Status::InProgress,
+                status_changed_at: now.into(),
+                created_at: now.into(),
+                updated_at: now.into(),
+                deleted_at: None,
+            },
+            carried_from_session_id: Id::new_v4(),
+            carry_count: 2,
+        };
+        let related = RelatedData {
+            carried_actions: HashMap::from([(session.id, vec![carried.clone()])]),
+            ..RelatedData::default()
+        };
+        let includes = IncludeOptions {
+            carried_actions: true,
+            ..IncludeOptions::none()
+        };
+
+        let views = HashMap::new();
+        let display_titles = HashMap::new();
+        let enriched =
+            assemble_enriched_session(session.clone(), &related, includes, &views, &display_titles);
+        assert_eq!(enriched.carried_actions, Some(vec![carried]));
+
+        let enriched = assemble_enriched_session(
+            session,
+            &related,
+            IncludeOptions::none(),
+            &views,
+            &display_titles,
+        );
+        assert!(enriched.carried_actions.is_none());
+    }
+
     #[test]
     fn validate_allows_organization_with_relationship() {
         let includes = IncludeOptions {
@@ -1914,6 +2344,7 @@ mod tests {
             goal: false,
             agreements: false,
             topics: false,
+            carried_actions: false,
         };
         assert!(includes.validate().is_ok());
     }
@@ -1926,6 +2357,7 @@ mod tests {
             goal: false,
             agreements: false,
             topics: false,
+            carried_actions: false,
         };
         assert!(includes.validate().is_err());
     }
@@ -1938,6 +2370,7 @@ mod tests {
             goal: true,
             agreements: false,
             topics: false,
+            carried_actions: false,
         };
         assert!(includes.validate().is_ok());
     }
@@ -1950,6 +2383,7 @@ mod tests {
             goal: true,
             agreements: true,
             topics: true,
+            carried_actions: true,
         };
         assert!(includes.validate().is_ok());
     }
@@ -1981,6 +2415,8 @@ mod tests {
             hydrated_at: Some(
                 chrono::DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z").unwrap(),
             ),
+            session_type_id: None,
+            deleted_at: None,
         };
 
         // Session 2 (middle): also has a Google Meet URL — this is the one we want
@@ -1999,6 +2435,8 @@ mod tests {
             hydrated_at: Some(
                 chrono::DateTime::parse_from_rfc3339("2025-02-01T00:00:00Z").unwrap(),
             ),
+            session_type_id: None,
+            deleted_at: None,
         };
 
         // Session 3 (newest): no meeting URL — coach didn't request one this time
@@ -2017,6 +2455,8 @@ mod tests {
             hydrated_at: Some(
                 chrono::DateTime::parse_from_rfc3339("2025-03-01T00:00:00Z").unwrap(),
             ),
+            session_type_id: None,
+            deleted_at: None,
         };
 
         // The MockDatabase returns session_2 because our query filters for
@@ -2042,7 +2482,7 @@ mod tests {
             db.into_transaction_log(),
             [Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "coaching_sessions"."id", "coaching_sessions"."coaching_relationship_id", "coaching_sessions"."coaching_session_series_id", "coaching_sessions"."collab_document_name", "coaching_sessions"."date", "coaching_sessions"."duration_minutes", "coaching_sessions"."title", "coaching_sessions"."meeting_url", CAST("coaching_sessions"."provider" AS "text"), "coaching_sessions"."created_at", "coaching_sessions"."updated_at", "coaching_sessions"."hydrated_at" FROM "refactor_platform"."coaching_sessions" WHERE "coaching_sessions"."coaching_relationship_id" = $1 AND "coaching_sessions"."provider" = (CAST($2 AS "meeting_provider")) AND "coaching_sessions"."meeting_url" IS NOT NULL ORDER BY "coaching_sessions"."created_at" DESC LIMIT $3"#,
+                r#"SELECT "coaching_sessions"."id", "coaching_sessions"."coaching_relationship_id", "coaching_sessions"."coaching_session_series_id", "coaching_sessions"."collab_document_name", "coaching_sessions"."date", "coaching_sessions"."duration_minutes", "coaching_sessions"."title", "coaching_sessions"."meeting_url", CAST("coaching_sessions"."provider" AS "text"), "coaching_sessions"."created_at", "coaching_sessions"."updated_at", "coaching_sessions"."hydrated_at", "coaching_sessions"."session_type_id", "coaching_sessions"."deleted_at" FROM "refactor_platform"."coaching_sessions" WHERE "coaching_sessions"."deleted_at" IS NULL AND "coaching_sessions"."coaching_relationship_id" = $1 AND "coaching_sessions"."provider" = (CAST($2 AS "meeting_provider")) AND "coaching_sessions"."meeting_url" IS NOT NULL ORDER BY "coaching_sessions"."created_at" DESC LIMIT $3"#,
                 [
                     relationship_id.into(),
                     "google".into(),
@@ -17,6 +17,7 @@ fn goal_with_title(relationship_id: Id, title: Option<&str>) -> goals::Model {
         target_date: None,
         created_at: now,
         updated_at: now,
+        deleted_at: None,
     }
 }
 
@@ -48,6 +48,7 @@ pub async fn create(
     debug!("Linking goal {goal_id} to session {coaching_session_id}");
 
     let goal = goals::Entity::find_by_id(goal_id)
+        .filter(goals::Column::DeletedAt.is_null())
         .one(db)
         .await?
         .ok_or(Error {
@@ -102,6 +103,7 @@ pub async fn create(
             completed_at: Unchanged(goal.completed_at),
             target_date: Unchanged(goal.target_date),
             created_at: Unchanged(goal.created_at),
+            deleted_at: Unchanged(goal.deleted_at),
             updated_at: Set(now.into()),
         };
         Some(promoted.update(db).await?.try_into_model()?)
@@ -256,6 +258,7 @@ pub async fn find_goals_by_coaching_session_id(
     let links_with_goals = Entity::find()
         .filter(Column::CoachingSessionId.eq(coaching_session_id))
         .find_also_related(goals::Entity)
+        .filter(goals::Column::DeletedAt.is_null())
         .all(db)
         .await?;
 
@@ -372,6 +375,7 @@ pub async fn find_goals_grouped_by_session_ids(
     let links_with_goals = Entity::find()
         .filter(Column::CoachingSessionId.is_in(session_ids.iter().copied()))
         .find_also_related(goals::Entity)
+        .filter(goals::Column::DeletedAt.is_null())
         .order_by_asc(goals::Column::CreatedAt)
         .order_by_asc(goals::Column::Id)
         .all(db)
@@ -403,6 +407,7 @@ pub async fn find_session_ids_by_coaching_relationship_id(
 
     let sessions = coaching_sessions::Entity::find()
         .filter(coaching_sessions::Column::CoachingRelationshipId.eq(coaching_relationship_id))
+        .filter(coaching_sessions::Column::DeletedAt.is_null())
         .all(db)
         .await?;
 
@@ -683,6 +688,7 @@ mod tests {
             target_date: None,
             created_at: now.into(),
             updated_at: now.into(),
+            deleted_at: None,
         };
 
         let db = MockDatabase::new(DatabaseBackend::Postgres)
@@ -741,6 +747,7 @@ mod tests {
             target_date: None,
             created_at: now,
             updated_at: now,
+            deleted_at: None,
         }
     }
 
@@ -950,6 +957,8 @@ mod tests {
             created_at: now.into(),
             updated_at: now.into(),
             hydrated_at: Some(now.into()),
+            session_type_id: None,
+            deleted_at: None,
         };
         let session2 = entity::coaching_sessions::Model {
             id: session2_id,
@@ -964,6 +973,8 @@ mod tests {
             created_at: now.into(),
             updated_at: now.into(),
             hydrated_at: Some(now.into()),
+            session_type_id: None,
+            deleted_at: None,
         };
 
         let db = MockDatabase::new(DatabaseBackend::Postgres)
@@ -0,0 +1,138 @@
+use super::error::{EntityApiErrorKind, Error};
+use entity::dead_letter_events::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{
+    entity::prelude::*, ActiveValue::Set, ConnectionTrait, IntoActiveModel, QueryOrder,
+    QuerySelect, TryIntoModel,
+};
+
+use log::*;
+
+/// Stores a dead-lettered event; `id`, `requeue_count`, `requeued_at` and the
+/// timestamps are assigned here.
+pub async fn create(db: &impl ConnectionTrait, dead_letter: Model) -> Result<Model, Error> {
+    debug!(
+        "Dead-lettering {} event for {}",
+        dead_letter.event_type, dead_letter.handler_name
+    );
+
+    let now = chrono::Utc::now();
+    let active_model = ActiveModel {
+        handler_name: Set(dead_letter.handler_name),
+        event_type: Set(dead_letter.event_type),
+        payload: Set(dead_letter.payload),
+        error: Set(dead_letter.error),
+        attempts: Set(dead_letter.attempts),
+        requeue_count: Set(0),
+        requeued_at: Set(None),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+        ..Default::default()
+    };
+
+    Ok(active_model.insert(db).await?.try_into_model()?)
+}
+
+pub async fn find_by_id(db: &impl ConnectionTrait, id: Id) -> Result<Model, Error> {
+    Entity::find_by_id(id).one(db).await?.ok_or(Error {
+        source: None,
+        error_kind: EntityApiErrorKind::RecordNotFound,
+    })
+}
+
+/// Up to `limit` dead letters, newest first, optionally only `handler_name`'s and only
+/// those of `event_type`. Requeued ones are left out unless `include_requeued` is set.
+pub async fn find(
+    db: &impl ConnectionTrait,
+    handler_name: Option<&str>,
+    event_type: Option<&str>,
+    include_requeued: bool,
+    limit: u64,
+) -> Result<Vec<Model>, Error> {
+    let mut query = Entity::find();
+    if let Some(handler_name) = handler_name {
+        query = query.filter(Column::HandlerName.eq(handler_name));
+    }
+    if let Some(event_type) = event_type {
+        query = query.filter(Column::EventType.eq(event_type));
+    }
+    if !include_requeued {
+        query = query.filter(Column::RequeuedAt.is_null());
+    }
+
+    Ok(query
+        .order_by_desc(Column::CreatedAt)
+        .limit(limit)
+        .all(db)
+        .await?)
+}
+
+/// The number of dead letters not yet requeued, per handler and event type.
+pub async fn count_pending(db: &impl ConnectionTrait) -> Result<Vec<(String, String, i64)>, Error> {
+    Ok(Entity::find()
+        .select_only()
+        .column(Column::HandlerName)
+        .column(Column::EventType)
+        .column_as(Column::Id.count(), "count")
+        .filter(Column::RequeuedAt.is_null())
+        .group_by(Column::HandlerName)
+        .group_by(Column::EventType)
+        .into_tuple()
+        .all(db)
+        .await?)
+}
+
+/// Records a requeue of the dead letter that made `attempts` more attempts, ending in
+/// `error`, or delivering the event when `error` is `None`.
+pub async fn record_requeue(
+    db: &impl ConnectionTrait,
+    dead_letter: Model,
+    attempts: i32,
+    error: Option<String>,
+) -> Result<Model, Error> {
+    debug!(
+        "Recording requeue of dead letter {} ({})",
+        dead_letter.id,
+        if error.is_some() {
+            "failed"
+        } else {
+            "delivered"
+        }
+    );
+
+    let now = chrono::Utc::now();
+    let mut active_model = ActiveModel {
+        attempts: Set(dead_letter.attempts.saturating_add(attempts)),
+        requeue_count: Set(dead_letter.requeue_count + 1),
+        updated_at: Set(now.into()),
+        ..dead_letter.into_active_model()
+    };
+    match error {
+        Some(error) => active_model.error = Set(error),
+        None => active_model.requeued_at = Set(Some(now.into())),
+    }
+
+    Ok(active_model.update(db).await?.try_into_model()?)
+}
+
+#[cfg(test)]
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    #[tokio::test]
+    async fn find_filters_by_handler_and_event_type() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<Model>::new()])
+            .into_connection();
+
+        find(&db, Some("audit_log"), Some("goal_created"), false, 100).await?;
+
+        let sql = db.into_transaction_log()[0].statements()[0].sql.clone();
+        assert!(sql.contains(r#""dead_letter_events"."handler_name" = $1"#));
+        assert!(sql.contains(r#""dead_letter_events"."event_type" = $2"#));
+        assert!(sql.contains(r#""dead_letter_events"."requeued_at" IS NULL"#));
+        Ok(())
+    }
+}
@@ -0,0 +1,292 @@
+//! Storage for the pgvector embedding index.
+//!
+//! SeaORM has no vector column type, so this module talks to
+//! `refactor_platform.embeddings` with raw statements, passing vectors as pgvector
+//! text literals (`[0.1,0.2,...]`). The table only exists where the pgvector extension
+//! is installed; check [`is_available`] before calling anything else.
+
+use super::error::Error;
+use entity::embedding_source_type::EmbeddingSourceType;
+use entity::Id;
+use sea_orm::{
+    ActiveEnum, ConnectionTrait, DatabaseBackend, DatabaseConnection, FromQueryResult, Statement,
+    TransactionTrait,
+};
+
+use log::*;
+
+/// Length of every stored vector (the `vector(1536)` column).
+pub const DIMENSIONS: usize = 1536;
+
+#[derive(Debug, FromQueryResult)]
+struct Availability {
+    available: bool,
+}
+
+/// A note or transcript segment that has no up-to-date embedding yet.
+#[derive(Debug, Clone, PartialEq, FromQueryResult)]
+pub struct Pending {
+    pub source_id: Id,
+    pub coaching_relationship_id: Id,
+    pub coaching_session_id: Id,
+    pub content: String,
+}
+
+/// A vector to store for one source record.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewEmbedding {
+    pub source_type: EmbeddingSourceType,
+    pub source_id: Id,
+    pub coaching_relationship_id: Id,
+    pub coaching_session_id: Id,
+    pub content: String,
+    pub model: String,
+    pub vector: Vec<f32>,
+}
+
+/// A stored embedding close to a search vector. `distance` is the cosine distance
+/// (0 = same direction, 2 = opposite).
+#[derive(Debug, Clone, PartialEq, FromQueryResult)]
+pub struct Match {
+    pub source_type: EmbeddingSourceType,
+    pub source_id: Id,
+    pub coaching_session_id: Id,
+    pub content: String,
+    pub distance: f64,
+}
+
+/// Whether the embeddings table exists, i.e. pgvector was available when migrating.
+pub async fn is_available(db: &impl ConnectionTrait) -> Result<bool, Error> {
+    let stmt = Statement::from_string(
+        DatabaseBackend::Postgres,
+        "SELECT to_regclass('refactor_platform.embeddings') IS NOT NULL AS available",
+    );
+    Ok(Availability::find_by_statement(stmt)
+        .one(db)
+        .await?
+        .is_some_and(|row| row.available))
+}
+
+/// Notes with text (ignoring markup) whose embedding is missing, older than the note's
+/// last edit, or from a different `model`, oldest edit first. Relationships with AI
+/// disabled are skipped, as are encrypted notes (see [`crate::field_encryption`]) and
+/// soft-deleted ones.
+pub async fn find_pending_notes(
+    db: &impl ConnectionTrait,
+    model: &str,
+    limit: u64,
+) -> Result<Vec<Pending>, Error> {
+    let stmt = Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"SELECT n.id AS source_id, s.coaching_relationship_id, n.coaching_session_id,
+                  n.body AS content
+           FROM refactor_platform.notes n
+           JOIN refactor_platform.coaching_sessions s ON s.id = n.coaching_session_id
+           JOIN refactor_platform.coaching_relationships r ON r.id = s.coaching_relationship_id
+           LEFT JOIN refactor_platform.embeddings e
+               ON e.source_type = 'note' AND e.source_id = n.id
+           WHERE regexp_replace(n.body, '<[^>]*>|\s+', '', 'g') <> ''
+             AND n.body NOT LIKE 'enc:v1:%'
+             AND n.deleted_at IS NULL
+             AND s.deleted_at IS NULL
+             AND r.ai_privacy_level <> 'disabled'
+             AND (e.id IS NULL OR e.updated_at < n.updated_at OR e.model <> $1)
+           ORDER BY n.updated_at
+           LIMIT $2"#,
+        [model.into(), limit.into()],
+    );
+    Ok(Pending::find_by_statement(stmt).all(db).await?)
+}
+
+/// Transcript segments without an embedding from `model`, in transcript order. Only
+/// relationships whose privacy level allows transcripts are included.
+pub async fn find_pending_transcript_segments(
+    db: &impl ConnectionTrait,
+    model: &str,
+    limit: u64,
+) -> Result<Vec<Pending>, Error> {
+    let stmt = Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"SELECT ts.id AS source_id, s.coaching_relationship_id, t.coaching_session_id,
+                  ts.speaker_label || ': ' || ts.text AS content
+           FROM refactor_platform.transcript_segments ts
+           JOIN refactor_platform.transcriptions t ON t.id = ts.transcription_id
+           JOIN refactor_platform.coaching_sessions s ON s.id = t.coaching_session_id
+           JOIN refactor_platform.coaching_relationships r ON r.id = s.coaching_relationship_id
+           LEFT JOIN refactor_platform.embeddings e
+               ON e.source_type = 'transcript_segment' AND e.source_id = ts.id
+           WHERE btrim(ts.text) <> ''
+             AND s.deleted_at IS NULL
+             AND r.ai_privacy_level = 'full'
+             AND (e.id IS NULL OR e.model <> $1)
+           ORDER BY ts.transcription_id, ts.start_ms
+           LIMIT $2"#,
+        [model.into(), limit.into()],
+    );
+    Ok(Pending::find_by_statement(stmt).all(db).await?)
+}
+
+/// Inserts or replaces the embedding of each source record, in a single transaction.
+pub async fn upsert(db: &DatabaseConnection, embeddings: &[NewEmbedding]) -> Result<(), Error> {
+    if embeddings.is_empty() {
+        return Ok(());
+    }
+    debug!("Upserting {} embedding(s)", embeddings.len());
+
+    let txn = db.begin().await?;
+    for embedding in embeddings {
+        txn.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"INSERT INTO refactor_platform.embeddings
+                   (source_type, source_id, coaching_relationship_id, coaching_session_id,
+                    content, model, embedding, created_at, updated_at)
+               VALUES ($1::refactor_platform.embedding_source_type, $2, $3, $4, $5, $6,
+                       $7::refactor_platform.vector, NOW(), NOW())
+               ON CONFLICT (source_type, source_id)
+               DO UPDATE SET content = EXCLUDED.content, model = EXCLUDED.model,
+                             embedding = EXCLUDED.embedding, updated_at = NOW()"#,
+            [
+                embedding.source_type.to_value().into(),
+                embedding.source_id.into(),
+                embedding.coaching_relationship_id.into(),
+                embedding.coaching_session_id.into(),
+                embedding.content.clone().into(),
+                embedding.model.clone().into(),
+                vector_literal(&embedding.vector).into(),
+            ],
+        ))
+        .await?;
+    }
+    txn.commit().await?;
+
+    Ok(())
+}
+
+/// Deletes embeddings whose source record is gone (or no longer has text, or was
+/// encrypted, or it or its session was soft-deleted) and those the relationship's privacy
+/// level no longer allows. Returns how many were removed.
+pub async fn delete_stale(db: &impl ConnectionTrait) -> Result<u64, Error> {
+    let stmt = Statement::from_string(
+        DatabaseBackend::Postgres,
+        r#"DELETE FROM refactor_platform.embeddings e
+           WHERE NOT EXISTS (
+                   SELECT 1 FROM refactor_platform.coaching_relationships r
+                   WHERE r.id = e.coaching_relationship_id
+                     AND (r.ai_privacy_level = 'full'
+                          OR (r.ai_privacy_level = 'notes_only' AND e.source_type = 'note')))
+              OR (e.source_type = 'note' AND NOT EXISTS (
+                   SELECT 1 FROM refactor_platform.notes n
+                   WHERE n.id = e.source_id
+                     AND regexp_replace(n.body, '<[^>]*>|\s+', '', 'g') <> ''
+                     AND n.body NOT LIKE 'enc:v1:%'
+                     AND n.deleted_at IS NULL))
+              OR (e.source_type = 'transcript_segment' AND NOT EXISTS (
+                   SELECT 1 FROM refactor_platform.transcript_segments ts
+                   WHERE ts.id = e.source_id))
+              OR EXISTS (
+                   SELECT 1 FROM refactor_platform.coaching_sessions s
+                   WHERE s.id = e.coaching_session_id AND s.deleted_at IS NOT NULL)"#,
+    );
+    Ok(db.execute(stmt).await?.rows_affected())
+}
+
+/// The `limit` embeddings of a relationship closest to `vector` by cosine distance,
+/// nearest first. Only vectors from `model` are compared; transcript segments are
+/// left out unless `include_transcripts`.
+pub async fn search(
+    db: &impl ConnectionTrait,
+    coaching_relationship_id: Id,
+    vector: &[f32],
+    model: &str,
+    include_transcripts: bool,
+    limit: u64,
+) -> Result<Vec<Match>, Error> {
+    let stmt = Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"SELECT source_type::text AS source_type, source_id, coaching_session_id, content,
+                  (embedding <=> $2::refactor_platform.vector)::float8 AS distance
+           FROM refactor_platform.embeddings
+           WHERE coaching_relationship_id = $1
+             AND model = $3
+             AND ($4 OR source_type = 'note')
+           ORDER BY distance
+           LIMIT $5"#,
+        [
+            coaching_relationship_id.into(),
+            vector_literal(vector).into(),
+            model.into(),
+            include_transcripts.into(),
+            limit.into(),
+        ],
+    );
+    Ok(Match::find_by_statement(stmt).all(db).await?)
+}
+
+/// pgvector's text input format.
+fn vector_literal(vector: &[f32]) -> String {
+    let components: Vec<String> = vector.iter().map(f32::to_string).collect();
+    format!("[{}]", components.join(","))
+}
+
+#[cfg(test)]
+// We need to gate seaORM's mock feature behind conditional compilation because
+// the feature removes the Clone trait implementation from seaORM's DatabaseConnection.
+// see https://github.com/SeaQL/sea-orm/issues/830
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase, Value};
+    use std::collections::BTreeMap;
+
+    #[tokio::test]
+    async fn search_passes_the_vector_as_a_pgvector_literal() -> Result<(), Error> {
+        let source_id = Id::new_v4();
+        let coaching_session_id = Id::new_v4();
+        let row = BTreeMap::from([
+            ("source_type", Value::from("transcript_segment")),
+            ("source_id", Value::from(source_id)),
+            ("coaching_session_id", Value::from(coaching_session_id)),
+            (
+                "content",
+                Value::from("Coachee: I keep redoing my team's work"),
+            ),
+            ("distance", Value::from(0.25_f64)),
+        ]);
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![row]])
+            .into_connection();
+
+        let matches = search(
+            &db,
+            Id::new_v4(),
+            &[0.5, -1.0, 0.25],
+            "text-embedding-3-small",
+            true,
+            12,
+        )
+        .await?;
+
+        assert_eq!(
+            matches[0].source_type,
+            EmbeddingSourceType::TranscriptSegment
+        );
+        assert_eq!(matches[0].source_id, source_id);
+        let log = db.into_transaction_log();
+        let values = log[0].statements()[0].values.as_ref().unwrap();
+        assert_eq!(
+            values.0[1],
+            Value::String(Some(Box::new("[0.5,-1,0.25]".to_string())))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn upsert_skips_the_transaction_for_no_embeddings() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
+
+        upsert(&db, &[]).await?;
+
+        assert!(db.into_transaction_log().is_empty());
+        Ok(())
+    }
+}
@@ -4,7 +4,7 @@ use std::fmt;
 
 use serde::Serialize;
 
-use sea_orm::error::DbErr;
+use sea_orm::error::{DbErr, RuntimeErr};
 
 use entity::duration::OutOfRange;
 
@@ -28,10 +28,14 @@ pub enum EntityApiErrorKind {
     RecordNotFound,
     // Record not updated
     RecordNotUpdated,
+    // Record changed since the version the write expected to replace
+    StaleVersion,
     // Record not authenticated
     RecordUnauthenticated,
     // Errors related to interactions with the database itself. Ex DbError::Conn
     SystemError,
+    // Postgres cancelled the statement for outrunning `statement_timeout`
+    StatementTimeout,
     // Validation error with descriptive message and optional structured details
     ValidationError {
         message: String,
@@ -95,8 +99,28 @@ impl fmt::Display for Error {
 
 impl StdError for Error {}
 
+/// SQLSTATE `query_canceled`, which Postgres raises when a statement outruns
+/// `statement_timeout`.
+const QUERY_CANCELED: &str = "57014";
+
+fn is_statement_timeout(err: &DbErr) -> bool {
+    match err {
+        DbErr::Exec(RuntimeErr::SqlxError(e)) | DbErr::Query(RuntimeErr::SqlxError(e)) => e
+            .as_database_error()
+            .and_then(|e| e.code())
+            .is_some_and(|code| code == QUERY_CANCELED),
+        _ => false,
+    }
+}
+
 impl From<DbErr> for Error {
     fn from(err: DbErr) -> Self {
+        if is_statement_timeout(&err) {
+            return Error {
+                source: Some(err),
+                error_kind: EntityApiErrorKind::StatementTimeout,
+            };
+        }
         match err {
             DbErr::RecordNotFound(_) => Error {
                 source: Some(err),
@@ -0,0 +1,620 @@
+//! Field-level encryption of note and journal entry bodies.
+//!
+//! An organization that treats its coaching notes as highly sensitive can have their
+//! bodies encrypted at rest. While it has an active data key (see
+//! [`crate::organization_data_key`]), [`crate::note`] and [`crate::journal_entry`]
+//! encrypt bodies as they're written and decrypt them as they're read, so callers only
+//! ever see plaintext. An encrypted body is stored as `enc:v1:<data key id>:<ciphertext>`;
+//! a body without the prefix is plaintext and is read as it is.
+//!
+//! Encrypted notes can't be matched with `ILIKE`, so unless turned off their words are
+//! kept in a blind index (`note_blind_index_tokens`): each distinct word as an HMAC keyed
+//! by the organization's data key and scoped to the relationship. A keyword search of a
+//! relationship's encrypted notes blinds its terms the same way and matches whole words.
+//!
+//! The cryptography is `domain::encryption`'s, which installs a [`FieldCipher`] at
+//! startup when `ENCRYPTION_KEY` is set. Without one, no extra queries are made, bodies
+//! are written in plaintext, and encrypted bodies can't be read.
+
+use super::error::{EntityApiErrorKind, Error};
+use super::organization_data_key;
+use entity::organization_data_keys::Model as DataKey;
+use entity::{
+    coaching_relationships, coaching_sessions, journal_entries, note_blind_index_tokens, notes, Id,
+};
+use log::*;
+use sea_orm::{
+    entity::prelude::*, sea_query::Expr, ActiveValue::Set, ConnectionTrait, QueryOrder,
+    QuerySelect, QueryTrait,
+};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{LazyLock, Mutex, OnceLock};
+
+/// Marks an encrypted body.
+pub const PREFIX: &str = "enc:v1:";
+
+/// How many rows [`reseal_organization`] rewrites per query.
+const RESEAL_BATCH_SIZE: u64 = 500;
+
+/// Most distinct words of a note kept in the blind index.
+const MAX_INDEXED_TERMS: usize = 2000;
+
+/// Encrypts and decrypts bodies with an organization's data key, given as stored.
+pub trait FieldCipher: Send + Sync {
+    fn encrypt(&self, wrapped_key: &str, plaintext: &str) -> Result<String, String>;
+    fn decrypt(&self, wrapped_key: &str, ciphertext: &str) -> Result<String, String>;
+    /// The blind index token of a normalized `term` within `scope`: deterministic for a
+    /// key, scope and term, and revealing nothing about the term without the key.
+    fn blind_index(&self, wrapped_key: &str, scope: Id, term: &str) -> Result<String, String>;
+}
+
+struct Installed {
+    cipher: Box<dyn FieldCipher>,
+    blind_index: bool,
+}
+
+static CIPHER: OnceLock<Installed> = OnceLock::new();
+
+/// Wrapped data keys by id. A key's material never changes, so entries never go stale.
+static DATA_KEYS: LazyLock<Mutex<HashMap<Id, String>>> = LazyLock::new(Default::default);
+
+/// Installs the process's cipher, and whether encrypted notes are kept searchable. Only
+/// the first call takes effect.
+pub fn install(cipher: Box<dyn FieldCipher>, blind_index: bool) {
+    if CIPHER
+        .set(Installed {
+            cipher,
+            blind_index,
+        })
+        .is_err()
+    {
+        warn!("A field cipher is already installed; ignoring another");
+    }
+}
+
+/// Whether this process can encrypt and decrypt bodies.
+pub fn is_installed() -> bool {
+    CIPHER.get().is_some()
+}
+
+/// Whether a stored body is encrypted.
+pub fn is_encrypted(body: &str) -> bool {
+    body.starts_with(PREFIX)
+}
+
+fn installed() -> Option<&'static dyn FieldCipher> {
+    CIPHER.get().map(|installed| installed.cipher.as_ref())
+}
+
+/// The cipher, if encrypted notes are kept searchable.
+fn indexing() -> Option<&'static dyn FieldCipher> {
+    CIPHER
+        .get()
+        .filter(|installed| installed.blind_index)
+        .map(|installed| installed.cipher.as_ref())
+}
+
+/// The distinct words of `text` as the blind index keeps them: markup dropped,
+/// lowercased, split on anything but letters and digits, and at least 2 characters long.
+pub fn blind_index_terms(text: &str) -> BTreeSet<String> {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '<' => {
+                chars.by_ref().find(|&c| c == '>');
+                plain.push(' ');
+            }
+            // A character reference such as `&amp;` separates words like markup does
+            '&' => {
+                let name = chars
+                    .clone()
+                    .take_while(|c| c.is_ascii_alphanumeric() || *c == '#')
+                    .count();
+                if name > 0 && chars.clone().nth(name) == Some(';') {
+                    chars.nth(name);
+                }
+                plain.push(' ');
+            }
+            _ => plain.extend(c.to_lowercase()),
+        }
+    }
+    plain
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() >= 2)
+        .take(MAX_INDEXED_TERMS * 4)
+        .map(str::to_string)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .take(MAX_INDEXED_TERMS)
+        .collect()
+}
+
+/// Encrypts a note body if the session's organization encrypts its bodies.
+pub(crate) async fn seal_note_body(
+    db: &impl ConnectionTrait,
+    coaching_session_id: Id,
+    body: Option<String>,
+) -> Result<Option<String>, Error> {
+    match (installed(), body) {
+        (Some(cipher), Some(body)) => {
+            let key =
+                organization_data_key::find_active_for_session(db, coaching_session_id).await?;
+            Ok(Some(seal(cipher, key.as_ref(), body)?))
+        }
+        (_, body) => Ok(body),
+    }
+}
+
+/// Encrypts a journal entry body if the relationship's organization encrypts its bodies.
+pub(crate) async fn seal_journal_entry_body(
+    db: &impl ConnectionTrait,
+    coaching_relationship_id: Id,
+    body: String,
+) -> Result<String, Error> {
+    let Some(cipher) = installed() else {
+        return Ok(body);
+    };
+    let key =
+        organization_data_key::find_active_for_relationship(db, coaching_relationship_id).await?;
+    seal(cipher, key.as_ref(), body)
+}
+
+/// Decrypts the bodies of `notes` that are encrypted.
+pub(crate) async fn open_notes(
+    db: &impl ConnectionTrait,
+    mut notes: Vec<notes::Model>,
+) -> Result<Vec<notes::Model>, Error> {
+    for note in &mut notes {
+        if let Some(body) = note.body.take() {
+            note.body = Some(open(db, installed(), body).await?);
+        }
+    }
+    Ok(notes)
+}
+
+pub(crate) async fn open_note(
+    db: &impl ConnectionTrait,
+    note: notes::Model,
+) -> Result<notes::Model, Error> {
+    Ok(open_notes(db, vec![note]).await?.remove(0))
+}
+
+/// Refreshes the blind index of a note just written, if it's encrypted, and decrypts it.
+pub(crate) async fn index_and_open_note(
+    db: &impl ConnectionTrait,
+    note: notes::Model,
+) -> Result<notes::Model, Error> {
+    if let (Some(cipher), Some(body)) = (indexing(), note.body.as_deref()) {
+        if is_encrypted(body) {
+            index_note(db, cipher, note.id, note.coaching_session_id, body).await?;
+        }
+    }
+    open_note(db, note).await
+}
+
+/// The blind index tokens `terms` would have in the relationship's encrypted notes, or
+/// `None` if its organization doesn't encrypt or encrypted notes aren't searchable.
+pub(crate) async fn blind_index_tokens(
+    db: &impl ConnectionTrait,
+    coaching_relationship_id: Id,
+    terms: &[String],
+) -> Result<Option<Vec<String>>, Error> {
+    let Some(cipher) = indexing() else {
+        return Ok(None);
+    };
+    let Some(key) =
+        organization_data_key::find_active_for_relationship(db, coaching_relationship_id).await?
+    else {
+        return Ok(None);
+    };
+    let terms: BTreeSet<String> = terms
+        .iter()
+        .flat_map(|term| blind_index_terms(term))
+        .collect();
+    let tokens = terms
+        .iter()
+        .map(|term| blind_index(cipher, &key.wrapped_key, coaching_relationship_id, term))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Some(tokens))
+}
+
+/// Decrypts the bodies of `entries` that are encrypted.
+pub(crate) async fn open_journal_entries(
+    db: &impl ConnectionTrait,
+    mut entries: Vec<journal_entries::Model>,
+) -> Result<Vec<journal_entries::Model>, Error> {
+    for entry in &mut entries {
+        entry.body = open(db, installed(), std::mem::take(&mut entry.body)).await?;
+    }
+    Ok(entries)
+}
+
+pub(crate) async fn open_journal_entry(
+    db: &impl ConnectionTrait,
+    entry: journal_entries::Model,
+) -> Result<journal_entries::Model, Error> {
+    Ok(open_journal_entries(db, vec![entry]).await?.remove(0))
+}
+
+/// How many bodies [`reseal_organization`] rewrote.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Resealed {
+    pub notes: u64,
+    pub journal_entries: u64,
+}
+
+/// Rewrites every note and journal entry body of the organization that isn't sealed
+/// with its active key: plaintext bodies are encrypted, bodies sealed with a retired key
+/// are re-encrypted, and with no active key every body is decrypted. The notes' blind
+/// index is rebuilt. `updated_at` is left alone, so nobody's unread markers change.
+pub async fn reseal_organization(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+) -> Result<Resealed, Error> {
+    let Some(cipher) = installed() else {
+        return Err(not_installed());
+    };
+    let key = organization_data_key::find_active(db, organization_id).await?;
+
+    let relationship_ids = coaching_relationships::Entity::find()
+        .select_only()
+        .column(coaching_relationships::Column::Id)
+        .filter(coaching_relationships::Column::OrganizationId.eq(organization_id))
+        .into_query();
+    let session_ids = coaching_sessions::Entity::find()
+        .select_only()
+        .column(coaching_sessions::Column::Id)
+        .filter(
+            coaching_sessions::Column::CoachingRelationshipId.in_subquery(relationship_ids.clone()),
+        )
+        .into_query();
+
+    let mut resealed = Resealed::default();
+    let mut after = None;
+    loop {
+        let mut query = notes::Entity::find()
+            .filter(notes::Column::CoachingSessionId.in_subquery(session_ids.clone()))
+            .filter(notes::Column::Body.is_not_null())
+            .order_by_asc(notes::Column::Id)
+            .limit(RESEAL_BATCH_SIZE);
+        if let Some(after) = after {
+            query = query.filter(notes::Column::Id.gt(after));
+        }
+        let batch = query.all(db).await?;
+        let Some(last) = batch.last() else { break };
+        after = Some(last.id);
+
+        for note in batch {
+            let Some(body) = note.body else { continue };
+            let body = match reseal(db, cipher, key.as_ref(), body.clone()).await? {
+                Some(body) => {
+                    notes::Entity::update_many()
+                        .col_expr(notes::Column::Body, Expr::value(body.clone()))
+                        .filter(notes::Column::Id.eq(note.id))
+                        .exec(db)
+                        .await?;
+                    resealed.notes += 1;
+                    body
+                }
+                None => body,
+            };
+            // Rebuilt for every note, so this also indexes notes written while the
+            // index was turned off, and clears the index of decrypted ones.
+            if let Some(cipher) = indexing() {
+                index_note(db, cipher, note.id, note.coaching_session_id, &body).await?;
+            }
+        }
+    }
+
+    let mut after = None;
+    loop {
+        let mut query = journal_entries::Entity::find()
+            .filter(
+                journal_entries::Column::CoachingRelationshipId
+                    .in_subquery(relationship_ids.clone()),
+            )
+            .order_by_asc(journal_entries::Column::Id)
+            .limit(RESEAL_BATCH_SIZE);
+        if let Some(after) = after {
+            query = query.filter(journal_entries::Column::Id.gt(after));
+        }
+        let batch = query.all(db).await?;
+        let Some(last) = batch.last() else { break };
+        after = Some(last.id);
+
+        for entry in batch {
+            if let Some(body) = reseal(db, cipher, key.as_ref(), entry.body).await? {
+                journal_entries::Entity::update_many()
+                    .col_expr(journal_entries::Column::Body, Expr::value(body))
+                    .filter(journal_entries::Column::Id.eq(entry.id))
+                    .exec(db)
+                    .await?;
+                resealed.journal_entries += 1;
+            }
+        }
+    }
+
+    info!(
+        "Resealed {} note(s) and {} journal entry(ies) of organization {organization_id}",
+        resealed.notes, resealed.journal_entries
+    );
+    Ok(resealed)
+}
+
+/// The body rewritten for `key`, or `None` if it's already as it should be.
+async fn reseal(
+    db: &impl ConnectionTrait,
+    cipher: &dyn FieldCipher,
+    key: Option<&DataKey>,
+    body: String,
+) -> Result<Option<String>, Error> {
+    let sealed_with = parse(&body).map(|(key_id, _)| key_id);
+    if sealed_with == key.map(|key| key.id) {
+        return Ok(None);
+    }
+    let plaintext = open(db, Some(cipher), body).await?;
+    Ok(Some(seal(cipher, key, plaintext)?))
+}
+
+/// Replaces a note's blind index tokens with those of its stored `body`; a plaintext body
+/// has none.
+async fn index_note(
+    db: &impl ConnectionTrait,
+    cipher: &dyn FieldCipher,
+    note_id: Id,
+    coaching_session_id: Id,
+    body: &str,
+) -> Result<(), Error> {
+    note_blind_index_tokens::Entity::delete_many()
+        .filter(note_blind_index_tokens::Column::NoteId.eq(note_id))
+        .exec(db)
+        .await?;
+    let Some((key_id, _)) = parse(body) else {
+        return Ok(());
+    };
+
+    let Some(session) = coaching_sessions::Entity::find_by_id(coaching_session_id)
+        .one(db)
+        .await?
+    else {
+        return Ok(());
+    };
+    let relationship_id = session.coaching_relationship_id;
+    let wrapped_key = wrapped_key(db, key_id).await?;
+    let plaintext = open(db, Some(cipher), body.to_string()).await?;
+
+    let tokens = blind_index_terms(&plaintext)
+        .iter()
+        .map(|term| {
+            blind_index(cipher, &wrapped_key, relationship_id, term).map(|token| {
+                note_blind_index_tokens::ActiveModel {
+                    note_id: Set(note_id),
+                    coaching_relationship_id: Set(relationship_id),
+                    token: Set(token),
+                }
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if !tokens.is_empty() {
+        note_blind_index_tokens::Entity::insert_many(tokens)
+            .exec_without_returning(db)
+            .await?;
+    }
+    Ok(())
+}
+
+fn blind_index(
+    cipher: &dyn FieldCipher,
+    wrapped_key: &str,
+    scope: Id,
+    term: &str,
+) -> Result<String, Error> {
+    cipher
+        .blind_index(wrapped_key, scope, term)
+        .map_err(|e| cipher_error(format!("Failed to blind a search term: {e}")))
+}
+
+/// `plaintext` encrypted with `key`, or as it is without one.
+fn seal(
+    cipher: &dyn FieldCipher,
+    key: Option<&DataKey>,
+    plaintext: String,
+) -> Result<String, Error> {
+    let Some(key) = key else {
+        return Ok(plaintext);
+    };
+    remember(key);
+    let ciphertext = cipher
+        .encrypt(&key.wrapped_key, &plaintext)
+        .map_err(|e| cipher_error(format!("Failed to encrypt a body: {e}")))?;
+    Ok(format!("{PREFIX}{}:{ciphertext}", key.id))
+}
+
+/// `body` decrypted if it's encrypted, or as it is.
+async fn open(
+    db: &impl ConnectionTrait,
+    cipher: Option<&dyn FieldCipher>,
+    body: String,
+) -> Result<String, Error> {
+    let Some((key_id, ciphertext)) = parse(&body) else {
+        return Ok(body);
+    };
+    let Some(cipher) = cipher else {
+        return Err(not_installed());
+    };
+    let wrapped_key = wrapped_key(db, key_id).await?;
+    cipher
+        .decrypt(&wrapped_key, ciphertext)
+        .map_err(|e| cipher_error(format!("Failed to decrypt a body: {e}")))
+}
+
+/// The data key id and ciphertext of an encrypted body.
+fn parse(body: &str) -> Option<(Id, &str)> {
+    let (key_id, ciphertext) = body.strip_prefix(PREFIX)?.split_once(':')?;
+    Some((Id::parse_str(key_id).ok()?, ciphertext))
+}
+
+async fn wrapped_key(db: &impl ConnectionTrait, key_id: Id) -> Result<String, Error> {
+    if let Some(wrapped_key) = DATA_KEYS.lock().unwrap().get(&key_id) {
+        return Ok(wrapped_key.clone());
+    }
+    let key = organization_data_key::find_by_id(db, key_id)
+        .await?
+        .ok_or_else(|| cipher_error(format!("Data key {key_id} not found")))?;
+    remember(&key);
+    Ok(key.wrapped_key)
+}
+
+fn remember(key: &DataKey) {
+    DATA_KEYS
+        .lock()
+        .unwrap()
+        .entry(key.id)
+        .or_insert_with(|| key.wrapped_key.clone());
+}
+
+fn not_installed() -> Error {
+    cipher_error("Encrypted bodies can't be read or written without ENCRYPTION_KEY".to_string())
+}
+
+fn cipher_error(message: String) -> Error {
+    error!("{message}");
+    Error {
+        source: None,
+        error_kind: EntityApiErrorKind::Other(message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reverses the plaintext and tags it with the wrapped key.
+    struct Reversing;
+
+    impl FieldCipher for Reversing {
+        fn encrypt(&self, wrapped_key: &str, plaintext: &str) -> Result<String, String> {
+            Ok(format!(
+                "{wrapped_key}/{}",
+                plaintext.chars().rev().collect::<String>()
+            ))
+        }
+
+        fn decrypt(&self, wrapped_key: &str, ciphertext: &str) -> Result<String, String> {
+            let reversed = ciphertext
+                .strip_prefix(&format!("{wrapped_key}/"))
+                .ok_or("wrong key")?;
+            Ok(reversed.chars().rev().collect())
+        }
+
+        fn blind_index(&self, wrapped_key: &str, scope: Id, term: &str) -> Result<String, String> {
+            Ok(format!("{wrapped_key}/{scope}/{term}"))
+        }
+    }
+
+    fn data_key(wrapped_key: &str) -> DataKey {
+        DataKey {
+            id: Id::new_v4(),
+            organization_id: Id::new_v4(),
+            wrapped_key: wrapped_key.to_string(),
+            created_at: chrono::Utc::now().into(),
+            retired_at: None,
+        }
+    }
+
+    #[test]
+    fn sealed_bodies_name_their_key() {
+        let key = data_key("k1");
+
+        let sealed = seal(&Reversing, Some(&key), "hello".to_string()).unwrap();
+
+        assert_eq!(sealed, format!("enc:v1:{}:k1/olleh", key.id));
+        assert_eq!(parse(&sealed), Some((key.id, "k1/olleh")));
+        assert!(is_encrypted(&sealed));
+    }
+
+    #[test]
+    fn blind_index_terms_are_the_distinct_lowercased_words() {
+        let terms = blind_index_terms("<p>Delegation, <b>delegation</b> &amp; a Q3-plan!</p>");
+
+        assert_eq!(
+            terms.into_iter().collect::<Vec<_>>(),
+            ["delegation", "plan", "q3"]
+        );
+    }
+
+    #[test]
+    fn without_a_key_bodies_stay_plaintext() {
+        assert_eq!(
+            seal(&Reversing, None, "hello".to_string()).unwrap(),
+            "hello"
+        );
+        assert_eq!(parse("hello"), None);
+        assert_eq!(parse("enc:v1:not-a-uuid:abc"), None);
+    }
+
+    #[cfg(feature = "mock")]
+    mod mock_tests {
+        use super::*;
+        use sea_orm::{DatabaseBackend, MockDatabase};
+
+        #[tokio::test]
+        async fn plaintext_bodies_are_read_without_queries() {
+            let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
+
+            let body = open(&db, None, "<p>hello</p>".to_string()).await.unwrap();
+
+            assert_eq!(body, "<p>hello</p>");
+            assert!(db.into_transaction_log().is_empty());
+        }
+
+        #[tokio::test]
+        async fn encrypted_bodies_are_decrypted_with_the_named_key() {
+            let key = data_key("k2");
+            let sealed = format!("enc:v1:{}:k2/olleh", key.id);
+            let db = MockDatabase::new(DatabaseBackend::Postgres)
+                .append_query_results([vec![key]])
+                .into_connection();
+
+            let body = open(&db, Some(&Reversing), sealed).await.unwrap();
+
+            assert_eq!(body, "hello");
+        }
+
+        #[tokio::test]
+        async fn encrypted_bodies_cant_be_read_without_a_cipher() {
+            let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
+            let sealed = format!("enc:v1:{}:k/olleh", Id::new_v4());
+
+            assert!(open(&db, None, sealed).await.is_err());
+        }
+
+        #[tokio::test]
+        async fn reseal_leaves_bodies_sealed_with_the_active_key_alone() {
+            let (old, new) = (data_key("old"), data_key("new"));
+            let db = MockDatabase::new(DatabaseBackend::Postgres)
+                .append_query_results([vec![old.clone()]])
+                .into_connection();
+
+            let current = seal(&Reversing, Some(&new), "hi".to_string()).unwrap();
+            assert_eq!(
+                reseal(&db, &Reversing, Some(&new), current).await.unwrap(),
+                None
+            );
+
+            let stale = seal(&Reversing, Some(&old), "hi".to_string()).unwrap();
+            let resealed = reseal(&db, &Reversing, Some(&new), stale)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(parse(&resealed), Some((new.id, "new/ih")));
+
+            let plaintext = reseal(&db, &Reversing, Some(&new), "hi".to_string())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(parse(&plaintext), Some((new.id, "new/ih")));
+        }
+    }
+}
@@ -1,6 +1,10 @@
+use std::collections::HashMap;
+
+use super::coaching_session::{batch_load_relationships, batch_load_sessions};
 use super::error::{EntityApiErrorKind, Error};
+use super::mutate::{update_if_version, ExpectedVersion};
 use entity::goals::{ActiveModel, Column, Entity, Model};
-use entity::{status::Status, Id};
+use entity::{coaching_relationships, coaching_sessions, status::Status, Id};
 use sea_orm::ActiveValue;
 use sea_orm::{
     entity::prelude::*,
@@ -8,6 +12,8 @@ use sea_orm::{
     ActiveValue::{Set, Unchanged},
     ConnectionTrait, DatabaseConnection, QueryFilter, TransactionTrait, TryIntoModel,
 };
+use serde::Serialize;
+use utoipa::ToSchema;
 
 use log::*;
 
@@ -50,8 +56,17 @@ pub async fn create(
     Ok(goal_active_model.save(db).await?.try_into_model()?)
 }
 
-pub async fn update(db: &DatabaseConnection, id: Id, model: Model) -> Result<Model, Error> {
-    let result = Entity::find_by_id(id).one(db).await?;
+/// Updates a goal, provided it's still at a version `expected` names.
+pub async fn update(
+    db: &DatabaseConnection,
+    id: Id,
+    model: Model,
+    expected: &ExpectedVersion,
+) -> Result<Model, Error> {
+    let result = Entity::find_by_id(id)
+        .filter(Column::DeletedAt.is_null())
+        .one(db)
+        .await?;
 
     match result {
         Some(goal) => {
@@ -84,9 +99,10 @@ pub async fn update(db: &DatabaseConnection, id: Id, model: Model) -> Result<Mod
                 target_date: Set(model.target_date),
                 updated_at: Set(chrono::Utc::now().into()),
                 created_at: Unchanged(goal.created_at),
+                deleted_at: Unchanged(goal.deleted_at),
             };
 
-            Ok(active_model.update(db).await?.try_into_model()?)
+            update_if_version(db, active_model, Column::UpdatedAt, expected).await
         }
         None => {
             error!("Goal with id {id} not found");
@@ -99,12 +115,17 @@ pub async fn update(db: &DatabaseConnection, id: Id, model: Model) -> Result<Mod
     }
 }
 
+/// Sets a goal's status, provided it's still at a version `expected` names.
 pub async fn update_status(
     db: &DatabaseConnection,
     id: Id,
     status: Status,
+    expected: &ExpectedVersion,
 ) -> Result<Model, Error> {
-    let result = Entity::find_by_id(id).one(db).await?;
+    let result = Entity::find_by_id(id)
+        .filter(Column::DeletedAt.is_null())
+        .one(db)
+        .await?;
 
     match result {
         Some(goal) => {
@@ -128,9 +149,10 @@ pub async fn update_status(
                 target_date: Unchanged(goal.target_date),
                 updated_at: Set(chrono::Utc::now().into()),
                 created_at: Unchanged(goal.created_at),
+                deleted_at: Unchanged(goal.deleted_at),
             };
 
-            Ok(active_model.update(db).await?.try_into_model()?)
+            update_if_version(db, active_model, Column::UpdatedAt, expected).await
         }
         None => {
             error!("Goal with id {id} not found");
@@ -143,25 +165,63 @@ pub async fn update_status(
     }
 }
 
+/// Soft-deletes a goal: it's left out of reads until restored, or purged once past the
+/// retention window. Returns the deleted goal.
 pub async fn delete_by_id(db: &DatabaseConnection, id: Id) -> Result<Model, Error> {
+    let goal = find_by_id(db, id).await?;
+    let mut active_model: ActiveModel = goal.into();
+    active_model.deleted_at = Set(Some(chrono::Utc::now().into()));
+    Ok(active_model.update(db).await?.try_into_model()?)
+}
+
+/// Restores a soft-deleted goal. An in-progress goal counts against its relationship's
+/// limit again, so it's restored only while under the limit.
+pub async fn restore(db: &DatabaseConnection, id: Id) -> Result<Model, Error> {
     let txn = db.begin().await?;
-    let goal = Entity::find_by_id(id)
-        .one(&txn)
+    let goal = find_deleted_by_id(&txn, id).await?;
+    if goal.in_progress() {
+        check_in_progress_goal_limit(&txn, goal.coaching_relationship_id).await?;
+    }
+    let mut active_model: ActiveModel = goal.into();
+    active_model.deleted_at = Set(None);
+    let goal = active_model.update(&txn).await?.try_into_model()?;
+    txn.commit().await?;
+    Ok(goal)
+}
+
+/// Permanently deletes the goals soft-deleted before `cutoff`, returning how many.
+pub async fn purge_deleted_before(
+    db: &impl ConnectionTrait,
+    cutoff: DateTimeWithTimeZone,
+) -> Result<u64, Error> {
+    let result = Entity::delete_many()
+        .filter(Column::DeletedAt.lt(cutoff))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected)
+}
+
+pub async fn find_by_id(db: &DatabaseConnection, id: Id) -> Result<Model, Error> {
+    Entity::find_by_id(id)
+        .filter(Column::DeletedAt.is_null())
+        .one(db)
         .await?
         .ok_or_else(|| Error {
             source: None,
             error_kind: EntityApiErrorKind::RecordNotFound,
-        })?;
-    Entity::delete_by_id(id).exec(&txn).await?;
-    txn.commit().await?;
-    Ok(goal)
+        })
 }
 
-pub async fn find_by_id(db: &DatabaseConnection, id: Id) -> Result<Model, Error> {
-    Entity::find_by_id(id).one(db).await?.ok_or_else(|| Error {
-        source: None,
-        error_kind: EntityApiErrorKind::RecordNotFound,
-    })
+/// Like [`find_by_id`], but finds the goal only while it's soft-deleted.
+pub async fn find_deleted_by_id(db: &impl ConnectionTrait, id: Id) -> Result<Model, Error> {
+    Entity::find_by_id(id)
+        .filter(Column::DeletedAt.is_not_null())
+        .one(db)
+        .await?
+        .ok_or_else(|| Error {
+            source: None,
+            error_kind: EntityApiErrorKind::RecordNotFound,
+        })
 }
 
 /// Finds all in-progress goals (`InProgress` status) for a given coaching relationship.
@@ -174,6 +234,7 @@ pub async fn find_in_progress_goals_by_coaching_relationship_id(
     coaching_relationship_id: Id,
 ) -> Result<Vec<Model>, Error> {
     Ok(Entity::find()
+        .filter(Column::DeletedAt.is_null())
         .filter(Column::CoachingRelationshipId.eq(coaching_relationship_id))
         .filter(Column::Status.eq(Status::InProgress))
         .all(db)
@@ -218,6 +279,63 @@ pub(crate) async fn check_in_progress_goal_limit(
     Ok(())
 }
 
+/// A goal with the related resources requested via `?include=`, batch-loaded for the
+/// whole list the way [`super::coaching_session::EnrichedSession`] is. Related resources
+/// only appear in the JSON when requested.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[schema(as = domain::goal::EnrichedGoal)]
+pub struct EnrichedGoal {
+    #[serde(flatten)]
+    pub goal: Model,
+    /// The session the goal was created in; absent for goals created outside a session
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session: Option<coaching_sessions::Model>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relationship: Option<coaching_relationships::Model>,
+}
+
+/// Which related resources to batch-load alongside a list of goals.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IncludeOptions {
+    pub session: bool,
+    pub relationship: bool,
+}
+
+/// Attaches the requested related resources to `goals` with one `IN` query per kind of
+/// resource. Runs no queries when nothing is requested.
+pub async fn with_includes(
+    db: &impl ConnectionTrait,
+    goals: Vec<Model>,
+    includes: IncludeOptions,
+) -> Result<Vec<EnrichedGoal>, Error> {
+    let mut sessions = HashMap::new();
+    if includes.session {
+        let session_ids: Vec<Id> = goals
+            .iter()
+            .filter_map(|g| g.created_in_session_id)
+            .collect();
+        sessions = batch_load_sessions(db, &session_ids).await?;
+    }
+
+    let mut relationships = HashMap::new();
+    if includes.relationship {
+        let relationship_ids: Vec<Id> = goals.iter().map(|g| g.coaching_relationship_id).collect();
+        relationships = batch_load_relationships(db, &relationship_ids).await?;
+    }
+
+    Ok(goals
+        .into_iter()
+        .map(|goal| EnrichedGoal {
+            session: goal
+                .created_in_session_id
+                .and_then(|id| sessions.get(&id))
+                .cloned(),
+            relationship: relationships.get(&goal.coaching_relationship_id).cloned(),
+            goal,
+        })
+        .collect())
+}
+
 #[cfg(test)]
 // We need to gate seaORM's mock feature behind conditional compilation because
 // the feature removes the Clone trait implementation from seaORM's DatabaseConnection.
@@ -245,6 +363,7 @@ mod tests {
             target_date: None,
             created_at: now.into(),
             updated_at: now.into(),
+            deleted_at: None,
         };
 
         // Default status is InProgress, so the limit check runs first (returns empty → under limit)
@@ -277,13 +396,20 @@ mod tests {
             target_date: None,
             created_at: now.into(),
             updated_at: now.into(),
+            deleted_at: None,
         };
 
         let db = MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results(vec![vec![goal_model.clone()], vec![goal_model.clone()]])
             .into_connection();
 
-        let goal = update(&db, goal_model.id, goal_model.clone()).await?;
+        let goal = update(
+            &db,
+            goal_model.id,
+            goal_model.clone(),
+            &ExpectedVersion::Any,
+        )
+        .await?;
 
         assert_eq!(goal.body, goal_model.body);
 
@@ -307,6 +433,7 @@ mod tests {
             target_date: None,
             created_at: now.into(),
             updated_at: now.into(),
+            deleted_at: None,
         };
 
         let updated_goal_model = Model {
@@ -322,6 +449,7 @@ mod tests {
             target_date: None,
             created_at: now.into(),
             updated_at: now.into(),
+            deleted_at: None,
         };
 
         let db = MockDatabase::new(DatabaseBackend::Postgres)
@@ -331,7 +459,8 @@ mod tests {
             ])
             .into_connection();
 
-        let goal = update_status(&db, goal_model.id, Status::Completed).await?;
+        let goal =
+            update_status(&db, goal_model.id, Status::Completed, &ExpectedVersion::Any).await?;
 
         assert_eq!(goal.status, Status::Completed);
 
@@ -342,7 +471,8 @@ mod tests {
     async fn update_status_returns_error_when_goal_not_found() -> Result<(), Error> {
         let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
 
-        let result = update_status(&db, Id::new_v4(), Status::Completed).await;
+        let result =
+            update_status(&db, Id::new_v4(), Status::Completed, &ExpectedVersion::Any).await;
 
         assert!(result.is_err());
 
@@ -367,6 +497,7 @@ mod tests {
             target_date: None,
             created_at: now.into(),
             updated_at: now.into(),
+            deleted_at: None,
         };
 
         let db = MockDatabase::new(DatabaseBackend::Postgres)
@@ -414,6 +545,7 @@ mod tests {
             target_date: None,
             created_at: now.into(),
             updated_at: now.into(),
+            deleted_at: None,
         }
     }
 
@@ -529,7 +661,13 @@ mod tests {
             .append_query_results(vec![in_progress_goals])
             .into_connection();
 
-        let result = update_status(&db, current_goal.id, Status::InProgress).await;
+        let result = update_status(
+            &db,
+            current_goal.id,
+            Status::InProgress,
+            &ExpectedVersion::Any,
+        )
+        .await;
 
         assert!(result.is_err());
         let err = result.unwrap_err();
@@ -556,8 +694,43 @@ mod tests {
             .append_query_results(vec![vec![current_goal.clone()]])
             .into_connection();
 
-        let result = update_status(&db, current_goal.id, Status::InProgress).await;
+        let result = update_status(
+            &db,
+            current_goal.id,
+            Status::InProgress,
+            &ExpectedVersion::Any,
+        )
+        .await;
 
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn with_includes_runs_no_queries_when_nothing_is_included() -> Result<(), Error> {
+        let now = chrono::Utc::now();
+        let goal = Model {
+            id: Id::new_v4(),
+            user_id: Id::new_v4(),
+            coaching_relationship_id: Id::new_v4(),
+            created_in_session_id: Some(Id::new_v4()),
+            title: Some("title".to_owned()),
+            body: None,
+            status_changed_at: None,
+            status: Default::default(),
+            completed_at: None,
+            target_date: None,
+            created_at: now.into(),
+            updated_at: now.into(),
+            deleted_at: None,
+        };
+        let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
+
+        let enriched = with_includes(&db, vec![goal.clone()], IncludeOptions::default()).await?;
+
+        assert_eq!(enriched[0].goal, goal);
+        assert_eq!(enriched[0].session, None);
+        assert_eq!(enriched[0].relationship, None);
+        assert!(db.into_transaction_log().is_empty());
+        Ok(())
+    }
 }
@@ -12,6 +12,7 @@ use sea_orm::{
 
 use log::*;
 
+use super::action_work_log;
 use super::error::{EntityApiErrorKind, Error};
 use entity::{
     actions, actions_users, coaching_sessions, coaching_sessions_goals, goals, status::Status, Id,
@@ -59,6 +60,8 @@ pub struct ProgressData {
     pub linked_coaching_session_count: usize,
     /// Date of the most recent linked coaching session.
     pub last_coaching_session_date: Option<DateTime>,
+    /// Seconds of focused work logged across the goal's actions.
+    pub total_work_seconds: i64,
 }
 
 /// Gathers all data needed to compute progress metrics for a goal.
@@ -76,6 +79,11 @@ pub async fn gather_progress_data(
     let goal = find_goal(db, goal_id).await?;
     let actions = find_actions_for_goal(db, goal_id).await?;
     let action_stats = summarize_action_stats(&actions);
+    let action_ids: Vec<Id> = actions.iter().map(|a| a.id).collect();
+    let total_work_seconds = action_work_log::find_totals(db, &action_ids)
+        .await?
+        .values()
+        .sum();
     let coaching_session_stats = find_linked_coaching_session_stats(db, goal_id).await?;
 
     debug!(
@@ -91,9 +99,38 @@ pub async fn gather_progress_data(
         next_action_due: action_stats.next_due,
         linked_coaching_session_count: coaching_session_stats.count,
         last_coaching_session_date: coaching_session_stats.last_date,
+        total_work_seconds,
     })
 }
 
+/// Raw data for a goal's effort burndown: the goal and its live actions.
+pub struct BurndownData {
+    /// The goal itself.
+    pub goal: goals::Model,
+    /// Every non-deleted action linked to the goal.
+    pub actions: Vec<actions::Model>,
+}
+
+/// Gathers the goal and its actions for burndown computation.
+///
+/// # Errors
+///
+/// Returns `Error` if the goal is not found or any database query fails.
+pub async fn gather_burndown_data(
+    db: &impl ConnectionTrait,
+    goal_id: Id,
+) -> Result<BurndownData, Error> {
+    let goal = find_goal(db, goal_id).await?;
+    let actions = find_actions_for_goal(db, goal_id).await?;
+
+    debug!(
+        "Burndown data for goal {goal_id}: {} actions linked",
+        actions.len()
+    );
+
+    Ok(BurndownData { goal, actions })
+}
+
 /// Aggregate row for action stats per goal.
 #[derive(Debug, FromQueryResult)]
 struct ActionStatsRow {
@@ -101,6 +138,7 @@ struct ActionStatsRow {
     actions_total: i64,
     actions_completed: i64,
     next_action_due: Option<DateTimeWithTimeZone>,
+    total_work_seconds: i64,
 }
 
 /// Aggregate row for session stats per goal.
@@ -122,7 +160,7 @@ struct CompletedDateRow {
 ///
 /// Uses 3-4 optimized queries regardless of goal count:
 /// 1. All goals for the relationship
-/// 2. Action stats per goal (total, completed, next due) via `GROUP BY` with `CASE WHEN`
+/// 2. Action stats per goal (total, completed, next due, logged work) via `GROUP BY` with `CASE WHEN`
 /// 3. Session stats per goal (count, last date) via `GROUP BY` with `JOIN`
 /// 4. (conditional) Completed action dates for momentum-based goals only
 ///
@@ -136,7 +174,8 @@ pub async fn gather_batch_progress_data(
 ) -> Result<Vec<ProgressData>, Error> {
     // Query 1: Goals for the coaching relationship, with optional filter/sort/limit.
     let query = goals::Entity::find()
-        .filter(goals::Column::CoachingRelationshipId.eq(coaching_relationship_id));
+        .filter(goals::Column::CoachingRelationshipId.eq(coaching_relationship_id))
+        .filter(goals::Column::DeletedAt.is_null());
 
     let query = match &params.status {
         Some(status) => query.filter(goals::Column::Status.eq(status.clone())),
@@ -176,7 +215,8 @@ pub async fn gather_batch_progress_data(
     let goal_ids: Vec<Id> = goals.iter().map(|g| g.id).collect();
 
     // Query 2: Action stats aggregated per goal — single query with CASE WHEN
-    // for conditional count (completed) and conditional MIN (next due for non-completed).
+    // for conditional count (completed) and conditional MIN (next due for non-completed),
+    // plus a correlated subquery summing each action's logged work.
     // When `assignee_user_id` is set, INNER JOIN actions_users so counts only include
     // actions assigned to that user.
     let action_stats_query = actions::Entity::find()
@@ -191,7 +231,16 @@ pub async fn gather_batch_progress_data(
             Expr::cust("MIN(CASE WHEN status != 'completed' THEN due_by END)"),
             "next_action_due",
         )
-        .filter(actions::Column::GoalId.is_in(goal_ids.clone()));
+        .column_as(
+            Expr::cust(
+                r#"COALESCE(SUM((SELECT SUM(w.duration_seconds)
+                     FROM refactor_platform.action_work_logs w
+                     WHERE w.action_id = "actions"."id")), 0)::bigint"#,
+            ),
+            "total_work_seconds",
+        )
+        .filter(actions::Column::GoalId.is_in(goal_ids.clone()))
+        .filter(actions::Column::DeletedAt.is_null());
 
     let action_stats_query = match params.assignee_user_id {
         Some(user_id) => action_stats_query
@@ -226,6 +275,7 @@ pub async fn gather_batch_progress_data(
             coaching_sessions_goals::Relation::CoachingSessions.def(),
         )
         .filter(coaching_sessions_goals::Column::GoalId.is_in(goal_ids.clone()))
+        .filter(coaching_sessions::Column::DeletedAt.is_null())
         .group_by(coaching_sessions_goals::Column::GoalId)
         .into_model::<SessionStatsRow>()
         .all(db)
@@ -251,6 +301,7 @@ pub async fn gather_batch_progress_data(
                 .column(actions::Column::GoalId)
                 .column(actions::Column::StatusChangedAt)
                 .filter(actions::Column::GoalId.is_in(momentum_goal_ids))
+                .filter(actions::Column::DeletedAt.is_null())
                 .filter(actions::Column::Status.eq("completed"));
 
             let date_query = match params.assignee_user_id {
@@ -282,14 +333,15 @@ pub async fn gather_batch_progress_data(
         .map(|goal| {
             let goal_id = goal.id;
 
-            let (actions_total, actions_completed, next_action_due) =
+            let (actions_total, actions_completed, next_action_due, total_work_seconds) =
                 match action_stats.get(&goal_id) {
                     Some(stats) => (
                         stats.actions_total as usize,
                         stats.actions_completed as usize,
                         stats.next_action_due,
+                        stats.total_work_seconds,
                     ),
-                    None => (0, 0, None),
+                    None => (0, 0, None, 0),
                 };
 
             let (linked_coaching_session_count, last_coaching_session_date) =
@@ -314,6 +366,7 @@ pub async fn gather_batch_progress_data(
                 next_action_due,
                 linked_coaching_session_count,
                 last_coaching_session_date,
+                total_work_seconds,
             }
         })
         .collect();
@@ -330,6 +383,7 @@ pub async fn gather_batch_progress_data(
 
 async fn find_goal(db: &impl ConnectionTrait, goal_id: Id) -> Result<goals::Model, Error> {
     goals::Entity::find_by_id(goal_id)
+        .filter(goals::Column::DeletedAt.is_null())
         .one(db)
         .await?
         .ok_or(Error {
@@ -344,6 +398,7 @@ async fn find_actions_for_goal(
 ) -> Result<Vec<actions::Model>, Error> {
     Ok(actions::Entity::find()
         .filter(actions::Column::GoalId.eq(goal_id))
+        .filter(actions::Column::DeletedAt.is_null())
         .all(db)
         .await?)
 }
@@ -399,6 +454,7 @@ async fn find_linked_coaching_session_stats(
     let links_with_sessions = coaching_sessions_goals::Entity::find()
         .filter(coaching_sessions_goals::Column::GoalId.eq(goal_id))
         .find_also_related(coaching_sessions::Entity)
+        .filter(coaching_sessions::Column::DeletedAt.is_null())
         .all(db)
         .await?;
 
@@ -448,6 +504,7 @@ mod tests {
             target_date,
             created_at: now,
             updated_at: now,
+            deleted_at: None,
         }
     }
 
@@ -462,12 +519,16 @@ mod tests {
             coaching_session_id: Id::new_v4(),
             goal_id: Some(goal_id),
             user_id: Id::new_v4(),
+            action_series_id: None,
+            previous_action_id: None,
             body: Some("Test action".to_string()),
+            effort_points: None,
             due_by,
             status,
             status_changed_at: now,
             created_at: now,
             updated_at: now,
+            deleted_at: None,
         }
     }
 
@@ -0,0 +1,170 @@
+use super::error::{EntityApiErrorKind, Error};
+use entity::goal_templates::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{
+    entity::prelude::*,
+    sea_query::Expr,
+    ActiveValue::{Set, Unchanged},
+    ConnectionTrait, QueryOrder, TryIntoModel,
+};
+
+use log::*;
+
+/// Inserts a template for `organization_id`. The usage counters start at zero.
+pub async fn create(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+    created_by_user_id: Id,
+    model: Model,
+) -> Result<Model, Error> {
+    debug!("New goal template for organization {organization_id}: {model:?}");
+
+    let now = chrono::Utc::now();
+    let active_model = ActiveModel {
+        organization_id: Set(organization_id),
+        title: Set(model.title),
+        body: Set(model.body),
+        target_duration_days: Set(model.target_duration_days),
+        milestones: Set(model.milestones),
+        created_by_user_id: Set(created_by_user_id),
+        usage_count: Set(0),
+        last_used_at: Set(None),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+        ..Default::default()
+    };
+
+    Ok(active_model.save(db).await?.try_into_model()?)
+}
+
+/// Replaces a template's content, leaving its owner and usage counters untouched.
+pub async fn update(
+    db: &impl ConnectionTrait,
+    existing: Model,
+    model: Model,
+) -> Result<Model, Error> {
+    let active_model = ActiveModel {
+        id: Unchanged(existing.id),
+        organization_id: Unchanged(existing.organization_id),
+        title: Set(model.title),
+        body: Set(model.body),
+        target_duration_days: Set(model.target_duration_days),
+        milestones: Set(model.milestones),
+        created_by_user_id: Unchanged(existing.created_by_user_id),
+        usage_count: Unchanged(existing.usage_count),
+        last_used_at: Unchanged(existing.last_used_at),
+        created_at: Unchanged(existing.created_at),
+        updated_at: Set(chrono::Utc::now().into()),
+    };
+
+    Ok(active_model.update(db).await?.try_into_model()?)
+}
+
+pub async fn delete_by_id(db: &impl ConnectionTrait, id: Id) -> Result<(), Error> {
+    Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
+
+pub async fn find_by_id(db: &impl ConnectionTrait, id: Id) -> Result<Model, Error> {
+    Entity::find_by_id(id).one(db).await?.ok_or_else(|| {
+        error!("Goal template with id {id} not found");
+
+        Error {
+            source: None,
+            error_kind: EntityApiErrorKind::RecordNotFound,
+        }
+    })
+}
+
+/// All of an organization's templates, alphabetically by title.
+pub async fn find_by_organization(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::OrganizationId.eq(organization_id))
+        .order_by_asc(Column::Title)
+        .all(db)
+        .await?)
+}
+
+/// Counts one more goal created from the template. Increments in SQL so concurrent
+/// instantiations don't lose updates.
+pub async fn record_usage(db: &impl ConnectionTrait, id: Id) -> Result<(), Error> {
+    let now: DateTimeWithTimeZone = chrono::Utc::now().into();
+
+    Entity::update_many()
+        .col_expr(Column::UsageCount, Expr::col(Column::UsageCount).add(1))
+        .col_expr(Column::LastUsedAt, Expr::value(Some(now)))
+        .filter(Column::Id.eq(id))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+// We need to gate seaORM's mock feature behind conditional compilation because
+// the feature removes the Clone trait implementation from seaORM's DatabaseConnection.
+// see https://github.com/SeaQL/sea-orm/issues/830
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use entity::goal_templates::{Milestone, Milestones};
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+
+    fn template() -> Model {
+        let now = chrono::Utc::now();
+        Model {
+            id: Id::new_v4(),
+            organization_id: Id::new_v4(),
+            title: "Improve delegation".to_string(),
+            body: None,
+            target_duration_days: Some(90),
+            milestones: Milestones(vec![Milestone {
+                body: "List tasks only you can do".to_string(),
+                due_offset_days: Some(7),
+            }]),
+            created_by_user_id: Id::new_v4(),
+            usage_count: 3,
+            last_used_at: None,
+            created_at: now.into(),
+            updated_at: now.into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn update_keeps_the_usage_counters() -> Result<(), Error> {
+        let existing = template();
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![existing.clone()]])
+            .into_connection();
+
+        update(&db, existing.clone(), existing).await?;
+
+        let log = db.into_transaction_log();
+        let sql = &log[0].statements()[0].sql;
+        let set_clause = &sql[sql.find(" SET ").unwrap()..sql.find(" WHERE ").unwrap()];
+        assert!(!set_clause.contains("usage_count"));
+        assert!(!set_clause.contains("last_used_at"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn record_usage_increments_in_sql() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results([MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            }])
+            .into_connection();
+
+        record_usage(&db, Id::new_v4()).await?;
+
+        let log = db.into_transaction_log();
+        assert!(log[0].statements()[0]
+            .sql
+            .contains(r#""usage_count" = "usage_count" + $1"#));
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+//! Coachee journal entries.
+//!
+//! An entry is private to its author until shared with the coach. The finders here take
+//! the viewing user and only ever return entries that user may read, so callers can't
+//! leak an unshared entry by forgetting a check.
+
+use super::error::{EntityApiErrorKind, Error};
+use super::field_encryption;
+use entity::journal_entries::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{
+    entity::prelude::*,
+    ActiveValue::{Set, Unchanged},
+    Condition, ConnectionTrait, QueryOrder, TryIntoModel,
+};
+
+use log::*;
+
+pub async fn create(
+    db: &impl ConnectionTrait,
+    coaching_relationship_id: Id,
+    user_id: Id,
+    model: Model,
+) -> Result<Model, Error> {
+    debug!("New journal entry by user {user_id} in relationship {coaching_relationship_id}");
+
+    let body =
+        field_encryption::seal_journal_entry_body(db, coaching_relationship_id, model.body).await?;
+    let now = chrono::Utc::now();
+    let active_model = ActiveModel {
+        coaching_relationship_id: Set(coaching_relationship_id),
+        user_id: Set(user_id),
+        title: Set(model.title),
+        body: Set(body),
+        shared_with_coach: Set(model.shared_with_coach),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+        ..Default::default()
+    };
+
+    let entry = active_model.save(db).await?.try_into_model()?;
+    field_encryption::open_journal_entry(db, entry).await
+}
+
+/// Replaces an entry's content and sharing, leaving its relationship and author untouched.
+pub async fn update(
+    db: &impl ConnectionTrait,
+    existing: Model,
+    model: Model,
+) -> Result<Model, Error> {
+    let body = field_encryption::seal_journal_entry_body(
+        db,
+        existing.coaching_relationship_id,
+        model.body,
+    )
+    .await?;
+    let active_model = ActiveModel {
+        id: Unchanged(existing.id),
+        coaching_relationship_id: Unchanged(existing.coaching_relationship_id),
+        user_id: Unchanged(existing.user_id),
+        title: Set(model.title),
+        body: Set(body),
+        shared_with_coach: Set(model.shared_with_coach),
+        created_at: Unchanged(existing.created_at),
+        updated_at: Set(chrono::Utc::now().into()),
+    };
+
+    let entry = active_model.update(db).await?.try_into_model()?;
+    field_encryption::open_journal_entry(db, entry).await
+}
+
+pub async fn delete_by_id(db: &impl ConnectionTrait, id: Id) -> Result<(), Error> {
+    Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
+
+/// An entry by id if `viewer_id` may read it: they wrote it, or it's shared with the coach.
+/// Entries the viewer may not read are reported as not found.
+pub async fn find_visible_by_id(
+    db: &impl ConnectionTrait,
+    viewer_id: Id,
+    id: Id,
+) -> Result<Model, Error> {
+    let entry = Entity::find_by_id(id)
+        .filter(visible_to(viewer_id))
+        .one(db)
+        .await?
+        .ok_or_else(|| {
+            debug!("Journal entry {id} not found or not visible to user {viewer_id}");
+
+            Error {
+                source: None,
+                error_kind: EntityApiErrorKind::RecordNotFound,
+            }
+        })?;
+    field_encryption::open_journal_entry(db, entry).await
+}
+
+/// A relationship's entries that `viewer_id` may read, newest first.
+pub async fn find_visible_by_relationship(
+    db: &impl ConnectionTrait,
+    viewer_id: Id,
+    coaching_relationship_id: Id,
+) -> Result<Vec<Model>, Error> {
+    let entries = Entity::find()
+        .filter(Column::CoachingRelationshipId.eq(coaching_relationship_id))
+        .filter(visible_to(viewer_id))
+        .order_by_desc(Column::CreatedAt)
+        .all(db)
+        .await?;
+    field_encryption::open_journal_entries(db, entries).await
+}
+
+/// A relationship's entries shared with the coach since `since`, newest first.
+pub async fn find_shared_by_relationship_since(
+    db: &impl ConnectionTrait,
+    coaching_relationship_id: Id,
+    since: DateTimeWithTimeZone,
+) -> Result<Vec<Model>, Error> {
+    let entries = Entity::find()
+        .filter(Column::CoachingRelationshipId.eq(coaching_relationship_id))
+        .filter(Column::SharedWithCoach.eq(true))
+        .filter(Column::CreatedAt.gte(since))
+        .order_by_desc(Column::CreatedAt)
+        .all(db)
+        .await?;
+    field_encryption::open_journal_entries(db, entries).await
+}
+
+fn visible_to(viewer_id: Id) -> Condition {
+    Condition::any()
+        .add(Column::UserId.eq(viewer_id))
+        .add(Column::SharedWithCoach.eq(true))
+}
+
+#[cfg(test)]
+// We need to gate seaORM's mock feature behind conditional compilation because
+// the feature removes the Clone trait implementation from seaORM's DatabaseConnection.
+// see https://github.com/SeaQL/sea-orm/issues/830
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    #[tokio::test]
+    async fn find_visible_by_relationship_limits_others_to_shared_entries() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<Model>::new()])
+            .into_connection();
+        let viewer_id = Id::new_v4();
+
+        find_visible_by_relationship(&db, viewer_id, Id::new_v4()).await?;
+
+        let log = db.into_transaction_log();
+        let sql = log[0].statements()[0].sql.clone();
+        assert!(sql.contains(
+            r#"("journal_entries"."user_id" = $2 OR "journal_entries"."shared_with_coach" = $3)"#
+        ));
+        Ok(())
+    }
+}
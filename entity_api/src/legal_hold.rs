@@ -0,0 +1,176 @@
+//! Legal holds pausing the purge and deletion of organizations' and coaching
+//! relationships' data.
+
+use super::error::{EntityApiErrorKind, Error};
+use entity::legal_holds::{ActiveModel, Column, Entity, Model};
+use entity::{coaching_relationships, user_roles, Id};
+use sea_orm::{
+    entity::prelude::*, ActiveValue::Set, Condition, ConnectionTrait, IntoActiveModel,
+    PaginatorTrait, QueryOrder, QuerySelect, QueryTrait,
+};
+
+/// Places a hold; `id`, `released_*` and the timestamps are assigned here.
+pub async fn create(
+    db: &impl ConnectionTrait,
+    hold: Model,
+    placed_by_user_id: Id,
+) -> Result<Model, Error> {
+    let now = chrono::Utc::now();
+    let active_model = ActiveModel {
+        organization_id: Set(hold.organization_id),
+        coaching_relationship_id: Set(hold.coaching_relationship_id),
+        reason: Set(hold.reason),
+        placed_by_user_id: Set(placed_by_user_id),
+        released_by_user_id: Set(None),
+        released_at: Set(None),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+        ..Default::default()
+    };
+
+    Ok(active_model.insert(db).await?)
+}
+
+/// Releases `hold`, recording who released it.
+pub async fn release(
+    db: &impl ConnectionTrait,
+    hold: Model,
+    released_by_user_id: Id,
+) -> Result<Model, Error> {
+    let now = chrono::Utc::now();
+    let mut active_model = hold.into_active_model();
+    active_model.released_by_user_id = Set(Some(released_by_user_id));
+    active_model.released_at = Set(Some(now.into()));
+    active_model.updated_at = Set(now.into());
+
+    Ok(active_model.update(db).await?)
+}
+
+pub async fn find_by_id(db: &impl ConnectionTrait, id: Id) -> Result<Model, Error> {
+    Entity::find_by_id(id).one(db).await?.ok_or(Error {
+        source: None,
+        error_kind: EntityApiErrorKind::RecordNotFound,
+    })
+}
+
+/// All holds, newest first; only the active ones when `active_only` is set.
+pub async fn find(db: &impl ConnectionTrait, active_only: bool) -> Result<Vec<Model>, Error> {
+    let mut query = Entity::find();
+    if active_only {
+        query = query.filter(Column::ReleasedAt.is_null());
+    }
+    Ok(query.order_by_desc(Column::CreatedAt).all(db).await?)
+}
+
+/// The active hold on the organization or the relationship a new hold would target.
+pub async fn find_active_on(
+    db: &impl ConnectionTrait,
+    organization_id: Option<Id>,
+    coaching_relationship_id: Option<Id>,
+) -> Result<Option<Model>, Error> {
+    let mut query = Entity::find().filter(Column::ReleasedAt.is_null());
+    query = match (organization_id, coaching_relationship_id) {
+        (Some(organization_id), _) => query.filter(Column::OrganizationId.eq(organization_id)),
+        (None, Some(relationship_id)) => {
+            query.filter(Column::CoachingRelationshipId.eq(relationship_id))
+        }
+        (None, None) => return Ok(None),
+    };
+    Ok(query.one(db).await?)
+}
+
+/// The active holds covering the organization: its own and those on its relationships.
+pub async fn find_active_covering_organization(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+) -> Result<Vec<Model>, Error> {
+    let relationships = coaching_relationships::Entity::find()
+        .select_only()
+        .column(coaching_relationships::Column::Id)
+        .filter(coaching_relationships::Column::OrganizationId.eq(organization_id))
+        .into_query();
+
+    Ok(Entity::find()
+        .filter(Column::ReleasedAt.is_null())
+        .filter(
+            Condition::any()
+                .add(Column::OrganizationId.eq(organization_id))
+                .add(Column::CoachingRelationshipId.in_subquery(relationships)),
+        )
+        .order_by_asc(Column::CreatedAt)
+        .all(db)
+        .await?)
+}
+
+/// The active holds covering the user's data: those on the organizations they belong
+/// to and on the relationships they coach or are coached in.
+pub async fn find_active_covering_user(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+) -> Result<Vec<Model>, Error> {
+    let organizations = user_roles::Entity::find()
+        .select_only()
+        .column(user_roles::Column::OrganizationId)
+        .filter(user_roles::Column::UserId.eq(user_id))
+        .filter(user_roles::Column::OrganizationId.is_not_null())
+        .into_query();
+    let relationships = coaching_relationships::Entity::find()
+        .select_only()
+        .column(coaching_relationships::Column::Id)
+        .filter(
+            Condition::any()
+                .add(coaching_relationships::Column::CoachId.eq(user_id))
+                .add(coaching_relationships::Column::CoacheeId.eq(user_id)),
+        )
+        .into_query();
+
+    Ok(Entity::find()
+        .filter(Column::ReleasedAt.is_null())
+        .filter(
+            Condition::any()
+                .add(Column::OrganizationId.in_subquery(organizations))
+                .add(Column::CoachingRelationshipId.in_subquery(relationships)),
+        )
+        .order_by_asc(Column::CreatedAt)
+        .all(db)
+        .await?)
+}
+
+/// How many holds are active.
+pub async fn count_active(db: &impl ConnectionTrait) -> Result<u64, Error> {
+    Ok(Entity::find()
+        .filter(Column::ReleasedAt.is_null())
+        .count(db)
+        .await?)
+}
+
+#[cfg(test)]
+// We need to gate seaORM's mock feature behind conditional compilation because
+// the feature removes the Clone trait implementation from seaORM's DatabaseConnection.
+// see https://github.com/SeaQL/sea-orm/issues/830
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    #[tokio::test]
+    async fn find_active_covering_user_checks_their_organizations_and_relationships(
+    ) -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<Model>::new()])
+            .into_connection();
+
+        find_active_covering_user(&db, Id::new_v4()).await?;
+
+        let log = db.into_transaction_log();
+        let sql = &log[0].statements()[0].sql;
+        assert!(sql.contains(r#""legal_holds"."released_at" IS NULL"#));
+        assert!(sql.contains(
+            r#""legal_holds"."organization_id" IN (SELECT "user_roles"."organization_id" FROM "refactor_platform"."user_roles""#
+        ));
+        assert!(sql.contains(
+            r#"OR "legal_holds"."coaching_relationship_id" IN (SELECT "coaching_relationships"."id" FROM "refactor_platform"."coaching_relationships""#
+        ));
+        Ok(())
+    }
+}
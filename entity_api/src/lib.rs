@@ -1,19 +1,43 @@
 use chrono::{Days, Utc};
 use password_auth::generate_hash;
-use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set,
+    TransactionTrait,
+};
 
 pub use entity::{
-    actions, actions_users, agreements, coachees, coaches, coaching_relationships,
-    coaching_session_topics, coaching_session_views, coaching_sessions, coaching_sessions_goals,
-    cost_metric, cost_unit, duration, goals, jwts, magic_link_tokens, meeting_provider, notes,
-    oauth_connections, organizations, password_reset_attempts, pipeline_provider, status,
-    token_purpose, topic_priority, topic_status, user_invite_status, user_roles, users,
-    users::Role, Id,
+    action_work_logs, actions, actions_users, agreements, ai_privacy_level, api_usage_rollups,
+    attachments, audit_entries, calendar_feeds, carried_actions, coachees, coaches,
+    coaching_relationships, coaching_session_topics, coaching_session_views, coaching_sessions,
+    coaching_sessions_goals, cost_metric, cost_unit, dead_letter_events, duration,
+    embedding_source_type, event_store, goal_templates, goals, journal_entries, jwts, legal_holds,
+    library_assignments, library_item_kind, library_items, magic_link_tokens, meeting_provider,
+    mentions, note_blind_index_tokens, notes, oauth_authorization_codes, oauth_clients,
+    oauth_connections, oauth_grants, organization_ai_settings, organization_api_quotas,
+    organization_data_keys, organization_holidays, organization_transcription_vocabularies,
+    organizations, out_of_office_periods, outbox_events, password_reset_attempts,
+    pipeline_provider, platform_settings, policy_acceptances, policy_documents, policy_kind,
+    progress_report_settings, progress_reports, prompt_key, prompt_templates, push_subscriptions,
+    question_quality_summaries, reactions, relationship_health_scores,
+    relationship_health_settings, remembered_devices, resource_type, resource_views,
+    scheduled_events, scim_tokens, scim_users, session_prep_briefs, session_pulses,
+    session_record_status, session_records, session_request_status, session_requests,
+    session_types, status, theme_reports, token_purpose, topic_priority, topic_status,
+    user_invite_status, user_locks, user_roles, users, users::Role, warehouse_checkpoints,
+    webhook_subscriptions, Id,
 };
 
 pub mod action;
+pub mod action_series;
+pub mod action_work_log;
 pub mod actions_user;
 pub mod agreement;
+pub mod api_usage_rollup;
+pub mod attachment;
+pub mod audit_entry;
+pub mod authorized_session;
+pub mod calendar_feed;
+pub mod carried_action;
 pub mod coaching_relationship;
 pub mod coaching_session;
 pub mod coaching_session_display_title;
@@ -22,23 +46,68 @@ pub mod coaching_session_series;
 pub mod coaching_session_topic;
 pub mod coaching_session_view;
 pub mod cost_pricing_config;
+pub mod dead_letter_event;
+pub mod embedding;
 pub mod error;
+pub mod field_encryption;
 pub mod goal;
 pub mod goal_progress;
+pub mod goal_template;
+pub mod journal_entry;
+pub mod legal_hold;
+pub mod library;
 pub mod magic_link_token;
 pub mod meeting_recording;
+pub mod mention;
 pub mod mutate;
 pub mod note;
+pub mod oauth_authorization_code;
+pub mod oauth_client;
 pub mod oauth_connection;
+pub mod oauth_grant;
 pub mod organization;
+pub mod organization_ai_setting;
+pub mod organization_api_quota;
+pub mod organization_data_key;
+pub mod organization_holiday;
+pub mod organization_transcription_vocabulary;
+pub mod out_of_office_period;
+pub mod outbox_event;
 pub mod password_reset_attempt;
 pub mod platform_cost_metrics;
+pub mod platform_setting;
+pub mod policy_acceptance;
+pub mod policy_document;
+pub mod progress_report;
+pub mod progress_report_setting;
+pub mod prompt_template;
+pub mod push_subscription;
 pub mod query;
+pub mod question_quality_summary;
+pub mod reaction;
+pub mod relationship_health;
+pub mod remembered_device;
+pub mod resource_view;
+pub mod scheduled_event;
+pub mod scim_token;
+pub mod scim_user;
+pub mod search;
+pub mod session_prep_brief;
+pub mod session_pulse;
+pub mod session_record;
+pub mod session_request;
+pub mod session_type;
+pub mod stored_event;
+pub mod theme_report;
 pub mod tiptap_metrics;
+pub mod transcript_chapter;
 pub mod transcript_segment;
 pub mod transcription;
 pub mod user;
+pub mod user_lock;
 pub mod user_role;
+pub mod warehouse_checkpoint;
+pub mod webhook_subscription;
 
 pub(crate) fn uuid_parse_str(uuid_str: &str) -> Result<Id, error::Error> {
     Id::parse_str(uuid_str).map_err(|_| error::Error {
@@ -47,6 +116,8 @@ pub(crate) fn uuid_parse_str(uuid_str: &str) -> Result<Id, error::Error> {
     })
 }
 
+/// Inserts demo organizations, users and coaching sessions. The users' password is
+/// `password`, so this is for development databases only.
 pub async fn seed_database(db: &DatabaseConnection) {
     let now = Utc::now();
 
@@ -409,6 +480,51 @@ pub async fn seed_database(db: &DatabaseConnection) {
     .unwrap();
 }
 
+/// Creates the platform's first SuperAdmin, the only account a production database is
+/// seeded with. Returns `None`, creating nothing, when there is a SuperAdmin already.
+pub async fn seed_bootstrap_admin(
+    db: &DatabaseConnection,
+    email: &str,
+    password: &str,
+) -> Result<Option<users::Model>, error::Error> {
+    let txn = db.begin().await?;
+    let super_admin = user_roles::Entity::find()
+        .filter(user_roles::Column::Role.eq(Role::SuperAdmin))
+        .filter(user_roles::Column::OrganizationId.is_null())
+        .one(&txn)
+        .await?;
+    if super_admin.is_some() {
+        return Ok(None);
+    }
+
+    let now = Utc::now();
+    let admin = users::ActiveModel {
+        email: Set(email.to_owned()),
+        first_name: Set("Admin".to_owned()),
+        last_name: Set("User".to_owned()),
+        display_name: Set(Some("Admin User".to_owned())),
+        password: Set(Some(generate_hash(password))),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+    user_roles::ActiveModel {
+        role: Set(Role::SuperAdmin),
+        organization_id: Set(None),
+        user_id: Set(admin.id),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+    txn.commit().await?;
+
+    Ok(Some(admin))
+}
+
 #[cfg(test)]
 // We need to gate seaORM's mock feature behind conditional compilation because
 // the feature removes the Clone trait implementation from seaORM's DatabaseConnection.
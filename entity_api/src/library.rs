@@ -0,0 +1,212 @@
+//! Organization content library: items and their assignments to coaching relationships.
+
+use super::error::{EntityApiErrorKind, Error};
+use entity::library_assignments;
+use entity::library_items::{self, ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{
+    entity::prelude::*,
+    sea_query::Expr,
+    ActiveValue::{Set, Unchanged},
+    ConnectionTrait, QueryOrder, TryIntoModel,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use log::*;
+
+/// An assignment together with the library item it points at.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct AssignmentWithItem {
+    #[serde(flatten)]
+    pub assignment: library_assignments::Model,
+    pub item: library_items::Model,
+}
+
+pub async fn create_item(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+    created_by_user_id: Id,
+    model: Model,
+) -> Result<Model, Error> {
+    debug!("New library item for organization {organization_id}: {model:?}");
+
+    let now = chrono::Utc::now();
+    let active_model = ActiveModel {
+        organization_id: Set(organization_id),
+        kind: Set(model.kind),
+        title: Set(model.title),
+        body: Set(model.body),
+        url: Set(model.url),
+        tags: Set(model.tags),
+        created_by_user_id: Set(created_by_user_id),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+        ..Default::default()
+    };
+
+    Ok(active_model.save(db).await?.try_into_model()?)
+}
+
+/// Replaces an item's content, leaving its organization and author untouched.
+pub async fn update_item(
+    db: &impl ConnectionTrait,
+    existing: Model,
+    model: Model,
+) -> Result<Model, Error> {
+    let active_model = ActiveModel {
+        id: Unchanged(existing.id),
+        organization_id: Unchanged(existing.organization_id),
+        kind: Set(model.kind),
+        title: Set(model.title),
+        body: Set(model.body),
+        url: Set(model.url),
+        tags: Set(model.tags),
+        created_by_user_id: Unchanged(existing.created_by_user_id),
+        created_at: Unchanged(existing.created_at),
+        updated_at: Set(chrono::Utc::now().into()),
+    };
+
+    Ok(active_model.update(db).await?.try_into_model()?)
+}
+
+/// Deletes an item along with its assignments (ON DELETE CASCADE).
+pub async fn delete_item(db: &impl ConnectionTrait, id: Id) -> Result<(), Error> {
+    Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
+
+pub async fn find_item_by_id(db: &impl ConnectionTrait, id: Id) -> Result<Model, Error> {
+    Entity::find_by_id(id).one(db).await?.ok_or_else(|| {
+        error!("Library item with id {id} not found");
+
+        Error {
+            source: None,
+            error_kind: EntityApiErrorKind::RecordNotFound,
+        }
+    })
+}
+
+/// An organization's items, alphabetically by title, optionally only those carrying `tag`.
+pub async fn find_items_by_organization(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+    tag: Option<&str>,
+) -> Result<Vec<Model>, Error> {
+    let query = Entity::find().filter(Column::OrganizationId.eq(organization_id));
+
+    let query = match tag {
+        Some(tag) => query.filter(Expr::cust_with_values(
+            "tags @> $1::jsonb",
+            [serde_json::json!([tag])],
+        )),
+        None => query,
+    };
+
+    Ok(query.order_by_asc(Column::Title).all(db).await?)
+}
+
+pub async fn create_assignment(
+    db: &impl ConnectionTrait,
+    coaching_relationship_id: Id,
+    assigned_by_user_id: Id,
+    library_item_id: Id,
+    due_by: Option<DateTimeWithTimeZone>,
+) -> Result<library_assignments::Model, Error> {
+    debug!("Assigning library item {library_item_id} in relationship {coaching_relationship_id}");
+
+    let now = chrono::Utc::now();
+    let active_model = library_assignments::ActiveModel {
+        library_item_id: Set(library_item_id),
+        coaching_relationship_id: Set(coaching_relationship_id),
+        assigned_by_user_id: Set(assigned_by_user_id),
+        due_by: Set(due_by),
+        completed_at: Set(None),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+        ..Default::default()
+    };
+
+    Ok(active_model.save(db).await?.try_into_model()?)
+}
+
+pub async fn find_assignment_by_id(
+    db: &impl ConnectionTrait,
+    id: Id,
+) -> Result<library_assignments::Model, Error> {
+    library_assignments::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| {
+            error!("Library assignment with id {id} not found");
+
+            Error {
+                source: None,
+                error_kind: EntityApiErrorKind::RecordNotFound,
+            }
+        })
+}
+
+/// A relationship's assignments with their items, soonest due first (undated last).
+pub async fn find_assignments_by_relationship(
+    db: &impl ConnectionTrait,
+    coaching_relationship_id: Id,
+) -> Result<Vec<AssignmentWithItem>, Error> {
+    let rows = library_assignments::Entity::find()
+        .filter(library_assignments::Column::CoachingRelationshipId.eq(coaching_relationship_id))
+        .find_also_related(Entity)
+        .order_by_asc(library_assignments::Column::DueBy)
+        .order_by_asc(library_assignments::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    // The FK guarantees the item exists; the Option is an artifact of the LEFT JOIN.
+    Ok(rows
+        .into_iter()
+        .filter_map(|(assignment, item)| item.map(|item| AssignmentWithItem { assignment, item }))
+        .collect())
+}
+
+/// Sets or clears an assignment's `completed_at`.
+pub async fn set_assignment_completed_at(
+    db: &impl ConnectionTrait,
+    assignment: library_assignments::Model,
+    completed_at: Option<DateTimeWithTimeZone>,
+) -> Result<library_assignments::Model, Error> {
+    let active_model = library_assignments::ActiveModel {
+        id: Unchanged(assignment.id),
+        library_item_id: Unchanged(assignment.library_item_id),
+        coaching_relationship_id: Unchanged(assignment.coaching_relationship_id),
+        assigned_by_user_id: Unchanged(assignment.assigned_by_user_id),
+        due_by: Unchanged(assignment.due_by),
+        completed_at: Set(completed_at),
+        created_at: Unchanged(assignment.created_at),
+        updated_at: Set(chrono::Utc::now().into()),
+    };
+
+    Ok(active_model.update(db).await?.try_into_model()?)
+}
+
+#[cfg(test)]
+// We need to gate seaORM's mock feature behind conditional compilation because
+// the feature removes the Clone trait implementation from seaORM's DatabaseConnection.
+// see https://github.com/SeaQL/sea-orm/issues/830
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    #[tokio::test]
+    async fn find_items_by_organization_filters_by_tag_containment() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<Model>::new()])
+            .into_connection();
+
+        find_items_by_organization(&db, Id::new_v4(), Some("delegation")).await?;
+
+        let log = db.into_transaction_log();
+        let statement = &log[0].statements()[0];
+        assert!(statement.sql.contains("tags @> $2::jsonb"));
+        Ok(())
+    }
+}
@@ -54,6 +54,31 @@ pub async fn find_latest_by_coaching_session(
         .await?)
 }
 
+/// The recorded durations, in seconds, of the sessions' completed recordings
+pub async fn find_completed_durations(
+    db: &DatabaseConnection,
+    session_ids: Vec<Id>,
+) -> Result<Vec<(Id, i32)>, Error> {
+    if session_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    Ok(Entity::find()
+        .filter(Column::CoachingSessionId.is_in(session_ids))
+        .filter(Column::Status.eq(MeetingRecordingStatus::Completed))
+        .filter(Column::DurationSeconds.is_not_null())
+        .order_by(Column::CreatedAt, Order::Asc)
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|recording| {
+            recording
+                .duration_seconds
+                .map(|seconds| (recording.coaching_session_id, seconds))
+        })
+        .collect())
+}
+
 /// Finds a recording by its primary key
 pub async fn find_by_id(db: &DatabaseConnection, id: Id) -> Result<Option<Model>, Error> {
     Ok(Entity::find_by_id(id).one(db).await?)
@@ -0,0 +1,158 @@
+use super::error::Error;
+use entity::mentions::{ActiveModel, Column, Entity, Model};
+use entity::resource_type::ResourceType;
+use entity::Id;
+use sea_orm::{
+    sea_query::OnConflict, ActiveValue::Set, ColumnTrait, DatabaseConnection, EntityTrait,
+    QueryFilter, QueryOrder, TransactionTrait,
+};
+
+use log::*;
+
+/// Reconciles a note's or action's stored mentions with the users currently mentioned
+/// in its body, returning only the mentions that are new (i.e. the ones to notify).
+///
+/// Mentions no longer present are removed; existing ones are left untouched so an edit
+/// never re-notifies. Runs in a single transaction.
+pub async fn sync(
+    db: &DatabaseConnection,
+    resource_type: ResourceType,
+    resource_id: Id,
+    coaching_session_id: Id,
+    author_user_id: Id,
+    mentioned_user_ids: &[Id],
+) -> Result<Vec<Model>, Error> {
+    debug!("Syncing mentions for {resource_type:?} {resource_id}: {mentioned_user_ids:?}");
+
+    let txn = db.begin().await?;
+
+    Entity::delete_many()
+        .filter(Column::ResourceType.eq(resource_type))
+        .filter(Column::ResourceId.eq(resource_id))
+        .filter(Column::MentionedUserId.is_not_in(mentioned_user_ids.iter().copied()))
+        .exec(&txn)
+        .await?;
+
+    let created = if mentioned_user_ids.is_empty() {
+        Vec::new()
+    } else {
+        let now = chrono::Utc::now();
+        let rows = mentioned_user_ids
+            .iter()
+            .map(|mentioned_user_id| ActiveModel {
+                resource_type: Set(resource_type),
+                resource_id: Set(resource_id),
+                coaching_session_id: Set(coaching_session_id),
+                mentioned_user_id: Set(*mentioned_user_id),
+                author_user_id: Set(author_user_id),
+                created_at: Set(now.into()),
+                updated_at: Set(now.into()),
+                ..Default::default()
+            });
+
+        let on_conflict = OnConflict::columns([
+            Column::ResourceType,
+            Column::ResourceId,
+            Column::MentionedUserId,
+        ])
+        .do_nothing()
+        .to_owned();
+
+        // DO NOTHING skips rows that already exist, so RETURNING yields only new mentions.
+        Entity::insert_many(rows)
+            .on_conflict(on_conflict)
+            .exec_with_returning_many(&txn)
+            .await?
+    };
+
+    txn.commit().await?;
+
+    Ok(created)
+}
+
+/// All mentions of a user, newest first.
+pub async fn find_by_mentioned_user(
+    db: &DatabaseConnection,
+    mentioned_user_id: Id,
+) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::MentionedUserId.eq(mentioned_user_id))
+        .order_by_desc(Column::CreatedAt)
+        .all(db)
+        .await?)
+}
+
+#[cfg(test)]
+// We need to gate seaORM's mock feature behind conditional compilation because
+// the feature removes the Clone trait implementation from seaORM's DatabaseConnection.
+// see https://github.com/SeaQL/sea-orm/issues/830
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+
+    fn mention(resource_id: Id, mentioned_user_id: Id) -> Model {
+        let now = chrono::Utc::now();
+        Model {
+            id: Id::new_v4(),
+            resource_type: ResourceType::Note,
+            resource_id,
+            coaching_session_id: Id::new_v4(),
+            mentioned_user_id,
+            author_user_id: Id::new_v4(),
+            created_at: now.into(),
+            updated_at: now.into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn sync_returns_only_newly_inserted_mentions() -> Result<(), Error> {
+        let note_id = Id::new_v4();
+        let coachee_id = Id::new_v4();
+        let inserted = mention(note_id, coachee_id);
+
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results([MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            }])
+            .append_query_results([vec![inserted.clone()]])
+            .into_connection();
+
+        let created = sync(
+            &db,
+            ResourceType::Note,
+            note_id,
+            inserted.coaching_session_id,
+            inserted.author_user_id,
+            &[coachee_id],
+        )
+        .await?;
+
+        assert_eq!(created, vec![inserted]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn sync_with_no_mentions_only_clears_existing_rows() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results([MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 2,
+            }])
+            .into_connection();
+
+        let created = sync(
+            &db,
+            ResourceType::Action,
+            Id::new_v4(),
+            Id::new_v4(),
+            Id::new_v4(),
+            &[],
+        )
+        .await?;
+
+        assert!(created.is_empty());
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use crate::error::{EntityApiErrorKind, Error};
 use sea_orm::{
-    ActiveModelBehavior, ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait,
-    IntoActiveModel, Value,
+    prelude::DateTimeWithTimeZone, ActiveModelBehavior, ActiveModelTrait, ColumnTrait,
+    ConnectionTrait, DbErr, EntityTrait, IntoActiveModel, QueryFilter, Value,
 };
 use std::collections::HashMap;
 
@@ -44,6 +44,50 @@ where
     Ok(active_model.update(db).await?)
 }
 
+/// The versions of a row, named by its `updated_at`, that a write may replace.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum ExpectedVersion {
+    /// Whichever version is current, for writes that don't name one.
+    #[default]
+    Any,
+    /// Only a row last changed at one of these times. An empty list matches nothing.
+    UpdatedAt(Vec<DateTimeWithTimeZone>),
+}
+
+/// Writes `active_model` only while its row is still at a version `expected` names.
+///
+/// The check is part of the `UPDATE` itself (`WHERE id = $1 AND updated_at IN (...)`), so a
+/// change committed after the caller read the row can't be overwritten; the write fails
+/// with `StaleVersion` instead.
+pub async fn update_if_version<A, C>(
+    db: &impl ConnectionTrait,
+    active_model: A,
+    updated_at: C,
+    expected: &ExpectedVersion,
+) -> Result<<A::Entity as EntityTrait>::Model, Error>
+where
+    A: ActiveModelTrait,
+    C: ColumnTrait,
+    A::Entity: EntityTrait<Column = C>,
+    <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+{
+    let update = A::Entity::update(active_model);
+    let update = match expected {
+        ExpectedVersion::Any => update,
+        ExpectedVersion::UpdatedAt(versions) => {
+            update.filter(updated_at.is_in(versions.iter().copied()))
+        }
+    };
+    match update.exec(db).await {
+        Ok(model) => Ok(model),
+        Err(DbErr::RecordNotUpdated) => Err(Error {
+            source: None,
+            error_kind: EntityApiErrorKind::StaleVersion,
+        }),
+        Err(e) => Err(e.into()),
+    }
+}
+
 /// A map structure that holds column names and their corresponding values for updates.
 ///
 /// This structure provides a flexible way to specify which fields should be updated
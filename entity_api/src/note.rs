@@ -1,15 +1,16 @@
 use super::error::{EntityApiErrorKind, Error};
-use crate::uuid_parse_str;
+use super::field_encryption;
+use crate::mutate::{update_if_version, ExpectedVersion};
+use crate::query::{contains_any_term, select_by, IntoQueryFilterMap, QuerySort};
+use entity::note_blind_index_tokens;
 use entity::notes::{self, ActiveModel, Entity, Model};
 use entity::Id;
+use log::*;
 use sea_orm::{
     entity::prelude::*,
     ActiveValue::{Set, Unchanged},
-    DatabaseConnection, TryIntoModel,
+    DatabaseConnection, QueryOrder, QuerySelect, QueryTrait, TryIntoModel,
 };
-use std::collections::HashMap;
-
-use log::*;
 
 pub async fn create(
     db: &DatabaseConnection,
@@ -18,37 +19,57 @@ pub async fn create(
 ) -> Result<Model, Error> {
     debug!("New Note Model to be inserted: {note_model:?}");
 
+    let body =
+        field_encryption::seal_note_body(db, note_model.coaching_session_id, note_model.body)
+            .await?;
+
     let now = chrono::Utc::now();
 
     let note_active_model: ActiveModel = ActiveModel {
         coaching_session_id: Set(note_model.coaching_session_id),
-        body: Set(note_model.body),
+        body: Set(body),
         user_id: Set(user_id),
         created_at: Set(now.into()),
         updated_at: Set(now.into()),
         ..Default::default()
     };
 
-    Ok(note_active_model.save(db).await?.try_into_model()?)
+    let note = note_active_model.save(db).await?.try_into_model()?;
+    field_encryption::index_and_open_note(db, note).await
 }
 
-pub async fn update(db: &DatabaseConnection, id: Id, model: Model) -> Result<Model, Error> {
-    let result = Entity::find_by_id(id).one(db).await?;
+/// Updates a note's body, provided it's still at a version `expected` names.
+pub async fn update(
+    db: &DatabaseConnection,
+    id: Id,
+    model: Model,
+    expected: &ExpectedVersion,
+) -> Result<Model, Error> {
+    let result = Entity::find_by_id(id)
+        .filter(notes::Column::DeletedAt.is_null())
+        .one(db)
+        .await?;
 
     match result {
         Some(note) => {
             debug!("Existing Note model to be Updated: {note:?}");
 
+            let body =
+                field_encryption::seal_note_body(db, note.coaching_session_id, model.body).await?;
+
             let active_model: ActiveModel = ActiveModel {
                 id: Unchanged(note.id),
                 coaching_session_id: Unchanged(note.coaching_session_id),
-                body: Set(model.body),
+                body: Set(body),
                 user_id: Unchanged(note.user_id),
                 updated_at: Set(chrono::Utc::now().into()),
                 created_at: Unchanged(note.created_at),
+                deleted_at: Unchanged(note.deleted_at),
             };
 
-            Ok(active_model.update(db).await?.try_into_model()?)
+            let note =
+                update_if_version(db, active_model, notes::Column::UpdatedAt, expected).await?;
+            field_encryption::index_and_open_note(db, note).await
         }
         None => {
             error!("Note with id {id} not found");
@@ -62,7 +83,11 @@ pub async fn update(db: &DatabaseConnection, id: Id, model: Model) -> Result<Mod
 }
 
 pub async fn find_by_id(db: &DatabaseConnection, id: Id) -> Result<Option<Model>, Error> {
-    match Entity::find_by_id(id).one(db).await {
+    match Entity::find_by_id(id)
+        .filter(notes::Column::DeletedAt.is_null())
+        .one(db)
+        .await
+    {
         Ok(Some(note)) => {
             debug!("Note found: {note:?}");
 
@@ -86,29 +111,146 @@ pub async fn find_by_id(db: &DatabaseConnection, id: Id) -> Result<Option<Model>
     }
 }
 
-pub async fn find_by(
+/// Soft-deletes a note: it's left out of reads until restored, or purged once past the
+/// retention window.
+pub async fn delete_by_id(db: &DatabaseConnection, id: Id) -> Result<Model, Error> {
+    let note = find_by_id(db, id).await?.ok_or(Error {
+        source: None,
+        error_kind: EntityApiErrorKind::RecordNotFound,
+    })?;
+    let mut active_model: ActiveModel = note.into();
+    active_model.deleted_at = Set(Some(chrono::Utc::now().into()));
+    Ok(active_model.update(db).await?.try_into_model()?)
+}
+
+/// Restores a soft-deleted note, returning it with its body decrypted.
+pub async fn restore(db: &DatabaseConnection, id: Id) -> Result<Model, Error> {
+    let note = find_deleted_by_id(db, id).await?;
+    let mut active_model: ActiveModel = note.into();
+    active_model.deleted_at = Set(None);
+    let note = active_model.update(db).await?.try_into_model()?;
+    field_encryption::open_note(db, note).await
+}
+
+/// Permanently deletes the notes soft-deleted before `cutoff`, returning how many.
+pub async fn purge_deleted_before(
+    db: &impl ConnectionTrait,
+    cutoff: DateTimeWithTimeZone,
+) -> Result<u64, Error> {
+    let result = Entity::delete_many()
+        .filter(notes::Column::DeletedAt.lt(cutoff))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected)
+}
+
+/// A soft-deleted note by id, body still encrypted; `RecordNotFound` if it's live or gone.
+pub async fn find_deleted_by_id(db: &impl ConnectionTrait, id: Id) -> Result<Model, Error> {
+    Entity::find_by_id(id)
+        .filter(notes::Column::DeletedAt.is_not_null())
+        .one(db)
+        .await?
+        .ok_or(Error {
+            source: None,
+            error_kind: EntityApiErrorKind::RecordNotFound,
+        })
+}
+
+/// Notes matching the filters of `params`, sorted as it asks, with their bodies decrypted.
+pub async fn find_by<P>(db: &DatabaseConnection, params: P) -> Result<Vec<Model>, Error>
+where
+    P: IntoQueryFilterMap + QuerySort<notes::Column>,
+{
+    let notes = select_by::<Entity, notes::Column, P>(params)
+        .all(db)
+        .await?;
+    field_encryption::open_notes(db, notes).await
+}
+
+/// Notes of all of `coaching_session_ids`, oldest first.
+pub async fn find_by_coaching_session_ids(
     db: &DatabaseConnection,
-    query_params: HashMap<String, String>,
+    coaching_session_ids: &[Id],
 ) -> Result<Vec<Model>, Error> {
-    let mut query = Entity::find();
+    if coaching_session_ids.is_empty() {
+        return Ok(Vec::new());
+    }
 
-    for (key, value) in query_params {
-        match key.as_str() {
-            "coaching_session_id" => {
-                let coaching_session_id = uuid_parse_str(&value)?;
+    let notes = Entity::find()
+        .filter(notes::Column::DeletedAt.is_null())
+        .filter(notes::Column::CoachingSessionId.is_in(coaching_session_ids.iter().copied()))
+        .order_by_asc(notes::Column::CreatedAt)
+        .all(db)
+        .await?;
+    field_encryption::open_notes(db, notes).await
+}
 
-                query = query.filter(notes::Column::CoachingSessionId.eq(coaching_session_id));
-            }
-            _ => {
-                return Err(Error {
-                    source: None,
-                    error_kind: EntityApiErrorKind::InvalidQueryTerm,
-                });
-            }
-        }
+/// Up to `limit` notes from `coaching_session_ids` whose body mentions any of `terms`,
+/// most recently updated first. Encrypted notes are left out; see
+/// [`search_encrypted_in_relationship`].
+pub async fn search_in_sessions(
+    db: &DatabaseConnection,
+    coaching_session_ids: &[Id],
+    terms: &[String],
+    limit: u64,
+) -> Result<Vec<Model>, Error> {
+    if coaching_session_ids.is_empty() || terms.is_empty() {
+        return Ok(Vec::new());
     }
 
-    Ok(query.all(db).await?)
+    let notes = Entity::find()
+        .filter(notes::Column::DeletedAt.is_null())
+        .filter(notes::Column::CoachingSessionId.is_in(coaching_session_ids.iter().copied()))
+        .filter(contains_any_term(notes::Column::Body, terms))
+        .filter(notes::Column::Body.not_like(format!("{}%", field_encryption::PREFIX)))
+        .order_by_desc(notes::Column::UpdatedAt)
+        .limit(limit)
+        .all(db)
+        .await?;
+    field_encryption::open_notes(db, notes).await
+}
+
+/// Up to `limit` of the relationship's encrypted notes containing any of `terms` as a
+/// whole word, most recently updated first, found through their blind index. Empty if
+/// its organization doesn't encrypt its notes or encrypted notes aren't searchable.
+pub async fn search_encrypted_in_relationship(
+    db: &DatabaseConnection,
+    coaching_relationship_id: Id,
+    terms: &[String],
+    limit: u64,
+) -> Result<Vec<Model>, Error> {
+    if terms.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(tokens) =
+        field_encryption::blind_index_tokens(db, coaching_relationship_id, terms).await?
+    else {
+        return Ok(Vec::new());
+    };
+    if tokens.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let notes = Entity::find()
+        .filter(notes::Column::DeletedAt.is_null())
+        .filter(
+            notes::Column::Id.in_subquery(
+                note_blind_index_tokens::Entity::find()
+                    .select_only()
+                    .column(note_blind_index_tokens::Column::NoteId)
+                    .filter(
+                        note_blind_index_tokens::Column::CoachingRelationshipId
+                            .eq(coaching_relationship_id),
+                    )
+                    .filter(note_blind_index_tokens::Column::Token.is_in(tokens))
+                    .into_query(),
+            ),
+        )
+        .order_by_desc(notes::Column::UpdatedAt)
+        .limit(limit)
+        .all(db)
+        .await?;
+    field_encryption::open_notes(db, notes).await
 }
 
 #[cfg(test)]
@@ -118,6 +260,7 @@ pub async fn find_by(
 #[cfg(feature = "mock")]
 mod tests {
     use super::*;
+    use crate::query::QueryFilterMap;
     use entity::{notes::Model, Id};
     use sea_orm::{DatabaseBackend, MockDatabase, Transaction};
 
@@ -132,6 +275,7 @@ mod tests {
             body: Some("This is a note".to_owned()),
             created_at: now.into(),
             updated_at: now.into(),
+            deleted_at: None,
         };
 
         let db = MockDatabase::new(DatabaseBackend::Postgres)
@@ -156,13 +300,20 @@ mod tests {
             user_id: Id::new_v4(),
             created_at: now.into(),
             updated_at: now.into(),
+            deleted_at: None,
         };
 
         let db = MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results(vec![vec![note_model.clone()], vec![note_model.clone()]])
             .into_connection();
 
-        let note = update(&db, note_model.id, note_model.clone()).await?;
+        let note = update(
+            &db,
+            note_model.id,
+            note_model.clone(),
+            &ExpectedVersion::Any,
+        )
+        .await?;
 
         assert_eq!(note.body, note_model.body);
 
@@ -171,26 +322,69 @@ mod tests {
 
     #[tokio::test]
     async fn find_by_returns_all_notes_associated_with_coaching_session() -> Result<(), Error> {
+        struct Params(Id);
+
+        impl IntoQueryFilterMap for Params {
+            fn into_query_filter_map(self) -> QueryFilterMap {
+                let mut query_filter_map = QueryFilterMap::new();
+                query_filter_map.insert("coaching_session_id".to_owned(), Some(self.0.into()));
+                query_filter_map
+            }
+        }
+
+        impl QuerySort<notes::Column> for Params {
+            fn get_sort_column(&self) -> Option<notes::Column> {
+                Some(notes::Column::CreatedAt)
+            }
+
+            fn get_sort_order(&self) -> Option<sea_orm::Order> {
+                Some(sea_orm::Order::Desc)
+            }
+        }
+
         let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
-        let mut query_params = HashMap::new();
         let coaching_session_id = Id::new_v4();
 
-        query_params.insert(
-            "coaching_session_id".to_owned(),
-            coaching_session_id.to_string(),
-        );
-
-        let _ = find_by(&db, query_params).await;
+        let _ = find_by(&db, Params(coaching_session_id)).await;
 
         assert_eq!(
             db.into_transaction_log(),
             [Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "notes"."id", "notes"."coaching_session_id", "notes"."body", "notes"."user_id", "notes"."created_at", "notes"."updated_at" FROM "refactor_platform"."notes" WHERE "notes"."coaching_session_id" = $1"#,
+                r#"SELECT "notes"."id", "notes"."coaching_session_id", "notes"."body", "notes"."user_id", "notes"."created_at", "notes"."updated_at", "notes"."deleted_at" FROM "refactor_platform"."notes" WHERE "notes"."coaching_session_id" = $1 AND "notes"."deleted_at" IS NULL ORDER BY "notes"."created_at" DESC"#,
                 [coaching_session_id.into()]
             )]
         );
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn search_in_sessions_matches_any_term_case_insensitively() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<Model>::new()])
+            .into_connection();
+
+        let terms = vec!["delegation".to_string(), "100%".to_string()];
+        search_in_sessions(&db, &[Id::new_v4()], &terms, 10).await?;
+
+        let log = db.into_transaction_log();
+        let statement = &log[0].statements()[0];
+        assert!(statement.sql.contains(r#"("notes"."body" ILIKE ($2 ESCAPE E'\\')) OR ("notes"."body" ILIKE ($3 ESCAPE E'\\'))"#));
+        assert!(statement.sql.contains(r#""notes"."body" NOT LIKE $4"#));
+        let values = statement.values.as_ref().unwrap();
+        assert_eq!(values.0[2], sea_orm::Value::from("%100\\%%"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn search_in_sessions_skips_the_query_without_terms() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
+
+        assert!(search_in_sessions(&db, &[Id::new_v4()], &[], 10)
+            .await?
+            .is_empty());
+        assert!(db.into_transaction_log().is_empty());
+        Ok(())
+    }
 }
@@ -0,0 +1,41 @@
+use super::error::Error;
+use chrono::Utc;
+use entity::oauth_authorization_codes::{ActiveModel, Column, Entity, Model};
+use sea_orm::{entity::prelude::*, ActiveValue::Set, ConnectionTrait};
+
+/// Inserts a code, first clearing out codes that expired unused.
+pub async fn create(db: &impl ConnectionTrait, model: Model) -> Result<Model, Error> {
+    Entity::delete_many()
+        .filter(Column::ExpiresAt.lt(Utc::now()))
+        .exec(db)
+        .await?;
+
+    let active_model = ActiveModel {
+        code_hash: Set(model.code_hash),
+        client_id: Set(model.client_id),
+        user_id: Set(model.user_id),
+        redirect_uri: Set(model.redirect_uri),
+        code_challenge: Set(model.code_challenge),
+        scope: Set(model.scope),
+        expires_at: Set(model.expires_at),
+        created_at: Set(Utc::now().into()),
+        ..Default::default()
+    };
+
+    Ok(active_model.insert(db).await?)
+}
+
+/// Deletes and returns the code with the SHA-256 hash `code_hash`, so it can be
+/// exchanged only once even by concurrent requests. Expired codes are returned too, for
+/// the caller to reject.
+pub async fn take_by_code_hash(
+    db: &impl ConnectionTrait,
+    code_hash: &str,
+) -> Result<Option<Model>, Error> {
+    Ok(Entity::delete_many()
+        .filter(Column::CodeHash.eq(code_hash))
+        .exec_with_returning(db)
+        .await?
+        .into_iter()
+        .next())
+}
@@ -0,0 +1,76 @@
+use super::error::{EntityApiErrorKind, Error};
+use entity::oauth_clients::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{entity::prelude::*, ActiveValue::Set, ConnectionTrait, QueryOrder, TryIntoModel};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use log::*;
+
+/// A newly registered client with its secret, which isn't returned again.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[schema(as = domain::oauth_server::CreatedOAuthClient)]
+pub struct CreatedOAuthClient {
+    #[serde(flatten)]
+    pub client: Model,
+    /// The client secret of a confidential client; `None` for a public one. Store it
+    /// now: it can't be read back.
+    pub client_secret: Option<String>,
+}
+
+/// Inserts a client for `organization_id`, confidential when `client_secret_hash` is set.
+pub async fn create(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+    created_by_user_id: Id,
+    client_secret_hash: Option<String>,
+    model: Model,
+) -> Result<Model, Error> {
+    debug!(
+        "New OAuth client `{}` for organization {organization_id}",
+        model.name
+    );
+
+    let now = chrono::Utc::now();
+    let active_model = ActiveModel {
+        organization_id: Set(organization_id),
+        name: Set(model.name),
+        client_secret_hash: Set(client_secret_hash),
+        redirect_uris: Set(model.redirect_uris),
+        created_by_user_id: Set(Some(created_by_user_id)),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+        ..Default::default()
+    };
+
+    Ok(active_model.insert(db).await?.try_into_model()?)
+}
+
+/// Deletes a client along with its codes and grants.
+pub async fn delete_by_id(db: &impl ConnectionTrait, id: Id) -> Result<(), Error> {
+    Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
+
+pub async fn find_by_id(db: &impl ConnectionTrait, id: Id) -> Result<Model, Error> {
+    Entity::find_by_id(id).one(db).await?.ok_or_else(|| {
+        error!("OAuth client with id {id} not found");
+
+        Error {
+            source: None,
+            error_kind: EntityApiErrorKind::RecordNotFound,
+        }
+    })
+}
+
+/// All of an organization's clients, oldest first.
+pub async fn find_by_organization(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::OrganizationId.eq(organization_id))
+        .order_by_asc(Column::CreatedAt)
+        .all(db)
+        .await?)
+}
@@ -0,0 +1,211 @@
+//! The tokens OAuth clients hold on behalf of users.
+
+use super::error::Error;
+use chrono::Utc;
+use entity::oauth_grants::{ActiveModel, Column, Entity, Model};
+use entity::{oauth_clients, Id};
+use sea_orm::{
+    entity::prelude::*,
+    sea_query::Condition,
+    ActiveValue::{Set, Unchanged},
+    ConnectionTrait, IntoActiveModel, QueryOrder,
+};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use utoipa::ToSchema;
+
+/// A client a user has authorized and not revoked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[schema(as = domain::oauth_server::AuthorizedApp)]
+pub struct AuthorizedApp {
+    pub client_id: Id,
+    pub name: String,
+    pub organization_id: Id,
+    /// Space-separated scopes granted across the client's live tokens.
+    pub scope: String,
+    /// When the user first authorized the client.
+    #[schema(value_type = String, format = DateTime)]
+    pub authorized_at: DateTimeWithTimeZone,
+}
+
+pub async fn create(db: &impl ConnectionTrait, model: Model) -> Result<Model, Error> {
+    let now = Utc::now();
+    let active_model = ActiveModel {
+        client_id: Set(model.client_id),
+        user_id: Set(model.user_id),
+        scope: Set(model.scope),
+        access_token_hash: Set(model.access_token_hash),
+        access_token_expires_at: Set(model.access_token_expires_at),
+        refresh_token_hash: Set(model.refresh_token_hash),
+        refresh_token_expires_at: Set(model.refresh_token_expires_at),
+        revoked_at: Set(None),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+        ..Default::default()
+    };
+
+    Ok(active_model.insert(db).await?)
+}
+
+/// Replaces a grant's tokens with `replacement`'s, invalidating the old ones.
+pub async fn rotate(
+    db: &impl ConnectionTrait,
+    existing: Model,
+    replacement: Model,
+) -> Result<Model, Error> {
+    let active_model = ActiveModel {
+        id: Unchanged(existing.id),
+        access_token_hash: Set(replacement.access_token_hash),
+        access_token_expires_at: Set(replacement.access_token_expires_at),
+        refresh_token_hash: Set(replacement.refresh_token_hash),
+        refresh_token_expires_at: Set(replacement.refresh_token_expires_at),
+        updated_at: Set(Utc::now().into()),
+        ..existing.into_active_model()
+    };
+
+    Ok(active_model.update(db).await?)
+}
+
+/// The unrevoked grant whose access or refresh token has the SHA-256 hash `token_hash`,
+/// whether or not the token has expired.
+pub async fn find_by_token_hash(
+    db: &impl ConnectionTrait,
+    token_hash: &str,
+) -> Result<Option<Model>, Error> {
+    Ok(Entity::find()
+        .filter(
+            Condition::any()
+                .add(Column::AccessTokenHash.eq(token_hash))
+                .add(Column::RefreshTokenHash.eq(token_hash)),
+        )
+        .filter(Column::RevokedAt.is_null())
+        .one(db)
+        .await?)
+}
+
+/// Revokes a grant, invalidating both its tokens.
+pub async fn revoke(db: &impl ConnectionTrait, id: Id) -> Result<(), Error> {
+    Entity::update_many()
+        .col_expr(Column::RevokedAt, Expr::value(Utc::now()))
+        .filter(Column::Id.eq(id))
+        .filter(Column::RevokedAt.is_null())
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+/// Revokes every grant the user gave the client, returning how many were revoked.
+pub async fn revoke_for_client(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+    client_id: Id,
+) -> Result<u64, Error> {
+    let result = Entity::update_many()
+        .col_expr(Column::RevokedAt, Expr::value(Utc::now()))
+        .filter(Column::UserId.eq(user_id))
+        .filter(Column::ClientId.eq(client_id))
+        .filter(Column::RevokedAt.is_null())
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected)
+}
+
+/// The clients holding live (unrevoked, refreshable) grants from the user, in the order
+/// they were first authorized.
+pub async fn find_authorized_apps(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+) -> Result<Vec<AuthorizedApp>, Error> {
+    let grants = Entity::find()
+        .find_also_related(oauth_clients::Entity)
+        .filter(Column::UserId.eq(user_id))
+        .filter(Column::RevokedAt.is_null())
+        .filter(Column::RefreshTokenExpiresAt.gt(Utc::now()))
+        .order_by_asc(Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    let mut apps: Vec<(AuthorizedApp, BTreeSet<String>)> = Vec::new();
+    for (grant, client) in grants {
+        let Some(client) = client else { continue };
+        let scopes = grant.scope.split_whitespace().map(str::to_string);
+        match apps.iter_mut().find(|(app, _)| app.client_id == client.id) {
+            Some((_, granted)) => granted.extend(scopes),
+            None => apps.push((
+                AuthorizedApp {
+                    client_id: client.id,
+                    name: client.name,
+                    organization_id: client.organization_id,
+                    scope: String::new(),
+                    authorized_at: grant.created_at,
+                },
+                scopes.collect(),
+            )),
+        }
+    }
+
+    Ok(apps
+        .into_iter()
+        .map(|(app, granted)| AuthorizedApp {
+            scope: granted.into_iter().collect::<Vec<_>>().join(" "),
+            ..app
+        })
+        .collect())
+}
+
+#[cfg(test)]
+// We need to gate seaORM's mock feature behind conditional compilation because
+// the feature removes the Clone trait implementation from seaORM's DatabaseConnection.
+// see https://github.com/SeaQL/sea-orm/issues/830
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use entity::oauth_clients::RedirectUris;
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    fn grant(client_id: Id, scope: &str) -> Model {
+        let now = Utc::now();
+        Model {
+            id: Id::new_v4(),
+            client_id,
+            user_id: Id::new_v4(),
+            scope: scope.to_string(),
+            access_token_hash: "access".to_string(),
+            access_token_expires_at: now.into(),
+            refresh_token_hash: "refresh".to_string(),
+            refresh_token_expires_at: now.into(),
+            revoked_at: None,
+            created_at: now.into(),
+            updated_at: now.into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn find_authorized_apps_merges_the_grants_of_each_client() -> Result<(), Error> {
+        let now = Utc::now();
+        let client = oauth_clients::Model {
+            id: Id::new_v4(),
+            organization_id: Id::new_v4(),
+            name: "Calendar sync".to_string(),
+            client_secret_hash: None,
+            redirect_uris: RedirectUris(vec!["https://app.example.com/callback".to_string()]),
+            created_by_user_id: None,
+            created_at: now.into(),
+            updated_at: now.into(),
+        };
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![
+                (grant(client.id, "read"), client.clone()),
+                (grant(client.id, "write read"), client.clone()),
+            ]])
+            .into_connection();
+
+        let apps = find_authorized_apps(&db, Id::new_v4()).await?;
+
+        assert_eq!(apps.len(), 1);
+        assert_eq!(apps[0].client_id, client.id);
+        assert_eq!(apps[0].name, "Calendar sync");
+        assert_eq!(apps[0].scope, "read write");
+        Ok(())
+    }
+}
@@ -3,14 +3,16 @@ use crate::{organization::Entity, uuid_parse_str};
 use chrono::Utc;
 use entity::{
     coaching_relationships, coaching_sessions, organizations::*, prelude::Organizations, roles,
-    user_roles, Id,
+    user_locks, user_roles, Id,
 };
 use sea_orm::{
     entity::prelude::*, ActiveValue::Set, ConnectionTrait, IntoActiveModel, JoinType, QuerySelect,
-    SqlErr, TransactionTrait, TryIntoModel,
+    QueryTrait, SqlErr, TransactionTrait, TryIntoModel,
 };
+use serde::Serialize;
 use slugify::slugify;
 use std::collections::HashMap;
+use utoipa::ToSchema;
 
 use log::*;
 
@@ -151,6 +153,18 @@ pub async fn update(db: &impl TransactionTrait, id: Id, model: Model) -> Result<
     Ok(updated)
 }
 
+/// Sets the organization's logo URL, leaving its name and slug alone.
+pub async fn update_logo(
+    db: &impl ConnectionTrait,
+    id: Id,
+    logo: Option<String>,
+) -> Result<Model, Error> {
+    let mut active_model = find_by_id(db, id).await?.into_active_model();
+    active_model.logo = Set(logo);
+    active_model.updated_at = Set(Utc::now().into());
+    Ok(active_model.update(db).await?.try_into_model()?)
+}
+
 /// Archive an organization (idempotent). Re-archiving is a no-op that avoids
 /// timestamp churn.
 pub async fn archive(db: &impl TransactionTrait, id: Id, archived_by: Id) -> Result<Model, Error> {
@@ -313,6 +327,77 @@ pub async fn find_by_user(
     Ok(organizations)
 }
 
+/// Counts of what an organization holds, for platform admins.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[schema(as = domain::organization::OrganizationStats)]
+pub struct OrganizationStats {
+    pub organization_id: Id,
+    /// Users with any role in the organization.
+    pub member_count: u64,
+    /// Members whose accounts are locked.
+    pub locked_member_count: u64,
+    pub coaching_relationship_count: u64,
+    /// Distinct coaches and coachees across its relationships.
+    pub coach_count: u64,
+    pub coachee_count: u64,
+    pub coaching_session_count: u64,
+    /// Sessions scheduled from now on.
+    pub upcoming_coaching_session_count: u64,
+}
+
+/// Counts the organization's members, relationships and sessions.
+pub async fn stats(db: &impl ConnectionTrait, id: Id) -> Result<OrganizationStats, Error> {
+    find_by_id(db, id).await?;
+
+    let members = || {
+        user_roles::Entity::find()
+            .select_only()
+            .column(user_roles::Column::UserId)
+            .filter(user_roles::Column::OrganizationId.eq(id))
+    };
+    let relationships = || {
+        coaching_relationships::Entity::find()
+            .filter(coaching_relationships::Column::OrganizationId.eq(id))
+    };
+    let sessions = || {
+        coaching_sessions::Entity::find().filter(
+            coaching_sessions::Column::CoachingRelationshipId.in_subquery(
+                relationships()
+                    .select_only()
+                    .column(coaching_relationships::Column::Id)
+                    .into_query(),
+            ),
+        )
+    };
+
+    Ok(OrganizationStats {
+        organization_id: id,
+        member_count: members().distinct().count(db).await?,
+        locked_member_count: user_locks::Entity::find()
+            .filter(user_locks::Column::UserId.in_subquery(members().into_query()))
+            .count(db)
+            .await?,
+        coaching_relationship_count: relationships().count(db).await?,
+        coach_count: relationships()
+            .select_only()
+            .column(coaching_relationships::Column::CoachId)
+            .distinct()
+            .count(db)
+            .await?,
+        coachee_count: relationships()
+            .select_only()
+            .column(coaching_relationships::Column::CoacheeId)
+            .distinct()
+            .count(db)
+            .await?,
+        coaching_session_count: sessions().count(db).await?,
+        upcoming_coaching_session_count: sessions()
+            .filter(coaching_sessions::Column::Date.gte(Utc::now().naive_utc()))
+            .count(db)
+            .await?,
+    })
+}
+
 async fn by_user(query: Select<Organizations>, user_id: Id) -> Select<Organizations> {
     query
         .join(JoinType::InnerJoin, Relation::UserRoles.def())
@@ -514,18 +599,9 @@ mod tests {
     #[tokio::test]
     async fn delete_by_id_blocks_when_not_empty() {
         let org = test_org("Acme", false);
-        let relationship = coaching_relationships::Model {
-            id: Id::new_v4(),
-            organization_id: org.id,
-            coach_id: Id::new_v4(),
-            coachee_id: Id::new_v4(),
-            slug: "rel".to_owned(),
-            created_at: Utc::now().into(),
-            updated_at: Utc::now().into(),
-        };
         let db = MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results(vec![vec![org.clone()]]) // find_by_id
-            .append_query_results(vec![vec![relationship.clone()]]) // rel ids (len = 1)
+            .append_query_results(vec![vec![maplike_id(Id::new_v4())]]) // rel ids (len = 1)
             .append_query_results(vec![vec![maplike_count(3)]]) // member count
             .append_query_results(vec![vec![maplike_count(2)]]) // session count
             .into_connection();
@@ -542,6 +618,45 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn stats_counts_members_relationships_and_sessions() -> Result<(), Error> {
+        let org = test_org("Acme", false);
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![org.clone()]]) // find_by_id
+            .append_query_results(vec![vec![maplike_count(9)]]) // members
+            .append_query_results(vec![vec![maplike_count(1)]]) // locked members
+            .append_query_results(vec![vec![maplike_count(6)]]) // relationships
+            .append_query_results(vec![vec![maplike_count(2)]]) // coaches
+            .append_query_results(vec![vec![maplike_count(6)]]) // coachees
+            .append_query_results(vec![vec![maplike_count(40)]]) // sessions
+            .append_query_results(vec![vec![maplike_count(4)]]) // upcoming sessions
+            .into_connection();
+
+        let stats = stats(&db, org.id).await?;
+
+        assert_eq!(
+            stats,
+            OrganizationStats {
+                organization_id: org.id,
+                member_count: 9,
+                locked_member_count: 1,
+                coaching_relationship_count: 6,
+                coach_count: 2,
+                coachee_count: 6,
+                coaching_session_count: 40,
+                upcoming_coaching_session_count: 4,
+            }
+        );
+        let log = db.into_transaction_log();
+        assert!(log[1].statements()[0]
+            .sql
+            .contains(r#"SELECT DISTINCT "user_roles"."user_id""#));
+        assert!(log[4].statements()[0]
+            .sql
+            .contains(r#"SELECT DISTINCT "coaching_relationships"."coach_id""#));
+        Ok(())
+    }
+
     #[tokio::test]
     async fn delete_by_id_blocks_when_only_members() {
         // An org with members but zero relationships must still block (not silently
@@ -724,6 +839,12 @@ mod tests {
     }
 
     // Helper to produce a `.count()` scalar result row.
+    fn maplike_id(id: Id) -> std::collections::BTreeMap<String, sea_orm::Value> {
+        let mut m = std::collections::BTreeMap::new();
+        m.insert("id".to_owned(), sea_orm::Value::Uuid(Some(Box::new(id))));
+        m
+    }
+
     fn maplike_count(n: i64) -> std::collections::BTreeMap<String, sea_orm::Value> {
         let mut m = std::collections::BTreeMap::new();
         m.insert("num_items".to_owned(), sea_orm::Value::BigInt(Some(n)));
@@ -0,0 +1,46 @@
+use super::error::Error;
+use entity::organization_ai_settings::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{entity::prelude::*, sea_query::OnConflict, ActiveValue::Set, ConnectionTrait};
+
+use log::*;
+
+pub async fn find_by_organization_id(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+) -> Result<Option<Model>, Error> {
+    Ok(Entity::find_by_id(organization_id).one(db).await?)
+}
+
+/// Stores an organization's AI settings, replacing any earlier ones.
+pub async fn upsert(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+    model: Model,
+) -> Result<Model, Error> {
+    debug!("Storing AI settings for organization {organization_id}");
+
+    let now = chrono::Utc::now();
+    let active_model = ActiveModel {
+        organization_id: Set(organization_id),
+        allowed_providers: Set(model.allowed_providers),
+        allowed_models: Set(model.allowed_models),
+        tasks: Set(model.tasks),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+    };
+
+    let on_conflict = OnConflict::column(Column::OrganizationId)
+        .update_columns([
+            Column::AllowedProviders,
+            Column::AllowedModels,
+            Column::Tasks,
+            Column::UpdatedAt,
+        ])
+        .to_owned();
+
+    Ok(Entity::insert(active_model)
+        .on_conflict(on_conflict)
+        .exec_with_returning(db)
+        .await?)
+}
@@ -0,0 +1,56 @@
+use super::error::Error;
+use entity::organization_api_quotas::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{entity::prelude::*, sea_query::OnConflict, ActiveValue::Set, ConnectionTrait};
+
+use log::*;
+
+pub async fn find_by_organization_id(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+) -> Result<Option<Model>, Error> {
+    Ok(Entity::find_by_id(organization_id).one(db).await?)
+}
+
+pub async fn find_all(db: &impl ConnectionTrait) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find().all(db).await?)
+}
+
+/// Stores an organization's API quota, replacing any earlier one.
+pub async fn upsert(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+    model: Model,
+) -> Result<Model, Error> {
+    debug!("Storing API quota for organization {organization_id}");
+
+    let now = chrono::Utc::now();
+    let active_model = ActiveModel {
+        organization_id: Set(organization_id),
+        requests_per_hour: Set(model.requests_per_hour),
+        endpoint_limits: Set(model.endpoint_limits),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+    };
+
+    let on_conflict = OnConflict::column(Column::OrganizationId)
+        .update_columns([
+            Column::RequestsPerHour,
+            Column::EndpointLimits,
+            Column::UpdatedAt,
+        ])
+        .to_owned();
+
+    Ok(Entity::insert(active_model)
+        .on_conflict(on_conflict)
+        .exec_with_returning(db)
+        .await?)
+}
+
+pub async fn delete_by_organization_id(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+) -> Result<(), Error> {
+    Entity::delete_by_id(organization_id).exec(db).await?;
+    Ok(())
+}
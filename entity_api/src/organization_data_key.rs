@@ -0,0 +1,144 @@
+//! Data keys organizations encrypt note and journal entry bodies with. See
+//! [`crate::field_encryption`].
+
+use super::error::Error;
+use chrono::Utc;
+use entity::organization_data_keys::{ActiveModel, Column, Entity, Model};
+use entity::{coaching_relationships, coaching_sessions, Id};
+use sea_orm::{
+    entity::prelude::*, sea_query::Expr, ActiveValue::Set, ConnectionTrait, QueryOrder,
+    QuerySelect, QueryTrait,
+};
+
+/// Stores a new active key for the organization. Retire its current key first.
+pub async fn create(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+    wrapped_key: String,
+) -> Result<Model, Error> {
+    let active_model = ActiveModel {
+        organization_id: Set(organization_id),
+        wrapped_key: Set(wrapped_key),
+        created_at: Set(Utc::now().into()),
+        ..Default::default()
+    };
+
+    Ok(active_model.insert(db).await?)
+}
+
+pub async fn find_by_id(db: &impl ConnectionTrait, id: Id) -> Result<Option<Model>, Error> {
+    Ok(Entity::find_by_id(id).one(db).await?)
+}
+
+/// The organization's active key, if it encrypts its bodies.
+pub async fn find_active(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+) -> Result<Option<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::OrganizationId.eq(organization_id))
+        .filter(Column::RetiredAt.is_null())
+        .one(db)
+        .await?)
+}
+
+/// The active key of the organization a coaching relationship belongs to.
+pub async fn find_active_for_relationship(
+    db: &impl ConnectionTrait,
+    coaching_relationship_id: Id,
+) -> Result<Option<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::RetiredAt.is_null())
+        .filter(
+            Column::OrganizationId.in_subquery(
+                coaching_relationships::Entity::find()
+                    .select_only()
+                    .column(coaching_relationships::Column::OrganizationId)
+                    .filter(coaching_relationships::Column::Id.eq(coaching_relationship_id))
+                    .into_query(),
+            ),
+        )
+        .one(db)
+        .await?)
+}
+
+/// The active key of the organization a coaching session belongs to.
+pub async fn find_active_for_session(
+    db: &impl ConnectionTrait,
+    coaching_session_id: Id,
+) -> Result<Option<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::RetiredAt.is_null())
+        .filter(
+            Column::OrganizationId.in_subquery(
+                coaching_relationships::Entity::find()
+                    .select_only()
+                    .column(coaching_relationships::Column::OrganizationId)
+                    .filter(
+                        coaching_relationships::Column::Id.in_subquery(
+                            coaching_sessions::Entity::find()
+                                .select_only()
+                                .column(coaching_sessions::Column::CoachingRelationshipId)
+                                .filter(coaching_sessions::Column::Id.eq(coaching_session_id))
+                                .into_query(),
+                        ),
+                    )
+                    .into_query(),
+            ),
+        )
+        .one(db)
+        .await?)
+}
+
+/// All of the organization's keys, newest first.
+pub async fn find_by_organization(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::OrganizationId.eq(organization_id))
+        .order_by_desc(Column::CreatedAt)
+        .all(db)
+        .await?)
+}
+
+/// Retires the organization's active key, if any, so a new one can be created.
+pub async fn retire_active(db: &impl ConnectionTrait, organization_id: Id) -> Result<u64, Error> {
+    Ok(Entity::update_many()
+        .col_expr(Column::RetiredAt, Expr::current_timestamp().into())
+        .filter(Column::OrganizationId.eq(organization_id))
+        .filter(Column::RetiredAt.is_null())
+        .exec(db)
+        .await?
+        .rows_affected)
+}
+
+#[cfg(test)]
+// We need to gate seaORM's mock feature behind conditional compilation because
+// the feature removes the Clone trait implementation from seaORM's DatabaseConnection.
+// see https://github.com/SeaQL/sea-orm/issues/830
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase, Transaction};
+
+    #[tokio::test]
+    async fn find_active_for_session_resolves_the_sessions_organization() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<Model>::new()])
+            .into_connection();
+        let coaching_session_id = Id::new_v4();
+
+        find_active_for_session(&db, coaching_session_id).await?;
+
+        assert_eq!(
+            db.into_transaction_log(),
+            [Transaction::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"SELECT "organization_data_keys"."id", "organization_data_keys"."organization_id", "organization_data_keys"."wrapped_key", "organization_data_keys"."created_at", "organization_data_keys"."retired_at" FROM "refactor_platform"."organization_data_keys" WHERE "organization_data_keys"."retired_at" IS NULL AND "organization_data_keys"."organization_id" IN (SELECT "coaching_relationships"."organization_id" FROM "refactor_platform"."coaching_relationships" WHERE "coaching_relationships"."id" IN (SELECT "coaching_sessions"."coaching_relationship_id" FROM "refactor_platform"."coaching_sessions" WHERE "coaching_sessions"."id" = $1)) LIMIT $2"#,
+                [coaching_session_id.into(), 1u64.into()]
+            )]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use super::error::Error;
+use entity::organization_holidays::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{
+    entity::prelude::*, sea_query::OnConflict, ActiveValue::Set, ConnectionTrait, QueryOrder,
+};
+
+/// Stores `holidays` in the organization's calendar; one whose `uid` the calendar already
+/// has replaces that holiday's name and dates. Returns how many were stored.
+pub async fn upsert_many(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+    holidays: Vec<Model>,
+) -> Result<u64, Error> {
+    if holidays.is_empty() {
+        return Ok(0);
+    }
+
+    let now = chrono::Utc::now();
+    let active_models = holidays.into_iter().map(|holiday| ActiveModel {
+        organization_id: Set(organization_id),
+        name: Set(holiday.name),
+        starts_on: Set(holiday.starts_on),
+        ends_on: Set(holiday.ends_on),
+        uid: Set(holiday.uid),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+        ..Default::default()
+    });
+
+    let on_conflict = OnConflict::columns([Column::OrganizationId, Column::Uid])
+        .update_columns([
+            Column::Name,
+            Column::StartsOn,
+            Column::EndsOn,
+            Column::UpdatedAt,
+        ])
+        .to_owned();
+
+    Ok(Entity::insert_many(active_models)
+        .on_conflict(on_conflict)
+        .exec_without_returning(db)
+        .await?)
+}
+
+/// The organization's holidays, earliest first.
+pub async fn find_by_organization(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::OrganizationId.eq(organization_id))
+        .order_by_asc(Column::StartsOn)
+        .all(db)
+        .await?)
+}
+
+/// The organization's holidays on any day from `from` through `to`, earliest first.
+pub async fn find_overlapping(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+    from: Date,
+    to: Date,
+) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::OrganizationId.eq(organization_id))
+        .filter(Column::StartsOn.lte(to))
+        .filter(Column::EndsOn.gte(from))
+        .order_by_asc(Column::StartsOn)
+        .all(db)
+        .await?)
+}
+
+/// Deletes the organization's holiday with `id`, returning how many were deleted (0 or 1).
+pub async fn delete_by_organization_and_id(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+    id: Id,
+) -> Result<u64, Error> {
+    Ok(Entity::delete_many()
+        .filter(Column::OrganizationId.eq(organization_id))
+        .filter(Column::Id.eq(id))
+        .exec(db)
+        .await?
+        .rows_affected)
+}
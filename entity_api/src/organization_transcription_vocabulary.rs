@@ -0,0 +1,42 @@
+use super::error::Error;
+use entity::organization_transcription_vocabularies::{ActiveModel, Column, Entity, Model, Terms};
+use entity::Id;
+use sea_orm::{entity::prelude::*, sea_query::OnConflict, ActiveValue::Set, ConnectionTrait};
+
+use log::*;
+
+pub async fn find_by_organization_id(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+) -> Result<Option<Model>, Error> {
+    Ok(Entity::find_by_id(organization_id).one(db).await?)
+}
+
+/// Stores an organization's transcription vocabulary, replacing any earlier one.
+pub async fn upsert(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+    terms: Terms,
+) -> Result<Model, Error> {
+    debug!(
+        "Storing {} transcription vocabulary term(s) for organization {organization_id}",
+        terms.0.len()
+    );
+
+    let now = chrono::Utc::now();
+    let active_model = ActiveModel {
+        organization_id: Set(organization_id),
+        terms: Set(terms),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+    };
+
+    let on_conflict = OnConflict::column(Column::OrganizationId)
+        .update_columns([Column::Terms, Column::UpdatedAt])
+        .to_owned();
+
+    Ok(Entity::insert(active_model)
+        .on_conflict(on_conflict)
+        .exec_with_returning(db)
+        .await?)
+}
@@ -0,0 +1,124 @@
+use super::error::{EntityApiErrorKind, Error};
+use entity::out_of_office_periods::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{
+    entity::prelude::*,
+    ActiveValue::{Set, Unchanged},
+    ConnectionTrait, QueryOrder, TryIntoModel,
+};
+
+use log::*;
+
+pub async fn create(db: &impl ConnectionTrait, user_id: Id, model: Model) -> Result<Model, Error> {
+    debug!("New out of office period for user {user_id}: {model:?}");
+
+    let now = chrono::Utc::now();
+    let active_model = ActiveModel {
+        user_id: Set(user_id),
+        starts_at: Set(model.starts_at),
+        ends_at: Set(model.ends_at),
+        note: Set(model.note),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+        ..Default::default()
+    };
+
+    Ok(active_model.insert(db).await?.try_into_model()?)
+}
+
+pub async fn update(
+    db: &impl ConnectionTrait,
+    existing: Model,
+    model: Model,
+) -> Result<Model, Error> {
+    let active_model = ActiveModel {
+        id: Unchanged(existing.id),
+        user_id: Unchanged(existing.user_id),
+        starts_at: Set(model.starts_at),
+        ends_at: Set(model.ends_at),
+        note: Set(model.note),
+        created_at: Unchanged(existing.created_at),
+        updated_at: Set(chrono::Utc::now().into()),
+    };
+
+    Ok(active_model.update(db).await?.try_into_model()?)
+}
+
+pub async fn delete_by_id(db: &impl ConnectionTrait, id: Id) -> Result<(), Error> {
+    Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
+
+pub async fn find_by_id(db: &impl ConnectionTrait, id: Id) -> Result<Model, Error> {
+    Entity::find_by_id(id).one(db).await?.ok_or_else(|| {
+        error!("Out of office period with id {id} not found");
+
+        Error {
+            source: None,
+            error_kind: EntityApiErrorKind::RecordNotFound,
+        }
+    })
+}
+
+/// The user's periods that haven't ended by `after`, soonest first.
+pub async fn find_by_user(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+    after: DateTimeWithTimeZone,
+) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::UserId.eq(user_id))
+        .filter(Column::EndsAt.gt(after))
+        .order_by_asc(Column::StartsAt)
+        .all(db)
+        .await?)
+}
+
+/// The periods of any of `user_ids` that overlap `[from, to)`, soonest first.
+pub async fn find_overlapping(
+    db: &impl ConnectionTrait,
+    user_ids: &[Id],
+    from: DateTimeWithTimeZone,
+    to: DateTimeWithTimeZone,
+) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::UserId.is_in(user_ids.iter().copied()))
+        .filter(Column::StartsAt.lt(to))
+        .filter(Column::EndsAt.gt(from))
+        .order_by_asc(Column::StartsAt)
+        .all(db)
+        .await?)
+}
+
+#[cfg(test)]
+// We need to gate seaORM's mock feature behind conditional compilation because
+// the feature removes the Clone trait implementation from seaORM's DatabaseConnection.
+// see https://github.com/SeaQL/sea-orm/issues/830
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    #[tokio::test]
+    async fn find_overlapping_matches_periods_crossing_the_range() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<Model>::new()])
+            .into_connection();
+        let from = chrono::Utc::now();
+
+        find_overlapping(
+            &db,
+            &[Id::new_v4(), Id::new_v4()],
+            from.into(),
+            (from + chrono::Duration::hours(1)).into(),
+        )
+        .await?;
+
+        let log = db.into_transaction_log();
+        let sql = &log[0].statements()[0].sql;
+        assert!(sql.contains(r#""out_of_office_periods"."user_id" IN ($1, $2)"#));
+        assert!(sql.contains(r#""out_of_office_periods"."starts_at" < $3"#));
+        assert!(sql.contains(r#""out_of_office_periods"."ends_at" > $4"#));
+        Ok(())
+    }
+}
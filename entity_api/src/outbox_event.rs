@@ -0,0 +1,124 @@
+use super::error::Error;
+use entity::outbox_events::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{
+    entity::prelude::*, ActiveValue::Set, ConnectionTrait, DbBackend, Statement, TryIntoModel,
+};
+use std::time::Duration;
+
+use log::*;
+
+/// Stores an event for the events worker; `id`, the claim fields and `created_at` are
+/// assigned here.
+pub async fn create(
+    db: &impl ConnectionTrait,
+    event_type: &str,
+    payload: Json,
+    actor_id: Option<Id>,
+) -> Result<Model, Error> {
+    debug!("Adding {event_type} event to the outbox");
+
+    let active_model = ActiveModel {
+        event_type: Set(event_type.to_string()),
+        payload: Set(payload),
+        actor_id: Set(actor_id),
+        attempts: Set(0),
+        locked_by: Set(None),
+        locked_until: Set(None),
+        dispatched_at: Set(None),
+        created_at: Set(chrono::Utc::now().into()),
+        ..Default::default()
+    };
+
+    Ok(active_model.insert(db).await?.try_into_model()?)
+}
+
+/// Claims up to `limit` of the oldest undispatched events for `worker_id` for `lease`,
+/// oldest first. Events claimed by another worker whose lease hasn't lapsed are skipped,
+/// as are rows another worker is claiming concurrently, so any number of workers can
+/// poll the same outbox.
+pub async fn claim(
+    db: &impl ConnectionTrait,
+    worker_id: &str,
+    limit: u64,
+    lease: Duration,
+) -> Result<Vec<Model>, Error> {
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        r#"UPDATE refactor_platform.outbox_events
+           SET locked_by = $1,
+               locked_until = NOW() + make_interval(secs => $2),
+               attempts = attempts + 1
+           WHERE id IN (
+               SELECT id FROM refactor_platform.outbox_events
+               WHERE dispatched_at IS NULL
+                 AND (locked_until IS NULL OR locked_until < NOW())
+               ORDER BY created_at
+               LIMIT $3
+               FOR UPDATE SKIP LOCKED
+           )
+           RETURNING *"#,
+        [
+            worker_id.into(),
+            lease.as_secs_f64().into(),
+            (limit as i64).into(),
+        ],
+    );
+
+    // RETURNING doesn't preserve the subquery's order.
+    let mut claimed = Entity::find().from_raw_sql(stmt).all(db).await?;
+    claimed.sort_by_key(|event| event.created_at);
+    Ok(claimed)
+}
+
+/// Marks the event as dispatched and releases its claim.
+pub async fn mark_dispatched(db: &impl ConnectionTrait, id: Id) -> Result<(), Error> {
+    Entity::update_many()
+        .col_expr(
+            Column::DispatchedAt,
+            Expr::value(chrono::Utc::now().fixed_offset()),
+        )
+        .col_expr(Column::LockedBy, Expr::value(Option::<String>::None))
+        .col_expr(
+            Column::LockedUntil,
+            Expr::value(Option::<DateTimeWithTimeZone>::None),
+        )
+        .filter(Column::Id.eq(id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+/// Deletes events dispatched before `before`, returning how many were deleted.
+pub async fn delete_dispatched_before(
+    db: &impl ConnectionTrait,
+    before: DateTimeWithTimeZone,
+) -> Result<u64, Error> {
+    Ok(Entity::delete_many()
+        .filter(Column::DispatchedAt.lt(before))
+        .exec(db)
+        .await?
+        .rows_affected)
+}
+
+#[cfg(test)]
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    #[tokio::test]
+    async fn claim_skips_events_other_workers_hold() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<Model>::new()])
+            .into_connection();
+
+        claim(&db, "worker-1", 100, Duration::from_secs(60)).await?;
+
+        let sql = db.into_transaction_log()[0].statements()[0].sql.clone();
+        assert!(sql.contains("WHERE dispatched_at IS NULL"));
+        assert!(sql.contains("locked_until IS NULL OR locked_until < NOW()"));
+        assert!(sql.contains("FOR UPDATE SKIP LOCKED"));
+        Ok(())
+    }
+}
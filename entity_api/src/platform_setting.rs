@@ -0,0 +1,189 @@
+//! The platform's settings, and the one-time bootstrap that writes them.
+//!
+//! A fresh database has no users and no settings. [`bootstrap`] creates the first
+//! SuperAdmin, the first organization and the settings together; once the settings row
+//! exists it refuses to run again, even if every user is deleted later.
+
+use super::error::{EntityApiErrorKind, Error};
+use chrono::Utc;
+use entity::platform_settings::{ActiveModel, Entity, Model};
+use entity::{organizations, roles::Role, user_roles, users};
+use log::*;
+use sea_orm::{entity::prelude::*, ActiveValue::Set, ConnectionTrait, SqlErr, TransactionTrait};
+
+/// Primary key of the only settings row.
+const SETTINGS_ID: i16 = 1;
+
+/// The platform's settings, unless it hasn't been bootstrapped.
+pub async fn find(db: &impl ConnectionTrait) -> Result<Option<Model>, Error> {
+    Ok(Entity::find_by_id(SETTINGS_ID).one(db).await?)
+}
+
+/// Whether the platform can still be bootstrapped: it has neither settings nor users.
+pub async fn is_bootstrappable(db: &impl ConnectionTrait) -> Result<bool, Error> {
+    if find(db).await?.is_some() {
+        return Ok(false);
+    }
+    Ok(users::Entity::find().one(db).await?.is_none())
+}
+
+/// What [`bootstrap`] created.
+#[derive(Debug)]
+pub struct Bootstrapped {
+    pub user: users::Model,
+    pub organization: organizations::Model,
+    pub settings: Model,
+}
+
+/// Creates `user` as a SuperAdmin and the admin of the new `organization`, and saves
+/// `settings` as the platform's, all in one transaction.
+///
+/// # Errors
+///
+/// Returns a `ValidationError` if the platform has users or settings already, or the
+/// error of whichever record couldn't be created.
+pub async fn bootstrap(
+    db: &impl TransactionTrait,
+    user: users::Model,
+    organization: organizations::Model,
+    settings: Model,
+) -> Result<Bootstrapped, Error> {
+    let txn = db.begin().await?;
+    if !is_bootstrappable(&txn).await? {
+        return Err(already_bootstrapped());
+    }
+
+    let organization = crate::organization::create(&txn, organization).await?;
+    let mut user = crate::user::create(&txn, user).await?;
+    let now = Utc::now();
+    for (role, organization_id) in [
+        (Role::SuperAdmin, None),
+        (Role::Admin, Some(organization.id)),
+    ] {
+        let role = user_roles::ActiveModel {
+            user_id: Set(user.id),
+            organization_id: Set(organization_id),
+            role: Set(role),
+            created_at: Set(now.into()),
+            updated_at: Set(now.into()),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+        user.roles.push(role);
+    }
+
+    let settings = ActiveModel {
+        id: Set(SETTINGS_ID),
+        platform_name: Set(settings.platform_name),
+        support_email: Set(settings.support_email),
+        default_timezone: Set(settings.default_timezone),
+        bootstrapped_by_id: Set(Some(user.id)),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+    }
+    .insert(&txn)
+    .await
+    .map_err(|e| match e.sql_err() {
+        // A bootstrap that ran at the same time got there first
+        Some(SqlErr::UniqueConstraintViolation(_)) => already_bootstrapped(),
+        _ => e.into(),
+    })?;
+
+    txn.commit().await?;
+    info!(
+        "Bootstrapped the platform with SuperAdmin {} and organization {}",
+        user.id, organization.id
+    );
+
+    Ok(Bootstrapped {
+        user,
+        organization,
+        settings,
+    })
+}
+
+fn already_bootstrapped() -> Error {
+    Error {
+        source: None,
+        error_kind: EntityApiErrorKind::ValidationError {
+            message: "The platform has already been set up".to_string(),
+            details: None,
+        },
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    fn settings() -> Model {
+        let now = Utc::now();
+        Model {
+            id: SETTINGS_ID,
+            platform_name: "Refactor Platform".to_string(),
+            support_email: None,
+            default_timezone: "UTC".to_string(),
+            bootstrapped_by_id: None,
+            created_at: now.into(),
+            updated_at: now.into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn a_platform_with_settings_is_not_bootstrappable() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![settings()]])
+            .into_connection();
+
+        assert!(!is_bootstrappable(&db).await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn a_platform_without_settings_or_users_is_bootstrappable() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<Model>::new()])
+            .append_query_results([Vec::<users::Model>::new()])
+            .into_connection();
+
+        assert!(is_bootstrappable(&db).await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn bootstrap_refuses_to_run_twice() {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![settings()]])
+            .into_connection();
+        let now = Utc::now();
+        let organization = organizations::Model {
+            id: entity::Id::new_v4(),
+            name: "Acme".to_string(),
+            logo: None,
+            slug: String::new(),
+            created_at: now.into(),
+            updated_at: now.into(),
+            archived_at: None,
+            archived_by: None,
+        };
+        let user: users::Model = serde_json::from_value(serde_json::json!({
+            "email": "admin@example.com",
+            "first_name": "Ada",
+            "last_name": "Admin",
+            "display_name": null,
+            "password": "a long passphrase",
+            "github_username": null,
+            "github_profile_url": null,
+        }))
+        .unwrap();
+
+        let result = bootstrap(&db, user, organization, settings()).await;
+
+        assert_eq!(result.unwrap_err(), already_bootstrapped());
+    }
+}
@@ -0,0 +1,47 @@
+use super::error::Error;
+use chrono::Utc;
+use entity::policy_acceptances::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{entity::prelude::*, ActiveValue::Set, ConnectionTrait};
+
+/// Records that the user accepted the policy document.
+pub async fn create(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+    policy_document_id: Id,
+) -> Result<Model, Error> {
+    let active_model = ActiveModel {
+        user_id: Set(user_id),
+        policy_document_id: Set(policy_document_id),
+        accepted_at: Set(Utc::now().into()),
+        ..Default::default()
+    };
+
+    Ok(active_model.insert(db).await?)
+}
+
+/// The user's acceptance of the policy document, if they've accepted it.
+pub async fn find_by_user_and_document(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+    policy_document_id: Id,
+) -> Result<Option<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::UserId.eq(user_id))
+        .filter(Column::PolicyDocumentId.eq(policy_document_id))
+        .one(db)
+        .await?)
+}
+
+/// The user's acceptances of any of `policy_document_ids`.
+pub async fn find_by_user_and_documents(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+    policy_document_ids: &[Id],
+) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::UserId.eq(user_id))
+        .filter(Column::PolicyDocumentId.is_in(policy_document_ids.to_vec()))
+        .all(db)
+        .await?)
+}
@@ -0,0 +1,71 @@
+use super::error::{EntityApiErrorKind, Error};
+use chrono::{DateTime, Utc};
+use entity::policy_documents::{Column, Entity, Model};
+use entity::Id;
+use sea_orm::{entity::prelude::*, ConnectionTrait, QueryOrder};
+
+pub async fn find_by_id(db: &impl ConnectionTrait, id: Id) -> Result<Model, Error> {
+    Entity::find_by_id(id).one(db).await?.ok_or_else(|| Error {
+        source: None,
+        error_kind: EntityApiErrorKind::RecordNotFound,
+    })
+}
+
+/// The version of each policy in effect at `now`: per kind, the one with the latest
+/// `effective_at` that has passed.
+pub async fn find_in_effect(
+    db: &impl ConnectionTrait,
+    now: DateTime<Utc>,
+) -> Result<Vec<Model>, Error> {
+    let documents = Entity::find()
+        .filter(Column::EffectiveAt.lte(now))
+        .order_by_desc(Column::EffectiveAt)
+        .all(db)
+        .await?;
+
+    let mut in_effect: Vec<Model> = Vec::new();
+    for document in documents {
+        if !in_effect
+            .iter()
+            .any(|current| current.kind == document.kind)
+        {
+            in_effect.push(document);
+        }
+    }
+    Ok(in_effect)
+}
+
+#[cfg(test)]
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use entity::policy_kind::PolicyKind;
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    fn document(kind: PolicyKind, version: &str, days_ago: i64) -> Model {
+        let effective_at = Utc::now() - chrono::Duration::days(days_ago);
+        Model {
+            id: Id::new_v4(),
+            kind,
+            version: version.to_string(),
+            effective_at: effective_at.into(),
+            content_url: format!("https://example.com/{version}"),
+            created_at: effective_at.into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn find_in_effect_returns_the_latest_version_of_each_kind() -> Result<(), Error> {
+        let tos_v2 = document(PolicyKind::TermsOfService, "2", 1);
+        let privacy_v1 = document(PolicyKind::PrivacyPolicy, "1", 10);
+        let tos_v1 = document(PolicyKind::TermsOfService, "1", 30);
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![tos_v2.clone(), privacy_v1.clone(), tos_v1]])
+            .into_connection();
+
+        let in_effect = find_in_effect(&db, Utc::now()).await?;
+
+        assert_eq!(in_effect, [tos_v2, privacy_v1]);
+        Ok(())
+    }
+}
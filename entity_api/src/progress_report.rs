@@ -0,0 +1,93 @@
+use super::error::Error;
+use chrono::NaiveDate;
+use entity::progress_reports::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{
+    entity::prelude::*, ActiveValue::Set, ConnectionTrait, IntoActiveModel, QueryOrder,
+    TryIntoModel,
+};
+
+use log::*;
+
+/// The relationship's progress reports, most recent week first.
+pub async fn find_by_coaching_relationship_id(
+    db: &impl ConnectionTrait,
+    coaching_relationship_id: Id,
+) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::CoachingRelationshipId.eq(coaching_relationship_id))
+        .order_by_desc(Column::PeriodStart)
+        .all(db)
+        .await?)
+}
+
+/// The relationship's report for the week starting on `period_start`, if there is one.
+pub async fn find_by_period(
+    db: &impl ConnectionTrait,
+    coaching_relationship_id: Id,
+    period_start: NaiveDate,
+) -> Result<Option<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::CoachingRelationshipId.eq(coaching_relationship_id))
+        .filter(Column::PeriodStart.eq(period_start))
+        .one(db)
+        .await?)
+}
+
+/// Stores a progress report; `id`, `emailed_at` and the timestamps are assigned here.
+pub async fn create(db: &impl ConnectionTrait, report: Model) -> Result<Model, Error> {
+    debug!(
+        "Storing progress report for coaching relationship {} (week of {})",
+        report.coaching_relationship_id, report.period_start
+    );
+
+    let now = chrono::Utc::now();
+    let active_model = ActiveModel {
+        coaching_relationship_id: Set(report.coaching_relationship_id),
+        period_start: Set(report.period_start),
+        period_end: Set(report.period_end),
+        narrative: Set(report.narrative),
+        session_count: Set(report.session_count),
+        open_action_count: Set(report.open_action_count),
+        model: Set(report.model),
+        prompt_version: Set(report.prompt_version),
+        generated_at: Set(report.generated_at),
+        emailed_at: Set(None),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+        ..Default::default()
+    };
+
+    Ok(active_model.insert(db).await?.try_into_model()?)
+}
+
+/// Records that the report has been emailed to the coach.
+pub async fn mark_emailed(db: &impl ConnectionTrait, report: Model) -> Result<Model, Error> {
+    let now = chrono::Utc::now();
+    let mut active_model = report.into_active_model();
+    active_model.emailed_at = Set(Some(now.into()));
+    active_model.updated_at = Set(now.into());
+
+    Ok(active_model.update(db).await?.try_into_model()?)
+}
+
+#[cfg(test)]
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    #[tokio::test]
+    async fn find_by_coaching_relationship_id_lists_the_latest_week_first() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<Model>::new()])
+            .into_connection();
+
+        find_by_coaching_relationship_id(&db, Id::new_v4()).await?;
+
+        let sql = db.into_transaction_log()[0].statements()[0].sql.clone();
+        assert!(sql.contains(r#""progress_reports"."coaching_relationship_id" = $1"#));
+        assert!(sql.contains(r#"ORDER BY "progress_reports"."period_start" DESC"#));
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use super::error::Error;
+use entity::progress_report_settings::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{entity::prelude::*, sea_query::OnConflict, ActiveValue::Set, ConnectionTrait};
+
+use log::*;
+
+pub async fn find_by_user_id(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+) -> Result<Option<Model>, Error> {
+    Ok(Entity::find_by_id(user_id).one(db).await?)
+}
+
+/// Settings of every user who opted in to the weekly progress email.
+pub async fn find_weekly_email_enabled(db: &impl ConnectionTrait) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::WeeklyEmailEnabled.eq(true))
+        .all(db)
+        .await?)
+}
+
+/// Stores a user's progress report settings, replacing any earlier ones.
+pub async fn upsert(db: &impl ConnectionTrait, user_id: Id, model: Model) -> Result<Model, Error> {
+    debug!("Storing progress report settings for user {user_id}");
+
+    let now = chrono::Utc::now();
+    let active_model = ActiveModel {
+        user_id: Set(user_id),
+        weekly_email_enabled: Set(model.weekly_email_enabled),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+    };
+
+    let on_conflict = OnConflict::column(Column::UserId)
+        .update_columns([Column::WeeklyEmailEnabled, Column::UpdatedAt])
+        .to_owned();
+
+    Ok(Entity::insert(active_model)
+        .on_conflict(on_conflict)
+        .exec_with_returning(db)
+        .await?)
+}
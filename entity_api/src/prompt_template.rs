@@ -0,0 +1,165 @@
+use super::error::{EntityApiErrorKind, Error};
+use entity::prompt_key::PromptKey;
+use entity::prompt_templates::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{
+    entity::prelude::*, sea_query::Expr, ActiveValue::Set, ConnectionTrait, QueryOrder,
+    TransactionTrait, TryIntoModel,
+};
+
+use log::*;
+
+/// The active version of `key`'s prompt, if an edited one is in use.
+pub async fn find_active(
+    db: &impl ConnectionTrait,
+    key: PromptKey,
+) -> Result<Option<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::Key.eq(key))
+        .filter(Column::IsActive.eq(true))
+        .one(db)
+        .await?)
+}
+
+/// Every stored version of `key`'s prompt, newest first.
+pub async fn find_by_key(db: &impl ConnectionTrait, key: PromptKey) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::Key.eq(key))
+        .order_by_desc(Column::Version)
+        .all(db)
+        .await?)
+}
+
+/// Stores `body` as the next version of `key`'s prompt and makes it the active one.
+pub async fn create_version(
+    db: &impl TransactionTrait,
+    key: PromptKey,
+    created_by_user_id: Id,
+    body: String,
+) -> Result<Model, Error> {
+    let txn = db.begin().await?;
+
+    let latest = Entity::find()
+        .filter(Column::Key.eq(key))
+        .order_by_desc(Column::Version)
+        .one(&txn)
+        .await?;
+    let version = latest.map_or(1, |latest| latest.version + 1);
+    debug!("New {key} prompt version {version}");
+
+    deactivate_all(&txn, key).await?;
+    let active_model = ActiveModel {
+        key: Set(key),
+        version: Set(version),
+        body: Set(body),
+        is_active: Set(true),
+        created_by_user_id: Set(created_by_user_id),
+        created_at: Set(chrono::Utc::now().into()),
+        ..Default::default()
+    };
+    let model = active_model.insert(&txn).await?.try_into_model()?;
+
+    txn.commit().await?;
+    Ok(model)
+}
+
+/// Makes `version` of `key`'s prompt the active one. Version 0 deactivates every stored
+/// version, returning the feature to its built-in prompt; `None` is returned then.
+pub async fn activate(
+    db: &impl TransactionTrait,
+    key: PromptKey,
+    version: i32,
+) -> Result<Option<Model>, Error> {
+    debug!("Activating {key} prompt version {version}");
+
+    let txn = db.begin().await?;
+    deactivate_all(&txn, key).await?;
+
+    let activated = if version == 0 {
+        None
+    } else {
+        let existing = Entity::find()
+            .filter(Column::Key.eq(key))
+            .filter(Column::Version.eq(version))
+            .one(&txn)
+            .await?
+            .ok_or_else(|| {
+                error!("{key} prompt version {version} not found");
+
+                Error {
+                    source: None,
+                    error_kind: EntityApiErrorKind::RecordNotFound,
+                }
+            })?;
+        let mut active_model: ActiveModel = existing.into();
+        active_model.is_active = Set(true);
+        Some(active_model.update(&txn).await?)
+    };
+
+    txn.commit().await?;
+    Ok(activated)
+}
+
+async fn deactivate_all(db: &impl ConnectionTrait, key: PromptKey) -> Result<(), Error> {
+    Entity::update_many()
+        .col_expr(Column::IsActive, Expr::value(false))
+        .filter(Column::Key.eq(key))
+        .filter(Column::IsActive.eq(true))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+// We need to gate seaORM's mock feature behind conditional compilation because
+// the feature removes the Clone trait implementation from seaORM's DatabaseConnection.
+// see https://github.com/SeaQL/sea-orm/issues/830
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+
+    fn template(version: i32, is_active: bool) -> Model {
+        Model {
+            id: Id::new_v4(),
+            key: PromptKey::ThemesReport,
+            version,
+            body: "Find themes.".to_string(),
+            is_active,
+            created_by_user_id: Id::new_v4(),
+            created_at: chrono::Utc::now().into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn create_version_numbers_after_the_latest_version() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![template(3, true)]])
+            .append_exec_results([MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            }])
+            .append_query_results([vec![template(4, true)]])
+            .into_connection();
+
+        let created = create_version(
+            &db,
+            PromptKey::ThemesReport,
+            Id::new_v4(),
+            "Find themes.".to_string(),
+        )
+        .await?;
+
+        assert_eq!(created.version, 4);
+        let log = db.into_transaction_log();
+        let insert = &log[0].statements()[3];
+        assert!(insert
+            .sql
+            .starts_with(r#"INSERT INTO "refactor_platform"."prompt_templates""#));
+        assert_eq!(
+            insert.values.as_ref().unwrap().0[1],
+            sea_orm::Value::Int(Some(4))
+        );
+        Ok(())
+    }
+}
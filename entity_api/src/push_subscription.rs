@@ -0,0 +1,72 @@
+use super::error::Error;
+use chrono::Utc;
+use entity::push_subscriptions::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{
+    entity::prelude::*, sea_query::OnConflict, ActiveValue::Set, ConnectionTrait, QueryOrder,
+};
+
+/// Stores the user's subscription at `endpoint`. A browser subscribing again (e.g. after
+/// its keys rotated, or with another user signed in) replaces the endpoint's keys and
+/// owner.
+pub async fn upsert(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+    endpoint: String,
+    p256dh: String,
+    auth: String,
+) -> Result<Model, Error> {
+    let now = Utc::now();
+    let active_model = ActiveModel {
+        user_id: Set(user_id),
+        endpoint: Set(endpoint),
+        p256dh: Set(p256dh),
+        auth: Set(auth),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+        ..Default::default()
+    };
+
+    let on_conflict = OnConflict::column(Column::Endpoint)
+        .update_columns([
+            Column::UserId,
+            Column::P256dh,
+            Column::Auth,
+            Column::UpdatedAt,
+        ])
+        .to_owned();
+
+    Ok(Entity::insert(active_model)
+        .on_conflict(on_conflict)
+        .exec_with_returning(db)
+        .await?)
+}
+
+/// The user's subscriptions, oldest first.
+pub async fn find_by_user(db: &impl ConnectionTrait, user_id: Id) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::UserId.eq(user_id))
+        .order_by_asc(Column::CreatedAt)
+        .all(db)
+        .await?)
+}
+
+/// Deletes the user's subscription with `id`, returning how many were deleted (0 or 1).
+pub async fn delete_by_user_and_id(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+    id: Id,
+) -> Result<u64, Error> {
+    Ok(Entity::delete_many()
+        .filter(Column::UserId.eq(user_id))
+        .filter(Column::Id.eq(id))
+        .exec(db)
+        .await?
+        .rows_affected)
+}
+
+/// Deletes the subscription with `id`, e.g. once its push service reports it gone.
+pub async fn delete_by_id(db: &impl ConnectionTrait, id: Id) -> Result<(), Error> {
+    Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
@@ -1,10 +1,15 @@
 use crate::error::Error;
+use sea_orm::sea_query::{extension::postgres::PgExpr, Expr, LikeExpr};
 use sea_orm::strum::IntoEnumIterator;
 use sea_orm::{
-    ColumnTrait, DatabaseConnection, EntityTrait, Order, QueryFilter, QueryOrder, Value,
+    ColumnTrait, Condition, DatabaseConnection, EntityTrait, Order, QueryFilter, QueryOrder,
+    Select, Value,
 };
 use std::collections::HashMap;
 
+/// The column marking a row soft-deleted, when set. [`select_by`] leaves such rows out.
+pub const SOFT_DELETE_COLUMN: &str = "deleted_at";
+
 /// `QueryFilterMap` is a data structure that serves as a bridge for translating filter parameters
 /// between different layers of the application. It is essentially a wrapper around a `HashMap`
 /// where the keys are filter parameter names (as `String`) and the values are optional `Value` types
@@ -232,6 +237,17 @@ where
 /// # }
 /// ```
 pub async fn find_by<E, C, P>(db: &DatabaseConnection, params: P) -> Result<Vec<E::Model>, Error>
+where
+    E: EntityTrait,
+    C: ColumnTrait + IntoEnumIterator,
+    P: IntoQueryFilterMap + QuerySort<C>,
+{
+    Ok(select_by::<E, C, P>(params).all(db).await?)
+}
+
+/// The query [`find_by`] runs, for queries that need more than its rows, e.g. decryption
+/// of their bodies or further conditions.
+pub fn select_by<E, C, P>(params: P) -> Select<E>
 where
     E: EntityTrait,
     C: ColumnTrait + IntoEnumIterator,
@@ -244,9 +260,12 @@ where
 
     let mut query = E::find();
 
-    // Apply filters by iterating through the entity's defined columns
+    // Apply filters by iterating through the entity's defined columns, leaving out
+    // soft-deleted rows of entities that have them
     for column in C::iter() {
-        if let Some(value) = query_filter_map.get(&column.to_string()) {
+        if column.to_string() == SOFT_DELETE_COLUMN {
+            query = query.filter(column.is_null());
+        } else if let Some(value) = query_filter_map.get(&column.to_string()) {
             query = query.filter(column.eq(value));
         }
     }
@@ -256,5 +275,20 @@ where
         query = query.order_by(column, order);
     }
 
-    Ok(query.all(db).await?)
+    query
+}
+
+/// Matches rows whose `column` contains any of `terms`, case-insensitively (Postgres
+/// `ILIKE`). Terms are matched literally: `%`, `_` and `\` are escaped.
+pub fn contains_any_term<C: ColumnTrait>(column: C, terms: &[String]) -> Condition {
+    terms.iter().fold(Condition::any(), |condition, term| {
+        let escaped = term
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        condition.add(
+            Expr::col((column.entity_name(), column))
+                .ilike(LikeExpr::new(format!("%{escaped}%")).escape('\\')),
+        )
+    })
 }
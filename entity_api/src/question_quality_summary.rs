@@ -0,0 +1,107 @@
+use super::error::Error;
+use entity::question_quality_summaries::{ActiveModel, Column, Entity, Model};
+use entity::{coaching_relationships, coaching_sessions, Id};
+use sea_orm::{
+    entity::prelude::*, sea_query::OnConflict, ActiveValue::Set, ConnectionTrait, JoinType,
+    QueryOrder, QuerySelect,
+};
+
+use log::*;
+
+pub async fn find_by_coaching_session_id(
+    db: &impl ConnectionTrait,
+    coaching_session_id: Id,
+) -> Result<Option<Model>, Error> {
+    Ok(Entity::find_by_id(coaching_session_id).one(db).await?)
+}
+
+/// Summaries of every session the user coached, with their sessions, oldest session first.
+pub async fn find_by_coach(
+    db: &impl ConnectionTrait,
+    coach_id: Id,
+) -> Result<Vec<(Model, coaching_sessions::Model)>, Error> {
+    let summaries = Entity::find()
+        .find_also_related(coaching_sessions::Entity)
+        .join(
+            JoinType::InnerJoin,
+            coaching_sessions::Relation::CoachingRelationships.def(),
+        )
+        .filter(coaching_relationships::Column::CoachId.eq(coach_id))
+        .filter(coaching_sessions::Column::DeletedAt.is_null())
+        .order_by_asc(coaching_sessions::Column::Date)
+        .all(db)
+        .await?;
+
+    Ok(summaries
+        .into_iter()
+        .filter_map(|(summary, session)| Some((summary, session?)))
+        .collect())
+}
+
+/// Stores the classification summary for a session, replacing any earlier one.
+pub async fn upsert(db: &impl ConnectionTrait, model: Model) -> Result<Model, Error> {
+    debug!(
+        "Storing question quality summary for coaching session {}",
+        model.coaching_session_id
+    );
+
+    let now = chrono::Utc::now();
+    let active_model = ActiveModel {
+        coaching_session_id: Set(model.coaching_session_id),
+        transcription_id: Set(model.transcription_id),
+        coach_speaker_label: Set(model.coach_speaker_label),
+        utterance_count: Set(model.utterance_count),
+        open_question_count: Set(model.open_question_count),
+        closed_question_count: Set(model.closed_question_count),
+        advice_count: Set(model.advice_count),
+        inquiry_count: Set(model.inquiry_count),
+        model: Set(model.model),
+        prompt_version: Set(model.prompt_version),
+        generated_at: Set(model.generated_at),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+    };
+
+    let on_conflict = OnConflict::column(Column::CoachingSessionId)
+        .update_columns([
+            Column::TranscriptionId,
+            Column::CoachSpeakerLabel,
+            Column::UtteranceCount,
+            Column::OpenQuestionCount,
+            Column::ClosedQuestionCount,
+            Column::AdviceCount,
+            Column::InquiryCount,
+            Column::Model,
+            Column::PromptVersion,
+            Column::GeneratedAt,
+            Column::UpdatedAt,
+        ])
+        .to_owned();
+
+    Ok(Entity::insert(active_model)
+        .on_conflict(on_conflict)
+        .exec_with_returning(db)
+        .await?)
+}
+
+#[cfg(test)]
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    #[tokio::test]
+    async fn find_by_coach_joins_the_coachs_relationships() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<Model>::new()])
+            .into_connection();
+        let coach_id = Id::new_v4();
+
+        find_by_coach(&db, coach_id).await?;
+
+        let sql = db.into_transaction_log()[0].statements()[0].sql.clone();
+        assert!(sql.contains(r#"INNER JOIN "refactor_platform"."coaching_relationships""#));
+        assert!(sql.contains(r#""coaching_relationships"."coach_id" = $1"#));
+        Ok(())
+    }
+}
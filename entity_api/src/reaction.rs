@@ -0,0 +1,185 @@
+use super::error::{EntityApiErrorKind, Error};
+use entity::reactions::{ActiveModel, Column, Entity, Model};
+use entity::resource_type::ResourceType;
+use entity::Id;
+use sea_orm::{
+    sea_query::OnConflict, ActiveValue::Set, ColumnTrait, ConnectionTrait, EntityTrait, ModelTrait,
+    QueryFilter, QueryOrder,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use utoipa::ToSchema;
+
+use log::*;
+
+/// Aggregated reactions for one emoji on one target.
+///
+/// Carries the reacting user ids rather than a viewer-relative flag so the same
+/// payload can be serialized for every participant (including over SSE).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ReactionCount {
+    pub emoji: String,
+    pub count: i64,
+    pub user_ids: Vec<Id>,
+}
+
+/// Adds `user_id`'s `emoji` reaction to a target. Idempotent: reacting twice with the
+/// same emoji returns the existing row (the unique constraint is the conflict target).
+pub async fn create(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+    resource_type: ResourceType,
+    resource_id: Id,
+    emoji: String,
+) -> Result<Model, Error> {
+    let now = chrono::Utc::now();
+
+    let active_model = ActiveModel {
+        user_id: Set(user_id),
+        resource_type: Set(resource_type),
+        resource_id: Set(resource_id),
+        emoji: Set(emoji),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+        ..Default::default()
+    };
+
+    let on_conflict = OnConflict::columns([
+        Column::UserId,
+        Column::ResourceType,
+        Column::ResourceId,
+        Column::Emoji,
+    ])
+    .update_column(Column::UpdatedAt)
+    .to_owned();
+
+    Ok(Entity::insert(active_model)
+        .on_conflict(on_conflict)
+        .exec_with_returning(db)
+        .await?)
+}
+
+pub async fn find_by_id(db: &impl ConnectionTrait, id: Id) -> Result<Model, Error> {
+    Entity::find_by_id(id).one(db).await?.ok_or_else(|| {
+        error!("Reaction with id {id} not found");
+
+        Error {
+            source: None,
+            error_kind: EntityApiErrorKind::RecordNotFound,
+        }
+    })
+}
+
+pub async fn delete(db: &impl ConnectionTrait, reaction: Model) -> Result<(), Error> {
+    reaction.delete(db).await?;
+    Ok(())
+}
+
+/// Batch load aggregated reactions for targets of one type, keyed by target id.
+/// Targets without reactions are absent from the map.
+pub async fn find_counts(
+    db: &impl ConnectionTrait,
+    resource_type: ResourceType,
+    resource_ids: &[Id],
+) -> Result<HashMap<Id, Vec<ReactionCount>>, Error> {
+    if resource_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let reactions = Entity::find()
+        .filter(Column::ResourceType.eq(resource_type))
+        .filter(Column::ResourceId.is_in(resource_ids.iter().copied()))
+        .order_by_asc(Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    Ok(summarize(reactions))
+}
+
+/// Aggregated reactions for a single target.
+pub async fn find_counts_for(
+    db: &impl ConnectionTrait,
+    resource_type: ResourceType,
+    resource_id: Id,
+) -> Result<Vec<ReactionCount>, Error> {
+    Ok(find_counts(db, resource_type, &[resource_id])
+        .await?
+        .remove(&resource_id)
+        .unwrap_or_default())
+}
+
+/// Groups reactions per target and emoji. Emojis keep the order of their first
+/// reaction, so counts don't reshuffle in the UI as people add to them.
+fn summarize(reactions: Vec<Model>) -> HashMap<Id, Vec<ReactionCount>> {
+    let mut counts: HashMap<Id, Vec<ReactionCount>> = HashMap::new();
+
+    for reaction in reactions {
+        let target = counts.entry(reaction.resource_id).or_default();
+        match target.iter_mut().find(|c| c.emoji == reaction.emoji) {
+            Some(count) => {
+                count.count += 1;
+                count.user_ids.push(reaction.user_id);
+            }
+            None => target.push(ReactionCount {
+                emoji: reaction.emoji,
+                count: 1,
+                user_ids: vec![reaction.user_id],
+            }),
+        }
+    }
+
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reaction(resource_id: Id, user_id: Id, emoji: &str) -> Model {
+        let now = chrono::Utc::now();
+        Model {
+            id: Id::new_v4(),
+            user_id,
+            resource_type: ResourceType::Note,
+            resource_id,
+            emoji: emoji.to_string(),
+            created_at: now.into(),
+            updated_at: now.into(),
+        }
+    }
+
+    #[test]
+    fn summarize_groups_by_target_and_emoji_in_first_use_order() {
+        let (note_a, note_b) = (Id::new_v4(), Id::new_v4());
+        let (coach, coachee) = (Id::new_v4(), Id::new_v4());
+
+        let counts = summarize(vec![
+            reaction(note_a, coach, "🎉"),
+            reaction(note_a, coachee, "👍"),
+            reaction(note_b, coach, "👍"),
+            reaction(note_a, coachee, "🎉"),
+        ]);
+
+        assert_eq!(
+            counts[&note_a],
+            vec![
+                ReactionCount {
+                    emoji: "🎉".to_string(),
+                    count: 2,
+                    user_ids: vec![coach, coachee],
+                },
+                ReactionCount {
+                    emoji: "👍".to_string(),
+                    count: 1,
+                    user_ids: vec![coachee],
+                },
+            ]
+        );
+        assert_eq!(counts[&note_b].len(), 1);
+    }
+
+    #[test]
+    fn summarize_omits_targets_without_reactions() {
+        assert!(summarize(Vec::new()).is_empty());
+    }
+}
@@ -0,0 +1,258 @@
+//! Relationship health scores: each organization's weighting of the signals, the scores
+//! recorded per scoring run, and the raw signal data a run combines.
+
+use std::collections::HashMap;
+
+use super::error::Error;
+use entity::relationship_health_scores;
+use entity::relationship_health_settings::{ActiveModel, Column, Entity, Model};
+use entity::{actions, coaching_sessions, status::Status, transcript_segment, transcription, Id};
+use sea_orm::{
+    entity::prelude::*, sea_query::OnConflict, ActiveValue::Set, ConnectionTrait, FromQueryResult,
+    JoinType, QueryOrder, QuerySelect,
+};
+
+use log::*;
+
+pub async fn find_settings_by_organization_id(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+) -> Result<Option<Model>, Error> {
+    Ok(Entity::find_by_id(organization_id).one(db).await?)
+}
+
+/// Stores an organization's health score settings, replacing any earlier ones.
+pub async fn upsert_settings(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+    model: Model,
+) -> Result<Model, Error> {
+    debug!("Storing relationship health settings for organization {organization_id}");
+
+    let now = chrono::Utc::now();
+    let active_model = ActiveModel {
+        organization_id: Set(organization_id),
+        pulse_weight: Set(model.pulse_weight),
+        cadence_weight: Set(model.cadence_weight),
+        action_completion_weight: Set(model.action_completion_weight),
+        sentiment_weight: Set(model.sentiment_weight),
+        alert_threshold: Set(model.alert_threshold),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+    };
+
+    let on_conflict = OnConflict::column(Column::OrganizationId)
+        .update_columns([
+            Column::PulseWeight,
+            Column::CadenceWeight,
+            Column::ActionCompletionWeight,
+            Column::SentimentWeight,
+            Column::AlertThreshold,
+            Column::UpdatedAt,
+        ])
+        .to_owned();
+
+    Ok(Entity::insert(active_model)
+        .on_conflict(on_conflict)
+        .exec_with_returning(db)
+        .await?)
+}
+
+/// Records one scoring run's scores.
+pub async fn insert_scores(
+    db: &impl ConnectionTrait,
+    scores: Vec<relationship_health_scores::Model>,
+) -> Result<(), Error> {
+    if scores.is_empty() {
+        return Ok(());
+    }
+
+    let active_models = scores
+        .into_iter()
+        .map(relationship_health_scores::ActiveModel::from);
+    relationship_health_scores::Entity::insert_many(active_models)
+        .exec_without_returning(db)
+        .await?;
+    Ok(())
+}
+
+/// The most recent score of each of `relationship_ids` that has been scored.
+pub async fn find_latest_scores(
+    db: &impl ConnectionTrait,
+    relationship_ids: &[Id],
+) -> Result<HashMap<Id, relationship_health_scores::Model>, Error> {
+    if relationship_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    Ok(relationship_health_scores::Entity::find()
+        .distinct_on([relationship_health_scores::Column::CoachingRelationshipId])
+        .filter(
+            relationship_health_scores::Column::CoachingRelationshipId
+                .is_in(relationship_ids.iter().copied()),
+        )
+        .order_by_asc(relationship_health_scores::Column::CoachingRelationshipId)
+        .order_by_desc(relationship_health_scores::Column::ComputedAt)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|score| (score.coaching_relationship_id, score))
+        .collect())
+}
+
+/// The relationship's scores recorded since `since`, oldest first.
+pub async fn find_scores_by_relationship(
+    db: &impl ConnectionTrait,
+    coaching_relationship_id: Id,
+    since: DateTimeWithTimeZone,
+) -> Result<Vec<relationship_health_scores::Model>, Error> {
+    Ok(relationship_health_scores::Entity::find()
+        .filter(
+            relationship_health_scores::Column::CoachingRelationshipId.eq(coaching_relationship_id),
+        )
+        .filter(relationship_health_scores::Column::ComputedAt.gte(since))
+        .order_by_asc(relationship_health_scores::Column::ComputedAt)
+        .all(db)
+        .await?)
+}
+
+/// A live session held in one of the scored relationships.
+#[derive(Debug, Clone, PartialEq, Eq, FromQueryResult)]
+pub struct HeldSession {
+    pub coaching_relationship_id: Id,
+    pub date: DateTime,
+}
+
+/// Live sessions of `relationship_ids` dated within `[since, until]`, earliest first.
+pub async fn find_held_sessions(
+    db: &impl ConnectionTrait,
+    relationship_ids: &[Id],
+    since: DateTime,
+    until: DateTime,
+) -> Result<Vec<HeldSession>, Error> {
+    if relationship_ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    Ok(coaching_sessions::Entity::find()
+        .select_only()
+        .column(coaching_sessions::Column::CoachingRelationshipId)
+        .column(coaching_sessions::Column::Date)
+        .filter(
+            coaching_sessions::Column::CoachingRelationshipId
+                .is_in(relationship_ids.iter().copied()),
+        )
+        .filter(coaching_sessions::Column::Date.between(since, until))
+        .filter(coaching_sessions::Column::DeletedAt.is_null())
+        .order_by_asc(coaching_sessions::Column::Date)
+        .into_model::<HeldSession>()
+        .all(db)
+        .await?)
+}
+
+/// A live action that fell due in one of the scored relationships.
+#[derive(Debug, Clone, PartialEq, Eq, FromQueryResult)]
+pub struct DueAction {
+    pub coaching_relationship_id: Id,
+    pub status: Status,
+}
+
+/// Live actions on live sessions of `relationship_ids` due within `[since, until]`.
+pub async fn find_due_actions(
+    db: &impl ConnectionTrait,
+    relationship_ids: &[Id],
+    since: DateTimeWithTimeZone,
+    until: DateTimeWithTimeZone,
+) -> Result<Vec<DueAction>, Error> {
+    if relationship_ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    Ok(actions::Entity::find()
+        .select_only()
+        .column(coaching_sessions::Column::CoachingRelationshipId)
+        .column(actions::Column::Status)
+        .join(
+            JoinType::InnerJoin,
+            actions::Relation::CoachingSessions.def(),
+        )
+        .filter(
+            coaching_sessions::Column::CoachingRelationshipId
+                .is_in(relationship_ids.iter().copied()),
+        )
+        .filter(actions::Column::DueBy.between(since, until))
+        .filter(actions::Column::DeletedAt.is_null())
+        .filter(coaching_sessions::Column::DeletedAt.is_null())
+        .into_model::<DueAction>()
+        .all(db)
+        .await?)
+}
+
+/// The sentiment label of one transcript segment in one of the scored relationships.
+#[derive(Debug, Clone, PartialEq, Eq, FromQueryResult)]
+pub struct SegmentSentiment {
+    pub coaching_relationship_id: Id,
+    pub sentiment: String,
+}
+
+/// Sentiment labels of the transcript segments of live sessions of `relationship_ids`
+/// dated since `since`. Segments without a label are left out.
+pub async fn find_segment_sentiments(
+    db: &impl ConnectionTrait,
+    relationship_ids: &[Id],
+    since: DateTime,
+) -> Result<Vec<SegmentSentiment>, Error> {
+    if relationship_ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    Ok(transcript_segment::Entity::find()
+        .select_only()
+        .column(coaching_sessions::Column::CoachingRelationshipId)
+        .column(transcript_segment::Column::Sentiment)
+        .join(
+            JoinType::InnerJoin,
+            transcript_segment::Relation::Transcriptions.def(),
+        )
+        .join(
+            JoinType::InnerJoin,
+            transcription::Relation::CoachingSessions.def(),
+        )
+        .filter(
+            coaching_sessions::Column::CoachingRelationshipId
+                .is_in(relationship_ids.iter().copied()),
+        )
+        .filter(coaching_sessions::Column::Date.gte(since))
+        .filter(coaching_sessions::Column::DeletedAt.is_null())
+        .filter(transcript_segment::Column::Sentiment.is_not_null())
+        .into_model::<SegmentSentiment>()
+        .all(db)
+        .await?)
+}
+
+#[cfg(test)]
+// We need to gate seaORM's mock feature behind conditional compilation because
+// the feature removes the Clone trait implementation from seaORM's DatabaseConnection.
+// see https://github.com/SeaQL/sea-orm/issues/830
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    #[tokio::test]
+    async fn find_latest_scores_takes_the_newest_row_per_relationship() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<relationship_health_scores::Model>::new()])
+            .into_connection();
+
+        find_latest_scores(&db, &[Id::new_v4()]).await?;
+
+        let log = db.into_transaction_log();
+        let sql = &log[0].statements()[0].sql;
+        assert!(sql.starts_with(r#"SELECT DISTINCT ON ("coaching_relationship_id")"#));
+        assert!(sql.ends_with(
+            r#"ORDER BY "relationship_health_scores"."coaching_relationship_id" ASC, "relationship_health_scores"."computed_at" DESC"#
+        ));
+        Ok(())
+    }
+}
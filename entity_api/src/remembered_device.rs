@@ -0,0 +1,80 @@
+use super::error::Error;
+use chrono::Utc;
+use entity::remembered_devices::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{entity::prelude::*, ActiveValue::Set, ConnectionTrait, QueryOrder};
+
+/// Stores a device the user logged in on with "remember me".
+pub async fn create(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+    token_hash: String,
+    user_agent: Option<String>,
+    expires_at: DateTimeWithTimeZone,
+) -> Result<Model, Error> {
+    let now = Utc::now();
+    let active_model = ActiveModel {
+        user_id: Set(user_id),
+        token_hash: Set(token_hash),
+        user_agent: Set(user_agent),
+        expires_at: Set(expires_at),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+        ..Default::default()
+    };
+
+    Ok(active_model.insert(db).await?)
+}
+
+pub async fn find_by_id(db: &impl ConnectionTrait, id: Id) -> Result<Option<Model>, Error> {
+    Ok(Entity::find_by_id(id).one(db).await?)
+}
+
+/// The user's devices that haven't expired, oldest first.
+pub async fn find_by_user(db: &impl ConnectionTrait, user_id: Id) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::UserId.eq(user_id))
+        .filter(Column::ExpiresAt.gt(Utc::now()))
+        .order_by_asc(Column::CreatedAt)
+        .all(db)
+        .await?)
+}
+
+/// Deletes the user's device with `id`, returning how many were deleted (0 or 1).
+pub async fn delete_by_user_and_id(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+    id: Id,
+) -> Result<u64, Error> {
+    Ok(Entity::delete_many()
+        .filter(Column::UserId.eq(user_id))
+        .filter(Column::Id.eq(id))
+        .exec(db)
+        .await?
+        .rows_affected)
+}
+
+/// Deletes the device with `id`, e.g. when its session logs out.
+pub async fn delete_by_id(db: &impl ConnectionTrait, id: Id) -> Result<(), Error> {
+    Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
+
+/// Deletes all of the user's devices, e.g. once their password changes.
+pub async fn delete_all_for_user(db: &impl ConnectionTrait, user_id: Id) -> Result<u64, Error> {
+    Ok(Entity::delete_many()
+        .filter(Column::UserId.eq(user_id))
+        .exec(db)
+        .await?
+        .rows_affected)
+}
+
+/// Deletes the user's expired devices.
+pub async fn delete_expired_for_user(db: &impl ConnectionTrait, user_id: Id) -> Result<u64, Error> {
+    Ok(Entity::delete_many()
+        .filter(Column::UserId.eq(user_id))
+        .filter(Column::ExpiresAt.lte(Utc::now()))
+        .exec(db)
+        .await?
+        .rows_affected)
+}
@@ -0,0 +1,131 @@
+use super::coaching_session_view::MarkViewed;
+use super::error::{EntityApiErrorKind, Error};
+use super::reaction::ReactionCount;
+use entity::resource_type::ResourceType;
+use entity::{resource_views, Id};
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::{
+    ActiveEnum, ColumnTrait, ConnectionTrait, DatabaseBackend, EntityTrait, FromQueryResult,
+    QueryFilter, Statement,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use utoipa::ToSchema;
+
+/// A resource serialized alongside the viewer's unread flag, its reactions and, for
+/// actions, the total focused work logged on it.
+///
+/// The resource's fields are flattened into the JSON root, so this only adds
+/// `has_unread`, `reactions` and `total_work_seconds` to the existing shape.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[aliases(
+    AnnotatedAction = Annotated<crate::action::ActionWithAssignees>,
+    AnnotatedNote = Annotated<entity::notes::Model>
+)]
+pub struct Annotated<T> {
+    #[serde(flatten)]
+    pub resource: T,
+    pub has_unread: bool,
+    pub reactions: Vec<ReactionCount>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_work_seconds: Option<i64>,
+}
+
+/// Whether a resource changed since the viewer last marked it seen.
+///
+/// Never-viewed resources are unread unless the viewer authored them, so a
+/// user's own notes and actions don't light up for them immediately after creation.
+pub fn has_unread(
+    updated_at: DateTimeWithTimeZone,
+    last_viewed_at: Option<DateTimeWithTimeZone>,
+    authored_by_viewer: bool,
+) -> bool {
+    match last_viewed_at {
+        Some(viewed) => updated_at > viewed,
+        None => !authored_by_viewer,
+    }
+}
+
+/// Upsert the caller's view marker for a resource to now() and return the prior value
+/// atomically. Mirrors `coaching_session_view::mark_viewed` for non-session resources.
+pub async fn mark_viewed(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+    resource_type: ResourceType,
+    resource_id: Id,
+) -> Result<MarkViewed, Error> {
+    let stmt = Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"WITH prev AS (
+               SELECT last_viewed_at
+               FROM refactor_platform.resource_views
+               WHERE user_id = $1 AND resource_type = $2::refactor_platform.resource_type AND resource_id = $3
+           )
+           INSERT INTO refactor_platform.resource_views
+               (user_id, resource_type, resource_id, last_viewed_at, created_at, updated_at)
+           VALUES ($1, $2::refactor_platform.resource_type, $3, NOW(), NOW(), NOW())
+           ON CONFLICT (user_id, resource_type, resource_id)
+           DO UPDATE SET last_viewed_at = NOW(), updated_at = NOW()
+           RETURNING last_viewed_at, (SELECT last_viewed_at FROM prev) AS previous_last_viewed_at"#,
+        [
+            user_id.into(),
+            resource_type.to_value().into(),
+            resource_id.into(),
+        ],
+    );
+    MarkViewed::find_by_statement(stmt)
+        .one(db)
+        .await?
+        .ok_or_else(|| Error {
+            source: None,
+            error_kind: EntityApiErrorKind::RecordNotFound,
+        })
+}
+
+/// Batch load the viewer's markers for resources of one type, keyed by resource id.
+pub async fn find_last_viewed(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+    resource_type: ResourceType,
+    resource_ids: &[Id],
+) -> Result<HashMap<Id, DateTimeWithTimeZone>, Error> {
+    if resource_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    Ok(resource_views::Entity::find()
+        .filter(resource_views::Column::UserId.eq(user_id))
+        .filter(resource_views::Column::ResourceType.eq(resource_type))
+        .filter(resource_views::Column::ResourceId.is_in(resource_ids.iter().copied()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|v| (v.resource_id, v.last_viewed_at))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn has_unread_compares_updated_at_against_marker() {
+        let viewed: DateTimeWithTimeZone = Utc::now().into();
+        let earlier = viewed - Duration::minutes(5);
+        let later = viewed + Duration::minutes(5);
+
+        assert!(has_unread(later, Some(viewed), false));
+        assert!(has_unread(later, Some(viewed), true));
+        assert!(!has_unread(earlier, Some(viewed), false));
+        assert!(!has_unread(viewed, Some(viewed), false));
+    }
+
+    #[test]
+    fn has_unread_without_marker_depends_on_authorship() {
+        let now: DateTimeWithTimeZone = Utc::now().into();
+
+        assert!(has_unread(now, None, false));
+        assert!(!has_unread(now, None, true));
+    }
+}
@@ -0,0 +1,138 @@
+use super::error::{EntityApiErrorKind, Error};
+use entity::scheduled_events::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{
+    entity::prelude::*, ActiveValue::Set, ConnectionTrait, DbBackend, Statement, TryIntoModel,
+};
+use std::time::Duration;
+
+use log::*;
+
+/// Stores an event to publish at `publish_at`; `id`, the claim fields and `created_at`
+/// are assigned here.
+pub async fn create(
+    db: &impl ConnectionTrait,
+    event_type: &str,
+    payload: Json,
+    actor_id: Option<Id>,
+    publish_at: DateTimeWithTimeZone,
+) -> Result<Model, Error> {
+    debug!("Scheduling {event_type} event for {publish_at}");
+
+    let active_model = ActiveModel {
+        event_type: Set(event_type.to_string()),
+        payload: Set(payload),
+        actor_id: Set(actor_id),
+        publish_at: Set(publish_at),
+        attempts: Set(0),
+        locked_until: Set(None),
+        published_at: Set(None),
+        created_at: Set(chrono::Utc::now().into()),
+        ..Default::default()
+    };
+
+    Ok(active_model.insert(db).await?.try_into_model()?)
+}
+
+/// Claims up to `limit` unpublished events that are due for `lease`, earliest first.
+/// Events another process has claimed, and whose claim hasn't lapsed, are skipped.
+pub async fn claim_due(
+    db: &impl ConnectionTrait,
+    limit: u64,
+    lease: Duration,
+) -> Result<Vec<Model>, Error> {
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        r#"UPDATE refactor_platform.scheduled_events
+           SET locked_until = NOW() + make_interval(secs => $1),
+               attempts = attempts + 1
+           WHERE id IN (
+               SELECT id FROM refactor_platform.scheduled_events
+               WHERE published_at IS NULL
+                 AND publish_at <= NOW()
+                 AND (locked_until IS NULL OR locked_until < NOW())
+               ORDER BY publish_at
+               LIMIT $2
+               FOR UPDATE SKIP LOCKED
+           )
+           RETURNING *"#,
+        [lease.as_secs_f64().into(), (limit as i64).into()],
+    );
+
+    // RETURNING doesn't preserve the subquery's order.
+    let mut claimed = Entity::find().from_raw_sql(stmt).all(db).await?;
+    claimed.sort_by_key(|event| event.publish_at);
+    Ok(claimed)
+}
+
+/// Marks the event as published and releases its claim.
+pub async fn mark_published(db: &impl ConnectionTrait, id: Id) -> Result<(), Error> {
+    Entity::update_many()
+        .col_expr(
+            Column::PublishedAt,
+            Expr::value(chrono::Utc::now().fixed_offset()),
+        )
+        .col_expr(
+            Column::LockedUntil,
+            Expr::value(Option::<DateTimeWithTimeZone>::None),
+        )
+        .filter(Column::Id.eq(id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+/// Deletes the event unless it has been published.
+pub async fn delete_unpublished(db: &impl ConnectionTrait, id: Id) -> Result<(), Error> {
+    let result = Entity::delete_many()
+        .filter(Column::Id.eq(id))
+        .filter(Column::PublishedAt.is_null())
+        .exec(db)
+        .await?;
+    if result.rows_affected == 0 {
+        return Err(Error {
+            source: None,
+            error_kind: EntityApiErrorKind::RecordNotFound,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+
+    #[tokio::test]
+    async fn claim_due_only_claims_unpublished_events_that_are_due() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<Model>::new()])
+            .into_connection();
+
+        claim_due(&db, 100, Duration::from_secs(60)).await?;
+
+        let sql = db.into_transaction_log()[0].statements()[0].sql.clone();
+        assert!(sql.contains("WHERE published_at IS NULL"));
+        assert!(sql.contains("AND publish_at <= NOW()"));
+        assert!(sql.contains("FOR UPDATE SKIP LOCKED"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_unpublished_reports_a_published_event_as_not_found() {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results([MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 0,
+            }])
+            .into_connection();
+
+        let result = delete_unpublished(&db, Id::new_v4()).await;
+
+        assert_eq!(
+            result.unwrap_err().error_kind,
+            EntityApiErrorKind::RecordNotFound
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use super::error::Error;
+use chrono::Utc;
+use entity::scim_tokens::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{entity::prelude::*, sea_query::OnConflict, ActiveValue::Set, ConnectionTrait};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// A newly created token with its value, which isn't returned again.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[schema(as = domain::scim::CreatedScimToken)]
+pub struct CreatedScimToken {
+    #[serde(flatten)]
+    pub scim_token: Model,
+    /// The bearer token to configure the identity provider with. Store it now: it can't
+    /// be read back.
+    pub token: String,
+}
+
+/// Sets the organization's token to the one hashing to `token_hash`, replacing any it had.
+pub async fn upsert(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+    created_by_user_id: Id,
+    token_hash: String,
+) -> Result<Model, Error> {
+    let active_model = ActiveModel {
+        organization_id: Set(organization_id),
+        token_hash: Set(token_hash),
+        created_by_user_id: Set(Some(created_by_user_id)),
+        created_at: Set(Utc::now().into()),
+        ..Default::default()
+    };
+
+    let on_conflict = OnConflict::column(Column::OrganizationId)
+        .update_columns([
+            Column::TokenHash,
+            Column::CreatedByUserId,
+            Column::CreatedAt,
+        ])
+        .to_owned();
+
+    Ok(Entity::insert(active_model)
+        .on_conflict(on_conflict)
+        .exec_with_returning(db)
+        .await?)
+}
+
+pub async fn find_by_organization(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+) -> Result<Option<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::OrganizationId.eq(organization_id))
+        .one(db)
+        .await?)
+}
+
+/// The token whose SHA-256 hash is `token_hash`.
+pub async fn find_by_token_hash(
+    db: &impl ConnectionTrait,
+    token_hash: &str,
+) -> Result<Option<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::TokenHash.eq(token_hash))
+        .one(db)
+        .await?)
+}
+
+/// Deletes the organization's token, returning how many were deleted (0 or 1).
+pub async fn delete_by_organization(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+) -> Result<u64, Error> {
+    Ok(Entity::delete_many()
+        .filter(Column::OrganizationId.eq(organization_id))
+        .exec(db)
+        .await?
+        .rows_affected)
+}
@@ -0,0 +1,80 @@
+//! The users organizations' identity providers provisioned through SCIM.
+
+use super::error::Error;
+use chrono::Utc;
+use entity::scim_users::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{
+    entity::prelude::*,
+    ActiveValue::{Set, Unchanged},
+    ConnectionTrait, IntoActiveModel, QueryOrder,
+};
+
+/// Records that the organization's identity provider provisioned `user_id`.
+pub async fn create(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+    user_id: Id,
+    external_id: Option<String>,
+) -> Result<Model, Error> {
+    let now = Utc::now();
+    let active_model = ActiveModel {
+        organization_id: Set(organization_id),
+        user_id: Set(user_id),
+        external_id: Set(external_id),
+        active: Set(true),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+        ..Default::default()
+    };
+
+    Ok(active_model.insert(db).await?)
+}
+
+pub async fn find_by_organization_and_user(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+    user_id: Id,
+) -> Result<Option<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::OrganizationId.eq(organization_id))
+        .filter(Column::UserId.eq(user_id))
+        .one(db)
+        .await?)
+}
+
+/// Every user the organization's identity provider provisioned, in the order it did.
+pub async fn find_by_organization(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::OrganizationId.eq(organization_id))
+        .order_by_asc(Column::CreatedAt)
+        .order_by_asc(Column::Id)
+        .all(db)
+        .await?)
+}
+
+/// Sets a provisioned user's external ID and whether they're active.
+pub async fn update(
+    db: &impl ConnectionTrait,
+    existing: Model,
+    external_id: Option<String>,
+    active: bool,
+) -> Result<Model, Error> {
+    let active_model = ActiveModel {
+        id: Unchanged(existing.id),
+        external_id: Set(external_id),
+        active: Set(active),
+        updated_at: Set(Utc::now().into()),
+        ..existing.into_active_model()
+    };
+
+    Ok(active_model.update(db).await?)
+}
+
+pub async fn delete_by_id(db: &impl ConnectionTrait, id: Id) -> Result<(), Error> {
+    Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
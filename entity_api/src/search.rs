@@ -0,0 +1,166 @@
+//! Full-text search over the notes, actions, agreements and goals of a user's coaching
+//! relationships.
+//!
+//! Each searched table has a generated `search_vector` column with a GIN index (see
+//! migration `m20261015_000041_add_search_vectors`). SeaORM has no tsvector column type,
+//! so the search is a raw statement. Encrypted notes aren't indexed and never match.
+
+use super::error::Error;
+use entity::Id;
+use sea_orm::{entity::prelude::*, ConnectionTrait, DatabaseBackend, FromQueryResult, Statement};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Options for `ts_headline`: up to two fragments of the matched text, with markup
+/// already stripped, and each matched word wrapped in `<mark>`.
+const HEADLINE_OPTIONS: &str = "StartSel=<mark>, StopSel=</mark>, MaxWords=30, MinWords=10, \
+     MaxFragments=2, FragmentDelimiter=\" … \"";
+
+/// Which kind of record a search result is.
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, Hash, EnumIter, DeriveActiveEnum, Serialize, ToSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+#[serde(rename_all = "snake_case")]
+#[schema(as = domain::search::SearchResultType)]
+pub enum SearchResultType {
+    #[sea_orm(string_value = "note")]
+    Note,
+    #[sea_orm(string_value = "action")]
+    Action,
+    #[sea_orm(string_value = "agreement")]
+    Agreement,
+    #[sea_orm(string_value = "goal")]
+    Goal,
+}
+
+/// One record matching a search.
+#[derive(Debug, Clone, PartialEq, Serialize, FromQueryResult, ToSchema)]
+#[schema(as = domain::search::SearchResult)]
+pub struct SearchResult {
+    pub result_type: SearchResultType,
+    /// The note's, action's, agreement's or goal's id
+    pub id: Id,
+    pub coaching_relationship_id: Id,
+    /// The session the record belongs to; for goals, the one it was created in, if any
+    pub coaching_session_id: Option<Id>,
+    /// The goal's title; `null` for other records
+    pub title: Option<String>,
+    /// Fragments of the matched text, markup removed, with matched words in `<mark>`
+    pub highlight: String,
+    /// How well the record matches; results come best first
+    pub rank: f32,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+/// The `limit` best matches of `query` (web search syntax: words, `"quoted phrases"`,
+/// `or`, `-excluded`) among the live records of the relationships `user_id` coaches or
+/// is coached in, most recently updated first among equal ranks.
+pub async fn search(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+    query: &str,
+    limit: u64,
+) -> Result<Vec<SearchResult>, Error> {
+    let stmt = Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"WITH q AS (SELECT websearch_to_tsquery('english', $2) AS query),
+                relationships AS (
+                    SELECT id FROM refactor_platform.coaching_relationships
+                    WHERE coach_id = $1 OR coachee_id = $1
+                ),
+                hits AS (
+                    SELECT 'note' AS result_type, n.id, s.coaching_relationship_id,
+                           n.coaching_session_id, NULL::text AS title, n.body AS document,
+                           ts_rank(n.search_vector, q.query) AS rank, n.updated_at
+                    FROM refactor_platform.notes n
+                    JOIN refactor_platform.coaching_sessions s ON s.id = n.coaching_session_id
+                    CROSS JOIN q
+                    WHERE n.search_vector @@ q.query
+                      AND s.coaching_relationship_id IN (SELECT id FROM relationships)
+                      AND n.deleted_at IS NULL
+                      AND s.deleted_at IS NULL
+                    UNION ALL
+                    SELECT 'action', a.id, s.coaching_relationship_id, a.coaching_session_id,
+                           NULL, a.body, ts_rank(a.search_vector, q.query), a.updated_at
+                    FROM refactor_platform.actions a
+                    JOIN refactor_platform.coaching_sessions s ON s.id = a.coaching_session_id
+                    CROSS JOIN q
+                    WHERE a.search_vector @@ q.query
+                      AND s.coaching_relationship_id IN (SELECT id FROM relationships)
+                      AND a.deleted_at IS NULL
+                      AND s.deleted_at IS NULL
+                    UNION ALL
+                    SELECT 'agreement', ag.id, s.coaching_relationship_id,
+                           ag.coaching_session_id, NULL, ag.body,
+                           ts_rank(ag.search_vector, q.query), ag.updated_at
+                    FROM refactor_platform.agreements ag
+                    JOIN refactor_platform.coaching_sessions s ON s.id = ag.coaching_session_id
+                    CROSS JOIN q
+                    WHERE ag.search_vector @@ q.query
+                      AND s.coaching_relationship_id IN (SELECT id FROM relationships)
+                      AND ag.deleted_at IS NULL
+                      AND s.deleted_at IS NULL
+                    UNION ALL
+                    SELECT 'goal', g.id, g.coaching_relationship_id, g.created_in_session_id,
+                           g.title, concat_ws(' ', g.title, g.body),
+                           ts_rank(g.search_vector, q.query), g.updated_at
+                    FROM refactor_platform.goals g
+                    CROSS JOIN q
+                    WHERE g.search_vector @@ q.query
+                      AND g.coaching_relationship_id IN (SELECT id FROM relationships)
+                      AND g.deleted_at IS NULL
+                    ORDER BY rank DESC, updated_at DESC
+                    LIMIT $3
+                )
+           SELECT hits.result_type, hits.id, hits.coaching_relationship_id,
+                  hits.coaching_session_id, hits.title,
+                  ts_headline('english', regexp_replace(hits.document, '<[^>]*>', ' ', 'g'),
+                              q.query, $4) AS highlight,
+                  hits.rank, hits.updated_at
+           FROM hits CROSS JOIN q
+           ORDER BY hits.rank DESC, hits.updated_at DESC"#,
+        [
+            user_id.into(),
+            query.into(),
+            limit.into(),
+            HEADLINE_OPTIONS.into(),
+        ],
+    );
+    Ok(SearchResult::find_by_statement(stmt).all(db).await?)
+}
+
+#[cfg(test)]
+// We need to gate seaORM's mock feature behind conditional compilation because
+// the feature removes the Clone trait implementation from seaORM's DatabaseConnection.
+// see https://github.com/SeaQL/sea-orm/issues/830
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use sea_orm::{MockDatabase, Value};
+    use std::collections::BTreeMap;
+
+    #[tokio::test]
+    async fn search_is_scoped_to_the_users_relationships_and_limited() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<BTreeMap<&str, Value>>::new()])
+            .into_connection();
+        let user_id = Id::new_v4();
+
+        search(&db, user_id, "career change", 20).await?;
+
+        let log = db.into_transaction_log();
+        let statement = &log[0].statements()[0];
+        assert!(statement
+            .sql
+            .contains("WHERE coach_id = $1 OR coachee_id = $1"));
+        let values = statement.values.as_ref().unwrap();
+        assert_eq!(values.0[0], Value::Uuid(Some(Box::new(user_id))));
+        assert_eq!(
+            values.0[1],
+            Value::String(Some(Box::new("career change".to_string())))
+        );
+        assert_eq!(values.0[2], Value::BigUnsigned(Some(20)));
+        Ok(())
+    }
+}
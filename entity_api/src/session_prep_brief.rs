@@ -0,0 +1,64 @@
+use super::error::Error;
+use entity::session_prep_briefs::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{entity::prelude::*, sea_query::OnConflict, ActiveValue::Set, ConnectionTrait};
+
+use log::*;
+
+pub async fn find_by_coaching_session_id(
+    db: &impl ConnectionTrait,
+    coaching_session_id: Id,
+) -> Result<Option<Model>, Error> {
+    Ok(Entity::find_by_id(coaching_session_id).one(db).await?)
+}
+
+/// The cached briefs of any of `coaching_session_ids`.
+pub async fn find_by_coaching_session_ids(
+    db: &impl ConnectionTrait,
+    coaching_session_ids: &[Id],
+) -> Result<Vec<Model>, Error> {
+    if coaching_session_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    Ok(Entity::find()
+        .filter(Column::CoachingSessionId.is_in(coaching_session_ids.iter().copied()))
+        .all(db)
+        .await?)
+}
+
+/// Stores the generated brief for a session, replacing any earlier one.
+pub async fn upsert(db: &impl ConnectionTrait, model: Model) -> Result<Model, Error> {
+    debug!(
+        "Storing session prep brief for coaching session {}",
+        model.coaching_session_id
+    );
+
+    let now = chrono::Utc::now();
+    let active_model = ActiveModel {
+        coaching_session_id: Set(model.coaching_session_id),
+        last_session_summary: Set(model.last_session_summary),
+        focus_questions: Set(model.focus_questions),
+        model: Set(model.model),
+        prompt_version: Set(model.prompt_version),
+        generated_at: Set(model.generated_at),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+    };
+
+    let on_conflict = OnConflict::column(Column::CoachingSessionId)
+        .update_columns([
+            Column::LastSessionSummary,
+            Column::FocusQuestions,
+            Column::Model,
+            Column::PromptVersion,
+            Column::GeneratedAt,
+            Column::UpdatedAt,
+        ])
+        .to_owned();
+
+    Ok(Entity::insert(active_model)
+        .on_conflict(on_conflict)
+        .exec_with_returning(db)
+        .await?)
+}
@@ -0,0 +1,205 @@
+//! Post-session pulse check-ins: a prompt row per participant once a session ends, and
+//! the 1–5 score they answer with.
+
+use super::error::{EntityApiErrorKind, Error};
+use entity::session_pulses::{ActiveModel, Column, Entity, Model, Relation};
+use entity::{coaching_relationships, coaching_sessions, Id};
+use sea_orm::{
+    entity::prelude::*, sea_query::OnConflict, ActiveValue::Set, ConnectionTrait, FromQueryResult,
+    JoinType, QueryOrder, QuerySelect, QueryTrait,
+};
+
+use log::*;
+
+/// A participant's answered pulse, with the relationship its session belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, FromQueryResult)]
+pub struct PulseResponse {
+    pub coaching_relationship_id: Id,
+    pub user_id: Id,
+    pub score: i16,
+}
+
+/// Live sessions starting within `[from, to]` whose participants haven't been prompted
+/// yet, each with its coaching relationship, earliest first.
+pub async fn find_sessions_awaiting_pulse(
+    db: &impl ConnectionTrait,
+    from: DateTime,
+    to: DateTime,
+) -> Result<Vec<(coaching_sessions::Model, coaching_relationships::Model)>, Error> {
+    let prompted = Entity::find()
+        .select_only()
+        .column(Column::CoachingSessionId)
+        .into_query();
+
+    let sessions = coaching_sessions::Entity::find()
+        .find_also_related(coaching_relationships::Entity)
+        .filter(coaching_sessions::Column::Date.between(from, to))
+        .filter(coaching_sessions::Column::DeletedAt.is_null())
+        .filter(coaching_sessions::Column::Id.not_in_subquery(prompted))
+        .order_by_asc(coaching_sessions::Column::Date)
+        .all(db)
+        .await?;
+
+    Ok(sessions
+        .into_iter()
+        .filter_map(|(session, relationship)| relationship.map(|r| (session, r)))
+        .collect())
+}
+
+/// Records that each of `user_ids` was prompted for the session. Users already prompted
+/// are left as they are. Returns how many prompts were newly recorded.
+pub async fn request(
+    db: &impl ConnectionTrait,
+    coaching_session_id: Id,
+    user_ids: &[Id],
+) -> Result<u64, Error> {
+    if user_ids.is_empty() {
+        return Ok(0);
+    }
+
+    let now = chrono::Utc::now();
+    let active_models = user_ids.iter().map(|user_id| ActiveModel {
+        coaching_session_id: Set(coaching_session_id),
+        user_id: Set(*user_id),
+        score: Set(None),
+        requested_at: Set(now.into()),
+        responded_at: Set(None),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+        ..Default::default()
+    });
+
+    let on_conflict = OnConflict::columns([Column::CoachingSessionId, Column::UserId])
+        .do_nothing()
+        .to_owned();
+
+    Ok(Entity::insert_many(active_models)
+        .on_conflict(on_conflict)
+        .exec_without_returning(db)
+        .await?)
+}
+
+/// Stores the user's score for the session, replacing an earlier answer. Answering
+/// without having been prompted records the prompt as well.
+pub async fn respond(
+    db: &impl ConnectionTrait,
+    coaching_session_id: Id,
+    user_id: Id,
+    score: i16,
+) -> Result<Model, Error> {
+    debug!("User {user_id} scored coaching session {coaching_session_id} a {score}");
+
+    let now = chrono::Utc::now();
+    let active_model = ActiveModel {
+        coaching_session_id: Set(coaching_session_id),
+        user_id: Set(user_id),
+        score: Set(Some(score)),
+        requested_at: Set(now.into()),
+        responded_at: Set(Some(now.into())),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+        ..Default::default()
+    };
+
+    let on_conflict = OnConflict::columns([Column::CoachingSessionId, Column::UserId])
+        .update_columns([Column::Score, Column::RespondedAt, Column::UpdatedAt])
+        .to_owned();
+
+    Ok(Entity::insert(active_model)
+        .on_conflict(on_conflict)
+        .exec_with_returning(db)
+        .await?)
+}
+
+/// The user's pulse for the session, prompted or answered.
+pub async fn find_by_session_and_user(
+    db: &impl ConnectionTrait,
+    coaching_session_id: Id,
+    user_id: Id,
+) -> Result<Model, Error> {
+    Entity::find()
+        .filter(Column::CoachingSessionId.eq(coaching_session_id))
+        .filter(Column::UserId.eq(user_id))
+        .one(db)
+        .await?
+        .ok_or(Error {
+            source: None,
+            error_kind: EntityApiErrorKind::RecordNotFound,
+        })
+}
+
+/// The user's unanswered prompts sent since `since`, on sessions that still exist,
+/// newest first.
+pub async fn find_pending_by_user(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+    since: DateTimeWithTimeZone,
+) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .join(JoinType::InnerJoin, Relation::CoachingSessions.def())
+        .filter(Column::UserId.eq(user_id))
+        .filter(Column::Score.is_null())
+        .filter(Column::RequestedAt.gte(since))
+        .filter(coaching_sessions::Column::DeletedAt.is_null())
+        .order_by_desc(Column::RequestedAt)
+        .all(db)
+        .await?)
+}
+
+/// Every answered pulse on the live sessions of `relationship_ids` held since `since`.
+pub async fn find_responses_by_relationships(
+    db: &impl ConnectionTrait,
+    relationship_ids: &[Id],
+    since: DateTime,
+) -> Result<Vec<PulseResponse>, Error> {
+    if relationship_ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    Ok(Entity::find()
+        .select_only()
+        .column(coaching_sessions::Column::CoachingRelationshipId)
+        .column(Column::UserId)
+        .column(Column::Score)
+        .join(JoinType::InnerJoin, Relation::CoachingSessions.def())
+        .filter(
+            coaching_sessions::Column::CoachingRelationshipId
+                .is_in(relationship_ids.iter().copied()),
+        )
+        .filter(coaching_sessions::Column::Date.gte(since))
+        .filter(coaching_sessions::Column::DeletedAt.is_null())
+        .filter(Column::Score.is_not_null())
+        .into_model::<PulseResponse>()
+        .all(db)
+        .await?)
+}
+
+#[cfg(test)]
+// We need to gate seaORM's mock feature behind conditional compilation because
+// the feature removes the Clone trait implementation from seaORM's DatabaseConnection.
+// see https://github.com/SeaQL/sea-orm/issues/830
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    #[tokio::test]
+    async fn find_sessions_awaiting_pulse_skips_prompted_and_deleted_sessions() -> Result<(), Error>
+    {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<coaching_sessions::Model>::new()])
+            .into_connection();
+        let now = chrono::Utc::now().naive_utc();
+
+        find_sessions_awaiting_pulse(&db, now - chrono::Duration::days(1), now).await?;
+
+        let log = db.into_transaction_log();
+        let sql = &log[0].statements()[0].sql;
+        assert!(sql.contains(r#""coaching_sessions"."date" BETWEEN $1 AND $2"#));
+        assert!(sql.contains(r#""coaching_sessions"."deleted_at" IS NULL"#));
+        assert!(sql.contains(
+            r#""coaching_sessions"."id" NOT IN (SELECT "session_pulses"."coaching_session_id" FROM "refactor_platform"."session_pulses")"#
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,155 @@
+//! Coaching sessions' compiled records of engagement.
+
+use super::error::Error;
+use chrono::Utc;
+use entity::session_record_status::SessionRecordStatus;
+use entity::session_records::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{
+    entity::prelude::*, sea_query::OnConflict, ActiveValue::Set, ConnectionTrait, QueryOrder,
+    QuerySelect,
+};
+
+pub async fn find_by_coaching_session_id(
+    db: &impl ConnectionTrait,
+    coaching_session_id: Id,
+) -> Result<Option<Model>, Error> {
+    Ok(Entity::find_by_id(coaching_session_id).one(db).await?)
+}
+
+/// Queues the session's record for compiling, keeping any stored PDF until it's replaced.
+pub async fn queue(db: &impl ConnectionTrait, coaching_session_id: Id) -> Result<Model, Error> {
+    let now = Utc::now();
+    let active_model = ActiveModel {
+        coaching_session_id: Set(coaching_session_id),
+        status: Set(SessionRecordStatus::Pending),
+        storage_key: Set(None),
+        byte_size: Set(None),
+        error_message: Set(None),
+        generated_at: Set(None),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+    };
+
+    let on_conflict = OnConflict::column(Column::CoachingSessionId)
+        .update_columns([Column::Status, Column::ErrorMessage, Column::UpdatedAt])
+        .to_owned();
+
+    Ok(Entity::insert(active_model)
+        .on_conflict(on_conflict)
+        .exec_with_returning(db)
+        .await?)
+}
+
+/// Up to `limit` records waiting to be compiled, longest waiting first.
+pub async fn find_pending(db: &impl ConnectionTrait, limit: u64) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::Status.eq(SessionRecordStatus::Pending))
+        .order_by_asc(Column::UpdatedAt)
+        .limit(limit)
+        .all(db)
+        .await?)
+}
+
+/// Records that the PDF compiled from the pending `record` is stored under `storage_key`.
+/// Returns false, changing nothing, when the record was requested again meanwhile, so
+/// the next pass compiles it afresh.
+pub async fn mark_ready(
+    db: &impl ConnectionTrait,
+    record: &Model,
+    storage_key: String,
+    byte_size: i64,
+) -> Result<bool, Error> {
+    let now = Utc::now();
+    settle(
+        db,
+        record,
+        ActiveModel {
+            status: Set(SessionRecordStatus::Ready),
+            storage_key: Set(Some(storage_key)),
+            byte_size: Set(Some(byte_size)),
+            error_message: Set(None),
+            generated_at: Set(Some(now.into())),
+            updated_at: Set(now.into()),
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+/// Records that compiling the pending `record` failed; like [`mark_ready`], unless it
+/// was requested again meanwhile.
+pub async fn mark_failed(
+    db: &impl ConnectionTrait,
+    record: &Model,
+    error_message: String,
+) -> Result<bool, Error> {
+    settle(
+        db,
+        record,
+        ActiveModel {
+            status: Set(SessionRecordStatus::Failed),
+            error_message: Set(Some(error_message)),
+            updated_at: Set(Utc::now().into()),
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+async fn settle(
+    db: &impl ConnectionTrait,
+    record: &Model,
+    changes: ActiveModel,
+) -> Result<bool, Error> {
+    let result = Entity::update_many()
+        .set(changes)
+        .filter(Column::CoachingSessionId.eq(record.coaching_session_id))
+        .filter(Column::Status.eq(SessionRecordStatus::Pending))
+        .filter(Column::UpdatedAt.eq(record.updated_at))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected > 0)
+}
+
+#[cfg(test)]
+// We need to gate seaORM's mock feature behind conditional compilation because
+// the feature removes the Clone trait implementation from seaORM's DatabaseConnection.
+// see https://github.com/SeaQL/sea-orm/issues/830
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+
+    #[tokio::test]
+    async fn mark_ready_leaves_a_record_requested_again_meanwhile() -> Result<(), Error> {
+        let now = Utc::now();
+        let record = Model {
+            coaching_session_id: Id::new_v4(),
+            status: SessionRecordStatus::Pending,
+            storage_key: None,
+            byte_size: None,
+            error_message: None,
+            generated_at: None,
+            created_at: now.into(),
+            updated_at: now.into(),
+        };
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results([MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 0,
+            }])
+            .into_connection();
+
+        let stored = mark_ready(&db, &record, "key".to_string(), 1024).await?;
+
+        assert!(!stored);
+        let log = db.into_transaction_log();
+        let sql = &log[0].statements()[0].sql;
+        assert!(sql.starts_with(r#"UPDATE "refactor_platform"."session_records" SET"#));
+        assert!(sql.contains(r#""status" = CAST($1 AS "session_record_status")"#));
+        assert!(sql.contains(r#"AND "session_records"."status" = (CAST($"#));
+        assert!(sql.contains(r#"AND "session_records"."updated_at" = $"#));
+        Ok(())
+    }
+}
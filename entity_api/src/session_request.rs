@@ -0,0 +1,215 @@
+//! Coachees' requests for ad-hoc sessions.
+//!
+//! A request is created pending and answered once: [`resolve`] only moves a request that
+//! is still pending, so two answers racing each other can't both land.
+
+use super::error::{EntityApiErrorKind, Error};
+use entity::session_request_status::SessionRequestStatus;
+use entity::session_requests::{ActiveModel, Column, Entity, Model, StatusChange, StatusHistory};
+use entity::Id;
+use sea_orm::{
+    entity::prelude::*,
+    ActiveValue::{Set, Unchanged},
+    ConnectionTrait, QueryOrder, TryIntoModel,
+};
+
+use log::*;
+
+/// Inserts a pending request by `requested_by_user_id`, its history starting with that.
+pub async fn create(
+    db: &impl ConnectionTrait,
+    coaching_relationship_id: Id,
+    requested_by_user_id: Id,
+    model: Model,
+) -> Result<Model, Error> {
+    debug!(
+        "New session request by user {requested_by_user_id} in relationship \
+         {coaching_relationship_id}"
+    );
+
+    let now = chrono::Utc::now();
+    let active_model = ActiveModel {
+        coaching_relationship_id: Set(coaching_relationship_id),
+        requested_by_user_id: Set(requested_by_user_id),
+        preferred_windows: Set(model.preferred_windows),
+        duration_minutes: Set(model.duration_minutes),
+        message: Set(model.message),
+        status: Set(SessionRequestStatus::Pending),
+        decline_reason: Set(None),
+        coaching_session_id: Set(None),
+        status_history: Set(StatusHistory(vec![StatusChange {
+            status: SessionRequestStatus::Pending,
+            changed_by_user_id: requested_by_user_id,
+            reason: None,
+            changed_at: now.into(),
+        }])),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+        ..Default::default()
+    };
+
+    Ok(active_model.insert(db).await?.try_into_model()?)
+}
+
+/// Answers a pending request: moves it to `change.status`, records the change in its
+/// history and, for an accepted request, links the session scheduled for it. A request
+/// that is no longer pending is left alone and reported as a validation error.
+pub async fn resolve(
+    db: &impl ConnectionTrait,
+    existing: Model,
+    change: StatusChange,
+    coaching_session_id: Option<Id>,
+) -> Result<Model, Error> {
+    let id = existing.id;
+    let mut history = existing.status_history.0;
+    let decline_reason = match change.status {
+        SessionRequestStatus::Declined => change.reason.clone(),
+        _ => None,
+    };
+    let status = change.status;
+    let updated_at = change.changed_at;
+    history.push(change);
+
+    let active_model = ActiveModel {
+        id: Unchanged(id),
+        coaching_relationship_id: Unchanged(existing.coaching_relationship_id),
+        requested_by_user_id: Unchanged(existing.requested_by_user_id),
+        preferred_windows: Unchanged(existing.preferred_windows),
+        duration_minutes: Unchanged(existing.duration_minutes),
+        message: Unchanged(existing.message),
+        status: Set(status),
+        decline_reason: Set(decline_reason),
+        coaching_session_id: Set(coaching_session_id),
+        status_history: Set(StatusHistory(history)),
+        created_at: Unchanged(existing.created_at),
+        updated_at: Set(updated_at),
+    };
+
+    match Entity::update(active_model)
+        .filter(Column::Status.eq(SessionRequestStatus::Pending))
+        .exec(db)
+        .await
+    {
+        Ok(request) => Ok(request),
+        Err(DbErr::RecordNotUpdated) => {
+            warn!("Session request {id} was already answered");
+
+            Err(Error {
+                source: None,
+                error_kind: EntityApiErrorKind::ValidationError {
+                    message: "The session request was already answered".to_string(),
+                    details: None,
+                },
+            })
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub async fn find_by_id(db: &impl ConnectionTrait, id: Id) -> Result<Model, Error> {
+    Entity::find_by_id(id).one(db).await?.ok_or_else(|| {
+        error!("Session request with id {id} not found");
+
+        Error {
+            source: None,
+            error_kind: EntityApiErrorKind::RecordNotFound,
+        }
+    })
+}
+
+/// A relationship's requests, optionally only those in `status`, oldest first so that
+/// the pending ones read as a waiting list.
+pub async fn find_by_relationship(
+    db: &impl ConnectionTrait,
+    coaching_relationship_id: Id,
+    status: Option<SessionRequestStatus>,
+) -> Result<Vec<Model>, Error> {
+    let mut query =
+        Entity::find().filter(Column::CoachingRelationshipId.eq(coaching_relationship_id));
+    if let Some(status) = status {
+        query = query.filter(Column::Status.eq(status));
+    }
+
+    Ok(query.order_by_asc(Column::CreatedAt).all(db).await?)
+}
+
+#[cfg(test)]
+// We need to gate seaORM's mock feature behind conditional compilation because
+// the feature removes the Clone trait implementation from seaORM's DatabaseConnection.
+// see https://github.com/SeaQL/sea-orm/issues/830
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use entity::session_requests::{TimeWindow, TimeWindows};
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    fn pending_request() -> Model {
+        let now = chrono::Utc::now();
+        Model {
+            id: Id::new_v4(),
+            coaching_relationship_id: Id::new_v4(),
+            requested_by_user_id: Id::new_v4(),
+            preferred_windows: TimeWindows(vec![TimeWindow {
+                starts_at: (now + chrono::Duration::days(1)).into(),
+                ends_at: (now + chrono::Duration::days(1) + chrono::Duration::hours(2)).into(),
+            }]),
+            duration_minutes: None,
+            message: None,
+            status: SessionRequestStatus::Pending,
+            decline_reason: None,
+            coaching_session_id: None,
+            status_history: StatusHistory::default(),
+            created_at: now.into(),
+            updated_at: now.into(),
+        }
+    }
+
+    fn decline(reason: &str) -> StatusChange {
+        StatusChange {
+            status: SessionRequestStatus::Declined,
+            changed_by_user_id: Id::new_v4(),
+            reason: Some(reason.to_string()),
+            changed_at: chrono::Utc::now().into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn resolve_only_updates_a_pending_request() -> Result<(), Error> {
+        let existing = pending_request();
+        let declined = Model {
+            status: SessionRequestStatus::Declined,
+            decline_reason: Some("Travelling that week".to_string()),
+            ..existing.clone()
+        };
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![declined.clone()]])
+            .into_connection();
+
+        let request = resolve(&db, existing, decline("Travelling that week"), None).await?;
+
+        assert_eq!(request, declined);
+        let log = db.into_transaction_log();
+        let sql = &log[0].statements()[0].sql;
+        assert!(sql.contains(
+            r#"WHERE "session_requests"."id" = $6 AND "session_requests"."status" = (CAST($7 AS "session_request_status"))"#
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn resolve_refuses_a_request_already_answered() {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<Model>::new()])
+            .into_connection();
+
+        let result = resolve(&db, pending_request(), decline("No"), None).await;
+
+        assert!(matches!(
+            result,
+            Err(Error {
+                error_kind: EntityApiErrorKind::ValidationError { .. },
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,243 @@
+use super::error::{EntityApiErrorKind, Error};
+use entity::session_types::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{
+    entity::prelude::*,
+    ActiveValue::{Set, Unchanged},
+    ConnectionTrait, DatabaseBackend, FromQueryResult, QueryOrder, SqlErr, Statement, TryIntoModel,
+};
+
+use log::*;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Sessions of one type held in an organization over a reporting window.
+#[derive(Debug, Clone, PartialEq, Eq, FromQueryResult)]
+pub struct TypeCount {
+    /// `None` for the sessions without a type.
+    pub session_type_id: Option<Id>,
+    pub session_count: i64,
+    pub total_minutes: i64,
+}
+
+/// Sessions of one type held over a reported window.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[schema(as = domain::session_type::SessionTypeUsage)]
+pub struct SessionTypeUsage {
+    /// `None` for the sessions without a type.
+    pub session_type_id: Option<Id>,
+    /// The type's name; `None` for the sessions without a type.
+    pub name: Option<String>,
+    pub session_count: i64,
+    pub total_minutes: i64,
+}
+
+/// Inserts a session type for `organization_id`. Names are unique within an organization.
+pub async fn create(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+    model: Model,
+) -> Result<Model, Error> {
+    debug!("New session type for organization {organization_id}: {model:?}");
+
+    let now = chrono::Utc::now();
+    let active_model = ActiveModel {
+        organization_id: Set(organization_id),
+        name: Set(model.name),
+        default_duration_minutes: Set(model.default_duration_minutes),
+        color: Set(model.color),
+        requires_recording: Set(model.requires_recording),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+        ..Default::default()
+    };
+
+    active_model
+        .insert(db)
+        .await
+        .map_err(name_taken_or)?
+        .try_into_model()
+        .map_err(Into::into)
+}
+
+/// Replaces a session type's settings. Sessions already of the type keep their durations.
+pub async fn update(
+    db: &impl ConnectionTrait,
+    existing: Model,
+    model: Model,
+) -> Result<Model, Error> {
+    let active_model = ActiveModel {
+        id: Unchanged(existing.id),
+        organization_id: Unchanged(existing.organization_id),
+        name: Set(model.name),
+        default_duration_minutes: Set(model.default_duration_minutes),
+        color: Set(model.color),
+        requires_recording: Set(model.requires_recording),
+        created_at: Unchanged(existing.created_at),
+        updated_at: Set(chrono::Utc::now().into()),
+    };
+
+    active_model
+        .update(db)
+        .await
+        .map_err(name_taken_or)?
+        .try_into_model()
+        .map_err(Into::into)
+}
+
+pub async fn delete_by_id(db: &impl ConnectionTrait, id: Id) -> Result<(), Error> {
+    Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
+
+pub async fn find_by_id(db: &impl ConnectionTrait, id: Id) -> Result<Model, Error> {
+    Entity::find_by_id(id).one(db).await?.ok_or_else(|| {
+        error!("Session type with id {id} not found");
+
+        Error {
+            source: None,
+            error_kind: EntityApiErrorKind::RecordNotFound,
+        }
+    })
+}
+
+/// All of an organization's session types, alphabetically by name.
+pub async fn find_by_organization(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::OrganizationId.eq(organization_id))
+        .order_by_asc(Column::Name)
+        .all(db)
+        .await?)
+}
+
+/// Counts and total minutes of the organization's sessions dated within `[from, to)`,
+/// one row per session type they are of, plus one for untyped sessions. Types without
+/// sessions in the window have no row.
+pub async fn count_sessions_by_type(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+    from: DateTime,
+    to: DateTime,
+) -> Result<Vec<TypeCount>, Error> {
+    let stmt = Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"SELECT s.session_type_id,
+                  COUNT(*)::BIGINT AS session_count,
+                  COALESCE(SUM(s.duration_minutes), 0)::BIGINT AS total_minutes
+           FROM refactor_platform.coaching_sessions s
+           JOIN refactor_platform.coaching_relationships r
+               ON r.id = s.coaching_relationship_id
+           WHERE r.organization_id = $1 AND s.date >= $2 AND s.date < $3
+             AND s.deleted_at IS NULL
+           GROUP BY s.session_type_id"#,
+        [organization_id.into(), from.into(), to.into()],
+    );
+    Ok(TypeCount::find_by_statement(stmt).all(db).await?)
+}
+
+/// Reports a second type of the same name in the organization as a validation error.
+fn name_taken_or(err: DbErr) -> Error {
+    if matches!(err.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) {
+        return Error {
+            source: None,
+            error_kind: EntityApiErrorKind::ValidationError {
+                message: "The organization already has a session type with this name".to_string(),
+                details: None,
+            },
+        };
+    }
+    err.into()
+}
+
+#[cfg(test)]
+// We need to gate seaORM's mock feature behind conditional compilation because
+// the feature removes the Clone trait implementation from seaORM's DatabaseConnection.
+// see https://github.com/SeaQL/sea-orm/issues/830
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase, Value};
+    use std::collections::BTreeMap;
+
+    fn session_type() -> Model {
+        let now = chrono::Utc::now();
+        Model {
+            id: Id::new_v4(),
+            organization_id: Id::new_v4(),
+            name: "Intro call".to_string(),
+            default_duration_minutes: 30,
+            color: Some("#3b82f6".to_string()),
+            requires_recording: false,
+            created_at: now.into(),
+            updated_at: now.into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn update_keeps_the_organization() -> Result<(), Error> {
+        let existing = session_type();
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![existing.clone()]])
+            .into_connection();
+
+        update(&db, existing.clone(), existing).await?;
+
+        let log = db.into_transaction_log();
+        let sql = &log[0].statements()[0].sql;
+        let set_clause = &sql[sql.find(" SET ").unwrap()..sql.find(" WHERE ").unwrap()];
+        assert!(!set_clause.contains("organization_id"));
+        assert!(!set_clause.contains("created_at"));
+        Ok(())
+    }
+
+    // `TypeCount` is a `FromQueryResult` (not a `Model`), so canned rows are column maps.
+    fn count_row(session_type_id: Option<Id>, session_count: i64) -> BTreeMap<String, Value> {
+        BTreeMap::from([
+            ("session_type_id".to_owned(), Value::from(session_type_id)),
+            ("session_count".to_owned(), Value::from(session_count)),
+            ("total_minutes".to_owned(), Value::from(session_count * 30)),
+        ])
+    }
+
+    #[tokio::test]
+    async fn count_sessions_by_type_groups_the_organizations_sessions() -> Result<(), Error> {
+        let session_type_id = Id::new_v4();
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![
+                count_row(Some(session_type_id), 3),
+                count_row(None, 1),
+            ]])
+            .into_connection();
+        let from = chrono::NaiveDate::from_ymd_opt(2026, 10, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+
+        let counts =
+            count_sessions_by_type(&db, Id::new_v4(), from, from + chrono::Days::new(30)).await?;
+
+        assert_eq!(
+            counts,
+            vec![
+                TypeCount {
+                    session_type_id: Some(session_type_id),
+                    session_count: 3,
+                    total_minutes: 90,
+                },
+                TypeCount {
+                    session_type_id: None,
+                    session_count: 1,
+                    total_minutes: 30,
+                },
+            ]
+        );
+        let log = db.into_transaction_log();
+        assert!(log[0].statements()[0]
+            .sql
+            .contains("GROUP BY s.session_type_id"));
+        Ok(())
+    }
+}
@@ -0,0 +1,279 @@
+use super::error::Error;
+use chrono::NaiveDate;
+use entity::event_store::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{
+    entity::prelude::*, sea_query::Expr, ActiveValue::Set, ConnectionTrait, DbBackend,
+    FromQueryResult, QueryOrder, QuerySelect, Select, Statement, TryIntoModel,
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use log::*;
+
+/// A page of stored events, newest first.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[schema(as = domain::event_log::EventPage)]
+pub struct EventPage {
+    pub events: Vec<Model>,
+    /// Pass as `cursor` to fetch the next page; `None` on the last page.
+    pub next_cursor: Option<String>,
+}
+
+/// Appends an event to the store; `id` and `created_at` are assigned here.
+pub async fn create(db: &impl ConnectionTrait, event: Model) -> Result<Model, Error> {
+    debug!(
+        "Storing {} event for {} {:?}",
+        event.event_type, event.entity_type, event.entity_id
+    );
+
+    let active_model = ActiveModel {
+        id: Set(Id::new_v4()),
+        event_type: Set(event.event_type),
+        entity_type: Set(event.entity_type),
+        entity_id: Set(event.entity_id),
+        coaching_relationship_id: Set(event.coaching_relationship_id),
+        coaching_session_id: Set(event.coaching_session_id),
+        actor_user_id: Set(event.actor_user_id),
+        payload: Set(event.payload),
+        created_at: Set(chrono::Utc::now().into()),
+    };
+
+    Ok(active_model.insert(db).await?.try_into_model()?)
+}
+
+/// Up to `limit` stored events, newest first, optionally only those about `entity_type`
+/// and created within `[from, to)`. `before` continues a previous page from the
+/// `(created_at, id)` of its last event.
+pub async fn find(
+    db: &impl ConnectionTrait,
+    entity_type: Option<&str>,
+    from: Option<DateTimeWithTimeZone>,
+    to: Option<DateTimeWithTimeZone>,
+    before: Option<(DateTimeWithTimeZone, Id)>,
+    limit: u64,
+) -> Result<Vec<Model>, Error> {
+    let mut query = Entity::find();
+    if let Some(entity_type) = entity_type {
+        query = query.filter(Column::EntityType.eq(entity_type));
+    }
+    if let Some(from) = from {
+        query = query.filter(Column::CreatedAt.gte(from));
+    }
+    if let Some(to) = to {
+        query = query.filter(Column::CreatedAt.lt(to));
+    }
+
+    Ok(page(query, before, limit).all(db).await?)
+}
+
+/// Up to `limit` events that happened in the coaching relationship, newest first;
+/// `before` as for [`find`].
+pub async fn find_by_coaching_relationship(
+    db: &impl ConnectionTrait,
+    coaching_relationship_id: Id,
+    before: Option<(DateTimeWithTimeZone, Id)>,
+    limit: u64,
+) -> Result<Vec<Model>, Error> {
+    let query = Entity::find().filter(Column::CoachingRelationshipId.eq(coaching_relationship_id));
+
+    Ok(page(query, before, limit).all(db).await?)
+}
+
+/// Up to `limit` events created before `until`, oldest first, continuing after the
+/// `(created_at, id)` of `after`.
+pub async fn find_after(
+    db: &impl ConnectionTrait,
+    after: (DateTimeWithTimeZone, Id),
+    until: DateTimeWithTimeZone,
+    limit: u64,
+) -> Result<Vec<Model>, Error> {
+    let (created_at, id) = after;
+    Ok(Entity::find()
+        .filter(
+            Expr::tuple([
+                Expr::col((Entity, Column::CreatedAt)).into(),
+                Expr::col((Entity, Column::Id)).into(),
+            ])
+            .gt(Expr::tuple([Expr::value(created_at), Expr::value(id)])),
+        )
+        .filter(Column::CreatedAt.lt(until))
+        .order_by_asc(Column::CreatedAt)
+        .order_by_asc(Column::Id)
+        .limit(limit)
+        .all(db)
+        .await?)
+}
+
+/// Orders `query` newest first and limits it to the `limit` events after `before`.
+fn page(
+    query: Select<Entity>,
+    before: Option<(DateTimeWithTimeZone, Id)>,
+    limit: u64,
+) -> Select<Entity> {
+    let query = match before {
+        Some((created_at, id)) => query.filter(
+            Expr::tuple([
+                Expr::col((Entity, Column::CreatedAt)).into(),
+                Expr::col((Entity, Column::Id)).into(),
+            ])
+            .lt(Expr::tuple([Expr::value(created_at), Expr::value(id)])),
+        ),
+        None => query,
+    };
+
+    query
+        .order_by_desc(Column::CreatedAt)
+        .order_by_desc(Column::Id)
+        .limit(limit)
+}
+
+/// Creates the partition holding the events of `month`'s month unless it exists.
+pub async fn ensure_partition(db: &impl ConnectionTrait, month: NaiveDate) -> Result<(), Error> {
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "SELECT refactor_platform.create_event_store_partition($1)",
+        [month.into()],
+    ))
+    .await?;
+    Ok(())
+}
+
+#[derive(Debug, FromQueryResult)]
+struct Partition {
+    name: String,
+}
+
+/// Names of the table's partitions, e.g. `event_store_2026_10`.
+pub async fn partitions(db: &impl ConnectionTrait) -> Result<Vec<String>, Error> {
+    let partitions = Partition::find_by_statement(Statement::from_string(
+        DbBackend::Postgres,
+        r#"SELECT child.relname AS name
+           FROM pg_inherits
+           JOIN pg_class parent ON parent.oid = pg_inherits.inhparent
+           JOIN pg_class child ON child.oid = pg_inherits.inhrelid
+           JOIN pg_namespace ON pg_namespace.oid = parent.relnamespace
+           WHERE pg_namespace.nspname = 'refactor_platform'
+             AND parent.relname = 'event_store'
+           ORDER BY child.relname"#,
+    ))
+    .all(db)
+    .await?;
+
+    Ok(partitions.into_iter().map(|p| p.name).collect())
+}
+
+/// Drops the partition named `name` and every event in it. Names other than those of
+/// monthly partitions are ignored.
+pub async fn drop_partition(db: &impl ConnectionTrait, name: &str) -> Result<(), Error> {
+    // Identifiers can't be bound as parameters, so only interpolate a name of the
+    // `event_store_YYYY_MM` shape.
+    let is_monthly = name
+        .strip_prefix("event_store_")
+        .is_some_and(|month| NaiveDate::parse_from_str(&format!("{month}_01"), "%Y_%m_%d").is_ok());
+    if !is_monthly {
+        warn!("Not dropping {name}, which isn't a monthly event store partition");
+        return Ok(());
+    }
+
+    info!("Dropping event store partition {name}");
+    db.execute_unprepared(&format!("DROP TABLE IF EXISTS refactor_platform.{name}"))
+        .await?;
+    Ok(())
+}
+
+/// Deletes the `*_updated` events created before `cutoff` that a later event of the same
+/// type about the same entity supersedes, keeping the entity's latest state. Returns how
+/// many were deleted.
+pub async fn compact_updates_before(
+    db: &impl ConnectionTrait,
+    cutoff: DateTimeWithTimeZone,
+) -> Result<u64, Error> {
+    let result = db
+        .execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            r#"DELETE FROM refactor_platform.event_store AS old
+               WHERE old.created_at < $1
+                 AND old.event_type LIKE '%\_updated'
+                 AND old.entity_id IS NOT NULL
+                 AND EXISTS (
+                     SELECT 1 FROM refactor_platform.event_store AS newer
+                     WHERE newer.event_type = old.event_type
+                       AND newer.entity_type = old.entity_type
+                       AND newer.entity_id = old.entity_id
+                       AND (newer.created_at, newer.id) > (old.created_at, old.id)
+                 )"#,
+            [cutoff.into()],
+        ))
+        .await?;
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+
+    #[tokio::test]
+    async fn find_continues_after_the_cursor_newest_first() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<Model>::new()])
+            .into_connection();
+
+        find(
+            &db,
+            Some("goal"),
+            None,
+            None,
+            Some((chrono::Utc::now().into(), Id::new_v4())),
+            10,
+        )
+        .await?;
+
+        let sql = db.into_transaction_log()[0].statements()[0].sql.clone();
+        assert!(sql.contains(r#"("event_store"."created_at", "event_store"."id") < ($2, $3)"#));
+        assert!(
+            sql.contains(r#"ORDER BY "event_store"."created_at" DESC, "event_store"."id" DESC"#)
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_after_continues_after_the_position_oldest_first() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<Model>::new()])
+            .into_connection();
+
+        let now = chrono::Utc::now();
+        find_after(&db, (now.into(), Id::new_v4()), now.into(), 500).await?;
+
+        let sql = db.into_transaction_log()[0].statements()[0].sql.clone();
+        assert!(sql.contains(r#"("event_store"."created_at", "event_store"."id") > ($1, $2)"#));
+        assert!(sql.contains(r#""event_store"."created_at" < $3"#));
+        assert!(sql.contains(r#"ORDER BY "event_store"."created_at" ASC, "event_store"."id" ASC"#));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn drop_partition_ignores_names_of_other_tables() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results([MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 0,
+            }])
+            .into_connection();
+
+        drop_partition(&db, "event_store_default").await?;
+        drop_partition(&db, "users; --").await?;
+        drop_partition(&db, "event_store_2024_01").await?;
+
+        let log = db.into_transaction_log();
+        assert_eq!(log.len(), 1);
+        assert_eq!(
+            log[0].statements()[0].sql,
+            "DROP TABLE IF EXISTS refactor_platform.event_store_2024_01"
+        );
+        Ok(())
+    }
+}
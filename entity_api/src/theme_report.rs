@@ -0,0 +1,55 @@
+use super::error::Error;
+use entity::theme_reports::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{entity::prelude::*, sea_query::OnConflict, ActiveValue::Set, ConnectionTrait};
+
+use log::*;
+
+pub async fn find_by_coaching_relationship_id(
+    db: &impl ConnectionTrait,
+    coaching_relationship_id: Id,
+) -> Result<Option<Model>, Error> {
+    Ok(Entity::find_by_id(coaching_relationship_id).one(db).await?)
+}
+
+/// Stores the generated report for a relationship, replacing any earlier one.
+pub async fn upsert(db: &impl ConnectionTrait, model: Model) -> Result<Model, Error> {
+    debug!(
+        "Storing themes report for coaching relationship {}",
+        model.coaching_relationship_id
+    );
+
+    let now = chrono::Utc::now();
+    let active_model = ActiveModel {
+        coaching_relationship_id: Set(model.coaching_relationship_id),
+        overview: Set(model.overview),
+        recurring_challenges: Set(model.recurring_challenges),
+        progress_arcs: Set(model.progress_arcs),
+        session_count: Set(model.session_count),
+        covered_through: Set(model.covered_through),
+        model: Set(model.model),
+        prompt_version: Set(model.prompt_version),
+        generated_at: Set(model.generated_at),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+    };
+
+    let on_conflict = OnConflict::column(Column::CoachingRelationshipId)
+        .update_columns([
+            Column::Overview,
+            Column::RecurringChallenges,
+            Column::ProgressArcs,
+            Column::SessionCount,
+            Column::CoveredThrough,
+            Column::Model,
+            Column::PromptVersion,
+            Column::GeneratedAt,
+            Column::UpdatedAt,
+        ])
+        .to_owned();
+
+    Ok(Entity::insert(active_model)
+        .on_conflict(on_conflict)
+        .exec_with_returning(db)
+        .await?)
+}
@@ -0,0 +1,45 @@
+use super::error::Error;
+use entity::transcript_chapter::{ActiveModel, Column, Entity, Model, Relation};
+use entity::Id;
+use log::debug;
+use sea_orm::{entity::prelude::*, DatabaseConnection, JoinType, Order, QueryOrder, QuerySelect};
+
+/// Inserts multiple transcript chapters in a single operation
+pub async fn create_batch(
+    db: &DatabaseConnection,
+    chapters: Vec<ActiveModel>,
+) -> Result<Vec<Model>, Error> {
+    debug!("Inserting {} transcript chapters", chapters.len());
+
+    Ok(Entity::insert_many(chapters)
+        .exec_with_returning_many(db)
+        .await?)
+}
+
+/// Returns all chapters for a transcription ordered by start time
+pub async fn find_by_transcription(
+    db: &DatabaseConnection,
+    transcription_id: Id,
+) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::TranscriptionId.eq(transcription_id))
+        .order_by(Column::StartMs, Order::Asc)
+        .all(db)
+        .await?)
+}
+
+/// Returns the chapters of the transcriptions made from a meeting recording, ordered by
+/// start time.
+pub async fn find_by_meeting_recording(
+    db: &DatabaseConnection,
+    meeting_recording_id: Id,
+) -> Result<Vec<Model>, Error> {
+    use entity::transcription::Column as TranscriptionColumn;
+
+    Ok(Entity::find()
+        .join(JoinType::InnerJoin, Relation::Transcriptions.def())
+        .filter(TranscriptionColumn::MeetingRecordingId.eq(meeting_recording_id))
+        .order_by(Column::StartMs, Order::Asc)
+        .all(db)
+        .await?)
+}
@@ -1,6 +1,7 @@
 use super::error::Error;
+use crate::query::contains_any_term;
 use entity::transcript_segment::{ActiveModel, Column, Entity, Model, Relation};
-use entity::Id;
+use entity::{transcription, Id};
 use log::debug;
 use sea_orm::{entity::prelude::*, DatabaseConnection, JoinType, Order, QueryOrder, QuerySelect};
 
@@ -49,6 +50,35 @@ pub async fn find_by_transcription(
         .await?)
 }
 
+/// Up to `limit` segments from the transcriptions of `coaching_session_ids` whose text
+/// mentions any of `terms`, each paired with its coaching session id.
+pub async fn search_in_sessions(
+    db: &DatabaseConnection,
+    coaching_session_ids: &[Id],
+    terms: &[String],
+    limit: u64,
+) -> Result<Vec<(Model, Id)>, Error> {
+    if coaching_session_ids.is_empty() || terms.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    Ok(Entity::find()
+        .find_also_related(transcription::Entity)
+        .filter(
+            transcription::Column::CoachingSessionId.is_in(coaching_session_ids.iter().copied()),
+        )
+        .filter(contains_any_term(Column::Text, terms))
+        .order_by(Column::CreatedAt, Order::Desc)
+        .limit(limit)
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|(segment, transcription)| {
+            transcription.map(|transcription| (segment, transcription.coaching_session_id))
+        })
+        .collect())
+}
+
 #[cfg(test)]
 #[cfg(feature = "mock")]
 mod tests {
@@ -1,5 +1,7 @@
 use super::error::{EntityApiErrorKind, Error};
-use entity::transcription::{ActiveModel, Column, Entity, Model, TranscriptionStatus};
+use entity::transcription::{
+    ActiveModel, Column, ConversationMetrics, Entity, Model, TranscriptionStatus,
+};
 use entity::Id;
 use log::debug;
 use sea_orm::{
@@ -132,6 +134,7 @@ pub async fn update_status(
         duration_seconds: Unchanged(existing.duration_seconds),
         confidence: Set(confidence.or(existing.confidence)),
         error_message: Set(error_message.or(existing.error_message)),
+        conversation_metrics: Unchanged(existing.conversation_metrics),
         created_at: Unchanged(existing.created_at),
         updated_at: Set(chrono::Utc::now().into()),
     };
@@ -139,6 +142,41 @@ pub async fn update_status(
     Ok(active_model.update(db).await?.try_into_model()?)
 }
 
+/// Stores the conversation metrics computed from a transcription's segments.
+pub async fn update_conversation_metrics(
+    db: &DatabaseConnection,
+    id: Id,
+    metrics: ConversationMetrics,
+) -> Result<Model, Error> {
+    let existing = Entity::find_by_id(id).one(db).await?.ok_or(Error {
+        source: None,
+        error_kind: EntityApiErrorKind::RecordNotFound,
+    })?;
+
+    debug!("Storing conversation metrics for transcription: {id}");
+
+    let active_model = ActiveModel {
+        conversation_metrics: Set(Some(metrics)),
+        updated_at: Set(chrono::Utc::now().into()),
+        ..existing.into_active_model()
+    };
+
+    Ok(active_model.update(db).await?.try_into_model()?)
+}
+
+/// Completed transcriptions of the given coaching sessions, newest first.
+pub async fn find_completed_by_coaching_sessions(
+    db: &DatabaseConnection,
+    session_ids: &[Id],
+) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::CoachingSessionId.is_in(session_ids.iter().copied()))
+        .filter(Column::Status.eq(TranscriptionStatus::Completed))
+        .order_by(Column::CreatedAt, Order::Desc)
+        .all(db)
+        .await?)
+}
+
 #[cfg(test)]
 #[cfg(feature = "mock")]
 mod tests {
@@ -160,6 +198,7 @@ mod tests {
             duration_seconds: None,
             confidence: None,
             error_message: None,
+            conversation_metrics: None,
             created_at: now.into(),
             updated_at: now.into(),
         }
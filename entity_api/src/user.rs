@@ -1,14 +1,16 @@
 use super::error::{EntityApiErrorKind, Error};
+use crate::query::contains_any_term;
 use async_trait::async_trait;
 use axum_login::{AuthnBackend, UserId};
 use chrono::Utc;
 
 use entity::users::{ActiveModel, Column, Entity, Model};
-use entity::{roles, user_roles, Id};
+use entity::{roles, user_locks, user_roles, Id};
 use log::*;
 use password_auth;
 use sea_orm::{
-    entity::prelude::*, Condition, ConnectionTrait, DatabaseConnection, Set, TransactionTrait,
+    entity::prelude::*, Condition, ConnectionTrait, DatabaseConnection, QueryOrder, QuerySelect,
+    QueryTrait, Set, TransactionTrait,
 };
 use serde::Deserialize;
 use std::sync::Arc;
@@ -92,6 +94,34 @@ pub async fn find_by_email(db: &impl ConnectionTrait, email: &str) -> Result<Opt
     }
 }
 
+/// Like [`find_by_email`], but a locked user (see `user_locks`) isn't found, so they
+/// can't log in.
+async fn find_unlocked_by_email(
+    db: &impl ConnectionTrait,
+    email: &str,
+) -> Result<Option<Model>, Error> {
+    let results = Entity::find()
+        .filter(Column::Email.eq(email))
+        .filter(
+            Column::Id.not_in_subquery(
+                user_locks::Entity::find()
+                    .select_only()
+                    .column(user_locks::Column::UserId)
+                    .into_query(),
+            ),
+        )
+        .find_with_related(user_roles::Entity)
+        .all(db)
+        .await?;
+    match results.into_iter().next() {
+        Some((mut user, roles)) => {
+            user.roles = roles;
+            Ok(Some(user))
+        }
+        None => Ok(None),
+    }
+}
+
 pub async fn find_by_id(db: &impl ConnectionTrait, id: Id) -> Result<Model, Error> {
     let results = Entity::find_by_id(id)
         .find_with_related(user_roles::Entity)
@@ -173,6 +203,19 @@ pub async fn has_admin_access(
     Ok(admin_role.is_some())
 }
 
+/// The admins of an organization (users with its `Admin` role), without their roles.
+pub async fn find_admins_of_organization(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .inner_join(user_roles::Entity)
+        .filter(user_roles::Column::Role.eq(Role::Admin))
+        .filter(user_roles::Column::OrganizationId.eq(organization_id))
+        .all(db)
+        .await?)
+}
+
 /// Finds all users matching the given IDs, including their associated roles.
 pub async fn find_by_ids(db: &impl ConnectionTrait, ids: &[Id]) -> Result<Vec<Model>, Error> {
     let results = Entity::find()
@@ -190,6 +233,75 @@ pub async fn find_by_ids(db: &impl ConnectionTrait, ids: &[Id]) -> Result<Vec<Mo
         .collect())
 }
 
+/// Users across all organizations, ordered by name, whose email, first, last or display
+/// name contains `query` (case-insensitively), optionally only the members of
+/// `organization_id`. Returns `limit` users from `offset` on, with their roles.
+pub async fn search(
+    db: &impl ConnectionTrait,
+    query: Option<&str>,
+    organization_id: Option<Id>,
+    limit: u64,
+    offset: u64,
+) -> Result<Vec<Model>, Error> {
+    let mut select = Entity::find();
+    if let Some(query) = query {
+        let terms = [query.to_string()];
+        select = select.filter(
+            Condition::any()
+                .add(contains_any_term(Column::Email, &terms))
+                .add(contains_any_term(Column::FirstName, &terms))
+                .add(contains_any_term(Column::LastName, &terms))
+                .add(contains_any_term(Column::DisplayName, &terms)),
+        );
+    }
+    if let Some(organization_id) = organization_id {
+        select = select.filter(
+            Column::Id.in_subquery(
+                user_roles::Entity::find()
+                    .select_only()
+                    .column(user_roles::Column::UserId)
+                    .filter(user_roles::Column::OrganizationId.eq(organization_id))
+                    .into_query(),
+            ),
+        );
+    }
+    let mut users = select
+        .order_by_asc(Column::LastName)
+        .order_by_asc(Column::FirstName)
+        .order_by_asc(Column::Id)
+        .limit(limit)
+        .offset(offset)
+        .all(db)
+        .await?;
+    if users.is_empty() {
+        return Ok(users);
+    }
+
+    let roles = user_roles::Entity::find()
+        .filter(user_roles::Column::UserId.is_in(users.iter().map(|user| user.id)))
+        .all(db)
+        .await?;
+    for user in &mut users {
+        user.roles = roles
+            .iter()
+            .filter(|role| role.user_id == user.id)
+            .cloned()
+            .collect();
+    }
+    Ok(users)
+}
+
+/// Marks the user as changed by bumping `updated_at`, e.g. after their roles changed, so
+/// clients comparing it see their copy is stale.
+pub async fn touch(db: &impl ConnectionTrait, user_id: Id) -> Result<(), Error> {
+    Entity::update_many()
+        .col_expr(Column::UpdatedAt, Expr::value(Utc::now()))
+        .filter(Column::Id.eq(user_id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
 pub async fn delete(db: &impl ConnectionTrait, user_id: Id) -> Result<(), Error> {
     Entity::delete_by_id(user_id).exec(db).await?;
     Ok(())
@@ -243,6 +355,9 @@ pub struct Credentials {
     pub email: String,
     pub password: String,
     pub next: Option<String>,
+    /// Keep the session for the "remember me" lifetime, bound to this browser
+    #[serde(default)]
+    pub remember_me: bool,
 }
 
 impl Backend {
@@ -265,7 +380,7 @@ impl AuthnBackend for Backend {
         &self,
         creds: Self::Credentials,
     ) -> Result<Option<Self::User>, Self::Error> {
-        match find_by_email(self.db.as_ref(), &creds.email).await? {
+        match find_unlocked_by_email(self.db.as_ref(), &creds.email).await? {
             Some(user) => authenticate_user(creds, user).await,
             None => Err(Error {
                 source: None,
@@ -670,6 +785,7 @@ mod test {
             email: "test@test.com".to_string(),
             password: "any_password".to_string(),
             next: None,
+            remember_me: false,
         };
 
         let result = backend.authenticate(creds).await;
@@ -680,6 +796,49 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn find_unlocked_by_email_excludes_locked_users() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
+
+        let _ = find_unlocked_by_email(&db, "test@test.com").await;
+
+        assert_eq!(
+            db.into_transaction_log(),
+            [Transaction::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"SELECT "users"."id" AS "A_id", "users"."email" AS "A_email", "users"."first_name" AS "A_first_name", "users"."last_name" AS "A_last_name", "users"."display_name" AS "A_display_name", "users"."password" AS "A_password", "users"."github_username" AS "A_github_username", "users"."github_profile_url" AS "A_github_profile_url", "users"."timezone" AS "A_timezone", "users"."default_coaching_session_duration_minutes" AS "A_default_coaching_session_duration_minutes", CAST("users"."role" AS "text") AS "A_role", "users"."created_at" AS "A_created_at", "users"."updated_at" AS "A_updated_at", "user_roles"."id" AS "B_id", CAST("user_roles"."role" AS "text") AS "B_role", "user_roles"."organization_id" AS "B_organization_id", "user_roles"."user_id" AS "B_user_id", "user_roles"."created_at" AS "B_created_at", "user_roles"."updated_at" AS "B_updated_at" FROM "refactor_platform"."users" LEFT JOIN "refactor_platform"."user_roles" ON "users"."id" = "user_roles"."user_id" WHERE "users"."email" = $1 AND "users"."id" NOT IN (SELECT "user_locks"."user_id" FROM "refactor_platform"."user_locks") ORDER BY "users"."id" ASC"#,
+                ["test@test.com".into()]
+            )]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn search_matches_names_and_emails_within_an_organization() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<Model>::new()])
+            .into_connection();
+
+        let users = search(&db, Some("ada"), Some(Id::new_v4()), 50, 100).await?;
+
+        assert!(users.is_empty());
+        let log = db.into_transaction_log();
+        assert_eq!(log.len(), 1, "no roles are loaded for an empty page");
+        let sql = &log[0].statements()[0].sql;
+        for column in ["email", "first_name", "last_name", "display_name"] {
+            assert!(sql.contains(&format!(r#""users"."{column}" ILIKE"#)));
+        }
+        assert!(sql.contains(
+            r#""users"."id" IN (SELECT "user_roles"."user_id" FROM "refactor_platform"."user_roles" WHERE "user_roles"."organization_id" = "#
+        ));
+        assert!(sql.contains(
+            r#"ORDER BY "users"."last_name" ASC, "users"."first_name" ASC, "users"."id" ASC LIMIT "#
+        ));
+        assert!(sql.contains("OFFSET"));
+        Ok(())
+    }
+
     #[tokio::test]
     async fn has_admin_access_with_admin_role_for_multiple_organizations() -> Result<(), Error> {
         let user_id = Id::new_v4();
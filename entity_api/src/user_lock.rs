@@ -0,0 +1,42 @@
+use super::error::Error;
+use chrono::Utc;
+use entity::user_locks::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{entity::prelude::*, ActiveValue::Set, ConnectionTrait};
+
+/// Locks the user's account.
+pub async fn create(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+    organization_id: Id,
+    locked_by_id: Id,
+    reason: Option<String>,
+) -> Result<Model, Error> {
+    let active_model = ActiveModel {
+        user_id: Set(user_id),
+        organization_id: Set(organization_id),
+        locked_by_id: Set(Some(locked_by_id)),
+        reason: Set(reason),
+        created_at: Set(Utc::now().into()),
+        ..Default::default()
+    };
+
+    Ok(active_model.insert(db).await?)
+}
+
+/// The user's lock, if their account is locked.
+pub async fn find_by_user(db: &impl ConnectionTrait, user_id: Id) -> Result<Option<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::UserId.eq(user_id))
+        .one(db)
+        .await?)
+}
+
+/// Unlocks the user's account, returning how many locks were deleted (0 or 1).
+pub async fn delete_by_user(db: &impl ConnectionTrait, user_id: Id) -> Result<u64, Error> {
+    Ok(Entity::delete_many()
+        .filter(Column::UserId.eq(user_id))
+        .exec(db)
+        .await?
+        .rows_affected)
+}
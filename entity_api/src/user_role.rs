@@ -1,7 +1,12 @@
 use super::error::Error;
-use entity::user_roles::{Column, Entity};
+use chrono::Utc;
+use entity::roles::Role;
+use entity::user_roles::{ActiveModel, Column, Entity};
 use entity::Id;
-use sea_orm::{ColumnTrait, Condition, ConnectionTrait, EntityTrait, QueryFilter};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, Condition, ConnectionTrait, EntityTrait,
+    QueryFilter,
+};
 
 pub async fn delete_by_user_id(db: &impl ConnectionTrait, user_id: Id) -> Result<(), Error> {
     Entity::delete_many()
@@ -11,6 +16,65 @@ pub async fn delete_by_user_id(db: &impl ConnectionTrait, user_id: Id) -> Result
     Ok(())
 }
 
+/// Gives the user `role` in the organization, unless they already have it.
+pub async fn add(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+    organization_id: Id,
+    role: Role,
+) -> Result<(), Error> {
+    let existing = Entity::find()
+        .filter(Column::UserId.eq(user_id))
+        .filter(Column::OrganizationId.eq(organization_id))
+        .filter(Column::Role.eq(role.clone()))
+        .one(db)
+        .await?;
+    if existing.is_none() {
+        let now = Utc::now();
+        ActiveModel {
+            user_id: Set(user_id),
+            organization_id: Set(Some(organization_id)),
+            role: Set(role),
+            created_at: Set(now.into()),
+            updated_at: Set(now.into()),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Takes `role` in the organization away from the user.
+pub async fn remove(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+    organization_id: Id,
+    role: Role,
+) -> Result<(), Error> {
+    Entity::delete_many()
+        .filter(Column::UserId.eq(user_id))
+        .filter(Column::OrganizationId.eq(organization_id))
+        .filter(Column::Role.eq(role))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+/// Takes every role in the organization away from the user.
+pub async fn delete_by_user_and_organization(
+    db: &impl ConnectionTrait,
+    user_id: Id,
+    organization_id: Id,
+) -> Result<(), Error> {
+    Entity::delete_many()
+        .filter(Column::UserId.eq(user_id))
+        .filter(Column::OrganizationId.eq(organization_id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
 #[cfg(test)]
 #[cfg(feature = "mock")]
 mod test {
@@ -0,0 +1,176 @@
+//! How far each stream of domain events exported to the analytics warehouse has got.
+
+use super::error::Error;
+use entity::warehouse_checkpoints::{Column, Entity, Model};
+use entity::Id;
+use sea_orm::{
+    entity::prelude::*, sea_query::LockBehavior, sea_query::LockType, ConnectionTrait, DbBackend,
+    QueryOrder, QuerySelect, Statement,
+};
+
+/// Every stream's checkpoint, by stream.
+pub async fn find_all(db: &impl ConnectionTrait) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find().order_by_asc(Column::Stream).all(db).await?)
+}
+
+pub async fn find(db: &impl ConnectionTrait, stream: &str) -> Result<Option<Model>, Error> {
+    Ok(Entity::find_by_id(stream).one(db).await?)
+}
+
+/// Starts `stream` over from `position_at` up to `until_at`, clearing its counts, error
+/// and completion.
+pub async fn start(
+    db: &impl ConnectionTrait,
+    stream: &str,
+    position_at: DateTimeWithTimeZone,
+    until_at: DateTimeWithTimeZone,
+) -> Result<Model, Error> {
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        r#"INSERT INTO refactor_platform.warehouse_checkpoints
+               (stream, position_at, position_id, until_at)
+           VALUES ($1, $2, $3, $4)
+           ON CONFLICT (stream) DO UPDATE
+           SET position_at = EXCLUDED.position_at,
+               position_id = EXCLUDED.position_id,
+               until_at = EXCLUDED.until_at,
+               events_exported = 0,
+               batches_exported = 0,
+               last_object_key = NULL,
+               error_message = NULL,
+               completed_at = NULL,
+               updated_at = NOW()
+           RETURNING *"#,
+        [
+            stream.into(),
+            position_at.into(),
+            // Sorts before every other ID, so events created at `position_at` are included
+            Id::nil().into(),
+            until_at.into(),
+        ],
+    );
+
+    Entity::find()
+        .from_raw_sql(stmt)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotInserted.into())
+}
+
+/// Locks `stream`'s checkpoint unless it's complete or another process holds it, so only
+/// one process exports the stream at a time. Must run in a transaction.
+pub async fn claim(db: &impl ConnectionTrait, stream: &str) -> Result<Option<Model>, Error> {
+    Ok(Entity::find_by_id(stream)
+        .filter(Column::CompletedAt.is_null())
+        .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+        .one(db)
+        .await?)
+}
+
+/// Adds a batch of `events` written to `object_key` to `stream`'s counts, moving its
+/// position to the last exported stored event when given, and clears its error.
+pub async fn record_batch(
+    db: &impl ConnectionTrait,
+    stream: &str,
+    events: i64,
+    object_key: &str,
+    position: Option<(DateTimeWithTimeZone, Id)>,
+) -> Result<(), Error> {
+    let (position_at, position_id) = position.unzip();
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        r#"INSERT INTO refactor_platform.warehouse_checkpoints
+               (stream, position_at, position_id, events_exported, batches_exported, last_object_key)
+           VALUES ($1, $2, $3, $4, 1, $5)
+           ON CONFLICT (stream) DO UPDATE
+           SET position_at = COALESCE(EXCLUDED.position_at, warehouse_checkpoints.position_at),
+               position_id = COALESCE(EXCLUDED.position_id, warehouse_checkpoints.position_id),
+               events_exported = warehouse_checkpoints.events_exported + EXCLUDED.events_exported,
+               batches_exported = warehouse_checkpoints.batches_exported + 1,
+               last_object_key = EXCLUDED.last_object_key,
+               error_message = NULL,
+               updated_at = NOW()"#,
+        [
+            stream.into(),
+            position_at.into(),
+            position_id.into(),
+            events.into(),
+            object_key.into(),
+        ],
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Notes why `stream`'s last export failed.
+pub async fn record_error(
+    db: &impl ConnectionTrait,
+    stream: &str,
+    message: &str,
+) -> Result<(), Error> {
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        r#"INSERT INTO refactor_platform.warehouse_checkpoints (stream, error_message)
+           VALUES ($1, $2)
+           ON CONFLICT (stream) DO UPDATE
+           SET error_message = EXCLUDED.error_message,
+               updated_at = NOW()"#,
+        [stream.into(), message.into()],
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Marks `stream` as having exported everything up to its `until_at`.
+pub async fn complete(db: &impl ConnectionTrait, stream: &str) -> Result<(), Error> {
+    Entity::update_many()
+        .col_expr(Column::CompletedAt, Expr::current_timestamp().into())
+        .col_expr(Column::UpdatedAt, Expr::current_timestamp().into())
+        .filter(Column::Stream.eq(stream))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+// We need to gate seaORM's mock feature behind conditional compilation because
+// the feature removes the Clone trait implementation from seaORM's DatabaseConnection.
+// see https://github.com/SeaQL/sea-orm/issues/830
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+
+    #[tokio::test]
+    async fn claim_skips_a_checkpoint_another_process_holds() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<Model>::new()])
+            .into_connection();
+
+        assert!(claim(&db, "backfill").await?.is_none());
+
+        let sql = db.into_transaction_log()[0].statements()[0].sql.clone();
+        assert!(sql.contains(r#""warehouse_checkpoints"."completed_at" IS NULL"#));
+        assert!(sql.ends_with("FOR UPDATE SKIP LOCKED"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn record_batch_keeps_the_position_when_none_is_given() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results([MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            }])
+            .into_connection();
+
+        record_batch(&db, "live", 500, "warehouse/key.ndjson", None).await?;
+
+        let sql = db.into_transaction_log()[0].statements()[0].sql.clone();
+        assert!(sql.contains(
+            "position_at = COALESCE(EXCLUDED.position_at, warehouse_checkpoints.position_at)"
+        ));
+        assert!(sql.contains("batches_exported = warehouse_checkpoints.batches_exported + 1"));
+        Ok(())
+    }
+}
@@ -0,0 +1,185 @@
+use super::error::{EntityApiErrorKind, Error};
+use entity::webhook_subscriptions::{ActiveModel, Column, Entity, Model};
+use entity::Id;
+use sea_orm::{
+    entity::prelude::*,
+    sea_query::Expr,
+    ActiveValue::{Set, Unchanged},
+    ConnectionTrait, QueryOrder, TryIntoModel,
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use log::*;
+
+/// A newly created subscription with its signing secret, which isn't returned again.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[schema(as = domain::webhook_subscription::CreatedWebhookSubscription)]
+pub struct CreatedWebhookSubscription {
+    #[serde(flatten)]
+    pub subscription: Model,
+    /// Key the payloads are signed with. Store it now: it can't be read back.
+    pub secret: String,
+}
+
+/// Inserts a subscription for `organization_id` signed with `secret`.
+pub async fn create(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+    created_by_user_id: Id,
+    secret: String,
+    model: Model,
+) -> Result<Model, Error> {
+    debug!(
+        "New webhook subscription for organization {organization_id} to {}",
+        model.url
+    );
+
+    let now = chrono::Utc::now();
+    let active_model = ActiveModel {
+        organization_id: Set(organization_id),
+        url: Set(model.url),
+        secret: Set(secret),
+        event_types: Set(model.event_types),
+        enabled: Set(model.enabled),
+        created_by_user_id: Set(Some(created_by_user_id)),
+        created_at: Set(now.into()),
+        updated_at: Set(now.into()),
+        ..Default::default()
+    };
+
+    Ok(active_model.insert(db).await?.try_into_model()?)
+}
+
+/// Replaces a subscription's URL, event types and enabled flag, keeping its secret.
+pub async fn update(
+    db: &impl ConnectionTrait,
+    existing: Model,
+    model: Model,
+) -> Result<Model, Error> {
+    let active_model = ActiveModel {
+        id: Unchanged(existing.id),
+        organization_id: Unchanged(existing.organization_id),
+        url: Set(model.url),
+        secret: Unchanged(existing.secret),
+        event_types: Set(model.event_types),
+        enabled: Set(model.enabled),
+        created_by_user_id: Unchanged(existing.created_by_user_id),
+        created_at: Unchanged(existing.created_at),
+        updated_at: Set(chrono::Utc::now().into()),
+    };
+
+    Ok(active_model.update(db).await?.try_into_model()?)
+}
+
+pub async fn delete_by_id(db: &impl ConnectionTrait, id: Id) -> Result<(), Error> {
+    Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
+
+pub async fn find_by_id(db: &impl ConnectionTrait, id: Id) -> Result<Model, Error> {
+    Entity::find_by_id(id).one(db).await?.ok_or_else(|| {
+        error!("Webhook subscription with id {id} not found");
+
+        Error {
+            source: None,
+            error_kind: EntityApiErrorKind::RecordNotFound,
+        }
+    })
+}
+
+/// All of an organization's subscriptions, oldest first.
+pub async fn find_by_organization(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::OrganizationId.eq(organization_id))
+        .order_by_asc(Column::CreatedAt)
+        .all(db)
+        .await?)
+}
+
+/// The organization's enabled subscriptions to events of `event_type`.
+pub async fn find_enabled_for_event(
+    db: &impl ConnectionTrait,
+    organization_id: Id,
+    event_type: &str,
+) -> Result<Vec<Model>, Error> {
+    Ok(Entity::find()
+        .filter(Column::OrganizationId.eq(organization_id))
+        .filter(Column::Enabled.eq(true))
+        .filter(Expr::cust_with_values(
+            "event_types @> $1::jsonb",
+            [serde_json::json!([event_type])],
+        ))
+        .all(db)
+        .await?)
+}
+
+#[cfg(test)]
+// We need to gate seaORM's mock feature behind conditional compilation because
+// the feature removes the Clone trait implementation from seaORM's DatabaseConnection.
+// see https://github.com/SeaQL/sea-orm/issues/830
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use entity::webhook_subscriptions::EventTypes;
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    fn subscription() -> Model {
+        let now = chrono::Utc::now();
+        Model {
+            id: Id::new_v4(),
+            organization_id: Id::new_v4(),
+            url: "https://hooks.example.com/refactor".to_string(),
+            secret: "secret".to_string(),
+            event_types: EventTypes(vec!["goal_created".to_string()]),
+            enabled: true,
+            created_by_user_id: Some(Id::new_v4()),
+            created_at: now.into(),
+            updated_at: now.into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn update_keeps_the_secret() -> Result<(), Error> {
+        let existing = subscription();
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![existing.clone()]])
+            .into_connection();
+
+        update(
+            &db,
+            existing.clone(),
+            Model {
+                secret: String::new(),
+                ..existing
+            },
+        )
+        .await?;
+
+        let log = db.into_transaction_log();
+        let sql = &log[0].statements()[0].sql;
+        let set_clause = &sql[sql.find(" SET ").unwrap()..sql.find(" WHERE ").unwrap()];
+        assert!(!set_clause.contains("secret"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn find_enabled_for_event_matches_the_event_type() -> Result<(), Error> {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<Model>::new()])
+            .into_connection();
+
+        find_enabled_for_event(&db, Id::new_v4(), "goal_created").await?;
+
+        let log = db.into_transaction_log();
+        let statement = &log[0].statements()[0];
+        assert!(statement
+            .sql
+            .contains(r#""webhook_subscriptions"."enabled" = $2"#));
+        assert!(statement.sql.contains("event_types @> $3::jsonb"));
+        Ok(())
+    }
+}
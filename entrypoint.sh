@@ -136,6 +136,8 @@ main() {
             # idempotent — running it twice on the same DB will fail with a
             # unique-constraint violation on user emails. Callers must only
             # invoke the seed_db role on a freshly created (empty) database.
+            # With RUNTIME_ENV=production it refuses to seed demo data; the
+            # bootstrap admin is created by running seed_db --force-seed by hand.
             exec /app/seed_db
             ;;
 
@@ -168,8 +170,26 @@ main() {
                 "$@"
             ;;
             
+        events_worker)
+            log_info "Running in EVENTS_WORKER mode"
+            validate_binary "events-worker"
+            validate_env "DATABASE_URL"
+            validate_env "RUST_ENV"
+
+            log_info "Running in $RUST_ENV environment"
+
+            local rust_env="${RUST_ENV:-development}"
+            local log_level="${BACKEND_LOG_FILTER_LEVEL:-INFO}"
+
+            log_info "Starting Refactor Platform events worker..."
+            exec /app/events-worker \
+                -r "$rust_env" \
+                -l "$log_level" \
+                "$@"
+            ;;
+
         *)
-            log_error "Unknown ROLE: '$ROLE'. Valid roles are: migrator, seed_db, app"
+            log_error "Unknown ROLE: '$ROLE'. Valid roles are: migrator, seed_db, app, events_worker"
             log_error "Set ROLE environment variable to one of the valid values"
             exit 1
             ;;
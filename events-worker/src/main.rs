@@ -0,0 +1,159 @@
+//! **Events worker**
+//!
+//! Dispatches the domain events the web process stores in its outbox (see
+//! `domain::outbox`) to the handlers that don't need to run in the web process, e.g. the
+//! audit log. Run the web process with `EVENTS_WORKER_ENABLED=true` and one or more of
+//! these alongside it; workers claim events with a lease, so they scale horizontally.
+
+use events::{BusPublisher, EventPublisher};
+use log::*;
+use service::{config::Config, logging::Logger};
+use std::process;
+use std::sync::Arc;
+
+/// How often dispatched events older than [`RETENTION_DAYS`] are deleted.
+const PRUNE_INTERVAL: tokio::time::Duration = tokio::time::Duration::from_secs(60 * 60);
+
+/// Days dispatched events are kept in the outbox, for debugging.
+const RETENTION_DAYS: i64 = 7;
+
+#[tokio::main]
+async fn main() {
+    service::load_env_file();
+    let config = Config::new();
+    Logger::init_logger(&config);
+
+    let db_conn = match service::init_database(&config).await {
+        Ok(db) => Arc::new(db),
+        Err(e) => {
+            error!("Failed to establish database connection: {e}");
+            process::exit(1);
+        }
+    };
+
+    // Handlers read notes, which may be encrypted.
+    if let Err(e) = domain::encryption::install(&config) {
+        error!("[events-worker] ENCRYPTION_KEY is set but invalid: {e}");
+        process::exit(1);
+    }
+
+    // No outbox here: this publisher runs every handler it has.
+    let mut event_publisher = domain::outbox::register_handlers(EventPublisher::new(), &db_conn)
+        .with_dead_letters(Arc::new(domain::dead_letter::DeadLetterStore::new(
+            Arc::clone(&db_conn),
+        )));
+    // This process holds no SSE connections, so every recipient is pushed to.
+    match domain::push::Vapid::from_config(&config) {
+        Ok(Some(vapid)) => {
+            event_publisher = event_publisher.with_handler_for(
+                domain::push::PushNotificationHandler::KINDS,
+                Arc::new(domain::push::PushNotificationHandler::new(
+                    Arc::clone(&db_conn),
+                    vapid,
+                )),
+            );
+        }
+        Ok(None) => {}
+        Err(e) => {
+            error!("[events-worker] VAPID keys are set but invalid: {e}");
+            process::exit(1);
+        }
+    }
+
+    let warehouse_sink =
+        match domain::warehouse::WarehouseSink::from_config(&config, Arc::clone(&db_conn)) {
+            Ok(Some(sink)) => {
+                let sink = Arc::new(sink);
+                sink.spawn_flusher(config.warehouse_export_flush_interval());
+                event_publisher = event_publisher.with_handler(Arc::clone(&sink) as _);
+                Some(sink)
+            }
+            Ok(None) => None,
+            Err(e) => {
+                error!("[events-worker] failed to set up the warehouse export: {e}");
+                process::exit(1);
+            }
+        };
+
+    if let Some(url) = config.event_bus_url() {
+        match BusPublisher::new(&url, config.event_bus_topic()) {
+            Ok(bus) => event_publisher = event_publisher.with_handler(Arc::new(bus)),
+            Err(e) => {
+                error!("[events-worker] EVENT_BUS_URL is set but invalid: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    let worker_id = format!(
+        "{}-{}",
+        std::env::var("HOSTNAME").unwrap_or_else(|_| "events-worker".to_string()),
+        process::id()
+    );
+    let poll_interval = config.events_worker_poll_interval();
+    let batch_size = config.events_worker_batch_size();
+    let lease = config.events_worker_lease();
+
+    info!(
+        "[events-worker] {worker_id} polling every {poll_interval:?}, \
+         {batch_size} events at a time"
+    );
+
+    let mut last_pruned = tokio::time::Instant::now();
+    loop {
+        let dispatched = match domain::outbox::dispatch_pending(
+            &db_conn,
+            &event_publisher,
+            &worker_id,
+            batch_size,
+            lease,
+        )
+        .await
+        {
+            Ok(dispatched) => {
+                if dispatched > 0 {
+                    debug!("[events-worker] dispatched {dispatched} event(s)");
+                }
+                dispatched
+            }
+            Err(e) => {
+                warn!("[events-worker] dispatch iteration failed: {e:?}");
+                0
+            }
+        };
+
+        if last_pruned.elapsed() >= PRUNE_INTERVAL {
+            last_pruned = tokio::time::Instant::now();
+            match domain::outbox::delete_dispatched(&db_conn, RETENTION_DAYS).await {
+                Ok(deleted) if deleted > 0 => {
+                    info!(
+                        "[events-worker] removed {deleted} event(s) dispatched more than \
+                         {RETENTION_DAYS}d ago"
+                    );
+                }
+                Ok(_) => {}
+                Err(e) => warn!("[events-worker] prune failed: {e:?}"),
+            }
+        }
+
+        // A full batch means more are likely waiting; poll again straight away.
+        if dispatched as u64 >= batch_size {
+            continue;
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(poll_interval) => {}
+            _ = tokio::signal::ctrl_c() => {
+                info!("[events-worker] {worker_id} shutting down");
+                break;
+            }
+        }
+    }
+
+    // Export the events still waiting for a batch before exiting
+    if let Some(sink) = warehouse_sink {
+        if let Err(e) = sink.flush().await {
+            warn!("[events-worker] failed to export the remaining events to the warehouse: {e:?}");
+        }
+    }
+}
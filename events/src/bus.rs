@@ -0,0 +1,180 @@
+//! Publishing domain events to a message bus, Kafka or NATS, for other systems to consume.
+//!
+//! [`BusPublisher`] is an [`EventHandler`] that publishes every event it's given, in its
+//! [`EventEnvelope`](crate::EventEnvelope), keyed by the event's coaching relationship so
+//! a relationship's events stay in order:
+//!
+//! - On Kafka (`kafka::KafkaProducer`, with `rdkafka`), to one topic, in the partition the
+//!   relationship ID hashes to with the Java client's default partitioner, so consumers in
+//!   other languages agree on it. Events without a relationship go to a random partition.
+//!   Each record has an `event_type` header.
+//! - On NATS (`nats::NatsPublisher`, with `async-nats`), to the subject
+//!   `{topic}.{coaching_relationship_id}.{event_type}` (`none` for events without a
+//!   relationship), so consumers subscribe to `{topic}.>`, one relationship's
+//!   `{topic}.{id}.*` or one type's `{topic}.*.{event_type}`.
+//!
+//! # Delivery
+//!
+//! An event is published once the handler returns `Ok`: Kafka has written it to every
+//! in-sync replica (`acks=all`), or the NATS server has received it. Failures are retried
+//! like any handler's and then dead-lettered, so run with the events worker, whose outbox
+//! survives restarts, and events are delivered at least once: a retry after a lost reply,
+//! or a requeued dead letter, publishes an event again, and a requeued one out of order.
+//! Consumers should tolerate duplicates. Core NATS delivers to the subscribers connected
+//! at the time only; capture the subjects in a JetStream stream to keep events for
+//! consumers that connect later (the publisher doesn't wait for JetStream's ack).
+//!
+//! # Features
+//!
+//! Each client is built only with its feature, `kafka` or `nats`; `kafka` builds
+//! librdkafka from source. A URL for a bus whose feature wasn't built is refused.
+
+use async_trait::async_trait;
+use std::fmt;
+use uuid::Uuid;
+
+use crate::{envelope, DomainEvent, EventHandler, HandlerError};
+
+#[cfg(feature = "kafka")]
+pub mod kafka;
+#[cfg(feature = "nats")]
+pub mod nats;
+
+/// An event as published on the bus.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    /// The event's kind, e.g. `goal_created`.
+    pub event_type: &'static str,
+    /// The coaching relationship the event belongs to, which orders it.
+    pub key: Option<Uuid>,
+    /// The event's envelope as JSON.
+    pub payload: Vec<u8>,
+}
+
+impl Message {
+    pub fn new(event: &DomainEvent) -> Result<Self, HandlerError> {
+        Ok(Self {
+            event_type: event.kind().as_str(),
+            key: event.coaching_relationship_id(),
+            payload: envelope::to_vec(event).map_err(|e| HandlerError::Permanent(e.to_string()))?,
+        })
+    }
+}
+
+/// Why a bus URL couldn't be used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BusError(pub String);
+
+impl fmt::Display for BusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for BusError {}
+
+/// Publishes messages to one kind of bus.
+#[async_trait]
+trait Client: Send + Sync {
+    async fn publish(&self, message: &Message) -> Result<(), HandlerError>;
+}
+
+/// Publishes every event it handles to Kafka or NATS.
+pub struct BusPublisher {
+    client: Box<dyn Client>,
+}
+
+impl BusPublisher {
+    /// A publisher to the bus at `url`, `kafka://host:port[,host:port...]` or
+    /// `nats://[[user:password|token]@]host[:port]`, publishing to `topic`. Connects to
+    /// NATS on first use; Kafka's brokers are connected to in the background.
+    #[cfg_attr(not(any(feature = "kafka", feature = "nats")), allow(unused_variables))]
+    pub fn new(url: &str, topic: &str) -> Result<Self, BusError> {
+        let client: Result<Box<dyn Client>, BusError> = match url.split_once("://") {
+            #[cfg(feature = "kafka")]
+            Some(("kafka", brokers)) => {
+                kafka::KafkaProducer::new(brokers, topic).map(|p| Box::new(p) as _)
+            }
+            #[cfg(feature = "nats")]
+            Some(("nats", server)) => {
+                nats::NatsPublisher::new(server, topic).map(|p| Box::new(p) as _)
+            }
+            #[cfg(not(feature = "kafka"))]
+            Some(("kafka", _)) => Err(missing_feature("kafka")),
+            #[cfg(not(feature = "nats"))]
+            Some(("nats", _)) => Err(missing_feature("nats")),
+            _ => Err(BusError(format!(
+                "`{url}` is neither a kafka:// nor a nats:// URL"
+            ))),
+        };
+        Ok(Self { client: client? })
+    }
+}
+
+#[async_trait]
+impl EventHandler for BusPublisher {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), HandlerError> {
+        self.client.publish(&Message::new(event)?).await
+    }
+
+    fn name(&self) -> &str {
+        "BusPublisher"
+    }
+}
+
+#[cfg(not(all(feature = "kafka", feature = "nats")))]
+fn missing_feature(scheme: &str) -> BusError {
+    BusError(format!(
+        "Publishing to {scheme}:// needs a build with the `{scheme}` feature"
+    ))
+}
+
+/// `host:port` of `host`, with `default_port` when it names none.
+#[cfg(any(feature = "kafka", feature = "nats"))]
+fn address(host: &str, default_port: u16) -> Result<String, BusError> {
+    if host.is_empty() {
+        return Err(BusError("The bus URL names no host".to_string()));
+    }
+    match host.rsplit_once(':') {
+        Some((_, port)) if port.parse::<u16>().is_err() => {
+            Err(BusError(format!("`{port}` is not a valid port")))
+        }
+        Some(_) => Ok(host.to_string()),
+        None => Ok(format!("{host}:{default_port}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_picks_the_client_by_the_url_scheme() {
+        assert_eq!(
+            BusPublisher::new("kafka://kafka-1:9092,kafka-2:9092", "events").is_ok(),
+            cfg!(feature = "kafka")
+        );
+        assert_eq!(
+            BusPublisher::new("nats://token@nats", "events").is_ok(),
+            cfg!(feature = "nats")
+        );
+        assert!(BusPublisher::new("tls://nats:4222", "events").is_err());
+        assert!(BusPublisher::new("kafka://", "events").is_err());
+        assert!(BusPublisher::new("nats://nats:port", "events").is_err());
+    }
+
+    #[test]
+    fn message_is_keyed_by_the_events_relationship() {
+        let relationship_id = Uuid::new_v4();
+        let message = Message::new(&DomainEvent::GoalDeleted {
+            coaching_relationship_id: relationship_id,
+            goal_id: Uuid::nil(),
+        })
+        .unwrap();
+
+        assert_eq!(message.event_type, "goal_deleted");
+        assert_eq!(message.key, Some(relationship_id));
+        let envelope = envelope::parse_slice(&message.payload).unwrap();
+        assert_eq!(envelope.event_type, "goal_deleted");
+    }
+}
@@ -0,0 +1,147 @@
+//! Publishing to Kafka, with librdkafka through `rdkafka`.
+//!
+//! Records are partitioned with librdkafka's `murmur2_random` partitioner, the Java
+//! client's default, so a relationship's events land where consumers in other languages
+//! expect them; events without a relationship go to a random partition. Plain TCP and
+//! without SASL: the `ssl` and `sasl` features of `rdkafka` aren't enabled.
+
+use async_trait::async_trait;
+use rdkafka::config::ClientConfig;
+use rdkafka::error::{KafkaError, RDKafkaErrorCode};
+use rdkafka::message::{Header, OwnedHeaders};
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use std::time::Duration;
+
+use super::{BusError, Client, Message};
+use crate::HandlerError;
+
+/// Port used when a broker's address doesn't name one.
+const DEFAULT_PORT: u16 = 9092;
+
+/// How long a publish may take, including the brokers replicating the record.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a record may wait for room in librdkafka's queue.
+const QUEUE_TIMEOUT: Duration = Duration::from_secs(5);
+
+const CLIENT_ID: &str = "refactor-platform";
+
+/// Errors that retrying won't fix.
+const PERMANENT_ERRORS: [RDKafkaErrorCode; 3] = [
+    RDKafkaErrorCode::MessageSizeTooLarge,
+    RDKafkaErrorCode::InvalidMessageSize,
+    RDKafkaErrorCode::TopicAuthorizationFailed,
+];
+
+/// Publishes events to the partitions of one Kafka topic.
+pub struct KafkaProducer {
+    producer: FutureProducer,
+    topic: String,
+}
+
+impl KafkaProducer {
+    /// A producer to the cluster reachable through `brokers`, `host:port[,host:port...]`,
+    /// publishing to `topic`.
+    pub fn new(brokers: &str, topic: &str) -> Result<Self, BusError> {
+        let bootstrap = bootstrap_servers(brokers)?;
+        let valid_topic = !topic.is_empty()
+            && topic.len() <= 249
+            && topic
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b'-'));
+        if !valid_topic {
+            return Err(BusError(format!("`{topic}` is not a valid Kafka topic")));
+        }
+
+        let producer = ClientConfig::new()
+            .set("bootstrap.servers", bootstrap.join(","))
+            .set("client.id", CLIENT_ID)
+            .set("acks", "all")
+            .set("partitioner", "murmur2_random")
+            .set(
+                "message.timeout.ms",
+                DELIVERY_TIMEOUT.as_millis().to_string(),
+            )
+            .create()
+            .map_err(|e| BusError(format!("Creating the Kafka producer failed: {e}")))?;
+        Ok(Self {
+            producer,
+            topic: topic.to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl Client for KafkaProducer {
+    async fn publish(&self, message: &Message) -> Result<(), HandlerError> {
+        let key = message.key.map(|id| id.to_string());
+        let headers = OwnedHeaders::new().insert(Header {
+            key: "event_type",
+            value: Some(message.event_type),
+        });
+        let mut record = FutureRecord::<str, [u8]>::to(&self.topic)
+            .payload(&message.payload)
+            .headers(headers);
+        if let Some(key) = &key {
+            record = record.key(key);
+        }
+
+        self.producer
+            .send(record, QUEUE_TIMEOUT)
+            .await
+            .map(|_| ())
+            .map_err(|(e, _)| handler_error(e))
+    }
+}
+
+/// `host:port` of each of the bootstrap `brokers`.
+fn bootstrap_servers(brokers: &str) -> Result<Vec<String>, BusError> {
+    brokers
+        .trim_end_matches('/')
+        .split(',')
+        .map(|broker| super::address(broker.trim(), DEFAULT_PORT))
+        .collect()
+}
+
+fn handler_error(error: KafkaError) -> HandlerError {
+    let message = format!("Kafka refused the event: {error}");
+    match error.rdkafka_error_code() {
+        Some(code) if PERMANENT_ERRORS.contains(&code) => HandlerError::Permanent(message),
+        _ => HandlerError::Transient(message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_reads_the_bootstrap_brokers_from_the_url() {
+        assert_eq!(
+            bootstrap_servers("kafka-1:9093, kafka-2").unwrap(),
+            ["kafka-1:9093", "kafka-2:9092"]
+        );
+        assert!(KafkaProducer::new("kafka-1:9093, kafka-2", "refactor_platform.events").is_ok());
+
+        assert!(KafkaProducer::new("kafka", "events/all").is_err());
+        assert!(KafkaProducer::new("kafka,", "events").is_err());
+    }
+
+    #[test]
+    fn only_refusals_retrying_wont_fix_are_permanent() {
+        let refused = |code| handler_error(KafkaError::MessageProduction(code));
+
+        assert!(matches!(
+            refused(RDKafkaErrorCode::MessageSizeTooLarge),
+            HandlerError::Permanent(_)
+        ));
+        assert!(matches!(
+            refused(RDKafkaErrorCode::NotLeaderForPartition),
+            HandlerError::Transient(_)
+        ));
+        assert!(matches!(
+            refused(RDKafkaErrorCode::MessageTimedOut),
+            HandlerError::Transient(_)
+        ));
+    }
+}
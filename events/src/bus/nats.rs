@@ -0,0 +1,178 @@
+//! Publishing to NATS, with `async-nats`.
+//!
+//! Connects on first use and keeps the connection, which `async-nats` reconnects when it's
+//! lost. Each publish is followed by a flush, whose `PONG` confirms the server received
+//! the message. TLS is used when the server requires it.
+
+use async_nats::client::PublishErrorKind;
+use async_nats::ConnectOptions;
+use async_trait::async_trait;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+
+use super::{BusError, Client, Message};
+use crate::HandlerError;
+
+/// Port used when the URL doesn't name one.
+const DEFAULT_PORT: u16 = 4222;
+
+/// How long connecting, including authentication, may take.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long the server may take to confirm a message.
+const PUBLISH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Subject token of events that belong to no coaching relationship.
+const NO_RELATIONSHIP: &str = "none";
+
+/// Credentials sent on connecting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Auth {
+    UserPassword(String, String),
+    Token(String),
+}
+
+/// Publishes events to subjects under one prefix on a NATS server.
+pub struct NatsPublisher {
+    /// `host:port` of the server.
+    address: String,
+    auth: Option<Auth>,
+    topic: String,
+    /// Opened on first use; retried on the next publish if that fails.
+    client: OnceCell<async_nats::Client>,
+}
+
+impl NatsPublisher {
+    /// A publisher to the server at `server`, `[[user:password|token]@]host[:port]`,
+    /// publishing to subjects under `topic`.
+    pub fn new(server: &str, topic: &str) -> Result<Self, BusError> {
+        let (credentials, host) = match server.rsplit_once('@') {
+            Some((credentials, host)) => (Some(credentials), host),
+            None => (None, server),
+        };
+        let auth = credentials.map(|credentials| match credentials.split_once(':') {
+            Some((user, password)) => Auth::UserPassword(user.to_string(), password.to_string()),
+            None => Auth::Token(credentials.to_string()),
+        });
+        let valid_topic = !topic.is_empty()
+            && topic
+                .split('.')
+                .all(|token| !token.is_empty() && !token.contains(['*', '>', ' ', '\t']));
+        if !valid_topic {
+            return Err(BusError(format!("`{topic}` is not a valid NATS subject")));
+        }
+
+        Ok(Self {
+            address: super::address(host.split('/').next().unwrap_or_default(), DEFAULT_PORT)?,
+            auth,
+            topic: topic.to_string(),
+            client: OnceCell::new(),
+        })
+    }
+
+    fn subject(&self, message: &Message) -> String {
+        let relationship = message
+            .key
+            .map_or_else(|| NO_RELATIONSHIP.to_string(), |id| id.to_string());
+        format!("{}.{relationship}.{}", self.topic, message.event_type)
+    }
+
+    async fn connect(&self) -> Result<async_nats::Client, HandlerError> {
+        let options = match self.auth.clone() {
+            Some(Auth::UserPassword(user, password)) => {
+                ConnectOptions::with_user_and_password(user, password)
+            }
+            Some(Auth::Token(token)) => ConnectOptions::with_token(token),
+            None => ConnectOptions::new(),
+        };
+        options
+            .name("refactor-platform")
+            .connection_timeout(CONNECT_TIMEOUT)
+            .connect(&self.address)
+            .await
+            .map_err(|e| transient(format!("connecting to {}: {e}", self.address)))
+    }
+}
+
+#[async_trait]
+impl Client for NatsPublisher {
+    async fn publish(&self, message: &Message) -> Result<(), HandlerError> {
+        let client = self.client.get_or_try_init(|| self.connect()).await?;
+        client
+            .publish(self.subject(message), message.payload.clone().into())
+            .await
+            .map_err(|e| match e.kind() {
+                PublishErrorKind::MaxPayloadExceeded | PublishErrorKind::InvalidSubject => {
+                    HandlerError::Permanent(format!("NATS refused the event: {e}"))
+                }
+                PublishErrorKind::Send => transient(format!("NATS refused the event: {e}")),
+            })?;
+        tokio::time::timeout(PUBLISH_TIMEOUT, client.flush())
+            .await
+            .map_err(|_| transient("The NATS server didn't confirm the event in time".into()))?
+            .map_err(|e| transient(format!("The NATS server didn't confirm the event: {e}")))
+    }
+}
+
+fn transient(message: String) -> HandlerError {
+    HandlerError::Transient(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn new_reads_the_address_and_credentials_from_the_url() {
+        let publisher = NatsPublisher::new("app:s3cret@nats:4223", "events").unwrap();
+        assert_eq!(publisher.address, "nats:4223");
+        assert_eq!(
+            publisher.auth,
+            Some(Auth::UserPassword("app".to_string(), "s3cret".to_string()))
+        );
+
+        let publisher = NatsPublisher::new("t0ken@nats", "events").unwrap();
+        assert_eq!(publisher.address, "nats:4222");
+        assert_eq!(publisher.auth, Some(Auth::Token("t0ken".to_string())));
+
+        assert!(NatsPublisher::new("nats", "events.>").is_err());
+        assert!(NatsPublisher::new("nats", "events..goals").is_err());
+    }
+
+    #[test]
+    fn subject_names_the_relationship_and_event_type() {
+        let publisher = NatsPublisher::new("nats", "refactor_platform.events").unwrap();
+        let id = Uuid::new_v4();
+        let message = |key| Message {
+            event_type: "goal_created",
+            key,
+            payload: Vec::new(),
+        };
+
+        assert_eq!(
+            publisher.subject(&message(Some(id))),
+            format!("refactor_platform.events.{id}.goal_created")
+        );
+        assert_eq!(
+            publisher.subject(&message(None)),
+            "refactor_platform.events.none.goal_created"
+        );
+    }
+
+    #[tokio::test]
+    async fn publish_fails_transiently_while_the_server_is_unreachable() {
+        // Nothing listens on port 1
+        let publisher = NatsPublisher::new("127.0.0.1:1", "events").unwrap();
+        let message = Message {
+            event_type: "goal_created",
+            key: None,
+            payload: b"{}".to_vec(),
+        };
+
+        let result = publisher.publish(&message).await;
+
+        assert!(matches!(result, Err(HandlerError::Transient(_))));
+        assert!(publisher.client.get().is_none());
+    }
+}
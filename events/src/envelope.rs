@@ -0,0 +1,307 @@
+//! The versioned form of a [`DomainEvent`] outside the process that published it.
+//!
+//! Events leave the process as JSON (to outbound webhooks, other replicas, the outbox
+//! and the tables events wait in), where they may be read by code built against a
+//! different version of [`DomainEvent`]. An [`EventEnvelope`] carries the event's fields
+//! with the schema version of its kind (see [`EventKind::version`]), so a reader can tell
+//! an event it can't read from a malformed one, and upgrade one written by older code:
+//!
+//! ```json
+//! {
+//!   "event_type": "goal_created",
+//!   "version": 2,
+//!   "payload": { "coaching_relationship_id": "…", "goal": { … } },
+//!   "occurred_at": "2026-10-14T09:30:00Z",
+//!   "correlation_id": null
+//! }
+//! ```
+//!
+//! [`to_value`] and [`from_value`] (and [`to_vec`] and [`from_slice`] for bytes) convert
+//! between events and envelopes. [`from_value`] also reads the bare events written before
+//! envelopes existed, as version 1. [`parse`] stops at the envelope, for readers that
+//! want its `correlation_id` too.
+//!
+//! An envelope is stamped with the correlation ID the event was published under (see
+//! [`with_correlation_id`](crate::with_correlation_id)), so whoever receives it can tie it
+//! back to the request that caused it.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+
+use crate::{DomainEvent, EventKind, Id};
+
+/// Rewrites a payload's fields to those of the next version.
+type Upgrade = fn(Value) -> Value;
+
+/// Upgrades of older payloads, each from a version of a kind to the next. Add one
+/// whenever [`EventKind::version`] is bumped.
+const UPGRADES: &[(EventKind, u32, Upgrade)] = &[
+    (EventKind::GoalCreated, 1, drop_notify_user_ids),
+    (EventKind::GoalUpdated, 1, drop_notify_user_ids),
+    (EventKind::GoalDeleted, 1, drop_notify_user_ids),
+    (
+        EventKind::CoachingSessionGoalCreated,
+        1,
+        drop_notify_user_ids,
+    ),
+    (
+        EventKind::CoachingSessionGoalDeleted,
+        1,
+        drop_notify_user_ids,
+    ),
+];
+
+/// Relationship-wide events stopped carrying their recipients in version 2.
+fn drop_notify_user_ids(mut payload: Value) -> Value {
+    if let Some(fields) = payload.as_object_mut() {
+        fields.remove("notify_user_ids");
+    }
+    payload
+}
+
+/// A serialized event with the schema version of its fields.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventEnvelope {
+    /// The event's kind, e.g. `goal_created`.
+    pub event_type: String,
+    /// Schema version `payload` was written at.
+    pub version: u32,
+    /// The event's fields, without its `type` tag.
+    pub payload: Value,
+    pub occurred_at: DateTime<Utc>,
+    /// Ties the event to the request that caused it, when known.
+    #[serde(default)]
+    pub correlation_id: Option<Id>,
+}
+
+impl EventEnvelope {
+    /// Wraps `event` at its kind's current version, with the current correlation ID.
+    pub fn new(event: &DomainEvent, occurred_at: DateTime<Utc>) -> Result<Self, EnvelopeError> {
+        let kind = event.kind();
+        let mut payload = serde_json::to_value(event)?;
+        if let Some(fields) = payload.as_object_mut() {
+            fields.remove("type");
+        }
+        Ok(Self {
+            event_type: kind.as_str().to_string(),
+            version: kind.version(),
+            payload,
+            occurred_at,
+            correlation_id: crate::current_correlation_id(),
+        })
+    }
+
+    /// The wrapped event, its payload first upgraded to the current version. Fails for
+    /// an event type this build doesn't know or a version newer than it reads.
+    pub fn into_event(self) -> Result<DomainEvent, EnvelopeError> {
+        let kind = EventKind::from_name(&self.event_type)
+            .ok_or_else(|| EnvelopeError::UnknownEventType(self.event_type.clone()))?;
+
+        let mut payload = self.payload;
+        let mut version = self.version;
+        while version != kind.version() {
+            let upgrade = UPGRADES
+                .iter()
+                .find(|(of, from, _)| *of == kind && *from == version)
+                .map(|(_, _, upgrade)| upgrade)
+                .ok_or_else(|| EnvelopeError::UnsupportedVersion {
+                    event_type: self.event_type.clone(),
+                    version: self.version,
+                })?;
+            payload = upgrade(payload);
+            version += 1;
+        }
+
+        match payload.as_object_mut() {
+            Some(fields) => {
+                fields.insert("type".to_string(), Value::String(self.event_type));
+            }
+            None => {
+                return Err(EnvelopeError::Serialization(
+                    "the payload is not an object".to_string(),
+                ))
+            }
+        }
+        Ok(serde_json::from_value(payload)?)
+    }
+}
+
+/// Why an event couldn't be wrapped or unwrapped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnvelopeError {
+    /// The JSON isn't an envelope or an event of the type it names.
+    Serialization(String),
+    /// The envelope names an event type this build doesn't know, e.g. one added later.
+    UnknownEventType(String),
+    /// The payload's version is newer than this build reads, or older than the upgrades
+    /// it has.
+    UnsupportedVersion { event_type: String, version: u32 },
+}
+
+impl fmt::Display for EnvelopeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvelopeError::Serialization(message) => f.write_str(message),
+            EnvelopeError::UnknownEventType(event_type) => {
+                write!(f, "unknown event type `{event_type}`")
+            }
+            EnvelopeError::UnsupportedVersion {
+                event_type,
+                version,
+            } => write!(f, "unsupported version {version} of `{event_type}` events"),
+        }
+    }
+}
+
+impl std::error::Error for EnvelopeError {}
+
+impl From<serde_json::Error> for EnvelopeError {
+    fn from(e: serde_json::Error) -> Self {
+        EnvelopeError::Serialization(e.to_string())
+    }
+}
+
+/// `event` wrapped in an envelope stamped now, as JSON.
+pub fn to_value(event: &DomainEvent) -> Result<Value, EnvelopeError> {
+    let envelope = EventEnvelope::new(event, Utc::now())?;
+    Ok(serde_json::to_value(envelope)?)
+}
+
+/// The envelope in `value`. A bare event, as written before envelopes, is wrapped at
+/// version 1 with no correlation ID.
+pub fn parse(value: Value) -> Result<EventEnvelope, EnvelopeError> {
+    if value.get("version").is_some() {
+        return Ok(serde_json::from_value(value)?);
+    }
+    let event: DomainEvent = serde_json::from_value(value)?;
+    Ok(EventEnvelope {
+        correlation_id: None,
+        ..EventEnvelope::new(&event, Utc::now())?
+    })
+}
+
+/// [`parse`] of serialized JSON.
+pub fn parse_slice(bytes: &[u8]) -> Result<EventEnvelope, EnvelopeError> {
+    parse(serde_json::from_slice(bytes)?)
+}
+
+/// The event in `value`: an envelope, or a bare event as written before envelopes.
+pub fn from_value(value: Value) -> Result<DomainEvent, EnvelopeError> {
+    parse(value)?.into_event()
+}
+
+/// [`to_value`], serialized.
+pub fn to_vec(event: &DomainEvent) -> Result<Vec<u8>, EnvelopeError> {
+    Ok(serde_json::to_vec(&to_value(event)?)?)
+}
+
+/// [`from_value`] of serialized JSON.
+pub fn from_slice(bytes: &[u8]) -> Result<DomainEvent, EnvelopeError> {
+    from_value(serde_json::from_slice(bytes)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn event() -> DomainEvent {
+        DomainEvent::TopicsChanged {
+            coaching_session_id: Id::new_v4(),
+            notify_user_ids: vec![Id::new_v4()],
+        }
+    }
+
+    #[test]
+    fn envelope_carries_the_fields_at_the_current_version() {
+        let event = event();
+
+        let envelope = EventEnvelope::new(&event, Utc::now()).unwrap();
+
+        assert_eq!(envelope.event_type, "topics_changed");
+        assert_eq!(envelope.version, EventKind::TopicsChanged.version());
+        assert!(envelope.payload.get("type").is_none());
+        assert!(envelope.payload.get("coaching_session_id").is_some());
+        assert_eq!(
+            serde_json::to_value(envelope.into_event().unwrap()).unwrap(),
+            serde_json::to_value(&event).unwrap()
+        );
+    }
+
+    #[test]
+    fn from_value_reads_envelopes_and_bare_events() {
+        let event = event();
+        let expected = serde_json::to_value(&event).unwrap();
+
+        let enveloped = from_value(to_value(&event).unwrap()).unwrap();
+        let bare = from_value(expected.clone()).unwrap();
+
+        assert_eq!(serde_json::to_value(&enveloped).unwrap(), expected);
+        assert_eq!(serde_json::to_value(&bare).unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn envelope_carries_the_correlation_id_it_was_published_under() {
+        let correlation_id = Id::new_v4();
+
+        let value =
+            crate::with_correlation_id(Some(correlation_id), async { to_value(&event()).unwrap() })
+                .await;
+
+        assert_eq!(parse(value).unwrap().correlation_id, Some(correlation_id));
+        assert_eq!(
+            parse(serde_json::to_value(event()).unwrap())
+                .unwrap()
+                .correlation_id,
+            None
+        );
+    }
+
+    #[test]
+    fn from_value_upgrades_older_versions() {
+        let relationship_id = Id::new_v4();
+        let goal_id = Id::new_v4();
+        let v1 = json!({
+            "event_type": "goal_deleted",
+            "version": 1,
+            "payload": {
+                "coaching_relationship_id": relationship_id,
+                "goal_id": goal_id,
+                "notify_user_ids": [Id::new_v4()]
+            },
+            "occurred_at": "2026-10-14T09:30:00Z"
+        });
+
+        let event = from_value(v1).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            json!({
+                "type": "goal_deleted",
+                "coaching_relationship_id": relationship_id,
+                "goal_id": goal_id
+            })
+        );
+    }
+
+    #[test]
+    fn from_value_rejects_unknown_types_and_versions() {
+        let mut envelope = to_value(&event()).unwrap();
+        envelope["version"] = json!(EventKind::TopicsChanged.version() + 1);
+        assert_eq!(
+            from_value(envelope.clone()).unwrap_err(),
+            EnvelopeError::UnsupportedVersion {
+                event_type: "topics_changed".to_string(),
+                version: EventKind::TopicsChanged.version() + 1,
+            }
+        );
+
+        envelope["event_type"] = json!("something_new");
+        assert_eq!(
+            from_value(envelope).unwrap_err(),
+            EnvelopeError::UnknownEventType("something_new".to_string())
+        );
+    }
+}
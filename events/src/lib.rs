@@ -7,16 +7,44 @@
 //!
 //! - **DomainEvent**: Enum representing all business events in the system
 //! - **EventHandler**: Trait for implementing event handlers
-//! - **EventPublisher**: Publishes events to registered handlers
+//! - **EventInterceptor**: Hooks run around every publish, e.g. for logging or redaction
+//! - **EventPublisher**: Publishes events to the handlers subscribed to their kind
+//! - **EventKind / EventKinds**: An event's kind, and the set a handler subscribes to
+//! - **RetryPolicy**: How a handler is retried after a transient failure
+//! - **DeadLetterSink**: Where events a handler failed to process are kept for requeueing
+//! - **Outbox**: Where events are written for a separate worker process to dispatch
+//! - **EventTransport**: Carries events to the SSE handlers of every backend replica
+//! - **BusPublisher**: Publishes events to Kafka or NATS for other systems to consume
+//! - **EventEnvelope**: The versioned form events take outside the process
+//! - **schema**: JSON Schemas of each kind's payload, for consumers outside the process
+//! - **Metrics**: Receives publish counts and handler latencies for monitoring
+//! - **with_actor / current_actor**: The user whose request emits an event
+//! - **with_correlation_id / current_correlation_id**: The request an event traces back to
+//! - **with_client_ip / current_client_ip**: The address the current request came from
 //!
 //! This crate has no dependencies on internal crates (entity, domain, etc.),
 //! avoiding circular dependencies. Entity data is carried as serialized JSON values.
 
 use async_trait::async_trait;
+use log::*;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::fmt;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+pub mod bus;
+pub mod envelope;
+pub mod schema;
+pub mod transport;
+
+pub use bus::{BusError, BusPublisher};
+pub use envelope::{EnvelopeError, EventEnvelope};
+pub use transport::{EventStream, EventTransport, InProcessTransport, TransportError};
+
 /// A type alias that represents any Entity's internal id field data type.
 /// This matches the definition in the entity crate to maintain compatibility.
 pub type Id = Uuid;
@@ -25,74 +53,60 @@ pub type Id = Uuid;
 /// These events are emitted when domain operations complete successfully.
 ///
 /// Events include user IDs for notification routing. The domain layer is
-/// responsible for determining which users should be notified.
+/// responsible for determining which users should be notified, except for events
+/// addressed to a whole coaching relationship, whose members the SSE manager resolves.
 ///
 /// Entity data is carried as `serde_json::Value` to avoid dependencies on
-/// the entity crate.
-#[derive(Debug, Clone)]
+/// the entity crate. Serializes as its fields plus a `type` tag (see [`DomainEvent::kind`]).
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum DomainEvent {
     /// Emitted when a new goal is created within a coaching session.
     /// Triggers SSE notifications to coach and coachee for real-time UI updates.
     GoalCreated {
-        /// Parent coaching relationship ID for context and potential scoped cache invalidation.
-        /// Currently used for event tracing and future frontend optimization.
+        /// Parent coaching relationship ID; its coach and coachee are notified.
         coaching_relationship_id: Id,
         /// Complete serialized goal entity (includes id, title, details, status, etc.).
         /// Sent to frontend for optimistic UI updates without requiring a separate API call.
         goal: Value,
-        /// User IDs to receive SSE notifications (determined by domain layer from relationship).
-        /// SSE manager routes events only to these users' active connections.
-        notify_user_ids: Vec<Id>,
     },
     /// Emitted when a goal is modified (title, details, status, etc.).
     /// Triggers SSE notifications to keep all participants' UIs synchronized.
     GoalUpdated {
-        /// Parent coaching relationship ID for context and potential scoped cache invalidation.
-        /// Currently used for event tracing and future frontend optimization.
+        /// Parent coaching relationship ID; its coach and coachee are notified.
         coaching_relationship_id: Id,
         /// Complete updated goal entity with all current field values.
         /// Sent to frontend for optimistic UI updates without requiring a separate API call.
         goal: Value,
-        /// User IDs to receive SSE notifications (determined by domain layer from relationship).
-        /// SSE manager routes events only to these users' active connections.
-        notify_user_ids: Vec<Id>,
     },
     /// Emitted when a goal is permanently removed from the system.
     /// Triggers SSE notifications to remove the goal from all participants' UIs.
     GoalDeleted {
-        /// Parent coaching relationship ID for context and potential scoped cache invalidation.
-        /// Currently used for event tracing and future frontend optimization.
+        /// Parent coaching relationship ID; its coach and coachee are notified.
         coaching_relationship_id: Id,
         /// ID of the deleted goal (full entity not included since it no longer exists).
         /// Frontend uses this to remove the goal from local cache and UI.
         goal_id: Id,
-        /// User IDs to receive SSE notifications (determined by domain layer from relationship).
-        /// SSE manager routes events only to these users' active connections.
-        notify_user_ids: Vec<Id>,
     },
     /// Emitted when a goal is linked to a coaching session via the join table.
     /// Triggers SSE notifications so participants see updated session-goal associations.
     CoachingSessionGoalCreated {
-        /// Parent coaching relationship ID for scoped event routing.
+        /// Parent coaching relationship ID; its coach and coachee are notified.
         coaching_relationship_id: Id,
         /// The coaching session that the goal was linked to.
         coaching_session_id: Id,
         /// The goal that was linked to the session.
         goal_id: Id,
-        /// User IDs to receive SSE notifications (coach + coachee from relationship).
-        notify_user_ids: Vec<Id>,
     },
     /// Emitted when a goal is unlinked from a coaching session.
     /// Triggers SSE notifications so participants see the removed association.
     CoachingSessionGoalDeleted {
-        /// Parent coaching relationship ID for scoped event routing.
+        /// Parent coaching relationship ID; its coach and coachee are notified.
         coaching_relationship_id: Id,
         /// The coaching session that the goal was unlinked from.
         coaching_session_id: Id,
         /// The goal that was unlinked from the session.
         goal_id: Id,
-        /// User IDs to receive SSE notifications (coach + coachee from relationship).
-        notify_user_ids: Vec<Id>,
     },
     /// Emitted when an agreement is created within a coaching session.
     /// Carries the full serialized agreement entity for optimistic UI updates.
@@ -150,6 +164,92 @@ pub enum DomainEvent {
         /// User IDs to receive SSE notifications (coach + coachee from the session's relationship).
         notify_user_ids: Vec<Id>,
     },
+    /// Emitted when a reaction is added to or removed from a note or action.
+    /// Carries the target's full aggregated reactions so clients replace rather than patch.
+    ReactionsChanged {
+        /// The coaching session the reacted-to note or action belongs to.
+        coaching_session_id: Id,
+        /// Kind of target (`"note"` or `"action"`).
+        resource_type: String,
+        /// ID of the note or action.
+        resource_id: Id,
+        /// Serialized per-emoji counts (with reacting user IDs) after the change.
+        reactions: Value,
+        /// User IDs to receive SSE notifications (coach + coachee from the session's relationship).
+        notify_user_ids: Vec<Id>,
+    },
+    /// Emitted when a user is newly @-mentioned in a note or action body.
+    /// Sent only to the mentioned user.
+    MentionCreated {
+        /// The coaching session the note or action belongs to.
+        coaching_session_id: Id,
+        /// Complete serialized mention record (target, author, mentioned user).
+        mention: Value,
+        /// User IDs to receive SSE notifications (the mentioned user).
+        notify_user_ids: Vec<Id>,
+    },
+    /// Emitted when a library item is assigned to a relationship's coachee.
+    /// Sent to the coachee.
+    LibraryAssignmentCreated {
+        /// The coaching relationship the item was assigned in.
+        coaching_relationship_id: Id,
+        /// Complete serialized assignment, including the library item.
+        assignment: Value,
+        /// User IDs to receive SSE notifications (the coachee).
+        notify_user_ids: Vec<Id>,
+    },
+    /// Emitted when a library assignment is marked completed.
+    /// Sent to the coach.
+    LibraryAssignmentCompleted {
+        /// The coaching relationship the item was assigned in.
+        coaching_relationship_id: Id,
+        /// Complete serialized assignment, including the library item.
+        assignment: Value,
+        /// User IDs to receive SSE notifications (the coach).
+        notify_user_ids: Vec<Id>,
+    },
+    /// Emitted when a coachee requests an ad-hoc session.
+    /// Sent to the coach.
+    SessionRequestCreated {
+        /// The coaching relationship the session was requested in.
+        coaching_relationship_id: Id,
+        /// Complete serialized session request, including its preferred windows.
+        session_request: Value,
+        /// User IDs to receive SSE notifications (the coach).
+        notify_user_ids: Vec<Id>,
+    },
+    /// Emitted when the coach accepts or declines a session request.
+    /// Sent to the coachee.
+    SessionRequestResolved {
+        /// The coaching relationship the session was requested in.
+        coaching_relationship_id: Id,
+        /// Complete serialized session request, with its new status.
+        session_request: Value,
+        /// User IDs to receive SSE notifications (the coachee).
+        notify_user_ids: Vec<Id>,
+    },
+    /// Emitted once a session has ended, prompting both participants for their pulse
+    /// check-in ("How valuable was this session? 1–5").
+    SessionPulseRequested {
+        /// The coaching relationship the session belongs to.
+        coaching_relationship_id: Id,
+        /// The session that ended.
+        coaching_session_id: Id,
+        /// User IDs to receive SSE notifications (the coach and the coachee).
+        notify_user_ids: Vec<Id>,
+    },
+    /// Emitted when a relationship's health score drops below its organization's alert
+    /// threshold, alerting the organization's admins.
+    RelationshipHealthAtRisk {
+        /// The coaching relationship whose score dropped.
+        coaching_relationship_id: Id,
+        /// The new 0–100 health score.
+        score: i16,
+        /// The organization's alert threshold it dropped below.
+        threshold: i16,
+        /// User IDs to receive SSE notifications (the organization's admins).
+        notify_user_ids: Vec<Id>,
+    },
     /// Emitted when a meeting recording status changes (any webhook-driven transition).
     /// Triggers SSE notifications so participants see the current recording state without polling.
     MeetingRecordingUpdated {
@@ -182,47 +282,980 @@ pub enum DomainEvent {
         /// User IDs to receive SSE notifications (coach + coachee from coaching relationship).
         notify_user_ids: Vec<Id>,
     },
+    /// Emitted alongside `MeetingRecordingUpdated` when a Recall.ai webhook moves a
+    /// recording to a new status. Carries the status so participants needn't refetch.
+    RecordingStatusChanged {
+        /// The coaching session whose recording changed.
+        coaching_session_id: Id,
+        /// The recording that changed.
+        meeting_recording_id: Id,
+        /// The recording's new status, e.g. `"recording"` or `"failed"`.
+        status: String,
+        /// Why the recording failed, when it did.
+        error_message: Option<String>,
+        /// User IDs to receive SSE notifications (coach + coachee from coaching relationship).
+        notify_user_ids: Vec<Id>,
+    },
+    /// Emitted alongside `TranscriptionUpdated` once a transcript's segments are stored.
+    TranscriptionCompleted {
+        /// The coaching session that was transcribed.
+        coaching_session_id: Id,
+        /// Complete serialized transcription (status, word count, ...).
+        transcription: Value,
+        /// How many transcript segments were stored.
+        segment_count: usize,
+        /// User IDs to receive SSE notifications (coach + coachee from coaching relationship).
+        notify_user_ids: Vec<Id>,
+    },
+    /// Emitted once the AI-generated chapters (titles, summaries and gists) of a
+    /// completed transcript are stored. Not emitted when the provider detected none.
+    AiSuggestionsReady {
+        /// The coaching session that was transcribed.
+        coaching_session_id: Id,
+        /// The transcription the chapters belong to.
+        transcription_id: Id,
+        /// How many chapters were stored.
+        chapter_count: usize,
+        /// User IDs to receive SSE notifications (coach + coachee from coaching relationship).
+        notify_user_ids: Vec<Id>,
+    },
+    /// Emitted when an organization admin locks a user's account.
+    /// The user's SSE connections are sent `force_logout` and closed.
+    UserLocked {
+        /// The organization whose admin locked the user.
+        organization_id: Id,
+        /// The locked user.
+        user_id: Id,
+        /// Why the user was locked, as the admin gave it.
+        reason: Option<String>,
+    },
+    /// Emitted when an organization admin unlocks a user's account. Not sent to clients.
+    UserUnlocked {
+        /// The organization whose admin unlocked the user.
+        organization_id: Id,
+        /// The unlocked user.
+        user_id: Id,
+    },
+    /// Emitted when a user's roles in an organization change, e.g. through SCIM. The
+    /// user's SSE connections are sent `session_invalidated` so the client refetches the
+    /// user and their permissions.
+    UserRolesChanged {
+        /// The organization the roles are in.
+        organization_id: Id,
+        /// The user whose roles changed.
+        user_id: Id,
+    },
+}
+
+impl DomainEvent {
+    /// The event's kind, e.g. [`EventKind::GoalCreated`].
+    pub fn kind(&self) -> EventKind {
+        match self {
+            DomainEvent::GoalCreated { .. } => EventKind::GoalCreated,
+            DomainEvent::GoalUpdated { .. } => EventKind::GoalUpdated,
+            DomainEvent::GoalDeleted { .. } => EventKind::GoalDeleted,
+            DomainEvent::CoachingSessionGoalCreated { .. } => EventKind::CoachingSessionGoalCreated,
+            DomainEvent::CoachingSessionGoalDeleted { .. } => EventKind::CoachingSessionGoalDeleted,
+            DomainEvent::AgreementCreated { .. } => EventKind::AgreementCreated,
+            DomainEvent::AgreementUpdated { .. } => EventKind::AgreementUpdated,
+            DomainEvent::AgreementDeleted { .. } => EventKind::AgreementDeleted,
+            DomainEvent::ActionCreated { .. } => EventKind::ActionCreated,
+            DomainEvent::ActionUpdated { .. } => EventKind::ActionUpdated,
+            DomainEvent::ActionDeleted { .. } => EventKind::ActionDeleted,
+            DomainEvent::ReactionsChanged { .. } => EventKind::ReactionsChanged,
+            DomainEvent::MentionCreated { .. } => EventKind::MentionCreated,
+            DomainEvent::LibraryAssignmentCreated { .. } => EventKind::LibraryAssignmentCreated,
+            DomainEvent::LibraryAssignmentCompleted { .. } => EventKind::LibraryAssignmentCompleted,
+            DomainEvent::SessionRequestCreated { .. } => EventKind::SessionRequestCreated,
+            DomainEvent::SessionRequestResolved { .. } => EventKind::SessionRequestResolved,
+            DomainEvent::SessionPulseRequested { .. } => EventKind::SessionPulseRequested,
+            DomainEvent::RelationshipHealthAtRisk { .. } => EventKind::RelationshipHealthAtRisk,
+            DomainEvent::MeetingRecordingUpdated { .. } => EventKind::MeetingRecordingUpdated,
+            DomainEvent::TopicsChanged { .. } => EventKind::TopicsChanged,
+            DomainEvent::CoachingSessionTitleUpdated { .. } => {
+                EventKind::CoachingSessionTitleUpdated
+            }
+            DomainEvent::TranscriptionUpdated { .. } => EventKind::TranscriptionUpdated,
+            DomainEvent::RecordingStatusChanged { .. } => EventKind::RecordingStatusChanged,
+            DomainEvent::TranscriptionCompleted { .. } => EventKind::TranscriptionCompleted,
+            DomainEvent::AiSuggestionsReady { .. } => EventKind::AiSuggestionsReady,
+            DomainEvent::UserLocked { .. } => EventKind::UserLocked,
+            DomainEvent::UserUnlocked { .. } => EventKind::UserUnlocked,
+            DomainEvent::UserRolesChanged { .. } => EventKind::UserRolesChanged,
+        }
+    }
+
+    /// The type of entity the event is about and, where the event identifies a single
+    /// one, its ID. Coarse events that only name their coaching session report no ID.
+    pub fn entity(&self) -> (&str, Option<Id>) {
+        match self {
+            DomainEvent::GoalCreated { goal, .. } | DomainEvent::GoalUpdated { goal, .. } => {
+                ("goal", value_id(goal))
+            }
+            DomainEvent::GoalDeleted { goal_id, .. } => ("goal", Some(*goal_id)),
+            DomainEvent::CoachingSessionGoalCreated { goal_id, .. }
+            | DomainEvent::CoachingSessionGoalDeleted { goal_id, .. } => {
+                ("coaching_session_goal", Some(*goal_id))
+            }
+            DomainEvent::AgreementCreated { agreement, .. }
+            | DomainEvent::AgreementUpdated { agreement, .. } => ("agreement", value_id(agreement)),
+            DomainEvent::AgreementDeleted { agreement_id, .. } => {
+                ("agreement", Some(*agreement_id))
+            }
+            DomainEvent::ActionCreated { action, .. }
+            | DomainEvent::ActionUpdated { action, .. } => ("action", value_id(action)),
+            DomainEvent::ActionDeleted { action_id, .. } => ("action", Some(*action_id)),
+            DomainEvent::ReactionsChanged {
+                resource_type,
+                resource_id,
+                ..
+            } => (resource_type, Some(*resource_id)),
+            DomainEvent::MentionCreated { mention, .. } => ("mention", value_id(mention)),
+            DomainEvent::LibraryAssignmentCreated { assignment, .. }
+            | DomainEvent::LibraryAssignmentCompleted { assignment, .. } => {
+                ("library_assignment", value_id(assignment))
+            }
+            DomainEvent::SessionRequestCreated {
+                session_request, ..
+            }
+            | DomainEvent::SessionRequestResolved {
+                session_request, ..
+            } => ("session_request", value_id(session_request)),
+            DomainEvent::SessionPulseRequested {
+                coaching_session_id,
+                ..
+            } => ("coaching_session", Some(*coaching_session_id)),
+            DomainEvent::RelationshipHealthAtRisk {
+                coaching_relationship_id,
+                ..
+            } => ("coaching_relationship", Some(*coaching_relationship_id)),
+            DomainEvent::MeetingRecordingUpdated { .. } => ("meeting_recording", None),
+            DomainEvent::TopicsChanged { .. } => ("coaching_session_topic", None),
+            DomainEvent::CoachingSessionTitleUpdated {
+                coaching_session_id,
+                ..
+            } => ("coaching_session", Some(*coaching_session_id)),
+            DomainEvent::TranscriptionUpdated { .. } => ("transcription", None),
+            DomainEvent::RecordingStatusChanged {
+                meeting_recording_id,
+                ..
+            } => ("meeting_recording", Some(*meeting_recording_id)),
+            DomainEvent::TranscriptionCompleted { transcription, .. } => {
+                ("transcription", value_id(transcription))
+            }
+            DomainEvent::AiSuggestionsReady {
+                transcription_id, ..
+            } => ("transcription", Some(*transcription_id)),
+            DomainEvent::UserLocked { user_id, .. }
+            | DomainEvent::UserUnlocked { user_id, .. }
+            | DomainEvent::UserRolesChanged { user_id, .. } => ("user", Some(*user_id)),
+        }
+    }
+
+    /// The coaching relationship the event happened in, when the event names it.
+    /// Session-scoped events name only their session (see
+    /// [`DomainEvent::coaching_session_id`]).
+    pub fn coaching_relationship_id(&self) -> Option<Id> {
+        match self {
+            DomainEvent::GoalCreated {
+                coaching_relationship_id,
+                ..
+            }
+            | DomainEvent::GoalUpdated {
+                coaching_relationship_id,
+                ..
+            }
+            | DomainEvent::GoalDeleted {
+                coaching_relationship_id,
+                ..
+            }
+            | DomainEvent::CoachingSessionGoalCreated {
+                coaching_relationship_id,
+                ..
+            }
+            | DomainEvent::CoachingSessionGoalDeleted {
+                coaching_relationship_id,
+                ..
+            }
+            | DomainEvent::LibraryAssignmentCreated {
+                coaching_relationship_id,
+                ..
+            }
+            | DomainEvent::LibraryAssignmentCompleted {
+                coaching_relationship_id,
+                ..
+            }
+            | DomainEvent::SessionRequestCreated {
+                coaching_relationship_id,
+                ..
+            }
+            | DomainEvent::SessionRequestResolved {
+                coaching_relationship_id,
+                ..
+            }
+            | DomainEvent::SessionPulseRequested {
+                coaching_relationship_id,
+                ..
+            }
+            | DomainEvent::RelationshipHealthAtRisk {
+                coaching_relationship_id,
+                ..
+            } => Some(*coaching_relationship_id),
+            DomainEvent::AgreementCreated { .. }
+            | DomainEvent::AgreementUpdated { .. }
+            | DomainEvent::AgreementDeleted { .. }
+            | DomainEvent::ActionCreated { .. }
+            | DomainEvent::ActionUpdated { .. }
+            | DomainEvent::ActionDeleted { .. }
+            | DomainEvent::ReactionsChanged { .. }
+            | DomainEvent::MentionCreated { .. }
+            | DomainEvent::MeetingRecordingUpdated { .. }
+            | DomainEvent::TopicsChanged { .. }
+            | DomainEvent::CoachingSessionTitleUpdated { .. }
+            | DomainEvent::TranscriptionUpdated { .. }
+            | DomainEvent::RecordingStatusChanged { .. }
+            | DomainEvent::TranscriptionCompleted { .. }
+            | DomainEvent::AiSuggestionsReady { .. }
+            | DomainEvent::UserLocked { .. }
+            | DomainEvent::UserUnlocked { .. }
+            | DomainEvent::UserRolesChanged { .. } => None,
+        }
+    }
+
+    /// The coaching session the event happened in, if it's about one.
+    pub fn coaching_session_id(&self) -> Option<Id> {
+        match self {
+            DomainEvent::CoachingSessionGoalCreated {
+                coaching_session_id,
+                ..
+            }
+            | DomainEvent::CoachingSessionGoalDeleted {
+                coaching_session_id,
+                ..
+            }
+            | DomainEvent::AgreementCreated {
+                coaching_session_id,
+                ..
+            }
+            | DomainEvent::AgreementUpdated {
+                coaching_session_id,
+                ..
+            }
+            | DomainEvent::AgreementDeleted {
+                coaching_session_id,
+                ..
+            }
+            | DomainEvent::ActionCreated {
+                coaching_session_id,
+                ..
+            }
+            | DomainEvent::ActionUpdated {
+                coaching_session_id,
+                ..
+            }
+            | DomainEvent::ActionDeleted {
+                coaching_session_id,
+                ..
+            }
+            | DomainEvent::ReactionsChanged {
+                coaching_session_id,
+                ..
+            }
+            | DomainEvent::MentionCreated {
+                coaching_session_id,
+                ..
+            }
+            | DomainEvent::MeetingRecordingUpdated {
+                coaching_session_id,
+                ..
+            }
+            | DomainEvent::TopicsChanged {
+                coaching_session_id,
+                ..
+            }
+            | DomainEvent::CoachingSessionTitleUpdated {
+                coaching_session_id,
+                ..
+            }
+            | DomainEvent::TranscriptionUpdated {
+                coaching_session_id,
+                ..
+            }
+            | DomainEvent::RecordingStatusChanged {
+                coaching_session_id,
+                ..
+            }
+            | DomainEvent::TranscriptionCompleted {
+                coaching_session_id,
+                ..
+            }
+            | DomainEvent::AiSuggestionsReady {
+                coaching_session_id,
+                ..
+            }
+            | DomainEvent::SessionPulseRequested {
+                coaching_session_id,
+                ..
+            } => Some(*coaching_session_id),
+            DomainEvent::GoalCreated { .. }
+            | DomainEvent::GoalUpdated { .. }
+            | DomainEvent::GoalDeleted { .. }
+            | DomainEvent::LibraryAssignmentCreated { .. }
+            | DomainEvent::LibraryAssignmentCompleted { .. }
+            | DomainEvent::SessionRequestCreated { .. }
+            | DomainEvent::SessionRequestResolved { .. }
+            | DomainEvent::RelationshipHealthAtRisk { .. }
+            | DomainEvent::UserLocked { .. }
+            | DomainEvent::UserUnlocked { .. }
+            | DomainEvent::UserRolesChanged { .. } => None,
+        }
+    }
+}
+
+/// The kind of a [`DomainEvent`], without its data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    GoalCreated,
+    GoalUpdated,
+    GoalDeleted,
+    CoachingSessionGoalCreated,
+    CoachingSessionGoalDeleted,
+    AgreementCreated,
+    AgreementUpdated,
+    AgreementDeleted,
+    ActionCreated,
+    ActionUpdated,
+    ActionDeleted,
+    ReactionsChanged,
+    MentionCreated,
+    LibraryAssignmentCreated,
+    LibraryAssignmentCompleted,
+    MeetingRecordingUpdated,
+    TopicsChanged,
+    CoachingSessionTitleUpdated,
+    TranscriptionUpdated,
+    UserLocked,
+    UserUnlocked,
+    RecordingStatusChanged,
+    TranscriptionCompleted,
+    AiSuggestionsReady,
+    UserRolesChanged,
+    SessionRequestCreated,
+    SessionRequestResolved,
+    SessionPulseRequested,
+    RelationshipHealthAtRisk,
 }
 
+impl EventKind {
+    /// Every kind, in declaration order.
+    pub const ALL: [EventKind; 29] = [
+        EventKind::GoalCreated,
+        EventKind::GoalUpdated,
+        EventKind::GoalDeleted,
+        EventKind::CoachingSessionGoalCreated,
+        EventKind::CoachingSessionGoalDeleted,
+        EventKind::AgreementCreated,
+        EventKind::AgreementUpdated,
+        EventKind::AgreementDeleted,
+        EventKind::ActionCreated,
+        EventKind::ActionUpdated,
+        EventKind::ActionDeleted,
+        EventKind::ReactionsChanged,
+        EventKind::MentionCreated,
+        EventKind::LibraryAssignmentCreated,
+        EventKind::LibraryAssignmentCompleted,
+        EventKind::MeetingRecordingUpdated,
+        EventKind::TopicsChanged,
+        EventKind::CoachingSessionTitleUpdated,
+        EventKind::TranscriptionUpdated,
+        EventKind::UserLocked,
+        EventKind::UserUnlocked,
+        EventKind::RecordingStatusChanged,
+        EventKind::TranscriptionCompleted,
+        EventKind::AiSuggestionsReady,
+        EventKind::UserRolesChanged,
+        EventKind::SessionRequestCreated,
+        EventKind::SessionRequestResolved,
+        EventKind::SessionPulseRequested,
+        EventKind::RelationshipHealthAtRisk,
+    ];
+
+    /// The kind named `name`, the inverse of [`EventKind::as_str`].
+    pub fn from_name(name: &str) -> Option<EventKind> {
+        EventKind::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == name)
+    }
+
+    /// Current schema version of the kind's serialized fields. Bump it on a change
+    /// older readers couldn't read (a field renamed, removed or retyped) and teach
+    /// [`envelope`] to upgrade the previous version; adding a field needs neither.
+    pub fn version(self) -> u32 {
+        match self {
+            // 2: `notify_user_ids` dropped; the relationship's members are resolved on delivery.
+            EventKind::GoalCreated
+            | EventKind::GoalUpdated
+            | EventKind::GoalDeleted
+            | EventKind::CoachingSessionGoalCreated
+            | EventKind::CoachingSessionGoalDeleted => 2,
+            _ => 1,
+        }
+    }
+
+    /// Snake-case name of the kind, e.g. `"goal_created"`; matches the `type` tag of
+    /// the serialized event.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EventKind::GoalCreated => "goal_created",
+            EventKind::GoalUpdated => "goal_updated",
+            EventKind::GoalDeleted => "goal_deleted",
+            EventKind::CoachingSessionGoalCreated => "coaching_session_goal_created",
+            EventKind::CoachingSessionGoalDeleted => "coaching_session_goal_deleted",
+            EventKind::AgreementCreated => "agreement_created",
+            EventKind::AgreementUpdated => "agreement_updated",
+            EventKind::AgreementDeleted => "agreement_deleted",
+            EventKind::ActionCreated => "action_created",
+            EventKind::ActionUpdated => "action_updated",
+            EventKind::ActionDeleted => "action_deleted",
+            EventKind::ReactionsChanged => "reactions_changed",
+            EventKind::MentionCreated => "mention_created",
+            EventKind::LibraryAssignmentCreated => "library_assignment_created",
+            EventKind::LibraryAssignmentCompleted => "library_assignment_completed",
+            EventKind::MeetingRecordingUpdated => "meeting_recording_updated",
+            EventKind::TopicsChanged => "topics_changed",
+            EventKind::CoachingSessionTitleUpdated => "coaching_session_title_updated",
+            EventKind::TranscriptionUpdated => "transcription_updated",
+            EventKind::UserLocked => "user_locked",
+            EventKind::UserUnlocked => "user_unlocked",
+            EventKind::UserRolesChanged => "user_roles_changed",
+            EventKind::RecordingStatusChanged => "recording_status_changed",
+            EventKind::TranscriptionCompleted => "transcription_completed",
+            EventKind::AiSuggestionsReady => "ai_suggestions_ready",
+            EventKind::SessionRequestCreated => "session_request_created",
+            EventKind::SessionRequestResolved => "session_request_resolved",
+            EventKind::SessionPulseRequested => "session_pulse_requested",
+            EventKind::RelationshipHealthAtRisk => "relationship_health_at_risk",
+        }
+    }
+}
+
+impl fmt::Display for EventKind {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(self.as_str())
+    }
+}
+
+/// A set of [`EventKind`]s a handler subscribes to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventKinds(u32);
+
+impl EventKinds {
+    /// Every kind of event.
+    pub const fn all() -> Self {
+        Self(u32::MAX)
+    }
+
+    /// Only the given kinds.
+    pub const fn of(kinds: &[EventKind]) -> Self {
+        let mut bits = 0;
+        let mut i = 0;
+        while i < kinds.len() {
+            bits |= Self::bit(kinds[i]);
+            i += 1;
+        }
+        Self(bits)
+    }
+
+    pub const fn contains(self, kind: EventKind) -> bool {
+        self.0 & Self::bit(kind) != 0
+    }
+
+    const fn bit(kind: EventKind) -> u32 {
+        1 << kind as u32
+    }
+}
+
+impl From<EventKind> for EventKinds {
+    fn from(kind: EventKind) -> Self {
+        Self::of(&[kind])
+    }
+}
+
+fn value_id(entity: &Value) -> Option<Id> {
+    entity.get("id")?.as_str()?.parse().ok()
+}
+
+tokio::task_local! {
+    static ACTOR: Option<Id>;
+    static CORRELATION_ID: Option<Id>;
+    static CLIENT_IP: Option<String>;
+}
+
+/// Runs `future` with `actor_id` as the user on whose behalf it emits events. The web
+/// layer wraps each authenticated request in this so handlers can attribute events.
+pub async fn with_actor<F: Future>(actor_id: Id, future: F) -> F::Output {
+    ACTOR.scope(Some(actor_id), future).await
+}
+
+/// The user whose request is emitting events, if any. `None` for work outside a
+/// request (webhooks, background tasks).
+pub fn current_actor() -> Option<Id> {
+    ACTOR.try_with(|actor| *actor).ok().flatten()
+}
+
+/// Runs `future` with `correlation_id` identifying the request its events trace back to.
+/// The web layer wraps each request in this; dispatching an event stored or sent
+/// elsewhere restores the ID it was published with, `None` included.
+pub async fn with_correlation_id<F: Future>(correlation_id: Option<Id>, future: F) -> F::Output {
+    CORRELATION_ID.scope(correlation_id, future).await
+}
+
+/// The ID of the request events published now trace back to, if any. Handlers read it
+/// to tie what they do (e.g. SSE messages) to the request.
+pub fn current_correlation_id() -> Option<Id> {
+    CORRELATION_ID.try_with(|id| *id).ok().flatten()
+}
+
+/// Runs `future` with `client_ip` as the address of the request it serves. The web layer
+/// wraps each request in this so the audit trail can record where changes came from.
+/// Unlike the actor and correlation ID, it never leaves the process with an event.
+pub async fn with_client_ip<F: Future>(client_ip: Option<String>, future: F) -> F::Output {
+    CLIENT_IP.scope(client_ip, future).await
+}
+
+/// The address of the request being served, if any.
+pub fn current_client_ip() -> Option<String> {
+    CLIENT_IP.try_with(|ip| ip.clone()).ok().flatten()
+}
+
+/// Why a handler couldn't process an event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HandlerError {
+    /// A failure that may succeed on another attempt (e.g. a downstream service was
+    /// briefly unreachable). Retried according to the handler's [`RetryPolicy`].
+    Transient(String),
+    /// A failure that will recur however often the event is retried.
+    Permanent(String),
+}
+
+impl fmt::Display for HandlerError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HandlerError::Transient(message) => write!(fmt, "transient: {message}"),
+            HandlerError::Permanent(message) => write!(fmt, "permanent: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for HandlerError {}
+
 /// Trait for handling domain events.
 /// Implementations can perform side effects like sending notifications,
 /// updating caches, logging, etc.
 #[async_trait]
 pub trait EventHandler: Send + Sync {
-    async fn handle(&self, event: &DomainEvent);
+    async fn handle(&self, event: &DomainEvent) -> Result<(), HandlerError>;
+
+    /// Name used when logging the handler's failures.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Whether the handler must run in the process that published the event, e.g.
+    /// because it pushes to connections held there. A publisher with an [`Outbox`] runs
+    /// these handlers itself and leaves the others to the worker.
+    fn in_process(&self) -> bool {
+        false
+    }
+}
+
+/// How often a handler is retried after a transient failure.
+///
+/// The delay before retry `n` (1-based) is `initial_backoff * 2^(n - 1)`, capped at
+/// `max_backoff`. Retries run inline in [`EventPublisher::publish`], so keep the total
+/// backoff short for handlers called on a request path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts in total, including the first; 1 disables retries.
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// A single attempt with no retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Delay before retry number `retry` (1-based).
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+        }
+    }
+}
+
+/// An event a handler failed to process: it failed permanently, or transiently on
+/// every attempt its [`RetryPolicy`] allowed.
+#[derive(Debug, Clone)]
+pub struct DeadLetter {
+    /// The [`EventHandler::name`] of the handler that failed.
+    pub handler_name: String,
+    pub event: DomainEvent,
+    /// The handler's last error.
+    pub error: HandlerError,
+    pub attempts: u32,
+}
+
+/// Keeps dead-lettered events so they can be inspected and requeued with
+/// [`EventPublisher::redeliver`].
+#[async_trait]
+pub trait DeadLetterSink: Send + Sync {
+    /// Stores `dead_letter`. Failures are the sink's to log; publishing carries on.
+    async fn record(&self, dead_letter: DeadLetter);
+}
+
+/// Durable queue of published events for another process to dispatch to its own
+/// handlers, so slow handlers don't run on the publishing path.
+#[async_trait]
+pub trait Outbox: Send + Sync {
+    /// Stores `event`, published on behalf of `actor_id`. Failures are the outbox's to
+    /// log; publishing carries on.
+    async fn enqueue(&self, event: &DomainEvent, actor_id: Option<Id>);
+}
+
+/// Receives measurements of event publishing, e.g. to export them to Prometheus.
+/// Called inline on the publishing path, so implementations must be cheap.
+pub trait Metrics: Send + Sync {
+    /// An event of `kind` was published.
+    fn event_published(&self, kind: EventKind);
+
+    /// The handler named `handler_name` finished with an event of `kind` after `elapsed`,
+    /// including retries and their backoff. `succeeded` is false when it was
+    /// dead-lettered.
+    fn handler_finished(
+        &self,
+        handler_name: &str,
+        kind: EventKind,
+        elapsed: Duration,
+        succeeded: bool,
+    );
+}
+
+/// Hooks [`EventPublisher::publish`] runs around delivering every event, for
+/// cross-cutting concerns (logging, metrics, redaction) that shouldn't each be a
+/// handler. Called inline on the publishing path, so implementations must be cheap.
+///
+/// Interceptors wrap one another like middleware: `before_publish` runs in registration
+/// order and `after_publish` in reverse. Events received through an [`EventTransport`]
+/// or redelivered aren't intercepted again.
+pub trait EventInterceptor: Send + Sync {
+    /// Called before the event reaches any handler, outbox or transport; changes made to
+    /// `event` (e.g. redacting a field) are what they receive.
+    fn before_publish(&self, _event: &mut DomainEvent) {}
+
+    /// Called once every handler has finished with `event`, `elapsed` after publishing
+    /// started.
+    fn after_publish(&self, _event: &DomainEvent, _elapsed: Duration) {}
+}
+
+/// Logs every published event at debug level, with the actor and correlation ID it was
+/// published under.
+pub struct LoggingInterceptor;
+
+impl EventInterceptor for LoggingInterceptor {
+    fn after_publish(&self, event: &DomainEvent, elapsed: Duration) {
+        let (entity_type, entity_id) = event.entity();
+        debug!(
+            "Published {} event for {entity_type} {entity_id:?} in {elapsed:?}; actor: {:?}; \
+             correlation_id: {:?}",
+            event.kind(),
+            current_actor(),
+            current_correlation_id()
+        );
+    }
+}
+
+/// First wait before [`EventPublisher::listen`] retries a failed subscribe; doubles up
+/// to [`LISTEN_BACKOFF_MAX`].
+const LISTEN_BACKOFF_MIN: Duration = Duration::from_secs(1);
+const LISTEN_BACKOFF_MAX: Duration = Duration::from_secs(30);
+
+/// A registered handler, the kinds of event it receives and how it is retried.
+#[derive(Clone)]
+struct Subscription {
+    handler: Arc<dyn EventHandler>,
+    kinds: EventKinds,
+    retry_policy: RetryPolicy,
 }
 
 /// Publishes domain events to registered handlers.
 /// Handlers are called sequentially in registration order.
 #[derive(Clone)]
 pub struct EventPublisher {
-    handlers: Arc<Vec<Arc<dyn EventHandler>>>,
+    subscriptions: Arc<Vec<Subscription>>,
+    interceptors: Arc<Vec<Arc<dyn EventInterceptor>>>,
+    dead_letters: Option<Arc<dyn DeadLetterSink>>,
+    metrics: Option<Arc<dyn Metrics>>,
+    outbox: Option<Arc<dyn Outbox>>,
+    transport: Option<Arc<dyn EventTransport>>,
 }
 
 impl EventPublisher {
     pub fn new() -> Self {
         Self {
-            handlers: Arc::new(Vec::new()),
+            subscriptions: Arc::new(Vec::new()),
+            interceptors: Arc::new(Vec::new()),
+            dead_letters: None,
+            metrics: None,
+            outbox: None,
+            transport: None,
         }
     }
 
-    /// Register a new event handler.
+    /// Send events over `transport` instead of delivering them to
+    /// [`EventHandler::in_process`] handlers; every replica's [`EventPublisher::listen`]
+    /// delivers them from there, so they reach SSE connections held by other replicas.
+    pub fn with_transport(mut self, transport: Arc<dyn EventTransport>) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Write events to `outbox` instead of delivering them to handlers that aren't
+    /// [`EventHandler::in_process`]; a worker process dispatches them from there.
+    /// Those handlers stay registered so [`EventPublisher::redeliver`] can still reach
+    /// them.
+    pub fn with_outbox(mut self, outbox: Arc<dyn Outbox>) -> Self {
+        self.outbox = Some(outbox);
+        self
+    }
+
+    /// Run `interceptor` around every publish, inside the interceptors registered before
+    /// it.
+    pub fn with_interceptor(mut self, interceptor: Arc<dyn EventInterceptor>) -> Self {
+        let mut interceptors = (*self.interceptors).clone();
+        interceptors.push(interceptor);
+        self.interceptors = Arc::new(interceptors);
+        self
+    }
+
+    /// Report publish counts and handler latencies to `metrics`.
+    pub fn with_metrics(mut self, metrics: Arc<dyn Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Send events that handlers fail to process to `sink` instead of only logging them.
+    pub fn with_dead_letters(mut self, sink: Arc<dyn DeadLetterSink>) -> Self {
+        self.dead_letters = Some(sink);
+        self
+    }
+
+    /// Register a new event handler for every kind of event, with the default
+    /// [`RetryPolicy`].
     /// Note: This creates a new publisher instance with the additional handler.
     /// Store the returned publisher in your application state.
-    pub fn with_handler(mut self, handler: Arc<dyn EventHandler>) -> Self {
-        let mut handlers = (*self.handlers).clone();
-        handlers.push(handler);
-        self.handlers = Arc::new(handlers);
+    pub fn with_handler(self, handler: Arc<dyn EventHandler>) -> Self {
+        self.subscribe(EventKinds::all(), handler, RetryPolicy::default())
+    }
+
+    /// Register a new event handler that only receives events of the given `kinds`.
+    pub fn with_handler_for(
+        self,
+        kinds: impl Into<EventKinds>,
+        handler: Arc<dyn EventHandler>,
+    ) -> Self {
+        self.subscribe(kinds.into(), handler, RetryPolicy::default())
+    }
+
+    /// Register a new event handler for every kind of event that is retried according
+    /// to `retry_policy`.
+    pub fn with_handler_policy(
+        self,
+        handler: Arc<dyn EventHandler>,
+        retry_policy: RetryPolicy,
+    ) -> Self {
+        self.subscribe(EventKinds::all(), handler, retry_policy)
+    }
+
+    /// Register a new event handler for events of the given `kinds` that is retried
+    /// according to `retry_policy`.
+    pub fn subscribe(
+        mut self,
+        kinds: EventKinds,
+        handler: Arc<dyn EventHandler>,
+        retry_policy: RetryPolicy,
+    ) -> Self {
+        let mut subscriptions = (*self.subscriptions).clone();
+        subscriptions.push(Subscription {
+            handler,
+            kinds,
+            retry_policy,
+        });
+        self.subscriptions = Arc::new(subscriptions);
         self
     }
 
-    /// Publish an event to all registered handlers.
-    /// Handlers are called sequentially. If a handler panics or errors,
-    /// we log it but continue with remaining handlers.
-    pub async fn publish(&self, event: DomainEvent) {
-        for handler in self.handlers.iter() {
-            handler.handle(&event).await;
+    /// Publish an event to the handlers subscribed to its kind.
+    /// Handlers are called sequentially. A transient failure is retried per the
+    /// handler's policy; a permanent failure, or one that outlasts its retries, is
+    /// logged and dead-lettered, and the remaining handlers still run. With an
+    /// [`Outbox`], only in-process handlers are called and the event is written to the
+    /// outbox when any other handler is subscribed to it. With an [`EventTransport`],
+    /// in-process handlers are left to [`EventPublisher::listen`]; should sending fail,
+    /// they are called here so this replica's users still get the event. Registered
+    /// [`EventInterceptor`]s run around all of this.
+    pub async fn publish(&self, mut event: DomainEvent) {
+        let started = Instant::now();
+        for interceptor in self.interceptors.iter() {
+            interceptor.before_publish(&mut event);
+        }
+        let kind = event.kind();
+        if let Some(metrics) = &self.metrics {
+            metrics.event_published(kind);
+        }
+        let mut deferred = false;
+        let mut broadcast = false;
+        for subscription in self.subscriptions.iter() {
+            if !subscription.kinds.contains(kind) {
+                continue;
+            }
+            let in_process = subscription.handler.in_process();
+            if self.transport.is_some() && in_process {
+                broadcast = true;
+                continue;
+            }
+            if self.outbox.is_some() && !in_process {
+                deferred = true;
+                continue;
+            }
+            self.run(subscription, &event).await;
+        }
+        if let Some(outbox) = self.outbox.as_ref().filter(|_| deferred) {
+            outbox.enqueue(&event, current_actor()).await;
+        }
+        if let Some(transport) = self.transport.as_ref().filter(|_| broadcast) {
+            if let Err(e) = transport.send(&event).await {
+                warn!("Failed to send {kind} event over the event transport ({e}); delivering it locally");
+                self.deliver_in_process(&event).await;
+            }
+        }
+        for interceptor in self.interceptors.iter().rev() {
+            interceptor.after_publish(&event, started.elapsed());
+        }
+    }
+
+    /// Delivers the events the [`EventTransport`] receives to the in-process handlers
+    /// subscribed to them, resubscribing whenever the subscription ends. Returns at once
+    /// without a transport; otherwise runs until the task is dropped.
+    pub async fn listen(&self) {
+        let Some(transport) = &self.transport else {
+            return;
+        };
+        let mut backoff = LISTEN_BACKOFF_MIN;
+        loop {
+            match transport.subscribe().await {
+                Ok(mut stream) => {
+                    backoff = LISTEN_BACKOFF_MIN;
+                    while let Some(envelope) = stream.next().await {
+                        let correlation_id = envelope.correlation_id;
+                        match envelope.into_event() {
+                            Ok(event) => {
+                                with_correlation_id(correlation_id, self.deliver_in_process(&event))
+                                    .await
+                            }
+                            Err(e) => warn!("Ignoring an event from the event transport: {e}"),
+                        }
+                    }
+                    warn!("Event transport subscription ended; resubscribing");
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to subscribe to the event transport ({e}); retrying in {backoff:?}"
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(LISTEN_BACKOFF_MAX);
+                }
+            }
+        }
+    }
+
+    async fn deliver_in_process(&self, event: &DomainEvent) {
+        let kind = event.kind();
+        for subscription in self.subscriptions.iter() {
+            if subscription.kinds.contains(kind) && subscription.handler.in_process() {
+                self.run(subscription, event).await;
+            }
+        }
+    }
+
+    /// Delivers `event` to the subscription's handler, dead-lettering a failure.
+    async fn run(&self, subscription: &Subscription, event: &DomainEvent) {
+        let handler = subscription.handler.as_ref();
+        let started = Instant::now();
+        let result = deliver(handler, &subscription.retry_policy, event).await;
+        if let Some(metrics) = &self.metrics {
+            metrics.handler_finished(
+                handler.name(),
+                event.kind(),
+                started.elapsed(),
+                result.is_ok(),
+            );
         }
+        if let Err((error, attempts)) = result {
+            error!(
+                "{} dropped event after {attempts} attempt(s): {error}; event: {event:?}; \
+                 correlation_id: {:?}",
+                handler.name(),
+                current_correlation_id()
+            );
+            if let Some(sink) = &self.dead_letters {
+                sink.record(DeadLetter {
+                    handler_name: handler.name().to_string(),
+                    event: event.clone(),
+                    error,
+                    attempts,
+                })
+                .await;
+            }
+        }
+    }
+
+    /// Deliver `event` again to the registered handler named `handler_name`, retrying
+    /// per its policy, without dead-lettering another failure. `None` when no handler
+    /// has that name; otherwise the handler's last error and the attempts made.
+    pub async fn redeliver(
+        &self,
+        handler_name: &str,
+        event: &DomainEvent,
+    ) -> Option<Result<(), (HandlerError, u32)>> {
+        let subscription = self
+            .subscriptions
+            .iter()
+            .find(|subscription| subscription.handler.name() == handler_name)?;
+        Some(
+            deliver(
+                subscription.handler.as_ref(),
+                &subscription.retry_policy,
+                event,
+            )
+            .await,
+        )
     }
 }
 
@@ -231,3 +1264,486 @@ impl Default for EventPublisher {
         Self::new()
     }
 }
+
+/// Calls the handler until it succeeds, fails permanently or runs out of attempts; on
+/// failure returns the last error and the attempts made.
+async fn deliver(
+    handler: &dyn EventHandler,
+    retry_policy: &RetryPolicy,
+    event: &DomainEvent,
+) -> Result<(), (HandlerError, u32)> {
+    let mut attempt = 1;
+    loop {
+        match handler.handle(event).await {
+            Ok(()) => return Ok(()),
+            Err(HandlerError::Transient(message)) if attempt < retry_policy.max_attempts => {
+                let backoff = retry_policy.backoff(attempt);
+                warn!(
+                    "{} failed attempt {attempt}/{} ({message}); retrying in {backoff:?}",
+                    handler.name(),
+                    retry_policy.max_attempts
+                );
+                tokio::time::sleep(backoff).await;
+                attempt += 1;
+            }
+            Err(e) => return Err((e, attempt)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Mutex;
+
+    /// Fails transiently until it has been called `succeed_on` times.
+    struct Flaky {
+        calls: AtomicU32,
+        succeed_on: u32,
+    }
+
+    #[async_trait]
+    impl EventHandler for Flaky {
+        async fn handle(&self, _event: &DomainEvent) -> Result<(), HandlerError> {
+            let calls = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            if calls < self.succeed_on {
+                return Err(HandlerError::Transient("unavailable".to_string()));
+            }
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct Collect(Mutex<Vec<DeadLetter>>);
+
+    #[derive(Default)]
+    struct Recorder {
+        published: Mutex<Vec<EventKind>>,
+        finished: Mutex<Vec<(String, EventKind, bool)>>,
+    }
+
+    impl Metrics for Recorder {
+        fn event_published(&self, kind: EventKind) {
+            self.published.lock().unwrap().push(kind);
+        }
+
+        fn handler_finished(
+            &self,
+            handler_name: &str,
+            kind: EventKind,
+            _elapsed: Duration,
+            succeeded: bool,
+        ) {
+            self.finished
+                .lock()
+                .unwrap()
+                .push((handler_name.to_string(), kind, succeeded));
+        }
+    }
+
+    #[async_trait]
+    impl DeadLetterSink for Collect {
+        async fn record(&self, dead_letter: DeadLetter) {
+            self.0.lock().unwrap().push(dead_letter);
+        }
+    }
+
+    /// Counts its calls; must run where events are published.
+    #[derive(Default)]
+    struct Pinned(AtomicU32);
+
+    #[async_trait]
+    impl EventHandler for Pinned {
+        async fn handle(&self, _event: &DomainEvent) -> Result<(), HandlerError> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn in_process(&self) -> bool {
+            true
+        }
+    }
+
+    fn event() -> DomainEvent {
+        DomainEvent::TopicsChanged {
+            coaching_session_id: Id::new_v4(),
+            notify_user_ids: Vec::new(),
+        }
+    }
+
+    fn fast_policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(1),
+        }
+    }
+
+    #[test]
+    fn entity_reads_the_id_of_the_carried_entity() {
+        let goal_id = Id::new_v4();
+        let event = DomainEvent::GoalUpdated {
+            coaching_relationship_id: Id::new_v4(),
+            goal: serde_json::json!({ "id": goal_id, "title": "Delegate more" }),
+        };
+
+        assert_eq!(event.kind(), EventKind::GoalUpdated);
+        assert_eq!(event.entity(), ("goal", Some(goal_id)));
+        assert!(event.coaching_relationship_id().is_some());
+        assert_eq!(event.coaching_session_id(), None);
+        assert_eq!(
+            serde_json::to_value(&event).unwrap()["type"],
+            "goal_updated"
+        );
+    }
+
+    #[tokio::test]
+    async fn current_correlation_id_is_scoped_to_with_correlation_id() {
+        let correlation_id = Id::new_v4();
+
+        assert_eq!(current_correlation_id(), None);
+        assert_eq!(
+            with_correlation_id(Some(correlation_id), async { current_correlation_id() }).await,
+            Some(correlation_id)
+        );
+    }
+
+    #[tokio::test]
+    async fn current_client_ip_is_scoped_to_with_client_ip() {
+        assert_eq!(current_client_ip(), None);
+        assert_eq!(
+            with_client_ip(Some("203.0.113.7".to_string()), async {
+                current_client_ip()
+            })
+            .await,
+            Some("203.0.113.7".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn current_actor_is_scoped_to_with_actor() {
+        let actor_id = Id::new_v4();
+
+        assert_eq!(current_actor(), None);
+        assert_eq!(
+            with_actor(actor_id, async { current_actor() }).await,
+            Some(actor_id)
+        );
+    }
+
+    #[test]
+    fn kind_names_match_the_serialized_type_tag() {
+        let event = event();
+
+        assert_eq!(
+            serde_json::to_value(&event).unwrap()["type"],
+            event.kind().as_str()
+        );
+    }
+
+    #[tokio::test]
+    async fn interceptors_wrap_publish_and_can_rewrite_the_event() {
+        /// Records its hook calls in a log shared with the other interceptors.
+        struct Traced {
+            name: &'static str,
+            log: Arc<Mutex<Vec<String>>>,
+        }
+
+        impl EventInterceptor for Traced {
+            fn before_publish(&self, event: &mut DomainEvent) {
+                self.log
+                    .lock()
+                    .unwrap()
+                    .push(format!("before {}", self.name));
+                if let DomainEvent::TopicsChanged {
+                    notify_user_ids, ..
+                } = event
+                {
+                    notify_user_ids.clear();
+                }
+            }
+
+            fn after_publish(&self, _event: &DomainEvent, _elapsed: Duration) {
+                self.log
+                    .lock()
+                    .unwrap()
+                    .push(format!("after {}", self.name));
+            }
+        }
+
+        /// Records the recipients of the events it handles.
+        #[derive(Default)]
+        struct Recipients(Mutex<Vec<usize>>);
+
+        #[async_trait]
+        impl EventHandler for Recipients {
+            async fn handle(&self, event: &DomainEvent) -> Result<(), HandlerError> {
+                if let DomainEvent::TopicsChanged {
+                    notify_user_ids, ..
+                } = event
+                {
+                    self.0.lock().unwrap().push(notify_user_ids.len());
+                }
+                Ok(())
+            }
+        }
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let recipients = Arc::new(Recipients::default());
+        let publisher = EventPublisher::new()
+            .with_interceptor(Arc::new(Traced {
+                name: "outer",
+                log: log.clone(),
+            }))
+            .with_interceptor(Arc::new(Traced {
+                name: "inner",
+                log: log.clone(),
+            }))
+            .with_handler(recipients.clone());
+
+        publisher.publish(event()).await;
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            ["before outer", "before inner", "after inner", "after outer"]
+        );
+        assert_eq!(*recipients.0.lock().unwrap(), vec![0]);
+    }
+
+    #[tokio::test]
+    async fn publish_only_delivers_subscribed_kinds() {
+        let handler = Arc::new(Flaky {
+            calls: AtomicU32::new(0),
+            succeed_on: 0,
+        });
+        let publisher = EventPublisher::new().with_handler_for(
+            EventKinds::of(&[EventKind::TopicsChanged, EventKind::GoalDeleted]),
+            handler.clone(),
+        );
+
+        publisher.publish(event()).await;
+        publisher
+            .publish(DomainEvent::TranscriptionUpdated {
+                coaching_session_id: Id::new_v4(),
+                notify_user_ids: Vec::new(),
+            })
+            .await;
+
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 1);
+        assert!(!EventKinds::from(EventKind::GoalCreated).contains(EventKind::GoalUpdated));
+        assert!(EventKind::ALL
+            .iter()
+            .all(|kind| EventKinds::all().contains(*kind)));
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let policy = RetryPolicy::default();
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(400));
+        assert_eq!(policy.backoff(10), Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn publish_retries_transient_failures_until_success() {
+        let handler = Arc::new(Flaky {
+            calls: AtomicU32::new(0),
+            succeed_on: 3,
+        });
+        let publisher = EventPublisher::new().with_handler_policy(handler.clone(), fast_policy(3));
+
+        publisher.publish(event()).await;
+
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn publish_gives_up_after_max_attempts() {
+        let handler = Arc::new(Flaky {
+            calls: AtomicU32::new(0),
+            succeed_on: u32::MAX,
+        });
+        let publisher = EventPublisher::new().with_handler_policy(handler.clone(), fast_policy(2));
+
+        publisher.publish(event()).await;
+
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn publish_reports_metrics_for_each_subscribed_handler() {
+        let handler = Arc::new(Flaky {
+            calls: AtomicU32::new(0),
+            succeed_on: 2,
+        });
+        let recorder = Arc::new(Recorder::default());
+        let publisher = EventPublisher::new()
+            .with_handler_policy(handler.clone(), fast_policy(1))
+            .with_handler_for(EventKind::GoalDeleted, handler.clone())
+            .with_metrics(recorder.clone());
+
+        publisher.publish(event()).await;
+        publisher.publish(event()).await;
+
+        assert_eq!(
+            *recorder.published.lock().unwrap(),
+            vec![EventKind::TopicsChanged, EventKind::TopicsChanged]
+        );
+        let name = handler.name().to_string();
+        assert_eq!(
+            *recorder.finished.lock().unwrap(),
+            vec![
+                (name.clone(), EventKind::TopicsChanged, false),
+                (name, EventKind::TopicsChanged, true),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn publish_dead_letters_failures_that_redeliver_can_retry() {
+        let handler = Arc::new(Flaky {
+            calls: AtomicU32::new(0),
+            succeed_on: 3,
+        });
+        let sink = Arc::new(Collect::default());
+        let publisher = EventPublisher::new()
+            .with_handler_policy(handler.clone(), fast_policy(2))
+            .with_dead_letters(sink.clone());
+
+        publisher.publish(event()).await;
+
+        let dead_letter = sink.0.lock().unwrap().pop().unwrap();
+        assert_eq!(dead_letter.handler_name, handler.name());
+        assert_eq!(dead_letter.attempts, 2);
+        assert_eq!(
+            dead_letter.error,
+            HandlerError::Transient("unavailable".to_string())
+        );
+
+        // The stored payload round-trips, and the handler succeeds on its third call.
+        let payload = serde_json::to_value(&dead_letter.event).unwrap();
+        let event: DomainEvent = serde_json::from_value(payload).unwrap();
+        assert_eq!(
+            publisher.redeliver(&dead_letter.handler_name, &event).await,
+            Some(Ok(()))
+        );
+        assert_eq!(publisher.redeliver("unknown", &event).await, None);
+        assert!(sink.0.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn publish_with_outbox_only_calls_in_process_handlers() {
+        #[derive(Default)]
+        struct Queue(Mutex<Vec<(EventKind, Option<Id>)>>);
+
+        #[async_trait]
+        impl Outbox for Queue {
+            async fn enqueue(&self, event: &DomainEvent, actor_id: Option<Id>) {
+                self.0.lock().unwrap().push((event.kind(), actor_id));
+            }
+        }
+
+        let pinned = Arc::new(Pinned::default());
+        let background = Arc::new(Flaky {
+            calls: AtomicU32::new(0),
+            succeed_on: 1,
+        });
+        let outbox = Arc::new(Queue::default());
+        let publisher = EventPublisher::new()
+            .with_handler(pinned.clone())
+            .with_handler_for(EventKind::TopicsChanged, background.clone())
+            .with_outbox(outbox.clone());
+        let actor_id = Id::new_v4();
+
+        with_actor(actor_id, publisher.publish(event())).await;
+        publisher
+            .publish(DomainEvent::GoalDeleted {
+                coaching_relationship_id: Id::new_v4(),
+                goal_id: Id::new_v4(),
+            })
+            .await;
+
+        assert_eq!(pinned.0.load(Ordering::SeqCst), 2);
+        assert_eq!(background.calls.load(Ordering::SeqCst), 0);
+        // Only the event a deferred handler subscribes to is written to the outbox.
+        assert_eq!(
+            *outbox.0.lock().unwrap(),
+            vec![(EventKind::TopicsChanged, Some(actor_id))]
+        );
+        // A deferred handler can still be redelivered to in the publishing process.
+        assert_eq!(
+            publisher.redeliver(background.name(), &event()).await,
+            Some(Ok(()))
+        );
+    }
+
+    #[tokio::test]
+    async fn publish_with_transport_leaves_in_process_handlers_to_listen() {
+        let pinned = Arc::new(Pinned::default());
+        let background = Arc::new(Flaky {
+            calls: AtomicU32::new(0),
+            succeed_on: 1,
+        });
+        let publisher = EventPublisher::new()
+            .with_handler(pinned.clone())
+            .with_handler(background.clone())
+            .with_transport(Arc::new(InProcessTransport::new()));
+        let listener = tokio::spawn({
+            let publisher = publisher.clone();
+            async move { publisher.listen().await }
+        });
+        // Let the listener subscribe.
+        tokio::task::yield_now().await;
+
+        publisher.publish(event()).await;
+
+        assert_eq!(background.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(pinned.0.load(Ordering::SeqCst), 0);
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(pinned.0.load(Ordering::SeqCst), 1);
+        listener.abort();
+    }
+
+    #[tokio::test]
+    async fn listen_delivers_with_the_publishing_request_correlation_id() {
+        /// Records the correlation ID each event is delivered with.
+        #[derive(Default)]
+        struct Correlated(Mutex<Vec<Option<Id>>>);
+
+        #[async_trait]
+        impl EventHandler for Correlated {
+            async fn handle(&self, _event: &DomainEvent) -> Result<(), HandlerError> {
+                self.0.lock().unwrap().push(current_correlation_id());
+                Ok(())
+            }
+
+            fn in_process(&self) -> bool {
+                true
+            }
+        }
+
+        let correlated = Arc::new(Correlated::default());
+        let publisher = EventPublisher::new()
+            .with_handler(correlated.clone())
+            .with_transport(Arc::new(InProcessTransport::new()));
+        let listener = tokio::spawn({
+            let publisher = publisher.clone();
+            async move { publisher.listen().await }
+        });
+        tokio::task::yield_now().await;
+        let correlation_id = Id::new_v4();
+
+        with_correlation_id(Some(correlation_id), publisher.publish(event())).await;
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+
+        assert_eq!(*correlated.0.lock().unwrap(), vec![Some(correlation_id)]);
+        listener.abort();
+    }
+}
@@ -0,0 +1,74 @@
+//! JSON Schemas of events' payloads, generated from [`DomainEvent`] so they can't drift
+//! from what's serialized. Fields are described by their doc comments.
+
+use serde_json::Value;
+use utoipa::ToSchema;
+
+use crate::{DomainEvent, EventKind};
+
+/// The schema of the `payload` of a `kind` event's [`EventEnvelope`]: the event's fields,
+/// without its `type` tag.
+///
+/// [`EventEnvelope`]: crate::EventEnvelope
+pub fn payload_schema(kind: EventKind) -> Value {
+    let (_, schema) = DomainEvent::schema();
+    let mut schema = serde_json::to_value(schema).expect("a schema serializes");
+    let mut variant = schema["oneOf"]
+        .as_array_mut()
+        .and_then(|variants| {
+            variants
+                .iter_mut()
+                .find(|variant| variant["properties"]["type"]["enum"][0] == kind.as_str())
+        })
+        .map(Value::take)
+        .expect("every kind has a variant");
+
+    if let Some(properties) = variant["properties"].as_object_mut() {
+        properties.remove("type");
+    }
+    if let Some(required) = variant["required"].as_array_mut() {
+        required.retain(|field| field != "type");
+    }
+    inline_ids(&mut variant);
+    variant
+}
+
+/// Replaces references to the `Id` alias, which a standalone schema can't resolve.
+fn inline_ids(schema: &mut Value) {
+    match schema {
+        Value::Object(fields) => {
+            if fields.get("$ref").and_then(Value::as_str) == Some("#/components/schemas/Id") {
+                fields.remove("$ref");
+                fields.insert("type".to_string(), "string".into());
+                fields.insert("format".to_string(), "uuid".into());
+            }
+            fields.values_mut().for_each(inline_ids);
+        }
+        Value::Array(items) => items.iter_mut().for_each(inline_ids),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_kind_has_a_payload_schema_of_its_fields() {
+        for kind in EventKind::ALL {
+            let schema = payload_schema(kind);
+            assert_eq!(schema["type"], "object", "{kind}");
+            assert!(schema["properties"].get("type").is_none(), "{kind}");
+        }
+
+        let schema = payload_schema(EventKind::ActionDeleted);
+        assert_eq!(
+            schema["properties"]["action_id"],
+            serde_json::json!({ "type": "string", "format": "uuid" })
+        );
+        assert_eq!(
+            schema["required"],
+            serde_json::json!(["coaching_session_id", "action_id", "notify_user_ids"])
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! Carrying published events between backend replicas.
+//!
+//! SSE connections are held by the replica the user connected to, so an event published
+//! on one replica must reach the [`EventHandler::in_process`] handlers of every replica.
+//! With an [`EventTransport`] set (see [`EventPublisher::with_transport`]), publishing
+//! sends the event over the transport instead of calling those handlers, and each
+//! replica's [`EventPublisher::listen`] delivers what it receives, its own events
+//! included. Without one, events are delivered in the publishing process only.
+//!
+//! [`InProcessTransport`] loops events back within one process; [`redis::RedisTransport`]
+//! fans them out over Redis pub/sub.
+//!
+//! [`EventHandler::in_process`]: crate::EventHandler::in_process
+//! [`EventPublisher::with_transport`]: crate::EventPublisher::with_transport
+//! [`EventPublisher::listen`]: crate::EventPublisher::listen
+
+use async_trait::async_trait;
+use chrono::Utc;
+use log::*;
+use std::fmt;
+use tokio::sync::broadcast;
+
+use crate::{DomainEvent, EventEnvelope};
+
+pub mod redis;
+
+/// Why an event couldn't be sent or received.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransportError(pub String);
+
+impl fmt::Display for TransportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for TransportError {}
+
+/// Broadcasts events to every replica subscribed to it.
+#[async_trait]
+pub trait EventTransport: Send + Sync {
+    /// Sends `event` to every subscriber, this replica's included.
+    async fn send(&self, event: &DomainEvent) -> Result<(), TransportError>;
+
+    /// Starts receiving the events sent from now on.
+    async fn subscribe(&self) -> Result<Box<dyn EventStream>, TransportError>;
+}
+
+/// The events a subscription receives, in the envelopes they were sent in so their
+/// correlation IDs survive the trip.
+#[async_trait]
+pub trait EventStream: Send {
+    /// The next event; `None` once the subscription has ended, e.g. its connection was
+    /// lost. Events sent while no subscription is open are not received.
+    async fn next(&mut self) -> Option<EventEnvelope>;
+}
+
+/// Events a subscriber of an [`InProcessTransport`] can fall behind by before it misses
+/// some.
+const IN_PROCESS_CAPACITY: usize = 1024;
+
+/// Delivers events to the subscribers in this process.
+pub struct InProcessTransport {
+    sender: broadcast::Sender<EventEnvelope>,
+}
+
+impl InProcessTransport {
+    pub fn new() -> Self {
+        Self {
+            sender: broadcast::channel(IN_PROCESS_CAPACITY).0,
+        }
+    }
+}
+
+impl Default for InProcessTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl EventTransport for InProcessTransport {
+    async fn send(&self, event: &DomainEvent) -> Result<(), TransportError> {
+        let envelope =
+            EventEnvelope::new(event, Utc::now()).map_err(|e| TransportError(e.to_string()))?;
+        // An error only means nobody is subscribed yet, which drops the event just as
+        // a broker would.
+        let _ = self.sender.send(envelope);
+        Ok(())
+    }
+
+    async fn subscribe(&self) -> Result<Box<dyn EventStream>, TransportError> {
+        Ok(Box::new(InProcessStream {
+            receiver: self.sender.subscribe(),
+        }))
+    }
+}
+
+struct InProcessStream {
+    receiver: broadcast::Receiver<EventEnvelope>,
+}
+
+#[async_trait]
+impl EventStream for InProcessStream {
+    async fn next(&mut self) -> Option<EventEnvelope> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) => return Some(event),
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    warn!("In-process event subscriber fell behind and missed {missed} event(s)");
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+}
@@ -4,6 +4,7 @@
 //! - Recording bots that join and record meetings
 //! - Speech-to-text transcription with enhancements
 //! - LLM-powered analysis and resource extraction
+//! - Text embeddings for semantic retrieval
 //!
 //! The design is provider-agnostic, enabling applications to swap between
 //! different service providers (Recall.ai, AssemblyAI, Deepgram, etc.) without
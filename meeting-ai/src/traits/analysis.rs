@@ -0,0 +1,48 @@
+//! LLM analysis provider trait.
+
+use std::pin::Pin;
+
+use crate::types::analysis::{Completion, Request, StreamChunk};
+use crate::Error;
+use async_trait::async_trait;
+use futures::stream::{self, Stream};
+
+/// Stream of [`StreamChunk`]s; ends after `StreamChunk::Done` or the first error.
+pub type CompletionStream = Pin<Box<dyn Stream<Item = Result<StreamChunk, Error>> + Send>>;
+
+/// Abstraction for LLM services that analyze coaching content (summaries, briefs, Q&A).
+///
+/// Implementations send a conversation to a hosted or self-hosted model and return its
+/// reply. Supports OpenAI-compatible gateways, Anthropic, local models and the like,
+/// so providers can be swapped for cost or data-residency reasons.
+#[async_trait]
+pub trait Provider: Send + Sync {
+    /// Run a single, non-streaming completion.
+    ///
+    /// Returns the full reply once the model has finished generating it.
+    async fn complete(&self, request: Request) -> std::result::Result<Completion, Error>;
+
+    /// Run a completion, yielding the reply as it's generated.
+    ///
+    /// The default implementation waits for [`Provider::complete`] and yields the whole
+    /// reply as a single text chunk, for providers without streaming support.
+    async fn complete_stream(
+        &self,
+        request: Request,
+    ) -> std::result::Result<CompletionStream, Error> {
+        let completion = self.complete(request).await?;
+        Ok(Box::pin(stream::iter([
+            Ok(StreamChunk::Text(completion.text.clone())),
+            Ok(StreamChunk::Done(completion)),
+        ])))
+    }
+
+    /// Model used when a request doesn't name one.
+    fn default_model(&self) -> &str;
+
+    /// Return unique identifier for this provider (e.g., "llm_gateway", "anthropic").
+    ///
+    /// Used for logging, cost tracking, and selecting providers at runtime.
+    /// Must be lowercase, alphanumeric with underscores only.
+    fn provider_id(&self) -> &str;
+}
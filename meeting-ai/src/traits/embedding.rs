@@ -0,0 +1,27 @@
+//! Text embedding provider trait.
+
+use crate::types::embedding::{Embeddings, Request};
+use crate::Error;
+use async_trait::async_trait;
+
+/// Abstraction for services that turn text into vectors for semantic search.
+///
+/// Supports hosted APIs (OpenAI and compatible gateways) as well as local models
+/// served behind the same interface. Vectors are only comparable with vectors from
+/// the same model, so callers store [`Provider::model`] alongside them.
+#[async_trait]
+pub trait Provider: Send + Sync {
+    /// Embed every input of the request.
+    ///
+    /// Returns exactly one vector per input, in the same order.
+    async fn embed(&self, request: Request) -> std::result::Result<Embeddings, Error>;
+
+    /// Model used when a request doesn't name one.
+    fn model(&self) -> &str;
+
+    /// Return unique identifier for this provider (e.g., "llm_gateway").
+    ///
+    /// Used for logging, cost tracking, and selecting providers at runtime.
+    /// Must be lowercase, alphanumeric with underscores only.
+    fn provider_id(&self) -> &str;
+}
@@ -1,4 +1,6 @@
 //! Provider trait definitions for meeting AI operations.
 
+pub mod analysis;
+pub mod embedding;
 pub mod recording_bot;
 pub mod transcription;
@@ -0,0 +1,72 @@
+//! Types for LLM analysis operations.
+
+use serde::{Deserialize, Serialize};
+
+/// Author of a message in a completion conversation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    System,
+    User,
+    Assistant,
+}
+
+/// One turn of the conversation sent to the model.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Message {
+    pub role: Role,
+    pub content: String,
+}
+
+impl Message {
+    pub fn system(content: impl Into<String>) -> Self {
+        Self {
+            role: Role::System,
+            content: content.into(),
+        }
+    }
+
+    pub fn user(content: impl Into<String>) -> Self {
+        Self {
+            role: Role::User,
+            content: content.into(),
+        }
+    }
+}
+
+/// A single completion request.
+///
+/// `model` overrides the provider's configured default. Set `json_response` when the
+/// prompt asks for a JSON object so providers that support it can enforce the format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Request {
+    pub model: Option<String>,
+    pub messages: Vec<Message>,
+    pub max_tokens: u32,
+    pub temperature: f32,
+    pub json_response: bool,
+}
+
+/// The model's reply with the token usage the provider reported, for cost tracking.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Completion {
+    pub text: String,
+    pub model: String,
+    pub input_tokens: Option<u32>,
+    pub output_tokens: Option<u32>,
+}
+
+impl Completion {
+    /// Input plus output tokens, when the provider reported both.
+    pub fn total_tokens(&self) -> Option<u32> {
+        Some(self.input_tokens? + self.output_tokens?)
+    }
+}
+
+/// A piece of a streamed completion: text deltas as the model generates them, then the
+/// assembled completion (with usage) once it has finished.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum StreamChunk {
+    Text(String),
+    Done(Completion),
+}
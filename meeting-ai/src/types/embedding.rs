@@ -0,0 +1,22 @@
+//! Types for text embedding operations.
+
+use serde::{Deserialize, Serialize};
+
+/// A batch of texts to embed.
+///
+/// `model` overrides the provider's configured default. `dimensions` asks models that
+/// support shortened embeddings for vectors of that length.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Request {
+    pub model: Option<String>,
+    pub inputs: Vec<String>,
+    pub dimensions: Option<u32>,
+}
+
+/// One vector per input, in input order, with the token usage the provider reported.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Embeddings {
+    pub vectors: Vec<Vec<f32>>,
+    pub model: String,
+    pub input_tokens: Option<u32>,
+}
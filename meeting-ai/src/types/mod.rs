@@ -1,4 +1,6 @@
 //! Type definitions for meeting AI operations.
 
+pub mod analysis;
+pub mod embedding;
 pub mod recording;
 pub mod transcription;
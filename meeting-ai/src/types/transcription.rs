@@ -116,5 +116,8 @@ pub struct Config {
     pub enable_auto_chapters: bool,
     pub enable_entity_detection: bool,
     pub language_code: Option<String>,
+    /// Domain terms to bias recognition toward (e.g. AssemblyAI word boost, Deepgram
+    /// keywords). Providers without custom vocabulary support ignore it.
+    pub custom_vocabulary: Vec<String>,
     pub provider_options: HashMap<String, String>,
 }
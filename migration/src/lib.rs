@@ -43,6 +43,57 @@ mod m20260611_000000_add_topic_deleted_at;
 mod m20260624_000000_add_archive_to_organizations;
 mod m20260624_000001_add_organizations_name_slug_unique;
 mod m20260701_000000_user_roles_org_fk_restrict;
+mod m20261014_000000_add_resource_views;
+mod m20261014_000001_add_reactions;
+mod m20261014_000002_add_mentions;
+mod m20261014_000003_add_action_series;
+mod m20261014_000004_add_action_work_logs;
+mod m20261014_000005_add_goal_templates;
+mod m20261014_000006_add_library;
+mod m20261014_000007_add_journal_entries;
+mod m20261014_000008_add_session_prep_briefs;
+mod m20261014_000009_add_ai_privacy_level_to_coaching_relationships;
+mod m20261014_000010_add_embeddings;
+mod m20261014_000011_add_theme_reports;
+mod m20261014_000012_add_organization_ai_settings;
+mod m20261014_000013_add_prompt_templates;
+mod m20261014_000014_add_organization_transcription_vocabularies;
+mod m20261014_000015_add_transcript_chapters;
+mod m20261014_000016_add_domain_events;
+mod m20261014_000017_add_conversation_metrics_to_transcriptions;
+mod m20261014_000018_add_question_quality_summaries;
+mod m20261014_000019_add_dead_letter_events;
+mod m20261014_000020_add_progress_reports;
+mod m20261014_000021_add_outbox_events;
+mod m20261014_000022_add_scheduled_events;
+mod m20261014_000023_add_event_store;
+mod m20261014_000024_add_webhook_subscriptions;
+mod m20261014_000025_add_api_usage;
+mod m20261014_000026_add_oauth_apps;
+mod m20261014_000027_add_scim_provisioning;
+mod m20261014_000028_add_push_subscriptions;
+mod m20261014_000029_add_remembered_devices;
+mod m20261014_000030_add_user_locks;
+mod m20261014_000031_add_policy_documents;
+mod m20261015_000032_add_organization_data_keys;
+mod m20261015_000033_add_note_blind_index_tokens;
+mod m20261015_000034_add_platform_settings;
+mod m20261015_000035_add_session_types;
+mod m20261015_000036_add_session_requests;
+mod m20261015_000037_add_out_of_office_and_holidays;
+mod m20261015_000038_add_carried_actions;
+mod m20261015_000039_add_legal_holds;
+mod m20261015_000040_add_session_records;
+mod m20261015_000041_add_search_vectors;
+mod m20261015_000042_add_coaching_relationship_pro_bono;
+mod m20261015_000043_add_attachments;
+mod m20261015_000044_add_warehouse_checkpoints;
+mod m20261015_000045_add_soft_delete;
+mod m20261015_000046_add_audit_entries;
+mod m20261015_000047_add_action_effort_points;
+mod m20261015_000048_add_session_pulses;
+mod m20261015_000049_add_relationship_health;
+mod m20261015_000050_add_calendar_feeds;
 
 pub struct Migrator;
 
@@ -95,6 +146,57 @@ impl MigratorTrait for Migrator {
             Box::new(m20260624_000000_add_archive_to_organizations::Migration),
             Box::new(m20260624_000001_add_organizations_name_slug_unique::Migration),
             Box::new(m20260701_000000_user_roles_org_fk_restrict::Migration),
+            Box::new(m20261014_000000_add_resource_views::Migration),
+            Box::new(m20261014_000001_add_reactions::Migration),
+            Box::new(m20261014_000002_add_mentions::Migration),
+            Box::new(m20261014_000003_add_action_series::Migration),
+            Box::new(m20261014_000004_add_action_work_logs::Migration),
+            Box::new(m20261014_000005_add_goal_templates::Migration),
+            Box::new(m20261014_000006_add_library::Migration),
+            Box::new(m20261014_000007_add_journal_entries::Migration),
+            Box::new(m20261014_000008_add_session_prep_briefs::Migration),
+            Box::new(m20261014_000009_add_ai_privacy_level_to_coaching_relationships::Migration),
+            Box::new(m20261014_000010_add_embeddings::Migration),
+            Box::new(m20261014_000011_add_theme_reports::Migration),
+            Box::new(m20261014_000012_add_organization_ai_settings::Migration),
+            Box::new(m20261014_000013_add_prompt_templates::Migration),
+            Box::new(m20261014_000014_add_organization_transcription_vocabularies::Migration),
+            Box::new(m20261014_000015_add_transcript_chapters::Migration),
+            Box::new(m20261014_000016_add_domain_events::Migration),
+            Box::new(m20261014_000017_add_conversation_metrics_to_transcriptions::Migration),
+            Box::new(m20261014_000018_add_question_quality_summaries::Migration),
+            Box::new(m20261014_000019_add_dead_letter_events::Migration),
+            Box::new(m20261014_000020_add_progress_reports::Migration),
+            Box::new(m20261014_000021_add_outbox_events::Migration),
+            Box::new(m20261014_000022_add_scheduled_events::Migration),
+            Box::new(m20261014_000023_add_event_store::Migration),
+            Box::new(m20261014_000024_add_webhook_subscriptions::Migration),
+            Box::new(m20261014_000025_add_api_usage::Migration),
+            Box::new(m20261014_000026_add_oauth_apps::Migration),
+            Box::new(m20261014_000027_add_scim_provisioning::Migration),
+            Box::new(m20261014_000028_add_push_subscriptions::Migration),
+            Box::new(m20261014_000029_add_remembered_devices::Migration),
+            Box::new(m20261014_000030_add_user_locks::Migration),
+            Box::new(m20261014_000031_add_policy_documents::Migration),
+            Box::new(m20261015_000032_add_organization_data_keys::Migration),
+            Box::new(m20261015_000033_add_note_blind_index_tokens::Migration),
+            Box::new(m20261015_000034_add_platform_settings::Migration),
+            Box::new(m20261015_000035_add_session_types::Migration),
+            Box::new(m20261015_000036_add_session_requests::Migration),
+            Box::new(m20261015_000037_add_out_of_office_and_holidays::Migration),
+            Box::new(m20261015_000038_add_carried_actions::Migration),
+            Box::new(m20261015_000039_add_legal_holds::Migration),
+            Box::new(m20261015_000040_add_session_records::Migration),
+            Box::new(m20261015_000041_add_search_vectors::Migration),
+            Box::new(m20261015_000042_add_coaching_relationship_pro_bono::Migration),
+            Box::new(m20261015_000043_add_attachments::Migration),
+            Box::new(m20261015_000044_add_warehouse_checkpoints::Migration),
+            Box::new(m20261015_000045_add_soft_delete::Migration),
+            Box::new(m20261015_000046_add_audit_entries::Migration),
+            Box::new(m20261015_000047_add_action_effort_points::Migration),
+            Box::new(m20261015_000048_add_session_pulses::Migration),
+            Box::new(m20261015_000049_add_relationship_health::Migration),
+            Box::new(m20261015_000050_add_calendar_feeds::Migration),
         ]
     }
 }
@@ -0,0 +1,63 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Kinds of records a user can mark as seen. Coaching sessions keep their
+        // dedicated `coaching_session_views` marker; the value exists so one enum
+        // can name any session-scoped resource.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TYPE refactor_platform.resource_type AS ENUM ('coaching_session', 'action', 'note')",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TYPE refactor_platform.resource_type OWNER TO refactor")
+            .await?;
+
+        // Per-(user, resource) "last viewed at" marker. The UNIQUE constraint is
+        // the upsert conflict target and serves the per-user batch lookup.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.resource_views (
+                    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                    user_id UUID NOT NULL REFERENCES refactor_platform.users(id) ON DELETE CASCADE,
+                    resource_type refactor_platform.resource_type NOT NULL,
+                    resource_id UUID NOT NULL,
+                    last_viewed_at TIMESTAMPTZ NOT NULL,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    CONSTRAINT uq_resource_views_user_resource UNIQUE (user_id, resource_type, resource_id)
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.resource_views OWNER TO refactor")
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.resource_views")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TYPE IF EXISTS refactor_platform.resource_type")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // One row per (user, target, emoji). Targets reuse `resource_type`; the
+        // domain layer only accepts actions and notes.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.reactions (
+                    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                    user_id UUID NOT NULL REFERENCES refactor_platform.users(id) ON DELETE CASCADE,
+                    resource_type refactor_platform.resource_type NOT NULL,
+                    resource_id UUID NOT NULL,
+                    emoji VARCHAR(32) NOT NULL,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    CONSTRAINT uq_reactions_user_resource_emoji UNIQUE (user_id, resource_type, resource_id, emoji)
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.reactions OWNER TO refactor")
+            .await?;
+
+        // Serves the per-target aggregation used when serializing notes and actions.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_reactions_resource
+                 ON refactor_platform.reactions (resource_type, resource_id)",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.reactions")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // One row per user @-mentioned in a note or action body. Rows are reconciled
+        // against the parsed body on every save; the UNIQUE constraint is the
+        // conflict target that keeps re-saves from re-notifying.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.mentions (
+                    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                    resource_type refactor_platform.resource_type NOT NULL,
+                    resource_id UUID NOT NULL,
+                    coaching_session_id UUID NOT NULL REFERENCES refactor_platform.coaching_sessions(id) ON DELETE CASCADE,
+                    mentioned_user_id UUID NOT NULL REFERENCES refactor_platform.users(id) ON DELETE CASCADE,
+                    author_user_id UUID NOT NULL REFERENCES refactor_platform.users(id) ON DELETE CASCADE,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    CONSTRAINT uq_mentions_resource_user UNIQUE (resource_type, resource_id, mentioned_user_id)
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.mentions OWNER TO refactor")
+            .await?;
+
+        // Serves the mentioned user's newest-first mention feed.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_mentions_mentioned_user_created_at
+                 ON refactor_platform.mentions (mentioned_user_id, created_at DESC)",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.mentions")
+            .await?;
+
+        Ok(())
+    }
+}
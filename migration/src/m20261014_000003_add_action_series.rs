@@ -0,0 +1,105 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // An action_series owns the recurrence rule for a repeating action and groups
+        // its instances. Unlike coaching_session_series, instances are generated one at
+        // a time (on completion or when the period rolls over), so each action links
+        // back to the instance it was generated from via previous_action_id.
+        //
+        // ON DELETE CASCADE from coaching_relationships matches coaching_session_series.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.action_series (
+                    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                    coaching_relationship_id UUID NOT NULL
+                        REFERENCES refactor_platform.coaching_relationships(id) ON DELETE CASCADE,
+                    rule JSONB NOT NULL,
+                    created_by_user_id UUID NOT NULL
+                        REFERENCES refactor_platform.users(id) ON DELETE RESTRICT,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.action_series OWNER TO refactor")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS action_series_relationship_idx
+                 ON refactor_platform.action_series(coaching_relationship_id)",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.actions
+                 ADD COLUMN action_series_id UUID
+                     REFERENCES refactor_platform.action_series(id) ON DELETE SET NULL,
+                 ADD COLUMN previous_action_id UUID
+                     REFERENCES refactor_platform.actions(id) ON DELETE SET NULL",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS actions_series_idx
+                 ON refactor_platform.actions(action_series_id)",
+            )
+            .await?;
+
+        // At most one successor per instance: the generator's conflict target, so a
+        // completion racing the roll-over job can't create the next instance twice.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE UNIQUE INDEX IF NOT EXISTS actions_previous_action_id_key
+                 ON refactor_platform.actions(previous_action_id)",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "DROP INDEX IF EXISTS refactor_platform.actions_previous_action_id_key",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP INDEX IF EXISTS refactor_platform.actions_series_idx")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.actions
+                 DROP COLUMN IF EXISTS previous_action_id,
+                 DROP COLUMN IF EXISTS action_series_id",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.action_series")
+            .await?;
+
+        Ok(())
+    }
+}
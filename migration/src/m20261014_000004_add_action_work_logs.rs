@@ -0,0 +1,62 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Focused work logged against an action, either timed (start/stop) or entered
+        // after the fact. A NULL duration means the timer is still running.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.action_work_logs (
+                    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                    action_id UUID NOT NULL REFERENCES refactor_platform.actions(id) ON DELETE CASCADE,
+                    user_id UUID NOT NULL REFERENCES refactor_platform.users(id) ON DELETE CASCADE,
+                    started_at TIMESTAMPTZ NOT NULL,
+                    duration_seconds INTEGER CHECK (duration_seconds > 0),
+                    note TEXT,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.action_work_logs OWNER TO refactor")
+            .await?;
+
+        // Serves the per-action rollups used when serializing actions and goal progress.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_action_work_logs_action
+                 ON refactor_platform.action_work_logs (action_id)",
+            )
+            .await?;
+
+        // One running timer per user.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE UNIQUE INDEX IF NOT EXISTS uq_action_work_logs_running_user
+                 ON refactor_platform.action_work_logs (user_id)
+                 WHERE duration_seconds IS NULL",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.action_work_logs")
+            .await?;
+
+        Ok(())
+    }
+}
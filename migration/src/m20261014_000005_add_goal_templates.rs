@@ -0,0 +1,58 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Reusable goals ("Improve delegation") shared across an organization. A template
+        // carries the goal's structure (title, body, target duration) and its milestones,
+        // which become actions when the template is instantiated in a relationship.
+        // usage_count/last_used_at are bumped on each instantiation for admin reporting.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.goal_templates (
+                    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                    organization_id UUID NOT NULL
+                        REFERENCES refactor_platform.organizations(id) ON DELETE CASCADE,
+                    title VARCHAR(255) NOT NULL,
+                    body TEXT,
+                    target_duration_days INTEGER CHECK (target_duration_days > 0),
+                    milestones JSONB NOT NULL DEFAULT '[]'::jsonb,
+                    created_by_user_id UUID NOT NULL
+                        REFERENCES refactor_platform.users(id) ON DELETE RESTRICT,
+                    usage_count INTEGER NOT NULL DEFAULT 0,
+                    last_used_at TIMESTAMPTZ,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.goal_templates OWNER TO refactor")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_goal_templates_organization
+                 ON refactor_platform.goal_templates (organization_id)",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.goal_templates")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TYPE refactor_platform.library_item_kind AS ENUM ('article', 'exercise')",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TYPE refactor_platform.library_item_kind OWNER TO refactor")
+            .await?;
+
+        // An organization's content library. An item carries inline content, a link to
+        // external content, or both. Tags are a JSONB array of strings so items can be
+        // filtered with a containment (@>) query.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.library_items (
+                    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                    organization_id UUID NOT NULL
+                        REFERENCES refactor_platform.organizations(id) ON DELETE CASCADE,
+                    kind refactor_platform.library_item_kind NOT NULL,
+                    title VARCHAR(255) NOT NULL,
+                    body TEXT,
+                    url TEXT,
+                    tags JSONB NOT NULL DEFAULT '[]'::jsonb,
+                    created_by_user_id UUID NOT NULL
+                        REFERENCES refactor_platform.users(id) ON DELETE RESTRICT,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    CONSTRAINT library_items_content_check CHECK (body IS NOT NULL OR url IS NOT NULL)
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.library_items OWNER TO refactor")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_library_items_organization
+                 ON refactor_platform.library_items (organization_id)",
+            )
+            .await?;
+
+        // A library item assigned to a relationship's coachee. completed_at is NULL
+        // until the assignment is marked done.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.library_assignments (
+                    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                    library_item_id UUID NOT NULL
+                        REFERENCES refactor_platform.library_items(id) ON DELETE CASCADE,
+                    coaching_relationship_id UUID NOT NULL
+                        REFERENCES refactor_platform.coaching_relationships(id) ON DELETE CASCADE,
+                    assigned_by_user_id UUID NOT NULL
+                        REFERENCES refactor_platform.users(id) ON DELETE CASCADE,
+                    due_by TIMESTAMPTZ,
+                    completed_at TIMESTAMPTZ,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.library_assignments OWNER TO refactor",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_library_assignments_relationship
+                 ON refactor_platform.library_assignments (coaching_relationship_id)",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.library_assignments")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.library_items")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TYPE IF EXISTS refactor_platform.library_item_kind")
+            .await?;
+
+        Ok(())
+    }
+}
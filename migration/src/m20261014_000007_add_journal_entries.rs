@@ -0,0 +1,54 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // A coachee's private journal within a coaching relationship, written between
+        // sessions. Entries are visible to the relationship's coach only once
+        // shared_with_coach is set.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.journal_entries (
+                    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                    coaching_relationship_id UUID NOT NULL
+                        REFERENCES refactor_platform.coaching_relationships(id) ON DELETE CASCADE,
+                    user_id UUID NOT NULL
+                        REFERENCES refactor_platform.users(id) ON DELETE CASCADE,
+                    title VARCHAR(255),
+                    body TEXT NOT NULL,
+                    shared_with_coach BOOLEAN NOT NULL DEFAULT FALSE,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.journal_entries OWNER TO refactor")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_journal_entries_relationship_created
+                 ON refactor_platform.journal_entries (coaching_relationship_id, created_at DESC)",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.journal_entries")
+            .await?;
+
+        Ok(())
+    }
+}
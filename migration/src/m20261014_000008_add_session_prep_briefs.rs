@@ -0,0 +1,46 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Cached AI-generated part of a coaching session's prep brief (at most one per
+        // session). The rest of the brief is read live, so only the costly LLM output
+        // is stored. focus_questions is a JSONB array of strings.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.session_prep_briefs (
+                    coaching_session_id UUID PRIMARY KEY
+                        REFERENCES refactor_platform.coaching_sessions(id) ON DELETE CASCADE,
+                    last_session_summary TEXT,
+                    focus_questions JSONB NOT NULL DEFAULT '[]'::jsonb,
+                    model VARCHAR(255) NOT NULL,
+                    generated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.session_prep_briefs OWNER TO refactor",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.session_prep_briefs")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TYPE refactor_platform.ai_privacy_level AS ENUM ('disabled', 'notes_only', 'full')",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TYPE refactor_platform.ai_privacy_level OWNER TO refactor")
+            .await?;
+
+        // How much of a relationship's content may be sent to the LLM provider. Existing
+        // relationships keep today's behavior (`full`, transcripts included).
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.coaching_relationships
+                    ADD COLUMN IF NOT EXISTS ai_privacy_level refactor_platform.ai_privacy_level
+                        NOT NULL DEFAULT 'full'",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.coaching_relationships DROP COLUMN IF EXISTS ai_privacy_level",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TYPE IF EXISTS refactor_platform.ai_privacy_level")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DO $$ BEGIN
+                    CREATE TYPE refactor_platform.embedding_source_type AS ENUM ('note', 'transcript_segment');
+                EXCEPTION
+                    WHEN duplicate_object THEN null;
+                END $$;
+                "#,
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TYPE refactor_platform.embedding_source_type OWNER TO refactor",
+            )
+            .await?;
+
+        // Embedding calls are priced separately from (and far below) completion tokens.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TYPE refactor_platform.cost_metric ADD VALUE IF NOT EXISTS 'embedding_tokens'",
+            )
+            .await?;
+
+        // Vectors for semantic retrieval, one row per embedded note or transcript segment.
+        // `content` is the text that was embedded, so search results need no join back
+        // to the source. Searches are always scoped to one relationship, whose few
+        // thousand rows are scanned exactly, so there is no approximate (HNSW) index.
+        //
+        // pgvector isn't installed everywhere (local dev and PR previews run stock
+        // Postgres), so the table is only created where the extension is available.
+        // The application checks for the table and falls back to keyword retrieval.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DO $$ BEGIN
+                    IF EXISTS (SELECT 1 FROM pg_available_extensions WHERE name = 'vector') THEN
+                        CREATE EXTENSION IF NOT EXISTS vector SCHEMA refactor_platform;
+
+                        CREATE TABLE IF NOT EXISTS refactor_platform.embeddings (
+                            id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                            source_type refactor_platform.embedding_source_type NOT NULL,
+                            source_id UUID NOT NULL,
+                            coaching_relationship_id UUID NOT NULL
+                                REFERENCES refactor_platform.coaching_relationships(id) ON DELETE CASCADE,
+                            coaching_session_id UUID NOT NULL
+                                REFERENCES refactor_platform.coaching_sessions(id) ON DELETE CASCADE,
+                            content TEXT NOT NULL,
+                            model VARCHAR(255) NOT NULL,
+                            embedding refactor_platform.vector(1536) NOT NULL,
+                            created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                            updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                            UNIQUE (source_type, source_id)
+                        );
+
+                        CREATE INDEX IF NOT EXISTS embeddings_coaching_relationship_id_idx
+                            ON refactor_platform.embeddings (coaching_relationship_id);
+
+                        ALTER TABLE refactor_platform.embeddings OWNER TO refactor;
+                    ELSE
+                        RAISE NOTICE 'pgvector is not available; skipping the embeddings table';
+                    END IF;
+                END $$;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.embeddings")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TYPE IF EXISTS refactor_platform.embedding_source_type")
+            .await?;
+
+        // Postgres can't drop an enum value; an unused 'embedding_tokens' is harmless.
+        // The vector extension is left installed as other schemas may rely on it.
+
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // The latest AI-generated cross-session themes report of a coaching relationship
+        // (at most one per relationship). recurring_challenges and progress_arcs are JSONB
+        // arrays of {title, summary, session_dates}. session_count and covered_through
+        // record which sessions the report was generated from, to detect newer ones.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.theme_reports (
+                    coaching_relationship_id UUID PRIMARY KEY
+                        REFERENCES refactor_platform.coaching_relationships(id) ON DELETE CASCADE,
+                    overview TEXT,
+                    recurring_challenges JSONB NOT NULL DEFAULT '[]'::jsonb,
+                    progress_arcs JSONB NOT NULL DEFAULT '[]'::jsonb,
+                    session_count INTEGER NOT NULL,
+                    covered_through TIMESTAMP NOT NULL,
+                    model VARCHAR(255) NOT NULL,
+                    generated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.theme_reports OWNER TO refactor")
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.theme_reports")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // An organization's AI configuration (at most one row per organization; none means
+        // the platform defaults). allowed_providers and allowed_models are JSONB arrays of
+        // ids, empty meaning unrestricted. tasks is a JSONB object with an optional
+        // {model, temperature, max_tokens} per task type (extraction, summary, chat).
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.organization_ai_settings (
+                    organization_id UUID PRIMARY KEY
+                        REFERENCES refactor_platform.organizations(id) ON DELETE CASCADE,
+                    allowed_providers JSONB NOT NULL DEFAULT '[]'::jsonb,
+                    allowed_models JSONB NOT NULL DEFAULT '[]'::jsonb,
+                    tasks JSONB NOT NULL DEFAULT '{}'::jsonb,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.organization_ai_settings OWNER TO refactor",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.organization_ai_settings")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DO $$ BEGIN
+                    CREATE TYPE refactor_platform.prompt_key AS ENUM ('session_prep', 'themes_report', 'ai_chat');
+                EXCEPTION
+                    WHEN duplicate_object THEN null;
+                END $$;
+                "#,
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TYPE refactor_platform.prompt_key OWNER TO refactor")
+            .await?;
+
+        // Edited versions of the system prompts AI features send, numbered per key from 1.
+        // Rows are never changed apart from is_active; at most one version per key is
+        // active, and with none active the feature uses its built-in prompt (version 0).
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.prompt_templates (
+                    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                    key refactor_platform.prompt_key NOT NULL,
+                    version INTEGER NOT NULL CHECK (version > 0),
+                    body TEXT NOT NULL,
+                    is_active BOOLEAN NOT NULL DEFAULT false,
+                    created_by_user_id UUID NOT NULL
+                        REFERENCES refactor_platform.users(id) ON DELETE RESTRICT,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    UNIQUE (key, version)
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE UNIQUE INDEX IF NOT EXISTS prompt_templates_active_key_idx
+                    ON refactor_platform.prompt_templates (key) WHERE is_active",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.prompt_templates OWNER TO refactor")
+            .await?;
+
+        // The prompt version each stored AI output was generated with; NULL for output
+        // generated before prompts were versioned.
+        for table in ["session_prep_briefs", "theme_reports"] {
+            manager
+                .get_connection()
+                .execute_unprepared(&format!(
+                    "ALTER TABLE refactor_platform.{table} ADD COLUMN IF NOT EXISTS prompt_version INTEGER"
+                ))
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in ["session_prep_briefs", "theme_reports"] {
+            manager
+                .get_connection()
+                .execute_unprepared(&format!(
+                    "ALTER TABLE refactor_platform.{table} DROP COLUMN IF EXISTS prompt_version"
+                ))
+                .await?;
+        }
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.prompt_templates")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TYPE IF EXISTS refactor_platform.prompt_key")
+            .await?;
+
+        Ok(())
+    }
+}
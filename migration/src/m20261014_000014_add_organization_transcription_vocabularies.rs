@@ -0,0 +1,45 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Domain terms (acronyms, product and people names) an organization wants
+        // transcription providers to recognize, as a JSONB array of strings. At most one
+        // row per organization; none means no custom vocabulary.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.organization_transcription_vocabularies (
+                    organization_id UUID PRIMARY KEY
+                        REFERENCES refactor_platform.organizations(id) ON DELETE CASCADE,
+                    terms JSONB NOT NULL DEFAULT '[]'::jsonb,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.organization_transcription_vocabularies OWNER TO refactor",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "DROP TABLE IF EXISTS refactor_platform.organization_transcription_vocabularies",
+            )
+            .await?;
+
+        Ok(())
+    }
+}
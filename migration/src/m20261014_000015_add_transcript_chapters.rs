@@ -0,0 +1,57 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Topical chapters the transcription provider detected, with their offsets into
+        // the recording, for navigating long sessions.
+        let create_table_sql = r#"
+            CREATE TABLE IF NOT EXISTS refactor_platform.transcript_chapters (
+                id               UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                transcription_id UUID NOT NULL
+                    REFERENCES refactor_platform.transcriptions(id) ON DELETE CASCADE,
+                title            TEXT NOT NULL,
+                summary          TEXT NOT NULL,
+                gist             TEXT NOT NULL,
+                start_ms         INTEGER NOT NULL,
+                end_ms           INTEGER NOT NULL,
+                created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW()
+            )
+        "#;
+
+        manager
+            .get_connection()
+            .execute_unprepared(create_table_sql)
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.transcript_chapters OWNER TO refactor",
+            )
+            .await?;
+
+        // Supports ordered fetch for chapter navigation
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_transcript_chapters_transcription_start \
+                 ON refactor_platform.transcript_chapters(transcription_id, start_ms ASC)",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.transcript_chapters")
+            .await?;
+
+        Ok(())
+    }
+}
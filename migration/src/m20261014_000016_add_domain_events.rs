@@ -0,0 +1,63 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Append-only log of every domain event, for debugging and compliance. Deleting a
+        // user clears the actor of their events rather than deleting the events.
+        let create_table_sql = r#"
+            CREATE TABLE IF NOT EXISTS refactor_platform.domain_events (
+                id            UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                event_type    VARCHAR(64) NOT NULL,
+                entity_type   VARCHAR(64) NOT NULL,
+                entity_id     UUID,
+                actor_user_id UUID
+                    REFERENCES refactor_platform.users(id) ON DELETE SET NULL,
+                payload       JSONB NOT NULL,
+                created_at    TIMESTAMPTZ NOT NULL DEFAULT NOW()
+            )
+        "#;
+
+        manager
+            .get_connection()
+            .execute_unprepared(create_table_sql)
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.domain_events OWNER TO refactor")
+            .await?;
+
+        // Supports filtering by entity type within a date range
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_domain_events_entity_type_created_at \
+                 ON refactor_platform.domain_events(entity_type, created_at DESC)",
+            )
+            .await?;
+
+        // Supports listing all events within a date range
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_domain_events_created_at \
+                 ON refactor_platform.domain_events(created_at DESC)",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.domain_events")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Talk time, interruptions and questions per speaker, computed from the
+        // transcript's segments. NULL until computed; older transcriptions are backfilled
+        // when their metrics are first requested.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.transcriptions
+                    ADD COLUMN IF NOT EXISTS conversation_metrics JSONB",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.transcriptions DROP COLUMN IF EXISTS conversation_metrics",
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TYPE refactor_platform.prompt_key ADD VALUE IF NOT EXISTS 'question_quality'",
+            )
+            .await?;
+
+        // The coach's question and advice counts in a session's transcript, one row per
+        // session. Regenerated when the session gets a newer transcription.
+        let create_table_sql = r#"
+            CREATE TABLE IF NOT EXISTS refactor_platform.question_quality_summaries (
+                coaching_session_id   UUID PRIMARY KEY
+                    REFERENCES refactor_platform.coaching_sessions(id) ON DELETE CASCADE,
+                transcription_id      UUID NOT NULL
+                    REFERENCES refactor_platform.transcriptions(id) ON DELETE CASCADE,
+                coach_speaker_label   TEXT,
+                utterance_count       INTEGER NOT NULL DEFAULT 0,
+                open_question_count   INTEGER NOT NULL DEFAULT 0,
+                closed_question_count INTEGER NOT NULL DEFAULT 0,
+                advice_count          INTEGER NOT NULL DEFAULT 0,
+                inquiry_count         INTEGER NOT NULL DEFAULT 0,
+                model                 VARCHAR(255) NOT NULL,
+                prompt_version        INTEGER,
+                generated_at          TIMESTAMPTZ NOT NULL,
+                created_at            TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                updated_at            TIMESTAMPTZ NOT NULL DEFAULT NOW()
+            )
+        "#;
+
+        manager
+            .get_connection()
+            .execute_unprepared(create_table_sql)
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.question_quality_summaries OWNER TO refactor",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Postgres can't drop an enum value, so 'question_quality' stays on prompt_key.
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.question_quality_summaries")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Events an event handler failed to process, kept until a SuperAdmin requeues
+        // them. `requeued_at` is set once a requeue succeeds; failed requeues update
+        // `error` and add to `attempts`.
+        let create_table_sql = r#"
+            CREATE TABLE IF NOT EXISTS refactor_platform.dead_letter_events (
+                id            UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                handler_name  VARCHAR(255) NOT NULL,
+                event_type    VARCHAR(64) NOT NULL,
+                payload       JSONB NOT NULL,
+                error         TEXT NOT NULL,
+                attempts      INTEGER NOT NULL,
+                requeue_count INTEGER NOT NULL DEFAULT 0,
+                requeued_at   TIMESTAMPTZ,
+                created_at    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                updated_at    TIMESTAMPTZ NOT NULL DEFAULT NOW()
+            )
+        "#;
+
+        manager
+            .get_connection()
+            .execute_unprepared(create_table_sql)
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.dead_letter_events OWNER TO refactor",
+            )
+            .await?;
+
+        // Supports listing a handler's dead letters, newest first
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_dead_letter_events_handler_created_at \
+                 ON refactor_platform.dead_letter_events(handler_name, created_at DESC)",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.dead_letter_events")
+            .await?;
+
+        Ok(())
+    }
+}
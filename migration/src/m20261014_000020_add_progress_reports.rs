@@ -0,0 +1,89 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TYPE refactor_platform.prompt_key ADD VALUE IF NOT EXISTS 'progress_report'",
+            )
+            .await?;
+
+        // Coaches' opt-in to the weekly progress email; no row means opted out.
+        let create_settings_sql = r#"
+            CREATE TABLE IF NOT EXISTS refactor_platform.progress_report_settings (
+                user_id              UUID PRIMARY KEY
+                    REFERENCES refactor_platform.users(id) ON DELETE CASCADE,
+                weekly_email_enabled BOOLEAN NOT NULL DEFAULT FALSE,
+                created_at           TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                updated_at           TIMESTAMPTZ NOT NULL DEFAULT NOW()
+            )
+        "#;
+
+        manager
+            .get_connection()
+            .execute_unprepared(create_settings_sql)
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.progress_report_settings OWNER TO refactor",
+            )
+            .await?;
+
+        // One AI-written progress narrative per relationship per week, kept after it's
+        // emailed. `emailed_at` stays NULL until the email has been sent, so a failed
+        // send is retried on the next pass.
+        let create_reports_sql = r#"
+            CREATE TABLE IF NOT EXISTS refactor_platform.progress_reports (
+                id                       UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                coaching_relationship_id UUID NOT NULL
+                    REFERENCES refactor_platform.coaching_relationships(id) ON DELETE CASCADE,
+                period_start             DATE NOT NULL,
+                period_end               DATE NOT NULL,
+                narrative                TEXT NOT NULL,
+                session_count            INTEGER NOT NULL DEFAULT 0,
+                open_action_count        INTEGER NOT NULL DEFAULT 0,
+                model                    VARCHAR(255) NOT NULL,
+                prompt_version           INTEGER,
+                generated_at             TIMESTAMPTZ NOT NULL,
+                emailed_at               TIMESTAMPTZ,
+                created_at               TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                updated_at               TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                UNIQUE (coaching_relationship_id, period_start)
+            )
+        "#;
+
+        manager
+            .get_connection()
+            .execute_unprepared(create_reports_sql)
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.progress_reports OWNER TO refactor")
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Postgres can't drop an enum value, so 'progress_report' stays on prompt_key.
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.progress_reports")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.progress_report_settings")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Events the web process published for the events worker to dispatch. A worker
+        // claims a batch by setting `locked_by` and `locked_until`; a claim that lapses
+        // (the worker stopped) can be taken by another. `dispatched_at` is set once the
+        // event has been delivered to the worker's handlers.
+        let create_table_sql = r#"
+            CREATE TABLE IF NOT EXISTS refactor_platform.outbox_events (
+                id            UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                event_type    VARCHAR(64) NOT NULL,
+                payload       JSONB NOT NULL,
+                actor_id      UUID,
+                attempts      INTEGER NOT NULL DEFAULT 0,
+                locked_by     VARCHAR(255),
+                locked_until  TIMESTAMPTZ,
+                dispatched_at TIMESTAMPTZ,
+                created_at    TIMESTAMPTZ NOT NULL DEFAULT NOW()
+            )
+        "#;
+
+        manager
+            .get_connection()
+            .execute_unprepared(create_table_sql)
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.outbox_events OWNER TO refactor")
+            .await?;
+
+        // Supports claiming the oldest undispatched events
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_outbox_events_pending \
+                 ON refactor_platform.outbox_events(created_at) WHERE dispatched_at IS NULL",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.outbox_events")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Domain events to publish at a later time. The scheduler claims due events by
+        // setting `locked_until`, so a process that stops mid-publish leaves them to be
+        // claimed again; `published_at` is set once the event has been published.
+        let create_table_sql = r#"
+            CREATE TABLE IF NOT EXISTS refactor_platform.scheduled_events (
+                id           UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                event_type   VARCHAR(64) NOT NULL,
+                payload      JSONB NOT NULL,
+                actor_id     UUID,
+                publish_at   TIMESTAMPTZ NOT NULL,
+                attempts     INTEGER NOT NULL DEFAULT 0,
+                locked_until TIMESTAMPTZ,
+                published_at TIMESTAMPTZ,
+                created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+            )
+        "#;
+
+        manager
+            .get_connection()
+            .execute_unprepared(create_table_sql)
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.scheduled_events OWNER TO refactor")
+            .await?;
+
+        // Supports claiming the events that are due
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_scheduled_events_due \
+                 ON refactor_platform.scheduled_events(publish_at) WHERE published_at IS NULL",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.scheduled_events")
+            .await?;
+
+        Ok(())
+    }
+}
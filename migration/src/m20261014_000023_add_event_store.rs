@@ -0,0 +1,224 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Append-only store of every domain event, replacing the domain_events log. Rows
+        // are partitioned by month so expired months are dropped whole; the primary key
+        // leads with `created_at` both because partition keys must be part of it and to
+        // page through events newest first. Events are kept after their relationship or
+        // session is deleted, for the audit log.
+        let create_table_sql = r#"
+            CREATE TABLE IF NOT EXISTS refactor_platform.event_store (
+                id                       UUID NOT NULL DEFAULT gen_random_uuid(),
+                event_type               VARCHAR(64) NOT NULL,
+                entity_type              VARCHAR(64) NOT NULL,
+                entity_id                UUID,
+                coaching_relationship_id UUID,
+                coaching_session_id      UUID,
+                actor_user_id            UUID
+                    REFERENCES refactor_platform.users(id) ON DELETE SET NULL,
+                payload                  JSONB NOT NULL,
+                created_at               TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                PRIMARY KEY (created_at, id)
+            ) PARTITION BY RANGE (created_at)
+        "#;
+
+        manager
+            .get_connection()
+            .execute_unprepared(create_table_sql)
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.event_store OWNER TO refactor")
+            .await?;
+
+        // Creates the partition holding `month`'s events, named event_store_YYYY_MM. The
+        // application calls it ahead of each month.
+        let create_partition_function_sql = r#"
+            CREATE OR REPLACE FUNCTION refactor_platform.create_event_store_partition(month DATE)
+            RETURNS VOID AS $$
+            DECLARE
+                start_date DATE := date_trunc('month', month)::DATE;
+                end_date   DATE := (date_trunc('month', month) + INTERVAL '1 month')::DATE;
+            BEGIN
+                EXECUTE format(
+                    'CREATE TABLE IF NOT EXISTS refactor_platform.%I '
+                    'PARTITION OF refactor_platform.event_store FOR VALUES FROM (%L) TO (%L)',
+                    'event_store_' || to_char(start_date, 'YYYY_MM'),
+                    start_date,
+                    end_date
+                );
+            END;
+            $$ LANGUAGE plpgsql
+        "#;
+
+        manager
+            .get_connection()
+            .execute_unprepared(create_partition_function_sql)
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER FUNCTION refactor_platform.create_event_store_partition(DATE) \
+                 OWNER TO refactor",
+            )
+            .await?;
+
+        // Catches events of a month whose partition wasn't created in time.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.event_store_default \
+                 PARTITION OF refactor_platform.event_store DEFAULT",
+            )
+            .await?;
+
+        // Partitions for every month of the existing log through two months ahead
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DO $$
+                DECLARE
+                    month DATE;
+                BEGIN
+                    FOR month IN
+                        SELECT generate_series(
+                            date_trunc('month', COALESCE(
+                                (SELECT MIN(created_at) FROM refactor_platform.domain_events),
+                                NOW()
+                            )),
+                            date_trunc('month', NOW()) + INTERVAL '2 months',
+                            INTERVAL '1 month'
+                        )::DATE
+                    LOOP
+                        PERFORM refactor_platform.create_event_store_partition(month);
+                    END LOOP;
+                END
+                $$
+                "#,
+            )
+            .await?;
+
+        // Activity feed: a relationship's events, newest first
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_event_store_relationship_created_at \
+                 ON refactor_platform.event_store(coaching_relationship_id, created_at DESC, id DESC) \
+                 WHERE coaching_relationship_id IS NOT NULL",
+            )
+            .await?;
+
+        // Audit log: filtering by entity type within a date range
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_event_store_entity_type_created_at \
+                 ON refactor_platform.event_store(entity_type, created_at DESC)",
+            )
+            .await?;
+
+        // Move the existing log over. Session-scoped events name their relationship only
+        // through the session.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                INSERT INTO refactor_platform.event_store
+                    (id, event_type, entity_type, entity_id, coaching_relationship_id,
+                     coaching_session_id, actor_user_id, payload, created_at)
+                SELECT e.id, e.event_type, e.entity_type, e.entity_id,
+                       COALESCE((e.payload->>'coaching_relationship_id')::UUID,
+                                s.coaching_relationship_id),
+                       (e.payload->>'coaching_session_id')::UUID,
+                       e.actor_user_id, e.payload, e.created_at
+                FROM refactor_platform.domain_events e
+                LEFT JOIN refactor_platform.coaching_sessions s
+                    ON s.id = (e.payload->>'coaching_session_id')::UUID
+                "#,
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.domain_events")
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let create_table_sql = r#"
+            CREATE TABLE IF NOT EXISTS refactor_platform.domain_events (
+                id            UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                event_type    VARCHAR(64) NOT NULL,
+                entity_type   VARCHAR(64) NOT NULL,
+                entity_id     UUID,
+                actor_user_id UUID
+                    REFERENCES refactor_platform.users(id) ON DELETE SET NULL,
+                payload       JSONB NOT NULL,
+                created_at    TIMESTAMPTZ NOT NULL DEFAULT NOW()
+            )
+        "#;
+
+        manager
+            .get_connection()
+            .execute_unprepared(create_table_sql)
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.domain_events OWNER TO refactor")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_domain_events_entity_type_created_at \
+                 ON refactor_platform.domain_events(entity_type, created_at DESC)",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_domain_events_created_at \
+                 ON refactor_platform.domain_events(created_at DESC)",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                INSERT INTO refactor_platform.domain_events
+                    (id, event_type, entity_type, entity_id, actor_user_id, payload, created_at)
+                SELECT id, event_type, entity_type, entity_id, actor_user_id, payload, created_at
+                FROM refactor_platform.event_store
+                "#,
+            )
+            .await?;
+
+        // Drops every partition with it
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.event_store")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "DROP FUNCTION IF EXISTS refactor_platform.create_event_store_partition(DATE)",
+            )
+            .await?;
+
+        Ok(())
+    }
+}
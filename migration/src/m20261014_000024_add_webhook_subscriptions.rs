@@ -0,0 +1,57 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // An organization's outbound webhook: the URL its domain events are POSTed to,
+        // signed with `secret`. `event_types` is a JSONB array of the event type names
+        // delivered.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.webhook_subscriptions (
+                    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                    organization_id UUID NOT NULL
+                        REFERENCES refactor_platform.organizations(id) ON DELETE CASCADE,
+                    url TEXT NOT NULL,
+                    secret TEXT NOT NULL,
+                    event_types JSONB NOT NULL DEFAULT '[]'::jsonb,
+                    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+                    created_by_user_id UUID
+                        REFERENCES refactor_platform.users(id) ON DELETE SET NULL,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.webhook_subscriptions OWNER TO refactor",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_webhook_subscriptions_organization
+                 ON refactor_platform.webhook_subscriptions (organization_id)",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.webhook_subscriptions")
+            .await?;
+
+        Ok(())
+    }
+}
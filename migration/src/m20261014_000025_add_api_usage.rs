@@ -0,0 +1,80 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Requests an organization made to one endpoint (e.g. `GET /actions/:id`) in the
+        // hour starting at `period_start`. Every replica adds its counts to the same row.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.api_usage_rollups (
+                    organization_id UUID NOT NULL
+                        REFERENCES refactor_platform.organizations(id) ON DELETE CASCADE,
+                    endpoint TEXT NOT NULL,
+                    period_start TIMESTAMPTZ NOT NULL,
+                    request_count BIGINT NOT NULL DEFAULT 0,
+                    error_count BIGINT NOT NULL DEFAULT 0,
+                    total_latency_ms BIGINT NOT NULL DEFAULT 0,
+                    max_latency_ms BIGINT NOT NULL DEFAULT 0,
+                    PRIMARY KEY (organization_id, endpoint, period_start)
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.api_usage_rollups OWNER TO refactor")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_api_usage_rollups_organization_period
+                 ON refactor_platform.api_usage_rollups (organization_id, period_start)",
+            )
+            .await?;
+
+        // Requests per hour an organization may make, overall and per endpoint;
+        // `endpoint_limits` is a JSONB object of endpoint to limit. No row, no quota.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.organization_api_quotas (
+                    organization_id UUID PRIMARY KEY
+                        REFERENCES refactor_platform.organizations(id) ON DELETE CASCADE,
+                    requests_per_hour INTEGER,
+                    endpoint_limits JSONB NOT NULL DEFAULT '{}'::jsonb,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.organization_api_quotas OWNER TO refactor",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.organization_api_quotas")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.api_usage_rollups")
+            .await?;
+
+        Ok(())
+    }
+}
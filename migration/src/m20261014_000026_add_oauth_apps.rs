@@ -0,0 +1,121 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // A third-party app an organization's admins registered to act on behalf of its
+        // users. Its `id` is the OAuth client ID. Public clients (e.g. native apps) have
+        // no secret and rely on PKCE alone. `redirect_uris` is a JSONB array of the URIs
+        // authorization codes may be sent to.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.oauth_clients (
+                    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                    organization_id UUID NOT NULL
+                        REFERENCES refactor_platform.organizations(id) ON DELETE CASCADE,
+                    name VARCHAR(255) NOT NULL,
+                    client_secret_hash TEXT,
+                    redirect_uris JSONB NOT NULL DEFAULT '[]'::jsonb,
+                    created_by_user_id UUID
+                        REFERENCES refactor_platform.users(id) ON DELETE SET NULL,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.oauth_clients OWNER TO refactor")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_oauth_clients_organization
+                 ON refactor_platform.oauth_clients (organization_id)",
+            )
+            .await?;
+
+        // Single-use codes a user's consent produced, waiting to be exchanged for tokens.
+        // Only the SHA-256 hash of each code is stored.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.oauth_authorization_codes (
+                    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                    code_hash TEXT NOT NULL UNIQUE,
+                    client_id UUID NOT NULL
+                        REFERENCES refactor_platform.oauth_clients(id) ON DELETE CASCADE,
+                    user_id UUID NOT NULL
+                        REFERENCES refactor_platform.users(id) ON DELETE CASCADE,
+                    redirect_uri TEXT NOT NULL,
+                    code_challenge TEXT NOT NULL,
+                    scope TEXT NOT NULL,
+                    expires_at TIMESTAMPTZ NOT NULL,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.oauth_authorization_codes OWNER TO refactor",
+            )
+            .await?;
+
+        // The access and refresh token an app holds for a user, by SHA-256 hash. Refreshing
+        // replaces both hashes; revoking sets `revoked_at`.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.oauth_grants (
+                    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                    client_id UUID NOT NULL
+                        REFERENCES refactor_platform.oauth_clients(id) ON DELETE CASCADE,
+                    user_id UUID NOT NULL
+                        REFERENCES refactor_platform.users(id) ON DELETE CASCADE,
+                    scope TEXT NOT NULL,
+                    access_token_hash TEXT NOT NULL UNIQUE,
+                    access_token_expires_at TIMESTAMPTZ NOT NULL,
+                    refresh_token_hash TEXT NOT NULL UNIQUE,
+                    refresh_token_expires_at TIMESTAMPTZ NOT NULL,
+                    revoked_at TIMESTAMPTZ,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.oauth_grants OWNER TO refactor")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_oauth_grants_user
+                 ON refactor_platform.oauth_grants (user_id, client_id)",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in ["oauth_grants", "oauth_authorization_codes", "oauth_clients"] {
+            manager
+                .get_connection()
+                .execute_unprepared(&format!("DROP TABLE IF EXISTS refactor_platform.{table}"))
+                .await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TYPE refactor_platform.policy_kind AS ENUM ('terms_of_service', 'privacy_policy')",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TYPE refactor_platform.policy_kind OWNER TO refactor")
+            .await?;
+
+        // A published version of a legal policy. The version of a kind in effect is the
+        // one with the latest `effective_at` that has passed; a newer one can be added
+        // ahead of time.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.policy_documents (
+                    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                    kind refactor_platform.policy_kind NOT NULL,
+                    version TEXT NOT NULL,
+                    effective_at TIMESTAMPTZ NOT NULL,
+                    content_url TEXT NOT NULL,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    UNIQUE (kind, version)
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.policy_documents OWNER TO refactor")
+            .await?;
+
+        // A user's acceptance of one policy version, kept as the record of consent
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.policy_acceptances (
+                    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                    user_id UUID NOT NULL
+                        REFERENCES refactor_platform.users(id) ON DELETE CASCADE,
+                    policy_document_id UUID NOT NULL
+                        REFERENCES refactor_platform.policy_documents(id) ON DELETE CASCADE,
+                    accepted_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    UNIQUE (user_id, policy_document_id)
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.policy_acceptances OWNER TO refactor",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.policy_acceptances")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.policy_documents")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TYPE IF EXISTS refactor_platform.policy_kind")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Blind index of encrypted note bodies: one row per distinct word of a note,
+        // stored as an HMAC keyed by the organization's data key and scoped to the
+        // relationship, so encrypted notes can be keyword-searched without storing
+        // their words.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.note_blind_index_tokens (
+                    note_id UUID NOT NULL
+                        REFERENCES refactor_platform.notes(id) ON DELETE CASCADE,
+                    coaching_relationship_id UUID NOT NULL
+                        REFERENCES refactor_platform.coaching_relationships(id) ON DELETE CASCADE,
+                    token TEXT NOT NULL,
+                    PRIMARY KEY (note_id, token)
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS note_blind_index_tokens_relationship_token
+                 ON refactor_platform.note_blind_index_tokens (coaching_relationship_id, token)",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.note_blind_index_tokens OWNER TO refactor",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.note_blind_index_tokens")
+            .await?;
+
+        Ok(())
+    }
+}
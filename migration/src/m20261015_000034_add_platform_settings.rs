@@ -0,0 +1,55 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // The platform-wide settings, one row at most. POST /bootstrap inserts it along
+        // with the first SuperAdmin and organization, and refuses to run again while it
+        // exists, so the setup flow disables itself for good.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.platform_settings (
+                    id SMALLINT PRIMARY KEY DEFAULT 1 CHECK (id = 1),
+                    platform_name TEXT NOT NULL,
+                    support_email TEXT,
+                    default_timezone TEXT NOT NULL DEFAULT 'UTC',
+                    bootstrapped_by_id UUID
+                        REFERENCES refactor_platform.users(id) ON DELETE SET NULL,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.platform_settings OWNER TO refactor")
+            .await?;
+
+        // Databases that already have users were set up before the bootstrap flow existed
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "INSERT INTO refactor_platform.platform_settings (platform_name)
+                 SELECT 'Refactor Platform'
+                 WHERE EXISTS (SELECT 1 FROM refactor_platform.users)
+                 ON CONFLICT (id) DO NOTHING",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.platform_settings")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Kinds of coaching session an organization runs ("Intro call", "Review"), each
+        // with the duration new sessions of that kind default to. Deleting a type leaves
+        // its sessions in place, untyped.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.session_types (
+                    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                    organization_id UUID NOT NULL
+                        REFERENCES refactor_platform.organizations(id) ON DELETE CASCADE,
+                    name VARCHAR(100) NOT NULL,
+                    default_duration_minutes SMALLINT NOT NULL DEFAULT 60
+                        CHECK (default_duration_minutes BETWEEN 1 AND 480),
+                    color VARCHAR(7),
+                    requires_recording BOOLEAN NOT NULL DEFAULT FALSE,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    UNIQUE (organization_id, name)
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.session_types OWNER TO refactor")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.coaching_sessions
+                 ADD COLUMN IF NOT EXISTS session_type_id UUID
+                     REFERENCES refactor_platform.session_types(id) ON DELETE SET NULL",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_coaching_sessions_session_type
+                 ON refactor_platform.coaching_sessions (session_type_id)",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.coaching_sessions
+                 DROP COLUMN IF EXISTS session_type_id",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.session_types")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TYPE refactor_platform.session_request_status AS ENUM ('pending', 'accepted', 'declined')",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TYPE refactor_platform.session_request_status OWNER TO refactor",
+            )
+            .await?;
+
+        // A coachee's ask for an ad-hoc session, with the time windows that suit them.
+        // Pending requests are the coach's waiting list; accepting one creates the
+        // session. status_history holds every status the request has been in, oldest
+        // first, with who moved it there.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.session_requests (
+                    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                    coaching_relationship_id UUID NOT NULL
+                        REFERENCES refactor_platform.coaching_relationships(id) ON DELETE CASCADE,
+                    requested_by_user_id UUID NOT NULL
+                        REFERENCES refactor_platform.users(id) ON DELETE CASCADE,
+                    preferred_windows JSONB NOT NULL,
+                    duration_minutes SMALLINT CHECK (duration_minutes BETWEEN 1 AND 480),
+                    message TEXT,
+                    status refactor_platform.session_request_status NOT NULL DEFAULT 'pending',
+                    decline_reason TEXT,
+                    coaching_session_id UUID
+                        REFERENCES refactor_platform.coaching_sessions(id) ON DELETE SET NULL,
+                    status_history JSONB NOT NULL DEFAULT '[]',
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.session_requests OWNER TO refactor")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_session_requests_relationship_status
+                 ON refactor_platform.session_requests (coaching_relationship_id, status, created_at)",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.session_requests")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TYPE IF EXISTS refactor_platform.session_request_status")
+            .await?;
+
+        Ok(())
+    }
+}
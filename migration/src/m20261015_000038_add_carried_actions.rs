@@ -0,0 +1,53 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // An open action brought forward into a later session of its relationship, from
+        // the session before it (where it was created, or carried into). An action's
+        // rows count how many times it has been carried.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.carried_actions (
+                    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                    action_id UUID NOT NULL
+                        REFERENCES refactor_platform.actions(id) ON DELETE CASCADE,
+                    coaching_session_id UUID NOT NULL
+                        REFERENCES refactor_platform.coaching_sessions(id) ON DELETE CASCADE,
+                    carried_from_session_id UUID NOT NULL
+                        REFERENCES refactor_platform.coaching_sessions(id) ON DELETE CASCADE,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    UNIQUE (action_id, coaching_session_id)
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.carried_actions OWNER TO refactor")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_carried_actions_coaching_session_id
+                 ON refactor_platform.carried_actions (coaching_session_id)",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.carried_actions")
+            .await?;
+
+        Ok(())
+    }
+}
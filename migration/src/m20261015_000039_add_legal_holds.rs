@@ -0,0 +1,66 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // A legal hold on an organization or a coaching relationship: while it's
+        // active (not yet released), nothing it covers may be purged or deleted. Rows
+        // are kept once released, as the record of who held what, when and why, so
+        // they don't reference their target, which may be deleted afterwards.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.legal_holds (
+                    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                    organization_id UUID,
+                    coaching_relationship_id UUID,
+                    reason TEXT NOT NULL,
+                    placed_by_user_id UUID NOT NULL,
+                    released_by_user_id UUID,
+                    released_at TIMESTAMPTZ,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    CHECK ((organization_id IS NULL) <> (coaching_relationship_id IS NULL))
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.legal_holds OWNER TO refactor")
+            .await?;
+
+        // At most one active hold per organization and per relationship
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE UNIQUE INDEX IF NOT EXISTS idx_legal_holds_active_organization
+                 ON refactor_platform.legal_holds (organization_id)
+                 WHERE released_at IS NULL AND organization_id IS NOT NULL",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE UNIQUE INDEX IF NOT EXISTS idx_legal_holds_active_relationship
+                 ON refactor_platform.legal_holds (coaching_relationship_id)
+                 WHERE released_at IS NULL AND coaching_relationship_id IS NOT NULL",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.legal_holds")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TYPE refactor_platform.session_record_status AS ENUM ('pending', 'ready', 'failed')",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TYPE refactor_platform.session_record_status OWNER TO refactor",
+            )
+            .await?;
+
+        // A session's compiled record of engagement: the PDF lives in object storage
+        // under storage_key once a background pass has compiled it. Requesting it again
+        // puts it back to pending, and the next pass replaces the stored document.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.session_records (
+                    coaching_session_id UUID PRIMARY KEY
+                        REFERENCES refactor_platform.coaching_sessions(id) ON DELETE CASCADE,
+                    status refactor_platform.session_record_status NOT NULL DEFAULT 'pending',
+                    storage_key TEXT,
+                    byte_size BIGINT,
+                    error_message TEXT,
+                    generated_at TIMESTAMPTZ,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.session_records OWNER TO refactor")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_session_records_pending
+                 ON refactor_platform.session_records (updated_at)
+                 WHERE status = 'pending'",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.session_records")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TYPE IF EXISTS refactor_platform.session_record_status")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Tables searched by GET /search, with the text each one's search vector is built from.
+/// Goal titles weigh more than their bodies, so a match on the title ranks first.
+const SEARCH_VECTORS: [(&str, &str); 4] = [
+    (
+        "notes",
+        // Encrypted bodies (`enc:v1:...`) can't be indexed; they're left out of search
+        "CASE WHEN body LIKE 'enc:v1:%' THEN ''::tsvector
+              ELSE to_tsvector('english', coalesce(body, '')) END",
+    ),
+    ("actions", "to_tsvector('english', coalesce(body, ''))"),
+    ("agreements", "to_tsvector('english', coalesce(body, ''))"),
+    (
+        "goals",
+        "setweight(to_tsvector('english', coalesce(title, '')), 'A')
+         || setweight(to_tsvector('english', coalesce(body, '')), 'B')",
+    ),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Generated columns keep the vectors in step with every write, and SeaORM's
+        // models don't list them, so inserts and updates leave them alone.
+        for (table, expression) in SEARCH_VECTORS {
+            manager
+                .get_connection()
+                .execute_unprepared(&format!(
+                    "ALTER TABLE refactor_platform.{table}
+                     ADD COLUMN IF NOT EXISTS search_vector tsvector
+                     GENERATED ALWAYS AS ({expression}) STORED"
+                ))
+                .await?;
+
+            manager
+                .get_connection()
+                .execute_unprepared(&format!(
+                    "CREATE INDEX IF NOT EXISTS idx_{table}_search_vector
+                     ON refactor_platform.{table} USING GIN (search_vector)"
+                ))
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (table, _) in SEARCH_VECTORS {
+            manager
+                .get_connection()
+                .execute_unprepared(&format!(
+                    "ALTER TABLE refactor_platform.{table} DROP COLUMN IF EXISTS search_vector"
+                ))
+                .await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Whether the coach coaches the relationship without pay, which coaching logs
+        // (e.g. for ICF credentials) count separately from paid hours.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.coaching_relationships
+                 ADD COLUMN IF NOT EXISTS pro_bono BOOLEAN NOT NULL DEFAULT false",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.coaching_relationships DROP COLUMN IF EXISTS pro_bono",
+            )
+            .await?;
+
+        Ok(())
+    }
+}
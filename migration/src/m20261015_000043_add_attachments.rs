@@ -0,0 +1,70 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Files shared in a coaching session, optionally on one of its notes or actions.
+        // The file itself lives in object storage under storage_key; organization_id is
+        // denormalized from the session's relationship so quotas sum over one index.
+        // Deleting the note or action leaves its attachments on the session.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.attachments (
+                    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                    organization_id UUID NOT NULL
+                        REFERENCES refactor_platform.organizations(id) ON DELETE CASCADE,
+                    coaching_session_id UUID NOT NULL
+                        REFERENCES refactor_platform.coaching_sessions(id) ON DELETE CASCADE,
+                    note_id UUID
+                        REFERENCES refactor_platform.notes(id) ON DELETE SET NULL,
+                    action_id UUID
+                        REFERENCES refactor_platform.actions(id) ON DELETE SET NULL,
+                    user_id UUID
+                        REFERENCES refactor_platform.users(id) ON DELETE SET NULL,
+                    file_name TEXT NOT NULL,
+                    content_type TEXT NOT NULL,
+                    byte_size BIGINT NOT NULL CHECK (byte_size > 0),
+                    storage_key TEXT NOT NULL UNIQUE,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.attachments OWNER TO refactor")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_attachments_coaching_session_id
+                 ON refactor_platform.attachments (coaching_session_id, created_at)",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_attachments_organization_id
+                 ON refactor_platform.attachments (organization_id)",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.attachments")
+            .await?;
+
+        Ok(())
+    }
+}
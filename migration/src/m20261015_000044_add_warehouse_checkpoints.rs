@@ -0,0 +1,50 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // How far each stream of domain events exported to the analytics warehouse has
+        // got: `live` (events as they're published) and `backfill` (events re-exported
+        // from the event store, up to until_at). A backfill resumes after
+        // (position_at, position_id), the last stored event it exported.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.warehouse_checkpoints (
+                    stream TEXT PRIMARY KEY,
+                    position_at TIMESTAMPTZ,
+                    position_id UUID,
+                    until_at TIMESTAMPTZ,
+                    events_exported BIGINT NOT NULL DEFAULT 0,
+                    batches_exported BIGINT NOT NULL DEFAULT 0,
+                    last_object_key TEXT,
+                    error_message TEXT,
+                    completed_at TIMESTAMPTZ,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.warehouse_checkpoints OWNER TO refactor",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.warehouse_checkpoints")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Tables whose rows are soft-deleted, then purged once past the retention window.
+const TABLES: [&str; 5] = [
+    "actions",
+    "agreements",
+    "notes",
+    "goals",
+    "coaching_sessions",
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Soft-delete marker; null for live rows. Reads exclude non-null. The partial
+        // index serves the purge, which looks only at deleted rows.
+        for table in TABLES {
+            manager
+                .get_connection()
+                .execute_unprepared(&format!(
+                    "ALTER TABLE refactor_platform.{table} ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ"
+                ))
+                .await?;
+
+            manager
+                .get_connection()
+                .execute_unprepared(&format!(
+                    "CREATE INDEX IF NOT EXISTS idx_{table}_deleted_at \
+                     ON refactor_platform.{table} (deleted_at) WHERE deleted_at IS NOT NULL"
+                ))
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in TABLES {
+            manager
+                .get_connection()
+                .execute_unprepared(&format!(
+                    "DROP INDEX IF EXISTS refactor_platform.idx_{table}_deleted_at"
+                ))
+                .await?;
+
+            manager
+                .get_connection()
+                .execute_unprepared(&format!(
+                    "ALTER TABLE refactor_platform.{table} DROP COLUMN IF EXISTS deleted_at"
+                ))
+                .await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // One row per change to an audited entity: who made it, from where, and which
+        // fields it changed. Rows are the record of what happened, so they don't
+        // reference the organization, actor or entity, any of which may be deleted
+        // afterwards.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.audit_entries (
+                    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                    organization_id UUID,
+                    actor_user_id UUID,
+                    action TEXT NOT NULL,
+                    entity_type TEXT NOT NULL,
+                    entity_id UUID NOT NULL,
+                    changes JSONB NOT NULL,
+                    snapshot JSONB,
+                    ip_address TEXT,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.audit_entries OWNER TO refactor")
+            .await?;
+
+        // An organization's trail, newest first
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_audit_entries_organization_created_at
+                 ON refactor_platform.audit_entries (organization_id, created_at DESC, id DESC)",
+            )
+            .await?;
+
+        // An entity's latest entry, whose snapshot the next change is diffed against
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_audit_entries_entity_created_at
+                 ON refactor_platform.audit_entries (entity_type, entity_id, created_at DESC)",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.audit_entries")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Optional estimate of an action's effort, which goal burndowns weigh it by.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.actions
+                 ADD COLUMN IF NOT EXISTS effort_points SMALLINT
+                 CHECK (effort_points BETWEEN 1 AND 100)",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.actions DROP COLUMN IF EXISTS effort_points",
+            )
+            .await?;
+
+        Ok(())
+    }
+}
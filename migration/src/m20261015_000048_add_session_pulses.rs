@@ -0,0 +1,55 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // A participant's one-question check-in after a session: "How valuable was this
+        // session?" on a 1–5 scale. The row is created when the prompt goes out, so a
+        // null score marks a prompt not yet answered.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.session_pulses (
+                    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                    coaching_session_id UUID NOT NULL
+                        REFERENCES refactor_platform.coaching_sessions(id) ON DELETE CASCADE,
+                    user_id UUID NOT NULL
+                        REFERENCES refactor_platform.users(id) ON DELETE CASCADE,
+                    score SMALLINT CHECK (score BETWEEN 1 AND 5),
+                    requested_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    responded_at TIMESTAMPTZ,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    UNIQUE (coaching_session_id, user_id)
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE refactor_platform.session_pulses OWNER TO refactor")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_session_pulses_user_requested_at
+                 ON refactor_platform.session_pulses (user_id, requested_at)",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.session_pulses")
+            .await?;
+
+        Ok(())
+    }
+}
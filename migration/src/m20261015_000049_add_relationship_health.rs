@@ -0,0 +1,95 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // How an organization weighs the signals of a relationship's health score, and the
+        // score below which its admins are alerted. Organizations without a row use the
+        // defaults below.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.relationship_health_settings (
+                    organization_id UUID PRIMARY KEY
+                        REFERENCES refactor_platform.organizations(id) ON DELETE CASCADE,
+                    pulse_weight SMALLINT NOT NULL DEFAULT 25
+                        CHECK (pulse_weight BETWEEN 0 AND 100),
+                    cadence_weight SMALLINT NOT NULL DEFAULT 25
+                        CHECK (cadence_weight BETWEEN 0 AND 100),
+                    action_completion_weight SMALLINT NOT NULL DEFAULT 25
+                        CHECK (action_completion_weight BETWEEN 0 AND 100),
+                    sentiment_weight SMALLINT NOT NULL DEFAULT 25
+                        CHECK (sentiment_weight BETWEEN 0 AND 100),
+                    alert_threshold SMALLINT NOT NULL DEFAULT 40
+                        CHECK (alert_threshold BETWEEN 0 AND 100),
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.relationship_health_settings OWNER TO refactor",
+            )
+            .await?;
+
+        // One row per relationship per scoring run, so the history shows the trend. Each
+        // signal's own 0–100 score is kept next to the combined one; a null signal had
+        // nothing to go on in that run and was left out of the combination.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE TABLE IF NOT EXISTS refactor_platform.relationship_health_scores (
+                    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                    coaching_relationship_id UUID NOT NULL
+                        REFERENCES refactor_platform.coaching_relationships(id) ON DELETE CASCADE,
+                    score SMALLINT NOT NULL CHECK (score BETWEEN 0 AND 100),
+                    pulse_score SMALLINT CHECK (pulse_score BETWEEN 0 AND 100),
+                    cadence_score SMALLINT CHECK (cadence_score BETWEEN 0 AND 100),
+                    action_completion_score SMALLINT
+                        CHECK (action_completion_score BETWEEN 0 AND 100),
+                    sentiment_score SMALLINT CHECK (sentiment_score BETWEEN 0 AND 100),
+                    computed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                )",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE refactor_platform.relationship_health_scores OWNER TO refactor",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_relationship_health_scores_relationship_computed_at
+                 ON refactor_platform.relationship_health_scores (coaching_relationship_id, computed_at DESC)",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS refactor_platform.relationship_health_scores")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "DROP TABLE IF EXISTS refactor_platform.relationship_health_settings",
+            )
+            .await?;
+
+        Ok(())
+    }
+}
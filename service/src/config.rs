@@ -7,8 +7,11 @@ use serde::Deserialize;
 use std::collections::HashMap;
 use std::fmt;
 use std::str::FromStr;
+use std::time::Duration;
 use utoipa::IntoParams;
 
+use crate::runtime_config::RuntimeConfig;
+
 type APiVersionList = [&'static str; 1];
 
 const DEFAULT_API_VERSION: &str = "1.0.0-beta1";
@@ -27,6 +30,14 @@ const DEFAULT_SESSION_SCHEDULED_EMAIL_URL_PATH: &str = "/coaching-sessions/{sess
 /// Default URL path for action-assigned email links.
 const DEFAULT_ACTION_ASSIGNED_EMAIL_URL_PATH: &str = "/coaching-sessions/{session_id}?tab=actions";
 
+/// Default URL path template for the progress reports link in weekly progress emails.
+const DEFAULT_PROGRESS_REPORT_EMAIL_URL_PATH: &str =
+    "/coaching-relationships/{relationship_id}/progress-reports";
+
+/// Default URL path for the session requests link in session request emails.
+const DEFAULT_SESSION_REQUEST_EMAIL_URL_PATH: &str =
+    "/coaching-relationships/{relationship_id}/session-requests";
+
 /// Default URL path for magic link setup page.
 const DEFAULT_MAGIC_LINK_EMAIL_URL_PATH: &str = "/setup/{token}";
 
@@ -53,6 +64,11 @@ const CONFIG_FIELD_KEYS: &[&str] = &[
     "db_acquire_timeout_secs",
     "db_idle_timeout_secs",
     "db_max_lifetime_secs",
+    "db_statement_timeout_secs",
+    "db_heavy_statement_timeout_secs",
+    "db_heavy_max_connections",
+    "db_health_check_interval_secs",
+    "db_health_failure_threshold",
     "tiptap_url",
     "tiptap_auth_key",
     "tiptap_jwt_signing_key",
@@ -63,19 +79,45 @@ const CONFIG_FIELD_KEYS: &[&str] = &[
     "session_scheduled_email_template_id",
     "recurring_sessions_scheduled_email_template_id",
     "action_assigned_email_template_id",
+    "mention_email_template_id",
+    "progress_report_email_template_id",
+    "account_lock_email_template_id",
+    "session_request_email_template_id",
     "frontend_base_url",
     "session_scheduled_email_url_path",
     "action_assigned_email_url_path",
+    "progress_report_email_url_path",
+    "session_request_email_url_path",
     "magic_link_email_url_path",
     "magic_link_expiry_seconds",
     "password_reset_email_template_id",
     "password_reset_email_url_path",
     "password_reset_token_expiry_seconds",
+    "password_min_length",
+    "password_required_character_classes",
+    "password_deny_list",
+    "password_breach_check_enabled",
+    "pwned_passwords_api_url",
+    "encrypted_note_search_enabled",
     "interface",
     "port",
     "log_level_filter",
+    "runtime_config_file",
     "runtime_env",
+    "api_docs_access",
     "backend_session_expiry_seconds",
+    "backend_session_expiry_warning_seconds",
+    "remember_me_lifetime_seconds",
+    "reauthentication_window_seconds",
+    "sse_channel_capacity",
+    "sse_overflow_policy",
+    "sse_max_connections_per_user",
+    "sse_drain_reconnect_delay_seconds",
+    "sse_keep_alive_interval_seconds",
+    "sse_idle_timeout_seconds",
+    "sse_max_connection_lifetime_seconds",
+    "rate_limit_burst",
+    "rate_limit_replenish_interval_ms",
     "oauth_success_redirect_uri",
     "google_oauth_auth_url",
     "google_oauth_token_url",
@@ -85,6 +127,39 @@ const CONFIG_FIELD_KEYS: &[&str] = &[
     "recall_ai_api_key",
     "recall_ai_region",
     "recall_ai_webhook_secret",
+    "llm_gateway_api_key",
+    "llm_gateway_url",
+    "llm_gateway_model",
+    "llm_gateway_embedding_model",
+    "metrics_token",
+    "events_worker_enabled",
+    "events_worker_poll_interval_ms",
+    "events_worker_batch_size",
+    "events_worker_lease_seconds",
+    "event_transport_url",
+    "event_transport_channel",
+    "event_bus_url",
+    "event_bus_topic",
+    "event_store_retention_months",
+    "event_store_compact_after_days",
+    "soft_delete_retention_days",
+    "vapid_public_key",
+    "vapid_private_key",
+    "vapid_subject",
+    "calendar_feed_signing_key",
+    "object_storage_endpoint",
+    "object_storage_bucket",
+    "object_storage_region",
+    "object_storage_access_key_id",
+    "object_storage_secret_access_key",
+    "attachment_max_size_mb",
+    "attachment_organization_quota_mb",
+    "organization_logo_base_url",
+    "warehouse_export_enabled",
+    "warehouse_export_bucket",
+    "warehouse_export_prefix",
+    "warehouse_export_batch_size",
+    "warehouse_export_flush_interval_seconds",
 ];
 
 #[derive(Deserialize, IntoParams)]
@@ -117,6 +192,32 @@ impl FromStr for RustEnv {
     }
 }
 
+/// Who may open the OpenAPI web UI (`/rapidoc`) and the spec it serves (`/api-docs`).
+#[derive(Clone, Debug, PartialEq)]
+pub enum ApiDocsAccess {
+    /// Anyone, signed in or not
+    Public,
+    /// Signed-in system SuperAdmins only
+    SuperAdmin,
+    /// No one: the routes aren't served at all
+    Disabled,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ApiDocsAccessParseError;
+
+impl FromStr for ApiDocsAccess {
+    type Err = ApiDocsAccessParseError;
+    fn from_str(access: &str) -> Result<ApiDocsAccess, Self::Err> {
+        match access.to_lowercase().as_str() {
+            "public" => Ok(ApiDocsAccess::Public),
+            "super_admin" => Ok(ApiDocsAccess::SuperAdmin),
+            "disabled" => Ok(ApiDocsAccess::Disabled),
+            _ => Err(ApiDocsAccessParseError),
+        }
+    }
+}
+
 impl fmt::Display for RustEnv {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -157,6 +258,12 @@ impl ConfigDisplay for u64 {
     }
 }
 
+impl ConfigDisplay for bool {
+    fn display_value(&self) -> String {
+        self.to_string()
+    }
+}
+
 impl ConfigDisplay for Vec<String> {
     fn display_value(&self) -> String {
         format!("{:?}", self)
@@ -175,6 +282,12 @@ impl ConfigDisplay for RustEnv {
     }
 }
 
+impl ConfigDisplay for ApiDocsAccess {
+    fn display_value(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
 impl<T: ConfigDisplay> ConfigDisplay for Option<T> {
     fn display_value(&self) -> String {
         match self {
@@ -238,6 +351,29 @@ pub struct Config {
     #[arg(long, env, default_value_t = 1800)]
     pub db_max_lifetime_secs: u64,
 
+    /// Seconds a single SQL statement may run before Postgres cancels it (0: no limit)
+    #[arg(long, env, default_value_t = 30)]
+    pub db_statement_timeout_secs: u64,
+
+    /// Statement timeout in seconds for the heavy endpoints (AI, analytics, audit log),
+    /// which query through a pool of their own (0: no limit)
+    #[arg(long, env, default_value_t = 120)]
+    pub db_heavy_statement_timeout_secs: u64,
+
+    /// Maximum number of connections in the heavy endpoints' pool, on top of
+    /// `db_max_connections`
+    #[arg(long, env, default_value_t = 3)]
+    pub db_heavy_max_connections: u32,
+
+    /// Seconds between probes of the database; after `db_health_failure_threshold`
+    /// failed probes in a row the pool is replaced (0: don't probe)
+    #[arg(long, env, default_value_t = 15)]
+    pub db_health_check_interval_secs: u64,
+
+    /// Failed database probes in a row before the pool is considered broken and replaced
+    #[arg(long, env, default_value_t = 3)]
+    pub db_health_failure_threshold: u32,
+
     /// The URL for the Tiptap Cloud API provider
     #[arg(long, env)]
     tiptap_url: Option<String>,
@@ -274,6 +410,19 @@ pub struct Config {
     /// The Resend template ID for action-assigned emails.
     #[arg(long, env)]
     action_assigned_email_template_id: Option<String>,
+    /// The Resend template ID for emails notifying a user they were @-mentioned.
+    #[arg(long, env)]
+    mention_email_template_id: Option<String>,
+    /// The Resend template ID for coaches' weekly relationship progress emails.
+    #[arg(long, env)]
+    progress_report_email_template_id: Option<String>,
+    /// The Resend template ID for emails telling an organization's admins that one of
+    /// its users was locked or unlocked.
+    #[arg(long, env)]
+    account_lock_email_template_id: Option<String>,
+    /// The Resend template ID for emails telling a coach their coachee requested a session.
+    #[arg(long, env)]
+    session_request_email_template_id: Option<String>,
     /// The base URL of the frontend application (e.g. https://app.myrefactor.com).
     /// Used to construct links in email notifications.
     #[arg(long, env)]
@@ -290,6 +439,14 @@ pub struct Config {
         default_value = "/coaching-sessions/{session_id}?tab=actions"
     )]
     action_assigned_email_url_path: String,
+    /// URL path template for the progress reports link in weekly progress emails.
+    /// Use `{relationship_id}` as a placeholder for the coaching relationship ID.
+    #[arg(long, env, default_value = DEFAULT_PROGRESS_REPORT_EMAIL_URL_PATH)]
+    progress_report_email_url_path: String,
+    /// URL path template for the session requests link in session request emails.
+    /// Use `{relationship_id}` as a placeholder for the coaching relationship ID.
+    #[arg(long, env, default_value = DEFAULT_SESSION_REQUEST_EMAIL_URL_PATH)]
+    session_request_email_url_path: String,
     /// URL path template for magic link setup page.
     /// Use `{token}` as a placeholder for the magic link token.
     #[arg(long, env, default_value = DEFAULT_MAGIC_LINK_EMAIL_URL_PATH)]
@@ -308,6 +465,31 @@ pub struct Config {
     /// Expiry duration in seconds for password reset tokens (default: 30 minutes).
     #[arg(long, env, default_value_t = DEFAULT_PASSWORD_RESET_TOKEN_EXPIRY_SECONDS)]
     password_reset_token_expiry_seconds: u64,
+    /// Minimum length of a new password, in characters (default: 12).
+    #[arg(long, env, default_value_t = 12)]
+    password_min_length: u32,
+    /// Character classes a new password must include, comma-separated: any of
+    /// `lowercase`, `uppercase`, `digit` and `symbol` (default: none).
+    #[arg(
+        long,
+        env,
+        value_delimiter = ',',
+        use_value_delimiter = true,
+        value_parser = clap::builder::PossibleValuesParser::new([
+            "lowercase", "uppercase", "digit", "symbol"
+        ]),
+    )]
+    password_required_character_classes: Vec<String>,
+    /// Passwords refused on top of the built-in list of common ones, comma-separated.
+    #[arg(long, env, value_delimiter = ',', use_value_delimiter = true)]
+    password_deny_list: Vec<String>,
+    /// Refuse new passwords that appear in known data breaches, checked against the
+    /// Pwned Passwords API without sending the password or its full hash.
+    #[arg(long, env, default_value_t = false)]
+    password_breach_check_enabled: bool,
+    /// Pwned Passwords (HaveIBeenPwned) API base URL
+    #[arg(long, env, default_value = "https://api.pwnedpasswords.com")]
+    pwned_passwords_api_url: String,
 
     /// The host interface to listen for incoming connections
     #[arg(short, long, env, default_value = "127.0.0.1")]
@@ -328,6 +510,11 @@ pub struct Config {
         )]
     pub log_level_filter: LevelFilter,
 
+    /// JSON file overriding the settings that can be reloaded without a restart: allowed
+    /// origins, log level and the password breach check (see `runtime_config`)
+    #[arg(long, env)]
+    pub runtime_config_file: Option<String>,
+
     /// Set the Rust runtime environment to use.
     #[arg(
     short,
@@ -342,14 +529,100 @@ pub struct Config {
     )]
     pub runtime_env: RustEnv,
 
+    /// Who may open the OpenAPI web UI at /rapidoc and its spec under /api-docs: `public`,
+    /// `super_admin` for signed-in SuperAdmins only, or `disabled` (default: public, or
+    /// disabled in staging and production)
+    #[arg(
+        long,
+        env,
+        value_parser = clap::builder::PossibleValuesParser::new(["public", "super_admin", "disabled"])
+            .map(|s| s.parse::<ApiDocsAccess>().unwrap()),
+    )]
+    api_docs_access: Option<ApiDocsAccess>,
+
     /// Session expiry duration in seconds (default: 24 hours = 86400 seconds)
     #[arg(long, env, default_value_t = 86400)]
     pub backend_session_expiry_seconds: u64,
 
+    /// How long before an idle session expires its SSE connections are sent a
+    /// `session_expiring_soon` event, in seconds (default: 5 minutes)
+    #[arg(long, env, default_value_t = 300)]
+    pub backend_session_expiry_warning_seconds: u64,
+
+    /// How long a session logged in with "remember me" lasts, however much it's used, in
+    /// seconds (default: 30 days = 2592000 seconds)
+    #[arg(long, env, default_value_t = 2592000)]
+    pub remember_me_lifetime_seconds: u64,
+
+    /// How long a session stays in sudo mode after entering its password, allowing
+    /// sensitive actions like granting roles or creating API tokens, in seconds
+    /// (default: 15 minutes)
+    #[arg(long, env, default_value_t = 900)]
+    pub reauthentication_window_seconds: u64,
+
+    /// Events an SSE connection holds for a client that isn't reading them before it
+    /// overflows (default: 256)
+    #[arg(long, env, default_value_t = 256)]
+    pub sse_channel_capacity: u64,
+
+    /// What an overflowing SSE connection does: `drop_oldest` drops its oldest events
+    /// and sends a `resync` event, `disconnect` closes it (default: drop_oldest)
+    #[arg(
+        long,
+        env,
+        default_value = "drop_oldest",
+        value_parser = clap::builder::PossibleValuesParser::new(["drop_oldest", "disconnect"]),
+    )]
+    pub sse_overflow_policy: String,
+
+    /// Most SSE connections a user may have open to a replica, e.g. across tabs; opening
+    /// one more closes their oldest with a `superseded` event. 0 for no limit (default: 10)
+    #[arg(long, env, default_value_t = 10)]
+    pub sse_max_connections_per_user: u64,
+
+    /// How long SSE clients are told to wait before reconnecting when the server shuts
+    /// down, in seconds (default: 5)
+    #[arg(long, env, default_value_t = 5)]
+    pub sse_drain_reconnect_delay_seconds: u64,
+
+    /// How often an SSE connection that has nothing to send is sent a keep-alive comment,
+    /// so proxies don't time it out, in seconds (default: 15, or 20 in staging and
+    /// production)
+    #[arg(long, env)]
+    sse_keep_alive_interval_seconds: Option<u64>,
+
+    /// How long an SSE connection may go without an event before it's closed for its
+    /// client to reconnect, in seconds; 0 never closes it (default: 0, or 1800 in staging
+    /// and production)
+    #[arg(long, env)]
+    sse_idle_timeout_seconds: Option<u64>,
+
+    /// Longest an SSE connection stays open before it's closed for its client to
+    /// reconnect, e.g. to a less busy replica, in seconds; 0 never closes it (default: 0,
+    /// or 3600 in staging and production)
+    #[arg(long, env)]
+    sse_max_connection_lifetime_seconds: Option<u64>,
+
+    /// Requests a user may make in a burst before being refused with 429; requests not
+    /// made as a user, e.g. to `/login`, are limited per client IP instead. SSE isn't
+    /// limited. 0 for no limit (default: 100)
+    #[arg(long, env, default_value_t = 100)]
+    pub rate_limit_burst: u32,
+
+    /// How often a rate-limited user or IP may make one more request, in milliseconds
+    /// (default: 100, i.e. 10 requests a second)
+    #[arg(long, env, default_value_t = 100)]
+    pub rate_limit_replenish_interval_ms: u64,
+
     /// 32-byte AES encryption key for encrypting sensitive API keys in database (hex-encoded)
     #[arg(long, env)]
     encryption_key: Option<String>,
 
+    /// Keep a blind index of encrypted note bodies so organizations that encrypt their
+    /// notes can still keyword-search them. Unencrypted notes are always searchable.
+    #[arg(long, env, default_value_t = true, action = clap::ArgAction::Set)]
+    encrypted_note_search_enabled: bool,
+
     /// Google OAuth client ID
     #[arg(long, env)]
     google_client_id: Option<String>,
@@ -418,6 +691,162 @@ pub struct Config {
     #[arg(long, env)]
     recall_ai_webhook_secret: Option<String>,
 
+    /// API key for the OpenAI-compatible LLM gateway used for AI analysis (session prep
+    /// briefs and similar). AI features fall back to non-AI behavior when unset.
+    #[arg(long, env)]
+    llm_gateway_api_key: Option<String>,
+
+    /// Base URL of the OpenAI-compatible LLM gateway (the `/chat/completions` parent)
+    #[arg(long, env, default_value = "https://api.openai.com/v1")]
+    llm_gateway_url: String,
+
+    /// Model the LLM gateway uses when a request doesn't name one
+    #[arg(long, env, default_value = "gpt-4o-mini")]
+    llm_gateway_model: String,
+
+    /// Model the LLM gateway uses for text embeddings (semantic retrieval). Must produce,
+    /// or be able to shorten its vectors to, 1536 dimensions.
+    #[arg(long, env, default_value = "text-embedding-3-small")]
+    llm_gateway_embedding_model: String,
+
+    /// Bearer token a Prometheus scraper must send to GET /metrics. The endpoint is
+    /// disabled when unset.
+    #[arg(long, env)]
+    metrics_token: Option<String>,
+
+    /// Leave background event handlers (such as the audit log) to the `events-worker`
+    /// process: the web process writes published events to the outbox and only runs
+    /// the handlers that must run where the event was published, like SSE.
+    #[arg(long, env, default_value_t = false)]
+    events_worker_enabled: bool,
+
+    /// Milliseconds the events worker waits before polling an empty outbox again
+    #[arg(long, env, default_value_t = 500)]
+    events_worker_poll_interval_ms: u64,
+
+    /// Most outbox events an events worker claims at once
+    #[arg(long, env, default_value_t = 100)]
+    events_worker_batch_size: u64,
+
+    /// Seconds an events worker's claim on outbox events lasts. Events claimed by a
+    /// worker that stopped are dispatched by another once the claim lapses.
+    #[arg(long, env, default_value_t = 60)]
+    events_worker_lease_seconds: u64,
+
+    /// Redis server that carries published events to every backend replica, as
+    /// `redis://[[username]:password@]host[:port]`, so SSE connections held by one replica
+    /// get the events published on another. Events stay in-process when unset, which is
+    /// only correct with a single replica.
+    #[arg(long, env)]
+    event_transport_url: Option<String>,
+
+    /// Redis pub/sub channel events are sent on; replicas sharing it see each other's
+    /// events.
+    #[arg(long, env, default_value = "refactor_platform:events")]
+    event_transport_channel: String,
+
+    /// Message bus every domain event is also published to, for other systems to consume:
+    /// Kafka as `kafka://host:port[,host:port...]`, or NATS as
+    /// `nats://[[user:password|token]@]host[:port]`. Not published when unset.
+    #[arg(long, env)]
+    event_bus_url: Option<String>,
+
+    /// Kafka topic, or prefix of the NATS subjects, events are published to
+    #[arg(long, env, default_value = "refactor_platform.events")]
+    event_bus_topic: String,
+
+    /// Months of domain events the event store keeps; older months are dropped whole.
+    /// 0 keeps every event.
+    #[arg(long, env, default_value_t = 24)]
+    event_store_retention_months: u32,
+
+    /// Days after which an entity's `*_updated` events are compacted to the latest one.
+    /// 0 keeps every update.
+    #[arg(long, env, default_value_t = 180)]
+    event_store_compact_after_days: u32,
+
+    /// Days a soft-deleted action, agreement, note, goal or coaching session stays
+    /// restorable before it's purged for good. 0 keeps deleted records forever.
+    #[arg(long, env, default_value_t = 30)]
+    soft_delete_retention_days: u32,
+
+    /// VAPID public key (base64url, uncompressed P-256 point) browsers subscribe to Web
+    /// Push notifications with. Push notifications are disabled unless both VAPID keys
+    /// are set.
+    #[arg(long, env)]
+    vapid_public_key: Option<String>,
+
+    /// VAPID private key (base64url, 32-byte P-256 scalar) push requests are signed with
+    #[arg(long, env)]
+    vapid_private_key: Option<String>,
+
+    /// Contact push services can reach the operator at, as a `mailto:` or `https:` URL.
+    /// Defaults to the frontend base URL.
+    #[arg(long, env)]
+    vapid_subject: Option<String>,
+
+    /// Secret the tokens in users' calendar feed URLs are signed with. Changing it
+    /// invalidates every feed URL handed out; feeds are disabled unless it's set.
+    #[arg(long, env)]
+    calendar_feed_signing_key: Option<String>,
+
+    /// Base URL of the S3-compatible object storage generated documents (such as session
+    /// records) are kept in, e.g. `https://nyc3.digitaloceanspaces.com`. Session records
+    /// are unavailable unless the endpoint, bucket and both keys are set.
+    #[arg(long, env)]
+    object_storage_endpoint: Option<String>,
+
+    /// Object storage bucket generated documents are kept in
+    #[arg(long, env)]
+    object_storage_bucket: Option<String>,
+
+    /// Region requests to the object storage are signed for
+    #[arg(long, env, default_value = "us-east-1")]
+    object_storage_region: String,
+
+    /// Access key ID for the object storage
+    #[arg(long, env)]
+    object_storage_access_key_id: Option<String>,
+
+    /// Secret access key for the object storage
+    #[arg(long, env)]
+    object_storage_secret_access_key: Option<String>,
+
+    /// Largest file, in megabytes, that may be attached to a coaching session, note or
+    /// action
+    #[arg(long, env, default_value_t = 25)]
+    attachment_max_size_mb: u64,
+
+    /// Megabytes of attachments each organization may store
+    #[arg(long, env, default_value_t = 1024)]
+    attachment_organization_quota_mb: u64,
+
+    /// Public base URL organization logos are served from, such as a CDN in front of this
+    /// backend; logo URLs are paths on this backend when unset
+    #[arg(long, env)]
+    organization_logo_base_url: Option<String>,
+
+    /// Exports every domain event to object storage as newline-delimited JSON, for an
+    /// analytics warehouse (BigQuery, Snowflake, ...) to load. Needs object storage.
+    #[arg(long, env, default_value_t = false)]
+    warehouse_export_enabled: bool,
+
+    /// Bucket exported events are written to; the object storage bucket when unset
+    #[arg(long, env)]
+    warehouse_export_bucket: Option<String>,
+
+    /// Key prefix exported events are written under
+    #[arg(long, env, default_value = "warehouse")]
+    warehouse_export_prefix: String,
+
+    /// Most events written to one exported file
+    #[arg(long, env, default_value_t = 500)]
+    warehouse_export_batch_size: u64,
+
+    /// Seconds events wait to be exported before a partial batch is written
+    #[arg(long, env, default_value_t = 60)]
+    warehouse_export_flush_interval_seconds: u64,
+
     /// Tracks whether each config field was explicitly set or uses its default.
     /// Populated during construction; not a CLI argument.
     #[arg(skip)]
@@ -569,21 +998,80 @@ impl Config {
     pub fn log_non_secret_config(&self) {
         debug!("Configuration:");
         self.debug_field("runtime_env", &self.runtime_env);
+        self.debug_field("api_docs_access", &self.api_docs_access());
         self.debug_field("api_version", &self.api_version);
         self.debug_field("interface", &self.interface);
         self.debug_field("port", &self.port);
         self.debug_field("log_level_filter", &self.log_level_filter);
         self.debug_field("allowed_origins", &self.allowed_origins);
+        self.debug_field("runtime_config_file", &self.runtime_config_file);
         self.debug_field("db_max_connections", &self.db_max_connections);
         self.debug_field("db_min_connections", &self.db_min_connections);
         self.debug_field("db_connect_timeout_secs", &self.db_connect_timeout_secs);
         self.debug_field("db_acquire_timeout_secs", &self.db_acquire_timeout_secs);
         self.debug_field("db_idle_timeout_secs", &self.db_idle_timeout_secs);
         self.debug_field("db_max_lifetime_secs", &self.db_max_lifetime_secs);
+        self.debug_field("db_statement_timeout_secs", &self.db_statement_timeout_secs);
+        self.debug_field(
+            "db_heavy_statement_timeout_secs",
+            &self.db_heavy_statement_timeout_secs,
+        );
+        self.debug_field("db_heavy_max_connections", &self.db_heavy_max_connections);
+        self.debug_field(
+            "db_health_check_interval_secs",
+            &self.db_health_check_interval_secs,
+        );
+        self.debug_field(
+            "db_health_failure_threshold",
+            &self.db_health_failure_threshold,
+        );
         self.debug_field(
             "backend_session_expiry_seconds",
             &self.backend_session_expiry_seconds,
         );
+        self.debug_field(
+            "backend_session_expiry_warning_seconds",
+            &self.backend_session_expiry_warning_seconds,
+        );
+        self.debug_field(
+            "remember_me_lifetime_seconds",
+            &self.remember_me_lifetime_seconds,
+        );
+        self.debug_field(
+            "reauthentication_window_seconds",
+            &self.reauthentication_window_seconds,
+        );
+        self.debug_field("sse_channel_capacity", &self.sse_channel_capacity);
+        self.debug_field("sse_overflow_policy", &self.sse_overflow_policy);
+        self.debug_field(
+            "sse_max_connections_per_user",
+            &self.sse_max_connections_per_user,
+        );
+        self.debug_field(
+            "sse_drain_reconnect_delay_seconds",
+            &self.sse_drain_reconnect_delay_seconds,
+        );
+        self.debug_field(
+            "sse_keep_alive_interval_seconds",
+            &self.sse_keep_alive_interval().as_secs(),
+        );
+        self.debug_field(
+            "sse_idle_timeout_seconds",
+            &self
+                .sse_idle_timeout()
+                .map_or(0, |timeout| timeout.as_secs()),
+        );
+        self.debug_field(
+            "sse_max_connection_lifetime_seconds",
+            &self
+                .sse_max_connection_lifetime()
+                .map_or(0, |lifetime| lifetime.as_secs()),
+        );
+        self.debug_field("rate_limit_burst", &self.rate_limit_burst);
+        self.debug_field(
+            "rate_limit_replenish_interval_ms",
+            &self.rate_limit_replenish_interval_ms,
+        );
         self.debug_field("tiptap_app_id", &self.tiptap_app_id);
         self.debug_field("resend_base_url", &self.resend_base_url);
         self.debug_field("welcome_email_template_id", &self.welcome_email_template_id);
@@ -599,6 +1087,19 @@ impl Config {
             "action_assigned_email_template_id",
             &self.action_assigned_email_template_id,
         );
+        self.debug_field("mention_email_template_id", &self.mention_email_template_id);
+        self.debug_field(
+            "progress_report_email_template_id",
+            &self.progress_report_email_template_id,
+        );
+        self.debug_field(
+            "account_lock_email_template_id",
+            &self.account_lock_email_template_id,
+        );
+        self.debug_field(
+            "session_request_email_template_id",
+            &self.session_request_email_template_id,
+        );
         self.debug_field("frontend_base_url", &self.frontend_base_url);
         self.debug_field(
             "session_scheduled_email_url_path",
@@ -608,6 +1109,14 @@ impl Config {
             "action_assigned_email_url_path",
             &self.action_assigned_email_url_path,
         );
+        self.debug_field(
+            "progress_report_email_url_path",
+            &self.progress_report_email_url_path,
+        );
+        self.debug_field(
+            "session_request_email_url_path",
+            &self.session_request_email_url_path,
+        );
         self.debug_field("magic_link_email_url_path", &self.magic_link_email_url_path);
         self.debug_field("magic_link_expiry_seconds", &self.magic_link_expiry_seconds);
         self.debug_field(
@@ -622,6 +1131,70 @@ impl Config {
             "password_reset_token_expiry_seconds",
             &self.password_reset_token_expiry_seconds,
         );
+        self.debug_field("password_min_length", &self.password_min_length);
+        self.debug_field(
+            "password_required_character_classes",
+            &self.password_required_character_classes,
+        );
+        self.debug_field("password_deny_list", &self.password_deny_list);
+        self.debug_field(
+            "password_breach_check_enabled",
+            &self.password_breach_check_enabled,
+        );
+        self.debug_field("pwned_passwords_api_url", &self.pwned_passwords_api_url);
+        self.debug_field(
+            "encrypted_note_search_enabled",
+            &self.encrypted_note_search_enabled,
+        );
+        self.debug_field("events_worker_enabled", &self.events_worker_enabled);
+        self.debug_field(
+            "events_worker_poll_interval_ms",
+            &self.events_worker_poll_interval_ms,
+        );
+        self.debug_field("events_worker_batch_size", &self.events_worker_batch_size);
+        self.debug_field(
+            "events_worker_lease_seconds",
+            &self.events_worker_lease_seconds,
+        );
+        self.debug_field("event_transport_channel", &self.event_transport_channel);
+        self.debug_field("event_bus_topic", &self.event_bus_topic);
+        self.debug_field(
+            "event_store_retention_months",
+            &self.event_store_retention_months,
+        );
+        self.debug_field(
+            "event_store_compact_after_days",
+            &self.event_store_compact_after_days,
+        );
+        self.debug_field(
+            "soft_delete_retention_days",
+            &self.soft_delete_retention_days,
+        );
+        self.debug_field("vapid_public_key", &self.vapid_public_key);
+        self.debug_field("vapid_subject", &self.vapid_subject);
+        self.debug_field("object_storage_endpoint", &self.object_storage_endpoint);
+        self.debug_field("object_storage_bucket", &self.object_storage_bucket);
+        self.debug_field("object_storage_region", &self.object_storage_region);
+        self.debug_field("attachment_max_size_mb", &self.attachment_max_size_mb);
+        self.debug_field(
+            "attachment_organization_quota_mb",
+            &self.attachment_organization_quota_mb,
+        );
+        self.debug_field(
+            "organization_logo_base_url",
+            &self.organization_logo_base_url,
+        );
+        self.debug_field("warehouse_export_enabled", &self.warehouse_export_enabled);
+        self.debug_field("warehouse_export_bucket", &self.warehouse_export_bucket);
+        self.debug_field("warehouse_export_prefix", &self.warehouse_export_prefix);
+        self.debug_field(
+            "warehouse_export_batch_size",
+            &self.warehouse_export_batch_size,
+        );
+        self.debug_field(
+            "warehouse_export_flush_interval_seconds",
+            &self.warehouse_export_flush_interval_seconds,
+        );
     }
 
     pub fn api_version(&self) -> &str {
@@ -692,6 +1265,26 @@ impl Config {
         self.action_assigned_email_template_id.clone()
     }
 
+    /// Returns the Resend template ID for mention notification emails, if configured.
+    pub fn mention_email_template_id(&self) -> Option<String> {
+        self.mention_email_template_id.clone()
+    }
+
+    /// Returns the Resend template ID for weekly progress report emails, if configured.
+    pub fn progress_report_email_template_id(&self) -> Option<String> {
+        self.progress_report_email_template_id.clone()
+    }
+
+    /// Returns the Resend template ID for account lock notification emails, if configured.
+    pub fn account_lock_email_template_id(&self) -> Option<String> {
+        self.account_lock_email_template_id.clone()
+    }
+
+    /// Returns the Resend template ID for session request emails, if configured.
+    pub fn session_request_email_template_id(&self) -> Option<String> {
+        self.session_request_email_template_id.clone()
+    }
+
     /// Returns the frontend application base URL used to construct links in emails.
     pub fn frontend_base_url(&self) -> Option<String> {
         self.frontend_base_url.clone()
@@ -717,6 +1310,26 @@ impl Config {
         }
     }
 
+    /// Returns the URL path template for the progress reports link in progress emails.
+    /// Falls back to the default if the configured value is empty.
+    pub fn progress_report_email_url_path(&self) -> &str {
+        if self.progress_report_email_url_path.is_empty() {
+            DEFAULT_PROGRESS_REPORT_EMAIL_URL_PATH
+        } else {
+            &self.progress_report_email_url_path
+        }
+    }
+
+    /// Returns the URL path template for the session requests link in session request emails.
+    /// Falls back to the default if the configured value is empty.
+    pub fn session_request_email_url_path(&self) -> &str {
+        if self.session_request_email_url_path.is_empty() {
+            DEFAULT_SESSION_REQUEST_EMAIL_URL_PATH
+        } else {
+            &self.session_request_email_url_path
+        }
+    }
+
     /// Returns the URL path template for magic link setup page.
     /// Falls back to the default if the configured value is empty.
     pub fn magic_link_email_url_path(&self) -> &str {
@@ -752,21 +1365,101 @@ impl Config {
         self.password_reset_token_expiry_seconds
     }
 
+    /// Returns the minimum length of a new password, in characters.
+    pub fn password_min_length(&self) -> usize {
+        self.password_min_length as usize
+    }
+
+    /// Returns the character classes a new password must include.
+    pub fn password_required_character_classes(&self) -> &[String] {
+        &self.password_required_character_classes
+    }
+
+    /// Returns the passwords refused on top of the built-in list.
+    pub fn password_deny_list(&self) -> &[String] {
+        &self.password_deny_list
+    }
+
+    /// Returns whether new passwords found in known data breaches are refused.
+    pub fn password_breach_check_enabled(&self) -> bool {
+        self.password_breach_check_enabled
+    }
+
+    /// A copy of this config with the reloadable settings taken from `runtime`.
+    pub fn with_runtime(&self, runtime: &RuntimeConfig) -> Self {
+        let mut config = self.clone();
+        config.allowed_origins.clone_from(&runtime.allowed_origins);
+        config.log_level_filter = runtime.log_level_filter;
+        config.password_breach_check_enabled = runtime.password_breach_check_enabled;
+        config
+    }
+
+    /// Returns the Pwned Passwords API base URL when breached passwords are refused.
+    pub fn pwned_passwords_api_url(&self) -> Option<&str> {
+        self.password_breach_check_enabled
+            .then_some(self.pwned_passwords_api_url.as_str())
+    }
+
     pub fn runtime_env(&self) -> RustEnv {
         self.runtime_env.clone()
     }
 
+    /// Who may open the OpenAPI web UI; only deployed environments hide it by default.
+    pub fn api_docs_access(&self) -> ApiDocsAccess {
+        self.api_docs_access
+            .clone()
+            .unwrap_or(match self.runtime_env {
+                RustEnv::Development => ApiDocsAccess::Public,
+                RustEnv::Staging | RustEnv::Production => ApiDocsAccess::Disabled,
+            })
+    }
+
     pub fn is_production(&self) -> bool {
         // This could check an environment variable, or a config field
         self.runtime_env() == RustEnv::Production
     }
 
+    /// How often an SSE connection with nothing to send is sent a keep-alive comment.
+    pub fn sse_keep_alive_interval(&self) -> Duration {
+        let seconds = self
+            .sse_keep_alive_interval_seconds
+            .unwrap_or(match self.runtime_env {
+                RustEnv::Development => 15,
+                RustEnv::Staging | RustEnv::Production => 20,
+            });
+        Duration::from_secs(seconds.max(1))
+    }
+
+    /// How long an SSE connection may go without an event before it's closed, if ever.
+    pub fn sse_idle_timeout(&self) -> Option<Duration> {
+        self.deployed_default(self.sse_idle_timeout_seconds, 30 * 60)
+    }
+
+    /// How long an SSE connection stays open before it's closed, if ever.
+    pub fn sse_max_connection_lifetime(&self) -> Option<Duration> {
+        self.deployed_default(self.sse_max_connection_lifetime_seconds, 60 * 60)
+    }
+
+    /// `seconds`, defaulting to off in development and `deployed_seconds` elsewhere;
+    /// 0 is off.
+    fn deployed_default(&self, seconds: Option<u64>, deployed_seconds: u64) -> Option<Duration> {
+        let seconds = seconds.unwrap_or(match self.runtime_env {
+            RustEnv::Development => 0,
+            RustEnv::Staging | RustEnv::Production => deployed_seconds,
+        });
+        (seconds > 0).then(|| Duration::from_secs(seconds))
+    }
+
     // AI Meeting Integration accessors
 
     pub fn encryption_key(&self) -> Option<String> {
         self.encryption_key.clone()
     }
 
+    pub fn encrypted_note_search_enabled(&self) -> bool {
+        self.encrypted_note_search_enabled
+    }
+
     pub fn google_client_id(&self) -> Option<String> {
         self.google_client_id.clone()
     }
@@ -828,6 +1521,153 @@ impl Config {
     pub fn recall_ai_webhook_secret(&self) -> Option<String> {
         self.recall_ai_webhook_secret.clone()
     }
+
+    // LLM gateway accessors
+
+    pub fn llm_gateway_api_key(&self) -> Option<String> {
+        self.llm_gateway_api_key.clone()
+    }
+
+    pub fn llm_gateway_url(&self) -> &str {
+        &self.llm_gateway_url
+    }
+
+    pub fn llm_gateway_model(&self) -> &str {
+        &self.llm_gateway_model
+    }
+
+    pub fn llm_gateway_embedding_model(&self) -> &str {
+        &self.llm_gateway_embedding_model
+    }
+
+    pub fn metrics_token(&self) -> Option<String> {
+        self.metrics_token.clone()
+    }
+
+    // Events worker accessors
+
+    pub fn events_worker_enabled(&self) -> bool {
+        self.events_worker_enabled
+    }
+
+    pub fn events_worker_poll_interval(&self) -> Duration {
+        Duration::from_millis(self.events_worker_poll_interval_ms)
+    }
+
+    pub fn events_worker_batch_size(&self) -> u64 {
+        self.events_worker_batch_size
+    }
+
+    pub fn events_worker_lease(&self) -> Duration {
+        Duration::from_secs(self.events_worker_lease_seconds)
+    }
+
+    pub fn event_transport_url(&self) -> Option<String> {
+        self.event_transport_url.clone()
+    }
+
+    pub fn event_transport_channel(&self) -> &str {
+        &self.event_transport_channel
+    }
+
+    pub fn event_bus_url(&self) -> Option<String> {
+        self.event_bus_url.clone()
+    }
+
+    pub fn event_bus_topic(&self) -> &str {
+        &self.event_bus_topic
+    }
+
+    /// `None` when events are kept forever.
+    pub fn event_store_retention_months(&self) -> Option<u32> {
+        Some(self.event_store_retention_months).filter(|&months| months > 0)
+    }
+
+    /// `None` when updates are never compacted.
+    pub fn event_store_compact_after_days(&self) -> Option<u32> {
+        Some(self.event_store_compact_after_days).filter(|&days| days > 0)
+    }
+
+    /// `None` when soft-deleted records are never purged.
+    pub fn soft_delete_retention_days(&self) -> Option<u32> {
+        Some(self.soft_delete_retention_days).filter(|&days| days > 0)
+    }
+
+    pub fn vapid_public_key(&self) -> Option<String> {
+        self.vapid_public_key.clone()
+    }
+
+    pub fn vapid_private_key(&self) -> Option<String> {
+        self.vapid_private_key.clone()
+    }
+
+    /// The VAPID subject, falling back to the frontend base URL.
+    pub fn vapid_subject(&self) -> Option<String> {
+        self.vapid_subject
+            .clone()
+            .or_else(|| self.frontend_base_url.clone())
+    }
+
+    pub fn calendar_feed_signing_key(&self) -> Option<String> {
+        self.calendar_feed_signing_key.clone()
+    }
+
+    pub fn object_storage_endpoint(&self) -> Option<String> {
+        self.object_storage_endpoint.clone()
+    }
+
+    pub fn object_storage_bucket(&self) -> Option<String> {
+        self.object_storage_bucket.clone()
+    }
+
+    pub fn object_storage_region(&self) -> &str {
+        &self.object_storage_region
+    }
+
+    pub fn object_storage_access_key_id(&self) -> Option<String> {
+        self.object_storage_access_key_id.clone()
+    }
+
+    pub fn object_storage_secret_access_key(&self) -> Option<String> {
+        self.object_storage_secret_access_key.clone()
+    }
+
+    pub fn attachment_max_size_mb(&self) -> u64 {
+        self.attachment_max_size_mb
+    }
+
+    pub fn attachment_organization_quota_mb(&self) -> u64 {
+        self.attachment_organization_quota_mb
+    }
+
+    /// The base URL organization logos are served from, without a trailing slash; empty
+    /// when they're served from this backend's own paths.
+    pub fn organization_logo_base_url(&self) -> &str {
+        self.organization_logo_base_url
+            .as_deref()
+            .unwrap_or_default()
+            .trim_end_matches('/')
+    }
+
+    pub fn warehouse_export_enabled(&self) -> bool {
+        self.warehouse_export_enabled
+    }
+
+    pub fn warehouse_export_bucket(&self) -> Option<String> {
+        self.warehouse_export_bucket.clone()
+    }
+
+    pub fn warehouse_export_prefix(&self) -> &str {
+        self.warehouse_export_prefix.trim_matches('/')
+    }
+
+    pub fn warehouse_export_batch_size(&self) -> usize {
+        self.warehouse_export_batch_size.max(1) as usize
+    }
+
+    pub fn warehouse_export_flush_interval(&self) -> Duration {
+        Duration::from_secs(self.warehouse_export_flush_interval_seconds.max(1))
+    }
 }
 
 impl ApiVersion {
@@ -959,6 +1799,71 @@ mod tests {
         }
     }
 
+    #[test]
+    fn sse_timeouts_default_by_runtime_env() {
+        let development = Config::from_args(["test_binary"]);
+        assert_eq!(
+            development.sse_keep_alive_interval(),
+            Duration::from_secs(15)
+        );
+        assert_eq!(development.sse_idle_timeout(), None);
+        assert_eq!(development.sse_max_connection_lifetime(), None);
+
+        let production = Config::from_args(["test_binary", "--runtime-env", "production"]);
+        assert_eq!(
+            production.sse_keep_alive_interval(),
+            Duration::from_secs(20)
+        );
+        assert_eq!(
+            production.sse_idle_timeout(),
+            Some(Duration::from_secs(1800))
+        );
+        assert_eq!(
+            production.sse_max_connection_lifetime(),
+            Some(Duration::from_secs(3600))
+        );
+    }
+
+    #[test]
+    fn api_docs_are_public_only_in_development_by_default() {
+        let development = Config::from_args(["test_binary"]);
+        assert_eq!(development.api_docs_access(), ApiDocsAccess::Public);
+
+        let staging = Config::from_args(["test_binary", "--runtime-env", "staging"]);
+        assert_eq!(staging.api_docs_access(), ApiDocsAccess::Disabled);
+
+        let production = Config::from_args([
+            "test_binary",
+            "--runtime-env",
+            "production",
+            "--api-docs-access",
+            "super_admin",
+        ]);
+        assert_eq!(production.api_docs_access(), ApiDocsAccess::SuperAdmin);
+    }
+
+    #[test]
+    fn sse_timeouts_can_be_set_or_turned_off() {
+        let config = Config::from_args([
+            "test_binary",
+            "--runtime-env",
+            "production",
+            "--sse-keep-alive-interval-seconds",
+            "45",
+            "--sse-idle-timeout-seconds",
+            "0",
+            "--sse-max-connection-lifetime-seconds",
+            "600",
+        ]);
+
+        assert_eq!(config.sse_keep_alive_interval(), Duration::from_secs(45));
+        assert_eq!(config.sse_idle_timeout(), None);
+        assert_eq!(
+            config.sse_max_connection_lifetime(),
+            Some(Duration::from_secs(600))
+        );
+    }
+
     #[test]
     #[serial]
     fn env_var_populates_field_when_no_flag() {
@@ -0,0 +1,268 @@
+//! Health of the database pool.
+//!
+//! [`monitor`] pings the database every `DB_HEALTH_CHECK_INTERVAL_SECS`. The pool
+//! reopens broken connections by itself, so a restarted database normally just costs a
+//! few failed probes. If the probes keep failing `DB_HEALTH_FAILURE_THRESHOLD` times in a
+//! row, the pool is assumed broken: the monitor builds a new one, backing off while the
+//! database is unreachable, and swaps it into the [`DatabaseSlot`] that requests take
+//! their pool from, so the server recovers without a restart.
+//!
+//! [`PoolHealth::render`] adds the pools' saturation and the probes' outcomes to the
+//! GET /metrics scrape, so an exhausted pool shows up in monitoring rather than as
+//! request timeouts.
+
+use crate::config::Config;
+use log::*;
+use sea_orm::DatabaseConnection;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Duration;
+
+/// First wait between attempts to build a new pool; doubled after each failure.
+const RECONNECT_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Longest wait between attempts to build a new pool.
+const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(60);
+
+/// The pool requests use, replaced when the health monitor builds a new one.
+#[derive(Debug)]
+pub struct DatabaseSlot {
+    current: RwLock<Arc<DatabaseConnection>>,
+}
+
+impl DatabaseSlot {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            current: RwLock::new(db),
+        }
+    }
+
+    /// The pool to query through now
+    pub fn current(&self) -> Arc<DatabaseConnection> {
+        Arc::clone(&self.current.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// Swaps in `db`. Queries already running finish on the old pool, which closes once
+    /// the last of them drops it.
+    pub fn replace(&self, db: Arc<DatabaseConnection>) {
+        *self.current.write().unwrap_or_else(PoisonError::into_inner) = db;
+    }
+}
+
+/// Outcomes of the health monitor's probes, scraped at GET /metrics.
+#[derive(Debug)]
+pub struct PoolHealth {
+    healthy: AtomicBool,
+    consecutive_failures: AtomicU64,
+    probe_failures: AtomicU64,
+    reconnects: AtomicU64,
+}
+
+impl PoolHealth {
+    pub fn new() -> Self {
+        Self {
+            healthy: AtomicBool::new(true),
+            consecutive_failures: AtomicU64::new(0),
+            probe_failures: AtomicU64::new(0),
+            reconnects: AtomicU64::new(0),
+        }
+    }
+
+    /// Whether the last probe reached the database
+    pub fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::Relaxed)
+    }
+
+    /// Records a probe, returning how many have failed in a row
+    fn probed(&self, succeeded: bool) -> u64 {
+        self.healthy.store(succeeded, Ordering::Relaxed);
+        if succeeded {
+            self.consecutive_failures.store(0, Ordering::Relaxed);
+            return 0;
+        }
+        self.probe_failures.fetch_add(1, Ordering::Relaxed);
+        self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    fn reconnected(&self) {
+        self.reconnects.fetch_add(1, Ordering::Relaxed);
+        self.probed(true);
+    }
+
+    /// The probes' outcomes and the saturation of each named pool in the Prometheus text
+    /// exposition format.
+    pub fn render<'a>(
+        &self,
+        pools: impl IntoIterator<Item = (&'a str, &'a DatabaseConnection)>,
+    ) -> String {
+        let mut out = String::new();
+
+        out.push_str("# HELP db_healthy Whether the last database probe succeeded.\n");
+        out.push_str("# TYPE db_healthy gauge\n");
+        let _ = writeln!(out, "db_healthy {}", u8::from(self.is_healthy()));
+        out.push_str("# HELP db_probe_failures_total Database probes that failed.\n");
+        out.push_str("# TYPE db_probe_failures_total counter\n");
+        let _ = writeln!(
+            out,
+            "db_probe_failures_total {}",
+            self.probe_failures.load(Ordering::Relaxed)
+        );
+        out.push_str(
+            "# HELP db_pool_reconnects_total Times the pool was replaced after failing probes.\n",
+        );
+        out.push_str("# TYPE db_pool_reconnects_total counter\n");
+        let _ = writeln!(
+            out,
+            "db_pool_reconnects_total {}",
+            self.reconnects.load(Ordering::Relaxed)
+        );
+
+        out.push_str(
+            "# HELP db_pool_connections Open connections in the pool, by whether a query \
+             holds them.\n",
+        );
+        out.push_str("# TYPE db_pool_connections gauge\n");
+        let mut saturation = String::new();
+        for (name, db) in pools {
+            // Only Postgres pools (not the mock database of tests) can be inspected
+            let DatabaseConnection::SqlxPostgresPoolConnection(_) = db else {
+                continue;
+            };
+            let pool = db.get_postgres_connection_pool();
+            let open = pool.size() as usize;
+            let idle = pool.num_idle().min(open);
+            let max = pool.options().get_max_connections();
+            let _ = writeln!(
+                out,
+                "db_pool_connections{{pool=\"{name}\",state=\"in_use\"}} {}",
+                open - idle
+            );
+            let _ = writeln!(
+                out,
+                "db_pool_connections{{pool=\"{name}\",state=\"idle\"}} {idle}"
+            );
+            let _ = writeln!(
+                saturation,
+                "db_pool_saturation{{pool=\"{name}\"}} {}",
+                (open - idle) as f64 / f64::from(max.max(1))
+            );
+        }
+        out.push_str(
+            "# HELP db_pool_saturation Share of the pool's maximum connections held by \
+             queries.\n",
+        );
+        out.push_str("# TYPE db_pool_saturation gauge\n");
+        out.push_str(&saturation);
+
+        out
+    }
+}
+
+impl Default for PoolHealth {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Probes the database through the pool in `database` for as long as the process runs,
+/// replacing the pool when it fails `DB_HEALTH_FAILURE_THRESHOLD` probes in a row.
+/// Returns at once when `DB_HEALTH_CHECK_INTERVAL_SECS` is 0.
+pub async fn monitor(config: Config, database: Arc<DatabaseSlot>, health: Arc<PoolHealth>) {
+    if config.db_health_check_interval_secs == 0 {
+        info!("Database health checks are disabled");
+        return;
+    }
+    let interval = Duration::from_secs(config.db_health_check_interval_secs);
+    let probe_timeout = Duration::from_secs(config.db_acquire_timeout_secs.max(1));
+    let threshold = u64::from(config.db_health_failure_threshold.max(1));
+
+    loop {
+        tokio::time::sleep(interval).await;
+
+        let was_healthy = health.is_healthy();
+        let succeeded = probe(&database.current(), probe_timeout).await;
+        let failures = health.probed(succeeded);
+        if succeeded {
+            if !was_healthy {
+                info!("Database is reachable again");
+            }
+            continue;
+        }
+        warn!("Database probe failed ({failures} in a row)");
+        if failures < threshold {
+            continue;
+        }
+
+        error!("Database pool failed {failures} probes in a row, replacing it");
+        let db = reconnect(&config, probe_timeout).await;
+        database.replace(Arc::new(db));
+        health.reconnected();
+        info!("Replaced the database pool");
+    }
+}
+
+/// Whether the database answers a ping through `db` within `timeout`
+async fn probe(db: &DatabaseConnection, timeout: Duration) -> bool {
+    matches!(tokio::time::timeout(timeout, db.ping()).await, Ok(Ok(())))
+}
+
+/// A new pool that reaches the database, retrying with exponential backoff until one does
+async fn reconnect(config: &Config, probe_timeout: Duration) -> DatabaseConnection {
+    let mut backoff = RECONNECT_BACKOFF;
+    loop {
+        match crate::init_database(config).await {
+            Ok(db) if probe(&db, probe_timeout).await => return db,
+            Ok(_) => warn!("New database pool can't reach the database yet"),
+            Err(e) => warn!("Failed to build a new database pool: {e}"),
+        }
+        debug!("Retrying the database connection in {backoff:?}");
+        tokio::time::sleep(backoff).await;
+        backoff = next_backoff(backoff);
+    }
+}
+
+fn next_backoff(backoff: Duration) -> Duration {
+    (backoff * 2).min(MAX_RECONNECT_BACKOFF)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slot_hands_out_the_pool_last_swapped_in() {
+        let first = Arc::new(DatabaseConnection::Disconnected);
+        let slot = DatabaseSlot::new(Arc::clone(&first));
+        assert!(Arc::ptr_eq(&slot.current(), &first));
+
+        let second = Arc::new(DatabaseConnection::Disconnected);
+        slot.replace(Arc::clone(&second));
+
+        assert!(Arc::ptr_eq(&slot.current(), &second));
+    }
+
+    #[test]
+    fn probes_count_failures_in_a_row_until_one_succeeds() {
+        let health = PoolHealth::new();
+
+        assert_eq!(health.probed(false), 1);
+        assert_eq!(health.probed(false), 2);
+        assert!(!health.is_healthy());
+        assert_eq!(health.probed(true), 0);
+        assert!(health.is_healthy());
+        assert_eq!(health.probed(false), 1);
+
+        let rendered = health.render([("main", &DatabaseConnection::Disconnected)]);
+        assert!(rendered.contains("db_healthy 0\n"));
+        assert!(rendered.contains("db_probe_failures_total 3\n"));
+        assert!(rendered.contains("db_pool_reconnects_total 0\n"));
+    }
+
+    #[test]
+    fn reconnect_backoff_doubles_up_to_a_minute() {
+        assert_eq!(next_backoff(Duration::from_secs(1)), Duration::from_secs(2));
+        assert_eq!(next_backoff(Duration::from_secs(32)), MAX_RECONNECT_BACKOFF);
+        assert_eq!(next_backoff(MAX_RECONNECT_BACKOFF), MAX_RECONNECT_BACKOFF);
+    }
+}
@@ -1,11 +1,17 @@
 use config::Config;
+use db_health::{DatabaseSlot, PoolHealth};
 use log::info;
+use metrics::EventMetrics;
+use runtime_config::RuntimeSettings;
 use sea_orm::{ConnectOptions, Database, DatabaseConnection, DbErr};
 use std::sync::Arc;
 use tokio::time::Duration;
 
 pub mod config;
+pub mod db_health;
 pub mod logging;
+pub mod metrics;
+pub mod runtime_config;
 
 /// Load environment variables from the `.env` file into the process environment.
 ///
@@ -17,20 +23,50 @@ pub fn load_env_file() {
 }
 
 pub async fn init_database(config: &Config) -> Result<DatabaseConnection, DbErr> {
-    info!(
-        "Database pool config: max_connections={}, min_connections={}, \
-         connect_timeout={}s, acquire_timeout={}s, idle_timeout={}s, max_lifetime={}s",
+    connect(
+        config,
         config.db_max_connections,
         config.db_min_connections,
+        config.db_statement_timeout_secs,
+    )
+    .await
+}
+
+/// A small pool for the heavy endpoints (AI, analytics, audit log), whose statements may
+/// run longer than the main pool allows. Keeping them apart also stops a burst of slow
+/// queries from holding every connection the rest of the API needs.
+pub async fn init_heavy_database(config: &Config) -> Result<DatabaseConnection, DbErr> {
+    connect(
+        config,
+        config.db_heavy_max_connections,
+        0,
+        config.db_heavy_statement_timeout_secs,
+    )
+    .await
+}
+
+async fn connect(
+    config: &Config,
+    max_connections: u32,
+    min_connections: u32,
+    statement_timeout_secs: u64,
+) -> Result<DatabaseConnection, DbErr> {
+    info!(
+        "Database pool config: max_connections={}, min_connections={}, \
+         connect_timeout={}s, acquire_timeout={}s, idle_timeout={}s, max_lifetime={}s, \
+         statement_timeout={}s",
+        max_connections,
+        min_connections,
         config.db_connect_timeout_secs,
         config.db_acquire_timeout_secs,
         config.db_idle_timeout_secs,
         config.db_max_lifetime_secs,
+        statement_timeout_secs,
     );
 
     let mut opt = ConnectOptions::new::<&str>(config.database_url());
-    opt.max_connections(config.db_max_connections)
-        .min_connections(config.db_min_connections)
+    opt.max_connections(max_connections)
+        .min_connections(min_connections)
         .connect_timeout(Duration::from_secs(config.db_connect_timeout_secs))
         .acquire_timeout(Duration::from_secs(config.db_acquire_timeout_secs))
         .idle_timeout(Duration::from_secs(config.db_idle_timeout_secs))
@@ -38,6 +74,12 @@ pub async fn init_database(config: &Config) -> Result<DatabaseConnection, DbErr>
         .sqlx_logging(true)
         .sqlx_logging_level(log::LevelFilter::Info)
         .set_schema_search_path("refactor_platform"); // Setting default PostgreSQL schema
+                                                      // Sent with every new connection, so Postgres cancels statements running past it
+                                                      // (SQLSTATE 57014) and frees the connection. 0 is Postgres' own "no limit".
+    let statement_timeout = format!("{}s", statement_timeout_secs);
+    opt.map_sqlx_postgres_opts(move |options| {
+        options.options([("statement_timeout", statement_timeout.as_str())])
+    });
 
     let db = Database::connect(opt).await?;
 
@@ -50,12 +92,24 @@ pub async fn init_database(config: &Config) -> Result<DatabaseConnection, DbErr>
 pub struct AppState {
     pub database_connection: Arc<DatabaseConnection>,
     pub config: Config,
+    /// Measurements of the event publisher, scraped at GET /metrics.
+    pub event_metrics: Arc<EventMetrics>,
+    /// The pool requests use, swapped by the database health monitor.
+    pub database_slot: Arc<DatabaseSlot>,
+    /// The health monitor's probes, scraped at GET /metrics.
+    pub pool_health: Arc<PoolHealth>,
+    /// The settings reloadable without a restart, as they apply now.
+    pub runtime_settings: Arc<RuntimeSettings>,
 }
 
 impl AppState {
     pub fn new(app_config: Config, db: &Arc<DatabaseConnection>) -> Self {
         Self {
             database_connection: Arc::clone(db),
+            event_metrics: Arc::new(EventMetrics::new()),
+            database_slot: Arc::new(DatabaseSlot::new(Arc::clone(db))),
+            pool_health: Arc::new(PoolHealth::new()),
+            runtime_settings: Arc::new(RuntimeSettings::new(&app_config)),
             config: app_config,
         }
     }
@@ -64,7 +118,10 @@ impl AppState {
         self.database_connection.as_ref()
     }
 
+    /// Swaps in a new pool, here and for every state sharing this one's database slot
     pub fn set_db_conn(&mut self, db: DatabaseConnection) {
         self.database_connection = Arc::new(db);
+        self.database_slot
+            .replace(Arc::clone(&self.database_connection));
     }
 }
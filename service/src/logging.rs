@@ -23,18 +23,26 @@ impl Logger {
     ///
     /// When the log level is set to Trace, all logs including dependency logs are shown.
     /// For all other log levels, verbose dependency logs are filtered out.
+    ///
+    /// The logger itself passes every level, so [`Logger::set_level`] can raise the level
+    /// later on; whether dependency logs are filtered is fixed here.
     pub fn init_logger(config: &Config) {
-        let log_level_filter = Self::convert_level_filter(config.log_level_filter);
         let apply_filters = Self::should_filter_dependencies(config.log_level_filter);
         let log_config = Self::build_log_config(apply_filters);
 
         simplelog::TermLogger::init(
-            log_level_filter,
+            simplelog::LevelFilter::Trace,
             log_config,
             simplelog::TerminalMode::Mixed,
             simplelog::ColorChoice::Auto,
         )
         .expect("Failed to start simplelog");
+        Self::set_level(config.log_level_filter);
+    }
+
+    /// Changes the log level of the running process.
+    pub fn set_level(level: LevelFilter) {
+        log::set_max_level(Self::convert_level_filter(level));
     }
 
     /// Converts log::LevelFilter to simplelog::LevelFilter.
@@ -0,0 +1,247 @@
+//! In-process metrics in the Prometheus text exposition format.
+//!
+//! [`EventMetrics`] implements the events crate's [`Metrics`] trait: it counts published
+//! domain events per event type and keeps a latency histogram per event handler, so slow
+//! handlers holding up SSE fan-out show up in monitoring. [`EventMetrics::render`]
+//! produces the body of a Prometheus scrape, and [`render_dead_letter_depth`] the gauge
+//! of events waiting in the dead-letter queue, which lives in the database.
+
+use events::{EventKind, Metrics};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Upper bounds of the handler latency histogram buckets, in seconds.
+const LATENCY_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Latencies of one handler with one outcome.
+#[derive(Debug, Default, Clone)]
+struct Histogram {
+    /// Observations per bucket (not cumulative); the last is above every bound.
+    buckets: [u64; LATENCY_BUCKETS.len() + 1],
+    sum_seconds: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, seconds: f64) {
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|bound| seconds <= *bound)
+            .unwrap_or(LATENCY_BUCKETS.len());
+        self.buckets[bucket] += 1;
+        self.sum_seconds += seconds;
+        self.count += 1;
+    }
+}
+
+/// Publish counts and handler latencies of the application's event publisher.
+#[derive(Debug)]
+pub struct EventMetrics {
+    /// Indexed like [`EventKind::ALL`].
+    published: [AtomicU64; EventKind::ALL.len()],
+    /// Keyed by handler name and whether the handler succeeded.
+    latencies: Mutex<HashMap<(String, bool), Histogram>>,
+}
+
+impl EventMetrics {
+    pub fn new() -> Self {
+        Self {
+            published: std::array::from_fn(|_| AtomicU64::new(0)),
+            latencies: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The metrics in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        out.push_str("# HELP events_published_total Domain events published, by event type.\n");
+        out.push_str("# TYPE events_published_total counter\n");
+        for (kind, count) in EventKind::ALL.iter().zip(&self.published) {
+            let _ = writeln!(
+                out,
+                "events_published_total{{event_type=\"{kind}\"}} {}",
+                count.load(Ordering::Relaxed)
+            );
+        }
+
+        out.push_str(
+            "# HELP event_handler_duration_seconds Time an event handler took to process an \
+             event, including retries.\n",
+        );
+        out.push_str("# TYPE event_handler_duration_seconds histogram\n");
+        // Sorted so scrapes list series in a stable order.
+        let latencies: BTreeMap<(String, bool), Histogram> = self
+            .latencies
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .map(|(key, histogram)| (key.clone(), histogram.clone()))
+            .collect();
+        for ((handler, succeeded), histogram) in latencies {
+            let labels = format!(
+                "handler=\"{}\",outcome=\"{}\"",
+                escape(&handler),
+                if succeeded { "success" } else { "failure" }
+            );
+            let mut cumulative = 0;
+            for (bound, count) in LATENCY_BUCKETS.iter().zip(&histogram.buckets) {
+                cumulative += count;
+                let _ = writeln!(
+                    out,
+                    "event_handler_duration_seconds_bucket{{{labels},le=\"{bound}\"}} {cumulative}"
+                );
+            }
+            let _ = writeln!(
+                out,
+                "event_handler_duration_seconds_bucket{{{labels},le=\"+Inf\"}} {}",
+                histogram.count
+            );
+            let _ = writeln!(
+                out,
+                "event_handler_duration_seconds_sum{{{labels}}} {}",
+                histogram.sum_seconds
+            );
+            let _ = writeln!(
+                out,
+                "event_handler_duration_seconds_count{{{labels}}} {}",
+                histogram.count
+            );
+        }
+
+        out
+    }
+}
+
+impl Default for EventMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Metrics for EventMetrics {
+    fn event_published(&self, kind: EventKind) {
+        if let Some(index) = EventKind::ALL.iter().position(|k| *k == kind) {
+            self.published[index].fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn handler_finished(
+        &self,
+        handler_name: &str,
+        _kind: EventKind,
+        elapsed: Duration,
+        succeeded: bool,
+    ) {
+        self.latencies
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry((handler_name.to_string(), succeeded))
+            .or_default()
+            .observe(elapsed.as_secs_f64());
+    }
+}
+
+/// The dead letters not yet requeued, given as `(handler_name, event_type, count)`, in
+/// the Prometheus text exposition format.
+pub fn render_dead_letter_depth<'a>(
+    depths: impl IntoIterator<Item = (&'a str, &'a str, u64)>,
+) -> String {
+    let mut out = String::new();
+    out.push_str(
+        "# HELP dead_letter_events Dead-lettered events waiting to be requeued, by handler \
+         and event type.\n",
+    );
+    out.push_str("# TYPE dead_letter_events gauge\n");
+    // Sorted so scrapes list series in a stable order.
+    let depths: BTreeMap<(&str, &str), u64> = depths
+        .into_iter()
+        .map(|(handler, event_type, count)| ((handler, event_type), count))
+        .collect();
+    for ((handler, event_type), count) in depths {
+        let _ = writeln!(
+            out,
+            "dead_letter_events{{handler=\"{}\",event_type=\"{}\"}} {count}",
+            escape(handler),
+            escape(event_type)
+        );
+    }
+    out
+}
+
+/// Escapes a label value per the exposition format.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace('"', "\\\"")
+        .replace('\n', r"\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_counts_events_and_accumulates_latency_buckets() {
+        let metrics = EventMetrics::new();
+        metrics.event_published(EventKind::GoalCreated);
+        metrics.event_published(EventKind::GoalCreated);
+        metrics.handler_finished(
+            "sse::Handler",
+            EventKind::GoalCreated,
+            Duration::from_millis(20),
+            true,
+        );
+        metrics.handler_finished(
+            "sse::Handler",
+            EventKind::GoalCreated,
+            Duration::from_secs(30),
+            true,
+        );
+
+        let rendered = metrics.render();
+
+        assert!(rendered.contains("events_published_total{event_type=\"goal_created\"} 2\n"));
+        assert!(rendered.contains("events_published_total{event_type=\"goal_deleted\"} 0\n"));
+        let labels = "handler=\"sse::Handler\",outcome=\"success\"";
+        assert!(rendered.contains(&format!(
+            "event_handler_duration_seconds_bucket{{{labels},le=\"0.01\"}} 0\n"
+        )));
+        assert!(rendered.contains(&format!(
+            "event_handler_duration_seconds_bucket{{{labels},le=\"0.025\"}} 1\n"
+        )));
+        assert!(rendered.contains(&format!(
+            "event_handler_duration_seconds_bucket{{{labels},le=\"10\"}} 1\n"
+        )));
+        assert!(rendered.contains(&format!(
+            "event_handler_duration_seconds_bucket{{{labels},le=\"+Inf\"}} 2\n"
+        )));
+        assert!(rendered.contains(&format!(
+            "event_handler_duration_seconds_count{{{labels}}} 2\n"
+        )));
+    }
+
+    #[test]
+    fn render_dead_letter_depth_lists_a_gauge_per_handler_and_event_type() {
+        let rendered = render_dead_letter_depth([
+            ("sse::Handler", "goal_created", 3),
+            ("audit_log", "goal_deleted", 1),
+        ]);
+
+        assert!(rendered.contains("# TYPE dead_letter_events gauge\n"));
+        assert!(rendered.ends_with(
+            "dead_letter_events{handler=\"audit_log\",event_type=\"goal_deleted\"} 1\n\
+             dead_letter_events{handler=\"sse::Handler\",event_type=\"goal_created\"} 3\n"
+        ));
+    }
+
+    #[test]
+    fn escape_quotes_label_values() {
+        assert_eq!(escape("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+}
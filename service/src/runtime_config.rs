@@ -0,0 +1,328 @@
+//! Settings that can change while the server runs.
+//!
+//! The allowed CORS origins, the log level and the password breach check start out as
+//! configured, and the JSON file named by `RUNTIME_CONFIG_FILE` may override any of them:
+//!
+//! ```json
+//! {
+//!   "allowed_origins": ["https://myrefactor.com"],
+//!   "log_level_filter": "DEBUG",
+//!   "password_breach_check_enabled": true
+//! }
+//! ```
+//!
+//! [`RuntimeSettings::reload`] re-reads the file, on SIGHUP or POST /admin/config/reload,
+//! and swaps in its settings once they validate, logging what changed. Requests started
+//! after the swap see the new settings; a file that doesn't validate leaves the current
+//! ones in place. Settings the file leaves out go back to their configured values.
+
+use crate::config::Config;
+use log::*;
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::sync::{Arc, PoisonError, RwLock};
+
+/// The settings in the runtime config file; those left out keep their configured values.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Overrides {
+    allowed_origins: Option<Vec<String>>,
+    log_level_filter: Option<String>,
+    password_breach_check_enabled: Option<bool>,
+}
+
+/// The settings that can be reloaded, as they apply now.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeConfig {
+    pub allowed_origins: Vec<String>,
+    pub log_level_filter: LevelFilter,
+    pub password_breach_check_enabled: bool,
+}
+
+impl RuntimeConfig {
+    /// The settings as configured at startup
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            allowed_origins: config.allowed_origins.clone(),
+            log_level_filter: config.log_level_filter,
+            password_breach_check_enabled: config.password_breach_check_enabled(),
+        }
+    }
+
+    /// These settings with the file's overrides applied, or why the overrides are invalid
+    fn overridden(&self, overrides: Overrides) -> Result<Self, ReloadError> {
+        let mut config = self.clone();
+        if let Some(allowed_origins) = overrides.allowed_origins {
+            if let Some(origin) = allowed_origins.iter().find(|origin| !is_origin(origin)) {
+                return Err(ReloadError::Invalid(format!(
+                    "allowed_origins: {origin:?} is neither \"*\" nor an http(s) origin"
+                )));
+            }
+            config.allowed_origins = allowed_origins;
+        }
+        if let Some(level) = overrides.log_level_filter {
+            config.log_level_filter = level.parse().map_err(|_| {
+                ReloadError::Invalid(format!(
+                    "log_level_filter: {level:?} is not one of OFF, ERROR, WARN, INFO, DEBUG, TRACE"
+                ))
+            })?;
+        }
+        if let Some(enabled) = overrides.password_breach_check_enabled {
+            config.password_breach_check_enabled = enabled;
+        }
+        Ok(config)
+    }
+
+    /// What differs in `new`, one "setting: old -> new" line per changed setting
+    pub fn changes(&self, new: &Self) -> Vec<String> {
+        let mut changes = Vec::new();
+        if self.allowed_origins != new.allowed_origins {
+            changes.push(format!(
+                "allowed_origins: {:?} -> {:?}",
+                self.allowed_origins, new.allowed_origins
+            ));
+        }
+        if self.log_level_filter != new.log_level_filter {
+            changes.push(format!(
+                "log_level_filter: {} -> {}",
+                self.log_level_filter, new.log_level_filter
+            ));
+        }
+        if self.password_breach_check_enabled != new.password_breach_check_enabled {
+            changes.push(format!(
+                "password_breach_check_enabled: {} -> {}",
+                self.password_breach_check_enabled, new.password_breach_check_enabled
+            ));
+        }
+        changes
+    }
+
+    /// Whether a request from `origin` may read responses. "*" allows every origin, except
+    /// in production, where it's ignored.
+    pub fn allows_origin(&self, origin: &str, production: bool) -> bool {
+        self.allowed_origins
+            .iter()
+            .any(|allowed| allowed == origin || (allowed == "*" && !production))
+    }
+}
+
+/// "*" or a scheme, host and optional port, as in the Origin header
+fn is_origin(origin: &str) -> bool {
+    if origin == "*" {
+        return true;
+    }
+    let Some((scheme, host)) = origin.split_once("://") else {
+        return false;
+    };
+    matches!(scheme, "http" | "https")
+        && !host.is_empty()
+        && origin.is_ascii()
+        && !host.contains(|c: char| c.is_whitespace() || matches!(c, '/' | '?' | '#'))
+}
+
+/// Why the runtime config file wasn't applied.
+#[derive(Debug)]
+pub enum ReloadError {
+    Read(std::io::Error),
+    Parse(serde_json::Error),
+    Invalid(String),
+}
+
+impl std::fmt::Display for ReloadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Read(e) => write!(f, "can't read the runtime config file: {e}"),
+            Self::Parse(e) => write!(f, "the runtime config file isn't valid: {e}"),
+            Self::Invalid(message) => write!(f, "the runtime config file isn't valid: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for ReloadError {}
+
+/// The runtime settings requests read, swapped by [`RuntimeSettings::reload`].
+#[derive(Debug)]
+pub struct RuntimeSettings {
+    configured: RuntimeConfig,
+    file: Option<PathBuf>,
+    production: bool,
+    current: RwLock<Arc<RuntimeConfig>>,
+}
+
+impl RuntimeSettings {
+    /// The settings as configured, until the first reload applies `RUNTIME_CONFIG_FILE`
+    pub fn new(config: &Config) -> Self {
+        let configured = RuntimeConfig::from_config(config);
+        Self {
+            current: RwLock::new(Arc::new(configured.clone())),
+            configured,
+            file: config.runtime_config_file.as_ref().map(PathBuf::from),
+            production: config.is_production(),
+        }
+    }
+
+    /// The settings that apply now
+    pub fn current(&self) -> Arc<RuntimeConfig> {
+        Arc::clone(&self.current.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// Whether a request from `origin` may read responses, under the current settings
+    pub fn allows_origin(&self, origin: &str) -> bool {
+        self.current().allows_origin(origin, self.production)
+    }
+
+    /// Re-reads `RUNTIME_CONFIG_FILE` and swaps in the configured settings with its
+    /// overrides, returning what changed. Without a file, the configured settings apply.
+    pub fn reload(&self) -> Result<Vec<String>, ReloadError> {
+        let overrides = match &self.file {
+            Some(file) => {
+                let contents = std::fs::read_to_string(file).map_err(ReloadError::Read)?;
+                serde_json::from_str(&contents).map_err(ReloadError::Parse)?
+            }
+            None => Overrides::default(),
+        };
+        let new = self.configured.overridden(overrides)?;
+
+        let mut current = self.current.write().unwrap_or_else(PoisonError::into_inner);
+        let changes = current.changes(&new);
+        if new.log_level_filter != current.log_level_filter {
+            crate::logging::Logger::set_level(new.log_level_filter);
+        }
+        *current = Arc::new(new);
+        drop(current);
+
+        if changes.is_empty() {
+            info!("Runtime config reloaded, nothing changed");
+        }
+        for change in &changes {
+            info!("Runtime config reloaded: {change}");
+        }
+        Ok(changes)
+    }
+}
+
+/// Reloads `settings` on every SIGHUP for as long as the process runs.
+#[cfg(unix)]
+pub async fn reload_on_hangup(settings: Arc<RuntimeSettings>) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangups = match signal(SignalKind::hangup()) {
+        Ok(hangups) => hangups,
+        Err(e) => {
+            warn!("Failed to listen for SIGHUP, the runtime config reloads only over HTTP: {e:?}");
+            return;
+        }
+    };
+    while hangups.recv().await.is_some() {
+        if let Err(e) = settings.reload() {
+            error!("Runtime config not reloaded on SIGHUP: {e}");
+        }
+    }
+}
+
+#[cfg(not(unix))]
+pub async fn reload_on_hangup(_settings: Arc<RuntimeSettings>) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn configured() -> RuntimeConfig {
+        RuntimeConfig {
+            allowed_origins: vec!["http://localhost:3000".to_string()],
+            log_level_filter: LevelFilter::Info,
+            password_breach_check_enabled: false,
+        }
+    }
+
+    fn overrides(json: &str) -> Overrides {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn the_file_overrides_only_the_settings_it_names() {
+        let new = configured()
+            .overridden(overrides(r#"{"log_level_filter": "debug"}"#))
+            .unwrap();
+
+        assert_eq!(new.log_level_filter, LevelFilter::Debug);
+        assert_eq!(new.allowed_origins, configured().allowed_origins);
+        assert!(!new.password_breach_check_enabled);
+    }
+
+    #[test]
+    fn invalid_overrides_are_refused() {
+        assert!(matches!(
+            configured().overridden(overrides(r#"{"log_level_filter": "loud"}"#)),
+            Err(ReloadError::Invalid(_))
+        ));
+        for origin in [
+            "localhost:3000",
+            "ftp://example.com",
+            "https://example.com/app",
+            "",
+        ] {
+            let json = format!(r#"{{"allowed_origins": ["{origin}"]}}"#);
+            assert!(
+                configured().overridden(overrides(&json)).is_err(),
+                "{origin:?} should be refused"
+            );
+        }
+        assert!(serde_json::from_str::<Overrides>(r#"{"rate_limit": 5}"#).is_err());
+    }
+
+    #[test]
+    fn changes_list_each_setting_that_differs() {
+        let new = configured()
+            .overridden(overrides(
+                r#"{"allowed_origins": ["https://myrefactor.com"], "password_breach_check_enabled": true}"#,
+            ))
+            .unwrap();
+
+        assert_eq!(
+            configured().changes(&new),
+            vec![
+                r#"allowed_origins: ["http://localhost:3000"] -> ["https://myrefactor.com"]"#,
+                "password_breach_check_enabled: false -> true",
+            ]
+        );
+        assert!(new.changes(&new).is_empty());
+    }
+
+    #[test]
+    fn a_wildcard_origin_allows_any_origin_outside_production() {
+        let config = RuntimeConfig {
+            allowed_origins: vec!["*".to_string()],
+            ..configured()
+        };
+
+        assert!(config.allows_origin("https://example.com", false));
+        assert!(!config.allows_origin("https://example.com", true));
+        assert!(configured().allows_origin("http://localhost:3000", true));
+        assert!(!configured().allows_origin("http://localhost:3001", false));
+    }
+
+    #[test]
+    fn reloading_swaps_in_the_file_and_keeps_the_settings_when_it_is_invalid() {
+        let file = std::env::temp_dir().join(format!("runtime-config-{}.json", std::process::id()));
+        std::fs::write(&file, r#"{"password_breach_check_enabled": true}"#).unwrap();
+        let mut config = Config::default();
+        config.runtime_config_file = Some(file.display().to_string());
+        let settings = RuntimeSettings::new(&config);
+
+        let changes = settings.reload().unwrap();
+
+        assert_eq!(
+            changes,
+            vec!["password_breach_check_enabled: false -> true"]
+        );
+        assert!(settings.current().password_breach_check_enabled);
+
+        std::fs::write(&file, r#"{"password_breach_check_enabled": "yes"}"#).unwrap();
+        assert!(matches!(settings.reload(), Err(ReloadError::Parse(_))));
+        assert!(settings.current().password_breach_check_enabled);
+
+        std::fs::remove_file(&file).unwrap();
+        assert!(matches!(settings.reload(), Err(ReloadError::Read(_))));
+    }
+}
@@ -0,0 +1,120 @@
+//! Manages field-level encryption of an organization's note and journal entry bodies.
+//!
+//! Reads the same environment as the web process (`DATABASE_URL`, `ENCRYPTION_KEY`, ...).
+//!
+//! ```text
+//! field_encryption enable <ORGANIZATION_ID>   # encrypt new bodies, then existing ones
+//! field_encryption rotate <ORGANIZATION_ID>   # switch to a new key and re-encrypt
+//! field_encryption disable <ORGANIZATION_ID>  # stop encrypting and decrypt existing bodies
+//! field_encryption reseal <ORGANIZATION_ID>   # bring existing bodies in line with the key
+//! field_encryption status <ORGANIZATION_ID>
+//! ```
+
+use clap::{Parser, Subcommand};
+use domain::{encryption, Id};
+use log::{error, info};
+use service::{config::Config, logging::Logger};
+use std::process;
+
+#[derive(Parser)]
+#[command(about = "Manages field-level encryption of note and journal entry bodies")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Creates the organization's data key and encrypts its existing bodies
+    Enable { organization_id: Id },
+    /// Replaces the organization's data key and re-encrypts its bodies with the new one
+    Rotate { organization_id: Id },
+    /// Retires the organization's data key and decrypts its bodies
+    Disable { organization_id: Id },
+    /// Encrypts, re-encrypts or decrypts existing bodies to match the active key, e.g.
+    /// after an interrupted run
+    Reseal { organization_id: Id },
+    /// Lists the organization's data keys
+    Status { organization_id: Id },
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+    service::load_env_file();
+    let config = Config::from_args(["field_encryption"]);
+    Logger::init_logger(&config);
+
+    match encryption::install(&config) {
+        Ok(true) => {}
+        Ok(false) => {
+            error!("ENCRYPTION_KEY must be set");
+            process::exit(1);
+        }
+        Err(e) => {
+            error!("ENCRYPTION_KEY is invalid: {e}");
+            process::exit(1);
+        }
+    }
+
+    let db = match service::init_database(&config).await {
+        Ok(db) => db,
+        Err(e) => {
+            error!("Failed to establish database connection: {e}");
+            process::exit(1);
+        }
+    };
+
+    // Every command but `status` leaves the bodies to be brought in line with the key.
+    let result = match cli.command {
+        Command::Enable { organization_id } => encryption::enable(&db, &config, organization_id)
+            .await
+            .map(|_| Some(organization_id)),
+        Command::Rotate { organization_id } => encryption::rotate(&db, &config, organization_id)
+            .await
+            .map(|_| Some(organization_id)),
+        Command::Disable { organization_id } => encryption::disable(&db, organization_id)
+            .await
+            .map(|()| Some(organization_id)),
+        Command::Reseal { organization_id } => Ok(Some(organization_id)),
+        Command::Status { organization_id } => {
+            match encryption::find_by_organization(&db, organization_id).await {
+                Ok(keys) => {
+                    if keys.is_empty() {
+                        info!("Organization {organization_id} has never encrypted its bodies");
+                    }
+                    for key in keys {
+                        match key.retired_at {
+                            Some(retired_at) => info!(
+                                "Key {} created {} retired {retired_at}",
+                                key.id, key.created_at
+                            ),
+                            None => info!("Key {} created {} (active)", key.id, key.created_at),
+                        }
+                    }
+                    Ok(None)
+                }
+                Err(e) => Err(e.into()),
+            }
+        }
+    };
+
+    let result = match result {
+        Ok(Some(organization_id)) => encryption::reseal_organization(&db, organization_id)
+            .await
+            .map(|resealed| {
+                info!(
+                    "Rewrote {} note(s) and {} journal entry(ies)",
+                    resealed.notes, resealed.journal_entries
+                )
+            })
+            .map_err(domain::error::Error::from),
+        Ok(None) => Ok(()),
+        Err(e) => Err(e),
+    };
+
+    if let Err(e) = result {
+        error!("Failed: {e}");
+        process::exit(1);
+    }
+}
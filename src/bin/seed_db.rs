@@ -1,24 +1,95 @@
+//! Seeds the database.
+//!
+//! Reads the same environment as the web process (`DATABASE_URL`, `RUNTIME_ENV`, ...).
+//! Outside production it inserts demo organizations, users and coaching sessions. With
+//! `RUNTIME_ENV=production` it refuses unless given `--force-seed` and the database's
+//! name typed at the prompt, and then only creates the bootstrap SuperAdmin, whose
+//! generated password is printed once.
+//!
+//! ```text
+//! seed_db                                       # demo data, outside production
+//! seed_db --force-seed [--admin-email <EMAIL>]  # bootstrap admin, in production
+//! ```
+
+use clap::Parser;
+use domain::seed;
 use log::{error, info};
 use service::{config::Config, logging::Logger};
-use std::sync::Arc;
+use std::io::{self, BufRead, Write};
+use std::process;
+
+#[derive(Parser)]
+#[command(about = "Seeds the database with demo data, or a production one with its first admin")]
+struct Cli {
+    /// Seed a production database, after typing its name to confirm. Only the bootstrap
+    /// SuperAdmin is created.
+    #[arg(long)]
+    force_seed: bool,
+    /// Email of the bootstrap SuperAdmin created in production
+    #[arg(long, default_value = "admin@refactorcoach.com")]
+    admin_email: String,
+}
 
 #[tokio::main]
 async fn main() {
+    let cli = Cli::parse();
     service::load_env_file();
-    let config = Config::new();
-    Logger::init_logger(&config as &Config);
+    let config = Config::from_args(["seed_db"]);
+    Logger::init_logger(&config);
 
-    info!("Seeding database [{}]...", config.database_url());
+    let database = database_name(config.database_url());
+    if config.is_production() {
+        if !cli.force_seed {
+            error!(
+                "Refusing to seed the production database {database}; pass --force-seed to \
+                 create its bootstrap admin"
+            );
+            process::exit(1);
+        }
+        if !confirmed(database) {
+            error!("Confirmation didn't match, nothing was seeded");
+            process::exit(1);
+        }
+    }
 
     let db = match service::init_database(&config).await {
-        Ok(db) => Arc::new(db),
+        Ok(db) => db,
         Err(e) => {
             error!("Failed to establish database connection: {e}");
-            std::process::exit(1);
+            process::exit(1);
         }
     };
 
-    let service_state = service::AppState::new(config, &db);
+    if !config.is_production() {
+        info!("Seeding database {database} with demo data...");
+        seed::demo(&db).await;
+        return;
+    }
+
+    match seed::bootstrap_admin(&db, &cli.admin_email).await {
+        Ok(admin) => {
+            // Printed rather than logged, so it doesn't end up in log storage
+            println!("Bootstrap SuperAdmin: {}", admin.user.email);
+            println!("One-time password:   {}", admin.password);
+            println!("Sign in and change this password now; it isn't shown again.");
+        }
+        Err(e) => {
+            error!("Failed to create the bootstrap admin: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Whether the operator typed the name of the database at the prompt
+fn confirmed(database: &str) -> bool {
+    eprint!("This seeds the PRODUCTION database. Type its name ({database}) to continue: ");
+    let _ = io::stderr().flush();
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer).is_ok() && answer.trim() == database
+}
 
-    entity_api::seed_database(service_state.db_conn_ref()).await;
+/// The database a Postgres URL points at, without its credentials or host
+fn database_name(url: &str) -> &str {
+    let path = url.rsplit('/').next().unwrap_or(url);
+    path.split('?').next().unwrap_or(path)
 }
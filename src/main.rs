@@ -23,10 +23,13 @@
 //! and/or teams by providing a single application that facilitates and enhances
 //! your coaching practice.
 
-use domain::gateway::recall_ai;
-use events::EventPublisher;
+use domain::gateway::{llm_gateway, recall_ai};
+use events::transport::redis::RedisTransport;
+use events::{BusPublisher, EventPublisher, LoggingInterceptor};
 use log::*;
-use meeting_ai::traits::{recording_bot, transcription as transcription_trait};
+use meeting_ai::traits::{
+    analysis, embedding, recording_bot, transcription as transcription_trait,
+};
 use meeting_auth::webhook::svix::Validator as SvixValidator;
 use service::{config::Config, logging::Logger};
 use std::process;
@@ -53,6 +56,14 @@ async fn main() {
         process::exit(1);
     }
 
+    let heavy_db_conn = match service::init_heavy_database(&config).await {
+        Ok(db) => Arc::new(db),
+        Err(e) => {
+            error!("Failed to establish the heavy endpoints' database connection: {e}");
+            process::exit(1);
+        }
+    };
+
     // Fail fast on a malformed secret rather than returning 401 on the first webhook.
     if let Some(secret) = config.recall_ai_webhook_secret() {
         if let Err(e) = SvixValidator::new("recall_ai".to_string(), &secret) {
@@ -61,15 +72,139 @@ async fn main() {
         }
     }
 
+    if let Err(e) = domain::encryption::install(&config) {
+        error!("ENCRYPTION_KEY is set but invalid: {e}");
+        process::exit(1);
+    }
+
     // Create service-level state (infrastructure only - no SSE)
     let service_state = service::AppState::new(config, &db_conn);
 
-    // Create SSE manager (web/application layer concern)
-    let sse_manager = Arc::new(sse::Manager::new());
+    // Apply RUNTIME_CONFIG_FILE, refusing to start on one a reload would refuse.
+    if let Err(e) = service_state.runtime_settings.reload() {
+        error!("RUNTIME_CONFIG_FILE is set but not applied: {e}");
+        process::exit(1);
+    }
 
-    // Create event publisher and register SSE event handler
+    // Create SSE manager (web/application layer concern). Relationship-scoped events go
+    // to the relationship's coach and coachee, looked up here and cached by the manager.
+    // Each connection's channel is bounded, so a stalled client can't grow memory, and
+    // each user's connections are capped, so many open tabs can't grow the registry.
+    let sse_channel_config = sse::channel::ChannelConfig {
+        capacity: service_state.config.sse_channel_capacity as usize,
+        overflow: service_state
+            .config
+            .sse_overflow_policy
+            .parse()
+            .unwrap_or_default(),
+    };
+    let sse_manager = Arc::new(
+        sse::Manager::new()
+            .with_relationship_members(Arc::new(web::sse::DbRelationshipMembers::new(Arc::clone(
+                &db_conn,
+            ))))
+            .with_channel_config(sse_channel_config)
+            .with_max_connections_per_user(
+                Some(service_state.config.sse_max_connections_per_user as usize)
+                    .filter(|&max| max > 0),
+            ),
+    );
+
+    // Create event publisher and register the SSE and background event handlers. Events
+    // a handler fails to process are dead-lettered for SuperAdmins to requeue; publish
+    // counts and handler latencies are exported at GET /metrics. With the events worker
+    // enabled, only the SSE handler runs here and the rest are left to the worker; with
+    // an event transport, SSE events reach the handler of every replica through it.
+    // Every published event is logged at debug level.
     let sse_event_handler = Arc::new(sse::SseDomainEventHandler::new(Arc::clone(&sse_manager)));
-    let event_publisher = EventPublisher::new().with_handler(sse_event_handler);
+    let mut event_publisher = domain::outbox::register_handlers(
+        EventPublisher::new()
+            .with_handler_for(sse::SseDomainEventHandler::KINDS, sse_event_handler),
+        &db_conn,
+    )
+    .with_dead_letters(Arc::new(domain::dead_letter::DeadLetterStore::new(
+        Arc::clone(&db_conn),
+    )))
+    .with_metrics(service_state.event_metrics.clone())
+    .with_interceptor(Arc::new(LoggingInterceptor));
+    // Push notifications skip users with an SSE connection open here; run in the events
+    // worker, which has none, they go to every recipient.
+    match domain::push::Vapid::from_config(&service_state.config) {
+        Ok(Some(vapid)) => {
+            let sse_manager = Arc::clone(&sse_manager);
+            let push_handler =
+                domain::push::PushNotificationHandler::new(Arc::clone(&db_conn), vapid)
+                    .with_presence(Arc::new(move |user_id| {
+                        sse_manager.is_connected(&user_id.to_string())
+                    }));
+            event_publisher = event_publisher.with_handler_for(
+                domain::push::PushNotificationHandler::KINDS,
+                Arc::new(push_handler),
+            );
+        }
+        Ok(None) => info!("VAPID keys not set — push notifications disabled"),
+        Err(e) => {
+            error!("VAPID_PUBLIC_KEY / VAPID_PRIVATE_KEY are set but invalid: {e}");
+            process::exit(1);
+        }
+    }
+    match domain::gateway::object_storage::ObjectStorage::from_config(&service_state.config) {
+        Ok(Some(_)) => info!("Object storage configured — session records enabled"),
+        Ok(None) => info!("Object storage not configured — session records disabled"),
+        Err(e) => {
+            error!("OBJECT_STORAGE_ENDPOINT is set but invalid: {e}");
+            process::exit(1);
+        }
+    }
+    // With the events worker enabled, the worker exports instead (see `domain::warehouse`).
+    let warehouse_sink = match domain::warehouse::WarehouseSink::from_config(
+        &service_state.config,
+        Arc::clone(&db_conn),
+    ) {
+        Ok(Some(sink)) => {
+            info!("WAREHOUSE_EXPORT_ENABLED set — domain events are exported to the warehouse");
+            let sink = Arc::new(sink);
+            sink.spawn_flusher(service_state.config.warehouse_export_flush_interval());
+            event_publisher = event_publisher.with_handler(Arc::clone(&sink) as _);
+            Some(sink)
+        }
+        Ok(None) => None,
+        Err(e) => {
+            error!("Failed to set up the warehouse export: {e}");
+            process::exit(1);
+        }
+    };
+    // With the events worker enabled, the worker publishes instead (see `events::bus`).
+    if let Some(url) = service_state.config.event_bus_url() {
+        match BusPublisher::new(&url, service_state.config.event_bus_topic()) {
+            Ok(bus) => {
+                info!("EVENT_BUS_URL set — domain events are published to the event bus");
+                event_publisher = event_publisher.with_handler(Arc::new(bus));
+            }
+            Err(e) => {
+                error!("EVENT_BUS_URL is set but invalid: {e}");
+                process::exit(1);
+            }
+        }
+    }
+    if let Some(url) = service_state.config.event_transport_url() {
+        match RedisTransport::new(&url, service_state.config.event_transport_channel()) {
+            Ok(transport) => {
+                info!("EVENT_TRANSPORT_URL set — SSE events fan out to every replica via Redis");
+                event_publisher = event_publisher.with_transport(Arc::new(transport));
+            }
+            Err(e) => {
+                error!("EVENT_TRANSPORT_URL is set but invalid: {e}");
+                process::exit(1);
+            }
+        }
+    }
+    if service_state.config.events_worker_enabled() {
+        info!("EVENTS_WORKER_ENABLED set — background event handlers run in events-worker");
+        event_publisher = event_publisher.with_outbox(Arc::new(domain::outbox::OutboxStore::new(
+            Arc::clone(&db_conn),
+        )));
+    }
 
     // Build meeting provider from config. Both bot and transcript traits share the same
     // underlying client so we build one instance and wrap it in two Arc<dyn Trait>s.
@@ -97,6 +232,32 @@ async fn main() {
             }
         };
 
+    // The gateway serves both completions and embeddings.
+    let llm_gateway: Option<Arc<llm_gateway::Provider>> =
+        match service_state.config.llm_gateway_api_key() {
+            Some(key) => match llm_gateway::Provider::new(
+                &key,
+                service_state.config.llm_gateway_url(),
+                service_state.config.llm_gateway_model(),
+                service_state.config.llm_gateway_embedding_model(),
+            ) {
+                Ok(p) => Some(Arc::new(p)),
+                Err(e) => {
+                    warn!("Failed to build LLM gateway provider — AI analysis disabled: {e:?}");
+                    None
+                }
+            },
+            None => {
+                info!("LLM_GATEWAY_API_KEY not set — AI analysis disabled");
+                None
+            }
+        };
+    let analysis_provider: Option<Arc<dyn analysis::Provider>> = llm_gateway
+        .clone()
+        .map(|gateway| gateway as Arc<dyn analysis::Provider>);
+    let embedding_provider: Option<Arc<dyn embedding::Provider>> =
+        llm_gateway.map(|gateway| gateway as Arc<dyn embedding::Provider>);
+
     // Create web-level state (adds domain and SSE concerns)
     let web_state = web::AppState::new(
         service_state,
@@ -104,9 +265,19 @@ async fn main() {
         event_publisher,
         recording_bot_provider,
         transcription_provider,
-    );
+    )
+    .with_analysis_provider(analysis_provider)
+    .with_embedding_provider(embedding_provider)
+    .with_heavy_database_connection(Some(heavy_db_conn));
 
     web::init_server(web_state).await.unwrap();
+
+    // Export the events still waiting for a batch before exiting
+    if let Some(sink) = warehouse_sink {
+        if let Err(e) = sink.flush().await {
+            warn!("Failed to export the remaining events to the warehouse: {e:?}");
+        }
+    }
 }
 
 // This is the parent test "runner" that initiates all other crate
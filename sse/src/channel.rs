@@ -0,0 +1,354 @@
+//! Bounded per-connection event channels.
+//!
+//! A connection's channel holds at most [`ChannelConfig::capacity`] undelivered frames,
+//! so a client that stops reading (a stalled tab, a dead proxy) costs a fixed amount of
+//! memory however many events it's sent. What happens once it's full is the channel's
+//! [`OverflowPolicy`].
+
+use crate::connection::Frame;
+use crate::message::{Event as SseEvent, EventType, Message as SseMessage, MessageScope};
+use log::*;
+use std::collections::VecDeque;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use tokio::sync::mpsc::error::{SendError, TryRecvError};
+use tokio::sync::Notify;
+
+/// Undelivered frames a connection's channel holds by default
+pub const DEFAULT_CAPACITY: usize = 256;
+
+/// What a connection's channel does with a frame sent while it's full
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Drop the oldest undelivered frame. The client is sent a `resync` event with the
+    /// number dropped before the frames that remain, and refetches what it shows.
+    #[default]
+    DropOldest,
+    /// Close the connection. The client reconnects with its `Last-Event-ID` and gets
+    /// what it missed from the replay buffer, if it's still there.
+    Disconnect,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct OverflowPolicyParseError;
+
+impl FromStr for OverflowPolicy {
+    type Err = OverflowPolicyParseError;
+
+    fn from_str(policy: &str) -> Result<Self, Self::Err> {
+        match policy.to_lowercase().as_str() {
+            "drop_oldest" => Ok(Self::DropOldest),
+            "disconnect" => Ok(Self::Disconnect),
+            _ => Err(OverflowPolicyParseError),
+        }
+    }
+}
+
+/// Size and overflow policy of a connection's channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelConfig {
+    pub capacity: usize,
+    pub overflow: OverflowPolicy,
+}
+
+impl Default for ChannelConfig {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_CAPACITY,
+            overflow: OverflowPolicy::default(),
+        }
+    }
+}
+
+/// Creates a connection's channel. A capacity of zero is taken as one.
+pub fn channel(config: ChannelConfig) -> (EventSender, EventReceiver) {
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            frames: VecDeque::new(),
+            dropped: 0,
+            overflowed: false,
+            senders: 1,
+            receiver_alive: true,
+        }),
+        notify: Notify::new(),
+        config: ChannelConfig {
+            capacity: config.capacity.max(1),
+            ..config
+        },
+    });
+    (
+        EventSender {
+            shared: Arc::clone(&shared),
+        },
+        EventReceiver { shared },
+    )
+}
+
+#[derive(Debug)]
+struct Shared {
+    state: Mutex<State>,
+    /// Wakes the receiver when a frame is sent or the channel closes
+    notify: Notify,
+    config: ChannelConfig,
+}
+
+#[derive(Debug)]
+struct State {
+    /// Undelivered frames, oldest first
+    frames: VecDeque<Frame>,
+    /// Frames dropped since the receiver last read, still to be told with a `resync`
+    dropped: u64,
+    /// Closed by [`OverflowPolicy::Disconnect`]
+    overflowed: bool,
+    senders: usize,
+    receiver_alive: bool,
+}
+
+/// Sending half of a connection's event channel
+#[derive(Debug)]
+pub struct EventSender {
+    shared: Arc<Shared>,
+}
+
+impl EventSender {
+    /// Queues `frame` for the connection, applying the overflow policy if the channel
+    /// is full. Fails once the receiver is gone or the channel has overflowed with
+    /// [`OverflowPolicy::Disconnect`].
+    pub fn send(&self, frame: Frame) -> Result<(), SendError<Frame>> {
+        let mut state = lock(&self.shared.state);
+        if !state.receiver_alive || state.overflowed {
+            return Err(SendError(frame));
+        }
+
+        if state.frames.len() >= self.shared.config.capacity {
+            match self.shared.config.overflow {
+                OverflowPolicy::DropOldest => {
+                    state.frames.pop_front();
+                    state.dropped += 1;
+                }
+                OverflowPolicy::Disconnect => {
+                    state.overflowed = true;
+                    state.frames.clear();
+                    drop(state);
+                    self.shared.notify.notify_one();
+                    return Err(SendError(frame));
+                }
+            }
+        }
+        state.frames.push_back(frame);
+        drop(state);
+
+        self.shared.notify.notify_one();
+        Ok(())
+    }
+}
+
+impl Clone for EventSender {
+    fn clone(&self) -> Self {
+        lock(&self.shared.state).senders += 1;
+        Self {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl Drop for EventSender {
+    fn drop(&mut self) {
+        let mut state = lock(&self.shared.state);
+        state.senders -= 1;
+        if state.senders == 0 {
+            drop(state);
+            self.shared.notify.notify_one();
+        }
+    }
+}
+
+/// Receiving half of a connection's event channel
+#[derive(Debug)]
+pub struct EventReceiver {
+    shared: Arc<Shared>,
+}
+
+impl EventReceiver {
+    /// Waits for the next frame. `None` once every sender is gone and the channel is
+    /// drained, or the channel has overflowed with [`OverflowPolicy::Disconnect`].
+    pub async fn recv(&mut self) -> Option<Frame> {
+        loop {
+            match self.try_recv() {
+                Ok(frame) => return Some(frame),
+                Err(TryRecvError::Disconnected) => return None,
+                Err(TryRecvError::Empty) => self.shared.notify.notified().await,
+            }
+        }
+    }
+
+    /// The next frame, if one is waiting. After the channel dropped frames, that's a
+    /// `resync` frame numbered just below the oldest one kept.
+    pub fn try_recv(&mut self) -> Result<Frame, TryRecvError> {
+        let mut state = lock(&self.shared.state);
+        if state.overflowed {
+            return Err(TryRecvError::Disconnected);
+        }
+
+        if state.dropped > 0 {
+            if let Some(oldest_kept) = state.frames.front().map(|frame| frame.id) {
+                let dropped = std::mem::take(&mut state.dropped);
+                warn!("SSE connection fell behind; dropped {dropped} events");
+                if let Some(resync) = resync(oldest_kept.saturating_sub(1), dropped) {
+                    return Ok(resync);
+                }
+            }
+        }
+
+        match state.frames.pop_front() {
+            Some(frame) => Ok(frame),
+            None if state.senders == 0 => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+}
+
+impl Drop for EventReceiver {
+    fn drop(&mut self) {
+        let mut state = lock(&self.shared.state);
+        state.receiver_alive = false;
+        state.frames.clear();
+    }
+}
+
+/// The frame telling a client that `dropped` events never reached it
+fn resync(id: u64, dropped: u64) -> Option<Frame> {
+    let message = SseMessage {
+        event: SseEvent::Resync { dropped },
+        // Not sent through the manager, so the scope isn't used
+        scope: MessageScope::Broadcast,
+        correlation_id: None,
+    };
+    match message.to_json() {
+        Ok(json) => Some(Frame {
+            id,
+            event_type: message.event.event_type(),
+            data: json.into(),
+        }),
+        Err(e) => {
+            error!("Failed to serialize SSE resync event: {e}");
+            None
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn frame(id: u64) -> Frame {
+        Frame {
+            id,
+            event_type: "test",
+            data: "{}".into(),
+        }
+    }
+
+    fn bounded(capacity: usize, overflow: OverflowPolicy) -> (EventSender, EventReceiver) {
+        channel(ChannelConfig { capacity, overflow })
+    }
+
+    #[test]
+    fn slow_consumer_gets_a_resync_then_the_newest_frames() {
+        let (tx, mut rx) = bounded(3, OverflowPolicy::DropOldest);
+        for id in 1..=5 {
+            tx.send(frame(id)).unwrap();
+        }
+
+        let resync = rx.try_recv().unwrap();
+        assert_eq!(resync.event_type, "resync");
+        assert_eq!(resync.id, 2);
+        assert!(resync.data.contains(r#""dropped":2"#));
+
+        let ids: Vec<u64> = std::iter::from_fn(|| rx.try_recv().ok())
+            .map(|frame| frame.id)
+            .collect();
+        assert_eq!(ids, [3, 4, 5]);
+    }
+
+    #[test]
+    fn resync_is_sent_once_per_gap() {
+        let (tx, mut rx) = bounded(1, OverflowPolicy::DropOldest);
+        tx.send(frame(1)).unwrap();
+        tx.send(frame(2)).unwrap();
+
+        assert_eq!(rx.try_recv().unwrap().event_type, "resync");
+        assert_eq!(rx.try_recv().unwrap().id, 2);
+        assert!(matches!(rx.try_recv(), Err(TryRecvError::Empty)));
+
+        // Keeping up again delivers everything, with no resync
+        tx.send(frame(3)).unwrap();
+        assert_eq!(rx.try_recv().unwrap().id, 3);
+    }
+
+    #[test]
+    fn slow_consumer_is_disconnected_under_the_disconnect_policy() {
+        let (tx, mut rx) = bounded(2, OverflowPolicy::Disconnect);
+        tx.send(frame(1)).unwrap();
+        tx.send(frame(2)).unwrap();
+
+        assert!(tx.send(frame(3)).is_err());
+        assert!(tx.send(frame(4)).is_err());
+        assert!(matches!(rx.try_recv(), Err(TryRecvError::Disconnected)));
+    }
+
+    #[test]
+    fn send_fails_once_the_receiver_is_gone() {
+        let (tx, rx) = bounded(2, OverflowPolicy::DropOldest);
+        drop(rx);
+
+        assert!(tx.send(frame(1)).is_err());
+    }
+
+    #[tokio::test]
+    async fn recv_waits_for_frames_and_ends_when_every_sender_is_gone() {
+        let (tx, mut rx) = channel(ChannelConfig::default());
+        let pending = tokio::spawn(async move {
+            let mut ids = Vec::new();
+            while let Some(frame) = rx.recv().await {
+                ids.push(frame.id);
+            }
+            ids
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        let clone = tx.clone();
+        tx.send(frame(1)).unwrap();
+        drop(tx);
+        clone.send(frame(2)).unwrap();
+        drop(clone);
+
+        assert_eq!(pending.await.unwrap(), [1, 2]);
+    }
+
+    #[tokio::test]
+    async fn recv_wakes_when_the_channel_overflows_with_disconnect() {
+        let (tx, mut rx) = bounded(1, OverflowPolicy::Disconnect);
+        tx.send(frame(1)).unwrap();
+        assert_eq!(rx.recv().await.unwrap().id, 1);
+
+        let pending = tokio::spawn(async move { rx.recv().await });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        tx.send(frame(2)).unwrap();
+        assert!(tx.send(frame(3)).is_err());
+
+        assert!(pending.await.unwrap().is_none());
+    }
+
+    #[test]
+    fn overflow_policy_parses_config_values() {
+        assert_eq!("drop_oldest".parse(), Ok(OverflowPolicy::DropOldest));
+        assert_eq!("DISCONNECT".parse(), Ok(OverflowPolicy::Disconnect));
+        assert!("block".parse::<OverflowPolicy>().is_err());
+    }
+}
@@ -1,13 +1,37 @@
 use axum::response::sse::Event;
 use dashmap::DashMap;
 use log::*;
-use std::collections::HashSet;
-use std::convert::Infallible;
-use tokio::sync::mpsc::UnboundedSender;
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, VecDeque};
+use std::hash::BuildHasher;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc::error::SendError;
 
 // Type alias for user IDs (web layer converts domain::Id to String)
 pub type UserId = String;
 
+pub use crate::channel::EventSender;
+use crate::filter::EventFilter;
+use crate::message::{Event as SseEvent, EventType, Message as SseMessage, MessageScope};
+
+/// Number of user index shards. Power of two so the shard is a mask of the hash.
+const SHARD_COUNT: usize = 32;
+
+/// Most recent events kept per user for replay to a reconnecting client
+const REPLAY_CAPACITY: usize = 100;
+
+/// Most recent broadcasts kept for replay to a reconnecting client
+const BROADCAST_REPLAY_CAPACITY: usize = 100;
+
+/// How long an event stays available for replay
+const REPLAY_WINDOW: Duration = Duration::from_secs(5 * 60);
+
+/// One unregister in this many also sweeps out users who disconnected and whose
+/// buffered events have all expired.
+const SWEEP_INTERVAL: u64 = 256;
+
 /// Unique identifier for a connection (server-generated)
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ConnectionId(String);
@@ -28,104 +52,574 @@ impl Default for ConnectionId {
     }
 }
 
-/// Connection information (no redundant connection_id)
+/// A registered connection as stored in the user index and broadcast snapshot
+#[derive(Debug, Clone)]
+struct Connection {
+    id: ConnectionId,
+    sender: EventSender,
+    connected_at: Instant,
+    /// The events the connection subscribed to
+    filter: Arc<EventFilter>,
+}
+
+/// A point-in-time view of the registry, for diagnosing events that don't arrive
 #[derive(Debug, Clone)]
-pub struct ConnectionInfo {
+pub struct RegistryStats {
+    /// Open connections, across all users
+    pub connections: usize,
+    /// Events handed to a connection since the registry was created
+    pub sent: u64,
+    /// Events that couldn't be handed to a connection because it had closed
+    pub dropped: u64,
+    /// Users with open connections or events buffered for replay
+    pub users: Vec<UserStats>,
+}
+
+/// A user's open connections and buffered events
+#[derive(Debug, Clone)]
+pub struct UserStats {
     pub user_id: UserId,
-    pub sender: UnboundedSender<Result<Event, Infallible>>,
+    /// How long each open connection has been open, oldest first
+    pub connection_ages: Vec<Duration>,
+    /// Events held for replay to a reconnecting client
+    pub buffered_events: usize,
+}
+
+/// An event as sent to connections: the ID it was sent under, its SSE event name
+/// and its JSON data. Written to an SSE stream as an [`Event`], or returned as-is to
+/// a long-poll request.
+#[derive(Debug, Clone)]
+pub struct Frame {
+    pub id: u64,
+    pub event_type: &'static str,
+    pub data: Arc<str>,
+}
+
+impl Frame {
+    /// The frame as an SSE message
+    pub fn to_sse(&self) -> Event {
+        Event::default()
+            .id(self.id.to_string())
+            .event(self.event_type)
+            .data(&*self.data)
+    }
+}
+
+/// An event as kept for replay
+#[derive(Debug, Clone)]
+struct BufferedEvent {
+    sent_at: Instant,
+    frame: Frame,
+}
+
+/// Event replay buffer, oldest first
+type ReplayBuffer = VecDeque<BufferedEvent>;
+
+/// A user's connections and the events recently sent to them. The entry outlives
+/// the user's last connection for the replay window, so events sent while they
+/// reconnect (or between long-polls) are buffered, and while it still holds events
+/// to replay.
+#[derive(Debug, Default)]
+struct UserEntry {
+    connections: Vec<Connection>,
+    /// When the user's last connection closed
+    disconnected_at: Option<Instant>,
+    /// Locked on its own so sends to different users of one shard don't contend
+    replay: Mutex<ReplayBuffer>,
+}
+
+impl UserEntry {
+    /// Whether the entry has no connections and nothing left worth replaying
+    fn is_idle(&self) -> bool {
+        if !self.connections.is_empty()
+            || self
+                .disconnected_at
+                .is_some_and(|disconnected_at| disconnected_at.elapsed() < REPLAY_WINDOW)
+        {
+            return false;
+        }
+        let mut replay = lock(&self.replay);
+        prune_expired(&mut replay);
+        replay.is_empty()
+    }
 }
 
-/// High-performance connection registry with dual indices for O(1) lookups
+type Shard = RwLock<HashMap<UserId, UserEntry>>;
+
+/// Connection registry sharded by hashed user id.
+///
+/// - User-scoped sends take a read lock on a single shard and write straight to
+///   the senders stored there; no secondary lookup and no recipient vector.
+/// - Broadcasts iterate an immutable snapshot of every connection. The snapshot
+///   is rebuilt lazily on the first broadcast after a register/unregister, so
+///   steady-state broadcasts hold no registry lock while sending.
+/// - Every event is given an ID from a single increasing counter and kept for a
+///   while in its user's replay buffer (or the broadcast one), so a client that
+///   reconnects with the last ID it saw gets the events it missed.
 pub struct ConnectionRegistry {
-    /// Primary storage: lookup by connection_id for registration/cleanup - O(1)
-    connections: DashMap<ConnectionId, ConnectionInfo>,
+    /// Owning user per connection, so unregister can find the right shard - O(1)
+    owners: DashMap<ConnectionId, UserId>,
+
+    /// User index split into `SHARD_COUNT` independently locked maps - O(1)
+    shards: Box<[Shard]>,
+    hasher: RandomState,
 
-    /// Secondary index: fast lookup by user_id for message routing - O(1)
-    user_index: DashMap<UserId, HashSet<ConnectionId>>,
+    /// Bumped on every membership change; guards against storing a stale snapshot
+    generation: AtomicU64,
+    /// Copy-on-write view of all connections for broadcast; `None` once invalidated
+    snapshot: RwLock<Option<Arc<[Connection]>>>,
+
+    /// ID of the next event sent. Seeded from the clock so IDs keep increasing
+    /// across restarts and a stale `Last-Event-ID` never skips new events.
+    next_event_id: AtomicU64,
+    /// Recent broadcasts. Held for the whole of a broadcast, and by a replaying
+    /// register, so a reconnecting client gets each broadcast exactly once.
+    broadcasts: Mutex<ReplayBuffer>,
+    /// Counts unregisters towards the next idle user sweep
+    unregisters: AtomicU64,
+    /// Set by [`ConnectionRegistry::close`]; connections registered since are closed
+    closed: AtomicBool,
+    /// Most connections a user may have open; registering one more closes their oldest
+    max_connections_per_user: Option<usize>,
+    /// Events handed to connections, and those lost to a closed connection
+    sent: AtomicU64,
+    dropped: AtomicU64,
 }
 
 impl ConnectionRegistry {
     pub fn new() -> Self {
         Self {
-            connections: DashMap::new(),
-            user_index: DashMap::new(),
+            owners: DashMap::new(),
+            shards: (0..SHARD_COUNT).map(|_| Shard::default()).collect(),
+            hasher: RandomState::new(),
+            generation: AtomicU64::new(0),
+            snapshot: RwLock::new(None),
+            next_event_id: AtomicU64::new(first_event_id()),
+            broadcasts: Mutex::new(ReplayBuffer::new()),
+            unregisters: AtomicU64::new(0),
+            closed: AtomicBool::new(false),
+            max_connections_per_user: None,
+            sent: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
         }
     }
 
+    /// Caps the connections a user may have open at `max` (at least one). Registering
+    /// one more sends their oldest a `superseded` event and closes it.
+    pub fn with_max_connections_per_user(mut self, max: Option<usize>) -> Self {
+        self.max_connections_per_user = max.map(|max| max.max(1));
+        self
+    }
+
     /// Register a new connection - O(1)
+    ///
+    /// With the ID of the last event the client received, first sends the
+    /// connection every buffered event for its user, and every buffered broadcast,
+    /// sent after it - O(buffer size).
     pub fn register(
         &self,
         user_id: UserId,
-        sender: UnboundedSender<Result<Event, Infallible>>,
+        sender: EventSender,
+        last_event_id: Option<u64>,
+    ) -> ConnectionId {
+        self.register_filtered(user_id, sender, last_event_id, EventFilter::default())
+    }
+
+    /// Register a new connection that's only sent the events `filter` matches, whether
+    /// live or replayed - O(1)
+    pub fn register_filtered(
+        &self,
+        user_id: UserId,
+        sender: EventSender,
+        last_event_id: Option<u64>,
+        filter: EventFilter,
     ) -> ConnectionId {
         let connection_id = ConnectionId::new();
+        self.owners.insert(connection_id.clone(), user_id.clone());
 
-        // Insert into primary storage
-        self.connections.insert(
-            connection_id.clone(),
-            ConnectionInfo {
-                user_id: user_id.clone(),
+        // Replaying holds off broadcasts until the connection is in the snapshot, so
+        // each one is either replayed or delivered live, never both or neither.
+        let broadcasts = last_event_id.map(|_| lock(&self.broadcasts));
+        let superseded = {
+            let mut shard = write(self.shard(&user_id));
+            // Checked under the shard lock, so `close` either sees this connection or
+            // this sees that it's closed
+            if self.closed.load(Ordering::SeqCst) {
+                self.owners.remove(&connection_id);
+                return connection_id;
+            }
+            let entry = shard.entry(user_id).or_default();
+
+            if let (Some(last_event_id), Some(broadcasts)) = (last_event_id, &broadcasts) {
+                let mut missed: Vec<&BufferedEvent> = Vec::new();
+                let replay = lock(&entry.replay);
+                missed.extend(replayable(&replay, last_event_id));
+                missed.extend(replayable(broadcasts, last_event_id));
+                missed.retain(|buffered| filter.matches(buffered.frame.event_type));
+                missed.sort_unstable_by_key(|buffered| buffered.frame.id);
+
+                debug!(
+                    "Replaying {} missed events to connection {}",
+                    missed.len(),
+                    connection_id.as_str()
+                );
+                for buffered in missed {
+                    // The receiver is still held by the caller, so this can't fail
+                    let _ = sender.send(buffered.frame.clone());
+                }
+            }
+
+            entry.connections.push(Connection {
+                id: connection_id.clone(),
                 sender,
-            },
-        );
+                connected_at: Instant::now(),
+                filter: Arc::new(filter),
+            });
+
+            // Connections are kept oldest first
+            let excess = self
+                .max_connections_per_user
+                .map_or(0, |max| entry.connections.len().saturating_sub(max));
+            entry.connections.drain(..excess).collect::<Vec<_>>()
+        };
+        self.invalidate_snapshot();
+        drop(broadcasts);
 
-        // Update secondary index
-        self.user_index
-            .entry(user_id)
-            .or_default()
-            .insert(connection_id.clone());
+        if !superseded.is_empty() {
+            self.supersede(&superseded);
+        }
 
         connection_id
     }
 
-    /// Unregister a connection - O(1)
-    pub fn unregister(&self, connection_id: &ConnectionId) {
-        // Remove from primary storage
-        if let Some((_, info)) = self.connections.remove(connection_id) {
-            let user_id = info.user_id;
+    /// Unregister a connection - O(1) (plus O(k) within the user's own connections)
+    ///
+    /// Returns the connection's user, or `None` if it was already unregistered.
+    pub fn unregister(&self, connection_id: &ConnectionId) -> Option<UserId> {
+        let (_, user_id) = self.owners.remove(connection_id)?;
+
+        {
+            let mut shard = write(self.shard(&user_id));
+            if let Some(entry) = shard.get_mut(&user_id) {
+                entry
+                    .connections
+                    .retain(|connection| &connection.id != connection_id);
+                if entry.connections.is_empty() {
+                    entry.disconnected_at = Some(Instant::now());
+                }
+            }
+        }
+        self.invalidate_snapshot();
+
+        if self.unregisters.fetch_add(1, Ordering::Relaxed) % SWEEP_INTERVAL == SWEEP_INTERVAL - 1 {
+            self.sweep_idle_users();
+        }
+
+        Some(user_id)
+    }
+
+    /// Closes the user's connections - O(k) where k = user's connections
+    ///
+    /// Like [`ConnectionRegistry::close`], each connection's receiver gets the events
+    /// already queued for it and then ends. Returns how many connections were closed.
+    pub fn disconnect_user(&self, user_id: &UserId) -> usize {
+        let closed = {
+            let mut shard = write(self.shard(user_id));
+            let Some(entry) = shard.get_mut(user_id) else {
+                return 0;
+            };
+            let closed = std::mem::take(&mut entry.connections);
+            if !closed.is_empty() {
+                entry.disconnected_at = Some(Instant::now());
+            }
+            closed
+        };
+        if closed.is_empty() {
+            return 0;
+        }
 
-            // Update secondary index
-            if let Some(mut entry) = self.user_index.get_mut(&user_id) {
-                entry.remove(connection_id);
+        for connection in &closed {
+            self.owners.remove(&connection.id);
+        }
+        self.invalidate_snapshot();
+        closed.len()
+    }
 
-                // Clean up empty user entries
-                if entry.is_empty() {
-                    drop(entry); // Release lock before removal
-                    self.user_index.remove(&user_id);
+    /// Closes every connection and refuses new ones, for shutdown - O(n)
+    ///
+    /// Drops the registry's senders, so each connection's receiver gets the events
+    /// already queued for it and then ends. A connection registered afterwards ends
+    /// straight away.
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+        for shard in self.shards.iter() {
+            for entry in write(shard).values_mut() {
+                if !entry.connections.is_empty() {
+                    entry.connections.clear();
+                    entry.disconnected_at = Some(Instant::now());
                 }
             }
         }
+        self.owners.clear();
+        self.invalidate_snapshot();
+    }
+
+    /// Whether the user has any connection open - O(1)
+    pub fn is_connected(&self, user_id: &UserId) -> bool {
+        read(self.shard(user_id))
+            .get(user_id)
+            .is_some_and(|entry| !entry.connections.is_empty())
+    }
+
+    /// Whether an event of `event_type` sent to the user would be delivered or kept:
+    /// they have a connection subscribed to it, or are offline but still have events
+    /// kept for their reconnect - O(k) where k = user's connections
+    pub fn wants(&self, user_id: &UserId, event_type: &str) -> bool {
+        read(self.shard(user_id)).get(user_id).is_some_and(|entry| {
+            entry.connections.is_empty()
+                || entry
+                    .connections
+                    .iter()
+                    .any(|connection| connection.filter.matches(event_type))
+        })
+    }
+
+    /// The ID of the last event sent, or just below the first one to come
+    pub fn last_event_id(&self) -> u64 {
+        self.next_event_id.load(Ordering::Relaxed) - 1
     }
 
     /// Send message to specific user - O(1) lookup + O(k) send where k = user's connections
-    pub fn send_to_user(&self, user_id: &UserId, event: Event) {
-        if let Some(connection_ids) = self.user_index.get(user_id) {
-            for conn_id in connection_ids.iter() {
-                if let Some(info) = self.connections.get(conn_id) {
-                    if let Err(e) = info.sender.send(Ok(event.clone())) {
-                        warn!(
-                            "Failed to send event to connection {}: {}. Connection will be cleaned up.",
-                            conn_id.as_str(),
-                            e
-                        );
-                    }
-                }
+    ///
+    /// The event is buffered for replay if the user has connected to this registry
+    /// and hasn't been idle past the replay window, even when they're offline now.
+    pub fn send_to_user(&self, user_id: &UserId, event_type: &'static str, data: Arc<str>) {
+        let shard = read(self.shard(user_id));
+        let Some(entry) = shard.get(user_id) else {
+            return;
+        };
+
+        // Numbered and buffered under the user's lock so their buffer stays in ID order
+        let mut replay = lock(&entry.replay);
+        let frame = self.buffer(&mut replay, REPLAY_CAPACITY, event_type, data);
+        for connection in &entry.connections {
+            if let Err(e) = self.send(connection, &frame) {
+                warn!(
+                    "Failed to send event to connection {}: {}. Connection will be cleaned up.",
+                    connection.id.as_str(),
+                    e
+                );
+            }
+        }
+    }
+
+    /// Send message to the user's open connections only - O(1) lookup + O(k) send
+    ///
+    /// The event is numbered like any other but not buffered, so it's lost on a
+    /// connection that isn't open when it's sent.
+    pub fn send_ephemeral_to_user(
+        &self,
+        user_id: &UserId,
+        event_type: &'static str,
+        data: Arc<str>,
+    ) {
+        let shard = read(self.shard(user_id));
+        let Some(entry) = shard.get(user_id) else {
+            return;
+        };
+
+        let frame = Frame {
+            id: self.next_event_id.fetch_add(1, Ordering::Relaxed),
+            event_type,
+            data,
+        };
+        for connection in &entry.connections {
+            if let Err(e) = self.send(connection, &frame) {
+                warn!(
+                    "Failed to send event to connection {}: {}. Connection will be cleaned up.",
+                    connection.id.as_str(),
+                    e
+                );
             }
         }
     }
 
     /// Broadcast message to all connections - O(n) (unavoidable, but explicit)
-    pub fn broadcast(&self, event: Event) {
-        for entry in self.connections.iter() {
-            if let Err(e) = entry.value().sender.send(Ok(event.clone())) {
+    pub fn broadcast(&self, event_type: &'static str, data: Arc<str>) {
+        let mut broadcasts = lock(&self.broadcasts);
+        let frame = self.buffer(&mut broadcasts, BROADCAST_REPLAY_CAPACITY, event_type, data);
+        for connection in self.broadcast_snapshot().iter() {
+            if let Err(e) = self.send(connection, &frame) {
                 warn!(
                     "Failed to send broadcast to connection {}: {}",
-                    entry.key().as_str(),
+                    connection.id.as_str(),
                     e
                 );
             }
         }
     }
+
+    /// The registry's connections and counters, for one user or all of them - O(n)
+    pub fn stats(&self, user_id: Option<&UserId>) -> RegistryStats {
+        let mut users = Vec::new();
+        let mut record = |user_id: &UserId, entry: &UserEntry| {
+            users.push(UserStats {
+                user_id: user_id.clone(),
+                connection_ages: entry
+                    .connections
+                    .iter()
+                    .map(|connection| connection.connected_at.elapsed())
+                    .collect(),
+                buffered_events: lock(&entry.replay).len(),
+            });
+        };
+        match user_id {
+            Some(user_id) => {
+                if let Some(entry) = read(self.shard(user_id)).get(user_id) {
+                    record(user_id, entry);
+                }
+            }
+            None => {
+                for shard in self.shards.iter() {
+                    for (user_id, entry) in read(shard).iter() {
+                        record(user_id, entry);
+                    }
+                }
+            }
+        }
+
+        RegistryStats {
+            connections: self.owners.len(),
+            sent: self.sent.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+            users,
+        }
+    }
+
+    /// Tells connections that were replaced by newer ones of their user why they're
+    /// closing, then closes them once that's delivered - O(k)
+    fn supersede(&self, connections: &[Connection]) {
+        let max_connections = self.max_connections_per_user.unwrap_or_default();
+        let message = SseMessage {
+            event: SseEvent::Superseded { max_connections },
+            // Sent straight to the connections, so the scope isn't used
+            scope: MessageScope::Broadcast,
+            correlation_id: None,
+        };
+        let data: Arc<str> = match message.to_json() {
+            Ok(json) => json.into(),
+            Err(e) => {
+                error!("Failed to serialize SSE superseded event: {e}");
+                Arc::from("{}")
+            }
+        };
+
+        for connection in connections {
+            self.owners.remove(&connection.id);
+            info!(
+                "Closing SSE connection {}: its user opened more than {max_connections}",
+                connection.id.as_str()
+            );
+            let frame = Frame {
+                id: self.next_event_id.fetch_add(1, Ordering::Relaxed),
+                event_type: message.event.event_type(),
+                data: Arc::clone(&data),
+            };
+            // Closed either way once the registry's sender is dropped
+            let _ = self.send(connection, &frame);
+        }
+    }
+
+    /// Hands `frame` to `connection`, counting it as sent or dropped. A frame the
+    /// connection didn't subscribe to is skipped.
+    fn send(&self, connection: &Connection, frame: &Frame) -> Result<(), SendError<Frame>> {
+        if !connection.filter.matches(frame.event_type) {
+            return Ok(());
+        }
+        let result = connection.sender.send(frame.clone());
+        let counter = if result.is_ok() {
+            &self.sent
+        } else {
+            &self.dropped
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        result
+    }
+
+    /// Gives the event the next ID and appends it to `buffer`, dropping whatever no
+    /// longer fits or has expired. Returns the numbered event.
+    fn buffer(
+        &self,
+        buffer: &mut ReplayBuffer,
+        capacity: usize,
+        event_type: &'static str,
+        data: Arc<str>,
+    ) -> Frame {
+        let frame = Frame {
+            id: self.next_event_id.fetch_add(1, Ordering::Relaxed),
+            event_type,
+            data,
+        };
+
+        buffer.push_back(BufferedEvent {
+            sent_at: Instant::now(),
+            frame: frame.clone(),
+        });
+        while buffer.len() > capacity {
+            buffer.pop_front();
+        }
+        prune_expired(buffer);
+
+        frame
+    }
+
+    /// Removes users with no connections whose buffered events have all expired
+    fn sweep_idle_users(&self) {
+        for shard in self.shards.iter() {
+            write(shard).retain(|_, entry| !entry.is_idle());
+        }
+    }
+
+    fn shard(&self, user_id: &UserId) -> &Shard {
+        let index = self.hasher.hash_one(user_id) as usize & (SHARD_COUNT - 1);
+        &self.shards[index]
+    }
+
+    /// Drops the broadcast snapshot so it never outlives a membership change
+    /// (and never keeps an unregistered connection's sender alive).
+    fn invalidate_snapshot(&self) {
+        let mut snapshot = write(&self.snapshot);
+        self.generation.fetch_add(1, Ordering::Release);
+        *snapshot = None;
+    }
+
+    /// Returns the current broadcast snapshot, rebuilding it from the shards if a
+    /// register/unregister invalidated it.
+    fn broadcast_snapshot(&self) -> Arc<[Connection]> {
+        if let Some(snapshot) = read(&self.snapshot).as_ref() {
+            return Arc::clone(snapshot);
+        }
+
+        let generation = self.generation.load(Ordering::Acquire);
+        let mut connections = Vec::with_capacity(self.owners.len());
+        for shard in self.shards.iter() {
+            connections.extend(
+                read(shard)
+                    .values()
+                    .flat_map(|entry| entry.connections.iter().cloned()),
+            );
+        }
+        let rebuilt: Arc<[Connection]> = connections.into();
+
+        // Only publish if nothing changed while the shards were being read; otherwise
+        // this broadcast still uses its view and the next one rebuilds.
+        let mut snapshot = write(&self.snapshot);
+        if self.generation.load(Ordering::Acquire) == generation {
+            *snapshot = Some(Arc::clone(&rebuilt));
+        }
+
+        rebuilt
+    }
 }
 
 impl Default for ConnectionRegistry {
@@ -133,3 +627,378 @@ impl Default for ConnectionRegistry {
         Self::new()
     }
 }
+
+/// The first event ID this process hands out: microseconds since the Unix epoch.
+fn first_event_id() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_micros() as u64)
+}
+
+/// The events in `buffer` sent after `last_event_id` and still within the window
+fn replayable(buffer: &ReplayBuffer, last_event_id: u64) -> impl Iterator<Item = &BufferedEvent> {
+    buffer
+        .iter()
+        .filter(move |buffered| buffered.frame.id > last_event_id)
+        .filter(|buffered| buffered.sent_at.elapsed() < REPLAY_WINDOW)
+}
+
+/// Drops events from the front of `buffer` that are past the replay window
+fn prune_expired(buffer: &mut ReplayBuffer) {
+    while buffer
+        .front()
+        .is_some_and(|buffered| buffered.sent_at.elapsed() >= REPLAY_WINDOW)
+    {
+        buffer.pop_front();
+    }
+}
+
+// A panic while holding a shard lock cannot leave the maps half-updated, so
+// recover from poisoning instead of taking the whole SSE subsystem down.
+fn read<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn write<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    lock.write().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channel::{channel, ChannelConfig, EventReceiver};
+    use tokio::sync::mpsc::error::TryRecvError;
+
+    fn connect(registry: &ConnectionRegistry, user_id: &str) -> (ConnectionId, EventReceiver) {
+        reconnect(registry, user_id, None)
+    }
+
+    fn reconnect(
+        registry: &ConnectionRegistry,
+        user_id: &str,
+        last_event_id: Option<u64>,
+    ) -> (ConnectionId, EventReceiver) {
+        let (tx, rx) = channel(ChannelConfig::default());
+        (
+            registry.register(user_id.to_string(), tx, last_event_id),
+            rx,
+        )
+    }
+
+    fn received(rx: &mut EventReceiver) -> usize {
+        let mut count = 0;
+        while rx.try_recv().is_ok() {
+            count += 1;
+        }
+        count
+    }
+
+    #[test]
+    fn send_to_user_reaches_every_connection_of_that_user_only() {
+        let registry = ConnectionRegistry::new();
+        let (_, mut alice_tab1) = connect(&registry, "alice");
+        let (_, mut alice_tab2) = connect(&registry, "alice");
+        let (_, mut bob) = connect(&registry, "bob");
+
+        registry.send_to_user(&"alice".to_string(), "test", "hi".into());
+
+        assert_eq!(received(&mut alice_tab1), 1);
+        assert_eq!(received(&mut alice_tab2), 1);
+        assert_eq!(received(&mut bob), 0);
+    }
+
+    #[test]
+    fn filtered_connections_skip_other_events_live_and_on_replay() {
+        let registry = ConnectionRegistry::new();
+        let (_, mut alice) = connect(&registry, "alice");
+        registry.send_to_user(&"alice".to_string(), "goal_created", "{}".into());
+        registry.send_to_user(&"alice".to_string(), "action_created", "{}".into());
+        let first_id = alice.try_recv().unwrap().id;
+
+        let (tx, mut actions) = channel(ChannelConfig::default());
+        let filter = "action.*".parse().unwrap();
+        registry.register_filtered("alice".to_string(), tx, Some(first_id - 1), filter);
+        registry.send_to_user(&"alice".to_string(), "goal_updated", "{}".into());
+        registry.send_to_user(&"alice".to_string(), "action_updated", "{}".into());
+
+        let types: Vec<&str> = std::iter::from_fn(|| actions.try_recv().ok())
+            .map(|frame| frame.event_type)
+            .collect();
+        assert_eq!(types, ["action_created", "action_updated"]);
+        assert!(registry.wants(&"alice".to_string(), "goal_updated"));
+    }
+
+    #[test]
+    fn slow_connection_falls_behind_without_holding_up_the_others() {
+        let registry = ConnectionRegistry::new();
+        let (slow_tx, mut slow) = channel(ChannelConfig {
+            capacity: 2,
+            ..ChannelConfig::default()
+        });
+        registry.register("alice".to_string(), slow_tx, None);
+        let (_, mut fast) = connect(&registry, "alice");
+
+        for _ in 0..5 {
+            registry.send_to_user(&"alice".to_string(), "test", "hi".into());
+        }
+
+        assert_eq!(received(&mut fast), 5);
+        assert_eq!(slow.try_recv().unwrap().event_type, "resync");
+        assert_eq!(received(&mut slow), 2);
+        assert_eq!(registry.stats(None).dropped, 0);
+    }
+
+    #[test]
+    fn close_delivers_queued_events_then_ends_every_connection() {
+        let registry = ConnectionRegistry::new();
+        let (alice_id, mut alice) = connect(&registry, "alice");
+        registry.broadcast("test", "bye".into());
+
+        registry.close();
+
+        assert!(alice.try_recv().is_ok());
+        assert!(matches!(alice.try_recv(), Err(TryRecvError::Disconnected)));
+        assert!(!registry.is_connected(&"alice".to_string()));
+        assert_eq!(registry.unregister(&alice_id), None);
+
+        let (_, mut bob) = connect(&registry, "bob");
+        assert!(matches!(bob.try_recv(), Err(TryRecvError::Disconnected)));
+        assert_eq!(registry.stats(None).connections, 0);
+    }
+
+    #[test]
+    fn disconnect_user_delivers_queued_events_then_ends_only_their_connections() {
+        let registry = ConnectionRegistry::new();
+        let (alice_id, mut alice) = connect(&registry, "alice");
+        let (_, mut alice_tab2) = connect(&registry, "alice");
+        let (_, mut bob) = connect(&registry, "bob");
+        registry.send_to_user(&"alice".to_string(), "force_logout", "{}".into());
+
+        assert_eq!(registry.disconnect_user(&"alice".to_string()), 2);
+
+        assert_eq!(alice.try_recv().unwrap().event_type, "force_logout");
+        assert!(matches!(alice.try_recv(), Err(TryRecvError::Disconnected)));
+        assert_eq!(received(&mut alice_tab2), 1);
+        assert!(!registry.is_connected(&"alice".to_string()));
+        assert_eq!(registry.unregister(&alice_id), None);
+
+        registry.send_to_user(&"bob".to_string(), "test", "hi".into());
+        assert_eq!(received(&mut bob), 1);
+        assert_eq!(registry.disconnect_user(&"carol".to_string()), 0);
+    }
+
+    #[test]
+    fn unregister_removes_only_that_connection() {
+        let registry = ConnectionRegistry::new();
+        let (tab1, mut alice_tab1) = connect(&registry, "alice");
+        let (_, mut alice_tab2) = connect(&registry, "alice");
+
+        registry.unregister(&tab1);
+        registry.send_to_user(&"alice".to_string(), "test", "hi".into());
+
+        assert_eq!(received(&mut alice_tab1), 0);
+        assert_eq!(received(&mut alice_tab2), 1);
+    }
+
+    #[test]
+    fn is_connected_tracks_open_connections() {
+        let registry = ConnectionRegistry::new();
+        let alice = "alice".to_string();
+        assert!(!registry.is_connected(&alice));
+
+        let (id, _rx) = connect(&registry, &alice);
+        assert!(registry.is_connected(&alice));
+
+        // Buffered events keep the entry, but not the connection
+        registry.send_to_user(&alice, "test", "hi".into());
+        registry.unregister(&id);
+        assert!(!registry.is_connected(&alice));
+    }
+
+    #[test]
+    fn unregister_last_connection_keeps_user_entry_for_the_replay_window() {
+        let registry = ConnectionRegistry::new();
+        let user_id = "alice".to_string();
+        let (id, _rx) = connect(&registry, &user_id);
+
+        registry.unregister(&id);
+        // Unregistering twice is a no-op
+        registry.unregister(&id);
+        assert!(registry.owners.is_empty());
+
+        // Events sent before the user reconnects are still buffered
+        registry.send_to_user(&user_id, "test", "missed".into());
+        assert_eq!(
+            lock(&read(registry.shard(&user_id))[&user_id].replay).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn broadcast_reaches_all_connections() {
+        let registry = ConnectionRegistry::new();
+        let mut receivers: Vec<_> = (0..100)
+            .map(|i| connect(&registry, &format!("user-{i}")).1)
+            .collect();
+
+        registry.broadcast("test", "all".into());
+
+        assert!(receivers.iter_mut().all(|rx| received(rx) == 1));
+    }
+
+    #[test]
+    fn broadcast_snapshot_tracks_membership_changes() {
+        let registry = ConnectionRegistry::new();
+        let (alice_id, mut alice) = connect(&registry, "alice");
+        registry.broadcast("test", "first".into());
+
+        // Registering after a snapshot was built must include the new connection
+        let (_, mut bob) = connect(&registry, "bob");
+        registry.broadcast("test", "second".into());
+        assert_eq!(received(&mut alice), 2);
+        assert_eq!(received(&mut bob), 1);
+
+        // Unregistering must drop the connection (and its sender) from the snapshot
+        registry.unregister(&alice_id);
+        registry.broadcast("test", "third".into());
+        assert_eq!(received(&mut bob), 1);
+        assert!(matches!(alice.try_recv(), Err(TryRecvError::Disconnected)));
+    }
+
+    #[test]
+    fn stats_count_connections_and_deliveries() {
+        let registry = ConnectionRegistry::new();
+        let alice = "alice".to_string();
+        let (_, _alice_tab1) = connect(&registry, &alice);
+        let (_, alice_tab2) = connect(&registry, &alice);
+        let (bob_id, _bob) = connect(&registry, "bob");
+        registry.unregister(&bob_id);
+
+        drop(alice_tab2);
+        registry.send_to_user(&alice, "test", "hi".into());
+
+        let stats = registry.stats(None);
+        assert_eq!(stats.connections, 2);
+        assert_eq!((stats.sent, stats.dropped), (1, 1));
+        assert_eq!(stats.users.len(), 2);
+
+        let stats = registry.stats(Some(&alice));
+        assert_eq!(stats.users.len(), 1);
+        assert_eq!(stats.users[0].connection_ages.len(), 2);
+        assert_eq!(stats.users[0].buffered_events, 1);
+    }
+
+    #[test]
+    fn unchanged_membership_reuses_broadcast_snapshot() {
+        let registry = ConnectionRegistry::new();
+        let (_, _rx) = connect(&registry, "alice");
+
+        let first = registry.broadcast_snapshot();
+        let second = registry.broadcast_snapshot();
+
+        assert!(Arc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn reconnecting_replays_events_sent_after_the_last_one_seen() {
+        let registry = ConnectionRegistry::new();
+        let alice = "alice".to_string();
+        let (id, _rx) = connect(&registry, &alice);
+
+        registry.send_to_user(&alice, "test", "seen".into());
+        let last_seen = registry.next_event_id.load(Ordering::Relaxed) - 1;
+        registry.unregister(&id);
+        registry.send_to_user(&alice, "test", "missed".into());
+        registry.broadcast("test", "missed broadcast".into());
+        registry.send_to_user(&"bob".to_string(), "test", "not alice's".into());
+
+        let (_, mut rx) = reconnect(&registry, &alice, Some(last_seen));
+        assert_eq!(received(&mut rx), 2);
+
+        // Without a last event ID nothing is replayed
+        let (_, mut rx) = connect(&registry, &alice);
+        assert_eq!(received(&mut rx), 0);
+    }
+
+    #[test]
+    fn ephemeral_events_reach_open_connections_but_are_never_replayed() {
+        let registry = ConnectionRegistry::new();
+        let alice = "alice".to_string();
+        let (id, mut rx) = connect(&registry, &alice);
+
+        registry.send_ephemeral_to_user(&alice, "test", "typing".into());
+        let last_seen = rx.try_recv().unwrap().id;
+        registry.unregister(&id);
+        registry.send_ephemeral_to_user(&alice, "test", "missed".into());
+
+        let (_, mut rx) = reconnect(&registry, &alice, Some(last_seen - 1));
+        assert_eq!(received(&mut rx), 0);
+        assert_eq!(registry.stats(Some(&alice)).users[0].buffered_events, 0);
+    }
+
+    #[test]
+    fn replay_buffer_keeps_only_the_most_recent_events() {
+        let registry = ConnectionRegistry::new();
+        let alice = "alice".to_string();
+        let first_id = registry.next_event_id.load(Ordering::Relaxed);
+        let (_, _rx) = connect(&registry, &alice);
+
+        for _ in 0..REPLAY_CAPACITY + 10 {
+            registry.send_to_user(&alice, "test", "hi".into());
+        }
+
+        let (_, mut rx) = reconnect(&registry, &alice, Some(first_id - 1));
+        assert_eq!(received(&mut rx), REPLAY_CAPACITY);
+    }
+
+    #[test]
+    fn expired_events_are_pruned_and_idle_users_swept() {
+        let registry = ConnectionRegistry::new();
+        let alice = "alice".to_string();
+        let (id, _rx) = connect(&registry, &alice);
+        registry.send_to_user(&alice, "test", "hi".into());
+        registry.unregister(&id);
+
+        // Buffered events keep a disconnected user's entry around
+        assert!(read(registry.shard(&alice)).contains_key(&alice));
+
+        let expired = Instant::now().checked_sub(REPLAY_WINDOW).unwrap();
+        {
+            let mut shard = write(registry.shard(&alice));
+            let entry = shard.get_mut(&alice).unwrap();
+            entry.disconnected_at = Some(expired);
+            for buffered in lock(&entry.replay).iter_mut() {
+                buffered.sent_at = expired;
+            }
+        }
+        registry.sweep_idle_users();
+
+        assert!(!read(registry.shard(&alice)).contains_key(&alice));
+    }
+
+    #[test]
+    fn a_connection_past_the_limit_supersedes_the_users_oldest() {
+        let registry = ConnectionRegistry::new().with_max_connections_per_user(Some(2));
+        let (oldest_id, mut oldest) = connect(&registry, "alice");
+        let (_, mut middle) = connect(&registry, "alice");
+        let (_, mut bob) = connect(&registry, "bob");
+
+        let (_, mut newest) = connect(&registry, "alice");
+
+        let superseded = oldest.try_recv().unwrap();
+        assert_eq!(superseded.event_type, "superseded");
+        assert!(superseded.data.contains(r#""max_connections":2"#));
+        assert!(matches!(oldest.try_recv(), Err(TryRecvError::Disconnected)));
+        assert_eq!(registry.unregister(&oldest_id), None);
+
+        registry.send_to_user(&"alice".to_string(), "test", "hi".into());
+        assert_eq!(received(&mut middle), 1);
+        assert_eq!(received(&mut newest), 1);
+        assert_eq!(received(&mut bob), 0);
+        assert_eq!(registry.stats(None).connections, 3);
+    }
+}
@@ -1,92 +1,165 @@
 use crate::message::{Event as SseEvent, EventType, Message as SseMessage, MessageScope};
 use crate::Manager;
 use async_trait::async_trait;
-use events::{DomainEvent, EventHandler};
+use events::{DomainEvent, EventHandler, EventKind, EventKinds, HandlerError};
 use log::*;
 use std::sync::Arc;
 
+/// `reason` of the `force_logout` event sent to a user whose account was locked
+const ACCOUNT_LOCKED: &str = "account_locked";
+
+/// `reason` of the `session_invalidated` event sent to a user whose roles changed
+const ROLES_CHANGED: &str = "roles_changed";
+
 /// Handles domain events by converting them to SSE messages and broadcasting to affected users.
 ///
 /// This handler is responsible for:
 /// 1. Converting domain events into SSE events
-/// 2. Sending SSE messages to the user IDs specified in the event
+/// 2. Sending SSE messages to the user IDs specified in the event, or to the
+///    coaching relationship the event is addressed to
 ///
 /// The domain layer determines which users should be notified and includes
-/// their IDs in the event. This handler simply routes the SSE messages.
+/// their IDs in the event, except for relationship-wide events, whose members
+/// the manager resolves. This handler simply routes the SSE messages.
 pub struct SseDomainEventHandler {
     sse_manager: Arc<Manager>,
 }
 
 impl SseDomainEventHandler {
+    /// The events forwarded to clients; register the handler for only these.
+    pub const KINDS: EventKinds = EventKinds::of(&[
+        EventKind::GoalCreated,
+        EventKind::GoalUpdated,
+        EventKind::GoalDeleted,
+        EventKind::CoachingSessionGoalCreated,
+        EventKind::CoachingSessionGoalDeleted,
+        EventKind::AgreementCreated,
+        EventKind::AgreementUpdated,
+        EventKind::AgreementDeleted,
+        EventKind::ActionCreated,
+        EventKind::ActionUpdated,
+        EventKind::ActionDeleted,
+        EventKind::ReactionsChanged,
+        EventKind::MentionCreated,
+        EventKind::LibraryAssignmentCreated,
+        EventKind::LibraryAssignmentCompleted,
+        EventKind::SessionRequestCreated,
+        EventKind::SessionRequestResolved,
+        EventKind::SessionPulseRequested,
+        EventKind::RelationshipHealthAtRisk,
+        EventKind::MeetingRecordingUpdated,
+        EventKind::TopicsChanged,
+        EventKind::CoachingSessionTitleUpdated,
+        EventKind::TranscriptionUpdated,
+        EventKind::RecordingStatusChanged,
+        EventKind::TranscriptionCompleted,
+        EventKind::AiSuggestionsReady,
+        EventKind::UserLocked,
+        EventKind::UserRolesChanged,
+    ]);
+
     pub fn new(sse_manager: Arc<Manager>) -> Self {
         Self { sse_manager }
     }
 
     /// Send an SSE message to all specified users.
-    fn send_to_users(&self, sse_event: SseEvent, user_ids: &[events::Id]) {
+    async fn send_to_users(&self, sse_event: SseEvent, user_ids: &[events::Id]) {
         let event_type = sse_event.event_type();
+        let correlation_id = events::current_correlation_id().map(|id| id.to_string());
 
         for user_id in user_ids {
-            self.sse_manager.send_message(SseMessage {
-                event: sse_event.clone(),
-                scope: MessageScope::User {
-                    user_id: user_id.to_string(),
-                },
-            });
+            self.sse_manager
+                .send_message(SseMessage {
+                    event: sse_event.clone(),
+                    scope: MessageScope::User {
+                        user_id: user_id.to_string(),
+                    },
+                    correlation_id: correlation_id.clone(),
+                })
+                .await;
         }
 
-        info!("Sent {} event to {} user(s)", event_type, user_ids.len());
+        info!(
+            "Sent {} event to {} user(s); correlation_id: {:?}",
+            event_type,
+            user_ids.len(),
+            correlation_id
+        );
+    }
+
+    /// Send an SSE message to the coach and coachee of a coaching relationship.
+    async fn send_to_relationship(
+        &self,
+        sse_event: SseEvent,
+        coaching_relationship_id: events::Id,
+    ) {
+        let event_type = sse_event.event_type();
+        let correlation_id = events::current_correlation_id().map(|id| id.to_string());
+
+        self.sse_manager
+            .send_message(SseMessage {
+                event: sse_event,
+                scope: MessageScope::Relationship {
+                    coaching_relationship_id: coaching_relationship_id.to_string(),
+                },
+                correlation_id: correlation_id.clone(),
+            })
+            .await;
+
+        info!(
+            "Sent {} event to coaching relationship {}; correlation_id: {:?}",
+            event_type, coaching_relationship_id, correlation_id
+        );
     }
 }
 
 #[async_trait]
 impl EventHandler for SseDomainEventHandler {
-    async fn handle(&self, event: &DomainEvent) {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), HandlerError> {
         match event {
             DomainEvent::GoalCreated {
                 coaching_relationship_id,
                 goal,
-                notify_user_ids,
             } => {
                 let sse_event = SseEvent::GoalCreated {
                     coaching_relationship_id: coaching_relationship_id.to_string(),
                     goal: goal.clone(),
                 };
 
-                self.send_to_users(sse_event, notify_user_ids);
+                self.send_to_relationship(sse_event, *coaching_relationship_id)
+                    .await;
             }
 
             DomainEvent::GoalUpdated {
                 coaching_relationship_id,
                 goal,
-                notify_user_ids,
             } => {
                 let sse_event = SseEvent::GoalUpdated {
                     coaching_relationship_id: coaching_relationship_id.to_string(),
                     goal: goal.clone(),
                 };
 
-                self.send_to_users(sse_event, notify_user_ids);
+                self.send_to_relationship(sse_event, *coaching_relationship_id)
+                    .await;
             }
 
             DomainEvent::GoalDeleted {
                 coaching_relationship_id,
                 goal_id,
-                notify_user_ids,
             } => {
                 let sse_event = SseEvent::GoalDeleted {
                     coaching_relationship_id: coaching_relationship_id.to_string(),
                     goal_id: goal_id.to_string(),
                 };
 
-                self.send_to_users(sse_event, notify_user_ids);
+                self.send_to_relationship(sse_event, *coaching_relationship_id)
+                    .await;
             }
 
             DomainEvent::CoachingSessionGoalCreated {
                 coaching_relationship_id,
                 coaching_session_id,
                 goal_id,
-                notify_user_ids,
             } => {
                 let sse_event = SseEvent::CoachingSessionGoalCreated {
                     coaching_relationship_id: coaching_relationship_id.to_string(),
@@ -94,14 +167,14 @@ impl EventHandler for SseDomainEventHandler {
                     goal_id: goal_id.to_string(),
                 };
 
-                self.send_to_users(sse_event, notify_user_ids);
+                self.send_to_relationship(sse_event, *coaching_relationship_id)
+                    .await;
             }
 
             DomainEvent::CoachingSessionGoalDeleted {
                 coaching_relationship_id,
                 coaching_session_id,
                 goal_id,
-                notify_user_ids,
             } => {
                 let sse_event = SseEvent::CoachingSessionGoalDeleted {
                     coaching_relationship_id: coaching_relationship_id.to_string(),
@@ -109,7 +182,8 @@ impl EventHandler for SseDomainEventHandler {
                     goal_id: goal_id.to_string(),
                 };
 
-                self.send_to_users(sse_event, notify_user_ids);
+                self.send_to_relationship(sse_event, *coaching_relationship_id)
+                    .await;
             }
 
             DomainEvent::AgreementCreated {
@@ -122,7 +196,7 @@ impl EventHandler for SseDomainEventHandler {
                     agreement: agreement.clone(),
                 };
 
-                self.send_to_users(sse_event, notify_user_ids);
+                self.send_to_users(sse_event, notify_user_ids).await;
             }
 
             DomainEvent::ActionCreated {
@@ -135,7 +209,7 @@ impl EventHandler for SseDomainEventHandler {
                     action: action.clone(),
                 };
 
-                self.send_to_users(sse_event, notify_user_ids);
+                self.send_to_users(sse_event, notify_user_ids).await;
             }
 
             DomainEvent::AgreementUpdated {
@@ -148,7 +222,7 @@ impl EventHandler for SseDomainEventHandler {
                     agreement: agreement.clone(),
                 };
 
-                self.send_to_users(sse_event, notify_user_ids);
+                self.send_to_users(sse_event, notify_user_ids).await;
             }
 
             DomainEvent::ActionUpdated {
@@ -161,7 +235,7 @@ impl EventHandler for SseDomainEventHandler {
                     action: action.clone(),
                 };
 
-                self.send_to_users(sse_event, notify_user_ids);
+                self.send_to_users(sse_event, notify_user_ids).await;
             }
 
             DomainEvent::AgreementDeleted {
@@ -174,7 +248,7 @@ impl EventHandler for SseDomainEventHandler {
                     agreement_id: agreement_id.to_string(),
                 };
 
-                self.send_to_users(sse_event, notify_user_ids);
+                self.send_to_users(sse_event, notify_user_ids).await;
             }
 
             DomainEvent::ActionDeleted {
@@ -187,7 +261,117 @@ impl EventHandler for SseDomainEventHandler {
                     action_id: action_id.to_string(),
                 };
 
-                self.send_to_users(sse_event, notify_user_ids);
+                self.send_to_users(sse_event, notify_user_ids).await;
+            }
+
+            DomainEvent::MentionCreated {
+                coaching_session_id,
+                mention,
+                notify_user_ids,
+            } => {
+                let sse_event = SseEvent::MentionCreated {
+                    coaching_session_id: coaching_session_id.to_string(),
+                    mention: mention.clone(),
+                };
+
+                self.send_to_users(sse_event, notify_user_ids).await;
+            }
+
+            DomainEvent::ReactionsChanged {
+                coaching_session_id,
+                resource_type,
+                resource_id,
+                reactions,
+                notify_user_ids,
+            } => {
+                let sse_event = SseEvent::ReactionsChanged {
+                    coaching_session_id: coaching_session_id.to_string(),
+                    resource_type: resource_type.clone(),
+                    resource_id: resource_id.to_string(),
+                    reactions: reactions.clone(),
+                };
+
+                self.send_to_users(sse_event, notify_user_ids).await;
+            }
+
+            DomainEvent::LibraryAssignmentCreated {
+                coaching_relationship_id,
+                assignment,
+                notify_user_ids,
+            } => {
+                let sse_event = SseEvent::LibraryAssignmentCreated {
+                    coaching_relationship_id: coaching_relationship_id.to_string(),
+                    assignment: assignment.clone(),
+                };
+
+                self.send_to_users(sse_event, notify_user_ids).await;
+            }
+
+            DomainEvent::LibraryAssignmentCompleted {
+                coaching_relationship_id,
+                assignment,
+                notify_user_ids,
+            } => {
+                let sse_event = SseEvent::LibraryAssignmentCompleted {
+                    coaching_relationship_id: coaching_relationship_id.to_string(),
+                    assignment: assignment.clone(),
+                };
+
+                self.send_to_users(sse_event, notify_user_ids).await;
+            }
+
+            DomainEvent::SessionRequestCreated {
+                coaching_relationship_id,
+                session_request,
+                notify_user_ids,
+            } => {
+                let sse_event = SseEvent::SessionRequestCreated {
+                    coaching_relationship_id: coaching_relationship_id.to_string(),
+                    session_request: session_request.clone(),
+                };
+
+                self.send_to_users(sse_event, notify_user_ids).await;
+            }
+
+            DomainEvent::SessionRequestResolved {
+                coaching_relationship_id,
+                session_request,
+                notify_user_ids,
+            } => {
+                let sse_event = SseEvent::SessionRequestResolved {
+                    coaching_relationship_id: coaching_relationship_id.to_string(),
+                    session_request: session_request.clone(),
+                };
+
+                self.send_to_users(sse_event, notify_user_ids).await;
+            }
+
+            DomainEvent::SessionPulseRequested {
+                coaching_relationship_id,
+                coaching_session_id,
+                notify_user_ids,
+            } => {
+                let sse_event = SseEvent::SessionPulseRequested {
+                    coaching_relationship_id: coaching_relationship_id.to_string(),
+                    coaching_session_id: coaching_session_id.to_string(),
+                };
+
+                self.send_to_users(sse_event, notify_user_ids).await;
+            }
+
+            DomainEvent::RelationshipHealthAtRisk {
+                coaching_relationship_id,
+                score,
+                threshold,
+                notify_user_ids,
+            } => {
+                let sse_event = SseEvent::RelationshipHealthAtRisk {
+                    coaching_relationship_id: coaching_relationship_id.to_string(),
+                    score: *score,
+                    threshold: *threshold,
+                };
+
+                self.send_to_users(sse_event, notify_user_ids).await;
             }
 
             DomainEvent::MeetingRecordingUpdated {
@@ -198,7 +382,7 @@ impl EventHandler for SseDomainEventHandler {
                     coaching_session_id: coaching_session_id.to_string(),
                 };
 
-                self.send_to_users(sse_event, notify_user_ids);
+                self.send_to_users(sse_event, notify_user_ids).await;
             }
 
             DomainEvent::TopicsChanged {
@@ -209,7 +393,7 @@ impl EventHandler for SseDomainEventHandler {
                     coaching_session_id: coaching_session_id.to_string(),
                 };
 
-                self.send_to_users(sse_event, notify_user_ids);
+                self.send_to_users(sse_event, notify_user_ids).await;
             }
 
             DomainEvent::CoachingSessionTitleUpdated {
@@ -220,7 +404,7 @@ impl EventHandler for SseDomainEventHandler {
                     coaching_session_id: coaching_session_id.to_string(),
                 };
 
-                self.send_to_users(sse_event, notify_user_ids);
+                self.send_to_users(sse_event, notify_user_ids).await;
             }
 
             DomainEvent::TranscriptionUpdated {
@@ -231,8 +415,86 @@ impl EventHandler for SseDomainEventHandler {
                     coaching_session_id: coaching_session_id.to_string(),
                 };
 
-                self.send_to_users(sse_event, notify_user_ids);
+                self.send_to_users(sse_event, notify_user_ids).await;
+            }
+
+            DomainEvent::RecordingStatusChanged {
+                coaching_session_id,
+                meeting_recording_id,
+                status,
+                error_message,
+                notify_user_ids,
+            } => {
+                let sse_event = SseEvent::RecordingStatusChanged {
+                    coaching_session_id: coaching_session_id.to_string(),
+                    meeting_recording_id: meeting_recording_id.to_string(),
+                    status: status.clone(),
+                    error_message: error_message.clone(),
+                };
+
+                self.send_to_users(sse_event, notify_user_ids).await;
             }
+
+            DomainEvent::TranscriptionCompleted {
+                coaching_session_id,
+                transcription,
+                segment_count,
+                notify_user_ids,
+            } => {
+                let sse_event = SseEvent::TranscriptionCompleted {
+                    coaching_session_id: coaching_session_id.to_string(),
+                    transcription: transcription.clone(),
+                    segment_count: *segment_count,
+                };
+
+                self.send_to_users(sse_event, notify_user_ids).await;
+            }
+
+            DomainEvent::AiSuggestionsReady {
+                coaching_session_id,
+                transcription_id,
+                chapter_count,
+                notify_user_ids,
+            } => {
+                let sse_event = SseEvent::AiSuggestionsReady {
+                    coaching_session_id: coaching_session_id.to_string(),
+                    transcription_id: transcription_id.to_string(),
+                    chapter_count: *chapter_count,
+                };
+
+                self.send_to_users(sse_event, notify_user_ids).await;
+            }
+
+            DomainEvent::UserLocked { user_id, .. } => {
+                let sse_event = SseEvent::ForceLogout {
+                    reason: ACCOUNT_LOCKED.to_string(),
+                };
+
+                self.send_to_users(sse_event, &[*user_id]).await;
+                self.sse_manager.disconnect_user(&user_id.to_string()).await;
+            }
+
+            DomainEvent::UserRolesChanged {
+                organization_id,
+                user_id,
+            } => {
+                let sse_event = SseEvent::SessionInvalidated {
+                    reason: ROLES_CHANGED.to_string(),
+                    organization_id: Some(organization_id.to_string()),
+                };
+
+                self.send_to_users(sse_event, &[*user_id]).await;
+            }
+
+            // Only kept in the audit log
+            DomainEvent::UserUnlocked { .. } => {}
         }
+
+        Ok(())
+    }
+
+    /// SSE connections are held by the web process that published the event.
+    fn in_process(&self) -> bool {
+        true
     }
 }
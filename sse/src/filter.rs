@@ -0,0 +1,183 @@
+//! Which events a connection subscribes to.
+//!
+//! A client that only shows some kinds of events (e.g. the mobile app) connects with
+//! `?events=action.*,note.*` and isn't sent the rest. `category.*` subscribes to the
+//! events named `category_…`, so `action.*` is `action_created`, `action_updated` and
+//! `action_deleted`; a full event name subscribes to that event only, and `*` to all of
+//! them. System events are always sent, since a client that misses one misbehaves.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Events every connection is sent, whatever it subscribed to
+const SYSTEM_EVENTS: [&str; 6] = [
+    "force_logout",
+    "session_expiring_soon",
+    "session_invalidated",
+    "resync",
+    "server_restarting",
+    "superseded",
+];
+
+/// Most patterns a filter may list
+const MAX_PATTERNS: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Pattern {
+    /// `*`
+    All,
+    /// `category.*`, held as `category_`
+    Category(String),
+    /// A full event name
+    Event(String),
+}
+
+impl Pattern {
+    fn matches(&self, event_type: &str) -> bool {
+        match self {
+            Pattern::All => true,
+            Pattern::Category(prefix) => event_type.starts_with(prefix.as_str()),
+            Pattern::Event(name) => event_type == name,
+        }
+    }
+}
+
+/// The events a connection subscribed to. The default subscribes to all of them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventFilter {
+    /// Empty for every event
+    patterns: Vec<Pattern>,
+}
+
+impl EventFilter {
+    /// Whether a connection with this filter is sent events of `event_type`
+    pub fn matches(&self, event_type: &str) -> bool {
+        self.patterns.is_empty()
+            || SYSTEM_EVENTS.contains(&event_type)
+            || self
+                .patterns
+                .iter()
+                .any(|pattern| pattern.matches(event_type))
+    }
+
+    /// Whether this filter lets every event through
+    pub fn is_all(&self) -> bool {
+        self.patterns.is_empty()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct EventFilterParseError(String);
+
+impl fmt::Display for EventFilterParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for EventFilterParseError {}
+
+impl FromStr for EventFilter {
+    type Err = EventFilterParseError;
+
+    /// Parses comma-separated patterns, e.g. `action.*,note.*,goal_created`
+    fn from_str(patterns: &str) -> Result<Self, Self::Err> {
+        let mut parsed = Vec::new();
+        for pattern in patterns.split(',').map(str::trim) {
+            let pattern = match (pattern, pattern.strip_suffix(".*")) {
+                ("*", _) => Pattern::All,
+                (_, Some(category)) if is_name(category) => {
+                    Pattern::Category(format!("{category}_"))
+                }
+                (_, None) if is_name(pattern) => Pattern::Event(pattern.to_string()),
+                _ => {
+                    return Err(EventFilterParseError(format!(
+                        "Invalid event pattern `{pattern}`; expected `category.*`, an event \
+                         name or `*`"
+                    )))
+                }
+            };
+            if pattern == Pattern::All {
+                return Ok(Self::default());
+            }
+            if !parsed.contains(&pattern) {
+                parsed.push(pattern);
+            }
+        }
+        if parsed.len() > MAX_PATTERNS {
+            return Err(EventFilterParseError(format!(
+                "At most {MAX_PATTERNS} event patterns are allowed"
+            )));
+        }
+        Ok(Self { patterns: parsed })
+    }
+}
+
+/// Whether `name` looks like an event name or category: lowercase words joined by `_`
+fn is_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('_')
+        && !name.ends_with('_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(patterns: &str) -> EventFilter {
+        patterns.parse().unwrap()
+    }
+
+    #[test]
+    fn categories_match_the_events_named_after_them() {
+        let filter = filter("action.*, note.*");
+
+        assert!(filter.matches("action_created"));
+        assert!(filter.matches("note_deleted"));
+        assert!(!filter.matches("goal_created"));
+        assert!(!filter.matches("actionable_thing"));
+    }
+
+    #[test]
+    fn event_names_match_only_themselves() {
+        let filter = filter("goal_created");
+
+        assert!(filter.matches("goal_created"));
+        assert!(!filter.matches("goal_updated"));
+    }
+
+    #[test]
+    fn system_events_are_always_matched() {
+        let filter = filter("action.*");
+
+        for event_type in SYSTEM_EVENTS {
+            assert!(filter.matches(event_type));
+        }
+    }
+
+    #[test]
+    fn a_wildcard_matches_everything() {
+        assert!(filter("action.*,*").is_all());
+        assert!(EventFilter::default().matches("goal_created"));
+    }
+
+    #[test]
+    fn malformed_patterns_are_rejected() {
+        for patterns in [
+            "",
+            "action.*,",
+            "Action.*",
+            "action*",
+            "_.*",
+            "action.created",
+        ] {
+            assert!(
+                patterns.parse::<EventFilter>().is_err(),
+                "{patterns:?} should be rejected"
+            );
+        }
+    }
+}
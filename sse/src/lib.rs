@@ -6,13 +6,27 @@
 //! # Architecture
 //!
 //! - **Single connection per user**: Each authenticated user establishes one
-//!   SSE connection that stays open across page navigation.
-//! - **Dual-index registry**: O(1) lookups for both connection management and
-//!   user-scoped message routing via separate DashMap indices.
-//! - **User and Broadcast scopes**: Messages can be sent to specific users or
-//!   broadcast to all connected users.
-//! - **Ephemeral messages**: All events are ephemeral - if a user is offline,
-//!   they miss the event and see fresh data on next page load.
+//!   SSE connection that stays open across page navigation. Users with many tabs
+//!   open are capped; their oldest connection is closed with a `superseded` event.
+//! - **Sharded registry**: O(1) user-scoped routing through a user index sharded
+//!   by hashed user id; broadcasts iterate a copy-on-write snapshot.
+//! - **User, Relationship and Broadcast scopes**: Messages can be sent to specific
+//!   users, to a coaching relationship's coach and coachee (resolved through a
+//!   `RelationshipMembers` lookup and cached for a few minutes), or broadcast to all
+//!   connected users.
+//! - **Short-lived replay**: Each event carries an ID. A client that reconnects
+//!   with `Last-Event-ID` within a few minutes gets the events it missed from the
+//!   replica's buffer; anything older is gone and the user sees fresh data on the
+//!   next page load.
+//! - **Long-poll fallback**: Clients behind proxies that break SSE poll
+//!   `/events/poll` instead, which returns the same buffered events (or waits for
+//!   the next ones) through a short-lived connection.
+//! - **Bounded channels**: Each connection's channel holds a fixed number of
+//!   undelivered events. A client that falls further behind loses the oldest and
+//!   gets a `resync` event instead, or is disconnected, as configured.
+//! - **Event filtering**: A connection can subscribe to some categories of events
+//!   only (`/sse?events=action.*,note.*`); it isn't sent the rest, and an event no
+//!   recipient subscribed to isn't serialized.
 //! - **Type-safe events**: All event types are strongly typed for compile-time
 //!   safety and better frontend TypeScript integration.
 //!
@@ -20,11 +34,13 @@
 //!
 //! 1. Frontend establishes SSE connection via `/sse` endpoint
 //! 2. Backend extracts user from session cookie (AuthenticatedUser)
-//! 3. Connection registered in ConnectionRegistry with dual indices
+//! 3. Connection registered in ConnectionRegistry's user shard
 //! 4. When a resource changes (e.g., action created):
-//!    - Controller determines recipient (e.g., other user in relationship)
+//!    - Controller determines recipient (e.g., other user in relationship), or
+//!      addresses the whole coaching relationship and leaves it to the Manager
 //!    - Controller sends message via `app_state.sse_manager.send_message()`
-//!    - Manager performs O(1) lookup in user_index to find connections
+//!    - Manager resolves a relationship's members (cached), then performs an O(1)
+//!      lookup in each user's shard to find connections
 //!    - Events sent only to matching connections
 //! 5. Frontend receives event and updates UI based on context
 //!
@@ -40,7 +56,9 @@
 //!         action: action.clone(),
 //!     },
 //!     scope: MessageScope::User { user_id: recipient_id },
-//! });
+//!     correlation_id: events::current_correlation_id().map(|id| id.to_string()),
+//! })
+//! .await;
 //! ```
 //!
 //! # Security Considerations
@@ -53,14 +71,21 @@
 //!
 //! # Modules
 //!
-//! - `connection`: ConnectionRegistry with dual-index architecture and type-safe ConnectionId
+//! - `channel`: Bounded per-connection event channels and their overflow policy
+//! - `connection`: Sharded ConnectionRegistry and type-safe ConnectionId
+//! - `filter`: The events a connection subscribes to
 //! - `manager`: High-level message routing (delegates to ConnectionRegistry)
 //! - `message`: Type-safe event and scope definitions
+//! - `relationship`: Resolution of a coaching relationship's members, with caching
 
+pub mod channel;
 pub mod connection;
 pub mod domain_event_handler;
+pub mod filter;
 pub mod manager;
 pub mod message;
+pub mod relationship;
 
 pub use domain_event_handler::SseDomainEventHandler;
 pub use manager::Manager;
+pub use relationship::{Partner, RelationshipMembers};
@@ -1,58 +1,293 @@
-use crate::connection::{ConnectionId, ConnectionRegistry, UserId};
-use crate::message::{EventType, Message as SseMessage, MessageScope};
-use axum::response::sse::Event;
+use crate::channel::{channel, ChannelConfig, EventReceiver};
+use crate::connection::{
+    ConnectionId, ConnectionRegistry, EventSender, Frame, RegistryStats, UserId,
+};
+use crate::filter::EventFilter;
+use crate::message::{Event as SseEvent, EventType, Message as SseMessage, MessageScope};
+use crate::relationship::{CachedRelationshipMembers, RelationshipMembers};
 use log::*;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+/// The events a long-poll returned, and the ID to poll from next
+#[derive(Debug)]
+pub struct Poll {
+    pub frames: Vec<Frame>,
+    pub last_event_id: u64,
+}
+
+/// Unregisters a connection when dropped, so an abandoned long-poll doesn't leak it
+struct Registration<'a> {
+    registry: &'a ConnectionRegistry,
+    connection_id: ConnectionId,
+}
+
+impl Drop for Registration<'_> {
+    fn drop(&mut self) {
+        self.registry.unregister(&self.connection_id);
+    }
+}
 
 pub struct Manager {
     registry: Arc<ConnectionRegistry>,
+    relationship_members: Option<CachedRelationshipMembers>,
+    channel_config: ChannelConfig,
+    /// The reconnect delay clients were given, once draining for shutdown
+    draining: OnceLock<Duration>,
 }
 
 impl Manager {
     pub fn new() -> Self {
         Self {
             registry: Arc::new(ConnectionRegistry::new()),
+            relationship_members: None,
+            channel_config: ChannelConfig::default(),
+            draining: OnceLock::new(),
         }
     }
 
-    /// Register a new connection and return its unique ID
-    pub fn register_connection(
+    /// Resolves the members of `MessageScope::Relationship` messages with `resolver`,
+    /// caching each relationship's for a few minutes, and whom to tell when a user comes
+    /// online or goes offline. Without one, such messages are dropped and presence isn't
+    /// announced.
+    pub fn with_relationship_members(mut self, resolver: Arc<dyn RelationshipMembers>) -> Self {
+        self.relationship_members = Some(CachedRelationshipMembers::new(resolver));
+        self
+    }
+
+    /// Sizes each connection's channel, and sets what happens when a client falls too
+    /// far behind to fit in it.
+    pub fn with_channel_config(mut self, channel_config: ChannelConfig) -> Self {
+        self.channel_config = channel_config;
+        self
+    }
+
+    /// Caps the connections each user may have open to this process, e.g. across many
+    /// tabs. Opening one more sends their oldest a `superseded` event and closes it.
+    /// Set before any connection registers.
+    pub fn with_max_connections_per_user(mut self, max: Option<usize>) -> Self {
+        self.registry = Arc::new(ConnectionRegistry::new().with_max_connections_per_user(max));
+        self
+    }
+
+    /// A channel for a new connection to register with
+    pub fn channel(&self) -> (EventSender, EventReceiver) {
+        channel(self.channel_config)
+    }
+
+    /// Register a new connection and return its unique ID. `last_event_id` is the
+    /// client's `Last-Event-ID`; events it missed since are replayed first. Only the
+    /// events `filter` matches are sent to it. The user's first connection announces
+    /// them online.
+    pub async fn register_connection(
         &self,
         user_id: UserId,
-        sender: tokio::sync::mpsc::UnboundedSender<Result<Event, std::convert::Infallible>>,
+        sender: EventSender,
+        last_event_id: Option<u64>,
+        filter: EventFilter,
     ) -> ConnectionId {
-        let connection_id = self.registry.register(user_id.clone(), sender);
+        let came_online = !self.registry.is_connected(&user_id) && self.draining().is_none();
+        let connection_id =
+            self.registry
+                .register_filtered(user_id.clone(), sender, last_event_id, filter);
         info!("Registered new SSE connection");
+
+        if came_online {
+            self.announce_presence(&user_id).await;
+        }
         connection_id
     }
 
-    /// Unregister a connection by ID
-    pub fn unregister_connection(&self, connection_id: &ConnectionId) {
+    /// Unregister a connection by ID. The user's last connection announces them offline.
+    pub async fn unregister_connection(&self, connection_id: &ConnectionId) {
         info!("Unregistering SSE connection");
-        self.registry.unregister(connection_id);
+        let Some(user_id) = self.registry.unregister(connection_id) else {
+            return;
+        };
+
+        if !self.registry.is_connected(&user_id) {
+            self.announce_presence(&user_id).await;
+        }
     }
 
-    /// Send a message based on its scope
-    pub fn send_message(&self, message: SseMessage) {
-        let event_type = message.event.event_type();
+    /// Long-polls for the user's events sent after `since` (or, without it, from now
+    /// on): returns those still buffered right away, otherwise waits up to `wait` for
+    /// the next ones. Shares the replay buffers of SSE connections.
+    pub async fn poll(&self, user_id: UserId, since: Option<u64>, wait: Duration) -> Poll {
+        let since = since.unwrap_or_else(|| self.registry.last_event_id());
+        let (sender, mut receiver) = self.channel();
+        let _registration = Registration {
+            registry: &self.registry,
+            connection_id: self.registry.register(user_id, sender, Some(since)),
+        };
+
+        let mut frames = Vec::new();
+        if let Ok(Some(frame)) = tokio::time::timeout(wait, receiver.recv()).await {
+            frames.push(frame);
+            while let Ok(frame) = receiver.try_recv() {
+                frames.push(frame);
+            }
+        }
 
-        let event_data = match serde_json::to_string(&message.event) {
-            Ok(json) => json,
+        let last_event_id = frames.last().map_or(since, |frame| frame.id);
+        debug!("Long-poll returned {} events", frames.len());
+        Poll {
+            frames,
+            last_event_id,
+        }
+    }
+
+    /// Drains the manager for shutdown: tells every connection the server is restarting
+    /// and to reconnect after `reconnect_after`, then closes them once that's queued.
+    /// Connections registered from then on are closed straight away. Only the first
+    /// call has an effect.
+    pub async fn drain(&self, reconnect_after: Duration) {
+        if self.draining.set(reconnect_after).is_err() {
+            return;
+        }
+        info!(
+            "Draining {} SSE connections for shutdown",
+            self.registry.stats(None).connections
+        );
+
+        self.send_message(SseMessage {
+            event: SseEvent::ServerRestarting {
+                reconnect_after_ms: reconnect_after.as_millis() as u64,
+            },
+            scope: MessageScope::Broadcast,
+            correlation_id: None,
+        })
+        .await;
+        self.registry.close();
+    }
+
+    /// Closes the user's connections to this process once the events already sent to
+    /// them are delivered, e.g. after telling them to log out. Announces them offline.
+    pub async fn disconnect_user(&self, user_id: &UserId) {
+        let closed = self.registry.disconnect_user(user_id);
+        if closed > 0 {
+            info!("Closed {closed} SSE connections of user {user_id}");
+            self.announce_presence(user_id).await;
+        }
+    }
+
+    /// The reconnect delay clients were given, once [`Manager::drain`] has been called
+    pub fn draining(&self) -> Option<Duration> {
+        self.draining.get().copied()
+    }
+
+    /// This process's connections and delivery counters, for one user or all of them
+    pub fn stats(&self, user_id: Option<&UserId>) -> RegistryStats {
+        self.registry.stats(user_id)
+    }
+
+    /// Whether the user has an SSE connection open to this process
+    pub fn is_connected(&self, user_id: &UserId) -> bool {
+        self.registry.is_connected(user_id)
+    }
+
+    /// Tells the other member of each of the user's coaching relationships whether the
+    /// user is online.
+    async fn announce_presence(&self, user_id: &UserId) {
+        let Some(relationship_members) = &self.relationship_members else {
+            return;
+        };
+        let partners = match relationship_members.partners(user_id).await {
+            Ok(partners) => partners,
             Err(e) => {
-                error!("Failed to serialize SSE event: {e}");
+                error!("Failed to resolve the coaching relationships of user {user_id}: {e}");
                 return;
             }
         };
 
-        let event = Event::default().event(event_type).data(event_data);
+        // Checked after the lookup rather than taken from the change that prompted it, so
+        // a disconnect and reconnect announced out of order still end on the right state
+        let online = self.registry.is_connected(user_id);
+        for partner in partners {
+            let coaching_relationship_id = partner.coaching_relationship_id;
+            let user_id = user_id.clone();
+            let event = if online {
+                SseEvent::UserOnline {
+                    coaching_relationship_id,
+                    user_id,
+                }
+            } else {
+                SseEvent::UserOffline {
+                    coaching_relationship_id,
+                    user_id,
+                }
+            };
+            self.send_message(SseMessage {
+                event,
+                scope: MessageScope::User {
+                    user_id: partner.user_id,
+                },
+                correlation_id: None,
+            })
+            .await;
+        }
+    }
 
-        match message.scope {
-            MessageScope::User { user_id } => {
-                self.registry.send_to_user(&user_id, event);
+    /// Send a message based on its scope. The message is only serialized if one of
+    /// its recipients has a connection subscribed to it.
+    pub async fn send_message(&self, message: SseMessage) {
+        let event_type = message.event.event_type();
+
+        let recipients: Arc<[UserId]> = match &message.scope {
+            MessageScope::User { user_id } => Arc::new([user_id.clone()]),
+            MessageScope::Relationship {
+                coaching_relationship_id,
+            } => {
+                let Some(relationship_members) = &self.relationship_members else {
+                    warn!("No relationship member resolver; dropping {event_type} event");
+                    return;
+                };
+                match relationship_members.members(coaching_relationship_id).await {
+                    Ok(members) => members,
+                    Err(e) => {
+                        error!(
+                            "Failed to resolve the members of coaching relationship {coaching_relationship_id}: {e}"
+                        );
+                        return;
+                    }
+                }
             }
             MessageScope::Broadcast => {
-                self.registry.broadcast(event);
+                if let Some(event_data) = serialize(&message) {
+                    self.registry.broadcast(event_type, event_data);
+                }
+                return;
             }
+        };
+
+        let recipients: Vec<&UserId> = recipients
+            .iter()
+            .filter(|user_id| self.registry.wants(user_id, event_type))
+            .collect();
+        if recipients.is_empty() {
+            return;
+        }
+        let Some(event_data) = serialize(&message) else {
+            return;
+        };
+        for user_id in recipients {
+            if message.event.is_ephemeral() {
+                self.registry
+                    .send_ephemeral_to_user(user_id, event_type, Arc::clone(&event_data));
+            } else {
+                self.registry
+                    .send_to_user(user_id, event_type, Arc::clone(&event_data));
+            }
+        }
+    }
+}
+
+fn serialize(message: &SseMessage) -> Option<Arc<str>> {
+    match message.to_json() {
+        Ok(json) => Some(json.into()),
+        Err(e) => {
+            error!("Failed to serialize SSE event: {e}");
+            None
         }
     }
 }
@@ -62,3 +297,203 @@ impl Default for Manager {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::relationship::Partner;
+    use async_trait::async_trait;
+
+    /// Every relationship's members are `coach` and `coachee`, who share `rel-1`.
+    struct Members;
+
+    #[async_trait]
+    impl RelationshipMembers for Members {
+        async fn members(&self, _: &str) -> Result<Vec<UserId>, String> {
+            Ok(vec!["coach".to_string(), "coachee".to_string()])
+        }
+
+        async fn partners(&self, user_id: &str) -> Result<Vec<Partner>, String> {
+            let partner = match user_id {
+                "coach" => "coachee",
+                "coachee" => "coach",
+                _ => return Ok(Vec::new()),
+            };
+            Ok(vec![Partner {
+                coaching_relationship_id: "rel-1".to_string(),
+                user_id: partner.to_string(),
+            }])
+        }
+    }
+
+    async fn connect(manager: &Manager, user_id: &str) -> EventReceiver {
+        let (sender, receiver) = manager.channel();
+        manager
+            .register_connection(user_id.to_string(), sender, None, EventFilter::default())
+            .await;
+        receiver
+    }
+
+    fn message() -> SseMessage {
+        SseMessage {
+            event: SseEvent::GoalDeleted {
+                coaching_relationship_id: "rel-1".to_string(),
+                goal_id: "goal-1".to_string(),
+            },
+            scope: MessageScope::Relationship {
+                coaching_relationship_id: "rel-1".to_string(),
+            },
+            correlation_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn relationship_messages_reach_only_its_members() {
+        let manager = Manager::new().with_relationship_members(Arc::new(Members));
+        let mut receivers = Vec::new();
+        for user_id in ["coach", "coachee", "someone-else"] {
+            receivers.push(connect(&manager, user_id).await);
+        }
+        for receiver in &mut receivers {
+            while receiver.try_recv().is_ok() {}
+        }
+
+        manager.send_message(message()).await;
+
+        assert!(receivers[0].try_recv().is_ok());
+        assert!(receivers[1].try_recv().is_ok());
+        assert!(receivers[2].try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn connections_are_only_sent_the_events_they_subscribed_to() {
+        let manager = Manager::new().with_relationship_members(Arc::new(Members));
+        let (sender, mut goals) = manager.channel();
+        manager
+            .register_connection("coach".to_string(), sender, None, "goal.*".parse().unwrap())
+            .await;
+        let (sender, mut actions) = manager.channel();
+        manager
+            .register_connection(
+                "coachee".to_string(),
+                sender,
+                None,
+                "action.*".parse().unwrap(),
+            )
+            .await;
+        while actions.try_recv().is_ok() {}
+
+        manager.send_message(message()).await;
+
+        assert_eq!(goals.try_recv().unwrap().event_type, "goal_deleted");
+        assert!(actions.try_recv().is_err());
+        assert!(!manager
+            .registry
+            .wants(&"coachee".to_string(), "goal_deleted"));
+    }
+
+    #[tokio::test]
+    async fn poll_returns_buffered_events_right_away() {
+        let manager = Manager::new().with_relationship_members(Arc::new(Members));
+        let since = manager
+            .poll("coach".to_string(), None, Duration::ZERO)
+            .await
+            .last_event_id;
+        manager.send_message(message()).await;
+
+        let poll = manager
+            .poll("coach".to_string(), Some(since), Duration::from_secs(30))
+            .await;
+
+        assert_eq!(poll.frames.len(), 1);
+        assert_eq!(poll.frames[0].event_type, "goal_deleted");
+        assert_eq!(poll.last_event_id, poll.frames[0].id);
+        assert!(!manager.is_connected(&"coach".to_string()));
+    }
+
+    #[tokio::test]
+    async fn poll_waits_for_the_next_event() {
+        let manager = Arc::new(Manager::new().with_relationship_members(Arc::new(Members)));
+        let sender = Arc::clone(&manager);
+        tokio::spawn(async move {
+            while !sender.is_connected(&"coachee".to_string()) {
+                tokio::task::yield_now().await;
+            }
+            sender.send_message(message()).await;
+        });
+
+        let poll = manager
+            .poll("coachee".to_string(), None, Duration::from_secs(30))
+            .await;
+
+        assert_eq!(poll.frames.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn poll_times_out_empty() {
+        let manager = Manager::new();
+
+        let poll = manager
+            .poll("coach".to_string(), Some(42), Duration::from_millis(10))
+            .await;
+
+        assert!(poll.frames.is_empty());
+        assert_eq!(poll.last_event_id, 42);
+    }
+
+    #[tokio::test]
+    async fn relationship_messages_are_dropped_without_a_resolver() {
+        let manager = Manager::new();
+        let mut receiver = connect(&manager, "coach").await;
+
+        manager.send_message(message()).await;
+
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn partners_are_told_when_a_user_comes_online_and_goes_offline() {
+        let manager = Manager::new().with_relationship_members(Arc::new(Members));
+        let mut coach = connect(&manager, "coach").await;
+
+        let (sender, _coachee) = manager.channel();
+        let first = manager
+            .register_connection("coachee".to_string(), sender, None, EventFilter::default())
+            .await;
+        let (sender, _coachee_tab) = manager.channel();
+        let second = manager
+            .register_connection("coachee".to_string(), sender, None, EventFilter::default())
+            .await;
+
+        let online = coach.try_recv().unwrap();
+        assert_eq!(online.event_type, "user_online");
+        assert!(online.data.contains(r#""user_id":"coachee""#));
+        assert!(
+            coach.try_recv().is_err(),
+            "only the first connection announces"
+        );
+
+        manager.unregister_connection(&first).await;
+        assert!(coach.try_recv().is_err(), "still connected in another tab");
+        manager.unregister_connection(&second).await;
+        assert_eq!(coach.try_recv().unwrap().event_type, "user_offline");
+    }
+
+    #[tokio::test]
+    async fn drain_tells_connections_to_reconnect_then_closes_them() {
+        let manager = Manager::new().with_relationship_members(Arc::new(Members));
+        let mut coach = connect(&manager, "coach").await;
+
+        manager.drain(Duration::from_secs(5)).await;
+
+        let restarting = coach.recv().await.unwrap();
+        assert_eq!(restarting.event_type, "server_restarting");
+        assert!(restarting.data.contains(r#""reconnect_after_ms":5000"#));
+        assert!(coach.recv().await.is_none());
+        assert_eq!(manager.draining(), Some(Duration::from_secs(5)));
+
+        // A connection registered while draining ends at once
+        let mut coachee = connect(&manager, "coachee").await;
+        assert!(coachee.recv().await.is_none());
+    }
+}
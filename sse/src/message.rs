@@ -43,6 +43,22 @@ pub enum Event {
         agreement_id: String,
     },
 
+    // Mentions (delivered only to the mentioned user)
+    #[serde(rename = "mention_created")]
+    MentionCreated {
+        coaching_session_id: String,
+        mention: Value,
+    },
+
+    // Reactions on notes and actions (session-scoped)
+    #[serde(rename = "reactions_changed")]
+    ReactionsChanged {
+        coaching_session_id: String,
+        resource_type: String,
+        resource_id: String,
+        reactions: Value,
+    },
+
     // Goals (relationship-scoped)
     #[serde(rename = "goal_created")]
     GoalCreated {
@@ -74,9 +90,96 @@ pub enum Event {
         goal_id: String,
     },
 
+    // Library assignments (relationship-scoped, sent to the other participant)
+    #[serde(rename = "library_assignment_created")]
+    LibraryAssignmentCreated {
+        coaching_relationship_id: String,
+        assignment: Value,
+    },
+    #[serde(rename = "library_assignment_completed")]
+    LibraryAssignmentCompleted {
+        coaching_relationship_id: String,
+        assignment: Value,
+    },
+
+    // Session requests (relationship-scoped, sent to the other participant)
+    #[serde(rename = "session_request_created")]
+    SessionRequestCreated {
+        coaching_relationship_id: String,
+        session_request: Value,
+    },
+    #[serde(rename = "session_request_resolved")]
+    SessionRequestResolved {
+        coaching_relationship_id: String,
+        session_request: Value,
+    },
+
+    // Post-session pulse prompts (sent to both participants once a session ends)
+    #[serde(rename = "session_pulse_requested")]
+    SessionPulseRequested {
+        coaching_relationship_id: String,
+        coaching_session_id: String,
+    },
+
+    // Relationship health alerts (sent to the organization's admins)
+    #[serde(rename = "relationship_health_at_risk")]
+    RelationshipHealthAtRisk {
+        coaching_relationship_id: String,
+        score: i16,
+        threshold: i16,
+    },
+
+    // Presence events (sent to the other member of each of the user's relationships)
+    #[serde(rename = "user_online")]
+    UserOnline {
+        coaching_relationship_id: String,
+        user_id: String,
+    },
+    #[serde(rename = "user_offline")]
+    UserOffline {
+        coaching_relationship_id: String,
+        user_id: String,
+    },
+
+    // Typing indicators (sent to the other participant of the session; never replayed)
+    #[serde(rename = "note_editing_started")]
+    NoteEditingStarted {
+        coaching_session_id: String,
+        user_id: String,
+    },
+    #[serde(rename = "note_editing_stopped")]
+    NoteEditingStopped {
+        coaching_session_id: String,
+        user_id: String,
+    },
+
     // System events
     #[serde(rename = "force_logout")]
     ForceLogout { reason: String },
+    /// The connection's login session expires soon unless it's used again, e.g. by
+    /// `POST /session/refresh`.
+    #[serde(rename = "session_expiring_soon")]
+    SessionExpiringSoon { expires_at: String },
+    /// What the client knows about its user is out of date, e.g. their roles changed in
+    /// `organization_id`; the client refetches the user and their permissions. The
+    /// session itself stays logged in.
+    #[serde(rename = "session_invalidated")]
+    SessionInvalidated {
+        reason: String,
+        organization_id: Option<String>,
+    },
+    /// The connection fell behind and `dropped` events never reached it; the client
+    /// refetches what it shows.
+    #[serde(rename = "resync")]
+    Resync { dropped: u64 },
+    /// The replica is shutting down and about to close the connection; the client
+    /// reconnects after `reconnect_after_ms`, by when another replica has taken over.
+    #[serde(rename = "server_restarting")]
+    ServerRestarting { reconnect_after_ms: u64 },
+    /// The user opened more than `max_connections` connections and this, their oldest,
+    /// is about to be closed; the client doesn't reconnect.
+    #[serde(rename = "superseded")]
+    Superseded { max_connections: usize },
 
     // Meeting recording events (session-scoped)
     #[serde(rename = "meeting_recording_updated")]
@@ -93,6 +196,27 @@ pub enum Event {
     // Transcription events (session-scoped)
     #[serde(rename = "transcription_updated")]
     TranscriptionUpdated { coaching_session_id: String },
+
+    // Recording and transcription pipeline progress (session-scoped, typed: no refetch needed)
+    #[serde(rename = "recording_status_changed")]
+    RecordingStatusChanged {
+        coaching_session_id: String,
+        meeting_recording_id: String,
+        status: String,
+        error_message: Option<String>,
+    },
+    #[serde(rename = "transcription_completed")]
+    TranscriptionCompleted {
+        coaching_session_id: String,
+        transcription: Value,
+        segment_count: usize,
+    },
+    #[serde(rename = "ai_suggestions_ready")]
+    AiSuggestionsReady {
+        coaching_session_id: String,
+        transcription_id: String,
+        chapter_count: usize,
+    },
 }
 
 impl EventType for Event {
@@ -104,30 +228,94 @@ impl EventType for Event {
             Event::AgreementCreated { .. } => "agreement_created",
             Event::AgreementUpdated { .. } => "agreement_updated",
             Event::AgreementDeleted { .. } => "agreement_deleted",
+            Event::MentionCreated { .. } => "mention_created",
+            Event::ReactionsChanged { .. } => "reactions_changed",
             Event::GoalCreated { .. } => "goal_created",
             Event::GoalUpdated { .. } => "goal_updated",
             Event::GoalDeleted { .. } => "goal_deleted",
             Event::CoachingSessionGoalCreated { .. } => "coaching_session_goal_created",
             Event::CoachingSessionGoalDeleted { .. } => "coaching_session_goal_deleted",
+            Event::LibraryAssignmentCreated { .. } => "library_assignment_created",
+            Event::LibraryAssignmentCompleted { .. } => "library_assignment_completed",
+            Event::SessionRequestCreated { .. } => "session_request_created",
+            Event::SessionRequestResolved { .. } => "session_request_resolved",
+            Event::SessionPulseRequested { .. } => "session_pulse_requested",
+            Event::RelationshipHealthAtRisk { .. } => "relationship_health_at_risk",
+            Event::UserOnline { .. } => "user_online",
+            Event::UserOffline { .. } => "user_offline",
+            Event::NoteEditingStarted { .. } => "note_editing_started",
+            Event::NoteEditingStopped { .. } => "note_editing_stopped",
             Event::ForceLogout { .. } => "force_logout",
+            Event::SessionExpiringSoon { .. } => "session_expiring_soon",
+            Event::SessionInvalidated { .. } => "session_invalidated",
+            Event::Resync { .. } => "resync",
+            Event::ServerRestarting { .. } => "server_restarting",
+            Event::Superseded { .. } => "superseded",
             Event::MeetingRecordingUpdated { .. } => "meeting_recording_updated",
             Event::TopicsChanged { .. } => "topics_changed",
             Event::CoachingSessionTitleUpdated { .. } => "coaching_session_title_updated",
             Event::TranscriptionUpdated { .. } => "transcription_updated",
+            Event::RecordingStatusChanged { .. } => "recording_status_changed",
+            Event::TranscriptionCompleted { .. } => "transcription_completed",
+            Event::AiSuggestionsReady { .. } => "ai_suggestions_ready",
         }
     }
 }
 
+impl Event {
+    /// Schema version of the event's `data`, so clients can tell a shape they don't read.
+    /// An event forwarded from a domain event shares its kind's version (see
+    /// [`events::EventKind::version`]).
+    pub fn version(&self) -> u32 {
+        events::EventKind::from_name(self.event_type()).map_or(1, events::EventKind::version)
+    }
+
+    /// Whether the event only matters as it happens, so it's not kept for replay: a
+    /// client that reconnects has no use for a stale typing indicator.
+    pub fn is_ephemeral(&self) -> bool {
+        matches!(
+            self,
+            Event::NoteEditingStarted { .. } | Event::NoteEditingStopped { .. }
+        )
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Message {
     pub event: Event,
     pub scope: MessageScope,
+    /// The request that caused the event (its `X-Request-ID`), when known, so the
+    /// frontend can tie a notification to the user action behind it.
+    pub correlation_id: Option<String>,
+}
+
+impl Message {
+    /// The JSON sent as the SSE message's data: the event's `type` and `data`, with its
+    /// `version` and, when known, `correlation_id`.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        #[derive(Serialize)]
+        struct Versioned<'a> {
+            version: u32,
+            #[serde(flatten)]
+            event: &'a Event,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            correlation_id: Option<&'a str>,
+        }
+
+        serde_json::to_string(&Versioned {
+            version: self.event.version(),
+            event: &self.event,
+            correlation_id: self.correlation_id.as_deref(),
+        })
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum MessageScope {
     /// Send to all connections for a specific user
     User { user_id: String },
+    /// Send to all connections of a coaching relationship's coach and coachee
+    Relationship { coaching_relationship_id: String },
     /// Send to all connected users
     Broadcast,
 }
@@ -196,6 +384,59 @@ mod tests {
         assert_eq!(deleted.event_type(), "action_deleted");
     }
 
+    // Pins the reaction event wire shape (full aggregate for the target, session-scoped).
+    #[test]
+    fn reactions_changed_serializes_to_expected_wire_shape() {
+        let event = Event::ReactionsChanged {
+            coaching_session_id: "sess-1".to_string(),
+            resource_type: "note".to_string(),
+            resource_id: "note-1".to_string(),
+            reactions: serde_json::json!([{ "emoji": "👍", "count": 1, "user_ids": ["u-1"] }]),
+        };
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            serde_json::json!({
+                "type": "reactions_changed",
+                "data": {
+                    "coaching_session_id": "sess-1",
+                    "resource_type": "note",
+                    "resource_id": "note-1",
+                    "reactions": [{ "emoji": "👍", "count": 1, "user_ids": ["u-1"] }]
+                }
+            })
+        );
+        assert_eq!(event.event_type(), "reactions_changed");
+    }
+
+    #[test]
+    fn to_json_adds_the_version_and_correlation_id_to_the_wire_shape() {
+        let mut message = Message {
+            event: Event::TopicsChanged {
+                coaching_session_id: "sess-1".to_string(),
+            },
+            scope: MessageScope::Broadcast,
+            correlation_id: None,
+        };
+        let json: serde_json::Value = serde_json::from_str(&message.to_json().unwrap()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "type": "topics_changed",
+                "version": events::EventKind::TopicsChanged.version(),
+                "data": { "coaching_session_id": "sess-1" }
+            })
+        );
+
+        message.correlation_id = Some("req-1".to_string());
+        let json: serde_json::Value = serde_json::from_str(&message.to_json().unwrap()).unwrap();
+        assert_eq!(json["correlation_id"], "req-1");
+
+        let logout = Event::ForceLogout {
+            reason: "password changed".to_string(),
+        };
+        assert_eq!(logout.version(), 1);
+    }
+
     // Pins the coarse session-title event wire shape consumers depend on.
     #[test]
     fn coaching_session_title_updated_serializes_to_expected_wire_shape() {
@@ -212,4 +453,66 @@ mod tests {
         );
         assert_eq!(event.event_type(), "coaching_session_title_updated");
     }
+
+    #[test]
+    fn note_editing_events_serialize_to_expected_wire_shape_and_are_ephemeral() {
+        let event = Event::NoteEditingStarted {
+            coaching_session_id: "sess-1".to_string(),
+            user_id: "user-1".to_string(),
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "type": "note_editing_started",
+                "data": { "coaching_session_id": "sess-1", "user_id": "user-1" }
+            })
+        );
+        assert!(event.is_ephemeral());
+        assert!(!Event::TopicsChanged {
+            coaching_session_id: "sess-1".to_string(),
+        }
+        .is_ephemeral());
+    }
+
+    // Pins the pipeline progress wire shapes the frontend renders without refetching.
+    #[test]
+    fn pipeline_progress_events_serialize_to_expected_wire_shape() {
+        let event = Event::RecordingStatusChanged {
+            coaching_session_id: "sess-1".to_string(),
+            meeting_recording_id: "rec-1".to_string(),
+            status: "failed".to_string(),
+            error_message: Some("bot_kicked_from_call".to_string()),
+        };
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            serde_json::json!({
+                "type": "recording_status_changed",
+                "data": {
+                    "coaching_session_id": "sess-1",
+                    "meeting_recording_id": "rec-1",
+                    "status": "failed",
+                    "error_message": "bot_kicked_from_call"
+                }
+            })
+        );
+
+        let event = Event::AiSuggestionsReady {
+            coaching_session_id: "sess-1".to_string(),
+            transcription_id: "tr-1".to_string(),
+            chapter_count: 3,
+        };
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            serde_json::json!({
+                "type": "ai_suggestions_ready",
+                "data": {
+                    "coaching_session_id": "sess-1",
+                    "transcription_id": "tr-1",
+                    "chapter_count": 3
+                }
+            })
+        );
+        assert_eq!(event.event_type(), "ai_suggestions_ready");
+    }
 }
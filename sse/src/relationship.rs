@@ -0,0 +1,158 @@
+use crate::connection::UserId;
+use async_trait::async_trait;
+use dashmap::DashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How long a relationship's resolved members are reused before being looked up again.
+/// A relationship's coach and coachee don't change, so this only bounds how long a
+/// deleted relationship keeps receiving events.
+const MEMBERS_TTL: Duration = Duration::from_secs(300);
+
+/// Expired entries are dropped once every this many lookups.
+const SWEEP_INTERVAL: u64 = 256;
+
+/// Another member of one of a user's coaching relationships
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Partner {
+    pub coaching_relationship_id: String,
+    pub user_id: UserId,
+}
+
+/// Looks up the users a coaching relationship's events go to (its coach and coachee).
+/// Implemented outside this crate, which has no database access of its own.
+#[async_trait]
+pub trait RelationshipMembers: Send + Sync {
+    /// The relationship's members; empty if it doesn't exist.
+    async fn members(&self, coaching_relationship_id: &str) -> Result<Vec<UserId>, String>;
+
+    /// The other member of each of the user's relationships, who are told when the
+    /// user comes online or goes offline.
+    async fn partners(&self, user_id: &str) -> Result<Vec<Partner>, String>;
+}
+
+/// [`RelationshipMembers`] with each relationship's members cached for [`MEMBERS_TTL`].
+pub(crate) struct CachedRelationshipMembers {
+    resolver: Arc<dyn RelationshipMembers>,
+    cache: DashMap<String, (Instant, Arc<[UserId]>)>,
+    ttl: Duration,
+    lookups: AtomicU64,
+}
+
+impl CachedRelationshipMembers {
+    pub(crate) fn new(resolver: Arc<dyn RelationshipMembers>) -> Self {
+        Self {
+            resolver,
+            cache: DashMap::new(),
+            ttl: MEMBERS_TTL,
+            lookups: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) async fn members(
+        &self,
+        coaching_relationship_id: &str,
+    ) -> Result<Arc<[UserId]>, String> {
+        if self.lookups.fetch_add(1, Ordering::Relaxed) % SWEEP_INTERVAL == SWEEP_INTERVAL - 1 {
+            self.cache
+                .retain(|_, (resolved_at, _)| resolved_at.elapsed() < self.ttl);
+        }
+
+        if let Some(entry) = self.cache.get(coaching_relationship_id) {
+            let (resolved_at, members) = entry.value();
+            if resolved_at.elapsed() < self.ttl {
+                return Ok(Arc::clone(members));
+            }
+        }
+
+        let members: Arc<[UserId]> = self
+            .resolver
+            .members(coaching_relationship_id)
+            .await?
+            .into();
+        self.cache.insert(
+            coaching_relationship_id.to_string(),
+            (Instant::now(), Arc::clone(&members)),
+        );
+        Ok(members)
+    }
+
+    /// The user's partners, looked up every time: they're only needed when the user
+    /// connects or disconnects.
+    pub(crate) async fn partners(&self, user_id: &str) -> Result<Vec<Partner>, String> {
+        self.resolver.partners(user_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    /// Resolves every relationship to a coach and coachee, counting its lookups.
+    #[derive(Default)]
+    struct Counting(AtomicUsize);
+
+    #[async_trait]
+    impl RelationshipMembers for Counting {
+        async fn members(&self, coaching_relationship_id: &str) -> Result<Vec<UserId>, String> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(vec![
+                format!("{coaching_relationship_id}-coach"),
+                format!("{coaching_relationship_id}-coachee"),
+            ])
+        }
+
+        async fn partners(&self, _: &str) -> Result<Vec<Partner>, String> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn members_are_resolved_once_per_relationship() {
+        let resolver = Arc::new(Counting::default());
+        let cache = CachedRelationshipMembers::new(resolver.clone());
+
+        let first = cache.members("rel-1").await.unwrap();
+        let again = cache.members("rel-1").await.unwrap();
+        cache.members("rel-2").await.unwrap();
+
+        assert_eq!(&*first, ["rel-1-coach", "rel-1-coachee"]);
+        assert_eq!(first, again);
+        assert_eq!(resolver.0.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn expired_members_are_resolved_again() {
+        let resolver = Arc::new(Counting::default());
+        let mut cache = CachedRelationshipMembers::new(resolver.clone());
+        cache.ttl = Duration::ZERO;
+
+        cache.members("rel-1").await.unwrap();
+        cache.members("rel-1").await.unwrap();
+
+        assert_eq!(resolver.0.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn failed_lookups_are_not_cached() {
+        struct Failing;
+
+        #[async_trait]
+        impl RelationshipMembers for Failing {
+            async fn members(&self, _: &str) -> Result<Vec<UserId>, String> {
+                Err("database unavailable".to_string())
+            }
+
+            async fn partners(&self, _: &str) -> Result<Vec<Partner>, String> {
+                Err("database unavailable".to_string())
+            }
+        }
+
+        let cache = CachedRelationshipMembers::new(Arc::new(Failing));
+
+        assert!(cache.members("rel-1").await.is_err());
+        assert!(cache.cache.is_empty());
+    }
+}
@@ -0,0 +1,55 @@
+use anyhow::Result;
+use clap::Parser;
+use colored::*;
+
+use testing_tools::bus_consumer;
+
+#[derive(Parser)]
+#[command(name = "bus-consumer")]
+#[command(about = "Print the domain events the backend publishes to Kafka or NATS")]
+struct Cli {
+    /// The bus, as the backend's EVENT_BUS_URL: kafka://host:port or nats://host:port
+    #[arg(long, env = "EVENT_BUS_URL")]
+    url: String,
+
+    /// Kafka topic, or prefix of the NATS subjects, as the backend's EVENT_BUS_TOPIC
+    #[arg(
+        long,
+        env = "EVENT_BUS_TOPIC",
+        default_value = "refactor_platform.events"
+    )]
+    topic: String,
+
+    /// Read a Kafka topic from its earliest retained event instead of the next one
+    #[arg(long)]
+    from_beginning: bool,
+
+    /// Print each event's payload
+    #[arg(long)]
+    verbose: bool,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    let cli = Cli::parse();
+
+    println!("{}", "=== EVENT BUS CONSUMER ===".bright_white().bold());
+    bus_consumer::consume(&cli.url, &cli.topic, cli.from_beginning, |received| {
+        let envelope = &received.envelope;
+        println!(
+            "{} {} v{} relationship={} at {}",
+            received.source.dimmed(),
+            envelope.event_type.yellow(),
+            envelope.version,
+            received.key.as_deref().unwrap_or("-"),
+            envelope.occurred_at
+        );
+        if cli.verbose {
+            if let Ok(pretty) = serde_json::to_string_pretty(&envelope.payload) {
+                println!("   {}", pretty.dimmed());
+            }
+        }
+    })
+    .await
+}
@@ -0,0 +1,107 @@
+use anyhow::{Context, Result};
+use clap::Parser;
+use colored::*;
+
+use service::config::Config;
+use testing_tools::load_data::{self, GeneratorConfig, GENERATED_USER_PASSWORD};
+
+#[derive(Parser)]
+#[command(name = "load-data-generator")]
+#[command(about = "Bulk-insert synthetic coaching data for load testing and benchmarking")]
+struct Cli {
+    /// Postgres URL of the database to populate (schema must already be migrated)
+    #[arg(long, env = "DATABASE_URL")]
+    database_url: String,
+
+    /// Number of organizations to create
+    #[arg(long, default_value_t = 2)]
+    organizations: usize,
+
+    /// Coaches per organization (relationships are spread across them round-robin)
+    #[arg(long, default_value_t = 3)]
+    coaches_per_organization: usize,
+
+    /// Coaching relationships per organization (each gets its own coachee)
+    #[arg(long, default_value_t = 10)]
+    relationships: usize,
+
+    /// Coaching sessions per relationship
+    #[arg(long, default_value_t = 10)]
+    sessions: usize,
+
+    /// Actions per session
+    #[arg(long, default_value_t = 3)]
+    actions: usize,
+
+    /// Notes per session
+    #[arg(long, default_value_t = 1)]
+    notes: usize,
+
+    /// Goals per relationship
+    #[arg(long, default_value_t = 2)]
+    goals: usize,
+
+    /// Fake transcript segments per session (0 = no recordings/transcriptions)
+    #[arg(long, default_value_t = 0)]
+    transcript_segments: usize,
+
+    /// Rows per multi-row INSERT
+    #[arg(long, default_value_t = 500)]
+    batch_size: usize,
+
+    /// Allow running against a production RUST_ENV
+    #[arg(long)]
+    allow_production: bool,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let config = Config::default().set_database_url(cli.database_url.clone());
+    if config.is_production() && !cli.allow_production {
+        anyhow::bail!(
+            "RUST_ENV is production; refusing to generate load data without --allow-production"
+        );
+    }
+
+    println!("{}", "=== LOAD DATA GENERATOR ===".bright_white().bold());
+    let db = service::init_database(&config)
+        .await
+        .context("failed to connect to database")?;
+
+    let generator_config = GeneratorConfig {
+        organizations: cli.organizations,
+        coaches_per_organization: cli.coaches_per_organization,
+        relationships_per_organization: cli.relationships,
+        sessions_per_relationship: cli.sessions,
+        actions_per_session: cli.actions,
+        notes_per_session: cli.notes,
+        goals_per_relationship: cli.goals,
+        transcript_segments_per_session: cli.transcript_segments,
+        batch_size: cli.batch_size,
+    };
+    println!("{} Generating with {:?}", "→".blue(), generator_config);
+
+    let summary = load_data::generate(&db, &generator_config).await?;
+
+    println!("\n{}", "=== SUMMARY ===".bright_white().bold());
+    println!("Run tag:                {}", summary.run_tag.yellow());
+    println!("Organizations:          {}", summary.organizations);
+    println!("Users:                  {}", summary.users);
+    println!("Coaching relationships: {}", summary.coaching_relationships);
+    println!("Coaching sessions:      {}", summary.coaching_sessions);
+    println!("Goals:                  {}", summary.goals);
+    println!("Actions:                {}", summary.actions);
+    println!("Notes:                  {}", summary.notes);
+    println!("Transcript segments:    {}", summary.transcript_segments);
+    println!("Elapsed:                {:?}", summary.elapsed);
+    println!(
+        "\n{} Generated users log in with password {:?} (emails: load-{}-*@example.com)",
+        "✓".green(),
+        GENERATED_USER_PASSWORD,
+        summary.run_tag
+    );
+
+    Ok(())
+}
@@ -0,0 +1,187 @@
+//! Consumes the domain events the backend publishes to Kafka or NATS (see `events::bus`),
+//! as an example for systems integrating with the platform through the event bus.
+//!
+//! Each consumer is built with its feature, `kafka` or `nats`, like the backend's
+//! publishers: the NATS one subscribes to `{topic}.>` with `async-nats`, and the Kafka one
+//! joins a consumer group of its own with `rdkafka`, so it's handed every partition of the
+//! topic. Either hands on each event's [`EventEnvelope`], which `events::envelope` turns
+//! back into a `DomainEvent` when the consumer is built against the same release.
+
+use anyhow::{bail, Result};
+use events::EventEnvelope;
+
+/// An event read from the bus.
+#[derive(Debug, Clone)]
+pub struct Received {
+    /// Where it was read from: the NATS subject, or the Kafka partition and offset.
+    pub source: String,
+    /// The coaching relationship it's keyed by, if any.
+    pub key: Option<String>,
+    pub envelope: EventEnvelope,
+}
+
+/// Consumes events from the bus at `url` (as `EVENT_BUS_URL`) published to `topic` (as
+/// `EVENT_BUS_TOPIC`) until the connection fails. `from_beginning` reads a Kafka topic
+/// from its earliest retained event rather than the next one published.
+#[cfg_attr(
+    not(all(feature = "kafka", feature = "nats")),
+    allow(unused_variables, unused_mut)
+)]
+pub async fn consume(
+    url: &str,
+    topic: &str,
+    from_beginning: bool,
+    on_event: impl FnMut(Received),
+) -> Result<()> {
+    match url.split_once("://") {
+        #[cfg(feature = "nats")]
+        Some(("nats", server)) => nats::consume(server, topic, on_event).await,
+        #[cfg(feature = "kafka")]
+        Some(("kafka", brokers)) => kafka::consume(brokers, topic, from_beginning, on_event).await,
+        #[cfg(not(feature = "nats"))]
+        Some(("nats", _)) => bail!("Consuming from nats:// needs a build with the `nats` feature"),
+        #[cfg(not(feature = "kafka"))]
+        Some(("kafka", _)) => {
+            bail!("Consuming from kafka:// needs a build with the `kafka` feature")
+        }
+        _ => bail!("`{url}` is neither a kafka:// nor a nats:// URL"),
+    }
+}
+
+#[cfg(feature = "nats")]
+mod nats {
+    use anyhow::{bail, Context, Result};
+    use async_nats::ConnectOptions;
+    use events::envelope;
+    use futures_util::StreamExt;
+    use log::*;
+
+    use super::Received;
+
+    pub(super) async fn consume(
+        server: &str,
+        topic: &str,
+        mut on_event: impl FnMut(Received),
+    ) -> Result<()> {
+        let (credentials, host) = match server.rsplit_once('@') {
+            Some((credentials, host)) => (Some(credentials), host),
+            None => (None, server),
+        };
+        let host = host.trim_end_matches('/');
+        let address = if host.contains(':') {
+            host.to_string()
+        } else {
+            format!("{host}:4222")
+        };
+        let options =
+            match credentials.map(|credentials| (credentials, credentials.split_once(':'))) {
+                Some((_, Some((user, password)))) => {
+                    ConnectOptions::with_user_and_password(user.to_string(), password.to_string())
+                }
+                Some((token, None)) => ConnectOptions::with_token(token.to_string()),
+                None => ConnectOptions::new(),
+            };
+        let client = options
+            .name("bus-consumer")
+            .connect(&address)
+            .await
+            .with_context(|| format!("connecting to {address}"))?;
+        let mut subscriber = client.subscribe(format!("{topic}.>")).await?;
+        info!("Subscribed to {topic}.> at {address}");
+
+        while let Some(message) = subscriber.next().await {
+            let subject = message.subject.as_str();
+            match envelope::parse_slice(&message.payload) {
+                Ok(envelope) => on_event(Received {
+                    source: subject.to_string(),
+                    key: relationship(subject, topic),
+                    envelope,
+                }),
+                Err(e) => warn!("Skipping an unreadable event on {subject}: {e}"),
+            }
+        }
+        bail!("The NATS subscription ended")
+    }
+
+    /// The relationship in a `{topic}.{relationship or none}.{event_type}` subject.
+    pub(super) fn relationship(subject: &str, topic: &str) -> Option<String> {
+        subject
+            .strip_prefix(&format!("{topic}."))
+            .and_then(|rest| rest.split('.').next())
+            .filter(|relationship| *relationship != "none")
+            .map(str::to_string)
+    }
+}
+
+#[cfg(feature = "kafka")]
+mod kafka {
+    use anyhow::{Context, Result};
+    use events::envelope;
+    use log::*;
+    use rdkafka::config::ClientConfig;
+    use rdkafka::consumer::{Consumer, StreamConsumer};
+    use rdkafka::Message;
+    use uuid::Uuid;
+
+    use super::Received;
+
+    pub(super) async fn consume(
+        brokers: &str,
+        topic: &str,
+        from_beginning: bool,
+        mut on_event: impl FnMut(Received),
+    ) -> Result<()> {
+        let brokers = brokers.trim_end_matches('/');
+        // A group of its own, committing nothing, so every run starts over
+        let consumer: StreamConsumer = ClientConfig::new()
+            .set("bootstrap.servers", brokers)
+            .set("group.id", format!("bus-consumer-{}", Uuid::new_v4()))
+            .set("enable.auto.commit", "false")
+            .set(
+                "auto.offset.reset",
+                if from_beginning { "earliest" } else { "latest" },
+            )
+            .create()
+            .context("creating the Kafka consumer")?;
+        consumer
+            .subscribe(&[topic])
+            .with_context(|| format!("subscribing to {topic}"))?;
+        info!("Consuming {topic} from {brokers}");
+
+        loop {
+            let message = consumer.recv().await.context("reading from Kafka")?;
+            let source = format!("{}/{}@{}", topic, message.partition(), message.offset());
+            let key = message
+                .key()
+                .map(|key| String::from_utf8_lossy(key).into_owned());
+            match envelope::parse_slice(message.payload().unwrap_or_default()) {
+                Ok(envelope) => on_event(Received {
+                    source,
+                    key,
+                    envelope,
+                }),
+                Err(e) => warn!("Skipping an unreadable event at {source}: {e}"),
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "nats"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nats_subjects_name_the_relationship_unless_there_is_none() {
+        let topic = "refactor_platform.events";
+
+        assert_eq!(
+            nats::relationship("refactor_platform.events.42.goal_created", topic).as_deref(),
+            Some("42")
+        );
+        assert_eq!(
+            nats::relationship("refactor_platform.events.none.goal_created", topic),
+            None
+        );
+        assert_eq!(nats::relationship("other.42.goal_created", topic), None);
+    }
+}
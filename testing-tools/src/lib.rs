@@ -3,9 +3,13 @@
 // This crate provides various testing utilities and tools for the Refactor Platform.
 // Currently includes:
 // - sse-test-client: SSE integration testing tool
+// - load-data-generator: bulk synthetic data for load testing
+// - bus-consumer: example consumer of the events published to Kafka or NATS
 
 pub mod api_client;
 pub mod auth;
+pub mod bus_consumer;
+pub mod load_data;
 pub mod output;
 pub mod scenarios;
 pub mod sse_client;
@@ -0,0 +1,516 @@
+//! Synthetic data generator for load testing.
+//!
+//! Writes configurable volumes of coaching data (organizations × relationships ×
+//! sessions, plus actions, notes, goals, and fake transcript segments) straight
+//! into Postgres through `entity_api`, bypassing the HTTP layer. Rows are built
+//! in memory with pre-assigned ids and flushed with multi-row `INSERT`s so that
+//! seeding hundreds of thousands of rows takes seconds instead of hours.
+//!
+//! Every run stamps its rows with a short random tag (emails, slugs, org names)
+//! so the generator can be run repeatedly against the same database without
+//! tripping unique constraints.
+
+use anyhow::{Context, Result};
+use chrono::{Duration, FixedOffset, Utc};
+use entity::{meeting_recording, transcript_segment, transcription};
+use entity_api::{
+    actions, coaching_relationships, coaching_sessions, goals, notes, organizations, status,
+    user_roles, users, Id, Role,
+};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, DatabaseConnection, EntityTrait, IntoActiveModel,
+};
+use std::time::Instant;
+
+/// Postgres caps a single statement at 65 535 bind parameters; the widest table
+/// we insert into has ~13 columns, so anything above ~5 000 rows per batch fails.
+pub const MAX_BATCH_SIZE: usize = 4_000;
+
+/// The password every generated user can log in with.
+pub const GENERATED_USER_PASSWORD: &str = "password";
+
+/// Volumes to generate. Counts multiply: `organizations × relationships_per_organization
+/// × sessions_per_relationship × actions_per_session` actions are created in total.
+#[derive(Debug, Clone)]
+pub struct GeneratorConfig {
+    pub organizations: usize,
+    /// Coaches per organization; relationships are assigned to coaches round-robin.
+    pub coaches_per_organization: usize,
+    pub relationships_per_organization: usize,
+    pub sessions_per_relationship: usize,
+    pub actions_per_session: usize,
+    pub notes_per_session: usize,
+    pub goals_per_relationship: usize,
+    /// Fake transcript segments per session. `0` skips recordings and transcriptions entirely.
+    pub transcript_segments_per_session: usize,
+    /// Rows per multi-row `INSERT`. Clamped to `1..=MAX_BATCH_SIZE`.
+    pub batch_size: usize,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        Self {
+            organizations: 2,
+            coaches_per_organization: 3,
+            relationships_per_organization: 10,
+            sessions_per_relationship: 10,
+            actions_per_session: 3,
+            notes_per_session: 1,
+            goals_per_relationship: 2,
+            transcript_segments_per_session: 0,
+            batch_size: 500,
+        }
+    }
+}
+
+/// Row counts actually written, plus the unique tag stamped on this run's data.
+#[derive(Debug, Default, Clone)]
+pub struct GenerationSummary {
+    pub run_tag: String,
+    pub organizations: usize,
+    pub users: usize,
+    pub coaching_relationships: usize,
+    pub coaching_sessions: usize,
+    pub goals: usize,
+    pub actions: usize,
+    pub notes: usize,
+    pub transcript_segments: usize,
+    pub elapsed: std::time::Duration,
+}
+
+/// Buffers active models for one table and writes them in `batch_size` chunks via `insert_many`.
+struct Batcher<A: ActiveModelTrait> {
+    rows: Vec<A>,
+    batch_size: usize,
+    written: usize,
+}
+
+impl<A> Batcher<A>
+where
+    A: ActiveModelTrait + Send,
+    <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+{
+    fn new(batch_size: usize) -> Self {
+        Self {
+            rows: Vec::with_capacity(batch_size),
+            batch_size,
+            written: 0,
+        }
+    }
+
+    fn push(&mut self, row: A) {
+        self.rows.push(row);
+    }
+
+    fn is_full(&self) -> bool {
+        self.rows.len() >= self.batch_size
+    }
+
+    async fn flush(&mut self, db: &DatabaseConnection) -> Result<()> {
+        while !self.rows.is_empty() {
+            let take = self.rows.len().min(self.batch_size);
+            let chunk: Vec<A> = self.rows.drain(..take).collect();
+            A::Entity::insert_many(chunk)
+                .exec_without_returning(db)
+                .await
+                .context("batched insert failed")?;
+            self.written += take;
+        }
+        Ok(())
+    }
+}
+
+/// One batcher per generated table. Tables are always flushed together, parents
+/// before children, so every child row's foreign key already exists when written.
+struct Tables {
+    organizations: Batcher<organizations::ActiveModel>,
+    users: Batcher<users::ActiveModel>,
+    user_roles: Batcher<user_roles::ActiveModel>,
+    coaching_relationships: Batcher<coaching_relationships::ActiveModel>,
+    goals: Batcher<goals::ActiveModel>,
+    coaching_sessions: Batcher<coaching_sessions::ActiveModel>,
+    actions: Batcher<actions::ActiveModel>,
+    notes: Batcher<notes::ActiveModel>,
+    meeting_recordings: Batcher<meeting_recording::ActiveModel>,
+    transcriptions: Batcher<transcription::ActiveModel>,
+    transcript_segments: Batcher<transcript_segment::ActiveModel>,
+}
+
+impl Tables {
+    fn new(batch_size: usize) -> Self {
+        Self {
+            organizations: Batcher::new(batch_size),
+            users: Batcher::new(batch_size),
+            user_roles: Batcher::new(batch_size),
+            coaching_relationships: Batcher::new(batch_size),
+            goals: Batcher::new(batch_size),
+            coaching_sessions: Batcher::new(batch_size),
+            actions: Batcher::new(batch_size),
+            notes: Batcher::new(batch_size),
+            meeting_recordings: Batcher::new(batch_size),
+            transcriptions: Batcher::new(batch_size),
+            transcript_segments: Batcher::new(batch_size),
+        }
+    }
+
+    fn any_full(&self) -> bool {
+        self.organizations.is_full()
+            || self.users.is_full()
+            || self.user_roles.is_full()
+            || self.coaching_relationships.is_full()
+            || self.goals.is_full()
+            || self.coaching_sessions.is_full()
+            || self.actions.is_full()
+            || self.notes.is_full()
+            || self.meeting_recordings.is_full()
+            || self.transcriptions.is_full()
+            || self.transcript_segments.is_full()
+    }
+
+    async fn flush_all(&mut self, db: &DatabaseConnection) -> Result<()> {
+        self.organizations.flush(db).await?;
+        self.users.flush(db).await?;
+        self.user_roles.flush(db).await?;
+        self.coaching_relationships.flush(db).await?;
+        self.goals.flush(db).await?;
+        self.coaching_sessions.flush(db).await?;
+        self.actions.flush(db).await?;
+        self.notes.flush(db).await?;
+        self.meeting_recordings.flush(db).await?;
+        self.transcriptions.flush(db).await?;
+        self.transcript_segments.flush(db).await?;
+        Ok(())
+    }
+}
+
+/// Generates a full synthetic dataset according to `config`.
+///
+/// Rows are buffered per table and written once any buffer reaches `batch_size`;
+/// see [`Tables::flush_all`] for the ordering that keeps foreign keys valid.
+pub async fn generate(
+    db: &DatabaseConnection,
+    config: &GeneratorConfig,
+) -> Result<GenerationSummary> {
+    let started = Instant::now();
+    let batch_size = config.batch_size.clamp(1, MAX_BATCH_SIZE);
+    let run_tag = Id::new_v4().simple().to_string()[..8].to_string();
+    let now = Utc::now().fixed_offset();
+    // Hashing is deliberately slow; do it once and share the digest across all users.
+    let password_hash = entity_api::user::generate_hash(GENERATED_USER_PASSWORD.to_string());
+
+    let mut tables = Tables::new(batch_size);
+
+    for org_index in 0..config.organizations {
+        let org_id = Id::new_v4();
+        tables.organizations.push(organizations::ActiveModel {
+            id: Set(org_id),
+            name: Set(format!("Load Test Org {run_tag}-{org_index}")),
+            slug: Set(format!("load-test-org-{run_tag}-{org_index}")),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        });
+
+        let coach_ids: Vec<Id> = (0..config.coaches_per_organization.max(1))
+            .map(|_| Id::new_v4())
+            .collect();
+        for (coach_index, coach_id) in coach_ids.iter().enumerate() {
+            let label = format!("{run_tag}-o{org_index}-coach{coach_index}");
+            tables
+                .users
+                .push(user_row(*coach_id, &label, &password_hash, now));
+            tables.user_roles.push(role_row(*coach_id, org_id, now));
+        }
+
+        for rel_index in 0..config.relationships_per_organization {
+            let coach_id = coach_ids[rel_index % coach_ids.len()];
+            let coachee_id = Id::new_v4();
+            let label = format!("{run_tag}-o{org_index}-coachee{rel_index}");
+            tables
+                .users
+                .push(user_row(coachee_id, &label, &password_hash, now));
+            tables.user_roles.push(role_row(coachee_id, org_id, now));
+
+            let relationship_id = Id::new_v4();
+            tables
+                .coaching_relationships
+                .push(coaching_relationships::ActiveModel {
+                    id: Set(relationship_id),
+                    organization_id: Set(org_id),
+                    coach_id: Set(coach_id),
+                    coachee_id: Set(coachee_id),
+                    slug: Set(format!("load-{label}")),
+                    ai_privacy_level: Set(Default::default()),
+                    pro_bono: Set(false),
+                    created_at: Set(now),
+                    updated_at: Set(now),
+                });
+
+            let goal_ids: Vec<Id> = (0..config.goals_per_relationship)
+                .map(|_| Id::new_v4())
+                .collect();
+            for (goal_index, goal_id) in goal_ids.iter().enumerate() {
+                tables.goals.push(goals::ActiveModel {
+                    id: Set(*goal_id),
+                    coaching_relationship_id: Set(relationship_id),
+                    created_in_session_id: Set(None),
+                    user_id: Set(coach_id),
+                    title: Set(Some(format!("Goal {goal_index}"))),
+                    body: Set(Some(lorem(goal_index, 24))),
+                    status: Set(cycle_status(goal_index)),
+                    status_changed_at: Set(Some(now)),
+                    completed_at: Set(None),
+                    target_date: Set(None),
+                    created_at: Set(now),
+                    updated_at: Set(now),
+                    deleted_at: Set(None),
+                });
+            }
+
+            for session_index in 0..config.sessions_per_relationship {
+                let session_id = Id::new_v4();
+                // Spread sessions a week apart, centred on today, so date-range
+                // filters and "upcoming vs past" queries both have data to chew on.
+                let offset_weeks =
+                    session_index as i64 - (config.sessions_per_relationship as i64 / 2);
+                let date = (now + Duration::weeks(offset_weeks)).naive_utc();
+                tables
+                    .coaching_sessions
+                    .push(coaching_sessions::ActiveModel {
+                        id: Set(session_id),
+                        coaching_relationship_id: Set(relationship_id),
+                        date: Set(date),
+                        duration_minutes: Set(60),
+                        title: Set(Some(format!("Session {session_index}"))),
+                        created_at: Set(now),
+                        updated_at: Set(now),
+                        ..Default::default()
+                    });
+
+                for action_index in 0..config.actions_per_session {
+                    let goal_id =
+                        (!goal_ids.is_empty()).then(|| goal_ids[action_index % goal_ids.len()]);
+                    tables.actions.push(actions::ActiveModel {
+                        id: Set(Id::new_v4()),
+                        coaching_session_id: Set(session_id),
+                        goal_id: Set(goal_id),
+                        user_id: Set(coach_id),
+                        action_series_id: Set(None),
+                        previous_action_id: Set(None),
+                        body: Set(Some(lorem(action_index, 12))),
+                        effort_points: Set(None),
+                        due_by: Set(Some(now + Duration::days(7))),
+                        status: Set(cycle_status(action_index)),
+                        status_changed_at: Set(now),
+                        created_at: Set(now),
+                        updated_at: Set(now),
+                        deleted_at: Set(None),
+                    });
+                }
+
+                for note_index in 0..config.notes_per_session {
+                    tables.notes.push(notes::ActiveModel {
+                        id: Set(Id::new_v4()),
+                        coaching_session_id: Set(session_id),
+                        body: Set(Some(lorem(note_index, 80))),
+                        user_id: Set(coach_id),
+                        created_at: Set(now),
+                        updated_at: Set(now),
+                        deleted_at: Set(None),
+                    });
+                }
+
+                if config.transcript_segments_per_session > 0 {
+                    let recording_id = Id::new_v4();
+                    let transcription_id = Id::new_v4();
+                    tables
+                        .meeting_recordings
+                        .push(meeting_recording::ActiveModel {
+                            id: Set(recording_id),
+                            coaching_session_id: Set(session_id),
+                            bot_id: Set(format!("load-bot-{recording_id}")),
+                            status: Set(meeting_recording::MeetingRecordingStatus::Completed),
+                            created_at: Set(now),
+                            updated_at: Set(now),
+                            ..Default::default()
+                        });
+                    tables.transcriptions.push(transcription::ActiveModel {
+                        id: Set(transcription_id),
+                        coaching_session_id: Set(session_id),
+                        meeting_recording_id: Set(recording_id),
+                        external_id: Set(format!("load-transcript-{transcription_id}")),
+                        status: Set(transcription::TranscriptionStatus::Completed),
+                        speaker_count: Set(Some(2)),
+                        created_at: Set(now),
+                        updated_at: Set(now),
+                        ..Default::default()
+                    });
+                    for segment_index in 0..config.transcript_segments_per_session {
+                        tables.transcript_segments.push(segment_row(
+                            transcription_id,
+                            segment_index,
+                            now,
+                        ));
+                    }
+                }
+            }
+
+            // A relationship's rows are complete here, so flushing every table
+            // (parents first) can never strand a child without its parent.
+            if tables.any_full() {
+                tables.flush_all(db).await?;
+            }
+        }
+    }
+
+    tables.flush_all(db).await?;
+
+    Ok(GenerationSummary {
+        run_tag,
+        organizations: tables.organizations.written,
+        users: tables.users.written,
+        coaching_relationships: tables.coaching_relationships.written,
+        coaching_sessions: tables.coaching_sessions.written,
+        goals: tables.goals.written,
+        actions: tables.actions.written,
+        notes: tables.notes.written,
+        transcript_segments: tables.transcript_segments.written,
+        elapsed: started.elapsed(),
+    })
+}
+
+fn user_row(
+    id: Id,
+    label: &str,
+    password_hash: &str,
+    now: chrono::DateTime<FixedOffset>,
+) -> users::ActiveModel {
+    users::ActiveModel {
+        id: Set(id),
+        email: Set(format!("load-{label}@example.com")),
+        first_name: Set("Load".to_string()),
+        last_name: Set(label.to_string()),
+        display_name: Set(Some(format!("Load {label}"))),
+        password: Set(Some(password_hash.to_string())),
+        role: Set(Role::User),
+        created_at: Set(now),
+        updated_at: Set(now),
+        ..Default::default()
+    }
+}
+
+fn role_row(
+    user_id: Id,
+    organization_id: Id,
+    now: chrono::DateTime<FixedOffset>,
+) -> user_roles::ActiveModel {
+    user_roles::ActiveModel {
+        id: Set(Id::new_v4()),
+        role: Set(Role::User),
+        organization_id: Set(Some(organization_id)),
+        user_id: Set(user_id),
+        created_at: Set(now),
+        updated_at: Set(now),
+    }
+}
+
+fn segment_row(
+    transcription_id: Id,
+    index: usize,
+    now: chrono::DateTime<FixedOffset>,
+) -> transcript_segment::ActiveModel {
+    // Ten-second segments alternating between two speakers.
+    let start_ms = (index as i32).saturating_mul(10_000);
+    let speaker = if index.is_multiple_of(2) {
+        "Coach"
+    } else {
+        "Coachee"
+    };
+    transcript_segment::ActiveModel {
+        id: Set(Id::new_v4()),
+        transcription_id: Set(transcription_id),
+        speaker_label: Set(speaker.to_string()),
+        text: Set(lorem(index, 30)),
+        start_ms: Set(start_ms),
+        end_ms: Set(start_ms.saturating_add(9_500)),
+        confidence: Set(Some(0.92)),
+        sentiment: Set(Some("neutral".to_string())),
+        created_at: Set(now),
+    }
+}
+
+fn cycle_status(index: usize) -> status::Status {
+    match index % 4 {
+        0 => status::Status::NotStarted,
+        1 => status::Status::InProgress,
+        2 => status::Status::Completed,
+        _ => status::Status::OnHold,
+    }
+}
+
+const WORDS: &[&str] = &[
+    "feedback",
+    "delegate",
+    "career",
+    "growth",
+    "priorities",
+    "stakeholder",
+    "review",
+    "roadmap",
+    "mentor",
+    "conflict",
+    "clarity",
+    "ownership",
+    "promotion",
+    "energy",
+    "focus",
+    "boundaries",
+    "architecture",
+    "estimate",
+    "retro",
+    "onboarding",
+    "listening",
+    "curiosity",
+    "trust",
+    "scope",
+];
+
+/// Deterministic filler text of `len` words; `seed` varies the starting word so
+/// rows aren't byte-identical (which would make full-text search benchmarks lie).
+fn lorem(seed: usize, len: usize) -> String {
+    (0..len)
+        .map(|i| WORDS[(seed.wrapping_mul(7) + i * 3) % WORDS.len()])
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lorem_produces_requested_word_count() {
+        assert_eq!(lorem(3, 12).split(' ').count(), 12);
+    }
+
+    #[test]
+    fn lorem_varies_by_seed() {
+        assert_ne!(lorem(0, 5), lorem(1, 5));
+    }
+
+    #[test]
+    fn cycle_status_rotates_through_four_statuses() {
+        let statuses: Vec<_> = (0..4).map(cycle_status).collect();
+        assert_eq!(statuses[0], status::Status::NotStarted);
+        assert_eq!(statuses[3], status::Status::OnHold);
+        assert_eq!(cycle_status(4), status::Status::NotStarted);
+    }
+
+    #[test]
+    fn segment_rows_do_not_overlap() {
+        let now = Utc::now().fixed_offset();
+        let first = segment_row(Id::new_v4(), 0, now);
+        let second = segment_row(Id::new_v4(), 1, now);
+        assert!(first.end_ms.unwrap() < second.start_ms.unwrap());
+    }
+}
@@ -2,20 +2,26 @@ use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::Json;
-use domain::action::ActionWithAssignees;
-use domain::{action as ActionApi, actions::Model, emails as EmailsApi, users, Id};
+use domain::action::{ActionWithAssignees, BulkOperation, BulkOutcome};
+use domain::action_series::ActionRecurrence;
+use domain::{
+    action as ActionApi, actions::Model, emails as EmailsApi, mention as MentionApi,
+    resource_view as ResourceViewApi, status::Status, users, Id,
+};
 use log::*;
 use sea_orm::DatabaseConnection;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use service::config::ApiVersion;
 use utoipa::ToSchema;
 
 use crate::controller::ApiResponse;
 use crate::extractors::{
-    authenticated_user::AuthenticatedUser, compare_api_version::CompareApiVersion,
+    authenticated_user::AuthenticatedUser,
+    compare_api_version::CompareApiVersion,
+    if_match::{with_etag, IfMatch},
 };
-use crate::params::action::{IndexParams, SortField};
+use crate::params::action::{IncludeParam, IndexParams, SortField};
 use crate::params::WithSortDefaults;
 use crate::{AppState, Error};
 
@@ -28,6 +34,10 @@ pub struct ActionRequest {
     /// For updates, if provided, replaces all existing assignees.
     /// If omitted during update, assignees remain unchanged.
     pub assignee_ids: Option<Vec<Id>>,
+    /// Makes the action repeat, e.g. `{"frequency": "weekly", "until": "2026-12-31"}`.
+    /// Requires `due_by`. Only read on create; ignored on update.
+    #[schema(value_type = Option<Object>)]
+    pub recurrence: Option<ActionRecurrence>,
 }
 
 impl ActionRequest {
@@ -73,6 +83,7 @@ pub async fn create(
         request.action,
         user.id,
         request.assignee_ids,
+        request.recurrence,
     )
     .await?;
 
@@ -87,6 +98,15 @@ pub async fn create(
         .await;
     }
 
+    MentionApi::sync_and_notify(
+        app_state.db_conn_ref(),
+        &app_state.config,
+        app_state.event_publisher.as_ref(),
+        &user,
+        (&action.action).into(),
+    )
+    .await;
+
     Ok(Json(ApiResponse::new(StatusCode::CREATED.into(), action)))
 }
 
@@ -99,7 +119,8 @@ pub async fn create(
         ("id" = String, Path, description = "Action id to retrieve")
     ),
     responses(
-        (status = 200, description = "Successfully retrieved a specific Action by its id", body = [domain::action::ActionWithAssignees]),
+        (status = 200, description = "Successfully retrieved a specific Action by its id", body = [domain::action::ActionWithAssignees],
+            headers(("ETag" = String, description = "Version of the resource to send back in If-Match"))),
         (status = 401, description = "Unauthorized"),
         (status = 404, description = "Action not found"),
         (status = 405, description = "Method not allowed"),
@@ -118,7 +139,10 @@ pub async fn read(
 
     let action = ActionApi::find_by_id_with_assignees(app_state.db_conn_ref(), id).await?;
 
-    Ok(Json(ApiResponse::new(StatusCode::OK.into(), action)))
+    Ok(with_etag(
+        action.action.updated_at,
+        Json(ApiResponse::new(StatusCode::OK.into(), action)),
+    ))
 }
 
 /// Fetch the current assignee IDs for an action before an update.
@@ -169,8 +193,10 @@ async fn notify_added_assignees(
     ),
     request_body = ActionRequest,
     responses(
-        (status = 200, description = "Successfully Updated Action", body = [domain::action::ActionWithAssignees]),
+        (status = 200, description = "Successfully Updated Action", body = [domain::action::ActionWithAssignees],
+            headers(("ETag" = String, description = "Version of the resource to send back in If-Match"))),
         (status = 401, description = "Unauthorized"),
+        (status = 412, description = "Changed since the version named in If-Match"),
         (status = 405, description = "Method not allowed"),
         (status = 503, description = "Service temporarily unavailable")
     ),
@@ -184,6 +210,7 @@ pub async fn update(
     // TODO: create a new Extractor to authorize the user to access
     // the data requested
     State(app_state): State<AppState>,
+    if_match: IfMatch,
     Path(id): Path<Id>,
     Json(request): Json<ActionRequest>,
 ) -> Result<impl IntoResponse, Error> {
@@ -204,6 +231,7 @@ pub async fn update(
         id,
         request.action,
         request.assignee_ids,
+        if_match.expected(),
     )
     .await?;
 
@@ -214,7 +242,19 @@ pub async fn update(
         notify_added_assignees(&app_state, &action, &user, prev_ids).await;
     }
 
-    Ok(Json(ApiResponse::new(StatusCode::OK.into(), action)))
+    MentionApi::sync_and_notify(
+        app_state.db_conn_ref(),
+        &app_state.config,
+        app_state.event_publisher.as_ref(),
+        &user,
+        (&action.action).into(),
+    )
+    .await;
+
+    Ok(with_etag(
+        action.action.updated_at,
+        Json(ApiResponse::new(StatusCode::OK.into(), action)),
+    ))
 }
 
 #[utoipa::path(
@@ -227,8 +267,10 @@ pub async fn update(
     ),
     request_body = actions::Model,
     responses(
-        (status = 200, description = "Successfully Updated Action", body = [actions::Model]),
+        (status = 200, description = "Successfully Updated Action", body = [actions::Model],
+            headers(("ETag" = String, description = "Version of the resource to send back in If-Match"))),
         (status = 401, description = "Unauthorized"),
+        (status = 412, description = "Changed since the version named in If-Match"),
         (status = 405, description = "Method not allowed"),
         (status = 503, description = "Service temporarily unavailable")
     ),
@@ -242,6 +284,7 @@ pub async fn update_status(
     Query(status): Query<String>,
     Path(id): Path<Id>,
     State(app_state): State<AppState>,
+    if_match: IfMatch,
 ) -> Result<impl IntoResponse, Error> {
     debug!("PUT Update Action Status with id: {id}");
 
@@ -250,12 +293,16 @@ pub async fn update_status(
         app_state.event_publisher.as_ref(),
         id,
         status.as_str().into(),
+        if_match.expected(),
     )
     .await?;
 
     debug!("Updated Action: {action:?}");
 
-    Ok(Json(ApiResponse::new(StatusCode::OK.into(), action)))
+    Ok(with_etag(
+        action.updated_at,
+        Json(ApiResponse::new(StatusCode::OK.into(), action)),
+    ))
 }
 
 #[utoipa::path(
@@ -263,13 +310,17 @@ pub async fn update_status(
     path = "/actions",
     params(
         ApiVersion,
-        ("coaching_session_id" = Option<Id>, Query, description = "Filter by coaching_session_id"),
-        ("goal_id" = Option<Id>, Query, description = "Filter by goal_id"),
+        ("filter[coaching_session_id]" = Id, Query, description = "Filter by coaching_session_id (also accepted as coaching_session_id)"),
+        ("filter[goal_id]" = Option<Id>, Query, description = "Filter by goal_id (also accepted as goal_id)"),
+        ("filter[status]" = Option<String>, Query, description = "Filter by status: 'not_started', 'in_progress', 'completed', 'on_hold' or 'wont_do' (also accepted as status)", example = "in_progress"),
+        ("sort" = Option<String>, Query, description = "Sort by a field, descending when prefixed with '-'. Takes precedence over sort_by and sort_order.", example = "-due_by"),
         ("sort_by" = Option<crate::params::action::SortField>, Query, description = "Sort by field. Valid values: 'due_by', 'created_at', 'updated_at'. Must be provided with sort_order.", example = "due_by"),
-        ("sort_order" = Option<crate::params::sort::SortOrder>, Query, description = "Sort order. Valid values: 'asc' (ascending), 'desc' (descending). Must be provided with sort_by.", example = "desc")
+        ("sort_order" = Option<crate::params::sort::SortOrder>, Query, description = "Sort order. Valid values: 'asc' (ascending), 'desc' (descending). Must be provided with sort_by.", example = "desc"),
+        ("include" = Option<String>, Query, description = "Comma-separated related resources to load with each action: 'session', 'relationship', 'assignee'", example = "session,assignee")
     ),
     responses(
-        (status = 200, description = "Successfully retrieved all Actions", body = [domain::action::ActionWithAssignees]),
+        (status = 200, description = "Successfully retrieved all Actions with the caller's unread flag, reactions and any included resources", body = [domain::action::EnrichedAction]),
+        (status = 400, description = "Unknown or invalid filter, sort or include parameter"),
         (status = 401, description = "Unauthorized"),
         (status = 405, description = "Method not allowed"),
         (status = 503, description = "Service temporarily unavailable")
@@ -280,7 +331,7 @@ pub async fn update_status(
 )]
 pub async fn index(
     CompareApiVersion(_v): CompareApiVersion,
-    AuthenticatedUser(_user): AuthenticatedUser,
+    AuthenticatedUser(user): AuthenticatedUser,
     // TODO: create a new Extractor to authorize the user to access
     // the data requested
     State(app_state): State<AppState>,
@@ -291,16 +342,26 @@ pub async fn index(
 
     // Apply default sorting parameters
     let mut params = params;
-    IndexParams::apply_sort_defaults(
+    IndexParams::apply_sort(
+        params.sort.take(),
         &mut params.sort_by,
         &mut params.sort_order,
         SortField::DueBy,
     );
+    let includes = ActionApi::IncludeOptions {
+        session: params.include.contains(&IncludeParam::Session),
+        relationship: params.include.contains(&IncludeParam::Relationship),
+        assignee: params.include.contains(&IncludeParam::Assignee),
+    };
 
     let actions = ActionApi::find_by_with_assignees(app_state.db_conn_ref(), params).await?;
 
     debug!("Found Actions: {actions:?}");
 
+    let actions =
+        ResourceViewApi::annotate_actions(app_state.db_conn_ref(), user.id, actions).await?;
+    let actions = ActionApi::with_includes(app_state.db_conn_ref(), actions, includes).await?;
+
     Ok(Json(ApiResponse::new(StatusCode::OK.into(), actions)))
 }
 
@@ -338,3 +399,304 @@ pub async fn delete(
     .await?;
     Ok(Json(json!({"id": id})))
 }
+
+/// POST restore a deleted Action, until it's purged.
+#[utoipa::path(
+    post,
+    path = "/actions/{id}/restore",
+    params(
+        ApiVersion,
+        ("id" = Id, Path, description = "Id of the deleted Action to restore")
+    ),
+    responses(
+        (status = 200, description = "Successfully restored an Action", body = domain::action::ActionWithAssignees),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "No deleted Action with this id"),
+        (status = 405, description = "Method not allowed"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn restore(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("POST restore Action by id: {id}");
+
+    let action = ActionApi::restore(
+        app_state.db_conn_ref(),
+        app_state.event_publisher.as_ref(),
+        id,
+    )
+    .await?;
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), action)))
+}
+
+/// One operation of a bulk request, named by `op`.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum BulkOperationRequest {
+    /// Creates an action. Recurring actions can't be created in bulk.
+    Create {
+        action: Model,
+        assignee_ids: Option<Vec<Id>>,
+    },
+    /// Updates an action; `assignee_ids`, when given, replaces its assignees.
+    Update {
+        id: Id,
+        action: Model,
+        assignee_ids: Option<Vec<Id>>,
+    },
+    /// Changes only an action's status, e.g. to `Completed`.
+    UpdateStatus {
+        id: Id,
+        status: Status,
+    },
+    Delete {
+        id: Id,
+    },
+}
+
+impl From<BulkOperationRequest> for BulkOperation {
+    fn from(operation: BulkOperationRequest) -> Self {
+        match operation {
+            BulkOperationRequest::Create {
+                action,
+                assignee_ids,
+            } => Self::Create {
+                action,
+                assignee_ids,
+            },
+            BulkOperationRequest::Update {
+                id,
+                action,
+                assignee_ids,
+            } => Self::Update {
+                id,
+                action,
+                assignee_ids,
+            },
+            BulkOperationRequest::UpdateStatus { id, status } => Self::UpdateStatus { id, status },
+            BulkOperationRequest::Delete { id } => Self::Delete { id },
+        }
+    }
+}
+
+/// Request body for POST `/actions/bulk`.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BulkRequest {
+    /// Applied in order, all or none of them
+    pub operations: Vec<BulkOperationRequest>,
+}
+
+/// What one operation of a bulk request did.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkOperationResult {
+    /// Position of the operation in the request
+    pub index: usize,
+    /// The status code the operation would get on its own endpoint, or 424 when it
+    /// succeeded but was rolled back because another operation failed
+    pub status: u16,
+    /// The created or updated action
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<ActionWithAssignees>,
+    /// Id of the deleted action
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Id>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Response body for POST `/actions/bulk`.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkResponse {
+    /// Whether the operations took effect, which they do only if all of them succeeded
+    pub committed: bool,
+    pub results: Vec<BulkOperationResult>,
+}
+
+impl BulkResponse {
+    fn new(outcomes: Vec<Result<BulkOutcome, domain::error::Error>>) -> Self {
+        let committed = outcomes.iter().all(Result::is_ok);
+        let results = outcomes
+            .into_iter()
+            .enumerate()
+            .map(|(index, outcome)| {
+                let mut result = BulkOperationResult {
+                    index,
+                    status: StatusCode::OK.into(),
+                    action: None,
+                    id: None,
+                    error: None,
+                };
+                match outcome {
+                    Err(e) => {
+                        let status = Error::Domain(e).into_response().status();
+                        result.status = status.into();
+                        result.error = status.canonical_reason().map(str::to_string);
+                    }
+                    Ok(_) if !committed => {
+                        result.status = StatusCode::FAILED_DEPENDENCY.into();
+                        result.error = Some("Rolled back, another operation failed".to_string());
+                    }
+                    Ok(BulkOutcome::Created(action)) => {
+                        result.status = StatusCode::CREATED.into();
+                        result.action = Some(action);
+                    }
+                    Ok(BulkOutcome::Updated { action, .. }) => result.action = Some(action),
+                    Ok(BulkOutcome::Deleted(action)) => result.id = Some(action.id),
+                }
+                result
+            })
+            .collect();
+        Self { committed, results }
+    }
+}
+
+/// POST several action changes at once, e.g. to complete many actions after a session
+///
+/// Creates, updates, changes the status of and deletes actions in one transaction, for
+/// actions of coaching sessions the user takes part in. The changes take effect only if
+/// every one of them succeeds, and the response reports each one's result either way.
+#[utoipa::path(
+    post,
+    path = "/actions/bulk",
+    params(ApiVersion),
+    request_body = BulkRequest,
+    responses(
+        (status = 200, description = "Every operation succeeded and took effect", body = BulkResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 422, description = "An operation failed so none took effect, or the request has no or more than 100 operations", body = BulkResponse),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn bulk(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    State(app_state): State<AppState>,
+    Json(request): Json<BulkRequest>,
+) -> Result<impl IntoResponse, Error> {
+    debug!(
+        "POST {} bulk action operations by user {}",
+        request.operations.len(),
+        user.id
+    );
+
+    let outcomes = ActionApi::bulk(
+        app_state.db_conn_ref(),
+        app_state.event_publisher.as_ref(),
+        user.id,
+        request.operations.into_iter().map(Into::into).collect(),
+    )
+    .await?;
+
+    let committed = outcomes.iter().all(Result::is_ok);
+    if committed {
+        // Best-effort emails and mentions, as for the single-action endpoints
+        for outcome in outcomes.iter().flatten() {
+            let (action, previous_assignee_ids) = match outcome {
+                BulkOutcome::Created(action) => (action, &[][..]),
+                BulkOutcome::Updated {
+                    action,
+                    previous_assignee_ids,
+                } => (action, &previous_assignee_ids[..]),
+                BulkOutcome::Deleted(_) => continue,
+            };
+            notify_added_assignees(&app_state, action, &user, previous_assignee_ids).await;
+            MentionApi::sync_and_notify(
+                app_state.db_conn_ref(),
+                &app_state.config,
+                app_state.event_publisher.as_ref(),
+                &user,
+                (&action.action).into(),
+            )
+            .await;
+        }
+    }
+
+    let status = if committed {
+        StatusCode::OK
+    } else {
+        StatusCode::UNPROCESSABLE_ENTITY
+    };
+    Ok((
+        status,
+        Json(ApiResponse::new(status.into(), BulkResponse::new(outcomes))),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::error::{DomainErrorKind, EntityErrorKind, InternalErrorKind};
+
+    #[test]
+    fn bulk_operations_are_named_by_op() {
+        let id = Id::new_v4();
+        let request: BulkRequest = serde_json::from_value(json!({
+            "operations": [
+                {"op": "update_status", "id": id, "status": "Completed"},
+                {"op": "delete", "id": id},
+            ]
+        }))
+        .unwrap();
+
+        let operations: Vec<BulkOperation> =
+            request.operations.into_iter().map(Into::into).collect();
+        assert_eq!(
+            operations,
+            vec![
+                BulkOperation::UpdateStatus {
+                    id,
+                    status: Status::Completed,
+                },
+                BulkOperation::Delete { id },
+            ]
+        );
+        assert!(serde_json::from_value::<BulkRequest>(
+            json!({"operations": [{"op": "archive", "id": id}]})
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn a_failed_operation_marks_the_others_rolled_back() {
+        let now = chrono::Utc::now().fixed_offset();
+        let deleted = Model {
+            id: Id::new_v4(),
+            coaching_session_id: Id::new_v4(),
+            goal_id: None,
+            user_id: Id::new_v4(),
+            action_series_id: None,
+            previous_action_id: None,
+            body: None,
+            effort_points: None,
+            due_by: None,
+            status: Status::default(),
+            status_changed_at: now,
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+        };
+        let not_found = domain::error::Error {
+            source: None,
+            error_kind: DomainErrorKind::Internal(InternalErrorKind::Entity(
+                EntityErrorKind::NotFound,
+            )),
+        };
+
+        let response = BulkResponse::new(vec![Ok(BulkOutcome::Deleted(deleted)), Err(not_found)]);
+
+        assert!(!response.committed);
+        assert_eq!(response.results[0].status, 424);
+        assert_eq!(response.results[0].id, None);
+        assert_eq!(response.results[1].index, 1);
+        assert_eq!(response.results[1].status, 404);
+    }
+}
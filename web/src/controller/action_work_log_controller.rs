@@ -0,0 +1,161 @@
+use crate::controller::ApiResponse;
+use crate::extractors::{
+    authenticated_user::AuthenticatedUser, compare_api_version::CompareApiVersion,
+};
+use crate::params::action_work_log::{CreateParams, IndexParams, StartParams, StopParams};
+use crate::{AppState, Error};
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::{action_work_log as ActionWorkLogApi, Id};
+use log::*;
+use service::config::ApiVersion;
+
+/// POST start a focus timer on an action.
+///
+/// Participant-only. A user can have one running timer at a time.
+#[utoipa::path(
+    post,
+    path = "/action_work_logs/start",
+    params(ApiVersion),
+    request_body = crate::params::action_work_log::StartParams,
+    responses(
+        (status = 201, description = "Timer started", body = domain::action_work_logs::Model),
+        (status = 401, description = "Unauthorized or not a participant"),
+        (status = 404, description = "Action not found"),
+        (status = 422, description = "A timer is already running"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn start(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    State(app_state): State<AppState>,
+    Json(params): Json<StartParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!(
+        "POST start work log on action {} by user {}",
+        params.action_id, user.id
+    );
+
+    let work_log = ActionWorkLogApi::start(
+        app_state.db_conn_ref(),
+        user.id,
+        params.action_id,
+        params.note,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::CREATED.into(), work_log)))
+}
+
+/// POST stop one of the caller's running focus timers, recording the elapsed time.
+///
+/// The request body is optional.
+#[utoipa::path(
+    post,
+    path = "/action_work_logs/{id}/stop",
+    params(
+        ApiVersion,
+        ("id" = Id, Path, description = "Work log id to stop")
+    ),
+    request_body = crate::params::action_work_log::StopParams,
+    responses(
+        (status = 200, description = "Timer stopped", body = domain::action_work_logs::Model),
+        (status = 401, description = "Unauthorized or not the timer's owner"),
+        (status = 404, description = "Work log not found"),
+        (status = 422, description = "Timer already stopped"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn stop(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    State(app_state): State<AppState>,
+    Path(id): Path<Id>,
+    params: Option<Json<StopParams>>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("POST stop work log {id} by user {}", user.id);
+
+    // The body is optional: a bare POST stops the timer and keeps its note.
+    let note = params.and_then(|Json(params)| params.note);
+    let work_log = ActionWorkLogApi::stop(app_state.db_conn_ref(), user.id, id, note).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), work_log)))
+}
+
+/// POST a finished span of work on an action, logged after the fact.
+#[utoipa::path(
+    post,
+    path = "/action_work_logs",
+    params(ApiVersion),
+    request_body = crate::params::action_work_log::CreateParams,
+    responses(
+        (status = 201, description = "Work logged", body = domain::action_work_logs::Model),
+        (status = 401, description = "Unauthorized or not a participant"),
+        (status = 404, description = "Action not found"),
+        (status = 422, description = "Invalid duration or start time"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn create(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    State(app_state): State<AppState>,
+    Json(params): Json<CreateParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!(
+        "POST work log of {}s on action {} by user {}",
+        params.duration_seconds, params.action_id, user.id
+    );
+
+    let work_log = ActionWorkLogApi::log(
+        app_state.db_conn_ref(),
+        user.id,
+        params.action_id,
+        params.started_at,
+        params.duration_seconds,
+        params.note,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::CREATED.into(), work_log)))
+}
+
+/// GET all work logged on an action, most recent first.
+#[utoipa::path(
+    get,
+    path = "/action_work_logs",
+    params(
+        ApiVersion,
+        ("action_id" = Id, Query, description = "Action to list work logs for")
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved work logs", body = [domain::action_work_logs::Model]),
+        (status = 401, description = "Unauthorized or not a participant"),
+        (status = 404, description = "Action not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn index(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    State(app_state): State<AppState>,
+    Query(params): Query<IndexParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!(
+        "GET work logs for action {} by user {}",
+        params.action_id, user.id
+    );
+
+    let work_logs =
+        ActionWorkLogApi::find_by_action(app_state.db_conn_ref(), user.id, params.action_id)
+            .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), work_logs)))
+}
@@ -0,0 +1,45 @@
+use crate::extractors::compare_api_version::CompareApiVersion;
+use crate::params::admin::ReassignParams;
+use crate::{controller::ApiResponse, AppState, Error};
+use axum::extract::{Path, State};
+use axum::{http::StatusCode, response::IntoResponse, Json};
+use domain::{coaching_relationship as CoachingRelationshipApi, Id};
+use service::config::ApiVersion;
+
+/// PUT a Coaching Relationship's new coach and/or coachee
+///
+/// Both must belong to the relationship's organization. Its sessions, goals and the
+/// rest of its history stay with the relationship.
+#[utoipa::path(
+    put,
+    path = "/admin/coaching_relationships/{id}/participants",
+    params(
+        ApiVersion,
+        ("id" = Id, Path, description = "The ID of the coaching relationship to reassign")
+    ),
+    request_body = ReassignParams,
+    responses(
+        (status = 200, description = "Coaching relationship reassigned", body = domain::coaching_relationships::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - SuperAdmin only"),
+        (status = 404, description = "Coaching relationship or user not found"),
+        (status = 422, description = "Nobody given, a user outside the organization, the same user as coach and coachee, or an existing pair"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn reassign(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(id): Path<Id>,
+    Json(params): Json<ReassignParams>,
+) -> Result<impl IntoResponse, Error> {
+    let relationship = CoachingRelationshipApi::reassign(
+        app_state.db_conn_ref(),
+        id,
+        params.coach_id,
+        params.coachee_id,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), relationship)))
+}
@@ -0,0 +1,7 @@
+//! Platform administration endpoints under /admin, each gated to SuperAdmins by its
+//! own `protect::admin` middleware in the router.
+
+pub(crate) mod coaching_relationship_controller;
+pub(crate) mod organization_controller;
+pub(crate) mod user_controller;
+pub(crate) mod warehouse_export_controller;
@@ -0,0 +1,32 @@
+use crate::extractors::compare_api_version::CompareApiVersion;
+use crate::{controller::ApiResponse, AppState, Error};
+use axum::extract::{Path, State};
+use axum::{http::StatusCode, response::IntoResponse, Json};
+use domain::{organization as OrganizationApi, Id};
+use service::config::ApiVersion;
+
+/// GET an Organization's counts of members, relationships and sessions
+#[utoipa::path(
+    get,
+    path = "/admin/organizations/{id}/stats",
+    params(
+        ApiVersion,
+        ("id" = Id, Path, description = "The ID of the organization")
+    ),
+    responses(
+        (status = 200, description = "Organization stats retrieved", body = domain::organization::OrganizationStats),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - SuperAdmin only"),
+        (status = 404, description = "Organization not found"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn stats(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    let stats = OrganizationApi::stats(app_state.db_conn_ref(), id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), stats)))
+}
@@ -0,0 +1,149 @@
+use crate::extractors::{
+    authenticated_user::AuthenticatedUser, compare_api_version::CompareApiVersion,
+};
+use crate::params::admin::{LockParams, UserIndexParams};
+use crate::{controller::ApiResponse, AppState, Error};
+use axum::extract::{Path, Query, State};
+use axum::{http::StatusCode, response::IntoResponse, Json};
+use domain::emails::AccountLockChange;
+use domain::{emails as EmailsAPI, user as UserApi, user_lock as UserLockApi, Id};
+use service::config::ApiVersion;
+
+/// INDEX Users across all organizations, ordered by name, optionally searched and only
+/// those of one organization
+#[utoipa::path(
+    get,
+    path = "/admin/users",
+    params(
+        ApiVersion,
+        UserIndexParams,
+    ),
+    responses(
+        (status = 200, description = "Users retrieved, with their roles", body = [domain::users::Model]),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - SuperAdmin only"),
+        (status = 422, description = "`limit` out of range"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn index(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Query(params): Query<UserIndexParams>,
+) -> Result<impl IntoResponse, Error> {
+    let users = UserApi::search(
+        app_state.db_conn_ref(),
+        params.q.as_deref(),
+        params.organization_id,
+        params.limit,
+        params.offset,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), users)))
+}
+
+/// LOCK any User's account, in the name of one of their organizations
+///
+/// As when an organization admin locks them: the user can't log in until unlocked and
+/// their sessions are logged out. The organization's admins are emailed.
+#[utoipa::path(
+    post,
+    path = "/admin/users/{user_id}/lock",
+    params(
+        ApiVersion,
+        ("user_id" = Id, Path, description = "The ID of the user to lock")
+    ),
+    request_body = LockParams,
+    responses(
+        (status = 200, description = "User locked", body = domain::user_locks::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - SuperAdmin only, and not yourself"),
+        (status = 404, description = "User not found"),
+        (status = 409, description = "The user is already locked"),
+        (status = 422, description = "The user isn't in the organization, or the reason is too long"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn lock(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    AuthenticatedUser(authenticated_user): AuthenticatedUser,
+    Path(user_id): Path<Id>,
+    Json(params): Json<LockParams>,
+) -> Result<impl IntoResponse, Error> {
+    let lock = UserLockApi::lock_as_super_admin(
+        app_state.db_conn_ref(),
+        app_state.event_publisher.as_ref(),
+        params.organization_id,
+        user_id,
+        authenticated_user.id,
+        params.reason,
+    )
+    .await?;
+
+    let user = UserApi::find_by_id(app_state.db_conn_ref(), user_id).await?;
+    EmailsAPI::notify_account_lock(
+        app_state.db_conn_ref(),
+        &app_state.config,
+        lock.organization_id,
+        &AccountLockChange {
+            user: &user,
+            actor: &authenticated_user,
+            locked: true,
+            reason: lock.reason.as_deref(),
+        },
+    )
+    .await;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), lock)))
+}
+
+/// UNLOCK any User's account, whichever organization locked it
+#[utoipa::path(
+    post,
+    path = "/admin/users/{user_id}/unlock",
+    params(
+        ApiVersion,
+        ("user_id" = Id, Path, description = "The ID of the user to unlock")
+    ),
+    responses(
+        (status = 204, description = "User unlocked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - SuperAdmin only, and not yourself"),
+        (status = 404, description = "The user isn't locked"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn unlock(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    AuthenticatedUser(authenticated_user): AuthenticatedUser,
+    Path(user_id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    let lock = UserLockApi::unlock_as_super_admin(
+        app_state.db_conn_ref(),
+        app_state.event_publisher.as_ref(),
+        user_id,
+        authenticated_user.id,
+    )
+    .await?;
+
+    let user = UserApi::find_by_id(app_state.db_conn_ref(), user_id).await?;
+    EmailsAPI::notify_account_lock(
+        app_state.db_conn_ref(),
+        &app_state.config,
+        lock.organization_id,
+        &AccountLockChange {
+            user: &user,
+            actor: &authenticated_user,
+            locked: false,
+            reason: None,
+        },
+    )
+    .await;
+
+    Ok(Json(ApiResponse::<()>::no_content(
+        StatusCode::NO_CONTENT.into(),
+    )))
+}
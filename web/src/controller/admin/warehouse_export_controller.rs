@@ -0,0 +1,73 @@
+use crate::extractors::compare_api_version::CompareApiVersion;
+use crate::params::admin::BackfillParams;
+use crate::{controller::ApiResponse, AppState, Error};
+use axum::extract::State;
+use axum::{http::StatusCode, response::IntoResponse, Json};
+use domain::warehouse as WarehouseApi;
+use service::config::ApiVersion;
+
+use log::*;
+
+/// GET how far the export of domain events to the analytics warehouse has got
+///
+/// One checkpoint per stream: `live` for events exported as they're published, and
+/// `backfill` for stored events re-exported by the last backfill.
+#[utoipa::path(
+    get,
+    path = "/admin/warehouse_export",
+    params(ApiVersion),
+    responses(
+        (status = 200, description = "Export checkpoints retrieved", body = [domain::warehouse_checkpoints::Model]),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - SuperAdmin only"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn index(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+) -> Result<impl IntoResponse, Error> {
+    let checkpoints = WarehouseApi::checkpoints(app_state.db_conn_ref()).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), checkpoints)))
+}
+
+/// POST a backfill of the stored domain events created within `[from, to)` to the
+/// analytics warehouse
+///
+/// Replaces any backfill under way. The events are exported in the background, a batch
+/// at a time; follow along at GET /admin/warehouse_export.
+#[utoipa::path(
+    post,
+    path = "/admin/warehouse_export/backfill",
+    params(ApiVersion),
+    request_body = BackfillParams,
+    responses(
+        (status = 202, description = "Backfill started", body = domain::warehouse_checkpoints::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - SuperAdmin only"),
+        (status = 422, description = "`from` isn't before `to` and the present"),
+        (status = 503, description = "The warehouse export isn't configured"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn backfill(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Json(params): Json<BackfillParams>,
+) -> Result<impl IntoResponse, Error> {
+    info!("Starting a warehouse backfill: {params:?}");
+
+    let checkpoint = WarehouseApi::start_backfill(
+        app_state.db_conn_ref(),
+        &app_state.config,
+        params.from,
+        params.to,
+    )
+    .await?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ApiResponse::new(StatusCode::ACCEPTED.into(), checkpoint)),
+    ))
+}
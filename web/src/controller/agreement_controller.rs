@@ -1,6 +1,8 @@
 use crate::controller::ApiResponse;
 use crate::extractors::{
-    authenticated_user::AuthenticatedUser, compare_api_version::CompareApiVersion,
+    authenticated_user::AuthenticatedUser,
+    compare_api_version::CompareApiVersion,
+    if_match::{with_etag, IfMatch},
 };
 use crate::params::agreement::{IndexParams, SortField};
 use crate::params::WithSortDefaults;
@@ -69,7 +71,8 @@ pub async fn create(
         ("id" = String, Path, description = "Agreement id to retrieve")
     ),
     responses(
-        (status = 200, description = "Successfully retrieved a specific Agreement by its id", body = [notes::Model]),
+        (status = 200, description = "Successfully retrieved a specific Agreement by its id", body = [notes::Model],
+            headers(("ETag" = String, description = "Version of the resource to send back in If-Match"))),
         (status = 401, description = "Unauthorized"),
         (status = 404, description = "Agreement not found"),
         (status = 405, description = "Method not allowed"),
@@ -88,7 +91,10 @@ pub async fn read(
 
     let agreement = AgreementApi::find_by_id(app_state.db_conn_ref(), id).await?;
 
-    Ok(Json(ApiResponse::new(StatusCode::OK.into(), agreement)))
+    Ok(with_etag(
+        agreement.updated_at,
+        Json(ApiResponse::new(StatusCode::OK.into(), agreement)),
+    ))
 }
 
 #[utoipa::path(
@@ -100,8 +106,10 @@ pub async fn read(
     ),
     request_body = agreements::Model,
     responses(
-        (status = 200, description = "Successfully Updated Agreement", body = [agreements::Model]),
+        (status = 200, description = "Successfully Updated Agreement", body = [agreements::Model],
+            headers(("ETag" = String, description = "Version of the resource to send back in If-Match"))),
         (status = 401, description = "Unauthorized"),
+        (status = 412, description = "Changed since the version named in If-Match"),
         (status = 405, description = "Method not allowed"),
         (status = 503, description = "Service temporarily unavailable")
     ),
@@ -115,6 +123,7 @@ pub async fn update(
     // TODO: create a new Extractor to authorize the user to access
     // the data requested
     State(app_state): State<AppState>,
+    if_match: IfMatch,
     Path(id): Path<Id>,
     Json(agreement_model): Json<Model>,
 ) -> Result<impl IntoResponse, Error> {
@@ -125,12 +134,16 @@ pub async fn update(
         app_state.event_publisher.as_ref(),
         id,
         agreement_model,
+        if_match.expected(),
     )
     .await?;
 
     debug!("Updated Agreement: {agreement:?}");
 
-    Ok(Json(ApiResponse::new(StatusCode::OK.into(), agreement)))
+    Ok(with_etag(
+        agreement.updated_at,
+        Json(ApiResponse::new(StatusCode::OK.into(), agreement)),
+    ))
 }
 
 #[utoipa::path(
@@ -138,12 +151,14 @@ pub async fn update(
     path = "/agreements",
     params(
         ApiVersion,
-        ("coaching_session_id" = Id, Query, description = "Filter by coaching_session_id"),
+        ("filter[coaching_session_id]" = Id, Query, description = "Filter by coaching_session_id (also accepted as coaching_session_id)"),
+        ("sort" = Option<String>, Query, description = "Sort by a field, descending when prefixed with '-'. Takes precedence over sort_by and sort_order.", example = "-created_at"),
         ("sort_by" = Option<crate::params::agreement::SortField>, Query, description = "Sort by field. Valid values: 'body', 'created_at', 'updated_at'. Must be provided with sort_order.", example = "body"),
         ("sort_order" = Option<crate::params::sort::SortOrder>, Query, description = "Sort order. Valid values: 'asc' (ascending), 'desc' (descending). Must be provided with sort_by.", example = "desc")
     ),
     responses(
         (status = 200, description = "Successfully retrieved all Agreements", body = [agreements::Model]),
+        (status = 400, description = "Unknown or invalid filter or sort parameter"),
         (status = 401, description = "Unauthorized"),
         (status = 405, description = "Method not allowed"),
         (status = 503, description = "Service temporarily unavailable")
@@ -165,7 +180,12 @@ pub async fn index(
 
     // Apply default sorting parameters
     let mut params = params;
-    IndexParams::apply_sort_defaults(&mut params.sort_by, &mut params.sort_order, SortField::Body);
+    IndexParams::apply_sort(
+        params.sort.take(),
+        &mut params.sort_by,
+        &mut params.sort_order,
+        SortField::Body,
+    );
 
     let agreements = AgreementApi::find_by(app_state.db_conn_ref(), params).await?;
 
@@ -208,3 +228,38 @@ pub async fn delete(
     .await?;
     Ok(Json(json!({"id": id})))
 }
+
+/// POST restore a deleted Agreement, until it's purged.
+#[utoipa::path(
+    post,
+    path = "/agreements/{id}/restore",
+    params(
+        ApiVersion,
+        ("id" = Id, Path, description = "Id of the deleted Agreement to restore")
+    ),
+    responses(
+        (status = 200, description = "Successfully restored an Agreement", body = agreements::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "No deleted Agreement with this id"),
+        (status = 405, description = "Method not allowed"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn restore(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("POST restore Agreement by id: {id}");
+
+    let agreement = AgreementApi::restore(
+        app_state.db_conn_ref(),
+        app_state.event_publisher.as_ref(),
+        id,
+    )
+    .await?;
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), agreement)))
+}
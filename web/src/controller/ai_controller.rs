@@ -0,0 +1,246 @@
+use crate::controller::ApiResponse;
+use crate::extractors::{
+    authenticated_user::AuthenticatedUser,
+    coaching_relationship_access::CoachingRelationshipAccess,
+    compare_api_version::CompareApiVersion,
+};
+use crate::{AppState, Error};
+use async_stream::stream;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::ai_privacy_level::AiPrivacyLevel;
+use domain::{
+    ai_chat as AiChatApi, coaching_relationship as CoachingRelationshipApi,
+    progress_report as ProgressReportApi, themes as ThemesApi,
+};
+use futures::StreamExt;
+use serde::Deserialize;
+use service::config::ApiVersion;
+use std::convert::Infallible;
+use utoipa::{IntoParams, ToSchema};
+
+use log::*;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PrivacyLevelParams {
+    pub ai_privacy_level: AiPrivacyLevel,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ChatParams {
+    pub question: String,
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct ThemesParams {
+    /// Regenerate the report instead of using the stored one.
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+/// PUT how much of a coaching relationship's content AI features may use. The coachee may
+/// choose any level; the coach may only make it stricter.
+#[utoipa::path(
+    put,
+    path = "/coaching_relationships/{relationship_id}/ai/privacy_level",
+    params(
+        ApiVersion,
+        ("relationship_id" = Id, Path, description = "Coaching relationship to update"),
+    ),
+    request_body = PrivacyLevelParams,
+    responses(
+        (status = 200, description = "AI privacy level updated", body = domain::coaching_relationships::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Coaching relationship not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn update_privacy_level(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    CoachingRelationshipAccess(relationship): CoachingRelationshipAccess,
+    State(app_state): State<AppState>,
+    Json(params): Json<PrivacyLevelParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!(
+        "PUT AI privacy level {:?} for relationship {}",
+        params.ai_privacy_level, relationship.id
+    );
+
+    let relationship = CoachingRelationshipApi::set_ai_privacy_level(
+        app_state.db_conn_ref(),
+        user.id,
+        relationship,
+        params.ai_privacy_level,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), relationship)))
+}
+
+/// POST a question about the coaching relationship's history (coach only)
+///
+/// Responds with a server-sent event stream: one `sources` event listing the cited
+/// excerpts, `delta` events carrying the answer's text as it's generated, then `done`
+/// (or `error` if generation fails part-way).
+#[utoipa::path(
+    post,
+    path = "/coaching_relationships/{relationship_id}/ai/chat",
+    params(
+        ApiVersion,
+        ("relationship_id" = Id, Path, description = "Coaching relationship to ask about"),
+    ),
+    request_body = ChatParams,
+    responses(
+        (status = 200, description = "Answer stream (text/event-stream)"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Coaching relationship not found"),
+        (status = 422, description = "Empty or overlong question, or AI disabled for the relationship"),
+        (status = 500, description = "AI analysis is not configured"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn chat(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    CoachingRelationshipAccess(relationship): CoachingRelationshipAccess,
+    State(app_state): State<AppState>,
+    Json(params): Json<ChatParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("POST AI chat for relationship {}", relationship.id);
+
+    let answer = AiChatApi::ask(
+        app_state.database_connection.clone(),
+        app_state.analysis_provider.clone(),
+        app_state.embedding_provider.clone(),
+        user.id,
+        &relationship,
+        &params.question,
+    )
+    .await?;
+
+    let sources = Event::default()
+        .event("sources")
+        .json_data(&answer.sources)
+        .unwrap_or_else(|_| Event::default().event("sources").data("[]"));
+    let mut text = answer.text;
+
+    let stream = stream! {
+        yield Ok::<_, Infallible>(sources);
+        while let Some(delta) = text.next().await {
+            match delta {
+                Ok(delta) => {
+                    yield Ok(Event::default()
+                        .event("delta")
+                        .json_data(serde_json::json!({ "text": delta }))
+                        .unwrap_or_default());
+                }
+                Err(e) => {
+                    warn!("AI chat answer failed part-way: {e:?}");
+                    yield Ok(Event::default()
+                        .event("error")
+                        .data("The answer could not be completed"));
+                    return;
+                }
+            }
+        }
+        yield Ok(Event::default().event("done").data(""));
+    };
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+/// GET the recurring themes across a coaching relationship's sessions (coach only)
+///
+/// The report names recurring challenges and progress arcs across the most recent held
+/// sessions. It's generated on first request and again once another session has been
+/// held; pass `refresh` to regenerate it now.
+#[utoipa::path(
+    get,
+    path = "/coaching_relationships/{relationship_id}/themes",
+    params(
+        ApiVersion,
+        ("relationship_id" = Id, Path, description = "Coaching relationship to report on"),
+        ThemesParams,
+    ),
+    responses(
+        (status = 200, description = "Themes report retrieved", body = domain::theme_reports::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Coaching relationship not found"),
+        (status = 422, description = "Too few sessions with content, or AI disabled for the relationship"),
+        (status = 500, description = "AI analysis is not configured"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn themes(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    CoachingRelationshipAccess(relationship): CoachingRelationshipAccess,
+    State(app_state): State<AppState>,
+    Query(params): Query<ThemesParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("GET themes report for relationship {}", relationship.id);
+
+    let report = ThemesApi::report(
+        app_state.db_conn_ref(),
+        app_state.analysis_provider.as_deref(),
+        user.id,
+        &relationship,
+        params.refresh,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), report)))
+}
+
+/// GET the weekly progress reports on a coaching relationship, most recent week first
+/// (coach only)
+///
+/// A report is written after each week in which the relationship held a session, for
+/// coaches who opted in to the weekly progress email; this lists the saved copies.
+#[utoipa::path(
+    get,
+    path = "/coaching_relationships/{relationship_id}/progress_reports",
+    params(
+        ApiVersion,
+        ("relationship_id" = Id, Path, description = "Coaching relationship the reports are on"),
+    ),
+    responses(
+        (status = 200, description = "Progress reports retrieved", body = [domain::progress_reports::Model]),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Coaching relationship not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn progress_reports(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    CoachingRelationshipAccess(relationship): CoachingRelationshipAccess,
+    State(app_state): State<AppState>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("GET progress reports for relationship {}", relationship.id);
+
+    let reports = ProgressReportApi::find_by_coaching_relationship(
+        app_state.db_conn_ref(),
+        user.id,
+        &relationship,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), reports)))
+}
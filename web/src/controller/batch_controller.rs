@@ -0,0 +1,80 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::{batch as BatchApi, Id};
+use log::*;
+use serde::Deserialize;
+use service::config::ApiVersion;
+use utoipa::ToSchema;
+
+use crate::controller::ApiResponse;
+use crate::extractors::{
+    authenticated_user::AuthenticatedUser, compare_api_version::CompareApiVersion,
+};
+use crate::{AppState, Error};
+
+/// The kinds of records that can be fetched in a batch.
+#[derive(Debug, Clone, Copy, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchResource {
+    Actions,
+    Users,
+}
+
+/// Request body for POST `/batch/{resource}`.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BatchRequest {
+    /// Ids of the records to fetch, at most 100; repeats are ignored
+    pub ids: Vec<Id>,
+}
+
+/// FETCH specific actions or users by id in one request. Each record is authorized as
+/// its own read would be; the response partitions the ids into `found` records and
+/// `missing` and `forbidden` ids.
+#[utoipa::path(
+    post,
+    path = "/batch/{resource}",
+    params(
+        ApiVersion,
+        ("resource" = BatchResource, Path, description = "Kind of record to fetch: 'actions' or 'users'"),
+    ),
+    request_body = BatchRequest,
+    responses(
+        (status = 200, description = "Successfully fetched the batch, partitioned into found, missing and forbidden"),
+        (status = 400, description = "Unknown resource or malformed ids"),
+        (status = 401, description = "Unauthorized"),
+        (status = 422, description = "Too many ids requested"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn fetch(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    State(app_state): State<AppState>,
+    Path(resource): Path<BatchResource>,
+    Json(request): Json<BatchRequest>,
+) -> Result<impl IntoResponse, Error> {
+    debug!(
+        "POST batch fetch of {} {resource:?} for user {}",
+        request.ids.len(),
+        user.id
+    );
+
+    let db = app_state.db_conn_ref();
+    let response = match resource {
+        BatchResource::Actions => {
+            let batch = BatchApi::actions(db, &user, request.ids).await?;
+            Json(ApiResponse::new(StatusCode::OK.into(), batch)).into_response()
+        }
+        BatchResource::Users => {
+            let batch = BatchApi::users(db, &user, request.ids).await?;
+            Json(ApiResponse::new(StatusCode::OK.into(), batch)).into_response()
+        }
+    };
+
+    Ok(response)
+}
@@ -0,0 +1,116 @@
+//! First-run setup of a self-hosted platform.
+//!
+//! Unauthenticated, since there is nobody to sign in as yet. Available only while the
+//! database has no users and no platform settings; the first successful POST creates
+//! both, which turns these endpoints off for good.
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::{organizations, platform_setting as PlatformSettingApi, platform_settings, users};
+use log::*;
+use serde::{Deserialize, Serialize};
+use service::config::ApiVersion;
+use utoipa::ToSchema;
+
+use crate::controller::ApiResponse;
+use crate::extractors::compare_api_version::CompareApiVersion;
+use crate::{AppState, Error};
+
+/// Whether the platform still needs setting up
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BootstrapStatus {
+    pub available: bool,
+}
+
+/// Request body for POST `/bootstrap`.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BootstrapRequest {
+    /// The first SuperAdmin, who also administers `organization`. Requires a password
+    /// that passes the password policy.
+    pub user: users::Model,
+    /// The first organization
+    pub organization: organizations::Model,
+    /// The platform's settings
+    pub settings: platform_settings::Model,
+}
+
+/// What POST `/bootstrap` created
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BootstrapResponse {
+    pub user: users::Model,
+    pub organization: organizations::Model,
+    pub settings: platform_settings::Model,
+}
+
+/// GET whether the platform can still be bootstrapped
+#[utoipa::path(
+    get,
+    path = "/bootstrap",
+    params(ApiVersion),
+    responses(
+        (status = 200, description = "Whether POST /bootstrap is available", body = BootstrapStatus),
+        (status = 503, description = "Service temporarily unavailable")
+    )
+)]
+pub async fn read(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+) -> Result<impl IntoResponse, Error> {
+    let available = PlatformSettingApi::is_bootstrappable(app_state.db_conn_ref()).await?;
+
+    Ok(Json(ApiResponse::new(
+        StatusCode::OK.into(),
+        BootstrapStatus { available },
+    )))
+}
+
+/// POST the first SuperAdmin, organization and platform settings
+///
+/// Creates all three in one transaction. Works once, on a database without users;
+/// afterwards it answers 409.
+#[utoipa::path(
+    post,
+    path = "/bootstrap",
+    params(ApiVersion),
+    request_body = BootstrapRequest,
+    responses(
+        (status = 201, description = "The platform is set up", body = BootstrapResponse),
+        (status = 409, description = "The platform has already been set up"),
+        (status = 422, description = "The password, timezone, platform name or organization name is invalid"),
+        (status = 429, description = "Too many requests"),
+        (status = 503, description = "Service temporarily unavailable")
+    )
+)]
+pub async fn create(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Json(request): Json<BootstrapRequest>,
+) -> Result<impl IntoResponse, Error> {
+    debug!(
+        "POST bootstrap the platform with SuperAdmin {}",
+        request.user.email
+    );
+
+    let bootstrapped = PlatformSettingApi::bootstrap(
+        app_state.db_conn_ref(),
+        &app_state.config,
+        request.user,
+        request.organization,
+        request.settings,
+    )
+    .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::new(
+            StatusCode::CREATED.into(),
+            BootstrapResponse {
+                user: bootstrapped.user,
+                organization: bootstrapped.organization,
+                settings: bootstrapped.settings,
+            },
+        )),
+    ))
+}
@@ -0,0 +1,201 @@
+use crate::controller::ApiResponse;
+use crate::extractors::{
+    authenticated_user::AuthenticatedUser, coaching_session_access::CoachingSessionAccess,
+    compare_api_version::CompareApiVersion,
+};
+use crate::{AppState, Error};
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::attachment::{self as AttachmentApi, NewAttachment};
+use domain::Id;
+use serde::Deserialize;
+use service::config::ApiVersion;
+use utoipa::{IntoParams, ToSchema};
+
+use log::*;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateParams {
+    pub file_name: String,
+    /// MIME type of the file, e.g. `application/pdf`.
+    pub content_type: String,
+    /// Exact size of the file in bytes.
+    pub byte_size: i64,
+    /// Attach the file to this note of the session.
+    pub note_id: Option<Id>,
+    /// Attach the file to this action of the session.
+    pub action_id: Option<Id>,
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct IndexParams {
+    /// Only the attachments on this note.
+    pub note_id: Option<Id>,
+    /// Only the attachments on this action.
+    pub action_id: Option<Id>,
+}
+
+/// POST a file to attach to a coaching session, or one of its notes or actions (either
+/// participant)
+///
+/// Records the attachment and answers a presigned `upload_url` to PUT the file to before
+/// `expires_at`, with the declared `Content-Type` and `Content-Length`. PDFs, office
+/// documents, text, CSV and images are accepted, up to the configured size and within
+/// the organization's storage quota.
+#[utoipa::path(
+    post,
+    path = "/coaching_sessions/{coaching_session_id}/attachments",
+    params(
+        ApiVersion,
+        ("coaching_session_id" = Id, Path, description = "Coaching session id"),
+    ),
+    request_body = CreateParams,
+    responses(
+        (status = 201, description = "The attachment, with where to upload its file"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Coaching session, note or action not found"),
+        (status = 422, description = "The file's type or size isn't allowed, or the organization's quota would be exceeded"),
+        (status = 503, description = "Object storage for attachments isn't configured"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn create(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    CoachingSessionAccess(session): CoachingSessionAccess,
+    State(app_state): State<AppState>,
+    Json(params): Json<CreateParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("POST attachment to session {}: {params:?}", session.id);
+
+    let upload = AttachmentApi::create(
+        app_state.db_conn_ref(),
+        &app_state.config,
+        &session,
+        user.id,
+        NewAttachment {
+            file_name: params.file_name,
+            content_type: params.content_type,
+            byte_size: params.byte_size,
+            note_id: params.note_id,
+            action_id: params.action_id,
+        },
+    )
+    .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::new(StatusCode::CREATED.into(), upload)),
+    ))
+}
+
+/// GET a coaching session's attachments, oldest first (either participant)
+#[utoipa::path(
+    get,
+    path = "/coaching_sessions/{coaching_session_id}/attachments",
+    params(
+        ApiVersion,
+        ("coaching_session_id" = Id, Path, description = "Coaching session id"),
+        IndexParams,
+    ),
+    responses(
+        (status = 200, description = "The session's attachments", body = [domain::attachments::Model]),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Coaching session not found"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn index(
+    CompareApiVersion(_v): CompareApiVersion,
+    CoachingSessionAccess(session): CoachingSessionAccess,
+    State(app_state): State<AppState>,
+    Query(params): Query<IndexParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("GET attachments of session {}: {params:?}", session.id);
+
+    let attachments = AttachmentApi::find_by_coaching_session(
+        app_state.db_conn_ref(),
+        &session,
+        params.note_id,
+        params.action_id,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), attachments)))
+}
+
+/// GET where to download one of a coaching session's attachments (either participant)
+///
+/// Answers a presigned `download_url` valid until `expires_at`.
+#[utoipa::path(
+    get,
+    path = "/coaching_sessions/{coaching_session_id}/attachments/{id}/download",
+    params(
+        ApiVersion,
+        ("coaching_session_id" = Id, Path, description = "Coaching session id"),
+        ("id" = Id, Path, description = "Attachment id"),
+    ),
+    responses(
+        (status = 200, description = "Where to download the attachment's file"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Coaching session or attachment not found"),
+        (status = 503, description = "Object storage for attachments isn't configured"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn download(
+    CompareApiVersion(_v): CompareApiVersion,
+    CoachingSessionAccess(session): CoachingSessionAccess,
+    State(app_state): State<AppState>,
+    Path((_coaching_session_id, id)): Path<(Id, Id)>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("GET download of attachment {id} of session {}", session.id);
+
+    let download =
+        AttachmentApi::download(app_state.db_conn_ref(), &app_state.config, &session, id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), download)))
+}
+
+/// DELETE one of a coaching session's attachments and its file (whoever attached it, or
+/// the coach)
+#[utoipa::path(
+    delete,
+    path = "/coaching_sessions/{coaching_session_id}/attachments/{id}",
+    params(
+        ApiVersion,
+        ("coaching_session_id" = Id, Path, description = "Coaching session id"),
+        ("id" = Id, Path, description = "Attachment id"),
+    ),
+    responses(
+        (status = 204, description = "The attachment was deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Coaching session or attachment not found"),
+        (status = 503, description = "Object storage for attachments isn't configured"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn delete(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    CoachingSessionAccess(session): CoachingSessionAccess,
+    State(app_state): State<AppState>,
+    Path((_coaching_session_id, id)): Path<(Id, Id)>,
+) -> Result<impl IntoResponse, Error> {
+    info!("Deleting attachment {id} of session {}", session.id);
+
+    AttachmentApi::delete(
+        app_state.db_conn_ref(),
+        &app_state.config,
+        &session,
+        user.id,
+        id,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::<()>::no_content(
+        StatusCode::NO_CONTENT.into(),
+    )))
+}
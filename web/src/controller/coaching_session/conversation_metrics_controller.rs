@@ -0,0 +1,43 @@
+use crate::controller::ApiResponse;
+use crate::extractors::{
+    coaching_session_access::CoachingSessionAccess, compare_api_version::CompareApiVersion,
+};
+use crate::{AppState, Error};
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::conversation_metrics as ConversationMetricsApi;
+use log::*;
+use service::config::ApiVersion;
+
+/// GET the talk time, questions and interruptions per speaker of a coaching session's
+/// latest transcript
+#[utoipa::path(
+    get,
+    path = "/coaching_sessions/{coaching_session_id}/conversation_metrics",
+    params(
+        ApiVersion,
+        ("coaching_session_id" = Id, Path, description = "Coaching session id"),
+    ),
+    responses(
+        (status = 200, description = "Conversation metrics retrieved", body = domain::conversation_metrics::ConversationMetrics),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "The session has no completed transcription"),
+        (status = 503, description = "Service temporarily unavailable"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn read(
+    CompareApiVersion(_v): CompareApiVersion,
+    CoachingSessionAccess(session): CoachingSessionAccess,
+    State(app_state): State<AppState>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("GET conversation metrics for session {}", session.id);
+
+    let metrics =
+        ConversationMetricsApi::find_by_coaching_session(app_state.db_conn_ref(), session.id)
+            .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), metrics)))
+}
@@ -0,0 +1,66 @@
+use crate::controller::ApiResponse;
+use crate::extractors::{
+    coaching_session_access::CoachingSessionAccess, compare_api_version::CompareApiVersion,
+};
+use crate::{AppState, Error};
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::cost_estimate::{self as CostEstimateApi, Operation};
+use log::*;
+use serde::Deserialize;
+use service::config::ApiVersion;
+use utoipa::IntoParams;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct EstimateParams {
+    /// `summary` (the prep brief), `extraction` (the relationship's themes report) or
+    /// `transcription` (the latest recording).
+    #[param(value_type = String)]
+    pub operation: Operation,
+}
+
+/// GET the expected usage and cost of an AI operation on a coaching session
+///
+/// Token counts are estimated from the stored notes, agreements and transcript the
+/// operation would send and the organization's completion budget for the task;
+/// transcription is priced by the recording's duration. `cost` is null when no rate is
+/// configured.
+#[utoipa::path(
+    get,
+    path = "/coaching_sessions/{coaching_session_id}/ai/estimate",
+    params(
+        ApiVersion,
+        ("coaching_session_id" = Id, Path, description = "Coaching session id"),
+        EstimateParams,
+    ),
+    responses(
+        (status = 200, description = "Cost estimate computed"),
+        (status = 400, description = "The operation can't run for this session"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Coaching session not found"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn read(
+    CompareApiVersion(_v): CompareApiVersion,
+    CoachingSessionAccess(session): CoachingSessionAccess,
+    State(app_state): State<AppState>,
+    Query(params): Query<EstimateParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!(
+        "GET {:?} cost estimate for session {}",
+        params.operation, session.id
+    );
+
+    let estimate = CostEstimateApi::estimate(
+        app_state.db_conn_ref(),
+        app_state.analysis_provider.as_deref(),
+        session.id,
+        params.operation,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), estimate)))
+}
@@ -20,7 +20,8 @@ pub struct StartRecordingParams {
     pub meeting_url: String,
 }
 
-/// GET the current recording status and artifact URLs for a coaching session
+/// GET the current recording status and artifact URLs for a coaching session, with the
+/// chapter markers of its transcript for playback navigation
 #[utoipa::path(
     get,
     path = "/coaching_sessions/{coaching_session_id}/meeting_recording",
@@ -43,13 +44,13 @@ pub async fn read(
     let coaching_session_id = session.id;
     debug!("GET meeting_recording for session {}", coaching_session_id);
 
-    let recording = MeetingRecordingApi::find_latest_by_coaching_session(
+    let playback = MeetingRecordingApi::find_playback_by_coaching_session(
         app_state.db_conn_ref(),
         coaching_session_id,
     )
     .await?;
 
-    Ok(Json(ApiResponse::new(StatusCode::OK.into(), recording)))
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), playback)))
 }
 
 /// POST create a Recall.ai bot and start recording a coaching session
@@ -303,8 +304,10 @@ mod tests {
             meeting_url: None,
             provider: None,
             hydrated_at: None,
+            session_type_id: None,
             created_at: now.into(),
             updated_at: now.into(),
+            deleted_at: None,
         };
 
         let relationship = coaching_relationships::Model {
@@ -313,6 +316,8 @@ mod tests {
             coachee_id: user.id,
             organization_id: Id::new_v4(),
             slug: "test".to_string(),
+            ai_privacy_level: Default::default(),
+            pro_bono: false,
             created_at: now.into(),
             updated_at: now.into(),
         };
@@ -322,6 +327,7 @@ mod tests {
         let db = Arc::new(
             MockDatabase::new(DatabaseBackend::Postgres)
                 .append_query_results([vec![(user.clone(), role.clone())]])
+                .append_query_results([Vec::<domain::policy_documents::Model>::new()])
                 .append_query_results([vec![(user.clone(), role.clone())]])
                 .append_query_results(vec![vec![(session, relationship)]])
                 .append_query_results(vec![vec![active_recording]])
@@ -363,8 +369,10 @@ mod tests {
             meeting_url: None,
             provider: None,
             hydrated_at: None,
+            session_type_id: None,
             created_at: now.into(),
             updated_at: now.into(),
+            deleted_at: None,
         };
 
         let relationship = coaching_relationships::Model {
@@ -373,6 +381,8 @@ mod tests {
             coachee_id: user.id,
             organization_id: Id::new_v4(),
             slug: "test".to_string(),
+            ai_privacy_level: Default::default(),
+            pro_bono: false,
             created_at: now.into(),
             updated_at: now.into(),
         };
@@ -380,6 +390,7 @@ mod tests {
         let db = Arc::new(
             MockDatabase::new(DatabaseBackend::Postgres)
                 .append_query_results([vec![(user.clone(), role.clone())]])
+                .append_query_results([Vec::<domain::policy_documents::Model>::new()])
                 .append_query_results([vec![(user.clone(), role.clone())]])
                 .append_query_results(vec![vec![(session, relationship)]])
                 .into_connection(),
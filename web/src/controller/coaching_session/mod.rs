@@ -1,5 +1,13 @@
+pub(crate) mod attachment_controller;
+pub(crate) mod conversation_metrics_controller;
+pub(crate) mod estimate_controller;
 pub(crate) mod goal_controller;
 pub(crate) mod meeting_recording_controller;
+pub(crate) mod note_typing_controller;
+pub(crate) mod prep_controller;
+pub(crate) mod question_quality_controller;
+pub(crate) mod record_controller;
 pub(crate) mod topic_controller;
+pub(crate) mod transcript_chapter_controller;
 pub(crate) mod transcription_controller;
 pub(crate) mod transcription_segment_controller;
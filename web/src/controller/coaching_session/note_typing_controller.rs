@@ -0,0 +1,93 @@
+use crate::extractors::{
+    authenticated_user::AuthenticatedUser, coaching_session_access::CoachingSessionAccess,
+    compare_api_version::CompareApiVersion,
+};
+use crate::{AppState, Error};
+use ::sse::message::{Event as SseEvent, Message as SseMessage, MessageScope};
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::coaching_relationship as CoachingRelationshipApi;
+use log::*;
+use serde::Deserialize;
+use service::config::ApiVersion;
+use utoipa::ToSchema;
+
+/// Body for POST `/coaching_sessions/{coaching_session_id}/notes/typing`
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TypingParams {
+    /// Whether the user is writing in the session's notes now
+    pub typing: bool,
+}
+
+/// POST whether the user is writing in a coaching session's notes
+///
+/// Tells the session's other participant with a `note_editing_started` or
+/// `note_editing_stopped` SSE event. The events aren't kept for replay, so a client
+/// resends `typing: true` every few seconds while the user writes and the other side
+/// hides the indicator when it hasn't heard one for a while.
+#[utoipa::path(
+    post,
+    path = "/coaching_sessions/{coaching_session_id}/notes/typing",
+    params(
+        ApiVersion,
+        ("coaching_session_id" = Id, Path, description = "Coaching session id"),
+    ),
+    request_body = TypingParams,
+    responses(
+        (status = 204, description = "The other participant was told"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Coaching session not found"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn create(
+    CompareApiVersion(_v): CompareApiVersion,
+    CoachingSessionAccess(session): CoachingSessionAccess,
+    AuthenticatedUser(user): AuthenticatedUser,
+    State(app_state): State<AppState>,
+    Json(params): Json<TypingParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!(
+        "POST typing={} in the notes of session {} by user {}",
+        params.typing, session.id, user.id
+    );
+
+    let relationship = CoachingRelationshipApi::find_by_id(
+        app_state.db_conn_ref(),
+        session.coaching_relationship_id,
+    )
+    .await?;
+    let other_participant = if relationship.coach_id == user.id {
+        relationship.coachee_id
+    } else {
+        relationship.coach_id
+    };
+
+    let coaching_session_id = session.id.to_string();
+    let user_id = user.id.to_string();
+    let event = if params.typing {
+        SseEvent::NoteEditingStarted {
+            coaching_session_id,
+            user_id,
+        }
+    } else {
+        SseEvent::NoteEditingStopped {
+            coaching_session_id,
+            user_id,
+        }
+    };
+    app_state
+        .sse_manager
+        .send_message(SseMessage {
+            event,
+            scope: MessageScope::User {
+                user_id: other_participant.to_string(),
+            },
+            correlation_id: None,
+        })
+        .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
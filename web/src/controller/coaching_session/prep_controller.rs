@@ -0,0 +1,64 @@
+use crate::controller::ApiResponse;
+use crate::extractors::{
+    authenticated_user::AuthenticatedUser, coaching_session_access::CoachingSessionAccess,
+    compare_api_version::CompareApiVersion,
+};
+use crate::{AppState, Error};
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::session_prep as SessionPrepApi;
+use log::*;
+use serde::Deserialize;
+use service::config::ApiVersion;
+use utoipa::IntoParams;
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct PrepParams {
+    /// Regenerate the AI summary and focus questions instead of using the cached ones.
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+/// GET a prep brief for an upcoming coaching session (coach only)
+///
+/// Gathers the previous session's agreements, open actions, in-progress goals and journal
+/// entries the coachee shared since then. When AI analysis is configured the brief also
+/// carries a summary of the previous session and suggested focus questions; otherwise the
+/// questions are derived from overdue actions and stalled goals.
+#[utoipa::path(
+    get,
+    path = "/coaching_sessions/{coaching_session_id}/prep",
+    params(
+        ApiVersion,
+        ("coaching_session_id" = Id, Path, description = "Coaching session id"),
+        PrepParams,
+    ),
+    responses(
+        (status = 200, description = "Session prep brief retrieved"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Coaching session not found"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn read(
+    CompareApiVersion(_v): CompareApiVersion,
+    CoachingSessionAccess(session): CoachingSessionAccess,
+    AuthenticatedUser(user): AuthenticatedUser,
+    State(app_state): State<AppState>,
+    Query(params): Query<PrepParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("GET prep brief for session {}", session.id);
+
+    let brief = SessionPrepApi::brief(
+        app_state.db_conn_ref(),
+        app_state.analysis_provider.as_deref(),
+        user.id,
+        session.id,
+        params.refresh,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), brief)))
+}
@@ -0,0 +1,62 @@
+use crate::controller::ApiResponse;
+use crate::extractors::{
+    authenticated_user::AuthenticatedUser, coaching_session_access::CoachingSessionAccess,
+    compare_api_version::CompareApiVersion,
+};
+use crate::{AppState, Error};
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::question_quality as QuestionQualityApi;
+use log::*;
+use serde::Deserialize;
+use service::config::ApiVersion;
+use utoipa::IntoParams;
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct QuestionQualityParams {
+    /// Reclassify the transcript instead of using the stored summary.
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+/// GET counts of the coach's open and closed questions, advice and inquiry in a coaching
+/// session's latest transcript (coach only)
+#[utoipa::path(
+    get,
+    path = "/coaching_sessions/{coaching_session_id}/question_quality",
+    params(
+        ApiVersion,
+        ("coaching_session_id" = Id, Path, description = "Coaching session id"),
+        QuestionQualityParams,
+    ),
+    responses(
+        (status = 200, description = "Question quality summary retrieved", body = domain::question_quality_summaries::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "The session has no completed transcription"),
+        (status = 422, description = "Transcripts may not be analyzed for this coaching relationship"),
+        (status = 503, description = "Service temporarily unavailable"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn read(
+    CompareApiVersion(_v): CompareApiVersion,
+    CoachingSessionAccess(session): CoachingSessionAccess,
+    AuthenticatedUser(user): AuthenticatedUser,
+    State(app_state): State<AppState>,
+    Query(params): Query<QuestionQualityParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("GET question quality for session {}", session.id);
+
+    let summary = QuestionQualityApi::summary(
+        app_state.db_conn_ref(),
+        app_state.analysis_provider.as_deref(),
+        user.id,
+        session.id,
+        params.refresh,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), summary)))
+}
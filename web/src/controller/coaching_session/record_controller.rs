@@ -0,0 +1,80 @@
+use crate::controller::ApiResponse;
+use crate::extractors::{
+    coaching_session_access::CoachingSessionAccess, compare_api_version::CompareApiVersion,
+};
+use crate::{AppState, Error};
+use axum::extract::{Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use domain::session_record::{self as SessionRecordApi, Record};
+use log::*;
+use serde::Deserialize;
+use service::config::ApiVersion;
+use utoipa::IntoParams;
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct RecordParams {
+    /// Compile the record afresh from the session as it is now.
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+/// GET a coaching session's record of engagement (either participant)
+///
+/// A single PDF with the session's summary, agreements, actions with their status, notes
+/// and the consent in force for it, e.g. as evidence for a coaching certification. Records
+/// are compiled in the background: until the session's record is compiled, this queues it
+/// and answers 202 Accepted with where it stands, so poll until the PDF is returned.
+#[utoipa::path(
+    get,
+    path = "/coaching_sessions/{coaching_session_id}/record",
+    params(
+        ApiVersion,
+        ("coaching_session_id" = Id, Path, description = "Coaching session id"),
+        RecordParams,
+    ),
+    responses(
+        (status = 200, description = "The compiled record", content_type = "application/pdf", body = Vec<u8>),
+        (status = 202, description = "The record is queued to be compiled (or failed to be)", body = domain::session_records::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Coaching session not found"),
+        (status = 503, description = "Object storage for records isn't configured"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn read(
+    CompareApiVersion(_v): CompareApiVersion,
+    CoachingSessionAccess(session): CoachingSessionAccess,
+    State(app_state): State<AppState>,
+    Query(params): Query<RecordParams>,
+) -> Result<Response, Error> {
+    debug!("GET record of engagement for session {}", session.id);
+
+    let record = SessionRecordApi::fetch(
+        app_state.db_conn_ref(),
+        &app_state.config,
+        &session,
+        params.refresh,
+    )
+    .await?;
+
+    Ok(match record {
+        Record::Document { file_name, bytes } => (
+            [
+                (header::CONTENT_TYPE, "application/pdf".to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{file_name}\""),
+                ),
+            ],
+            bytes,
+        )
+            .into_response(),
+        Record::Queued(record) => (
+            StatusCode::ACCEPTED,
+            Json(ApiResponse::new(StatusCode::ACCEPTED.into(), record)),
+        )
+            .into_response(),
+    })
+}
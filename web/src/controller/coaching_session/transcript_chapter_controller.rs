@@ -0,0 +1,41 @@
+use crate::controller::ApiResponse;
+use crate::extractors::{
+    coaching_session_access::CoachingSessionAccess, compare_api_version::CompareApiVersion,
+};
+use crate::{AppState, Error};
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::transcript_chapter as TranscriptChapterApi;
+use log::*;
+use service::config::ApiVersion;
+
+/// GET the chapters of a coaching session's transcript ordered by start time (powers
+/// transcript and playback navigation)
+#[utoipa::path(
+    get,
+    path = "/coaching_sessions/{coaching_session_id}/transcript/chapters",
+    params(
+        ApiVersion,
+        ("coaching_session_id" = Id, Path, description = "Coaching session id"),
+    ),
+    responses(
+        (status = 200, description = "Transcript chapters retrieved ordered by start time"),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Service temporarily unavailable"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn index(
+    CompareApiVersion(_v): CompareApiVersion,
+    CoachingSessionAccess(session): CoachingSessionAccess,
+    State(app_state): State<AppState>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("GET transcript chapters for session {}", session.id);
+
+    let chapters =
+        TranscriptChapterApi::find_by_coaching_session(app_state.db_conn_ref(), session.id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), chapters)))
+}
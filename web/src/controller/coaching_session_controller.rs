@@ -12,7 +12,9 @@ use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::Json;
-use domain::{coaching_session as CoachingSessionApi, emails as EmailsApi, Id};
+use domain::{
+    calendar as CalendarApi, coaching_session as CoachingSessionApi, emails as EmailsApi, Id,
+};
 use service::config::ApiVersion;
 
 use log::*;
@@ -132,7 +134,8 @@ pub async fn index(
     )))
 }
 
-/// POST create a new Coaching Session
+/// POST create a new Coaching Session. The response's `warnings` say whether a
+/// participant is out of office then or the session falls on an organization holiday.
 #[utoipa::path(
     post,
     path = "/coaching_sessions",
@@ -182,10 +185,11 @@ pub async fn create(
     )
     .await;
 
-    Ok(Json(ApiResponse::new(
-        StatusCode::CREATED.into(),
-        coaching_session,
-    )))
+    let warnings = CalendarApi::booking_warnings(app_state.db_conn_ref(), &coaching_session).await;
+
+    Ok(Json(
+        ApiResponse::new(StatusCode::CREATED.into(), coaching_session).with_warnings(warnings),
+    ))
 }
 
 /// PUT update a Coaching Session
@@ -276,16 +280,41 @@ pub async fn delete(
     State(app_state): State<AppState>,
     Path(coaching_session_id): Path<Id>,
 ) -> Result<impl IntoResponse, Error> {
-    CoachingSessionApi::delete(
-        app_state.db_conn_ref(),
-        &app_state.config,
-        coaching_session_id,
-    )
-    .await?;
+    CoachingSessionApi::delete(app_state.db_conn_ref(), coaching_session_id).await?;
 
     Ok(Json(ApiResponse::new(StatusCode::NO_CONTENT.into(), ())))
 }
 
+/// POST restore a deleted Coaching Session, with its notes, agreements and actions
+#[utoipa::path(
+    post,
+    path = "/coaching_sessions/{id}/restore",
+    params(ApiVersion, ("id" = Id, Path, description = "Id of the deleted Coaching Session to restore")),
+    responses(
+        (status = 200, description = "Successfully restored a Coaching Session", body = coaching_sessions::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "No deleted Coaching Session with this id"),
+        (status = 503, description = "Service temporarily unavailable"),
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn restore(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(_user): AuthenticatedUser,
+    State(app_state): State<AppState>,
+    Path(coaching_session_id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    let coaching_session =
+        CoachingSessionApi::restore(app_state.db_conn_ref(), coaching_session_id).await?;
+
+    Ok(Json(ApiResponse::new(
+        StatusCode::OK.into(),
+        coaching_session,
+    )))
+}
+
 #[cfg(test)]
 #[cfg(feature = "mock")]
 mod tests {
@@ -321,6 +350,8 @@ mod tests {
             created_at: now.into(),
             updated_at: now.into(),
             hydrated_at: None,
+            session_type_id: None,
+            deleted_at: None,
         }
     }
 
@@ -267,6 +267,8 @@ mod tests {
             coach_id: Id::new_v4(),
             coachee_id: Id::new_v4(),
             slug: "test".to_string(),
+            ai_privacy_level: Default::default(),
+            pro_bono: false,
             created_at: now.into(),
             updated_at: now.into(),
         };
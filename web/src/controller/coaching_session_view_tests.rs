@@ -67,6 +67,8 @@ fn test_session(session_id: Id, relationship_id: Id) -> coaching_sessions::Model
         created_at: now.into(),
         updated_at: now.into(),
         hydrated_at: Some(now.into()),
+        session_type_id: None,
+        deleted_at: None,
     }
 }
 
@@ -130,6 +132,7 @@ async fn view_returns_200_for_participant() {
     let db = Arc::new(
         MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results([vec![(user.clone(), role.clone())]])
+            .append_query_results([Vec::<domain::policy_documents::Model>::new()])
             .append_query_results([vec![(user.clone(), role.clone())]])
             .append_query_results(vec![vec![(
                 test_session(session_id, relationship_id),
@@ -139,6 +142,8 @@ async fn view_returns_200_for_participant() {
                     coachee_id: user.id,
                     organization_id: Id::new_v4(),
                     slug: "test".to_string(),
+                    ai_privacy_level: Default::default(),
+                    pro_bono: false,
                     created_at: now.into(),
                     updated_at: now.into(),
                 },
@@ -180,6 +185,7 @@ async fn view_returns_401_for_non_participant() {
     let db = Arc::new(
         MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results([vec![(user.clone(), role.clone())]])
+            .append_query_results([Vec::<domain::policy_documents::Model>::new()])
             .append_query_results([vec![(user.clone(), role.clone())]])
             .append_query_results(vec![vec![(
                 test_session(session_id, relationship_id),
@@ -189,6 +195,8 @@ async fn view_returns_401_for_non_participant() {
                     coachee_id: Id::new_v4(),
                     organization_id: Id::new_v4(),
                     slug: "test".to_string(),
+                    ai_privacy_level: Default::default(),
+                    pro_bono: false,
                     created_at: now.into(),
                     updated_at: now.into(),
                 },
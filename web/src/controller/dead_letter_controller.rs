@@ -0,0 +1,110 @@
+//! Admin endpoints for events that event handlers failed to process.
+//!
+//! Gated by SuperAdmin via the `protect::dead_letters::admin_only` middleware in the
+//! router.
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::{dead_letter as DeadLetterApi, Id};
+use service::config::ApiVersion;
+
+use crate::controller::ApiResponse;
+use crate::extractors::compare_api_version::CompareApiVersion;
+use crate::params::dead_letter::IndexParams;
+use crate::{AppState, Error};
+
+/// INDEX dead-lettered events, newest first, optionally filtered by handler and event type
+#[utoipa::path(
+    get,
+    path = "/admin/dead_letters",
+    params(
+        ApiVersion,
+        IndexParams,
+    ),
+    responses(
+        (status = 200, description = "Dead letters retrieved", body = [domain::dead_letter_events::Model]),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - SuperAdmin only"),
+        (status = 422, description = "`limit` out of range"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn index(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Query(params): Query<IndexParams>,
+) -> Result<impl IntoResponse, Error> {
+    let dead_letters = DeadLetterApi::find(
+        app_state.db_conn_ref(),
+        params.handler_name.as_deref(),
+        params.event_type.as_deref(),
+        params.include_requeued,
+        params.limit,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), dead_letters)))
+}
+
+/// POST a dead-lettered event back to the handler that failed on it
+///
+/// Responds with the dead letter: `requeued_at` is set when the handler processed the
+/// event, otherwise `error` and `attempts` reflect the new failure.
+#[utoipa::path(
+    post,
+    path = "/admin/dead_letters/{id}/requeue",
+    params(
+        ApiVersion,
+        ("id" = Id, Path, description = "Dead letter id"),
+    ),
+    responses(
+        (status = 200, description = "Event redelivered", body = domain::dead_letter_events::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - SuperAdmin only"),
+        (status = 404, description = "Dead letter not found"),
+        (status = 422, description = "Already requeued, or its handler is no longer registered"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn requeue(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    let dead_letter =
+        DeadLetterApi::requeue(app_state.db_conn_ref(), &app_state.event_publisher, id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), dead_letter)))
+}
+
+/// POST a dead-lettered event to be dispatched again, once the handler is fixed
+///
+/// The same as requeueing: the event goes back to the handler that failed on it.
+#[utoipa::path(
+    post,
+    path = "/admin/dead_letters/{id}/replay",
+    params(
+        ApiVersion,
+        ("id" = Id, Path, description = "Dead letter id"),
+    ),
+    responses(
+        (status = 200, description = "Event redelivered", body = domain::dead_letter_events::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - SuperAdmin only"),
+        (status = 404, description = "Dead letter not found"),
+        (status = 422, description = "Already replayed, or its handler is no longer registered"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn replay(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    let dead_letter =
+        DeadLetterApi::requeue(app_state.db_conn_ref(), &app_state.event_publisher, id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), dead_letter)))
+}
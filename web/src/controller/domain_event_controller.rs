@@ -0,0 +1,97 @@
+//! Endpoints reading the domain event store: the admin audit log and a coaching
+//! relationship's activity feed.
+//!
+//! The audit log is gated by SuperAdmin via the `protect::domain_events::admin_only`
+//! middleware in the router; the activity feed by `CoachingRelationshipAccess`.
+
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::event_log as EventLogApi;
+use service::config::ApiVersion;
+
+use crate::controller::ApiResponse;
+use crate::extractors::{
+    coaching_relationship_access::CoachingRelationshipAccess,
+    compare_api_version::CompareApiVersion,
+};
+use crate::params::domain_event::{ActivityParams, IndexParams};
+use crate::{AppState, Error};
+
+use log::*;
+
+/// INDEX stored domain events, newest first, optionally filtered by entity type and date range
+///
+/// Pages with `cursor`: pass the response's `next_cursor` to fetch the next page.
+#[utoipa::path(
+    get,
+    path = "/admin/events",
+    params(
+        ApiVersion,
+        IndexParams,
+    ),
+    responses(
+        (status = 200, description = "Stored events retrieved", body = domain::event_log::EventPage),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - SuperAdmin only"),
+        (status = 422, description = "`from` not before `to`, `limit` out of range, or an invalid `cursor`"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn index(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Query(params): Query<IndexParams>,
+) -> Result<impl IntoResponse, Error> {
+    let events = EventLogApi::find(
+        app_state.db_conn_ref(),
+        params.entity_type.as_deref(),
+        params.from,
+        params.to,
+        params.cursor.as_deref(),
+        params.limit,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), events)))
+}
+
+/// GET the activity feed of a coaching relationship: the events that happened in it,
+/// newest first
+///
+/// Pages with `cursor`: pass the response's `next_cursor` to fetch the next page.
+#[utoipa::path(
+    get,
+    path = "/coaching_relationships/{relationship_id}/activity",
+    params(
+        ApiVersion,
+        ("relationship_id" = Id, Path, description = "Coaching relationship whose activity to retrieve"),
+        ActivityParams,
+    ),
+    responses(
+        (status = 200, description = "Activity retrieved", body = domain::event_log::EventPage),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Coaching relationship not found"),
+        (status = 422, description = "`limit` out of range, or an invalid `cursor`"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn activity(
+    CompareApiVersion(_v): CompareApiVersion,
+    CoachingRelationshipAccess(relationship): CoachingRelationshipAccess,
+    State(app_state): State<AppState>,
+    Query(params): Query<ActivityParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("GET activity for relationship {}", relationship.id);
+
+    let page = EventLogApi::activity(
+        app_state.db_conn_ref(),
+        relationship.id,
+        params.cursor.as_deref(),
+        params.limit,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), page)))
+}
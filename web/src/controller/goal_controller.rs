@@ -1,8 +1,12 @@
 use crate::controller::ApiResponse;
 use crate::extractors::{
-    authenticated_user::AuthenticatedUser, compare_api_version::CompareApiVersion,
+    authenticated_user::AuthenticatedUser,
+    coaching_relationship_access::CoachingRelationshipAccess,
+    compare_api_version::CompareApiVersion,
+    if_match::{with_etag, IfMatch},
 };
-use crate::params::goal::{IndexParams, SortField};
+use crate::params::goal::{IncludeParam, IndexParams, SortField};
+use crate::params::goal_template::InstantiateParams;
 use crate::params::WithSortDefaults;
 use crate::{AppState, Error};
 use axum::extract::{Path, Query, State};
@@ -11,6 +15,7 @@ use axum::response::IntoResponse;
 use axum::Json;
 use domain::goal as GoalApi;
 use domain::goal_progress as GoalProgressApi;
+use domain::goal_template as GoalTemplateApi;
 use domain::{goals::Model, Id};
 use serde_json::json;
 use service::config::ApiVersion;
@@ -85,6 +90,57 @@ pub async fn create(
     Ok(Json(ApiResponse::new(StatusCode::CREATED.into(), goal)))
 }
 
+/// POST a new Goal in a coaching relationship from one of its organization's templates.
+///
+/// Copies the template's title, body and target duration, and creates an action
+/// per milestone on `coaching_session_id`. Participant-only.
+#[utoipa::path(
+    post,
+    path = "/coaching_relationships/{relationship_id}/overarching_goals/from_template/{template_id}",
+    params(
+        ApiVersion,
+        ("relationship_id" = Id, Path, description = "Coaching relationship to create the goal in"),
+        ("template_id" = Id, Path, description = "Goal template to copy"),
+    ),
+    request_body = Option<crate::params::goal_template::InstantiateParams>,
+    responses(
+        (status = 201, description = "Successfully created a Goal and its milestone actions from the template"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Coaching relationship, template or session not found"),
+        (status = 422, description = "Session missing or outside the relationship, or in-progress goal limit reached"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn create_from_template(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    CoachingRelationshipAccess(relationship): CoachingRelationshipAccess,
+    State(app_state): State<AppState>,
+    Path((_relationship_id, template_id)): Path<(Id, Id)>,
+    params: Option<Json<InstantiateParams>>,
+) -> Result<impl IntoResponse, Error> {
+    debug!(
+        "POST Create a Goal from template {template_id} in relationship {}",
+        relationship.id
+    );
+
+    let coaching_session_id = params.and_then(|Json(params)| params.coaching_session_id);
+    let created = GoalTemplateApi::instantiate(
+        app_state.db_conn_ref(),
+        app_state.event_publisher.as_ref(),
+        user.id,
+        &relationship,
+        template_id,
+        coaching_session_id,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::CREATED.into(), created)))
+}
+
 /// GET a particular Goal specified by its id.
 #[utoipa::path(
     get,
@@ -94,7 +150,8 @@ pub async fn create(
         ("id" = String, Path, description = "Goal id to retrieve")
     ),
     responses(
-        (status = 200, description = "Successfully retrieved a specific Goal by its id", body = [entity::goals::Model]),
+        (status = 200, description = "Successfully retrieved a specific Goal by its id", body = [entity::goals::Model],
+            headers(("ETag" = String, description = "Version of the resource to send back in If-Match"))),
         (status = 401, description = "Unauthorized"),
         (status = 404, description = "Goal not found"),
         (status = 405, description = "Method not allowed"),
@@ -113,7 +170,10 @@ pub async fn read(
 
     let goal = GoalApi::find_by_id(app_state.db_conn_ref(), id).await?;
 
-    Ok(Json(ApiResponse::new(StatusCode::OK.into(), goal)))
+    Ok(with_etag(
+        goal.updated_at,
+        Json(ApiResponse::new(StatusCode::OK.into(), goal)),
+    ))
 }
 
 #[utoipa::path(
@@ -125,8 +185,10 @@ pub async fn read(
     ),
     request_body = entity::goals::Model,
     responses(
-        (status = 200, description = "Successfully Updated Goal", body = [entity::goals::Model]),
+        (status = 200, description = "Successfully Updated Goal", body = [entity::goals::Model],
+            headers(("ETag" = String, description = "Version of the resource to send back in If-Match"))),
         (status = 401, description = "Unauthorized"),
+        (status = 412, description = "Changed since the version named in If-Match"),
         (status = 405, description = "Method not allowed"),
         (status = 503, description = "Service temporarily unavailable")
     ),
@@ -138,6 +200,7 @@ pub async fn update(
     CompareApiVersion(_v): CompareApiVersion,
     AuthenticatedUser(_user): AuthenticatedUser,
     State(app_state): State<AppState>,
+    if_match: IfMatch,
     Path(id): Path<Id>,
     Json(goal_model): Json<Model>,
 ) -> Result<impl IntoResponse, Error> {
@@ -148,12 +211,16 @@ pub async fn update(
         app_state.event_publisher.as_ref(),
         id,
         goal_model,
+        if_match.expected(),
     )
     .await?;
 
     debug!("Updated Goal: {goal:?}");
 
-    Ok(Json(ApiResponse::new(StatusCode::OK.into(), goal)))
+    Ok(with_etag(
+        goal.updated_at,
+        Json(ApiResponse::new(StatusCode::OK.into(), goal)),
+    ))
 }
 
 #[utoipa::path(
@@ -166,8 +233,10 @@ pub async fn update(
     ),
     request_body = entity::actions::Model,
     responses(
-        (status = 200, description = "Successfully Updated Goal", body = [entity::goals::Model]),
+        (status = 200, description = "Successfully Updated Goal", body = [entity::goals::Model],
+            headers(("ETag" = String, description = "Version of the resource to send back in If-Match"))),
         (status = 401, description = "Unauthorized"),
+        (status = 412, description = "Changed since the version named in If-Match"),
         (status = 405, description = "Method not allowed"),
         (status = 503, description = "Service temporarily unavailable")
     ),
@@ -181,6 +250,7 @@ pub async fn update_status(
     Query(status): Query<String>,
     Path(id): Path<Id>,
     State(app_state): State<AppState>,
+    if_match: IfMatch,
 ) -> Result<impl IntoResponse, Error> {
     debug!("PUT Update Goal Status with id: {id}");
 
@@ -189,12 +259,16 @@ pub async fn update_status(
         app_state.event_publisher.as_ref(),
         id,
         status.as_str().into(),
+        if_match.expected(),
     )
     .await?;
 
     debug!("Updated Goal: {goal:?}");
 
-    Ok(Json(ApiResponse::new(StatusCode::OK.into(), goal)))
+    Ok(with_etag(
+        goal.updated_at,
+        Json(ApiResponse::new(StatusCode::OK.into(), goal)),
+    ))
 }
 
 /// DELETE a Goal by id
@@ -234,19 +308,59 @@ pub async fn delete(
     Ok(Json(json!({"id": id})))
 }
 
+/// POST restore a deleted Goal, until it's purged
+#[utoipa::path(
+    post,
+    path = "/goals/{id}/restore",
+    params(
+        ApiVersion,
+        ("id" = Id, Path, description = "Id of the deleted goal to restore"),
+    ),
+    responses(
+        (status = 200, description = "Successfully restored Goal", body = entity::goals::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "No deleted goal with this id"),
+        (status = 405, description = "Method not allowed"),
+        (status = 422, description = "The relationship already has as many in-progress goals as allowed"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn restore(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(_user): AuthenticatedUser,
+    State(app_state): State<AppState>,
+    Path(id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("POST restore Goal by id: {id}");
+
+    let goal = GoalApi::restore(
+        app_state.db_conn_ref(),
+        app_state.event_publisher.as_ref(),
+        id,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), goal)))
+}
+
 #[utoipa::path(
     get,
     path = "/goals",
     params(
         ApiVersion,
-        ("coaching_relationship_id" = Id, Query, description = "Filter by coaching_relationship_id"),
-        ("status" = Option<domain::status::Status>, Query, description = "Filter by status (e.g., 'InProgress', 'Completed')"),
+        ("filter[coaching_relationship_id]" = Id, Query, description = "Filter by coaching_relationship_id (also accepted as coaching_relationship_id)"),
+        ("filter[status]" = Option<String>, Query, description = "Filter by status: 'not_started', 'in_progress', 'completed', 'on_hold' or 'wont_do' (also accepted as status, e.g. 'InProgress')", example = "in_progress"),
+        ("sort" = Option<String>, Query, description = "Sort by a field, descending when prefixed with '-'. Takes precedence over sort_by and sort_order.", example = "title"),
         ("sort_by" = Option<crate::params::goal::SortField>, Query, description = "Sort by field. Valid values: 'title', 'created_at', 'updated_at'. Must be provided with sort_order.", example = "title"),
-        ("sort_order" = Option<crate::params::sort::SortOrder>, Query, description = "Sort order. Valid values: 'asc' (ascending), 'desc' (descending). Must be provided with sort_by.", example = "desc")
+        ("sort_order" = Option<crate::params::sort::SortOrder>, Query, description = "Sort order. Valid values: 'asc' (ascending), 'desc' (descending). Must be provided with sort_by.", example = "desc"),
+        ("include" = Option<String>, Query, description = "Comma-separated related resources to load with each goal: 'session' (the session it was created in), 'relationship'", example = "relationship")
     ),
     responses(
-        (status = 200, description = "Successfully retrieved all Goals", body = [entity::goals::Model]),
-        (status = 400, description = "Invalid query parameter value"),
+        (status = 200, description = "Successfully retrieved all Goals with any included resources", body = [domain::goal::EnrichedGoal]),
+        (status = 400, description = "Unknown or invalid filter, sort or include parameter"),
         (status = 401, description = "Unauthorized"),
         (status = 405, description = "Method not allowed"),
         (status = 503, description = "Service temporarily unavailable")
@@ -266,16 +380,23 @@ pub async fn index(
 
     // Apply default sorting parameters
     let mut params = params;
-    IndexParams::apply_sort_defaults(
+    IndexParams::apply_sort(
+        params.sort.take(),
         &mut params.sort_by,
         &mut params.sort_order,
         SortField::Title,
     );
+    let includes = GoalApi::IncludeOptions {
+        session: params.include.contains(&IncludeParam::Session),
+        relationship: params.include.contains(&IncludeParam::Relationship),
+    };
 
     let goals = GoalApi::find_by(app_state.db_conn_ref(), params).await?;
 
     debug!("Found Goals: {goals:?}");
 
+    let goals = GoalApi::with_includes(app_state.db_conn_ref(), goals, includes).await?;
+
     Ok(Json(ApiResponse::new(StatusCode::OK.into(), goals)))
 }
 
@@ -309,3 +430,34 @@ pub async fn progress(
 
     Ok(Json(ApiResponse::new(StatusCode::OK.into(), metrics)))
 }
+
+/// GET the effort burndown for a specific goal
+#[utoipa::path(
+    get,
+    path = "/goals/{id}/burndown",
+    params(
+        ApiVersion,
+        ("id" = Id, Path, description = "Goal id"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the goal's burndown series"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Goal not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn burndown(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(_user): AuthenticatedUser,
+    State(app_state): State<AppState>,
+    Path(id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("GET Goal burndown for id: {id}");
+
+    let burndown = GoalProgressApi::burndown(app_state.db_conn_ref(), id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), burndown)))
+}
@@ -0,0 +1,199 @@
+use crate::controller::ApiResponse;
+use crate::extractors::{
+    authenticated_user::AuthenticatedUser,
+    coaching_relationship_access::CoachingRelationshipAccess,
+    compare_api_version::CompareApiVersion,
+};
+use crate::{AppState, Error};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::{journal_entries::Model, journal_entry as JournalEntryApi, Id};
+use service::config::ApiVersion;
+
+use log::*;
+
+/// GET a coaching relationship's journal entries, newest first. The coachee sees all of
+/// their entries; the coach sees only those shared with them.
+#[utoipa::path(
+    get,
+    path = "/coaching_relationships/{relationship_id}/journal_entries",
+    params(
+        ApiVersion,
+        ("relationship_id" = Id, Path, description = "Coaching relationship to list journal entries for"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the visible journal entries", body = [domain::journal_entries::Model]),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Coaching relationship not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn index(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    CoachingRelationshipAccess(relationship): CoachingRelationshipAccess,
+    State(app_state): State<AppState>,
+) -> Result<impl IntoResponse, Error> {
+    let entries =
+        JournalEntryApi::find_by_relationship(app_state.db_conn_ref(), user.id, &relationship)
+            .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), entries)))
+}
+
+/// POST a new journal entry. Coachee-only; it stays private unless `shared_with_coach` is set.
+#[utoipa::path(
+    post,
+    path = "/coaching_relationships/{relationship_id}/journal_entries",
+    params(
+        ApiVersion,
+        ("relationship_id" = Id, Path, description = "Coaching relationship to journal in"),
+    ),
+    request_body = domain::journal_entries::Model,
+    responses(
+        (status = 201, description = "Journal entry created", body = domain::journal_entries::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Coaching relationship not found"),
+        (status = 422, description = "Empty body or overlong title"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn create(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    CoachingRelationshipAccess(relationship): CoachingRelationshipAccess,
+    State(app_state): State<AppState>,
+    Json(entry_model): Json<Model>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("POST journal entry in relationship {}", relationship.id);
+
+    let entry =
+        JournalEntryApi::create(app_state.db_conn_ref(), user.id, &relationship, entry_model)
+            .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::CREATED.into(), entry)))
+}
+
+/// GET a journal entry. Visible to its author, and to the coach once shared.
+#[utoipa::path(
+    get,
+    path = "/coaching_relationships/{relationship_id}/journal_entries/{id}",
+    params(
+        ApiVersion,
+        ("relationship_id" = Id, Path, description = "Coaching relationship the entry belongs to"),
+        ("id" = Id, Path, description = "Journal entry to retrieve"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the journal entry", body = domain::journal_entries::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Journal entry not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn read(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    CoachingRelationshipAccess(relationship): CoachingRelationshipAccess,
+    State(app_state): State<AppState>,
+    Path((_relationship_id, id)): Path<(Id, Id)>,
+) -> Result<impl IntoResponse, Error> {
+    let entry =
+        JournalEntryApi::find_by_id(app_state.db_conn_ref(), user.id, &relationship, id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), entry)))
+}
+
+/// PUT a journal entry's title, body and sharing. Author-only.
+#[utoipa::path(
+    put,
+    path = "/coaching_relationships/{relationship_id}/journal_entries/{id}",
+    params(
+        ApiVersion,
+        ("relationship_id" = Id, Path, description = "Coaching relationship the entry belongs to"),
+        ("id" = Id, Path, description = "Journal entry to update"),
+    ),
+    request_body = domain::journal_entries::Model,
+    responses(
+        (status = 200, description = "Journal entry updated", body = domain::journal_entries::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Journal entry not found"),
+        (status = 422, description = "Empty body or overlong title"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn update(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    CoachingRelationshipAccess(relationship): CoachingRelationshipAccess,
+    State(app_state): State<AppState>,
+    Path((_relationship_id, id)): Path<(Id, Id)>,
+    Json(entry_model): Json<Model>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("PUT journal entry {id} in relationship {}", relationship.id);
+
+    let entry = JournalEntryApi::update(
+        app_state.db_conn_ref(),
+        user.id,
+        &relationship,
+        id,
+        entry_model,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), entry)))
+}
+
+/// DELETE a journal entry. Author-only.
+#[utoipa::path(
+    delete,
+    path = "/coaching_relationships/{relationship_id}/journal_entries/{id}",
+    params(
+        ApiVersion,
+        ("relationship_id" = Id, Path, description = "Coaching relationship the entry belongs to"),
+        ("id" = Id, Path, description = "Journal entry to delete"),
+    ),
+    responses(
+        (status = 204, description = "Journal entry deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Journal entry not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn delete(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    CoachingRelationshipAccess(relationship): CoachingRelationshipAccess,
+    State(app_state): State<AppState>,
+    Path((_relationship_id, id)): Path<(Id, Id)>,
+) -> Result<impl IntoResponse, Error> {
+    info!(
+        "Deleting journal entry {id} in relationship {}",
+        relationship.id
+    );
+
+    JournalEntryApi::delete(app_state.db_conn_ref(), user.id, &relationship, id).await?;
+
+    Ok(Json(ApiResponse::<()>::no_content(
+        StatusCode::NO_CONTENT.into(),
+    )))
+}
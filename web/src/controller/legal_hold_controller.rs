@@ -0,0 +1,102 @@
+//! Admin endpoints for legal holds, which preserve an organization's or a coaching
+//! relationship's data while litigation requires it.
+//!
+//! Gated by SuperAdmin via the `protect::legal_holds::admin_only` middleware in the
+//! router.
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::{legal_hold as LegalHoldApi, legal_holds::Model, Id};
+use service::config::ApiVersion;
+
+use crate::controller::ApiResponse;
+use crate::extractors::{
+    authenticated_user::AuthenticatedUser, compare_api_version::CompareApiVersion,
+};
+use crate::params::legal_hold::IndexParams;
+use crate::{AppState, Error};
+
+/// INDEX legal holds, newest first, released ones included unless `active` is set
+#[utoipa::path(
+    get,
+    path = "/admin/legal_holds",
+    params(
+        ApiVersion,
+        IndexParams,
+    ),
+    responses(
+        (status = 200, description = "Legal holds retrieved", body = [domain::legal_holds::Model]),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - SuperAdmin only"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn index(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Query(params): Query<IndexParams>,
+) -> Result<impl IntoResponse, Error> {
+    let holds = LegalHoldApi::find(app_state.db_conn_ref(), params.active).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), holds)))
+}
+
+/// POST a legal hold on an organization or a coaching relationship
+///
+/// Exactly one of `organization_id` and `coaching_relationship_id` must be set. While the
+/// hold is active, retention purges pause and deleting the data it covers fails.
+#[utoipa::path(
+    post,
+    path = "/admin/legal_holds",
+    params(ApiVersion),
+    request_body = domain::legal_holds::Model,
+    responses(
+        (status = 201, description = "Legal hold placed", body = domain::legal_holds::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - SuperAdmin only"),
+        (status = 404, description = "Organization or coaching relationship not found"),
+        (status = 409, description = "A hold is already active there"),
+        (status = 422, description = "Not exactly one target, or no reason given"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn create(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    State(app_state): State<AppState>,
+    Json(hold): Json<Model>,
+) -> Result<impl IntoResponse, Error> {
+    let hold = LegalHoldApi::place(app_state.db_conn_ref(), user.id, hold).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::CREATED.into(), hold)))
+}
+
+/// POST the release of a legal hold, resuming purges and deletion of the data it covered
+#[utoipa::path(
+    post,
+    path = "/admin/legal_holds/{id}/release",
+    params(
+        ApiVersion,
+        ("id" = Id, Path, description = "Legal hold id"),
+    ),
+    responses(
+        (status = 200, description = "Legal hold released", body = domain::legal_holds::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - SuperAdmin only"),
+        (status = 404, description = "Legal hold not found"),
+        (status = 409, description = "Already released"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn release(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    State(app_state): State<AppState>,
+    Path(id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    let hold = LegalHoldApi::release(app_state.db_conn_ref(), user.id, id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), hold)))
+}
@@ -0,0 +1,137 @@
+use crate::controller::ApiResponse;
+use crate::extractors::{
+    authenticated_user::AuthenticatedUser,
+    coaching_relationship_access::CoachingRelationshipAccess,
+    compare_api_version::CompareApiVersion,
+};
+use crate::params::library::AssignParams;
+use crate::{AppState, Error};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::{library as LibraryApi, Id};
+use service::config::ApiVersion;
+
+use log::*;
+
+/// POST assign a library item to the coachee of a coaching relationship. Coach-only;
+/// the coachee is notified.
+#[utoipa::path(
+    post,
+    path = "/coaching_relationships/{relationship_id}/assignments",
+    params(
+        ApiVersion,
+        ("relationship_id" = Id, Path, description = "Coaching relationship to assign the item in"),
+    ),
+    request_body = crate::params::library::AssignParams,
+    responses(
+        (status = 201, description = "Library item assigned", body = domain::library::AssignmentWithItem),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Coaching relationship or library item not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn create(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    CoachingRelationshipAccess(relationship): CoachingRelationshipAccess,
+    State(app_state): State<AppState>,
+    Json(params): Json<AssignParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!(
+        "POST assign library item {} in relationship {}",
+        params.library_item_id, relationship.id
+    );
+
+    let assignment = LibraryApi::assign(
+        app_state.db_conn_ref(),
+        app_state.event_publisher.as_ref(),
+        user.id,
+        &relationship,
+        params.library_item_id,
+        params.due_by,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(
+        StatusCode::CREATED.into(),
+        assignment,
+    )))
+}
+
+/// GET a coaching relationship's library assignments, soonest due first.
+#[utoipa::path(
+    get,
+    path = "/coaching_relationships/{relationship_id}/assignments",
+    params(
+        ApiVersion,
+        ("relationship_id" = Id, Path, description = "Coaching relationship to list assignments for"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the relationship's assignments", body = [domain::library::AssignmentWithItem]),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Coaching relationship not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn index(
+    CompareApiVersion(_v): CompareApiVersion,
+    CoachingRelationshipAccess(relationship): CoachingRelationshipAccess,
+    State(app_state): State<AppState>,
+) -> Result<impl IntoResponse, Error> {
+    let assignments =
+        LibraryApi::find_assignments(app_state.db_conn_ref(), relationship.id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), assignments)))
+}
+
+/// POST mark a library assignment completed. Either participant may complete it and
+/// the other is notified; completing it again is a no-op.
+#[utoipa::path(
+    post,
+    path = "/coaching_relationships/{relationship_id}/assignments/{assignment_id}/complete",
+    params(
+        ApiVersion,
+        ("relationship_id" = Id, Path, description = "Coaching relationship the assignment belongs to"),
+        ("assignment_id" = Id, Path, description = "Assignment to complete"),
+    ),
+    responses(
+        (status = 200, description = "Assignment completed", body = domain::library::AssignmentWithItem),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Coaching relationship or assignment not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn complete(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    CoachingRelationshipAccess(relationship): CoachingRelationshipAccess,
+    State(app_state): State<AppState>,
+    Path((_relationship_id, assignment_id)): Path<(Id, Id)>,
+) -> Result<impl IntoResponse, Error> {
+    debug!(
+        "POST complete assignment {assignment_id} in relationship {}",
+        relationship.id
+    );
+
+    let assignment = LibraryApi::complete(
+        app_state.db_conn_ref(),
+        app_state.event_publisher.as_ref(),
+        user.id,
+        &relationship,
+        assignment_id,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), assignment)))
+}
@@ -59,7 +59,7 @@ pub(crate) async fn validate(
     request_body = CompleteSetupParams,
     responses(
         (status = 200, description = "User profile successfully updated", body = domain::users::Model),
-        (status = 422, description = "Password confirmation does not match"),
+        (status = 422, description = "Password confirmation does not match, or the password fails the password policy"),
         (status = 503, description = "Service temporarily unavailable")
     )
 )]
@@ -67,7 +67,9 @@ pub(crate) async fn complete_setup(
     State(app_state): State<AppState>,
     Json(params): Json<CompleteSetupParams>,
 ) -> Result<impl IntoResponse, Error> {
-    let updated_user = MagicLinkTokenApi::complete_setup(app_state.db_conn_ref(), params).await?;
+    let updated_user =
+        MagicLinkTokenApi::complete_setup(app_state.db_conn_ref(), &app_state.config, params)
+            .await?;
 
     Ok(Json(ApiResponse::new(StatusCode::OK.into(), updated_user)))
 }
@@ -0,0 +1,83 @@
+use crate::AppState;
+use axum::extract::State;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::IntoResponse;
+use log::*;
+use service::metrics::render_dead_letter_depth;
+
+/// GET event publishing, database pool and dead-letter queue metrics in the Prometheus
+/// text exposition format
+///
+/// Requires `Authorization: Bearer <METRICS_TOKEN>`; responds 404 when no metrics token
+/// is configured.
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    responses(
+        (status = 200, description = "Metrics in the Prometheus text format", body = String),
+        (status = 401, description = "Missing or wrong bearer token"),
+        (status = 404, description = "Metrics are disabled"),
+    )
+)]
+pub async fn read(State(app_state): State<AppState>, headers: HeaderMap) -> impl IntoResponse {
+    let Some(token) = app_state.config.metrics_token() else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let presented = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    if !presented.is_some_and(|presented| tokens_match(presented, &token)) {
+        warn!("Rejected metrics scrape without a valid bearer token");
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+
+    let mut body = app_state.event_metrics.render();
+    body.push_str(
+        &app_state.pool_health.render(
+            std::iter::once(("main", app_state.db_conn_ref())).chain(
+                app_state
+                    .heavy_database_connection
+                    .as_deref()
+                    .map(|heavy| ("heavy", heavy)),
+            ),
+        ),
+    );
+    // A failed count leaves the gauge out rather than failing the whole scrape.
+    match domain::dead_letter::depth(app_state.db_conn_ref()).await {
+        Ok(depths) => body.push_str(&render_dead_letter_depth(depths.iter().map(|depth| {
+            (
+                depth.handler_name.as_str(),
+                depth.event_type.as_str(),
+                depth.count,
+            )
+        }))),
+        Err(e) => warn!("Failed to count dead letters for metrics: {e:?}"),
+    }
+
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], body).into_response()
+}
+
+/// Compares in time independent of where the tokens first differ.
+fn tokens_match(presented: &str, expected: &str) -> bool {
+    presented.len() == expected.len()
+        && presented
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_match_requires_identical_tokens() {
+        assert!(tokens_match("s3cret", "s3cret"));
+        assert!(!tokens_match("s3cres", "s3cret"));
+        assert!(!tokens_match("s3cret-longer", "s3cret"));
+        assert!(!tokens_match("", "s3cret"));
+    }
+}
@@ -1,24 +1,47 @@
 use serde::Serialize;
 pub(crate) mod action_controller;
+pub(crate) mod action_work_log_controller;
+pub(crate) mod admin;
 pub(crate) mod agreement_controller;
+pub(crate) mod ai_controller;
+pub(crate) mod batch_controller;
+pub(crate) mod bootstrap_controller;
 pub(crate) mod coaching_session;
 pub(crate) mod coaching_session_controller;
 pub(crate) mod coaching_session_series_controller;
+pub(crate) mod dead_letter_controller;
+pub(crate) mod domain_event_controller;
 pub(crate) mod goal_controller;
 pub(crate) mod health_check_controller;
+pub(crate) mod journal_entry_controller;
 pub(crate) mod jwt_controller;
+pub(crate) mod legal_hold_controller;
+pub(crate) mod library_assignment_controller;
 pub(crate) mod magic_link_controller;
+pub(crate) mod metrics_controller;
 pub(crate) mod note_controller;
 pub(crate) mod oauth_callback_controller;
 pub(crate) mod oauth_controller;
+pub(crate) mod oauth_server_controller;
 pub(crate) mod organization;
 pub(crate) mod organization_controller;
 pub(crate) mod password_reset_controller;
+pub(crate) mod prompt_template_controller;
+pub(crate) mod push_controller;
+pub(crate) mod reaction_controller;
+pub(crate) mod resource_view_controller;
+pub(crate) mod runtime_config_controller;
+pub(crate) mod scim;
+pub(crate) mod search_controller;
+pub(crate) mod session_pulse_controller;
+pub(crate) mod session_request_controller;
+pub(crate) mod sse_connection_controller;
 pub(crate) mod tiptap_metrics_controller;
 pub(crate) mod user;
 pub(crate) mod user_controller;
 pub(crate) mod user_session_controller;
 pub(crate) mod webhook_controller;
+pub(crate) mod webhook_event_type_controller;
 
 #[cfg(test)]
 #[cfg(feature = "mock")]
@@ -30,6 +53,10 @@ struct ApiResponse<T: Serialize> {
     status_code: u16,
     #[serde(skip_serializing_if = "Option::is_none")]
     data: Option<T>,
+    /// Things the client should tell the user about a request that otherwise succeeded,
+    /// e.g. booking a session while a participant is out of office.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<String>,
 }
 
 impl<T: Serialize> ApiResponse<T> {
@@ -37,13 +64,20 @@ impl<T: Serialize> ApiResponse<T> {
         Self {
             status_code,
             data: Some(data),
+            warnings: Vec::new(),
         }
     }
 
+    pub fn with_warnings(mut self, warnings: Vec<String>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
     pub fn no_content(status_code: u16) -> ApiResponse<()> {
         ApiResponse {
             status_code,
             data: None,
+            warnings: Vec::new(),
         }
     }
 }
@@ -59,6 +93,7 @@ mod tests {
         let response = ApiResponse {
             status_code: StatusCode::OK.into(),
             data: Some(23),
+            warnings: Vec::new(),
         };
         let serialized = serde_json::to_string(&response).unwrap();
 
@@ -77,4 +112,16 @@ mod tests {
         let serialized = serde_json::to_string(&response).unwrap();
         assert_eq!(serialized, json!({"status_code": 204}).to_string());
     }
+
+    #[tokio::test]
+    async fn test_serialize_api_response_with_warnings() {
+        let response = ApiResponse::new(StatusCode::CREATED.into(), 23)
+            .with_warnings(vec!["The coach is out of office".to_string()]);
+        let serialized = serde_json::to_value(&response).unwrap();
+
+        assert_eq!(
+            serialized,
+            json!({"data": 23, "status_code": 201, "warnings": ["The coach is out of office"]})
+        );
+    }
 }
@@ -1,15 +1,18 @@
 use crate::controller::ApiResponse;
 use crate::extractors::{
-    authenticated_user::AuthenticatedUser, compare_api_version::CompareApiVersion,
+    authenticated_user::AuthenticatedUser,
+    compare_api_version::CompareApiVersion,
+    if_match::{with_etag, IfMatch},
 };
+use crate::params::note::{IndexParams, SortField};
+use crate::params::WithSortDefaults;
 use crate::{AppState, Error};
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::Json;
-use domain::{note as NoteApi, notes, Id};
+use domain::{mention as MentionApi, note as NoteApi, notes, resource_view as ResourceViewApi, Id};
 use service::config::ApiVersion;
-use std::collections::HashMap;
 
 use log::*;
 
@@ -45,6 +48,15 @@ pub async fn create(
 
     debug!("New Note: {note:?}");
 
+    MentionApi::sync_and_notify(
+        app_state.db_conn_ref(),
+        &app_state.config,
+        app_state.event_publisher.as_ref(),
+        &user,
+        (&note).into(),
+    )
+    .await;
+
     Ok(Json(ApiResponse::new(StatusCode::CREATED.into(), note)))
 }
 
@@ -57,8 +69,10 @@ pub async fn create(
     ),
     request_body = notes::Model,
     responses(
-        (status = 200, description = "Successfully Updated Note", body = [notes::Model]),
+        (status = 200, description = "Successfully Updated Note", body = [notes::Model],
+            headers(("ETag" = String, description = "Version of the resource to send back in If-Match"))),
         (status = 401, description = "Unauthorized"),
+        (status = 412, description = "Changed since the version named in If-Match"),
         (status = 405, description = "Method not allowed"),
         (status = 503, description = "Service temporarily unavailable")
     ),
@@ -68,20 +82,34 @@ pub async fn create(
 )]
 pub async fn update(
     CompareApiVersion(_v): CompareApiVersion,
-    AuthenticatedUser(_user): AuthenticatedUser,
+    AuthenticatedUser(user): AuthenticatedUser,
     // TODO: create a new Extractor to authorize the user to access
     // the data requested
     State(app_state): State<AppState>,
+    if_match: IfMatch,
     Path(id): Path<Id>,
     Json(note_model): Json<notes::Model>,
 ) -> Result<impl IntoResponse, Error> {
     debug!("PUT Update Note with id: {id}");
 
-    let note = NoteApi::update(app_state.db_conn_ref(), id, note_model).await?;
+    let note =
+        NoteApi::update(app_state.db_conn_ref(), id, note_model, if_match.expected()).await?;
 
     debug!("Updated Note: {note:?}");
 
-    Ok(Json(ApiResponse::new(StatusCode::OK.into(), note)))
+    MentionApi::sync_and_notify(
+        app_state.db_conn_ref(),
+        &app_state.config,
+        app_state.event_publisher.as_ref(),
+        &user,
+        (&note).into(),
+    )
+    .await;
+
+    Ok(with_etag(
+        note.updated_at,
+        Json(ApiResponse::new(StatusCode::OK.into(), note)),
+    ))
 }
 
 #[utoipa::path(
@@ -89,10 +117,14 @@ pub async fn update(
     path = "/notes",
     params(
         ApiVersion,
-        ("coaching_session_id" = Option<Id>, Query, description = "Filter by coaching_session_id")
+        ("filter[coaching_session_id]" = Option<Id>, Query, description = "Filter by coaching_session_id (also accepted as coaching_session_id)"),
+        ("sort" = Option<String>, Query, description = "Sort by a field, descending when prefixed with '-'. Takes precedence over sort_by and sort_order.", example = "-updated_at"),
+        ("sort_by" = Option<crate::params::note::SortField>, Query, description = "Sort by field. Valid values: 'created_at', 'updated_at'.", example = "created_at"),
+        ("sort_order" = Option<crate::params::sort::SortOrder>, Query, description = "Sort order. Valid values: 'asc' (ascending), 'desc' (descending).", example = "desc")
     ),
     responses(
-        (status = 200, description = "Successfully retrieved all Notes", body = [coaching_sessions::Model]),
+        (status = 200, description = "Successfully retrieved all Notes with the caller's unread flag and reactions", body = [domain::resource_view::AnnotatedNote]),
+        (status = 400, description = "Unknown or invalid filter or sort parameter"),
         (status = 401, description = "Unauthorized"),
         (status = 405, description = "Method not allowed"),
         (status = 503, description = "Service temporarily unavailable")
@@ -103,19 +135,30 @@ pub async fn update(
 )]
 pub async fn index(
     CompareApiVersion(_v): CompareApiVersion,
-    AuthenticatedUser(_user): AuthenticatedUser,
+    AuthenticatedUser(user): AuthenticatedUser,
     // TODO: create a new Extractor to authorize the user to access
     // the data requested
     State(app_state): State<AppState>,
-    Query(params): Query<HashMap<String, String>>,
+    Query(params): Query<IndexParams>,
 ) -> Result<impl IntoResponse, Error> {
     debug!("GET all Notes");
     debug!("Filter Params: {params:?}");
 
+    // Apply default sorting parameters
+    let mut params = params;
+    IndexParams::apply_sort(
+        params.sort.take(),
+        &mut params.sort_by,
+        &mut params.sort_order,
+        SortField::CreatedAt,
+    );
+
     let notes = NoteApi::find_by(app_state.db_conn_ref(), params).await?;
 
     debug!("Found Notes: {notes:?}");
 
+    let notes = ResourceViewApi::annotate_notes(app_state.db_conn_ref(), user.id, notes).await?;
+
     Ok(Json(ApiResponse::new(StatusCode::OK.into(), notes)))
 }
 
@@ -128,7 +171,8 @@ pub async fn index(
         ("id" = String, Path, description = "Note id to retrieve")
     ),
     responses(
-        (status = 200, description = "Successfully retrieved a certain Note by its id", body = [notes::Model]),
+        (status = 200, description = "Successfully retrieved a certain Note by its id", body = [notes::Model],
+            headers(("ETag" = String, description = "Version of the resource to send back in If-Match"))),
         (status = 401, description = "Unauthorized"),
         (status = 404, description = "Note not found"),
         (status = 405, description = "Method not allowed"),
@@ -146,6 +190,71 @@ pub async fn read(
     debug!("GET Organization by id: {id}");
 
     let note: Option<notes::Model> = NoteApi::find_by_id(app_state.db_conn_ref(), id).await?;
+    let updated_at = note.as_ref().map(|note| note.updated_at);
+
+    let body = Json(ApiResponse::new(StatusCode::OK.into(), note));
+    Ok(match updated_at {
+        Some(updated_at) => with_etag(updated_at, body),
+        None => body.into_response(),
+    })
+}
+
+/// DELETE a Note specified by its id; it can be restored until it's purged.
+#[utoipa::path(
+    delete,
+    path = "/notes/{id}",
+    params(
+        ApiVersion,
+        ("id" = Id, Path, description = "Note id to delete")
+    ),
+    responses(
+        (status = 200, description = "Successfully deleted a certain Note by its id"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Note not found"),
+        (status = 405, description = "Method not allowed"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn delete(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("DELETE Note by id: {id}");
+
+    NoteApi::delete_by_id(app_state.db_conn_ref(), id).await?;
+    Ok(Json(serde_json::json!({"id": id})))
+}
+
+/// POST restore a deleted Note, until it's purged.
+#[utoipa::path(
+    post,
+    path = "/notes/{id}/restore",
+    params(
+        ApiVersion,
+        ("id" = Id, Path, description = "Id of the deleted Note to restore")
+    ),
+    responses(
+        (status = 200, description = "Successfully restored a Note", body = notes::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "No deleted Note with this id"),
+        (status = 405, description = "Method not allowed"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn restore(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("POST restore Note by id: {id}");
 
+    let note = NoteApi::restore(app_state.db_conn_ref(), id).await?;
     Ok(Json(ApiResponse::new(StatusCode::OK.into(), note)))
 }
@@ -0,0 +1,464 @@
+//! The OAuth 2.0 authorization server third-party apps integrate through.
+//!
+//! `GET`/`POST /oauth/authorize` back the frontend's consent page and take the user's
+//! session. `/oauth/token`, `/oauth/introspect` and `/oauth/revoke` are called by the
+//! apps themselves, authenticating as the client, and answer in the shapes the RFCs
+//! define rather than in an `ApiResponse`.
+
+use crate::extractors::{
+    authenticated_user::AuthenticatedUser, compare_api_version::CompareApiVersion,
+};
+use crate::params::oauth::{AuthorizeParams, TokenOperationParams, TokenParams};
+use crate::{controller::ApiResponse, AppState, Error};
+use axum::{
+    extract::{Query, State},
+    http::{
+        header::{AUTHORIZATION, CACHE_CONTROL, WWW_AUTHENTICATE},
+        HeaderMap, StatusCode,
+    },
+    response::{IntoResponse, Response},
+    Form, Json,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use domain::oauth_server::{self as OAuthServerApi, IssuedTokens, TokenError};
+use domain::Id;
+use serde::Serialize;
+use service::config::ApiVersion;
+use utoipa::ToSchema;
+
+use log::*;
+
+/// An authorization request as shown on the consent page.
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct AuthorizationDetails {
+    client_id: Id,
+    /// The app's name
+    name: String,
+    /// The organization that registered the app
+    organization_id: Id,
+    redirect_uri: String,
+    /// Space-separated scopes the app is asking for
+    scope: String,
+}
+
+/// Where to send the user after they authorized the app.
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct AuthorizationRedirect {
+    /// The app's redirect URI with the authorization `code` and `state`
+    redirect_to: String,
+}
+
+/// Tokens issued to an app (RFC 6749 §5.1).
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct TokenResponse {
+    access_token: String,
+    /// Always `Bearer`
+    token_type: &'static str,
+    /// Seconds until the access token expires
+    expires_in: i64,
+    refresh_token: String,
+    scope: String,
+}
+
+impl From<IssuedTokens> for TokenResponse {
+    fn from(tokens: IssuedTokens) -> Self {
+        Self {
+            access_token: tokens.access_token,
+            token_type: "Bearer",
+            expires_in: tokens.expires_in,
+            refresh_token: tokens.refresh_token,
+            scope: tokens.scope,
+        }
+    }
+}
+
+/// What a token grants (RFC 7662 §2.2); only `active` when it isn't active.
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub(crate) struct IntrospectionResponse {
+    active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    client_id: Option<Id>,
+    /// The user the token acts on behalf of
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sub: Option<Id>,
+    /// Expiry as a Unix timestamp
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exp: Option<i64>,
+    /// `access_token` or `refresh_token`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token_type: Option<&'static str>,
+}
+
+/// A refused token request (RFC 6749 §5.2).
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct OAuthErrorResponse {
+    /// e.g. `invalid_grant`
+    error: &'static str,
+    error_description: String,
+}
+
+/// GET an authorization request's details for the consent page, checking that the app
+/// may ask the signed-in user for them.
+#[utoipa::path(
+    get,
+    path = "/oauth/authorize",
+    params(ApiVersion, AuthorizeParams),
+    responses(
+        (status = 200, description = "The authorization request is valid", body = AuthorizationDetails),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "No such app in the user's organizations"),
+        (status = 422, description = "Invalid redirect URI, PKCE challenge or scope"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn read_authorization(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Query(params): Query<AuthorizeParams>,
+) -> Result<impl IntoResponse, Error> {
+    check_response_type(&params)?;
+    let (client, scope) = OAuthServerApi::check_authorization(
+        app_state.db_conn_ref(),
+        &user,
+        params.client_id,
+        &params.redirect_uri,
+        params.scope.as_deref(),
+        &params.code_challenge,
+        params.code_challenge_method.as_deref(),
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(
+        StatusCode::OK.into(),
+        AuthorizationDetails {
+            client_id: client.id,
+            name: client.name,
+            organization_id: client.organization_id,
+            redirect_uri: params.redirect_uri,
+            scope,
+        },
+    )))
+}
+
+/// AUTHORIZE an app on behalf of the signed-in user, returning the URL that sends them
+/// back to the app with an authorization code.
+#[utoipa::path(
+    post,
+    path = "/oauth/authorize",
+    params(ApiVersion),
+    request_body = AuthorizeParams,
+    responses(
+        (status = 201, description = "The app was authorized", body = AuthorizationRedirect),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "No such app in the user's organizations"),
+        (status = 422, description = "Invalid redirect URI, PKCE challenge or scope"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn authorize(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(params): Json<AuthorizeParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!(
+        "POST authorize OAuth client {} for user {}",
+        params.client_id, user.id
+    );
+    check_response_type(&params)?;
+
+    let redirect_to = OAuthServerApi::authorize(
+        app_state.db_conn_ref(),
+        &user,
+        params.client_id,
+        &params.redirect_uri,
+        params.scope.as_deref(),
+        &params.code_challenge,
+        params.code_challenge_method.as_deref(),
+        params.state.as_deref(),
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(
+        StatusCode::CREATED.into(),
+        AuthorizationRedirect { redirect_to },
+    )))
+}
+
+/// ISSUE tokens for an authorization code, or new ones for a refresh token.
+#[utoipa::path(
+    post,
+    path = "/oauth/token",
+    request_body(content = TokenParams, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 200, description = "Tokens issued", body = TokenResponse),
+        (status = 400, description = "Invalid or expired grant", body = OAuthErrorResponse),
+        (status = 401, description = "Client authentication failed", body = OAuthErrorResponse),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("client_auth" = [])
+    )
+)]
+pub async fn token(
+    State(app_state): State<AppState>,
+    headers: HeaderMap,
+    Form(params): Form<TokenParams>,
+) -> Response {
+    let db = app_state.db_conn_ref();
+    let result = async {
+        let client = authenticate_client(
+            &app_state,
+            &headers,
+            params.client_id,
+            params.client_secret.as_deref(),
+        )
+        .await?;
+        match params.grant_type.as_str() {
+            "authorization_code" => {
+                let (Some(code), Some(redirect_uri), Some(code_verifier)) =
+                    (&params.code, &params.redirect_uri, &params.code_verifier)
+                else {
+                    return Err(rejected(
+                        "invalid_request",
+                        "`code`, `redirect_uri` and `code_verifier` are required",
+                    ));
+                };
+                OAuthServerApi::exchange_code(db, &client, code, redirect_uri, code_verifier).await
+            }
+            "refresh_token" => {
+                let Some(refresh_token) = &params.refresh_token else {
+                    return Err(rejected("invalid_request", "`refresh_token` is required"));
+                };
+                OAuthServerApi::refresh(db, &client, refresh_token).await
+            }
+            _ => Err(rejected(
+                "unsupported_grant_type",
+                "`grant_type` must be `authorization_code` or `refresh_token`",
+            )),
+        }
+    }
+    .await;
+
+    match result {
+        Ok(tokens) => (
+            [(CACHE_CONTROL, "no-store")],
+            Json(TokenResponse::from(tokens)),
+        )
+            .into_response(),
+        Err(e) => token_error_response(e),
+    }
+}
+
+/// INTROSPECT one of the client's tokens.
+#[utoipa::path(
+    post,
+    path = "/oauth/introspect",
+    request_body(content = TokenOperationParams, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 200, description = "What the token grants", body = IntrospectionResponse),
+        (status = 401, description = "Client authentication failed", body = OAuthErrorResponse),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("client_auth" = [])
+    )
+)]
+pub async fn introspect(
+    State(app_state): State<AppState>,
+    headers: HeaderMap,
+    Form(params): Form<TokenOperationParams>,
+) -> Response {
+    let client = match authenticate_client(
+        &app_state,
+        &headers,
+        params.client_id,
+        params.client_secret.as_deref(),
+    )
+    .await
+    {
+        Ok(client) => client,
+        Err(e) => return token_error_response(e),
+    };
+
+    match OAuthServerApi::introspect(app_state.db_conn_ref(), &client, &params.token).await {
+        Ok(info) => Json(
+            info.map(|info| IntrospectionResponse {
+                active: true,
+                scope: Some(info.scope),
+                client_id: Some(info.client_id),
+                sub: Some(info.user_id),
+                exp: Some(info.expires_at.timestamp()),
+                token_type: Some(info.token_type),
+            })
+            .unwrap_or_default(),
+        )
+        .into_response(),
+        Err(e) => Error::from(e).into_response(),
+    }
+}
+
+/// REVOKE one of the client's tokens along with the other token of its pair. Unknown
+/// tokens are ignored.
+#[utoipa::path(
+    post,
+    path = "/oauth/revoke",
+    request_body(content = TokenOperationParams, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 200, description = "The token is no longer valid"),
+        (status = 401, description = "Client authentication failed", body = OAuthErrorResponse),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("client_auth" = [])
+    )
+)]
+pub async fn revoke(
+    State(app_state): State<AppState>,
+    headers: HeaderMap,
+    Form(params): Form<TokenOperationParams>,
+) -> Response {
+    let client = match authenticate_client(
+        &app_state,
+        &headers,
+        params.client_id,
+        params.client_secret.as_deref(),
+    )
+    .await
+    {
+        Ok(client) => client,
+        Err(e) => return token_error_response(e),
+    };
+
+    match OAuthServerApi::revoke(app_state.db_conn_ref(), &client, &params.token).await {
+        Ok(()) => StatusCode::OK.into_response(),
+        Err(e) => Error::from(e).into_response(),
+    }
+}
+
+fn check_response_type(params: &AuthorizeParams) -> Result<(), Error> {
+    if params.response_type != "code" {
+        return Err(Error::from(domain::error::Error {
+            source: None,
+            error_kind: domain::error::DomainErrorKind::Validation(
+                "`response_type` must be `code`".to_string(),
+            ),
+        }));
+    }
+    Ok(())
+}
+
+/// The client authenticating a request: from HTTP Basic credentials, otherwise from the
+/// form's `client_id` and `client_secret`.
+async fn authenticate_client(
+    app_state: &AppState,
+    headers: &HeaderMap,
+    client_id: Option<Id>,
+    client_secret: Option<&str>,
+) -> Result<domain::oauth_clients::Model, TokenError> {
+    let (client_id, client_secret) = match basic_credentials(headers) {
+        Some((client_id, client_secret)) => (Some(client_id), Some(client_secret)),
+        None => (client_id, client_secret.map(str::to_string)),
+    };
+    let Some(client_id) = client_id else {
+        return Err(rejected(
+            "invalid_client",
+            "Client authentication is required",
+        ));
+    };
+    OAuthServerApi::authenticate_client(
+        app_state.db_conn_ref(),
+        client_id,
+        client_secret.as_deref(),
+    )
+    .await
+}
+
+/// The client ID and secret of an `Authorization: Basic` header (RFC 6749 §2.3.1).
+fn basic_credentials(headers: &HeaderMap) -> Option<(Id, String)> {
+    let encoded = headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Basic ")?;
+    let decoded = String::from_utf8(STANDARD.decode(encoded.trim()).ok()?).ok()?;
+    // Client IDs and generated secrets have no characters that need form-decoding.
+    let (client_id, client_secret) = decoded.split_once(':')?;
+    Some((client_id.parse().ok()?, client_secret.to_string()))
+}
+
+fn rejected(error: &'static str, description: &str) -> TokenError {
+    TokenError::Rejected {
+        error,
+        description: description.to_string(),
+    }
+}
+
+fn token_error_response(error: TokenError) -> Response {
+    match error {
+        TokenError::Rejected { error, description } => {
+            warn!("Refused an OAuth token request: {error}: {description}");
+            let body = Json(OAuthErrorResponse {
+                error,
+                error_description: description,
+            });
+            if error == "invalid_client" {
+                (
+                    StatusCode::UNAUTHORIZED,
+                    [(WWW_AUTHENTICATE, "Basic realm=\"oauth\"")],
+                    body,
+                )
+                    .into_response()
+            } else {
+                (StatusCode::BAD_REQUEST, body).into_response()
+            }
+        }
+        TokenError::Internal(e) => Error::from(e).into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn basic_credentials_reads_the_client_id_and_secret() {
+        let client_id = Id::new_v4();
+        let mut headers = HeaderMap::new();
+        let encoded = STANDARD.encode(format!("{client_id}:rfp_cs_secret"));
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Basic {encoded}")).unwrap(),
+        );
+
+        assert_eq!(
+            basic_credentials(&headers),
+            Some((client_id, "rfp_cs_secret".to_string()))
+        );
+
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer rfp_at_x"));
+        assert_eq!(basic_credentials(&headers), None);
+    }
+
+    #[test]
+    fn token_errors_use_the_rfc_status_codes() {
+        assert_eq!(
+            token_error_response(rejected("invalid_client", "x")).status(),
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            token_error_response(rejected("invalid_grant", "x")).status(),
+            StatusCode::BAD_REQUEST
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use crate::extractors::compare_api_version::CompareApiVersion;
+use crate::extractors::organization_member_access::OrganizationMemberAccess;
+use crate::{controller::ApiResponse, AppState, Error};
+use axum::{extract::Path, extract::State, http::StatusCode, response::IntoResponse, Json};
+use domain::{ai_settings as AiSettingsApi, organization_ai_settings::Model, Id};
+use service::config::ApiVersion;
+
+use log::*;
+
+/// GET an organization's AI settings: allowed providers and models, and the model,
+/// temperature and token budget per task type. Unset values use the platform defaults.
+#[utoipa::path(
+    get,
+    path = "/organizations/{organization_id}/ai_settings",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the organization's AI settings", body = domain::organization_ai_settings::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Organization not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn read(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    OrganizationMemberAccess(organization_id): OrganizationMemberAccess,
+) -> Result<impl IntoResponse, Error> {
+    let settings = AiSettingsApi::find(app_state.db_conn_ref(), organization_id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), settings)))
+}
+
+/// UPDATE an organization's AI settings. Admin-only; providers and models must be in the
+/// platform's model registry.
+#[utoipa::path(
+    put,
+    path = "/organizations/{organization_id}/ai_settings",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+    ),
+    request_body = domain::organization_ai_settings::Model,
+    responses(
+        (status = 200, description = "AI settings updated", body = domain::organization_ai_settings::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 422, description = "Unknown or disallowed provider or model, or an out-of-range temperature or token budget"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn update(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(organization_id): Path<Id>,
+    Json(settings): Json<Model>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("PUT AI settings for organization {organization_id}: {settings:?}");
+
+    let settings =
+        AiSettingsApi::update(app_state.db_conn_ref(), organization_id, settings).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), settings)))
+}
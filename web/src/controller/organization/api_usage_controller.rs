@@ -0,0 +1,148 @@
+use crate::extractors::compare_api_version::CompareApiVersion;
+use crate::params::api_usage::ReadParams;
+use crate::{controller::ApiResponse, AppState, Error};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use domain::{api_usage as ApiUsageApi, organization_api_quotas::Model, Id};
+use service::config::ApiVersion;
+
+use log::*;
+
+/// GET an organization's API usage per endpoint over a window of hours: requests, errors
+/// and latencies, busiest endpoint first. Admin-only; the current hour lags by up to a
+/// minute.
+#[utoipa::path(
+    get,
+    path = "/organizations/{organization_id}/api_usage",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+        ReadParams,
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the organization's API usage", body = [domain::api_usage::EndpointUsage]),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - organization admins only"),
+        (status = 422, description = "`from` not before `to`, or a window longer than 366 days"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn read(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(organization_id): Path<Id>,
+    Query(params): Query<ReadParams>,
+) -> Result<impl IntoResponse, Error> {
+    let usage = ApiUsageApi::report(
+        app_state.db_conn_ref(),
+        organization_id,
+        params.from,
+        params.to,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), usage)))
+}
+
+/// GET an organization's API quota: requests per hour allowed overall and per endpoint.
+/// SuperAdmin-only; an organization without a quota has no limits.
+#[utoipa::path(
+    get,
+    path = "/organizations/{organization_id}/api_quota",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the organization's API quota", body = domain::organization_api_quotas::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - SuperAdmin only"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn read_quota(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(organization_id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    let quota = ApiUsageApi::find_quota(app_state.db_conn_ref(), organization_id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), quota)))
+}
+
+/// UPDATE an organization's API quota. SuperAdmin-only; requests past it are refused
+/// with 429 until the hour ends. Endpoints are named by method and route, e.g.
+/// `GET /actions/:id`.
+#[utoipa::path(
+    put,
+    path = "/organizations/{organization_id}/api_quota",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+    ),
+    request_body = domain::organization_api_quotas::Model,
+    responses(
+        (status = 200, description = "API quota updated", body = domain::organization_api_quotas::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - SuperAdmin only"),
+        (status = 422, description = "A limit below 1 or a malformed endpoint"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn update_quota(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(organization_id): Path<Id>,
+    Json(quota): Json<Model>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("PUT API quota for organization {organization_id}: {quota:?}");
+
+    let quota = ApiUsageApi::update_quota(app_state.db_conn_ref(), organization_id, quota).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), quota)))
+}
+
+/// DELETE an organization's API quota, lifting its limits. SuperAdmin-only.
+#[utoipa::path(
+    delete,
+    path = "/organizations/{organization_id}/api_quota",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+    ),
+    responses(
+        (status = 204, description = "API quota removed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - SuperAdmin only"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn delete_quota(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(organization_id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    info!("Removing API quota of organization {organization_id}");
+
+    ApiUsageApi::delete_quota(app_state.db_conn_ref(), organization_id).await?;
+
+    Ok(Json(ApiResponse::<()>::no_content(
+        StatusCode::NO_CONTENT.into(),
+    )))
+}
@@ -0,0 +1,59 @@
+use crate::extractors::compare_api_version::CompareApiVersion;
+use crate::params::audit::IndexParams;
+use crate::{controller::ApiResponse, AppState, Error};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use domain::{audit as AuditApi, Id};
+use service::config::ApiVersion;
+
+/// GET a page of an organization's audit trail, newest first: who created, updated,
+/// deleted or restored what, from where, and which fields changed. Admin-only; what
+/// coaches and coachees write shows only as `"[redacted]"`.
+#[utoipa::path(
+    get,
+    path = "/organizations/{organization_id}/audit",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+        IndexParams,
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved a page of the audit trail", body = domain::audit::AuditPage),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - organization admins only"),
+        (status = 422, description = "`from` not before `to`, an invalid cursor or a limit out of range"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn index(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(organization_id): Path<Id>,
+    Query(params): Query<IndexParams>,
+) -> Result<impl IntoResponse, Error> {
+    let filter = AuditApi::AuditFilter {
+        entity_type: params.entity_type,
+        entity_id: params.entity_id,
+        actor_user_id: params.actor_user_id,
+        action: params.action,
+        from: params.from,
+        to: params.to,
+    };
+    let page = AuditApi::find(
+        app_state.db_conn_ref(),
+        organization_id,
+        filter,
+        params.cursor.as_deref(),
+        params.limit,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), page)))
+}
@@ -205,6 +205,8 @@ mod tests {
             coach_id,
             coachee_id,
             slug: "test".to_string(),
+            ai_privacy_level: Default::default(),
+            pro_bono: false,
             created_at: now,
             updated_at: now,
         }
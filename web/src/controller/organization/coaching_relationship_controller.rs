@@ -13,10 +13,13 @@ use axum::response::IntoResponse;
 use axum::Json;
 use domain::coaching_relationship::CoachingRelationshipWithUserNames;
 use domain::{
-    action as ActionApi, coaching_relationship as CoachingRelationshipApi, coaching_relationships,
-    goal_progress as GoalProgressApi, Id,
+    action as ActionApi, action_series as ActionSeriesApi,
+    coaching_relationship as CoachingRelationshipApi, coaching_relationships,
+    conversation_metrics as ConversationMetricsApi, goal_progress as GoalProgressApi, Id,
 };
+use serde::Deserialize;
 use service::config::ApiVersion;
+use utoipa::ToSchema;
 
 use log::*;
 
@@ -217,3 +220,126 @@ pub async fn goal_progress(
 
     Ok(Json(ApiResponse::new(StatusCode::OK.into(), progress)))
 }
+
+/// GET streak statistics for every recurring action in a coaching relationship.
+#[utoipa::path(
+    get,
+    path = "/organizations/{organization_id}/coaching_relationships/{relationship_id}/action_streaks",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "Organization id"),
+        ("relationship_id" = Id, Path, description = "Coaching relationship id"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved action streaks for the coaching relationship"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Coaching relationship not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn action_streaks(
+    CompareApiVersion(_v): CompareApiVersion,
+    CoachingRelationshipAccess(relationship): CoachingRelationshipAccess,
+    State(app_state): State<AppState>,
+) -> Result<impl IntoResponse, Error> {
+    debug!(
+        "GET action streaks for coaching relationship: {}",
+        relationship.id
+    );
+
+    let streaks =
+        ActionSeriesApi::relationship_streaks(app_state.db_conn_ref(), relationship.id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), streaks)))
+}
+
+/// GET conversation metrics across a coaching relationship's transcribed sessions, with
+/// per-speaker averages.
+#[utoipa::path(
+    get,
+    path = "/organizations/{organization_id}/coaching_relationships/{relationship_id}/conversation_metrics",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "Organization id"),
+        ("relationship_id" = Id, Path, description = "Coaching relationship id"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved conversation metrics for the coaching relationship"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Coaching relationship not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn conversation_metrics(
+    CompareApiVersion(_v): CompareApiVersion,
+    CoachingRelationshipAccess(relationship): CoachingRelationshipAccess,
+    State(app_state): State<AppState>,
+) -> Result<impl IntoResponse, Error> {
+    debug!(
+        "GET conversation metrics for coaching relationship: {}",
+        relationship.id
+    );
+
+    let trend = ConversationMetricsApi::find_trend_by_coaching_relationship(
+        app_state.db_conn_ref(),
+        relationship.id,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), trend)))
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ProBonoParams {
+    pub pro_bono: bool,
+}
+
+/// PUT whether the coach coaches a relationship without pay, so that their coaching log
+/// counts its hours as pro bono (coach only).
+#[utoipa::path(
+    put,
+    path = "/organizations/{organization_id}/coaching_relationships/{relationship_id}/pro_bono",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "Organization id"),
+        ("relationship_id" = Id, Path, description = "Coaching relationship to update"),
+    ),
+    request_body = ProBonoParams,
+    responses(
+        (status = 200, description = "Pro bono flag updated", body = coaching_relationships::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Coaching relationship not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn update_pro_bono(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    CoachingRelationshipAccess(relationship): CoachingRelationshipAccess,
+    State(app_state): State<AppState>,
+    Json(params): Json<ProBonoParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!(
+        "PUT pro bono {} for coaching relationship: {}",
+        params.pro_bono, relationship.id
+    );
+
+    let relationship = CoachingRelationshipApi::set_pro_bono(
+        app_state.db_conn_ref(),
+        user.id,
+        relationship,
+        params.pro_bono,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), relationship)))
+}
@@ -0,0 +1,156 @@
+use crate::extractors::organization_member_access::OrganizationMemberAccess;
+use crate::extractors::{
+    authenticated_user::AuthenticatedUser, compare_api_version::CompareApiVersion,
+};
+use crate::{controller::ApiResponse, AppState, Error};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use domain::{goal_template as GoalTemplateApi, goal_templates::Model, Id};
+use service::config::ApiVersion;
+
+use log::*;
+
+/// INDEX an organization's goal templates, with how often each has been used.
+#[utoipa::path(
+    get,
+    path = "/organizations/{organization_id}/goal_templates",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the organization's goal templates", body = [domain::goal_templates::Model]),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Organization not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn index(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    OrganizationMemberAccess(organization_id): OrganizationMemberAccess,
+) -> Result<impl IntoResponse, Error> {
+    let templates =
+        GoalTemplateApi::find_by_organization(app_state.db_conn_ref(), organization_id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), templates)))
+}
+
+/// CREATE a goal template for an organization. Admin-only.
+#[utoipa::path(
+    post,
+    path = "/organizations/{organization_id}/goal_templates",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+    ),
+    request_body = domain::goal_templates::Model,
+    responses(
+        (status = 201, description = "Goal template created", body = domain::goal_templates::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 422, description = "Invalid title, target duration or milestones"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn create(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    OrganizationMemberAccess(organization_id): OrganizationMemberAccess,
+    Json(template_model): Json<Model>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("POST goal template for organization {organization_id}: {template_model:?}");
+
+    let template = GoalTemplateApi::create(
+        app_state.db_conn_ref(),
+        organization_id,
+        user.id,
+        template_model,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::CREATED.into(), template)))
+}
+
+/// UPDATE a goal template's content. Admin-only; usage counts are preserved.
+#[utoipa::path(
+    put,
+    path = "/organizations/{organization_id}/goal_templates/{template_id}",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+        ("template_id" = Id, Path, description = "The ID of the goal template to update"),
+    ),
+    request_body = domain::goal_templates::Model,
+    responses(
+        (status = 200, description = "Goal template updated", body = domain::goal_templates::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Goal template not found"),
+        (status = 422, description = "Invalid title, target duration or milestones"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn update(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path((organization_id, template_id)): Path<(Id, Id)>,
+    Json(template_model): Json<Model>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("PUT goal template {template_id} in organization {organization_id}");
+
+    let template = GoalTemplateApi::update(
+        app_state.db_conn_ref(),
+        organization_id,
+        template_id,
+        template_model,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), template)))
+}
+
+/// DELETE a goal template. Admin-only; goals created from it are kept.
+#[utoipa::path(
+    delete,
+    path = "/organizations/{organization_id}/goal_templates/{template_id}",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+        ("template_id" = Id, Path, description = "The ID of the goal template to delete"),
+    ),
+    responses(
+        (status = 204, description = "Goal template deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Goal template not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn delete(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path((organization_id, template_id)): Path<(Id, Id)>,
+) -> Result<impl IntoResponse, Error> {
+    info!("Deleting goal template {template_id} in organization {organization_id}");
+
+    GoalTemplateApi::delete(app_state.db_conn_ref(), organization_id, template_id).await?;
+
+    Ok(Json(ApiResponse::<()>::no_content(
+        StatusCode::NO_CONTENT.into(),
+    )))
+}
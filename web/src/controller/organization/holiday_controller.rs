@@ -0,0 +1,111 @@
+use crate::extractors::compare_api_version::CompareApiVersion;
+use crate::extractors::organization_member_access::OrganizationMemberAccess;
+use crate::{controller::ApiResponse, AppState, Error};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use domain::{holiday as HolidayApi, Id};
+use service::config::ApiVersion;
+
+use log::*;
+
+/// INDEX an organization's holidays, earliest first.
+#[utoipa::path(
+    get,
+    path = "/organizations/{organization_id}/holidays",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the organization's holidays", body = [domain::organization_holidays::Model]),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Organization not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn index(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    OrganizationMemberAccess(organization_id): OrganizationMemberAccess,
+) -> Result<impl IntoResponse, Error> {
+    let holidays =
+        HolidayApi::find_by_organization(app_state.db_conn_ref(), organization_id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), holidays)))
+}
+
+/// IMPORT holidays into an organization's calendar from an ICS file sent as the request
+/// body. Admin-only; events imported before are replaced by `UID`.
+#[utoipa::path(
+    post,
+    path = "/organizations/{organization_id}/holidays/import",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+    ),
+    request_body(content = String, content_type = "text/calendar", description = "An iCalendar (ICS) file"),
+    responses(
+        (status = 200, description = "Holidays imported; the organization's holidays after the import", body = [domain::organization_holidays::Model]),
+        (status = 401, description = "Unauthorized"),
+        (status = 422, description = "The calendar has no usable events or too many"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn import(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(organization_id): Path<Id>,
+    ics: String,
+) -> Result<impl IntoResponse, Error> {
+    debug!(
+        "POST import {} bytes of holidays into organization {organization_id}",
+        ics.len()
+    );
+
+    let holidays = HolidayApi::import(app_state.db_conn_ref(), organization_id, &ics).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), holidays)))
+}
+
+/// DELETE a holiday from an organization's calendar. Admin-only.
+#[utoipa::path(
+    delete,
+    path = "/organizations/{organization_id}/holidays/{holiday_id}",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+        ("holiday_id" = Id, Path, description = "The ID of the holiday to delete"),
+    ),
+    responses(
+        (status = 204, description = "Holiday deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Holiday not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn delete(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path((organization_id, holiday_id)): Path<(Id, Id)>,
+) -> Result<impl IntoResponse, Error> {
+    info!("Deleting holiday {holiday_id} in organization {organization_id}");
+
+    HolidayApi::delete(app_state.db_conn_ref(), organization_id, holiday_id).await?;
+
+    Ok(Json(ApiResponse::<()>::no_content(
+        StatusCode::NO_CONTENT.into(),
+    )))
+}
@@ -0,0 +1,167 @@
+use crate::extractors::organization_member_access::OrganizationMemberAccess;
+use crate::extractors::{
+    authenticated_user::AuthenticatedUser, compare_api_version::CompareApiVersion,
+};
+use crate::params::library::IndexParams;
+use crate::{controller::ApiResponse, AppState, Error};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use domain::{library as LibraryApi, library_items::Model, Id};
+use service::config::ApiVersion;
+
+use log::*;
+
+/// INDEX an organization's library items, optionally filtered by tag.
+#[utoipa::path(
+    get,
+    path = "/organizations/{organization_id}/library_items",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+        IndexParams,
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the organization's library items", body = [domain::library_items::Model]),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Organization not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn index(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    OrganizationMemberAccess(organization_id): OrganizationMemberAccess,
+    Query(params): Query<IndexParams>,
+) -> Result<impl IntoResponse, Error> {
+    let items = LibraryApi::find_items(
+        app_state.db_conn_ref(),
+        organization_id,
+        params.tag.as_deref(),
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), items)))
+}
+
+/// CREATE a library item for an organization. Restricted to its admins and coaches.
+#[utoipa::path(
+    post,
+    path = "/organizations/{organization_id}/library_items",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+    ),
+    request_body = domain::library_items::Model,
+    responses(
+        (status = 201, description = "Library item created", body = domain::library_items::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 422, description = "Invalid title, content, URL or tags"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn create(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    OrganizationMemberAccess(organization_id): OrganizationMemberAccess,
+    Json(item_model): Json<Model>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("POST library item for organization {organization_id}: {item_model:?}");
+
+    let item = LibraryApi::create_item(
+        app_state.db_conn_ref(),
+        organization_id,
+        user.id,
+        item_model,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::CREATED.into(), item)))
+}
+
+/// UPDATE a library item's content. Restricted to the organization's admins and coaches.
+#[utoipa::path(
+    put,
+    path = "/organizations/{organization_id}/library_items/{item_id}",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+        ("item_id" = Id, Path, description = "The ID of the library item to update"),
+    ),
+    request_body = domain::library_items::Model,
+    responses(
+        (status = 200, description = "Library item updated", body = domain::library_items::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Library item not found"),
+        (status = 422, description = "Invalid title, content, URL or tags"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn update(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path((organization_id, item_id)): Path<(Id, Id)>,
+    Json(item_model): Json<Model>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("PUT library item {item_id} in organization {organization_id}");
+
+    let item = LibraryApi::update_item(
+        app_state.db_conn_ref(),
+        organization_id,
+        item_id,
+        item_model,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), item)))
+}
+
+/// DELETE a library item along with its assignments. Restricted to the organization's
+/// admins and coaches.
+#[utoipa::path(
+    delete,
+    path = "/organizations/{organization_id}/library_items/{item_id}",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+        ("item_id" = Id, Path, description = "The ID of the library item to delete"),
+    ),
+    responses(
+        (status = 204, description = "Library item deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Library item not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn delete(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path((organization_id, item_id)): Path<(Id, Id)>,
+) -> Result<impl IntoResponse, Error> {
+    info!("Deleting library item {item_id} in organization {organization_id}");
+
+    LibraryApi::delete_item(app_state.db_conn_ref(), organization_id, item_id).await?;
+
+    Ok(Json(ApiResponse::<()>::no_content(
+        StatusCode::NO_CONTENT.into(),
+    )))
+}
@@ -0,0 +1,92 @@
+use crate::controller::ApiResponse;
+use crate::extractors::compare_api_version::CompareApiVersion;
+use crate::{AppState, Error};
+use axum::body::Bytes;
+use axum::extract::{Path, State};
+use axum::http::{header, StatusCode};
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::{organization_logo as OrganizationLogoApi, Id};
+use service::config::ApiVersion;
+
+use log::*;
+
+/// POST a new logo for an organization. Admin-only.
+///
+/// The body is the PNG itself, up to 2 MB. It's scaled down to fit within 512x512 pixels
+/// and stored under a URL derived from its content, which is set as the organization's
+/// `logo` and may be cached indefinitely; uploading another logo changes the URL.
+#[utoipa::path(
+    post,
+    path = "/organizations/{organization_id}/logo",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+    ),
+    request_body(content = Vec<u8>, content_type = "image/png", description = "The logo, as a PNG"),
+    responses(
+        (status = 200, description = "The organization with its new logo URL", body = domain::organizations::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Organization not found"),
+        (status = 413, description = "The logo is larger than 2 MB"),
+        (status = 422, description = "The logo isn't a PNG, or one that can be read"),
+        (status = 503, description = "Object storage for logos isn't configured")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn create(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(organization_id): Path<Id>,
+    body: Bytes,
+) -> Result<impl IntoResponse, Error> {
+    debug!(
+        "POST logo for organization {organization_id}: {} bytes",
+        body.len()
+    );
+
+    let organization = OrganizationLogoApi::upload(
+        app_state.db_conn_ref(),
+        &app_state.config,
+        organization_id,
+        &body,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), organization)))
+}
+
+/// GET one of an organization's logos, as the URL set as its `logo`.
+///
+/// Public, so the logo can be shown anywhere, e.g. on sign-in pages and in emails. The URL
+/// names the logo by its content, so the response may be cached indefinitely.
+#[utoipa::path(
+    get,
+    path = "/organizations/{organization_id}/logo/{file_name}",
+    params(
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+        ("file_name" = String, Path, description = "The logo's file name, from its URL"),
+    ),
+    responses(
+        (status = 200, description = "The logo", content_type = "image/png", body = Vec<u8>),
+        (status = 404, description = "Logo not found"),
+        (status = 503, description = "Object storage for logos isn't configured")
+    )
+)]
+pub async fn read(
+    State(app_state): State<AppState>,
+    Path((organization_id, file_name)): Path<(Id, String)>,
+) -> Result<impl IntoResponse, Error> {
+    let png = OrganizationLogoApi::fetch(&app_state.config, organization_id, &file_name).await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, OrganizationLogoApi::CONTENT_TYPE),
+            (header::CACHE_CONTROL, "public, max-age=31536000, immutable"),
+        ],
+        png,
+    ))
+}
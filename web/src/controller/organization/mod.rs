@@ -1,3 +1,16 @@
+pub(crate) mod ai_settings_controller;
+pub(crate) mod api_usage_controller;
+pub(crate) mod audit_controller;
 pub(crate) mod coaching_relationship;
 pub(crate) mod coaching_relationship_controller;
+pub(crate) mod goal_template_controller;
+pub(crate) mod holiday_controller;
+pub(crate) mod library_item_controller;
+pub(crate) mod logo_controller;
+pub(crate) mod oauth_client_controller;
+pub(crate) mod relationship_health_controller;
+pub(crate) mod scim_token_controller;
+pub(crate) mod session_type_controller;
+pub(crate) mod transcription_vocabulary_controller;
 pub(crate) mod user_controller;
+pub(crate) mod webhook_subscription_controller;
@@ -0,0 +1,125 @@
+use crate::extractors::{
+    authenticated_user::AuthenticatedUser, compare_api_version::CompareApiVersion,
+};
+use crate::params::oauth::CreateClientParams;
+use crate::{controller::ApiResponse, AppState, Error};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use domain::{oauth_server as OAuthServerApi, Id};
+use service::config::ApiVersion;
+
+use log::*;
+
+/// INDEX the third-party apps registered for an organization, oldest first. Admin-only;
+/// client secrets are not included.
+#[utoipa::path(
+    get,
+    path = "/organizations/{organization_id}/oauth_clients",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the organization's OAuth clients", body = [domain::oauth_clients::Model]),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - organization admins only"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn index(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(organization_id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    let clients = OAuthServerApi::find_clients(app_state.db_conn_ref(), organization_id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), clients)))
+}
+
+/// CREATE (register) a third-party app its users may authorize. Admin-only.
+///
+/// The response carries a confidential client's secret; it isn't returned again.
+#[utoipa::path(
+    post,
+    path = "/organizations/{organization_id}/oauth_clients",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+    ),
+    request_body = CreateClientParams,
+    responses(
+        (status = 201, description = "OAuth client registered", body = domain::oauth_server::CreatedOAuthClient),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - organization admins only"),
+        (status = 422, description = "Invalid name or redirect URIs"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn create(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(organization_id): Path<Id>,
+    Json(params): Json<CreateClientParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!(
+        "POST OAuth client `{}` for organization {organization_id}",
+        params.name
+    );
+
+    let client = OAuthServerApi::register_client(
+        app_state.db_conn_ref(),
+        organization_id,
+        user.id,
+        params.name,
+        params.redirect_uris,
+        params.confidential,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::CREATED.into(), client)))
+}
+
+/// DELETE a third-party app, revoking every token issued to it. Admin-only.
+#[utoipa::path(
+    delete,
+    path = "/organizations/{organization_id}/oauth_clients/{client_id}",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+        ("client_id" = Id, Path, description = "The client ID of the app to delete"),
+    ),
+    responses(
+        (status = 204, description = "OAuth client deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - organization admins only"),
+        (status = 404, description = "OAuth client not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn delete(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path((organization_id, client_id)): Path<(Id, Id)>,
+) -> Result<impl IntoResponse, Error> {
+    info!("Deleting OAuth client {client_id} in organization {organization_id}");
+
+    OAuthServerApi::delete_client(app_state.db_conn_ref(), organization_id, client_id).await?;
+
+    Ok(Json(ApiResponse::<()>::no_content(
+        StatusCode::NO_CONTENT.into(),
+    )))
+}
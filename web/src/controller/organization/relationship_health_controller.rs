@@ -0,0 +1,144 @@
+use crate::extractors::compare_api_version::CompareApiVersion;
+use crate::{controller::ApiResponse, AppState, Error};
+use axum::{extract::Path, extract::State, http::StatusCode, response::IntoResponse, Json};
+use domain::{
+    relationship_health as RelationshipHealthApi, relationship_health_settings::Model, Id,
+};
+use service::config::ApiVersion;
+
+use log::*;
+
+/// GET how an organization weighs the signals of its relationships' health scores and
+/// the score below which its admins are alerted. Unset values use the defaults. Admin-only.
+#[utoipa::path(
+    get,
+    path = "/organizations/{organization_id}/relationship_health_settings",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the organization's health score settings", body = domain::relationship_health_settings::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - organization admins only"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn read_settings(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(organization_id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    let settings =
+        RelationshipHealthApi::find_settings(app_state.db_conn_ref(), organization_id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), settings)))
+}
+
+/// UPDATE an organization's health score settings. Weights and the alert threshold are
+/// 0–100 and at least one weight must be above 0. Takes effect on the next daily run.
+/// Admin-only.
+#[utoipa::path(
+    put,
+    path = "/organizations/{organization_id}/relationship_health_settings",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+    ),
+    request_body = domain::relationship_health_settings::Model,
+    responses(
+        (status = 200, description = "Health score settings updated", body = domain::relationship_health_settings::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - organization admins only"),
+        (status = 422, description = "A weight or the threshold out of range, or every weight 0"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn update_settings(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(organization_id): Path<Id>,
+    Json(settings): Json<Model>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("PUT relationship health settings for organization {organization_id}: {settings:?}");
+
+    let settings =
+        RelationshipHealthApi::update_settings(app_state.db_conn_ref(), organization_id, settings)
+            .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), settings)))
+}
+
+/// GET the latest health score of each of the organization's relationships, with the
+/// signals it combined. Relationships not scored yet are left out. Admin-only.
+#[utoipa::path(
+    get,
+    path = "/organizations/{organization_id}/relationship_health",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the latest health scores", body = [domain::relationship_health_scores::Model]),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - organization admins only"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn index(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(organization_id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    let scores = RelationshipHealthApi::find_latest_by_organization(
+        app_state.db_conn_ref(),
+        organization_id,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), scores)))
+}
+
+/// GET a relationship's health scores over the last year, oldest first. Admin-only.
+#[utoipa::path(
+    get,
+    path = "/organizations/{organization_id}/relationship_health/{relationship_id}",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+        ("relationship_id" = Id, Path, description = "The ID of the coaching relationship"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the relationship's health history", body = [domain::relationship_health_scores::Model]),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - organization admins only"),
+        (status = 404, description = "Coaching relationship not found in the organization"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn history(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path((organization_id, relationship_id)): Path<(Id, Id)>,
+) -> Result<impl IntoResponse, Error> {
+    let scores = RelationshipHealthApi::find_history(
+        app_state.db_conn_ref(),
+        organization_id,
+        relationship_id,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), scores)))
+}
@@ -0,0 +1,114 @@
+use crate::extractors::{
+    authenticated_user::AuthenticatedUser, compare_api_version::CompareApiVersion,
+};
+use crate::{controller::ApiResponse, AppState, Error};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use domain::{scim as ScimApi, Id};
+use service::config::ApiVersion;
+
+use log::*;
+
+/// GET the organization's SCIM token, without its value. Admin-only.
+#[utoipa::path(
+    get,
+    path = "/organizations/{organization_id}/scim_token",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the SCIM token", body = domain::scim_tokens::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - organization admins only"),
+        (status = 404, description = "The organization has no SCIM token"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn read(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(organization_id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    let scim_token = ScimApi::find_token(app_state.db_conn_ref(), organization_id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), scim_token)))
+}
+
+/// CREATE the token the organization's identity provider provisions users with, replacing
+/// any it had. Admin-only.
+///
+/// The response carries the token; it isn't returned again.
+#[utoipa::path(
+    post,
+    path = "/organizations/{organization_id}/scim_token",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+    ),
+    responses(
+        (status = 201, description = "SCIM token created", body = domain::scim::CreatedScimToken),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - organization admins only"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn create(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(organization_id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("POST SCIM token for organization {organization_id}");
+
+    let scim_token =
+        ScimApi::create_token(app_state.db_conn_ref(), organization_id, user.id).await?;
+
+    Ok(Json(ApiResponse::new(
+        StatusCode::CREATED.into(),
+        scim_token,
+    )))
+}
+
+/// DELETE (revoke) the organization's SCIM token. Admin-only.
+#[utoipa::path(
+    delete,
+    path = "/organizations/{organization_id}/scim_token",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+    ),
+    responses(
+        (status = 204, description = "SCIM token revoked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - organization admins only"),
+        (status = 404, description = "The organization has no SCIM token"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn delete(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(organization_id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    info!("Revoking the SCIM token of organization {organization_id}");
+
+    ScimApi::delete_token(app_state.db_conn_ref(), organization_id).await?;
+
+    Ok(Json(ApiResponse::<()>::no_content(
+        StatusCode::NO_CONTENT.into(),
+    )))
+}
@@ -0,0 +1,193 @@
+use crate::extractors::compare_api_version::CompareApiVersion;
+use crate::extractors::organization_member_access::OrganizationMemberAccess;
+use crate::params::session_type::AnalyticsParams;
+use crate::{controller::ApiResponse, AppState, Error};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use domain::{session_type as SessionTypeApi, session_types::Model, Id};
+use service::config::ApiVersion;
+
+use log::*;
+
+/// INDEX an organization's session types, alphabetically by name.
+#[utoipa::path(
+    get,
+    path = "/organizations/{organization_id}/session_types",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the organization's session types", body = [domain::session_types::Model]),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Organization not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn index(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    OrganizationMemberAccess(organization_id): OrganizationMemberAccess,
+) -> Result<impl IntoResponse, Error> {
+    let session_types =
+        SessionTypeApi::find_by_organization(app_state.db_conn_ref(), organization_id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), session_types)))
+}
+
+/// CREATE a session type for an organization. Admin-only.
+#[utoipa::path(
+    post,
+    path = "/organizations/{organization_id}/session_types",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+    ),
+    request_body = domain::session_types::Model,
+    responses(
+        (status = 201, description = "Session type created", body = domain::session_types::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 409, description = "The organization already has a session type with this name"),
+        (status = 422, description = "Invalid name, default duration or color"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn create(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(organization_id): Path<Id>,
+    Json(session_type_model): Json<Model>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("POST session type for organization {organization_id}: {session_type_model:?}");
+
+    let session_type =
+        SessionTypeApi::create(app_state.db_conn_ref(), organization_id, session_type_model)
+            .await?;
+
+    Ok(Json(ApiResponse::new(
+        StatusCode::CREATED.into(),
+        session_type,
+    )))
+}
+
+/// UPDATE a session type. Admin-only; sessions already of the type keep their durations.
+#[utoipa::path(
+    put,
+    path = "/organizations/{organization_id}/session_types/{session_type_id}",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+        ("session_type_id" = Id, Path, description = "The ID of the session type to update"),
+    ),
+    request_body = domain::session_types::Model,
+    responses(
+        (status = 200, description = "Session type updated", body = domain::session_types::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Session type not found"),
+        (status = 409, description = "The organization already has a session type with this name"),
+        (status = 422, description = "Invalid name, default duration or color"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn update(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path((organization_id, session_type_id)): Path<(Id, Id)>,
+    Json(session_type_model): Json<Model>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("PUT session type {session_type_id} in organization {organization_id}");
+
+    let session_type = SessionTypeApi::update(
+        app_state.db_conn_ref(),
+        organization_id,
+        session_type_id,
+        session_type_model,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), session_type)))
+}
+
+/// DELETE a session type. Admin-only; its sessions are kept, untyped.
+#[utoipa::path(
+    delete,
+    path = "/organizations/{organization_id}/session_types/{session_type_id}",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+        ("session_type_id" = Id, Path, description = "The ID of the session type to delete"),
+    ),
+    responses(
+        (status = 204, description = "Session type deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Session type not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn delete(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path((organization_id, session_type_id)): Path<(Id, Id)>,
+) -> Result<impl IntoResponse, Error> {
+    info!("Deleting session type {session_type_id} in organization {organization_id}");
+
+    SessionTypeApi::delete(app_state.db_conn_ref(), organization_id, session_type_id).await?;
+
+    Ok(Json(ApiResponse::<()>::no_content(
+        StatusCode::NO_CONTENT.into(),
+    )))
+}
+
+/// GET the organization's sessions in a date range counted per session type: each type's
+/// sessions and their total minutes, then the sessions without a type. Admin-only.
+#[utoipa::path(
+    get,
+    path = "/organizations/{organization_id}/session_types/analytics",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+        AnalyticsParams,
+    ),
+    responses(
+        (status = 200, description = "Successfully counted the organization's sessions per type", body = [domain::session_type::SessionTypeUsage]),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - organization admins only"),
+        (status = 422, description = "`from_date` after `to_date`"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn analytics(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(organization_id): Path<Id>,
+    Query(params): Query<AnalyticsParams>,
+) -> Result<impl IntoResponse, Error> {
+    let usage = SessionTypeApi::usage(
+        app_state.db_conn_ref(),
+        organization_id,
+        params.from_date,
+        params.to_date,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), usage)))
+}
@@ -0,0 +1,83 @@
+use crate::extractors::compare_api_version::CompareApiVersion;
+use crate::extractors::organization_member_access::OrganizationMemberAccess;
+use crate::{controller::ApiResponse, AppState, Error};
+use axum::{extract::Path, extract::State, http::StatusCode, response::IntoResponse, Json};
+use domain::{
+    organization_transcription_vocabularies::Model,
+    transcription_vocabulary as TranscriptionVocabularyApi, Id,
+};
+use service::config::ApiVersion;
+
+use log::*;
+
+/// GET an organization's transcription vocabulary: the domain terms transcription
+/// providers are asked to recognize.
+#[utoipa::path(
+    get,
+    path = "/organizations/{organization_id}/transcription_vocabulary",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the organization's transcription vocabulary", body = domain::organization_transcription_vocabularies::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Organization not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn read(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    OrganizationMemberAccess(organization_id): OrganizationMemberAccess,
+) -> Result<impl IntoResponse, Error> {
+    let vocabulary =
+        TranscriptionVocabularyApi::find(app_state.db_conn_ref(), organization_id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), vocabulary)))
+}
+
+/// UPDATE an organization's transcription vocabulary, replacing the whole list.
+/// Admin-only; applies to transcriptions started afterwards.
+#[utoipa::path(
+    put,
+    path = "/organizations/{organization_id}/transcription_vocabulary",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+    ),
+    request_body = domain::organization_transcription_vocabularies::Model,
+    responses(
+        (status = 200, description = "Transcription vocabulary updated", body = domain::organization_transcription_vocabularies::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 422, description = "Too many terms, or a term that is too long"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn update(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(organization_id): Path<Id>,
+    Json(vocabulary): Json<Model>,
+) -> Result<impl IntoResponse, Error> {
+    debug!(
+        "PUT {} transcription vocabulary term(s) for organization {organization_id}",
+        vocabulary.terms.0.len()
+    );
+
+    let vocabulary = TranscriptionVocabularyApi::update(
+        app_state.db_conn_ref(),
+        organization_id,
+        vocabulary.terms,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), vocabulary)))
+}
@@ -3,9 +3,11 @@ use crate::extractors::organization_user_access::OrganizationUserAccess;
 use crate::extractors::{
     authenticated_user::AuthenticatedUser, compare_api_version::CompareApiVersion,
 };
+use crate::params::user::LockParams;
 use crate::{controller::ApiResponse, AppState, Error};
 use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
-use domain::{emails as EmailsAPI, user as UserApi, users};
+use domain::emails::AccountLockChange;
+use domain::{emails as EmailsAPI, user as UserApi, user_lock as UserLockApi, users};
 use service::config::ApiVersion;
 
 use log::*;
@@ -55,6 +57,7 @@ pub async fn index(
         (status = 201, description = "User created successfully", body = domain::users::Model),
         (status = 401, description = "Unauthorized"),
         (status = 405, description = "Method not allowed"),
+        (status = 422, description = "The password fails the password policy"),
         (status = 503, description = "Service temporarily unavailable")
     ),
     security(
@@ -68,9 +71,13 @@ pub(crate) async fn create(
     OrganizationMemberAccess(organization_id): OrganizationMemberAccess,
     Json(user_model): Json<users::Model>,
 ) -> Result<impl IntoResponse, Error> {
-    let user =
-        UserApi::create_by_organization(app_state.db_conn_ref(), organization_id, user_model)
-            .await?;
+    let user = UserApi::create_by_organization(
+        app_state.db_conn_ref(),
+        &app_state.config,
+        organization_id,
+        user_model,
+    )
+    .await?;
     info!("User created: {user:?}");
 
     EmailsAPI::notify_welcome_email(
@@ -146,6 +153,7 @@ pub(crate) async fn resend_invite(
         (status = 200, description = "User deleted successfully"),
         (status = 401, description = "Unauthorized"),
         (status = 405, description = "Method not allowed"),
+        (status = 409, description = "The user's data is under a legal hold"),
         (status = 503, description = "Service temporarily unavailable")
     ),
     security(
@@ -164,3 +172,118 @@ pub async fn delete(
         StatusCode::NO_CONTENT.into(),
     )))
 }
+
+/// LOCK a User's account
+///
+/// The user can't log in until unlocked, their sessions are logged out on their next
+/// request and their open SSE connections get a `force_logout` event (reason
+/// `account_locked`) and are closed. The organization's admins are emailed.
+#[utoipa::path(
+    post,
+    path = "/organizations/{organization_id}/users/{user_id}/lock",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+        ("user_id" = Id, Path, description = "The ID of the user to lock")
+    ),
+    request_body = LockParams,
+    responses(
+        (status = 200, description = "User locked", body = domain::user_locks::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not an admin of the organization, locking yourself, or locking a SuperAdmin or an admin of another organization"),
+        (status = 409, description = "The user is already locked"),
+        (status = 422, description = "The reason is too long"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn lock(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    AuthenticatedUser(authenticated_user): AuthenticatedUser,
+    OrganizationMemberAccess(organization_id): OrganizationMemberAccess,
+    OrganizationUserAccess(user): OrganizationUserAccess,
+    Json(params): Json<LockParams>,
+) -> Result<impl IntoResponse, Error> {
+    let lock = UserLockApi::lock(
+        app_state.db_conn_ref(),
+        app_state.event_publisher.as_ref(),
+        organization_id,
+        user.id,
+        authenticated_user.id,
+        params.reason,
+    )
+    .await?;
+
+    EmailsAPI::notify_account_lock(
+        app_state.db_conn_ref(),
+        &app_state.config,
+        organization_id,
+        &AccountLockChange {
+            user: &user,
+            actor: &authenticated_user,
+            locked: true,
+            reason: lock.reason.as_deref(),
+        },
+    )
+    .await;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), lock)))
+}
+
+/// UNLOCK a User's account locked by this organization
+#[utoipa::path(
+    post,
+    path = "/organizations/{organization_id}/users/{user_id}/unlock",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+        ("user_id" = Id, Path, description = "The ID of the user to unlock")
+    ),
+    responses(
+        (status = 204, description = "User unlocked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not an admin of the organization, or unlocking yourself"),
+        (status = 404, description = "The user isn't locked"),
+        (status = 409, description = "The user was locked by another organization"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn unlock(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    AuthenticatedUser(authenticated_user): AuthenticatedUser,
+    OrganizationMemberAccess(organization_id): OrganizationMemberAccess,
+    OrganizationUserAccess(user): OrganizationUserAccess,
+) -> Result<impl IntoResponse, Error> {
+    UserLockApi::unlock(
+        app_state.db_conn_ref(),
+        app_state.event_publisher.as_ref(),
+        organization_id,
+        user.id,
+        authenticated_user.id,
+    )
+    .await?;
+
+    EmailsAPI::notify_account_lock(
+        app_state.db_conn_ref(),
+        &app_state.config,
+        organization_id,
+        &AccountLockChange {
+            user: &user,
+            actor: &authenticated_user,
+            locked: false,
+            reason: None,
+        },
+    )
+    .await;
+
+    Ok(Json(ApiResponse::<()>::no_content(
+        StatusCode::NO_CONTENT.into(),
+    )))
+}
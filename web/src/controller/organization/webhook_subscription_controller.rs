@@ -0,0 +1,169 @@
+use crate::extractors::{
+    authenticated_user::AuthenticatedUser, compare_api_version::CompareApiVersion,
+};
+use crate::{controller::ApiResponse, AppState, Error};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use domain::{webhook_subscription as WebhookSubscriptionApi, webhook_subscriptions::Model, Id};
+use service::config::ApiVersion;
+
+use log::*;
+
+/// INDEX an organization's webhook subscriptions, oldest first. Admin-only; secrets are
+/// not included.
+#[utoipa::path(
+    get,
+    path = "/organizations/{organization_id}/webhooks",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the organization's webhook subscriptions", body = [domain::webhook_subscriptions::Model]),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - organization admins only"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn index(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(organization_id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    let subscriptions =
+        WebhookSubscriptionApi::find_by_organization(app_state.db_conn_ref(), organization_id)
+            .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), subscriptions)))
+}
+
+/// CREATE a webhook subscription for an organization. Admin-only.
+///
+/// The response carries the secret payloads are signed with; it isn't returned again.
+#[utoipa::path(
+    post,
+    path = "/organizations/{organization_id}/webhooks",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+    ),
+    request_body = domain::webhook_subscriptions::Model,
+    responses(
+        (status = 201, description = "Webhook subscription created", body = domain::webhook_subscription::CreatedWebhookSubscription),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - organization admins only"),
+        (status = 422, description = "Invalid or non-public URL, or invalid event types"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn create(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(organization_id): Path<Id>,
+    Json(subscription_model): Json<Model>,
+) -> Result<impl IntoResponse, Error> {
+    debug!(
+        "POST webhook subscription for organization {organization_id} to {}",
+        subscription_model.url
+    );
+
+    let subscription = WebhookSubscriptionApi::create(
+        app_state.db_conn_ref(),
+        organization_id,
+        user.id,
+        subscription_model,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(
+        StatusCode::CREATED.into(),
+        subscription,
+    )))
+}
+
+/// UPDATE a webhook subscription's URL, event types or enabled flag. Admin-only; the
+/// secret is kept.
+#[utoipa::path(
+    put,
+    path = "/organizations/{organization_id}/webhooks/{webhook_id}",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+        ("webhook_id" = Id, Path, description = "The ID of the webhook subscription to update"),
+    ),
+    request_body = domain::webhook_subscriptions::Model,
+    responses(
+        (status = 200, description = "Webhook subscription updated", body = domain::webhook_subscriptions::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - organization admins only"),
+        (status = 404, description = "Webhook subscription not found"),
+        (status = 422, description = "Invalid or non-public URL, or invalid event types"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn update(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path((organization_id, webhook_id)): Path<(Id, Id)>,
+    Json(subscription_model): Json<Model>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("PUT webhook subscription {webhook_id} in organization {organization_id}");
+
+    let subscription = WebhookSubscriptionApi::update(
+        app_state.db_conn_ref(),
+        organization_id,
+        webhook_id,
+        subscription_model,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), subscription)))
+}
+
+/// DELETE a webhook subscription. Admin-only.
+#[utoipa::path(
+    delete,
+    path = "/organizations/{organization_id}/webhooks/{webhook_id}",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+        ("webhook_id" = Id, Path, description = "The ID of the webhook subscription to delete"),
+    ),
+    responses(
+        (status = 204, description = "Webhook subscription deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - organization admins only"),
+        (status = 404, description = "Webhook subscription not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn delete(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path((organization_id, webhook_id)): Path<(Id, Id)>,
+) -> Result<impl IntoResponse, Error> {
+    info!("Deleting webhook subscription {webhook_id} in organization {organization_id}");
+
+    WebhookSubscriptionApi::delete(app_state.db_conn_ref(), organization_id, webhook_id).await?;
+
+    Ok(Json(ApiResponse::<()>::no_content(
+        StatusCode::NO_CONTENT.into(),
+    )))
+}
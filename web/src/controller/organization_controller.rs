@@ -176,6 +176,7 @@ pub async fn update(
         (status = 401, description = "Unauthorized"),
         (status = 404, description = "Organization not found"),
         (status = 405, description = "Method not allowed"),
+        (status = 409, description = "Organization still has members or relationships, or is under a legal hold"),
         (status = 503, description = "Service temporarily unavailable")
     ),
     security(
@@ -37,12 +37,7 @@ const USER_AGENT_LOG_MAX_CHARS: usize = 200;
 
 /// Best-effort extraction of the client's IP and User-Agent for logging.
 ///
-/// IP precedence (matches the trust model documented in
-/// `web/src/middleware/throttle.rs`):
-/// 1. `X-Forwarded-For` (first hop) — set by our nginx front-end.
-/// 2. `X-Real-IP` — also set by nginx; used if XFF is absent.
-/// 3. TCP peer address from `ConnectInfo` — accurate only when no proxy is in
-///    front of us (e.g. local dev).
+/// The IP is taken as `crate::middleware::client_ip` documents.
 ///
 /// Both values are **for logging only**. Spoofable without a trusted proxy;
 /// never use them for authorization or rate-limit decisions.
@@ -50,20 +45,7 @@ fn extract_client_metadata(
     connect_info: SocketAddr,
     headers: &HeaderMap,
 ) -> (Option<String>, Option<String>) {
-    let ip = headers
-        .get("x-forwarded-for")
-        .and_then(|v| v.to_str().ok())
-        .and_then(|s| s.split(',').next())
-        .map(|s| s.trim().to_string())
-        .filter(|s| !s.is_empty())
-        .or_else(|| {
-            headers
-                .get("x-real-ip")
-                .and_then(|v| v.to_str().ok())
-                .map(|s| s.trim().to_string())
-                .filter(|s| !s.is_empty())
-        })
-        .or_else(|| Some(connect_info.ip().to_string()));
+    let ip = crate::middleware::client_ip::client_ip(headers, Some(connect_info.ip()));
 
     let user_agent = headers
         .get("user-agent")
@@ -207,7 +189,7 @@ pub(crate) async fn validate(
     responses(
         (status = 200, description = "Password updated; user can now log in with the new password", body = domain::users::Model),
         (status = 400, description = "Token invalid, expired, or wrong purpose"),
-        (status = 422, description = "Password confirmation does not match"),
+        (status = 422, description = "Password confirmation does not match, or the password fails the password policy"),
         (status = 503, description = "Service temporarily unavailable"),
     )
 )]
@@ -221,8 +203,12 @@ pub(crate) async fn complete(
     // DB-bound complete flow runs).
     validate_token_length(&params.token)?;
 
-    let updated_user =
-        PasswordResetApi::complete_password_reset(app_state.db_conn_ref(), params).await?;
+    let updated_user = PasswordResetApi::complete_password_reset(
+        app_state.db_conn_ref(),
+        &app_state.config,
+        params,
+    )
+    .await?;
 
     Ok(Json(ApiResponse::new(StatusCode::OK.into(), updated_user)))
 }
@@ -0,0 +1,123 @@
+//! Admin endpoints for the versioned system prompts of AI features.
+//!
+//! Gated by SuperAdmin via the `protect::prompt_templates::admin_only` middleware in the
+//! router.
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::prompt_key::PromptKey;
+use domain::prompt_template as PromptTemplateApi;
+use serde::Deserialize;
+use service::config::ApiVersion;
+use utoipa::ToSchema;
+
+use crate::controller::ApiResponse;
+use crate::extractors::{
+    authenticated_user::AuthenticatedUser, compare_api_version::CompareApiVersion,
+};
+use crate::{AppState, Error};
+
+use log::*;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateVersionParams {
+    /// Prompt text; may use the key's `{{variable}}` placeholders.
+    pub body: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ActivateParams {
+    /// Stored version to use, or 0 for the built-in prompt.
+    pub version: i32,
+}
+
+/// GET a prompt's built-in text, placeholders, stored versions and the active version
+#[utoipa::path(
+    get,
+    path = "/admin/prompt_templates/{key}",
+    params(
+        ApiVersion,
+        ("key" = PromptKey, Path, description = "AI feature whose prompt to read"),
+    ),
+    responses(
+        (status = 200, description = "Prompt versions retrieved"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - SuperAdmin only"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn read(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(key): Path<PromptKey>,
+) -> Result<impl IntoResponse, Error> {
+    let history = PromptTemplateApi::find_history(app_state.db_conn_ref(), key).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), history)))
+}
+
+/// POST a new version of a prompt, which becomes the active one
+#[utoipa::path(
+    post,
+    path = "/admin/prompt_templates/{key}",
+    params(
+        ApiVersion,
+        ("key" = PromptKey, Path, description = "AI feature whose prompt to edit"),
+    ),
+    request_body = CreateVersionParams,
+    responses(
+        (status = 201, description = "Prompt version created", body = domain::prompt_templates::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - SuperAdmin only"),
+        (status = 422, description = "Empty or overlong prompt, or an unknown placeholder"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn create(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    State(app_state): State<AppState>,
+    Path(key): Path<PromptKey>,
+    Json(params): Json<CreateVersionParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("POST {key} prompt version by user {}", user.id);
+
+    let template =
+        PromptTemplateApi::create_version(app_state.db_conn_ref(), key, user.id, &params.body)
+            .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::CREATED.into(), template)))
+}
+
+/// PUT which version of a prompt is active, e.g. to roll back an edit
+#[utoipa::path(
+    put,
+    path = "/admin/prompt_templates/{key}/active",
+    params(
+        ApiVersion,
+        ("key" = PromptKey, Path, description = "AI feature whose prompt to roll back"),
+    ),
+    request_body = ActivateParams,
+    responses(
+        (status = 200, description = "Active prompt version changed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - SuperAdmin only"),
+        (status = 404, description = "Prompt version not found"),
+        (status = 422, description = "Negative version"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn activate(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(key): Path<PromptKey>,
+    Json(params): Json<ActivateParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("PUT active {key} prompt version {}", params.version);
+
+    let history = PromptTemplateApi::activate(app_state.db_conn_ref(), key, params.version).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), history)))
+}
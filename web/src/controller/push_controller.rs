@@ -0,0 +1,48 @@
+use crate::controller::ApiResponse;
+use crate::extractors::{
+    authenticated_user::AuthenticatedUser, compare_api_version::CompareApiVersion,
+};
+use crate::{AppState, Error};
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::push as PushApi;
+use serde::Serialize;
+use service::config::ApiVersion;
+use utoipa::ToSchema;
+
+/// The key browsers subscribe to push notifications with.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VapidPublicKey {
+    /// Base64url-encoded; pass it to `PushManager.subscribe` as `applicationServerKey`.
+    pub public_key: String,
+}
+
+/// GET the server's VAPID public key, for subscribing a browser to push notifications.
+#[utoipa::path(
+    get,
+    path = "/push/vapid_public_key",
+    params(ApiVersion),
+    responses(
+        (status = 200, description = "Successfully retrieved the VAPID public key", body = VapidPublicKey),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Push notifications aren't configured"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn vapid_public_key(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(_user): AuthenticatedUser,
+    State(app_state): State<AppState>,
+) -> Result<impl IntoResponse, Error> {
+    let public_key = PushApi::vapid_public_key(&app_state.config)?;
+
+    Ok(Json(ApiResponse::new(
+        StatusCode::OK.into(),
+        VapidPublicKey { public_key },
+    )))
+}
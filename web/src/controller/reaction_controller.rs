@@ -0,0 +1,91 @@
+use crate::controller::ApiResponse;
+use crate::extractors::{
+    authenticated_user::AuthenticatedUser, compare_api_version::CompareApiVersion,
+};
+use crate::params::reaction::CreateParams;
+use crate::{AppState, Error};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::{reaction as ReactionApi, Id};
+use log::*;
+use serde_json::json;
+use service::config::ApiVersion;
+
+/// POST an emoji reaction on a note or action.
+///
+/// Idempotent per (caller, target, emoji). Participant-only: the caller must be the
+/// coach or coachee of the owning relationship. Publishes `reactions_changed` over SSE.
+#[utoipa::path(
+    post,
+    path = "/reactions",
+    params(ApiVersion),
+    request_body = crate::params::reaction::CreateParams,
+    responses(
+        (status = 201, description = "Reaction added (or already present)", body = domain::reactions::Model),
+        (status = 401, description = "Unauthorized or not a participant"),
+        (status = 404, description = "Note or action not found"),
+        (status = 422, description = "Invalid emoji or target type"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn create(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    State(app_state): State<AppState>,
+    Json(params): Json<CreateParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!(
+        "POST reaction {} on {:?} {} by user {}",
+        params.emoji, params.resource_type, params.resource_id, user.id
+    );
+
+    let reaction = ReactionApi::create(
+        app_state.db_conn_ref(),
+        app_state.event_publisher.as_ref(),
+        user.id,
+        params.resource_type,
+        params.resource_id,
+        params.emoji,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::CREATED.into(), reaction)))
+}
+
+/// DELETE one of the caller's own reactions by its id.
+#[utoipa::path(
+    delete,
+    path = "/reactions/{id}",
+    params(
+        ApiVersion,
+        ("id" = Id, Path, description = "Reaction id to delete")
+    ),
+    responses(
+        (status = 200, description = "Reaction removed"),
+        (status = 401, description = "Unauthorized or not the reaction's author"),
+        (status = 404, description = "Reaction not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn delete(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    State(app_state): State<AppState>,
+    Path(id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("DELETE reaction {id} by user {}", user.id);
+
+    ReactionApi::delete(
+        app_state.db_conn_ref(),
+        app_state.event_publisher.as_ref(),
+        user.id,
+        id,
+    )
+    .await?;
+
+    Ok(Json(json!({"id": id})))
+}
@@ -0,0 +1,52 @@
+use crate::controller::ApiResponse;
+use crate::extractors::{
+    authenticated_user::AuthenticatedUser, compare_api_version::CompareApiVersion,
+};
+use crate::params::resource_view::CreateParams;
+use crate::{AppState, Error};
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::resource_view as ResourceViewApi;
+use log::*;
+use service::config::ApiVersion;
+
+/// Mark a coaching session, action or note as seen by the caller, returning the prior marker.
+///
+/// Clears `has_unread` for the caller until the resource next changes. Idempotent.
+/// Participant-only: the caller must be the coach or coachee of the owning relationship.
+#[utoipa::path(
+    post,
+    path = "/views",
+    params(ApiVersion),
+    request_body = crate::params::resource_view::CreateParams,
+    responses(
+        (status = 200, description = "Marker advanced; prior value returned", body = domain::coaching_session_view::MarkViewed),
+        (status = 401, description = "Unauthorized or not a participant"),
+        (status = 404, description = "Resource not found"),
+        (status = 422, description = "Unprocessable Entity"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn create(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    State(app_state): State<AppState>,
+    Json(params): Json<CreateParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!(
+        "POST view of {:?} {} by user {}",
+        params.resource_type, params.resource_id, user.id
+    );
+
+    let marker = ResourceViewApi::mark_viewed(
+        app_state.db_conn_ref(),
+        user.id,
+        params.resource_type,
+        params.resource_id,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), marker)))
+}
@@ -0,0 +1,61 @@
+//! Admin endpoint for reloading the runtime settings without a restart.
+//!
+//! Gated by SuperAdmin via the `protect::runtime_config::admin_only` middleware in the
+//! router.
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::error::{DomainErrorKind, Error as DomainError};
+use log::*;
+use serde::Serialize;
+use service::config::ApiVersion;
+use utoipa::ToSchema;
+
+use crate::controller::ApiResponse;
+use crate::extractors::compare_api_version::CompareApiVersion;
+use crate::{AppState, Error};
+
+/// The outcome of a reload
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReloadResponse {
+    /// One "setting: old -> new" line per setting the reload changed
+    pub changes: Vec<String>,
+}
+
+/// POST to re-read `RUNTIME_CONFIG_FILE` and apply its settings
+///
+/// Applies on the replica that served the request; with several behind a load balancer,
+/// send the others SIGHUP or reload each of them.
+#[utoipa::path(
+    post,
+    path = "/admin/config/reload",
+    params(ApiVersion),
+    responses(
+        (status = 200, description = "Runtime config reloaded", body = ReloadResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - SuperAdmin only"),
+        (status = 422, description = "The runtime config file is invalid; nothing changed"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn reload(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("POST reload the runtime config");
+
+    let changes = app_state.runtime_settings.reload().map_err(|e| {
+        warn!("Runtime config not reloaded: {e}");
+        Error::Domain(DomainError {
+            source: None,
+            error_kind: DomainErrorKind::Validation(e.to_string()),
+        })
+    })?;
+
+    Ok(Json(ApiResponse::new(
+        StatusCode::OK.into(),
+        ReloadResponse { changes },
+    )))
+}
@@ -0,0 +1,112 @@
+use super::{group_resource, list_response, ScimError, ScimJson};
+use crate::extractors::scim_organization::ScimOrganization;
+use crate::params::scim::{GroupParams, ListParams, PatchParams};
+use crate::AppState;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use domain::scim::{self as ScimApi, Filter};
+use domain::Id;
+
+/// GET /scim/v2/Groups — the organization's groups matching `filter`.
+pub async fn index(
+    State(app_state): State<AppState>,
+    ScimOrganization(organization_id): ScimOrganization,
+    Query(params): Query<ListParams>,
+) -> Result<impl IntoResponse, ScimError> {
+    let filter = params
+        .filter
+        .as_deref()
+        .map(Filter::parse)
+        .transpose()
+        .map_err(|err| ScimError {
+            scim_type: Some("invalidFilter"),
+            ..err.into()
+        })?;
+    let groups =
+        ScimApi::find_groups(app_state.db_conn_ref(), organization_id, filter.as_ref()).await?;
+
+    Ok(ScimJson(
+        StatusCode::OK,
+        list_response(groups, params.start_index, params.count, |group| {
+            group_resource(&group)
+        }),
+    ))
+}
+
+/// GET /scim/v2/Groups/:id
+pub async fn read(
+    State(app_state): State<AppState>,
+    ScimOrganization(organization_id): ScimOrganization,
+    Path(group_id): Path<String>,
+) -> Result<impl IntoResponse, ScimError> {
+    let group = ScimApi::find_group(app_state.db_conn_ref(), organization_id, &group_id).await?;
+
+    Ok(ScimJson(StatusCode::OK, group_resource(&group)))
+}
+
+/// POST /scim/v2/Groups — links to the fixed group with the given `displayName`, setting
+/// its members when any are given.
+pub async fn create(
+    State(app_state): State<AppState>,
+    ScimOrganization(organization_id): ScimOrganization,
+    Json(params): Json<GroupParams>,
+) -> Result<impl IntoResponse, ScimError> {
+    let db = app_state.db_conn_ref();
+    let mut group = ScimApi::create_group(db, organization_id, &params.display_name).await?;
+    if !params.members.is_empty() {
+        let member_ids: Vec<Id> = params.members.iter().map(|member| member.value).collect();
+        group = ScimApi::replace_group_members(
+            db,
+            &app_state.event_publisher,
+            organization_id,
+            &group.role.to_string(),
+            &member_ids,
+        )
+        .await?;
+    }
+
+    Ok(ScimJson(StatusCode::CREATED, group_resource(&group)))
+}
+
+/// PUT /scim/v2/Groups/:id — replaces a group's members.
+pub async fn update(
+    State(app_state): State<AppState>,
+    ScimOrganization(organization_id): ScimOrganization,
+    Path(group_id): Path<String>,
+    Json(params): Json<GroupParams>,
+) -> Result<impl IntoResponse, ScimError> {
+    let member_ids: Vec<Id> = params.members.iter().map(|member| member.value).collect();
+    let group = ScimApi::replace_group_members(
+        app_state.db_conn_ref(),
+        &app_state.event_publisher,
+        organization_id,
+        &group_id,
+        &member_ids,
+    )
+    .await?;
+
+    Ok(ScimJson(StatusCode::OK, group_resource(&group)))
+}
+
+/// PATCH /scim/v2/Groups/:id — adds or removes members.
+pub async fn patch(
+    State(app_state): State<AppState>,
+    ScimOrganization(organization_id): ScimOrganization,
+    Path(group_id): Path<String>,
+    Json(params): Json<PatchParams>,
+) -> Result<impl IntoResponse, ScimError> {
+    let group = ScimApi::patch_group(
+        app_state.db_conn_ref(),
+        &app_state.event_publisher,
+        organization_id,
+        &group_id,
+        &params.operations,
+    )
+    .await?;
+
+    Ok(ScimJson(StatusCode::OK, group_resource(&group)))
+}
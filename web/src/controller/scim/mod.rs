@@ -0,0 +1,233 @@
+//! SCIM 2.0 API (RFC 7644) through which organizations' identity providers provision
+//! their users (see `domain::scim`).
+//!
+//! Requests authenticate with the organization's SCIM token as an `Authorization:
+//! Bearer` header (see [`ScimOrganization`]) and act on that organization only. Responses
+//! and errors use SCIM's own JSON shapes and `application/scim+json` content type rather
+//! than the platform's `ApiResponse` envelope, which identity providers don't understand.
+//!
+//! [`ScimOrganization`]: crate::extractors::scim_organization::ScimOrganization
+
+use axum::{
+    http::{header::CONTENT_TYPE, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use domain::error::{DomainErrorKind, EntityErrorKind, Error as DomainError, InternalErrorKind};
+use domain::scim::{Group, ProvisionedUser, GROUPS};
+use domain::Id;
+use log::*;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+pub(crate) mod group_controller;
+pub(crate) mod service_provider_config_controller;
+pub(crate) mod user_controller;
+
+const USER_SCHEMA: &str = "urn:ietf:params:scim:schemas:core:2.0:User";
+const GROUP_SCHEMA: &str = "urn:ietf:params:scim:schemas:core:2.0:Group";
+const LIST_RESPONSE_SCHEMA: &str = "urn:ietf:params:scim:api:messages:2.0:ListResponse";
+const ERROR_SCHEMA: &str = "urn:ietf:params:scim:api:messages:2.0:Error";
+
+/// Default and largest page size of list responses.
+const MAX_PAGE_SIZE: usize = 1000;
+
+/// A SCIM response body, sent as `application/scim+json`.
+pub(crate) struct ScimJson<T>(pub StatusCode, pub T);
+
+impl<T: Serialize> IntoResponse for ScimJson<T> {
+    fn into_response(self) -> Response {
+        let mut response = (self.0, Json(self.1)).into_response();
+        response.headers_mut().insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/scim+json"),
+        );
+        response
+    }
+}
+
+/// A SCIM error response (RFC 7644 section 3.12).
+#[derive(Debug)]
+pub(crate) struct ScimError {
+    pub(crate) status: StatusCode,
+    /// e.g. `uniqueness`, `invalidFilter`, `invalidValue`
+    pub(crate) scim_type: Option<&'static str>,
+    pub(crate) detail: String,
+}
+
+impl ScimError {
+    pub(crate) fn new(status: StatusCode, scim_type: Option<&'static str>, detail: &str) -> Self {
+        ScimError {
+            status,
+            scim_type,
+            detail: detail.to_string(),
+        }
+    }
+}
+
+impl IntoResponse for ScimError {
+    fn into_response(self) -> Response {
+        let mut body = json!({
+            "schemas": [ERROR_SCHEMA],
+            "status": self.status.as_u16().to_string(),
+            "detail": self.detail,
+        });
+        if let Some(scim_type) = self.scim_type {
+            body["scimType"] = json!(scim_type);
+        }
+        ScimJson(self.status, body).into_response()
+    }
+}
+
+impl From<DomainError> for ScimError {
+    fn from(err: DomainError) -> Self {
+        match err.error_kind {
+            DomainErrorKind::Validation(message) => {
+                ScimError::new(StatusCode::BAD_REQUEST, Some("invalidValue"), &message)
+            }
+            DomainErrorKind::Internal(InternalErrorKind::Entity(EntityErrorKind::NotFound)) => {
+                ScimError::new(StatusCode::NOT_FOUND, None, "Resource not found")
+            }
+            DomainErrorKind::Internal(InternalErrorKind::Entity(EntityErrorKind::Conflict {
+                message,
+                ..
+            })) => ScimError::new(StatusCode::CONFLICT, Some("uniqueness"), &message),
+            error_kind => {
+                error!("SCIM request failed: {error_kind:?}");
+                ScimError::new(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    None,
+                    "Internal server error",
+                )
+            }
+        }
+    }
+}
+
+/// A page of resources (RFC 7644 section 3.4.2), `start_index` being 1-based.
+pub(crate) fn list_response<T>(
+    resources: Vec<T>,
+    start_index: Option<usize>,
+    count: Option<usize>,
+    render: impl Fn(T) -> Value,
+) -> Value {
+    let total_results = resources.len();
+    let start_index = start_index.unwrap_or(1).max(1);
+    let count = count.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE);
+    let page: Vec<Value> = resources
+        .into_iter()
+        .skip(start_index - 1)
+        .take(count)
+        .map(render)
+        .collect();
+
+    json!({
+        "schemas": [LIST_RESPONSE_SCHEMA],
+        "totalResults": total_results,
+        "startIndex": start_index,
+        "itemsPerPage": page.len(),
+        "Resources": page,
+    })
+}
+
+/// A provisioned user as a SCIM User resource, with the organization's groups they're in.
+pub(crate) fn user_resource(organization_id: Id, provisioned: &ProvisionedUser) -> Value {
+    let user = &provisioned.user;
+    let groups: Vec<Value> = GROUPS
+        .iter()
+        .filter(|(role, _)| provisioned.has_role(organization_id, role))
+        .map(|(role, display_name)| {
+            json!({
+                "value": role.to_string(),
+                "display": display_name,
+                "$ref": format!("/scim/v2/Groups/{role}"),
+            })
+        })
+        .collect();
+
+    json!({
+        "schemas": [USER_SCHEMA],
+        "id": user.id,
+        "externalId": provisioned.external_id,
+        "userName": user.email,
+        "name": {
+            "givenName": user.first_name,
+            "familyName": user.last_name,
+            "formatted": format!("{} {}", user.first_name, user.last_name).trim(),
+        },
+        "displayName": user.display_name,
+        "emails": [{ "value": user.email, "type": "work", "primary": true }],
+        "active": provisioned.active,
+        "groups": groups,
+        "meta": {
+            "resourceType": "User",
+            "created": user.created_at,
+            "lastModified": user.updated_at,
+            "location": format!("/scim/v2/Users/{}", user.id),
+        },
+    })
+}
+
+/// A group as a SCIM Group resource.
+pub(crate) fn group_resource(group: &Group) -> Value {
+    let members: Vec<Value> = group
+        .members
+        .iter()
+        .map(|member| {
+            json!({
+                "value": member.user.id,
+                "display": member.user.email,
+                "$ref": format!("/scim/v2/Users/{}", member.user.id),
+            })
+        })
+        .collect();
+
+    json!({
+        "schemas": [GROUP_SCHEMA],
+        "id": group.role.to_string(),
+        "displayName": group.display_name,
+        "members": members,
+        "meta": {
+            "resourceType": "Group",
+            "location": format!("/scim/v2/Groups/{}", group.role),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_response_pages_from_a_one_based_start_index() {
+        let page = list_response(vec![1, 2, 3, 4], Some(2), Some(2), |n| json!(n));
+
+        assert_eq!(page["totalResults"], 4);
+        assert_eq!(page["startIndex"], 2);
+        assert_eq!(page["itemsPerPage"], 2);
+        assert_eq!(page["Resources"], json!([2, 3]));
+    }
+
+    #[test]
+    fn domain_errors_become_scim_errors() {
+        let conflict: ScimError = DomainError {
+            source: None,
+            error_kind: DomainErrorKind::Internal(InternalErrorKind::Entity(
+                EntityErrorKind::Conflict {
+                    message: "taken".to_string(),
+                    details: None,
+                },
+            )),
+        }
+        .into();
+        assert_eq!(conflict.status, StatusCode::CONFLICT);
+        assert_eq!(conflict.scim_type, Some("uniqueness"));
+
+        let invalid: ScimError = DomainError {
+            source: None,
+            error_kind: DomainErrorKind::Validation("bad".to_string()),
+        }
+        .into();
+        assert_eq!(invalid.status, StatusCode::BAD_REQUEST);
+    }
+}
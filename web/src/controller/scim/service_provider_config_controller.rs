@@ -0,0 +1,27 @@
+use super::{ScimJson, MAX_PAGE_SIZE};
+use crate::extractors::scim_organization::ScimOrganization;
+use axum::{http::StatusCode, response::IntoResponse};
+use serde_json::json;
+
+/// GET /scim/v2/ServiceProviderConfig — the SCIM features supported, which identity
+/// providers check before provisioning.
+pub async fn read(ScimOrganization(_organization_id): ScimOrganization) -> impl IntoResponse {
+    ScimJson(
+        StatusCode::OK,
+        json!({
+            "schemas": ["urn:ietf:params:scim:schemas:core:2.0:ServiceProviderConfig"],
+            "patch": { "supported": true },
+            "bulk": { "supported": false, "maxOperations": 0, "maxPayloadSize": 0 },
+            "filter": { "supported": true, "maxResults": MAX_PAGE_SIZE },
+            "changePassword": { "supported": false },
+            "sort": { "supported": false },
+            "etag": { "supported": false },
+            "authenticationSchemes": [{
+                "type": "oauthbearertoken",
+                "name": "OAuth Bearer Token",
+                "description": "The organization's SCIM token",
+                "primary": true,
+            }],
+        }),
+    )
+}
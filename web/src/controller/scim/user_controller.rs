@@ -0,0 +1,139 @@
+use super::{list_response, user_resource, ScimError, ScimJson};
+use crate::extractors::scim_organization::ScimOrganization;
+use crate::params::scim::{ListParams, PatchParams, UserParams};
+use crate::AppState;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use domain::scim::{self as ScimApi, Filter};
+use domain::Id;
+use log::*;
+
+/// GET /scim/v2/Users — the organization's provisioned users matching `filter`.
+pub async fn index(
+    State(app_state): State<AppState>,
+    ScimOrganization(organization_id): ScimOrganization,
+    Query(params): Query<ListParams>,
+) -> Result<impl IntoResponse, ScimError> {
+    let filter = params
+        .filter
+        .as_deref()
+        .map(Filter::parse)
+        .transpose()
+        .map_err(|err| ScimError {
+            scim_type: Some("invalidFilter"),
+            ..err.into()
+        })?;
+    let users =
+        ScimApi::find_users(app_state.db_conn_ref(), organization_id, filter.as_ref()).await?;
+
+    Ok(ScimJson(
+        StatusCode::OK,
+        list_response(users, params.start_index, params.count, |user| {
+            user_resource(organization_id, &user)
+        }),
+    ))
+}
+
+/// GET /scim/v2/Users/:id
+pub async fn read(
+    State(app_state): State<AppState>,
+    ScimOrganization(organization_id): ScimOrganization,
+    Path(user_id): Path<Id>,
+) -> Result<impl IntoResponse, ScimError> {
+    let user = ScimApi::find_user(app_state.db_conn_ref(), organization_id, user_id).await?;
+
+    Ok(ScimJson(
+        StatusCode::OK,
+        user_resource(organization_id, &user),
+    ))
+}
+
+/// POST /scim/v2/Users — provisions a user.
+pub async fn create(
+    State(app_state): State<AppState>,
+    ScimOrganization(organization_id): ScimOrganization,
+    Json(params): Json<UserParams>,
+) -> Result<impl IntoResponse, ScimError> {
+    debug!(
+        "SCIM provisioning {} in organization {organization_id}",
+        params.user_name
+    );
+
+    let user = ScimApi::create_user(
+        app_state.db_conn_ref(),
+        &app_state.config,
+        organization_id,
+        params.into(),
+    )
+    .await?;
+
+    Ok(ScimJson(
+        StatusCode::CREATED,
+        user_resource(organization_id, &user),
+    ))
+}
+
+/// PUT /scim/v2/Users/:id — replaces a user's attributes.
+pub async fn update(
+    State(app_state): State<AppState>,
+    ScimOrganization(organization_id): ScimOrganization,
+    Path(user_id): Path<Id>,
+    Json(params): Json<UserParams>,
+) -> Result<impl IntoResponse, ScimError> {
+    let user = ScimApi::update_user(
+        app_state.db_conn_ref(),
+        &app_state.event_publisher,
+        organization_id,
+        user_id,
+        params.into(),
+    )
+    .await?;
+
+    Ok(ScimJson(
+        StatusCode::OK,
+        user_resource(organization_id, &user),
+    ))
+}
+
+/// PATCH /scim/v2/Users/:id — changes some of a user's attributes, e.g. `active`.
+pub async fn patch(
+    State(app_state): State<AppState>,
+    ScimOrganization(organization_id): ScimOrganization,
+    Path(user_id): Path<Id>,
+    Json(params): Json<PatchParams>,
+) -> Result<impl IntoResponse, ScimError> {
+    let user = ScimApi::patch_user(
+        app_state.db_conn_ref(),
+        &app_state.event_publisher,
+        organization_id,
+        user_id,
+        &params.operations,
+    )
+    .await?;
+
+    Ok(ScimJson(
+        StatusCode::OK,
+        user_resource(organization_id, &user),
+    ))
+}
+
+/// DELETE /scim/v2/Users/:id — deprovisions a user, keeping their account.
+pub async fn delete(
+    State(app_state): State<AppState>,
+    ScimOrganization(organization_id): ScimOrganization,
+    Path(user_id): Path<Id>,
+) -> Result<impl IntoResponse, ScimError> {
+    ScimApi::delete_user(
+        app_state.db_conn_ref(),
+        &app_state.event_publisher,
+        organization_id,
+        user_id,
+    )
+    .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
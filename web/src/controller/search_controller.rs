@@ -0,0 +1,48 @@
+use crate::controller::ApiResponse;
+use crate::extractors::{
+    authenticated_user::AuthenticatedUser, compare_api_version::CompareApiVersion,
+};
+use crate::params::search::SearchParams;
+use crate::{AppState, Error};
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::search as SearchApi;
+use service::config::ApiVersion;
+
+use log::*;
+
+/// SEARCH the notes, actions, agreements and goals of the authenticated user's coaching
+/// relationships, best match first. Each result carries its type and a highlighted
+/// snippet of the matched text. Encrypted notes aren't searched.
+#[utoipa::path(
+    get,
+    path = "/search",
+    params(
+        ApiVersion,
+        SearchParams,
+    ),
+    responses(
+        (status = 200, description = "Successfully searched", body = [domain::search::SearchResult]),
+        (status = 401, description = "Unauthorized"),
+        (status = 422, description = "`q` blank or too long, or `limit` out of range"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn search(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    State(app_state): State<AppState>,
+    Query(params): Query<SearchParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("GET search for user {}", user.id);
+
+    let results =
+        SearchApi::search(app_state.db_conn_ref(), user.id, &params.q, params.limit).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), results)))
+}
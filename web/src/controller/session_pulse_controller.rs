@@ -0,0 +1,182 @@
+use crate::controller::ApiResponse;
+use crate::extractors::{
+    authenticated_user::AuthenticatedUser,
+    coaching_relationship_access::CoachingRelationshipAccess,
+    coaching_session_access::CoachingSessionAccess, compare_api_version::CompareApiVersion,
+};
+use crate::{AppState, Error};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::{session_pulse as SessionPulseApi, Id};
+use serde::Deserialize;
+use service::config::ApiVersion;
+use utoipa::ToSchema;
+
+use log::*;
+
+/// Request body for PUT `/coaching_sessions/{coaching_session_id}/pulse`.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PulseRequest {
+    /// How valuable the session was, 1–5
+    pub score: i16,
+}
+
+/// GET the authenticated participant's own pulse for a coaching session
+#[utoipa::path(
+    get,
+    path = "/coaching_sessions/{coaching_session_id}/pulse",
+    params(
+        ApiVersion,
+        ("coaching_session_id" = Id, Path, description = "Coaching session id"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the pulse", body = domain::session_pulses::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Not prompted for or answered yet"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn read(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    CoachingSessionAccess(session): CoachingSessionAccess,
+    State(app_state): State<AppState>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("GET pulse of user {} for session {}", user.id, session.id);
+
+    let pulse = SessionPulseApi::find_own(app_state.db_conn_ref(), user.id, session.id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), pulse)))
+}
+
+/// PUT the authenticated participant's answer to "How valuable was this session?" once
+/// the session has ended. Answering again replaces the earlier score.
+#[utoipa::path(
+    put,
+    path = "/coaching_sessions/{coaching_session_id}/pulse",
+    params(
+        ApiVersion,
+        ("coaching_session_id" = Id, Path, description = "Coaching session id"),
+    ),
+    request_body = PulseRequest,
+    responses(
+        (status = 200, description = "Successfully recorded the pulse", body = domain::session_pulses::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 422, description = "Score out of range or session not over yet"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn update(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    CoachingSessionAccess(session): CoachingSessionAccess,
+    State(app_state): State<AppState>,
+    Json(request): Json<PulseRequest>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("PUT pulse of user {} for session {}", user.id, session.id);
+
+    let pulse =
+        SessionPulseApi::respond(app_state.db_conn_ref(), user.id, &session, request.score).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), pulse)))
+}
+
+/// GET a user's unanswered pulse prompts from the last week, newest first
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}/session_pulses",
+    params(
+        ApiVersion,
+        ("user_id" = Id, Path, description = "User ID to retrieve open prompts for"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved open prompts", body = [domain::session_pulses::Model]),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn pending(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(user_id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("GET open session pulses for user {user_id}");
+
+    let pulses = SessionPulseApi::find_pending(app_state.db_conn_ref(), user_id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), pulses)))
+}
+
+/// GET the coachee's pulse scores over the last 90 days, averaged for the coach to the
+/// nearest half point. The average is withheld until at least 3 sessions were scored.
+/// Coach-only.
+#[utoipa::path(
+    get,
+    path = "/coaching_relationships/{relationship_id}/pulse_summary",
+    params(
+        ApiVersion,
+        ("relationship_id" = Id, Path, description = "Coaching relationship id"),
+    ),
+    responses(
+        (status = 200, description = "Successfully aggregated the coachee's pulse scores"),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn summary(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    CoachingRelationshipAccess(relationship): CoachingRelationshipAccess,
+    State(app_state): State<AppState>,
+) -> Result<impl IntoResponse, Error> {
+    let summary =
+        SessionPulseApi::coachee_aggregate(app_state.db_conn_ref(), user.id, &relationship).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), summary)))
+}
+
+/// GET every coaching relationship in the organization with its pulse scores over the
+/// last 90 days averaged, to the nearest half point, into a 0–100 health score, withheld
+/// below 3 answers.
+/// Admin-only.
+#[utoipa::path(
+    get,
+    path = "/organizations/{organization_id}/session_pulses/analytics",
+    params(
+        ApiVersion,
+        ("organization_id" = Id, Path, description = "The ID of the organization"),
+    ),
+    responses(
+        (status = 200, description = "Successfully aggregated pulse scores per relationship"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - organization admins only"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn analytics(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(organization_id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    let analytics =
+        SessionPulseApi::organization_analytics(app_state.db_conn_ref(), organization_id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), analytics)))
+}
@@ -0,0 +1,240 @@
+use crate::controller::ApiResponse;
+use crate::extractors::{
+    authenticated_user::AuthenticatedUser,
+    coaching_relationship_access::CoachingRelationshipAccess,
+    compare_api_version::CompareApiVersion,
+};
+use crate::params::session_request::{AcceptParams, DeclineParams, IndexParams};
+use crate::{AppState, Error};
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::{
+    calendar as CalendarApi, emails as EmailsApi, session_request as SessionRequestApi,
+    session_requests::Model, Id,
+};
+use service::config::ApiVersion;
+
+use log::*;
+
+/// GET a coaching relationship's session requests, oldest first. Filtering on `pending`
+/// gives the coach's waiting list.
+#[utoipa::path(
+    get,
+    path = "/coaching_relationships/{relationship_id}/session_requests",
+    params(
+        ApiVersion,
+        ("relationship_id" = Id, Path, description = "Coaching relationship to list session requests for"),
+        IndexParams,
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the relationship's session requests", body = [domain::session_requests::Model]),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Coaching relationship not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn index(
+    CompareApiVersion(_v): CompareApiVersion,
+    CoachingRelationshipAccess(relationship): CoachingRelationshipAccess,
+    State(app_state): State<AppState>,
+    Query(params): Query<IndexParams>,
+) -> Result<impl IntoResponse, Error> {
+    let requests = SessionRequestApi::find_by_relationship(
+        app_state.db_conn_ref(),
+        &relationship,
+        params.status,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), requests)))
+}
+
+/// POST request an ad-hoc session with the coach, offering preferred time windows.
+/// Coachee-only; the coach is notified over SSE and by email.
+#[utoipa::path(
+    post,
+    path = "/coaching_relationships/{relationship_id}/session_requests",
+    params(
+        ApiVersion,
+        ("relationship_id" = Id, Path, description = "Coaching relationship to request the session in"),
+    ),
+    request_body = domain::session_requests::Model,
+    responses(
+        (status = 201, description = "Session requested", body = domain::session_requests::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Coaching relationship not found"),
+        (status = 422, description = "Invalid time windows, duration or message"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn create(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    CoachingRelationshipAccess(relationship): CoachingRelationshipAccess,
+    State(app_state): State<AppState>,
+    Json(session_request_model): Json<Model>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("POST session request in relationship {}", relationship.id);
+
+    let request = SessionRequestApi::create(
+        app_state.db_conn_ref(),
+        &app_state.config,
+        app_state.event_publisher.as_ref(),
+        user.id,
+        &relationship,
+        session_request_model,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::CREATED.into(), request)))
+}
+
+/// GET a session request with its status history.
+#[utoipa::path(
+    get,
+    path = "/coaching_relationships/{relationship_id}/session_requests/{id}",
+    params(
+        ApiVersion,
+        ("relationship_id" = Id, Path, description = "Coaching relationship the request belongs to"),
+        ("id" = Id, Path, description = "Session request to retrieve"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the session request", body = domain::session_requests::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Coaching relationship or session request not found"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn read(
+    CompareApiVersion(_v): CompareApiVersion,
+    CoachingRelationshipAccess(relationship): CoachingRelationshipAccess,
+    State(app_state): State<AppState>,
+    Path((_relationship_id, id)): Path<(Id, Id)>,
+) -> Result<impl IntoResponse, Error> {
+    let request = SessionRequestApi::find_by_id(app_state.db_conn_ref(), &relationship, id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), request)))
+}
+
+/// POST accept a pending session request, scheduling its session. Coach-only; the
+/// coachee is notified and both participants get the usual scheduling email. The
+/// response's `warnings` flag a participant out of office or an organization holiday.
+#[utoipa::path(
+    post,
+    path = "/coaching_relationships/{relationship_id}/session_requests/{id}/accept",
+    params(
+        ApiVersion,
+        ("relationship_id" = Id, Path, description = "Coaching relationship the request belongs to"),
+        ("id" = Id, Path, description = "Session request to accept"),
+    ),
+    request_body = crate::params::session_request::AcceptParams,
+    responses(
+        (status = 200, description = "Session request accepted; `coaching_session_id` links the new session", body = domain::session_requests::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Coaching relationship or session request not found"),
+        (status = 409, description = "The session request was already answered"),
+        (status = 422, description = "Invalid start or duration"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn accept(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    CoachingRelationshipAccess(relationship): CoachingRelationshipAccess,
+    State(app_state): State<AppState>,
+    Path((_relationship_id, id)): Path<(Id, Id)>,
+    Json(params): Json<AcceptParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!(
+        "POST accept session request {id} in relationship {}",
+        relationship.id
+    );
+
+    let (request, coaching_session) = SessionRequestApi::accept(
+        app_state.db_conn_ref(),
+        &app_state.config,
+        app_state.event_publisher.as_ref(),
+        user.id,
+        &relationship,
+        id,
+        params.starts_at,
+        params.duration_minutes,
+    )
+    .await?;
+
+    EmailsApi::notify_session_scheduled(
+        app_state.db_conn_ref(),
+        &app_state.config,
+        &coaching_session,
+    )
+    .await;
+
+    let warnings = CalendarApi::booking_warnings(app_state.db_conn_ref(), &coaching_session).await;
+
+    Ok(Json(
+        ApiResponse::new(StatusCode::OK.into(), request).with_warnings(warnings),
+    ))
+}
+
+/// POST decline a pending session request with a reason. Coach-only; the coachee is
+/// notified.
+#[utoipa::path(
+    post,
+    path = "/coaching_relationships/{relationship_id}/session_requests/{id}/decline",
+    params(
+        ApiVersion,
+        ("relationship_id" = Id, Path, description = "Coaching relationship the request belongs to"),
+        ("id" = Id, Path, description = "Session request to decline"),
+    ),
+    request_body = crate::params::session_request::DeclineParams,
+    responses(
+        (status = 200, description = "Session request declined", body = domain::session_requests::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Coaching relationship or session request not found"),
+        (status = 409, description = "The session request was already answered"),
+        (status = 422, description = "Missing or overlong reason"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn decline(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(user): AuthenticatedUser,
+    CoachingRelationshipAccess(relationship): CoachingRelationshipAccess,
+    State(app_state): State<AppState>,
+    Path((_relationship_id, id)): Path<(Id, Id)>,
+    Json(params): Json<DeclineParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!(
+        "POST decline session request {id} in relationship {}",
+        relationship.id
+    );
+
+    let request = SessionRequestApi::decline(
+        app_state.db_conn_ref(),
+        app_state.event_publisher.as_ref(),
+        user.id,
+        &relationship,
+        id,
+        &params.reason,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), request)))
+}
@@ -0,0 +1,99 @@
+//! Admin endpoint for debugging SSE delivery ("user not receiving events").
+//!
+//! Gated by SuperAdmin via the `protect::sse_connections::admin_only` middleware in the
+//! router.
+
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::Serialize;
+use service::config::ApiVersion;
+use sse::connection::{RegistryStats, UserStats};
+use utoipa::ToSchema;
+
+use crate::controller::ApiResponse;
+use crate::extractors::compare_api_version::CompareApiVersion;
+use crate::params::sse::ConnectionsParams;
+use crate::{AppState, Error};
+
+/// The SSE connections held by the replica that served the request
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ConnectionsResponse {
+    /// Open connections, across all users
+    pub connections: usize,
+    /// Events handed to a connection since the replica started
+    pub messages_sent: u64,
+    /// Events lost because their connection had already closed
+    pub messages_dropped: u64,
+    /// Users with open connections or events buffered for a reconnect
+    pub users: Vec<UserConnectionsResponse>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserConnectionsResponse {
+    pub user_id: String,
+    pub connections: usize,
+    /// Age of each open connection in seconds, oldest first
+    pub connection_age_seconds: Vec<u64>,
+    /// Events held for replay when the user reconnects
+    pub buffered_events: usize,
+}
+
+impl From<RegistryStats> for ConnectionsResponse {
+    fn from(stats: RegistryStats) -> Self {
+        Self {
+            connections: stats.connections,
+            messages_sent: stats.sent,
+            messages_dropped: stats.dropped,
+            users: stats.users.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<UserStats> for UserConnectionsResponse {
+    fn from(stats: UserStats) -> Self {
+        Self {
+            user_id: stats.user_id,
+            connections: stats.connection_ages.len(),
+            connection_age_seconds: stats
+                .connection_ages
+                .iter()
+                .map(|age| age.as_secs())
+                .collect(),
+            buffered_events: stats.buffered_events,
+        }
+    }
+}
+
+/// GET the SSE connections and delivery counters of the replica serving the request
+///
+/// Each replica holds its own connections; with several behind a load balancer, this
+/// shows only the one the request reached.
+#[utoipa::path(
+    get,
+    path = "/admin/sse/connections",
+    params(
+        ApiVersion,
+        ConnectionsParams,
+    ),
+    responses(
+        (status = 200, description = "SSE connections retrieved", body = ConnectionsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - SuperAdmin only"),
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn index(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Query(params): Query<ConnectionsParams>,
+) -> Result<impl IntoResponse, Error> {
+    let user_id = params.user_id.map(|id| id.to_string());
+    let stats = app_state.sse_manager.stats(user_id.as_ref());
+
+    Ok(Json(ApiResponse::new(
+        StatusCode::OK.into(),
+        ConnectionsResponse::from(stats),
+    )))
+}
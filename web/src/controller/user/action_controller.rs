@@ -8,7 +8,7 @@ use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::Json;
-use domain::{action as ActionApi, Id, QuerySort};
+use domain::{action as ActionApi, resource_view as ResourceViewApi, Id, QuerySort};
 use service::config::ApiVersion;
 
 use log::*;
@@ -34,7 +34,7 @@ use log::*;
         ("sort_order" = Option<String>, Query, description = "Sort order: 'asc' or 'desc'")
     ),
     responses(
-        (status = 200, description = "Successfully retrieved actions for user", body = [domain::action::ActionWithAssignees]),
+        (status = 200, description = "Successfully retrieved actions for user with their unread flag and reactions", body = [domain::resource_view::AnnotatedAction]),
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Forbidden"),
         (status = 404, description = "User not found"),
@@ -85,5 +85,8 @@ pub async fn index(
 
     debug!("Found {} actions for user {user_id}", actions.len());
 
+    let actions =
+        ResourceViewApi::annotate_actions(app_state.db_conn_ref(), user_id, actions).await?;
+
     Ok(Json(ApiResponse::new(StatusCode::OK.into(), actions)))
 }
@@ -0,0 +1,73 @@
+use crate::controller::ApiResponse;
+use crate::extractors::compare_api_version::CompareApiVersion;
+use crate::{AppState, Error};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::{oauth_server as OAuthServerApi, Id};
+use service::config::ApiVersion;
+
+use log::*;
+
+/// INDEX the third-party apps a user has authorized to act on their behalf and not
+/// revoked, in the order they were first authorized.
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}/authorized_apps",
+    params(
+        ApiVersion,
+        ("user_id" = Id, Path, description = "The ID of the user"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the user's authorized apps", body = [domain::oauth_server::AuthorizedApp]),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn index(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(user_id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    let apps = OAuthServerApi::find_authorized_apps(app_state.db_conn_ref(), user_id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), apps)))
+}
+
+/// DELETE (revoke) a user's authorization of an app, invalidating every token it holds
+/// for them.
+#[utoipa::path(
+    delete,
+    path = "/users/{user_id}/authorized_apps/{client_id}",
+    params(
+        ApiVersion,
+        ("user_id" = Id, Path, description = "The ID of the user"),
+        ("client_id" = Id, Path, description = "The client ID of the app to revoke"),
+    ),
+    responses(
+        (status = 204, description = "The app's authorization was revoked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "The user hasn't authorized this app"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn delete(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path((user_id, client_id)): Path<(Id, Id)>,
+) -> Result<impl IntoResponse, Error> {
+    info!("Revoking OAuth client {client_id} for user {user_id}");
+
+    OAuthServerApi::revoke_app(app_state.db_conn_ref(), user_id, client_id).await?;
+
+    Ok(Json(ApiResponse::<()>::no_content(
+        StatusCode::NO_CONTENT.into(),
+    )))
+}
@@ -0,0 +1,136 @@
+use crate::controller::ApiResponse;
+use crate::extractors::compare_api_version::CompareApiVersion;
+use crate::{AppState, Error};
+
+use axum::extract::{Path, Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::{calendar_feed as CalendarFeedApi, Id};
+use serde::{Deserialize, Serialize};
+use service::config::ApiVersion;
+use utoipa::{IntoParams, ToSchema};
+
+/// Where a user's calendar app subscribes to their coaching sessions
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CalendarFeed {
+    /// Path of the feed, relative to the API's base URL, including its token. Anyone
+    /// with it can read the user's sessions.
+    pub path: String,
+}
+
+impl CalendarFeed {
+    fn new(user_id: Id, token: &str) -> Self {
+        Self {
+            path: format!("/users/{user_id}/coaching_sessions.ics?token={token}"),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct FeedParams {
+    /// The token from the user's `calendar_feed` path
+    pub token: String,
+}
+
+/// GET the path of the authenticated user's iCalendar feed of coaching sessions
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}/coaching_sessions/calendar_feed",
+    params(
+        ApiVersion,
+        ("user_id" = Id, Path, description = "User whose feed to get"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the feed's path", body = CalendarFeed),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Reauthentication required"),
+        (status = 404, description = "Calendar feeds are disabled"),
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn read(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(user_id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    let token = CalendarFeedApi::token(app_state.db_conn_ref(), &app_state.config, user_id).await?;
+
+    Ok(Json(ApiResponse::new(
+        StatusCode::OK.into(),
+        CalendarFeed::new(user_id, &token),
+    )))
+}
+
+/// POST to replace the authenticated user's feed path, revoking the one handed out before
+///
+/// Calendar apps subscribed to the old path stop receiving sessions until they're given
+/// the new one.
+#[utoipa::path(
+    post,
+    path = "/users/{user_id}/coaching_sessions/calendar_feed",
+    params(
+        ApiVersion,
+        ("user_id" = Id, Path, description = "User whose feed to regenerate"),
+    ),
+    responses(
+        (status = 200, description = "Successfully regenerated the feed's path", body = CalendarFeed),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Reauthentication required"),
+        (status = 404, description = "Calendar feeds are disabled"),
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn regenerate(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(user_id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    let token =
+        CalendarFeedApi::regenerate(app_state.db_conn_ref(), &app_state.config, user_id).await?;
+
+    Ok(Json(ApiResponse::new(
+        StatusCode::OK.into(),
+        CalendarFeed::new(user_id, &token),
+    )))
+}
+
+/// GET a user's coaching sessions, from 90 days ago on, as an iCalendar feed
+///
+/// Fetched by calendar apps, so it takes no cookie or API version header; the `token`
+/// in its path authenticates it.
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}/coaching_sessions.ics",
+    params(
+        ("user_id" = Id, Path, description = "User whose coaching sessions to list"),
+        FeedParams,
+    ),
+    responses(
+        (status = 200, description = "The feed, as `text/calendar`", body = String),
+        (status = 401, description = "Wrong or revoked token"),
+        (status = 404, description = "Calendar feeds are disabled"),
+    )
+)]
+pub async fn feed(
+    State(app_state): State<AppState>,
+    Path(user_id): Path<Id>,
+    Query(params): Query<FeedParams>,
+) -> Result<impl IntoResponse, Error> {
+    let body = CalendarFeedApi::feed(
+        app_state.db_conn_ref(),
+        &app_state.config,
+        user_id,
+        &params.token,
+    )
+    .await?;
+
+    Ok((
+        [(header::CONTENT_TYPE, "text/calendar; charset=utf-8")],
+        body,
+    ))
+}
@@ -0,0 +1,103 @@
+use crate::controller::ApiResponse;
+use crate::extractors::compare_api_version::CompareApiVersion;
+use crate::{AppState, Error};
+use axum::extract::{Path, Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::IntoResponse;
+use axum::Json;
+use chrono::NaiveDate;
+use domain::{coaching_log as CoachingLogApi, Id};
+use serde::Deserialize;
+use service::config::ApiVersion;
+use utoipa::IntoParams;
+
+use log::*;
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct CoachingLogParams {
+    /// First day of sessions to log; from the coach's first session when omitted.
+    pub from: Option<NaiveDate>,
+    /// Last day of sessions to log; today when omitted.
+    pub to: Option<NaiveDate>,
+}
+
+/// GET a coach's log of coaching hours, one entry per client
+///
+/// Hours accumulate from the sessions the coach has held, each counting for the length of
+/// its completed recording or else its scheduled duration, split into paid and pro bono
+/// hours by the relationship's pro bono flag.
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}/coaching_log",
+    params(
+        ApiVersion,
+        ("user_id" = Id, Path, description = "User ID of the coach"),
+        CoachingLogParams,
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the coach's coaching log"),
+        (status = 401, description = "Unauthorized"),
+        (status = 405, description = "Method not allowed"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn read(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(user_id): Path<Id>,
+    Query(params): Query<CoachingLogParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("GET coaching log for coach: {user_id}, params: {params:?}");
+
+    let log =
+        CoachingLogApi::find_by_coach(app_state.db_conn_ref(), user_id, params.from, params.to)
+            .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), log)))
+}
+
+/// GET a coach's log of coaching hours as CSV, in the ICF client coaching log's columns
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}/coaching_log/export",
+    params(
+        ApiVersion,
+        ("user_id" = Id, Path, description = "User ID of the coach"),
+        CoachingLogParams,
+    ),
+    responses(
+        (status = 200, description = "The coaching log as CSV", content_type = "text/csv", body = String),
+        (status = 401, description = "Unauthorized"),
+        (status = 405, description = "Method not allowed"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn export(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(user_id): Path<Id>,
+    Query(params): Query<CoachingLogParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("GET coaching log export for coach: {user_id}, params: {params:?}");
+
+    let log =
+        CoachingLogApi::find_by_coach(app_state.db_conn_ref(), user_id, params.from, params.to)
+            .await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"coaching-log-{}.csv\"", log.to),
+            ),
+        ],
+        CoachingLogApi::to_csv(&log),
+    ))
+}
@@ -4,7 +4,7 @@ use crate::extractors::{
     authenticated_user::AuthenticatedUser, compare_api_version::CompareApiVersion,
 };
 use crate::params::user::coaching_session::{
-    CountsByMonthParams, GroupByParam, IncludeParam, IndexParams,
+    BadgesParams, CountsByMonthParams, GroupByParam, IncludeParam, IndexParams,
 };
 use crate::{AppState, Error};
 use std::str::FromStr;
@@ -36,7 +36,7 @@ use utoipa::ToSchema;
         ("from_date" = Option<chrono::NaiveDate>, Query, description = "Filter by from_date (inclusive). Evaluated in `tz` if supplied; otherwise UTC."),
         ("to_date" = Option<chrono::NaiveDate>, Query, description = "Filter by to_date (inclusive at calendar-day precision). Evaluated in `tz` if supplied; otherwise UTC."),
         ("tz" = Option<String>, Query, description = "Optional IANA timezone identifier (e.g. 'America/Los_Angeles'). Interprets `from_date`/`to_date` as local-day boundaries in that zone. Invalid value → 400 invalid_timezone."),
-        ("include" = Option<String>, Query, description = "Comma-separated list of related resources to include. Valid values: 'relationship', 'organization', 'goal', 'agreements', 'topics'. Example: 'relationship,organization,goal'"),
+        ("include" = Option<String>, Query, description = "Comma-separated list of related resources to include. Valid values: 'relationship', 'organization', 'goal', 'agreements', 'topics', 'carried_actions'. Example: 'relationship,organization,goal'"),
         ("sort_by" = Option<crate::params::coaching_session::SortField>, Query, description = "Sort by field. Valid values: 'date', 'created_at', 'updated_at'. Must be provided with sort_order.", example = "date"),
         ("sort_order" = Option<crate::params::sort::SortOrder>, Query, description = "Sort order. Valid values: 'asc' (ascending), 'desc' (descending). Must be provided with sort_by.", example = "desc")
     ),
@@ -87,6 +87,7 @@ pub async fn index(
         goal: params.include.contains(&IncludeParam::Goal),
         agreements: params.include.contains(&IncludeParam::Agreements),
         topics: params.include.contains(&IncludeParam::Topics),
+        carried_actions: params.include.contains(&IncludeParam::CarriedActions),
     };
     let sort_column = params.get_sort_column();
     let sort_order = params.get_sort_order();
@@ -97,6 +98,7 @@ pub async fn index(
         user_id,
         CoachingSessionApi::SessionQueryOptions {
             coaching_relationship_id: params.coaching_relationship_id,
+            session_type_id: params.session_type_id,
             from_date: params.from_date,
             to_date: params.to_date,
             tz: tz_name,
@@ -194,3 +196,45 @@ pub async fn counts(
         CountsResponse { counts },
     )))
 }
+
+/// GET badge counts for a page of the user's coaching sessions.
+///
+/// Returns unread note changes, open action counts and agreement counts for each
+/// requested session in one query, replacing per-session calls from session lists.
+/// Sessions the user does not participate in are omitted. Clients should refetch
+/// on the SSE events that change these counts (`action_*`, `agreement_*`) and
+/// after marking a session viewed.
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}/coaching_sessions/badges",
+    params(
+        ApiVersion,
+        ("user_id" = Id, Path, description = "User ID the badges are computed for (the viewer)"),
+        ("ids" = String, Query, description = "Comma-separated coaching session IDs (max 100)")
+    ),
+    responses(
+        (status = 200, description = "Badge counts per coaching session", body = [domain::coaching_session::SessionBadges]),
+        (status = 400, description = "Bad request (empty, malformed or too many ids)"),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(("cookie_auth" = []))
+)]
+pub async fn badges(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(_user): AuthenticatedUser,
+    State(app_state): State<AppState>,
+    Path(user_id): Path<Id>,
+    Query(params): Query<BadgesParams>,
+) -> Result<impl IntoResponse, Error> {
+    debug!(
+        "GET coaching session badges for user {user_id}, {} sessions",
+        params.ids.len()
+    );
+
+    let badges =
+        CoachingSessionApi::find_badges_for_user(app_state.db_conn_ref(), user_id, &params.ids)
+            .await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), badges)))
+}
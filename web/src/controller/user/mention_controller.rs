@@ -0,0 +1,46 @@
+use crate::controller::ApiResponse;
+use crate::extractors::{
+    authenticated_user::AuthenticatedUser, compare_api_version::CompareApiVersion,
+};
+use crate::{AppState, Error};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::{mention as MentionApi, Id};
+use service::config::ApiVersion;
+
+use log::*;
+
+/// GET every note and action where a user was @-mentioned, newest first
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}/mentions",
+    params(
+        ApiVersion,
+        ("user_id" = Id, Path, description = "User ID to retrieve mentions for"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved mentions for user", body = [domain::mentions::Model]),
+        (status = 401, description = "Unauthorized"),
+        (status = 405, description = "Method not allowed"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn index(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(_user): AuthenticatedUser,
+    State(app_state): State<AppState>,
+    Path(user_id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("GET Mentions for User: {user_id}");
+
+    let mentions = MentionApi::find_by_mentioned_user(app_state.db_conn_ref(), user_id).await?;
+
+    debug!("Found {} mentions for user {user_id}", mentions.len());
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), mentions)))
+}
@@ -1,6 +1,17 @@
 pub(crate) mod action_controller;
+pub(crate) mod authorized_app_controller;
+pub(crate) mod calendar_feed_controller;
+pub(crate) mod coaching_log_controller;
 pub(crate) mod coaching_relationships_controller;
 pub(crate) mod coaching_session_controller;
 pub(crate) mod goal_controller;
+pub(crate) mod mention_controller;
 pub(crate) mod organization_controller;
+pub(crate) mod out_of_office_controller;
 pub(crate) mod password_controller;
+pub(crate) mod policy_controller;
+pub(crate) mod presence_controller;
+pub(crate) mod progress_report_settings_controller;
+pub(crate) mod push_subscription_controller;
+pub(crate) mod question_quality_controller;
+pub(crate) mod remembered_device_controller;
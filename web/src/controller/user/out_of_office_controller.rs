@@ -0,0 +1,138 @@
+use crate::controller::ApiResponse;
+use crate::extractors::compare_api_version::CompareApiVersion;
+use crate::{AppState, Error};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::{out_of_office as OutOfOfficeApi, out_of_office_periods::Model, Id};
+use service::config::ApiVersion;
+
+use log::*;
+
+/// INDEX the user's out-of-office periods that haven't ended yet, soonest first.
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}/out_of_office",
+    params(
+        ApiVersion,
+        ("user_id" = Id, Path, description = "The ID of the user"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the user's out-of-office periods", body = [domain::out_of_office_periods::Model]),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn index(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(user_id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    let periods = OutOfOfficeApi::find_upcoming(app_state.db_conn_ref(), user_id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), periods)))
+}
+
+/// CREATE an out-of-office period for the user. Sessions booked inside it come back with
+/// a warning.
+#[utoipa::path(
+    post,
+    path = "/users/{user_id}/out_of_office",
+    params(
+        ApiVersion,
+        ("user_id" = Id, Path, description = "The ID of the user"),
+    ),
+    request_body = domain::out_of_office_periods::Model,
+    responses(
+        (status = 201, description = "Out-of-office period created", body = domain::out_of_office_periods::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 422, description = "The period ends before it starts, or the note is too long"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn create(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(user_id): Path<Id>,
+    Json(period_model): Json<Model>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("POST out of office period for user {user_id}: {period_model:?}");
+
+    let period = OutOfOfficeApi::create(app_state.db_conn_ref(), user_id, period_model).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::CREATED.into(), period)))
+}
+
+/// UPDATE one of the user's out-of-office periods.
+#[utoipa::path(
+    put,
+    path = "/users/{user_id}/out_of_office/{id}",
+    params(
+        ApiVersion,
+        ("user_id" = Id, Path, description = "The ID of the user"),
+        ("id" = Id, Path, description = "The ID of the out-of-office period to update"),
+    ),
+    request_body = domain::out_of_office_periods::Model,
+    responses(
+        (status = 200, description = "Out-of-office period updated", body = domain::out_of_office_periods::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "The user has no such out-of-office period"),
+        (status = 422, description = "The period ends before it starts, or the note is too long"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn update(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path((user_id, id)): Path<(Id, Id)>,
+    Json(period_model): Json<Model>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("PUT out of office period {id} for user {user_id}");
+
+    let period = OutOfOfficeApi::update(app_state.db_conn_ref(), user_id, id, period_model).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), period)))
+}
+
+/// DELETE one of the user's out-of-office periods, e.g. when plans change.
+#[utoipa::path(
+    delete,
+    path = "/users/{user_id}/out_of_office/{id}",
+    params(
+        ApiVersion,
+        ("user_id" = Id, Path, description = "The ID of the user"),
+        ("id" = Id, Path, description = "The ID of the out-of-office period to delete"),
+    ),
+    responses(
+        (status = 204, description = "The out-of-office period was deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "The user has no such out-of-office period"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn delete(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path((user_id, id)): Path<(Id, Id)>,
+) -> Result<impl IntoResponse, Error> {
+    info!("Deleting out of office period {id} for user {user_id}");
+
+    OutOfOfficeApi::delete(app_state.db_conn_ref(), user_id, id).await?;
+
+    Ok(Json(ApiResponse::<()>::no_content(
+        StatusCode::NO_CONTENT.into(),
+    )))
+}
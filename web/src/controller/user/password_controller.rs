@@ -23,6 +23,7 @@ use service::config::ApiVersion;
     responses(
         (status = 200, description = "Successfully updated a User's password"),
         (status = 401, description = "Unauthorized"),
+        (status = 422, description = "Password confirmation does not match, or the password fails the password policy"),
         (status = 503, description = "Service temporarily unavailable"),
     ),
     security(
@@ -36,6 +37,6 @@ pub async fn update_password(
     Path(user_id): Path<Id>,
     Json(params): Json<UpdatePasswordParams>,
 ) -> Result<impl IntoResponse, Error> {
-    UserApi::update_password(app_state.db_conn_ref(), user_id, params).await?;
+    UserApi::update_password(app_state.db_conn_ref(), &app_state.config, user_id, params).await?;
     Ok(Json(ApiResponse::new(StatusCode::NO_CONTENT.into(), ())))
 }
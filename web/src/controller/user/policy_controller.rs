@@ -0,0 +1,51 @@
+use crate::controller::ApiResponse;
+use crate::extractors::compare_api_version::CompareApiVersion;
+use crate::params::user::AcceptPolicyParams;
+use crate::{AppState, Error};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::{policy as PolicyApi, Id};
+use service::config::ApiVersion;
+
+use log::*;
+
+/// ACCEPT a policy version in effect (terms of service, privacy policy) on the user's
+/// behalf, recording their consent. Accepting a version again returns the original
+/// acceptance.
+#[utoipa::path(
+    post,
+    path = "/users/{user_id}/accept_policy",
+    params(
+        ApiVersion,
+        ("user_id" = Id, Path, description = "The ID of the user"),
+    ),
+    request_body = crate::params::user::AcceptPolicyParams,
+    responses(
+        (status = 200, description = "The user accepted the policy version", body = domain::policy_acceptances::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "No such policy document"),
+        (status = 422, description = "The policy version isn't the one in effect"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn accept(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(user_id): Path<Id>,
+    Json(params): Json<AcceptPolicyParams>,
+) -> Result<impl IntoResponse, Error> {
+    info!(
+        "User {user_id} accepting policy document {}",
+        params.policy_document_id
+    );
+
+    let acceptance =
+        PolicyApi::accept(app_state.db_conn_ref(), user_id, params.policy_document_id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), acceptance)))
+}
@@ -0,0 +1,56 @@
+use crate::controller::ApiResponse;
+use crate::extractors::compare_api_version::CompareApiVersion;
+use crate::AppState;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::Id;
+use serde::Serialize;
+use service::config::ApiVersion;
+use utoipa::ToSchema;
+
+use log::*;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PresenceResponse {
+    pub user_id: Id,
+    /// Whether the user has the app open, i.e. an SSE connection to this replica
+    pub online: bool,
+}
+
+/// READ whether a user is currently in the app. Changes are sent as `user_online` and
+/// `user_offline` SSE events to the other member of each of the user's coaching
+/// relationships.
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}/presence",
+    params(
+        ApiVersion,
+        ("user_id" = Id, Path, description = "The ID of the user"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the user's presence", body = PresenceResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn read(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(user_id): Path<Id>,
+) -> impl IntoResponse {
+    let online = app_state.sse_manager.is_connected(&user_id.to_string());
+    debug!(
+        "User {user_id} is {}",
+        if online { "online" } else { "offline" }
+    );
+
+    Json(ApiResponse::new(
+        StatusCode::OK.into(),
+        PresenceResponse { user_id, online },
+    ))
+}
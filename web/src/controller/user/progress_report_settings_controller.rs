@@ -0,0 +1,72 @@
+use crate::controller::ApiResponse;
+use crate::extractors::compare_api_version::CompareApiVersion;
+use crate::{AppState, Error};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::{progress_report as ProgressReportApi, progress_report_settings::Model, Id};
+use service::config::ApiVersion;
+
+use log::*;
+
+/// GET a user's progress report settings: whether they're emailed a weekly progress
+/// report on each relationship they coach. Users who haven't opted in aren't.
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}/progress_report_settings",
+    params(
+        ApiVersion,
+        ("user_id" = Id, Path, description = "User ID of the coach"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the user's progress report settings", body = domain::progress_report_settings::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn read(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(user_id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    let settings = ProgressReportApi::settings(app_state.db_conn_ref(), user_id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), settings)))
+}
+
+/// UPDATE a user's progress report settings, opting in to or out of the weekly
+/// progress email
+#[utoipa::path(
+    put,
+    path = "/users/{user_id}/progress_report_settings",
+    params(
+        ApiVersion,
+        ("user_id" = Id, Path, description = "User ID of the coach"),
+    ),
+    request_body = domain::progress_report_settings::Model,
+    responses(
+        (status = 200, description = "Progress report settings updated", body = domain::progress_report_settings::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn update(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(user_id): Path<Id>,
+    Json(settings): Json<Model>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("PUT progress report settings for user {user_id}: {settings:?}");
+
+    let settings =
+        ProgressReportApi::update_settings(app_state.db_conn_ref(), user_id, settings).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), settings)))
+}
@@ -0,0 +1,115 @@
+use crate::controller::ApiResponse;
+use crate::extractors::compare_api_version::CompareApiVersion;
+use crate::params::push::CreateParams;
+use crate::{AppState, Error};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::{push as PushApi, Id};
+use service::config::ApiVersion;
+
+use log::*;
+
+/// INDEX the browsers and devices a user has subscribed to push notifications, oldest
+/// first.
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}/push_subscriptions",
+    params(
+        ApiVersion,
+        ("user_id" = Id, Path, description = "The ID of the user"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the user's push subscriptions", body = [domain::push_subscriptions::Model]),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn index(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(user_id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    let subscriptions = PushApi::find_by_user(app_state.db_conn_ref(), user_id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), subscriptions)))
+}
+
+/// CREATE a push subscription for one of the user's browsers. Subscribing an endpoint
+/// again replaces its keys.
+#[utoipa::path(
+    post,
+    path = "/users/{user_id}/push_subscriptions",
+    params(
+        ApiVersion,
+        ("user_id" = Id, Path, description = "The ID of the user"),
+    ),
+    request_body = crate::params::push::CreateParams,
+    responses(
+        (status = 201, description = "Successfully subscribed the browser", body = domain::push_subscriptions::Model),
+        (status = 401, description = "Unauthorized"),
+        (status = 422, description = "The endpoint or keys are invalid"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn create(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(user_id): Path<Id>,
+    Json(params): Json<CreateParams>,
+) -> Result<impl IntoResponse, Error> {
+    let subscription = PushApi::subscribe(
+        app_state.db_conn_ref(),
+        user_id,
+        params.endpoint,
+        params.keys.p256dh,
+        params.keys.auth,
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::new(
+        StatusCode::CREATED.into(),
+        subscription,
+    )))
+}
+
+/// DELETE one of the user's push subscriptions, e.g. when they turn notifications off
+/// in that browser.
+#[utoipa::path(
+    delete,
+    path = "/users/{user_id}/push_subscriptions/{id}",
+    params(
+        ApiVersion,
+        ("user_id" = Id, Path, description = "The ID of the user"),
+        ("id" = Id, Path, description = "The ID of the push subscription to delete"),
+    ),
+    responses(
+        (status = 204, description = "The push subscription was deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "The user has no such push subscription"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn delete(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path((user_id, id)): Path<(Id, Id)>,
+) -> Result<impl IntoResponse, Error> {
+    info!("Deleting push subscription {id} for user {user_id}");
+
+    PushApi::unsubscribe(app_state.db_conn_ref(), user_id, id).await?;
+
+    Ok(Json(ApiResponse::<()>::no_content(
+        StatusCode::NO_CONTENT.into(),
+    )))
+}
@@ -0,0 +1,51 @@
+use crate::controller::ApiResponse;
+use crate::extractors::{
+    authenticated_user::AuthenticatedUser, compare_api_version::CompareApiVersion,
+};
+use crate::{AppState, Error};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::{question_quality as QuestionQualityApi, Id};
+use service::config::ApiVersion;
+
+use log::*;
+
+/// GET a coach's question quality across the sessions they coached, oldest first
+///
+/// Only sessions whose question quality has been requested are included.
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}/question_quality",
+    params(
+        ApiVersion,
+        ("user_id" = Id, Path, description = "User ID of the coach"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the coach's question quality trend"),
+        (status = 401, description = "Unauthorized"),
+        (status = 405, description = "Method not allowed"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn index(
+    CompareApiVersion(_v): CompareApiVersion,
+    AuthenticatedUser(_user): AuthenticatedUser,
+    State(app_state): State<AppState>,
+    Path(user_id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    debug!("GET question quality trend for coach: {user_id}");
+
+    let trend = QuestionQualityApi::trend(app_state.db_conn_ref(), user_id).await?;
+
+    debug!(
+        "Found question quality for {} sessions of coach {user_id}",
+        trend.sessions.len()
+    );
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), trend)))
+}
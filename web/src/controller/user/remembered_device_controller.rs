@@ -0,0 +1,73 @@
+use crate::controller::ApiResponse;
+use crate::extractors::compare_api_version::CompareApiVersion;
+use crate::{AppState, Error};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use domain::{remembered_device as RememberedDeviceApi, Id};
+use service::config::ApiVersion;
+
+use log::*;
+
+/// INDEX the browsers a user logged in on with "remember me" whose sessions haven't
+/// expired, oldest first.
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}/remembered_devices",
+    params(
+        ApiVersion,
+        ("user_id" = Id, Path, description = "The ID of the user"),
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the user's remembered devices", body = [domain::remembered_devices::Model]),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn index(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path(user_id): Path<Id>,
+) -> Result<impl IntoResponse, Error> {
+    let devices = RememberedDeviceApi::find_by_user(app_state.db_conn_ref(), user_id).await?;
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), devices)))
+}
+
+/// DELETE one of the user's remembered devices, logging out its session on its next
+/// request.
+#[utoipa::path(
+    delete,
+    path = "/users/{user_id}/remembered_devices/{id}",
+    params(
+        ApiVersion,
+        ("user_id" = Id, Path, description = "The ID of the user"),
+        ("id" = Id, Path, description = "The ID of the remembered device to revoke"),
+    ),
+    responses(
+        (status = 204, description = "The remembered device was revoked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "The user has no such remembered device"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn delete(
+    CompareApiVersion(_v): CompareApiVersion,
+    State(app_state): State<AppState>,
+    Path((user_id, id)): Path<(Id, Id)>,
+) -> Result<impl IntoResponse, Error> {
+    info!("Revoking remembered device {id} for user {user_id}");
+
+    RememberedDeviceApi::revoke(app_state.db_conn_ref(), user_id, id).await?;
+
+    Ok(Json(ApiResponse::<()>::no_content(
+        StatusCode::NO_CONTENT.into(),
+    )))
+}
@@ -1,9 +1,36 @@
 use crate::controller::ApiResponse;
 use crate::error::{Error as WebError, Result as WebResult};
+use crate::extractors::authenticated_user::AuthenticatedUser;
+use crate::middleware::remember_me;
+use crate::protect::sudo;
+use crate::AppState;
+use axum::extract::State;
+use axum::http::header::{SET_COOKIE, USER_AGENT};
+use axum::http::{HeaderMap, HeaderValue};
+use axum::response::Response;
 use axum::{http::StatusCode, response::IntoResponse, Form, Json};
+use chrono::{DateTime, Utc};
+use domain::policy as PolicyApi;
+use domain::remembered_device as RememberedDeviceApi;
 use domain::user::{AuthSession, Credentials};
 use log::*;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
+use tower_sessions::cookie::Cookie;
+use tower_sessions::Session;
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Clone, ToSchema, IntoParams, Deserialize)]
+pub struct ReauthenticateParams {
+    pub password: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SessionRefreshResponse {
+    /// When the session now expires unless it's used again
+    #[schema(value_type = String, format = DateTime)]
+    pub expires_at: DateTime<Utc>,
+}
 
 /// Logs the user into the platform and returns a new session cookie.
 ///
@@ -13,6 +40,13 @@ use serde_json::json;
 /// After logging in successfully, you must pass the session id back to the server for
 /// every API call, e.g.:
 /// curl -v --header "Cookie: id=07bbbe54-bd35-425f-8e63-618a8d8612df" --request GET http://localhost:4000/organizations
+///
+/// With `remember_me=true` the session lasts `remember_me_lifetime_seconds` however it's
+/// used, but only alongside the `device` cookie also set in the response.
+///
+/// `policy_acceptance_required` is set when a policy version in effect (terms of
+/// service, privacy policy) hasn't been accepted yet; those are listed in
+/// `pending_policies`, to accept with `POST /users/:id/accept_policy`.
 #[utoipa::path(
     post,
     path = "/login",
@@ -28,9 +62,12 @@ use serde_json::json;
     )
 )]
 pub async fn login(
+    State(app_state): State<AppState>,
     mut auth_session: AuthSession,
+    session: Session,
+    headers: HeaderMap,
     Form(creds): Form<Credentials>,
-) -> WebResult<impl IntoResponse> {
+) -> WebResult<Response> {
     let user = match auth_session.authenticate(creds.clone()).await {
         Ok(Some(user)) => user,
         Ok(None) => {
@@ -72,6 +109,37 @@ pub async fn login(
         }));
     }
 
+    sudo::mark_authenticated(&session)
+        .await
+        .map_err(session_error)?;
+
+    let device_cookie = if creds.remember_me {
+        let lifetime_seconds = app_state.config.remember_me_lifetime_seconds;
+        let user_agent = headers
+            .get(USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let (device, token) = RememberedDeviceApi::remember(
+            app_state.db_conn_ref(),
+            user.id,
+            user_agent,
+            lifetime_seconds,
+        )
+        .await?;
+        remember_me::bind(&session, &device)
+            .await
+            .map_err(session_error)?;
+        Some(remember_me::device_cookie(
+            token,
+            lifetime_seconds,
+            app_state.config.is_production(),
+        ))
+    } else {
+        None
+    };
+
+    let pending_policies = PolicyApi::pending_for_user(app_state.db_conn_ref(), user.id).await?;
+
     let user_session_json = json!({
             "id": user.id,
             "email": user.email,
@@ -80,15 +148,19 @@ pub async fn login(
             "display_name": user.display_name,
             "timezone": user.timezone,
             "role": user.role,
-            "roles": user.roles
+            "roles": user.roles,
+            "policy_acceptance_required": !pending_policies.is_empty(),
+            "pending_policies": pending_policies
     });
 
     debug!("user_session_json: {user_session_json}");
 
-    Ok(Json(ApiResponse::new(
-        StatusCode::OK.into(),
-        user_session_json,
-    )))
+    let mut response =
+        Json(ApiResponse::new(StatusCode::OK.into(), user_session_json)).into_response();
+    if let Some(cookie) = device_cookie {
+        set_cookie(&mut response, cookie);
+    }
+    Ok(response)
 }
 
 /// Logs the user out of the platform by destroying their session.
@@ -108,10 +180,111 @@ security(
     ("cookie_auth" = [])
 )
 )]
-pub async fn delete(mut auth_session: AuthSession) -> impl IntoResponse {
+pub async fn delete(
+    State(app_state): State<AppState>,
+    mut auth_session: AuthSession,
+    session: Session,
+) -> impl IntoResponse {
     trace!("UserSessionController::delete()");
-    match auth_session.logout().await {
-        Ok(_) => StatusCode::OK.into_response(),
-        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    // Read before logging out, which clears the session
+    let remembered_device_id = remember_me::remembered_device_id(&session).await;
+
+    if auth_session.logout().await.is_err() {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
     }
+
+    let mut response = StatusCode::OK.into_response();
+    if let Ok(Some(device_id)) = remembered_device_id {
+        if let Err(e) = RememberedDeviceApi::forget(app_state.db_conn_ref(), device_id).await {
+            warn!("Failed to forget remembered device {device_id} on logout: {e:?}");
+        }
+        set_cookie(
+            &mut response,
+            remember_me::expired_device_cookie(app_state.config.is_production()),
+        );
+    }
+    response
+}
+
+/// Confirms the user's password, putting the session in sudo mode: it may take sensitive
+/// actions (e.g. granting roles, creating API tokens) for the next
+/// `reauthentication_window_seconds`. Such actions answer `403 Reauthentication required`
+/// outside sudo mode.
+#[utoipa::path(
+    post,
+    path = "/auth/reauthenticate",
+    request_body(content = ReauthenticateParams, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 204, description = "Successfully reauthenticated"),
+        (status = 401, description = "Unauthorized, or the password is wrong"),
+        (status = 405, description = "Method not allowed"),
+        (status = 429, description = "Too many attempts; retry after `Retry-After` seconds"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn reauthenticate(
+    AuthenticatedUser(user): AuthenticatedUser,
+    session: Session,
+    Form(params): Form<ReauthenticateParams>,
+) -> WebResult<impl IntoResponse> {
+    trace!("UserSessionController::reauthenticate()");
+    domain::user::verify_password(&params.password, user.password.as_deref()).await?;
+    sudo::mark_authenticated(&session)
+        .await
+        .map_err(session_error)?;
+
+    Ok(Json(ApiResponse::<()>::no_content(
+        StatusCode::NO_CONTENT.into(),
+    )))
+}
+
+fn set_cookie(response: &mut Response, cookie: Cookie<'static>) {
+    match HeaderValue::from_str(&cookie.to_string()) {
+        Ok(value) => {
+            response.headers_mut().append(SET_COOKIE, value);
+        }
+        Err(e) => warn!("Failed to set the {} cookie: {e:?}", cookie.name()),
+    }
+}
+
+fn session_error(e: tower_sessions::session::Error) -> WebError {
+    WebError::from(domain::error::Error {
+        source: Some(Box::new(e)),
+        error_kind: domain::error::DomainErrorKind::Internal(
+            domain::error::InternalErrorKind::Other("Session update failed".to_string()),
+        ),
+    })
+}
+
+/// Extends the user's session, e.g. after a `session_expiring_soon` SSE event. Any
+/// authenticated request does the same; this one just does nothing else.
+#[utoipa::path(
+    post,
+    path = "/session/refresh",
+    responses(
+        (status = 200, description = "Successfully extended the session", body = SessionRefreshResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 405, description = "Method not allowed"),
+        (status = 503, description = "Service temporarily unavailable")
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn refresh(session: Session) -> impl IntoResponse {
+    trace!("UserSessionController::refresh()");
+    // The session layer saves every session it handles, which restarts its inactivity
+    // timer; this is the expiry that save sets.
+    let expiry_date = session.expiry_date();
+    let expires_at =
+        DateTime::from_timestamp(expiry_date.unix_timestamp(), expiry_date.nanosecond())
+            .unwrap_or_else(Utc::now);
+
+    Json(ApiResponse::new(
+        StatusCode::OK.into(),
+        SessionRefreshResponse { expires_at },
+    ))
 }
@@ -0,0 +1,55 @@
+use crate::controller::ApiResponse;
+use crate::extractors::compare_api_version::CompareApiVersion;
+use crate::Error;
+use axum::{http::StatusCode, response::IntoResponse, Json};
+use domain::webhook_subscription as WebhookSubscriptionApi;
+use serde::Serialize;
+use service::config::ApiVersion;
+use utoipa::ToSchema;
+
+/// An event type a webhook subscription may name in its `event_types`
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookEventType {
+    /// e.g. `goal_created`, as sent in the `X-Refactor-Event` header
+    pub event_type: &'static str,
+    /// Schema version of the `payload` its deliveries carry
+    pub version: u32,
+    pub description: Option<String>,
+    /// JSON Schema of the body POSTed for each of its events
+    #[schema(value_type = Object)]
+    pub schema: serde_json::Value,
+}
+
+impl From<WebhookSubscriptionApi::WebhookEventType> for WebhookEventType {
+    fn from(event_type: WebhookSubscriptionApi::WebhookEventType) -> Self {
+        Self {
+            event_type: event_type.event_type,
+            version: event_type.version,
+            description: event_type.description,
+            schema: event_type.schema,
+        }
+    }
+}
+
+/// INDEX the event types webhooks can subscribe to, with the JSON Schema of each one's
+/// deliveries. Generated from the events themselves, so it's always current.
+#[utoipa::path(
+    get,
+    path = "/webhook_event_types",
+    params(ApiVersion),
+    responses(
+        (status = 200, description = "Successfully retrieved the webhook event catalog", body = [WebhookEventType]),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(
+        ("cookie_auth" = [])
+    )
+)]
+pub async fn index(CompareApiVersion(_v): CompareApiVersion) -> Result<impl IntoResponse, Error> {
+    let event_types: Vec<WebhookEventType> = WebhookSubscriptionApi::event_types()
+        .into_iter()
+        .map(WebhookEventType::from)
+        .collect();
+
+    Ok(Json(ApiResponse::new(StatusCode::OK.into(), event_types)))
+}
@@ -1,9 +1,10 @@
 //! Error handling for the web layer.
 //! Errors from lower layers are translated through `domain` to `web`
-//! so that `web` can return appropriate HTTP status codes and messages to the client.
+//! so that `web` can return appropriate HTTP status codes and messages to the client,
+//! as `application/problem+json` bodies (see [`Problem`]).
 use std::error::Error as StdError;
 
-use axum::http::StatusCode;
+use axum::http::{header, StatusCode};
 use axum::response::{IntoResponse, Response};
 use axum::Json;
 
@@ -35,6 +36,9 @@ pub enum WebErrorKind {
     /// debuggability.
     InvalidTimezone(String),
     Conflict,
+    /// The write's `If-Match` names a version of the resource that someone has
+    /// since changed. 412 Precondition Failed; the client re-reads and retries.
+    PreconditionFailed,
     Other,
 }
 
@@ -46,6 +50,105 @@ impl std::fmt::Display for Error {
     }
 }
 
+/// The `type` URIs of this API's problems are this prefix followed by the problem's
+/// `error_code` in kebab case.
+const PROBLEM_TYPE_PREFIX: &str = "urn:refactor-platform:problem:";
+
+/// An RFC 7807 (now RFC 9457) problem details response, which every `Error` becomes.
+///
+/// Besides the standard `type`, `title`, `status`, `detail` and `instance` members, the
+/// body carries `error_code`, a stable snake_case discriminator clients branch on, and
+/// any members specific to the problem (e.g. `details`). `status_code`, `error` and
+/// `message` repeat `status`, `error_code` and `detail` for clients written against the
+/// earlier error bodies.
+struct Problem {
+    status: StatusCode,
+    error_code: &'static str,
+    title: &'static str,
+    detail: String,
+    extensions: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Problem {
+    fn new(
+        status: StatusCode,
+        error_code: &'static str,
+        title: &'static str,
+        detail: impl Into<String>,
+    ) -> Self {
+        Self {
+            status,
+            error_code,
+            title,
+            detail: detail.into(),
+            extensions: serde_json::Map::new(),
+        }
+    }
+
+    /// The 500 every unexpected failure becomes; the cause is only logged.
+    fn internal() -> Self {
+        Self::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "internal_error",
+            "Internal server error",
+            "The server failed to handle the request.",
+        )
+    }
+
+    fn unauthenticated() -> Self {
+        Self::new(
+            StatusCode::UNAUTHORIZED,
+            "unauthenticated",
+            "Unauthenticated",
+            "Log in to access this resource.",
+        )
+    }
+
+    fn precondition_failed() -> Self {
+        Self::new(
+            StatusCode::PRECONDITION_FAILED,
+            "precondition_failed",
+            "Precondition failed",
+            "This resource was changed since it was read. Reload it and try again.",
+        )
+    }
+
+    /// Adds a member specific to this problem.
+    fn with(mut self, member: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.extensions.insert(member.to_string(), value.into());
+        self
+    }
+}
+
+impl IntoResponse for Problem {
+    fn into_response(self) -> Response {
+        let mut body = serde_json::json!({
+            "type": format!("{PROBLEM_TYPE_PREFIX}{}", self.error_code.replace('_', "-")),
+            "title": self.title,
+            "status": self.status.as_u16(),
+            "detail": self.detail,
+            "error_code": self.error_code,
+            "status_code": self.status.as_u16(),
+            "error": self.error_code,
+            "message": self.detail,
+        });
+        // The occurrence is the request it happened in, as echoed in `X-Request-ID`
+        if let Some(correlation_id) = domain::events::current_correlation_id() {
+            body["instance"] = format!("urn:uuid:{correlation_id}").into();
+        }
+        for (member, value) in self.extensions {
+            body[member] = value;
+        }
+
+        (
+            self.status,
+            [(header::CONTENT_TYPE, "application/problem+json")],
+            Json(body),
+        )
+            .into_response()
+    }
+}
+
 // List of possible StatusCode variants https://docs.rs/http/latest/http/status/struct.StatusCode.html#associatedconstant.UNPROCESSABLE_ENTITY
 impl IntoResponse for Error {
     fn into_response(self) -> Response {
@@ -69,12 +172,31 @@ impl Error {
                 warn!(
                     "DomainErrorKind::Validation: Responding with 422 Unprocessable Entity. Error: {self:?}"
                 );
-                let body = serde_json::json!({
-                    "status_code": 422,
-                    "error": "validation_error",
-                    "message": message,
-                });
-                (StatusCode::UNPROCESSABLE_ENTITY, Json(body)).into_response()
+                Problem::new(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    "validation_error",
+                    "Validation failed",
+                    message.as_str(),
+                )
+                .into_response()
+            }
+            DomainErrorKind::PasswordPolicy(ref failures) => {
+                warn!(
+                    "DomainErrorKind::PasswordPolicy: Responding with 422 Unprocessable Entity. Error: {self:?}"
+                );
+                let message = failures
+                    .iter()
+                    .map(|failure| failure.message.as_str())
+                    .collect::<Vec<_>>()
+                    .join(". ");
+                Problem::new(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    "password_policy",
+                    "Password doesn't meet the policy",
+                    message,
+                )
+                .with("details", serde_json::json!({ "failed_rules": failures }))
+                .into_response()
             }
         }
     }
@@ -88,13 +210,13 @@ impl Error {
                 warn!(
                     "InternalErrorKind::Config: Responding with 500 Internal Server Error. Error: {self:?}"
                 );
-                (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL SERVER ERROR").into_response()
+                Problem::internal().into_response()
             }
             InternalErrorKind::Other(_description) => {
                 warn!(
                     "InternalErrorKind::Other: Responding with 500 Internal Server Error. Error:: {self:?}"
                 );
-                (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL SERVER ERROR").into_response()
+                Problem::internal().into_response()
             }
         }
     }
@@ -103,62 +225,77 @@ impl Error {
         match entity_error_kind {
             EntityErrorKind::NotFound => {
                 warn!("EntityErrorKind::NotFound: Responding with 404 Not Found. Error: {self:?}");
-                (StatusCode::NOT_FOUND, "NOT FOUND").into_response()
+                Problem::new(
+                    StatusCode::NOT_FOUND,
+                    "not_found",
+                    "Not found",
+                    "The requested resource doesn't exist.",
+                )
+                .into_response()
             }
             EntityErrorKind::Unauthenticated => {
                 warn!(
                     "EntityErrorKind::Unauthenticated: Responding with 401 Unauthorized. Error: {self:?}"
                 );
-                (StatusCode::UNAUTHORIZED, "UNAUTHORIZED").into_response()
+                Problem::unauthenticated().into_response()
             }
             EntityErrorKind::DbTransaction => {
                 warn!(
                     "EntityErrorKind::DbTransaction: Responding with 500 Internal Server Error. Error: {self:?}"
                 );
-                (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL SERVER ERROR").into_response()
+                Problem::internal().into_response()
             }
             EntityErrorKind::Invalid => {
                 warn!(
                     "EntityErrorKind::Invalid: Responding with 422 Unprocessable Entity. Error: {self:?}"
                 );
-                (StatusCode::UNPROCESSABLE_ENTITY, "UNPROCESSABLE ENTITY").into_response()
+                Problem::new(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    "invalid_entity",
+                    "Invalid entity",
+                    "The request can't be applied to the resource as given.",
+                )
+                .into_response()
             }
             EntityErrorKind::Conflict {
                 ref message,
                 ref details,
             } => {
                 warn!("EntityErrorKind::Conflict: Responding with 409 Conflict. Error: {self:?}");
-                let mut body = serde_json::json!({
-                    "status_code": 409,
-                    "error": "conflict",
-                    "message": message,
-                });
+                let mut problem = Problem::new(
+                    StatusCode::CONFLICT,
+                    "conflict",
+                    "Conflict",
+                    message.as_str(),
+                );
                 if let Some(d) = details {
-                    body["details"] = d.clone();
+                    problem = problem.with("details", d.clone());
                 }
-                (StatusCode::CONFLICT, Json(body)).into_response()
+                problem.into_response()
             }
             EntityErrorKind::CannotLinkCompletedGoal => {
                 warn!(
                     "EntityErrorKind::CannotLinkCompletedGoal: Responding with 422 Unprocessable Entity. Error: {self:?}"
                 );
-                let body = serde_json::json!({
-                    "status_code": 422,
-                    "error": "cannot_link_completed_goal",
-                    "message": "Completed goals cannot be linked to a coaching session.",
-                });
-                (StatusCode::UNPROCESSABLE_ENTITY, Json(body)).into_response()
+                Problem::new(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    "cannot_link_completed_goal",
+                    "Goal is completed",
+                    "Completed goals cannot be linked to a coaching session.",
+                )
+                .into_response()
             }
             EntityErrorKind::GoalAlreadyLinkedToSession => {
                 warn!(
                     "EntityErrorKind::GoalAlreadyLinkedToSession: Responding with 409 Conflict. Error: {self:?}"
                 );
-                let body = serde_json::json!({
-                    "status_code": 409,
-                    "error": "goal_already_linked_to_session",
-                    "message": "This goal is already linked to the coaching session.",
-                });
-                (StatusCode::CONFLICT, Json(body)).into_response()
+                Problem::new(
+                    StatusCode::CONFLICT,
+                    "goal_already_linked_to_session",
+                    "Goal already linked",
+                    "This goal is already linked to the coaching session.",
+                )
+                .into_response()
             }
             EntityErrorKind::OrganizationNotEmpty {
                 coaching_relationship_count,
@@ -166,70 +303,107 @@ impl Error {
                 member_count,
             } => {
                 warn!("EntityErrorKind::OrganizationNotEmpty: Responding with 409 Conflict. Error: {self:?}");
-                let body = serde_json::json!({
-                    "status_code": 409,
-                    "error": "organization_not_empty",
-                    "message": "This organization still has coaching relationships and cannot be deleted.",
-                    "details": {
+                Problem::new(
+                    StatusCode::CONFLICT,
+                    "organization_not_empty",
+                    "Organization not empty",
+                    "This organization still has coaching relationships and cannot be deleted.",
+                )
+                .with(
+                    "details",
+                    serde_json::json!({
                         "coaching_relationship_count": coaching_relationship_count,
                         "coaching_session_count": coaching_session_count,
                         "member_count": member_count,
-                    },
-                });
-                (StatusCode::CONFLICT, Json(body)).into_response()
+                    }),
+                )
+                .into_response()
             }
             EntityErrorKind::OrganizationNameTaken { name } => {
                 warn!("EntityErrorKind::OrganizationNameTaken: Responding with 409 Conflict. Error: {self:?}");
-                let body = serde_json::json!({
-                    "status_code": 409,
-                    "error": "organization_name_taken",
-                    "message": "An organization with that name already exists.",
-                    "details": { "name": name },
-                });
-                (StatusCode::CONFLICT, Json(body)).into_response()
+                Problem::new(
+                    StatusCode::CONFLICT,
+                    "organization_name_taken",
+                    "Organization name taken",
+                    "An organization with that name already exists.",
+                )
+                .with("details", serde_json::json!({ "name": name }))
+                .into_response()
             }
             EntityErrorKind::OrganizationArchived => {
                 warn!("EntityErrorKind::OrganizationArchived: Responding with 409 Conflict. Error: {self:?}");
-                let body = serde_json::json!({
-                    "status_code": 409,
-                    "error": "organization_archived",
-                    "message": "This organization is archived and cannot accept new changes.",
-                });
-                (StatusCode::CONFLICT, Json(body)).into_response()
+                Problem::new(
+                    StatusCode::CONFLICT,
+                    "organization_archived",
+                    "Organization archived",
+                    "This organization is archived and cannot accept new changes.",
+                )
+                .into_response()
             }
             EntityErrorKind::InvalidOrExpiredToken => {
                 warn!(
                     "EntityErrorKind::InvalidOrExpiredToken: Responding with 400 Bad Request. Error: {self:?}"
                 );
-                let body = serde_json::json!({
-                    "status_code": 400,
-                    "error": "invalid_or_expired_token",
-                    "message": "This reset link is invalid or has expired. Please request a new one.",
-                });
-                (StatusCode::BAD_REQUEST, Json(body)).into_response()
+                Problem::new(
+                    StatusCode::BAD_REQUEST,
+                    "invalid_or_expired_token",
+                    "Invalid or expired token",
+                    "This reset link is invalid or has expired. Please request a new one.",
+                )
+                .into_response()
             }
             EntityErrorKind::PasswordResetRateLimited => {
                 warn!(
                     "EntityErrorKind::PasswordResetRateLimited: Responding with 429 Too Many Requests. Error: {self:?}"
                 );
-                let body = serde_json::json!({
-                    "status_code": 429,
-                    "error": "password_reset_rate_limited",
-                    "message": "Too many password reset requests. Please wait before trying again.",
-                });
-                (StatusCode::TOO_MANY_REQUESTS, Json(body)).into_response()
+                Problem::new(
+                    StatusCode::TOO_MANY_REQUESTS,
+                    "password_reset_rate_limited",
+                    "Too many password reset requests",
+                    "Too many password reset requests. Please wait before trying again.",
+                )
+                .into_response()
             }
             EntityErrorKind::ServiceUnavailable => {
                 warn!(
                     "EntityErrorKind::ServiceUnavailable: Responding with 503 Service Unavailable. Error: {self:?}"
                 );
-                (StatusCode::SERVICE_UNAVAILABLE, "SERVICE UNAVAILABLE").into_response()
+                Problem::new(
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    "service_unavailable",
+                    "Service unavailable",
+                    "The service is temporarily unavailable. Try again shortly.",
+                )
+                .into_response()
+            }
+            EntityErrorKind::StatementTimeout => {
+                warn!(
+                    "EntityErrorKind::StatementTimeout: Responding with 503 Service Unavailable. Error: {self:?}"
+                );
+                // Its own type, so clients can tell a cancelled query from an outage
+                let mut response = Problem::new(
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    "statement_timeout",
+                    "Database query timed out",
+                    "The request took too long to query the database and was cancelled. Try again shortly or narrow the request.",
+                )
+                .into_response();
+                response
+                    .headers_mut()
+                    .insert(header::RETRY_AFTER, header::HeaderValue::from_static("5"));
+                response
+            }
+            EntityErrorKind::StaleVersion => {
+                warn!(
+                    "EntityErrorKind::StaleVersion: Responding with 412 Precondition Failed. Error: {self:?}"
+                );
+                Problem::precondition_failed().into_response()
             }
             EntityErrorKind::Other(_description) => {
                 warn!(
                     "EntityErrorKind::Other: Responding with 500 Internal Server Error. Error: {self:?}"
                 );
-                (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL SERVER ERROR").into_response()
+                Problem::internal().into_response()
             }
         }
     }
@@ -240,26 +414,34 @@ impl Error {
                 warn!(
                     "ExternalErrorKind::Network: Responding with 502 Bad Gateway. Error: {self:?}"
                 );
-                (StatusCode::BAD_GATEWAY, "BAD GATEWAY").into_response()
+                Problem::new(
+                    StatusCode::BAD_GATEWAY,
+                    "bad_gateway",
+                    "Upstream service unreachable",
+                    "A service this request depends on couldn't be reached.",
+                )
+                .into_response()
             }
             ExternalErrorKind::OauthTokenRevoked(ref provider) => {
                 warn!(
                     "ExternalErrorKind::OauthTokenRevoked: Responding with 409 Conflict. Error: {self:?}"
                 );
-                (
+                Problem::new(
                     StatusCode::CONFLICT,
-                    Json(serde_json::json!({
-                        "error": "oauth_token_revoked",
-                        "provider": provider,
-                    })),
+                    "oauth_token_revoked",
+                    "OAuth connection revoked",
+                    format!(
+                        "Access to {provider} was revoked. Reconnect the account and try again."
+                    ),
                 )
-                    .into_response()
+                .with("provider", provider.as_str())
+                .into_response()
             }
             ExternalErrorKind::Other(_description) => {
                 warn!(
                     "ExternalErrorKind::Other: Responding with 500 Internal Server Error. Error: {self:?}"
                 );
-                (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL SERVER ERROR").into_response()
+                Problem::internal().into_response()
             }
         }
     }
@@ -268,43 +450,63 @@ impl Error {
         match web_error_kind {
             WebErrorKind::Input => {
                 warn!("WebErrorKind::Input: Responding with 400 Bad Request. Error: {self:?}");
-                (StatusCode::BAD_REQUEST, "BAD REQUEST").into_response()
+                Problem::new(
+                    StatusCode::BAD_REQUEST,
+                    "bad_request",
+                    "Bad request",
+                    "The request is malformed.",
+                )
+                .into_response()
             }
             WebErrorKind::Auth => {
                 warn!("WebErrorKind::Auth: Responding with 401 Unauthorized. Error: {self:?}");
-                (StatusCode::UNAUTHORIZED, "UNAUTHORIZED").into_response()
+                Problem::unauthenticated().into_response()
             }
             WebErrorKind::ForbiddenAssigneeScope => {
                 warn!(
                     "WebErrorKind::ForbiddenAssigneeScope: Responding with 403 Forbidden. Error: {self:?}"
                 );
-                let body = serde_json::json!({
-                    "status_code": 403,
-                    "error": "forbidden_assignee_scope",
-                    "message": "Caller is not permitted to scope actions to that assignee.",
-                });
-                (StatusCode::FORBIDDEN, Json(body)).into_response()
+                Problem::new(
+                    StatusCode::FORBIDDEN,
+                    "forbidden_assignee_scope",
+                    "Assignee out of scope",
+                    "Caller is not permitted to scope actions to that assignee.",
+                )
+                .into_response()
             }
             WebErrorKind::InvalidTimezone(value) => {
                 warn!(
                     "WebErrorKind::InvalidTimezone: Responding with 400 Bad Request. Error: {self:?}"
                 );
-                let body = serde_json::json!({
-                    "status_code": 400,
-                    "error": "invalid_timezone",
-                    "message": format!("'{value}' is not a recognized IANA timezone identifier."),
-                });
-                (StatusCode::BAD_REQUEST, Json(body)).into_response()
+                Problem::new(
+                    StatusCode::BAD_REQUEST,
+                    "invalid_timezone",
+                    "Invalid timezone",
+                    format!("'{value}' is not a recognized IANA timezone identifier."),
+                )
+                .into_response()
             }
             WebErrorKind::Conflict => {
                 warn!("WebErrorKind::Conflict: Responding with 409 Conflict. Error: {self:?}");
-                (StatusCode::CONFLICT, "CONFLICT").into_response()
+                Problem::new(
+                    StatusCode::CONFLICT,
+                    "conflict",
+                    "Conflict",
+                    "The request conflicts with the resource's current state.",
+                )
+                .into_response()
+            }
+            WebErrorKind::PreconditionFailed => {
+                warn!(
+                    "WebErrorKind::PreconditionFailed: Responding with 412 Precondition Failed. Error: {self:?}"
+                );
+                Problem::precondition_failed().into_response()
             }
             WebErrorKind::Other => {
                 warn!(
                     "WebErrorKind::Other: Responding with 500 Internal Server Error. Error: {self:?}"
                 );
-                (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL SERVER ERROR").into_response()
+                Problem::internal().into_response()
             }
         }
     }
@@ -356,6 +558,31 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn precondition_failed_produces_structured_412() {
+        let response = Error::Web(WebErrorKind::PreconditionFailed).into_response();
+        assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("body collects");
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes).expect("body is JSON");
+        assert_eq!(body["status_code"], 412);
+        assert_eq!(body["error"], "precondition_failed");
+    }
+
+    #[tokio::test]
+    async fn stale_version_writes_are_412() {
+        let response = Error::Domain(DomainError {
+            source: None,
+            error_kind: DomainErrorKind::Internal(InternalErrorKind::Entity(
+                EntityErrorKind::StaleVersion,
+            )),
+        })
+        .into_response();
+        assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+    }
+
     #[tokio::test]
     async fn organization_not_empty_produces_structured_409_with_details() {
         let err = Error::Domain(DomainError {
@@ -381,6 +608,38 @@ mod tests {
         assert_eq!(body["details"]["member_count"], 4);
     }
 
+    #[tokio::test]
+    async fn password_policy_produces_422_listing_the_failed_rules() {
+        use domain::password_policy::{PasswordRule, PasswordRuleFailure};
+
+        let err = Error::Domain(DomainError {
+            source: None,
+            error_kind: DomainErrorKind::PasswordPolicy(vec![
+                PasswordRuleFailure {
+                    rule: PasswordRule::MinLength,
+                    message: "Password must be at least 12 characters".to_string(),
+                },
+                PasswordRuleFailure {
+                    rule: PasswordRule::Digit,
+                    message: "Password must contain a digit".to_string(),
+                },
+            ]),
+        });
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body_bytes = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("body collects");
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes).expect("body is JSON");
+        assert_eq!(body["error"], "password_policy");
+        assert_eq!(
+            body["message"],
+            "Password must be at least 12 characters. Password must contain a digit"
+        );
+        assert_eq!(body["details"]["failed_rules"][0]["rule"], "min_length");
+        assert_eq!(body["details"]["failed_rules"][1]["rule"], "digit");
+    }
+
     #[tokio::test]
     async fn organization_name_taken_produces_structured_409_with_name() {
         let err = Error::Domain(DomainError {
@@ -419,4 +678,83 @@ mod tests {
         assert_eq!(body["status_code"], 409);
         assert_eq!(body["error"], "organization_archived");
     }
+
+    #[tokio::test]
+    async fn errors_are_problem_details_naming_the_request() {
+        let correlation_id = domain::Id::new_v4();
+        let response = domain::events::with_correlation_id(Some(correlation_id), async {
+            Error::Domain(DomainError {
+                source: None,
+                error_kind: DomainErrorKind::Validation("`title` must not be empty".to_string()),
+            })
+            .into_response()
+        })
+        .await;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "application/problem+json"
+        );
+        let body_bytes = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("body collects");
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes).expect("body is JSON");
+        assert_eq!(
+            body["type"],
+            "urn:refactor-platform:problem:validation-error"
+        );
+        assert_eq!(body["title"], "Validation failed");
+        assert_eq!(body["status"], 422);
+        assert_eq!(body["detail"], "`title` must not be empty");
+        assert_eq!(body["instance"], format!("urn:uuid:{correlation_id}"));
+        assert_eq!(body["error_code"], "validation_error");
+        // Kept for clients reading the earlier shape
+        assert_eq!(body["status_code"], 422);
+        assert_eq!(body["error"], "validation_error");
+        assert_eq!(body["message"], "`title` must not be empty");
+    }
+
+    #[tokio::test]
+    async fn internal_errors_are_problem_details_without_their_cause() {
+        let response = Error::Domain(DomainError {
+            source: None,
+            error_kind: DomainErrorKind::Internal(InternalErrorKind::Other(
+                "connection refused by 10.0.0.7".to_string(),
+            )),
+        })
+        .into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        let body_bytes = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("body collects");
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes).expect("body is JSON");
+        assert_eq!(body["error_code"], "internal_error");
+        assert!(body.get("instance").is_none());
+        assert!(!String::from_utf8_lossy(&body_bytes).contains("10.0.0.7"));
+    }
+
+    #[tokio::test]
+    async fn statement_timeout_produces_problem_json_503() {
+        let err = Error::Domain(DomainError {
+            source: None,
+            error_kind: DomainErrorKind::Internal(InternalErrorKind::Entity(
+                EntityErrorKind::StatementTimeout,
+            )),
+        });
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "application/problem+json"
+        );
+        let body_bytes = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("body collects");
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes).expect("body is JSON");
+        assert_eq!(body["status"], 503);
+        assert_eq!(
+            body["type"],
+            "urn:refactor-platform:problem:statement-timeout"
+        );
+    }
 }
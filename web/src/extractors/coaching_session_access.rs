@@ -175,11 +175,14 @@ mod tests {
             created_at: now.into(),
             updated_at: now.into(),
             hydrated_at: Some(now.into()),
+            session_type_id: None,
+            deleted_at: None,
         };
 
         let db = Arc::new(
             MockDatabase::new(DatabaseBackend::Postgres)
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
+                .append_query_results([Vec::<domain::policy_documents::Model>::new()])
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
                 .append_query_results(vec![vec![(
                     test_session.clone(),
@@ -189,6 +192,8 @@ mod tests {
                         coachee_id: test_user.id,
                         organization_id: Id::new_v4(),
                         slug: "test".to_string(),
+                        ai_privacy_level: Default::default(),
+                        pro_bono: false,
                         created_at: now.into(),
                         updated_at: now.into(),
                     },
@@ -288,11 +293,14 @@ mod tests {
             created_at: now.into(),
             updated_at: now.into(),
             hydrated_at: Some(now.into()),
+            session_type_id: None,
+            deleted_at: None,
         };
 
         let db = Arc::new(
             MockDatabase::new(DatabaseBackend::Postgres)
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
+                .append_query_results([Vec::<domain::policy_documents::Model>::new()])
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
                 .append_query_results(vec![vec![test_session.clone()]])
                 .into_connection(),
@@ -361,6 +369,7 @@ mod tests {
         let db = Arc::new(
             MockDatabase::new(DatabaseBackend::Postgres)
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
+                .append_query_results([Vec::<domain::policy_documents::Model>::new()])
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
                 .into_connection(),
         );
@@ -458,11 +467,14 @@ mod tests {
             created_at: now.into(),
             updated_at: now.into(),
             hydrated_at: Some(now.into()),
+            session_type_id: None,
+            deleted_at: None,
         };
 
         let db = Arc::new(
             MockDatabase::new(DatabaseBackend::Postgres)
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
+                .append_query_results([Vec::<domain::policy_documents::Model>::new()])
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
                 .append_query_results(vec![vec![test_session.clone()]])
                 .into_connection(),
@@ -559,13 +571,16 @@ mod tests {
             meeting_url: None,
             provider: None,
             hydrated_at: None,
+            session_type_id: None,
             created_at: now.into(),
             updated_at: now.into(),
+            deleted_at: None,
         };
 
         let db = Arc::new(
             MockDatabase::new(DatabaseBackend::Postgres)
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
+                .append_query_results([Vec::<domain::policy_documents::Model>::new()])
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
                 .append_query_results(vec![vec![(
                     test_session.clone(),
@@ -575,6 +590,8 @@ mod tests {
                         coachee_id: test_user.id,
                         organization_id: Id::new_v4(),
                         slug: "test".to_string(),
+                        ai_privacy_level: Default::default(),
+                        pro_bono: false,
                         created_at: now.into(),
                         updated_at: now.into(),
                     },
@@ -666,13 +683,16 @@ mod tests {
             meeting_url: None,
             provider: None,
             hydrated_at: None,
+            session_type_id: None,
             created_at: now.into(),
             updated_at: now.into(),
+            deleted_at: None,
         };
 
         let db = Arc::new(
             MockDatabase::new(DatabaseBackend::Postgres)
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
+                .append_query_results([Vec::<domain::policy_documents::Model>::new()])
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
                 .append_query_results(vec![vec![(
                     test_session.clone(),
@@ -682,6 +702,8 @@ mod tests {
                         coachee_id: test_user.id,
                         organization_id: Id::new_v4(),
                         slug: "test".to_string(),
+                        ai_privacy_level: Default::default(),
+                        pro_bono: false,
                         created_at: now.into(),
                         updated_at: now.into(),
                     },
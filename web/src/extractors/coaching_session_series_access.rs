@@ -244,6 +244,8 @@ mod tests {
             coachee_id,
             organization_id: Id::new_v4(),
             slug: "test".to_string(),
+            ai_privacy_level: Default::default(),
+            pro_bono: false,
             created_at: now.into(),
             updated_at: now.into(),
         }
@@ -349,6 +351,7 @@ mod tests {
             MockDatabase::new(DatabaseBackend::Postgres)
                 // Login: AuthN -> users + roles
                 .append_query_results([vec![(user.clone(), role.clone())]])
+                .append_query_results([Vec::<domain::policy_documents::Model>::new()])
                 // require_auth: load again on the protected request
                 .append_query_results([vec![(user.clone(), role.clone())]])
                 // find_by_id: series row
@@ -382,6 +385,7 @@ mod tests {
         let db = Arc::new(
             MockDatabase::new(DatabaseBackend::Postgres)
                 .append_query_results([vec![(user.clone(), role.clone())]])
+                .append_query_results([Vec::<domain::policy_documents::Model>::new()])
                 .append_query_results([vec![(user.clone(), role.clone())]])
                 .append_query_results(vec![vec![series.clone()]])
                 .append_query_results(vec![vec![relationship.clone()]])
@@ -417,6 +421,7 @@ mod tests {
         let db = Arc::new(
             MockDatabase::new(DatabaseBackend::Postgres)
                 .append_query_results([vec![(user.clone(), role.clone())]])
+                .append_query_results([Vec::<domain::policy_documents::Model>::new()])
                 .append_query_results([vec![(user.clone(), role.clone())]])
                 .append_query_results(vec![vec![series.clone()]])
                 .append_query_results(vec![vec![relationship.clone()]])
@@ -451,6 +456,7 @@ mod tests {
         let db = Arc::new(
             MockDatabase::new(DatabaseBackend::Postgres)
                 .append_query_results([vec![(user.clone(), role.clone())]])
+                .append_query_results([Vec::<domain::policy_documents::Model>::new()])
                 .append_query_results([vec![(user.clone(), role.clone())]])
                 .append_query_results(vec![vec![relationship.clone()]])
                 .into_connection(),
@@ -485,6 +491,7 @@ mod tests {
         let db = Arc::new(
             MockDatabase::new(DatabaseBackend::Postgres)
                 .append_query_results([vec![(user.clone(), role.clone())]])
+                .append_query_results([Vec::<domain::policy_documents::Model>::new()])
                 .append_query_results([vec![(user.clone(), role.clone())]])
                 .append_query_results(vec![vec![relationship.clone()]])
                 .into_connection(),
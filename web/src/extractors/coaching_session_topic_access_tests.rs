@@ -70,8 +70,10 @@ fn test_session(session_id: Id, relationship_id: Id) -> coaching_sessions::Model
         meeting_url: None,
         provider: None,
         hydrated_at: None,
+        session_type_id: None,
         created_at: now.into(),
         updated_at: now.into(),
+        deleted_at: None,
     }
 }
 
@@ -83,6 +85,8 @@ fn test_relationship(relationship_id: Id, coachee_id: Id) -> coaching_relationsh
         coachee_id,
         organization_id: Id::new_v4(),
         slug: "test".to_string(),
+        ai_privacy_level: Default::default(),
+        pro_bono: false,
         created_at: now.into(),
         updated_at: now.into(),
     }
@@ -100,6 +104,8 @@ fn test_relationship_with_coach(
         coachee_id,
         organization_id: Id::new_v4(),
         slug: "test".to_string(),
+        ai_privacy_level: Default::default(),
+        pro_bono: false,
         created_at: now.into(),
         updated_at: now.into(),
     }
@@ -221,6 +227,7 @@ async fn delete_extractor_ok_when_author() {
     let db = Arc::new(
         MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results([vec![(user.clone(), role.clone())]])
+            .append_query_results([Vec::<domain::policy_documents::Model>::new()])
             .append_query_results([vec![(user.clone(), role.clone())]])
             .append_query_results(vec![vec![(
                 test_session(session_id, relationship_id),
@@ -259,6 +266,7 @@ async fn delete_extractor_404_when_coachee_deletes_coachs_topic() {
     let db = Arc::new(
         MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results([vec![(user.clone(), role.clone())]])
+            .append_query_results([Vec::<domain::policy_documents::Model>::new()])
             .append_query_results([vec![(user.clone(), role.clone())]])
             .append_query_results(vec![vec![(
                 test_session(session_id, relationship_id),
@@ -302,6 +310,7 @@ async fn delete_extractor_ok_when_coach_deletes_coachees_topic() {
     let db = Arc::new(
         MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results([vec![(user.clone(), role.clone())]])
+            .append_query_results([Vec::<domain::policy_documents::Model>::new()])
             .append_query_results([vec![(user.clone(), role.clone())]])
             .append_query_results(vec![vec![(
                 test_session(session_id, relationship_id),
@@ -343,6 +352,7 @@ async fn delete_extractor_404_when_topic_belongs_to_other_session() {
     let db = Arc::new(
         MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results([vec![(user.clone(), role.clone())]])
+            .append_query_results([Vec::<domain::policy_documents::Model>::new()])
             .append_query_results([vec![(user.clone(), role.clone())]])
             .append_query_results(vec![vec![(
                 test_session(session_id, relationship_id),
@@ -378,6 +388,7 @@ async fn coachee_extractor_ok_when_user_is_coachee() {
     let db = Arc::new(
         MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results([vec![(user.clone(), role.clone())]])
+            .append_query_results([Vec::<domain::policy_documents::Model>::new()])
             .append_query_results([vec![(user.clone(), role.clone())]])
             // CoachingSessionTopicAccess: participant check (caller is the coachee) + topic load.
             .append_query_results(vec![vec![(
@@ -421,6 +432,7 @@ async fn coachee_extractor_403_when_user_is_coach() {
     let db = Arc::new(
         MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results([vec![(user.clone(), role.clone())]])
+            .append_query_results([Vec::<domain::policy_documents::Model>::new()])
             .append_query_results([vec![(user.clone(), role.clone())]])
             // CoachingSessionTopicAccess: caller is the coach (a participant) -> passes; topic loads.
             .append_query_results(vec![vec![(
@@ -479,6 +491,7 @@ async fn undo_extractor_ok_for_live_topic_by_non_author() {
     let db = Arc::new(
         MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results([vec![(user.clone(), role.clone())]])
+            .append_query_results([Vec::<domain::policy_documents::Model>::new()])
             .append_query_results([vec![(user.clone(), role.clone())]])
             .append_query_results(vec![vec![(
                 test_session(session_id, relationship_id),
@@ -517,6 +530,7 @@ async fn undo_extractor_ok_for_deleted_topic_by_author() {
     let db = Arc::new(
         MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results([vec![(user.clone(), role.clone())]])
+            .append_query_results([Vec::<domain::policy_documents::Model>::new()])
             .append_query_results([vec![(user.clone(), role.clone())]])
             .append_query_results(vec![vec![(
                 test_session(session_id, relationship_id),
@@ -560,6 +574,7 @@ async fn undo_extractor_404_for_deleted_topic_by_non_author() {
     let db = Arc::new(
         MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results([vec![(user.clone(), role.clone())]])
+            .append_query_results([Vec::<domain::policy_documents::Model>::new()])
             .append_query_results([vec![(user.clone(), role.clone())]])
             .append_query_results(vec![vec![(
                 test_session(session_id, relationship_id),
@@ -604,6 +619,7 @@ async fn undo_extractor_404_when_topic_belongs_to_other_session() {
     let db = Arc::new(
         MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results([vec![(user.clone(), role.clone())]])
+            .append_query_results([Vec::<domain::policy_documents::Model>::new()])
             .append_query_results([vec![(user.clone(), role.clone())]])
             .append_query_results(vec![vec![(
                 test_session(session_id, relationship_id),
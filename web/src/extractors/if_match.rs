@@ -0,0 +1,141 @@
+//! Optimistic concurrency for resources more than one user edits.
+//!
+//! Reads of notes, agreements, actions and goals carry an `ETag` derived from the
+//! resource's `updated_at`. A client that sends it back in `If-Match` on a PUT has the
+//! write refused with 412 Precondition Failed when someone changed the resource since,
+//! rather than silently overwriting that edit. Writes without `If-Match` go through as
+//! before.
+
+use crate::error::{Error, WebErrorKind};
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header, request::Parts, HeaderValue},
+    response::{IntoResponse, Response},
+};
+use domain::ExpectedVersion;
+use sea_orm::prelude::DateTimeWithTimeZone;
+
+/// The versions of the resource a write's `If-Match` header lets it replace.
+///
+/// Handed down to the update, which makes the check part of its `UPDATE` so that a change
+/// committed in between can't slip past it. A header that isn't ASCII can't name any tag
+/// and is refused with 412 here.
+#[derive(Debug, Default)]
+pub(crate) struct IfMatch(ExpectedVersion);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for IfMatch
+where
+    S: Send + Sync,
+{
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let Some(if_match) = parts.headers.get(header::IF_MATCH) else {
+            return Ok(Self::default());
+        };
+        let if_match = if_match
+            .to_str()
+            .map_err(|_| Error::Web(WebErrorKind::PreconditionFailed))?;
+        Ok(Self(expected_version(if_match)))
+    }
+}
+
+impl IfMatch {
+    /// The versions the write may replace.
+    pub(crate) fn expected(&self) -> &ExpectedVersion {
+        &self.0
+    }
+}
+
+/// The strong entity tag of a resource last changed at `updated_at`.
+pub(crate) fn etag(updated_at: &DateTimeWithTimeZone) -> String {
+    format!("\"{:x}\"", updated_at.timestamp_micros())
+}
+
+/// `body` with the `ETag` of a resource last changed at `updated_at`.
+pub(crate) fn with_etag(updated_at: DateTimeWithTimeZone, body: impl IntoResponse) -> Response {
+    let tag = HeaderValue::from_str(&etag(&updated_at)).expect("hex entity tags are valid headers");
+    ([(header::ETAG, tag)], body).into_response()
+}
+
+/// The versions an `If-Match` list (`"a", "b"` or `*`) names. Weak and unrecognized tags
+/// name none, as If-Match compares strongly.
+fn expected_version(if_match: &str) -> ExpectedVersion {
+    let candidates: Vec<&str> = if_match.split(',').map(str::trim).collect();
+    if candidates.contains(&"*") {
+        return ExpectedVersion::Any;
+    }
+    ExpectedVersion::UpdatedAt(candidates.into_iter().filter_map(updated_at_of).collect())
+}
+
+/// The `updated_at` a strong tag made by [`etag`] stands for.
+fn updated_at_of(tag: &str) -> Option<DateTimeWithTimeZone> {
+    let micros = tag.strip_prefix('"')?.strip_suffix('"')?;
+    let micros = i64::from_str_radix(micros, 16).ok()?;
+    chrono::DateTime::from_timestamp_micros(micros).map(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+
+    fn at(micros: i64) -> DateTimeWithTimeZone {
+        chrono::DateTime::from_timestamp_micros(micros)
+            .unwrap()
+            .into()
+    }
+
+    async fn extract(if_match: Option<&[u8]>) -> Result<IfMatch, Error> {
+        let mut request = Request::builder();
+        if let Some(if_match) = if_match {
+            request = request.header(header::IF_MATCH, HeaderValue::from_bytes(if_match).unwrap());
+        }
+        let (mut parts, _) = request.body(()).unwrap().into_parts();
+        IfMatch::from_request_parts(&mut parts, &()).await
+    }
+
+    #[test]
+    fn tags_change_with_updated_at() {
+        assert_eq!(etag(&at(1_760_000_000_000_001)), "\"640b5eece0001\"");
+        assert_ne!(etag(&at(1)), etag(&at(2)));
+    }
+
+    #[test]
+    fn if_match_lists_name_versions_strongly() {
+        let tag = etag(&at(42));
+
+        assert_eq!(
+            expected_version(&tag),
+            ExpectedVersion::UpdatedAt(vec![at(42)])
+        );
+        assert_eq!(
+            expected_version(&format!("\"ff\", {tag}")),
+            ExpectedVersion::UpdatedAt(vec![at(0xff), at(42)])
+        );
+        assert_eq!(expected_version("*"), ExpectedVersion::Any);
+        assert_eq!(
+            expected_version(&format!("W/{tag}, \"not-a-tag\"")),
+            ExpectedVersion::UpdatedAt(vec![])
+        );
+    }
+
+    #[tokio::test]
+    async fn writes_without_if_match_replace_any_version() {
+        let if_match = extract(None).await.unwrap();
+
+        assert_eq!(if_match.expected(), &ExpectedVersion::Any);
+    }
+
+    #[tokio::test]
+    async fn a_non_ascii_if_match_fails_the_precondition() {
+        let result = extract(Some("\"caf\u{e9}\"".as_bytes())).await;
+
+        assert!(matches!(
+            result,
+            Err(Error::Web(WebErrorKind::PreconditionFailed))
+        ));
+    }
+}
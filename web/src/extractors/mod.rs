@@ -4,8 +4,10 @@ pub(crate) mod coaching_session_access;
 pub(crate) mod coaching_session_series_access;
 pub(crate) mod coaching_session_topic_access;
 pub(crate) mod compare_api_version;
+pub(crate) mod if_match;
 pub(crate) mod organization_member_access;
 pub(crate) mod organization_user_access;
+pub(crate) mod scim_organization;
 pub(crate) mod super_admin_access;
 pub(crate) mod svix_signature;
 
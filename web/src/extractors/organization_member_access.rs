@@ -202,6 +202,7 @@ mod tests {
         let db = Arc::new(
             MockDatabase::new(DatabaseBackend::Postgres)
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
+                .append_query_results([Vec::<domain::policy_documents::Model>::new()])
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
                 .append_query_results([vec![test_organization.clone()]])
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
@@ -289,6 +290,7 @@ mod tests {
         let db = Arc::new(
             MockDatabase::new(DatabaseBackend::Postgres)
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
+                .append_query_results([Vec::<domain::policy_documents::Model>::new()])
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
                 .append_query_results([vec![test_organization.clone()]])
                 .into_connection(),
@@ -376,6 +378,7 @@ mod tests {
         let db = Arc::new(
             MockDatabase::new(DatabaseBackend::Postgres)
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
+                .append_query_results([Vec::<domain::policy_documents::Model>::new()])
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
                 .append_query_results([vec![test_organization.clone()]])
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
@@ -462,6 +465,7 @@ mod tests {
         let db = Arc::new(
             MockDatabase::new(DatabaseBackend::Postgres)
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
+                .append_query_results([Vec::<domain::policy_documents::Model>::new()])
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
                 .append_query_results([Vec::<organizations::Model>::new()])
                 .into_connection(),
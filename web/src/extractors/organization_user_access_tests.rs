@@ -79,6 +79,7 @@ mod organization_user_access_integration_tests {
                     caller.clone(),
                     role_in_org(caller.id, Id::new_v4()),
                 )]])
+                .append_query_results([Vec::<domain::policy_documents::Model>::new()])
                 // 2. require_auth -> get_user(caller)
                 .append_query_results([vec![(
                     caller.clone(),
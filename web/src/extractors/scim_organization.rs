@@ -0,0 +1,60 @@
+//! SCIM token authentication extractor.
+
+use crate::controller::scim::ScimError;
+use crate::AppState;
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{
+        header::AUTHORIZATION, header::WWW_AUTHENTICATE, request::Parts, HeaderValue, StatusCode,
+    },
+    response::{IntoResponse, Response},
+};
+use domain::{scim as ScimApi, Id};
+use log::*;
+
+/// Authenticates a SCIM request by the organization's SCIM token, sent as an
+/// `Authorization: Bearer` header, yielding the organization's ID.
+///
+/// Rejects with a SCIM 401 error if the token is missing or isn't an organization's.
+pub(crate) struct ScimOrganization(pub Id);
+
+#[async_trait]
+impl FromRequestParts<AppState> for ScimOrganization {
+    type Rejection = Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let token = parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::trim)
+            .ok_or_else(unauthorized)?;
+
+        match ScimApi::authenticate(state.db_conn_ref(), token).await {
+            Ok(Some(organization_id)) => Ok(ScimOrganization(organization_id)),
+            Ok(None) => {
+                warn!("SCIM request with an unknown token");
+                Err(unauthorized())
+            }
+            Err(err) => Err(ScimError::from(err).into_response()),
+        }
+    }
+}
+
+fn unauthorized() -> Response {
+    let mut response = ScimError::new(
+        StatusCode::UNAUTHORIZED,
+        None,
+        "A valid SCIM bearer token is required",
+    )
+    .into_response();
+    response
+        .headers_mut()
+        .insert(WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+    response
+}
@@ -159,6 +159,7 @@ mod tests {
         let db = Arc::new(
             MockDatabase::new(DatabaseBackend::Postgres)
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
+                .append_query_results([Vec::<domain::policy_documents::Model>::new()])
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
                 .into_connection(),
         );
@@ -194,6 +195,7 @@ mod tests {
         let db = Arc::new(
             MockDatabase::new(DatabaseBackend::Postgres)
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
+                .append_query_results([Vec::<domain::policy_documents::Model>::new()])
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
                 .into_connection(),
         );
@@ -232,6 +234,7 @@ mod tests {
         let db = Arc::new(
             MockDatabase::new(DatabaseBackend::Postgres)
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
+                .append_query_results([Vec::<domain::policy_documents::Model>::new()])
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]])
                 .into_connection(),
         );
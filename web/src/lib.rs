@@ -1,7 +1,8 @@
 use axum::http::{
-    header::{AUTHORIZATION, CONTENT_TYPE},
+    header::{AUTHORIZATION, CONTENT_TYPE, ETAG, IF_MATCH},
     HeaderName, HeaderValue, Method,
 };
+use axum::middleware::from_fn_with_state;
 use axum_login::{
     tower_sessions::{Expiry, SessionManagerLayer},
     AuthManagerLayerBuilder,
@@ -12,7 +13,9 @@ use tower_sessions_sqlx_store::PostgresStore;
 
 pub use self::error::{Error, Result};
 use log::*;
-use meeting_ai::traits::{recording_bot, transcription as transcription_trait};
+use meeting_ai::traits::{
+    analysis, embedding, recording_bot, transcription as transcription_trait,
+};
 use sea_orm::DatabaseConnection;
 use service::config::{ApiVersion, Config};
 use std::net::SocketAddr;
@@ -33,15 +36,36 @@ pub mod sse;
 
 /// Web-layer application state that includes both infrastructure and domain concerns.
 /// This wraps the service-level state and adds the event publisher for domain events.
-#[derive(Clone)]
+///
+/// Axum clones the state for every request, and a clone takes the pool currently in the
+/// database slot, so requests pick up a pool the health monitor swapped in. Likewise its
+/// `config` takes the runtime settings that apply now, so requests see a reload.
 pub struct AppState {
     pub database_connection: Arc<DatabaseConnection>,
+    /// Where `database_connection` is refreshed from on clone; `None` keeps it as is,
+    /// e.g. for the heavy endpoints' pool.
+    pub database_slot: Option<Arc<service::db_health::DatabaseSlot>>,
+    /// The database health monitor's probes, in the Prometheus format at GET /metrics.
+    pub pool_health: Arc<service::db_health::PoolHealth>,
+    /// The settings reloadable without a restart, copied into `config` on clone.
+    pub runtime_settings: Arc<service::runtime_config::RuntimeSettings>,
+    /// Pool with a longer statement timeout for the heavy endpoints; see
+    /// [`AppState::for_heavy_queries`]. `None` shares `database_connection`.
+    pub heavy_database_connection: Option<Arc<DatabaseConnection>>,
     pub config: Config,
     pub sse_manager: Arc<::sse::Manager>,
     pub event_publisher: Arc<domain::events::EventPublisher>,
+    /// The event publisher's measurements, in the Prometheus format at GET /metrics.
+    pub event_metrics: Arc<service::metrics::EventMetrics>,
+    /// Organizations' API requests not yet flushed to the usage rollups, and their quotas.
+    pub api_usage: Arc<domain::api_usage::ApiUsageTracker>,
     pub oauth_state_manager: meeting_auth::oauth::StateManager,
     pub recording_bot_provider: Option<Arc<dyn recording_bot::Provider>>,
     pub transcription_provider: Option<Arc<dyn transcription_trait::Provider>>,
+    /// LLM used by AI-assisted features; `None` when no gateway is configured.
+    pub analysis_provider: Option<Arc<dyn analysis::Provider>>,
+    /// Text embeddings for semantic retrieval; `None` when no gateway is configured.
+    pub embedding_provider: Option<Arc<dyn embedding::Provider>>,
 }
 
 impl AppState {
@@ -54,18 +78,94 @@ impl AppState {
     ) -> Self {
         Self {
             database_connection: service_state.database_connection,
+            database_slot: Some(service_state.database_slot),
+            pool_health: service_state.pool_health,
+            runtime_settings: service_state.runtime_settings,
+            heavy_database_connection: None,
             config: service_state.config,
             sse_manager,
             event_publisher: Arc::new(event_publisher),
+            event_metrics: service_state.event_metrics,
+            api_usage: Arc::new(domain::api_usage::ApiUsageTracker::new()),
             oauth_state_manager: meeting_auth::oauth::StateManager::new(),
             recording_bot_provider,
             transcription_provider,
+            analysis_provider: None,
+            embedding_provider: None,
+        }
+    }
+
+    pub fn with_analysis_provider(
+        mut self,
+        analysis_provider: Option<Arc<dyn analysis::Provider>>,
+    ) -> Self {
+        self.analysis_provider = analysis_provider;
+        self
+    }
+
+    pub fn with_embedding_provider(
+        mut self,
+        embedding_provider: Option<Arc<dyn embedding::Provider>>,
+    ) -> Self {
+        self.embedding_provider = embedding_provider;
+        self
+    }
+
+    pub fn with_heavy_database_connection(
+        mut self,
+        heavy_database_connection: Option<Arc<DatabaseConnection>>,
+    ) -> Self {
+        self.heavy_database_connection = heavy_database_connection;
+        self
+    }
+
+    /// The state for routes whose queries may outrun the main pool's statement timeout,
+    /// e.g. analytics: its handlers query through the heavy pool when there is one.
+    pub fn for_heavy_queries(&self) -> Self {
+        let mut state = self.clone();
+        if let Some(heavy_database_connection) = &self.heavy_database_connection {
+            state.database_connection = Arc::clone(heavy_database_connection);
+            state.database_slot = None;
         }
+        state
     }
 
     pub fn db_conn_ref(&self) -> &DatabaseConnection {
         self.database_connection.as_ref()
     }
+
+    /// Swaps in a new pool for the requests started from now on
+    pub fn set_db_conn(&mut self, db: DatabaseConnection) {
+        self.database_connection = Arc::new(db);
+        if let Some(database_slot) = &self.database_slot {
+            database_slot.replace(Arc::clone(&self.database_connection));
+        }
+    }
+}
+
+impl Clone for AppState {
+    fn clone(&self) -> Self {
+        Self {
+            database_connection: self.database_slot.as_ref().map_or_else(
+                || Arc::clone(&self.database_connection),
+                |database_slot| database_slot.current(),
+            ),
+            database_slot: self.database_slot.clone(),
+            pool_health: Arc::clone(&self.pool_health),
+            runtime_settings: Arc::clone(&self.runtime_settings),
+            heavy_database_connection: self.heavy_database_connection.clone(),
+            config: self.config.with_runtime(&self.runtime_settings.current()),
+            sse_manager: Arc::clone(&self.sse_manager),
+            event_publisher: Arc::clone(&self.event_publisher),
+            event_metrics: Arc::clone(&self.event_metrics),
+            api_usage: Arc::clone(&self.api_usage),
+            oauth_state_manager: self.oauth_state_manager.clone(),
+            recording_bot_provider: self.recording_bot_provider.clone(),
+            transcription_provider: self.transcription_provider.clone(),
+            analysis_provider: self.analysis_provider.clone(),
+            embedding_provider: self.embedding_provider.clone(),
+        }
+    }
 }
 
 pub async fn init_server(app_state: AppState) -> Result<()> {
@@ -124,6 +224,302 @@ pub async fn init_server(app_state: AppState) -> Result<()> {
         }
     });
 
+    // Background roll-over of recurring actions: generates the next instance for
+    // every recurring action whose due date has passed without a successor.
+    // Completions generate their successor inline, so this only catches periods
+    // that lapse untouched; hourly is plenty for daily-or-coarser rules.
+    let action_roll_over_task = tokio::task::spawn({
+        let db = Arc::clone(&app_state.database_connection);
+        let event_publisher = Arc::clone(&app_state.event_publisher);
+        async move {
+            const ROLL_OVER_INTERVAL: tokio::time::Duration =
+                tokio::time::Duration::from_secs(60 * 60);
+            loop {
+                tokio::time::sleep(ROLL_OVER_INTERVAL).await;
+                match domain::action_series::roll_over(&db, &event_publisher).await {
+                    Ok(created) if created > 0 => {
+                        log::info!("[action-roll-over] generated {created} recurring action(s)");
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        log::warn!("[action-roll-over] roll-over iteration failed: {e:?}");
+                    }
+                }
+            }
+        }
+    });
+
+    // Background pulse prompts: asks both participants of each session that just ended
+    // how valuable it was. Every five minutes keeps the prompt close to the session's end.
+    let session_pulse_task = tokio::task::spawn({
+        let db = Arc::clone(&app_state.database_connection);
+        let event_publisher = Arc::clone(&app_state.event_publisher);
+        async move {
+            const PULSE_INTERVAL: tokio::time::Duration = tokio::time::Duration::from_secs(5 * 60);
+            loop {
+                tokio::time::sleep(PULSE_INTERVAL).await;
+                match domain::session_pulse::request_due(&db, &event_publisher).await {
+                    Ok(prompted) if prompted > 0 => {
+                        log::info!("[session-pulses] prompted for {prompted} ended session(s)");
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        log::warn!("[session-pulses] prompt iteration failed: {e:?}");
+                    }
+                }
+            }
+        }
+    });
+
+    // Background relationship health scoring: scores every relationship from its recent
+    // pulses, cadence, action completion and sentiment, and alerts admins of the ones
+    // that became at risk. The signals move over weeks, so once a day is enough.
+    let relationship_health_task = tokio::task::spawn({
+        let db = Arc::clone(&app_state.database_connection);
+        let event_publisher = Arc::clone(&app_state.event_publisher);
+        async move {
+            const HEALTH_INTERVAL: tokio::time::Duration =
+                tokio::time::Duration::from_secs(24 * 60 * 60);
+            loop {
+                tokio::time::sleep(HEALTH_INTERVAL).await;
+                match domain::relationship_health::score_all(&db, &event_publisher).await {
+                    Ok(scored) if scored > 0 => {
+                        log::info!("[relationship-health] scored {scored} relationship(s)");
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        log::warn!("[relationship-health] scoring iteration failed: {e:?}");
+                    }
+                }
+            }
+        }
+    });
+
+    // Background embedding of new and edited notes and transcript segments for semantic
+    // retrieval. A minute's lag before new content is searchable is fine for chat, and
+    // anything missed while the provider was unreachable is picked up on a later pass.
+    // Only runs when an embedding provider is configured.
+    let embedding_index_task = tokio::task::spawn({
+        let db = Arc::clone(&app_state.database_connection);
+        let embedding_provider = app_state.embedding_provider.clone();
+        async move {
+            const INDEX_INTERVAL: tokio::time::Duration = tokio::time::Duration::from_secs(60);
+            let Some(provider) = embedding_provider else {
+                return;
+            };
+            loop {
+                tokio::time::sleep(INDEX_INTERVAL).await;
+                match domain::embedding_index::index_pending(&db, provider.as_ref()).await {
+                    Ok(indexed) if indexed > 0 => {
+                        log::info!("[embedding-index] embedded {indexed} record(s)");
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        log::warn!("[embedding-index] indexing pass failed: {e:?}");
+                    }
+                }
+            }
+        }
+    });
+
+    // Background weekly progress reports: once a week has ended, writes and emails each
+    // opted-in coach a report on every relationship that held a session that week.
+    // Checking hourly means reports go out within an hour of the week ending and a pass
+    // missed during downtime is made up on restart. Only runs when an analysis provider
+    // is configured.
+    let progress_report_task = tokio::task::spawn({
+        let db = Arc::clone(&app_state.database_connection);
+        let config = app_state.config.clone();
+        let analysis_provider = app_state.analysis_provider.clone();
+        async move {
+            const REPORT_INTERVAL: tokio::time::Duration =
+                tokio::time::Duration::from_secs(60 * 60);
+            let Some(provider) = analysis_provider else {
+                return;
+            };
+            loop {
+                tokio::time::sleep(REPORT_INTERVAL).await;
+                match domain::progress_report::send_weekly(&db, &config, provider.as_ref()).await {
+                    Ok(sent) if sent > 0 => {
+                        log::info!("[progress-reports] emailed {sent} progress report(s)");
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        log::warn!("[progress-reports] report pass failed: {e:?}");
+                    }
+                }
+            }
+        }
+    });
+
+    // Background publishing of scheduled events (see `domain::scheduled_event`): every
+    // few seconds, publishes the events whose time has come. Events are stored, so those
+    // that fell due while the process was down are published on restart.
+    let scheduled_event_task = tokio::task::spawn({
+        let db = Arc::clone(&app_state.database_connection);
+        let event_publisher = Arc::clone(&app_state.event_publisher);
+        async move {
+            const PUBLISH_INTERVAL: tokio::time::Duration = tokio::time::Duration::from_secs(5);
+            loop {
+                tokio::time::sleep(PUBLISH_INTERVAL).await;
+                match domain::scheduled_event::publish_due(&db, &event_publisher).await {
+                    Ok(published) if published > 0 => {
+                        log::info!("[scheduled-events] published {published} event(s)");
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        log::warn!("[scheduled-events] publish pass failed: {e:?}");
+                    }
+                }
+            }
+        }
+    });
+
+    // Background compiling of session records of engagement (see `domain::session_record`):
+    // every few seconds, compiles the records participants asked for and stores them in
+    // object storage. Requests are stored, so those waiting while the process was down
+    // are compiled on restart. Does nothing unless object storage is configured.
+    let session_record_task = tokio::task::spawn({
+        let db = Arc::clone(&app_state.database_connection);
+        let config = app_state.config.clone();
+        async move {
+            const COMPILE_INTERVAL: tokio::time::Duration = tokio::time::Duration::from_secs(10);
+            loop {
+                tokio::time::sleep(COMPILE_INTERVAL).await;
+                match domain::session_record::compile_pending(&db, &config).await {
+                    Ok(stored) if stored > 0 => {
+                        log::info!("[session-records] compiled {stored} record(s)");
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        log::warn!("[session-records] compile pass failed: {e:?}");
+                    }
+                }
+            }
+        }
+    });
+
+    // Backfill of stored events to the analytics warehouse (see `domain::warehouse`): exports
+    // the backfill under way a batch at a time, straight after a full batch and every few
+    // seconds otherwise. Its checkpoint is stored, so a backfill resumes after a restart.
+    // Does nothing unless a backfill was started.
+    let warehouse_backfill_task = tokio::task::spawn({
+        let db = Arc::clone(&app_state.database_connection);
+        let config = app_state.config.clone();
+        async move {
+            const BACKFILL_INTERVAL: tokio::time::Duration = tokio::time::Duration::from_secs(10);
+            loop {
+                match domain::warehouse::backfill_pass(&db, &config).await {
+                    Ok(exported) if exported > 0 => {
+                        log::debug!("[warehouse] backfilled {exported} event(s)");
+                        continue;
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        log::warn!("[warehouse] backfill pass failed: {e:?}");
+                    }
+                }
+                tokio::time::sleep(BACKFILL_INTERVAL).await;
+            }
+        }
+    });
+
+    // Daily upkeep of the monthly-partitioned event store (see `domain::event_log::maintain`):
+    // creates the coming months' partitions, compacts old updates and drops months past
+    // retention. Runs once at startup first, so a new month never lacks its partition
+    // after downtime.
+    let event_store_task = tokio::task::spawn({
+        let db = Arc::clone(&app_state.database_connection);
+        let retention_months = app_state.config.event_store_retention_months();
+        let compact_after_days = app_state.config.event_store_compact_after_days();
+        async move {
+            const MAINTENANCE_INTERVAL: tokio::time::Duration =
+                tokio::time::Duration::from_secs(24 * 60 * 60);
+            loop {
+                match domain::event_log::maintain(&db, retention_months, compact_after_days).await {
+                    Ok(compacted) if compacted > 0 => {
+                        log::info!("[event-store] compacted {compacted} superseded update(s)");
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        log::warn!("[event-store] maintenance pass failed: {e:?}");
+                    }
+                }
+                tokio::time::sleep(MAINTENANCE_INTERVAL).await;
+            }
+        }
+    });
+
+    // Daily purge of records soft-deleted longer ago than the retention window (see
+    // `domain::soft_delete::purge`). Idle when deleted records are kept forever.
+    let soft_delete_purge_task = tokio::task::spawn({
+        let db = Arc::clone(&app_state.database_connection);
+        let config = app_state.config.clone();
+        async move {
+            const PURGE_INTERVAL: tokio::time::Duration =
+                tokio::time::Duration::from_secs(24 * 60 * 60);
+            let Some(retention_days) = config.soft_delete_retention_days() else {
+                return;
+            };
+            loop {
+                match domain::soft_delete::purge(&db, &config, retention_days).await {
+                    Ok(purged) if purged > 0 => {
+                        log::info!("[soft-delete] purged {purged} deleted record(s)");
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        log::warn!("[soft-delete] purge pass failed: {e:?}");
+                    }
+                }
+                tokio::time::sleep(PURGE_INTERVAL).await;
+            }
+        }
+    });
+
+    // Flushes the API requests counted since the last pass to the shared usage rollups and
+    // reloads organizations' quotas along with what every replica has counted this hour.
+    // The interval bounds both how stale usage reports are and how far other replicas'
+    // requests can overrun a quota.
+    let api_usage_task = tokio::task::spawn({
+        let db = Arc::clone(&app_state.database_connection);
+        let api_usage = Arc::clone(&app_state.api_usage);
+        async move {
+            const FLUSH_INTERVAL: tokio::time::Duration = tokio::time::Duration::from_secs(30);
+            loop {
+                tokio::time::sleep(FLUSH_INTERVAL).await;
+                if let Err(e) = api_usage.flush(&db).await {
+                    log::warn!("[api-usage] flush failed: {e:?}");
+                }
+            }
+        }
+    });
+
+    // Delivers the events other replicas (and this one) send over the event transport to
+    // this replica's SSE connections. Returns at once when no transport is configured.
+    let event_transport_task = tokio::task::spawn({
+        let event_publisher = Arc::clone(&app_state.event_publisher);
+        async move { event_publisher.listen().await }
+    });
+
+    // Probes the database and replaces the pool requests take when it stops recovering
+    // by itself (see `service::db_health`).
+    let db_health_task = tokio::task::spawn({
+        let config = app_state.config.clone();
+        let database_slot = app_state.database_slot.clone();
+        let pool_health = Arc::clone(&app_state.pool_health);
+        async move {
+            if let Some(database_slot) = database_slot {
+                service::db_health::monitor(config, database_slot, pool_health).await;
+            }
+        }
+    });
+
+    // Reloads the allowed origins, log level and feature flags on SIGHUP (see
+    // `service::runtime_config`); POST /admin/config/reload does the same.
+    let config_reload_task = tokio::task::spawn(service::runtime_config::reload_on_hangup(
+        Arc::clone(&app_state.runtime_settings),
+    ));
+
     let session_layer = SessionManagerLayer::new(session_store)
         // Get non-secure cookies for local testing, while production automatically gets secure cookies
         .with_secure(app_state.config.is_production())
@@ -158,34 +554,35 @@ pub async fn init_server(app_state: AppState) -> Result<()> {
     let listener = TcpListener::bind(listen_addr).await.unwrap();
 
     // Handle CORS origin configuration
-    // If wildcard (*) is present, mirror request origin; otherwise use explicit list
-    let has_wildcard = app_state
-        .config
+    // Origins are checked against the runtime settings on every request, so reloading
+    // them takes effect at once. A wildcard (*) mirrors the request origin to keep
+    // credentials enabled.
+    // SECURITY: Refuse wildcard CORS in production — mirroring with credentials allows
+    // any origin to make authenticated API requests (CSRF/data-exfiltration risk)
+    if app_state
+        .runtime_settings
+        .current()
         .allowed_origins
         .iter()
-        .any(|origin| origin == "*");
-
-    // Mirror the request origin when wildcard "*" is configured to keep credentials enabled
-    // SECURITY: Refuse wildcard CORS in production — mirror_request() with credentials
-    // allows any origin to make authenticated API requests (CSRF/data-exfiltration risk)
-    let allow_origin = if has_wildcard && app_state.config.is_production() {
-        warn!(
-            "ALLOWED_ORIGINS contains '*' in production — ignoring wildcard for security. \
-             Set explicit origins instead."
-        );
-        AllowOrigin::list(Vec::<HeaderValue>::new())
-    } else if has_wildcard {
-        info!("Using mirrored CORS origin (allows all origins with credentials)");
-        AllowOrigin::mirror_request()
-    } else {
-        let allowed_origins = app_state
-            .config
-            .allowed_origins
-            .iter()
-            .filter_map(|origin| origin.parse().ok())
-            .collect::<Vec<HeaderValue>>();
-        AllowOrigin::list(allowed_origins)
-    };
+        .any(|origin| origin == "*")
+    {
+        if app_state.config.is_production() {
+            warn!(
+                "ALLOWED_ORIGINS contains '*' in production — ignoring wildcard for security. \
+                 Set explicit origins instead."
+            );
+        } else {
+            info!("Using mirrored CORS origin (allows all origins with credentials)");
+        }
+    }
+    let allow_origin = AllowOrigin::predicate({
+        let runtime_settings = Arc::clone(&app_state.runtime_settings);
+        move |origin: &HeaderValue, _| {
+            origin
+                .to_str()
+                .is_ok_and(|origin| runtime_settings.allows_origin(origin))
+        }
+    });
 
     let cors_layer = CorsLayer::new()
         .allow_methods([
@@ -197,26 +594,66 @@ pub async fn init_server(app_state: AppState) -> Result<()> {
         ])
         // Essential to allow credentials through a reverse proxy like nginx
         .allow_credentials(true)
-        // Allow and expose the X-Version header across origins
+        // Allow and expose the X-Version and X-Request-ID headers across origins
         .allow_headers([
             ApiVersion::field_name().parse::<HeaderName>().unwrap(),
             AUTHORIZATION,
             CONTENT_TYPE,
+            IF_MATCH,
             // Headers that nginx reverse proxy might forward
             "X-Forwarded-For".parse::<HeaderName>().unwrap(),
             "X-Forwarded-Proto".parse::<HeaderName>().unwrap(),
             "X-Real-IP".parse::<HeaderName>().unwrap(),
             "X-Request-ID".parse::<HeaderName>().unwrap(),
         ])
-        .expose_headers([ApiVersion::field_name().parse::<HeaderName>().unwrap()])
+        .expose_headers([
+            ApiVersion::field_name().parse::<HeaderName>().unwrap(),
+            middleware::correlation::REQUEST_ID,
+            ETAG,
+        ])
         .allow_private_network(true)
         .allow_origin(allow_origin);
 
+    // Drained on shutdown, before the server waits for open connections to finish
+    let sse_manager = Arc::clone(&app_state.sse_manager);
+    let reconnect_after =
+        std::time::Duration::from_secs(app_state.config.sse_drain_reconnect_delay_seconds);
+
     axum::serve(
         listener,
-        router::define_routes(app_state)
+        router::define_routes(app_state.clone())
+            // Inside the auth layer, so requests can be attributed to the user's organization.
+            .layer(from_fn_with_state(
+                app_state.clone(),
+                middleware::api_usage::track,
+            ))
+            // Between oauth_bearer and api_usage, so a locked user is logged out however
+            // they authenticated, and their requests aren't attributed to them.
+            .layer(from_fn_with_state(
+                app_state.clone(),
+                middleware::account_lock::log_out_locked,
+            ))
+            // Runs before api_usage, so requests made with an app's token count against its
+            // user's organization.
+            .layer(from_fn_with_state(
+                app_state.clone(),
+                middleware::oauth_bearer::authenticate,
+            ))
+            // Inside the auth layer, so a remembered session without its device's token is
+            // logged out before anything reads its user.
+            .layer(from_fn_with_state(
+                app_state,
+                middleware::remember_me::bind_device,
+            ))
             .layer(cors_layer)
             .layer(auth_layer)
+            .layer(axum::middleware::from_fn(
+                middleware::client_ip::record_client_ip,
+            ))
+            // Outermost, so everything the request does runs under its correlation ID.
+            .layer(axum::middleware::from_fn(
+                middleware::correlation::correlate,
+            ))
             // `into_make_service_with_connect_info` (not just `into_make_service`)
             // injects `ConnectInfo<SocketAddr>` into every request's extensions.
             // Required by `tower_governor`'s `SmartIpKeyExtractor`: when none of
@@ -227,13 +664,66 @@ pub async fn init_server(app_state: AppState) -> Result<()> {
             // middleware before any route handler runs. See `web::middleware::throttle`.
             .into_make_service_with_connect_info::<SocketAddr>(),
     )
+    .with_graceful_shutdown(async move {
+        shutdown_signal().await;
+        info!("Shutting down: draining SSE connections");
+        sse_manager.drain(reconnect_after).await;
+    })
     .await
     .unwrap();
 
-    let _res = deletion_task.await.unwrap();
-    // No `let _res = …` here: the sweep task's future returns `()`,
-    // so binding it would trigger clippy's `let_unit_value` lint.
-    password_reset_sweep_task.await.unwrap();
+    // The background tasks loop for as long as the process runs
+    info!("Server stopped, stopping background tasks");
+    for task in [
+        deletion_task.abort_handle(),
+        password_reset_sweep_task.abort_handle(),
+        action_roll_over_task.abort_handle(),
+        session_pulse_task.abort_handle(),
+        relationship_health_task.abort_handle(),
+        embedding_index_task.abort_handle(),
+        progress_report_task.abort_handle(),
+        scheduled_event_task.abort_handle(),
+        session_record_task.abort_handle(),
+        warehouse_backfill_task.abort_handle(),
+        event_store_task.abort_handle(),
+        soft_delete_purge_task.abort_handle(),
+        api_usage_task.abort_handle(),
+        event_transport_task.abort_handle(),
+        db_health_task.abort_handle(),
+        config_reload_task.abort_handle(),
+    ] {
+        task.abort();
+    }
 
     Ok(())
 }
+
+/// Resolves on `SIGTERM`, which the container runtime sends on deploy, or Ctrl-C.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            warn!("Failed to listen for Ctrl-C: {e:?}");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                warn!("Failed to listen for SIGTERM: {e:?}");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
@@ -0,0 +1,46 @@
+//! Accounts locked by an organization admin (see `domain::user_lock`).
+//!
+//! [`log_out_locked`] wraps every route inside the auth layer, after the request's user
+//! is known however it authenticated. A request from a locked user logs their session
+//! out and runs unauthenticated, so locking takes effect on each of their sessions'
+//! next request, and app access tokens stop working until the user is unlocked.
+
+use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use axum_login::AuthSession;
+use domain::user_lock as UserLockApi;
+use log::*;
+
+use crate::AppState;
+
+/// Logs out the request's user if their account is locked.
+pub async fn log_out_locked(
+    State(app_state): State<AppState>,
+    mut auth_session: AuthSession<domain::user::Backend>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let Some(user_id) = auth_session.user.as_ref().map(|user| user.id) else {
+        return next.run(request).await;
+    };
+
+    match UserLockApi::is_locked(app_state.db_conn_ref(), user_id).await {
+        Ok(false) => {}
+        Ok(true) => {
+            warn!("Logging out a session of locked user {user_id}");
+            if let Err(err) = auth_session.logout().await {
+                warn!("Error logging out a locked user's session: {err:?}");
+            }
+            request.extensions_mut().insert(auth_session);
+        }
+        Err(err) => {
+            warn!("Error checking whether user {user_id} is locked: {err:?}");
+            return StatusCode::SERVICE_UNAVAILABLE.into_response();
+        }
+    }
+    next.run(request).await
+}
@@ -0,0 +1,151 @@
+//! Per-organization API usage tracking and quota enforcement.
+//!
+//! [`track`] wraps every route. It attributes each authenticated request to an
+//! organization — the one in the path for `/organizations/:id/...` routes, otherwise the
+//! user's — and reports it to the application's [`ApiUsageTracker`] by endpoint (method
+//! and route, e.g. `GET /actions/:id`). Once the organization has used up its quota for
+//! the hour, requests are refused with 429 and a `Retry-After` header.
+//!
+//! Unauthenticated requests, requests of users outside any organization and requests
+//! matching no route (static files) aren't counted.
+//!
+//! [`ApiUsageTracker`]: domain::api_usage::ApiUsageTracker
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::{header::RETRY_AFTER, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use axum_login::AuthSession;
+use domain::{users, Id};
+use std::time::{Duration, Instant};
+
+use crate::AppState;
+
+/// Counts the request against its organization's usage, refusing it when over quota.
+pub async fn track(
+    State(app_state): State<AppState>,
+    auth_session: AuthSession<domain::user::Backend>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(route) = request.extensions().get::<MatchedPath>() else {
+        return next.run(request).await;
+    };
+    let endpoint = format!("{} {}", request.method(), route.as_str());
+    let Some(organization_id) = organization_id(
+        route.as_str(),
+        request.uri().path(),
+        auth_session.user.as_ref(),
+    ) else {
+        return next.run(request).await;
+    };
+
+    let tracker = &app_state.api_usage;
+    if let Err(exceeded) = tracker.check_quota(organization_id, &endpoint) {
+        tracker.record(organization_id, &endpoint, true, Duration::ZERO);
+        // Whole seconds, rounded up, so a client retrying on time isn't refused again.
+        let retry_after =
+            exceeded.retry_after.as_secs() + u64::from(exceeded.retry_after.subsec_nanos() > 0);
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(RETRY_AFTER, retry_after.to_string())],
+            format!(
+                "The organization has used its quota of {} requests per hour",
+                exceeded.limit
+            ),
+        )
+            .into_response();
+    }
+
+    let started = Instant::now();
+    let response = next.run(request).await;
+    let status = response.status();
+    tracker.record(
+        organization_id,
+        &endpoint,
+        status.is_client_error() || status.is_server_error(),
+        started.elapsed(),
+    );
+    response
+}
+
+/// The organization a request is attributed to: the `:organization_id` (or `:id`) of an
+/// `/organizations/...` route, otherwise the first organization the user has a role in.
+fn organization_id(route: &str, path: &str, user: Option<&users::Model>) -> Option<Id> {
+    let user = user?;
+    if route.starts_with("/organizations/:") {
+        if let Some(id) = path.split('/').nth(2).and_then(|id| id.parse().ok()) {
+            return Some(id);
+        }
+    }
+    user.roles.iter().find_map(|role| role.organization_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use domain::user_roles;
+
+    fn user(organization_id: Option<Id>) -> users::Model {
+        let user_id = Id::new_v4();
+        users::Model {
+            id: user_id,
+            email: "test@example.com".to_string(),
+            first_name: "Test".to_string(),
+            last_name: "User".to_string(),
+            display_name: None,
+            password: None,
+            github_username: None,
+            github_profile_url: None,
+            timezone: "UTC".to_string(),
+            default_coaching_session_duration_minutes: domain::duration::Duration::default_minutes(
+            ),
+            role: users::Role::User,
+            roles: vec![user_roles::Model {
+                id: Id::new_v4(),
+                role: users::Role::User,
+                organization_id,
+                user_id,
+                created_at: Utc::now().into(),
+                updated_at: Utc::now().into(),
+            }],
+            invite_status: None,
+            created_at: Utc::now().into(),
+            updated_at: Utc::now().into(),
+        }
+    }
+
+    #[test]
+    fn organization_routes_are_attributed_to_the_organization_in_the_path() {
+        let member_of = Id::new_v4();
+        let in_path = Id::new_v4();
+
+        assert_eq!(
+            organization_id(
+                "/organizations/:organization_id/webhooks",
+                &format!("/organizations/{in_path}/webhooks"),
+                Some(&user(Some(member_of))),
+            ),
+            Some(in_path)
+        );
+        assert_eq!(
+            organization_id("/actions/:id", "/actions/1", Some(&user(Some(member_of)))),
+            Some(member_of)
+        );
+    }
+
+    #[test]
+    fn unauthenticated_and_unaffiliated_requests_are_not_attributed() {
+        let organization = Id::new_v4();
+        let path = format!("/organizations/{organization}");
+
+        assert_eq!(organization_id("/organizations/:id", &path, None), None);
+        assert_eq!(
+            organization_id("/actions/:id", "/actions/1", Some(&user(None))),
+            None
+        );
+    }
+}
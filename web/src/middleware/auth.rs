@@ -10,15 +10,19 @@ use axum_login::AuthSession;
 ///
 /// This replaces axum-login's `login_required!` macro which redirects to login URLs.
 /// For API endpoints, we want to return proper HTTP status codes instead of redirects.
+///
+/// Authenticated requests run with the user as the actor of any domain events they emit
+/// (see `events::with_actor`), which the event audit log records.
 pub async fn require_auth(
     auth_session: AuthSession<domain::user::Backend>,
     request: Request,
     next: Next,
 ) -> Response {
     match auth_session.user {
-        Some(_user) => {
-            // User is authenticated, continue to the handler
-            next.run(request).await
+        Some(user) => {
+            // User is authenticated, continue to the handler. Events the request emits
+            // are attributed to the user.
+            domain::events::with_actor(user.id, next.run(request)).await
         }
         None => {
             // User is not authenticated or session expired
@@ -169,7 +173,8 @@ mod tests {
                 // Mock find_with_related by providing flattened JOIN rows as (user, role) tuples
                 // Each tuple represents ONE row from the SQL JOIN result
                 // SeaORM will automatically group them into Vec<(User, Vec<Role>)>
-                .append_query_results([vec![(test_user.clone(), test_role.clone())]]) // For find_with_related in authentication
+                .append_query_results([vec![(test_user.clone(), test_role.clone())]])
+                .append_query_results([Vec::<domain::policy_documents::Model>::new()]) // For find_with_related in authentication
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]]) // For get_user after login
                 .append_query_results([vec![(test_user.clone(), test_role.clone())]]) // For session user lookup
                 .into_connection(),
@@ -0,0 +1,70 @@
+//! The client address of each request.
+//!
+//! [`record_client_ip`] wraps every route and runs the request under the address it came
+//! from (see `events::with_client_ip`), which the audit trail records with each change.
+//!
+//! Precedence (matches the trust model documented in `web/src/middleware/throttle.rs`):
+//! 1. `X-Forwarded-For` (first hop) — set by our nginx front-end.
+//! 2. `X-Real-IP` — also set by nginx; used if XFF is absent.
+//! 3. TCP peer address from `ConnectInfo` — accurate only when no proxy is in front of
+//!    us (e.g. local dev).
+//!
+//! Spoofable without a trusted proxy: the address is for the record only, never for
+//! authorization or rate-limit decisions.
+
+use axum::{
+    extract::{ConnectInfo, Request},
+    http::HeaderMap,
+    middleware::Next,
+    response::Response,
+};
+use std::net::{IpAddr, SocketAddr};
+
+/// Runs the request under the address it came from.
+pub async fn record_client_ip(request: Request, next: Next) -> Response {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    let client_ip = client_ip(request.headers(), peer);
+
+    domain::events::with_client_ip(client_ip, next.run(request)).await
+}
+
+/// The client address of a request with `headers` from the TCP `peer`.
+pub(crate) fn client_ip(headers: &HeaderMap, peer: Option<IpAddr>) -> Option<String> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|s| s.split(',').next())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+
+    header("x-forwarded-for")
+        .or_else(|| header("x-real-ip"))
+        .or_else(|| peer.map(|ip| ip.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn client_ip_prefers_the_first_forwarded_hop_then_real_ip_then_the_peer() {
+        let peer = Some(IpAddr::from([10, 0, 0, 1]));
+        let mut headers = HeaderMap::new();
+        assert_eq!(client_ip(&headers, peer).as_deref(), Some("10.0.0.1"));
+
+        headers.insert("x-real-ip", HeaderValue::from_static("198.51.100.2"));
+        assert_eq!(client_ip(&headers, peer).as_deref(), Some("198.51.100.2"));
+
+        headers.insert(
+            "x-forwarded-for",
+            HeaderValue::from_static("203.0.113.7, 10.0.0.9"),
+        );
+        assert_eq!(client_ip(&headers, peer).as_deref(), Some("203.0.113.7"));
+    }
+}
@@ -0,0 +1,90 @@
+//! Request correlation IDs.
+//!
+//! [`correlate`] wraps every route. It takes the request's ID from its `X-Request-ID`
+//! header (as set by nginx's `$request_id`) or makes one up, runs the request under it
+//! (see `events::with_correlation_id`) and echoes it back in the response's
+//! `X-Request-ID`. Domain events the request emits carry it in their envelopes, so the
+//! webhooks, other replicas and SSE messages they become can be tied back to the user
+//! action that caused them.
+
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use domain::Id;
+
+/// The header a request's correlation ID is read from and returned in.
+pub const REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Runs the request under its correlation ID, returning the ID in the response.
+pub async fn correlate(request: Request, next: Next) -> Response {
+    let correlation_id = correlation_id(request.headers().get(&REQUEST_ID));
+
+    let mut response =
+        domain::events::with_correlation_id(Some(correlation_id), next.run(request)).await;
+    if let Ok(value) = HeaderValue::from_str(&correlation_id.to_string()) {
+        response.headers_mut().insert(REQUEST_ID, value);
+    }
+    response
+}
+
+/// The ID in an `X-Request-ID` header — a UUID, hyphenated or as nginx's 32 hex
+/// digits — or a new one when there's none or it isn't one.
+fn correlation_id(header: Option<&HeaderValue>) -> Id {
+    header
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or_else(Id::new_v4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware::from_fn, routing::get, Router};
+    use tower::ServiceExt;
+
+    async fn current_correlation_id() -> String {
+        format!("{:?}", domain::events::current_correlation_id())
+    }
+
+    #[test]
+    fn correlation_id_reads_uuids_in_either_form() {
+        let id = Id::new_v4();
+        let simple = HeaderValue::from_str(&id.simple().to_string()).unwrap();
+        let hyphenated = HeaderValue::from_str(&id.to_string()).unwrap();
+
+        assert_eq!(correlation_id(Some(&simple)), id);
+        assert_eq!(correlation_id(Some(&hyphenated)), id);
+        assert_ne!(
+            correlation_id(Some(&HeaderValue::from_static("not-an-id"))),
+            id
+        );
+    }
+
+    #[tokio::test]
+    async fn correlate_runs_the_request_under_its_id_and_returns_it() {
+        let app = Router::new()
+            .route("/test", get(current_correlation_id))
+            .layer(from_fn(correlate));
+        let id = Id::new_v4();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/test")
+                    .header(REQUEST_ID, id.simple().to_string())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers()[REQUEST_ID], id.to_string());
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, format!("{:?}", Some(id)));
+    }
+}
@@ -1,2 +1,8 @@
+pub mod account_lock;
+pub mod api_usage;
 pub mod auth;
+pub mod client_ip;
+pub mod correlation;
+pub mod oauth_bearer;
+pub mod remember_me;
 pub mod throttle;
@@ -0,0 +1,127 @@
+//! Access-token authentication for third-party apps.
+//!
+//! [`authenticate`] wraps every route inside the auth layer. A request carrying an
+//! `Authorization: Bearer rfp_at_...` header — an access token issued by the OAuth
+//! server (see `domain::oauth_server`) — is run as the user who authorized the app, as
+//! though they were logged in. A token granted only the `read` scope may make safe
+//! (GET, HEAD, OPTIONS) requests alone.
+//!
+//! Other bearer tokens (e.g. the metrics token) are left to the routes that expect them,
+//! and the `/oauth/` routes themselves can't be reached with an app's token, so an app
+//! can't authorize other apps.
+
+use axum::{
+    extract::{Request, State},
+    http::{header::AUTHORIZATION, header::WWW_AUTHENTICATE, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use axum_login::AuthSession;
+use domain::{oauth_server as OAuthServerApi, user as UserApi};
+use log::*;
+
+use crate::AppState;
+
+/// Authenticates the request as the user behind its app access token, if it has one.
+pub async fn authenticate(
+    State(app_state): State<AppState>,
+    mut auth_session: AuthSession<domain::user::Backend>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let Some(token) = access_token(request.headers(), request.uri().path()) else {
+        return next.run(request).await;
+    };
+
+    let db = app_state.db_conn_ref();
+    let grant = match OAuthServerApi::find_access_grant(db, token).await {
+        Ok(Some(grant)) => grant,
+        Ok(None) => return challenge(StatusCode::UNAUTHORIZED, "invalid_token"),
+        Err(err) => {
+            warn!("Error looking up an OAuth access token: {err:?}");
+            return StatusCode::SERVICE_UNAVAILABLE.into_response();
+        }
+    };
+    if !OAuthServerApi::scope_allows(&grant.scope, !request.method().is_safe()) {
+        return challenge(StatusCode::FORBIDDEN, "insufficient_scope");
+    }
+
+    let user = match UserApi::find_by_id(db, grant.user_id).await {
+        Ok(user) => user,
+        Err(err) => {
+            warn!(
+                "Error loading the user behind OAuth grant {}: {err:?}",
+                grant.id
+            );
+            return challenge(StatusCode::UNAUTHORIZED, "invalid_token");
+        }
+    };
+    debug!(
+        "Request authenticated as user {} by OAuth client {}",
+        user.id, grant.client_id
+    );
+
+    auth_session.user = Some(user);
+    request.extensions_mut().insert(auth_session);
+    next.run(request).await
+}
+
+/// The app access token a request presents, unless it's for an `/oauth/` route.
+fn access_token<'a>(headers: &'a HeaderMap, path: &str) -> Option<&'a str> {
+    if path.starts_with("/oauth/") {
+        return None;
+    }
+    headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+        .filter(|token| token.starts_with(OAuthServerApi::ACCESS_TOKEN_PREFIX))
+}
+
+/// Refuses the request, telling the app why in a `WWW-Authenticate` header (RFC 6750).
+fn challenge(status: StatusCode, error: &'static str) -> Response {
+    (
+        status,
+        [(WWW_AUTHENTICATE, format!("Bearer error=\"{error}\""))],
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn headers(authorization: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_static(authorization));
+        headers
+    }
+
+    #[test]
+    fn access_token_only_picks_up_app_tokens() {
+        assert_eq!(
+            access_token(&headers("Bearer rfp_at_abc"), "/actions"),
+            Some("rfp_at_abc")
+        );
+        assert_eq!(
+            access_token(&headers("Bearer metrics-secret"), "/metrics"),
+            None
+        );
+        assert_eq!(
+            access_token(&headers("Basic cmZwX2F0X2FiYw=="), "/actions"),
+            None
+        );
+        assert_eq!(access_token(&HeaderMap::new(), "/actions"), None);
+    }
+
+    #[test]
+    fn access_token_is_ignored_on_oauth_routes() {
+        assert_eq!(
+            access_token(&headers("Bearer rfp_at_abc"), "/oauth/authorize"),
+            None
+        );
+    }
+}
@@ -0,0 +1,174 @@
+//! "Remember me" sessions (see `domain::remembered_device`).
+//!
+//! [`bind_device`] wraps every route inside the auth layer. A session logged in with
+//! "remember me" holds the ID of its device; a request in it that doesn't present the
+//! device's token in the `device` cookie, or whose device was revoked or has expired, is
+//! logged out and runs unauthenticated. Otherwise the session is kept until the device
+//! expires rather than for the usual inactivity window. Like any other session, it must
+//! have entered its password recently before sensitive actions (see
+//! [`crate::protect::sudo`]).
+
+use axum::{
+    extract::{Request, State},
+    http::{header::COOKIE, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use axum_login::AuthSession;
+use domain::{remembered_device as RememberedDeviceApi, Id};
+use log::*;
+use time::OffsetDateTime;
+use tower_sessions::cookie::{Cookie, SameSite};
+use tower_sessions::{Expiry, Session};
+
+use crate::AppState;
+
+/// The cookie holding the browser's device token.
+pub(crate) const DEVICE_COOKIE: &str = "device";
+
+/// Session key of the remembered device the session is bound to.
+const REMEMBERED_DEVICE_KEY: &str = "remembered_device_id";
+
+/// Logs out a remembered session whose request doesn't present its device's live token,
+/// and keeps one that does until its device expires.
+pub async fn bind_device(
+    State(app_state): State<AppState>,
+    session: Session,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let device_id = match remembered_device_id(&session).await {
+        Ok(Some(device_id)) => device_id,
+        Ok(None) => return next.run(request).await,
+        Err(status) => return status.into_response(),
+    };
+
+    let device = match device_token(request.headers()) {
+        Some(token) => {
+            match RememberedDeviceApi::verify(app_state.db_conn_ref(), device_id, &token).await {
+                Ok(device) => device,
+                Err(err) => {
+                    warn!("Error verifying remembered device {device_id}: {err:?}");
+                    return StatusCode::SERVICE_UNAVAILABLE.into_response();
+                }
+            }
+        }
+        None => None,
+    };
+
+    match device {
+        Some(device) => {
+            // The session layer reloads every session with the usual inactivity expiry
+            session.set_expiry(Some(expires_at(device.expires_at.timestamp())));
+        }
+        None => {
+            warn!("Logging out a remembered session without its device's token ({device_id})");
+            if let Some(mut auth_session) = request
+                .extensions()
+                .get::<AuthSession<domain::user::Backend>>()
+                .cloned()
+            {
+                if let Err(err) = auth_session.logout().await {
+                    warn!("Error logging out a remembered session: {err:?}");
+                }
+                request.extensions_mut().insert(auth_session);
+            }
+        }
+    }
+    next.run(request).await
+}
+
+/// Binds the session to a remembered device, keeping it until the device expires.
+pub(crate) async fn bind(
+    session: &Session,
+    device: &domain::remembered_devices::Model,
+) -> Result<(), tower_sessions::session::Error> {
+    session.insert(REMEMBERED_DEVICE_KEY, device.id).await?;
+    session.set_expiry(Some(expires_at(device.expires_at.timestamp())));
+    Ok(())
+}
+
+/// The remembered device the session is bound to, if any.
+pub(crate) async fn remembered_device_id(session: &Session) -> Result<Option<Id>, StatusCode> {
+    session
+        .get::<Id>(REMEMBERED_DEVICE_KEY)
+        .await
+        .map_err(|err| {
+            warn!("Error reading the session's remembered device: {err:?}");
+            StatusCode::SERVICE_UNAVAILABLE
+        })
+}
+
+/// The cookie handing the browser its device token, kept for the device's lifetime.
+pub(crate) fn device_cookie(token: String, lifetime_seconds: u64, secure: bool) -> Cookie<'static> {
+    Cookie::build((DEVICE_COOKIE, token))
+        .path("/")
+        .http_only(true)
+        .secure(secure)
+        .same_site(SameSite::Lax)
+        .max_age(time::Duration::seconds(lifetime_seconds as i64))
+        .build()
+}
+
+/// The cookie deleting the browser's device token.
+pub(crate) fn expired_device_cookie(secure: bool) -> Cookie<'static> {
+    device_cookie(String::new(), 0, secure)
+}
+
+/// The device token in the request's cookies.
+fn device_token(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(Cookie::split_parse)
+        .filter_map(Result::ok)
+        .find(|cookie| cookie.name() == DEVICE_COOKIE && !cookie.value().is_empty())
+        .map(|cookie| cookie.value().to_string())
+}
+
+fn expires_at(unix_timestamp: i64) -> Expiry {
+    Expiry::AtDateTime(
+        OffsetDateTime::from_unix_timestamp(unix_timestamp)
+            .unwrap_or_else(|_| OffsetDateTime::now_utc()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn device_token_is_read_from_any_cookie_header() {
+        let mut headers = HeaderMap::new();
+        headers.append(COOKIE, HeaderValue::from_static("id=session-id"));
+        headers.append(
+            COOKIE,
+            HeaderValue::from_static("theme=dark; device=abc123"),
+        );
+
+        assert_eq!(device_token(&headers).as_deref(), Some("abc123"));
+    }
+
+    #[test]
+    fn an_empty_device_cookie_is_no_token() {
+        let mut headers = HeaderMap::new();
+        headers.insert(COOKIE, HeaderValue::from_static("id=session-id; device="));
+
+        assert_eq!(device_token(&headers), None);
+    }
+
+    #[test]
+    fn device_cookie_is_http_only_and_expires_with_the_device() {
+        let cookie = device_cookie("abc123".to_string(), 60, true);
+
+        assert_eq!(cookie.http_only(), Some(true));
+        assert_eq!(cookie.secure(), Some(true));
+        assert_eq!(cookie.max_age(), Some(time::Duration::seconds(60)));
+        assert_eq!(
+            expired_device_cookie(true).max_age(),
+            Some(time::Duration::ZERO)
+        );
+    }
+}
@@ -1,8 +1,10 @@
-//! Per-IP request throttling middleware.
+//! Request throttling middleware.
 //!
-//! Exposes a small trait, `Throttle`, with a single production
-//! implementation: `PerIpThrottle` backed by `tower_governor`'s in-process
-//! token bucket. The trait exists so future implementations (e.g. a
+//! Exposes a small trait, `Throttle`, with two production implementations
+//! backed by `tower_governor`'s in-process token bucket: `PerIpThrottle`
+//! for abuse-prone unauthenticated endpoints, and `PerUserThrottle`, the
+//! platform-wide limit keyed by the authenticated user (or the client IP
+//! before login). The trait exists so future implementations (e.g. a
 //! `RedisBackedThrottle` for sharing state across horizontally-scaled
 //! instances) can be swapped in without changing route definitions —
 //! call sites take `impl Throttle` and depend only on `into_layer()`.
@@ -18,10 +20,21 @@
 //! See `docs/architecture/throttling.md` for the design, trust assumption,
 //! and horizontal-scaling notes.
 
+use axum::{
+    body::Body,
+    http::{header::RETRY_AFTER, HeaderValue, Request, Response, StatusCode},
+};
+use axum_login::AuthSession;
+use domain::Id;
 use governor::middleware::NoOpMiddleware;
+use service::config::Config;
+use std::net::IpAddr;
 use std::sync::Arc;
+use std::time::Duration;
 use tower_governor::{
-    governor::GovernorConfigBuilder, key_extractor::SmartIpKeyExtractor, GovernorLayer,
+    governor::GovernorConfigBuilder,
+    key_extractor::{KeyExtractor, SmartIpKeyExtractor},
+    GovernorError, GovernorLayer,
 };
 
 /// A request throttle that can produce a tower layer to attach to a Router.
@@ -67,7 +80,9 @@ impl ThrottlePolicy {
     /// Use for any new endpoint that: (1) is unauthenticated, AND
     /// (2) either issues an email, mutates user credentials, or returns
     /// information whose value scales with the number of requests
-    /// (e.g. enumeration probes).
+    /// (e.g. enumeration probes). Authenticated endpoints that check a
+    /// password (reauthentication) use it per user instead, so a stolen
+    /// session can't guess the password from many IPs.
     pub const AUTH_ENDPOINT: Self = Self {
         period_secs: 6,
         burst: 10,
@@ -122,6 +137,7 @@ impl Throttle for PerIpThrottle {
                 .per_second(self.policy.period_secs)
                 .burst_size(self.policy.burst)
                 .key_extractor(SmartIpKeyExtractor)
+                .error_handler(too_many_requests)
                 .finish()
                 .expect("invalid throttle config (period_secs and burst must be > 0)"),
         );
@@ -129,6 +145,121 @@ impl Throttle for PerIpThrottle {
     }
 }
 
+/// Per-user throttle backed by an in-process token bucket (`tower_governor`),
+/// configured by `rate_limit_burst` and `rate_limit_replenish_interval_ms`.
+///
+/// Requests are keyed by the user they're made as — whether by session cookie
+/// or an app's access token, so the layer must sit inside the auth and
+/// `oauth_bearer` layers — and requests made as no one (e.g. `/login`) by
+/// client IP, with the same trust model as [`PerIpThrottle`]. State is
+/// per-process, as for `PerIpThrottle`.
+pub struct PerUserThrottle {
+    replenish_interval: Duration,
+    burst: u32,
+}
+
+impl PerUserThrottle {
+    /// A throttle with `policy`'s limit, whatever `config` asks for, e.g. for
+    /// authenticated endpoints that check a password.
+    pub fn new(policy: ThrottlePolicy) -> Self {
+        Self {
+            replenish_interval: Duration::from_secs(policy.period_secs),
+            burst: policy.burst,
+        }
+    }
+
+    /// The throttle `config` asks for, or `None` when rate limiting is off
+    /// (`rate_limit_burst` is 0).
+    pub fn from_config(config: &Config) -> Option<Self> {
+        (config.rate_limit_burst > 0).then(|| Self {
+            replenish_interval: Duration::from_millis(
+                config.rate_limit_replenish_interval_ms.max(1),
+            ),
+            burst: config.rate_limit_burst,
+        })
+    }
+}
+
+impl Throttle for PerUserThrottle {
+    type Layer =
+        GovernorLayer<UserOrIpKeyExtractor, NoOpMiddleware<governor::clock::QuantaInstant>>;
+
+    fn into_layer(self) -> Self::Layer {
+        let config = Arc::new(
+            GovernorConfigBuilder::default()
+                .period(self.replenish_interval)
+                .burst_size(self.burst)
+                .key_extractor(UserOrIpKeyExtractor)
+                .error_handler(too_many_requests)
+                .finish()
+                .expect("invalid throttle config (replenish interval and burst must be > 0)"),
+        );
+        GovernorLayer { config }
+    }
+}
+
+/// Whose bucket a request draws from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UserOrIpKey {
+    User(Id),
+    Ip(IpAddr),
+}
+
+/// Keys a request by its authenticated user, falling back to the client IP
+/// (via [`SmartIpKeyExtractor`]) for requests made as no one.
+#[derive(Debug, Clone, Copy)]
+pub struct UserOrIpKeyExtractor;
+
+impl KeyExtractor for UserOrIpKeyExtractor {
+    type Key = UserOrIpKey;
+
+    fn extract<T>(&self, req: &Request<T>) -> Result<Self::Key, GovernorError> {
+        let user_id = req
+            .extensions()
+            .get::<AuthSession<domain::user::Backend>>()
+            .and_then(|auth_session| auth_session.user.as_ref())
+            .map(|user| user.id);
+        match user_id {
+            Some(user_id) => Ok(UserOrIpKey::User(user_id)),
+            None => SmartIpKeyExtractor.extract(req).map(UserOrIpKey::Ip),
+        }
+    }
+}
+
+/// The 429 a throttled request gets, telling the client when to retry with a
+/// `Retry-After` header (whole seconds, rounded up). Other errors get
+/// `tower_governor`'s default response.
+fn too_many_requests(error: GovernorError) -> Response<Body> {
+    let retry_after = match &error {
+        GovernorError::TooManyRequests { wait_time, .. } => wait_time + 1,
+        _ => return default_response(error),
+    };
+    let mut response = Response::new(Body::from("Too Many Requests"));
+    *response.status_mut() = StatusCode::TOO_MANY_REQUESTS;
+    response
+        .headers_mut()
+        .insert(RETRY_AFTER, HeaderValue::from(retry_after));
+    response
+}
+
+fn default_response(error: GovernorError) -> Response<Body> {
+    let (status, message) = match error {
+        GovernorError::Other {
+            code,
+            msg: Some(msg),
+            ..
+        } => (code, msg),
+        GovernorError::Other { code, .. } => (code, "Other Error!".to_string()),
+        _ => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Unable To Extract Key!".to_string(),
+        ),
+    };
+    let mut response = Response::new(Body::from(message));
+    *response.status_mut() = status;
+    response
+}
+
 #[cfg(test)]
 mod tests {
     //! Mocked-load tests for the per-IP throttle.
@@ -278,6 +409,71 @@ mod tests {
         );
     }
 
+    fn per_user_app(burst: u32) -> Router {
+        let config = Config::from_args([
+            "test".to_string(),
+            format!("--rate-limit-burst={burst}"),
+            "--rate-limit-replenish-interval-ms=60000".to_string(),
+        ]);
+        let throttle = PerUserThrottle::from_config(&config).expect("rate limiting is on");
+        Router::new()
+            .route("/test", get(ok_handler))
+            .layer(throttle.into_layer())
+    }
+
+    /// Without an authenticated user the per-user throttle falls back to the
+    /// client IP, and a throttled request is told when to retry.
+    #[tokio::test]
+    async fn per_user_throttle_falls_back_to_ip_and_sends_retry_after() {
+        let app = per_user_app(2);
+
+        for i in 0..2 {
+            let res = app
+                .clone()
+                .oneshot(req_from_ip("203.0.113.30"))
+                .await
+                .unwrap();
+            assert_eq!(res.status(), StatusCode::OK, "request {i} should be 200");
+        }
+
+        let res = app
+            .clone()
+            .oneshot(req_from_ip("203.0.113.30"))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+        let retry_after: u64 = res
+            .headers()
+            .get(RETRY_AFTER)
+            .expect("a throttled request gets Retry-After")
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(
+            (1..=60).contains(&retry_after),
+            "Retry-After should be the wait rounded up to whole seconds, got {retry_after}"
+        );
+
+        let res = app
+            .clone()
+            .oneshot(req_from_ip("203.0.113.40"))
+            .await
+            .unwrap();
+        assert_eq!(
+            res.status(),
+            StatusCode::OK,
+            "another IP has its own bucket"
+        );
+    }
+
+    #[test]
+    fn per_user_throttle_is_off_with_a_zero_burst() {
+        let config = Config::from_args(["test", "--rate-limit-burst=0"]);
+        assert!(PerUserThrottle::from_config(&config).is_none());
+        assert!(PerUserThrottle::from_config(&Config::from_args(["test"])).is_some());
+    }
+
     // Sanity check on AUTH_ENDPOINT policy bounds is now a compile-time
     // const assertion above (see `_AUTH_ENDPOINT_BOUNDS_CHECK`). Stronger
     // than a runtime test — the build fails if someone loosens the policy.
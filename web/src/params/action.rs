@@ -1,10 +1,12 @@
-use sea_orm::{Order, Value};
+use sea_orm::{ActiveEnum, Order, Value};
 use serde::Deserialize;
 use utoipa::{IntoParams, ToSchema};
 
-use super::sort::SortOrder;
+use super::filter;
+use super::include;
+use super::sort::{Sort, SortOrder};
 use super::WithSortDefaults;
-use domain::{actions, Id, IntoQueryFilterMap, QueryFilterMap, QuerySort};
+use domain::{actions, status::Status, Id, IntoQueryFilterMap, QueryFilterMap, QuerySort};
 
 /// Sortable fields for actions
 #[derive(Debug, Deserialize, ToSchema)]
@@ -18,12 +20,37 @@ pub(crate) enum SortField {
     UpdatedAt,
 }
 
+/// Related resources that can be batch-loaded with actions via `?include=session,assignee`.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum IncludeParam {
+    /// The coaching session the action belongs to
+    Session,
+    /// The coaching relationship of the action's session
+    Relationship,
+    /// The users the action is assigned to
+    Assignee,
+}
+
+/// GET /actions?filter[coaching_session_id]=..&filter[status]=in_progress&sort=-due_by
 #[derive(Debug, Deserialize, IntoParams)]
+#[serde(deny_unknown_fields)]
 pub(crate) struct IndexParams {
+    #[serde(alias = "filter[coaching_session_id]")]
     pub(crate) coaching_session_id: Id,
+    #[serde(alias = "filter[goal_id]")]
     pub(crate) goal_id: Option<Id>,
+    #[serde(
+        default,
+        alias = "filter[status]",
+        deserialize_with = "filter::active_enum"
+    )]
+    pub(crate) status: Option<Status>,
+    pub(crate) sort: Option<Sort<SortField>>,
     pub(crate) sort_by: Option<SortField>,
     pub(crate) sort_order: Option<SortOrder>,
+    #[serde(default, deserialize_with = "include::comma_separated")]
+    pub(crate) include: Vec<IncludeParam>,
 }
 
 impl IntoQueryFilterMap for IndexParams {
@@ -37,6 +64,11 @@ impl IntoQueryFilterMap for IndexParams {
             "goal_id".to_string(),
             self.goal_id.map(|id| Value::Uuid(Some(Box::new(id)))),
         );
+        query_filter_map.insert(
+            "status".to_string(),
+            self.status
+                .map(|status| Value::String(Some(Box::new(status.to_value())))),
+        );
 
         query_filter_map
     }
@@ -62,3 +94,83 @@ impl QuerySort<actions::Column> for IndexParams {
 impl WithSortDefaults for IndexParams {
     type SortField = SortField;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::Query;
+    use axum::http::Uri;
+
+    fn parse(query: &str) -> Result<IndexParams, String> {
+        let uri: Uri = format!("/actions?{query}").parse().unwrap();
+        Query::<IndexParams>::try_from_uri(&uri)
+            .map(|Query(params)| params)
+            .map_err(|e| e.body_text())
+    }
+
+    #[test]
+    fn filters_and_sort_parse_from_the_query_language() {
+        let id = Id::new_v4();
+        let mut params = parse(&format!(
+            "filter[coaching_session_id]={id}&filter[status]=in_progress&sort=-due_by"
+        ))
+        .unwrap();
+        IndexParams::apply_sort(
+            params.sort.take(),
+            &mut params.sort_by,
+            &mut params.sort_order,
+            SortField::CreatedAt,
+        );
+
+        assert!(matches!(
+            params.get_sort_column(),
+            Some(actions::Column::DueBy)
+        ));
+        assert_eq!(params.get_sort_order(), Some(Order::Desc));
+        let filters = params.into_query_filter_map();
+        assert_eq!(filters.get("coaching_session_id"), Some(id.into()));
+        assert_eq!(filters.get("status"), Some("in_progress".into()));
+        assert_eq!(filters.get("goal_id"), None);
+    }
+
+    #[test]
+    fn the_original_parameters_still_parse() {
+        let id = Id::new_v4();
+        let params = parse(&format!(
+            "coaching_session_id={id}&sort_by=created_at&sort_order=asc"
+        ))
+        .unwrap();
+
+        assert!(matches!(
+            params.get_sort_column(),
+            Some(actions::Column::CreatedAt)
+        ));
+        assert_eq!(params.get_sort_order(), Some(Order::Asc));
+    }
+
+    #[test]
+    fn includes_parse_from_a_comma_separated_list() {
+        let id = Id::new_v4();
+        let params = parse(&format!(
+            "coaching_session_id={id}&include=session,%20assignee"
+        ))
+        .unwrap();
+
+        assert_eq!(
+            params.include,
+            vec![IncludeParam::Session, IncludeParam::Assignee]
+        );
+        assert!(parse(&format!("coaching_session_id={id}&include=organization")).is_err());
+    }
+
+    #[test]
+    fn unknown_filters_and_sort_fields_are_refused() {
+        let id = Id::new_v4();
+
+        let error = parse(&format!("coaching_session_id={id}&filter[owner]=me")).unwrap_err();
+        assert!(error.contains("unknown field `filter[owner]`"), "{error}");
+        let error = parse(&format!("coaching_session_id={id}&sort=-body")).unwrap_err();
+        assert!(error.contains("unknown variant `body`"), "{error}");
+        assert!(parse(&format!("coaching_session_id={id}&filter[status]=done")).is_err());
+    }
+}
@@ -0,0 +1,32 @@
+use chrono::{DateTime, FixedOffset};
+use domain::Id;
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+/// Body for POST `/action_work_logs/start`: the action to start a focus timer on.
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct StartParams {
+    pub(crate) action_id: Id,
+    pub(crate) note: Option<String>,
+}
+
+/// Body for POST `/action_work_logs/:id/stop`: an optional note replacing the one given at start.
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct StopParams {
+    pub(crate) note: Option<String>,
+}
+
+/// Body for POST `/action_work_logs`: a finished span of work logged after the fact.
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct CreateParams {
+    pub(crate) action_id: Id,
+    #[schema(value_type = String, format = DateTime)]
+    pub(crate) started_at: DateTime<FixedOffset>,
+    pub(crate) duration_seconds: i32,
+    pub(crate) note: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct IndexParams {
+    pub(crate) action_id: Id,
+}
@@ -0,0 +1,50 @@
+use chrono::{DateTime, Utc};
+use domain::Id;
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct UserIndexParams {
+    /// Only return users whose email, first, last or display name contains this
+    /// (case-insensitively)
+    pub(crate) q: Option<String>,
+    /// Only return members of this organization
+    pub(crate) organization_id: Option<Id>,
+    /// Most users to return, ordered by name (default 50, at most 200)
+    pub(crate) limit: Option<u64>,
+    /// Users to skip, for the following pages
+    pub(crate) offset: Option<u64>,
+}
+
+/// Body of `POST /admin/users/:user_id/lock`
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LockParams {
+    /// The user's organization the lock is made in the name of: its admins are emailed
+    /// and may lift it
+    pub organization_id: Id,
+    /// Why the user is locked, shown to the organization's admins and kept in the
+    /// audit log (at most 500 characters)
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Body of `PUT /admin/coaching_relationships/:id/participants`
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReassignParams {
+    /// The new coach, if the coach changes
+    pub coach_id: Option<Id>,
+    /// The new coachee, if the coachee changes
+    pub coachee_id: Option<Id>,
+}
+
+/// Body of `POST /admin/warehouse_export/backfill`
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct BackfillParams {
+    /// Backfill the events stored from this time on; from the oldest when omitted
+    #[serde(default)]
+    pub from: Option<DateTime<Utc>>,
+    /// Backfill the events stored before this time; until the live export started (or
+    /// now) when omitted
+    #[serde(default)]
+    pub to: Option<DateTime<Utc>>,
+}
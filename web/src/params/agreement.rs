@@ -2,7 +2,7 @@ use sea_orm::{Order, Value};
 use serde::Deserialize;
 use utoipa::{IntoParams, ToSchema};
 
-use super::sort::SortOrder;
+use super::sort::{Sort, SortOrder};
 use super::WithSortDefaults;
 use domain::{agreements, Id, IntoQueryFilterMap, QueryFilterMap, QuerySort};
 
@@ -18,9 +18,13 @@ pub(crate) enum SortField {
     UpdatedAt,
 }
 
+/// GET /agreements?filter[coaching_session_id]=..&sort=-created_at
 #[derive(Debug, Deserialize, IntoParams)]
+#[serde(deny_unknown_fields)]
 pub(crate) struct IndexParams {
+    #[serde(alias = "filter[coaching_session_id]")]
     pub(crate) coaching_session_id: Id,
+    pub(crate) sort: Option<Sort<SortField>>,
     pub(crate) sort_by: Option<SortField>,
     pub(crate) sort_order: Option<SortOrder>,
 }
@@ -0,0 +1,13 @@
+use chrono::{DateTime, FixedOffset};
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct ReadParams {
+    /// Only count hours starting at or after this time (default 7 days before `to`)
+    #[param(value_type = Option<String>, format = DateTime)]
+    pub(crate) from: Option<DateTime<FixedOffset>>,
+    /// Only count hours starting before this time (default now)
+    #[param(value_type = Option<String>, format = DateTime)]
+    pub(crate) to: Option<DateTime<FixedOffset>>,
+}
@@ -0,0 +1,26 @@
+use chrono::{DateTime, FixedOffset};
+use domain::Id;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct IndexParams {
+    /// Only return entries about this entity type (e.g. `goal`, `coaching_session`)
+    pub(crate) entity_type: Option<String>,
+    /// Only return entries about this entity
+    pub(crate) entity_id: Option<Id>,
+    /// Only return changes made by this user
+    pub(crate) actor_user_id: Option<Id>,
+    /// Only return `created`, `updated`, `deleted` or `restored` entries
+    pub(crate) action: Option<String>,
+    /// Only return entries created at or after this time
+    #[param(value_type = Option<String>, format = DateTime)]
+    pub(crate) from: Option<DateTime<FixedOffset>>,
+    /// Only return entries created before this time
+    #[param(value_type = Option<String>, format = DateTime)]
+    pub(crate) to: Option<DateTime<FixedOffset>>,
+    /// `next_cursor` of the previous page, to fetch the page after it
+    pub(crate) cursor: Option<String>,
+    /// Most entries to return, newest first (default 100, at most 1000)
+    pub(crate) limit: Option<u64>,
+}
@@ -29,6 +29,9 @@ pub(crate) struct IndexParams {
     pub(crate) coaching_relationship_id: Id,
     pub(crate) from_date: NaiveDate,
     pub(crate) to_date: NaiveDate,
+    /// Only sessions of this session type
+    #[serde(alias = "filter[session_type_id]")]
+    pub(crate) session_type_id: Option<Id>,
     pub(crate) sort_by: Option<SortField>,
     pub(crate) sort_order: Option<SortOrder>,
 }
@@ -48,6 +51,11 @@ impl IntoQueryFilterMap for IndexParams {
             "to_date".to_string(),
             Some(Value::ChronoDate(Some(Box::new(self.to_date)))),
         );
+        query_filter_map.insert(
+            "session_type_id".to_string(),
+            self.session_type_id
+                .map(|id| Value::Uuid(Some(Box::new(id)))),
+        );
         query_filter_map
     }
 }
@@ -66,15 +74,21 @@ pub(crate) struct UpdateParams {
     #[serde(default, deserialize_with = "deserialize_clearable")]
     #[schema(value_type = Option<String>)]
     pub(crate) title: Option<Option<String>>,
+    /// Absent -> leave unchanged. Explicit null -> make the session untyped. Value -> one of
+    /// the organization's session types.
+    #[serde(default, deserialize_with = "deserialize_clearable")]
+    #[schema(value_type = Option<Id>)]
+    pub(crate) session_type_id: Option<Option<Id>>,
 }
 
 /// Distinguish an omitted field (`None`) from an explicit JSON null
 /// (`Some(None)`) so updates can clear the column to NULL.
-fn deserialize_clearable<'de, D>(deserializer: D) -> Result<Option<Option<String>>, D::Error>
+fn deserialize_clearable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
 where
     D: Deserializer<'de>,
+    T: Deserialize<'de>,
 {
-    Ok(Some(Option::<String>::deserialize(deserializer)?))
+    Ok(Some(Option::<T>::deserialize(deserializer)?))
 }
 
 /// Insert the clearable `title` entry when present: a value sets it, an explicit
@@ -114,6 +128,12 @@ impl IntoUpdateMap for UpdateParams {
             );
         }
         insert_title_update(&mut update_map, self.title);
+        if let Some(session_type_id) = self.session_type_id {
+            update_map.insert(
+                "session_type_id".to_string(),
+                Some(Value::Uuid(session_type_id.map(Box::new))),
+            );
+        }
         update_map
     }
 }
@@ -151,6 +171,9 @@ pub(crate) struct CreateParams {
     pub(crate) provider: Option<Provider>,
     /// Optional human-authored title. Omit or null to leave unset.
     pub(crate) title: Option<String>,
+    /// Optional session type of the relationship's organization. Without a
+    /// `duration_minutes`, the session takes the type's default duration.
+    pub(crate) session_type_id: Option<Id>,
 }
 
 impl CreateParams {
@@ -175,6 +198,8 @@ impl CreateParams {
             created_at: now.into(),
             updated_at: now.into(),
             hydrated_at: None,
+            session_type_id: self.session_type_id,
+            deleted_at: None,
         }
     }
 }
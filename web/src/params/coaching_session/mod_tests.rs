@@ -35,6 +35,7 @@ fn base_update_params() -> UpdateParams {
         meeting_url: None,
         provider: None,
         title: None,
+        session_type_id: None,
     }
 }
 
@@ -81,6 +82,7 @@ fn base_create_params() -> CreateParams {
         meeting_url: None,
         provider: None,
         title: None,
+        session_type_id: None,
     }
 }
 
@@ -103,3 +105,41 @@ fn into_model_leaves_title_none_when_absent() {
     .into_model();
     assert_eq!(model.title, None);
 }
+
+// session_type_id follows the same absent / null / value distinction as the title.
+
+#[test]
+fn into_update_map_sets_or_clears_the_session_type() {
+    let session_type_id = Id::new_v4();
+    let params: UpdateParams = serde_json::from_str(&format!(
+        r#"{{"date":"2026-06-07T10:00:00","session_type_id":"{session_type_id}"}}"#
+    ))
+    .unwrap();
+    assert!(matches!(
+        params.into_update_map().get_value("session_type_id"),
+        Some(Value::Uuid(Some(id))) if **id == session_type_id
+    ));
+
+    let params: UpdateParams =
+        serde_json::from_str(r#"{"date":"2026-06-07T10:00:00","session_type_id":null}"#).unwrap();
+    assert!(matches!(
+        params.into_update_map().get_value("session_type_id"),
+        Some(Value::Uuid(None))
+    ));
+
+    assert!(base_update_params()
+        .into_update_map()
+        .get_value("session_type_id")
+        .is_none());
+}
+
+#[test]
+fn into_model_carries_the_session_type() {
+    let session_type_id = Id::new_v4();
+    let model = CreateParams {
+        session_type_id: Some(session_type_id),
+        ..base_create_params()
+    }
+    .into_model();
+    assert_eq!(model.session_type_id, Some(session_type_id));
+}
@@ -0,0 +1,15 @@
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct IndexParams {
+    /// Only return dead letters of this event handler
+    pub(crate) handler_name: Option<String>,
+    /// Only return dead letters of this event type, e.g. `goal_created`
+    pub(crate) event_type: Option<String>,
+    /// Also return dead letters that have been requeued successfully
+    #[serde(default)]
+    pub(crate) include_requeued: bool,
+    /// Most dead letters to return, newest first (default 100, at most 1000)
+    pub(crate) limit: Option<u64>,
+}
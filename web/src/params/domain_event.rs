@@ -0,0 +1,27 @@
+use chrono::{DateTime, FixedOffset};
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct IndexParams {
+    /// Only return events about this entity type (e.g. `goal`, `action`)
+    pub(crate) entity_type: Option<String>,
+    /// Only return events created at or after this time
+    #[param(value_type = Option<String>, format = DateTime)]
+    pub(crate) from: Option<DateTime<FixedOffset>>,
+    /// Only return events created before this time
+    #[param(value_type = Option<String>, format = DateTime)]
+    pub(crate) to: Option<DateTime<FixedOffset>>,
+    /// `next_cursor` of the previous page, to fetch the page after it
+    pub(crate) cursor: Option<String>,
+    /// Most events to return, newest first (default 100, at most 1000)
+    pub(crate) limit: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct ActivityParams {
+    /// `next_cursor` of the previous page, to fetch the page after it
+    pub(crate) cursor: Option<String>,
+    /// Most events to return, newest first (default 100, at most 1000)
+    pub(crate) limit: Option<u64>,
+}
@@ -0,0 +1,52 @@
+//! Filters of the index endpoints, given as `filter[<field>]=<value>` query parameters
+//! (e.g. `filter[status]=in_progress`). Each filter field of an endpoint's params takes
+//! its `filter[...]` name as a serde alias, next to the bare name it has always accepted.
+
+use sea_orm::ActiveEnum;
+use serde::de::{DeserializeOwned, Error as _, IntoDeserializer};
+use serde::{Deserialize, Deserializer};
+
+/// Deserializes an enum filter from its database value (e.g. `in_progress`), or from the
+/// variant name (e.g. `InProgress`) earlier clients send.
+pub(crate) fn active_enum<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: ActiveEnum<Value = String> + DeserializeOwned,
+{
+    let value = String::deserialize(deserializer)?;
+    if let Ok(variant) = T::try_from_value(&value) {
+        return Ok(Some(variant));
+    }
+    T::deserialize(value.into_deserializer())
+        .map(Some)
+        .map_err(|e: serde::de::value::Error| D::Error::custom(e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::status::Status;
+
+    #[derive(Debug, Deserialize)]
+    struct Params {
+        #[serde(default, alias = "filter[status]", deserialize_with = "active_enum")]
+        status: Option<Status>,
+    }
+
+    fn parse(query: &str) -> Result<Params, serde::de::value::Error> {
+        serde::de::Deserialize::deserialize(serde::de::value::MapDeserializer::new(
+            query.split('&').filter_map(|pair| pair.split_once('=')),
+        ))
+    }
+
+    #[test]
+    fn enum_filters_take_the_database_value_or_the_variant_name() {
+        assert_eq!(
+            parse("filter[status]=in_progress").unwrap().status,
+            Some(Status::InProgress)
+        );
+        assert_eq!(parse("status=OnHold").unwrap().status, Some(Status::OnHold));
+        assert_eq!(parse("other=1").unwrap().status, None);
+        assert!(parse("filter[status]=started").is_err());
+    }
+}
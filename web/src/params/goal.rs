@@ -2,7 +2,9 @@ use sea_orm::{ActiveEnum, Order, Value};
 use serde::Deserialize;
 use utoipa::{IntoParams, ToSchema};
 
-use super::sort::SortOrder;
+use super::filter;
+use super::include;
+use super::sort::{Sort, SortOrder};
 use super::WithSortDefaults;
 use domain::{goals, status::Status, Id, IntoQueryFilterMap, QueryFilterMap, QuerySort};
 
@@ -18,12 +20,33 @@ pub(crate) enum SortField {
     UpdatedAt,
 }
 
+/// Related resources that can be batch-loaded with goals via `?include=relationship`.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum IncludeParam {
+    /// The coaching session the goal was created in
+    Session,
+    /// The coaching relationship the goal belongs to
+    Relationship,
+}
+
+/// GET /goals?filter[coaching_relationship_id]=..&filter[status]=in_progress&sort=title
 #[derive(Debug, Deserialize, IntoParams)]
+#[serde(deny_unknown_fields)]
 pub(crate) struct IndexParams {
+    #[serde(alias = "filter[coaching_relationship_id]")]
     pub(crate) coaching_relationship_id: Id,
+    #[serde(
+        default,
+        alias = "filter[status]",
+        deserialize_with = "filter::active_enum"
+    )]
     pub(crate) status: Option<Status>,
+    pub(crate) sort: Option<Sort<SortField>>,
     pub(crate) sort_by: Option<SortField>,
     pub(crate) sort_order: Option<SortOrder>,
+    #[serde(default, deserialize_with = "include::comma_separated")]
+    pub(crate) include: Vec<IncludeParam>,
 }
 
 impl IntoQueryFilterMap for IndexParams {
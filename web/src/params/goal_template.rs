@@ -0,0 +1,10 @@
+use domain::Id;
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+/// Body for POST `/coaching_relationships/:id/overarching_goals/from_template/:template_id`.
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct InstantiateParams {
+    /// Session the milestone actions are created on; required when the template has milestones.
+    pub(crate) coaching_session_id: Option<Id>,
+}
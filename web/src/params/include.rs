@@ -0,0 +1,28 @@
+//! The `?include=` parameter of endpoints that can batch-load related resources with the
+//! records they list, given as a comma-separated list (e.g. `?include=session,assignee`).
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
+
+/// Deserializes a comma-separated `include` list into its variants; blank entries are
+/// ignored and an unknown one is refused.
+pub(crate) fn comma_separated<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let s: Option<String> = Option::deserialize(deserializer)?;
+    match s {
+        None => Ok(Vec::new()),
+        Some(s) if s.is_empty() => Ok(Vec::new()),
+        Some(s) => s
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| {
+                serde_json::from_value(serde_json::Value::String(s.to_string()))
+                    .map_err(serde::de::Error::custom)
+            })
+            .collect(),
+    }
+}
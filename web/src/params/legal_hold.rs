@@ -0,0 +1,9 @@
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct IndexParams {
+    /// Only return holds that haven't been released
+    #[serde(default)]
+    pub(crate) active: bool,
+}
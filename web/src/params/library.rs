@@ -0,0 +1,18 @@
+use chrono::{DateTime, FixedOffset};
+use domain::Id;
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct IndexParams {
+    /// Only return items carrying this tag
+    pub(crate) tag: Option<String>,
+}
+
+/// Body for POST `/coaching_relationships/:id/assignments`: the item to assign to the coachee.
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct AssignParams {
+    pub(crate) library_item_id: Id,
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub(crate) due_by: Option<DateTime<FixedOffset>>,
+}
@@ -12,17 +12,38 @@
 //! ```
 
 pub(crate) mod action;
+pub(crate) mod action_work_log;
+pub(crate) mod admin;
 pub(crate) mod agreement;
+pub(crate) mod api_usage;
+pub(crate) mod audit;
 pub(crate) mod coaching_relationship;
 pub(crate) mod coaching_session;
 pub(crate) mod coaching_session_series;
+pub(crate) mod dead_letter;
+pub(crate) mod domain_event;
+pub(crate) mod filter;
 pub(crate) mod goal;
+pub(crate) mod goal_template;
+pub(crate) mod include;
 pub(crate) mod jwt;
+pub(crate) mod legal_hold;
+pub(crate) mod library;
+pub(crate) mod note;
+pub(crate) mod oauth;
+pub(crate) mod push;
+pub(crate) mod reaction;
+pub(crate) mod resource_view;
+pub(crate) mod scim;
+pub(crate) mod search;
+pub(crate) mod session_request;
+pub(crate) mod session_type;
 pub(crate) mod sort;
+pub(crate) mod sse;
 pub(crate) mod user;
 pub(crate) mod validation;
 
-use self::sort::SortOrder;
+use self::sort::{Sort, SortOrder};
 
 /// A trait for applying default sorting parameters when at least one sort parameter is provided.
 ///
@@ -46,4 +67,19 @@ pub(crate) trait WithSortDefaults {
             sort_order.get_or_insert(SortOrder::Asc);
         }
     }
+
+    /// Applies a `sort=[-]field` parameter, which takes precedence over `sort_by` and
+    /// `sort_order`, then the defaults of [`Self::apply_sort_defaults`].
+    fn apply_sort(
+        sort: Option<Sort<Self::SortField>>,
+        sort_by: &mut Option<Self::SortField>,
+        sort_order: &mut Option<SortOrder>,
+        default_field: Self::SortField,
+    ) {
+        if let Some(sort) = sort {
+            *sort_by = Some(sort.field);
+            *sort_order = Some(sort.order);
+        }
+        Self::apply_sort_defaults(sort_by, sort_order, default_field);
+    }
 }
@@ -0,0 +1,61 @@
+use sea_orm::{Order, Value};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use super::sort::{Sort, SortOrder};
+use super::WithSortDefaults;
+use domain::{notes, Id, IntoQueryFilterMap, QueryFilterMap, QuerySort};
+
+/// Sortable fields for notes
+#[derive(Debug, Deserialize, ToSchema)]
+#[schema(example = "created_at")]
+pub(crate) enum SortField {
+    #[serde(rename = "created_at")]
+    CreatedAt,
+    #[serde(rename = "updated_at")]
+    UpdatedAt,
+}
+
+/// GET /notes?filter[coaching_session_id]=..&sort=-updated_at
+#[derive(Debug, Deserialize, IntoParams)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct IndexParams {
+    #[serde(alias = "filter[coaching_session_id]")]
+    pub(crate) coaching_session_id: Option<Id>,
+    pub(crate) sort: Option<Sort<SortField>>,
+    pub(crate) sort_by: Option<SortField>,
+    pub(crate) sort_order: Option<SortOrder>,
+}
+
+impl IntoQueryFilterMap for IndexParams {
+    fn into_query_filter_map(self) -> QueryFilterMap {
+        let mut query_filter_map = QueryFilterMap::new();
+        query_filter_map.insert(
+            "coaching_session_id".to_string(),
+            self.coaching_session_id
+                .map(|id| Value::Uuid(Some(Box::new(id)))),
+        );
+
+        query_filter_map
+    }
+}
+
+impl QuerySort<notes::Column> for IndexParams {
+    fn get_sort_column(&self) -> Option<notes::Column> {
+        self.sort_by.as_ref().map(|field| match field {
+            SortField::CreatedAt => notes::Column::CreatedAt,
+            SortField::UpdatedAt => notes::Column::UpdatedAt,
+        })
+    }
+
+    fn get_sort_order(&self) -> Option<Order> {
+        self.sort_order.as_ref().map(|order| match order {
+            SortOrder::Asc => Order::Asc,
+            SortOrder::Desc => Order::Desc,
+        })
+    }
+}
+
+impl WithSortDefaults for IndexParams {
+    type SortField = SortField;
+}
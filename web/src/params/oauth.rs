@@ -0,0 +1,67 @@
+use domain::Id;
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+/// A third-party app to register for an organization.
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct CreateClientParams {
+    /// Shown to users when they're asked to authorize the app
+    pub(crate) name: String,
+    /// URIs authorization codes may be sent to: https://, or http:// to a loopback
+    /// address for native apps
+    pub(crate) redirect_uris: Vec<String>,
+    /// Whether the app can keep a client secret, e.g. runs on a server (default true).
+    /// Public apps authenticate with PKCE alone.
+    #[serde(default = "confidential_by_default")]
+    pub(crate) confidential: bool,
+}
+
+fn confidential_by_default() -> bool {
+    true
+}
+
+/// An OAuth 2.0 authorization request (RFC 6749 §4.1.1 with RFC 7636 PKCE), as the app
+/// sent it to the consent page.
+#[derive(Debug, Deserialize, IntoParams, ToSchema)]
+pub(crate) struct AuthorizeParams {
+    /// Must be `code`
+    pub(crate) response_type: String,
+    pub(crate) client_id: Id,
+    /// One of the client's registered redirect URIs
+    pub(crate) redirect_uri: String,
+    /// Space-separated scopes: `read`, `write` (default `read`)
+    pub(crate) scope: Option<String>,
+    /// Returned to the app unchanged with the code
+    pub(crate) state: Option<String>,
+    /// Base64url SHA-256 digest of the app's code verifier
+    pub(crate) code_challenge: String,
+    /// Must be `S256`
+    pub(crate) code_challenge_method: Option<String>,
+}
+
+/// A token request (RFC 6749 §4.1.3 and §6), form-encoded. The client authenticates with
+/// HTTP Basic or `client_id` and `client_secret`.
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct TokenParams {
+    /// `authorization_code` or `refresh_token`
+    pub(crate) grant_type: String,
+    pub(crate) code: Option<String>,
+    pub(crate) redirect_uri: Option<String>,
+    pub(crate) code_verifier: Option<String>,
+    pub(crate) refresh_token: Option<String>,
+    pub(crate) client_id: Option<Id>,
+    pub(crate) client_secret: Option<String>,
+}
+
+/// An introspection (RFC 7662) or revocation (RFC 7009) request, form-encoded. The
+/// client authenticates as for [`TokenParams`].
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct TokenOperationParams {
+    /// An access or refresh token
+    pub(crate) token: String,
+    /// Ignored: tokens are looked up by value
+    #[allow(dead_code)]
+    pub(crate) token_type_hint: Option<String>,
+    pub(crate) client_id: Option<Id>,
+    pub(crate) client_secret: Option<String>,
+}
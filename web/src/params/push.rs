@@ -0,0 +1,19 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+/// Body for POST `/users/{user_id}/push_subscriptions`: a browser's `PushSubscription`,
+/// as its `toJSON()` returns it. `expirationTime` is ignored.
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct CreateParams {
+    /// Push service URL notifications are sent to
+    pub(crate) endpoint: String,
+    pub(crate) keys: KeysParams,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct KeysParams {
+    /// The browser's P-256 public key (base64url)
+    pub(crate) p256dh: String,
+    /// The browser's authentication secret (base64url)
+    pub(crate) auth: String,
+}
@@ -0,0 +1,12 @@
+use domain::resource_view::ResourceType;
+use domain::Id;
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+/// Body for POST `/reactions`: the note or action being reacted to and the emoji.
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct CreateParams {
+    pub(crate) resource_type: ResourceType,
+    pub(crate) resource_id: Id,
+    pub(crate) emoji: String,
+}
@@ -0,0 +1,11 @@
+use domain::resource_view::ResourceType;
+use domain::Id;
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+/// Body for POST `/views`: which resource the caller has just looked at.
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct CreateParams {
+    pub(crate) resource_type: ResourceType,
+    pub(crate) resource_id: Id,
+}
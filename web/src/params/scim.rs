@@ -0,0 +1,79 @@
+//! SCIM 2.0 request bodies and query parameters (RFC 7644), as identity providers send
+//! them. Attributes the platform doesn't store are ignored.
+
+use domain::scim::{PatchOperation, UserAttributes};
+use domain::Id;
+use serde::Deserialize;
+
+/// `GET /scim/v2/Users` and `GET /scim/v2/Groups` query parameters.
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct ListParams {
+    /// A filter such as `userName eq "ada@example.com"`
+    pub(crate) filter: Option<String>,
+    /// 1-based index of the first result to return
+    #[serde(rename = "startIndex")]
+    pub(crate) start_index: Option<usize>,
+    /// Most results to return
+    pub(crate) count: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct NameParams {
+    #[serde(default)]
+    pub(crate) given_name: String,
+    #[serde(default)]
+    pub(crate) family_name: String,
+}
+
+/// A User resource, as sent to create or replace one.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UserParams {
+    /// The user's email address
+    pub(crate) user_name: String,
+    #[serde(default)]
+    pub(crate) name: NameParams,
+    pub(crate) display_name: Option<String>,
+    pub(crate) external_id: Option<String>,
+    #[serde(default = "active_by_default")]
+    pub(crate) active: bool,
+}
+
+impl From<UserParams> for UserAttributes {
+    fn from(params: UserParams) -> Self {
+        UserAttributes {
+            user_name: params.user_name.trim().to_string(),
+            given_name: params.name.given_name,
+            family_name: params.name.family_name,
+            display_name: params.display_name,
+            external_id: params.external_id,
+            active: params.active,
+        }
+    }
+}
+
+fn active_by_default() -> bool {
+    true
+}
+
+/// A `PATCH` request's operations.
+#[derive(Debug, Deserialize)]
+pub(crate) struct PatchParams {
+    #[serde(rename = "Operations")]
+    pub(crate) operations: Vec<PatchOperation>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct MemberParams {
+    pub(crate) value: Id,
+}
+
+/// A Group resource, as sent to create or replace one.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GroupParams {
+    pub(crate) display_name: String,
+    #[serde(default)]
+    pub(crate) members: Vec<MemberParams>,
+}
@@ -0,0 +1,10 @@
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct SearchParams {
+    /// What to search for: words, `"quoted phrases"`, `or` and `-excluded` words
+    pub(crate) q: String,
+    /// Most results to return, best first (default 20, at most 100)
+    pub(crate) limit: Option<u64>,
+}
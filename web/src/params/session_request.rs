@@ -0,0 +1,27 @@
+use chrono::{DateTime, FixedOffset};
+use domain::session_request_status::SessionRequestStatus;
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct IndexParams {
+    /// Only return requests in this status, e.g. `pending` for the coach's waiting list
+    pub(crate) status: Option<SessionRequestStatus>,
+}
+
+/// Body for POST `/coaching_relationships/:id/session_requests/:id/accept`. Both fields
+/// are optional: the session defaults to the start of the request's first window and to
+/// the duration the coachee asked for.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub(crate) struct AcceptParams {
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub(crate) starts_at: Option<DateTime<FixedOffset>>,
+    pub(crate) duration_minutes: Option<i16>,
+}
+
+/// Body for POST `/coaching_relationships/:id/session_requests/:id/decline`.
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct DeclineParams {
+    /// Why the coach can't take the session; shown to the coachee
+    pub(crate) reason: String,
+}
@@ -0,0 +1,11 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct AnalyticsParams {
+    /// Count sessions dated on or after this day
+    pub(crate) from_date: NaiveDate,
+    /// Count sessions dated on or before this day
+    pub(crate) to_date: NaiveDate,
+}
@@ -1,4 +1,5 @@
-use serde::Deserialize;
+use serde::de::{DeserializeOwned, Error as _, IntoDeserializer};
+use serde::{Deserialize, Deserializer};
 use utoipa::ToSchema;
 
 /// Common sort order values used across all entities
@@ -10,3 +11,55 @@ pub enum SortOrder {
     #[serde(rename = "desc")]
     Desc,
 }
+
+/// A `sort` query parameter: one of the endpoint's sort fields, descending when prefixed
+/// with `-` (e.g. `sort=-due_by`). It takes precedence over `sort_by` and `sort_order`.
+#[derive(Debug, Clone)]
+pub(crate) struct Sort<F> {
+    pub(crate) field: F,
+    pub(crate) order: SortOrder,
+}
+
+impl<'de, F: DeserializeOwned> Deserialize<'de> for Sort<F> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        let (field, order) = match value.strip_prefix('-') {
+            Some(field) => (field, SortOrder::Desc),
+            None => (value.strip_prefix('+').unwrap_or(&value), SortOrder::Asc),
+        };
+        let field = F::deserialize(field.into_deserializer())
+            .map_err(|e: serde::de::value::Error| D::Error::custom(e))?;
+        Ok(Self { field, order })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    enum Field {
+        #[serde(rename = "due_by")]
+        DueBy,
+    }
+
+    fn sort(value: &str) -> Result<Sort<Field>, serde::de::value::Error> {
+        Sort::deserialize(value.into_deserializer())
+    }
+
+    #[test]
+    fn a_leading_minus_sorts_descending() {
+        let descending = sort("-due_by").unwrap();
+        assert_eq!(descending.field, Field::DueBy);
+        assert!(matches!(descending.order, SortOrder::Desc));
+
+        assert!(matches!(sort("due_by").unwrap().order, SortOrder::Asc));
+        assert!(matches!(sort("+due_by").unwrap().order, SortOrder::Asc));
+    }
+
+    #[test]
+    fn unknown_sort_fields_are_refused() {
+        let error = sort("-body").unwrap_err().to_string();
+        assert!(error.contains("unknown variant `body`"), "{error}");
+    }
+}
@@ -0,0 +1,24 @@
+use domain::Id;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct PollParams {
+    /// ID of the last event received; events sent after it are returned
+    pub(crate) since: Option<u64>,
+    /// Most seconds to wait for an event when none is buffered (default and at most 25)
+    pub(crate) timeout: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct SseParams {
+    /// Comma-separated events to subscribe to: `category.*` (e.g. `action.*`), an event
+    /// name or `*`. All events when absent; system events are always sent.
+    pub(crate) events: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct ConnectionsParams {
+    /// Only return this user's connections
+    pub(crate) user_id: Option<Id>,
+}
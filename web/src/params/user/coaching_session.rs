@@ -4,6 +4,7 @@ use serde::Deserialize;
 use utoipa::{IntoParams, ToSchema};
 
 use crate::params::coaching_session::SortField;
+use crate::params::include;
 use crate::params::sort::SortOrder;
 use crate::params::WithSortDefaults;
 use domain::{coaching_sessions, Id, QuerySort};
@@ -27,6 +28,9 @@ pub enum IncludeParam {
     Agreements,
     /// Include session topics
     Topics,
+    /// Include open actions carried forward from earlier sessions
+    #[serde(rename = "carried_actions")]
+    CarriedActions,
 }
 
 /// Query parameters for GET `/users/{user_id}/coaching_sessions` endpoint.
@@ -40,6 +44,9 @@ pub(crate) struct IndexParams {
     pub(crate) user_id: Id,
     /// Optional: filter sessions to only those in this coaching relationship
     pub(crate) coaching_relationship_id: Option<Id>,
+    /// Optional: filter sessions to only those of this session type
+    #[serde(alias = "filter[session_type_id]")]
+    pub(crate) session_type_id: Option<Id>,
     /// Optional: filter sessions starting from this date (inclusive).
     ///
     /// Interpreted in `tz` when present; otherwise UTC.
@@ -64,31 +71,10 @@ pub(crate) struct IndexParams {
     /// Example: `?include=relationship,organization,goal`
     ///
     /// See `IncludeParam` for valid values and N+1 query optimization details.
-    #[serde(default, deserialize_with = "deserialize_comma_separated")]
+    #[serde(default, deserialize_with = "include::comma_separated")]
     pub(crate) include: Vec<IncludeParam>,
 }
 
-/// Custom deserializer for comma-separated include parameter
-fn deserialize_comma_separated<'de, D>(deserializer: D) -> Result<Vec<IncludeParam>, D::Error>
-where
-    D: serde::Deserializer<'de>,
-{
-    let s: Option<String> = Option::deserialize(deserializer)?;
-    match s {
-        None => Ok(Vec::new()),
-        Some(s) if s.is_empty() => Ok(Vec::new()),
-        Some(s) => s
-            .split(',')
-            .map(str::trim)
-            .filter(|s| !s.is_empty())
-            .map(|s| {
-                serde_json::from_value(serde_json::Value::String(s.to_string()))
-                    .map_err(serde::de::Error::custom)
-            })
-            .collect(),
-    }
-}
-
 impl IndexParams {
     /// Sets the user_id field (useful when user_id comes from path parameter).
     ///
@@ -171,9 +157,48 @@ impl CountsByMonthParams {
     }
 }
 
+/// Upper bound on `ids` per badges request; roughly one page of a session list.
+pub(crate) const MAX_BADGE_SESSION_IDS: usize = 100;
+
+/// Query parameters for GET `/users/{user_id}/coaching_sessions/badges`.
+///
+/// `ids` is a comma-separated list of coaching session IDs, e.g.
+/// `?ids=<uuid>,<uuid>`. Malformed UUIDs, an empty list, or more than
+/// `MAX_BADGE_SESSION_IDS` entries are rejected with a 400 at deserialization.
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct BadgesParams {
+    #[serde(deserialize_with = "deserialize_session_ids")]
+    pub(crate) ids: Vec<Id>,
+}
+
+fn deserialize_session_ids<'de, D>(deserializer: D) -> Result<Vec<Id>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    let mut ids: Vec<Id> = s
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| Id::parse_str(s).map_err(serde::de::Error::custom))
+        .collect::<Result<_, _>>()?;
+    ids.sort_unstable();
+    ids.dedup();
+
+    match ids.len() {
+        0 => Err(serde::de::Error::custom("ids must not be empty")),
+        n if n > MAX_BADGE_SESSION_IDS => Err(serde::de::Error::custom(format!(
+            "at most {MAX_BADGE_SESSION_IDS} ids are allowed per request"
+        ))),
+        _ => Ok(ids),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use axum::extract::{rejection::QueryRejection, Query};
+    use axum::http::Uri;
 
     // The v1 contract accepts only `group_by=month`. Anything else must fail
     // deserialization so axum rejects the request with 400 before the handler
@@ -188,4 +213,33 @@ mod tests {
         assert!(serde_json::from_str::<GroupByParam>(r#""MONTH""#).is_err());
         assert!(serde_json::from_str::<GroupByParam>(r#""""#).is_err());
     }
+
+    fn parse_badges(query: &str) -> Result<BadgesParams, QueryRejection> {
+        let uri: Uri = format!("/badges?{query}").parse().expect("valid uri");
+        Query::<BadgesParams>::try_from_uri(&uri).map(|Query(params)| params)
+    }
+
+    #[test]
+    fn badges_params_parses_and_dedupes_comma_separated_ids() {
+        let a = Id::new_v4();
+        let b = Id::new_v4();
+        let params = parse_badges(&format!("ids={a},%20{b},{a},")).expect("ids parse");
+
+        let mut expected = vec![a, b];
+        expected.sort_unstable();
+        assert_eq!(params.ids, expected);
+    }
+
+    #[test]
+    fn badges_params_rejects_empty_malformed_and_oversized_lists() {
+        assert!(parse_badges("ids=").is_err());
+        assert!(parse_badges("").is_err());
+        assert!(parse_badges("ids=not-a-uuid").is_err());
+
+        let too_many = (0..=MAX_BADGE_SESSION_IDS)
+            .map(|_| Id::new_v4().to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        assert!(parse_badges(&format!("ids={too_many}")).is_err());
+    }
 }
@@ -4,11 +4,27 @@ pub(crate) mod coaching_session;
 pub(crate) mod goal;
 
 // Re-export user profile update params for backward compatibility
-use domain::{IntoUpdateMap, UpdateMap};
+use domain::{Id, IntoUpdateMap, UpdateMap};
 use sea_orm::Value;
 use serde::Deserialize;
 use utoipa::{IntoParams, ToSchema};
 
+/// Body of `POST /users/:user_id/accept_policy`
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AcceptPolicyParams {
+    /// The policy document version being accepted
+    pub policy_document_id: Id,
+}
+
+/// Body of `POST /organizations/:organization_id/users/:user_id/lock`
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct LockParams {
+    /// Why the user is locked, shown to the organization's admins and kept in the
+    /// audit log (at most 500 characters)
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
 #[derive(Debug, Deserialize, IntoParams, ToSchema)]
 pub struct UpdateParams {
     pub email: Option<String>,
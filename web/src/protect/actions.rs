@@ -1,11 +1,11 @@
 use crate::{extractors::authenticated_user::AuthenticatedUser, AppState};
 use axum::{
-    extract::{Query, Request, State},
+    extract::{Path, Query, Request, State},
     http::StatusCode,
     middleware::Next,
     response::IntoResponse,
 };
-use domain::{coaching_session, Id};
+use domain::{action, coaching_session, Id};
 use log::*;
 use serde::Deserialize;
 
@@ -47,3 +47,32 @@ pub(crate) async fn index(
         }
     }
 }
+
+/// Checks that the soft-deleted action referenced by path `id` belongs to a live coaching
+/// session the authenticated user is a participant of.
+///  Intended to be given to axum::middleware::from_fn_with_state in the router
+pub(crate) async fn restore(
+    State(app_state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<Id>,
+    request: Request,
+    next: Next,
+) -> impl IntoResponse {
+    match action::find_deleted_by_id(app_state.db_conn_ref(), id).await {
+        Ok(action) => {
+            super::coaching_sessions::participant_only(
+                &app_state,
+                user.id,
+                action.coaching_session_id,
+                request,
+                next,
+            )
+            .await
+        }
+        Err(e) => {
+            let domain_err: domain::error::Error = e.into();
+            error!("Error finding deleted action for authorization: {domain_err:?}");
+            crate::error::domain_error_into_response(domain_err)
+        }
+    }
+}
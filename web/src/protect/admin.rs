@@ -0,0 +1,70 @@
+//! SuperAdmin gates for the platform administration endpoints under /admin.
+
+use axum::{
+    extract::{Path, Request, State},
+    middleware::Next,
+    response::IntoResponse,
+};
+use domain::Id;
+
+use crate::protect::{authorize, Predicate, UserIsNotSelf, UserIsSuperAdmin};
+use crate::{extractors::authenticated_user::AuthenticatedUser, AppState};
+
+/// Users of every organization are listed, so only platform admins may search them.
+pub(crate) async fn users(
+    State(app_state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    request: Request,
+    next: Next,
+) -> impl IntoResponse {
+    let checks = vec![Predicate::new(UserIsSuperAdmin, vec![])];
+    authorize(&app_state, user, request, next, checks).await
+}
+
+/// Locking and unlocking any user's account; a SuperAdmin can't lock themselves out.
+pub(crate) async fn lock_user(
+    State(app_state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(user_id): Path<Id>,
+    request: Request,
+    next: Next,
+) -> impl IntoResponse {
+    let checks = vec![
+        Predicate::new(UserIsNotSelf, vec![user_id]),
+        Predicate::new(UserIsSuperAdmin, vec![]),
+    ];
+    authorize(&app_state, user, request, next, checks).await
+}
+
+/// Reassigning a relationship's coach or coachee, in any organization.
+pub(crate) async fn reassign_coaching_relationship(
+    State(app_state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    request: Request,
+    next: Next,
+) -> impl IntoResponse {
+    let checks = vec![Predicate::new(UserIsSuperAdmin, vec![])];
+    authorize(&app_state, user, request, next, checks).await
+}
+
+/// Any organization's stats.
+pub(crate) async fn organization_stats(
+    State(app_state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    request: Request,
+    next: Next,
+) -> impl IntoResponse {
+    let checks = vec![Predicate::new(UserIsSuperAdmin, vec![])];
+    authorize(&app_state, user, request, next, checks).await
+}
+
+/// The export of every organization's events to the analytics warehouse.
+pub(crate) async fn warehouse_export(
+    State(app_state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    request: Request,
+    next: Next,
+) -> impl IntoResponse {
+    let checks = vec![Predicate::new(UserIsSuperAdmin, vec![])];
+    authorize(&app_state, user, request, next, checks).await
+}
@@ -1,12 +1,12 @@
 use crate::params::agreement::IndexParams;
 use crate::{extractors::authenticated_user::AuthenticatedUser, AppState};
 use axum::{
-    extract::{Query, Request, State},
+    extract::{Path, Query, Request, State},
     http::StatusCode,
     middleware::Next,
     response::IntoResponse,
 };
-use domain::coaching_session;
+use domain::{agreement, coaching_session, Id};
 use log::*;
 
 /// Checks that coaching relationship record associated with the coaching session
@@ -42,3 +42,32 @@ pub(crate) async fn index(
         }
     }
 }
+
+/// Checks that the soft-deleted agreement referenced by path `id` belongs to a live coaching
+/// session the authenticated user is a participant of.
+///  Intended to be given to axum::middleware::from_fn_with_state in the router
+pub(crate) async fn restore(
+    State(app_state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<Id>,
+    request: Request,
+    next: Next,
+) -> impl IntoResponse {
+    match agreement::find_deleted_by_id(app_state.db_conn_ref(), id).await {
+        Ok(agreement) => {
+            super::coaching_sessions::participant_only(
+                &app_state,
+                user.id,
+                agreement.coaching_session_id,
+                request,
+                next,
+            )
+            .await
+        }
+        Err(e) => {
+            let domain_err: domain::error::Error = e.into();
+            error!("Error finding deleted agreement for authorization: {domain_err:?}");
+            crate::error::domain_error_into_response(domain_err)
+        }
+    }
+}
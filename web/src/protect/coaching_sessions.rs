@@ -3,7 +3,7 @@ use axum::{
     extract::{Path, Query, Request, State},
     http::StatusCode,
     middleware::Next,
-    response::IntoResponse,
+    response::{IntoResponse, Response},
 };
 use serde::Deserialize;
 
@@ -142,3 +142,73 @@ pub(crate) async fn delete(
         (StatusCode::UNAUTHORIZED, "UNAUTHORIZED").into_response()
     }
 }
+
+/// Checks that the soft-deleted coaching session referenced by path `id` exists and
+/// that the authenticated user is its coach, who alone may delete it.
+///  Intended to be given to axum::middleware::from_fn_with_state in the router
+pub(crate) async fn restore(
+    State(app_state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(coaching_session_id): Path<Id>,
+    request: Request,
+    next: Next,
+) -> impl IntoResponse {
+    let coaching_session =
+        match coaching_session::find_deleted_by_id(app_state.db_conn_ref(), coaching_session_id)
+            .await
+        {
+            Ok(session) => session,
+            Err(e) => {
+                let domain_err: domain::error::Error = e.into();
+                error!("Error finding deleted coaching session for authorization: {domain_err:?}");
+                return crate::error::domain_error_into_response(domain_err);
+            }
+        };
+
+    match coaching_relationship::find_by_id(
+        app_state.db_conn_ref(),
+        coaching_session.coaching_relationship_id,
+    )
+    .await
+    {
+        Ok(relationship) if relationship.coach_id == user.id => next.run(request).await,
+        Ok(_) => {
+            warn!(
+                "RESTORE auth denied (not coach): coaching_session_id={coaching_session_id} user_id={}",
+                user.id
+            );
+            (StatusCode::UNAUTHORIZED, "UNAUTHORIZED").into_response()
+        }
+        Err(e) => {
+            error!("Error authorizing coaching session restore: {e:?}");
+            crate::error::domain_error_into_response(e.into())
+        }
+    }
+}
+
+/// Runs `next` if the live coaching session `coaching_session_id` has the user as its
+/// coach or coachee. Shared by the restores of records kept within a session, which
+/// can't come back into a deleted one.
+pub(crate) async fn participant_only(
+    app_state: &AppState,
+    user_id: Id,
+    coaching_session_id: Id,
+    request: Request,
+    next: Next,
+) -> Response {
+    match coaching_session::find_by_id_with_coaching_relationship(
+        app_state.db_conn_ref(),
+        coaching_session_id,
+    )
+    .await
+    {
+        Ok((_coaching_session, relationship)) if relationship.includes_user(user_id) => {
+            next.run(request).await
+        }
+        Ok(_) => (StatusCode::UNAUTHORIZED, "UNAUTHORIZED").into_response(),
+        Err(e) => {
+            error!("Error authorizing restore in coaching session {coaching_session_id}: {e:?}");
+            crate::error::domain_error_into_response(e)
+        }
+    }
+}
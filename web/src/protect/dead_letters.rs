@@ -0,0 +1,23 @@
+//! SuperAdmin gate for /admin/dead_letters endpoints.
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::IntoResponse,
+};
+
+use crate::protect::{authorize, Predicate, UserIsAdmin};
+use crate::{extractors::authenticated_user::AuthenticatedUser, AppState};
+
+/// Dead letters carry events from every organization, and requeueing re-runs platform
+/// event handlers, so only platform admins may use them.
+/// `UserIsAdmin` with empty args checks for SuperAdmin only.
+pub(crate) async fn admin_only(
+    State(app_state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    request: Request,
+    next: Next,
+) -> impl IntoResponse {
+    let checks = vec![Predicate::new(UserIsAdmin, vec![])];
+    authorize(&app_state, user, request, next, checks).await
+}
@@ -83,6 +83,36 @@ pub(crate) async fn by_id(
     }
 }
 
+/// Checks that the soft-deleted goal referenced by path `id` belongs to a coaching
+/// relationship that the authenticated user is a member of (coach or coachee).
+pub(crate) async fn restore(
+    State(app_state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<Id>,
+    request: Request,
+    next: Next,
+) -> impl IntoResponse {
+    let goal = match goal::find_deleted_by_id(app_state.db_conn_ref(), id).await {
+        Ok(goal) => goal,
+        Err(e) => {
+            let domain_err: domain::error::Error = e.into();
+            error!("Error finding deleted goal for authorization: {domain_err:?}");
+            return crate::error::domain_error_into_response(domain_err);
+        }
+    };
+
+    match coaching_relationship::find_by_id(app_state.db_conn_ref(), goal.coaching_relationship_id)
+        .await
+    {
+        Ok(relationship) if relationship.includes_user(user.id) => next.run(request).await,
+        Ok(_) => (StatusCode::UNAUTHORIZED, "UNAUTHORIZED").into_response(),
+        Err(e) => {
+            error!("Error authorizing goal restore: {e:?}");
+            crate::error::domain_error_into_response(e.into())
+        }
+    }
+}
+
 /// Checks that the coaching session referenced by path `coaching_session_id`
 /// belongs to a coaching relationship that the authenticated user is a member of.
 pub(crate) async fn by_coaching_session_id(
@@ -0,0 +1,38 @@
+use crate::protect::{Predicate, UserIsCoachee};
+use crate::{extractors::authenticated_user::AuthenticatedUser, AppState};
+use axum::{
+    extract::{Path, Request, State},
+    middleware::Next,
+    response::IntoResponse,
+};
+
+use domain::Id;
+
+/// Checks that the authenticated user is the coachee of the relationship specified by
+/// `relationship_id` before writing a journal entry in it.
+/// Intended to be given to axum::middleware::from_fn_with_state in the router
+pub(crate) async fn create(
+    State(app_state): State<AppState>,
+    AuthenticatedUser(authenticated_user): AuthenticatedUser,
+    Path(relationship_id): Path<Id>,
+    request: Request,
+    next: Next,
+) -> impl IntoResponse {
+    let checks: Vec<Predicate> = vec![Predicate::new(UserIsCoachee, vec![relationship_id])];
+
+    crate::protect::authorize(&app_state, authenticated_user, request, next, checks).await
+}
+
+/// Checks that the authenticated user is the coachee of the relationship before updating
+/// or deleting one of its journal entries. Authorship is checked in the domain.
+pub(crate) async fn by_id(
+    State(app_state): State<AppState>,
+    AuthenticatedUser(authenticated_user): AuthenticatedUser,
+    Path((relationship_id, _id)): Path<(Id, Id)>,
+    request: Request,
+    next: Next,
+) -> impl IntoResponse {
+    let checks: Vec<Predicate> = vec![Predicate::new(UserIsCoachee, vec![relationship_id])];
+
+    crate::protect::authorize(&app_state, authenticated_user, request, next, checks).await
+}
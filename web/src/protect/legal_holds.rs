@@ -0,0 +1,23 @@
+//! SuperAdmin gate for /admin/legal_holds endpoints.
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::IntoResponse,
+};
+
+use crate::protect::{authorize, Predicate, UserIsAdmin};
+use crate::{extractors::authenticated_user::AuthenticatedUser, AppState};
+
+/// Holds pause purges across the platform and block deleting any organization's data,
+/// so only platform admins may manage them.
+/// `UserIsAdmin` with empty args checks for SuperAdmin only.
+pub(crate) async fn admin_only(
+    State(app_state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    request: Request,
+    next: Next,
+) -> impl IntoResponse {
+    let checks = vec![Predicate::new(UserIsAdmin, vec![])];
+    authorize(&app_state, user, request, next, checks).await
+}
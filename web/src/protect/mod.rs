@@ -9,12 +9,22 @@
 //! to understand and maintain.
 
 pub(crate) mod actions;
+pub(crate) mod admin;
 pub(crate) mod agreements;
+pub(crate) mod api_docs;
 pub(crate) mod coaching_sessions;
+pub(crate) mod dead_letters;
+pub(crate) mod domain_events;
 pub(crate) mod goals;
+pub(crate) mod journal_entries;
 pub(crate) mod jwt;
+pub(crate) mod legal_holds;
 pub(crate) mod notes;
 pub(crate) mod organizations;
+pub(crate) mod prompt_templates;
+pub(crate) mod runtime_config;
+pub(crate) mod sse_connections;
+pub(crate) mod sudo;
 pub(crate) mod tiptap_metrics;
 pub(crate) mod users;
 
@@ -65,6 +75,11 @@ use log::*;
 #[async_trait]
 pub trait Check: Send + Sync {
     async fn eval(&self, app: &AppState, user: &domain::users::Model, args: Vec<Id>) -> bool;
+
+    /// The status and message a request this rule refuses is answered with.
+    fn refusal(&self) -> (StatusCode, &'static str) {
+        (StatusCode::FORBIDDEN, "FORBIDDEN")
+    }
 }
 
 /// Pairs a [`Check`] implementation with the concrete arguments that the rule
@@ -96,13 +111,17 @@ impl Predicate {
             .eval(app_state, user, self.args.clone())
             .await
     }
+
+    pub(crate) fn refusal(&self) -> (StatusCode, &'static str) {
+        self.predicate.refusal()
+    }
 }
 
 /// Axum middleware that enforces one or more [`Predicate`]s.
 ///
 /// Each predicate is evaluated in the order supplied; if any rule returns
-/// `false` the request is aborted with **403 FORBIDDEN**.  When all rules
-/// pass the wrapped handler (`next`) is executed.
+/// `false` the request is aborted with the rule's refusal, **403 FORBIDDEN** unless
+/// it says otherwise.  When all rules pass the wrapped handler (`next`) is executed.
 ///
 /// Typical usage inside a helper function in the `protect` namespace:
 /// ```rust,ignore
@@ -128,7 +147,7 @@ pub(crate) async fn authorize(
 ) -> impl IntoResponse {
     for check in checks {
         if !check.check(app_state, &authenticated_user).await {
-            return (StatusCode::FORBIDDEN, "FORBIDDEN").into_response();
+            return check.refusal().into_response();
         }
     }
     next.run(request).await
@@ -198,14 +217,117 @@ impl Check for UserIsAdmin {
         false
     }
 }
+
+/// Checks if the authenticated user is the coachee of a coaching relationship.
+///
+/// # Arguments
+/// * `args[0]` - The coaching relationship ID to check
+pub struct UserIsCoachee;
+
+#[async_trait]
+impl Check for UserIsCoachee {
+    async fn eval(
+        &self,
+        app_state: &AppState,
+        authenticated_user: &domain::users::Model,
+        args: Vec<Id>,
+    ) -> bool {
+        let Some(relationship_id) = args.first() else {
+            warn!("UserIsCoachee check failed: no relationship_id provided");
+            return false;
+        };
+
+        match domain::coaching_relationship::find_by_id(app_state.db_conn_ref(), *relationship_id)
+            .await
+        {
+            Ok(relationship) => relationship.coachee_id == authenticated_user.id,
+            Err(e) => {
+                warn!("UserIsCoachee check failed for relationship {relationship_id}: {e:?}");
+                false
+            }
+        }
+    }
+}
+
+/// Checks if the authenticated user may manage an organization's content library.
+///
+/// Returns `true` if the user is a SuperAdmin, an `Admin` of the organization, or the
+/// coach in any of the organization's coaching relationships.
+///
+/// # Arguments
+/// * `args[0]` - The organization ID whose library is being managed
+pub struct UserCanManageLibrary;
+
+#[async_trait]
+impl Check for UserCanManageLibrary {
+    async fn eval(
+        &self,
+        app_state: &AppState,
+        authenticated_user: &domain::users::Model,
+        args: Vec<Id>,
+    ) -> bool {
+        let Some(organization_id) = args.first() else {
+            warn!("UserCanManageLibrary check failed: no organization_id provided");
+            return false;
+        };
+
+        match domain::library::can_manage(
+            app_state.db_conn_ref(),
+            authenticated_user,
+            *organization_id,
+        )
+        .await
+        {
+            Ok(can_manage) => can_manage,
+            Err(e) => {
+                warn!(
+                    "UserCanManageLibrary check failed for user {}: {e:?}",
+                    authenticated_user.id
+                );
+                false
+            }
+        }
+    }
+}
+
+/// Checks if an organization's admins may lock a user: a member of it who is neither a
+/// SuperAdmin nor an admin of another organization.
+///
+/// # Arguments
+/// * `args[0]` - The organization ID locking the user
+/// * `args[1]` - The user ID being locked
+pub struct UserIsLockable;
+
+#[async_trait]
+impl Check for UserIsLockable {
+    async fn eval(
+        &self,
+        app_state: &AppState,
+        _authenticated_user: &domain::users::Model,
+        args: Vec<Id>,
+    ) -> bool {
+        let [organization_id, user_id] = args[..] else {
+            warn!("UserIsLockable check failed: expected organization_id and user_id");
+            return false;
+        };
+
+        match domain::user_lock::may_lock(app_state.db_conn_ref(), organization_id, user_id).await {
+            Ok(may_lock) => may_lock,
+            Err(e) => {
+                warn!("UserIsLockable check failed for user {user_id}: {e:?}");
+                false
+            }
+        }
+    }
+}
+
 /// Checks if the authenticated user is a SuperAdmin (global admin).
 ///
 /// Returns `true` if user has the `SuperAdmin` role with `organization_id = NULL`.
 ///
-/// **Note:** This check is deprecated in favor of using `UserIsAdmin` without
-/// an organization_id argument, as that also checks for SuperAdmin status.
-/// This struct is kept for explicit SuperAdmin-only checks if needed.
-#[allow(dead_code)]
+/// `UserIsAdmin` without an organization_id argument is equivalent; this makes
+/// SuperAdmin-only checks, like those of the /admin platform administration
+/// endpoints, explicit.
 pub struct UserIsSuperAdmin;
 
 #[async_trait]
@@ -0,0 +1,37 @@
+use crate::protect::{Predicate, UserIsAdmin};
+use crate::{extractors::authenticated_user::AuthenticatedUser, AppState};
+use axum::{
+    extract::{Path, Request, State},
+    middleware::Next,
+    response::IntoResponse,
+};
+
+use domain::Id;
+
+/// Checks that the authenticated user is an admin of the organization specified by
+/// `organization_id` before reporting its API usage.
+/// Intended to be given to axum::middleware::from_fn_with_state in the router
+pub(crate) async fn read(
+    State(app_state): State<AppState>,
+    AuthenticatedUser(authenticated_user): AuthenticatedUser,
+    Path(organization_id): Path<Id>,
+    request: Request,
+    next: Next,
+) -> impl IntoResponse {
+    let checks: Vec<Predicate> = vec![Predicate::new(UserIsAdmin, vec![organization_id])];
+
+    crate::protect::authorize(&app_state, authenticated_user, request, next, checks).await
+}
+
+/// Quotas limit what an organization's own admins can do, so only platform admins may
+/// read or change them. `UserIsAdmin` with empty args checks for SuperAdmin only.
+pub(crate) async fn quota(
+    State(app_state): State<AppState>,
+    AuthenticatedUser(authenticated_user): AuthenticatedUser,
+    request: Request,
+    next: Next,
+) -> impl IntoResponse {
+    let checks: Vec<Predicate> = vec![Predicate::new(UserIsAdmin, vec![])];
+
+    crate::protect::authorize(&app_state, authenticated_user, request, next, checks).await
+}
@@ -0,0 +1,24 @@
+use crate::protect::{Predicate, UserIsAdmin};
+use crate::{extractors::authenticated_user::AuthenticatedUser, AppState};
+use axum::{
+    extract::{Path, Request, State},
+    middleware::Next,
+    response::IntoResponse,
+};
+
+use domain::Id;
+
+/// Checks that the authenticated user is an admin of the organization specified by
+/// `organization_id` before reading its audit trail.
+/// Intended to be given to axum::middleware::from_fn_with_state in the router
+pub(crate) async fn read(
+    State(app_state): State<AppState>,
+    AuthenticatedUser(authenticated_user): AuthenticatedUser,
+    Path(organization_id): Path<Id>,
+    request: Request,
+    next: Next,
+) -> impl IntoResponse {
+    let checks: Vec<Predicate> = vec![Predicate::new(UserIsAdmin, vec![organization_id])];
+
+    crate::protect::authorize(&app_state, authenticated_user, request, next, checks).await
+}
@@ -1,2 +1,16 @@
+pub(crate) mod ai_settings;
+pub(crate) mod api_usage;
+pub(crate) mod audit;
 pub(crate) mod coaching_relationships;
+pub(crate) mod goal_templates;
+pub(crate) mod holidays;
+pub(crate) mod library_items;
+pub(crate) mod logo;
+pub(crate) mod oauth_clients;
+pub(crate) mod relationship_health;
+pub(crate) mod scim_token;
+pub(crate) mod session_pulses;
+pub(crate) mod session_types;
+pub(crate) mod transcription_vocabulary;
 pub(crate) mod users;
+pub(crate) mod webhook_subscriptions;
@@ -1,4 +1,4 @@
-use crate::protect::{Predicate, UserIsAdmin, UserIsNotSelf};
+use crate::protect::{Predicate, UserIsAdmin, UserIsLockable, UserIsNotSelf};
 use crate::{extractors::authenticated_user::AuthenticatedUser, AppState};
 use axum::{
     extract::{Path, Request, State},
@@ -56,3 +56,38 @@ pub(crate) async fn delete(
 
     crate::protect::authorize(&app_state, authenticated_user, request, next, checks).await
 }
+
+/// Checks that the authenticated user is an admin of the organization, isn't the user
+/// being locked, and that the organization may lock them
+pub(crate) async fn lock(
+    State(app_state): State<AppState>,
+    AuthenticatedUser(authenticated_user): AuthenticatedUser,
+    Path((organization_id, user_id)): Path<(Id, Id)>,
+    request: Request,
+    next: Next,
+) -> impl IntoResponse {
+    let checks: Vec<Predicate> = vec![
+        Predicate::new(UserIsNotSelf, vec![user_id]),
+        Predicate::new(UserIsAdmin, vec![organization_id]),
+        Predicate::new(UserIsLockable, vec![organization_id, user_id]),
+    ];
+
+    crate::protect::authorize(&app_state, authenticated_user, request, next, checks).await
+}
+
+/// Checks that the authenticated user is an admin of the organization, and isn't the user
+/// being unlocked
+pub(crate) async fn unlock(
+    State(app_state): State<AppState>,
+    AuthenticatedUser(authenticated_user): AuthenticatedUser,
+    Path((organization_id, user_id)): Path<(Id, Id)>,
+    request: Request,
+    next: Next,
+) -> impl IntoResponse {
+    let checks: Vec<Predicate> = vec![
+        Predicate::new(UserIsNotSelf, vec![user_id]),
+        Predicate::new(UserIsAdmin, vec![organization_id]),
+    ];
+
+    crate::protect::authorize(&app_state, authenticated_user, request, next, checks).await
+}
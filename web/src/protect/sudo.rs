@@ -0,0 +1,122 @@
+//! Sudo mode for sensitive routes.
+//!
+//! Entering the password, at login or with `POST /auth/reauthenticate`, puts the session
+//! in sudo mode for `reauthentication_window_seconds`. Routes that grant access, change
+//! credentials or integration keys, or export data are layered with [`required`]:
+//! outside sudo mode they answer `403 Reauthentication required`, and the client asks
+//! for the password again before retrying.
+
+use async_trait::async_trait;
+use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::IntoResponse,
+};
+use chrono::Utc;
+use domain::Id;
+use log::*;
+use tower_sessions::Session;
+
+use crate::protect::{authorize, Check, Predicate};
+use crate::{extractors::authenticated_user::AuthenticatedUser, AppState};
+
+/// Session key of when the session last entered its password (Unix seconds).
+const AUTHENTICATED_AT_KEY: &str = "authenticated_at";
+
+/// Checks that the session entered its password within the reauthentication window.
+///
+/// Carries when the session last entered it (Unix seconds), read by [`required`];
+/// sessions from before sudo mode existed have no time and never pass.
+pub struct RequireSudo {
+    pub authenticated_at: Option<i64>,
+}
+
+#[async_trait]
+impl Check for RequireSudo {
+    async fn eval(
+        &self,
+        app_state: &AppState,
+        _authenticated_user: &domain::users::Model,
+        _args: Vec<Id>,
+    ) -> bool {
+        in_sudo_mode(
+            self.authenticated_at,
+            Utc::now().timestamp(),
+            app_state.config.reauthentication_window_seconds,
+        )
+    }
+
+    fn refusal(&self) -> (StatusCode, &'static str) {
+        (StatusCode::FORBIDDEN, "Reauthentication required")
+    }
+}
+
+/// Only a session in sudo mode may proceed.
+pub(crate) async fn required(
+    State(app_state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    session: Session,
+    request: Request,
+    next: Next,
+) -> impl IntoResponse {
+    let authenticated_at = match authenticated_at(&session).await {
+        Ok(authenticated_at) => authenticated_at,
+        Err(status) => return status.into_response(),
+    };
+
+    let checks = vec![Predicate::new(RequireSudo { authenticated_at }, vec![])];
+    authorize(&app_state, user, request, next, checks)
+        .await
+        .into_response()
+}
+
+/// Records that the session just entered its password, putting it in sudo mode.
+pub(crate) async fn mark_authenticated(
+    session: &Session,
+) -> Result<(), tower_sessions::session::Error> {
+    session
+        .insert(AUTHENTICATED_AT_KEY, Utc::now().timestamp())
+        .await
+}
+
+/// When the session last entered its password (Unix seconds), if it has.
+async fn authenticated_at(session: &Session) -> Result<Option<i64>, StatusCode> {
+    session
+        .get::<i64>(AUTHENTICATED_AT_KEY)
+        .await
+        .map_err(|err| {
+            warn!("Error reading when the session last entered its password: {err:?}");
+            StatusCode::SERVICE_UNAVAILABLE
+        })
+}
+
+/// Whether a session that last entered its password at `authenticated_at` is still
+/// within `window_seconds` of it at `now`.
+fn in_sudo_mode(authenticated_at: Option<i64>, now: i64, window_seconds: u64) -> bool {
+    authenticated_at.is_some_and(|authenticated_at| {
+        (0..=window_seconds as i64).contains(&(now - authenticated_at))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sudo_mode_lasts_for_the_reauthentication_window() {
+        let now = Utc::now().timestamp();
+
+        assert!(in_sudo_mode(Some(now), now, 900));
+        assert!(in_sudo_mode(Some(now - 900), now, 900));
+        assert!(!in_sudo_mode(Some(now - 901), now, 900));
+    }
+
+    #[test]
+    fn sessions_that_never_entered_a_password_are_not_in_sudo_mode() {
+        let now = Utc::now().timestamp();
+
+        assert!(!in_sudo_mode(None, now, 900));
+        assert!(!in_sudo_mode(Some(now + 60), now, 900));
+    }
+}
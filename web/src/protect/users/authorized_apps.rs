@@ -0,0 +1,29 @@
+use crate::{extractors::authenticated_user::AuthenticatedUser, AppState};
+use axum::{
+    extract::{Path, Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::IntoResponse,
+};
+use domain::Id;
+use log::*;
+
+/// Checks that the `user_id` matches the `authenticated_user.id` before revoking one of
+/// the user's authorized apps.
+pub(crate) async fn delete(
+    State(_app_state): State<AppState>,
+    AuthenticatedUser(authenticated_user): AuthenticatedUser,
+    Path((user_id, _client_id)): Path<(Id, Id)>,
+    request: Request,
+    next: Next,
+) -> impl IntoResponse {
+    if authenticated_user.id == user_id {
+        next.run(request).await
+    } else {
+        error!(
+            "Unauthorized: user_id {} does not match authenticated_user_id {}",
+            user_id, authenticated_user.id
+        );
+        (StatusCode::UNAUTHORIZED, "Unauthorized").into_response()
+    }
+}
@@ -9,10 +9,15 @@ use domain::Id;
 use log::*;
 
 pub(crate) mod actions;
+pub(crate) mod authorized_apps;
 pub(crate) mod coaching_sessions;
 pub(crate) mod goals;
 pub(crate) mod organizations;
+pub(crate) mod out_of_office;
 pub(crate) mod passwords;
+pub(crate) mod presence;
+pub(crate) mod push_subscriptions;
+pub(crate) mod remembered_devices;
 
 /// Checks that the `user_id` matches the `authenticated_user.id`
 pub(crate) async fn read(
@@ -0,0 +1,45 @@
+use crate::{extractors::authenticated_user::AuthenticatedUser, AppState};
+use axum::{
+    extract::{Path, Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::IntoResponse,
+};
+use domain::{coaching_relationship, Id};
+use log::*;
+
+/// Checks that the `user_id` is the `authenticated_user.id` or shares a coaching
+/// relationship with them
+pub(crate) async fn read(
+    State(app_state): State<AppState>,
+    AuthenticatedUser(authenticated_user): AuthenticatedUser,
+    Path(user_id): Path<Id>,
+    request: Request,
+    next: Next,
+) -> impl IntoResponse {
+    if authenticated_user.id == user_id {
+        return next.run(request).await;
+    }
+
+    match coaching_relationship::find_by_user(app_state.db_conn_ref(), authenticated_user.id).await
+    {
+        Ok(relationships)
+            if relationships.iter().any(|relationship| {
+                relationship.coach_id == user_id || relationship.coachee_id == user_id
+            }) =>
+        {
+            next.run(request).await
+        }
+        Ok(_) => {
+            error!(
+                "Unauthorized: user_id {} shares no coaching relationship with authenticated_user_id {}",
+                user_id, authenticated_user.id
+            );
+            (StatusCode::UNAUTHORIZED, "Unauthorized").into_response()
+        }
+        Err(e) => {
+            error!("Error authorizing presence read: {e:?}");
+            crate::error::domain_error_into_response(e.into())
+        }
+    }
+}
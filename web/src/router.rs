@@ -1,4 +1,4 @@
-use crate::middleware::throttle::{PerIpThrottle, Throttle, ThrottlePolicy};
+use crate::middleware::throttle::{PerIpThrottle, PerUserThrottle, Throttle, ThrottlePolicy};
 use crate::{
     controller::{health_check_controller, oauth_callback_controller},
     middleware::auth::require_auth,
@@ -9,19 +9,26 @@ use axum::{
     routing::{delete, get, patch, post, put},
     Router,
 };
+use service::config::ApiDocsAccess;
 use tower_http::services::ServeDir;
 
 use crate::controller::{
-    action_controller, agreement_controller, coaching_session, coaching_session_controller,
-    coaching_session_series_controller, goal_controller, jwt_controller, magic_link_controller,
-    note_controller, oauth_controller, organization, organization_controller,
-    password_reset_controller, tiptap_metrics_controller, user, user_controller,
-    user_session_controller, webhook_controller,
+    action_controller, action_work_log_controller, admin, agreement_controller, ai_controller,
+    batch_controller, bootstrap_controller, coaching_session, coaching_session_controller,
+    coaching_session_series_controller, dead_letter_controller, domain_event_controller,
+    goal_controller, journal_entry_controller, jwt_controller, legal_hold_controller,
+    library_assignment_controller, magic_link_controller, metrics_controller, note_controller,
+    oauth_controller, oauth_server_controller, organization, organization_controller,
+    password_reset_controller, prompt_template_controller, push_controller, reaction_controller,
+    resource_view_controller, runtime_config_controller, scim, search_controller,
+    session_pulse_controller, session_request_controller, sse_connection_controller,
+    tiptap_metrics_controller, user, user_controller, user_session_controller, webhook_controller,
+    webhook_event_type_controller,
 };
 use crate::sse;
 
 use utoipa::{
-    openapi::security::{ApiKey, ApiKeyValue, SecurityScheme},
+    openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme},
     Modify, OpenApi,
 };
 use utoipa_rapidoc::RapiDoc;
@@ -40,18 +47,29 @@ use utoipa_rapidoc::RapiDoc;
             action_controller::read,
             action_controller::update_status,
             action_controller::delete,
+            action_controller::restore,
+            action_controller::bulk,
+            action_work_log_controller::start,
+            action_work_log_controller::stop,
+            action_work_log_controller::create,
+            action_work_log_controller::index,
             agreement_controller::create,
             agreement_controller::update,
             agreement_controller::index,
             agreement_controller::read,
             agreement_controller::delete,
+            agreement_controller::restore,
             coaching_session_controller::index,
             coaching_session_controller::read,
             coaching_session_controller::view,
+            resource_view_controller::create,
+            reaction_controller::create,
+            reaction_controller::delete,
             coaching_session_controller::create,
             coaching_session_controller::update,
             coaching_session_controller::update_title,
             coaching_session_controller::delete,
+            coaching_session_controller::restore,
             coaching_session_series_controller::create,
             coaching_session_series_controller::read,
             coaching_session_series_controller::index,
@@ -60,6 +78,18 @@ use utoipa_rapidoc::RapiDoc;
             coaching_session::meeting_recording_controller::create,
             coaching_session::meeting_recording_controller::read,
             coaching_session::meeting_recording_controller::delete,
+            coaching_session::prep_controller::read,
+            coaching_session::record_controller::read,
+            coaching_session::attachment_controller::create,
+            coaching_session::attachment_controller::index,
+            coaching_session::attachment_controller::download,
+            coaching_session::attachment_controller::delete,
+            coaching_session::note_typing_controller::create,
+            coaching_session::estimate_controller::read,
+            ai_controller::update_privacy_level,
+            ai_controller::chat,
+            ai_controller::themes,
+            ai_controller::progress_reports,
             coaching_session::topic_controller::index,
             coaching_session::topic_controller::create,
             coaching_session::topic_controller::update,
@@ -70,11 +100,17 @@ use utoipa_rapidoc::RapiDoc;
             coaching_session::topic_controller::undo,
             coaching_session::transcription_controller::read,
             coaching_session::transcription_segment_controller::index,
+            coaching_session::transcript_chapter_controller::index,
+            coaching_session::conversation_metrics_controller::read,
+            coaching_session::question_quality_controller::read,
             health_check_controller::health_check,
+            metrics_controller::read,
             magic_link_controller::validate,
             magic_link_controller::complete_setup,
             note_controller::create,
             note_controller::update,
+            note_controller::delete,
+            note_controller::restore,
             note_controller::index,
             note_controller::read,
             oauth_callback_controller::callback,
@@ -82,6 +118,17 @@ use utoipa_rapidoc::RapiDoc;
             oauth_controller::index,
             oauth_controller::read,
             oauth_controller::delete,
+            oauth_server_controller::read_authorization,
+            oauth_server_controller::authorize,
+            oauth_server_controller::token,
+            oauth_server_controller::introspect,
+            oauth_server_controller::revoke,
+            organization::oauth_client_controller::index,
+            organization::oauth_client_controller::create,
+            organization::oauth_client_controller::delete,
+            organization::scim_token_controller::read,
+            organization::scim_token_controller::create,
+            organization::scim_token_controller::delete,
             organization_controller::index,
             organization_controller::read,
             organization_controller::create,
@@ -93,47 +140,169 @@ use utoipa_rapidoc::RapiDoc;
             organization::coaching_relationship_controller::index,
             organization::coaching_relationship_controller::read,
             organization::coaching_relationship_controller::goal_progress,
+            organization::ai_settings_controller::read,
+            organization::ai_settings_controller::update,
+            organization::logo_controller::create,
+            organization::logo_controller::read,
+            organization::api_usage_controller::read,
+            organization::api_usage_controller::read_quota,
+            organization::api_usage_controller::update_quota,
+            organization::api_usage_controller::delete_quota,
+            organization::audit_controller::index,
+            organization::transcription_vocabulary_controller::read,
+            organization::transcription_vocabulary_controller::update,
+            organization::goal_template_controller::index,
+            organization::goal_template_controller::create,
+            organization::goal_template_controller::update,
+            organization::goal_template_controller::delete,
+            organization::session_type_controller::index,
+            organization::session_type_controller::create,
+            organization::session_type_controller::update,
+            organization::session_type_controller::delete,
+            organization::session_type_controller::analytics,
+            organization::holiday_controller::index,
+            organization::holiday_controller::import,
+            organization::holiday_controller::delete,
+            organization::library_item_controller::index,
+            organization::library_item_controller::create,
+            organization::library_item_controller::update,
+            organization::library_item_controller::delete,
+            organization::webhook_subscription_controller::index,
+            organization::webhook_subscription_controller::create,
+            organization::webhook_subscription_controller::update,
+            organization::webhook_subscription_controller::delete,
+            webhook_event_type_controller::index,
+            batch_controller::fetch,
+            search_controller::search,
+            session_pulse_controller::read,
+            session_pulse_controller::update,
+            session_pulse_controller::pending,
+            session_pulse_controller::summary,
+            session_pulse_controller::analytics,
+            organization::relationship_health_controller::read_settings,
+            organization::relationship_health_controller::update_settings,
+            organization::relationship_health_controller::index,
+            organization::relationship_health_controller::history,
+            journal_entry_controller::index,
+            journal_entry_controller::create,
+            journal_entry_controller::read,
+            journal_entry_controller::update,
+            journal_entry_controller::delete,
+            library_assignment_controller::create,
+            library_assignment_controller::index,
+            library_assignment_controller::complete,
+            session_request_controller::index,
+            session_request_controller::create,
+            session_request_controller::read,
+            session_request_controller::accept,
+            session_request_controller::decline,
+            organization::coaching_relationship_controller::action_streaks,
+            organization::coaching_relationship_controller::conversation_metrics,
+            organization::coaching_relationship_controller::update_pro_bono,
             organization::coaching_relationship::actions_controller::read,
             organization::coaching_relationship::actions_controller::index,
             organization::user_controller::index,
             organization::user_controller::create,
             organization::user_controller::resend_invite,
             organization::user_controller::delete,
+            organization::user_controller::lock,
+            organization::user_controller::unlock,
             goal_controller::create,
+            goal_controller::create_from_template,
             goal_controller::update,
             goal_controller::index,
             goal_controller::read,
             goal_controller::update_status,
             goal_controller::delete,
+            goal_controller::restore,
             coaching_session::goal_controller::create,
             coaching_session::goal_controller::delete,
             coaching_session::goal_controller::index,
             coaching_session::goal_controller::batch_index,
             goal_controller::coaching_sessions_by_goal,
             goal_controller::progress,
+            goal_controller::burndown,
             user_controller::read,
             user_controller::update,
             user_session_controller::login,
             user_session_controller::delete,
+            user_session_controller::refresh,
+            user_session_controller::reauthenticate,
+            user::remembered_device_controller::index,
+            user::remembered_device_controller::delete,
+            user::policy_controller::accept,
             password_reset_controller::request,
             password_reset_controller::validate,
             password_reset_controller::complete,
+            bootstrap_controller::read,
+            bootstrap_controller::create,
             user::password_controller::update_password,
             user::organization_controller::index,
             user::action_controller::index,
             user::coaching_relationships_controller::index,
             user::coaching_session_controller::index,
             user::coaching_session_controller::counts,
+            user::coaching_session_controller::badges,
+            user::calendar_feed_controller::read,
+            user::calendar_feed_controller::regenerate,
+            user::calendar_feed_controller::feed,
             user::goal_controller::index,
+            user::mention_controller::index,
+            user::question_quality_controller::index,
+            user::coaching_log_controller::read,
+            user::coaching_log_controller::export,
+            user::progress_report_settings_controller::read,
+            user::progress_report_settings_controller::update,
             jwt_controller::generate_collab_token,
+            prompt_template_controller::read,
+            prompt_template_controller::create,
+            prompt_template_controller::activate,
+            domain_event_controller::index,
+            domain_event_controller::activity,
+            dead_letter_controller::index,
+            dead_letter_controller::requeue,
+            dead_letter_controller::replay,
+            legal_hold_controller::index,
+            legal_hold_controller::create,
+            legal_hold_controller::release,
+            admin::user_controller::index,
+            admin::user_controller::lock,
+            admin::user_controller::unlock,
+            admin::coaching_relationship_controller::reassign,
+            admin::organization_controller::stats,
+            admin::warehouse_export_controller::index,
+            admin::warehouse_export_controller::backfill,
+            sse_connection_controller::index,
+            runtime_config_controller::reload,
             tiptap_metrics_controller::platform_totals,
             tiptap_metrics_controller::per_org_metrics,
             tiptap_metrics_controller::abandoned_documents,
+            user::authorized_app_controller::index,
+            user::authorized_app_controller::delete,
+            push_controller::vapid_public_key,
+            user::push_subscription_controller::index,
+            user::push_subscription_controller::create,
+            user::push_subscription_controller::delete,
+            user::out_of_office_controller::index,
+            user::out_of_office_controller::create,
+            user::out_of_office_controller::update,
+            user::out_of_office_controller::delete,
+            user::presence_controller::read,
         ),
         components(
             schemas(
                 crate::controller::action_controller::ActionRequest,
+                crate::controller::action_controller::BulkOperationRequest,
+                crate::controller::action_controller::BulkOperationResult,
+                crate::controller::action_controller::BulkRequest,
+                crate::controller::action_controller::BulkResponse,
+                crate::controller::ai_controller::ChatParams,
+                crate::controller::ai_controller::PrivacyLevelParams,
+                crate::controller::organization::coaching_relationship_controller::ProBonoParams,
+                crate::controller::prompt_template_controller::ActivateParams,
+                crate::controller::prompt_template_controller::CreateVersionParams,
                 crate::controller::coaching_session::meeting_recording_controller::StartRecordingParams,
+                crate::controller::coaching_session::note_typing_controller::TypingParams,
                 crate::controller::coaching_session_series_controller::SeriesWithSessions,
                 crate::controller::coaching_session::topic_controller::CreateParams,
                 crate::controller::coaching_session::topic_controller::UpdateParams,
@@ -141,9 +310,39 @@ use utoipa_rapidoc::RapiDoc;
                 crate::controller::coaching_session::topic_controller::RatingParams,
                 crate::controller::coaching_session::topic_controller::StatusParams,
                 crate::controller::oauth_controller::ConnectionResponse,
+                crate::controller::user_session_controller::SessionRefreshResponse,
+                crate::controller::user_session_controller::ReauthenticateParams,
+                domain::remembered_devices::Model,
+                domain::policy_documents::Model,
+                domain::policy_acceptances::Model,
+                domain::policy_kind::PolicyKind,
+                crate::controller::user::presence_controller::PresenceResponse,
+                crate::controller::oauth_server_controller::AuthorizationDetails,
+                crate::controller::oauth_server_controller::AuthorizationRedirect,
+                crate::controller::oauth_server_controller::IntrospectionResponse,
+                crate::controller::oauth_server_controller::OAuthErrorResponse,
+                crate::controller::oauth_server_controller::TokenResponse,
                 crate::controller::password_reset_controller::ValidateParams,
                 crate::controller::password_reset_controller::ValidateResponse,
+                crate::controller::bootstrap_controller::BootstrapStatus,
+                crate::controller::bootstrap_controller::BootstrapRequest,
+                crate::controller::bootstrap_controller::BootstrapResponse,
+                domain::platform_settings::Model,
+                crate::controller::push_controller::VapidPublicKey,
+                crate::controller::runtime_config_controller::ReloadResponse,
+                crate::controller::webhook_event_type_controller::WebhookEventType,
+                crate::controller::batch_controller::BatchResource,
+                crate::controller::batch_controller::BatchRequest,
+                domain::search::SearchResult,
+                domain::search::SearchResultType,
+                domain::session_pulses::Model,
+                crate::controller::session_pulse_controller::PulseRequest,
+                domain::relationship_health_settings::Model,
+                domain::relationship_health_scores::Model,
+                crate::controller::sse_connection_controller::ConnectionsResponse,
+                crate::controller::sse_connection_controller::UserConnectionsResponse,
                 crate::controller::user::coaching_session_controller::CountsResponse,
+                crate::controller::user::calendar_feed_controller::CalendarFeed,
                 crate::params::action::SortField,
                 crate::params::agreement::SortField,
                 crate::params::coaching_relationship::goal_progress::SortField,
@@ -152,6 +351,11 @@ use utoipa_rapidoc::RapiDoc;
                 crate::params::coaching_session_series::CreateParams,
                 crate::params::coaching_session_series::RescheduleParams,
                 crate::params::goal::SortField,
+                crate::params::note::SortField,
+                crate::params::oauth::AuthorizeParams,
+                crate::params::oauth::CreateClientParams,
+                crate::params::oauth::TokenOperationParams,
+                crate::params::oauth::TokenParams,
                 crate::params::sort::SortOrder,
                 crate::params::user::CompleteSetupParams,
                 crate::params::user::PasswordResetCompleteParams,
@@ -159,25 +363,105 @@ use utoipa_rapidoc::RapiDoc;
                 crate::params::user::goal::SortField,
                 domain::action::ActionWithAssignees,
                 domain::actions::Model,
+                domain::action_work_logs::Model,
                 domain::agreements::Model,
+                domain::ai_privacy_level::AiPrivacyLevel,
+                domain::api_usage::EndpointUsage,
+                domain::audit::AuditPage,
+                domain::audit_entries::Model,
                 domain::coaching_relationship::CoachingRelationshipWithUserNames,
                 domain::coaching_relationships::Model,
                 domain::coaching_session::CountByMonth,
                 domain::coaching_session::EnrichedSession,
+                domain::action::EnrichedAction,
+                domain::goal::EnrichedGoal,
+                domain::coaching_session::CarriedAction,
+                domain::coaching_session::SessionBadges,
                 domain::coaching_session::SessionWithDisplayTitle,
                 domain::coaching_session_topics::Model,
                 domain::coaching_session_view::MarkViewed,
                 domain::coaching_sessions::Model,
                 domain::coaching_sessions_goals::Model,
+                domain::conversation_metrics::ConversationMetrics,
+                domain::conversation_metrics::Monologue,
+                domain::conversation_metrics::SpeakerMetrics,
+                domain::dead_letter_events::Model,
+                domain::event_log::EventPage,
+                domain::event_store::Model,
+                domain::goal_templates::Milestone,
+                domain::goal_templates::Model,
                 domain::goals::Model,
+                domain::journal_entries::Model,
+                domain::legal_holds::Model,
+                domain::organization::OrganizationStats,
                 domain::jwts::Jwt,
+                domain::library::AssignmentWithItem,
+                domain::library_assignments::Model,
+                domain::library_item_kind::LibraryItemKind,
+                domain::library_items::Model,
                 domain::notes::Model,
+                domain::oauth_clients::Model,
+                domain::oauth_server::AuthorizedApp,
+                domain::oauth_server::CreatedOAuthClient,
+                domain::organization_ai_settings::Model,
+                domain::organization_ai_settings::TaskSetting,
+                domain::organization_ai_settings::TaskSettings,
+                domain::organization_api_quotas::Model,
+                domain::organization_holidays::Model,
+                domain::organization_transcription_vocabularies::Model,
                 domain::organizations::Model,
+                domain::out_of_office_periods::Model,
                 domain::meeting_provider::Provider,
+                domain::mentions::Model,
+                domain::progress_report_settings::Model,
+                domain::progress_reports::Model,
+                domain::prompt_key::PromptKey,
+                domain::prompt_templates::Model,
+                domain::push_subscriptions::Model,
+                domain::question_quality_summaries::Model,
+                domain::reaction::ReactionCount,
+                domain::reactions::Model,
+                domain::resource_type::ResourceType,
+                domain::resource_view::AnnotatedAction,
+                domain::resource_view::AnnotatedNote,
+                domain::scim::CreatedScimToken,
+                domain::scim_tokens::Model,
+                domain::session_record_status::SessionRecordStatus,
+                domain::session_records::Model,
+                domain::attachments::Model,
+                crate::controller::coaching_session::attachment_controller::CreateParams,
+                domain::session_request_status::SessionRequestStatus,
+                domain::session_requests::Model,
+                domain::session_requests::StatusChange,
+                domain::session_requests::TimeWindow,
+                domain::session_type::SessionTypeUsage,
+                domain::session_types::Model,
                 domain::status::Status,
+                domain::theme_reports::Model,
+                domain::theme_reports::Theme,
                 domain::user::Credentials,
+                domain::user_locks::Model,
                 domain::users::Model,
+                domain::warehouse_checkpoints::Model,
+                domain::webhook_subscription::CreatedWebhookSubscription,
+                domain::webhook_subscriptions::Model,
+                params::action_work_log::CreateParams,
+                params::admin::LockParams,
+                params::admin::ReassignParams,
+                params::admin::BackfillParams,
+                params::action_work_log::StartParams,
+                params::action_work_log::StopParams,
                 params::coaching_session::UpdateParams,
+                params::goal_template::InstantiateParams,
+                params::library::AssignParams,
+                params::push::CreateParams,
+                params::push::KeysParams,
+                params::reaction::CreateParams,
+                params::resource_view::CreateParams,
+                params::session_request::AcceptParams,
+                params::session_request::DeclineParams,
+                params::user::LockParams,
+                params::user::AcceptPolicyParams,
                 params::user::UpdateParams,
                 params::user::coaching_session::GroupByParam,
             )
@@ -192,7 +476,8 @@ struct ApiDoc;
 struct SecurityAddon;
 
 // Defines our cookie session based authentication requirement for gaining access to our
-// API endpoints for OpenAPI.
+// API endpoints for OpenAPI, and the HTTP Basic client authentication third-party apps
+// use at the OAuth token endpoints.
 impl Modify for SecurityAddon {
     fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
         if let Some(components) = openapi.components.as_mut() {
@@ -202,24 +487,72 @@ impl Modify for SecurityAddon {
                     "id",
                     "Session id value returned from successful login via Set-Cookie header",
                 ))),
-            )
+            );
+            components.add_security_scheme(
+                "client_auth",
+                SecurityScheme::Http(
+                    HttpBuilder::new()
+                        .scheme(HttpAuthScheme::Basic)
+                        .description(Some(
+                            "An OAuth client's ID and secret, as username and password",
+                        ))
+                        .build(),
+                ),
+            );
         }
     }
 }
 
 pub fn define_routes(app_state: AppState) -> Router {
-    Router::new()
-        .merge(sse_routes(app_state.clone()))
+    let routes = Router::new()
         .merge(action_routes(app_state.clone()))
+        .merge(action_work_log_routes(app_state.clone()))
         .merge(agreement_routes(app_state.clone()))
         .merge(health_routes())
+        .merge(metrics_routes(app_state.clone()))
         .merge(organization_routes(app_state.clone()))
         .merge(note_routes(app_state.clone()))
+        .merge(resource_view_routes(app_state.clone()))
+        .merge(reaction_routes(app_state.clone()))
         .merge(organization_coaching_relationship_routes(app_state.clone()))
         .merge(organization_user_routes(app_state.clone()))
+        .merge(organization_ai_settings_routes(app_state.clone()))
+        .merge(organization_logo_routes(app_state.clone()))
+        .merge(organization_api_usage_routes(app_state.for_heavy_queries()))
+        .merge(organization_audit_routes(app_state.for_heavy_queries()))
+        .merge(organization_transcription_vocabulary_routes(
+            app_state.clone(),
+        ))
+        .merge(organization_goal_template_routes(app_state.clone()))
+        .merge(organization_session_type_routes(app_state.clone()))
+        .merge(organization_holiday_routes(app_state.clone()))
+        .merge(organization_session_type_analytics_routes(
+            app_state.for_heavy_queries(),
+        ))
+        .merge(organization_library_item_routes(app_state.clone()))
+        .merge(organization_webhook_subscription_routes(app_state.clone()))
+        .merge(webhook_event_type_routes(app_state.clone()))
+        .merge(batch_routes(app_state.clone()))
+        .merge(search_routes(app_state.for_heavy_queries()))
+        .merge(session_pulse_routes(app_state.clone()))
+        .merge(organization_session_pulse_analytics_routes(
+            app_state.for_heavy_queries(),
+        ))
+        .merge(organization_relationship_health_routes(app_state.clone()))
+        .merge(library_assignment_routes(app_state.clone()))
+        .merge(journal_entry_routes(app_state.clone()))
+        .merge(session_request_routes(app_state.clone()))
+        .merge(coaching_relationship_ai_routes(
+            app_state.for_heavy_queries(),
+        ))
         .merge(goal_routes(app_state.clone()))
         .merge(coaching_session_goal_routes(app_state.clone()))
         .merge(coaching_session_meeting_recording_routes(app_state.clone()))
+        .merge(coaching_session_prep_routes(app_state.clone()))
+        .merge(coaching_session_record_routes(app_state.clone()))
+        .merge(coaching_session_attachment_routes(app_state.clone()))
+        .merge(coaching_session_note_typing_routes(app_state.clone()))
+        .merge(coaching_session_estimate_routes(app_state.clone()))
         .merge(coaching_session_topic_routes(app_state.clone()))
         .merge(coaching_session_transcription_routes(app_state.clone()))
         .merge(coaching_session_transcription_segment_routes(
@@ -228,32 +561,100 @@ pub fn define_routes(app_state: AppState) -> Router {
         .merge(webhook_routes(app_state.clone()))
         .merge(user_routes(app_state.clone()))
         .merge(oauth_routes(app_state.clone()))
+        .merge(oauth_server_routes(app_state.clone()))
+        .merge(organization_oauth_client_routes(app_state.clone()))
+        .merge(user_authorized_app_routes(app_state.clone()))
+        .merge(push_routes(app_state.clone()))
+        .merge(user_push_subscription_routes(app_state.clone()))
+        .merge(user_out_of_office_routes(app_state.clone()))
+        .merge(user_presence_routes(app_state.clone()))
+        .merge(user_remembered_device_routes(app_state.clone()))
+        .merge(user_policy_routes(app_state.clone()))
+        .merge(organization_scim_token_routes(app_state.clone()))
+        .merge(scim_routes(app_state.clone()))
         .merge(user_password_routes(app_state.clone()))
         .merge(user_organizations_routes(app_state.clone()))
         .merge(user_actions_routes(app_state.clone()))
         .merge(user_coaching_sessions_routes(app_state.clone()))
+        .merge(user_calendar_feed_routes(app_state.clone()))
         .merge(user_goals_routes(app_state.clone()))
+        .merge(user_mentions_routes(app_state.clone()))
+        .merge(user_question_quality_routes(app_state.for_heavy_queries()))
+        .merge(user_coaching_log_routes(app_state.for_heavy_queries()))
+        .merge(user_progress_report_settings_routes(app_state.clone()))
         .merge(user_coaching_relationships_routes(app_state.clone()))
         .merge(magic_link_routes(app_state.clone()))
         .merge(password_reset_routes(app_state.clone()))
-        .merge(user_session_routes())
+        .merge(bootstrap_routes(app_state.clone()))
+        .merge(user_session_routes(app_state.clone()))
         .merge(user_session_protected_routes(app_state.clone()))
         .merge(coaching_sessions_routes(app_state.clone()))
         .merge(coaching_session_series_routes(app_state.clone()))
         .merge(jwt_routes(app_state.clone()))
         .merge(tiptap_metrics_routes(app_state.clone()))
-        // **** FIXME: protect the OpenAPI web UI
-        .merge(RapiDoc::with_openapi("/api-docs/openapi2.json", ApiDoc::openapi()).path("/rapidoc"))
+        .merge(prompt_template_routes(app_state.clone()))
+        .merge(domain_event_routes(app_state.for_heavy_queries()))
+        .merge(coaching_relationship_activity_routes(
+            app_state.for_heavy_queries(),
+        ))
+        .merge(dead_letter_routes(app_state.clone()))
+        .merge(legal_hold_routes(app_state.clone()))
+        .merge(admin_routes(app_state.clone()))
+        .merge(sse_admin_routes(app_state.clone()))
+        .merge(runtime_config_admin_routes(app_state.clone()))
+        .merge(api_docs_routes(app_state.clone()));
+
+    // Rate limited per user (per IP before login). Layers only wrap the routes
+    // already added, so the SSE routes, merged after, are exempt: a client holds
+    // their connections open rather than making request after request.
+    let routes = match PerUserThrottle::from_config(&app_state.config) {
+        Some(throttle) => routes.layer(throttle.into_layer()),
+        None => routes,
+    };
+
+    routes
+        .merge(sse_routes(app_state))
         .fallback_service(static_routes())
 }
 
+/// /rapidoc and /api-docs/openapi2.json - the OpenAPI web UI and the spec it renders,
+/// served to whoever the `api_docs_access` setting allows
+fn api_docs_routes(app_state: AppState) -> Router {
+    let api_docs = || {
+        Router::new().merge(
+            RapiDoc::with_openapi("/api-docs/openapi2.json", ApiDoc::openapi()).path("/rapidoc"),
+        )
+    };
+
+    match app_state.config.api_docs_access() {
+        ApiDocsAccess::Public => api_docs(),
+        ApiDocsAccess::SuperAdmin => api_docs()
+            .route_layer(from_fn_with_state(
+                app_state.clone(),
+                protect::api_docs::admin_only,
+            ))
+            .route_layer(from_fn(require_auth)),
+        ApiDocsAccess::Disabled => Router::new(),
+    }
+}
+
 fn action_routes(app_state: AppState) -> Router {
     Router::new()
         .route("/actions", post(action_controller::create))
+        .route("/actions/bulk", post(action_controller::bulk))
         .route("/actions/:id", put(action_controller::update))
         .route("/actions/:id", get(action_controller::read))
         .route("/actions/:id/status", put(action_controller::update_status))
         .route("/actions/:id", delete(action_controller::delete))
+        .merge(
+            // POST /actions/:id/restore
+            Router::new()
+                .route("/actions/:id/restore", post(action_controller::restore))
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::actions::restore,
+                )),
+        )
         .merge(
             // GET /actions
             Router::new()
@@ -282,6 +683,18 @@ fn agreement_routes(app_state: AppState) -> Router {
         )
         .route("/agreements/:id", get(agreement_controller::read))
         .route("/agreements/:id", delete(agreement_controller::delete))
+        .merge(
+            // POST /agreements/:id/restore
+            Router::new()
+                .route(
+                    "/agreements/:id/restore",
+                    post(agreement_controller::restore),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::agreements::restore,
+                )),
+        )
         .route_layer(from_fn(require_auth))
         .with_state(app_state)
 }
@@ -350,6 +763,18 @@ pub fn coaching_sessions_routes(app_state: AppState) -> Router {
                     protect::coaching_sessions::delete,
                 )),
         )
+        .merge(
+            // POST /coaching_sessions/:id/restore
+            Router::new()
+                .route(
+                    "/coaching_sessions/:id/restore",
+                    post(coaching_session_controller::restore),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::coaching_sessions::restore,
+                )),
+        )
         .route_layer(from_fn(require_auth))
         .with_state(app_state)
 }
@@ -411,10 +836,208 @@ pub fn tiptap_metrics_routes(app_state: AppState) -> Router {
         .with_state(app_state)
 }
 
+/// /admin/prompt_templates/* - SuperAdmin-only management of AI system prompts
+fn prompt_template_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/admin/prompt_templates/:key",
+            get(prompt_template_controller::read).post(prompt_template_controller::create),
+        )
+        .route(
+            "/admin/prompt_templates/:key/active",
+            put(prompt_template_controller::activate),
+        )
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            protect::prompt_templates::admin_only,
+        ))
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+/// /admin/events - SuperAdmin-only read access to the domain event store
+fn domain_event_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route("/admin/events", get(domain_event_controller::index))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            protect::domain_events::admin_only,
+        ))
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+// CoachingRelationshipAccess extractor handles participant auth
+fn coaching_relationship_activity_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/coaching_relationships/:relationship_id/activity",
+            get(domain_event_controller::activity),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn dead_letter_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route("/admin/dead_letters", get(dead_letter_controller::index))
+        .route(
+            "/admin/dead_letters/:id/requeue",
+            post(dead_letter_controller::requeue),
+        )
+        .route(
+            "/admin/dead_letters/:id/replay",
+            post(dead_letter_controller::replay),
+        )
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            protect::dead_letters::admin_only,
+        ))
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn legal_hold_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/admin/legal_holds",
+            get(legal_hold_controller::index).post(legal_hold_controller::create),
+        )
+        .route(
+            "/admin/legal_holds/:id/release",
+            post(legal_hold_controller::release),
+        )
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            protect::legal_holds::admin_only,
+        ))
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+/// /admin/users, /admin/coaching_relationships, /admin/organizations and
+/// /admin/warehouse_export - SuperAdmin-only platform administration
+fn admin_routes(app_state: AppState) -> Router {
+    Router::new()
+        .merge(
+            // GET /admin/users
+            Router::new()
+                .route("/admin/users", get(admin::user_controller::index))
+                .route_layer(from_fn_with_state(app_state.clone(), protect::admin::users)),
+        )
+        .merge(
+            // POST /admin/users/:user_id/lock
+            // POST /admin/users/:user_id/unlock
+            Router::new()
+                .route(
+                    "/admin/users/:user_id/lock",
+                    post(admin::user_controller::lock),
+                )
+                .route(
+                    "/admin/users/:user_id/unlock",
+                    post(admin::user_controller::unlock),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::sudo::required,
+                ))
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::admin::lock_user,
+                )),
+        )
+        .merge(
+            // PUT /admin/coaching_relationships/:id/participants
+            Router::new()
+                .route(
+                    "/admin/coaching_relationships/:id/participants",
+                    put(admin::coaching_relationship_controller::reassign),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::sudo::required,
+                ))
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::admin::reassign_coaching_relationship,
+                )),
+        )
+        .merge(
+            // GET /admin/organizations/:id/stats
+            Router::new()
+                .route(
+                    "/admin/organizations/:id/stats",
+                    get(admin::organization_controller::stats),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::admin::organization_stats,
+                )),
+        )
+        .merge(
+            // GET /admin/warehouse_export
+            // POST /admin/warehouse_export/backfill
+            Router::new()
+                .route(
+                    "/admin/warehouse_export",
+                    get(admin::warehouse_export_controller::index),
+                )
+                .route(
+                    "/admin/warehouse_export/backfill",
+                    post(admin::warehouse_export_controller::backfill).route_layer(
+                        from_fn_with_state(app_state.clone(), protect::sudo::required),
+                    ),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::admin::warehouse_export,
+                )),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+/// /admin/sse/* - SuperAdmin-only SSE delivery diagnostics
+fn sse_admin_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/admin/sse/connections",
+            get(sse_connection_controller::index),
+        )
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            protect::sse_connections::admin_only,
+        ))
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+/// /admin/config/* - SuperAdmin-only reload of the runtime settings
+fn runtime_config_admin_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/admin/config/reload",
+            post(runtime_config_controller::reload),
+        )
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            protect::runtime_config::admin_only,
+        ))
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
 fn health_routes() -> Router {
     Router::new().route("/health", get(health_check_controller::health_check))
 }
 
+// Scraped by Prometheus with a bearer token rather than a user session
+fn metrics_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route("/metrics", get(metrics_controller::read))
+        .with_state(app_state)
+}
+
 fn note_routes(app_state: AppState) -> Router {
     Router::new()
         .route("/notes", post(note_controller::create))
@@ -426,6 +1049,16 @@ fn note_routes(app_state: AppState) -> Router {
                 .route_layer(from_fn_with_state(app_state.clone(), protect::notes::index)),
         )
         .route("/notes/:id", get(note_controller::read))
+        .route("/notes/:id", delete(note_controller::delete))
+        .merge(
+            // POST /notes/:id/restore
+            Router::new()
+                .route("/notes/:id/restore", post(note_controller::restore))
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::notes::restore,
+                )),
+        )
         .route_layer(from_fn(require_auth))
         .with_state(app_state)
 }
@@ -437,6 +1070,10 @@ fn organization_coaching_relationship_routes(app_state: AppState) -> Router {
             "/organizations/:organization_id/coaching_relationships",
             post(organization::coaching_relationship_controller::create),
         )
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            protect::sudo::required,
+        ))
         .route_layer(from_fn_with_state(
             app_state.clone(),
             protect::organizations::coaching_relationships::create,
@@ -456,6 +1093,20 @@ fn organization_coaching_relationship_routes(app_state: AppState) -> Router {
             "/organizations/:organization_id/coaching_relationships/:relationship_id/goal_progress",
             get(organization::coaching_relationship_controller::goal_progress),
         )
+        .route(
+            "/organizations/:organization_id/coaching_relationships/:relationship_id/action_streaks",
+            get(organization::coaching_relationship_controller::action_streaks),
+        )
+        .route(
+            "/organizations/:organization_id/coaching_relationships/:relationship_id/conversation_metrics",
+            get(organization::coaching_relationship_controller::conversation_metrics),
+        )
+        // PUT /organizations/:organization_id/coaching_relationships/:relationship_id/pro_bono
+        // CoachingRelationshipAccess extractor handles participant auth; only the coach may set it
+        .route(
+            "/organizations/:organization_id/coaching_relationships/:relationship_id/pro_bono",
+            put(organization::coaching_relationship_controller::update_pro_bono),
+        )
         // GET /organizations/:organization_id/coaching_relationships/actions
         // Batch endpoint — returns actions across all coaching relationships
         // where the authenticated user is the coach, with optional assignee filter
@@ -489,6 +1140,10 @@ fn organization_user_routes(app_state: AppState) -> Router {
                     "/organizations/:organization_id/users",
                     post(organization::user_controller::create),
                 )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::sudo::required,
+                ))
                 .route_layer(from_fn_with_state(
                     app_state.clone(),
                     protect::organizations::users::create,
@@ -518,95 +1173,757 @@ fn organization_user_routes(app_state: AppState) -> Router {
                     protect::organizations::users::delete,
                 )),
         )
-        .route_layer(from_fn(require_auth))
-        .with_state(app_state)
-}
-pub fn organization_routes(app_state: AppState) -> Router {
-    Router::new()
-        // The goal will be able to do something like the follow Node.js code does for
-        // versioning: https://www.codemzy.com/blog/nodejs-api-versioning
-        // except we can use axum-extras `or` like is show here:
-        // https://gist.github.com/davidpdrsn/eb4e703e7e068ece3efd975b8f6bc340#file-content_type_or-rs-L17
-        .route("/organizations", get(organization_controller::index))
-        .route("/organizations/:id", get(organization_controller::read))
-        .route("/organizations", post(organization_controller::create))
-        .route("/organizations/:id", put(organization_controller::update))
-        .route(
-            "/organizations/:id",
-            delete(organization_controller::delete),
-        )
-        .route(
-            "/organizations/:id/archive",
-            post(organization_controller::archive),
-        )
-        .route(
-            "/organizations/:id/unarchive",
-            post(organization_controller::unarchive),
-        )
-        .route_layer(from_fn(require_auth))
-        .with_state(app_state)
-}
-
-pub fn goal_routes(app_state: AppState) -> Router {
-    Router::new()
-        .route("/goals", post(goal_controller::create))
         .merge(
-            // GET /goals — protected by coaching_relationship_id query param
+            // POST /organizations/:organization_id/users/:user_id/lock
             Router::new()
-                .route("/goals", get(goal_controller::index))
-                .route_layer(from_fn_with_state(app_state.clone(), protect::goals::index)),
+                .route(
+                    "/organizations/:organization_id/users/:user_id/lock",
+                    post(organization::user_controller::lock),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::sudo::required,
+                ))
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::organizations::users::lock,
+                )),
         )
         .merge(
-            // Routes protected by goal :id path param
+            // POST /organizations/:organization_id/users/:user_id/unlock
             Router::new()
-                .route("/goals/:id", put(goal_controller::update))
-                .route("/goals/:id", delete(goal_controller::delete))
-                .route("/goals/:id", get(goal_controller::read))
-                .route("/goals/:id/status", put(goal_controller::update_status))
                 .route(
-                    "/goals/:id/sessions",
-                    get(goal_controller::coaching_sessions_by_goal),
+                    "/organizations/:organization_id/users/:user_id/unlock",
+                    post(organization::user_controller::unlock),
                 )
-                .route("/goals/:id/progress", get(goal_controller::progress))
-                .route_layer(from_fn_with_state(app_state.clone(), protect::goals::by_id)),
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::sudo::required,
+                ))
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::organizations::users::unlock,
+                )),
         )
         .route_layer(from_fn(require_auth))
         .with_state(app_state)
 }
-
-fn coaching_session_goal_routes(app_state: AppState) -> Router {
+fn organization_ai_settings_routes(app_state: AppState) -> Router {
     Router::new()
+        // GET /organizations/:organization_id/ai_settings
+        // OrganizationMemberAccess extractor handles membership auth
         .route(
-            "/coaching_sessions/:coaching_session_id/goals",
-            post(coaching_session::goal_controller::create),
-        )
-        .route(
-            "/coaching_sessions/:coaching_session_id/goals/:id",
-            delete(coaching_session::goal_controller::delete),
+            "/organizations/:organization_id/ai_settings",
+            get(organization::ai_settings_controller::read),
         )
         .merge(
-            // GET goals by session — protected by coaching_session_id path param
+            // PUT /organizations/:organization_id/ai_settings
             Router::new()
                 .route(
-                    "/coaching_sessions/:coaching_session_id/goals",
-                    get(coaching_session::goal_controller::index),
+                    "/organizations/:organization_id/ai_settings",
+                    put(organization::ai_settings_controller::update),
                 )
                 .route_layer(from_fn_with_state(
                     app_state.clone(),
-                    protect::goals::by_coaching_session_id,
+                    protect::organizations::ai_settings::update,
                 )),
         )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn organization_logo_routes(app_state: AppState) -> Router {
+    Router::new()
         .merge(
-            // GET batch session goals — protected by relationship or session ownership
+            // POST /organizations/:organization_id/logo
             Router::new()
                 .route(
-                    "/coaching_sessions/goals",
-                    get(coaching_session::goal_controller::batch_index),
+                    "/organizations/:organization_id/logo",
+                    post(organization::logo_controller::create),
                 )
                 .route_layer(from_fn_with_state(
                     app_state.clone(),
-                    protect::goals::batch_by_session,
-                )),
+                    protect::organizations::logo::create,
+                ))
+                .route_layer(from_fn(require_auth)),
+        )
+        // GET /organizations/:organization_id/logo/:file_name
+        // Public: logos are shown before sign-in and in emails, and cached by CDNs
+        .route(
+            "/organizations/:organization_id/logo/:file_name",
+            get(organization::logo_controller::read),
+        )
+        .with_state(app_state)
+}
+
+fn organization_api_usage_routes(app_state: AppState) -> Router {
+    Router::new()
+        .merge(
+            // GET /organizations/:organization_id/api_usage
+            Router::new()
+                .route(
+                    "/organizations/:organization_id/api_usage",
+                    get(organization::api_usage_controller::read),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::organizations::api_usage::read,
+                )),
+        )
+        .merge(
+            // GET/PUT/DELETE /organizations/:organization_id/api_quota
+            Router::new()
+                .route(
+                    "/organizations/:organization_id/api_quota",
+                    get(organization::api_usage_controller::read_quota)
+                        .put(organization::api_usage_controller::update_quota)
+                        .delete(organization::api_usage_controller::delete_quota),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::organizations::api_usage::quota,
+                )),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn organization_audit_routes(app_state: AppState) -> Router {
+    Router::new()
+        // GET /organizations/:organization_id/audit
+        .route(
+            "/organizations/:organization_id/audit",
+            get(organization::audit_controller::index),
+        )
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            protect::organizations::audit::read,
+        ))
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn organization_transcription_vocabulary_routes(app_state: AppState) -> Router {
+    Router::new()
+        // GET /organizations/:organization_id/transcription_vocabulary
+        // OrganizationMemberAccess extractor handles membership auth
+        .route(
+            "/organizations/:organization_id/transcription_vocabulary",
+            get(organization::transcription_vocabulary_controller::read),
+        )
+        .merge(
+            // PUT /organizations/:organization_id/transcription_vocabulary
+            Router::new()
+                .route(
+                    "/organizations/:organization_id/transcription_vocabulary",
+                    put(organization::transcription_vocabulary_controller::update),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::organizations::transcription_vocabulary::update,
+                )),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn organization_goal_template_routes(app_state: AppState) -> Router {
+    Router::new()
+        // GET /organizations/:organization_id/goal_templates
+        // OrganizationMemberAccess extractor handles membership auth
+        .route(
+            "/organizations/:organization_id/goal_templates",
+            get(organization::goal_template_controller::index),
+        )
+        .merge(
+            // POST /organizations/:organization_id/goal_templates
+            Router::new()
+                .route(
+                    "/organizations/:organization_id/goal_templates",
+                    post(organization::goal_template_controller::create),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::organizations::goal_templates::create,
+                )),
+        )
+        .merge(
+            // PUT/DELETE /organizations/:organization_id/goal_templates/:template_id
+            Router::new()
+                .route(
+                    "/organizations/:organization_id/goal_templates/:template_id",
+                    put(organization::goal_template_controller::update)
+                        .delete(organization::goal_template_controller::delete),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::organizations::goal_templates::by_id,
+                )),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn webhook_event_type_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/webhook_event_types",
+            get(webhook_event_type_controller::index),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+// Each record is authorized inside the handler, so a batch can partially succeed.
+fn batch_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route("/batch/:resource", post(batch_controller::fetch))
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn search_routes(app_state: AppState) -> Router {
+    Router::new()
+        // GET /search
+        // Results are scoped to the authenticated user's relationships
+        .route("/search", get(search_controller::search))
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+// CoachingSessionAccess and CoachingRelationshipAccess extractors handle participant auth
+// on the session and relationship routes here
+fn session_pulse_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/coaching_sessions/:coaching_session_id/pulse",
+            get(session_pulse_controller::read).put(session_pulse_controller::update),
+        )
+        .route(
+            "/coaching_relationships/:relationship_id/pulse_summary",
+            get(session_pulse_controller::summary),
+        )
+        .merge(
+            Router::new()
+                .route(
+                    "/users/:user_id/session_pulses",
+                    get(session_pulse_controller::pending),
+                )
+                .route_layer(from_fn_with_state(app_state.clone(), protect::users::read)),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn organization_session_pulse_analytics_routes(app_state: AppState) -> Router {
+    Router::new()
+        // GET /organizations/:organization_id/session_pulses/analytics
+        .route(
+            "/organizations/:organization_id/session_pulses/analytics",
+            get(session_pulse_controller::analytics),
+        )
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            protect::organizations::session_pulses::by_organization,
+        ))
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn organization_relationship_health_routes(app_state: AppState) -> Router {
+    Router::new()
+        .merge(
+            // GET/PUT /organizations/:organization_id/relationship_health_settings
+            // GET /organizations/:organization_id/relationship_health
+            Router::new()
+                .route(
+                    "/organizations/:organization_id/relationship_health_settings",
+                    get(organization::relationship_health_controller::read_settings)
+                        .put(organization::relationship_health_controller::update_settings),
+                )
+                .route(
+                    "/organizations/:organization_id/relationship_health",
+                    get(organization::relationship_health_controller::index),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::organizations::relationship_health::by_organization,
+                )),
+        )
+        .merge(
+            // GET /organizations/:organization_id/relationship_health/:relationship_id
+            Router::new()
+                .route(
+                    "/organizations/:organization_id/relationship_health/:relationship_id",
+                    get(organization::relationship_health_controller::history),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::organizations::relationship_health::by_relationship,
+                )),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+// Every route here is admin-only: subscriptions expose where the organization's events go.
+fn organization_webhook_subscription_routes(app_state: AppState) -> Router {
+    Router::new()
+        .merge(
+            // GET/POST /organizations/:organization_id/webhooks
+            Router::new()
+                .route(
+                    "/organizations/:organization_id/webhooks",
+                    get(organization::webhook_subscription_controller::index).merge(
+                        post(organization::webhook_subscription_controller::create).route_layer(
+                            from_fn_with_state(app_state.clone(), protect::sudo::required),
+                        ),
+                    ),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::organizations::webhook_subscriptions::index,
+                )),
+        )
+        .merge(
+            // PUT/DELETE /organizations/:organization_id/webhooks/:webhook_id
+            Router::new()
+                .route(
+                    "/organizations/:organization_id/webhooks/:webhook_id",
+                    put(organization::webhook_subscription_controller::update)
+                        .delete(organization::webhook_subscription_controller::delete)
+                        .route_layer(from_fn_with_state(
+                            app_state.clone(),
+                            protect::sudo::required,
+                        )),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::organizations::webhook_subscriptions::by_id,
+                )),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn organization_oauth_client_routes(app_state: AppState) -> Router {
+    Router::new()
+        .merge(
+            // GET/POST /organizations/:organization_id/oauth_clients
+            Router::new()
+                .route(
+                    "/organizations/:organization_id/oauth_clients",
+                    get(organization::oauth_client_controller::index).merge(
+                        post(organization::oauth_client_controller::create).route_layer(
+                            from_fn_with_state(app_state.clone(), protect::sudo::required),
+                        ),
+                    ),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::organizations::oauth_clients::index,
+                )),
+        )
+        .merge(
+            // DELETE /organizations/:organization_id/oauth_clients/:client_id
+            Router::new()
+                .route(
+                    "/organizations/:organization_id/oauth_clients/:client_id",
+                    delete(organization::oauth_client_controller::delete).route_layer(
+                        from_fn_with_state(app_state.clone(), protect::sudo::required),
+                    ),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::organizations::oauth_clients::by_id,
+                )),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn organization_scim_token_routes(app_state: AppState) -> Router {
+    Router::new()
+        // GET/POST/DELETE /organizations/:organization_id/scim_token
+        .route(
+            "/organizations/:organization_id/scim_token",
+            get(organization::scim_token_controller::read).merge(
+                post(organization::scim_token_controller::create)
+                    .delete(organization::scim_token_controller::delete)
+                    .route_layer(from_fn_with_state(
+                        app_state.clone(),
+                        protect::sudo::required,
+                    )),
+            ),
+        )
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            protect::organizations::scim_token::manage,
+        ))
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+// Identity providers authenticate with their organization's SCIM token (see the
+// `ScimOrganization` extractor) rather than a session.
+fn scim_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/scim/v2/ServiceProviderConfig",
+            get(scim::service_provider_config_controller::read),
+        )
+        .route(
+            "/scim/v2/Users",
+            get(scim::user_controller::index).post(scim::user_controller::create),
+        )
+        .route(
+            "/scim/v2/Users/:id",
+            get(scim::user_controller::read)
+                .put(scim::user_controller::update)
+                .patch(scim::user_controller::patch)
+                .delete(scim::user_controller::delete),
+        )
+        .route(
+            "/scim/v2/Groups",
+            get(scim::group_controller::index).post(scim::group_controller::create),
+        )
+        .route(
+            "/scim/v2/Groups/:id",
+            get(scim::group_controller::read)
+                .put(scim::group_controller::update)
+                .patch(scim::group_controller::patch),
+        )
+        .with_state(app_state)
+}
+
+fn organization_session_type_routes(app_state: AppState) -> Router {
+    Router::new()
+        // GET /organizations/:organization_id/session_types
+        // OrganizationMemberAccess extractor handles membership auth
+        .route(
+            "/organizations/:organization_id/session_types",
+            get(organization::session_type_controller::index),
+        )
+        .merge(
+            // POST /organizations/:organization_id/session_types
+            Router::new()
+                .route(
+                    "/organizations/:organization_id/session_types",
+                    post(organization::session_type_controller::create),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::organizations::session_types::by_organization,
+                )),
+        )
+        .merge(
+            // PUT/DELETE /organizations/:organization_id/session_types/:session_type_id
+            Router::new()
+                .route(
+                    "/organizations/:organization_id/session_types/:session_type_id",
+                    put(organization::session_type_controller::update)
+                        .delete(organization::session_type_controller::delete),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::organizations::session_types::by_id,
+                )),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn organization_holiday_routes(app_state: AppState) -> Router {
+    Router::new()
+        // GET /organizations/:organization_id/holidays
+        // OrganizationMemberAccess extractor handles membership auth
+        .route(
+            "/organizations/:organization_id/holidays",
+            get(organization::holiday_controller::index),
+        )
+        .merge(
+            // POST /organizations/:organization_id/holidays/import
+            Router::new()
+                .route(
+                    "/organizations/:organization_id/holidays/import",
+                    post(organization::holiday_controller::import),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::organizations::holidays::by_organization,
+                )),
+        )
+        .merge(
+            // DELETE /organizations/:organization_id/holidays/:holiday_id
+            Router::new()
+                .route(
+                    "/organizations/:organization_id/holidays/:holiday_id",
+                    delete(organization::holiday_controller::delete),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::organizations::holidays::by_id,
+                )),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn organization_session_type_analytics_routes(app_state: AppState) -> Router {
+    Router::new()
+        // GET /organizations/:organization_id/session_types/analytics
+        .route(
+            "/organizations/:organization_id/session_types/analytics",
+            get(organization::session_type_controller::analytics),
+        )
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            protect::organizations::session_types::by_organization,
+        ))
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn organization_library_item_routes(app_state: AppState) -> Router {
+    Router::new()
+        // GET /organizations/:organization_id/library_items
+        // OrganizationMemberAccess extractor handles membership auth
+        .route(
+            "/organizations/:organization_id/library_items",
+            get(organization::library_item_controller::index),
+        )
+        .merge(
+            // POST /organizations/:organization_id/library_items
+            Router::new()
+                .route(
+                    "/organizations/:organization_id/library_items",
+                    post(organization::library_item_controller::create),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::organizations::library_items::create,
+                )),
+        )
+        .merge(
+            // PUT/DELETE /organizations/:organization_id/library_items/:item_id
+            Router::new()
+                .route(
+                    "/organizations/:organization_id/library_items/:item_id",
+                    put(organization::library_item_controller::update)
+                        .delete(organization::library_item_controller::delete),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::organizations::library_items::by_id,
+                )),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+// CoachingRelationshipAccess extractor handles participant auth on every route here
+fn library_assignment_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/coaching_relationships/:relationship_id/assignments",
+            post(library_assignment_controller::create).get(library_assignment_controller::index),
+        )
+        .route(
+            "/coaching_relationships/:relationship_id/assignments/:assignment_id/complete",
+            post(library_assignment_controller::complete),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+// CoachingRelationshipAccess extractor handles participant auth on every route here;
+// writes are further limited to the relationship's coachee.
+fn journal_entry_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/coaching_relationships/:relationship_id/journal_entries",
+            get(journal_entry_controller::index),
+        )
+        .route(
+            "/coaching_relationships/:relationship_id/journal_entries/:id",
+            get(journal_entry_controller::read),
+        )
+        .merge(
+            // POST /coaching_relationships/:relationship_id/journal_entries
+            Router::new()
+                .route(
+                    "/coaching_relationships/:relationship_id/journal_entries",
+                    post(journal_entry_controller::create),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::journal_entries::create,
+                )),
+        )
+        .merge(
+            // PUT/DELETE /coaching_relationships/:relationship_id/journal_entries/:id
+            Router::new()
+                .route(
+                    "/coaching_relationships/:relationship_id/journal_entries/:id",
+                    put(journal_entry_controller::update).delete(journal_entry_controller::delete),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::journal_entries::by_id,
+                )),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+// CoachingRelationshipAccess extractor handles participant auth on every route here;
+// requesting is limited to the relationship's coachee and answering to its coach.
+fn session_request_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/coaching_relationships/:relationship_id/session_requests",
+            post(session_request_controller::create).get(session_request_controller::index),
+        )
+        .route(
+            "/coaching_relationships/:relationship_id/session_requests/:id",
+            get(session_request_controller::read),
+        )
+        .route(
+            "/coaching_relationships/:relationship_id/session_requests/:id/accept",
+            post(session_request_controller::accept),
+        )
+        .route(
+            "/coaching_relationships/:relationship_id/session_requests/:id/decline",
+            post(session_request_controller::decline),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn coaching_relationship_ai_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/coaching_relationships/:relationship_id/ai/privacy_level",
+            put(ai_controller::update_privacy_level),
+        )
+        .route(
+            "/coaching_relationships/:relationship_id/ai/chat",
+            post(ai_controller::chat),
+        )
+        .route(
+            "/coaching_relationships/:relationship_id/themes",
+            get(ai_controller::themes),
+        )
+        .route(
+            "/coaching_relationships/:relationship_id/progress_reports",
+            get(ai_controller::progress_reports),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+pub fn organization_routes(app_state: AppState) -> Router {
+    Router::new()
+        // The goal will be able to do something like the follow Node.js code does for
+        // versioning: https://www.codemzy.com/blog/nodejs-api-versioning
+        // except we can use axum-extras `or` like is show here:
+        // https://gist.github.com/davidpdrsn/eb4e703e7e068ece3efd975b8f6bc340#file-content_type_or-rs-L17
+        .route("/organizations", get(organization_controller::index))
+        .route("/organizations/:id", get(organization_controller::read))
+        .route("/organizations", post(organization_controller::create))
+        .route("/organizations/:id", put(organization_controller::update))
+        .route(
+            "/organizations/:id",
+            delete(organization_controller::delete),
+        )
+        .route(
+            "/organizations/:id/archive",
+            post(organization_controller::archive),
+        )
+        .route(
+            "/organizations/:id/unarchive",
+            post(organization_controller::unarchive),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+pub fn goal_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route("/goals", post(goal_controller::create))
+        .merge(
+            // GET /goals — protected by coaching_relationship_id query param
+            Router::new()
+                .route("/goals", get(goal_controller::index))
+                .route_layer(from_fn_with_state(app_state.clone(), protect::goals::index)),
+        )
+        .merge(
+            // Routes protected by goal :id path param
+            Router::new()
+                .route("/goals/:id", put(goal_controller::update))
+                .route("/goals/:id", delete(goal_controller::delete))
+                .route("/goals/:id", get(goal_controller::read))
+                .route("/goals/:id/status", put(goal_controller::update_status))
+                .route(
+                    "/goals/:id/sessions",
+                    get(goal_controller::coaching_sessions_by_goal),
+                )
+                .route("/goals/:id/progress", get(goal_controller::progress))
+                .route("/goals/:id/burndown", get(goal_controller::burndown))
+                .route_layer(from_fn_with_state(app_state.clone(), protect::goals::by_id)),
+        )
+        .merge(
+            // POST /goals/:id/restore
+            Router::new()
+                .route("/goals/:id/restore", post(goal_controller::restore))
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::goals::restore,
+                )),
+        )
+        // POST /coaching_relationships/:relationship_id/overarching_goals/from_template/:template_id
+        // CoachingRelationshipAccess extractor handles participant auth
+        .route(
+            "/coaching_relationships/:relationship_id/overarching_goals/from_template/:template_id",
+            post(goal_controller::create_from_template),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn coaching_session_goal_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/coaching_sessions/:coaching_session_id/goals",
+            post(coaching_session::goal_controller::create),
+        )
+        .route(
+            "/coaching_sessions/:coaching_session_id/goals/:id",
+            delete(coaching_session::goal_controller::delete),
+        )
+        .merge(
+            // GET goals by session — protected by coaching_session_id path param
+            Router::new()
+                .route(
+                    "/coaching_sessions/:coaching_session_id/goals",
+                    get(coaching_session::goal_controller::index),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::goals::by_coaching_session_id,
+                )),
+        )
+        .merge(
+            // GET batch session goals — protected by relationship or session ownership
+            Router::new()
+                .route(
+                    "/coaching_sessions/goals",
+                    get(coaching_session::goal_controller::batch_index),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::goals::batch_by_session,
+                )),
         )
         .route_layer(from_fn(require_auth))
         .with_state(app_state)
@@ -639,6 +1956,10 @@ pub fn user_password_routes(app_state: AppState) -> Router {
             "/users/:id/password",
             put(user::password_controller::update_password),
         )
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            protect::sudo::required,
+        ))
         .route_layer(from_fn_with_state(
             app_state.clone(),
             protect::users::passwords::update_password,
@@ -650,12 +1971,27 @@ pub fn user_password_routes(app_state: AppState) -> Router {
 pub fn user_session_protected_routes(app_state: AppState) -> Router {
     Router::new()
         .route("/delete", delete(user_session_controller::delete))
+        .route("/session/refresh", post(user_session_controller::refresh))
+        .merge(reauthenticate_routes())
         .route_layer(from_fn(require_auth))
         .with_state(app_state)
 }
 
-pub fn user_session_routes() -> Router {
-    Router::new().route("/login", post(user_session_controller::login))
+/// POST /auth/reauthenticate, limited per user like the unauthenticated credential
+/// endpoints are per IP, so a session can't be used to guess its user's password
+fn reauthenticate_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/auth/reauthenticate",
+            post(user_session_controller::reauthenticate),
+        )
+        .layer(PerUserThrottle::new(ThrottlePolicy::AUTH_ENDPOINT).into_layer())
+}
+
+pub fn user_session_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route("/login", post(user_session_controller::login))
+        .with_state(app_state)
 }
 
 fn magic_link_routes(app_state: AppState) -> Router {
@@ -668,115 +2004,383 @@ fn magic_link_routes(app_state: AppState) -> Router {
         .with_state(app_state)
 }
 
-fn password_reset_routes(app_state: AppState) -> Router {
-    // Per-IP rate limit applied to ALL password-reset endpoints. Defends
-    // the endpoint surface against mass scanning (an attacker varying
-    // emails or tokens per request) — orthogonal to the per-email DB rate
-    // limit which defends Alice's inbox from targeted flooding. Both
-    // layers are load-bearing.
-    //
-    // See `web::middleware::throttle` for the policy definition and
-    // `docs/architecture/throttling.md` for the design and trust model.
+fn password_reset_routes(app_state: AppState) -> Router {
+    // Per-IP rate limit applied to ALL password-reset endpoints. Defends
+    // the endpoint surface against mass scanning (an attacker varying
+    // emails or tokens per request) — orthogonal to the per-email DB rate
+    // limit which defends Alice's inbox from targeted flooding. Both
+    // layers are load-bearing.
+    //
+    // See `web::middleware::throttle` for the policy definition and
+    // `docs/architecture/throttling.md` for the design and trust model.
+    Router::new()
+        .route(
+            "/password-reset/request",
+            post(password_reset_controller::request),
+        )
+        .route(
+            "/password-reset/validate",
+            post(password_reset_controller::validate),
+        )
+        .route(
+            "/password-reset/complete",
+            post(password_reset_controller::complete),
+        )
+        .layer(PerIpThrottle::new(ThrottlePolicy::AUTH_ENDPOINT).into_layer())
+        .with_state(app_state)
+}
+
+fn bootstrap_routes(app_state: AppState) -> Router {
+    // Unauthenticated until the platform has its first user, so rate limited like the
+    // other endpoints anyone can call
+    Router::new()
+        .route("/bootstrap", get(bootstrap_controller::read))
+        .route("/bootstrap", post(bootstrap_controller::create))
+        .layer(PerIpThrottle::new(ThrottlePolicy::AUTH_ENDPOINT).into_layer())
+        .with_state(app_state)
+}
+
+fn jwt_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/jwt/generate_collab_token",
+            get(jwt_controller::generate_collab_token),
+        )
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            protect::jwt::generate_collab_token,
+        ))
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn user_organizations_routes(app_state: AppState) -> Router {
+    Router::new()
+        .merge(
+            Router::new()
+                .route(
+                    "/users/:user_id/organizations",
+                    get(user::organization_controller::index),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::users::organizations::index,
+                )),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn user_actions_routes(app_state: AppState) -> Router {
+    Router::new()
+        .merge(
+            Router::new()
+                .route(
+                    "/users/:user_id/actions",
+                    get(user::action_controller::index),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::users::actions::index,
+                )),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn user_coaching_sessions_routes(app_state: AppState) -> Router {
+    Router::new()
+        .merge(
+            Router::new()
+                .route(
+                    "/users/:user_id/coaching_sessions",
+                    get(user::coaching_session_controller::index),
+                )
+                .route(
+                    "/users/:user_id/coaching_sessions/counts",
+                    get(user::coaching_session_controller::counts),
+                )
+                .route(
+                    "/users/:user_id/coaching_sessions/badges",
+                    get(user::coaching_session_controller::badges),
+                )
+                .route(
+                    "/users/:user_id/coaching_sessions/calendar_feed",
+                    get(user::calendar_feed_controller::read)
+                        .post(user::calendar_feed_controller::regenerate)
+                        .route_layer(from_fn_with_state(
+                            app_state.clone(),
+                            protect::sudo::required,
+                        )),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::users::coaching_sessions::index,
+                )),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+/// Calendar apps fetch feeds without cookies; the token in the URL authenticates them.
+fn user_calendar_feed_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/users/:user_id/coaching_sessions.ics",
+            get(user::calendar_feed_controller::feed),
+        )
+        .with_state(app_state)
+}
+
+fn user_goals_routes(app_state: AppState) -> Router {
+    Router::new()
+        .merge(
+            Router::new()
+                .route("/users/:user_id/goals", get(user::goal_controller::index))
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::users::goals::index,
+                )),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn user_mentions_routes(app_state: AppState) -> Router {
+    Router::new()
+        .merge(
+            Router::new()
+                .route(
+                    "/users/:user_id/mentions",
+                    get(user::mention_controller::index),
+                )
+                .route_layer(from_fn_with_state(app_state.clone(), protect::users::read)),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn user_question_quality_routes(app_state: AppState) -> Router {
+    Router::new()
+        .merge(
+            Router::new()
+                .route(
+                    "/users/:user_id/question_quality",
+                    get(user::question_quality_controller::index),
+                )
+                .route_layer(from_fn_with_state(app_state.clone(), protect::users::read)),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn user_coaching_log_routes(app_state: AppState) -> Router {
+    Router::new()
+        .merge(
+            Router::new()
+                .route(
+                    "/users/:user_id/coaching_log",
+                    get(user::coaching_log_controller::read),
+                )
+                .route(
+                    "/users/:user_id/coaching_log/export",
+                    get(user::coaching_log_controller::export).route_layer(from_fn_with_state(
+                        app_state.clone(),
+                        protect::sudo::required,
+                    )),
+                )
+                .route_layer(from_fn_with_state(app_state.clone(), protect::users::read)),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn user_progress_report_settings_routes(app_state: AppState) -> Router {
+    Router::new()
+        .merge(
+            Router::new()
+                .route(
+                    "/users/:user_id/progress_report_settings",
+                    get(user::progress_report_settings_controller::read),
+                )
+                .route_layer(from_fn_with_state(app_state.clone(), protect::users::read)),
+        )
+        .merge(
+            Router::new()
+                .route(
+                    "/users/:user_id/progress_report_settings",
+                    put(user::progress_report_settings_controller::update),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::users::update,
+                )),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn user_authorized_app_routes(app_state: AppState) -> Router {
     Router::new()
-        .route(
-            "/password-reset/request",
-            post(password_reset_controller::request),
-        )
-        .route(
-            "/password-reset/validate",
-            post(password_reset_controller::validate),
+        .merge(
+            Router::new()
+                .route(
+                    "/users/:user_id/authorized_apps",
+                    get(user::authorized_app_controller::index),
+                )
+                .route_layer(from_fn_with_state(app_state.clone(), protect::users::read)),
         )
-        .route(
-            "/password-reset/complete",
-            post(password_reset_controller::complete),
+        .merge(
+            Router::new()
+                .route(
+                    "/users/:user_id/authorized_apps/:client_id",
+                    delete(user::authorized_app_controller::delete),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::users::authorized_apps::delete,
+                )),
         )
-        .layer(PerIpThrottle::new(ThrottlePolicy::AUTH_ENDPOINT).into_layer())
+        .route_layer(from_fn(require_auth))
         .with_state(app_state)
 }
 
-fn jwt_routes(app_state: AppState) -> Router {
+fn push_routes(app_state: AppState) -> Router {
     Router::new()
         .route(
-            "/jwt/generate_collab_token",
-            get(jwt_controller::generate_collab_token),
+            "/push/vapid_public_key",
+            get(push_controller::vapid_public_key),
         )
-        .route_layer(from_fn_with_state(
-            app_state.clone(),
-            protect::jwt::generate_collab_token,
-        ))
         .route_layer(from_fn(require_auth))
         .with_state(app_state)
 }
 
-fn user_organizations_routes(app_state: AppState) -> Router {
+fn user_push_subscription_routes(app_state: AppState) -> Router {
     Router::new()
         .merge(
             Router::new()
                 .route(
-                    "/users/:user_id/organizations",
-                    get(user::organization_controller::index),
+                    "/users/:user_id/push_subscriptions",
+                    get(user::push_subscription_controller::index),
+                )
+                .route_layer(from_fn_with_state(app_state.clone(), protect::users::read)),
+        )
+        .merge(
+            Router::new()
+                .route(
+                    "/users/:user_id/push_subscriptions",
+                    post(user::push_subscription_controller::create),
                 )
                 .route_layer(from_fn_with_state(
                     app_state.clone(),
-                    protect::users::organizations::index,
+                    protect::users::update,
                 )),
         )
-        .route_layer(from_fn(require_auth))
-        .with_state(app_state)
-}
-
-fn user_actions_routes(app_state: AppState) -> Router {
-    Router::new()
         .merge(
             Router::new()
                 .route(
-                    "/users/:user_id/actions",
-                    get(user::action_controller::index),
+                    "/users/:user_id/push_subscriptions/:id",
+                    delete(user::push_subscription_controller::delete),
                 )
                 .route_layer(from_fn_with_state(
                     app_state.clone(),
-                    protect::users::actions::index,
+                    protect::users::push_subscriptions::delete,
                 )),
         )
         .route_layer(from_fn(require_auth))
         .with_state(app_state)
 }
 
-fn user_coaching_sessions_routes(app_state: AppState) -> Router {
+fn user_out_of_office_routes(app_state: AppState) -> Router {
     Router::new()
         .merge(
             Router::new()
                 .route(
-                    "/users/:user_id/coaching_sessions",
-                    get(user::coaching_session_controller::index),
+                    "/users/:user_id/out_of_office",
+                    get(user::out_of_office_controller::index),
                 )
+                .route_layer(from_fn_with_state(app_state.clone(), protect::users::read)),
+        )
+        .merge(
+            Router::new()
                 .route(
-                    "/users/:user_id/coaching_sessions/counts",
-                    get(user::coaching_session_controller::counts),
+                    "/users/:user_id/out_of_office",
+                    post(user::out_of_office_controller::create),
                 )
                 .route_layer(from_fn_with_state(
                     app_state.clone(),
-                    protect::users::coaching_sessions::index,
+                    protect::users::update,
+                )),
+        )
+        .merge(
+            Router::new()
+                .route(
+                    "/users/:user_id/out_of_office/:id",
+                    put(user::out_of_office_controller::update)
+                        .delete(user::out_of_office_controller::delete),
+                )
+                .route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::users::out_of_office::by_id,
                 )),
         )
         .route_layer(from_fn(require_auth))
         .with_state(app_state)
 }
 
-fn user_goals_routes(app_state: AppState) -> Router {
+fn user_remembered_device_routes(app_state: AppState) -> Router {
     Router::new()
         .merge(
             Router::new()
-                .route("/users/:user_id/goals", get(user::goal_controller::index))
+                .route(
+                    "/users/:user_id/remembered_devices",
+                    get(user::remembered_device_controller::index),
+                )
+                .route_layer(from_fn_with_state(app_state.clone(), protect::users::read)),
+        )
+        .merge(
+            Router::new()
+                .route(
+                    "/users/:user_id/remembered_devices/:id",
+                    delete(user::remembered_device_controller::delete),
+                )
                 .route_layer(from_fn_with_state(
                     app_state.clone(),
-                    protect::users::goals::index,
+                    protect::users::remembered_devices::delete,
                 )),
         )
         .route_layer(from_fn(require_auth))
         .with_state(app_state)
 }
 
+fn user_policy_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/users/:user_id/accept_policy",
+            post(user::policy_controller::accept),
+        )
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            protect::users::update,
+        ))
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn user_presence_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/users/:user_id/presence",
+            get(user::presence_controller::read),
+        )
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            protect::users::presence::read,
+        ))
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
 fn user_coaching_relationships_routes(app_state: AppState) -> Router {
     Router::new()
         .merge(
@@ -791,9 +2395,50 @@ fn user_coaching_relationships_routes(app_state: AppState) -> Router {
         .with_state(app_state)
 }
 
+fn resource_view_routes(app_state: AppState) -> Router {
+    Router::new()
+        // POST /views
+        .route("/views", post(resource_view_controller::create))
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn action_work_log_routes(app_state: AppState) -> Router {
+    Router::new()
+        // GET/POST /action_work_logs
+        .route(
+            "/action_work_logs",
+            get(action_work_log_controller::index).post(action_work_log_controller::create),
+        )
+        // POST /action_work_logs/start
+        .route(
+            "/action_work_logs/start",
+            post(action_work_log_controller::start),
+        )
+        // POST /action_work_logs/:id/stop
+        .route(
+            "/action_work_logs/:id/stop",
+            post(action_work_log_controller::stop),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn reaction_routes(app_state: AppState) -> Router {
+    Router::new()
+        // POST /reactions
+        .route("/reactions", post(reaction_controller::create))
+        // DELETE /reactions/:id
+        .route("/reactions/:id", delete(reaction_controller::delete))
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
 fn sse_routes(app_state: AppState) -> Router {
     Router::new()
         .route("/sse", get(sse::handler::sse_handler))
+        // GET /events/poll — long-poll fallback for clients that can't hold an SSE connection
+        .route("/events/poll", get(sse::handler::poll_handler))
         .route_layer(from_fn(require_auth))
         .with_state(app_state)
 }
@@ -821,6 +2466,28 @@ fn oauth_routes(app_state: AppState) -> Router {
         .with_state(app_state)
 }
 
+fn oauth_server_routes(app_state: AppState) -> Router {
+    Router::new()
+        // GET/POST /oauth/authorize (the user's consent)
+        .route(
+            "/oauth/authorize",
+            get(oauth_server_controller::read_authorization)
+                .post(oauth_server_controller::authorize),
+        )
+        .route_layer(from_fn(require_auth))
+        .merge(
+            // Third-party apps authenticate themselves with their client credentials
+            Router::new()
+                .route("/oauth/token", post(oauth_server_controller::token))
+                .route(
+                    "/oauth/introspect",
+                    post(oauth_server_controller::introspect),
+                )
+                .route("/oauth/revoke", post(oauth_server_controller::revoke)),
+        )
+        .with_state(app_state)
+}
+
 fn coaching_session_meeting_recording_routes(app_state: AppState) -> Router {
     Router::new()
         .route(
@@ -833,6 +2500,66 @@ fn coaching_session_meeting_recording_routes(app_state: AppState) -> Router {
         .with_state(app_state)
 }
 
+fn coaching_session_prep_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/coaching_sessions/:coaching_session_id/prep",
+            get(coaching_session::prep_controller::read),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn coaching_session_record_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/coaching_sessions/:coaching_session_id/record",
+            get(coaching_session::record_controller::read),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+// CoachingSessionAccess extractor handles participant auth on every route here
+fn coaching_session_attachment_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/coaching_sessions/:coaching_session_id/attachments",
+            post(coaching_session::attachment_controller::create)
+                .get(coaching_session::attachment_controller::index),
+        )
+        .route(
+            "/coaching_sessions/:coaching_session_id/attachments/:id",
+            delete(coaching_session::attachment_controller::delete),
+        )
+        .route(
+            "/coaching_sessions/:coaching_session_id/attachments/:id/download",
+            get(coaching_session::attachment_controller::download),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn coaching_session_note_typing_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/coaching_sessions/:coaching_session_id/notes/typing",
+            post(coaching_session::note_typing_controller::create),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
+fn coaching_session_estimate_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/coaching_sessions/:coaching_session_id/ai/estimate",
+            get(coaching_session::estimate_controller::read),
+        )
+        .route_layer(from_fn(require_auth))
+        .with_state(app_state)
+}
+
 fn coaching_session_topic_routes(app_state: AppState) -> Router {
     Router::new()
         .route(
@@ -871,6 +2598,18 @@ fn coaching_session_transcription_routes(app_state: AppState) -> Router {
             "/coaching_sessions/:coaching_session_id/transcriptions",
             get(coaching_session::transcription_controller::read),
         )
+        .route(
+            "/coaching_sessions/:coaching_session_id/transcript/chapters",
+            get(coaching_session::transcript_chapter_controller::index),
+        )
+        .route(
+            "/coaching_sessions/:coaching_session_id/conversation_metrics",
+            get(coaching_session::conversation_metrics_controller::read),
+        )
+        .route(
+            "/coaching_sessions/:coaching_session_id/question_quality",
+            get(coaching_session::question_quality_controller::read),
+        )
         .route_layer(from_fn(require_auth))
         .with_state(app_state)
 }
@@ -895,3 +2634,332 @@ fn webhook_routes(app_state: AppState) -> Router {
 pub fn static_routes() -> Router {
     Router::new().nest_service("/", ServeDir::new("./"))
 }
+
+#[cfg(test)]
+// We need to gate seaORM's mock feature behind conditional compilation because
+// the feature removes the Clone trait implementation from seaORM's DatabaseConnection.
+// see https://github.com/SeaQL/sea-orm/issues/830
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use axum::{body::Body, extract::Request, http::StatusCode};
+    use axum_login::{
+        tower_sessions::{Expiry, MemoryStore, SessionManagerLayer},
+        AuthManagerLayerBuilder,
+    };
+    use chrono::Utc;
+    use domain::user::Backend;
+    use domain::{user_roles, users, Id};
+    use password_auth::generate_hash;
+    use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase};
+    use service::config::Config;
+    use std::sync::Arc;
+    use time::Duration;
+    use tower::ServiceExt;
+
+    fn user_with_role(role: users::Role) -> (users::Model, user_roles::Model) {
+        let now = Utc::now();
+        let user = users::Model {
+            id: Id::new_v4(),
+            email: "test@example.com".to_string(),
+            first_name: "Test".to_string(),
+            last_name: "User".to_string(),
+            display_name: Some("Test User".to_string()),
+            password: Some(generate_hash("password123")),
+            github_username: None,
+            github_profile_url: None,
+            timezone: "UTC".to_string(),
+            default_coaching_session_duration_minutes: domain::duration::Duration::default_minutes(
+            ),
+            role: users::Role::User,
+            roles: vec![],
+            invite_status: None,
+            created_at: now.into(),
+            updated_at: now.into(),
+        };
+        let user_role = user_roles::Model {
+            id: Id::new_v4(),
+            role,
+            organization_id: None,
+            user_id: user.id,
+            created_at: now.into(),
+            updated_at: now.into(),
+        };
+        (user, user_role)
+    }
+
+    /// Mocks logging the user in and then looking them up for the next request.
+    fn db_for(user: users::Model, user_role: user_roles::Model) -> DatabaseConnection {
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![(user.clone(), user_role.clone())]])
+            .append_query_results([Vec::<domain::policy_documents::Model>::new()])
+            .append_query_results([vec![(user, user_role)]])
+            .into_connection()
+    }
+
+    fn build_app(api_docs_access: &str, db: DatabaseConnection) -> Router {
+        let db = Arc::new(db);
+        let config = Config::from_args(["test_binary", "--api-docs-access", api_docs_access]);
+        let app_state = AppState::new(
+            service::AppState::new(config, &db),
+            Arc::new(::sse::Manager::default()),
+            domain::events::EventPublisher::default(),
+            None,
+            None,
+        );
+
+        let session_layer = SessionManagerLayer::new(MemoryStore::default())
+            .with_secure(false)
+            .with_expiry(Expiry::OnInactivity(Duration::days(1)))
+            .with_always_save(true);
+        let auth_layer = AuthManagerLayerBuilder::new(Backend::new(&db), session_layer).build();
+
+        Router::new()
+            .route("/login", post(user_session_controller::login))
+            .with_state(app_state.clone())
+            .merge(api_docs_routes(app_state))
+            .layer(auth_layer)
+    }
+
+    /// Logs in with the given reauthentication window, guarding `/sensitive` with sudo mode.
+    fn build_sudo_app(window_seconds: &str, db: DatabaseConnection) -> Router {
+        let db = Arc::new(db);
+        let config = Config::from_args([
+            "test_binary",
+            "--reauthentication-window-seconds",
+            window_seconds,
+        ]);
+        let app_state = AppState::new(
+            service::AppState::new(config, &db),
+            Arc::new(::sse::Manager::default()),
+            domain::events::EventPublisher::default(),
+            None,
+            None,
+        );
+
+        let session_layer = SessionManagerLayer::new(MemoryStore::default())
+            .with_secure(false)
+            .with_expiry(Expiry::OnInactivity(Duration::days(1)))
+            .with_always_save(true);
+        let auth_layer = AuthManagerLayerBuilder::new(Backend::new(&db), session_layer).build();
+
+        Router::new()
+            .route("/login", post(user_session_controller::login))
+            .route(
+                "/sensitive",
+                get(|| async { StatusCode::OK }).route_layer(from_fn_with_state(
+                    app_state.clone(),
+                    protect::sudo::required,
+                )),
+            )
+            .with_state(app_state)
+            .layer(auth_layer)
+    }
+
+    /// Logs in, with the user's session loaded for each of `requests` requests after.
+    fn build_reauthenticate_app(requests: usize) -> Router {
+        let (user, user_role) = user_with_role(users::Role::User);
+        let db = Arc::new(
+            MockDatabase::new(DatabaseBackend::Postgres)
+                .append_query_results([vec![(user.clone(), user_role.clone())]])
+                .append_query_results([Vec::<domain::policy_documents::Model>::new()])
+                .append_query_results(vec![vec![(user, user_role)]; requests])
+                .into_connection(),
+        );
+        let app_state = AppState::new(
+            service::AppState::new(Config::from_args(["test_binary"]), &db),
+            Arc::new(::sse::Manager::default()),
+            domain::events::EventPublisher::default(),
+            None,
+            None,
+        );
+
+        let session_layer = SessionManagerLayer::new(MemoryStore::default())
+            .with_secure(false)
+            .with_expiry(Expiry::OnInactivity(Duration::days(1)))
+            .with_always_save(true);
+        let auth_layer = AuthManagerLayerBuilder::new(Backend::new(&db), session_layer).build();
+
+        Router::new()
+            .route("/login", post(user_session_controller::login))
+            .with_state(app_state.clone())
+            .merge(user_session_protected_routes(app_state))
+            .layer(auth_layer)
+    }
+
+    async fn login_cookie(app: &Router) -> String {
+        let login_request = Request::builder()
+            .uri("/login")
+            .method("POST")
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body(Body::from("email=test@example.com&password=password123"))
+            .unwrap();
+
+        let login_response = app.clone().oneshot(login_request).await.unwrap();
+
+        login_response
+            .headers()
+            .get("set-cookie")
+            .and_then(|c| c.to_str().ok())
+            .expect("Login should return session cookie")
+            .to_string()
+    }
+
+    async fn get_status(app: &Router, uri: &str, cookie: Option<&str>) -> StatusCode {
+        let mut request = Request::builder().uri(uri);
+        if let Some(cookie) = cookie {
+            request = request.header("cookie", cookie);
+        }
+
+        let response = app
+            .clone()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        response.status()
+    }
+
+    #[tokio::test]
+    async fn public_api_docs_are_served_without_a_session() {
+        let app = build_app(
+            "public",
+            MockDatabase::new(DatabaseBackend::Postgres).into_connection(),
+        );
+
+        assert_eq!(get_status(&app, "/rapidoc", None).await, StatusCode::OK);
+        assert_eq!(
+            get_status(&app, "/api-docs/openapi2.json", None).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn disabled_api_docs_are_not_served() {
+        let app = build_app(
+            "disabled",
+            MockDatabase::new(DatabaseBackend::Postgres).into_connection(),
+        );
+
+        assert_eq!(
+            get_status(&app, "/rapidoc", None).await,
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            get_status(&app, "/api-docs/openapi2.json", None).await,
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[tokio::test]
+    async fn super_admin_api_docs_require_a_session() {
+        let app = build_app(
+            "super_admin",
+            MockDatabase::new(DatabaseBackend::Postgres).into_connection(),
+        );
+
+        assert_eq!(
+            get_status(&app, "/rapidoc", None).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            get_status(&app, "/api-docs/openapi2.json", None).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[tokio::test]
+    async fn super_admin_api_docs_are_served_to_super_admins() {
+        let (user, user_role) = user_with_role(users::Role::SuperAdmin);
+        let app = build_app("super_admin", db_for(user, user_role));
+        let cookie = login_cookie(&app).await;
+
+        assert_eq!(
+            get_status(&app, "/rapidoc", Some(&cookie)).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn logging_in_enters_sudo_mode() {
+        let (user, user_role) = user_with_role(users::Role::User);
+        let app = build_sudo_app("900", db_for(user, user_role));
+        let cookie = login_cookie(&app).await;
+
+        assert_eq!(
+            get_status(&app, "/sensitive", Some(&cookie)).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn sudo_mode_ends_after_the_reauthentication_window() {
+        let (user, user_role) = user_with_role(users::Role::User);
+        let app = build_sudo_app("0", db_for(user, user_role));
+        let cookie = login_cookie(&app).await;
+        tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/sensitive")
+                    .header("cookie", cookie)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"Reauthentication required");
+    }
+
+    #[tokio::test]
+    async fn reauthenticating_is_limited_like_the_other_credential_endpoints() {
+        let attempts = ThrottlePolicy::AUTH_ENDPOINT.burst as usize;
+        let app = build_reauthenticate_app(attempts + 1);
+        let cookie = login_cookie(&app).await;
+        let reauthenticate = |body: &'static str| {
+            Request::builder()
+                .uri("/auth/reauthenticate")
+                .method("POST")
+                .header("cookie", &cookie)
+                .header("content-type", "application/x-www-form-urlencoded")
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        // Attempts without a password are refused before hashing one, so the burst is
+        // spent before a token can be replenished
+        for attempt in 0..attempts {
+            let response = app.clone().oneshot(reauthenticate("")).await.unwrap();
+            assert_eq!(
+                response.status(),
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "attempt {attempt} should get through"
+            );
+        }
+
+        let response = app
+            .clone()
+            .oneshot(reauthenticate("password=password123"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[tokio::test]
+    async fn super_admin_api_docs_are_forbidden_to_other_users() {
+        let (user, user_role) = user_with_role(users::Role::User);
+        let app = build_app("super_admin", db_for(user, user_role));
+        let cookie = login_cookie(&app).await;
+
+        assert_eq!(
+            get_status(&app, "/rapidoc", Some(&cookie)).await,
+            StatusCode::FORBIDDEN
+        );
+    }
+}
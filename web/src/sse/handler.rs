@@ -1,40 +1,293 @@
 use crate::extractors::authenticated_user::AuthenticatedUser;
+use crate::params::sse::{PollParams, SseParams};
+use ::sse::connection::ConnectionId;
+use ::sse::filter::EventFilter;
+use ::sse::message::{Event as SseEvent, EventType, Message as SseMessage, MessageScope};
+use ::sse::Manager;
 use async_stream::stream;
-use axum::extract::State;
+use axum::extract::{Query, State};
+use axum::http::{header::RETRY_AFTER, HeaderMap, HeaderName, StatusCode};
 use axum::response::sse::{Event, KeepAlive, Sse};
-use futures::Stream;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use chrono::{DateTime, FixedOffset, Utc};
 use log::*;
+use serde::Serialize;
 use std::convert::Infallible;
-use tokio::sync::mpsc;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+use tower_sessions::Session;
+
+/// The header an `EventSource` sends on reconnect with the ID of the last event it got.
+const LAST_EVENT_ID: HeaderName = HeaderName::from_static("last-event-id");
 
 /// SSE handler that establishes a long-lived connection for real-time updates.
 /// One connection per authenticated user, stays open across page navigation.
+/// A reconnecting client first gets the events it missed since its `Last-Event-ID`.
+///
+/// The stream doesn't keep the login session alive, so it sends a
+/// `session_expiring_soon` event once the session is within
+/// `backend_session_expiry_warning_seconds` of expiring, and closes once it has ended.
+///
+/// With `?events=action.*,note.*`, only those categories of events (and system events)
+/// are sent; a malformed filter is refused with `400`.
+///
+/// Keep-alive comments are sent every `sse_keep_alive_interval_seconds`. The stream is
+/// closed, for the browser to reconnect, once it has sent no event for
+/// `sse_idle_timeout_seconds` or has been open for `sse_max_connection_lifetime_seconds`.
+///
+/// While the server drains for shutdown, new connections are refused with `503`.
 pub(crate) async fn sse_handler(
     AuthenticatedUser(user): AuthenticatedUser,
+    session: Session,
     State(app_state): State<crate::AppState>,
-) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    Query(params): Query<SseParams>,
+    headers: HeaderMap,
+) -> Response {
+    if let Some(reconnect_after) = app_state.sse_manager.draining() {
+        return server_restarting(reconnect_after);
+    }
+    let filter = match params.events.as_deref().map(str::parse::<EventFilter>) {
+        None => EventFilter::default(),
+        Some(Ok(filter)) => filter,
+        Some(Err(e)) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
     info!("Establishing new SSE connection");
 
-    let (tx, mut rx) = mpsc::unbounded_channel();
+    let (tx, mut rx) = app_state.sse_manager.channel();
+
+    // An ID this replica didn't issue just replays nothing
+    let last_event_id = headers
+        .get(LAST_EVENT_ID)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok());
 
     // Register returns the connection_id (convert domain::Id to String)
     let connection_id = app_state
         .sse_manager
-        .register_connection(user.id.to_string(), tx);
+        .register_connection(user.id.to_string(), tx, last_event_id, filter)
+        .await;
+    let registration = Registration {
+        manager: app_state.sse_manager.clone(),
+        connection_id,
+    };
 
     let manager = app_state.sse_manager.clone();
+    let db = app_state.database_connection.clone();
+    let user_id = user.id.to_string();
+    let session_id = session.id().map(|id| id.to_string());
+    let warning = Duration::from_secs(app_state.config.backend_session_expiry_warning_seconds);
+    let expiry = Duration::from_secs(app_state.config.backend_session_expiry_seconds);
+    let idle_timeout = app_state.config.sse_idle_timeout();
+    let closes_at = app_state
+        .config
+        .sse_max_connection_lifetime()
+        .map(|lifetime| Instant::now() + lifetime);
+    let mut idle_at = idle_timeout.map(|timeout| Instant::now() + timeout);
+
+    // The session was just saved by this request, so it can't expire any sooner
+    let mut next_check = Instant::now() + expiry.saturating_sub(warning);
+    let mut warned_for = None;
 
-    // Create the stream - events arrive from the channel
-    // The channel sends Result<Event, Infallible>, so we just pass them through
+    // Create the stream - events arrive from the channel as frames
     let stream = stream! {
-        while let Some(event) = rx.recv().await {
-            yield event;
+        let _registration = registration;
+        loop {
+            let wake = tokio::select! {
+                frame = rx.recv() => Wake::Frame(frame),
+                _ = tokio::time::sleep_until(next_check), if session_id.is_some() => Wake::CheckSession,
+                _ = sleep_until(idle_at) => Wake::Idle,
+                _ = sleep_until(closes_at) => Wake::LifetimeOver,
+            };
+
+            let session_id = match wake {
+                Wake::Frame(Some(frame)) => {
+                    idle_at = idle_timeout.map(|timeout| Instant::now() + timeout);
+                    let event = frame.to_sse();
+                    // Once draining, the browser waits this long to reconnect when the
+                    // stream closes
+                    yield Ok::<_, Infallible>(match manager.draining() {
+                        Some(reconnect_after) => event.retry(reconnect_after),
+                        None => event,
+                    });
+                    continue;
+                }
+                Wake::Frame(None) => break,
+                Wake::Idle => {
+                    info!("SSE connection idle for {idle_timeout:?}, closing it");
+                    break;
+                }
+                Wake::LifetimeOver => {
+                    info!("SSE connection reached its maximum lifetime, closing it");
+                    break;
+                }
+                Wake::CheckSession => session_id.as_deref().unwrap_or_default(),
+            };
+
+            let expires_at = match domain::user::find_session_expiry(db.as_ref(), session_id).await {
+                Ok(Some(expires_at)) => expires_at,
+                Ok(None) => {
+                    info!("SSE connection's session has ended, closing it");
+                    break;
+                }
+                Err(e) => {
+                    warn!("Failed to look up SSE connection's session expiry: {e:?}");
+                    next_check = Instant::now() + SESSION_CHECK_RETRY;
+                    continue;
+                }
+            };
+
+            // A negative remaining time fails the conversion: the session has expired
+            let Ok(remaining) = (expires_at.with_timezone(&Utc) - Utc::now()).to_std() else {
+                info!("SSE connection's session has expired, closing it");
+                break;
+            };
+
+            if remaining > warning {
+                // Used since the last check, so wait until it's nearly expiring again
+                next_check = Instant::now() + (remaining - warning);
+                continue;
+            }
+
+            if warned_for != Some(expires_at) {
+                warned_for = Some(expires_at);
+                yield Ok(session_expiring_soon(&user_id, expires_at));
+            }
+            // Check again when it would expire, in case it's refreshed by then
+            next_check = Instant::now() + remaining + Duration::from_secs(1);
         }
+    };
 
-        // Connection closed, clean up
+    Sse::new(stream)
+        .keep_alive(KeepAlive::new().interval(app_state.config.sse_keep_alive_interval()))
+        .into_response()
+}
+
+/// `503 Service Unavailable`, for a request to open a connection while draining
+fn server_restarting(reconnect_after: Duration) -> Response {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        [(RETRY_AFTER, reconnect_after.as_secs().max(1).to_string())],
+        "Server restarting",
+    )
+        .into_response()
+}
+
+/// Unregisters an SSE connection when its stream is dropped: a client disconnecting
+/// drops the stream mid-wait, so code after the stream's loop wouldn't run.
+struct Registration {
+    manager: Arc<Manager>,
+    connection_id: ConnectionId,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
         info!("SSE connection closed, cleaning up");
-        manager.unregister_connection(&connection_id);
+        let manager = Arc::clone(&self.manager);
+        let connection_id = self.connection_id.clone();
+        // Unregistering announces the user offline, which looks up their relationships
+        tokio::spawn(async move {
+            manager.unregister_connection(&connection_id).await;
+        });
+    }
+}
+
+/// How soon a session whose expiry couldn't be looked up is looked up again.
+const SESSION_CHECK_RETRY: Duration = Duration::from_secs(60);
+
+/// What an SSE stream woke up for.
+enum Wake {
+    Frame(Option<::sse::connection::Frame>),
+    CheckSession,
+    /// No event was sent for the idle timeout
+    Idle,
+    /// The connection has been open for its maximum lifetime
+    LifetimeOver,
+}
+
+/// Sleeps until `deadline`, or forever without one.
+async fn sleep_until(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// The `session_expiring_soon` event, sent only on the stream it's about rather than
+/// through the manager since other connections may belong to other sessions.
+fn session_expiring_soon(user_id: &str, expires_at: DateTime<FixedOffset>) -> Event {
+    let message = SseMessage {
+        event: SseEvent::SessionExpiringSoon {
+            expires_at: expires_at.with_timezone(&Utc).to_rfc3339(),
+        },
+        scope: MessageScope::User {
+            user_id: user_id.to_string(),
+        },
+        correlation_id: None,
     };
+    let data = message.to_json().unwrap_or_else(|e| {
+        warn!("Failed to serialize session_expiring_soon event: {e:?}");
+        "{}".to_string()
+    });
+    Event::default()
+        .event(message.event.event_type())
+        .data(data)
+}
+
+/// Longest a long-poll is held open waiting for an event, below common proxy timeouts.
+const MAX_POLL_WAIT: Duration = Duration::from_secs(25);
+
+/// An event as returned by a long-poll: the fields of the SSE message it would have been.
+#[derive(Debug, Serialize)]
+pub(crate) struct PolledEvent {
+    /// Pass the last one back as `since` to poll for the events after it
+    id: u64,
+    event: &'static str,
+    /// The message, as in the SSE message's data
+    data: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct PollResponse {
+    events: Vec<PolledEvent>,
+    /// Where the next poll should start from when `events` is empty
+    last_event_id: u64,
+}
+
+/// Long-poll fallback for clients whose network breaks SSE. Returns the events sent
+/// to the user after `since` that are still buffered; if there are none, holds the
+/// request until the next ones are sent or `timeout` seconds pass. Without `since`,
+/// waits for new events only. Refused with `503` while the server drains for shutdown.
+pub(crate) async fn poll_handler(
+    AuthenticatedUser(user): AuthenticatedUser,
+    State(app_state): State<crate::AppState>,
+    Query(params): Query<PollParams>,
+) -> Response {
+    if let Some(reconnect_after) = app_state.sse_manager.draining() {
+        return server_restarting(reconnect_after);
+    }
+    let wait = params.timeout.map_or(MAX_POLL_WAIT, |seconds| {
+        Duration::from_secs(seconds).min(MAX_POLL_WAIT)
+    });
+
+    let poll = app_state
+        .sse_manager
+        .poll(user.id.to_string(), params.since, wait)
+        .await;
 
-    Sse::new(stream).keep_alive(KeepAlive::default())
+    Json(PollResponse {
+        events: poll
+            .frames
+            .into_iter()
+            .map(|frame| PolledEvent {
+                id: frame.id,
+                event: frame.event_type,
+                data: serde_json::from_str(&frame.data)
+                    .unwrap_or_else(|_| serde_json::Value::String(frame.data.to_string())),
+            })
+            .collect(),
+        last_event_id: poll.last_event_id,
+    })
+    .into_response()
 }
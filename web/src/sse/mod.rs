@@ -1,7 +1,11 @@
 //! SSE HTTP handler for the web layer.
 //!
-//! This module contains only the Axum handler for SSE endpoints.
+//! This module contains the Axum handler for SSE endpoints and the database
+//! lookup of a coaching relationship's members for relationship-scoped messages.
 //! The core SSE infrastructure (Manager, ConnectionRegistry, Message types)
 //! lives in the `sse` crate to avoid circular dependencies.
 
 pub mod handler;
+pub mod relationship_members;
+
+pub use relationship_members::DbRelationshipMembers;
@@ -0,0 +1,69 @@
+use async_trait::async_trait;
+use domain::error::{DomainErrorKind, EntityErrorKind, InternalErrorKind};
+use domain::Id;
+use sea_orm::DatabaseConnection;
+use sse::connection::UserId;
+use sse::Partner;
+use std::sync::Arc;
+
+/// Resolves a coaching relationship's members (its coach and coachee) from the database
+/// for relationship-scoped SSE messages, and a user's relationships for presence.
+pub struct DbRelationshipMembers {
+    db: Arc<DatabaseConnection>,
+}
+
+impl DbRelationshipMembers {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl sse::RelationshipMembers for DbRelationshipMembers {
+    async fn members(&self, coaching_relationship_id: &str) -> Result<Vec<UserId>, String> {
+        let Ok(id) = coaching_relationship_id.parse::<Id>() else {
+            return Ok(Vec::new());
+        };
+
+        match domain::coaching_relationship::find_by_id(&self.db, id)
+            .await
+            .map_err(domain::error::Error::from)
+        {
+            Ok(relationship) => Ok(vec![
+                relationship.coach_id.to_string(),
+                relationship.coachee_id.to_string(),
+            ]),
+            Err(e)
+                if matches!(
+                    e.error_kind,
+                    DomainErrorKind::Internal(InternalErrorKind::Entity(EntityErrorKind::NotFound))
+                ) =>
+            {
+                Ok(Vec::new())
+            }
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    async fn partners(&self, user_id: &str) -> Result<Vec<Partner>, String> {
+        let Ok(user_id) = user_id.parse::<Id>() else {
+            return Ok(Vec::new());
+        };
+
+        let relationships = domain::coaching_relationship::find_by_user(&self.db, user_id)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(relationships
+            .into_iter()
+            .map(|relationship| Partner {
+                coaching_relationship_id: relationship.id.to_string(),
+                user_id: if relationship.coach_id == user_id {
+                    relationship.coachee_id
+                } else {
+                    relationship.coach_id
+                }
+                .to_string(),
+            })
+            .collect())
+    }
+}